@@ -0,0 +1,63 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Captures build provenance (git commit, builder identity, target triple)
+//! as compile-time environment variables for
+//! `infrastructure::config::build_info`.
+//!
+//! Release pipelines should set `ADAPIPE_BUILD_COMMIT` and `ADAPIPE_BUILDER`
+//! explicitly; otherwise the commit comes from `git rev-parse HEAD` and the
+//! builder from `$USER`. Anything that cannot be determined is left empty and
+//! recorded as `unknown`.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=ADAPIPE_BUILD_COMMIT");
+    println!("cargo:rerun-if-env-changed=ADAPIPE_BUILDER");
+
+    let commit = std::env::var("ADAPIPE_BUILD_COMMIT")
+        .ok()
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_default();
+    let builder = std::env::var("ADAPIPE_BUILDER")
+        .or_else(|_| std::env::var("USER"))
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+    let target = std::env::var("TARGET").unwrap_or_default();
+
+    println!("cargo:rustc-env=ADAPIPE_BUILD_COMMIT={}", commit.trim());
+    println!("cargo:rustc-env=ADAPIPE_BUILDER={}", builder.trim());
+    println!("cargo:rustc-env=ADAPIPE_BUILD_TARGET={}", target);
+
+    // Rebuild when HEAD moves so the embedded commit stays accurate
+    for git_path in ["HEAD", "packed-refs"] {
+        watch_git_path(git_path);
+    }
+    if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        watch_git_path(head_ref.trim());
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+fn watch_git_path(name: &str) {
+    if let Some(path) = git(&["rev-parse", "--git-path", name]) {
+        let path = path.trim();
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
@@ -97,6 +97,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         buffer_size: 4096,
         verify_checksums: true,
         max_concurrent_operations: 5,
+        direct_io: false,
     };
     
     let mut file_service = TokioFileIO::new(config);
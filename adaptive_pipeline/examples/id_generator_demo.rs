@@ -381,6 +381,9 @@
 //! - Testing strategies for ID generation systems
 //! - Security considerations for identifier systems
 
+// The docs sketch how generated IDs are tested
+#![allow(clippy::test_attr_in_doctest)]
+
 use adaptive_pipeline_domain::entities::pipeline_stage::{StageConfiguration, StageType};
 use adaptive_pipeline_domain::value_objects::{PipelineId, StageId};
 use adaptive_pipeline_domain::{Pipeline, PipelineStage};
@@ -0,0 +1,9 @@
+-- Role assignments for role-based access control
+-- Each principal holds at most one role (auditor, operator or admin)
+CREATE TABLE IF NOT EXISTS role_assignments (
+    principal TEXT PRIMARY KEY,
+    role TEXT NOT NULL CHECK (role IN ('auditor', 'operator', 'admin')),
+    assigned_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,58 @@
+-- Namespaces: tenant dimension for pipelines, role assignments and usage
+-- SQLite cannot drop the global UNIQUE(name) constraint in place, so the
+-- pipelines table is rebuilt with names unique per namespace. This relies on
+-- schema::ensure_schema running migrations with foreign keys disabled, so
+-- dependent rows are not cascaded away when the old table is dropped.
+CREATE TABLE pipelines_new (
+    id TEXT PRIMARY KEY,
+    namespace TEXT NOT NULL DEFAULT 'default',
+    name TEXT NOT NULL,
+    archived BOOLEAN NOT NULL DEFAULT false,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (namespace, name)
+);
+INSERT INTO pipelines_new (id, namespace, name, archived, created_at, updated_at)
+SELECT id,
+    'default',
+    name,
+    archived,
+    created_at,
+    updated_at
+FROM pipelines;
+DROP TABLE pipelines;
+ALTER TABLE pipelines_new
+    RENAME TO pipelines;
+CREATE INDEX IF NOT EXISTS idx_pipelines_namespace_name ON pipelines(namespace, name)
+WHERE archived = false;
+-- Role assignments: one role per principal per namespace
+CREATE TABLE role_assignments_new (
+    namespace TEXT NOT NULL DEFAULT 'default',
+    principal TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('auditor', 'operator', 'admin')),
+    assigned_by TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (namespace, principal)
+);
+INSERT INTO role_assignments_new (namespace, principal, role, assigned_by, created_at, updated_at)
+SELECT 'default',
+    principal,
+    role,
+    assigned_by,
+    created_at,
+    updated_at
+FROM role_assignments;
+DROP TABLE role_assignments;
+ALTER TABLE role_assignments_new
+    RENAME TO role_assignments;
+-- Namespace usage: per-day processing totals for quota accounting
+CREATE TABLE IF NOT EXISTS namespace_usage (
+    namespace TEXT NOT NULL,
+    day TEXT NOT NULL,
+    jobs INTEGER NOT NULL DEFAULT 0,
+    input_bytes INTEGER NOT NULL DEFAULT 0,
+    output_bytes INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (namespace, day)
+);
@@ -0,0 +1,11 @@
+-- Idempotency keys: completed requests that retries with the same key replay
+-- instead of processing again. The fingerprint identifies the original
+-- request's parameters; result holds its serialized result.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    namespace TEXT NOT NULL,
+    key TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    result TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (namespace, key)
+);
@@ -0,0 +1,14 @@
+-- Chunk size history: throughput each pipeline achieved at each chunk size
+-- on each storage type, accumulated over successful runs. Chunk-size
+-- selection favours the historically fastest size.
+CREATE TABLE IF NOT EXISTS chunk_size_history (
+    pipeline_id TEXT NOT NULL,
+    storage_type TEXT NOT NULL,
+    chunk_size INTEGER NOT NULL,
+    runs INTEGER NOT NULL DEFAULT 0,
+    bytes_processed INTEGER NOT NULL DEFAULT 0,
+    processing_ms INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (pipeline_id, storage_type, chunk_size),
+    FOREIGN KEY (pipeline_id) REFERENCES pipelines(id) ON DELETE CASCADE
+);
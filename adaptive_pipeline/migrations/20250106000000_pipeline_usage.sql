@@ -0,0 +1,12 @@
+-- Pipeline usage: running totals across every recorded run, kept next to the
+-- latest run so listings can show how much each pipeline is actually used.
+ALTER TABLE processing_metrics ADD COLUMN run_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE processing_metrics ADD COLUMN total_bytes_processed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE processing_metrics ADD COLUMN total_input_bytes INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE processing_metrics ADD COLUMN total_output_bytes INTEGER NOT NULL DEFAULT 0;
+-- Existing rows hold exactly one run
+UPDATE processing_metrics
+SET run_count = 1,
+    total_bytes_processed = bytes_processed,
+    total_input_bytes = input_file_size_bytes,
+    total_output_bytes = output_file_size_bytes;
@@ -0,0 +1,19 @@
+-- API sessions: who a bearer token acts for and until when. Only the SHA-256
+-- digest of the token's secret is stored. Audit records made through a
+-- session name it.
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    namespace TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    issued_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_namespace ON sessions(namespace, expires_at);
+
+ALTER TABLE audit_records ADD COLUMN session_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_audit_records_session ON audit_records(session_id);
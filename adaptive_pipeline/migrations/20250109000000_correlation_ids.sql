@@ -0,0 +1,10 @@
+-- Correlation IDs: the run (CLI invocation or API request) that wrote each
+-- audit record and domain event, matching the run's log lines and the
+-- headers of the files it wrote.
+ALTER TABLE audit_records ADD COLUMN correlation_id TEXT;
+
+ALTER TABLE domain_events ADD COLUMN correlation_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_audit_records_correlation ON audit_records(correlation_id);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_correlation ON domain_events(correlation_id);
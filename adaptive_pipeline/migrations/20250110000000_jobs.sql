@@ -0,0 +1,21 @@
+-- Job queue: files submitted for processing and run by the daemon. Jobs are
+-- handed out oldest first by their ULID within a namespace; the state column
+-- takes queued, running, completed, failed or cancelled.
+CREATE TABLE IF NOT EXISTS jobs (
+    id TEXT PRIMARY KEY,
+    namespace TEXT NOT NULL,
+    submitted_by TEXT NOT NULL,
+    pipeline TEXT NOT NULL,
+    input TEXT NOT NULL,
+    output TEXT NOT NULL,
+    workers INTEGER,
+    state TEXT NOT NULL,
+    submitted_at TEXT NOT NULL,
+    started_at TEXT,
+    finished_at TEXT,
+    error TEXT,
+    input_bytes INTEGER,
+    output_bytes INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_namespace_state ON jobs(namespace, state, id);
@@ -0,0 +1,8 @@
+-- Daemon leases: the one job daemon allowed to run a namespace's jobs. The
+-- holder renews its lease while it runs; once expires_at has passed the
+-- holder is presumed dead and another daemon may take the lease over.
+CREATE TABLE IF NOT EXISTS daemon_leases (
+    namespace TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
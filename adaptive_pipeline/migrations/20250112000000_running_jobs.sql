@@ -0,0 +1,10 @@
+-- Jobs running in each namespace, counted by the max_concurrent_jobs quota
+-- across processes. A process renews its jobs' leases while they run; rows
+-- whose expires_at has passed belong to processes that died and are ignored.
+CREATE TABLE IF NOT EXISTS running_jobs (
+    id TEXT PRIMARY KEY,
+    namespace TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_running_jobs_namespace ON running_jobs(namespace, expires_at);
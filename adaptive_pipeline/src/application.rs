@@ -26,9 +26,10 @@
 //!
 //! ```text
 //! application/
+//! ├── command_bus/  # Command routing with middleware
 //! ├── commands/     # Command objects representing user intentions
 //! ├── handlers/     # Command and query handlers
-//! ├── queries/      # Query objects for data retrieval
+//! ├── queries/      # Query objects, DTOs and the read model port
 //! └── services/     # Application services coordinating workflows
 //! ```
 //!
@@ -58,6 +59,9 @@
 //! - Support filtering and pagination
 //! - Return DTOs or view models
 //!
+//! Queries are answered by `queries::QueryHandler` implementations that read
+//! from a `queries::PipelineReadModel` instead of loading aggregates.
+//!
 //! **Example:**
 //!
 //!
@@ -72,8 +76,9 @@
 //! - Manage transactions
 //! - Handle cross-cutting concerns
 //!
-//! **Example:**
-//!
+//! Handlers implement `command_bus::CommandHandler` and are registered on a
+//! `command_bus::CommandBus`, whose middleware apply validation, metrics,
+//! audit logging and retry to every command in one place.
 //!
 //! ## Application Services
 //!
@@ -121,7 +126,9 @@
 //! - **Integration Tests**: Test complete workflows with real implementations
 //! - **Contract Tests**: Verify interfaces between layers
 
+pub mod command_bus;
 pub mod commands;
+pub mod queries;
 pub mod services;
 pub mod use_cases;
 pub mod utilities;
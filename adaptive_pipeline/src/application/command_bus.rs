@@ -0,0 +1,382 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Command Bus
+//!
+//! A lightweight in-process command bus. Each [`Command`] type is routed to
+//! one registered [`CommandHandler`], and every dispatch passes through a
+//! chain of [`Middleware`] first, so cross-cutting concerns (validation,
+//! metrics, audit logging, retry) are written once instead of in each use
+//! case.
+//!
+//! ## Dispatch Flow
+//!
+//! ```text
+//! dispatch(command)
+//!     │
+//!     ▼
+//! middleware[0] ──► middleware[1] ──► ... ──► handler
+//!     ▲                                          │
+//!     └──────────────── result ◄─────────────────┘
+//! ```
+//!
+//! Middleware run in registration order. Each receives the
+//! [`CommandEnvelope`] and a [`Next`] continuation; calling `next.run()`
+//! invokes the rest of the chain and may be skipped (to reject a command) or
+//! repeated (to retry it). The provided middleware live in [`middleware`].
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::command_bus::middleware::{
+//!     AuditMiddleware, MetricsMiddleware, RetryMiddleware, ValidationMiddleware,
+//! };
+//! use adaptive_pipeline::application::command_bus::CommandBus;
+//!
+//! let bus = CommandBus::new()
+//!     .with_middleware(AuditMiddleware::new("alice"))
+//!     .with_middleware(MetricsMiddleware::new(metrics_service.clone()))
+//!     .with_middleware(ValidationMiddleware)
+//!     .with_middleware(RetryMiddleware::new(3))
+//!     .register(RestoreFileUseCase::new(metrics_service));
+//!
+//! let result = bus.dispatch(RestoreFileCommand::new(archive, target)).await?;
+//! ```
+
+pub mod middleware;
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use ulid::Ulid;
+
+use adaptive_pipeline_domain::PipelineError;
+
+/// Type-erased handler output carried through the middleware chain
+pub type CommandOutput = Box<dyn Any + Send>;
+
+/// A state-changing request routed by the [`CommandBus`]
+///
+/// Commands are `Clone` so retry middleware can resubmit them, and `Debug`
+/// so audit middleware can record them; keep secrets out of `Debug` output.
+pub trait Command: Debug + Clone + Send + Sync + 'static {
+    /// Value returned by the command's handler
+    type Output: Send + 'static;
+
+    /// Stable command name used in metrics labels and audit records
+    fn name(&self) -> &'static str;
+
+    /// Checks the command's own parameters before it is handled
+    fn validate(&self) -> Result<(), PipelineError> {
+        Ok(())
+    }
+}
+
+/// Executes one command type
+#[async_trait]
+pub trait CommandHandler<C: Command>: Send + Sync {
+    /// Handles `command`
+    async fn handle(&self, command: C) -> Result<C::Output, PipelineError>;
+}
+
+/// Cross-cutting behavior wrapped around every dispatch
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Processes `envelope`, calling `next.run(envelope)` to continue the
+    /// chain
+    async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError>;
+}
+
+/// A dispatched command together with its dispatch metadata
+pub struct CommandEnvelope {
+    id: Ulid,
+    command: Box<dyn ErasedCommand>,
+}
+
+impl CommandEnvelope {
+    fn new<C: Command>(command: C) -> Self {
+        Self {
+            id: Ulid::new(),
+            command: Box::new(command),
+        }
+    }
+
+    /// Unique ID of this dispatch, shared by every retry attempt
+    pub fn id(&self) -> Ulid {
+        self.id
+    }
+
+    /// See [`Command::name`]
+    pub fn name(&self) -> &'static str {
+        self.command.name()
+    }
+
+    /// See [`Command::validate`]
+    pub fn validate(&self) -> Result<(), PipelineError> {
+        self.command.validate()
+    }
+
+    /// The command, if it is a `C`
+    pub fn command<C: Command>(&self) -> Option<&C> {
+        self.command.as_any().downcast_ref()
+    }
+}
+
+impl Debug for CommandEnvelope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandEnvelope")
+            .field("id", &self.id.to_string())
+            .field("command", &self.command)
+            .finish()
+    }
+}
+
+/// The remainder of the middleware chain
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    handler: &'a dyn ErasedHandler,
+}
+
+impl Next<'_> {
+    /// Runs the remaining middleware and then the handler
+    pub async fn run(self, envelope: &CommandEnvelope) -> Result<CommandOutput, PipelineError> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    middleware: rest,
+                    handler: self.handler,
+                };
+                middleware.handle(envelope, next).await
+            }
+            None => self.handler.handle(envelope).await,
+        }
+    }
+}
+
+/// Routes commands to their handlers through the middleware chain
+#[derive(Default)]
+pub struct CommandBus {
+    handlers: HashMap<TypeId, Arc<dyn ErasedHandler>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl CommandBus {
+    /// Creates a bus with no handlers or middleware
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes commands of type `C` to `handler`, replacing any previous
+    /// handler for `C`
+    pub fn register<C, H>(mut self, handler: H) -> Self
+    where
+        C: Command,
+        H: CommandHandler<C> + 'static,
+    {
+        let adapter = HandlerAdapter {
+            handler,
+            _command: PhantomData::<fn(C)>,
+        };
+        self.handlers.insert(TypeId::of::<C>(), Arc::new(adapter));
+        self
+    }
+
+    /// Appends `middleware` to the chain; earlier middleware wrap later ones
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Dispatches `command` to its handler
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if no handler is registered for `C`,
+    /// otherwise whatever the middleware or handler return.
+    pub async fn dispatch<C: Command>(&self, command: C) -> Result<C::Output, PipelineError> {
+        let handler = self.handlers.get(&TypeId::of::<C>()).ok_or_else(|| {
+            PipelineError::invalid_config(format!("No handler registered for command '{}'", command.name()))
+        })?;
+
+        let envelope = CommandEnvelope::new(command);
+        let next = Next {
+            middleware: &self.middleware,
+            handler: handler.as_ref(),
+        };
+        let output = next.run(&envelope).await?;
+
+        output.downcast::<C::Output>().map(|output| *output).map_err(|_| {
+            PipelineError::internal_error(format!("Handler for '{}' returned an unexpected type", envelope.name()))
+        })
+    }
+}
+
+/// Converts the `anyhow` error of a use case into a handler's error
+///
+/// A bare `PipelineError` passes through unchanged, so middleware and
+/// callers still see its category; any other error, including a
+/// `PipelineError` with added context, becomes `ProcessingFailed` carrying
+/// the error's message.
+pub fn handler_error(error: anyhow::Error) -> PipelineError {
+    if error.chain().nth(1).is_none() {
+        if let Some(error) = error.downcast_ref::<PipelineError>() {
+            return error.clone();
+        }
+    }
+    PipelineError::processing_failed(error.to_string())
+}
+
+trait ErasedCommand: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    fn validate(&self) -> Result<(), PipelineError>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<C: Command> ErasedCommand for C {
+    fn name(&self) -> &'static str {
+        Command::name(self)
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        Command::validate(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[async_trait]
+trait ErasedHandler: Send + Sync {
+    async fn handle(&self, envelope: &CommandEnvelope) -> Result<CommandOutput, PipelineError>;
+}
+
+struct HandlerAdapter<C, H> {
+    handler: H,
+    _command: PhantomData<fn(C)>,
+}
+
+#[async_trait]
+impl<C, H> ErasedHandler for HandlerAdapter<C, H>
+where
+    C: Command,
+    H: CommandHandler<C>,
+{
+    async fn handle(&self, envelope: &CommandEnvelope) -> Result<CommandOutput, PipelineError> {
+        let command = envelope.command::<C>().cloned().ok_or_else(|| {
+            PipelineError::internal_error(format!("Command '{}' routed to the wrong handler", envelope.name()))
+        })?;
+        let output = self.handler.handle(command).await?;
+        Ok(Box::new(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone)]
+    struct Double(i64);
+
+    impl Command for Double {
+        type Output = i64;
+
+        fn name(&self) -> &'static str {
+            "double"
+        }
+
+        fn validate(&self) -> Result<(), PipelineError> {
+            if self.0 < 0 {
+                return Err(PipelineError::validation_error("value must not be negative"));
+            }
+            Ok(())
+        }
+    }
+
+    struct DoubleHandler;
+
+    #[async_trait]
+    impl CommandHandler<Double> for DoubleHandler {
+        async fn handle(&self, command: Double) -> Result<i64, PipelineError> {
+            Ok(command.0 * 2)
+        }
+    }
+
+    /// Records the order in which middleware run
+    struct Recorder {
+        label: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError> {
+            self.log.lock().unwrap().push(format!("{}:before", self.label));
+            let result = next.run(envelope).await;
+            self.log.lock().unwrap().push(format!("{}:after", self.label));
+            result
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_to_handler() {
+        let bus = CommandBus::new().register(DoubleHandler);
+        assert_eq!(bus.dispatch(Double(21)).await.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_command_is_rejected() {
+        let err = CommandBus::new().dispatch(Double(1)).await.unwrap_err();
+        assert!(matches!(err, PipelineError::InvalidConfiguration(_)));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_wrap_in_registration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let bus = CommandBus::new()
+            .with_middleware(Recorder {
+                label: "outer",
+                log: log.clone(),
+            })
+            .with_middleware(Recorder {
+                label: "inner",
+                log: log.clone(),
+            })
+            .register(DoubleHandler);
+
+        bus.dispatch(Double(1)).await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["outer:before", "inner:before", "inner:after", "outer:after"]
+        );
+    }
+
+    #[test]
+    fn test_handler_error_keeps_bare_pipeline_errors() {
+        let quota = handler_error(PipelineError::quota_exceeded("too many jobs").into());
+        assert!(matches!(quota, PipelineError::QuotaExceeded(_)));
+
+        let wrapped = handler_error(anyhow::Error::from(PipelineError::io_error("disk")).context("Failed to write"));
+        assert!(matches!(&wrapped, PipelineError::ProcessingFailed(message) if message == "Failed to write"));
+
+        let other = handler_error(anyhow::anyhow!("Pipeline 'x' not found"));
+        assert!(other.to_string().contains("Pipeline 'x' not found"));
+    }
+
+    #[test]
+    fn test_envelope_exposes_typed_command() {
+        let envelope = CommandEnvelope::new(Double(7));
+        assert_eq!(envelope.name(), "double");
+        assert_eq!(envelope.command::<Double>().map(|c| c.0), Some(7));
+        assert!(envelope.validate().is_ok());
+    }
+}
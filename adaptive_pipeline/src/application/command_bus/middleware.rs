@@ -0,0 +1,279 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Command Bus Middleware
+//!
+//! Standard [`Middleware`] for the [`CommandBus`](super::CommandBus):
+//!
+//! | Middleware             | Behavior                                           |
+//! |------------------------|----------------------------------------------------|
+//! | [`AuditMiddleware`]    | Logs who dispatched which command and the outcome  |
+//! | [`MetricsMiddleware`]  | Counts outcomes and times handling in Prometheus   |
+//! | [`ValidationMiddleware`] | Rejects commands whose `validate()` fails        |
+//! | [`RetryMiddleware`]    | Retries recoverable failures with backoff          |
+//!
+//! Register them in the order above so audit and metrics observe rejected
+//! commands and each retry does not re-run validation.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use super::{CommandEnvelope, CommandOutput, Middleware, Next};
+use crate::infrastructure::metrics::MetricsService;
+use adaptive_pipeline_domain::PipelineError;
+
+/// Tracing target of audit records
+pub const AUDIT_TARGET: &str = "adaptive_pipeline::audit";
+
+/// Records every dispatch, with its principal and outcome, under
+/// [`AUDIT_TARGET`]
+#[derive(Debug, Clone)]
+pub struct AuditMiddleware {
+    principal: String,
+}
+
+impl AuditMiddleware {
+    /// Attributes audited commands to `principal`
+    pub fn new(principal: impl Into<String>) -> Self {
+        Self {
+            principal: principal.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for AuditMiddleware {
+    async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError> {
+        let started = Instant::now();
+        let result = next.run(envelope).await;
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        match &result {
+            Ok(_) => info!(
+                target: AUDIT_TARGET,
+                command_id = %envelope.id(),
+                command = envelope.name(),
+                principal = %self.principal,
+                duration_ms,
+                "Command succeeded: {:?}",
+                envelope
+            ),
+            Err(e) => warn!(
+                target: AUDIT_TARGET,
+                command_id = %envelope.id(),
+                command = envelope.name(),
+                principal = %self.principal,
+                duration_ms,
+                error_category = e.category(),
+                "Command failed: {:?}: {}",
+                envelope,
+                e
+            ),
+        }
+        result
+    }
+}
+
+/// Counts each dispatch by command and outcome and records handling time
+#[derive(Clone)]
+pub struct MetricsMiddleware {
+    metrics_service: Arc<MetricsService>,
+}
+
+impl MetricsMiddleware {
+    /// Records into `metrics_service`
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self { metrics_service }
+    }
+}
+
+#[async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError> {
+        let started = Instant::now();
+        let result = next.run(envelope).await;
+        let outcome = match &result {
+            Ok(_) => "ok",
+            Err(e) => e.category(),
+        };
+        self.metrics_service
+            .record_command(envelope.name(), outcome, started.elapsed());
+        result
+    }
+}
+
+/// Rejects commands whose [`Command::validate`](super::Command::validate)
+/// fails before they reach the handler
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ValidationMiddleware;
+
+#[async_trait]
+impl Middleware for ValidationMiddleware {
+    async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError> {
+        envelope.validate()?;
+        next.run(envelope).await
+    }
+}
+
+/// Retries recoverable failures ([`PipelineError::is_recoverable`]) with
+/// exponential backoff
+#[derive(Debug, Clone, Copy)]
+pub struct RetryMiddleware {
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl RetryMiddleware {
+    /// Default delay before the first retry
+    pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(100);
+
+    /// Makes at most `max_attempts` attempts (at least one)
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Self::DEFAULT_BACKOFF,
+        }
+    }
+
+    /// Waits `initial_backoff` before the first retry, doubling each time
+    pub fn with_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+}
+
+#[async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(&self, envelope: &CommandEnvelope, next: Next<'_>) -> Result<CommandOutput, PipelineError> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match next.run(envelope).await {
+                Err(e) if e.is_recoverable() && attempt < self.max_attempts => {
+                    warn!(
+                        command_id = %envelope.id(),
+                        command = envelope.name(),
+                        "Attempt {}/{} failed, retrying in {:?}: {}",
+                        attempt,
+                        self.max_attempts,
+                        backoff,
+                        e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => {
+                    debug!(
+                        command = envelope.name(),
+                        "Command finished after {} attempt(s)", attempt
+                    );
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Command, CommandBus, CommandHandler};
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Clone)]
+    struct Flaky {
+        failures: u32,
+    }
+
+    impl Command for Flaky {
+        type Output = u32;
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn validate(&self) -> Result<(), PipelineError> {
+            if self.failures > 10 {
+                return Err(PipelineError::validation_error("too many failures requested"));
+            }
+            Ok(())
+        }
+    }
+
+    /// Fails with an I/O error until it has been called `failures` times
+    #[derive(Clone, Default)]
+    struct FlakyHandler {
+        calls: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<Flaky> for FlakyHandler {
+        async fn handle(&self, command: Flaky) -> Result<u32, PipelineError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= command.failures {
+                return Err(PipelineError::io_error("transient"));
+            }
+            Ok(call)
+        }
+    }
+
+    fn bus(handler: FlakyHandler, max_attempts: u32) -> CommandBus {
+        CommandBus::new()
+            .with_middleware(ValidationMiddleware)
+            .with_middleware(RetryMiddleware::new(max_attempts).with_backoff(Duration::from_millis(1)))
+            .register(handler)
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_from_transient_failures() {
+        let handler = FlakyHandler::default();
+        let result = bus(handler.clone(), 3).dispatch(Flaky { failures: 2 }).await.unwrap();
+        assert_eq!(result, 3);
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_attempts() {
+        let handler = FlakyHandler::default();
+        let err = bus(handler.clone(), 2)
+            .dispatch(Flaky { failures: 5 })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::IoError(_)));
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_validation_rejects_before_handler() {
+        let handler = FlakyHandler::default();
+        let err = bus(handler.clone(), 3)
+            .dispatch(Flaky { failures: 11 })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_record_outcomes() {
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let bus = CommandBus::new()
+            .with_middleware(AuditMiddleware::new("tester"))
+            .with_middleware(MetricsMiddleware::new(metrics_service.clone()))
+            .register(FlakyHandler::default());
+
+        bus.dispatch(Flaky { failures: 1 }).await.unwrap_err();
+        bus.dispatch(Flaky { failures: 0 }).await.unwrap();
+
+        let output = metrics_service.get_metrics().unwrap();
+        assert!(output.contains(r#"adaptive_pipeline_commands_total{command="flaky",outcome="io"} 1"#));
+        assert!(output.contains(r#"adaptive_pipeline_commands_total{command="flaky",outcome="ok"} 1"#));
+    }
+}
@@ -55,6 +55,16 @@
 //! - `RestoreFileCommand`: Restore a file from .adapipe format
 //! - Future commands for batch restoration, selective restoration, etc.
 //!
+//! ### Processing Commands
+//!
+//! - `ProcessFileCommand`: Process one file into an .adapipe file
+//! - `ProcessDirectoryCommand`: Process the files under a directory
+//!
+//! ### Pipeline Management Commands
+//!
+//! - `CreatePipelineCommand`: Create and store a pipeline
+//! - `DeletePipelineCommand`: Delete a stored pipeline
+//!
 //! ## Usage Patterns
 //!
 //! ### Basic Command Creation and Execution
@@ -103,6 +113,14 @@
 
 use std::path::PathBuf;
 
+use crate::application::command_bus::Command;
+use crate::application::use_cases::{DirectoryReport, ProcessFileConfig, ProcessFileResult};
+use crate::infrastructure::adapters::CommitOutcome;
+use adaptive_pipeline_domain::value_objects::{
+    ByteRange, ExecutionTopology, FileMode, JobPriority, OverwritePolicy, PathFilter, SecretBytes,
+};
+use adaptive_pipeline_domain::{Pipeline, PipelineError};
+
 /// Command to restore a file from .adapipe format.
 ///
 /// This command encapsulates all the information needed to restore a file from
@@ -145,7 +163,7 @@ use std::path::PathBuf;
 /// - Source file not found or corrupted
 /// - Insufficient permissions
 /// - Disk space exhausted
-/// - Target file already exists (when overwrite is false)
+/// - Target file already exists (under the `fail` overwrite policy)
 /// - Invalid .adapipe format
 ///
 /// ## Performance Considerations
@@ -160,12 +178,30 @@ pub struct RestoreFileCommand {
     pub source_adapipe_path: PathBuf,
     /// Target directory or file path for restoration
     pub target_path: PathBuf,
-    /// Whether to overwrite existing files
-    pub overwrite: bool,
+    /// What to do if the target file already exists
+    pub overwrite_policy: OverwritePolicy,
     /// Whether to create missing directories
     pub create_directories: bool,
     /// Whether to validate permissions before restoration
     pub validate_permissions: bool,
+    /// Directory to stage the output in before it is moved to the target;
+    /// the target's own directory when `None`
+    pub staging_dir: Option<PathBuf>,
+    /// Permissions of the restored file; the umask decides when `None`
+    pub output_mode: Option<FileMode>,
+    /// Permissions of directories created for the restored file; the umask
+    /// decides when `None`
+    pub directory_mode: Option<FileMode>,
+    /// Priority for shared CPU tokens while other jobs run concurrently
+    pub priority: JobPriority,
+    /// Write a `<target>.restore-report.json` integrity report once the
+    /// restore succeeds
+    pub write_report: bool,
+    /// Passphrase the archive's encryption keys are derived from
+    pub password: Option<SecretBytes>,
+    /// Restore only these bytes of the original, decoding only the chunks
+    /// that hold them
+    pub range: Option<ByteRange>,
 }
 
 impl RestoreFileCommand {
@@ -173,17 +209,33 @@ impl RestoreFileCommand {
         Self {
             source_adapipe_path,
             target_path,
-            overwrite: false,
+            overwrite_policy: OverwritePolicy::default(),
             create_directories: true,
             validate_permissions: true,
+            staging_dir: None,
+            output_mode: None,
+            directory_mode: None,
+            priority: JobPriority::Interactive,
+            write_report: false,
+            password: None,
+            range: None,
         }
     }
 
-    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
-        self.overwrite = overwrite;
+    pub fn with_overwrite_policy(mut self, overwrite_policy: OverwritePolicy) -> Self {
+        self.overwrite_policy = overwrite_policy;
         self
     }
 
+    /// Shorthand for the `overwrite` (`true`) or `fail` (`false`) policy
+    pub fn with_overwrite(self, overwrite: bool) -> Self {
+        self.with_overwrite_policy(if overwrite {
+            OverwritePolicy::Overwrite
+        } else {
+            OverwritePolicy::Fail
+        })
+    }
+
     pub fn with_create_directories(mut self, create_directories: bool) -> Self {
         self.create_directories = create_directories;
         self
@@ -193,11 +245,69 @@ impl RestoreFileCommand {
         self.validate_permissions = validate;
         self
     }
+
+    pub fn with_staging_dir(mut self, staging_dir: Option<PathBuf>) -> Self {
+        self.staging_dir = staging_dir;
+        self
+    }
+
+    pub fn with_output_mode(mut self, output_mode: Option<FileMode>) -> Self {
+        self.output_mode = output_mode;
+        self
+    }
+
+    pub fn with_directory_mode(mut self, directory_mode: Option<FileMode>) -> Self {
+        self.directory_mode = directory_mode;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_report(mut self, write_report: bool) -> Self {
+        self.write_report = write_report;
+        self
+    }
+
+    pub fn with_password(mut self, password: SecretBytes) -> Self {
+        self.password = Some(password);
+        self
+    }
+
+    pub fn with_range(mut self, range: Option<ByteRange>) -> Self {
+        self.range = range;
+        self
+    }
+}
+
+impl Command for RestoreFileCommand {
+    type Output = RestoreFileResult;
+
+    fn name(&self) -> &'static str {
+        "restore_file"
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        if self.source_adapipe_path.as_os_str().is_empty() {
+            return Err(PipelineError::validation_error("Restore source path is empty"));
+        }
+        if self.target_path.as_os_str().is_empty() {
+            return Err(PipelineError::validation_error("Restore target path is empty"));
+        }
+        if self.source_adapipe_path == self.target_path {
+            return Err(PipelineError::validation_error(format!(
+                "Restore target would overwrite its source: {}",
+                self.target_path.display()
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// Result of file restoration command
 #[derive(Debug)]
-#[allow(dead_code)]
 pub struct RestoreFileResult {
     /// Path where the file was restored
     pub restored_path: PathBuf,
@@ -209,4 +319,187 @@ pub struct RestoreFileResult {
     pub calculated_checksum: String,
     /// Time taken for restoration
     pub restoration_time_ms: u64,
+    /// How the staged output was moved onto the target; `None` when the
+    /// restore was skipped
+    pub output_commit: Option<CommitOutcome>,
+    /// Overwrite policy the target was resolved with
+    pub overwrite_policy: OverwritePolicy,
+    /// Whether the policy left an existing target in place and nothing was
+    /// restored
+    pub skipped: bool,
+    /// Integrity report written next to the restored file, if requested
+    pub report: Option<PathBuf>,
+}
+
+/// Command to process a file through a pipeline into an .adapipe file
+#[derive(Debug, Clone)]
+pub struct ProcessFileCommand {
+    /// Input, output, pipeline and processing options
+    pub config: ProcessFileConfig,
+}
+
+impl ProcessFileCommand {
+    pub fn new(config: ProcessFileConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Command for ProcessFileCommand {
+    type Output = ProcessFileResult;
+
+    fn name(&self) -> &'static str {
+        "process_file"
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        validate_process_config(&self.config)
+    }
+}
+
+/// Command to process the files under a directory into the same tree under
+/// another
+#[derive(Debug, Clone)]
+pub struct ProcessDirectoryCommand {
+    /// Directory whose files are processed
+    pub input_dir: PathBuf,
+    /// Directory the .adapipe files are written under
+    pub output_dir: PathBuf,
+    /// Selects the files under `input_dir` to process
+    pub filter: PathFilter,
+    /// Pipeline and processing options; input and output are replaced for
+    /// each file
+    pub config: ProcessFileConfig,
+}
+
+impl ProcessDirectoryCommand {
+    pub fn new(input_dir: PathBuf, output_dir: PathBuf, filter: PathFilter, config: ProcessFileConfig) -> Self {
+        Self {
+            input_dir,
+            output_dir,
+            filter,
+            config,
+        }
+    }
+}
+
+impl Command for ProcessDirectoryCommand {
+    type Output = DirectoryReport;
+
+    fn name(&self) -> &'static str {
+        "process_directory"
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        if self.input_dir.as_os_str().is_empty() || self.output_dir.as_os_str().is_empty() {
+            return Err(PipelineError::validation_error(
+                "Input and output directories are required",
+            ));
+        }
+        if self.config.pipeline.trim().is_empty() {
+            return Err(PipelineError::validation_error("Pipeline name is empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the paths and pipeline of a single-file processing request
+fn validate_process_config(config: &ProcessFileConfig) -> Result<(), PipelineError> {
+    if config.input.as_os_str().is_empty() {
+        return Err(PipelineError::validation_error("Input path is empty"));
+    }
+    if config.output.as_os_str().is_empty() {
+        return Err(PipelineError::validation_error("Output path is empty"));
+    }
+    if config.input == config.output {
+        return Err(PipelineError::validation_error(format!(
+            "Output would overwrite its input: {}",
+            config.output.display()
+        )));
+    }
+    if config.pipeline.trim().is_empty() {
+        return Err(PipelineError::validation_error("Pipeline name is empty"));
+    }
+    Ok(())
+}
+
+/// Command to create and store a pipeline
+#[derive(Debug, Clone)]
+pub struct CreatePipelineCommand {
+    /// Pipeline name; normalized before it is stored
+    pub name: String,
+    /// Comma-separated stage specifications
+    pub stages: String,
+    /// File the pipeline is also written to
+    pub output: Option<PathBuf>,
+    /// How stages are assigned to workers; the default when `None`
+    pub topology: Option<ExecutionTopology>,
+}
+
+impl CreatePipelineCommand {
+    pub fn new(name: String, stages: String) -> Self {
+        Self {
+            name,
+            stages,
+            output: None,
+            topology: None,
+        }
+    }
+
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn with_topology(mut self, topology: Option<ExecutionTopology>) -> Self {
+        self.topology = topology;
+        self
+    }
+}
+
+impl Command for CreatePipelineCommand {
+    type Output = Pipeline;
+
+    fn name(&self) -> &'static str {
+        "create_pipeline"
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        if self.name.trim().is_empty() {
+            return Err(PipelineError::validation_error("Pipeline name is empty"));
+        }
+        if self.stages.split(',').all(|stage| stage.trim().is_empty()) {
+            return Err(PipelineError::validation_error("A pipeline needs at least one stage"));
+        }
+        Ok(())
+    }
+}
+
+/// Command to delete a stored pipeline
+#[derive(Debug, Clone)]
+pub struct DeletePipelineCommand {
+    /// Name of the pipeline to delete
+    pub pipeline_name: String,
+    /// Delete without asking for confirmation
+    pub force: bool,
+}
+
+impl DeletePipelineCommand {
+    pub fn new(pipeline_name: String, force: bool) -> Self {
+        Self { pipeline_name, force }
+    }
+}
+
+impl Command for DeletePipelineCommand {
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "delete_pipeline"
+    }
+
+    fn validate(&self) -> Result<(), PipelineError> {
+        if self.pipeline_name.trim().is_empty() {
+            return Err(PipelineError::validation_error("Pipeline name is empty"));
+        }
+        Ok(())
+    }
 }
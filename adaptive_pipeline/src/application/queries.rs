@@ -0,0 +1,378 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Application Queries
+//!
+//! The read side of the CQRS architecture. Commands change state through
+//! domain aggregates; queries only display it, so they skip the aggregates
+//! and read flat DTOs straight from a [`PipelineReadModel`].
+//!
+//! ## Why a Separate Read Side?
+//!
+//! Rebuilding a `Pipeline` aggregate costs one query per stage (for its
+//! parameters) plus validation of every stage. Listing pipelines only needs
+//! names, counts and timestamps, which a single `SELECT` can return. The read
+//! model answers each query with a fixed number of statements, whatever the
+//! number of pipelines or stages.
+//!
+//! ## DTOs
+//!
+//! | DTO                    | Serves                                      |
+//! |------------------------|---------------------------------------------|
+//! | [`PipelineSummaryDto`] | `pipeline list`, one row per pipeline       |
+//! | [`PipelineDetailsDto`] | `pipeline show`, stages and configuration   |
+//! | [`StageDto`]           | A stage within [`PipelineDetailsDto`]       |
+//! | [`ExecutionRecordDto`] | The most recent run of a pipeline           |
+//!
+//! All DTOs implement `Serialize`, so the same values can back JSON
+//! responses as well as CLI output.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::queries::{
+//!     ListPipelinesQuery, PipelineQueryHandler, QueryHandler,
+//! };
+//!
+//! let handler = PipelineQueryHandler::new(pipeline_repository);
+//! for summary in handler.handle(ListPipelinesQuery).await? {
+//!     println!("{} ({} stages)", summary.name, summary.stage_count);
+//! }
+//! ```
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::PipelineError;
+
+/// One row of the pipeline listing
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineSummaryDto {
+    pub id: String,
+    pub namespace: String,
+    pub name: String,
+    pub archived: bool,
+    pub stage_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// When the pipeline last finished processing a file, if ever
+    pub last_executed_at: Option<DateTime<Utc>>,
+    /// Number of recorded runs
+    pub run_count: u64,
+    /// Bytes processed across all recorded runs
+    pub total_bytes_processed: u64,
+    /// Input file bytes across all recorded runs
+    pub total_input_bytes: u64,
+    /// Output file bytes across all recorded runs
+    pub total_output_bytes: u64,
+}
+
+impl PipelineSummaryDto {
+    /// Output size over input size across all recorded runs, weighted by
+    /// bytes; `None` until the pipeline has processed a non-empty file
+    pub fn average_compression_ratio(&self) -> Option<f64> {
+        (self.total_input_bytes > 0).then(|| self.total_output_bytes as f64 / self.total_input_bytes as f64)
+    }
+
+    /// Display status, matching `Pipeline::status`
+    pub fn status(&self) -> &'static str {
+        if self.archived {
+            "Archived"
+        } else {
+            "Active"
+        }
+    }
+}
+
+/// A configured stage, in pipeline order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageDto {
+    pub name: String,
+    pub stage_type: String,
+    pub algorithm: String,
+    pub enabled: bool,
+    pub order: u32,
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// Everything `pipeline show` displays about one pipeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineDetailsDto {
+    #[serde(flatten)]
+    pub summary: PipelineSummaryDto,
+    pub stages: Vec<StageDto>,
+    pub configuration: BTreeMap<String, String>,
+    pub last_execution: Option<ExecutionRecordDto>,
+}
+
+/// Metrics of the most recent successful run of a pipeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutionRecordDto {
+    pub pipeline_id: String,
+    pub pipeline_name: String,
+    pub bytes_processed: u64,
+    pub chunks_processed: u64,
+    pub input_file_size_bytes: u64,
+    pub output_file_size_bytes: u64,
+    pub compression_ratio: Option<f64>,
+    pub throughput_bytes_per_second: f64,
+    pub duration_ms: Option<u64>,
+    pub error_count: u64,
+    pub warning_count: u64,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub input_file_checksum: Option<String>,
+    pub output_file_checksum: Option<String>,
+}
+
+/// Read-optimized access to pipeline data, scoped to one namespace
+///
+/// Implemented by the SQLite pipeline repository; unlike
+/// `PipelineRepository`, it never rebuilds domain aggregates.
+#[async_trait]
+pub trait PipelineReadModel: Send + Sync {
+    /// Summaries of all active pipelines, ordered by name
+    async fn pipeline_summaries(&self) -> Result<Vec<PipelineSummaryDto>, PipelineError>;
+
+    /// Details of the active pipeline called `name`
+    async fn pipeline_details(&self, name: &str) -> Result<Option<PipelineDetailsDto>, PipelineError>;
+
+    /// The latest run of each active pipeline, most recent first
+    async fn execution_records(&self) -> Result<Vec<ExecutionRecordDto>, PipelineError>;
+}
+
+/// A read-only request answered by a [`QueryHandler`]
+pub trait Query: Send + Sync + 'static {
+    /// Value returned by the query's handler
+    type Output: Send + 'static;
+}
+
+/// Answers one query type
+#[async_trait]
+pub trait QueryHandler<Q: Query>: Send + Sync {
+    /// Handles `query`
+    async fn handle(&self, query: Q) -> Result<Q::Output, PipelineError>;
+}
+
+/// Lists all active pipelines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListPipelinesQuery;
+
+impl Query for ListPipelinesQuery {
+    type Output = Vec<PipelineSummaryDto>;
+}
+
+/// Shows one pipeline by name
+#[derive(Debug, Clone)]
+pub struct ShowPipelineQuery {
+    pub name: String,
+}
+
+impl ShowPipelineQuery {
+    /// Looks up the pipeline called `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl Query for ShowPipelineQuery {
+    type Output = Option<PipelineDetailsDto>;
+}
+
+/// Lists the latest run of each pipeline
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListExecutionsQuery {
+    /// Maximum number of records to return
+    pub limit: Option<usize>,
+}
+
+impl ListExecutionsQuery {
+    /// Returns at most `limit` records
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Query for ListExecutionsQuery {
+    type Output = Vec<ExecutionRecordDto>;
+}
+
+/// Answers the pipeline queries from a [`PipelineReadModel`]
+#[derive(Clone)]
+pub struct PipelineQueryHandler {
+    read_model: Arc<dyn PipelineReadModel>,
+}
+
+impl PipelineQueryHandler {
+    /// Creates a handler reading from `read_model`
+    pub fn new(read_model: Arc<dyn PipelineReadModel>) -> Self {
+        Self { read_model }
+    }
+}
+
+#[async_trait]
+impl QueryHandler<ListPipelinesQuery> for PipelineQueryHandler {
+    async fn handle(&self, _query: ListPipelinesQuery) -> Result<Vec<PipelineSummaryDto>, PipelineError> {
+        self.read_model.pipeline_summaries().await
+    }
+}
+
+#[async_trait]
+impl QueryHandler<ShowPipelineQuery> for PipelineQueryHandler {
+    async fn handle(&self, query: ShowPipelineQuery) -> Result<Option<PipelineDetailsDto>, PipelineError> {
+        if query.name.trim().is_empty() {
+            return Err(PipelineError::validation_error("Pipeline name must not be empty"));
+        }
+        self.read_model.pipeline_details(&query.name).await
+    }
+}
+
+#[async_trait]
+impl QueryHandler<ListExecutionsQuery> for PipelineQueryHandler {
+    async fn handle(&self, query: ListExecutionsQuery) -> Result<Vec<ExecutionRecordDto>, PipelineError> {
+        let mut records = self.read_model.execution_records().await?;
+        if let Some(limit) = query.limit {
+            records.truncate(limit);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves fixed data
+    struct FixedReadModel {
+        summaries: Vec<PipelineSummaryDto>,
+        executions: Vec<ExecutionRecordDto>,
+    }
+
+    #[async_trait]
+    impl PipelineReadModel for FixedReadModel {
+        async fn pipeline_summaries(&self) -> Result<Vec<PipelineSummaryDto>, PipelineError> {
+            Ok(self.summaries.clone())
+        }
+
+        async fn pipeline_details(&self, name: &str) -> Result<Option<PipelineDetailsDto>, PipelineError> {
+            Ok(self
+                .summaries
+                .iter()
+                .find(|summary| summary.name == name)
+                .map(|summary| PipelineDetailsDto {
+                    summary: summary.clone(),
+                    stages: Vec::new(),
+                    configuration: BTreeMap::new(),
+                    last_execution: None,
+                }))
+        }
+
+        async fn execution_records(&self) -> Result<Vec<ExecutionRecordDto>, PipelineError> {
+            Ok(self.executions.clone())
+        }
+    }
+
+    fn summary(name: &str) -> PipelineSummaryDto {
+        PipelineSummaryDto {
+            id: format!("{}-id", name),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            archived: false,
+            stage_count: 2,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_executed_at: None,
+            run_count: 0,
+            total_bytes_processed: 0,
+            total_input_bytes: 0,
+            total_output_bytes: 0,
+        }
+    }
+
+    fn execution(name: &str) -> ExecutionRecordDto {
+        ExecutionRecordDto {
+            pipeline_id: format!("{}-id", name),
+            pipeline_name: name.to_string(),
+            bytes_processed: 1024,
+            chunks_processed: 1,
+            input_file_size_bytes: 1024,
+            output_file_size_bytes: 512,
+            compression_ratio: Some(0.5),
+            throughput_bytes_per_second: 2048.0,
+            duration_ms: Some(500),
+            error_count: 0,
+            warning_count: 0,
+            started_at: None,
+            completed_at: None,
+            input_file_checksum: None,
+            output_file_checksum: None,
+        }
+    }
+
+    fn handler() -> PipelineQueryHandler {
+        PipelineQueryHandler::new(Arc::new(FixedReadModel {
+            summaries: vec![summary("alpha"), summary("bravo")],
+            executions: vec![execution("alpha"), execution("bravo")],
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_list_pipelines_returns_summaries() {
+        let summaries = handler().handle(ListPipelinesQuery).await.unwrap();
+        let names: Vec<_> = summaries.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "bravo"]);
+        assert_eq!(summaries[0].status(), "Active");
+    }
+
+    #[tokio::test]
+    async fn test_show_pipeline_finds_by_name() {
+        let handler = handler();
+        let details = handler.handle(ShowPipelineQuery::new("bravo")).await.unwrap();
+        assert_eq!(details.unwrap().summary.name, "bravo");
+        assert!(handler
+            .handle(ShowPipelineQuery::new("charlie"))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_show_pipeline_rejects_empty_name() {
+        let err = handler().handle(ShowPipelineQuery::new("  ")).await.unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_executions_applies_limit() {
+        let handler = handler();
+        assert_eq!(handler.handle(ListExecutionsQuery::default()).await.unwrap().len(), 2);
+        let limited = handler
+            .handle(ListExecutionsQuery::default().with_limit(1))
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].pipeline_name, "alpha");
+    }
+
+    #[test]
+    fn test_details_serialize_flat() {
+        let details = PipelineDetailsDto {
+            summary: summary("alpha"),
+            stages: Vec::new(),
+            configuration: BTreeMap::new(),
+            last_execution: Some(execution("alpha")),
+        };
+        let json = serde_json::to_value(&details).unwrap();
+        assert_eq!(json["name"], "alpha");
+        assert_eq!(json["stage_count"], 2);
+        assert_eq!(json["last_execution"]["output_file_size_bytes"], 512);
+    }
+}
@@ -54,5 +54,13 @@
 //! ### Pipeline Management Service
 //! Orchestrates pipeline lifecycle operations:
 
+pub mod access_control;
+pub mod authentication;
+pub mod chunk_cache;
 pub mod file_processor;
 pub mod pipeline;
+pub mod pipeline_cache;
+pub mod quota;
+pub mod restore_permission_validator;
+pub mod security_context_guard;
+pub mod session;
@@ -0,0 +1,435 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Access Control Service
+//!
+//! Resolves the active `Role` of a principal and gates `ProtectedOperation`s
+//! on it.
+//!
+//! ## Role Resolution
+//!
+//! 1. The principal's role in the active namespace is read from the
+//!    `RoleRepository`. An admin of the default namespace is a platform admin
+//!    and acts as admin in every namespace.
+//! 2. A requested role (from configuration) may narrow it: an admin can act as
+//!    an operator, but an operator cannot request admin.
+//! 3. A principal without an assignment is denied. While no assignments exist
+//!    at all every protected operation is denied; the only way in is
+//!    [`AccessControlService::authorize_first_admin`], which lets the
+//!    principal make itself the first admin (`role init`).
+//!
+//! The security context returned by [`AccessControlService::authorize`]
+//! carries the role's permissions and the principal's security level, which
+//! pipelines with a security policy are checked against.
+//!
+//! The CLI acts for the OS user running it, or for the principal its
+//! credentials authenticate; serve-mode callers construct the service with
+//! the principal bound to the caller's auth token. A service bound to an API session with
+//! [`AccessControlService::with_session`] acts for the session's user in its
+//! namespace, and its contexts carry the session's ID and expire with it.
+//! One bound to an authenticated identity with
+//! [`AccessControlService::with_identity`] acts for the identity's principal
+//! and also holds the role its authentication provider granted, whichever of
+//! that and the assigned role is higher.
+
+use std::sync::Arc;
+
+use tracing::debug;
+
+use adaptive_pipeline_domain::entities::{SecurityContext, SecurityLevel, Session};
+use adaptive_pipeline_domain::repositories::RoleRepository;
+use adaptive_pipeline_domain::services::AuthenticatedIdentity;
+use adaptive_pipeline_domain::value_objects::{Namespace, ProtectedOperation, Role};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Resolves roles and authorizes protected operations for one principal
+#[derive(Clone)]
+pub struct AccessControlService {
+    repository: Arc<dyn RoleRepository>,
+    principal: String,
+    namespace: Namespace,
+    requested_role: Option<Role>,
+    security_level: SecurityLevel,
+    session: Option<Session>,
+    granted_role: Option<Role>,
+}
+
+impl AccessControlService {
+    /// Creates a service acting on behalf of `principal`
+    pub fn new(repository: Arc<dyn RoleRepository>, principal: impl Into<String>) -> Self {
+        Self {
+            repository,
+            principal: principal.into(),
+            namespace: Namespace::default(),
+            requested_role: None,
+            security_level: SecurityLevel::Internal,
+            session: None,
+            granted_role: None,
+        }
+    }
+
+    /// Requests a specific active role, which must not exceed the assigned one
+    pub fn with_requested_role(mut self, role: Option<Role>) -> Self {
+        self.requested_role = role;
+        self
+    }
+
+    /// Scopes role resolution to `namespace`
+    pub fn with_namespace(mut self, namespace: Namespace) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    /// Sets the security level of the contexts this service returns
+    /// (`Internal` by default)
+    pub fn with_security_level(mut self, security_level: SecurityLevel) -> Self {
+        self.security_level = security_level;
+        self
+    }
+
+    /// Binds the service to an authenticated API session, acting for its
+    /// user in its namespace
+    pub fn with_session(mut self, session: Session) -> Self {
+        self.principal = session.user_id().to_string();
+        self.namespace = session.namespace().clone();
+        self.session = Some(session);
+        self
+    }
+
+    /// Binds the service to an identity established by an authentication
+    /// provider, acting for its principal with any role it was granted
+    pub fn with_identity(mut self, identity: AuthenticatedIdentity) -> Self {
+        self.principal = identity.principal;
+        self.granted_role = identity.role;
+        if let Some(namespace) = identity.namespace {
+            self.namespace = namespace;
+        }
+        self
+    }
+
+    /// A service acting for `principal` in this service's namespace and at
+    /// its security level, without its session, identity or requested role
+    ///
+    /// Used to act for someone other than the caller, such as the submitter
+    /// of a queued job.
+    pub fn for_principal(&self, principal: impl Into<String>) -> Self {
+        Self::new(self.repository.clone(), principal)
+            .with_namespace(self.namespace.clone())
+            .with_security_level(self.security_level.clone())
+    }
+
+    /// Gets the principal
+    pub fn principal(&self) -> &str {
+        &self.principal
+    }
+
+    /// Gets the namespace roles are resolved in
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the API session the service is bound to, if any
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    /// Finds the principal's effective assigned role in the namespace
+    async fn assigned_role(&self) -> Result<Option<Role>, PipelineError> {
+        let assigned = self.repository.find_role(&self.namespace, &self.principal).await?;
+        if self.namespace.is_default() || assigned == Some(Role::Admin) {
+            return Ok(assigned);
+        }
+
+        let platform = self
+            .repository
+            .find_role(&Namespace::default(), &self.principal)
+            .await?
+            .filter(|role| *role == Role::Admin);
+        Ok(assigned.max(platform))
+    }
+
+    /// Resolves the role the principal acts with
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityViolation` if the principal has no
+    /// assignment (including while no roles are assigned at all), or requests
+    /// a role above the assigned one.
+    pub async fn active_role(&self) -> Result<Role, PipelineError> {
+        match self.assigned_role().await?.max(self.granted_role) {
+            Some(assigned) => match self.requested_role {
+                Some(requested) if requested > assigned => Err(PipelineError::security_violation(format!(
+                    "Permission denied: principal '{}' is assigned role '{}' in namespace '{}' and cannot act as '{}'",
+                    self.principal, assigned, self.namespace, requested
+                ))),
+                requested => Ok(requested.unwrap_or(assigned)),
+            },
+            None if self.repository.count().await? == 0 => Err(PipelineError::security_violation(format!(
+                "Permission denied: no roles are assigned yet, so '{}' may do nothing until the first admin runs `role init`",
+                self.principal
+            ))),
+            None => Err(PipelineError::security_violation(format!(
+                "Permission denied: no role assigned to principal '{}' in namespace '{}'",
+                self.principal, self.namespace
+            ))),
+        }
+    }
+
+    /// Authorizes the principal to make itself the first admin, which is only
+    /// allowed while no roles are assigned
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityViolation` once any role is assigned.
+    pub async fn authorize_first_admin(&self) -> Result<SecurityContext, PipelineError> {
+        if self.repository.count().await? > 0 {
+            return Err(PipelineError::security_violation(
+                "Permission denied: roles are already assigned; an admin assigns further roles with `role assign`",
+            ));
+        }
+        let context = SecurityContext::for_role(Some(self.principal.clone()), Role::Admin, self.security_level.clone());
+        context.authorize(ProtectedOperation::ManageRoles)?;
+
+        debug!(principal = %self.principal, "First admin authorized");
+        Ok(context)
+    }
+
+    /// Authorizes `operation`, returning a security context for the
+    /// principal's active role
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityViolation` if the role cannot be
+    /// resolved or does not permit the operation.
+    pub async fn authorize(&self, operation: ProtectedOperation) -> Result<SecurityContext, PipelineError> {
+        let role = self.active_role().await?;
+        let mut context = SecurityContext::for_role(Some(self.principal.clone()), role, self.security_level.clone());
+        if let Some(session) = &self.session {
+            context = context
+                .with_session_id(session.id().as_uuid())
+                .with_expiry(session.expires_at());
+        }
+        context.authorize(operation)?;
+
+        debug!(
+            principal = %self.principal,
+            namespace = %self.namespace,
+            role = %role,
+            operation = %operation,
+            "Operation authorized"
+        );
+        Ok(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::RoleAssignment;
+    use async_trait::async_trait;
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryRoleRepository {
+        assignments: Mutex<BTreeMap<(Namespace, String), RoleAssignment>>,
+    }
+
+    #[async_trait]
+    impl RoleRepository for InMemoryRoleRepository {
+        async fn assign(&self, assignment: &RoleAssignment) -> Result<(), PipelineError> {
+            self.assignments.lock().unwrap().insert(
+                (assignment.namespace().clone(), assignment.principal().to_string()),
+                assignment.clone(),
+            );
+            Ok(())
+        }
+
+        async fn revoke(&self, namespace: &Namespace, principal: &str) -> Result<bool, PipelineError> {
+            let key = (namespace.clone(), principal.to_string());
+            Ok(self.assignments.lock().unwrap().remove(&key).is_some())
+        }
+
+        async fn find_by_principal(
+            &self,
+            namespace: &Namespace,
+            principal: &str,
+        ) -> Result<Option<RoleAssignment>, PipelineError> {
+            let key = (namespace.clone(), principal.to_string());
+            Ok(self.assignments.lock().unwrap().get(&key).cloned())
+        }
+
+        async fn list_by_namespace(&self, namespace: &Namespace) -> Result<Vec<RoleAssignment>, PipelineError> {
+            Ok(self
+                .assignments
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|a| a.namespace() == namespace)
+                .cloned()
+                .collect())
+        }
+
+        async fn count(&self) -> Result<usize, PipelineError> {
+            Ok(self.assignments.lock().unwrap().len())
+        }
+    }
+
+    async fn repository_with(assignments: &[(&str, &str, Role)]) -> Arc<dyn RoleRepository> {
+        let repository = InMemoryRoleRepository::default();
+        for (namespace, principal, role) in assignments {
+            let namespace = Namespace::new(*namespace).unwrap();
+            repository
+                .assign(&RoleAssignment::new(namespace, *principal, *role, None).unwrap())
+                .await
+                .unwrap();
+        }
+        Arc::new(repository)
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_rbac_denies_everything_but_the_first_admin() {
+        let service = AccessControlService::new(repository_with(&[]).await, "anyone");
+        assert!(service.active_role().await.is_err());
+        let err = service.authorize(ProtectedOperation::ViewPipelines).await.unwrap_err();
+        assert!(matches!(err, PipelineError::SecurityViolation(_)));
+
+        let context = service.authorize_first_admin().await.unwrap();
+        assert_eq!(context.user_id(), Some("anyone"));
+        assert!(context.authorize(ProtectedOperation::ManageRoles).is_ok());
+
+        let configured = repository_with(&[("default", "root", Role::Admin)]).await;
+        let late = AccessControlService::new(configured, "anyone");
+        assert!(late.authorize_first_admin().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_contexts_carry_the_security_level() {
+        let repository = repository_with(&[("default", "root", Role::Admin)]).await;
+        let service = AccessControlService::new(repository, "root");
+        let context = service.authorize(ProtectedOperation::DeletePipeline).await.unwrap();
+        assert_eq!(*context.security_level(), SecurityLevel::Internal);
+
+        let cleared = service.with_security_level(SecurityLevel::Secret);
+        let context = cleared.authorize(ProtectedOperation::ProcessFile).await.unwrap();
+        assert_eq!(*context.security_level(), SecurityLevel::Secret);
+    }
+
+    #[tokio::test]
+    async fn test_assigned_role_gates_operations() {
+        let repository = repository_with(&[("default", "root", Role::Admin), ("default", "ops", Role::Operator)]).await;
+        let service = AccessControlService::new(repository.clone(), "ops");
+
+        assert!(service.authorize(ProtectedOperation::RestoreFile).await.is_ok());
+        let err = service.authorize(ProtectedOperation::DeletePipeline).await.unwrap_err();
+        assert!(matches!(err, PipelineError::SecurityViolation(_)));
+
+        let stranger = AccessControlService::new(repository, "stranger");
+        assert!(stranger.authorize(ProtectedOperation::ViewPipelines).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requested_role_can_only_narrow() {
+        let repository = repository_with(&[("default", "root", Role::Admin), ("default", "ops", Role::Operator)]).await;
+
+        let narrowed = AccessControlService::new(repository.clone(), "root").with_requested_role(Some(Role::Auditor));
+        assert_eq!(narrowed.active_role().await.unwrap(), Role::Auditor);
+        assert!(narrowed.authorize(ProtectedOperation::ProcessFile).await.is_err());
+
+        let escalated = AccessControlService::new(repository, "ops").with_requested_role(Some(Role::Admin));
+        assert!(escalated.active_role().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_roles_are_scoped_to_namespace() {
+        let repository = repository_with(&[
+            ("default", "root", Role::Admin),
+            ("team-a", "alice", Role::Admin),
+            ("team-b", "alice", Role::Auditor),
+        ])
+        .await;
+        let team_a = Namespace::new("team-a").unwrap();
+        let team_b = Namespace::new("team-b").unwrap();
+
+        let alice_a = AccessControlService::new(repository.clone(), "alice").with_namespace(team_a.clone());
+        assert!(alice_a.authorize(ProtectedOperation::DeletePipeline).await.is_ok());
+
+        let alice_b = AccessControlService::new(repository.clone(), "alice").with_namespace(team_b);
+        assert!(alice_b.authorize(ProtectedOperation::ViewPipelines).await.is_ok());
+        assert!(alice_b.authorize(ProtectedOperation::CreatePipeline).await.is_err());
+
+        let alice_default = AccessControlService::new(repository.clone(), "alice");
+        assert!(alice_default
+            .authorize(ProtectedOperation::ViewPipelines)
+            .await
+            .is_err());
+
+        // Default-namespace admins administer every namespace
+        let root = AccessControlService::new(repository, "root").with_namespace(team_a);
+        assert_eq!(root.active_role().await.unwrap(), Role::Admin);
+    }
+
+    #[tokio::test]
+    async fn test_identity_role_adds_to_assigned_role() {
+        let repository = repository_with(&[("default", "root", Role::Admin), ("default", "ops", Role::Auditor)]).await;
+
+        let granted = AuthenticatedIdentity::new("ops", "jwt").with_role(Some(Role::Operator));
+        let ops = AccessControlService::new(repository.clone(), "anyone").with_identity(granted);
+        assert_eq!(ops.principal(), "ops");
+        assert_eq!(ops.active_role().await.unwrap(), Role::Operator);
+
+        // A lower granted role does not narrow the assigned one
+        let root = AccessControlService::new(repository.clone(), "anyone")
+            .with_identity(AuthenticatedIdentity::new("root", "api_key").with_role(Some(Role::Auditor)));
+        assert_eq!(root.active_role().await.unwrap(), Role::Admin);
+
+        // A granted role admits principals without an assignment
+        let bot = AccessControlService::new(repository, "anyone")
+            .with_identity(AuthenticatedIdentity::new("bot", "api_key").with_role(Some(Role::Auditor)));
+        assert!(bot.authorize(ProtectedOperation::ViewPipelines).await.is_ok());
+        assert!(bot.authorize(ProtectedOperation::ProcessFile).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_acting_for_another_principal_drops_the_callers_role() {
+        let repository = repository_with(&[("default", "root", Role::Admin), ("team-a", "ops", Role::Auditor)]).await;
+        let team_a = Namespace::new("team-a").unwrap();
+        let root = AccessControlService::new(repository, "anyone")
+            .with_identity(AuthenticatedIdentity::new("root", "jwt").with_role(Some(Role::Admin)))
+            .with_namespace(team_a.clone());
+
+        let ops = root.for_principal("ops");
+        assert_eq!(ops.principal(), "ops");
+        assert_eq!(ops.namespace(), &team_a);
+        assert_eq!(ops.active_role().await.unwrap(), Role::Auditor);
+        assert!(ops.authorize(ProtectedOperation::ProcessFile).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_session_binds_principal_namespace_and_expiry() {
+        use adaptive_pipeline_domain::value_objects::UserId;
+
+        let repository =
+            repository_with(&[("default", "root", Role::Admin), ("team-a", "alice", Role::Operator)]).await;
+        let team_a = Namespace::new("team-a").unwrap();
+        let (session, _) = Session::issue(
+            UserId::parse("alice").unwrap(),
+            team_a.clone(),
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+
+        let service = AccessControlService::new(repository, "root").with_session(session.clone());
+        assert_eq!(service.principal(), "alice");
+        assert_eq!(service.namespace(), &team_a);
+        assert!(service.authorize(ProtectedOperation::DeletePipeline).await.is_err());
+
+        let context = service.authorize(ProtectedOperation::ProcessFile).await.unwrap();
+        assert_eq!(context.session_id(), session.id().as_uuid());
+        assert_eq!(context.expires_at(), Some(session.expires_at()));
+    }
+}
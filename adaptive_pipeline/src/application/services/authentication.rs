@@ -0,0 +1,132 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Authentication Service
+//!
+//! Gates API callers on the configured `AuthProvider`s. Credentials are
+//! offered to each provider that supports their kind, in registration order;
+//! the first provider to accept them establishes the caller's identity.
+//!
+//! The identity is handed to `AccessControlService::with_identity`, which
+//! resolves the principal's roles as usual and adds the role the provider
+//! granted, if any.
+
+use std::sync::Arc;
+
+use tracing::{debug, warn};
+
+use adaptive_pipeline_domain::services::{AuthProvider, AuthenticatedIdentity, Credentials};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Authenticates callers against a chain of providers
+#[derive(Default)]
+pub struct AuthenticationService {
+    providers: Vec<Arc<dyn AuthProvider>>,
+    required: bool,
+}
+
+impl AuthenticationService {
+    /// Creates a service with no providers, which accepts no credentials
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `provider` after those already registered
+    pub fn with_provider(mut self, provider: Arc<dyn AuthProvider>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// Adds `providers` after those already registered
+    pub fn with_providers(mut self, providers: impl IntoIterator<Item = Arc<dyn AuthProvider>>) -> Self {
+        self.providers.extend(providers);
+        self
+    }
+
+    /// Requires callers of protected operations to authenticate
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Whether callers of protected operations must authenticate
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Establishes who presented `credentials`
+    ///
+    /// # Errors
+    ///
+    /// Returns `SecurityViolation` if no provider supports the credential
+    /// kind, or the error of the last provider that rejected them.
+    pub async fn authenticate(&self, credentials: &Credentials) -> Result<AuthenticatedIdentity, PipelineError> {
+        let mut rejection = None;
+        for provider in self.providers.iter().filter(|provider| provider.supports(credentials)) {
+            match provider.authenticate(credentials).await {
+                Ok(identity) => {
+                    debug!(
+                        provider = provider.name(),
+                        principal = %identity.principal,
+                        "Caller authenticated"
+                    );
+                    return Ok(identity);
+                }
+                Err(e) => {
+                    debug!(provider = provider.name(), "Credentials rejected: {}", e);
+                    rejection = Some(e);
+                }
+            }
+        }
+
+        let error = rejection.unwrap_or_else(|| {
+            PipelineError::security_violation(format!(
+                "Permission denied: no authentication provider accepts a {}",
+                credentials.kind()
+            ))
+        });
+        warn!("Authentication failed: {}", error);
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::adapters::auth::ApiKeyProvider;
+    use adaptive_pipeline_domain::value_objects::Role;
+
+    fn provider(key: &str, principal: &str) -> Arc<dyn AuthProvider> {
+        Arc::new(
+            ApiKeyProvider::new()
+                .with_key(&ApiKeyProvider::digest(key), principal, Some(Role::Auditor), None)
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_first_accepting_provider_wins() {
+        let service = AuthenticationService::new()
+            .with_provider(provider("one", "first"))
+            .with_provider(provider("two", "second"));
+
+        let second = service
+            .authenticate(&Credentials::ApiKey("two".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(second.principal, "second");
+
+        let unknown = service.authenticate(&Credentials::ApiKey("three".to_string())).await;
+        assert!(matches!(unknown, Err(PipelineError::SecurityViolation(_))));
+
+        let unsupported = service
+            .authenticate(&Credentials::BearerToken("token".to_string()))
+            .await
+            .unwrap_err();
+        assert!(unsupported.to_string().contains("bearer token"), "{}", unsupported);
+    }
+}
@@ -0,0 +1,160 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Cache
+//!
+//! Keeps the restored data of recently read chunks of one archive, so random
+//! reads that keep returning to the same region (a mounted archive opened by
+//! a database, a media player seeking back and forth) decrypt and decompress
+//! each chunk once instead of once per read.
+//!
+//! ## Eviction
+//!
+//! The cache holds at most its byte budget of chunk data and drops the least
+//! recently used chunks to make room. The chunk just inserted is always kept,
+//! even over budget, so reads smaller than a chunk still decode it once; a
+//! budget of zero keeps only that chunk.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Bytes of restored chunk data a range reader keeps by default
+pub const DEFAULT_CHUNK_CACHE_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Default)]
+struct Entries {
+    chunks: HashMap<u32, (Arc<Vec<u8>>, u64)>,
+    /// Chunk indexes by the tick they were last used at, oldest first
+    recency: BTreeMap<u64, u32>,
+    bytes: u64,
+    tick: u64,
+}
+
+impl Entries {
+    fn touch(&mut self, index: u32) -> Option<Arc<Vec<u8>>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (data, used) = self.chunks.get_mut(&index)?;
+        self.recency.remove(used);
+        self.recency.insert(tick, index);
+        *used = tick;
+        Some(data.clone())
+    }
+}
+
+/// Least recently used cache of restored chunks, by chunk index, bounded by
+/// the bytes they hold
+pub struct ChunkCache {
+    budget: u64,
+    entries: Mutex<Entries>,
+}
+
+impl ChunkCache {
+    /// Creates an empty cache holding up to `budget` bytes of chunk data
+    pub fn new(budget: u64) -> Self {
+        Self {
+            budget,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// The restored data of chunk `index`, if it is cached; marks it as the
+    /// most recently used
+    pub fn get(&self, index: u32) -> Option<Arc<Vec<u8>>> {
+        self.lock().touch(index)
+    }
+
+    /// Caches the restored data of chunk `index`, evicting the least recently
+    /// used chunks until the rest fit the budget
+    pub fn insert(&self, index: u32, data: Arc<Vec<u8>>) {
+        let mut entries = self.lock();
+        if entries.touch(index).is_some() {
+            return;
+        }
+        let tick = entries.tick;
+        entries.bytes += data.len() as u64;
+        entries.chunks.insert(index, (data, tick));
+        entries.recency.insert(tick, index);
+
+        while entries.bytes > self.budget && entries.chunks.len() > 1 {
+            let Some((_, oldest)) = entries.recency.pop_first() else {
+                break;
+            };
+            if let Some((evicted, _)) = entries.chunks.remove(&oldest) {
+                entries.bytes -= evicted.len() as u64;
+            }
+        }
+    }
+
+    /// Bytes of chunk data cached
+    pub fn size(&self) -> u64 {
+        self.lock().bytes
+    }
+
+    /// Number of cached chunks
+    pub fn len(&self) -> usize {
+        self.lock().chunks.len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for ChunkCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHUNK_CACHE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(byte: u8, len: usize) -> Arc<Vec<u8>> {
+        Arc::new(vec![byte; len])
+    }
+
+    #[test]
+    fn test_least_recently_used_chunk_is_evicted_first() {
+        let cache = ChunkCache::new(30);
+        cache.insert(0, chunk(0, 10));
+        cache.insert(1, chunk(1, 10));
+        cache.insert(2, chunk(2, 10));
+        assert_eq!(cache.get(0).unwrap()[0], 0, "reading chunk 0 makes it the newest");
+
+        cache.insert(3, chunk(3, 10));
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(0).is_some() && cache.get(2).is_some() && cache.get(3).is_some());
+        assert_eq!((cache.len(), cache.size()), (3, 30));
+    }
+
+    #[test]
+    fn test_newest_chunk_is_kept_over_budget() {
+        let cache = ChunkCache::new(0);
+        cache.insert(0, chunk(0, 10));
+        assert_eq!(cache.get(0).unwrap().len(), 10);
+
+        cache.insert(1, chunk(1, 25));
+        assert!(cache.get(0).is_none());
+        assert_eq!((cache.len(), cache.size()), (1, 25));
+    }
+
+    #[test]
+    fn test_reinserting_a_cached_chunk_keeps_one_copy() {
+        let cache = ChunkCache::new(100);
+        cache.insert(7, chunk(7, 40));
+        cache.insert(7, chunk(7, 40));
+        assert_eq!((cache.len(), cache.size()), (1, 40));
+        assert!(!cache.is_empty());
+    }
+}
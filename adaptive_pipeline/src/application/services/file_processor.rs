@@ -178,6 +178,8 @@ use adaptive_pipeline_domain::services::file_processor_service::{
 };
 use adaptive_pipeline_domain::{FileChunk, PipelineError};
 
+use crate::infrastructure::runtime::temp_root::temp_root;
+
 /// Implementation of FileProcessorService
 ///
 /// This struct provides a high-performance implementation of the file processor
@@ -280,7 +282,13 @@ impl<T: FileIOService> StreamingFileProcessor<T> {
     /// Creates a temporary file path
     fn create_temp_file_path(&self, original_path: &Path) -> std::path::PathBuf {
         let config = self.config.read();
-        let temp_dir = config.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        // Default to this run's directory in the managed temp root, which is
+        // cleaned up at exit and after a crash
+        let temp_dir = config
+            .temp_dir
+            .clone()
+            .or_else(|| temp_root().map(|root| root.run_dir().to_path_buf()))
+            .unwrap_or_else(std::env::temp_dir);
 
         let file_name = original_path
             .file_name()
@@ -30,9 +30,13 @@
 //! - **Configuration System**: Dynamic configuration updates
 
 use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine as _;
 use byte_unit::Byte;
 use futures::future;
+use futures::StreamExt;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -43,17 +47,36 @@ use adaptive_pipeline_domain::entities::{
     Pipeline, PipelineStage, ProcessingContext, ProcessingMetrics, SecurityContext,
 };
 use adaptive_pipeline_domain::repositories::stage_executor::ResourceRequirements;
-use adaptive_pipeline_domain::repositories::{PipelineRepository, StageExecutor};
+use adaptive_pipeline_domain::repositories::{ChunkSizeHistoryRepository, PipelineRepository, StageExecutor};
 use adaptive_pipeline_domain::services::file_io_service::{FileIOService, ReadOptions};
 use adaptive_pipeline_domain::services::file_processor_service::ChunkProcessor;
 use adaptive_pipeline_domain::services::{
-    CompressionService, EncryptionService, ExecutionRecord, ExecutionState, ExecutionStatus, KeyMaterial,
+    CompressionService, CostModel, EncryptionService, ExecutionRecord, ExecutionState, ExecutionStatus, KeyMaterial,
     PipelineRequirements, PipelineService,
 };
-use adaptive_pipeline_domain::value_objects::{ChunkFormat, FileChunk, PipelineId, WorkerCount};
+use adaptive_pipeline_domain::value_objects::binary_file_format::FIPS_MODE_METADATA_KEY;
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::ASSOCIATED_DATA_KEY;
+use adaptive_pipeline_domain::value_objects::content_type::{CONTENT_TYPE_METADATA_KEY, DETECTION_LENGTH};
+use adaptive_pipeline_domain::value_objects::{
+    Algorithm, ChunkBinding, ChunkFormat, ContentType, ExecutionTopology, FileChunk, JobPriority, KdfCost,
+    NonceStrategy, PipelineId, SecretBytes, ShutdownCheckpoint, WorkerCount, FIPS_MODE, NONCE_STRATEGY_KEY,
+};
 use adaptive_pipeline_domain::PipelineError;
 
-use crate::infrastructure::services::binary_format::{BinaryFormatService, BinaryFormatWriter};
+use crate::application::services::security_context_guard::SecurityContextGuard;
+use crate::infrastructure::adapters::checksum::ContentHasher;
+use crate::infrastructure::adapters::chunk_prefetcher::{prefetch_depth, ChunkPrefetcher};
+use crate::infrastructure::adapters::file_permissions::apply_mode;
+use crate::infrastructure::adapters::random_access_sink::{FileSink, PreallocatedFileSink};
+use crate::infrastructure::adapters::MultiAlgoEncryption;
+use crate::infrastructure::config::build_info::build_provenance;
+use crate::infrastructure::metrics::worker_metrics::WorkerMetricsShards;
+use crate::infrastructure::metrics::CONCURRENCY_METRICS;
+use crate::infrastructure::runtime::supervisor::{catch_panic, RestartBudget};
+use crate::infrastructure::runtime::{
+    ChecksumFeed, ChecksumTee, InFlightWindow, WindowPermit, DEFAULT_INFLIGHT_WINDOW,
+};
+use crate::infrastructure::services::binary_format::{worst_case_output_size, BinaryFormatService, BinaryFormatWriter};
 use crate::infrastructure::services::progress_indicator::ProgressIndicatorService;
 
 // Concrete implementation of the pipeline service
@@ -101,7 +124,8 @@ use crate::infrastructure::services::progress_indicator::ProgressIndicatorServic
 ///
 /// ## Design Rationale:
 /// - `chunk_index`: Required for ordered writes (future enhancement)
-/// - `data`: Owned Vec<u8> for zero-copy channel transfer
+/// - `window_permit`: The chunk's share of the in-flight window, held until
+///   the worker drops the message after writing
 /// - `is_final`: Allows writer to finalize file on last chunk
 /// - `enqueued_at`: Timestamp for queue wait metrics
 #[derive(Debug)]
@@ -109,8 +133,8 @@ struct ChunkMessage {
     /// Index of this chunk in the file (0-based)
     chunk_index: usize,
 
-    /// Raw chunk data (owned for channel transfer)
-    data: Vec<u8>,
+    /// In-flight window reservation for the chunk's data
+    window_permit: WindowPermit,
 
     /// True if this is the last chunk in the file
     is_final: bool,
@@ -159,6 +183,10 @@ struct WorkerStats {
     chunks_processed: usize,
 }
 
+/// How often the sampler folds the worker metrics shards for the progress
+/// display and live throughput gauge
+const METRICS_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Statistics from the writer task
 #[derive(Debug)]
 struct WriterStats {
@@ -175,70 +203,90 @@ struct WriterStats {
 /// ## Educational: Single Reader Pattern
 ///
 /// This task demonstrates the "single reader" pattern, which eliminates
-/// coordination overhead. Only ONE task reads from disk, ensuring sequential
-/// access patterns optimal for filesystem performance.
+/// coordination overhead. Only ONE task hands chunks to workers, in file
+/// order, while a [`ChunkPrefetcher`] keeps the next `prefetch_depth` reads
+/// in flight so disk I/O overlaps with processing.
 ///
 /// ## Backpressure Mechanism
 ///
-/// The bounded channel creates natural backpressure:
-/// - When workers are fast: Channel stays empty, reader proceeds immediately
-/// - When workers are slow: Channel fills up, `tx_cpu.send()` blocks
+/// The in-flight window creates natural backpressure:
+/// - When workers are fast: Window has room, reader proceeds immediately
+/// - When workers are slow: Window fills up, the next read waits for bytes
+///   to be released by workers finishing chunks
 /// - Result: Automatic flow control without explicit rate limiting!
 ///
+/// Chunks read ahead, queued, and being processed together never hold more
+/// than the window's byte budget; the bounded channel additionally caps
+/// their count.
+///
 /// ## Arguments
 /// - `input_path`: File to read chunks from
 /// - `chunk_size`: Size of each chunk in bytes
 /// - `tx_cpu`: Channel sender to CPU workers (blocks when full)
 /// - `file_io_service`: Service for reading file chunks
+/// - `prefetch_depth`: Chunk reads kept in flight ahead of the channel
+/// - `window`: Byte budget for this file's chunk data in flight
+/// - `checksum`: With checksum offload, receives every chunk in file order
 /// - `cancel_token`: Token for graceful cancellation
 ///
 /// ## Returns
 /// `ReaderStats` with chunks read and bytes read
+#[allow(clippy::too_many_arguments)]
 async fn reader_task(
     input_path: PathBuf,
     chunk_size: usize,
     tx_cpu: tokio::sync::mpsc::Sender<ChunkMessage>,
     file_io_service: Arc<dyn FileIOService>,
-    channel_capacity: usize,
+    prefetch_depth: usize,
+    window: Arc<InFlightWindow>,
+    checksum: Option<ChecksumFeed>,
     cancel_token: adaptive_pipeline_bootstrap::shutdown::CancellationToken,
 ) -> Result<ReaderStats, PipelineError> {
-    use crate::infrastructure::metrics::CONCURRENCY_METRICS;
 
     // Check for cancellation before starting
     if cancel_token.is_cancelled() {
         return Err(PipelineError::cancelled());
     }
 
-    // Configure read options for streaming
-    let read_options = ReadOptions {
-        chunk_size: Some(chunk_size),
-        use_memory_mapping: false,  // Stream from disk, don't load all into memory
-        calculate_checksums: false, // We'll calculate during processing
-        ..Default::default()
-    };
-
-    // Read file into chunks using FileIOService
-    let read_result = file_io_service
-        .read_file_chunks(&input_path, read_options)
+    let mut prefetcher = ChunkPrefetcher::open(file_io_service, &input_path, chunk_size, prefetch_depth, window)
         .await
         .map_err(|e| PipelineError::IoError(format!("Failed to read file chunks: {}", e)))?;
+    debug!(
+        "Reading {} chunks with {} reads in flight",
+        prefetcher.total_chunks(),
+        prefetcher.depth()
+    );
 
-    let total_chunks = read_result.chunks.len();
+    let mut chunks_read = 0usize;
     let mut bytes_read = 0u64;
 
-    // Send each chunk to CPU workers
-    for (index, file_chunk) in read_result.chunks.into_iter().enumerate() {
-        let chunk_data = file_chunk.data().to_vec();
-        let chunk_size_bytes = chunk_data.len() as u64;
-        bytes_read += chunk_size_bytes;
+    // Send each chunk to CPU workers as soon as its read completes
+    loop {
+        let next = tokio::select! {
+            _ = cancel_token.cancelled() => {
+                return Err(PipelineError::cancelled_with_msg("reader cancelled during read"));
+            }
+            next = prefetcher.next_chunk() => next,
+        };
+        let Some(next) = next else {
+            break;
+        };
+        let (file_chunk, window_permit) =
+            next.map_err(|e| PipelineError::IoError(format!("Failed to read file chunks: {}", e)))?;
+
+        bytes_read += file_chunk.data().len() as u64;
+        if let Some(checksum) = &checksum {
+            checksum.update(file_chunk.data()).await?;
+        }
 
         let message = ChunkMessage {
-            chunk_index: index,
-            data: chunk_data,
-            is_final: index == total_chunks - 1,
+            chunk_index: chunks_read,
+            window_permit,
+            is_final: file_chunk.is_final(),
             file_chunk,
             enqueued_at: std::time::Instant::now(), // Timestamp for queue wait
         };
+        chunks_read += 1;
 
         // Educational: This blocks if channel is full → backpressure!
         // When workers are processing slowly, the reader waits here,
@@ -256,7 +304,7 @@ async fn reader_task(
         // Update queue depth metrics after send
         // Educational: Shows backpressure in real-time
         let remaining_capacity = tx_cpu.capacity();
-        let current_depth = channel_capacity.saturating_sub(remaining_capacity);
+        let current_depth = tx_cpu.max_capacity().saturating_sub(remaining_capacity);
         CONCURRENCY_METRICS.update_cpu_queue_depth(current_depth);
     }
 
@@ -265,7 +313,7 @@ async fn reader_task(
     drop(tx_cpu);
 
     Ok(ReaderStats {
-        chunks_read: total_chunks,
+        chunks_read,
         bytes_read,
     })
 }
@@ -279,8 +327,386 @@ struct CpuWorkerContext {
     stage_executor: Arc<dyn StageExecutor>,
     input_path: PathBuf,
     output_path: PathBuf,
-    input_size: u64,
-    security_context: SecurityContext,
+    /// File-level parent context; each chunk is processed in a child of it
+    processing_context: ProcessingContext,
+    /// Checks security context expiry at every stage boundary
+    security_guard: Arc<SecurityContextGuard>,
+}
+
+/// Runs one chunk through every stage of `pipeline`, in order.
+///
+/// See [`execute_stage`] for how each stage's failures are reported. After a
+/// failure `context` may be half-updated and must be discarded.
+async fn execute_stages(
+    pipeline: &Pipeline,
+    stage_executor: &Arc<dyn StageExecutor>,
+    security_guard: &SecurityContextGuard,
+    mut file_chunk: FileChunk,
+    context: &mut ProcessingContext,
+) -> Result<FileChunk, PipelineError> {
+    for stage in pipeline.stages() {
+        file_chunk = execute_stage(stage, stage_executor, security_guard, file_chunk, context).await?;
+    }
+    Ok(file_chunk)
+}
+
+/// Runs one chunk through one stage.
+///
+/// The stage runs behind [`catch_panic`], so a panicking stage service fails
+/// the chunk with `StagePanicked` (naming the stage and chunk) instead of
+/// killing the worker. After a panic `context` may be half-updated and must
+/// be discarded. Timeouts and panics keep their own error variants; other
+/// stage errors are reported as processing failures.
+async fn execute_stage(
+    stage: &PipelineStage,
+    stage_executor: &Arc<dyn StageExecutor>,
+    security_guard: &SecurityContextGuard,
+    file_chunk: FileChunk,
+    context: &mut ProcessingContext,
+) -> Result<FileChunk, PipelineError> {
+    security_guard.check_stage_boundary(context, stage.name()).await?;
+
+    let chunk_sequence = file_chunk.sequence_number();
+    catch_panic(stage_executor.execute(stage, file_chunk, context))
+        .await
+        .map_err(|message| {
+            PipelineError::stage_panicked(format!(
+                "stage '{}' panicked on chunk {}: {}",
+                stage.name(),
+                chunk_sequence,
+                message
+            ))
+        })?
+        .map_err(|e| match e {
+            PipelineError::StageTimeout(_) | PipelineError::StagePanicked(_) => e,
+            e => PipelineError::processing_failed(format!("Stage execution failed: {}", e)),
+        })
+}
+
+/// Writes a fully processed chunk at its position in the output.
+///
+/// If the encryption stage ran, the nonce it prepended to the data is split
+/// off into the chunk header; the header holds 12 bytes, so the rest of a
+/// 24-byte XChaCha nonce stays at the start of the payload. Returns the
+/// chunk's payload size.
+async fn write_processed_chunk(
+    writer: &dyn BinaryFormatWriter,
+    file_chunk: &FileChunk,
+    context: &ProcessingContext,
+    chunk_index: usize,
+) -> Result<u64, PipelineError> {
+    let is_encrypted = context
+        .metadata()
+        .get("encrypted")
+        .map(|v| v == "true")
+        .unwrap_or(false);
+    let (nonce, chunk_data) = if is_encrypted && file_chunk.data().len() >= 12 {
+        let mut nonce_array = [0u8; 12];
+        nonce_array.copy_from_slice(&file_chunk.data()[..12]);
+        (nonce_array, file_chunk.data()[12..].to_vec())
+    } else {
+        ([0u8; 12], file_chunk.data().to_vec())
+    };
+
+    let bytes_out = chunk_data.len() as u64;
+    let chunk_format = ChunkFormat::new(nonce, chunk_data);
+    CONCURRENCY_METRICS.writer_write_started();
+    let write_start = std::time::Instant::now();
+    let write_result = writer.write_chunk_at_position(chunk_format, chunk_index as u64).await;
+    CONCURRENCY_METRICS.writer_write_completed(write_start.elapsed(), write_result.is_ok());
+    write_result?;
+    Ok(bytes_out)
+}
+
+/// Writes the checkpoint of a file interrupted by shutdown next to its
+/// unfinished output
+async fn write_shutdown_checkpoint(
+    output_path: &std::path::Path,
+    checkpoint: &ShutdownCheckpoint,
+) -> Result<(), PipelineError> {
+    let checkpoint_path = ShutdownCheckpoint::path_for(output_path);
+    tokio::fs::write(&checkpoint_path, checkpoint.to_json()?)
+        .await
+        .map_err(|e| {
+            PipelineError::io_error(format!(
+                "Failed to write checkpoint {}: {}",
+                checkpoint_path.display(),
+                e
+            ))
+        })?;
+    warn!(
+        "Interrupted {}: {}; checkpoint written to {}",
+        output_path.display(),
+        checkpoint.summary(),
+        checkpoint_path.display()
+    );
+    Ok(())
+}
+
+/// Reads up to [`DETECTION_LENGTH`] bytes from the start of `path`, for
+/// content type detection
+async fn read_head(path: &std::path::Path) -> Result<Vec<u8>, PipelineError> {
+    use tokio::io::AsyncReadExt;
+
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| PipelineError::io_error(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut head = Vec::with_capacity(DETECTION_LENGTH);
+    file.take(DETECTION_LENGTH as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(|e| PipelineError::io_error(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(head)
+}
+
+/// A chunk handed from one stage pool to the next in the stage-parallel
+/// topology
+struct StageMessage {
+    /// Index of this chunk in the file (0-based)
+    chunk_index: usize,
+
+    /// In-flight window reservation, held until the chunk is written
+    _window_permit: WindowPermit,
+
+    /// The chunk as left by the previous stage
+    file_chunk: FileChunk,
+
+    /// The chunk's child context, carried from stage to stage
+    context: ProcessingContext,
+
+    /// Input size of the chunk, before any stage ran
+    chunk_bytes: u64,
+
+    /// Time spent in stages so far
+    busy: std::time::Duration,
+
+    /// Timestamp when message was enqueued (for queue wait metrics)
+    enqueued_at: std::time::Instant,
+}
+
+/// Dependencies shared by every stage pool of one file
+struct StagePoolContext {
+    pipeline: Arc<Pipeline>,
+    stage_executor: Arc<dyn StageExecutor>,
+    writer: Arc<Box<dyn BinaryFormatWriter>>,
+    /// Checks security context expiry at every stage boundary
+    security_guard: Arc<SecurityContextGuard>,
+    /// Shared by all pools: how many panicked stage runs may still be retried
+    restart_budget: Arc<RestartBudget>,
+    /// One cell per final-stage worker
+    worker_metrics: Arc<WorkerMetricsShards>,
+    /// One flag per chunk, set once the chunk is written
+    completed_chunks: Arc<Vec<AtomicBool>>,
+    /// Priority at which workers wait for CPU tokens
+    priority: JobPriority,
+    cancel_token: adaptive_pipeline_bootstrap::shutdown::CancellationToken,
+}
+
+/// Spawns one pool of `workers_per_stage` workers for every stage of the
+/// pipeline, chained by channels of `channel_depth` chunks.
+///
+/// The first pool takes chunks from the reader; the last writes them. Each
+/// chunk is processed in a child of `parent`, created as it enters the
+/// first pool. CPU tokens still bound how many stages run at once across
+/// all pools, so idle pools cost nothing.
+fn spawn_stage_pools(
+    rx_cpu: Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<ChunkMessage>>>,
+    parent: &ProcessingContext,
+    workers_per_stage: usize,
+    channel_depth: usize,
+    ctx: Arc<StagePoolContext>,
+) -> Vec<tokio::task::JoinHandle<Result<WorkerStats, PipelineError>>> {
+    let mut handles = Vec::new();
+
+    // Build from the last stage back, so each pool knows where to send
+    let mut tx_next: Option<tokio::sync::mpsc::Sender<StageMessage>> = None;
+    for stage_index in (1..ctx.pipeline.stages().len()).rev() {
+        let (tx, rx) = tokio::sync::mpsc::channel::<StageMessage>(channel_depth);
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+        for worker in 0..workers_per_stage {
+            handles.push(tokio::spawn(stage_worker(
+                stage_index * workers_per_stage + worker,
+                stage_index,
+                rx.clone(),
+                |message| message,
+                tx_next.clone(),
+                ctx.clone(),
+            )));
+        }
+        tx_next = Some(tx);
+    }
+
+    for worker in 0..workers_per_stage {
+        let parent = parent.clone();
+        let admit = move |message: ChunkMessage| StageMessage {
+            chunk_index: message.chunk_index,
+            _window_permit: message.window_permit,
+            chunk_bytes: message.file_chunk.data().len() as u64,
+            file_chunk: message.file_chunk,
+            context: parent.child(),
+            busy: std::time::Duration::ZERO,
+            enqueued_at: message.enqueued_at,
+        };
+        handles.push(tokio::spawn(stage_worker(
+            worker,
+            0,
+            rx_cpu.clone(),
+            admit,
+            tx_next.clone(),
+            ctx.clone(),
+        )));
+    }
+
+    handles
+}
+
+/// One worker of a stage pool in the stage-parallel topology
+///
+/// Runs stage `stage_index` on every chunk it receives, then forwards the
+/// chunk to the next pool, or writes it if this is the last stage. A
+/// panicking stage run is retried on the chunk and context as they were
+/// before the stage while the restart budget lasts; any other failure
+/// cancels the whole file.
+async fn stage_worker<T, F>(
+    worker_id: usize,
+    stage_index: usize,
+    rx: Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<T>>>,
+    admit: F,
+    tx_next: Option<tokio::sync::mpsc::Sender<StageMessage>>,
+    ctx: Arc<StagePoolContext>,
+) -> Result<WorkerStats, PipelineError>
+where
+    F: Fn(T) -> StageMessage,
+{
+    use crate::infrastructure::runtime::RESOURCE_MANAGER;
+
+    let stage = &ctx.pipeline.stages()[stage_index];
+    let mut chunks_processed = 0;
+
+    loop {
+        // Same work-stealing receive as the chunk-parallel pool
+        #[allow(clippy::await_holding_lock)]
+        let received = tokio::select! {
+            _ = ctx.cancel_token.cancelled() => break,
+            message = async {
+                let mut rx = rx.lock().await;
+                rx.recv().await
+            } => message,
+        };
+        let Some(received) = received else {
+            // Upstream pool finished and dropped its senders
+            break;
+        };
+        let mut message = admit(received);
+        CONCURRENCY_METRICS.record_cpu_queue_wait(message.enqueued_at.elapsed());
+
+        let cpu_wait_start = std::time::Instant::now();
+        let cpu_permit = RESOURCE_MANAGER
+            .acquire_cpu_at(ctx.priority)
+            .await
+            .map_err(|e| PipelineError::resource_exhausted(format!("Failed to acquire CPU token: {}", e)))?;
+        CONCURRENCY_METRICS.record_cpu_wait(cpu_wait_start.elapsed());
+        CONCURRENCY_METRICS.worker_started();
+
+        let busy_start = std::time::Instant::now();
+        let mut attempt_chunk = message.file_chunk;
+        message.file_chunk = loop {
+            // Keep the pre-stage chunk and context while a panic could still be retried
+            let retry_copy = ctx
+                .restart_budget
+                .has_remaining()
+                .then(|| (attempt_chunk.clone(), message.context.clone()));
+
+            match execute_stage(
+                stage,
+                &ctx.stage_executor,
+                &ctx.security_guard,
+                attempt_chunk,
+                &mut message.context,
+            )
+            .await
+            {
+                Ok(file_chunk) => break file_chunk,
+                Err(e) => {
+                    if matches!(e, PipelineError::StagePanicked(_)) {
+                        CONCURRENCY_METRICS.record_worker_panic();
+                    }
+                    match retry_copy {
+                        Some((chunk, context))
+                            if matches!(e, PipelineError::StagePanicked(_)) && ctx.restart_budget.try_restart() =>
+                        {
+                            CONCURRENCY_METRICS.record_worker_restart();
+                            warn!(
+                                "Stage '{}' worker {} replaced after {} ({}/{} restarts used)",
+                                stage.name(),
+                                worker_id,
+                                e,
+                                ctx.restart_budget.restarts(),
+                                ctx.restart_budget.max_restarts()
+                            );
+                            attempt_chunk = chunk;
+                            message.context = context;
+                        }
+                        _ => {
+                            ctx.cancel_token.cancel();
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+        };
+        message.busy += busy_start.elapsed();
+        drop(cpu_permit);
+        CONCURRENCY_METRICS.worker_completed();
+        chunks_processed += 1;
+
+        match &tx_next {
+            Some(tx) => {
+                message.enqueued_at = std::time::Instant::now();
+                let sent = tokio::select! {
+                    _ = ctx.cancel_token.cancelled() => break,
+                    sent = tx.send(message) => sent,
+                };
+                if sent.is_err() {
+                    // The next pool is gone; it has cancelled the file
+                    break;
+                }
+            }
+            None => {
+                let bytes_out = match write_processed_chunk(
+                    &**ctx.writer,
+                    &message.file_chunk,
+                    &message.context,
+                    message.chunk_index,
+                )
+                .await
+                {
+                    Ok(bytes_out) => bytes_out,
+                    Err(e) => {
+                        ctx.cancel_token.cancel();
+                        return Err(e);
+                    }
+                };
+                ctx.worker_metrics.record_chunk(
+                    worker_id,
+                    message.chunk_index as u64,
+                    message.chunk_bytes,
+                    bytes_out,
+                    message.busy,
+                );
+                ctx.completed_chunks[message.chunk_index].store(true, Ordering::Relaxed);
+
+                // Roll this chunk's metrics up to the file-level context
+                message.context.record_chunk_processed(message.chunk_bytes);
+                message.context.finalize();
+            }
+        }
+    }
+
+    Ok(WorkerStats {
+        worker_id,
+        chunks_processed,
+    })
 }
 
 /// CPU Worker Task - Stage 2 of Execution Pipeline
@@ -315,7 +741,6 @@ async fn cpu_worker_task(
     mut rx_cpu: tokio::sync::mpsc::Receiver<ChunkMessage>,
     ctx: CpuWorkerContext,
 ) -> Result<WorkerStats, PipelineError> {
-    use crate::infrastructure::metrics::CONCURRENCY_METRICS;
     use crate::infrastructure::runtime::RESOURCE_MANAGER;
 
     let mut chunks_processed = 0;
@@ -341,23 +766,23 @@ async fn cpu_worker_task(
         // PROCESSING PIPELINE: Business logic execution
         // ===================================================
 
-        // Create local processing context for this chunk
-        let mut local_context = ProcessingContext::new(
-            ctx.input_size,
-            ctx.security_context.clone(),
-        );
+        // Create a child context for this chunk; its metrics roll up to the
+        // file-level context when finalized
+        let mut local_context = ctx.processing_context.child();
 
         // Execute each configured stage sequentially on this chunk
         // Start with the FileChunk we received
-        let mut file_chunk = chunk_msg.file_chunk;
+        let file_chunk = chunk_msg.file_chunk;
+        let chunk_bytes = file_chunk.data().len() as u64;
 
-        for stage in ctx.pipeline.stages() {
-            file_chunk = ctx
-                .stage_executor
-                .execute(stage, file_chunk, &mut local_context)
-                .await
-                .map_err(|e| PipelineError::processing_failed(format!("Stage execution failed: {}", e)))?;
-        }
+        let file_chunk = execute_stages(
+            &ctx.pipeline,
+            &ctx.stage_executor,
+            &ctx.security_guard,
+            file_chunk,
+            &mut local_context,
+        )
+        .await?;
 
         // ===================================================
         // EXECUTION PIPELINE: Direct concurrent write
@@ -396,9 +821,17 @@ async fn cpu_worker_task(
         let chunk_format = ChunkFormat::new(nonce, chunk_data);
 
         // Direct concurrent write to calculated position
-        ctx.writer
+        CONCURRENCY_METRICS.writer_write_started();
+        let write_start = std::time::Instant::now();
+        let write_result = ctx
+            .writer
             .write_chunk_at_position(chunk_format, chunk_msg.chunk_index as u64)
-            .await?;
+            .await;
+        CONCURRENCY_METRICS.writer_write_completed(write_start.elapsed(), write_result.is_ok());
+        write_result?;
+
+        local_context.record_chunk_processed(chunk_bytes);
+        local_context.finalize();
 
         // Educational: CPU token released automatically (RAII drop)
         CONCURRENCY_METRICS.worker_completed();
@@ -423,6 +856,7 @@ pub struct ConcurrentPipeline {
     stage_executor: Arc<dyn StageExecutor>,
     binary_format_service: Arc<dyn BinaryFormatService>,
     active_pipelines: Arc<RwLock<std::collections::HashMap<String, PipelineAggregate>>>,
+    chunk_size_history: Option<Arc<dyn ChunkSizeHistoryRepository>>,
 }
 
 impl ConcurrentPipeline {
@@ -451,6 +885,36 @@ impl ConcurrentPipeline {
             stage_executor,
             binary_format_service,
             active_pipelines: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            chunk_size_history: None,
+        }
+    }
+
+    /// Bases processing time and resource estimates on the throughput runs
+    /// recorded in `chunk_size_history`
+    pub fn with_chunk_size_history(mut self, chunk_size_history: Arc<dyn ChunkSizeHistoryRepository>) -> Self {
+        self.chunk_size_history = Some(chunk_size_history);
+        self
+    }
+
+    /// Builds the cost model for `pipeline` from its recorded throughput on
+    /// the configured storage type
+    ///
+    /// History only refines estimates, so a failed lookup is logged and the
+    /// stage defaults are used.
+    async fn cost_model(&self, pipeline: &Pipeline) -> CostModel {
+        let Some(chunk_size_history) = &self.chunk_size_history else {
+            return CostModel::default();
+        };
+        let storage_type = crate::infrastructure::runtime::try_resource_manager()
+            .map(|rm| rm.storage_type())
+            .unwrap_or(crate::infrastructure::runtime::StorageType::Auto)
+            .to_string();
+        match chunk_size_history.history(pipeline.id(), &storage_type).await {
+            Ok(history) => CostModel::from_history(history),
+            Err(e) => {
+                warn!(pipeline = %pipeline.name(), "Failed to load chunk size history: {}", e);
+                CostModel::default()
+            }
         }
     }
 
@@ -472,16 +936,17 @@ impl ConcurrentPipeline {
             }
             StageType::Encryption => {
                 let encryption_config = self.extract_encryption_config(stage)?;
-                // Generate a temporary key material for demonstration (NOT secure for
-                // production)
-                let key_material = KeyMaterial {
-                    key: vec![0u8; 32],   // 32-byte key
-                    nonce: vec![0u8; 12], // 12-byte nonce
-                    salt: vec![0u8; 32],  // 32-byte salt
-                    algorithm: encryption_config.algorithm.clone(),
-                    created_at: chrono::Utc::now(),
-                    expires_at: None,
-                };
+                // The key is handed to the stage by `Pipeline::set_encryption_key`;
+                // chunk nonces come from the nonce strategy
+                let key = stage
+                    .configuration()
+                    .parameters
+                    .get("key")
+                    .ok_or_else(|| PipelineError::MissingParameter("key".into()))?;
+                let key = general_purpose::STANDARD
+                    .decode(key)
+                    .map_err(|e| PipelineError::InvalidParameter(format!("Invalid base64 key: {}", e)))?;
+                let key_material = KeyMaterial::new(key, Vec::new(), Vec::new(), encryption_config.algorithm.clone());
                 self.encryption_service
                     .encrypt_chunk(chunk, &encryption_config, &key_material, context)
             }
@@ -508,17 +973,16 @@ impl ConcurrentPipeline {
         stage: &PipelineStage,
     ) -> Result<adaptive_pipeline_domain::services::CompressionConfig, PipelineError> {
         let algorithm_str = stage.configuration().algorithm.as_str();
-        let algorithm = match algorithm_str {
-            "brotli" => adaptive_pipeline_domain::services::CompressionAlgorithm::Brotli,
-            "gzip" => adaptive_pipeline_domain::services::CompressionAlgorithm::Gzip,
-            "zstd" => adaptive_pipeline_domain::services::CompressionAlgorithm::Zstd,
-            "lz4" => adaptive_pipeline_domain::services::CompressionAlgorithm::Lz4,
-            _ => {
+        let algorithm = match Algorithm::parse(algorithm_str)
+            .and_then(|algorithm| adaptive_pipeline_domain::services::CompressionAlgorithm::try_from(&algorithm))
+        {
+            Ok(adaptive_pipeline_domain::services::CompressionAlgorithm::Custom(_)) | Err(_) => {
                 return Err(PipelineError::InvalidConfiguration(format!(
                     "Unsupported compression algorithm: {}",
                     algorithm_str
                 )));
             }
+            Ok(algorithm) => algorithm,
         };
 
         // Extract compression level from parameters
@@ -549,20 +1013,11 @@ impl ConcurrentPipeline {
         stage: &PipelineStage,
     ) -> Result<adaptive_pipeline_domain::services::EncryptionConfig, PipelineError> {
         let algorithm_str = stage.configuration().algorithm.as_str();
-        let algorithm = match algorithm_str {
-            "aes256-gcm" | "aes256gcm" => adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes256Gcm,
-            "chacha20-poly1305" | "chacha20poly1305" => {
-                adaptive_pipeline_domain::services::EncryptionAlgorithm::ChaCha20Poly1305
-            }
-            "aes128-gcm" | "aes128gcm" => adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes128Gcm,
-            "aes192-gcm" | "aes192gcm" => adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes192Gcm,
-            _ => {
-                return Err(PipelineError::InvalidConfiguration(format!(
-                    "Unsupported encryption algorithm: {}",
-                    algorithm_str
-                )));
-            }
-        };
+        let algorithm = Algorithm::parse(algorithm_str)
+            .and_then(|algorithm| adaptive_pipeline_domain::services::EncryptionAlgorithm::try_from(&algorithm))
+            .map_err(|_| {
+                PipelineError::InvalidConfiguration(format!("Unsupported encryption algorithm: {}", algorithm_str))
+            })?;
 
         let kdf = stage
             .configuration()
@@ -575,16 +1030,35 @@ impl ConcurrentPipeline {
                 _ => adaptive_pipeline_domain::services::KeyDerivationFunction::Argon2,
             });
 
+        let nonce_strategy = match stage.configuration().parameters.get(NONCE_STRATEGY_KEY) {
+            Some(name) => name.parse::<NonceStrategy>()?,
+            None => NonceStrategy::default(),
+        };
+
+        // Set per file by `Pipeline::bind_chunks`
+        let associated_data = stage
+            .configuration()
+            .parameters
+            .get(ASSOCIATED_DATA_KEY)
+            .map(|data| {
+                hex::decode(data)
+                    .map_err(|e| PipelineError::InvalidConfiguration(format!("Invalid associated data: {}", e)))
+            })
+            .transpose()?;
+
+        let kdf_cost = KdfCost::from_parameters(&stage.configuration().parameters)?.unwrap_or_default();
+
         Ok(adaptive_pipeline_domain::services::EncryptionConfig {
             algorithm,
             key_derivation: kdf.unwrap_or(adaptive_pipeline_domain::services::KeyDerivationFunction::Argon2),
-            key_size: 32,             // Default to 256-bit keys
-            nonce_size: 12,           // Standard for AES-GCM
-            salt_size: 16,            // Standard salt size
-            iterations: 100_000,      // Default iterations for PBKDF2
-            memory_cost: Some(65536), // Default for Argon2
-            parallel_cost: Some(1),   // Default for Argon2
-            associated_data: None,    // No additional authenticated data by default
+            key_size: 32,        // Default to 256-bit keys
+            salt_size: 16,       // Standard salt size
+            iterations: 100_000, // Default iterations for PBKDF2
+            memory_cost: Some(kdf_cost.memory_kib()),
+            parallel_cost: Some(kdf_cost.parallelism()),
+            associated_data,
+            nonce_size: nonce_strategy.nonce_length() as u32,
+            nonce_strategy,
         })
     }
 
@@ -619,15 +1093,20 @@ impl PipelineService for ConcurrentPipeline {
 
         let start_time = std::time::Instant::now();
 
-        // Load pipeline from repository using the provided PipelineId
-        let pipeline = self
-            .pipeline_repository
-            .find_by_id(context.pipeline_id.clone())
-            .await?
-            .ok_or_else(|| PipelineError::PipelineNotFound(context.pipeline_id.to_string()))?;
+        // Use the caller's pipeline, or load it by the provided PipelineId
+        let pipeline = match context.pipeline.clone() {
+            Some(pipeline) => pipeline,
+            None => self
+                .pipeline_repository
+                .find_by_id(context.pipeline_id.clone())
+                .await?
+                .ok_or_else(|| PipelineError::PipelineNotFound(context.pipeline_id.to_string()))?,
+        };
 
         // Validate pipeline before execution
         self.validate_pipeline(&pipeline).await?;
+        let checksum_algorithm = pipeline.checksum_algorithm()?;
+        checksum_algorithm.ensure_permitted()?;
 
         // Get file metadata first to determine optimal chunk size
         let input_metadata = tokio::fs::metadata(input_path)
@@ -635,41 +1114,67 @@ impl PipelineService for ConcurrentPipeline {
             .map_err(|e| PipelineError::IoError(e.to_string()))?;
         let input_size = input_metadata.len();
 
-        // Calculate optimal chunk size based on file size
-        let chunk_size = adaptive_pipeline_domain::value_objects::ChunkSize::optimal_for_file_size(input_size).bytes();
+        // Use the caller's chunk size, or the optimal one for the file size
+        let chunk_size = context
+            .chunk_size_override
+            .unwrap_or_else(|| adaptive_pipeline_domain::value_objects::ChunkSize::optimal_for_file_size(input_size))
+            .bytes();
 
-        // Use FileIOService to read file in chunks (streaming, memory-efficient)
-        // This avoids loading the entire file into memory
+        // Calculate original file checksum incrementally from a chunk stream
+        // Only one chunk is in memory at a time, so the input never has to
+        // fit in memory before the pipeline starts
         let read_options = adaptive_pipeline_domain::services::file_io_service::ReadOptions {
             chunk_size: Some(chunk_size),
-            use_memory_mapping: false,  // Start with streaming; can optimize later
+            use_memory_mapping: false,  // Stream from disk, don't load all into memory
             calculate_checksums: false, // We'll calculate overall checksum ourselves
             ..Default::default()
         };
-
-        let read_result = self.file_io_service.read_file_chunks(input_path, read_options).await?;
-
-        let input_chunks = read_result.chunks;
-
-        // Calculate original file checksum incrementally from chunks
-        // This way we don't need the entire file in memory
-        let original_checksum = {
-            let mut context = ring::digest::Context::new(&ring::digest::SHA256);
-            for chunk in &input_chunks {
-                context.update(chunk.data());
+        // The content type is detected from the first bytes of the same pass.
+        // With checksum offload only those bytes are read now; the reader
+        // feeds the checksum while the stages run
+        let checksum_offload = context.checksum_offload;
+        let (mut original_checksum, content_type) = if checksum_offload {
+            (String::new(), ContentType::detect(&read_head(input_path).await?))
+        } else {
+            let mut chunks = self
+                .file_io_service
+                .stream_file_chunks(input_path, read_options)
+                .await?;
+            let mut hasher = ContentHasher::new(checksum_algorithm);
+            let mut head = Vec::with_capacity(DETECTION_LENGTH);
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                let wanted = DETECTION_LENGTH - head.len();
+                head.extend_from_slice(&chunk.data()[..chunk.data().len().min(wanted)]);
+                hasher.update(chunk.data());
             }
-            let digest = context.finish();
-            hex::encode(digest.as_ref())
+            (hasher.finalize_hex(), ContentType::detect(&head))
         };
 
-        debug!(
-            "Input file: {}, SHA256: {}",
-            Byte::from_u128(input_size as u128)
-                .unwrap_or_else(|| Byte::from_u64(0))
-                .get_appropriate_unit(byte_unit::UnitType::Decimal)
-                .to_string(),
-            original_checksum
-        );
+        if !checksum_offload {
+            debug!(
+                "Input file: {}, {}: {}",
+                Byte::from_u128(input_size as u128)
+                    .unwrap_or_else(|| Byte::from_u64(0))
+                    .get_appropriate_unit(byte_unit::UnitType::Decimal)
+                    .to_string(),
+                checksum_algorithm,
+                original_checksum
+            );
+        }
+
+        // Stages whose skip_content rule matches sit this file out, and are
+        // left out of its header so restore doesn't reverse them
+        let (mut pipeline, skipped_stages) = pipeline.for_content(&content_type)?;
+        if skipped_stages.is_empty() {
+            debug!("Detected content type: {}", content_type);
+        } else {
+            info!(
+                "Skipping stage(s) {} for {} content",
+                skipped_stages.join(", "),
+                content_type
+            );
+        }
 
         // Create .adapipe file header
         let mut header = adaptive_pipeline_domain::value_objects::FileHeader::new(
@@ -680,7 +1185,21 @@ impl PipelineService for ConcurrentPipeline {
                 .to_string(),
             input_size,
             original_checksum.clone(),
-        );
+        )
+        .with_provenance(build_provenance())
+        .with_content_type(content_type.clone())
+        .with_checksum_algorithm(checksum_algorithm);
+        if let Some(correlation_id) = &context.correlation_id {
+            header = header.with_correlation_id(correlation_id.clone());
+        }
+        if let Ok(modified) = input_metadata.modified() {
+            header = header.with_original_modified(modified.into());
+        }
+        if FIPS_MODE {
+            header = header.with_metadata(FIPS_MODE_METADATA_KEY.to_string(), "true".to_string());
+        }
+        // Restoring the output is held to the pipeline's security policy
+        pipeline.security_policy()?.write_entries(&mut header.metadata);
 
         // Add processing steps based on pipeline stages
         for stage in pipeline.stages() {
@@ -694,13 +1213,7 @@ impl PipelineService for ConcurrentPipeline {
                 adaptive_pipeline_domain::entities::pipeline_stage::StageType::Compression => {
                     debug!("✅ Matched Compression stage: {}", stage.name());
                     let config = self.extract_compression_config(stage)?;
-                    let algorithm_str = match config.algorithm {
-                        adaptive_pipeline_domain::services::CompressionAlgorithm::Brotli => "brotli",
-                        adaptive_pipeline_domain::services::CompressionAlgorithm::Gzip => "gzip",
-                        adaptive_pipeline_domain::services::CompressionAlgorithm::Zstd => "zstd",
-                        adaptive_pipeline_domain::services::CompressionAlgorithm::Lz4 => "lz4",
-                        adaptive_pipeline_domain::services::CompressionAlgorithm::Custom(ref name) => name.as_str(),
-                    };
+                    let algorithm = Algorithm::from(&config.algorithm);
                     let level = match config.level {
                         adaptive_pipeline_domain::services::CompressionLevel::Fastest => 1,
                         adaptive_pipeline_domain::services::CompressionLevel::Fast => 3,
@@ -708,19 +1221,13 @@ impl PipelineService for ConcurrentPipeline {
                         adaptive_pipeline_domain::services::CompressionLevel::Best => 9,
                         adaptive_pipeline_domain::services::CompressionLevel::Custom(level) => level,
                     };
-                    header = header.add_compression_step(algorithm_str, level);
+                    header = header.add_compression_step(algorithm.name(), level);
                 }
                 adaptive_pipeline_domain::entities::pipeline_stage::StageType::Encryption => {
                     debug!("✅ Matched Encryption stage: {}", stage.name());
                     let config = self.extract_encryption_config(stage)?;
-                    let algorithm_str = match config.algorithm {
-                        adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes128Gcm => "aes128gcm",
-                        adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes192Gcm => "aes192gcm",
-                        adaptive_pipeline_domain::services::EncryptionAlgorithm::Aes256Gcm => "aes256gcm",
-                        adaptive_pipeline_domain::services::EncryptionAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
-                        adaptive_pipeline_domain::services::EncryptionAlgorithm::Custom(ref name) => name.as_str(),
-                    };
-                    header = header.add_encryption_step(algorithm_str, "argon2", 32, 12);
+                    let algorithm = Algorithm::from(&config.algorithm);
+                    header = header.add_encryption_step(algorithm.name(), "argon2", 32, config.nonce_strategy);
                 }
                 adaptive_pipeline_domain::entities::pipeline_stage::StageType::Checksum => {
                     debug!("✅ Matched Checksum stage: {}", stage.name());
@@ -752,20 +1259,60 @@ impl PipelineService for ConcurrentPipeline {
         // Set chunk info and pipeline ID - chunk_size already calculated above
         header = header
             .with_chunk_info(chunk_size as u32, 0) // chunk_count will be updated later
-            .with_pipeline_id(context.pipeline_id.to_string());
+            .with_pipeline_id(context.pipeline_id.to_string())
+            .with_chunk_binding();
+
+        // Encrypted chunks are authenticated with their position in this file
+        pipeline.bind_chunks(&ChunkBinding::new(header.pipeline_id.clone(), header.format_version));
+
+        // Encryption keys are derived from the caller's passphrase with a
+        // fresh salt; the salt and cost go in the header so restore can
+        // derive the same key again. The cost is the encryption stage's own,
+        // or else calibrated on this host.
+        if pipeline.encrypts() {
+            match &context.password {
+                Some(password) => {
+                    let stage_cost = pipeline
+                        .stages()
+                        .iter()
+                        .filter(|stage| stage.stage_type() == &StageType::Encryption)
+                        .map(|stage| KdfCost::from_parameters(&stage.configuration().parameters))
+                        .find_map(Result::transpose)
+                        .transpose()?;
+                    let cost = stage_cost.unwrap_or_else(MultiAlgoEncryption::calibrated_kdf_cost);
+                    let kdf = MultiAlgoEncryption::new_password_kdf(cost)?;
+                    let key = MultiAlgoEncryption::derive_password_key(password, &kdf)?;
+                    pipeline.set_encryption_key(&general_purpose::STANDARD.encode(key.expose_secret()));
+                    header = header.with_password_kdf(&kdf);
+                }
+                None if !pipeline.has_encryption_key() => {
+                    return Err(PipelineError::MissingParameter(format!(
+                        "password; pipeline '{}' encrypts, run with --password-prompt",
+                        pipeline.name()
+                    )));
+                }
+                None => {}
+            }
+        }
 
-        // Clone security context before moving it into ProcessingContext
-        let security_context_for_tasks = context.security_context.clone();
+        // Long-running jobs re-check (and if possible refresh) the security
+        // context at every stage boundary
+        let security_guard = Arc::new(
+            SecurityContextGuard::new(&context.pipeline_id, context.security_context.clone())
+                .with_refresher(context.security_refresher.clone())
+                .with_observer(context.observer.clone()),
+        );
 
         let mut processing_context = ProcessingContext::new(
             input_size,
             context.security_context,
         );
+        processing_context.add_metadata(CONTENT_TYPE_METADATA_KEY.to_string(), content_type.to_string());
 
-        // Set input file checksum in metrics
+        // Set input file checksum in metrics (once known, when offloaded)
         {
             let mut metrics = processing_context.metrics().clone();
-            metrics.set_input_file_info(input_size, Some(original_checksum.clone()));
+            metrics.set_input_file_info(input_size, (!checksum_offload).then(|| original_checksum.clone()));
             processing_context.update_metrics(metrics);
         }
 
@@ -794,10 +1341,57 @@ impl PipelineService for ConcurrentPipeline {
         // STEP 2: Create thread-safe writer
         // Writer uses &self for concurrent writes (no mutex on individual writes!)
         // But we wrap in Arc for sharing, and Mutex is needed only for finalization
-        let binary_writer = self
-            .binary_format_service
-            .create_writer(output_path, header.clone())
-            .await?;
+        //
+        // The output's worst-case size is known up front, so reserve it before
+        // workers start writing out of order: less fragmentation, and a full
+        // disk shows up now rather than halfway through the run.
+        let reserve = worst_case_output_size(chunk_size, total_chunks as u64);
+        let reserve_display = Byte::from_u64(reserve).get_appropriate_unit(byte_unit::UnitType::Binary);
+        // In direct I/O mode the output is kept out of the page cache too
+        let direct_io = self.file_io_service.get_config().direct_io;
+        let preallocated = if total_chunks > 0 {
+            let sink = PreallocatedFileSink::create(output_path, reserve)
+                .await
+                .and_then(|sink| if direct_io { sink.without_page_cache() } else { Ok(sink) });
+            match sink {
+                Ok(sink) => Some(Arc::new(sink)),
+                Err(e) => {
+                    warn!(
+                        "Could not reserve {:.1} for {} ({}); the disk may fill up during processing",
+                        reserve_display,
+                        output_path.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let binary_writer = match preallocated {
+            Some(sink) => {
+                debug!("Reserved {:.1} for {}", reserve_display, output_path.display());
+                self.binary_format_service
+                    .create_writer_to(sink, header.clone())
+                    .await?
+            }
+            None if direct_io => {
+                let sink = FileSink::create(output_path)?.without_page_cache()?;
+                self.binary_format_service
+                    .create_writer_to(Arc::new(sink), header.clone())
+                    .await?
+            }
+            None => {
+                self.binary_format_service
+                    .create_writer(output_path, header.clone())
+                    .await?
+            }
+        };
+        // The output now exists; give it its configured mode before any data
+        // lands in it
+        if let Some(mode) = context.output_mode {
+            apply_mode(output_path, mode)?;
+        }
         let writer_shared = Arc::new(binary_writer);
 
         // Create progress indicator for this operation
@@ -842,16 +1436,51 @@ impl PipelineService for ConcurrentPipeline {
         );
 
         // STEP 4: Create cancellation token for graceful shutdown
-        // Educational: Enables graceful cancellation of reader and worker tasks
-        // TODO: Wire this to global ShutdownCoordinator for Ctrl-C handling
+        // Educational: Enables graceful cancellation of reader and worker tasks.
+        // A failing worker cancels it, and so does the caller's shutdown signal
+        // (SIGINT, SIGTERM, service stop), forwarded here
         let shutdown_coordinator =
-            adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator::new(std::time::Duration::from_secs(5));
+            adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator::new(context.grace_period);
         let cancel_token = shutdown_coordinator.token();
-
-        // STEP 5: Create bounded channels for pipeline stages
-        // Educational: Channel depth creates backpressure to prevent memory overload
-        let channel_depth = context.channel_depth_override.unwrap_or(4);
-        debug!("Using channel depth: {}", channel_depth);
+        let grace_period = context.grace_period;
+        if context
+            .shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_requested())
+        {
+            cancel_token.cancel();
+        }
+        let shutdown_forwarder = context.shutdown.clone().map(|shutdown| {
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move {
+                shutdown.requested().await;
+                warn!(
+                    "Shutdown requested: finishing in-flight chunks for up to {:?}",
+                    grace_period
+                );
+                cancel_token.cancel();
+            })
+        });
+        // One flag per chunk, set once it is written; on shutdown these
+        // become the checkpoint
+        let completed_chunks: Arc<Vec<AtomicBool>> =
+            Arc::new((0..total_chunks).map(|_| AtomicBool::new(false)).collect());
+
+        // STEP 5: Create the in-flight window and bounded channel
+        // Educational: The window bounds the bytes in flight, so huge chunks
+        // cannot exhaust memory while small chunks still pipeline deeply
+        let window = Arc::new(InFlightWindow::new(
+            context.inflight_window_override.unwrap_or(DEFAULT_INFLIGHT_WINDOW),
+            chunk_size,
+        ));
+        let channel_depth = context
+            .channel_depth_override
+            .unwrap_or_else(|| window.chunk_capacity());
+        debug!(
+            "Using in-flight window of {} bytes, channel depth: {}",
+            window.budget(),
+            channel_depth
+        );
         let (tx_cpu, rx_cpu) = tokio::sync::mpsc::channel::<ChunkMessage>(channel_depth);
 
         // STEP 5: Wrap receiver in Arc<Mutex> for sharing among workers
@@ -859,14 +1488,25 @@ impl PipelineService for ConcurrentPipeline {
         // This adds some contention, but only on channel receive (not on writes!)
         let rx_cpu_shared = Arc::new(tokio::sync::Mutex::new(rx_cpu));
 
+        // With checksum offload the reader tees every chunk to a hashing
+        // thread, so the input checksum is ready when the workers finish
+        let checksum_tee = checksum_offload.then(|| ChecksumTee::spawn(checksum_algorithm, channel_depth));
+
         // STEP 6: Spawn reader task
         // Single reader streams chunks from disk to CPU workers
+        // Educational: Read-ahead depth follows the storage's latency
+        let prefetch_depth = prefetch_depth(
+            crate::infrastructure::runtime::resource_manager().storage_type(),
+            channel_depth,
+        );
         let reader_handle = tokio::spawn(reader_task(
             input_path.to_path_buf(),
             chunk_size,
             tx_cpu,
             self.file_io_service.clone(),
-            channel_depth,
+            prefetch_depth,
+            window,
+            checksum_tee.as_ref().map(ChecksumTee::feed),
             cancel_token.clone(),
         ));
 
@@ -874,21 +1514,106 @@ impl PipelineService for ConcurrentPipeline {
         // Multiple workers receive chunks, process them, and write directly
         let mut worker_handles = Vec::new();
         let pipeline_arc = Arc::new(pipeline.clone());
+        // Shared by the pool: how many panicked chunks may still be retried
+        let restart_budget = Arc::new(RestartBudget::new(context.max_worker_restarts));
+        // One lock-free metrics cell per worker, folded by the sampler and at
+        // finalize
+        let worker_metrics = Arc::new(WorkerMetricsShards::new(worker_count));
+        // Waits recorded from here on belong to this run's summary
+        let concurrency_start = CONCURRENCY_METRICS.wait_snapshot();
+        let workers_started_at = std::time::Instant::now();
+
+        // STEP 7b: Spawn the metrics sampler
+        // Educational: Workers only bump their own atomic cell; this task
+        // alone reads them, so progress and Prometheus never contend with
+        // the hot path
+        let (stop_sampler, mut sampler_stopped) = tokio::sync::oneshot::channel::<()>();
+        let sampler_handle = {
+            let worker_metrics = worker_metrics.clone();
+            let progress_indicator = progress_indicator.clone();
+            let observer = context.observer.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(METRICS_SAMPLE_INTERVAL);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                loop {
+                    tokio::select! {
+                        _ = &mut sampler_stopped => break,
+                        _ = interval.tick() => {}
+                    }
+                    let sample = worker_metrics.snapshot();
+                    let Some(last_chunk) = sample.last_chunk else {
+                        continue;
+                    };
+                    progress_indicator
+                        .show_sample(sample.chunks, last_chunk, Some(CONCURRENCY_METRICS.pipeline_pressure()))
+                        .await;
+                    if let Some(observer) = &observer {
+                        let elapsed = start_time.elapsed().as_secs_f64();
+                        let throughput = if elapsed > 0.0 {
+                            (sample.bytes_in as f64) / elapsed / (1024.0 * 1024.0)
+                        } else {
+                            0.0
+                        };
+                        observer.on_progress_update(sample.bytes_in, input_size, throughput).await;
+                    }
+                }
+            })
+        };
 
-        for worker_id in 0..worker_count {
+        // Stage-parallel: a pool per stage, chained by channels, replaces the
+        // chunk-parallel pool below
+        let topology = pipeline.execution_topology()?;
+        let pool_size = match topology {
+            ExecutionTopology::StageParallel => worker_count * pipeline.stages().len(),
+            ExecutionTopology::ChunkParallel => worker_count,
+        };
+        let chunk_parallel_workers = match topology {
+            ExecutionTopology::StageParallel => {
+                debug!(
+                    "Stage-parallel topology: {} pools of {} workers",
+                    pipeline.stages().len(),
+                    worker_count
+                );
+                worker_handles.extend(spawn_stage_pools(
+                    rx_cpu_shared.clone(),
+                    &processing_context,
+                    worker_count,
+                    channel_depth,
+                    Arc::new(StagePoolContext {
+                        pipeline: pipeline_arc.clone(),
+                        stage_executor: self.stage_executor.clone(),
+                        writer: writer_shared.clone(),
+                        security_guard: security_guard.clone(),
+                        restart_budget: restart_budget.clone(),
+                        worker_metrics: worker_metrics.clone(),
+                        completed_chunks: completed_chunks.clone(),
+                        priority: context.priority,
+                        cancel_token: cancel_token.clone(),
+                    }),
+                ));
+                0
+            }
+            ExecutionTopology::ChunkParallel => worker_count,
+        };
+
+        for worker_id in 0..chunk_parallel_workers {
             let rx_cpu_clone = rx_cpu_shared.clone();
             let writer_clone = writer_shared.clone();
             let pipeline_clone = pipeline_arc.clone();
             let stage_executor_clone = self.stage_executor.clone();
             let input_path_clone = input_path.to_path_buf();
             let output_path_clone = output_path.to_path_buf();
-            let security_context_clone = security_context_for_tasks.clone();
+            let worker_context = processing_context.child();
+            let security_guard_clone = security_guard.clone();
             let cancel_token_clone = cancel_token.clone();
+            let restart_budget_clone = restart_budget.clone();
+            let worker_metrics_clone = worker_metrics.clone();
+            let completed_chunks_clone = completed_chunks.clone();
+            let priority = context.priority;
 
             // Each worker shares the receiver via Arc<Mutex>
             let worker_handle = tokio::spawn(async move {
-                use crate::infrastructure::metrics::CONCURRENCY_METRICS;
-                use crate::infrastructure::runtime::RESOURCE_MANAGER;
+                            use crate::infrastructure::runtime::RESOURCE_MANAGER;
 
                 let mut chunks_processed = 0;
 
@@ -919,7 +1644,7 @@ impl PipelineService for ConcurrentPipeline {
 
                             // Acquire global CPU token
                             let cpu_wait_start = std::time::Instant::now();
-                            let _cpu_permit = RESOURCE_MANAGER.acquire_cpu().await.map_err(|e| {
+                            let _cpu_permit = RESOURCE_MANAGER.acquire_cpu_at(priority).await.map_err(|e| {
                                 PipelineError::resource_exhausted(format!("Failed to acquire CPU token: {}", e))
                             })?;
                             let cpu_wait_duration = cpu_wait_start.elapsed();
@@ -927,47 +1652,78 @@ impl PipelineService for ConcurrentPipeline {
                             CONCURRENCY_METRICS.record_cpu_wait(cpu_wait_duration);
                             CONCURRENCY_METRICS.worker_started();
 
-                            // Create local processing context
-                            let mut local_context = ProcessingContext::new(
-                                input_size,
-                                security_context_clone.clone(),
-                            );
-
-                            // Execute all processing stages
-                            let mut file_chunk = chunk_msg.file_chunk;
-                            for stage in pipeline_clone.stages() {
-                                file_chunk = stage_executor_clone
-                                    .execute(stage, file_chunk, &mut local_context)
-                                    .await
-                                    .map_err(|e| {
-                                        PipelineError::processing_failed(format!("Stage execution failed: {}", e))
-                                    })?;
-                            }
-
-                            // Prepare and write chunk
-                            // Extract nonce from encrypted data if encryption was applied
-                            let (nonce, chunk_data) = if file_chunk.data().len() >= 12 {
-                                let is_encrypted = local_context
-                                    .metadata()
-                                    .get("encrypted")
-                                    .map(|v| v == "true")
-                                    .unwrap_or(false);
-
-                                if is_encrypted {
-                                    let mut nonce_array = [0u8; 12];
-                                    nonce_array.copy_from_slice(&file_chunk.data()[..12]);
-                                    (nonce_array, file_chunk.data()[12..].to_vec())
-                                } else {
-                                    ([0u8; 12], file_chunk.data().to_vec())
+                            // Execute all processing stages, each attempt in a fresh
+                            // child context so a panicked attempt leaves nothing behind
+                            let busy_start = std::time::Instant::now();
+                            let mut attempt_chunk = chunk_msg.file_chunk;
+                            let chunk_bytes = attempt_chunk.data().len() as u64;
+                            let (file_chunk, mut local_context) = loop {
+                                // Keep a pristine copy while a panic could still be retried
+                                let retry_copy = restart_budget_clone.has_remaining().then(|| attempt_chunk.clone());
+                                let mut local_context = worker_context.child();
+
+                                match execute_stages(
+                                    &pipeline_clone,
+                                    &stage_executor_clone,
+                                    &security_guard_clone,
+                                    attempt_chunk,
+                                    &mut local_context,
+                                )
+                                .await
+                                {
+                                    Ok(file_chunk) => break (file_chunk, local_context),
+                                    Err(e) => {
+                                        if matches!(e, PipelineError::StagePanicked(_)) {
+                                            CONCURRENCY_METRICS.record_worker_panic();
+                                        }
+                                        match retry_copy {
+                                            Some(copy)
+                                                if matches!(e, PipelineError::StagePanicked(_))
+                                                    && restart_budget_clone.try_restart() =>
+                                            {
+                                                // Replace the worker's state and retry the chunk
+                                                CONCURRENCY_METRICS.record_worker_restart();
+                                                warn!(
+                                                    "Worker {} replaced after {} ({}/{} restarts used)",
+                                                    worker_id,
+                                                    e,
+                                                    restart_budget_clone.restarts(),
+                                                    restart_budget_clone.max_restarts()
+                                                );
+                                                attempt_chunk = copy;
+                                            }
+                                            _ => {
+                                                // The file can no longer complete: stop the reader
+                                                // and the other workers instead of leaving the
+                                                // reader blocked on a channel nobody drains
+                                                cancel_token_clone.cancel();
+                                                return Err(e);
+                                            }
+                                        }
+                                    }
                                 }
-                            } else {
-                                ([0u8; 12], file_chunk.data().to_vec())
                             };
 
-                            let chunk_format = ChunkFormat::new(nonce, chunk_data);
-                            writer_clone
-                                .write_chunk_at_position(chunk_format, chunk_msg.chunk_index as u64)
-                                .await?;
+                            // Write the chunk, splitting off the nonce if encryption ran
+                            let bytes_out = write_processed_chunk(
+                                &**writer_clone,
+                                &file_chunk,
+                                &local_context,
+                                chunk_msg.chunk_index,
+                            )
+                            .await?;
+                            worker_metrics_clone.record_chunk(
+                                worker_id,
+                                chunk_msg.chunk_index as u64,
+                                chunk_bytes,
+                                bytes_out,
+                                busy_start.elapsed(),
+                            );
+                            completed_chunks_clone[chunk_msg.chunk_index].store(true, Ordering::Relaxed);
+
+                            // Roll this chunk's metrics up to the file-level context
+                            local_context.record_chunk_processed(chunk_bytes);
+                            local_context.finalize();
 
                             CONCURRENCY_METRICS.worker_completed();
                             chunks_processed += 1;
@@ -991,30 +1747,137 @@ impl PipelineService for ConcurrentPipeline {
         // =============================================================================
         // STEP 7: WAIT FOR PIPELINE COMPLETION
         // =============================================================================
-        // Reader → Workers all complete independently, coordinated by channels
+        // Reader → Workers all complete independently, coordinated by channels.
+        // Once cancelled they get the grace period to drain; anything still
+        // running after that is aborted
+
+        let abort_handles: Vec<tokio::task::AbortHandle> = std::iter::once(reader_handle.abort_handle())
+            .chain(worker_handles.iter().map(|handle| handle.abort_handle()))
+            .collect();
+        let drain = async {
+            // Wait for reader to finish
+            let reader_result = reader_handle
+                .await
+                .map_err(|e| PipelineError::processing_failed(format!("Reader task failed: {}", e)))?;
+
+            // Wait for all workers to complete
+            let mut worker_error = None;
+            for (worker_id, worker_handle) in worker_handles.into_iter().enumerate() {
+                let worker_result = worker_handle
+                    .await
+                    .map_err(|e| PipelineError::processing_failed(format!("Worker {} failed: {}", worker_id, e)))?;
+
+                match worker_result {
+                    Ok(worker_stats) => {
+                        debug!(
+                            "Worker {} completed: {} chunks processed",
+                            worker_stats.worker_id, worker_stats.chunks_processed
+                        );
+                    }
+                    Err(e) => {
+                        worker_error.get_or_insert(e);
+                    }
+                }
+            }
+            Ok::<_, PipelineError>((reader_result, worker_error))
+        };
+        let grace_expired = async {
+            cancel_token.cancelled().await;
+            tokio::time::sleep(grace_period).await;
+        };
+        let drained = tokio::select! {
+            biased;
+            drained = drain => Some(drained),
+            _ = grace_expired => None,
+        };
+        if let Some(forwarder) = shutdown_forwarder {
+            forwarder.abort();
+        }
+        if drained.is_none() {
+            for handle in &abort_handles {
+                handle.abort();
+            }
+            warn!("Workers still busy {:?} after cancellation; aborted them", grace_period);
+        }
 
-        // Wait for reader to finish
-        let reader_stats = reader_handle
-            .await
-            .map_err(|e| PipelineError::processing_failed(format!("Reader task failed: {}", e)))??;
+        // Every worker is done: stop sampling and take the exact fold
+        let _ = stop_sampler.send(());
+        let _ = sampler_handle.await;
+        let worker_totals = worker_metrics.snapshot();
+        for (worker_id, chunks) in worker_totals.per_worker_chunks.iter().enumerate() {
+            debug!("Worker {} metrics: {} chunks", worker_id, chunks);
+        }
+        let concurrency = CONCURRENCY_METRICS.summary_since(
+            &concurrency_start,
+            pool_size,
+            worker_totals.busy,
+            workers_started_at.elapsed(),
+        );
+
+        // A shutdown that left chunks unwritten ends here: the output cannot be
+        // finalized, so record what was done next to it instead
+        let completed: Vec<u64> = completed_chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, done)| done.load(Ordering::Relaxed))
+            .map(|(index, _)| index as u64)
+            .collect();
+        let shutdown_requested = context
+            .shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_requested());
+        if shutdown_requested && completed.len() < total_chunks {
+            CONCURRENCY_METRICS.writer_finalized(worker_totals.chunks as usize);
+            let checkpoint = ShutdownCheckpoint::new(
+                input_path.display().to_string(),
+                output_path.display().to_string(),
+                context.pipeline_id.to_string(),
+                chunk_size as u64,
+                total_chunks as u64,
+            )
+            .with_completed_chunks(completed)
+            .with_drain(drained.is_some(), context.grace_period);
+            write_shutdown_checkpoint(output_path, &checkpoint).await?;
+            return Err(PipelineError::cancelled_with_msg(checkpoint.summary()));
+        }
+        let (reader_result, worker_error) = match drained {
+            Some(drained) => drained?,
+            None => {
+                CONCURRENCY_METRICS.writer_finalized(worker_totals.chunks as usize);
+                return Err(PipelineError::processing_failed(format!(
+                    "Workers did not stop within {:?} of cancellation",
+                    context.grace_period
+                )));
+            }
+        };
+
+        // A failed worker cancels the reader, so its error is the root cause;
+        // either way the chunks written so far will never be finalized
+        let reader_result = match worker_error {
+            Some(e) => Err(e),
+            None => reader_result,
+        };
+        let reader_stats = reader_result.inspect_err(|_| {
+            CONCURRENCY_METRICS.writer_finalized(worker_totals.chunks as usize);
+        })?;
 
         debug!(
             "Reader completed: {} chunks read, {} bytes",
             reader_stats.chunks_read, reader_stats.bytes_read
         );
 
-        // Wait for all workers to complete
-        let mut total_chunks_processed = 0;
-        for (worker_id, worker_handle) in worker_handles.into_iter().enumerate() {
-            let worker_stats = worker_handle
-                .await
-                .map_err(|e| PipelineError::processing_failed(format!("Worker {} failed: {}", worker_id, e)))??;
-
+        // The reader has fed the last chunk, so the offloaded input checksum
+        // completes now
+        if let Some(checksum_tee) = checksum_tee {
+            original_checksum = checksum_tee.finish().await?;
+            header.original_checksum = original_checksum.clone();
+            let mut metrics = processing_context.metrics().clone();
+            metrics.set_input_file_info(input_size, Some(original_checksum.clone()));
+            processing_context.update_metrics(metrics);
             debug!(
-                "Worker {} completed: {} chunks processed",
-                worker_stats.worker_id, worker_stats.chunks_processed
+                "Input {} (computed alongside the stages): {}",
+                checksum_algorithm, original_checksum
             );
-            total_chunks_processed += worker_stats.chunks_processed;
         }
 
         // =============================================================================
@@ -1024,14 +1887,23 @@ impl PipelineService for ConcurrentPipeline {
 
         // Finalize writer using &self signature (works perfectly with Arc!)
         // Educational: No Arc::try_unwrap needed, just call finalize directly
-        let _total_bytes_written = writer_shared.finalize(header).await?;
+        let finalize_result = writer_shared.finalize(header).await;
+        CONCURRENCY_METRICS.writer_finalized(worker_totals.chunks as usize);
+        let _total_bytes_written = finalize_result?;
 
         // =============================================================================
         // STEP 9: COLLECT METRICS AND COMPLETE
         // =============================================================================
 
-        // Calculate final metrics from task results
-        let chunks_processed = total_chunks_processed as u64;
+        // Fold the per-chunk metrics (including per-stage aggregates) rolled up
+        // by every worker into the file-level context
+        processing_context.collect_child_metrics();
+
+        // Calculate final metrics from the folded worker metrics
+        let chunks_processed = worker_totals.chunks;
+        if let Some(last_chunk) = worker_totals.last_chunk {
+            progress_indicator.show_sample(chunks_processed, last_chunk, None).await;
+        }
         let total_bytes_processed = reader_stats.bytes_read;
 
         // Show completion summary to user
@@ -1075,17 +1947,23 @@ impl PipelineService for ConcurrentPipeline {
         metrics.update_bytes_processed(total_bytes_processed);
         metrics.update_chunks_processed(chunks_processed);
 
-        // Calculate output file checksum
-        let output_checksum = {
-            let output_data = tokio::fs::read(output_path)
-                .await
-                .map_err(|e| PipelineError::io_error(e.to_string()))?;
-            let digest = ring::digest::digest(&ring::digest::SHA256, &output_data);
-            hex::encode(digest.as_ref())
+        // Calculate output file checksum; with checksum offload the writer
+        // hashed the file as it wrote it, so it is not read back
+        let offloaded_output_checksum = writer_shared.file_checksum().filter(|_| checksum_offload);
+        let output_checksum = match offloaded_output_checksum {
+            Some(checksum) => checksum,
+            None => {
+                let output_data = tokio::fs::read(output_path)
+                    .await
+                    .map_err(|e| PipelineError::io_error(e.to_string()))?;
+                let digest = ring::digest::digest(&ring::digest::SHA256, &output_data);
+                hex::encode(digest.as_ref())
+            }
         };
 
         // Set the actual output file size and checksum
         metrics.set_output_file_info(total_output_bytes, Some(output_checksum));
+        metrics.set_concurrency(concurrency);
         metrics.end();
 
         // Notify observer that processing completed with final metrics
@@ -1109,16 +1987,21 @@ impl PipelineService for ConcurrentPipeline {
             let stage_start = std::time::Instant::now();
 
             // Process chunks in parallel within this stage
-            // Note: Each chunk gets a cloned context since we're processing in parallel
+            // Note: Each chunk gets a child context whose metrics roll up to `context`
             let futures: Vec<_> = processed_chunks
                 .into_iter()
                 .map(|chunk| {
-                    let mut ctx = context.clone();
-                    async move { self.process_chunk_through_stage(chunk, stage, &mut ctx).await }
+                    let mut ctx = context.child();
+                    async move {
+                        let result = self.process_chunk_through_stage(chunk, stage, &mut ctx).await;
+                        ctx.finalize();
+                        result
+                    }
                 })
                 .collect();
 
             processed_chunks = future::try_join_all(futures).await?;
+            context.collect_child_metrics();
 
             let stage_duration = stage_start.elapsed();
             self.update_metrics(context, stage.name(), stage_duration);
@@ -1172,22 +2055,7 @@ impl PipelineService for ConcurrentPipeline {
         pipeline: &Pipeline,
         file_size: u64,
     ) -> Result<std::time::Duration, PipelineError> {
-        let mut total_seconds = 0.0;
-        let file_size_mb = (file_size as f64) / (1024.0 * 1024.0);
-
-        for stage in pipeline.stages() {
-            // Estimate based on stage type and file size
-            let stage_seconds = match stage.stage_type() {
-                adaptive_pipeline_domain::entities::StageType::Compression => file_size_mb / 50.0, // 50 MB/s
-                adaptive_pipeline_domain::entities::StageType::Encryption => file_size_mb / 100.0, // 100 MB/s
-                _ => file_size_mb / 200.0,
-                /* 200 MB/s for other
-                 * operations */
-            };
-            total_seconds += stage_seconds;
-        }
-
-        Ok(std::time::Duration::from_secs_f64(total_seconds))
+        Ok(self.cost_model(pipeline).await.estimate_processing_time(pipeline, file_size))
     }
 
     async fn get_resource_requirements(
@@ -1195,43 +2063,7 @@ impl PipelineService for ConcurrentPipeline {
         pipeline: &Pipeline,
         file_size: u64,
     ) -> Result<ResourceRequirements, PipelineError> {
-        let mut total_memory_mb = 0.0;
-        let mut total_cpu_cores = 0;
-        let mut estimated_time_seconds = 0.0;
-
-        for stage in pipeline.stages() {
-            // Estimate memory usage based on stage type and chunk size
-            let chunk_size = stage.configuration().chunk_size.unwrap_or(1024 * 1024) as f64;
-            let stage_memory = (chunk_size / (1024.0 * 1024.0)) * 2.0; // Estimate 2x chunk size for processing
-            total_memory_mb += stage_memory;
-
-            // Estimate CPU cores needed
-            if stage.configuration().parallel_processing {
-                total_cpu_cores = total_cpu_cores.max(4); // Assume 4 cores for
-                                                          // parallel stages
-            } else {
-                total_cpu_cores = total_cpu_cores.max(1);
-            }
-
-            // Estimate processing time
-            let throughput_mbps = match stage.stage_type() {
-                adaptive_pipeline_domain::entities::StageType::Compression => 50.0,
-                adaptive_pipeline_domain::entities::StageType::Encryption => 100.0,
-                _ => 200.0,
-            };
-
-            let file_size_mb = (file_size as f64) / (1024.0 * 1024.0);
-            estimated_time_seconds += file_size_mb / throughput_mbps;
-        }
-
-        Ok(ResourceRequirements {
-            memory_bytes: (total_memory_mb * 1024.0 * 1024.0) as u64,
-            cpu_cores: total_cpu_cores,
-            disk_space_bytes: ((file_size as f64) * 2.0) as u64, // Estimate 2x file size
-            network_bandwidth_bps: None,                         // Not applicable for local processing
-            gpu_memory_bytes: None,                              // Not implemented yet
-            estimated_duration: std::time::Duration::from_secs_f64(estimated_time_seconds),
-        })
+        Ok(self.cost_model(pipeline).await.resource_requirements(pipeline, file_size))
     }
 
     async fn create_optimized_pipeline(
@@ -1399,6 +2231,11 @@ mod tests {
     use tempfile::TempDir;
     use tokio::fs;
 
+    /// A window large enough not to limit `chunk_size` chunks in tests
+    fn test_window(chunk_size: usize) -> Arc<InFlightWindow> {
+        Arc::new(InFlightWindow::new(DEFAULT_INFLIGHT_WINDOW, chunk_size))
+    }
+
     /// Tests pipeline creation for database operations.
     ///
     /// This test validates that pipelines can be created with proper
@@ -1593,7 +2430,7 @@ mod tests {
 
         // Start reader task (should detect cancellation and exit)
         let file_io = Arc::new(TokioFileIO::new(FileIOConfig::default())) as Arc<dyn FileIOService>;
-        let result = reader_task(input_file, 1024, tx, file_io, 10, cancel_token).await;
+        let result = reader_task(input_file, 1024, tx, file_io, 4, test_window(1024), None, cancel_token).await;
 
         // Verify cancellation error
         assert!(result.is_err());
@@ -1648,8 +2485,9 @@ mod tests {
 
         // Spawn reader task
         let file_io = Arc::new(TokioFileIO::new(FileIOConfig::default())) as Arc<dyn FileIOService>;
-        let reader_handle =
-            tokio::spawn(async move { reader_task(input_file, 1024, tx, file_io, 5, cancel_clone).await });
+        let reader_handle = tokio::spawn(async move {
+            reader_task(input_file, 1024, tx, file_io, 4, test_window(1024), None, cancel_clone).await
+        });
 
         // Let some chunks be sent
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
@@ -1669,6 +2507,94 @@ mod tests {
         assert!(rx.recv().await.is_none(), "Channel should be closed after cancellation");
     }
 
+    /// Tests that the reader never runs far ahead of slow workers.
+    ///
+    /// With nobody receiving, the reader may fill the channel, hold one
+    /// chunk waiting to send, and have `prefetch_depth` reads in flight;
+    /// nothing more of the file may be read.
+    #[tokio::test]
+    async fn test_reader_memory_is_bounded_by_channel_and_prefetch() {
+        use crate::infrastructure::adapters::file_io::TokioFileIO;
+        use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+        use adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator;
+        use adaptive_pipeline_domain::services::file_io_service::FileIOConfig;
+        use std::time::Duration;
+
+        let _ = init_resource_manager(ResourceConfig::default());
+
+        let temp_dir = TempDir::new().unwrap();
+        let input_file = temp_dir.path().join("large_input.bin");
+        fs::write(&input_file, vec![b'Y'; 1024 * 200]).await.unwrap();
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<ChunkMessage>(2);
+        let coordinator = ShutdownCoordinator::new(Duration::from_secs(5));
+        let cancel_token = coordinator.token();
+        let cancel_clone = cancel_token.clone();
+
+        let tokio_io = Arc::new(TokioFileIO::new(FileIOConfig::default()));
+        let file_io = tokio_io.clone() as Arc<dyn FileIOService>;
+        let reader_handle = tokio::spawn(async move {
+            reader_task(input_file, 1024, tx, file_io, 3, test_window(1024), None, cancel_clone).await
+        });
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let chunks_read = tokio_io.get_stats().chunks_processed;
+        assert!(chunks_read >= 2, "reader should fill the channel, read {}", chunks_read);
+        assert!(
+            chunks_read <= 2 + 1 + 3,
+            "reader ran ahead: {} of 200 chunks read",
+            chunks_read
+        );
+
+        cancel_token.cancel();
+        assert!(reader_handle.await.unwrap().is_err());
+        drop(rx);
+    }
+
+    /// Tests that the in-flight window bounds the bytes the reader holds,
+    /// however deep the channel.
+    #[tokio::test]
+    async fn test_reader_memory_is_bounded_by_inflight_window() {
+        use crate::infrastructure::adapters::file_io::TokioFileIO;
+        use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+        use adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator;
+        use adaptive_pipeline_domain::services::file_io_service::FileIOConfig;
+        use std::time::Duration;
+
+        let _ = init_resource_manager(ResourceConfig::default());
+
+        let temp_dir = TempDir::new().unwrap();
+        let input_file = temp_dir.path().join("large_input.bin");
+        fs::write(&input_file, vec![b'Z'; 1024 * 200]).await.unwrap();
+
+        // A deep channel and read-ahead, but room for only four chunks
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<ChunkMessage>(64);
+        let window = Arc::new(InFlightWindow::new(4 * 1024, 1024));
+        let coordinator = ShutdownCoordinator::new(Duration::from_secs(5));
+        let cancel_token = coordinator.token();
+        let cancel_clone = cancel_token.clone();
+
+        let tokio_io = Arc::new(TokioFileIO::new(FileIOConfig::default()));
+        let file_io = tokio_io.clone() as Arc<dyn FileIOService>;
+        let reader_window = window.clone();
+        let reader_handle = tokio::spawn(async move {
+            reader_task(input_file, 1024, tx, file_io, 16, reader_window, None, cancel_clone).await
+        });
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(tokio_io.get_stats().chunks_processed, 4, "reader ran past the window");
+        assert_eq!(window.available(), 0);
+
+        // Finishing a chunk releases its bytes for the next read
+        let first = rx.recv().await.unwrap();
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(tokio_io.get_stats().chunks_processed, 5);
+
+        cancel_token.cancel();
+        assert!(reader_handle.await.unwrap().is_err());
+    }
+
     /// Tests that cancelled workers exit gracefully.
     ///
     /// This test validates that worker tasks respect cancellation
@@ -1760,7 +2686,7 @@ mod tests {
 
         // Attempt to start reader
         let file_io = Arc::new(TokioFileIO::new(FileIOConfig::default())) as Arc<dyn FileIOService>;
-        let result = reader_task(input_file, 1024, tx, file_io, 10, cancel_token).await;
+        let result = reader_task(input_file, 1024, tx, file_io, 4, test_window(1024), None, cancel_token).await;
 
         // Should immediately return cancellation error
         assert!(result.is_err());
@@ -1810,4 +2736,70 @@ mod tests {
             .await
             .unwrap();
     }
+
+    /// Panics on every chunk, like a stage service with a bug
+    struct PanickingService;
+
+    impl adaptive_pipeline_domain::services::StageService for PanickingService {
+        fn process_chunk(
+            &self,
+            _chunk: FileChunk,
+            _operation: adaptive_pipeline_domain::entities::Operation,
+            _config: &adaptive_pipeline_domain::entities::pipeline_stage::StageConfiguration,
+            _context: &mut ProcessingContext,
+        ) -> Result<FileChunk, PipelineError> {
+            panic!("corrupt dictionary");
+        }
+
+        fn position(&self) -> adaptive_pipeline_domain::entities::StagePosition {
+            adaptive_pipeline_domain::entities::StagePosition::Any
+        }
+
+        fn is_reversible(&self) -> bool {
+            true
+        }
+
+        fn stage_type(&self) -> StageType {
+            StageType::PassThrough
+        }
+    }
+
+    /// Tests that a panicking stage fails the chunk instead of the worker.
+    ///
+    /// The panic must surface as `StagePanicked` naming the stage and chunk,
+    /// so the worker can retry the chunk or fail the run.
+    #[tokio::test]
+    async fn test_execute_stages_isolates_stage_panic() {
+        let mut services: std::collections::HashMap<String, Arc<dyn adaptive_pipeline_domain::services::StageService>> =
+            std::collections::HashMap::new();
+        services.insert("panicking".to_string(), Arc::new(PanickingService));
+        let stage_executor: Arc<dyn StageExecutor> = Arc::new(BasicStageExecutor::new(services));
+
+        let stage = PipelineStage::new(
+            "buggy".to_string(),
+            StageType::PassThrough,
+            adaptive_pipeline_domain::entities::pipeline_stage::StageConfiguration::new(
+                "panicking".to_string(),
+                std::collections::HashMap::new(),
+                false,
+            ),
+            1,
+        )
+        .unwrap();
+        let pipeline = Pipeline::new("panic-isolation".to_string(), vec![stage]).unwrap();
+        let security_context = SecurityContext::new(None, adaptive_pipeline_domain::entities::SecurityLevel::Internal);
+        let guard = SecurityContextGuard::new(pipeline.id(), security_context.clone());
+        let mut context = ProcessingContext::new(3, security_context);
+        let chunk = FileChunk::new(4, 0, vec![1, 2, 3], true).unwrap();
+
+        let err = execute_stages(&pipeline, &stage_executor, &guard, chunk, &mut context)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, PipelineError::StagePanicked(_)));
+        let message = err.to_string();
+        assert!(message.contains("'buggy'"), "{}", message);
+        assert!(message.contains("chunk 4"), "{}", message);
+        assert!(message.contains("corrupt dictionary"), "{}", message);
+    }
 }
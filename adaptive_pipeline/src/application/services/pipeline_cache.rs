@@ -0,0 +1,193 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Pipeline Cache
+//!
+//! Keeps pipelines loaded from the repository in memory, so that processing
+//! many files with one pipeline (a batch run, an import, a long-running
+//! service) reads its definition once instead of once per file.
+//!
+//! ## Freshness
+//!
+//! An entry is served for at most the cache's time-to-live, then reloaded.
+//! Use cases that change or remove a pipeline in this process invalidate it
+//! by ID, which drops it immediately; the TTL bounds how long an edit made by
+//! another process goes unnoticed.
+//!
+//! Lookups that find no pipeline are not cached, so a pipeline created after
+//! a miss is found on the next lookup.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use adaptive_pipeline_domain::entities::Pipeline;
+use adaptive_pipeline_domain::value_objects::{Namespace, PipelineId};
+use adaptive_pipeline_domain::PipelineError;
+
+/// How long a cached pipeline is served before it is reloaded
+pub const DEFAULT_PIPELINE_CACHE_TTL: Duration = Duration::from_secs(30);
+
+struct CachedPipeline {
+    pipeline: Pipeline,
+    loaded_at: Instant,
+}
+
+/// In-process cache of loaded pipelines, by namespace and name
+pub struct PipelineCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(Namespace, String), CachedPipeline>>,
+}
+
+impl PipelineCache {
+    /// Creates an empty cache serving entries for at most `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the pipeline named `name` in `namespace`, calling `load` when
+    /// it is not cached or its entry has expired
+    ///
+    /// # Errors
+    ///
+    /// Returns the error `load` returns; nothing is cached then.
+    pub async fn get_or_load<F, Fut>(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        load: F,
+    ) -> Result<Option<Pipeline>, PipelineError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<Pipeline>, PipelineError>>,
+    {
+        let key = (namespace.clone(), name.to_string());
+        if let Some(entry) = self.lock().get(&key) {
+            if entry.loaded_at.elapsed() < self.ttl {
+                debug!("Pipeline '{}' served from cache", name);
+                return Ok(Some(entry.pipeline.clone()));
+            }
+        }
+
+        let loaded = load().await?;
+        let mut entries = self.lock();
+        match &loaded {
+            Some(pipeline) => {
+                entries.insert(
+                    key,
+                    CachedPipeline {
+                        pipeline: pipeline.clone(),
+                        loaded_at: Instant::now(),
+                    },
+                );
+            }
+            None => {
+                entries.remove(&key);
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Drops the pipeline with `id`, in whatever namespace and under
+    /// whatever name it was cached
+    pub fn invalidate(&self, id: &PipelineId) {
+        self.lock().retain(|_, entry| entry.pipeline.id() != id);
+    }
+
+    /// Drops every cached pipeline
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Number of cached pipelines, expired ones included
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(Namespace, String), CachedPipeline>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for PipelineCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PIPELINE_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::entities::{PipelineStage, StageConfiguration, StageType};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn pipeline(name: &str) -> Pipeline {
+        let configuration = StageConfiguration::new("brotli".to_string(), HashMap::new(), false);
+        let stage = PipelineStage::new("compress".to_string(), StageType::Compression, configuration, 0).unwrap();
+        Pipeline::new(name.to_string(), vec![stage]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cached_pipeline_is_served_until_invalidated() {
+        let cache = PipelineCache::default();
+        let namespace = Namespace::default();
+        let stored = pipeline("nightly-backup");
+        let loads = AtomicUsize::new(0);
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(stored.clone()))
+        };
+
+        for _ in 0..3 {
+            let found = cache.get_or_load(&namespace, "nightly-backup", load).await.unwrap();
+            assert_eq!(found.unwrap().id(), stored.id());
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1, "later lookups are cache hits");
+
+        cache.invalidate(stored.id());
+        assert!(cache.is_empty());
+        cache.get_or_load(&namespace, "nightly-backup", load).await.unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_and_misses_are_reloaded() {
+        let cache = PipelineCache::new(Duration::ZERO);
+        let namespace = Namespace::default();
+        let loads = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            cache
+                .get_or_load(&namespace, "archive-logs", || async {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    Ok(Some(pipeline("archive-logs")))
+                })
+                .await
+                .unwrap();
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 2, "an expired entry is reloaded");
+
+        let missing = cache
+            .get_or_load(&namespace, "archive-logs", || async { Ok(None) })
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+        assert!(cache.is_empty(), "a miss is not cached");
+    }
+}
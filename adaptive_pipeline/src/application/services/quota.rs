@@ -0,0 +1,366 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Quota Service
+//!
+//! Admits processing jobs against per-namespace `QuotaLimits` before any work
+//! starts.
+//!
+//! ## Admission
+//!
+//! [`QuotaService::admit`] checks, in order:
+//!
+//! 1. the input size against `max_input_bytes`,
+//! 2. today's recorded output (from the `UsageRepository`) against
+//!    `max_output_bytes_per_day`,
+//! 3. the namespace's running jobs against `max_concurrent_jobs`.
+//!
+//! A successful admission returns a [`JobPermit`] that counts as a running
+//! job until dropped. With a `RunningJobRepository`, running jobs are counted
+//! in the repository, so the concurrency limit holds across every process
+//! sharing the database. Each job holds a lease there that its permit renews
+//! every third of [`RUNNING_JOB_LEASE_TTL`]; the jobs of a process that dies
+//! stop counting once their leases expire. Without a repository, jobs are
+//! counted per service instance.
+//!
+//! Dropping a permit releases its lease in the background; call
+//! [`settle_releases`] before the runtime shuts down so those releases land.
+//!
+//! Rejections fail with `PipelineError::QuotaExceeded` and increment the
+//! `quota_rejections_total` metric, labelled by namespace and limit.
+//!
+//! Limits configured for a namespace override the defaults field by field;
+//! limits left unset fall back to the defaults.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use ulid::Ulid;
+
+use crate::infrastructure::metrics::MetricsService;
+use adaptive_pipeline_domain::repositories::{RunningJobRepository, UsageRepository};
+use adaptive_pipeline_domain::value_objects::{Namespace, QuotaLimit, QuotaLimits};
+use adaptive_pipeline_domain::PipelineError;
+
+type RunningJobs = Arc<Mutex<HashMap<Namespace, usize>>>;
+
+/// How long a running job counts without its lease being renewed
+pub const RUNNING_JOB_LEASE_TTL: Duration = Duration::from_secs(60);
+
+/// Lease releases of dropped permits that have not finished yet
+static PENDING_RELEASES: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Waits for the lease releases of dropped permits to finish
+pub async fn settle_releases() {
+    let pending = std::mem::take(&mut *PENDING_RELEASES.lock().unwrap_or_else(PoisonError::into_inner));
+    for release in pending {
+        let _ = release.await;
+    }
+}
+
+/// Enforces per-namespace quota limits on processing jobs
+pub struct QuotaService {
+    default_limits: QuotaLimits,
+    namespace_limits: HashMap<Namespace, QuotaLimits>,
+    usage_repository: Option<Arc<dyn UsageRepository>>,
+    running_job_repository: Option<Arc<dyn RunningJobRepository>>,
+    metrics_service: Option<Arc<MetricsService>>,
+    running: RunningJobs,
+}
+
+impl QuotaService {
+    /// Creates a service applying `default_limits` to every namespace
+    pub fn new(default_limits: QuotaLimits) -> Self {
+        Self {
+            default_limits,
+            namespace_limits: HashMap::new(),
+            usage_repository: None,
+            running_job_repository: None,
+            metrics_service: None,
+            running: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Overrides limits for one namespace
+    pub fn with_namespace_limits(mut self, namespace: Namespace, limits: QuotaLimits) -> Self {
+        self.namespace_limits.insert(namespace, limits);
+        self
+    }
+
+    /// Reads daily usage from `usage_repository`
+    ///
+    /// Without a usage repository the daily output limit is not enforced.
+    pub fn with_usage_repository(mut self, usage_repository: Arc<dyn UsageRepository>) -> Self {
+        self.usage_repository = Some(usage_repository);
+        self
+    }
+
+    /// Counts running jobs in `running_job_repository`
+    ///
+    /// Without a running job repository, `max_concurrent_jobs` counts only
+    /// the jobs admitted by this service.
+    pub fn with_running_job_repository(mut self, running_job_repository: Arc<dyn RunningJobRepository>) -> Self {
+        self.running_job_repository = Some(running_job_repository);
+        self
+    }
+
+    /// Reports rejections to `metrics_service`
+    pub fn with_metrics(mut self, metrics_service: Arc<MetricsService>) -> Self {
+        self.metrics_service = Some(metrics_service);
+        self
+    }
+
+    /// Gets the effective limits for `namespace`
+    pub fn limits_for(&self, namespace: &Namespace) -> QuotaLimits {
+        match self.namespace_limits.get(namespace) {
+            Some(limits) => limits.or(self.default_limits),
+            None => self.default_limits,
+        }
+    }
+
+    /// Gets the number of jobs this service admitted that still run in
+    /// `namespace`
+    pub fn running_jobs(&self, namespace: &Namespace) -> usize {
+        let running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        running.get(namespace).copied().unwrap_or(0)
+    }
+
+    /// Admits a job on `input_bytes` of input in `namespace`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::QuotaExceeded` if any limit would be exceeded,
+    /// or a repository error if today's usage cannot be read.
+    pub async fn admit(&self, namespace: &Namespace, input_bytes: u64) -> Result<JobPermit, PipelineError> {
+        let limits = self.limits_for(namespace);
+
+        limits
+            .check_input_size(namespace, input_bytes)
+            .map_err(|e| self.rejected(namespace, QuotaLimit::MaxInputBytes, e))?;
+
+        if let (Some(_), Some(usage_repository)) = (limits.max_output_bytes_per_day, &self.usage_repository) {
+            let today = chrono::Utc::now().date_naive();
+            let usage = usage_repository.usage_on(namespace, today).await?;
+            limits
+                .check_daily_output(&usage)
+                .map_err(|e| self.rejected(namespace, QuotaLimit::MaxOutputBytesPerDay, e))?;
+        }
+
+        let lease = match (limits.max_concurrent_jobs, &self.running_job_repository) {
+            (Some(limit), Some(repository)) => Some(self.start_lease(namespace, &limits, limit, repository).await?),
+            _ => None,
+        };
+
+        {
+            let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+            let jobs = running.entry(namespace.clone()).or_insert(0);
+            if lease.is_none() {
+                limits
+                    .check_concurrent_jobs(namespace, *jobs)
+                    .map_err(|e| self.rejected(namespace, QuotaLimit::MaxConcurrentJobs, e))?;
+            }
+            *jobs += 1;
+        }
+
+        debug!(namespace = %namespace, input_bytes, "Job admitted");
+        Ok(JobPermit {
+            namespace: namespace.clone(),
+            running: self.running.clone(),
+            lease,
+        })
+    }
+
+    /// Takes one of the `limit` running job slots of `namespace` in
+    /// `repository`, renewing its lease until the permit is dropped
+    async fn start_lease(
+        &self,
+        namespace: &Namespace,
+        limits: &QuotaLimits,
+        limit: usize,
+        repository: &Arc<dyn RunningJobRepository>,
+    ) -> Result<RunningJobLease, PipelineError> {
+        let ttl = chrono::Duration::seconds(RUNNING_JOB_LEASE_TTL.as_secs() as i64);
+        let id = Ulid::new().to_string();
+        let now = chrono::Utc::now();
+        if !repository.start(namespace, &id, limit, now, now + ttl).await? {
+            // Jobs may have finished since; the namespace was full when refused
+            let running = repository.count_running(namespace, now).await?.max(limit);
+            let error = limits
+                .check_concurrent_jobs(namespace, running)
+                .err()
+                .unwrap_or_else(|| {
+                    PipelineError::quota_exceeded(format!("namespace '{}' is running too many jobs", namespace))
+                });
+            return Err(self.rejected(namespace, QuotaLimit::MaxConcurrentJobs, error));
+        }
+
+        let renewal = {
+            let repository = repository.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(RUNNING_JOB_LEASE_TTL / 3);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = repository.renew(&id, chrono::Utc::now() + ttl).await {
+                        warn!(job = %id, "Failed to renew running job lease: {}", e);
+                    }
+                }
+            })
+        };
+        Ok(RunningJobLease {
+            id,
+            repository: repository.clone(),
+            renewal,
+        })
+    }
+
+    fn rejected(&self, namespace: &Namespace, limit: QuotaLimit, error: PipelineError) -> PipelineError {
+        warn!(namespace = %namespace, limit = %limit, "Job rejected: {}", error);
+        if let Some(metrics_service) = &self.metrics_service {
+            metrics_service.increment_quota_rejections(namespace.as_str(), limit.as_str());
+        }
+        error
+    }
+}
+
+/// A job admitted by [`QuotaService::admit`]; counts as running until dropped
+pub struct JobPermit {
+    namespace: Namespace,
+    running: RunningJobs,
+    lease: Option<RunningJobLease>,
+}
+
+/// A permit's job as recorded in the `RunningJobRepository`
+struct RunningJobLease {
+    id: String,
+    repository: Arc<dyn RunningJobRepository>,
+    renewal: JoinHandle<()>,
+}
+
+impl JobPermit {
+    /// Gets the namespace the job runs in
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+}
+
+impl Drop for JobPermit {
+    fn drop(&mut self) {
+        let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(jobs) = running.get_mut(&self.namespace) {
+            *jobs = jobs.saturating_sub(1);
+        }
+        drop(running);
+
+        if let Some(lease) = self.lease.take() {
+            lease.renewal.abort();
+            // Outside a runtime the lease is left to expire
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let release = runtime.spawn(async move {
+                    if let Err(e) = lease.repository.finish(&lease.id).await {
+                        warn!(job = %lease.id, "Failed to release running job lease: {}", e);
+                    }
+                });
+                let mut pending = PENDING_RELEASES.lock().unwrap_or_else(PoisonError::into_inner);
+                pending.retain(|release| !release.is_finished());
+                pending.push(release);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::NamespaceUsage;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+
+    struct FixedUsage(u64);
+
+    #[async_trait]
+    impl UsageRepository for FixedUsage {
+        async fn record_job(&self, _: &Namespace, _: NaiveDate, _: u64, _: u64) -> Result<(), PipelineError> {
+            Ok(())
+        }
+
+        async fn usage_on(&self, namespace: &Namespace, day: NaiveDate) -> Result<NamespaceUsage, PipelineError> {
+            Ok(NamespaceUsage::new(namespace.clone(), day, 1, self.0, self.0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_jobs_released_on_drop() {
+        let namespace = Namespace::default();
+        let service = QuotaService::new(QuotaLimits::unlimited().with_max_concurrent_jobs(1));
+
+        let permit = service.admit(&namespace, 10).await.unwrap();
+        assert_eq!(service.running_jobs(&namespace), 1);
+        let err = service.admit(&namespace, 10).await.err().unwrap();
+        assert!(matches!(err, PipelineError::QuotaExceeded(_)));
+
+        // Other namespaces have their own count
+        assert!(service.admit(&Namespace::new("team-a").unwrap(), 10).await.is_ok());
+
+        drop(permit);
+        assert_eq!(service.running_jobs(&namespace), 0);
+        assert!(service.admit(&namespace, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_jobs_counted_across_services_sharing_a_database() {
+        use crate::infrastructure::repositories::sqlite_running_job::SqliteRunningJobRepository;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("quota.db").to_string_lossy().into_owned();
+        let namespace = Namespace::default();
+        let limits = QuotaLimits::unlimited().with_max_concurrent_jobs(1);
+        // Two processes, each with its own service and connection
+        let first = QuotaService::new(limits)
+            .with_running_job_repository(Arc::new(SqliteRunningJobRepository::new(&path).await.unwrap()));
+        let second = QuotaService::new(limits)
+            .with_running_job_repository(Arc::new(SqliteRunningJobRepository::new(&path).await.unwrap()));
+
+        let permit = first.admit(&namespace, 10).await.unwrap();
+        let err = second.admit(&namespace, 10).await.err().unwrap();
+        assert!(matches!(err, PipelineError::QuotaExceeded(_)));
+        assert_eq!(second.running_jobs(&namespace), 0);
+
+        drop(permit);
+        settle_releases().await;
+        assert!(second.admit(&namespace, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_input_and_daily_output_limits() {
+        let metrics = Arc::new(MetricsService::new().unwrap());
+        let team_a = Namespace::new("team-a").unwrap();
+        let service = QuotaService::new(QuotaLimits::unlimited().with_max_input_bytes(1_000))
+            .with_namespace_limits(
+                team_a.clone(),
+                QuotaLimits::unlimited().with_max_output_bytes_per_day(500),
+            )
+            .with_usage_repository(Arc::new(FixedUsage(500)))
+            .with_metrics(metrics.clone());
+
+        assert!(service.admit(&Namespace::default(), 1_000).await.is_ok());
+        assert!(service.admit(&Namespace::default(), 1_001).await.is_err());
+
+        // team-a inherits the default input limit and has used its daily output
+        assert_eq!(service.limits_for(&team_a).max_input_bytes, Some(1_000));
+        assert!(service.admit(&team_a, 1).await.is_err());
+        assert_eq!(service.running_jobs(&team_a), 0);
+
+        let exported = metrics.get_metrics().unwrap();
+        assert!(exported.contains("quota_rejections_total"));
+        assert!(exported.contains("max_output_bytes_per_day"));
+    }
+}
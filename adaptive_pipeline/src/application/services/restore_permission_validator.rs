@@ -0,0 +1,296 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Restore Permission Validator
+//!
+//! Checks that a [`RestoreFileCommand`] can succeed before any data is
+//! written, so a restore fails up front instead of leaving a truncated file
+//! behind.
+//!
+//! ## Checks
+//!
+//! [`RestorePermissionValidator::validate`] checks, in order:
+//!
+//! 1. **Overwrite policy**: an existing target requires a policy that may
+//!    replace it (`overwrite` or `if-newer`), must not be a directory and
+//!    must not be read-only. The use case applies the policy first, so by
+//!    the time the validator runs a `skip` has returned early and a
+//!    `rename` target no longer exists.
+//! 2. **Directory creation**: a missing parent directory requires
+//!    `create_directories`, and the nearest existing ancestor must be a
+//!    directory.
+//! 3. **Writability**: a probe file can be created in the parent directory
+//!    (or, when it will be created, in its nearest existing ancestor). The
+//!    probe is tracked in the managed temp root while it exists.
+//! 4. **Disk space**: the filesystem has room for the restored size.
+//!
+//! Commands with `validate_permissions` disabled are accepted without checks.
+//!
+//! ## Errors
+//!
+//! - `ValidationError`: the overwrite or directory-creation policy forbids
+//!   the restore
+//! - `IoError`: the target or its directory is not writable
+//! - `ResourceExhausted`: not enough free disk space
+
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::application::commands::RestoreFileCommand;
+use crate::infrastructure::runtime::temp_root::{release_temp_file, track_temp_file};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Prefix of the temporary file used to probe directory writability
+const PROBE_PREFIX: &str = ".adapipe_permission_test";
+
+/// Pre-flight checks for file restoration
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RestorePermissionValidator;
+
+impl RestorePermissionValidator {
+    /// Creates a validator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Validates that `command` can restore `required_bytes` bytes
+    pub fn validate(&self, command: &RestoreFileCommand, required_bytes: u64) -> Result<(), PipelineError> {
+        if !command.validate_permissions {
+            debug!("Permission validation disabled for {}", command.target_path.display());
+            return Ok(());
+        }
+
+        let target = &command.target_path;
+        Self::check_overwrite(target, command.overwrite_policy.may_replace())?;
+
+        let parent = Self::parent_dir(target);
+        let writable_dir = if parent.exists() {
+            parent
+        } else {
+            Self::check_directory_creation(&parent, command.create_directories)?
+        };
+
+        Self::check_writable(&writable_dir)?;
+        Self::check_disk_space(&writable_dir, required_bytes)?;
+
+        debug!("Restore target {} passed permission validation", target.display());
+        Ok(())
+    }
+
+    fn check_overwrite(target: &Path, overwrite: bool) -> Result<(), PipelineError> {
+        let Ok(metadata) = std::fs::metadata(target) else {
+            return Ok(());
+        };
+
+        if metadata.is_dir() {
+            return Err(PipelineError::validation_error(format!(
+                "Target path is a directory: {}",
+                target.display()
+            )));
+        }
+        if !overwrite {
+            return Err(PipelineError::validation_error(format!(
+                "Target file already exists: {} (use --if-exists overwrite to replace it)",
+                target.display()
+            )));
+        }
+        if metadata.permissions().readonly() {
+            return Err(PipelineError::io_error(format!(
+                "Target file is read-only: {}",
+                target.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the nearest existing ancestor of a missing `parent`, which is
+    /// where the missing directories will be created
+    fn check_directory_creation(parent: &Path, create_directories: bool) -> Result<PathBuf, PipelineError> {
+        if !create_directories {
+            return Err(PipelineError::validation_error(format!(
+                "Output directory does not exist: {} (use --mkdir to create it)",
+                parent.display()
+            )));
+        }
+
+        let ancestor = parent
+            .ancestors()
+            .skip(1)
+            .map(|path| {
+                if path.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    path
+                }
+            })
+            .find(|path| path.exists())
+            .unwrap_or_else(|| Path::new("."));
+
+        if !ancestor.is_dir() {
+            return Err(PipelineError::validation_error(format!(
+                "Cannot create directory {}: {} is not a directory",
+                parent.display(),
+                ancestor.display()
+            )));
+        }
+        Ok(ancestor.to_path_buf())
+    }
+
+    fn check_writable(dir: &Path) -> Result<(), PipelineError> {
+        let probe = tempfile::Builder::new()
+            .prefix(PROBE_PREFIX)
+            .tempfile_in(dir)
+            .map_err(|e| PipelineError::io_error(format!("Cannot write to directory {}: {}", dir.display(), e)))?;
+        // Tracked so a crash between creating and removing it is cleaned up
+        let probe_path = probe.path().to_path_buf();
+        track_temp_file(&probe_path);
+        drop(probe);
+        release_temp_file(&probe_path);
+        Ok(())
+    }
+
+    fn check_disk_space(dir: &Path, required_bytes: u64) -> Result<(), PipelineError> {
+        let available = fs2::available_space(dir)
+            .map_err(|e| PipelineError::io_error(format!("Cannot determine free space on {}: {}", dir.display(), e)))?;
+
+        if available < required_bytes {
+            return Err(PipelineError::resource_exhausted(format!(
+                "Insufficient disk space on {}: {} bytes required, {} bytes available",
+                dir.display(),
+                required_bytes,
+                available
+            )));
+        }
+        Ok(())
+    }
+
+    fn parent_dir(target: &Path) -> PathBuf {
+        match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn command(target: PathBuf) -> RestoreFileCommand {
+        RestoreFileCommand::new(PathBuf::from("in.adapipe"), target)
+    }
+
+    #[test]
+    fn test_new_target_in_existing_directory_passes() {
+        let dir = TempDir::new().unwrap();
+        let validator = RestorePermissionValidator::new();
+        validator.validate(&command(dir.path().join("out.txt")), 1024).unwrap();
+    }
+
+    #[test]
+    fn test_existing_target_requires_overwrite() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("out.txt");
+        std::fs::write(&target, b"existing").unwrap();
+        let validator = RestorePermissionValidator::new();
+
+        let err = validator.validate(&command(target.clone()), 0).unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+
+        validator.validate(&command(target).with_overwrite(true), 0).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_target_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("out.txt");
+        std::fs::write(&target, b"existing").unwrap();
+        let mut permissions = std::fs::metadata(&target).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&target, permissions).unwrap();
+
+        let err = RestorePermissionValidator::new()
+            .validate(&command(target).with_overwrite(true), 0)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::IoError(_)));
+    }
+
+    #[test]
+    fn test_directory_target_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let err = RestorePermissionValidator::new()
+            .validate(&command(dir.path().to_path_buf()).with_overwrite(true), 0)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_missing_directory_requires_create_directories() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("a/b/out.txt");
+        let validator = RestorePermissionValidator::new();
+
+        let err = validator
+            .validate(&command(target.clone()).with_create_directories(false), 0)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+
+        validator.validate(&command(target), 0).unwrap();
+    }
+
+    #[test]
+    fn test_directory_under_a_file_cannot_be_created() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("file"), b"").unwrap();
+        let err = RestorePermissionValidator::new()
+            .validate(&command(dir.path().join("file/sub/out.txt")), 0)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unwritable_directory_is_rejected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let locked = dir.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Privileged users (e.g. root in CI containers) bypass mode bits
+        if std::fs::write(locked.join("probe"), b"").is_ok() {
+            return;
+        }
+
+        let err = RestorePermissionValidator::new()
+            .validate(&command(locked.join("out.txt")), 0)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::IoError(_)));
+    }
+
+    #[test]
+    fn test_insufficient_disk_space_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let err = RestorePermissionValidator::new()
+            .validate(&command(dir.path().join("out.txt")), u64::MAX)
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ResourceExhausted(_)));
+    }
+
+    #[test]
+    fn test_validation_can_be_disabled() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("out.txt");
+        std::fs::write(&target, b"existing").unwrap();
+        RestorePermissionValidator::new()
+            .validate(&command(target).with_permission_validation(false), u64::MAX)
+            .unwrap();
+    }
+}
@@ -0,0 +1,232 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Security Context Guard
+//!
+//! Enforces security context expiration at stage boundaries for long-running
+//! jobs and propagates refreshed contexts to every worker.
+//!
+//! ## Overview
+//!
+//! Each chunk runs in its own `ProcessingContext`, which carries a copy of the
+//! job's `SecurityContext`. Before a stage runs, the worker asks the guard to
+//! check that copy:
+//!
+//! 1. If it has not expired, the stage runs.
+//! 2. If another worker already refreshed the job's context, the fresh one is
+//!    adopted and the stage runs.
+//! 3. Otherwise a `SecurityContextExpiredEvent` is emitted to the observer and
+//!    the configured `SecurityContextRefresher` is asked for a replacement.
+//!    Only one worker refreshes at a time; the rest wait and adopt its result.
+//! 4. Without a refresher, or if refreshing fails, the stage fails with
+//!    `PipelineError::SecurityContextExpired`.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use adaptive_pipeline_domain::entities::{ProcessingContext, SecurityContext};
+use adaptive_pipeline_domain::events::SecurityContextExpiredEvent;
+use adaptive_pipeline_domain::services::{ProcessingObserver, SecurityContextRefresher};
+use adaptive_pipeline_domain::value_objects::PipelineId;
+use adaptive_pipeline_domain::PipelineError;
+
+/// Shared, job-wide holder of the current security context
+///
+/// Cloned into every worker as an `Arc`; see the module documentation for the
+/// checking and refresh protocol.
+pub struct SecurityContextGuard {
+    pipeline_id: Uuid,
+    current: RwLock<SecurityContext>,
+    refresh_lock: tokio::sync::Mutex<()>,
+    refresher: Option<Arc<dyn SecurityContextRefresher>>,
+    observer: Option<Arc<dyn ProcessingObserver>>,
+}
+
+impl SecurityContextGuard {
+    /// Creates a guard for a job on `pipeline_id` starting from `initial`
+    pub fn new(pipeline_id: &PipelineId, initial: SecurityContext) -> Self {
+        Self {
+            pipeline_id: Uuid::from_bytes(pipeline_id.as_ulid().to_bytes()),
+            current: RwLock::new(initial),
+            refresh_lock: tokio::sync::Mutex::new(()),
+            refresher: None,
+            observer: None,
+        }
+    }
+
+    /// Sets the hook used to refresh an expired context
+    pub fn with_refresher(mut self, refresher: Option<Arc<dyn SecurityContextRefresher>>) -> Self {
+        self.refresher = refresher;
+        self
+    }
+
+    /// Sets the observer notified when the context expires
+    pub fn with_observer(mut self, observer: Option<Arc<dyn ProcessingObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Returns a copy of the job's current security context
+    pub fn current(&self) -> SecurityContext {
+        self.current.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Checks the security context of `context` before `stage_name` runs
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityContextExpired` when the context has
+    /// expired and no refresher is configured, or the refresher's error when
+    /// refreshing fails.
+    pub async fn check_stage_boundary(
+        &self,
+        context: &mut ProcessingContext,
+        stage_name: &str,
+    ) -> Result<(), PipelineError> {
+        if !context.security_context().is_expired() {
+            return Ok(());
+        }
+
+        // Another worker may already have refreshed the job's context
+        if self.adopt_current(context) {
+            return Ok(());
+        }
+
+        // Serialize refreshes so a burst of expired workers triggers one
+        // re-authentication; re-check once we hold the lock
+        let _refreshing = self.refresh_lock.lock().await;
+        if self.adopt_current(context) {
+            return Ok(());
+        }
+
+        let expired = self.current();
+        let event = SecurityContextExpiredEvent::new(self.pipeline_id, &expired, Some(stage_name.to_string()));
+        warn!(
+            session_id = %expired.session_id(),
+            stage = stage_name,
+            "Security context expired at stage boundary"
+        );
+        if let Some(observer) = &self.observer {
+            observer.on_security_context_expired(&event).await;
+        }
+
+        let Some(refresher) = &self.refresher else {
+            return expired.ensure_not_expired();
+        };
+
+        let mut refreshed = expired.clone();
+        refreshed.refresh(refresher.refresh(&expired).await?)?;
+        info!(
+            session_id = %refreshed.session_id(),
+            stage = stage_name,
+            "Security context refreshed"
+        );
+
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = refreshed.clone();
+        context.update_security_context(refreshed);
+        Ok(())
+    }
+
+    /// Copies the job's current context into `context` if it is still valid
+    fn adopt_current(&self, context: &mut ProcessingContext) -> bool {
+        let current = self.current();
+        if current.is_expired() {
+            return false;
+        }
+        context.update_security_context(current);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::entities::SecurityLevel;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn expired_context() -> SecurityContext {
+        SecurityContext::new(Some("alice".to_string()), SecurityLevel::Internal)
+            .with_expiry(chrono::Utc::now() - chrono::Duration::seconds(1))
+    }
+
+    struct CountingRefresher {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SecurityContextRefresher for CountingRefresher {
+        async fn refresh(&self, expired: &SecurityContext) -> Result<SecurityContext, PipelineError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(
+                SecurityContext::new(expired.user_id().map(str::to_string), SecurityLevel::Internal)
+                    .with_ttl(chrono::Duration::hours(1)),
+            )
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        expired_events: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ProcessingObserver for RecordingObserver {
+        async fn on_security_context_expired(&self, _event: &SecurityContextExpiredEvent) {
+            self.expired_events.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unexpired_context_passes() {
+        let security = SecurityContext::new(None, SecurityLevel::Internal).with_ttl(chrono::Duration::hours(1));
+        let guard = SecurityContextGuard::new(&PipelineId::new(), security.clone());
+        let mut context = ProcessingContext::new(10, security);
+
+        assert!(guard.check_stage_boundary(&mut context, "compression").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_expired_context_without_refresher_fails_and_emits_event() {
+        let observer = Arc::new(RecordingObserver::default());
+        let guard = SecurityContextGuard::new(&PipelineId::new(), expired_context())
+            .with_observer(Some(observer.clone() as Arc<dyn ProcessingObserver>));
+        let mut context = ProcessingContext::new(10, expired_context());
+
+        let err = guard
+            .check_stage_boundary(&mut context, "compression")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, PipelineError::SecurityContextExpired(_)));
+        assert_eq!(observer.expired_events.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_propagates_to_all_workers_once() {
+        let refresher = Arc::new(CountingRefresher {
+            calls: AtomicUsize::new(0),
+        });
+        let initial = expired_context();
+        let session_id = initial.session_id();
+        let guard = SecurityContextGuard::new(&PipelineId::new(), initial.clone())
+            .with_refresher(Some(refresher.clone() as Arc<dyn SecurityContextRefresher>));
+
+        let mut first = ProcessingContext::new(10, initial.clone());
+        let mut second = ProcessingContext::new(10, initial);
+        guard.check_stage_boundary(&mut first, "compression").await.unwrap();
+        guard.check_stage_boundary(&mut second, "encryption").await.unwrap();
+
+        assert_eq!(refresher.calls.load(Ordering::SeqCst), 1);
+        assert!(!first.security_context().is_expired());
+        assert!(!second.security_context().is_expired());
+        // Refreshing keeps the session for audit continuity
+        assert_eq!(second.security_context().session_id(), session_id);
+    }
+}
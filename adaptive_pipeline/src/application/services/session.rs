@@ -0,0 +1,364 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Session Service
+//!
+//! Issues, authenticates and revokes API sessions (see `Session`) for the
+//! serve-mode interfaces, and for CLI invocations that present a session
+//! token in `ADAPIPE_SESSION_TOKEN`.
+//!
+//! ## Authentication
+//!
+//! [`SessionService::authenticate`] turns a bearer token into the session it
+//! belongs to, refusing it when:
+//!
+//! 1. the token is malformed or its secret is wrong (`SecurityViolation`),
+//! 2. the session was revoked (`SecurityViolation`),
+//! 3. the session has expired (`SecurityContextExpired`),
+//! 4. the session already made its `requests_per_minute` requests in the
+//!    current minute (`QuotaExceeded`).
+//!
+//! Request windows are counted per service instance, so the rate limit
+//! applies to requests served by one process.
+//!
+//! ## Audit
+//!
+//! Issuing and revoking a session are audited as `session.issue` and
+//! `session.revoke`, naming the session. Use cases run through a session
+//! name it in their audit records too, so the audit trail shows which token
+//! made each change.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tracing::{debug, info};
+
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::entities::session::{DEFAULT_SESSION_REQUESTS_PER_MINUTE, DEFAULT_SESSION_TTL};
+use adaptive_pipeline_domain::entities::Session;
+use adaptive_pipeline_domain::repositories::SessionRepository;
+use adaptive_pipeline_domain::value_objects::{AuditRecord, Namespace, SessionId, UserId};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Length of a rate limit window
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long ended sessions are kept before being purged
+const ENDED_SESSION_RETENTION: chrono::Duration = chrono::Duration::days(7);
+
+/// Requests counted against a session in the current window
+struct RateWindow {
+    started: Instant,
+    requests: u32,
+}
+
+/// Manages API sessions: issue, authentication, rate limits and revocation
+pub struct SessionService {
+    repository: Arc<dyn SessionRepository>,
+    audit_repository: Option<Arc<SqlitePipelineRepository>>,
+    ttl: chrono::Duration,
+    requests_per_minute: u32,
+    windows: Mutex<HashMap<SessionId, RateWindow>>,
+}
+
+impl SessionService {
+    /// Creates a service issuing sessions of the default lifetime and rate
+    /// limit
+    pub fn new(repository: Arc<dyn SessionRepository>) -> Self {
+        Self {
+            repository,
+            audit_repository: None,
+            ttl: DEFAULT_SESSION_TTL,
+            requests_per_minute: DEFAULT_SESSION_REQUESTS_PER_MINUTE,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the lifetime of issued sessions
+    pub fn with_ttl(mut self, ttl: chrono::Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the requests a session may make per minute; `0` disables the
+    /// limit
+    pub fn with_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.requests_per_minute = requests_per_minute;
+        self
+    }
+
+    /// Writes `session.issue` and `session.revoke` audit records to the
+    /// pipeline database of `pipeline_repository`
+    ///
+    /// Without it, session changes are only logged.
+    pub fn with_audit(mut self, pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        self.audit_repository = Some(pipeline_repository);
+        self
+    }
+
+    /// Gets the lifetime of issued sessions
+    pub fn ttl(&self) -> chrono::Duration {
+        self.ttl
+    }
+
+    /// Issues a session for `user_id` in `namespace`, on behalf of
+    /// `issued_by`, lasting `ttl` or the service's lifetime
+    ///
+    /// Returns the session and its bearer token, which is not stored and
+    /// cannot be recovered later. Sessions that ended more than a week ago
+    /// are purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` for a lifetime outside the allowed
+    /// range, or a repository error.
+    pub async fn issue(
+        &self,
+        user_id: UserId,
+        namespace: Namespace,
+        ttl: Option<chrono::Duration>,
+        issued_by: &str,
+    ) -> Result<(Session, String), PipelineError> {
+        let (session, token) = Session::issue(user_id, namespace, ttl.unwrap_or(self.ttl))?;
+
+        let purged = self
+            .repository
+            .purge_ended(Utc::now() - ENDED_SESSION_RETENTION)
+            .await?;
+        if purged > 0 {
+            debug!("Purged {} ended session(s)", purged);
+        }
+        self.repository.create(&session).await?;
+
+        info!(
+            session = %session.id(),
+            user = %session.user_id(),
+            namespace = %session.namespace(),
+            expires_at = %session.expires_at().to_rfc3339(),
+            "Session issued"
+        );
+        let record = AuditRecord::new(
+            issued_by,
+            "session.issue",
+            format!(
+                "for '{}' until {}",
+                session.user_id(),
+                session.expires_at().to_rfc3339()
+            ),
+        )
+        .with_session(session.id().clone());
+        self.record_audit(session.namespace(), &record).await?;
+        Ok((session, token))
+    }
+
+    /// Resolves `token` to its session, counting the request against the
+    /// session's rate limit
+    ///
+    /// # Errors
+    ///
+    /// See the module documentation for the refusals; also returns
+    /// repository errors.
+    pub async fn authenticate(&self, token: &str) -> Result<Session, PipelineError> {
+        let id = Session::token_session_id(token)?;
+        let mut session = self
+            .repository
+            .find_by_id(&id)
+            .await?
+            .ok_or_else(|| PipelineError::security_violation("Permission denied: invalid session token"))?;
+
+        let now = Utc::now();
+        session.verify_at(token, now)?;
+        self.count_request(session.id())?;
+
+        self.repository.touch(session.id(), now).await?;
+        session.touch(now);
+        debug!(session = %session.id(), user = %session.user_id(), "Session authenticated");
+        Ok(session)
+    }
+
+    /// Lists the active sessions in `namespace`
+    pub async fn list(&self, namespace: &Namespace) -> Result<Vec<Session>, PipelineError> {
+        self.repository.list_active(namespace, Utc::now()).await
+    }
+
+    /// Revokes the session `id` on behalf of `revoked_by`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` when no active session has the ID, or a
+    /// repository error.
+    pub async fn revoke(&self, id: &SessionId, revoked_by: &str) -> Result<Session, PipelineError> {
+        let session = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .filter(|session| session.is_active_at(Utc::now()));
+        let Some(mut session) = session else {
+            return Err(PipelineError::validation_error(format!(
+                "Active session '{}' not found",
+                id
+            )));
+        };
+
+        let now = Utc::now();
+        if !self.repository.revoke(id, now).await? {
+            return Err(PipelineError::validation_error(format!(
+                "Active session '{}' not found",
+                id
+            )));
+        }
+        session.revoke(now);
+        self.windows.lock().unwrap_or_else(PoisonError::into_inner).remove(id);
+
+        info!(session = %id, user = %session.user_id(), "Session revoked");
+        let record = AuditRecord::new(revoked_by, "session.revoke", format!("for '{}'", session.user_id()))
+            .with_session(id.clone());
+        self.record_audit(session.namespace(), &record).await?;
+        Ok(session)
+    }
+
+    fn count_request(&self, id: &SessionId) -> Result<(), PipelineError> {
+        if self.requests_per_minute == 0 {
+            return Ok(());
+        }
+
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        windows.retain(|_, window| window.started.elapsed() < RATE_WINDOW);
+        let window = windows.entry(id.clone()).or_insert_with(|| RateWindow {
+            started: Instant::now(),
+            requests: 0,
+        });
+        if window.requests >= self.requests_per_minute {
+            return Err(PipelineError::quota_exceeded(format!(
+                "session {} made {} requests this minute, limit is {}",
+                id, window.requests, self.requests_per_minute
+            )));
+        }
+        window.requests += 1;
+        Ok(())
+    }
+
+    async fn record_audit(&self, namespace: &Namespace, record: &AuditRecord) -> Result<(), PipelineError> {
+        let Some(repository) = &self.audit_repository else {
+            return Ok(());
+        };
+        let mut work = repository.in_namespace(namespace.clone()).begin().await?;
+        work.record_audit(record).await?;
+        work.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::sqlite_session::SqliteSessionRepository;
+    use tempfile::TempDir;
+
+    async fn service(dir: &TempDir) -> (SessionService, Arc<SqlitePipelineRepository>) {
+        let path = dir.path().join("sessions.db").to_string_lossy().to_string();
+        let pipelines = Arc::new(SqlitePipelineRepository::new(&path).await.unwrap());
+        let sessions = Arc::new(SqliteSessionRepository::new(&path).await.unwrap());
+        (SessionService::new(sessions).with_audit(pipelines.clone()), pipelines)
+    }
+
+    fn alice() -> UserId {
+        UserId::parse("alice").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_issued_token_authenticates_until_revoked() {
+        let dir = TempDir::new().unwrap();
+        let (service, pipelines) = service(&dir).await;
+
+        let (session, token) = service
+            .issue(alice(), Namespace::default(), None, "root")
+            .await
+            .unwrap();
+        let authenticated = service.authenticate(&token).await.unwrap();
+        assert_eq!(authenticated.id(), session.id());
+        assert_eq!(service.list(&Namespace::default()).await.unwrap().len(), 1);
+
+        service.revoke(session.id(), "root").await.unwrap();
+        let refused = service.authenticate(&token).await.unwrap_err();
+        assert!(refused.to_string().contains("revoked"), "{}", refused);
+        assert!(service.revoke(session.id(), "root").await.is_err());
+        assert!(service.list(&Namespace::default()).await.unwrap().is_empty());
+
+        let audits: Vec<(String, String, Option<String>)> =
+            sqlx::query_as("SELECT principal, action, session_id FROM audit_records ORDER BY id")
+                .fetch_all(pipelines.pool())
+                .await
+                .unwrap();
+        let session_id = Some(session.id().to_string());
+        assert_eq!(
+            audits,
+            vec![
+                ("root".to_string(), "session.issue".to_string(), session_id.clone()),
+                ("root".to_string(), "session.revoke".to_string(), session_id),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_forged_tokens_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let (service, _) = service(&dir).await;
+
+        let (_, token) = service
+            .issue(
+                alice(),
+                Namespace::default(),
+                Some(chrono::Duration::seconds(1)),
+                "root",
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let expired = service.authenticate(&token).await.unwrap_err();
+        assert!(
+            matches!(expired, PipelineError::SecurityContextExpired(_)),
+            "{}",
+            expired
+        );
+
+        let (session, _) = service
+            .issue(alice(), Namespace::default(), None, "root")
+            .await
+            .unwrap();
+        let forged = format!("{}.{}", session.id(), "ab".repeat(32));
+        assert!(matches!(
+            service.authenticate(&forged).await.unwrap_err(),
+            PipelineError::SecurityViolation(_)
+        ));
+        assert!(service.authenticate("garbage").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requests_beyond_the_rate_limit_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let (service, _) = service(&dir).await;
+        let service = service.with_rate_limit(2);
+
+        let (_, token) = service
+            .issue(alice(), Namespace::default(), None, "root")
+            .await
+            .unwrap();
+        let (_, other) = service
+            .issue(alice(), Namespace::default(), None, "root")
+            .await
+            .unwrap();
+        service.authenticate(&token).await.unwrap();
+        service.authenticate(&token).await.unwrap();
+        let limited = service.authenticate(&token).await.unwrap_err();
+        assert!(matches!(limited, PipelineError::QuotaExceeded(_)), "{}", limited);
+
+        // Limits are per session
+        service.authenticate(&other).await.unwrap();
+    }
+}
@@ -88,25 +88,72 @@
 //! ```
 
 // Use cases module - each CLI command has a corresponding use case
+pub mod audit_pipelines;
 pub mod benchmark_system;
+pub mod capabilities;
+pub mod cleanup_temp;
 pub mod compare_files;
 pub mod create_pipeline;
 pub mod delete_pipeline;
+pub mod encryption_vectors;
+pub mod estimate_cost;
+pub mod export_pipeline;
+pub mod export_tar;
+pub mod import_pipeline;
+pub mod import_tar;
+pub mod inspect_file;
+pub mod list_archive;
 pub mod list_pipelines;
+pub mod manage_database;
+pub mod manage_jobs;
+pub mod manage_roles;
+pub mod manage_sessions;
+pub mod mount_archive;
+pub mod process_batch;
+pub mod process_directory;
 pub mod process_file;
+pub mod replicate_archives;
 pub mod restore_file;
+pub mod scrub_archives;
+pub mod self_test;
 pub mod show_pipeline;
 pub mod validate_config;
 pub mod validate_file;
+pub mod verify_manifest;
 
 // Re-export use cases for convenient access
-pub use benchmark_system::BenchmarkSystemUseCase;
-pub use compare_files::CompareFilesUseCase;
+pub use audit_pipelines::{AdvisorySeverity, AuditPipelinesUseCase, PipelineAdvisory};
+pub use benchmark_system::{
+    fastest_chunk_size, find_regressions, render_sweep_chart, BenchmarkBaseline, BenchmarkRegression, BenchmarkResult,
+    BenchmarkSystemUseCase, ChunkSizeSweepUseCase, RegressionThresholds, SweepResult,
+};
+pub use capabilities::{CapabilitiesReport, CapabilitiesUseCase};
+pub use cleanup_temp::CleanupTempUseCase;
+pub use compare_files::{ArchiveComparison, CompareFilesUseCase};
 pub use create_pipeline::CreatePipelineUseCase;
 pub use delete_pipeline::DeletePipelineUseCase;
+pub use encryption_vectors::EncryptionVectorsUseCase;
+pub use estimate_cost::EstimateCostUseCase;
+pub use export_pipeline::ExportPipelineUseCase;
+pub use export_tar::ExportTarUseCase;
+pub use import_pipeline::ImportPipelineUseCase;
+pub use import_tar::ImportTarUseCase;
+pub use inspect_file::InspectFileUseCase;
+pub use list_archive::{ArchiveEntry, ListArchiveUseCase};
 pub use list_pipelines::ListPipelinesUseCase;
-pub use process_file::{ProcessFileConfig, ProcessFileUseCase};
-pub use restore_file::create_restoration_pipeline;
-pub use show_pipeline::ShowPipelineUseCase;
+pub use manage_database::ManageDatabaseUseCase;
+pub use manage_jobs::ManageJobsUseCase;
+pub use manage_roles::ManageRolesUseCase;
+pub use manage_sessions::ManageSessionsUseCase;
+pub use mount_archive::MountArchiveUseCase;
+pub use process_batch::ProcessBatchUseCase;
+pub use process_directory::{DirectoryFile, DirectoryReport, ProcessDirectoryUseCase};
+pub use process_file::{ProcessFileConfig, ProcessFileResult, ProcessFileUseCase, ProcessFileUseCaseBuilder};
+pub use replicate_archives::{ArchiveReplica, ReplicateArchivesUseCase, ReplicationReport};
+pub use restore_file::{create_restoration_pipeline, restoration_stage, ArchiveRangeReader, RestoreFileUseCase};
+pub use scrub_archives::{ArchiveScrub, ScrubArchivesUseCase, ScrubReport};
+pub use self_test::{SelfTestCheck, SelfTestReport, SelfTestUseCase};
+pub use show_pipeline::{ProcessingPlan, ShowPipelineUseCase};
 pub use validate_config::ValidateConfigUseCase;
-pub use validate_file::ValidateFileUseCase;
+pub use validate_file::{ChunkValidation, FileValidationReport, StepValidation, ValidateFileUseCase};
+pub use verify_manifest::VerifyManifestUseCase;
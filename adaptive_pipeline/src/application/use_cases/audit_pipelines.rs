@@ -0,0 +1,404 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Audit Pipelines Use Case
+//!
+//! Checks stored pipelines against what this version can run and suggests a
+//! migration for each problem, so that a pipeline created by an older version
+//! is fixed before a job fails part way through a file.
+//!
+//! ## Findings
+//!
+//! | Finding                                                | Severity |
+//! |--------------------------------------------------------|----------|
+//! | Algorithm unknown, unimplemented or refused by FIPS    | error    |
+//! | Compression level outside the algorithm's range        | error    |
+//! | Algorithm stored under a deprecated alias              | warning  |
+//! | Compression level that is not a number (ignored)       | warning  |
+//! | Checksum stage naming another algorithm (runs SHA-256) | warning  |
+//! | Transform that is not a built-in stage                 | warning  |
+//!
+//! Errors mean processing with the pipeline fails; warnings mean it runs,
+//! but not as its stages say.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::AuditPipelinesUseCase;
+//!
+//! let use_case = AuditPipelinesUseCase::new(pipeline_repository);
+//! for advisory in use_case.audit().await? {
+//!     println!("{}: {}", advisory.pipeline, advisory.suggestion);
+//! }
+//! ```
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::application::use_cases::CapabilitiesUseCase;
+use crate::infrastructure::adapters::MultiAlgoCompression;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::entities::{Pipeline, PipelineStage, StageType};
+use adaptive_pipeline_domain::services::{
+    CompressionAlgorithm, CompressionConfig, CompressionLevel, CompressionService,
+};
+use adaptive_pipeline_domain::value_objects::Algorithm;
+use adaptive_pipeline_domain::PipelineError;
+
+/// Algorithm suggested in place of a compression algorithm this version
+/// cannot run
+const COMPRESSION_REPLACEMENT: &str = "zstd";
+
+/// Algorithm suggested in place of an encryption algorithm this version
+/// cannot run
+const ENCRYPTION_REPLACEMENT: &str = "aes-256-gcm";
+
+/// The only algorithm checksum stages compute
+const CHECKSUM_ALGORITHM: &str = "sha256";
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisorySeverity {
+    /// Processing with the pipeline fails
+    Error,
+
+    /// The pipeline runs, but not as its stages say
+    Warning,
+}
+
+/// One problem with a stored pipeline and how to fix it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PipelineAdvisory {
+    /// Pipeline name
+    pub pipeline: String,
+
+    /// Stage name
+    pub stage: String,
+
+    /// How serious the problem is
+    pub severity: AdvisorySeverity,
+
+    /// What is wrong
+    pub problem: String,
+
+    /// What to change
+    pub suggestion: String,
+}
+
+/// Use case for checking stored pipelines against this version.
+///
+/// ## Dependencies
+///
+/// - **SqlitePipelineRepository**: The pipelines to audit
+pub struct AuditPipelinesUseCase {
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+    compression: MultiAlgoCompression,
+    algorithms: Vec<String>,
+    transforms: Vec<String>,
+}
+
+impl AuditPipelinesUseCase {
+    /// Creates a new Audit Pipelines use case.
+    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        let capabilities = CapabilitiesUseCase::new().report();
+        Self {
+            pipeline_repository,
+            compression: MultiAlgoCompression::new(),
+            algorithms: capabilities.algorithms.into_iter().map(|a| a.name).collect(),
+            transforms: capabilities.transforms,
+        }
+    }
+
+    /// Audits every active pipeline in the repository's namespace.
+    ///
+    /// ## Errors
+    ///
+    /// Fails only if the pipelines cannot be loaded.
+    pub async fn audit(&self) -> Result<Vec<PipelineAdvisory>, PipelineError> {
+        let pipelines = self.pipeline_repository.list_all().await?;
+        Ok(pipelines
+            .iter()
+            .flat_map(|pipeline| self.audit_pipeline(pipeline))
+            .collect())
+    }
+
+    /// Findings for one pipeline, in stage order.
+    pub fn audit_pipeline(&self, pipeline: &Pipeline) -> Vec<PipelineAdvisory> {
+        let mut advisories = Vec::new();
+        for stage in pipeline.stages() {
+            for (severity, problem, suggestion) in self.audit_stage(stage) {
+                advisories.push(PipelineAdvisory {
+                    pipeline: pipeline.name().to_string(),
+                    stage: stage.name().to_string(),
+                    severity,
+                    problem,
+                    suggestion,
+                });
+            }
+        }
+        advisories
+    }
+
+    /// Audits the stored pipelines and prints the findings, as a table or
+    /// with `json` as JSON.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 🔎 Audited 3 pipelines: 1 error(s), 1 warning(s)
+    ///    ❌ archive-logs/compress: 'lz4' is not implemented in this version
+    ///       → Replace lz4 with zstd
+    ///    ⚠️  archive-logs/encrypt: stored under the deprecated alias 'aes256gcm'
+    ///       → Recreate the stage as aes-256-gcm
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an invalid configuration error naming the affected pipelines
+    /// if any finding is an error.
+    pub async fn execute(&self, json: bool) -> Result<()> {
+        let pipelines = self.pipeline_repository.list_all().await?;
+        let advisories: Vec<PipelineAdvisory> = pipelines
+            .iter()
+            .flat_map(|pipeline| self.audit_pipeline(pipeline))
+            .collect();
+        let errors = advisories
+            .iter()
+            .filter(|advisory| advisory.severity == AdvisorySeverity::Error)
+            .count();
+        info!(
+            "Audited {} pipelines: {} errors, {} warnings",
+            pipelines.len(),
+            errors,
+            advisories.len() - errors
+        );
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&advisories)?);
+        } else {
+            println!(
+                "🔎 Audited {} pipelines: {} error(s), {} warning(s)",
+                pipelines.len(),
+                errors,
+                advisories.len() - errors
+            );
+            for advisory in &advisories {
+                let icon = match advisory.severity {
+                    AdvisorySeverity::Error => "❌",
+                    AdvisorySeverity::Warning => "⚠️ ",
+                };
+                println!(
+                    "   {} {}/{}: {}",
+                    icon, advisory.pipeline, advisory.stage, advisory.problem
+                );
+                println!("      → {}", advisory.suggestion);
+            }
+            if !advisories.is_empty() {
+                println!("Pipelines cannot be edited in place; recreate them with `delete` and `create`.");
+            }
+        }
+
+        if errors > 0 {
+            let mut affected: Vec<&str> = advisories
+                .iter()
+                .filter(|advisory| advisory.severity == AdvisorySeverity::Error)
+                .map(|advisory| advisory.pipeline.as_str())
+                .collect();
+            affected.dedup();
+            return Err(PipelineError::InvalidConfiguration(format!(
+                "{} stored pipeline(s) cannot run in this version: {}",
+                affected.len(),
+                affected.join(", ")
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    fn audit_stage(&self, stage: &PipelineStage) -> Vec<(AdvisorySeverity, String, String)> {
+        let stored = stage.configuration().algorithm.as_str();
+        match stage.stage_type() {
+            StageType::Compression => self.audit_algorithm(stage, stored, COMPRESSION_REPLACEMENT),
+            StageType::Encryption => self.audit_algorithm(stage, stored, ENCRYPTION_REPLACEMENT),
+            StageType::Checksum => match Algorithm::canonical_name(stored) {
+                Some(CHECKSUM_ALGORITHM) => Vec::new(),
+                _ => vec![(
+                    AdvisorySeverity::Warning,
+                    format!("checksum stages compute SHA-256; '{}' is ignored", stored),
+                    format!("Recreate the stage as {}", CHECKSUM_ALGORITHM),
+                )],
+            },
+            StageType::Transform | StageType::PassThrough => {
+                if self.transforms.iter().any(|transform| transform == stored) {
+                    Vec::new()
+                } else {
+                    vec![(
+                        AdvisorySeverity::Warning,
+                        format!("'{}' is not a built-in stage", stored),
+                        format!(
+                            "Run the pipeline only where a custom stage named '{}' is registered",
+                            stored
+                        ),
+                    )]
+                }
+            }
+        }
+    }
+
+    fn audit_algorithm(
+        &self,
+        stage: &PipelineStage,
+        stored: &str,
+        replacement: &str,
+    ) -> Vec<(AdvisorySeverity, String, String)> {
+        let replace = |name: &str| format!("Replace {} with {}", name, replacement);
+        let algorithm = match Algorithm::parse(stored) {
+            Ok(algorithm) => algorithm,
+            Err(_) => {
+                return vec![(
+                    AdvisorySeverity::Error,
+                    format!("'{}' is not a known algorithm", stored),
+                    replace(stored),
+                )]
+            }
+        };
+        let name = algorithm.name();
+
+        if !algorithm.is_permitted() {
+            return vec![(
+                AdvisorySeverity::Error,
+                format!("'{}' is not FIPS-approved and this build refuses it", name),
+                replace(name),
+            )];
+        }
+        if !self.algorithms.iter().any(|supported| supported == name) {
+            return vec![(
+                AdvisorySeverity::Error,
+                format!("'{}' is not implemented in this version", name),
+                replace(name),
+            )];
+        }
+
+        let mut findings = Vec::new();
+        if stored != name {
+            findings.push((
+                AdvisorySeverity::Warning,
+                format!("stored under the deprecated alias '{}'", stored),
+                format!("Recreate the stage as {}", name),
+            ));
+        }
+        if let Ok(compression) = CompressionAlgorithm::try_from(&algorithm) {
+            findings.extend(self.audit_level(stage, compression));
+        }
+        findings
+    }
+
+    fn audit_level(
+        &self,
+        stage: &PipelineStage,
+        algorithm: CompressionAlgorithm,
+    ) -> Option<(AdvisorySeverity, String, String)> {
+        let level = stage.configuration().parameters.get("level")?;
+        let Ok(numeric) = level.parse::<u32>() else {
+            return Some((
+                AdvisorySeverity::Warning,
+                format!("level '{}' is not a number and is ignored", level),
+                "Set level to a number, or remove it to use the balanced default".to_string(),
+            ));
+        };
+        let config = CompressionConfig {
+            level: CompressionLevel::Custom(numeric),
+            ..CompressionConfig::new(algorithm)
+        };
+        self.compression.validate_config(&config).err().map(|e| {
+            (
+                AdvisorySeverity::Error,
+                e.to_string(),
+                format!("Set level within range (was {})", level),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::entities::StageConfiguration;
+    use std::collections::HashMap;
+
+    fn stage(name: &str, stage_type: StageType, algorithm: &str, level: Option<&str>) -> PipelineStage {
+        let mut parameters = HashMap::from([("algorithm".to_string(), algorithm.to_string())]);
+        if let Some(level) = level {
+            parameters.insert("level".to_string(), level.to_string());
+        }
+        PipelineStage::new(
+            name.to_string(),
+            stage_type,
+            StageConfiguration::new(algorithm.to_string(), parameters, false),
+            0,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_audit_flags_stages_this_version_cannot_run_as_stored() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repository = Arc::new(
+            SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+                .await
+                .unwrap(),
+        );
+        let use_case = AuditPipelinesUseCase::new(repository);
+
+        let current = Pipeline::new(
+            "current".to_string(),
+            vec![
+                stage("compress", StageType::Compression, "brotli", Some("6")),
+                stage("encrypt", StageType::Encryption, "aes-256-gcm", None),
+                stage("encode", StageType::Transform, "base64", None),
+            ],
+        )
+        .unwrap();
+        assert_eq!(use_case.audit_pipeline(&current), Vec::new());
+
+        let legacy = Pipeline::new(
+            "legacy".to_string(),
+            vec![
+                stage("fast", StageType::Compression, "lz4", None),
+                stage("tuned", StageType::Compression, "gzip", Some("12")),
+                stage("loose", StageType::Compression, "zstd", Some("max")),
+                stage("encrypt", StageType::Encryption, "aes256gcm", None),
+                stage("digest", StageType::Checksum, "md5", None),
+                stage("plugin", StageType::Transform, "custom-redact", None),
+            ],
+        )
+        .unwrap();
+        let findings: Vec<(String, AdvisorySeverity)> = use_case
+            .audit_pipeline(&legacy)
+            .into_iter()
+            .map(|advisory| (advisory.stage, advisory.severity))
+            .collect();
+        let expected = [
+            ("fast", AdvisorySeverity::Error),
+            ("tuned", AdvisorySeverity::Error),
+            ("loose", AdvisorySeverity::Warning),
+            ("encrypt", AdvisorySeverity::Warning),
+            ("digest", AdvisorySeverity::Warning),
+            ("plugin", AdvisorySeverity::Warning),
+        ];
+        assert_eq!(
+            findings,
+            expected
+                .iter()
+                .map(|(stage, severity)| (stage.to_string(), *severity))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -27,26 +27,222 @@
 //! - **Chunk Sizes**: 1MB, 2MB, 4MB, 8MB, 16MB, 32MB, 64MB, 128MB
 //! - **Worker Counts**: 1 to (2 × CPU cores), max 16
 //! - **Iterations**: Configurable (default: 3)
+//!
+//! ## Baselines
+//!
+//! Results can be saved as a JSON baseline and later runs compared against
+//! it. Each configuration present in both runs is compared per metric: a
+//! throughput drop or a duration increase beyond its percentage threshold is
+//! a regression, and the run fails so deployment pipelines can gate on it.
+//! Configurations only one run tested (worker counts depend on the host's
+//! cores) are skipped.
+//!
+//! ## Chunk Size Sweep
+//!
+//! [`ChunkSizeSweepUseCase`] runs a real pipeline over one file at each
+//! chunk size of a range instead of simulating processing, prints the
+//! throughput per size as a bar chart, and can pin the pipeline
+//! to the fastest size. Each run is also recorded in the pipeline's chunk
+//! size history, like any other processing run.
 
 use anyhow::Result;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, warn};
 
+use crate::application::use_cases::process_file::{ProcessFileConfig, ProcessFileUseCase};
 use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
 use adaptive_pipeline_domain::value_objects::chunk_size::ChunkSize;
 use adaptive_pipeline_domain::value_objects::worker_count::WorkerCount;
+use adaptive_pipeline_domain::value_objects::{JobPriority, OverwritePolicy};
 
 /// Benchmark result for a single configuration.
-#[derive(Debug, Clone)]
-struct BenchmarkResult {
-    file_size_mb: usize,
-    chunk_size_mb: usize,
-    worker_count: usize,
-    avg_throughput_mbps: f64,
-    avg_duration_secs: f64,
-    config_type: String,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub file_size_mb: usize,
+    pub chunk_size_mb: usize,
+    pub worker_count: usize,
+    pub avg_throughput_mbps: f64,
+    pub avg_duration_secs: f64,
+    pub config_type: String,
+}
+
+impl BenchmarkResult {
+    /// Whether `other` measured the same configuration
+    fn same_configuration(&self, other: &BenchmarkResult) -> bool {
+        self.file_size_mb == other.file_size_mb
+            && self.chunk_size_mb == other.chunk_size_mb
+            && self.worker_count == other.worker_count
+            && self.config_type == other.config_type
+    }
+}
+
+/// Saved benchmark results that later runs are compared against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkBaseline {
+    /// When the baseline was recorded
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Iterations each configuration was averaged over
+    pub iterations: usize,
+    /// One result per tested configuration
+    pub results: Vec<BenchmarkResult>,
+}
+
+impl BenchmarkBaseline {
+    /// Reads a baseline from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read baseline {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| anyhow::anyhow!("Invalid baseline {}: {}", path.display(), e))
+    }
+
+    /// Writes the baseline to a JSON file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow::anyhow!("Failed to write baseline {}: {}", path.display(), e))
+    }
+}
+
+/// Percentage by which each metric may worsen before it counts as a
+/// regression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegressionThresholds {
+    /// Allowed throughput drop, in percent
+    pub throughput_pct: f64,
+    /// Allowed duration increase, in percent
+    pub duration_pct: f64,
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            throughput_pct: 10.0,
+            duration_pct: 10.0,
+        }
+    }
+}
+
+/// A metric of one configuration that worsened beyond its threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRegression {
+    pub file_size_mb: usize,
+    pub chunk_size_mb: usize,
+    pub worker_count: usize,
+    pub config_type: String,
+    /// `throughput` or `duration`
+    pub metric: &'static str,
+    pub baseline: f64,
+    pub current: f64,
+    /// Signed change from the baseline, in percent
+    pub change_pct: f64,
+}
+
+/// Compares `current` results with a baseline's, configuration by
+/// configuration, and returns every metric that worsened beyond its
+/// threshold.
+pub fn find_regressions(
+    baseline: &[BenchmarkResult],
+    current: &[BenchmarkResult],
+    thresholds: RegressionThresholds,
+) -> Vec<BenchmarkRegression> {
+    let change_pct = |before: f64, after: f64| {
+        if before > 0.0 {
+            (after - before) / before * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let mut regressions = Vec::new();
+    for result in current {
+        let Some(base) = baseline.iter().find(|base| base.same_configuration(result)) else {
+            continue;
+        };
+        let regression = |metric, baseline, current, change_pct| BenchmarkRegression {
+            file_size_mb: result.file_size_mb,
+            chunk_size_mb: result.chunk_size_mb,
+            worker_count: result.worker_count,
+            config_type: result.config_type.clone(),
+            metric,
+            baseline,
+            current,
+            change_pct,
+        };
+
+        let throughput_change = change_pct(base.avg_throughput_mbps, result.avg_throughput_mbps);
+        if -throughput_change > thresholds.throughput_pct {
+            regressions.push(regression(
+                "throughput",
+                base.avg_throughput_mbps,
+                result.avg_throughput_mbps,
+                throughput_change,
+            ));
+        }
+        let duration_change = change_pct(base.avg_duration_secs, result.avg_duration_secs);
+        if duration_change > thresholds.duration_pct {
+            regressions.push(regression(
+                "duration",
+                base.avg_duration_secs,
+                result.avg_duration_secs,
+                duration_change,
+            ));
+        }
+    }
+    regressions
+}
+
+/// Average throughput of one chunk size in a sweep.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepResult {
+    pub chunk_size: ChunkSize,
+    pub avg_throughput_mbps: f64,
+    pub avg_duration_secs: f64,
+}
+
+/// Returns the sweep result with the highest throughput
+pub fn fastest_chunk_size(results: &[SweepResult]) -> Option<&SweepResult> {
+    results.iter().max_by(|a, b| {
+        a.avg_throughput_mbps
+            .partial_cmp(&b.avg_throughput_mbps)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
+/// Renders sweep results as a bar chart, one row per chunk size, with the
+/// fastest size starred.
+///
+/// ```text
+///    1.0 MiB │████████████                            │  210.4 MB/s
+///    4.0 MiB │████████████████████████████████████████│  702.9 MB/s ★
+/// ```
+pub fn render_sweep_chart(results: &[SweepResult]) -> String {
+    const WIDTH: usize = 40;
+    let fastest = fastest_chunk_size(results).map(|result| result.chunk_size);
+    let max = fastest_chunk_size(results)
+        .map(|result| result.avg_throughput_mbps)
+        .unwrap_or(0.0);
+
+    let mut chart = String::new();
+    for result in results {
+        let filled = if max > 0.0 {
+            ((result.avg_throughput_mbps / max) * WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        chart.push_str(&format!(
+            "{:>9.1} MiB │{}{}│ {:>7.1} MB/s{}\n",
+            result.chunk_size.megabytes(),
+            "█".repeat(filled),
+            " ".repeat(WIDTH - filled),
+            result.avg_throughput_mbps,
+            if Some(result.chunk_size) == fastest { " ★" } else { "" }
+        ));
+    }
+    chart
 }
 
 /// Single test iteration result.
@@ -60,12 +256,31 @@ struct TestResult {
 ///
 /// This use case performs comprehensive performance testing across multiple
 /// configurations to identify optimal settings for different file sizes.
-pub struct BenchmarkSystemUseCase;
+pub struct BenchmarkSystemUseCase {
+    save_baseline: Option<PathBuf>,
+    compare_baseline: Option<(PathBuf, RegressionThresholds)>,
+}
 
 impl BenchmarkSystemUseCase {
     /// Creates a new Benchmark System use case.
     pub fn new() -> Self {
-        Self
+        Self {
+            save_baseline: None,
+            compare_baseline: None,
+        }
+    }
+
+    /// Saves the results as a JSON baseline at `path`
+    pub fn with_saved_baseline(mut self, path: PathBuf) -> Self {
+        self.save_baseline = Some(path);
+        self
+    }
+
+    /// Compares the results with the JSON baseline at `path`, failing the run
+    /// if any metric worsened beyond `thresholds`
+    pub fn with_baseline_comparison(mut self, path: PathBuf, thresholds: RegressionThresholds) -> Self {
+        self.compare_baseline = Some((path, thresholds));
+        self
     }
 
     /// Executes the benchmark system use case.
@@ -97,11 +312,22 @@ impl BenchmarkSystemUseCase {
     /// - Detailed results for all tested configurations
     /// - Summary recommendations for each file size
     ///
+    /// With a saved baseline configured, also writes the results as JSON;
+    /// with a comparison baseline, prints the regressions found.
+    ///
     /// ## Returns
     ///
     /// - `Ok(())` - Benchmark completed successfully
-    /// - `Err(anyhow::Error)` - Benchmark failed
+    /// - `Err(anyhow::Error)` - Benchmark failed, or regressed against the
+    ///   comparison baseline
     pub async fn execute(&self, file: Option<PathBuf>, size_mb: usize, iterations: usize) -> Result<()> {
+        // Fail on a missing or unreadable baseline before spending time
+        // benchmarking
+        let comparison = match &self.compare_baseline {
+            Some((path, thresholds)) => Some((path, BenchmarkBaseline::load(path)?, *thresholds)),
+            None => None,
+        };
+
         info!("Running comprehensive pipeline optimization benchmark");
         info!("Test size: {}MB", size_mb);
         info!("Iterations: {}", iterations);
@@ -254,12 +480,60 @@ impl BenchmarkSystemUseCase {
         // Generate comprehensive report
         Self::generate_optimization_report(&results).await?;
 
+        if let Some(path) = &self.save_baseline {
+            BenchmarkBaseline {
+                created_at: chrono::Utc::now(),
+                iterations,
+                results: results.clone(),
+            }
+            .save(path)?;
+            println!("💾 Baseline saved: {}", path.display());
+        }
+
+        if let Some((path, baseline, thresholds)) = comparison {
+            let regressions = find_regressions(&baseline.results, &results, thresholds);
+            Self::print_regressions(path, &regressions, thresholds);
+            if !regressions.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "{} benchmark regression(s) against baseline {}",
+                    regressions.len(),
+                    path.display()
+                ));
+            }
+        }
+
         println!("\n✅ Benchmark completed successfully!");
         println!("📊 Check the generated optimization report for detailed results.");
 
         Ok(())
     }
 
+    /// Prints the outcome of a baseline comparison.
+    fn print_regressions(baseline: &Path, regressions: &[BenchmarkRegression], thresholds: RegressionThresholds) {
+        println!("\n📉 Baseline comparison: {}", baseline.display());
+        println!(
+            "   Thresholds: throughput -{:.1}%, duration +{:.1}%",
+            thresholds.throughput_pct, thresholds.duration_pct
+        );
+        if regressions.is_empty() {
+            println!("   ✅ No regressions");
+            return;
+        }
+        for regression in regressions {
+            println!(
+                "   ❌ {} MB file, {} MB chunks, {} workers ({}): {} {:.3} → {:.3} ({:+.1}%)",
+                regression.file_size_mb,
+                regression.chunk_size_mb,
+                regression.worker_count,
+                regression.config_type,
+                regression.metric,
+                regression.baseline,
+                regression.current,
+                regression.change_pct
+            );
+        }
+    }
+
     /// Simulates pipeline processing for benchmarking.
     async fn simulate_pipeline_processing(
         input_file: &PathBuf,
@@ -527,10 +801,270 @@ impl Default for BenchmarkSystemUseCase {
     }
 }
 
+/// Use case for sweeping a pipeline's chunk size.
+///
+/// Processes one file through the pipeline at every chunk size of the
+/// sweep, averaging each over the iterations, and reports the fastest.
+pub struct ChunkSizeSweepUseCase {
+    process_file: ProcessFileUseCase,
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+    apply: bool,
+}
+
+impl ChunkSizeSweepUseCase {
+    /// Creates a sweep that processes with `process_file` and looks the
+    /// pipeline up in `pipeline_repository`
+    pub fn new(process_file: ProcessFileUseCase, pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        Self {
+            process_file,
+            pipeline_repository,
+            apply: false,
+        }
+    }
+
+    /// Pins the pipeline to the fastest chunk size once the sweep is done
+    pub fn with_apply(mut self, apply: bool) -> Self {
+        self.apply = apply;
+        self
+    }
+
+    /// Executes the sweep.
+    ///
+    /// ## Parameters
+    ///
+    /// * `pipeline` - Name of the pipeline to run
+    /// * `chunk_sizes` - Chunk sizes to try, in order
+    /// * `file` - File to process (otherwise a `size_mb` test file is
+    ///   generated and removed afterwards)
+    /// * `iterations` - Runs averaged per chunk size
+    ///
+    /// Chunk sizes the file is too small for are skipped, since processing
+    /// would replace them with the adaptive size.
+    ///
+    /// ## Returns
+    ///
+    /// - `Ok(Vec<SweepResult>)` - One result per chunk size that was run
+    /// - `Err(anyhow::Error)` - The pipeline does not exist, no chunk size
+    ///   suits the file, or a run failed
+    pub async fn execute(
+        &self,
+        pipeline: &str,
+        chunk_sizes: &[ChunkSize],
+        file: Option<PathBuf>,
+        size_mb: usize,
+        iterations: usize,
+    ) -> Result<Vec<SweepResult>> {
+        let test_file = match &file {
+            Some(path) => path.clone(),
+            None => {
+                let path = PathBuf::from(format!("benchmark_sweep_{}mb.txt", size_mb));
+                BenchmarkSystemUseCase::generate_test_file(&path, size_mb).await?;
+                path
+            }
+        };
+
+        let results = self.sweep(pipeline, chunk_sizes, &test_file, iterations).await;
+
+        if file.is_none() && test_file.exists() {
+            std::fs::remove_file(&test_file)?;
+        }
+        let results = results?;
+
+        println!("\n📈 Throughput by chunk size ({}):", pipeline);
+        print!("{}", render_sweep_chart(&results));
+        let fastest = fastest_chunk_size(&results).ok_or_else(|| anyhow::anyhow!("No sweep results"))?;
+        println!(
+            "🏆 Fastest: {:.1} MiB at {:.1} MB/s",
+            fastest.chunk_size.megabytes(),
+            fastest.avg_throughput_mbps
+        );
+
+        if self.apply {
+            let mut entity = self
+                .pipeline_repository
+                .find_by_name(pipeline)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Pipeline '{}' not found", pipeline))?;
+            entity.set_chunk_size(fastest.chunk_size);
+            self.pipeline_repository.save_configuration(&entity).await?;
+            println!(
+                "✅ Pipeline '{}' now processes with {:.1} MiB chunks unless --chunk-size is given",
+                pipeline,
+                fastest.chunk_size.megabytes()
+            );
+        } else {
+            println!("   Re-run with --apply to pin pipeline '{}' to it", pipeline);
+        }
+
+        Ok(results)
+    }
+
+    /// Runs `test_file` through `pipeline` at each usable chunk size
+    async fn sweep(
+        &self,
+        pipeline: &str,
+        chunk_sizes: &[ChunkSize],
+        test_file: &Path,
+        iterations: usize,
+    ) -> Result<Vec<SweepResult>> {
+        let file_size = std::fs::metadata(test_file)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", test_file.display(), e))?
+            .len();
+        let file_mb = (file_size as f64) / (1024.0 * 1024.0);
+
+        let mut results = Vec::new();
+        for &chunk_size in chunk_sizes {
+            if let Err(reason) = chunk_size.validate_for_file_size(file_size) {
+                println!("⏭️  Skipping {:.1} MiB: {}", chunk_size.megabytes(), reason);
+                continue;
+            }
+            info!("Sweeping {} at {} chunks", pipeline, chunk_size);
+
+            let mut total_secs = 0.0;
+            for i in 0..iterations {
+                let output = PathBuf::from(format!("benchmark_sweep_{}_{}.adapipe", std::process::id(), i));
+                let started = Instant::now();
+                let processed = self
+                    .process_file
+                    .execute(Self::run_config(pipeline, test_file, &output, chunk_size))
+                    .await;
+                let secs = started.elapsed().as_secs_f64();
+                if output.exists() {
+                    std::fs::remove_file(&output)?;
+                }
+                processed?;
+                total_secs += secs;
+            }
+
+            let avg_duration_secs = total_secs / (iterations as f64);
+            results.push(SweepResult {
+                chunk_size,
+                avg_throughput_mbps: if avg_duration_secs > 0.0 {
+                    file_mb / avg_duration_secs
+                } else {
+                    0.0
+                },
+                avg_duration_secs,
+            });
+        }
+
+        if results.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No chunk size in the sweep suits a {} byte file; use a larger --file or --size-mb",
+                file_size
+            ));
+        }
+        Ok(results)
+    }
+
+    /// Processing options for one sweep run: only the chunk size is fixed
+    fn run_config(pipeline: &str, input: &Path, output: &Path, chunk_size: ChunkSize) -> ProcessFileConfig {
+        ProcessFileConfig {
+            input: input.to_path_buf(),
+            output: output.to_path_buf(),
+            pipeline: pipeline.to_string(),
+            chunk_size: Some(chunk_size),
+            workers: None,
+            channel_depth: None,
+            inflight_window: None,
+            write_manifest: false,
+            signing_key: None,
+            idempotency_key: None,
+            stage_timeout: None,
+            chunk_timeout: None,
+            max_worker_restarts: 0,
+            direct_io: false,
+            checksum_offload: false,
+            overwrite_policy: OverwritePolicy::Overwrite,
+            output_mode: None,
+            priority: JobPriority::default(),
+            password: None,
+            passphrase_policy: PassphrasePolicy::default(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn result(chunk_size_mb: usize, throughput: f64, duration: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            file_size_mb: 10,
+            chunk_size_mb,
+            worker_count: 2,
+            avg_throughput_mbps: throughput,
+            avg_duration_secs: duration,
+            config_type: "Chunk Variation".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_regressions_applies_thresholds_per_metric() {
+        let baseline = vec![result(1, 100.0, 1.0), result(2, 100.0, 1.0), result(4, 100.0, 1.0)];
+        let current = vec![
+            result(1, 95.0, 1.05), // within both thresholds
+            result(2, 80.0, 1.0),  // throughput dropped 20%
+            result(8, 10.0, 10.0), // not in the baseline
+        ];
+
+        let regressions = find_regressions(&baseline, &current, RegressionThresholds::default());
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].chunk_size_mb, 2);
+        assert_eq!(regressions[0].metric, "throughput");
+        assert!((regressions[0].change_pct + 20.0).abs() < 1e-9);
+
+        let strict = RegressionThresholds {
+            throughput_pct: 1.0,
+            duration_pct: 1.0,
+        };
+        let metrics: Vec<_> = find_regressions(&baseline, &current, strict)
+            .iter()
+            .map(|r| (r.chunk_size_mb, r.metric))
+            .collect();
+        assert_eq!(metrics, vec![(1, "throughput"), (1, "duration"), (2, "throughput")]);
+    }
+
+    #[test]
+    fn test_baseline_round_trips_through_json() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("baseline.json");
+        let baseline = BenchmarkBaseline {
+            created_at: chrono::Utc::now(),
+            iterations: 3,
+            results: vec![result(4, 250.0, 0.04)],
+        };
+        baseline.save(&path).unwrap();
+
+        let loaded = BenchmarkBaseline::load(&path).unwrap();
+        assert_eq!(loaded.iterations, 3);
+        assert_eq!(loaded.results, baseline.results);
+        assert!(BenchmarkBaseline::load(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_sweep_chart_stars_the_fastest_size() {
+        let sweep = |mb, avg_throughput_mbps| SweepResult {
+            chunk_size: ChunkSize::from_mb(mb).unwrap(),
+            avg_throughput_mbps,
+            avg_duration_secs: 1.0,
+        };
+        let results = vec![sweep(1, 100.0), sweep(2, 400.0), sweep(4, 200.0)];
+        assert_eq!(
+            fastest_chunk_size(&results).unwrap().chunk_size.bytes(),
+            2 * 1024 * 1024
+        );
+
+        let chart = render_sweep_chart(&results);
+        let rows: Vec<&str> = chart.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].ends_with("★"));
+        assert_eq!(rows[1].matches('█').count(), 40);
+        assert_eq!(rows[0].matches('█').count(), 10);
+        assert!(!rows[2].contains('★'));
+        assert!(fastest_chunk_size(&[]).is_none());
+    }
+
     #[tokio::test]
     #[ignore] // Expensive benchmark test
     async fn test_benchmark_small_file() {
@@ -0,0 +1,308 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Capabilities Use Case
+//!
+//! Describes what this build can do, so that an orchestration layer can check
+//! a host before dispatching work to it: the algorithms it registers, the
+//! file format versions it reads and writes, the optional features compiled
+//! in, the runtime feature flags this deployment turns on, and its
+//! processing limits.
+//!
+//! Apart from the feature flags the report is built from the build alone; it
+//! needs no database.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::CapabilitiesUseCase;
+//!
+//! let report = CapabilitiesUseCase::new().report();
+//! assert!(report.algorithms.iter().any(|a| a.name == "brotli"));
+//! ```
+
+use serde::Serialize;
+
+use crate::infrastructure::adapters::{MultiAlgoCompression, MultiAlgoEncryption};
+use crate::infrastructure::config::build_info::build_provenance;
+use adaptive_pipeline_domain::services::{CompressionService, EncryptionService};
+use adaptive_pipeline_domain::value_objects::batch_retry_manifest::RETRY_MANIFEST_VERSION;
+use adaptive_pipeline_domain::value_objects::binary_file_format::{CURRENT_FORMAT_VERSION, MAX_HEADER_LENGTH};
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::TEST_VECTOR_SUITE_VERSION;
+use adaptive_pipeline_domain::value_objects::processing_manifest::MANIFEST_VERSION;
+use adaptive_pipeline_domain::value_objects::shutdown_checkpoint::CHECKPOINT_VERSION;
+use adaptive_pipeline_domain::value_objects::{
+    Algorithm, BuildProvenance, ChunkSize, FeatureFlags, WorkerCount, FIPS_MODE,
+};
+
+/// Transform stages registered alongside the algorithms (see
+/// `StageRegistry::builtin`)
+const TRANSFORM_STAGES: [&str; 5] = ["base64", "pii_masking", "tee", "passthrough", "debug"];
+
+/// An algorithm this build can run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlgorithmCapability {
+    /// Canonical name, as written in pipeline stages and file headers
+    pub name: String,
+
+    /// compression, encryption or checksum
+    pub category: String,
+
+    /// Whether the algorithm is a FIPS-approved primitive
+    pub fips_approved: bool,
+}
+
+/// Versions of the files this build reads and writes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FormatVersions {
+    /// `.adapipe` archive format
+    pub archive: u16,
+
+    /// Processing manifest layout
+    pub manifest: u16,
+
+    /// Shutdown checkpoint layout
+    pub checkpoint: u16,
+
+    /// Batch retry manifest layout
+    pub retry_manifest: u16,
+
+    /// Chunk encryption test vector suite
+    pub test_vectors: u16,
+}
+
+/// Optional features and whether this build has them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompiledFeatures {
+    /// Built with the `fips` feature: only FIPS-approved cryptography
+    pub fips: bool,
+
+    /// GPU-accelerated stages
+    pub gpu: bool,
+
+    /// Reading archives from `s3://` locations
+    pub s3: bool,
+
+    /// Reading archives from `http://` URLs with range requests
+    pub http_ranges: bool,
+
+    /// Mounting archives with `mount`
+    pub fuse: bool,
+}
+
+/// Processing limits enforced by this build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProcessingLimits {
+    /// Smallest accepted chunk size in bytes
+    pub min_chunk_size: usize,
+
+    /// Largest accepted chunk size in bytes
+    pub max_chunk_size: usize,
+
+    /// Chunk size used when none is given, in bytes
+    pub default_chunk_size: usize,
+
+    /// Most workers a file can be processed with
+    pub max_workers: usize,
+
+    /// Largest archive header read, in bytes
+    pub max_header_length: usize,
+}
+
+/// What this build can do
+#[derive(Debug, Clone, Serialize)]
+pub struct CapabilitiesReport {
+    /// Version and build provenance
+    pub build: BuildProvenance,
+
+    /// Algorithms this build permits, by category
+    pub algorithms: Vec<AlgorithmCapability>,
+
+    /// Non-algorithm stages, such as `base64`
+    pub transforms: Vec<String>,
+
+    /// File format versions
+    pub formats: FormatVersions,
+
+    /// Optional features compiled in
+    pub features: CompiledFeatures,
+
+    /// Runtime feature flags and whether this deployment turns them on
+    pub flags: FeatureFlags,
+
+    /// Processing limits
+    pub limits: ProcessingLimits,
+}
+
+/// Use case for describing this build's capabilities.
+#[derive(Default)]
+pub struct CapabilitiesUseCase {
+    compression: MultiAlgoCompression,
+    encryption: MultiAlgoEncryption,
+    flags: FeatureFlags,
+}
+
+impl CapabilitiesUseCase {
+    /// Creates a new Capabilities use case.
+    pub fn new() -> Self {
+        Self {
+            compression: MultiAlgoCompression::new(),
+            encryption: MultiAlgoEncryption::new(),
+            flags: FeatureFlags::default(),
+        }
+    }
+
+    /// Reports `flags` instead of the catalog defaults
+    pub fn with_feature_flags(mut self, flags: FeatureFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Builds the report.
+    ///
+    /// Algorithms a FIPS build does not permit are left out, as they are
+    /// left out of the stage registry.
+    pub fn report(&self) -> CapabilitiesReport {
+        let compression = self
+            .compression
+            .supported_algorithms()
+            .into_iter()
+            .map(|algorithm| ("compression", algorithm.to_string()));
+        let encryption = self
+            .encryption
+            .supported_algorithms()
+            .into_iter()
+            .map(|algorithm| ("encryption", algorithm.to_string()));
+        let checksum = std::iter::once(("checksum", Algorithm::sha256().to_string()));
+
+        let algorithms = compression
+            .chain(encryption)
+            .chain(checksum)
+            .filter_map(|(category, name)| {
+                let algorithm = Algorithm::parse(&name.to_lowercase()).ok()?;
+                algorithm.is_permitted().then(|| AlgorithmCapability {
+                    name: algorithm.name().to_string(),
+                    category: category.to_string(),
+                    fips_approved: algorithm.is_fips_approved(),
+                })
+            })
+            .collect();
+
+        CapabilitiesReport {
+            build: build_provenance(),
+            algorithms,
+            transforms: TRANSFORM_STAGES.iter().map(|name| name.to_string()).collect(),
+            formats: FormatVersions {
+                archive: CURRENT_FORMAT_VERSION,
+                manifest: MANIFEST_VERSION,
+                checkpoint: CHECKPOINT_VERSION,
+                retry_manifest: RETRY_MANIFEST_VERSION,
+                test_vectors: TEST_VECTOR_SUITE_VERSION,
+            },
+            features: CompiledFeatures {
+                fips: FIPS_MODE,
+                gpu: false,
+                s3: true,
+                http_ranges: true,
+                fuse: cfg!(all(unix, feature = "fuse")),
+            },
+            flags: self.flags.clone(),
+            limits: ProcessingLimits {
+                min_chunk_size: ChunkSize::MIN_SIZE,
+                max_chunk_size: ChunkSize::MAX_SIZE,
+                default_chunk_size: ChunkSize::DEFAULT_SIZE,
+                max_workers: WorkerCount::MAX_WORKERS,
+                max_header_length: MAX_HEADER_LENGTH,
+            },
+        }
+    }
+
+    /// Renders the report as a summary or, with `json`, as JSON.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// adaptive_pipeline 2.0.0 (1a2b3c4d, x86_64-unknown-linux-gnu)
+    /// Algorithms:
+    ///    compression  brotli, gzip, zstd, xz
+    ///    encryption   aes-256-gcm (FIPS), chacha20-poly1305, aes-128-gcm (FIPS)
+    ///    checksum     sha256 (FIPS)
+    /// Transforms:     base64, pii_masking, tee, passthrough, debug
+    /// Formats:        archive v1, manifest v1, checkpoint v1, retry manifest v1, test vectors v1
+    /// Features:       fips=false gpu=false s3=true http_ranges=true fuse=false
+    /// Flags:          distributed=off gpu=off dedup=off
+    /// Limits:         chunk size 1-536870912 bytes (default 1048576), 32 workers
+    /// ```
+    pub fn render(&self, json: bool) -> Result<String, serde_json::Error> {
+        let report = self.report();
+        if json {
+            return serde_json::to_string_pretty(&report).map(|json| json + "\n");
+        }
+
+        let mut text = format!(
+            "adaptive_pipeline {} ({}, {})\nAlgorithms:\n",
+            report.build.crate_version,
+            report.build.short_commit(),
+            report.build.target
+        );
+        for category in ["compression", "encryption", "checksum"] {
+            let names: Vec<String> = report
+                .algorithms
+                .iter()
+                .filter(|algorithm| algorithm.category == category)
+                .map(|algorithm| {
+                    if algorithm.fips_approved {
+                        format!("{} (FIPS)", algorithm.name)
+                    } else {
+                        algorithm.name.clone()
+                    }
+                })
+                .collect();
+            text.push_str(&format!("   {:<12} {}\n", category, names.join(", ")));
+        }
+        let formats = &report.formats;
+        let features = &report.features;
+        let limits = &report.limits;
+        text.push_str(&format!("Transforms:     {}\n", report.transforms.join(", ")));
+        text.push_str(&format!(
+            "Formats:        archive v{}, manifest v{}, checkpoint v{}, retry manifest v{}, test vectors v{}\n",
+            formats.archive, formats.manifest, formats.checkpoint, formats.retry_manifest, formats.test_vectors
+        ));
+        text.push_str(&format!(
+            "Features:       fips={} gpu={} s3={} http_ranges={} fuse={}\n",
+            features.fips, features.gpu, features.s3, features.http_ranges, features.fuse
+        ));
+        text.push_str(&format!("Flags:          {}\n", report.flags));
+        text.push_str(&format!(
+            "Limits:         chunk size {}-{} bytes (default {}), {} workers\n",
+            limits.min_chunk_size, limits.max_chunk_size, limits.default_chunk_size, limits.max_workers
+        ));
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_permitted_algorithms_by_canonical_name() {
+        let report = CapabilitiesUseCase::new().report();
+        let names: Vec<&str> = report.algorithms.iter().map(|a| a.name.as_str()).collect();
+
+        assert!(names.contains(&"brotli"));
+        assert!(names.contains(&"aes-256-gcm"));
+        assert!(names.contains(&"sha256"));
+        assert_eq!(names.contains(&"chacha20-poly1305"), !FIPS_MODE);
+        assert!(report
+            .algorithms
+            .iter()
+            .all(|a| Algorithm::parse(&a.name).unwrap().is_permitted()));
+        assert_eq!(report.formats.archive, CURRENT_FORMAT_VERSION);
+        assert_eq!(report.features.fips, FIPS_MODE);
+    }
+}
@@ -0,0 +1,117 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Cleanup Temp Use Case
+//!
+//! Implements `adaptive-pipeline cleanup`: finds the temporary files left
+//! behind by runs that crashed or were killed and removes them. Runs still
+//! in progress, including the current one, are never touched.
+//!
+//! What a crashed run leaves is its run directory in the managed temp root
+//! and any tracked files it had created elsewhere, typically `.partial`
+//! staging files next to restore targets. See
+//! [`crate::infrastructure::runtime::temp_root`].
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::CleanupTempUseCase;
+//!
+//! let use_case = CleanupTempUseCase::new(TempRoot::default_base());
+//! let report = use_case.execute(true).await?; // dry run: list only
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use byte_unit::Byte;
+use tracing::info;
+
+use crate::infrastructure::runtime::temp_root::{find_orphans, purge_orphans, CleanupReport};
+
+/// Use case for purging temporary files left by crashed runs
+pub struct CleanupTempUseCase {
+    base: PathBuf,
+}
+
+impl CleanupTempUseCase {
+    /// Creates a new Cleanup Temp use case for the temp root at `base`
+    pub fn new(base: PathBuf) -> Self {
+        Self { base }
+    }
+
+    /// Lists leftovers of crashed runs and, unless `dry_run`, removes them
+    ///
+    /// A dry run reports what would be removed without removing it.
+    pub async fn execute(&self, dry_run: bool) -> Result<CleanupReport> {
+        let base = self.base.clone();
+        let orphans = tokio::task::spawn_blocking(move || find_orphans(&base)).await??;
+        let size = |bytes: u64| Byte::from_u64(bytes).get_appropriate_unit(byte_unit::UnitType::Binary);
+
+        if orphans.is_empty() {
+            println!("No leftovers from crashed runs under {}", self.base.display());
+            return Ok(CleanupReport::default());
+        }
+
+        println!("\n=== Leftovers from crashed runs ({}) ===", self.base.display());
+        for orphan in &orphans {
+            println!("{} ({:.1})", orphan.run_dir.display(), size(orphan.bytes));
+            for file in &orphan.files {
+                println!("    {}", file.display());
+            }
+        }
+
+        if dry_run {
+            let files: usize = orphans.iter().map(|orphan| orphan.files.len()).sum();
+            let bytes: u64 = orphans.iter().map(|orphan| orphan.bytes).sum();
+            println!(
+                "\nDry run: would remove {} run(s) and {} tracked file(s), {:.1}",
+                orphans.len(),
+                files,
+                size(bytes)
+            );
+            return Ok(CleanupReport::default());
+        }
+
+        let report = tokio::task::spawn_blocking(move || purge_orphans(&orphans)).await?;
+        info!(
+            runs = report.runs_removed,
+            files = report.files_removed,
+            bytes = report.bytes_freed,
+            "Purged temp leftovers"
+        );
+        println!(
+            "\nRemoved {} run(s) and {} tracked file(s), freeing {:.1}",
+            report.runs_removed,
+            report.files_removed,
+            size(report.bytes_freed)
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_dry_run_keeps_leftovers_and_cleanup_removes_them() {
+        let base = TempDir::new().unwrap();
+        let crashed = base.path().join("run-999999-0badc0de");
+        std::fs::create_dir(&crashed).unwrap();
+        std::fs::write(crashed.join("scratch"), b"left behind").unwrap();
+        let use_case = CleanupTempUseCase::new(base.path().to_path_buf());
+
+        assert_eq!(use_case.execute(true).await.unwrap(), CleanupReport::default());
+        assert!(crashed.exists());
+
+        let report = use_case.execute(false).await.unwrap();
+        assert_eq!(report.runs_removed, 1);
+        assert!(!crashed.exists());
+    }
+}
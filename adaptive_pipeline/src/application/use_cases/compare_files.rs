@@ -9,17 +9,21 @@
 //!
 //! This module implements the use case for comparing original files against
 //! their `.adapipe` processed counterparts. It verifies file integrity by
-//! comparing sizes and checksums.
+//! comparing sizes and checksums. It can also compare two `.adapipe` archives
+//! directly, without restoring either.
 //!
 //! ## Overview
 //!
 //! The Compare Files use case provides:
 //!
 //! - **Size Comparison**: Compare file sizes to detect changes
-//! - **Checksum Verification**: Calculate and compare SHA-256 checksums
+//! - **Checksum Verification**: Calculate and compare SHA-256 or BLAKE3
+//!   checksums
 //! - **Metadata Display**: Show processing information from .adapipe file
 //! - **Detailed Reporting**: Optional detailed comparison output
 //! - **Change Detection**: Identify if files have been modified
+//! - **Archive Comparison**: Compare two archives' headers, stage lists and
+//!   chunk hashes to tell whether they restore to identical content
 //!
 //! ## Use Cases
 //!
@@ -27,6 +31,17 @@
 //! - **Backup Validation**: Confirm backup .adapipe matches current file
 //! - **Change Detection**: Determine if file needs reprocessing
 //! - **Audit Trail**: Document file state at processing time
+//! - **Deduplication**: Find archives in a backup set that hold the same
+//!   content
+//!
+//! ## Comparing Two Archives
+//!
+//! Each archive records the checksum of the data it restores to, so two
+//! archives hashed alike restore to identical content exactly when those
+//! checksums (and sizes) match, however differently they were chunked, compressed or
+//! encrypted. For archives that don't record a checksum, identical stage
+//! lists and identical stored chunks imply identical content; anything else
+//! is reported as unknown rather than guessed.
 //!
 //! ## Usage Examples
 //!
@@ -48,14 +63,122 @@
 //!     PathBuf::from("data.adapipe"),
 //!     true,
 //! ).await?;
+//!
+//! // Two archives, without restoring either
+//! let comparison = use_case.compare_archives(
+//!     PathBuf::from("monday.adapipe"),
+//!     PathBuf::from("tuesday.adapipe"),
+//! ).await?;
+//! if comparison.same_content == Some(true) {
+//!     // safe to keep only one
+//! }
 //! ```
 
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
 use adaptive_pipeline_domain::value_objects::binary_file_format::FileHeader;
 use anyhow::Result;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+use crate::infrastructure::adapters::ContentHasher;
+use crate::infrastructure::services::{
+    is_remote_location, open_source, AdapipeFormat, BinaryFormatReader, BinaryFormatService,
+};
+
+/// Result of comparing two `.adapipe` archives without restoring them
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchiveComparison {
+    /// Whether the archives restore to identical content; `None` when
+    /// neither the recorded checksums nor the stored chunks can tell
+    pub same_content: Option<bool>,
+    /// Whether the recorded original sizes match
+    pub same_size: bool,
+    /// Whether both archives record the same processing steps and
+    /// parameters
+    pub same_stages: bool,
+    /// Human-readable differences between the headers
+    pub differences: Vec<String>,
+    /// Chunk count of each archive
+    pub chunk_counts: (usize, usize),
+    /// Chunks identical at the same position in both archives
+    pub matching_chunks: usize,
+}
+
+impl ArchiveComparison {
+    /// Compares two archives from their headers and the SHA-256 of each
+    /// stored chunk (nonce and payload)
+    pub fn new(left: &FileHeader, right: &FileHeader, left_chunks: &[String], right_chunks: &[String]) -> Self {
+        let mut differences = Vec::new();
+
+        let same_size = left.original_size == right.original_size;
+        if !same_size {
+            differences.push(format!(
+                "Original size: {} vs {} bytes",
+                left.original_size, right.original_size
+            ));
+        }
+        if left.original_filename != right.original_filename {
+            differences.push(format!(
+                "Original filename: {} vs {}",
+                left.original_filename, right.original_filename
+            ));
+        }
+        if left.chunk_size != right.chunk_size {
+            differences.push(format!("Chunk size: {} vs {} bytes", left.chunk_size, right.chunk_size));
+        }
+
+        let same_stages = left.processing_steps == right.processing_steps;
+        if left.processing_steps.len() != right.processing_steps.len() {
+            differences.push(format!(
+                "Step count: {} vs {}",
+                left.processing_steps.len(),
+                right.processing_steps.len()
+            ));
+        }
+        for (index, (l, r)) in left.processing_steps.iter().zip(&right.processing_steps).enumerate() {
+            if l != r {
+                differences.push(format!(
+                    "Step {}: {:?} ({}) vs {:?} ({})",
+                    index, l.step_type, l.algorithm, r.step_type, r.algorithm
+                ));
+            }
+        }
+
+        let matching_chunks = left_chunks
+            .iter()
+            .zip(right_chunks)
+            .filter(|(l, r)| constant_time_eq_str(l, r))
+            .count();
+        let same_chunks = left_chunks.len() == right_chunks.len() && matching_chunks == left_chunks.len();
+
+        // Checksums taken with different hashes say nothing about each other
+        let checksums_comparable = !left.original_checksum.is_empty()
+            && !right.original_checksum.is_empty()
+            && left.checksum_algorithm == right.checksum_algorithm;
+        let same_content = if !same_size {
+            Some(false)
+        } else if checksums_comparable {
+            Some(constant_time_eq_str(&left.original_checksum, &right.original_checksum))
+        } else if same_stages && same_chunks {
+            // The same stored bytes reversed by the same steps
+            Some(true)
+        } else {
+            None
+        };
+
+        Self {
+            same_content,
+            same_size,
+            same_stages,
+            differences,
+            chunk_counts: (left_chunks.len(), right_chunks.len()),
+            matching_chunks,
+        }
+    }
+}
+
 /// Use case for comparing original files against .adapipe files.
 ///
 /// This use case compares a current file against the metadata stored in
@@ -66,14 +189,14 @@ use tracing::info;
 /// - Validate both files exist
 /// - Read .adapipe metadata
 /// - Compare file sizes
-/// - Calculate and compare SHA-256 checksums
+/// - Calculate and compare checksums with the hash the archive records
 /// - Display comparison results
 /// - Provide detailed information if requested
 ///
 /// ## Dependencies
 ///
 /// - **FileHeader**: For parsing .adapipe metadata
-/// - **SHA-256**: For checksum calculation
+/// - **ContentHasher**: For checksum calculation (SHA-256 or BLAKE3)
 pub struct CompareFilesUseCase;
 
 impl CompareFilesUseCase {
@@ -105,7 +228,7 @@ impl CompareFilesUseCase {
     /// - Compare and report differences
     ///
     /// **Step 3: Checksum Comparison**
-    /// - Calculate SHA-256 checksum of current file
+    /// - Calculate the checksum of current file with the archive's hash
     /// - Read expected checksum from .adapipe metadata
     /// - Compare and report match/mismatch
     ///
@@ -222,19 +345,23 @@ impl CompareFilesUseCase {
 
         // Compare checksums
         println!("\n🔐 Checksum Comparison:");
-        println!("   Expected checksum (from .adapipe): {}", metadata.original_checksum);
+        println!(
+            "   Expected checksum (from .adapipe, {}): {}",
+            metadata.checksum_algorithm, metadata.original_checksum
+        );
 
         // Calculate current file checksum
         println!("   🔄 Calculating current file checksum...");
 
-        let mut hasher = Sha256::new();
+        let mut hasher = ContentHasher::new(metadata.checksum_algorithm);
         let mut file = std::fs::File::open(&original)?;
         std::io::copy(&mut file, &mut hasher)?;
-        let current_checksum = format!("{:x}", hasher.finalize());
+        let current_checksum = hasher.finalize_hex();
 
         println!("   Current file checksum: {}", current_checksum);
 
-        if current_checksum == metadata.original_checksum {
+        let checksums_match = constant_time_eq_str(&current_checksum, &metadata.original_checksum);
+        if checksums_match {
             println!("   ✅ Checksums match - files are identical");
         } else {
             println!("   ❌ Checksums differ - files are not identical");
@@ -273,7 +400,7 @@ impl CompareFilesUseCase {
 
         // Summary
         println!("\n🎯 Comparison Summary:");
-        if original_size == metadata.original_size && current_checksum == metadata.original_checksum {
+        if original_size == metadata.original_size && checksums_match {
             println!("   ✅ Files are identical - no changes detected");
         } else {
             println!("   ❌ Files differ - changes detected");
@@ -284,6 +411,133 @@ impl CompareFilesUseCase {
 
         Ok(())
     }
+
+    /// Compares two `.adapipe` archives and prints the result.
+    ///
+    /// ## Parameters
+    ///
+    /// * `left` - First archive
+    /// * `right` - Second archive
+    /// * `detailed` - If true, also show each archive's processing details
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 📊 Archive Comparison:
+    ///    First:  /backups/monday.adapipe
+    ///    Second: /backups/tuesday.adapipe
+    ///
+    /// 🔍 Differences:
+    ///    Chunk size: 1048576 vs 4194304 bytes
+    ///
+    /// 📦 Chunks: 0 of 4 / 1 match
+    ///
+    /// 🎯 Comparison Summary:
+    ///    ✅ Archives restore to identical content
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors if either archive is missing or unreadable.
+    pub async fn execute_archives(&self, left: PathBuf, right: PathBuf, detailed: bool) -> Result<ArchiveComparison> {
+        let comparison = self.compare_archives(left.clone(), right.clone()).await?;
+
+        println!("📊 Archive Comparison:");
+        println!("   First:  {}", left.display());
+        println!("   Second: {}", right.display());
+
+        println!("\n🔍 Differences:");
+        if comparison.differences.is_empty() {
+            println!("   None in the headers");
+        }
+        for difference in &comparison.differences {
+            println!("   {}", difference);
+        }
+
+        let (left_chunks, right_chunks) = comparison.chunk_counts;
+        println!(
+            "\n📦 Chunks: {} of {} / {} match",
+            comparison.matching_chunks, left_chunks, right_chunks
+        );
+
+        if detailed {
+            println!("\n📋 Detailed Information:");
+            for path in [&left, &right] {
+                let header = Self::read_archive_header(path).await?;
+                println!("   {}", path.display());
+                println!(
+                    "      Processed at: {}",
+                    header.processed_at.format("%Y-%m-%d %H:%M:%S UTC")
+                );
+                println!("      Pipeline ID: {}", header.pipeline_id);
+                println!("      {}", header.get_processing_summary());
+                println!(
+                    "      Original checksum ({}): {}",
+                    header.checksum_algorithm, header.original_checksum
+                );
+            }
+        }
+
+        println!("\n🎯 Comparison Summary:");
+        match comparison.same_content {
+            Some(true) => println!("   ✅ Archives restore to identical content"),
+            Some(false) => println!("   ❌ Archives restore to different content"),
+            None => println!("   ⚠️  Unknown: the archives record no checksums and their chunks differ"),
+        }
+
+        Ok(comparison)
+    }
+
+    /// Compares two `.adapipe` archives without restoring either
+    ///
+    /// Reads both headers and hashes every stored chunk; nothing is
+    /// decrypted or decompressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors if either archive is missing or unreadable.
+    pub async fn compare_archives(&self, left: PathBuf, right: PathBuf) -> Result<ArchiveComparison> {
+        info!("Comparing archives: {} vs {}", left.display(), right.display());
+
+        let (left_header, left_chunks) = Self::read_archive(&left).await?;
+        let (right_header, right_chunks) = Self::read_archive(&right).await?;
+
+        Ok(ArchiveComparison::new(
+            &left_header,
+            &right_header,
+            &left_chunks,
+            &right_chunks,
+        ))
+    }
+
+    /// Reads an archive's header and the SHA-256 of each stored chunk
+    async fn read_archive(path: &Path) -> Result<(FileHeader, Vec<String>)> {
+        let mut reader = Self::open_archive(path).await?;
+        let header = reader.read_header()?;
+
+        let mut chunk_hashes = Vec::new();
+        while let Some(chunk) = reader.read_next_chunk().await? {
+            let mut hasher = Sha256::new();
+            hasher.update(chunk.nonce);
+            hasher.update(&chunk.payload);
+            chunk_hashes.push(format!("{:x}", hasher.finalize()));
+        }
+
+        Ok((header, chunk_hashes))
+    }
+
+    async fn read_archive_header(path: &Path) -> Result<FileHeader> {
+        Ok(Self::open_archive(path).await?.read_header()?)
+    }
+
+    async fn open_archive(path: &Path) -> Result<Box<dyn BinaryFormatReader>> {
+        // Remote sources (http://, s3://) are read through ranged requests
+        let location = path.to_string_lossy();
+        if !is_remote_location(&location) && !path.exists() {
+            return Err(anyhow::anyhow!(".adapipe file does not exist: {}", path.display()));
+        }
+        Ok(AdapipeFormat::new().create_reader_from(open_source(&location)?).await?)
+    }
 }
 
 impl Default for CompareFilesUseCase {
@@ -323,6 +577,100 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Writes an unprocessed archive of `data` split into `chunk_size`
+    /// chunks
+    async fn write_archive(path: &Path, data: &[u8], chunk_size: usize) -> PathBuf {
+        use adaptive_pipeline_domain::value_objects::binary_file_format::ChunkFormat;
+
+        let chunks: Vec<&[u8]> = data.chunks(chunk_size).collect();
+        let header = FileHeader::new(
+            "data.txt".to_string(),
+            data.len() as u64,
+            format!("{:x}", Sha256::digest(data)),
+        )
+        .with_chunk_info(chunk_size as u32, chunks.len() as u32)
+        .with_pipeline_id("compare-test".to_string());
+
+        let service = AdapipeFormat::new();
+        let mut writer = service.create_writer(path, header.clone()).await.unwrap();
+        for chunk in chunks {
+            writer.write_chunk(ChunkFormat::new([0u8; 12], chunk.to_vec())).unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+        path.to_path_buf()
+    }
+
+    #[tokio::test]
+    async fn test_compare_archives() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let data = b"same backup, taken twice".repeat(10);
+        let mut edited = data.clone();
+        edited[0] = b'S';
+        let first = write_archive(&temp_dir.path().join("a.adapipe"), &data, 60).await;
+        let rechunked = write_archive(&temp_dir.path().join("b.adapipe"), &data, 80).await;
+        let copy = write_archive(&temp_dir.path().join("c.adapipe"), &data, 60).await;
+        let changed = write_archive(&temp_dir.path().join("d.adapipe"), &edited, 60).await;
+
+        let use_case = CompareFilesUseCase::new();
+
+        let comparison = use_case.compare_archives(first.clone(), copy).await.unwrap();
+        assert_eq!(comparison.same_content, Some(true));
+        assert!(comparison.differences.is_empty());
+        assert_eq!(comparison.matching_chunks, comparison.chunk_counts.0);
+
+        // Different chunking, same content
+        let comparison = use_case.compare_archives(first.clone(), rechunked).await.unwrap();
+        assert_eq!(comparison.same_content, Some(true));
+        assert!(comparison.same_stages);
+        assert!(comparison.differences[0].starts_with("Chunk size"));
+
+        let comparison = use_case.compare_archives(first, changed).await.unwrap();
+        assert_eq!(comparison.same_content, Some(false));
+        assert!(comparison.same_size);
+        assert_eq!(comparison.matching_chunks, comparison.chunk_counts.0 - 1);
+    }
+
+    /// Tests the verdict for archives that record no original checksum.
+    #[test]
+    fn test_archive_comparison_without_checksums() {
+        let header = |size| FileHeader::new("data.txt".to_string(), size, String::new());
+        let chunks = ["aa".to_string(), "bb".to_string()];
+
+        let same = ArchiveComparison::new(&header(10), &header(10), &chunks, &chunks);
+        assert_eq!(same.same_content, Some(true));
+
+        let other = ["aa".to_string(), "cc".to_string()];
+        let unknown = ArchiveComparison::new(&header(10), &header(10), &chunks, &other);
+        assert_eq!(unknown.same_content, None);
+        assert_eq!(unknown.matching_chunks, 1);
+
+        let resized = ArchiveComparison::new(&header(10), &header(12), &chunks, &chunks);
+        assert_eq!(resized.same_content, Some(false));
+    }
+
+    /// Tests that checksums taken with different hashes are not compared.
+    #[test]
+    fn test_archive_comparison_across_checksum_algorithms() {
+        use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+
+        let data = b"same content, hashed two ways";
+        let sha256 = FileHeader::new(
+            "data.txt".to_string(),
+            data.len() as u64,
+            ContentHasher::digest_hex(ChecksumAlgorithm::Sha256, data),
+        );
+        let blake3 = FileHeader::new(
+            "data.txt".to_string(),
+            data.len() as u64,
+            ContentHasher::digest_hex(ChecksumAlgorithm::Blake3, data),
+        )
+        .with_checksum_algorithm(ChecksumAlgorithm::Blake3);
+        let chunks = ["aa".to_string()];
+
+        let comparison = ArchiveComparison::new(&sha256, &blake3, &chunks, &chunks);
+        assert_eq!(comparison.same_content, Some(true));
+    }
+
     #[tokio::test]
     async fn test_compare_missing_adapipe() {
         // Create temp file for original
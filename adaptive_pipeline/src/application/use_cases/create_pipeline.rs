@@ -39,11 +39,13 @@
 //! - Names are normalized to kebab-case
 //! - Reserved names (help, version, list, etc.) are rejected
 //! - Stages are specified as comma-separated values
-//! - Supported compression: brotli, gzip, zstd, lz4
+//! - Supported compression: brotli, gzip, zstd, lz4, xz
 //! - Supported encryption: aes256gcm, aes128gcm, chacha20poly1305
 //! - Supported transforms: base64, pii_masking, tee, debug, passthrough
 //! - Custom stages default to Transform type
 //! - Debug stages auto-generate unique ULID labels
+//! - A security policy, if given, is stored in the pipeline's configuration
+//! - The whole-file checksum uses SHA-256 unless BLAKE3 is chosen
 //!
 //! ## Usage Examples
 //!
@@ -57,13 +59,15 @@
 //!     "compress-files".to_string(),
 //!     "brotli".to_string(),
 //!     None,
+//!     None,
 //! ).await?;
 //!
-//! // Multi-stage pipeline
+//! // Multi-stage pipeline with a worker pool per stage
 //! use_case.execute(
 //!     "secure-backup".to_string(),
 //!     "brotli,aes256gcm,checksum".to_string(),
 //!     None,
+//!     Some(ExecutionTopology::StageParallel),
 //! ).await?;
 //! ```
 
@@ -73,9 +77,16 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
 
+use crate::application::command_bus::{handler_error, CommandHandler};
+use crate::application::commands::CreatePipelineCommand;
 use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
 use adaptive_pipeline_domain::entities::pipeline::Pipeline;
 use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageConfiguration, StageType};
+use adaptive_pipeline_domain::events::{PipelineCreatedEvent, PipelineEvent};
+use adaptive_pipeline_domain::value_objects::{
+    Algorithm, AuditRecord, ChecksumAlgorithm, ExecutionTopology, SecurityPolicy, SessionId,
+};
+use adaptive_pipeline_domain::PipelineError;
 
 /// Use case for creating new processing pipelines.
 ///
@@ -89,7 +100,7 @@ use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageCon
 /// - Parse stage specifications from comma-separated string
 /// - Map stage names to types and algorithms
 /// - Create pipeline domain entity
-/// - Save pipeline to repository
+/// - Save pipeline, audit record and creation event in one unit of work
 /// - Handle creation errors gracefully
 ///
 /// ## Dependencies
@@ -105,6 +116,7 @@ use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageCon
 ///     "data-backup".to_string(),
 ///     "brotli,aes256gcm".to_string(),
 ///     None,
+///     None,
 /// ).await {
 ///     Ok(()) => println!("Pipeline created successfully"),
 ///     Err(e) => eprintln!("Failed to create pipeline: {}", e),
@@ -112,6 +124,10 @@ use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageCon
 /// ```
 pub struct CreatePipelineUseCase {
     pipeline_repository: Arc<SqlitePipelineRepository>,
+    principal: String,
+    security_policy: SecurityPolicy,
+    checksum_algorithm: ChecksumAlgorithm,
+    session_id: Option<SessionId>,
 }
 
 impl CreatePipelineUseCase {
@@ -125,7 +141,39 @@ impl CreatePipelineUseCase {
     ///
     /// A new instance of `CreatePipelineUseCase`
     pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
-        Self { pipeline_repository }
+        Self {
+            pipeline_repository,
+            principal: "unknown".to_string(),
+            security_policy: SecurityPolicy::default(),
+            checksum_algorithm: ChecksumAlgorithm::default(),
+            session_id: None,
+        }
+    }
+
+    /// Attributes created pipelines to `principal` in the audit trail
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = principal.into();
+        self
+    }
+
+    /// Names the API session the pipelines are created through in the audit
+    /// trail
+    pub fn with_session(mut self, session_id: SessionId) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    /// Sets the security level and permissions the new pipeline requires of
+    /// whoever processes or restores with it
+    pub fn with_security_policy(mut self, security_policy: SecurityPolicy) -> Self {
+        self.security_policy = security_policy;
+        self
+    }
+
+    /// Sets the hash the new pipeline uses for whole-file checksums
+    pub fn with_checksum_algorithm(mut self, checksum_algorithm: ChecksumAlgorithm) -> Self {
+        self.checksum_algorithm = checksum_algorithm;
+        self
     }
 
     /// Executes the create pipeline use case.
@@ -141,6 +189,8 @@ impl CreatePipelineUseCase {
     ///     "compression,encryption,checksum"
     /// * `output` - Optional file path for pipeline configuration export (not
     ///   yet implemented)
+    /// * `topology` - How stages are spread over workers when the pipeline
+    ///   runs; `None` keeps the chunk-parallel default
     ///
     /// ## Stage Specifications
     ///
@@ -151,7 +201,7 @@ impl CreatePipelineUseCase {
     /// - `passthrough` → no-op
     ///
     /// **Specific Algorithms**:
-    /// - Compression: `brotli`, `gzip`, `zstd`, `lz4`
+    /// - Compression: `brotli`, `gzip`, `zstd`, `lz4`, `xz`
     /// - Encryption: `aes256gcm`, `aes128gcm`, `chacha20poly1305`
     /// - Transform: `base64`, `pii_masking`, `tee`, `debug`
     ///
@@ -161,7 +211,7 @@ impl CreatePipelineUseCase {
     ///
     /// ## Returns
     ///
-    /// - `Ok(())` - Pipeline created and saved successfully
+    /// - `Ok(Pipeline)` - The pipeline as created and saved
     /// - `Err(anyhow::Error)` - Validation or persistence failed
     ///
     /// ## Errors
@@ -171,6 +221,7 @@ impl CreatePipelineUseCase {
     /// - Name less than 4 characters after normalization
     /// - Reserved pipeline names
     /// - Invalid stage specifications
+    /// - Non-approved cryptographic algorithms in a FIPS-mode build
     /// - Repository save failures
     /// - Database connection errors
     ///
@@ -178,16 +229,23 @@ impl CreatePipelineUseCase {
     ///
     /// ```rust,ignore
     /// // Create simple compression pipeline
-    /// use_case.execute("backup".to_string(), "brotli".to_string(), None).await?;
+    /// use_case.execute("backup".to_string(), "brotli".to_string(), None, None).await?;
     ///
     /// // Create secure multi-stage pipeline
     /// use_case.execute(
     ///     "Secure Backup!".to_string(),  // Will be normalized to "secure-backup"
     ///     "brotli,aes256gcm,checksum".to_string(),
     ///     None,
+    ///     None,
     /// ).await?;
     /// ```
-    pub async fn execute(&self, name: String, stages: String, output: Option<PathBuf>) -> Result<()> {
+    pub async fn execute(
+        &self,
+        name: String,
+        stages: String,
+        output: Option<PathBuf>,
+        topology: Option<ExecutionTopology>,
+    ) -> Result<Pipeline> {
         info!("Creating pipeline: {}", name);
         info!("Stages: {}", stages);
 
@@ -201,45 +259,51 @@ impl CreatePipelineUseCase {
         for (index, stage_name) in stage_names.iter().enumerate() {
             let (stage_type, algorithm) = match stage_name.trim() {
                 // Generic stage types with default algorithms
-                "compression" => (StageType::Compression, "brotli".to_string()),
-                "encryption" => (StageType::Encryption, "aes256gcm".to_string()),
-                "integrity" | "checksum" => (StageType::Checksum, "sha256".to_string()),
-                custom_name if custom_name.contains("checksum") => (StageType::Checksum, "sha256".to_string()),
-                "passthrough" => (StageType::PassThrough, "passthrough".to_string()),
-
-                // Compression algorithms
-                "brotli" | "gzip" | "zstd" | "lz4" => (StageType::Compression, stage_name.trim().to_string()),
-
-                // Encryption algorithms
-                "aes256gcm" | "aes128gcm" | "chacha20poly1305" => {
-                    (StageType::Encryption, stage_name.trim().to_string())
+                "compression" => (StageType::Compression, Algorithm::brotli().to_string()),
+                "encryption" => (StageType::Encryption, Algorithm::aes_256_gcm().to_string()),
+                "integrity" | "checksum" => (StageType::Checksum, Algorithm::sha256().to_string()),
+                custom_name if custom_name.contains("checksum") => {
+                    (StageType::Checksum, Algorithm::sha256().to_string())
                 }
+                "passthrough" => (StageType::PassThrough, "passthrough".to_string()),
 
                 // Transform stages (production stages)
                 "base64" | "pii_masking" | "tee" | "debug" => (StageType::Transform, stage_name.trim().to_string()),
 
                 // Handle compression:algorithm syntax
                 custom_name if custom_name.starts_with("compression:") => {
-                    let algorithm = custom_name.strip_prefix("compression:").unwrap_or("brotli").to_string();
-                    (StageType::Compression, algorithm)
+                    let algorithm = Self::parse_stage_algorithm(custom_name, "compression:")?;
+                    if !algorithm.is_compression() {
+                        return Err(anyhow::anyhow!("'{}' is not a compression algorithm", algorithm));
+                    }
+                    (StageType::Compression, algorithm.to_string())
                 }
 
                 // Handle encryption:algorithm syntax
                 custom_name if custom_name.starts_with("encryption:") => {
-                    let algorithm = custom_name
-                        .strip_prefix("encryption:")
-                        .unwrap_or("aes256gcm")
-                        .to_string();
-                    (StageType::Encryption, algorithm)
+                    let algorithm = Self::parse_stage_algorithm(custom_name, "encryption:")?;
+                    if !algorithm.is_encryption() {
+                        return Err(anyhow::anyhow!("'{}' is not an encryption algorithm", algorithm));
+                    }
+                    (StageType::Encryption, algorithm.to_string())
                 }
 
-                _custom => {
+                // Bare algorithm names (any accepted alias) map to their category
+                custom_name => match Algorithm::parse(custom_name) {
+                    Ok(algorithm) if algorithm.is_compression() => (StageType::Compression, algorithm.to_string()),
+                    Ok(algorithm) if algorithm.is_encryption() => (StageType::Encryption, algorithm.to_string()),
+                    Ok(algorithm) if algorithm.is_hashing() => (StageType::Checksum, algorithm.to_string()),
                     // For unknown stages, treat them as Transform with the name as the algorithm
                     // This allows for custom stages to be used without code changes
-                    (StageType::Transform, stage_name.trim().to_string())
-                }
+                    _ => (StageType::Transform, custom_name.to_string()),
+                },
             };
 
+            // FIPS builds refuse non-approved cryptography up front
+            if let Ok(parsed) = Algorithm::parse(&algorithm) {
+                parsed.ensure_permitted()?;
+            }
+
             // Create parameters HashMap with algorithm
             let mut parameters = HashMap::new();
             parameters.insert("algorithm".to_string(), algorithm.clone());
@@ -260,18 +324,46 @@ impl CreatePipelineUseCase {
             pipeline_stages.push(stage);
         }
 
-        // Create pipeline domain entity
-        let pipeline = Pipeline::new(name, pipeline_stages)?;
+        // Create pipeline domain entity in the repository's namespace
+        let mut pipeline =
+            Pipeline::new(name, pipeline_stages)?.with_namespace(self.pipeline_repository.namespace().clone());
+        if let Some(topology) = topology {
+            pipeline.set_execution_topology(topology);
+        }
+        if !self.security_policy.is_unrestricted() {
+            pipeline.set_security_policy(&self.security_policy);
+        }
+        if !self.checksum_algorithm.is_default() {
+            self.checksum_algorithm.ensure_permitted()?;
+            pipeline.set_checksum_algorithm(self.checksum_algorithm);
+        }
 
-        // Save pipeline to repository
-        self.pipeline_repository
-            .save(&pipeline)
+        // Save the pipeline, its audit record and creation event atomically
+        let stage_count = pipeline.stages().len();
+        let audit = AuditRecord::new(&self.principal, "pipeline.create", format!("{} stages", stage_count))
+            .with_pipeline(pipeline.id().clone());
+        let audit = match &self.session_id {
+            Some(session_id) => audit.with_session(session_id.clone()),
+            None => audit,
+        };
+        let event = PipelineEvent::PipelineCreated(PipelineCreatedEvent::new(
+            pipeline.id().as_uuid(),
+            pipeline.name().to_string(),
+            stage_count,
+            Some(self.principal.clone()),
+        ));
+        let mut work = self.pipeline_repository.begin().await?;
+        work.save_pipeline(&pipeline)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to save pipeline: {}", e))?;
+        work.record_audit(&audit).await?;
+        work.record_event(&event).await?;
+        work.commit().await?;
 
         info!(
-            "Pipeline '{}' created successfully with ID: {}",
+            "Pipeline '{}' created successfully in namespace '{}' with ID: {}",
             pipeline.name(),
+            pipeline.namespace(),
             pipeline.id()
         );
         info!("Pipeline saved to database");
@@ -280,7 +372,7 @@ impl CreatePipelineUseCase {
             info!("Note: File output not yet implemented, pipeline saved to database only");
         }
 
-        Ok(())
+        Ok(pipeline)
     }
 
     /// Normalizes pipeline name to kebab-case.
@@ -361,7 +453,7 @@ impl CreatePipelineUseCase {
     /// assert!(validate_pipeline_name("help").is_err());  // Reserved
     /// assert_eq!(validate_pipeline_name("My Pipeline").unwrap(), "my-pipeline");
     /// ```
-    fn validate_pipeline_name(name: &str) -> Result<String> {
+    pub(crate) fn validate_pipeline_name(name: &str) -> Result<String> {
         // Check for empty name
         if name.is_empty() {
             return Err(anyhow::anyhow!("Pipeline name cannot be empty"));
@@ -396,6 +488,25 @@ impl CreatePipelineUseCase {
 
         Ok(normalized)
     }
+
+    /// Parses the algorithm half of a `category:algorithm` stage spec.
+    ///
+    /// Any alias accepted by [`Algorithm::parse`] is allowed, so
+    /// `encryption:aes256gcm` and `encryption:aes-256-gcm` produce the same
+    /// stage.
+    fn parse_stage_algorithm(spec: &str, prefix: &str) -> Result<Algorithm> {
+        let name = spec.strip_prefix(prefix).unwrap_or_default();
+        Algorithm::parse(name).map_err(|e| anyhow::anyhow!("Invalid algorithm in stage '{}': {}", spec, e))
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler<CreatePipelineCommand> for CreatePipelineUseCase {
+    async fn handle(&self, command: CreatePipelineCommand) -> std::result::Result<Pipeline, PipelineError> {
+        self.execute(command.name, command.stages, command.output, command.topology)
+            .await
+            .map_err(handler_error)
+    }
 }
 
 #[cfg(test)]
@@ -422,6 +533,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_stage_algorithm_normalizes_aliases() {
+        let algorithm = CreatePipelineUseCase::parse_stage_algorithm("encryption:aes256gcm", "encryption:").unwrap();
+        assert_eq!(algorithm, Algorithm::aes_256_gcm());
+        assert_eq!(algorithm.to_string(), "aes-256-gcm");
+
+        let algorithm = CreatePipelineUseCase::parse_stage_algorithm("compression:Zstd", "compression:").unwrap();
+        assert_eq!(algorithm, Algorithm::zstd());
+
+        assert!(CreatePipelineUseCase::parse_stage_algorithm("compression:", "compression:").is_err());
+    }
+
     #[test]
     fn test_validate_pipeline_name() {
         // Valid names
@@ -36,7 +36,8 @@
 //! - Missing pipelines return clear error messages
 //! - Interactive mode requires user confirmation (y/yes to proceed)
 //! - Force mode bypasses confirmation (for automation)
-//! - Deleted pipelines are removed permanently from the repository
+//! - Deleted pipelines are removed permanently from the repository, and from
+//!   the pipeline cache when one is attached
 //! - Pipeline details are displayed before deletion for verification
 //!
 //! ## Usage Examples
@@ -57,7 +58,14 @@ use std::io::{self, Write};
 use std::sync::Arc;
 use tracing::info;
 
+use crate::application::command_bus::{handler_error, CommandHandler};
+use crate::application::commands::DeletePipelineCommand;
+use crate::application::services::pipeline_cache::PipelineCache;
 use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::entities::Pipeline;
+use adaptive_pipeline_domain::events::{PipelineDeletedEvent, PipelineEvent};
+use adaptive_pipeline_domain::value_objects::{AuditRecord, SessionId};
+use adaptive_pipeline_domain::PipelineError;
 
 /// Use case for deleting pipelines from the system.
 ///
@@ -90,6 +98,9 @@ use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineReposito
 /// ```
 pub struct DeletePipelineUseCase {
     pipeline_repository: Arc<SqlitePipelineRepository>,
+    pipeline_cache: Option<Arc<PipelineCache>>,
+    principal: String,
+    session_id: Option<SessionId>,
 }
 
 impl DeletePipelineUseCase {
@@ -103,7 +114,32 @@ impl DeletePipelineUseCase {
     ///
     /// A new instance of `DeletePipelineUseCase`
     pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
-        Self { pipeline_repository }
+        Self {
+            pipeline_repository,
+            pipeline_cache: None,
+            principal: "unknown".to_string(),
+            session_id: None,
+        }
+    }
+
+    /// Attributes deleted pipelines to `principal` in the audit trail
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = principal.into();
+        self
+    }
+
+    /// Names the API session the pipelines are deleted through in the audit
+    /// trail
+    pub fn with_session(mut self, session_id: SessionId) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    /// Drops deleted pipelines from `pipeline_cache`, so jobs sharing it stop
+    /// seeing them at once
+    pub fn with_pipeline_cache(mut self, pipeline_cache: Arc<PipelineCache>) -> Self {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
     }
 
     /// Executes the delete pipeline use case.
@@ -204,15 +240,49 @@ impl DeletePipelineUseCase {
             }
         }
 
-        // Delete the pipeline from repository
-        self.pipeline_repository
-            .delete(pipeline.id().clone())
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to delete pipeline: {}", e))?;
+        self.delete(&pipeline).await?;
 
         println!("✅ Pipeline '{}' deleted successfully", pipeline_name);
         Ok(())
     }
+
+    /// Deletes `pipeline` without asking, recording its audit entry and
+    /// event atomically and dropping it from the pipeline cache
+    ///
+    /// For callers that have looked the pipeline up and confirmed the
+    /// deletion themselves, such as the HTTP API.
+    pub async fn delete(&self, pipeline: &Pipeline) -> Result<()> {
+        let audit = AuditRecord::new(&self.principal, "pipeline.delete", pipeline.name().to_string())
+            .with_pipeline(pipeline.id().clone());
+        let audit = match &self.session_id {
+            Some(session_id) => audit.with_session(session_id.clone()),
+            None => audit,
+        };
+        let event = PipelineEvent::PipelineDeleted(PipelineDeletedEvent::new(
+            pipeline.id().as_uuid(),
+            Some(self.principal.clone()),
+        ));
+        let mut work = self.pipeline_repository.begin().await?;
+        work.delete_pipeline(pipeline.id())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to delete pipeline: {}", e))?;
+        work.record_audit(&audit).await?;
+        work.record_event(&event).await?;
+        work.commit().await?;
+        if let Some(cache) = &self.pipeline_cache {
+            cache.invalidate(pipeline.id());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler<DeletePipelineCommand> for DeletePipelineUseCase {
+    async fn handle(&self, command: DeletePipelineCommand) -> std::result::Result<(), PipelineError> {
+        self.execute(command.pipeline_name, command.force)
+            .await
+            .map_err(handler_error)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,144 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Encryption Vectors Use Case
+//!
+//! Publishes and checks the chunk encryption test vectors that pin down the
+//! `.adapipe` encryption layout (see
+//! [`chunk_encryption_spec`](adaptive_pipeline_domain::value_objects::chunk_encryption_spec)).
+//!
+//! ## Business Rules
+//!
+//! - `generate` writes the vectors this build produces, as JSON, to a file or
+//!   standard output
+//! - `verify` checks a published suite against this build and fails if any
+//!   vector is not reproduced, so format drift is caught before release
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::EncryptionVectorsUseCase;
+//!
+//! let use_case = EncryptionVectorsUseCase::new();
+//! use_case.generate(Some(PathBuf::from("chunk-encryption-v2.json"))).await?;
+//! use_case.verify(PathBuf::from("chunk-encryption-v2.json")).await?;
+//! ```
+
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::infrastructure::services::ChunkTestVectorService;
+use adaptive_pipeline_domain::value_objects::TestVectorSuite;
+
+/// Use case for generating and verifying chunk encryption test vectors.
+///
+/// ## Dependencies
+///
+/// - **ChunkTestVectorService**: Runs the vectors through the production
+///   encryption adapter
+#[derive(Default)]
+pub struct EncryptionVectorsUseCase {
+    service: ChunkTestVectorService,
+}
+
+impl EncryptionVectorsUseCase {
+    /// Creates a new Encryption Vectors use case.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the vectors this build produces to `output`, or prints them
+    /// when no output is given.
+    pub async fn generate(&self, output: Option<PathBuf>) -> Result<()> {
+        let suite = self.service.generate()?;
+        let json = suite.to_json()?;
+
+        match output {
+            Some(path) => {
+                tokio::fs::write(&path, format!("{}\n", json))
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to write test vectors {}: {}", path.display(), e))?;
+                info!("Wrote {} test vectors to {}", suite.vectors.len(), path.display());
+                println!("✅ Wrote {} test vectors to {}", suite.vectors.len(), path.display());
+            }
+            None => println!("{}", json),
+        }
+        Ok(())
+    }
+
+    /// Checks every vector in the suite at `input` against this build.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 🔐 chunk-encryption-v2.json (format version 2, 6 vectors)
+    ///    ✅ aes-256-gcm/empty
+    ///    ✅ aes-256-gcm/short
+    ///    ...
+    /// ✅ All 6 test vectors verified
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the suite cannot be read or parsed, is empty, or
+    /// any vector fails.
+    pub async fn verify(&self, input: PathBuf) -> Result<()> {
+        info!("Verifying test vectors: {}", input.display());
+        let json = tokio::fs::read_to_string(&input)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read test vectors {}: {}", input.display(), e))?;
+        let suite = TestVectorSuite::from_json(&json)?;
+        if suite.vectors.is_empty() {
+            return Err(anyhow::anyhow!("{} contains no test vectors", input.display()));
+        }
+
+        println!(
+            "🔐 {} (format version {}, {} vectors)",
+            input.display(),
+            suite.format_version,
+            suite.vectors.len()
+        );
+        if let Some(provenance) = &suite.generated_by {
+            println!("   Generated by: {}", provenance);
+        }
+
+        let checks = self.service.verify(&suite);
+        for check in &checks {
+            match &check.error {
+                None => println!("   ✅ {}", check.name),
+                Some(error) => println!("   ❌ {}: {}", check.name, error),
+            }
+        }
+
+        let failed = checks.iter().filter(|check| !check.passed()).count();
+        if failed > 0 {
+            return Err(anyhow::anyhow!("{} of {} test vectors failed", failed, checks.len()));
+        }
+        println!("✅ All {} test vectors verified", checks.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generated_file_verifies_and_a_changed_one_does_not() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("vectors.json");
+        let use_case = EncryptionVectorsUseCase::new();
+        use_case.generate(Some(path.clone())).await.unwrap();
+        use_case.verify(path.clone()).await.unwrap();
+
+        let mut suite = TestVectorSuite::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        suite.vectors[0].key = "00".repeat(32);
+        std::fs::write(&path, suite.to_json().unwrap()).unwrap();
+        assert!(use_case.verify(path).await.is_err());
+    }
+}
@@ -0,0 +1,217 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Estimate Cost Use Case
+//!
+//! Estimates how long a pipeline would take to process an input of a given
+//! size, and the memory, CPU and disk the run would need, without an input
+//! file. This answers capacity-planning questions such as "how long will the
+//! nightly 200 GiB backup take?".
+//!
+//! ## Business Rules
+//!
+//! - Estimates come from the domain [`CostModel`], fed with the pipeline's
+//!   recorded throughput on the configured storage type, so they improve as
+//!   the pipeline runs
+//! - Pipelines that have never run are estimated from nominal per-stage rates,
+//!   and the output says so
+//! - The chunk size is the one `process` would pick for that size
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::EstimateCostUseCase;
+//!
+//! let use_case = EstimateCostUseCase::new(pipeline_repository).with_chunk_size_history(chunk_size_history);
+//! let estimate = use_case.estimate("nightly-backup", 200 << 30).await?;
+//! println!("{:?}", estimate.estimated_duration());
+//! ```
+
+use anyhow::Result;
+use byte_unit::Byte;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::application::use_cases::ProcessFileUseCase;
+use adaptive_pipeline_domain::repositories::pipeline_repository::PipelineRepository;
+use adaptive_pipeline_domain::repositories::ChunkSizeHistoryRepository;
+use adaptive_pipeline_domain::services::{CostEstimate, CostModel};
+
+/// JSON form of an estimate
+#[derive(Serialize)]
+struct EstimateReport<'a> {
+    pipeline: &'a str,
+    storage_type: &'a str,
+    #[serde(flatten)]
+    estimate: &'a CostEstimate,
+}
+
+/// Use case for estimating the cost of processing an input size.
+///
+/// ## Dependencies
+///
+/// - **Pipeline Repository**: For loading the pipeline to estimate
+/// - **Chunk Size History** (optional): Recorded throughput the estimate is
+///   based on; without it every estimate uses stage defaults
+pub struct EstimateCostUseCase {
+    pipeline_repository: Arc<dyn PipelineRepository>,
+    chunk_size_history: Option<Arc<dyn ChunkSizeHistoryRepository>>,
+}
+
+impl EstimateCostUseCase {
+    /// Creates a new Estimate Cost use case.
+    pub fn new(pipeline_repository: Arc<dyn PipelineRepository>) -> Self {
+        Self {
+            pipeline_repository,
+            chunk_size_history: None,
+        }
+    }
+
+    /// Bases estimates on the throughput recorded in `chunk_size_history`
+    pub fn with_chunk_size_history(mut self, chunk_size_history: Arc<dyn ChunkSizeHistoryRepository>) -> Self {
+        self.chunk_size_history = Some(chunk_size_history);
+        self
+    }
+
+    /// Estimates the cost of processing `file_size` bytes with a pipeline.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the pipeline does not exist or cannot be loaded.
+    /// A failed history lookup is logged and falls back to stage defaults.
+    pub async fn estimate(&self, pipeline_name: &str, file_size: u64) -> Result<CostEstimate> {
+        let pipeline = self
+            .pipeline_repository
+            .find_by_name(pipeline_name)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load pipeline: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", pipeline_name))?;
+
+        let history = match &self.chunk_size_history {
+            Some(chunk_size_history) => chunk_size_history
+                .history(pipeline.id(), &ProcessFileUseCase::storage_type_label())
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(pipeline = %pipeline_name, "Failed to load chunk size history: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+
+        Ok(CostModel::from_history(history).estimate(&pipeline, file_size))
+    }
+
+    /// Prints the estimate for processing `file_size` bytes with a pipeline.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// === Cost Estimate: nightly-backup ===
+    /// Input Size: 200.0 GiB
+    /// Storage: ssd
+    /// Based On: 12 recorded run(s) at this chunk size
+    ///
+    /// Chunk Size: 64.0 MiB
+    /// Chunks: 3200
+    /// Workers: 12
+    /// Throughput: 412.3 MiB/s
+    /// Estimated Duration: 496.7s
+    ///
+    /// Resource Requirements:
+    ///   Memory: 512.0 MiB
+    ///   CPU Cores: 1
+    ///   Disk Space: 400.0 GiB
+    /// ```
+    pub async fn execute(&self, pipeline_name: &str, file_size: u64, json: bool) -> Result<()> {
+        info!("Estimating pipeline {} for {} bytes", pipeline_name, file_size);
+        let estimate = self.estimate(pipeline_name, file_size).await?;
+        let storage_type = ProcessFileUseCase::storage_type_label();
+
+        if json {
+            let report = EstimateReport {
+                pipeline: pipeline_name,
+                storage_type: &storage_type,
+                estimate: &estimate,
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+
+        let size = |bytes: u64| Byte::from_u64(bytes).get_appropriate_unit(byte_unit::UnitType::Binary);
+        println!("\n=== Cost Estimate: {} ===", pipeline_name);
+        println!("Input Size: {:.1}", size(estimate.file_size));
+        println!("Storage: {}", storage_type);
+        println!("Based On: {}", estimate.basis);
+        println!();
+        println!("Chunk Size: {:.1}", size(estimate.chunk_size as u64));
+        println!("Chunks: {}", estimate.chunk_count);
+        println!("Workers: {}", estimate.worker_count);
+        println!("Throughput: {:.1}/s", size(estimate.bytes_per_second as u64));
+        println!("Estimated Duration: {:.1?}", estimate.estimated_duration());
+        println!();
+        println!("Resource Requirements:");
+        println!("  Memory: {:.1}", size(estimate.memory_bytes));
+        println!("  CPU Cores: {}", estimate.cpu_cores);
+        println!("  Disk Space: {:.1}", size(estimate.disk_space_bytes));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::sqlite_chunk_history::SqliteChunkSizeHistoryRepository;
+    use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+    use adaptive_pipeline_domain::entities::{PipelineStage, StageConfiguration, StageType};
+    use adaptive_pipeline_domain::services::CostBasis;
+    use adaptive_pipeline_domain::{ChunkSize, Pipeline};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_estimate_uses_recorded_runs_once_there_are_any() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let database = dir.path().join("pipeline.db").to_string_lossy().to_string();
+        let repository = Arc::new(SqlitePipelineRepository::new(&database).await.unwrap());
+        let history = Arc::new(SqliteChunkSizeHistoryRepository::new(&database).await.unwrap());
+        let stage = PipelineStage::new(
+            "compression".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            1,
+        )
+        .unwrap();
+        let pipeline = Pipeline::new("estimated".to_string(), vec![stage]).unwrap();
+        repository.save(&pipeline).await.unwrap();
+
+        let use_case = EstimateCostUseCase::new(repository.clone()).with_chunk_size_history(history.clone());
+        let size = 200u64 << 30;
+        let estimate = use_case.estimate("estimated", size).await.unwrap();
+        assert_eq!(estimate.basis, CostBasis::StageDefaults);
+        assert!(use_case.estimate("missing", size).await.is_err());
+
+        // One recorded run: 2 GiB in 4 s at the chunk size a 200 GiB run uses
+        let chunk_size = ChunkSize::new(estimate.chunk_size).unwrap();
+        history
+            .record_run(
+                pipeline.id(),
+                &ProcessFileUseCase::storage_type_label(),
+                chunk_size,
+                2 << 30,
+                Duration::from_secs(4),
+            )
+            .await
+            .unwrap();
+
+        let estimate = use_case.estimate("estimated", size).await.unwrap();
+        assert!(matches!(estimate.basis, CostBasis::ChunkSizeHistory { runs: 1, .. }));
+        assert_eq!(estimate.estimated_duration(), Duration::from_secs(400));
+        assert_eq!(estimate.disk_space_bytes, 2 * size);
+    }
+}
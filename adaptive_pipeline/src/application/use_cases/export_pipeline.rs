@@ -0,0 +1,108 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Export Pipeline Use Case
+//!
+//! Writes a stored pipeline as a portable TOML, YAML or JSON definition that
+//! `pipeline import` can recreate on another machine or check into version
+//! control.
+//!
+//! ## Business Rules
+//!
+//! - The format is the one asked for, else the one named by the output
+//!   file's extension, else YAML
+//! - A definition is only written once it has been read back and rebuilt
+//!   into the same pipeline, so every export can be imported
+//! - An existing output file is never replaced
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ExportPipelineUseCase;
+//!
+//! let use_case = ExportPipelineUseCase::new(pipeline_repository);
+//! use_case.execute("secure-backup".to_string(), None, Some(PathBuf::from("backup.toml"))).await?;
+//! ```
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::infrastructure::config::pipeline_definition;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::value_objects::{DefinitionFormat, PipelineDefinition};
+
+/// Use case for exporting a pipeline as a definition file.
+///
+/// ## Dependencies
+///
+/// - **Pipeline Repository**: For loading the pipeline to export
+pub struct ExportPipelineUseCase {
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+}
+
+impl ExportPipelineUseCase {
+    /// Creates a new Export Pipeline use case.
+    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        Self { pipeline_repository }
+    }
+
+    /// Writes the definition of `pipeline_name` to `output`, or to stdout
+    /// when no output is given.
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - A pipeline that does not exist
+    /// - A pipeline whose definition would not import as the same pipeline
+    /// - An existing output file
+    pub async fn execute(
+        &self,
+        pipeline_name: String,
+        format: Option<DefinitionFormat>,
+        output: Option<PathBuf>,
+    ) -> Result<()> {
+        let pipeline = self
+            .pipeline_repository
+            .find_by_name(&pipeline_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Pipeline '{}' not found", pipeline_name))?;
+        let format = format
+            .or_else(|| output.as_deref().and_then(DefinitionFormat::from_path))
+            .unwrap_or(DefinitionFormat::Yaml);
+
+        let definition = PipelineDefinition::from_pipeline(&pipeline)?;
+        let text = pipeline_definition::render(&definition, format)?;
+        let imported = pipeline_definition::parse(&text, format)?;
+        if imported != definition || PipelineDefinition::from_pipeline(&imported.to_pipeline()?)? != definition {
+            return Err(anyhow::anyhow!(
+                "Pipeline '{}' cannot be written as {} without losing settings",
+                pipeline_name,
+                format
+            ));
+        }
+
+        match output {
+            Some(output) => {
+                if output.exists() {
+                    return Err(anyhow::anyhow!("Output file already exists: {}", output.display()));
+                }
+                tokio::fs::write(&output, &text).await?;
+                info!(
+                    "Exported pipeline '{}' as {} to {}",
+                    pipeline_name,
+                    format,
+                    output.display()
+                );
+                println!("✅ Exported pipeline '{}' to {}", pipeline_name, output.display());
+            }
+            None => print!("{}", text),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Export Tar Use Case
+//!
+//! Writes the restored contents of one or more `.adapipe` archives as a tar
+//! file, one entry per archive, so existing tar tooling can consume them
+//! without restoring to a directory first.
+//!
+//! ## Business Rules
+//!
+//! - Each entry is named after the original filename recorded in its
+//!   archive, confined to a relative path as `restore` does, and two archives
+//!   may not produce the same entry
+//! - Restored data streams straight into the tar; every entry is verified
+//!   against its archive's size and checksum while it is written
+//! - The tar is staged next to the output and only appears once every entry
+//!   has been verified; an existing output is never replaced
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ExportTarUseCase;
+//!
+//! let use_case = ExportTarUseCase::new(metrics_service);
+//! use_case.execute(vec![PathBuf::from("data.adapipe")], PathBuf::from("data.tar")).await?;
+//! ```
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+use crate::application::use_cases::restore_file::{archive_relative_path, RestoreFileUseCase};
+use crate::infrastructure::adapters::StagedOutput;
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::runtime::StageRegistry;
+use crate::infrastructure::services::tar_container::{entry_header, entry_padding, END_OF_ARCHIVE};
+use adaptive_pipeline_domain::entities::SecurityContext;
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::JobPriority;
+
+/// Permissions recorded on exported entries
+const ENTRY_MODE: u32 = 0o644;
+
+/// Use case for exporting `.adapipe` archives as a tar file.
+///
+/// ## Dependencies
+///
+/// - **RestoreFileUseCase**: Streams and verifies each archive's restored data
+pub struct ExportTarUseCase {
+    restore: RestoreFileUseCase,
+}
+
+impl ExportTarUseCase {
+    /// Creates a new Export Tar use case.
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            restore: RestoreFileUseCase::new(metrics_service),
+        }
+    }
+
+    /// Restores through `stage_registry`; see
+    /// [`RestoreFileUseCase::with_stage_registry`]
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.restore = self.restore.with_stage_registry(stage_registry);
+        self
+    }
+
+    /// Stops the export at the next chunk boundary once `shutdown` is
+    /// requested; see [`RestoreFileUseCase::with_shutdown`]
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.restore = self.restore.with_shutdown(shutdown);
+        self
+    }
+
+    /// Exports on behalf of `security_context`; see
+    /// [`RestoreFileUseCase::with_security_context`]
+    pub fn with_security_context(mut self, security_context: SecurityContext) -> Self {
+        self.restore = self.restore.with_security_context(security_context);
+        self
+    }
+
+    /// Tar path used when none is given: the archive's path with `.adapipe`
+    /// replaced by `.tar` (`data.adapipe` → `data.tar`)
+    pub fn default_output(input: &Path) -> PathBuf {
+        match input.extension() {
+            Some(extension) if extension == "adapipe" => input.with_extension("tar"),
+            _ => {
+                let mut path = input.as_os_str().to_owned();
+                path.push(".tar");
+                PathBuf::from(path)
+            }
+        }
+    }
+
+    /// Writes the restored contents of `inputs` to the tar file `output`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - An existing output file
+    /// - An unreadable archive, or one whose original filename cannot be
+    ///   stored as a confined tar entry
+    /// - Two archives with the same entry name
+    /// - Restoration or verification failures; no tar is left behind
+    pub async fn execute(&self, inputs: Vec<PathBuf>, output: PathBuf) -> Result<()> {
+        if output.exists() {
+            return Err(anyhow::anyhow!("Output file already exists: {}", output.display()));
+        }
+        info!("Exporting {} archive(s) to {}", inputs.len(), output.display());
+        println!("📦 Exporting to tar: {}", output.display());
+
+        // Read every header first so a bad archive fails before any data
+        // is restored
+        let mut archives = Vec::with_capacity(inputs.len());
+        let mut entry_paths = HashSet::new();
+        for input in inputs {
+            let metadata = RestoreFileUseCase::read_metadata(&input).await?;
+            let entry_path = archive_relative_path(&metadata.original_filename, false)?;
+            if !entry_paths.insert(entry_path.clone()) {
+                return Err(anyhow::anyhow!(
+                    "{} would be exported twice; export those archives to separate tar files",
+                    entry_path.display()
+                ));
+            }
+            archives.push((input, metadata, entry_path));
+        }
+
+        let mut staged = StagedOutput::create(&output, None).await?;
+        let mut total_bytes = 0u64;
+        for (input, metadata, entry_path) in &archives {
+            let mtime = metadata.processed_at.timestamp().max(0) as u64;
+            let header = entry_header(entry_path, metadata.original_size, ENTRY_MODE, mtime)?;
+            staged.file().write_all(&header).await?;
+
+            let chunks = self
+                .restore
+                .restore_into(input, metadata, staged.file(), JobPriority::default())
+                .await?;
+            let padding = entry_padding(metadata.original_size);
+            staged.file().write_all(&END_OF_ARCHIVE[..padding]).await?;
+
+            total_bytes += metadata.original_size;
+            println!(
+                "   ✅ {} ({} bytes, {} chunks) from {}",
+                entry_path.display(),
+                metadata.original_size,
+                chunks,
+                input.display()
+            );
+        }
+        staged.file().write_all(&END_OF_ARCHIVE).await?;
+        staged.file().flush().await?;
+        staged.commit(|_, _| {}).await?;
+
+        println!(
+            "✅ Exported {} entries ({} bytes) to {}",
+            archives.len(),
+            total_bytes,
+            output.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_output_replaces_the_adapipe_extension() {
+        assert_eq!(
+            ExportTarUseCase::default_output(Path::new("out/data.csv.adapipe")),
+            Path::new("out/data.csv.tar")
+        );
+        assert_eq!(
+            ExportTarUseCase::default_output(Path::new("archive.bin")),
+            Path::new("archive.bin.tar")
+        );
+    }
+}
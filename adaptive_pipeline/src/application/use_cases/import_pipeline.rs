@@ -0,0 +1,149 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Import Pipeline Use Case
+//!
+//! Creates a pipeline from a TOML, YAML or JSON definition written by
+//! `pipeline export` or by hand.
+//!
+//! ## Business Rules
+//!
+//! - The format is the one asked for, else the one named by the file's
+//!   extension
+//! - Unknown fields, unknown stage types, algorithms that do not match their
+//!   stage type and invalid settings are rejected before anything is saved
+//! - The name follows the same rules as `create` and may not already be in
+//!   use; `--name` imports under a different name
+//! - The pipeline, its audit record and creation event are saved together
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ImportPipelineUseCase;
+//!
+//! let use_case = ImportPipelineUseCase::new(pipeline_repository).with_principal("alice");
+//! use_case.execute(PathBuf::from("backup.toml"), None, None).await?;
+//! ```
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::application::use_cases::CreatePipelineUseCase;
+use crate::infrastructure::config::pipeline_definition;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::entities::Pipeline;
+use adaptive_pipeline_domain::events::{PipelineCreatedEvent, PipelineEvent};
+use adaptive_pipeline_domain::value_objects::{AuditRecord, DefinitionFormat, SessionId};
+
+/// Use case for importing a pipeline from a definition file.
+///
+/// ## Dependencies
+///
+/// - **Pipeline Repository**: For checking the name and saving the pipeline
+pub struct ImportPipelineUseCase {
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+    principal: String,
+    session_id: Option<SessionId>,
+}
+
+impl ImportPipelineUseCase {
+    /// Creates a new Import Pipeline use case.
+    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        Self {
+            pipeline_repository,
+            principal: "unknown".to_string(),
+            session_id: None,
+        }
+    }
+
+    /// Records `principal` as the importer in the audit log
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = principal.into();
+        self
+    }
+
+    /// Records the import against `session_id` in the audit log
+    pub fn with_session(mut self, session_id: SessionId) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    /// Creates the pipeline defined in `file`, named `name` if given.
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - An unreadable file, or one whose format cannot be told from its
+    ///   extension when none is given
+    /// - A definition that does not parse or describes an invalid pipeline
+    /// - An invalid pipeline name, or one already in use
+    pub async fn execute(
+        &self,
+        file: PathBuf,
+        format: Option<DefinitionFormat>,
+        name: Option<String>,
+    ) -> Result<Pipeline> {
+        let format = format.or_else(|| DefinitionFormat::from_path(&file)).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot tell the format of {}; use --format toml, yaml or json",
+                file.display()
+            )
+        })?;
+        let text = tokio::fs::read_to_string(&file)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+        let mut definition = pipeline_definition::parse(&text, format)?;
+        if let Some(name) = name {
+            definition.name = name;
+        }
+
+        CreatePipelineUseCase::validate_pipeline_name(&definition.name)?;
+        if self.pipeline_repository.find_by_name(&definition.name).await?.is_some() {
+            return Err(anyhow::anyhow!(
+                "Pipeline '{}' already exists; use --name to import it under another name",
+                definition.name
+            ));
+        }
+        let pipeline = definition
+            .to_pipeline()?
+            .with_namespace(self.pipeline_repository.namespace().clone());
+
+        // Save the pipeline, its audit record and creation event atomically
+        let stage_count = pipeline.stages().len();
+        let audit = AuditRecord::new(&self.principal, "pipeline.import", format!("{} stages", stage_count))
+            .with_pipeline(pipeline.id().clone());
+        let audit = match &self.session_id {
+            Some(session_id) => audit.with_session(session_id.clone()),
+            None => audit,
+        };
+        let event = PipelineEvent::PipelineCreated(PipelineCreatedEvent::new(
+            pipeline.id().as_uuid(),
+            pipeline.name().to_string(),
+            stage_count,
+            Some(self.principal.clone()),
+        ));
+        let mut work = self.pipeline_repository.begin().await?;
+        work.save_pipeline(&pipeline)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to save pipeline: {}", e))?;
+        work.record_audit(&audit).await?;
+        work.record_event(&event).await?;
+        work.commit().await?;
+
+        info!(
+            "Imported pipeline '{}' from {} in namespace '{}' with ID: {}",
+            pipeline.name(),
+            file.display(),
+            pipeline.namespace(),
+            pipeline.id()
+        );
+        println!("✅ Imported pipeline '{}' from {}", pipeline.name(), file.display());
+        Ok(pipeline)
+    }
+}
@@ -0,0 +1,149 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Import Tar Use Case
+//!
+//! Processes every regular file in a tar stream through a pipeline, writing
+//! one `.adapipe` archive per entry. The stream may be a file or standard
+//! input, so `tar -c` output can be archived without unpacking it first.
+//!
+//! ## Business Rules
+//!
+//! - Entry `logs/app.log` becomes `<output-dir>/logs/app.log.adapipe`, and
+//!   its archive records `app.log` as the original filename
+//! - Entries are staged one at a time in the managed temp root and removed
+//!   once processed; directories are implied by their files, and links and
+//!   special files are skipped with a warning
+//! - Entries with absolute paths or `..` components stop the import
+//! - Each entry is processed exactly as `process` would process the file,
+//!   including the overwrite policy and quota checks
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ImportTarUseCase;
+//!
+//! let use_case = ImportTarUseCase::new(process_file_use_case);
+//! use_case.execute(PathBuf::from("-"), PathBuf::from("archives"), config).await?;
+//! ```
+
+use anyhow::Result;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::application::use_cases::{ProcessFileConfig, ProcessFileResult, ProcessFileUseCase};
+use crate::infrastructure::services::stage_tar_entries;
+
+/// Input path that reads the tar stream from standard input
+pub const STDIN_INPUT: &str = "-";
+
+/// Use case for processing the entries of a tar stream.
+///
+/// ## Dependencies
+///
+/// - **ProcessFileUseCase**: Processes each staged entry
+pub struct ImportTarUseCase {
+    process_file: ProcessFileUseCase,
+}
+
+impl ImportTarUseCase {
+    /// Creates a new Import Tar use case.
+    pub fn new(process_file: ProcessFileUseCase) -> Self {
+        Self { process_file }
+    }
+
+    /// Archive path for a tar entry: the entry path under `output_dir` with
+    /// `.adapipe` appended
+    pub fn output_path(output_dir: &Path, entry_path: &Path) -> PathBuf {
+        let mut path = output_dir.join(entry_path).into_os_string();
+        path.push(".adapipe");
+        PathBuf::from(path)
+    }
+
+    /// Processes every regular file of the tar at `input` (or standard input
+    /// for `-`) into `output_dir`.
+    ///
+    /// `config` supplies the pipeline and processing options; its input and
+    /// output are replaced for each entry.
+    ///
+    /// ## Errors
+    ///
+    /// Stops at the first unreadable entry, unsafe entry path or processing
+    /// failure. Archives already written for earlier entries are kept.
+    pub async fn execute(
+        &self,
+        input: PathBuf,
+        output_dir: PathBuf,
+        config: ProcessFileConfig,
+    ) -> Result<Vec<ProcessFileResult>> {
+        let source: Box<dyn Read + Send> = if input.as_os_str() == STDIN_INPUT {
+            Box::new(std::io::stdin())
+        } else {
+            Box::new(
+                std::fs::File::open(&input)
+                    .map_err(|e| anyhow::anyhow!("Failed to open tar file {}: {}", input.display(), e))?,
+            )
+        };
+        let source_name = if input.as_os_str() == STDIN_INPUT {
+            "standard input".to_string()
+        } else {
+            input.display().to_string()
+        };
+        info!("Importing tar from {} into {}", source_name, output_dir.display());
+        println!("📥 Importing tar: {}", source_name);
+
+        let mut entries = stage_tar_entries(source);
+        let mut results = Vec::new();
+        while let Some(entry) = entries.recv().await {
+            let entry = entry?;
+            let output = Self::output_path(&output_dir, &entry.path);
+            if let Some(parent) = output.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", parent.display(), e))?;
+            }
+
+            println!(
+                "   📄 {} ({} bytes) -> {}",
+                entry.path.display(),
+                entry.size,
+                output.display()
+            );
+            let result = self
+                .process_file
+                .execute(ProcessFileConfig {
+                    input: entry.file.clone(),
+                    output,
+                    ..config.clone()
+                })
+                .await?;
+            results.push(result);
+        }
+
+        println!(
+            "✅ Imported {} entries from {} into {}",
+            results.len(),
+            source_name,
+            output_dir.display()
+        );
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_path_mirrors_the_entry_path() {
+        assert_eq!(
+            ImportTarUseCase::output_path(Path::new("archives"), Path::new("logs/app.log")),
+            Path::new("archives/logs/app.log.adapipe")
+        );
+    }
+}
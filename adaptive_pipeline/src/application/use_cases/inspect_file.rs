@@ -0,0 +1,168 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Inspect .adapipe File Use Case
+//!
+//! Reports what is recorded in an `.adapipe` header without validating or
+//! restoring the payload: the original file, the processing steps, the
+//! correlation ID of the run that wrote it, and the build provenance of the
+//! binary. Intended for forensic review, where the question is *who
+//! produced this archive* rather than *is it intact* (use `validate-file`
+//! for that).
+//!
+//! Files written before provenance was recorded are reported as such rather
+//! than rejected.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::InspectFileUseCase;
+//!
+//! let use_case = InspectFileUseCase::new();
+//!
+//! // Human-readable summary
+//! use_case.execute(file_path, false).await?;
+//!
+//! // Complete header as JSON
+//! use_case.execute(file_path, true).await?;
+//! ```
+
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+
+/// Use case for inspecting the header of an `.adapipe` file.
+///
+/// ## Dependencies
+///
+/// - **BinaryFormatService**: For reading the file header
+pub struct InspectFileUseCase;
+
+impl InspectFileUseCase {
+    /// Creates a new Inspect File use case.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Executes the inspect file use case.
+    ///
+    /// ## Parameters
+    ///
+    /// * `file_path` - Path to the .adapipe file to inspect
+    /// * `json` - If true, print the complete header as pretty-printed JSON
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - File not found
+    /// - Unreadable or corrupt header
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 📦 data.txt.adapipe
+    ///    Original filename: data.txt
+    ///    Original size: 1048576 bytes
+    ///    Original modified: 2025-10-05 14:29:41 UTC
+    ///    Content type: text/plain
+    ///    Format version: 1
+    ///    Pipeline ID: 01H2X3Y4Z5...
+    ///    Processed at: 2025-10-05 14:30:00 UTC
+    ///    Chunks: 1 of 1048576 bytes, indexed
+    ///    Correlation ID: 01K7QH3ZC8V2R6M0XJ4N9T5B1E
+    ///    Processing: Compression (brotli) → Encryption (aes256gcm)
+    ///
+    /// 🏷️  Build provenance
+    ///    Version: 2.0.0
+    ///    Git commit: 4bacd47e0c1d2e3f...
+    ///    Builder: ci@build-01
+    ///    Target: x86_64-unknown-linux-gnu
+    /// ```
+    pub async fn execute(&self, file_path: PathBuf, json: bool) -> Result<()> {
+        info!("Inspecting .adapipe file: {}", file_path.display());
+
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File does not exist: {}", file_path.display()));
+        }
+
+        let header = AdapipeFormat::new()
+            .read_metadata(&file_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read header: {}", e))?;
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&header)?);
+            return Ok(());
+        }
+
+        println!("📦 {}", file_path.display());
+        println!("   Original filename: {}", header.original_filename);
+        println!("   Original size: {} bytes", header.original_size);
+        if let Some(modified) = &header.original_modified {
+            println!("   Original modified: {}", modified.format("%Y-%m-%d %H:%M:%S UTC"));
+        }
+        if let Some(content_type) = &header.content_type {
+            println!("   Content type: {}", content_type);
+        }
+        println!("   Format version: {}", header.format_version);
+        println!("   Pipeline ID: {}", header.pipeline_id);
+        println!(
+            "   Processed at: {}",
+            header.processed_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        // Archives with a chunk index in the footer seek straight to a range
+        let indexed = if header.chunk_index.is_empty() { "" } else { ", indexed" };
+        println!(
+            "   Chunks: {} of {} bytes{}",
+            header.chunk_count, header.chunk_size, indexed
+        );
+        if let Some(correlation_id) = &header.correlation_id {
+            println!("   Correlation ID: {}", correlation_id);
+        }
+        println!("   {}", header.get_processing_summary());
+        if header.is_fips_mode() {
+            println!("   FIPS mode: yes");
+        }
+
+        println!("\n🏷️  Build provenance");
+        match &header.provenance {
+            Some(provenance) => {
+                println!("   Version: {}", provenance.crate_version);
+                println!("   Git commit: {}", provenance.git_commit);
+                println!("   Builder: {}", provenance.builder);
+                println!("   Target: {}", provenance.target);
+            }
+            None => {
+                println!("   Not recorded (written by app version {})", header.app_version);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for InspectFileUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_inspect_missing_file() {
+        let use_case = InspectFileUseCase::new();
+        let result = use_case
+            .execute(PathBuf::from("/nonexistent/file.adapipe"), false)
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,269 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # List Archive Use Case
+//!
+//! Lists the files an `.adapipe` file, or a tree of them written by
+//! `process --recursive`, holds. Only the headers are read, so nothing is
+//! decrypted or decompressed and no passphrase is needed.
+//!
+//! ## Business Rules
+//!
+//! - A single file is one entry, named by its original filename
+//! - In a directory, every `*.adapipe` file at any depth is an entry, named
+//!   by its path relative to the directory without the `.adapipe` suffix, so
+//!   entries read as the tree they were processed from
+//! - Entries are filtered with the same globs as `process --recursive`
+//! - A file in a directory whose header can't be read is reported and left
+//!   out; the listing continues
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ListArchiveUseCase;
+//!
+//! let filter = PathFilter::new(vec!["*.log".into()], Vec::new())?;
+//! ListArchiveUseCase::new().execute(PathBuf::from("archives"), filter).await?;
+//! ```
+
+use anyhow::Result;
+use byte_unit::Byte;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+use adaptive_pipeline_domain::value_objects::PathFilter;
+
+/// One file held by an archive
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveEntry {
+    /// Name the entry is listed and filtered by
+    pub name: String,
+    /// The `.adapipe` file holding it
+    pub archive: PathBuf,
+    pub original_size: u64,
+    /// Size of the `.adapipe` file
+    pub stored_size: u64,
+    /// When the original was last modified, if its header records it
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl ArchiveEntry {
+    /// Stored size as a percentage of the original size
+    pub fn ratio(&self) -> f64 {
+        ratio(self.original_size, self.stored_size)
+    }
+}
+
+fn ratio(original: u64, stored: u64) -> f64 {
+    if original == 0 {
+        100.0
+    } else {
+        stored as f64 / original as f64 * 100.0
+    }
+}
+
+fn size(bytes: u64) -> String {
+    Byte::from_u64(bytes)
+        .get_appropriate_unit(byte_unit::UnitType::Decimal)
+        .to_string()
+}
+
+/// Use case for listing the entries of an archive.
+///
+/// ## Dependencies
+///
+/// - **BinaryFormatService**: For reading the file headers
+pub struct ListArchiveUseCase;
+
+impl ListArchiveUseCase {
+    /// Creates a new List Archive use case.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The entries of `archive` that `filter` selects, in name order
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `archive` doesn't exist, a single file's header can't be
+    /// read or a directory can't be walked.
+    pub async fn list(&self, archive: &Path, filter: &PathFilter) -> Result<Vec<ArchiveEntry>> {
+        if archive.is_file() {
+            let entry = Self::read_entry(archive, None).await?;
+            return Ok(if filter.matches(Path::new(&entry.name)) {
+                vec![entry]
+            } else {
+                Vec::new()
+            });
+        }
+        if !archive.is_dir() {
+            return Err(anyhow::anyhow!("Archive does not exist: {}", archive.display()));
+        }
+
+        let files = {
+            let archive = archive.to_path_buf();
+            tokio::task::spawn_blocking(move || Self::archive_files(&archive))
+                .await
+                .map_err(|e| anyhow::anyhow!("Directory walk failed: {}", e))??
+        };
+        let mut entries = Vec::new();
+        for (path, name) in files {
+            if !filter.matches(Path::new(&name)) {
+                continue;
+            }
+            match Self::read_entry(&path, Some(name)).await {
+                Ok(entry) => entries.push(entry),
+                Err(e) => {
+                    warn!("Skipping {}: {}", path.display(), e);
+                    println!("⚠️  {}: {}", path.display(), e);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Prints the entries of `archive` that `filter` selects, with their
+    /// sizes, ratios and modification times, then the totals.
+    ///
+    /// ## Errors
+    ///
+    /// As [`list`](Self::list).
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 📦 archives (2 entries)
+    ///          SIZE        STORED   RATIO  MODIFIED             ENTRY
+    ///     108.894 KB     12.69 KB   11.7%  2025-10-05 14:30:00  app.log
+    ///      48.894 KB     6.012 KB   12.3%  2025-10-05 14:29:12  svc/worker.log
+    ///     157.788 KB    18.702 KB   11.9%  2 entries
+    /// ```
+    pub async fn execute(&self, archive: PathBuf, filter: PathFilter) -> Result<Vec<ArchiveEntry>> {
+        info!("Listing archive: {}", archive.display());
+        let entries = self.list(&archive, &filter).await?;
+
+        let count = match entries.len() {
+            1 => "1 entry".to_string(),
+            n => format!("{} entries", n),
+        };
+        println!("📦 {} ({})", archive.display(), count);
+        println!(
+            "   {:>11}  {:>11}  {:>6}  {:<19}  ENTRY",
+            "SIZE", "STORED", "RATIO", "MODIFIED"
+        );
+        for entry in &entries {
+            let modified = entry
+                .modified
+                .map_or_else(|| "-".to_string(), |at| at.format("%Y-%m-%d %H:%M:%S").to_string());
+            println!(
+                "   {:>11}  {:>11}  {:>5.1}%  {:<19}  {}",
+                size(entry.original_size),
+                size(entry.stored_size),
+                entry.ratio(),
+                modified,
+                entry.name
+            );
+        }
+        let original: u64 = entries.iter().map(|entry| entry.original_size).sum();
+        let stored: u64 = entries.iter().map(|entry| entry.stored_size).sum();
+        println!(
+            "   {:>11}  {:>11}  {:>5.1}%  {}",
+            size(original),
+            size(stored),
+            ratio(original, stored),
+            count
+        );
+        Ok(entries)
+    }
+
+    /// Every `.adapipe` file under `dir` with its entry name, in path order
+    fn archive_files(dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(dir).follow_links(false).sort_by_file_name() {
+            let entry = entry.map_err(|e| anyhow::anyhow!("Failed to walk {}: {}", dir.display(), e))?;
+            if !entry.file_type().is_file() || entry.path().extension().is_none_or(|ext| ext != "adapipe") {
+                continue;
+            }
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .unwrap_or(entry.path())
+                .with_extension("");
+            files.push((entry.path().to_path_buf(), relative.to_string_lossy().into_owned()));
+        }
+        Ok(files)
+    }
+
+    async fn read_entry(path: &Path, name: Option<String>) -> Result<ArchiveEntry> {
+        let header = AdapipeFormat::new()
+            .read_metadata(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read header: {}", e))?;
+        let stored_size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?
+            .len();
+        Ok(ArchiveEntry {
+            name: name.unwrap_or(header.original_filename),
+            archive: path.to_path_buf(),
+            original_size: header.original_size,
+            stored_size,
+            modified: header.original_modified,
+        })
+    }
+}
+
+impl Default for ListArchiveUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::FileHeader;
+
+    fn write_archive(path: &Path, original_filename: &str, original_size: u64) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let header = FileHeader::new(original_filename.to_string(), original_size, String::new());
+        std::fs::write(path, header.to_footer_bytes().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lists_a_tree_by_relative_name_with_filters() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_archive(&dir.path().join("app.log.adapipe"), "app.log", 1000);
+        write_archive(&dir.path().join("svc/worker.log.adapipe"), "worker.log", 2000);
+        write_archive(&dir.path().join("svc/worker.tmp.adapipe"), "worker.tmp", 10);
+        std::fs::write(dir.path().join("svc/broken.log.adapipe"), b"not an archive").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"not listed").unwrap();
+
+        let filter = PathFilter::new(vec!["*.log".to_string()], Vec::new()).unwrap();
+        let entries = ListArchiveUseCase::new().list(dir.path(), &filter).await.unwrap();
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["app.log", "svc/worker.log"]);
+        assert_eq!(entries[1].original_size, 2000);
+    }
+
+    #[tokio::test]
+    async fn test_lists_a_single_file_by_original_name() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archive = dir.path().join("renamed.adapipe");
+        write_archive(&archive, "report.csv", 500);
+
+        let use_case = ListArchiveUseCase::new();
+        let entries = use_case.list(&archive, &PathFilter::default()).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "report.csv");
+
+        let filter = PathFilter::new(Vec::new(), vec!["*.csv".to_string()]).unwrap();
+        assert!(use_case.list(&archive, &filter).await.unwrap().is_empty());
+    }
+}
@@ -8,15 +8,17 @@
 //! # List Pipelines Use Case
 //!
 //! This module implements the use case for listing all available pipelines in
-//! the system. It retrieves pipeline metadata from the repository and presents
-//! it in a user-friendly format.
+//! the system. It retrieves pipeline summaries from the read model and presents
+//! them in a user-friendly format.
 //!
 //! ## Overview
 //!
 //! The List Pipelines use case provides:
 //!
-//! - **Pipeline Discovery**: Retrieve all active pipelines from the repository
+//! - **Pipeline Discovery**: Retrieve all active pipelines from the read model
 //! - **Summary Information**: Display key metadata for each pipeline
+//! - **Usage Statistics**: Optionally show run counts, bytes processed and
+//!   average compression ratio, to spot pipelines nobody uses any more
 //! - **User-Friendly Output**: Format pipeline information for CLI display
 //! - **Error Handling**: Handle repository access failures gracefully
 //!
@@ -25,16 +27,18 @@
 //! Following Clean Architecture and Domain-Driven Design principles:
 //!
 //! - **Use Case Layer**: Orchestrates the listing workflow
-//! - **Repository Pattern**: Delegates data access to repository interface
+//! - **Query Side**: Reads `PipelineSummaryDto`s through a query handler
+//!   instead of loading pipeline aggregates
 //! - **Dependency Inversion**: Depends on abstractions, not implementations
 //! - **Single Responsibility**: Focused solely on listing pipelines
 //!
 //! ## Business Rules
 //!
 //! - Only active (non-archived) pipelines are displayed
-//! - Pipelines are ordered by creation date (newest first)
+//! - Pipelines are ordered by name
 //! - Empty pipeline list is handled with helpful user message
 //! - All pipeline metadata is displayed: ID, name, status, stages, timestamps
+//! - Usage statistics cover every recorded run, not just the latest
 //!
 //! ## Usage Examples
 //!
@@ -42,43 +46,49 @@
 //! use adaptive_pipeline::application::use_cases::ListPipelinesUseCase;
 //!
 //! let use_case = ListPipelinesUseCase::new(pipeline_repository);
-//! use_case.execute().await?;
+//! use_case.execute(false).await?;
+//!
+//! // Include usage statistics
+//! use_case.execute(true).await?;
 //! ```
 
 use anyhow::Result;
+use byte_unit::Byte;
 use std::sync::Arc;
 use tracing::info;
 
-use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use crate::application::queries::{
+    ListPipelinesQuery, PipelineQueryHandler, PipelineReadModel, PipelineSummaryDto, QueryHandler,
+};
 
 /// Use case for listing all available pipelines.
 ///
-/// This use case retrieves all active pipelines from the repository and
-/// displays them in a user-friendly format. It handles empty result sets
+/// This use case answers a [`ListPipelinesQuery`] from the pipeline read
+/// model and displays the summaries in a user-friendly format. It handles empty result sets
 /// gracefully and provides helpful messages to guide users.
 ///
 /// ## Responsibilities
 ///
-/// - Query repository for all active pipelines
+/// - Query the read model for all active pipeline summaries
 /// - Format pipeline metadata for display
 /// - Handle empty result sets with user guidance
 /// - Report errors during repository access
 ///
 /// ## Dependencies
 ///
-/// - **Pipeline Repository**: For retrieving pipeline data
+/// - **Pipeline Read Model**: For retrieving pipeline summaries
 ///
 /// ## Example
 ///
 /// ```rust,ignore
-/// let use_case = ListPipelinesUseCase::new(pipeline_repository);
+/// let use_case = ListPipelinesUseCase::new(pipeline_repository.clone());
 /// match use_case.execute().await {
 ///     Ok(()) => println!("Pipelines listed successfully"),
 ///     Err(e) => eprintln!("Failed to list pipelines: {}", e),
 /// }
 /// ```
 pub struct ListPipelinesUseCase {
-    pipeline_repository: Arc<SqlitePipelineRepository>,
+    query_handler: PipelineQueryHandler,
 }
 
 impl ListPipelinesUseCase {
@@ -86,13 +96,15 @@ impl ListPipelinesUseCase {
     ///
     /// # Parameters
     ///
-    /// * `pipeline_repository` - Repository for accessing pipeline data
+    /// * `read_model` - Read model for querying pipeline summaries
     ///
     /// # Returns
     ///
     /// A new instance of `ListPipelinesUseCase`
-    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
-        Self { pipeline_repository }
+    pub fn new(read_model: Arc<dyn PipelineReadModel>) -> Self {
+        Self {
+            query_handler: PipelineQueryHandler::new(read_model),
+        }
     }
 
     /// Executes the list pipelines use case.
@@ -100,6 +112,10 @@ impl ListPipelinesUseCase {
     /// Retrieves all active pipelines from the repository and displays them
     /// in a formatted list with key metadata for each pipeline.
     ///
+    /// ## Parameters
+    ///
+    /// * `usage` - Also display usage statistics for each pipeline
+    ///
     /// ## Output Format
     ///
     /// For each pipeline, displays:
@@ -109,6 +125,9 @@ impl ListPipelinesUseCase {
     /// - Number of configured stages
     /// - Creation timestamp
     /// - Last update timestamp
+    /// - Last run timestamp, if the pipeline has processed a file
+    /// - With `usage`: run count, total bytes processed and average
+    ///   compression ratio across all runs
     ///
     /// ## Returns
     ///
@@ -140,38 +159,65 @@ impl ListPipelinesUseCase {
     ///   Stages: 2
     ///   Created: 2025-10-04 10:15:00 UTC
     ///   Updated: 2025-10-05 09:22:00 UTC
+    ///   Last Run: 2025-10-06 02:00:14 UTC
+    ///   Runs: 42
+    ///   Bytes Processed: 12.4 GiB
+    ///   Average Ratio: 0.37
     /// ```
-    pub async fn execute(&self) -> Result<()> {
+    pub async fn execute(&self, usage: bool) -> Result<()> {
         info!("Listing available pipelines:");
 
-        // Query all pipelines from repository
-        let pipelines = self
-            .pipeline_repository
-            .list_all()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to query pipelines: {}", e))?;
+        let summaries = self.summaries().await?;
 
         // Handle empty result set with helpful message
-        if pipelines.is_empty() {
+        if summaries.is_empty() {
             println!("No pipelines found. Use 'pipeline create' to create a new pipeline.");
         } else {
             // Display pipeline summary
-            println!("Found {} pipeline(s):", pipelines.len());
+            println!(
+                "Found {} pipeline(s) in namespace '{}':",
+                summaries.len(),
+                summaries[0].namespace
+            );
             println!();
 
-            for pipeline in pipelines {
-                println!("Pipeline: {}", pipeline.name());
-                println!("  ID: {}", pipeline.id());
-                println!("  Status: {}", pipeline.status());
-                println!("  Stages: {}", pipeline.stages().len());
-                println!("  Created: {}", pipeline.created_at().format("%Y-%m-%d %H:%M:%S UTC"));
-                println!("  Updated: {}", pipeline.updated_at().format("%Y-%m-%d %H:%M:%S UTC"));
+            for summary in summaries {
+                println!("Pipeline: {}", summary.name);
+                println!("  ID: {}", summary.id);
+                println!("  Status: {}", summary.status());
+                println!("  Stages: {}", summary.stage_count);
+                println!("  Created: {}", summary.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+                println!("  Updated: {}", summary.updated_at.format("%Y-%m-%d %H:%M:%S UTC"));
+                match summary.last_executed_at {
+                    Some(last_run) => println!("  Last Run: {}", last_run.format("%Y-%m-%d %H:%M:%S UTC")),
+                    None if usage => println!("  Last Run: never"),
+                    None => {}
+                }
+                if usage {
+                    println!("  Runs: {}", summary.run_count);
+                    println!(
+                        "  Bytes Processed: {:.1}",
+                        Byte::from_u64(summary.total_bytes_processed).get_appropriate_unit(byte_unit::UnitType::Binary)
+                    );
+                    if let Some(ratio) = summary.average_compression_ratio() {
+                        println!("  Average Ratio: {:.2}", ratio);
+                    }
+                }
                 println!();
             }
         }
 
         Ok(())
     }
+
+    /// Summaries of the pipelines in the read model's namespace, for callers
+    /// that render the listing themselves
+    pub async fn summaries(&self) -> Result<Vec<PipelineSummaryDto>> {
+        self.query_handler
+            .handle(ListPipelinesQuery)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query pipelines: {}", e))
+    }
 }
 
 #[cfg(test)]
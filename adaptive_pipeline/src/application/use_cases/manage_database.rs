@@ -0,0 +1,99 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Manage Database Use Case
+//!
+//! Backs the pipeline database up to a file and restores it from one.
+//! Callers are expected to authorize `ProtectedOperation::ManageDatabase`
+//! first; both act on the whole database, every namespace included.
+//!
+//! ## Business Rules
+//!
+//! - Backups are taken online: other processes keep reading and writing
+//!   while the copy is made, and the copy is a consistent snapshot.
+//! - A backup never overwrites an existing file.
+//! - Restore replaces every pipeline, role, usage record and audit record
+//!   with the backup's, so it asks for confirmation unless forced.
+//! - A backup from an older build is brought up to date by applying the
+//!   migrations it lacks; one from a newer build is refused.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ManageDatabaseUseCase;
+//!
+//! let use_case = ManageDatabaseUseCase::new("./pipeline.db");
+//! use_case.backup(Path::new("pipeline-2025-01-07.db")).await?;
+//! use_case.restore(Path::new("pipeline-2025-01-07.db"), true).await?;
+//! ```
+
+use anyhow::Result;
+use std::io::{self, Write};
+use std::path::Path;
+use tracing::info;
+
+use crate::infrastructure::repositories::{schema, sqlite_backup};
+
+/// Use case for backing up and restoring the pipeline database
+pub struct ManageDatabaseUseCase {
+    database_url: String,
+}
+
+impl ManageDatabaseUseCase {
+    /// Creates a new Manage Database use case for the database at
+    /// `database_path`
+    pub fn new(database_path: &str) -> Self {
+        Self {
+            database_url: format!("sqlite://{}", database_path),
+        }
+    }
+
+    /// Copies the database to the new file `path`
+    pub async fn backup(&self, path: &Path) -> Result<()> {
+        let pool = sqlx::SqlitePool::connect_with(schema::connect_options(&self.database_url)?).await?;
+        sqlite_backup::backup_database(&pool, path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to back up database to '{}': {}", path.display(), e))?;
+        pool.close().await;
+
+        println!("✅ Database backed up to {}", path.display());
+        Ok(())
+    }
+
+    /// Replaces the database's contents with the backup at `path`, asking
+    /// for confirmation unless `force` is set
+    pub async fn restore(&self, path: &Path, force: bool) -> Result<()> {
+        if !force {
+            print!(
+                "Replace every pipeline, role and history record with the contents of '{}'? [y/N]: ",
+                path.display()
+            );
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim().to_lowercase();
+            if input != "y" && input != "yes" {
+                println!("Database restore cancelled.");
+                return Ok(());
+            }
+        }
+
+        let pool = sqlx::SqlitePool::connect_with(schema::connect_options(&self.database_url)?).await?;
+        sqlite_backup::restore_database(&pool, path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to restore database from '{}': {}", path.display(), e))?;
+        pool.close().await;
+
+        // Apply any migrations newer than the backup
+        schema::initialize_database(&self.database_url).await?.close().await;
+        info!("Database restored from {}", path.display());
+
+        println!("✅ Database restored from {}", path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Manage Jobs Use Case
+//!
+//! Submits files to the job queue and lists, shows and cancels queued jobs
+//! within one namespace; `daemon` runs them. Callers are expected to
+//! authorize `ProtectedOperation::ProcessFile` (submit, cancel) or
+//! `ProtectedOperation::ViewPipelines` (list, status) in that namespace
+//! first.
+//!
+//! Submitting only records the job, so it returns as soon as the job is
+//! stored; the job ID is printed on the last line of output for scripts.
+//! Cancelling a running job marks it cancelled at once, and the daemon stops
+//! it at its next chunk.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ManageJobsUseCase;
+//!
+//! let use_case = ManageJobsUseCase::new(job_repository, pipeline_repository, Namespace::default());
+//! let job = use_case.submit("compress", input, output, None, "alice").await?;
+//! use_case.status(&job.id().to_string(), false).await?;
+//! ```
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::entities::QueuedJob;
+use adaptive_pipeline_domain::repositories::{JobRepository, PipelineRepository};
+use adaptive_pipeline_domain::value_objects::{JobId, Namespace};
+
+/// Times a cancel is retried when the daemon changes the job meanwhile
+const CANCEL_ATTEMPTS: usize = 3;
+
+/// Use case for submitting, listing, showing and cancelling queued jobs
+pub struct ManageJobsUseCase {
+    job_repository: Arc<dyn JobRepository>,
+    pipeline_repository: Arc<dyn PipelineRepository>,
+    namespace: Namespace,
+}
+
+impl ManageJobsUseCase {
+    /// Creates a new Manage Jobs use case for `namespace`
+    pub fn new(
+        job_repository: Arc<dyn JobRepository>,
+        pipeline_repository: Arc<dyn PipelineRepository>,
+        namespace: Namespace,
+    ) -> Self {
+        Self {
+            job_repository,
+            pipeline_repository,
+            namespace,
+        }
+    }
+
+    /// Queues `input` to be processed through `pipeline` into `output`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pipeline does not exist, or on repository errors.
+    pub async fn submit(
+        &self,
+        pipeline: &str,
+        input: PathBuf,
+        output: PathBuf,
+        workers: Option<usize>,
+        submitted_by: &str,
+    ) -> Result<QueuedJob> {
+        if self.pipeline_repository.find_by_name(pipeline).await?.is_none() {
+            anyhow::bail!("Pipeline '{}' not found", pipeline);
+        }
+
+        let job =
+            QueuedJob::submit(self.namespace.clone(), submitted_by, pipeline, input, output).with_workers(workers);
+        self.job_repository.enqueue(&job).await?;
+
+        println!(
+            "✅ Queued job {} to process {} through '{}' into {}",
+            job.id(),
+            job.input().display(),
+            job.pipeline(),
+            job.output().display()
+        );
+        println!("{}", job.id());
+        Ok(job)
+    }
+
+    /// Prints the jobs in the namespace, oldest first
+    pub async fn list(&self, json: bool) -> Result<()> {
+        let jobs = self.job_repository.list(&self.namespace).await?;
+
+        if json {
+            let jobs: Vec<_> = jobs.iter().map(job_json).collect();
+            println!("{}", serde_json::to_string_pretty(&jobs)?);
+            return Ok(());
+        }
+        if jobs.is_empty() {
+            println!("No jobs in namespace '{}'.", self.namespace);
+            return Ok(());
+        }
+
+        println!("\n=== Jobs ({}) ===", self.namespace);
+        println!(
+            "{:<28} {:<10} {:<20} {:<24} INPUT",
+            "JOB", "STATE", "PIPELINE", "SUBMITTED"
+        );
+        for job in &jobs {
+            println!(
+                "{:<28} {:<10} {:<20} {:<24} {}",
+                job.id().to_string(),
+                job.state().as_str(),
+                job.pipeline(),
+                format_time(job.submitted_at()),
+                job.input().display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Prints job `id`'s state and result
+    ///
+    /// # Errors
+    ///
+    /// Fails if no job in the namespace has the ID, or on repository errors.
+    pub async fn status(&self, id: &str, json: bool) -> Result<QueuedJob> {
+        let job = self.find(id).await?;
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&job_json(&job))?);
+            return Ok(job);
+        }
+
+        println!("Job:        {}", job.id());
+        println!("State:      {}", job.state());
+        println!("Pipeline:   {}", job.pipeline());
+        println!("Input:      {}", job.input().display());
+        println!("Output:     {}", job.output().display());
+        println!(
+            "Submitted:  {} by '{}'",
+            format_time(job.submitted_at()),
+            job.submitted_by()
+        );
+        if let Some(started_at) = job.started_at() {
+            println!("Started:    {}", format_time(started_at));
+        }
+        if let Some(finished_at) = job.finished_at() {
+            println!("Finished:   {}", format_time(finished_at));
+        }
+        if let (Some(input_bytes), Some(output_bytes)) = (job.input_bytes(), job.output_bytes()) {
+            println!("Bytes:      {} in, {} out", input_bytes, output_bytes);
+        }
+        if let Some(error) = job.error() {
+            println!("Error:      {}", error);
+        }
+        Ok(job)
+    }
+
+    /// Cancels job `id`
+    ///
+    /// # Errors
+    ///
+    /// Fails if no job in the namespace has the ID, if the job has already
+    /// finished, or on repository errors.
+    pub async fn cancel(&self, id: &str) -> Result<QueuedJob> {
+        for _ in 0..CANCEL_ATTEMPTS {
+            let mut job = self.find(id).await?;
+            let previous = job.state();
+            job.cancel(Utc::now())?;
+            if self.job_repository.update(&job, previous).await? {
+                println!("✅ Cancelled {} job {}", previous, job.id());
+                return Ok(job);
+            }
+        }
+        anyhow::bail!("Job '{}' kept changing state; try cancelling it again", id)
+    }
+
+    async fn find(&self, id: &str) -> Result<QueuedJob> {
+        let id = JobId::parse(id)?;
+        self.job_repository
+            .find_by_id(&id)
+            .await?
+            .filter(|job| *job.namespace() == self.namespace)
+            .ok_or_else(|| anyhow::anyhow!("Job '{}' not found in namespace '{}'", id, self.namespace))
+    }
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+fn job_json(job: &QueuedJob) -> serde_json::Value {
+    serde_json::json!({
+        "id": job.id(),
+        "namespace": job.namespace().as_str(),
+        "state": job.state(),
+        "pipeline": job.pipeline(),
+        "input": job.input(),
+        "output": job.output(),
+        "workers": job.workers(),
+        "submitted_by": job.submitted_by(),
+        "submitted_at": job.submitted_at(),
+        "started_at": job.started_at(),
+        "finished_at": job.finished_at(),
+        "error": job.error(),
+        "input_bytes": job.input_bytes(),
+        "output_bytes": job.output_bytes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::sqlite_job::SqliteJobRepository;
+    use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+    use adaptive_pipeline_domain::entities::{JobState, Pipeline, PipelineStage, StageConfiguration, StageType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    async fn use_case(dir: &TempDir, namespace: &str) -> ManageJobsUseCase {
+        let path = dir.path().join("jobs.db").to_string_lossy().to_string();
+        let namespace = Namespace::new(namespace).unwrap();
+        let pipelines = SqlitePipelineRepository::new(&path)
+            .await
+            .unwrap()
+            .in_namespace(namespace.clone());
+        let stage = PipelineStage::new(
+            "compression".to_string(),
+            StageType::Compression,
+            StageConfiguration::new(
+                "brotli".to_string(),
+                HashMap::from([("algorithm".to_string(), "brotli".to_string())]),
+                false,
+            ),
+            0,
+        )
+        .unwrap();
+        let pipeline = Pipeline::new("compress".to_string(), vec![stage])
+            .unwrap()
+            .with_namespace(namespace.clone());
+        pipelines.save(&pipeline).await.unwrap();
+        ManageJobsUseCase::new(
+            Arc::new(SqliteJobRepository::new(&path).await.unwrap()),
+            Arc::new(pipelines),
+            namespace,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_submit_checks_the_pipeline_and_queues_the_job() {
+        let dir = TempDir::new().unwrap();
+        let jobs = use_case(&dir, "default").await;
+
+        assert!(jobs
+            .submit("missing", "in.bin".into(), "out.adapipe".into(), None, "alice")
+            .await
+            .is_err());
+        let job = jobs
+            .submit("compress", "in.bin".into(), "out.adapipe".into(), Some(2), "alice")
+            .await
+            .unwrap();
+        let shown = jobs.status(&job.id().to_string(), true).await.unwrap();
+        assert_eq!(shown.state(), JobState::Queued);
+        assert_eq!(shown.workers(), Some(2));
+        assert!(jobs.status("not-a-job", false).await.is_err());
+
+        // Other namespaces cannot see or cancel the job
+        let other = use_case(&dir, "team-a").await;
+        assert!(other.status(&job.id().to_string(), false).await.is_err());
+        assert!(other.cancel(&job.id().to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_unfinished_jobs_only() {
+        let dir = TempDir::new().unwrap();
+        let jobs = use_case(&dir, "default").await;
+        let job = jobs
+            .submit("compress", "in.bin".into(), "out.adapipe".into(), None, "alice")
+            .await
+            .unwrap();
+
+        let cancelled = jobs.cancel(&job.id().to_string()).await.unwrap();
+        assert_eq!(cancelled.state(), JobState::Cancelled);
+        assert!(jobs.cancel(&job.id().to_string()).await.is_err());
+        jobs.list(false).await.unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Manage Roles Use Case
+//!
+//! Lists, assigns and revokes role assignments within one namespace for
+//! role-based access control. Callers are expected to authorize
+//! `ProtectedOperation::ManageRoles` in that namespace first.
+//!
+//! ## Business Rules
+//!
+//! - A namespace is managed by its own admins and by the admins of the
+//!   default namespace.
+//! - Until a role is assigned nobody may do anything protected. `init` makes
+//!   the caller the first admin, a platform admin of the default namespace;
+//!   it is refused once any role is assigned.
+//! - The first assignment in a namespace nobody can manage must be `admin`;
+//!   assigning anything else first would leave nobody able to manage it.
+//! - The last admin able to manage a namespace cannot be revoked or
+//!   downgraded while other assignments depend on it. Revoking every
+//!   assignment locks everyone out until `init` is run again.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ManageRolesUseCase;
+//!
+//! let use_case = ManageRolesUseCase::new(role_repository, Namespace::default());
+//! use_case.init("root".to_string()).await?;
+//! use_case.assign("alice".to_string(), Role::Operator, Some("root".to_string())).await?;
+//! use_case.list().await?;
+//! ```
+
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::info;
+
+use adaptive_pipeline_domain::repositories::RoleRepository;
+use adaptive_pipeline_domain::value_objects::{Namespace, Role, RoleAssignment};
+
+/// Use case for listing, assigning and revoking roles in a namespace
+pub struct ManageRolesUseCase {
+    role_repository: Arc<dyn RoleRepository>,
+    namespace: Namespace,
+}
+
+impl ManageRolesUseCase {
+    /// Creates a new Manage Roles use case for `namespace`
+    pub fn new(role_repository: Arc<dyn RoleRepository>, namespace: Namespace) -> Self {
+        Self {
+            role_repository,
+            namespace,
+        }
+    }
+
+    /// Prints every role assignment in the namespace
+    pub async fn list(&self) -> Result<()> {
+        let assignments = self.role_repository.list_by_namespace(&self.namespace).await?;
+
+        if assignments.is_empty() {
+            if self.role_repository.count().await? == 0 {
+                println!("No roles assigned; run `role init` to become the first admin.");
+            } else {
+                println!("No roles assigned in namespace '{}'.", self.namespace);
+            }
+            return Ok(());
+        }
+
+        println!("\n=== Role Assignments ({}) ===", self.namespace);
+        println!("{:<24} {:<10} {:<24} ASSIGNED", "PRINCIPAL", "ROLE", "ASSIGNED BY");
+        for assignment in &assignments {
+            println!(
+                "{:<24} {:<10} {:<24} {}",
+                assignment.principal(),
+                assignment.role(),
+                assignment.assigned_by().unwrap_or("-"),
+                assignment.assigned_at().format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+        Ok(())
+    }
+
+    /// Makes `principal` the first admin, an admin of the default namespace
+    ///
+    /// # Errors
+    ///
+    /// Fails if any role is already assigned, or on repository errors.
+    pub async fn init(&self, principal: String) -> Result<()> {
+        if self.role_repository.count().await? > 0 {
+            anyhow::bail!(
+                "Invalid role assignment: roles are already assigned; an admin assigns roles with `role assign`"
+            );
+        }
+        let assignment = RoleAssignment::new(Namespace::default(), principal, Role::Admin, None)?;
+
+        self.role_repository.assign(&assignment).await?;
+        info!(principal = assignment.principal(), "First admin assigned");
+        println!(
+            "✅ Assigned role '{}' to '{}' in namespace '{}'",
+            Role::Admin,
+            assignment.principal(),
+            assignment.namespace()
+        );
+        Ok(())
+    }
+
+    /// Assigns `role` to `principal`, replacing any existing assignment
+    ///
+    /// # Errors
+    ///
+    /// Fails if the assignment would leave RBAC without an admin, or on
+    /// repository errors.
+    pub async fn assign(&self, principal: String, role: Role, assigned_by: Option<String>) -> Result<()> {
+        let assignment = RoleAssignment::new(self.namespace.clone(), principal, role, assigned_by)?;
+
+        if role != Role::Admin {
+            let admins = self.admins_other_than(assignment.principal()).await?;
+            if admins == 0 {
+                anyhow::bail!(
+                    "Invalid role assignment: '{}' would leave namespace '{}' without an admin; assign an admin role \
+                     first",
+                    assignment.principal(),
+                    self.namespace
+                );
+            }
+        }
+
+        self.role_repository.assign(&assignment).await?;
+        info!(
+            namespace = %self.namespace,
+            principal = assignment.principal(),
+            role = %role,
+            "Role assigned"
+        );
+        println!(
+            "✅ Assigned role '{}' to '{}' in namespace '{}'",
+            role,
+            assignment.principal(),
+            self.namespace
+        );
+        Ok(())
+    }
+
+    /// Revokes the role of `principal`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the principal has no role, if revoking would leave other
+    /// assignments without an admin, or on repository errors.
+    pub async fn revoke(&self, principal: String) -> Result<()> {
+        let assignment = self
+            .role_repository
+            .find_by_principal(&self.namespace, &principal)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No role assigned to '{}' in namespace '{}' (not found)",
+                    principal,
+                    self.namespace
+                )
+            })?;
+
+        // Default-namespace admins also manage every other namespace
+        let dependents = if self.namespace.is_default() {
+            self.role_repository.count().await?
+        } else {
+            self.role_repository.list_by_namespace(&self.namespace).await?.len()
+        };
+        let remaining = dependents - 1;
+        if assignment.role() == Role::Admin && remaining > 0 && self.admins_other_than(&principal).await? == 0 {
+            anyhow::bail!(
+                "Invalid role revocation: '{}' is the last admin; assign another admin first",
+                principal
+            );
+        }
+
+        self.role_repository.revoke(&self.namespace, &principal).await?;
+        info!(namespace = %self.namespace, principal = %principal, "Role revoked");
+        println!(
+            "✅ Revoked role '{}' from '{}' in namespace '{}'",
+            assignment.role(),
+            principal,
+            self.namespace
+        );
+        Ok(())
+    }
+
+    /// Counts the admins able to manage the namespace, excluding
+    /// `principal`'s assignment in it
+    async fn admins_other_than(&self, principal: &str) -> Result<usize> {
+        let local = self
+            .role_repository
+            .list_by_namespace(&self.namespace)
+            .await?
+            .iter()
+            .filter(|a| a.role() == Role::Admin && a.principal() != principal)
+            .count();
+        if self.namespace.is_default() {
+            return Ok(local);
+        }
+
+        let platform = self
+            .role_repository
+            .list_by_namespace(&Namespace::default())
+            .await?
+            .iter()
+            .filter(|a| a.role() == Role::Admin)
+            .count();
+        Ok(local + platform)
+    }
+}
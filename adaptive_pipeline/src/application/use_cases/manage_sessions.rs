@@ -0,0 +1,113 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Manage Sessions Use Case
+//!
+//! Issues, lists and revokes API sessions within one namespace. Callers are
+//! expected to authorize `ProtectedOperation::ManageSessions` (issue, revoke)
+//! or `ProtectedOperation::ViewPipelines` (list) in that namespace first.
+//!
+//! The token of an issued session is printed once, on the last line of
+//! output, so scripts can capture it with `tail -n 1`; it cannot be shown
+//! again.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ManageSessionsUseCase;
+//!
+//! let use_case = ManageSessionsUseCase::new(session_service, Namespace::default());
+//! use_case.issue(UserId::parse("alice")?, None, "root").await?;
+//! use_case.list().await?;
+//! ```
+
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::application::services::session::SessionService;
+use adaptive_pipeline_domain::value_objects::{Namespace, SessionId, UserId};
+
+/// Use case for issuing, listing and revoking sessions in a namespace
+pub struct ManageSessionsUseCase {
+    session_service: Arc<SessionService>,
+    namespace: Namespace,
+}
+
+impl ManageSessionsUseCase {
+    /// Creates a new Manage Sessions use case for `namespace`
+    pub fn new(session_service: Arc<SessionService>, namespace: Namespace) -> Self {
+        Self {
+            session_service,
+            namespace,
+        }
+    }
+
+    /// Issues a session for `user_id`, lasting `ttl` or the configured
+    /// lifetime, and prints its token
+    pub async fn issue(&self, user_id: UserId, ttl: Option<chrono::Duration>, issued_by: &str) -> Result<()> {
+        let (session, token) = self
+            .session_service
+            .issue(user_id, self.namespace.clone(), ttl, issued_by)
+            .await?;
+
+        println!(
+            "✅ Issued session {} for '{}' in namespace '{}', expires {}",
+            session.id(),
+            session.user_id(),
+            self.namespace,
+            session.expires_at().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        println!("Store this token now; it will not be shown again:");
+        println!("{}", token);
+        Ok(())
+    }
+
+    /// Prints the active sessions in the namespace
+    pub async fn list(&self) -> Result<()> {
+        let sessions = self.session_service.list(&self.namespace).await?;
+
+        if sessions.is_empty() {
+            println!("No active sessions in namespace '{}'.", self.namespace);
+            return Ok(());
+        }
+
+        println!("\n=== Active Sessions ({}) ===", self.namespace);
+        println!("{:<28} {:<24} {:<24} LAST SEEN", "SESSION", "USER", "EXPIRES");
+        for session in &sessions {
+            println!(
+                "{:<28} {:<24} {:<24} {}",
+                session.id(),
+                session.user_id(),
+                session.expires_at().format("%Y-%m-%d %H:%M:%S UTC"),
+                session.last_seen_at().format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+        Ok(())
+    }
+
+    /// Revokes the session `id`
+    ///
+    /// # Errors
+    ///
+    /// Fails if no active session in the namespace has the ID, or on
+    /// repository errors.
+    pub async fn revoke(&self, id: &SessionId, revoked_by: &str) -> Result<()> {
+        let in_namespace = self
+            .session_service
+            .list(&self.namespace)
+            .await?
+            .iter()
+            .any(|session| session.id() == id);
+        if !in_namespace {
+            anyhow::bail!("Active session '{}' not found in namespace '{}'", id, self.namespace);
+        }
+
+        let session = self.session_service.revoke(id, revoked_by).await?;
+        println!("✅ Revoked session {} of '{}'", session.id(), session.user_id());
+        Ok(())
+    }
+}
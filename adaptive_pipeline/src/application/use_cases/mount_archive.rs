@@ -0,0 +1,174 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Mount Archive Use Case
+//!
+//! Mounts an `.adapipe` file as a read-only FUSE filesystem whose root holds
+//! the original file. Reads go through an [`ArchiveRangeReader`], so opening
+//! a large archive is instant and only the chunks actually read are
+//! decrypted and decompressed.
+//!
+//! ## Business Rules
+//!
+//! - The archive is authorized and unlocked as for `restore`
+//! - The file is named by the archive's original filename, confined as
+//!   [`archive_relative_path`] confines it, and carries the original's
+//!   modification time when the header records it
+//! - The mount stays until it is unmounted (`fusermount -u` or `umount`) or
+//!   shutdown is requested, which unmounts it
+//! - Decoded chunks are cached up to the restore use case's chunk cache
+//!   size ([`RestoreFileUseCase::with_chunk_cache_size`]), so hot regions
+//!   are decoded once
+//! - Encrypted chunks are authenticated as they are read; the whole-file
+//!   checksum is not checked, since most reads cover part of the file
+//!
+//! Needs a build with the `fuse` feature on Linux or macOS; other builds
+//! fail with `UnsupportedOperation`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::MountArchiveUseCase;
+//!
+//! let use_case = MountArchiveUseCase::new(restore_use_case).with_shutdown(shutdown);
+//! use_case.execute(PathBuf::from("data.adapipe"), PathBuf::from("/mnt/data"), None).await?;
+//! ```
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::SecretBytes;
+use adaptive_pipeline_domain::PipelineError;
+
+#[allow(unused_imports)] // Linked from the docs in every build
+use crate::application::use_cases::restore_file::{archive_relative_path, ArchiveRangeReader};
+use crate::application::use_cases::RestoreFileUseCase;
+
+/// Use case for mounting an archive as a read-only filesystem.
+///
+/// ## Dependencies
+///
+/// - **RestoreFileUseCase**: Authorizes, unlocks and reads the archive
+pub struct MountArchiveUseCase {
+    restore: RestoreFileUseCase,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+}
+
+impl MountArchiveUseCase {
+    /// Creates a new Mount Archive use case.
+    pub fn new(restore: RestoreFileUseCase) -> Self {
+        Self {
+            restore,
+            shutdown: None,
+        }
+    }
+
+    /// Unmounts once `shutdown` is requested
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Mounts `archive` read-only on the directory `mountpoint` and serves it
+    /// until it is unmounted or shutdown is requested.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the errors of [`RestoreFileUseCase::open_range_reader`],
+    /// `SecurityViolation` for an unsafe original filename, `IoError` if the
+    /// mount fails and `UnsupportedOperation` in builds without FUSE.
+    #[cfg(all(unix, feature = "fuse"))]
+    pub async fn execute(
+        &self,
+        archive: PathBuf,
+        mountpoint: PathBuf,
+        password: Option<SecretBytes>,
+    ) -> Result<(), PipelineError> {
+        use crate::infrastructure::adapters::archive_fs::ArchiveFs;
+        use adaptive_pipeline_domain::value_objects::JobPriority;
+        use std::time::Duration;
+        use tracing::info;
+
+        let reader = Arc::new(
+            self.restore
+                .open_range_reader(&archive, password.as_ref(), JobPriority::Interactive)
+                .await?,
+        );
+        let metadata = reader.metadata();
+        let file_name = archive_relative_path(&metadata.original_filename, false)?
+            .file_name()
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| {
+                PipelineError::SecurityViolation(format!(
+                    "Archive records no usable filename: {:?}",
+                    metadata.original_filename
+                ))
+            })?;
+        let modified = metadata.original_modified.unwrap_or(metadata.processed_at).into();
+
+        let runtime = tokio::runtime::Handle::current();
+        let range_reader = reader.clone();
+        // FUSE requests arrive on the session's own thread, outside the
+        // runtime, so each read blocks on the async restore
+        let filesystem = ArchiveFs::new(
+            file_name.clone(),
+            reader.len(),
+            modified,
+            Box::new(move |offset, len| runtime.block_on(range_reader.read_at(offset, len))),
+        );
+        let session = filesystem.spawn_mount(&mountpoint)?;
+        info!("Mounted {} at {}", archive.display(), mountpoint.display());
+        println!(
+            "📂 Mounted {} read-only at {}",
+            archive.display(),
+            mountpoint.join(&file_name).display()
+        );
+        println!("   Unmount with `umount {}` or stop with Ctrl-C", mountpoint.display());
+
+        let unmounted_externally = loop {
+            if session.guard.is_finished() {
+                break true;
+            }
+            match &self.shutdown {
+                Some(shutdown) => {
+                    tokio::select! {
+                        _ = shutdown.requested() => break false,
+                        _ = tokio::time::sleep(Duration::from_millis(250)) => {}
+                    }
+                }
+                None => tokio::time::sleep(Duration::from_millis(250)).await,
+            }
+        };
+        tokio::task::spawn_blocking(move || {
+            if unmounted_externally {
+                session.join()
+            } else {
+                session.umount_and_join()
+            }
+        })
+        .await
+        .map_err(|e| PipelineError::InternalError(format!("Unmount failed: {}", e)))?
+        .map_err(|e| PipelineError::IoError(format!("Failed to unmount {}: {}", mountpoint.display(), e)))?;
+        println!("📂 Unmounted {}", mountpoint.display());
+        Ok(())
+    }
+
+    /// Fails: this build has no FUSE support
+    #[cfg(not(all(unix, feature = "fuse")))]
+    pub async fn execute(
+        &self,
+        _archive: PathBuf,
+        _mountpoint: PathBuf,
+        _password: Option<SecretBytes>,
+    ) -> Result<(), PipelineError> {
+        let _ = (&self.restore, &self.shutdown);
+        Err(PipelineError::UnsupportedOperation(
+            "mount needs a build with the `fuse` feature on Linux or macOS".to_string(),
+        ))
+    }
+}
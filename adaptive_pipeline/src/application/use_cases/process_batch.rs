@@ -0,0 +1,283 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Process Batch Use Case
+//!
+//! Processes several files through one pipeline, continuing past files that
+//! fail. Failures are written to a [`BatchRetryManifest`] so that
+//! `process-batch --retry-failed` can reprocess only those files.
+//!
+//! ## Business Rules
+//!
+//! - Input `logs/app.log` becomes `<output-dir>/app.log.adapipe`; two inputs
+//!   with the same file name stop the batch before anything is processed
+//! - Each file is processed exactly as `process` would process it, including
+//!   the overwrite policy and quota checks
+//! - Once shutdown is requested, the file in progress stops as `process`
+//!   would and the files not yet started are recorded as failures
+//!
+//! ## Outcomes
+//!
+//! | Files processed     | Result                                       | Exit code         |
+//! |---------------------|----------------------------------------------|-------------------|
+//! | All                 | `Ok`                                         | 0                 |
+//! | Some                | `Partial success: ...`                       | 80                |
+//! | None                | `Batch failed: ...`, quoting the first error | the first error's |
+//! | Stopped by shutdown | `Cancelled: ...`                             | 82                |
+//!
+//! Whenever a file failed, the retry manifest is written before the error is
+//! returned. A retry that processes every remaining file removes the manifest
+//! it retried.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ProcessBatchUseCase;
+//!
+//! let use_case = ProcessBatchUseCase::new(process_file_use_case);
+//! use_case.execute(inputs, PathBuf::from("archives"), None, config).await?;
+//! use_case.retry(PathBuf::from("archives/batch-failures.json"), None, config).await?;
+//! ```
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::application::use_cases::{ProcessFileConfig, ProcessFileResult, ProcessFileUseCase};
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::batch_retry_manifest::RETRY_MANIFEST_FILE;
+use adaptive_pipeline_domain::value_objects::{BatchFailure, BatchRetryManifest};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Use case for processing several files, recording the ones that fail.
+///
+/// ## Dependencies
+///
+/// - **ProcessFileUseCase**: Processes each file
+pub struct ProcessBatchUseCase {
+    process_file: ProcessFileUseCase,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+}
+
+impl ProcessBatchUseCase {
+    /// Creates a new Process Batch use case.
+    pub fn new(process_file: ProcessFileUseCase) -> Self {
+        Self {
+            process_file,
+            shutdown: None,
+        }
+    }
+
+    /// Stops starting new files once `shutdown` is requested; the file in
+    /// progress stops as [`ProcessFileUseCase::with_shutdown`] describes
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Archive path for an input: its file name under `output_dir` with
+    /// `.adapipe` appended
+    pub fn output_path(output_dir: &Path, input: &Path) -> PathBuf {
+        let mut path = output_dir
+            .join(input.file_name().unwrap_or(input.as_os_str()))
+            .into_os_string();
+        path.push(".adapipe");
+        PathBuf::from(path)
+    }
+
+    /// Processes `inputs` into `output_dir` with `config.pipeline`.
+    ///
+    /// `config` supplies the pipeline and processing options; its input and
+    /// output are replaced for each file. Failures are written to
+    /// `failures_path`, by default `<output_dir>/batch-failures.json`.
+    ///
+    /// ## Errors
+    ///
+    /// See the module's outcome table. Inputs that share a file name are
+    /// rejected before any file is processed.
+    pub async fn execute(
+        &self,
+        inputs: Vec<PathBuf>,
+        output_dir: PathBuf,
+        failures_path: Option<PathBuf>,
+        config: ProcessFileConfig,
+    ) -> Result<Vec<ProcessFileResult>> {
+        let mut seen = HashSet::new();
+        let mut items = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let output = Self::output_path(&output_dir, &input);
+            if !seen.insert(output.clone()) {
+                return Err(PipelineError::validation_error(format!(
+                    "Two inputs would be written to {}; process them in separate batches",
+                    output.display()
+                ))
+                .into());
+            }
+            items.push((input, output));
+        }
+
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", output_dir.display(), e))?;
+        let failures_path = failures_path.unwrap_or_else(|| output_dir.join(RETRY_MANIFEST_FILE));
+        self.run(items, failures_path, None, config).await
+    }
+
+    /// Reprocesses the files listed in the retry manifest at `manifest_path`
+    /// with the manifest's pipeline and outputs.
+    ///
+    /// Files that fail again are written to `failures_path`, by default the
+    /// manifest being retried. If none fail, the retried manifest is removed.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if the manifest cannot be read or parsed; otherwise see the
+    /// module's outcome table.
+    pub async fn retry(
+        &self,
+        manifest_path: PathBuf,
+        failures_path: Option<PathBuf>,
+        config: ProcessFileConfig,
+    ) -> Result<Vec<ProcessFileResult>> {
+        let json = tokio::fs::read_to_string(&manifest_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read retry manifest {}: {}", manifest_path.display(), e))?;
+        let manifest = BatchRetryManifest::from_json(&json)?;
+        info!(
+            "Retrying {} failed files from {} with pipeline {}",
+            manifest.failures.len(),
+            manifest_path.display(),
+            manifest.pipeline
+        );
+
+        let items = manifest
+            .failures
+            .into_iter()
+            .map(|failure| (PathBuf::from(failure.input), PathBuf::from(failure.output)))
+            .collect();
+        let config = ProcessFileConfig {
+            pipeline: manifest.pipeline,
+            ..config
+        };
+        let failures_path = failures_path.unwrap_or_else(|| manifest_path.clone());
+        self.run(items, failures_path, Some(manifest_path), config).await
+    }
+
+    async fn run(
+        &self,
+        items: Vec<(PathBuf, PathBuf)>,
+        failures_path: PathBuf,
+        retried_manifest: Option<PathBuf>,
+        config: ProcessFileConfig,
+    ) -> Result<Vec<ProcessFileResult>> {
+        let total = items.len();
+        println!(
+            "📦 Processing batch of {} files with pipeline {}",
+            total, config.pipeline
+        );
+
+        let mut results = Vec::new();
+        let mut failures = Vec::new();
+        let mut cancelled = false;
+        for (input, output) in items {
+            if cancelled || self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+                cancelled = true;
+                failures.push(BatchFailure::new(
+                    input.to_string_lossy(),
+                    output.to_string_lossy(),
+                    PipelineError::cancelled_with_msg("not started before shutdown").to_string(),
+                ));
+                continue;
+            }
+
+            println!("   📄 {} -> {}", input.display(), output.display());
+            match self
+                .process_file
+                .execute(ProcessFileConfig {
+                    input: input.clone(),
+                    output: output.clone(),
+                    ..config.clone()
+                })
+                .await
+            {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    let error = e.to_string();
+                    warn!("Batch file {} failed: {}", input.display(), error);
+                    println!("   ❌ {}: {}", input.display(), error);
+                    cancelled = error.contains("Cancelled:");
+                    failures.push(BatchFailure::new(
+                        input.to_string_lossy(),
+                        output.to_string_lossy(),
+                        error,
+                    ));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            if let Some(retried) = retried_manifest {
+                tokio::fs::remove_file(&retried)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to remove retry manifest {}: {}", retried.display(), e))?;
+            }
+            println!("✅ Processed all {} files", total);
+            return Ok(results);
+        }
+
+        let failed = failures.len();
+        let first_error = failures[0].error.clone();
+        let manifest = BatchRetryManifest::new(config.pipeline.clone()).with_failures(failures);
+        if let Some(parent) = failures_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        tokio::fs::write(&failures_path, manifest.to_json()?)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write retry manifest {}: {}", failures_path.display(), e))?;
+        println!(
+            "⚠️  {} of {} files failed; retry with `process-batch --retry-failed {}`",
+            failed,
+            total,
+            failures_path.display()
+        );
+
+        let summary = format!(
+            "{} of {} files processed; failures written to {}",
+            results.len(),
+            total,
+            failures_path.display()
+        );
+        if cancelled {
+            Err(PipelineError::cancelled_with_msg(summary).into())
+        } else if results.is_empty() {
+            Err(anyhow::anyhow!(
+                "Batch failed: {}; first error: {}",
+                summary,
+                first_error
+            ))
+        } else {
+            Err(PipelineError::partial_success(summary).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_path_uses_the_file_name() {
+        assert_eq!(
+            ProcessBatchUseCase::output_path(Path::new("archives"), Path::new("/var/log/app.log")),
+            Path::new("archives/app.log.adapipe")
+        );
+    }
+}
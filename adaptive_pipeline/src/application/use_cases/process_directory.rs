@@ -0,0 +1,434 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Process Directory Use Case
+//!
+//! Processes every file under a directory that a [`PathFilter`] selects
+//! through one pipeline, several files at a time. This is
+//! `process --recursive`.
+//!
+//! ## Business Rules
+//!
+//! - Input `<input>/logs/app.log` becomes `<output>/logs/app.log.adapipe`;
+//!   the tree is mirrored under the output directory
+//! - Symbolic links are not followed, and an output directory inside the
+//!   input directory is not walked
+//! - Each file is processed exactly as `process` would process it. Files run
+//!   concurrently, and the global resource manager's CPU and I/O tokens
+//!   bound the work of all of them together
+//! - A failed file doesn't stop the others; once shutdown is requested, no
+//!   more files are started
+//!
+//! ## Metrics
+//!
+//! Each file prints its sizes, ratio, time and throughput as it finishes
+//! and is counted in `adaptive_pipeline_directory_files_total{outcome}`.
+//! The run ends with the totals, which are also returned as a
+//! [`DirectoryReport`].
+//!
+//! ## Outcomes
+//!
+//! As for `process-batch`: `Ok` if every file was processed or skipped,
+//! `Partial success: ...` (exit code 80) if some failed, `Batch failed: ...`
+//! if all failed and `Cancelled: ...` (82) after a shutdown. Running the same
+//! command again with `--if-exists skip` retries only the files that have no
+//! output yet.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ProcessDirectoryUseCase;
+//!
+//! let use_case = ProcessDirectoryUseCase::new(process_file_use_case, metrics_service);
+//! let filter = PathFilter::new(vec!["*.log".into()], vec!["*.tmp".into()])?;
+//! let report = use_case.execute(PathBuf::from("logs"), PathBuf::from("archives"), filter, config).await?;
+//! ```
+
+use anyhow::Result;
+use byte_unit::Byte;
+use futures::StreamExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::application::command_bus::{handler_error, CommandHandler};
+use crate::application::commands::ProcessDirectoryCommand;
+use crate::application::use_cases::{ProcessFileConfig, ProcessFileResult, ProcessFileUseCase};
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::runtime::try_resource_manager;
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::PathFilter;
+use adaptive_pipeline_domain::PipelineError;
+
+/// How one file of a directory run ended
+#[derive(Debug, Clone)]
+pub struct DirectoryFile {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub duration: Duration,
+    /// The file's result, or the error it failed with
+    pub outcome: std::result::Result<ProcessFileResult, String>,
+}
+
+impl DirectoryFile {
+    fn processed(&self) -> Option<&ProcessFileResult> {
+        self.outcome.as_ref().ok().filter(|result| !result.skipped)
+    }
+}
+
+/// Per-file outcomes and totals of a directory run
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryReport {
+    /// Every selected file, in path order
+    pub files: Vec<DirectoryFile>,
+    pub elapsed: Duration,
+}
+
+impl DirectoryReport {
+    /// Files written
+    pub fn processed(&self) -> usize {
+        self.files.iter().filter(|file| file.processed().is_some()).count()
+    }
+
+    /// Files left alone because their output existed
+    pub fn skipped(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.outcome.as_ref().is_ok_and(|result| result.skipped))
+            .count()
+    }
+
+    /// Files that failed or were not started before shutdown
+    pub fn failed(&self) -> usize {
+        self.files.iter().filter(|file| file.outcome.is_err()).count()
+    }
+
+    /// Bytes read from the files written
+    pub fn input_bytes(&self) -> u64 {
+        self.files
+            .iter()
+            .filter_map(DirectoryFile::processed)
+            .map(|result| result.input_size_bytes)
+            .sum()
+    }
+
+    /// Bytes of `.adapipe` output written
+    pub fn output_bytes(&self) -> u64 {
+        self.files
+            .iter()
+            .filter_map(DirectoryFile::processed)
+            .map(|result| result.output_size_bytes)
+            .sum()
+    }
+
+    /// Input bytes per second over the whole run
+    pub fn throughput_bytes_per_sec(&self) -> f64 {
+        throughput(self.input_bytes(), self.elapsed)
+    }
+}
+
+fn throughput(bytes: u64, elapsed: Duration) -> f64 {
+    bytes as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
+
+fn size(bytes: u64) -> String {
+    Byte::from_u64(bytes)
+        .get_appropriate_unit(byte_unit::UnitType::Decimal)
+        .to_string()
+}
+
+fn ratio(input: u64, output: u64) -> f64 {
+    if input == 0 {
+        100.0
+    } else {
+        output as f64 / input as f64 * 100.0
+    }
+}
+
+/// Use case for processing the files under a directory.
+///
+/// ## Dependencies
+///
+/// - **ProcessFileUseCase**: Processes each file
+/// - **MetricsService**: Counts the files by outcome
+pub struct ProcessDirectoryUseCase {
+    process_file: ProcessFileUseCase,
+    metrics_service: Arc<MetricsService>,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    files_in_flight: Option<usize>,
+}
+
+impl ProcessDirectoryUseCase {
+    /// Creates a new Process Directory use case.
+    pub fn new(process_file: ProcessFileUseCase, metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            process_file,
+            metrics_service,
+            shutdown: None,
+            files_in_flight: None,
+        }
+    }
+
+    /// Stops starting new files once `shutdown` is requested; the files in
+    /// progress stop as `process` would
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Processes at most `files` files at once instead of one per I/O token
+    /// of the global resource manager
+    pub fn with_files_in_flight(mut self, files: usize) -> Self {
+        self.files_in_flight = Some(files.max(1));
+        self
+    }
+
+    /// The files under `input_dir` that `filter` selects, each with the
+    /// output it is written to, in path order
+    ///
+    /// ## Errors
+    ///
+    /// Fails if the directory cannot be walked.
+    pub fn plan(input_dir: &Path, output_dir: &Path, filter: &PathFilter) -> Result<Vec<(PathBuf, PathBuf)>> {
+        // An output directory inside the input must not pick up its own
+        // archives
+        let output_canonical = output_dir.canonicalize().ok();
+        let mut items = Vec::new();
+        let walker = walkdir::WalkDir::new(input_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| output_canonical.as_deref() != Some(entry.path()));
+        for entry in walker {
+            let entry = entry.map_err(|e| anyhow::anyhow!("Failed to walk {}: {}", input_dir.display(), e))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(input_dir).unwrap_or(entry.path());
+            if !filter.matches(relative) {
+                continue;
+            }
+            let mut output = output_dir.join(relative).into_os_string();
+            output.push(".adapipe");
+            items.push((entry.path().to_path_buf(), PathBuf::from(output)));
+        }
+        Ok(items)
+    }
+
+    /// Processes the files under `input_dir` that `filter` selects into the
+    /// same tree under `output_dir`, with `config.pipeline`.
+    ///
+    /// `config` supplies the pipeline and processing options; its input and
+    /// output are replaced for each file.
+    ///
+    /// ## Errors
+    ///
+    /// See the module's outcomes. Also fails if the output directory cannot
+    /// be created or the input directory walked.
+    pub async fn execute(
+        &self,
+        input_dir: PathBuf,
+        output_dir: PathBuf,
+        filter: PathFilter,
+        config: ProcessFileConfig,
+    ) -> Result<DirectoryReport> {
+        let started = Instant::now();
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", output_dir.display(), e))?;
+        let items = {
+            let (input_dir, output_dir) = (input_dir.clone(), output_dir.clone());
+            tokio::task::spawn_blocking(move || Self::plan(&input_dir, &output_dir, &filter))
+                .await
+                .map_err(|e| anyhow::anyhow!("Directory walk failed: {}", e))??
+        };
+
+        let total = items.len();
+        if total == 0 {
+            println!("📂 No files under {} match the filters", input_dir.display());
+            return Ok(DirectoryReport::default());
+        }
+        let in_flight = self
+            .files_in_flight
+            .or_else(|| try_resource_manager().map(|manager| manager.io_tokens_total()))
+            .unwrap_or(1)
+            .clamp(1, total);
+        println!(
+            "📂 Processing {} files under {} with pipeline {} ({} at a time)",
+            total,
+            input_dir.display(),
+            config.pipeline,
+            in_flight
+        );
+
+        let mut files = Vec::with_capacity(total);
+        let mut runs = futures::stream::iter(items)
+            .map(|(input, output)| self.process(input, output, &config))
+            .buffer_unordered(in_flight);
+        while let Some(file) = runs.next().await {
+            self.report_file(&input_dir, &file);
+            files.push(file);
+        }
+        drop(runs);
+
+        files.sort_by(|a, b| a.input.cmp(&b.input));
+        let report = DirectoryReport {
+            files,
+            elapsed: started.elapsed(),
+        };
+        Self::summarize(&report, &config.pipeline)
+    }
+
+    async fn process(&self, input: PathBuf, output: PathBuf, config: &ProcessFileConfig) -> DirectoryFile {
+        let started = Instant::now();
+        let outcome = if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+            Err(PipelineError::cancelled_with_msg("not started before shutdown").to_string())
+        } else {
+            self.process_one(&input, &output, config)
+                .await
+                .map_err(|e| e.to_string())
+        };
+        DirectoryFile {
+            input,
+            output,
+            duration: started.elapsed(),
+            outcome,
+        }
+    }
+
+    async fn process_one(&self, input: &Path, output: &Path, config: &ProcessFileConfig) -> Result<ProcessFileResult> {
+        if let Some(parent) = output.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        self.process_file
+            .execute(ProcessFileConfig {
+                input: input.to_path_buf(),
+                output: output.to_path_buf(),
+                ..config.clone()
+            })
+            .await
+    }
+
+    fn report_file(&self, input_dir: &Path, file: &DirectoryFile) {
+        let name = file.input.strip_prefix(input_dir).unwrap_or(&file.input).display();
+        match &file.outcome {
+            Ok(result) if result.skipped => {
+                self.metrics_service.increment_directory_files("skipped");
+                println!("   ⏭️  {}: output exists; skipped", name);
+            }
+            Ok(result) => {
+                self.metrics_service.increment_directory_files("processed");
+                println!(
+                    "   ✅ {}: {} -> {} ({:.1}%) in {:.2}s, {}/s",
+                    name,
+                    size(result.input_size_bytes),
+                    size(result.output_size_bytes),
+                    ratio(result.input_size_bytes, result.output_size_bytes),
+                    file.duration.as_secs_f64(),
+                    size(throughput(result.input_size_bytes, file.duration) as u64)
+                );
+            }
+            Err(error) => {
+                self.metrics_service.increment_directory_files("failed");
+                warn!("Directory file {} failed: {}", file.input.display(), error);
+                println!("   ❌ {}: {}", name, error);
+            }
+        }
+    }
+
+    fn summarize(report: &DirectoryReport, pipeline: &str) -> Result<DirectoryReport> {
+        let total = report.files.len();
+        println!(
+            "📊 {} of {} files processed, {} skipped, {} failed: {} -> {} ({:.1}%) in {:.2}s, {}/s",
+            report.processed(),
+            total,
+            report.skipped(),
+            report.failed(),
+            size(report.input_bytes()),
+            size(report.output_bytes()),
+            ratio(report.input_bytes(), report.output_bytes()),
+            report.elapsed.as_secs_f64(),
+            size(report.throughput_bytes_per_sec() as u64)
+        );
+        info!(
+            pipeline = %pipeline,
+            processed = report.processed(),
+            skipped = report.skipped(),
+            failed = report.failed(),
+            input_bytes = report.input_bytes(),
+            output_bytes = report.output_bytes(),
+            elapsed_ms = report.elapsed.as_millis() as u64,
+            "Directory processing finished"
+        );
+
+        let failures: Vec<&String> = report
+            .files
+            .iter()
+            .filter_map(|file| file.outcome.as_ref().err())
+            .collect();
+        let Some(first_error) = failures.first() else {
+            return Ok(report.clone());
+        };
+        let summary = format!("{} of {} files failed", failures.len(), total);
+        if failures.iter().any(|error| error.contains("Cancelled:")) {
+            Err(PipelineError::cancelled_with_msg(summary).into())
+        } else if failures.len() == total {
+            Err(anyhow::anyhow!(
+                "Batch failed: {}; first error: {}",
+                summary,
+                first_error
+            ))
+        } else {
+            Err(PipelineError::partial_success(summary).into())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler<ProcessDirectoryCommand> for ProcessDirectoryUseCase {
+    async fn handle(&self, command: ProcessDirectoryCommand) -> std::result::Result<DirectoryReport, PipelineError> {
+        let ProcessDirectoryCommand {
+            input_dir,
+            output_dir,
+            filter,
+            config,
+        } = command;
+        self.execute(input_dir, output_dir, filter, config)
+            .await
+            .map_err(handler_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_mirrors_the_tree_and_skips_the_output_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = dir.path();
+        for file in ["a.log", "b.tmp", "nested/c.log", "out/old.log"] {
+            let path = input.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"data").unwrap();
+        }
+        let output = input.join("out");
+        let filter = PathFilter::new(vec!["*.log".to_string()], Vec::new()).unwrap();
+
+        let plan = ProcessDirectoryUseCase::plan(input, &output, &filter).unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                (input.join("a.log"), output.join("a.log.adapipe")),
+                (input.join("nested/c.log"), output.join("nested/c.log.adapipe")),
+            ]
+        );
+    }
+}
@@ -21,6 +21,14 @@
 //! - **Metrics Collection**: Comprehensive performance monitoring
 //! - **Error Handling**: Robust error reporting and recovery
 //! - **Progress Tracking**: Real-time processing status
+//! - **Usage Accounting**: Per-namespace job and byte totals for quotas
+//! - **Quota Enforcement**: Jobs over a namespace limit are rejected before
+//!   processing starts
+//! - **Idempotency**: A retried request carrying the idempotency key of a
+//!   completed request returns that request's result without reprocessing
+//! - **Learned Chunk Sizes**: Each run's throughput is recorded per
+//!   pipeline, storage type and chunk size, and later runs favour the
+//!   historically fastest size
 //!
 //! ## Processing Pipeline
 //!
@@ -31,6 +39,34 @@
 //! 5. **Output Generation**: Write .adapipe binary format
 //! 6. **Integrity Verification**: Calculate and verify checksums
 //!
+//! ## Idempotency Keys
+//!
+//! When [`ProcessFileConfig::idempotency_key`] is set and an
+//! `IdempotencyRepository` is configured, the key is looked up in the
+//! repository's namespace before any work is done:
+//!
+//! - **No record**: the file is processed and, on success, the
+//!   [`ProcessFileResult`] is stored under the key
+//! - **Matching record**: the stored result is returned with `replayed` set
+//! - **Record for another request**: the request is rejected, since the same
+//!   key with a different input, output, pipeline or chunk size is a caller
+//!   bug rather than a retry
+//!
+//! Only completed requests are recorded, so a retry of a failed request runs
+//! again, and concurrent requests with the same key may both run (the first
+//! to finish keeps the key).
+//!
+//! ## Security Policy
+//!
+//! A pipeline may declare a minimum security level and required permissions
+//! (see `SecurityPolicy`). The security context set with
+//! [`ProcessFileUseCase::with_security_context`] is checked against them once
+//! the pipeline is loaded; a context that falls short is refused before any
+//! output is written, and a `PermissionDenied` event is recorded in the
+//! pipeline database and passed to the metrics observer. The policy is also
+//! written into the output's header, so restoring it is held to the same
+//! requirements.
+//!
 //! ## Binary Format
 //!
 //! Output files use the `.adapipe` format containing:
@@ -48,27 +84,45 @@
 
 use anyhow::Result;
 use byte_unit::Byte;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
-use tracing::{debug, error, warn};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
 
+use crate::application::command_bus::{handler_error, CommandHandler};
+use crate::application::commands::ProcessFileCommand;
 use crate::application::services::pipeline::ConcurrentPipeline;
+use crate::application::services::pipeline_cache::PipelineCache;
+use crate::application::services::quota::QuotaService;
 use crate::infrastructure::adapters::file_io::TokioFileIO;
 use crate::infrastructure::adapters::{MultiAlgoCompression, MultiAlgoEncryption};
+use crate::infrastructure::config::build_info::build_provenance;
+use crate::infrastructure::config::database_path::resolve_sqlite_path;
 use crate::infrastructure::logging::ObservabilityService;
 use crate::infrastructure::metrics::MetricsService;
 use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
 use crate::infrastructure::runtime::stage_executor::BasicStageExecutor;
-use crate::infrastructure::services::{
-    AdapipeFormat, Base64EncodingService, DebugService, PassThroughService, PiiMaskingService, TeeService,
-};
+use crate::infrastructure::runtime::{try_resource_manager, StageRegistry, StorageType};
+use crate::infrastructure::services::{AdapipeFormat, ManifestSigner};
+use adaptive_pipeline_bootstrap::cli::{PassphrasePolicy, SecureArgParser};
 use adaptive_pipeline_domain::entities::security_context::{Permission, SecurityContext, SecurityLevel};
-use adaptive_pipeline_domain::services::PipelineService;
+use adaptive_pipeline_domain::events::{PermissionDeniedEvent, PipelineEvent};
+use adaptive_pipeline_domain::repositories::{ChunkSizeHistoryRepository, IdempotencyRepository, UsageRepository};
+use adaptive_pipeline_domain::services::file_io_service::{FileIOConfig, FileIOService};
+use adaptive_pipeline_domain::services::{
+    PipelineService, ProcessingObserver, ShutdownSignal, StageService, DEFAULT_GRACE_PERIOD,
+};
 use adaptive_pipeline_domain::value_objects::chunk_size::ChunkSize;
 use adaptive_pipeline_domain::value_objects::worker_count::WorkerCount;
+use adaptive_pipeline_domain::value_objects::{
+    ChunkThroughput, FileMode, IdempotencyKey, IdempotencyRecord, JobPriority, Namespace, OutputResolution,
+    OverwritePolicy, PipelineId, ProcessingManifest, SecretBytes,
+};
+use adaptive_pipeline_domain::PipelineError;
+use adaptive_pipeline_domain::{Pipeline, ProcessingMetrics};
 
 /// Configuration for file processing operations.
 #[derive(Debug, Clone)]
@@ -76,9 +130,60 @@ pub struct ProcessFileConfig {
     pub input: PathBuf,
     pub output: PathBuf,
     pub pipeline: String,
-    pub chunk_size_mb: Option<usize>,
+    pub chunk_size: Option<ChunkSize>,
     pub workers: Option<usize>,
     pub channel_depth: Option<usize>,
+    /// Bytes of chunk data allowed in flight between reader and workers
+    pub inflight_window: Option<usize>,
+    /// Write a detached `<output>.manifest` after successful processing
+    pub write_manifest: bool,
+    /// Ed25519 PKCS#8 key used to sign the manifest
+    pub signing_key: Option<PathBuf>,
+    /// Key that makes retries of this request return the original result
+    pub idempotency_key: Option<IdempotencyKey>,
+    /// Longest any one stage may spend on a chunk
+    pub stage_timeout: Option<std::time::Duration>,
+    /// Longest a chunk may spend in all of its stages
+    pub chunk_timeout: Option<std::time::Duration>,
+    /// Chunks whose stage panicked that may be retried before failing
+    pub max_worker_restarts: u32,
+    /// Read the input and write the output around the OS page cache
+    pub direct_io: bool,
+    /// Compute checksums alongside the stages instead of in separate passes
+    pub checksum_offload: bool,
+    /// What to do if the output file already exists
+    pub overwrite_policy: OverwritePolicy,
+    /// Permissions of the created output file; the umask decides when `None`
+    pub output_mode: Option<FileMode>,
+    /// Priority for shared CPU tokens while other jobs run concurrently
+    pub priority: JobPriority,
+    /// Passphrase the keys of encryption stages are derived from
+    pub password: Option<SecretBytes>,
+    /// Lowest passphrase strength accepted for sensitive pipelines
+    pub passphrase_policy: PassphrasePolicy,
+}
+
+/// Outcome of a successful [`ProcessFileUseCase::execute`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessFileResult {
+    pub output: PathBuf,
+    pub manifest: Option<PathBuf>,
+    pub input_size_bytes: u64,
+    pub output_size_bytes: u64,
+    pub input_checksum: Option<String>,
+    pub output_checksum: Option<String>,
+    pub completed_at: chrono::DateTime<chrono::Utc>,
+    /// Whether this is the stored result of an earlier request with the same
+    /// idempotency key rather than of a new run
+    #[serde(skip)]
+    pub replayed: bool,
+    /// Overwrite policy the output was resolved with
+    #[serde(default)]
+    pub overwrite_policy: OverwritePolicy,
+    /// Whether the policy left an existing output in place and nothing was
+    /// processed
+    #[serde(default)]
+    pub skipped: bool,
 }
 
 /// Use case for processing files through pipelines.
@@ -86,10 +191,21 @@ pub struct ProcessFileConfig {
 /// This is the core use case that orchestrates the entire file processing
 /// workflow, from loading the pipeline configuration through executing all
 /// stages and generating the output .adapipe file.
+#[derive(Clone)]
 pub struct ProcessFileUseCase {
     metrics_service: Arc<MetricsService>,
     observability_service: Arc<ObservabilityService>,
     pipeline_repository: Arc<SqlitePipelineRepository>,
+    pipeline_cache: Option<Arc<PipelineCache>>,
+    usage_repository: Option<Arc<dyn UsageRepository>>,
+    quota_service: Option<Arc<QuotaService>>,
+    idempotency_repository: Option<Arc<dyn IdempotencyRepository>>,
+    chunk_size_history: Option<Arc<dyn ChunkSizeHistoryRepository>>,
+    file_io_service: Option<Arc<dyn FileIOService>>,
+    stage_registry: StageRegistry,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    grace_period: Duration,
+    security_context: SecurityContext,
 }
 
 impl ProcessFileUseCase {
@@ -106,12 +222,124 @@ impl ProcessFileUseCase {
         pipeline_repository: Arc<SqlitePipelineRepository>,
     ) -> Self {
         Self {
+            stage_registry: StageRegistry::builtin(metrics_service.clone()),
             metrics_service,
             observability_service,
             pipeline_repository,
+            pipeline_cache: None,
+            usage_repository: None,
+            quota_service: None,
+            idempotency_repository: None,
+            chunk_size_history: None,
+            file_io_service: None,
+            shutdown: None,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            security_context: SecurityContext::with_permissions(
+                None,
+                vec![
+                    Permission::Read,
+                    Permission::Write,
+                    Permission::Compress,
+                    Permission::Encrypt,
+                ],
+                SecurityLevel::Internal,
+            ),
         }
     }
 
+    /// Creates a builder; dependencies that are not injected are created
+    /// from their defaults when the builder is built
+    pub fn builder() -> ProcessFileUseCaseBuilder {
+        ProcessFileUseCaseBuilder::default()
+    }
+
+    /// Creates the use case with the same default dependencies the CLI
+    /// wires up: a fresh metrics service, configured observability and the
+    /// pipeline repository at [`resolve_sqlite_path`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metrics service or the database cannot be
+    /// initialized.
+    pub async fn with_defaults() -> Result<Self> {
+        Self::builder().build().await
+    }
+
+    /// Looks pipelines up in `pipeline_cache` before the repository, so that
+    /// jobs sharing the cache load each pipeline once per time-to-live
+    pub fn with_pipeline_cache(mut self, pipeline_cache: Arc<PipelineCache>) -> Self {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
+    }
+
+    /// Records each successful job against the pipeline repository's
+    /// namespace in `usage_repository`
+    pub fn with_usage_repository(mut self, usage_repository: Arc<dyn UsageRepository>) -> Self {
+        self.usage_repository = Some(usage_repository);
+        self
+    }
+
+    /// Admits each job through `quota_service` before processing starts
+    pub fn with_quota_service(mut self, quota_service: Arc<QuotaService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// Honors idempotency keys, storing completed requests in
+    /// `idempotency_repository` under the pipeline repository's namespace
+    pub fn with_idempotency_repository(mut self, idempotency_repository: Arc<dyn IdempotencyRepository>) -> Self {
+        self.idempotency_repository = Some(idempotency_repository);
+        self
+    }
+
+    /// Records each run's throughput in `chunk_size_history` and biases the
+    /// adaptive chunk size toward the historically fastest one
+    ///
+    /// An explicit chunk size still overrides the learned one.
+    pub fn with_chunk_size_history(mut self, chunk_size_history: Arc<dyn ChunkSizeHistoryRepository>) -> Self {
+        self.chunk_size_history = Some(chunk_size_history);
+        self
+    }
+
+    /// Reads the input through `file_io_service` instead of the built-in
+    /// Tokio file I/O
+    ///
+    /// The injected service is used as-is, so `direct_io` no longer applies.
+    pub fn with_file_io_service(mut self, file_io_service: Arc<dyn FileIOService>) -> Self {
+        self.file_io_service = Some(file_io_service);
+        self
+    }
+
+    /// Runs stages whose algorithm is `algorithm` with `stage_service`,
+    /// replacing the built-in service for that algorithm if there is one
+    pub fn with_stage_service(mut self, algorithm: impl Into<String>, stage_service: Arc<dyn StageService>) -> Self {
+        self.stage_registry = self.stage_registry.with_service(algorithm, stage_service);
+        self
+    }
+
+    /// Runs stages through `stage_registry` instead of a registry of its own,
+    /// sharing the service instances with everything else built from it
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.stage_registry = stage_registry;
+        self
+    }
+
+    /// Stops the job when `shutdown` is requested: in-flight chunks get
+    /// `grace_period` to finish, then a checkpoint is written next to the
+    /// output and the job fails with `Cancelled`
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>, grace_period: Duration) -> Self {
+        self.shutdown = Some(shutdown);
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Processes on behalf of `security_context`, which must satisfy the
+    /// security policy of each pipeline run
+    pub fn with_security_context(mut self, security_context: SecurityContext) -> Self {
+        self.security_context = security_context;
+        self
+    }
+
     /// Executes the process file use case.
     ///
     /// Processes an input file through a configured pipeline, generating an
@@ -135,25 +363,42 @@ impl ProcessFileUseCase {
     ///
     /// ## Returns
     ///
-    /// - `Ok(())` - File processed successfully
+    /// - `Ok(ProcessFileResult)` - File processed successfully, or the stored
+    ///   result of an earlier request with the same idempotency key
     /// - `Err(anyhow::Error)` - Processing failed
     ///
     /// ## Errors
     ///
     /// Returns errors for:
+    /// - Idempotency key already used for a different request
     /// - Input file not found or unreadable
     /// - Pipeline not found in repository
+    /// - Security context not satisfying the pipeline's security policy
     /// - Processing stage failures
     /// - Output file write errors
     /// - Insufficient permissions
-    pub async fn execute(&self, config: ProcessFileConfig) -> Result<()> {
+    pub async fn execute(&self, config: ProcessFileConfig) -> Result<ProcessFileResult> {
         let ProcessFileConfig {
             input,
             output,
             pipeline,
-            chunk_size_mb,
+            chunk_size,
             workers,
             channel_depth,
+            inflight_window,
+            write_manifest,
+            signing_key,
+            idempotency_key,
+            stage_timeout,
+            chunk_timeout,
+            max_worker_restarts,
+            direct_io,
+            checksum_offload,
+            overwrite_policy,
+            output_mode,
+            priority,
+            password,
+            passphrase_policy,
         } = config;
 
         // Ensure output file has .adapipe extension
@@ -163,6 +408,46 @@ impl ProcessFileUseCase {
             output
         };
 
+        // Replay a completed request with the same idempotency key before
+        // doing any work
+        let idempotency = idempotency_key.map(|key| {
+            let fingerprint = Self::request_fingerprint(&input, &output, &pipeline, chunk_size);
+            (key, fingerprint)
+        });
+        if let Some((key, fingerprint)) = &idempotency {
+            if let Some(result) = self.find_replay(key, fingerprint).await? {
+                return Ok(result);
+            }
+        }
+
+        // Apply the overwrite policy to an existing output
+        let output = match overwrite_policy.resolve(&output, &input)? {
+            OutputResolution::Skip => {
+                println!(
+                    "⏭️  Skipped: {} exists (overwrite policy: {})",
+                    output.display(),
+                    overwrite_policy
+                );
+                return Ok(ProcessFileResult {
+                    input_size_bytes: fs::metadata(&input)?.len(),
+                    output_size_bytes: fs::metadata(&output)?.len(),
+                    output,
+                    manifest: None,
+                    input_checksum: None,
+                    output_checksum: None,
+                    completed_at: chrono::Utc::now(),
+                    replayed: false,
+                    overwrite_policy,
+                    skipped: true,
+                });
+            }
+            OutputResolution::Renamed(renamed) => {
+                println!("↪️  {} exists; writing {}", output.display(), renamed.display());
+                renamed
+            }
+            OutputResolution::New(path) | OutputResolution::Replace(path) => path,
+        };
+
         debug!(
             "Processing file: {} -> {} (.adapipe format)",
             input.display(),
@@ -181,45 +466,43 @@ impl ProcessFileUseCase {
                 .to_string()
         );
 
-        // Determine chunk size: user override with validation or adaptive
-        let (actual_chunk_size_bytes, chunk_size_source) = Self::determine_chunk_size(actual_input_size, chunk_size_mb);
+        // Load the signing key up front so a bad key fails before any work
+        let manifest_signer = match &signing_key {
+            Some(path) => Some(ManifestSigner::from_key_file(path)?),
+            None => None,
+        };
 
-        debug!(
-            "Final chunk size: {} bytes ({}) - {}",
-            actual_chunk_size_bytes,
-            Byte::from_u128(actual_chunk_size_bytes as u128)
-                .unwrap_or_default()
-                .get_appropriate_unit(byte_unit::UnitType::Decimal)
-                .to_string(),
-            chunk_size_source
-        );
+        // Admit the job against namespace quotas; the permit is held until
+        // processing finishes
+        let _permit = match &self.quota_service {
+            Some(quota_service) => Some(
+                quota_service
+                    .admit(self.pipeline_repository.namespace(), actual_input_size)
+                    .await?,
+            ),
+            None => None,
+        };
 
         if let Some(worker_count) = workers {
             debug!("Using {} workers", worker_count);
         }
 
-        // Create security context
-        let security_context = SecurityContext::with_permissions(
-            None,
-            vec![
-                Permission::Read,
-                Permission::Write,
-                Permission::Compress,
-                Permission::Encrypt,
-            ],
-            SecurityLevel::Internal,
-        );
-
+        let security_context = self.security_context.clone();
         debug!("Security context: {:?}", security_context.security_level());
 
         // Load pipeline from repository
         debug!("Loading pipeline configuration...");
-        let pipeline_entity = self
-            .pipeline_repository
-            .find_by_name(&pipeline)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to query pipeline: {}", e))?
-            .ok_or_else(|| anyhow::anyhow!("Pipeline '{}' not found", pipeline))?;
+        let repository = &self.pipeline_repository;
+        let pipeline_entity = match &self.pipeline_cache {
+            Some(cache) => {
+                cache
+                    .get_or_load(repository.namespace(), &pipeline, || repository.find_by_name(&pipeline))
+                    .await
+            }
+            None => repository.find_by_name(&pipeline).await,
+        }
+        .map_err(|e| anyhow::anyhow!("Failed to query pipeline: {}", e))?
+        .ok_or_else(|| anyhow::anyhow!("Pipeline '{}' not found", pipeline))?;
 
         debug!(
             "Loaded pipeline '{}' with {} stages",
@@ -230,14 +513,65 @@ impl ProcessFileUseCase {
             debug!("  - Stage: {} (type: {:?})", stage.name(), stage.stage_type());
         }
 
+        // Refuse a security context the pipeline's policy does not admit
+        if let Err(denial) = pipeline_entity.security_policy()?.check(&security_context) {
+            self.record_permission_denied(&pipeline_entity, &denial).await;
+            return Err(denial.into());
+        }
+
+        // Pipelines for sensitive data refuse easily guessed passphrases,
+        // including ones built from the pipeline or file name
+        if let Some(password) = &password {
+            let passphrase = std::str::from_utf8(password.expose_secret())
+                .map_err(|_| anyhow::anyhow!("Passphrase is not valid UTF-8"))?;
+            let file_name = input.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+            SecureArgParser::validate_passphrase(
+                passphrase,
+                pipeline_entity.security_policy()?.minimum_level(),
+                &passphrase_policy,
+                &[pipeline_entity.name(), file_name],
+            )?;
+        }
+
+        // Determine chunk size: user override with validation, the size the
+        // pipeline is pinned to, or adaptive biased by this pipeline's
+        // throughput history on this storage
+        let storage_type = Self::storage_type_label();
+        let chunk_history = self.chunk_history(pipeline_entity.id(), &storage_type).await;
+        let (actual_chunk_size_bytes, chunk_size_source) = Self::determine_chunk_size(
+            actual_input_size,
+            chunk_size,
+            pipeline_entity.chunk_size()?,
+            &chunk_history,
+        );
+
+        debug!(
+            "Final chunk size: {} bytes ({}) - {}",
+            actual_chunk_size_bytes,
+            Byte::from_u128(actual_chunk_size_bytes as u128)
+                .unwrap_or_default()
+                .get_appropriate_unit(byte_unit::UnitType::Decimal)
+                .to_string(),
+            chunk_size_source
+        );
+
         // Create and configure pipeline service
-        let pipeline_service = Self::create_pipeline_service(&self.metrics_service, &self.pipeline_repository);
+        let pipeline_service = Self::create_pipeline_service(
+            &self.metrics_service,
+            &self.pipeline_repository,
+            stage_timeout,
+            chunk_timeout,
+            direct_io,
+            self.file_io_service.clone(),
+            &self.stage_registry,
+        );
 
         // Track active pipeline processing
         self.metrics_service.increment_active_pipelines();
         let operation_tracker = self.observability_service.start_operation("file_processing").await;
 
         let processing_start = Instant::now();
+        let started_at = chrono::Utc::now();
 
         // Create metrics observer
         let metrics_observer = Arc::new(crate::infrastructure::metrics::MetricsObserver::new(
@@ -248,8 +582,11 @@ impl ProcessFileUseCase {
         let mut process_context = adaptive_pipeline_domain::services::pipeline_service::ProcessFileContext::new(
             pipeline_entity.id().clone(),
             security_context,
-        );
+        )
+        .with_pipeline(pipeline_entity.clone());
 
+        // A run's own worker count wins over the pipeline's
+        let workers = workers.or(pipeline_entity.worker_count()?.map(|workers| workers.count()));
         if let Some(w) = workers {
             process_context = process_context.with_workers(w);
         }
@@ -258,7 +595,36 @@ impl ProcessFileUseCase {
             process_context = process_context.with_channel_depth(depth);
         }
 
-        process_context = process_context.with_observer(metrics_observer);
+        if let Some(bytes) = inflight_window {
+            process_context = process_context.with_inflight_window(bytes);
+        }
+
+        process_context = process_context.with_chunk_size(ChunkSize::new(actual_chunk_size_bytes)?);
+
+        process_context = process_context.with_worker_restarts(max_worker_restarts);
+
+        if checksum_offload {
+            process_context = process_context.with_checksum_offload();
+        }
+
+        if let Some(mode) = output_mode {
+            process_context = process_context.with_output_mode(mode);
+        }
+
+        if let Some(password) = password {
+            process_context = process_context.with_password(password);
+        }
+
+        process_context = process_context.with_priority(priority).with_observer(metrics_observer);
+
+        if let Some(shutdown) = &self.shutdown {
+            process_context = process_context.with_shutdown(shutdown.clone(), self.grace_period);
+        }
+
+        // The output's header names the run, like the events recorded for it
+        if let Some(correlation_id) = self.pipeline_repository.correlation_id() {
+            process_context = process_context.with_correlation_id(correlation_id.clone());
+        }
 
         // Process the file through the pipeline
         let processing_result = pipeline_service
@@ -286,6 +652,36 @@ impl ProcessFileUseCase {
 
                 self.observability_service.record_processing_metrics(&metrics).await;
                 operation_tracker.complete_with_metrics(&metrics).await;
+                self.record_usage(actual_input_size, metrics.output_file_size_bytes())
+                    .await;
+                self.record_chunk_throughput(
+                    pipeline_entity.id(),
+                    &storage_type,
+                    actual_chunk_size_bytes,
+                    actual_input_size,
+                    total_processing_duration,
+                )
+                .await;
+                if let Err(e) = self
+                    .pipeline_repository
+                    .record_execution(pipeline_entity.id().clone(), &metrics)
+                    .await
+                {
+                    warn!(pipeline = %pipeline_entity.name(), "Failed to record pipeline execution: {}", e);
+                }
+
+                let manifest_path = if write_manifest || manifest_signer.is_some() {
+                    Some(Self::write_manifest(
+                        &input,
+                        &output,
+                        &pipeline_entity,
+                        &metrics,
+                        started_at,
+                        manifest_signer.as_ref(),
+                    )?)
+                } else {
+                    None
+                };
 
                 // Display processing summary
                 Self::display_processing_summary(
@@ -299,8 +695,27 @@ impl ProcessFileUseCase {
                     chunk_size_source,
                     workers,
                 );
+                if let Some(path) = &manifest_path {
+                    let signed = if manifest_signer.is_some() { " (signed)" } else { "" };
+                    println!("📜 Manifest: {}{}", path.display(), signed);
+                }
 
-                Ok(())
+                let result = ProcessFileResult {
+                    output,
+                    manifest: manifest_path,
+                    input_size_bytes: actual_input_size,
+                    output_size_bytes: metrics.output_file_size_bytes(),
+                    input_checksum: metrics.input_file_checksum().clone(),
+                    output_checksum: metrics.output_file_checksum().clone(),
+                    completed_at: chrono::Utc::now(),
+                    replayed: false,
+                    overwrite_policy,
+                    skipped: false,
+                };
+                if let Some((key, fingerprint)) = idempotency {
+                    self.remember(key, fingerprint, &result).await;
+                }
+                Ok(result)
             }
             Err(e) => {
                 Self::display_processing_error(&input, &output, &e);
@@ -310,22 +725,271 @@ impl ProcessFileUseCase {
         }
     }
 
-    /// Determines optimal chunk size for file processing.
-    fn determine_chunk_size(file_size: u64, user_chunk_mb: Option<usize>) -> (usize, &'static str) {
-        let optimal_chunk_size = ChunkSize::optimal_for_file_size(file_size);
+    /// Emits a `PermissionDenied` event for a refused run: to the metrics
+    /// observer, and to the pipeline database's event log
+    ///
+    /// Failing to store the event is logged, not returned, so the denial
+    /// itself is still reported to the caller.
+    async fn record_permission_denied(&self, pipeline: &Pipeline, denial: &PipelineError) {
+        let event = PermissionDeniedEvent::new(
+            pipeline.id().as_uuid(),
+            &self.security_context,
+            "process",
+            format!("pipeline '{}': {}", pipeline.name(), denial),
+        );
+        crate::infrastructure::metrics::MetricsObserver::new(self.metrics_service.clone())
+            .on_permission_denied(&event)
+            .await;
+
+        let stored = async {
+            let mut work = self.pipeline_repository.begin().await?;
+            work.record_event(&PipelineEvent::PermissionDenied(event)).await?;
+            work.commit().await
+        };
+        if let Err(e) = stored.await {
+            warn!("Failed to record permission denied event: {}", e);
+        }
+    }
+
+    /// Writes the completion metrics of a successful job to
+    /// `<output>.manifest`, signed when a signer is configured
+    fn write_manifest(
+        input: &Path,
+        output: &Path,
+        pipeline: &Pipeline,
+        metrics: &ProcessingMetrics,
+        started_at: chrono::DateTime<chrono::Utc>,
+        signer: Option<&ManifestSigner>,
+    ) -> Result<PathBuf> {
+        let file_name = |path: &Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let input_checksum = metrics
+            .input_file_checksum()
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Processing did not record an input checksum"))?;
+        let output_checksum = metrics
+            .output_file_checksum()
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Processing did not record an output checksum"))?;
+
+        let manifest = ProcessingManifest::new(
+            file_name(input),
+            metrics.input_file_size_bytes(),
+            input_checksum,
+            file_name(output),
+            metrics.output_file_size_bytes(),
+            output_checksum,
+        )
+        .with_pipeline(
+            pipeline.id().to_string(),
+            pipeline.name(),
+            pipeline.stages().iter().map(|stage| stage.name().to_string()).collect(),
+        )
+        .with_input_checksum_algorithm(pipeline.checksum_algorithm()?)
+        .with_timestamps(started_at, chrono::Utc::now())
+        .with_provenance(build_provenance());
+        let manifest = match metrics.concurrency() {
+            Some(concurrency) => manifest.with_concurrency(concurrency.clone()),
+            None => manifest,
+        };
+
+        let manifest = match signer {
+            Some(signer) => signer.sign(manifest)?,
+            None => manifest,
+        };
+
+        let path = ProcessingManifest::path_for(output);
+        fs::write(&path, manifest.to_json()?)?;
+        debug!("Wrote processing manifest: {}", path.display());
+        Ok(path)
+    }
+
+    /// Identifies a request by the parameters that determine its output, so
+    /// a retry can be told apart from a reused idempotency key
+    fn request_fingerprint(input: &Path, output: &Path, pipeline: &str, chunk_size: Option<ChunkSize>) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            input.to_string_lossy().as_ref(),
+            output.to_string_lossy().as_ref(),
+            pipeline,
+            &chunk_size.map(|size| size.bytes().to_string()).unwrap_or_default(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0u8]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Returns the stored result of the completed request bound to `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `key` is bound to a request with a
+    /// different `fingerprint`.
+    async fn find_replay(&self, key: &IdempotencyKey, fingerprint: &str) -> Result<Option<ProcessFileResult>> {
+        let Some(idempotency_repository) = &self.idempotency_repository else {
+            warn!(key = %key, "No idempotency store configured; ignoring idempotency key");
+            return Ok(None);
+        };
+        let namespace = self.pipeline_repository.namespace();
+        let Some(record) = idempotency_repository.find(namespace, key).await? else {
+            return Ok(None);
+        };
+
+        if !record.matches(fingerprint) {
+            return Err(PipelineError::validation_error(format!(
+                "Idempotency key '{}' was already used for a different request",
+                key
+            ))
+            .into());
+        }
+
+        let mut result: ProcessFileResult = serde_json::from_str(record.result()).map_err(|e| {
+            PipelineError::SerializationError(format!("Invalid stored result for idempotency key '{}': {}", key, e))
+        })?;
+        result.replayed = true;
+
+        info!(namespace = %namespace, key = %key, "Replaying completed request");
+        println!(
+            "♻️  Idempotency key '{}' matches a request completed at {}; not reprocessing",
+            key,
+            record.created_at().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        println!("   Output: {}", result.output.display());
+        Ok(Some(result))
+    }
+
+    /// Binds `key` to a completed request
+    ///
+    /// The output has already been written, so storage failures are logged
+    /// rather than failing the job.
+    async fn remember(&self, key: IdempotencyKey, fingerprint: String, result: &ProcessFileResult) {
+        let Some(idempotency_repository) = &self.idempotency_repository else {
+            return;
+        };
+        let namespace = self.pipeline_repository.namespace().clone();
+        let stored = match serde_json::to_string(result) {
+            Ok(json) => {
+                let record = IdempotencyRecord::new(namespace.clone(), key.clone(), fingerprint, json);
+                idempotency_repository.save(&record).await
+            }
+            Err(e) => Err(PipelineError::SerializationError(e.to_string())),
+        };
+        if let Err(e) = stored {
+            warn!(namespace = %namespace, key = %key, "Failed to record idempotency key: {}", e);
+        }
+    }
+
+    /// Adds a completed job to today's usage totals for the namespace
+    ///
+    /// The output has already been written, so accounting failures are
+    /// logged rather than failing the job.
+    async fn record_usage(&self, input_bytes: u64, output_bytes: u64) {
+        let Some(usage_repository) = &self.usage_repository else {
+            return;
+        };
+        let namespace = self.pipeline_repository.namespace();
+        let today = chrono::Utc::now().date_naive();
+        if let Err(e) = usage_repository
+            .record_job(namespace, today, input_bytes, output_bytes)
+            .await
+        {
+            warn!(namespace = %namespace, "Failed to record namespace usage: {}", e);
+        }
+    }
+
+    /// Label under which chunk-size history is kept for the storage the
+    /// resource manager was configured for
+    pub(crate) fn storage_type_label() -> String {
+        try_resource_manager()
+            .map(|rm| rm.storage_type())
+            .unwrap_or(StorageType::Auto)
+            .to_string()
+    }
+
+    /// Loads the pipeline's chunk-size throughput history on `storage_type`
+    ///
+    /// History only tunes the choice, so a failed lookup is logged and the
+    /// static heuristic is used.
+    async fn chunk_history(&self, pipeline_id: &PipelineId, storage_type: &str) -> Vec<ChunkThroughput> {
+        let Some(chunk_size_history) = &self.chunk_size_history else {
+            return Vec::new();
+        };
+        chunk_size_history
+            .history(pipeline_id, storage_type)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(pipeline_id = %pipeline_id, "Failed to load chunk size history: {}", e);
+                Vec::new()
+            })
+    }
+
+    /// Adds a completed run's throughput to the pipeline's chunk-size
+    /// history
+    ///
+    /// Empty inputs measure only overhead and are not recorded; storage
+    /// failures are logged rather than failing the job.
+    async fn record_chunk_throughput(
+        &self,
+        pipeline_id: &PipelineId,
+        storage_type: &str,
+        chunk_size_bytes: usize,
+        input_bytes: u64,
+        duration: std::time::Duration,
+    ) {
+        let Some(chunk_size_history) = &self.chunk_size_history else {
+            return;
+        };
+        if input_bytes == 0 {
+            return;
+        }
+        let recorded = match ChunkSize::new(chunk_size_bytes) {
+            Ok(chunk_size) => {
+                chunk_size_history
+                    .record_run(pipeline_id, storage_type, chunk_size, input_bytes, duration)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = recorded {
+            warn!(pipeline_id = %pipeline_id, "Failed to record chunk size history: {}", e);
+        }
+    }
+
+    /// Determines the chunk size for file processing.
+    ///
+    /// A valid user override wins, then a valid size the pipeline is
+    /// `pinned` to; otherwise the adaptive size is biased by `history` (see
+    /// [`ChunkSize::learned_for_file_size`]).
+    pub(crate) fn determine_chunk_size(
+        file_size: u64,
+        user_chunk_size: Option<ChunkSize>,
+        pinned_chunk_size: Option<ChunkSize>,
+        history: &[ChunkThroughput],
+    ) -> (usize, &'static str) {
+        let optimal_chunk_size = ChunkSize::learned_for_file_size(file_size, history);
+        let adaptive_source = if optimal_chunk_size.is_optimal_for_file(file_size) {
+            "adaptive"
+        } else {
+            "learned"
+        };
 
-        if let Some(user_chunk_mb) = user_chunk_mb {
-            match ChunkSize::validate_user_input(user_chunk_mb, file_size) {
-                Ok(validated_bytes) => {
-                    if validated_bytes == optimal_chunk_size.bytes() {
-                        debug!("User-specified chunk size {} MB matches adaptive choice", user_chunk_mb);
-                        (validated_bytes, "adaptive")
+        if let Some(user_chunk_size) = user_chunk_size {
+            match user_chunk_size.validate_for_file_size(file_size) {
+                Ok(()) => {
+                    if user_chunk_size == optimal_chunk_size {
+                        debug!("User-specified chunk size {} matches adaptive choice", user_chunk_size);
+                        (user_chunk_size.bytes(), adaptive_source)
                     } else {
                         debug!(
-                            "Using user-specified chunk size: {} MB ({} bytes)",
-                            user_chunk_mb, validated_bytes
+                            "Using user-specified chunk size: {} ({} bytes)",
+                            user_chunk_size,
+                            user_chunk_size.bytes()
                         );
-                        (validated_bytes, "user-override")
+                        (user_chunk_size.bytes(), "user-override")
                     }
                 }
                 Err(warning) => {
@@ -338,88 +1002,66 @@ impl ProcessFileUseCase {
                 }
             }
         } else {
-            debug!("Using adaptive chunk size: {} bytes", optimal_chunk_size.bytes());
-            (optimal_chunk_size.bytes(), "adaptive")
+            if let Some(pinned_chunk_size) = pinned_chunk_size {
+                match pinned_chunk_size.validate_for_file_size(file_size) {
+                    Ok(()) => {
+                        debug!("Using pipeline chunk size: {} bytes", pinned_chunk_size.bytes());
+                        return (pinned_chunk_size.bytes(), "configured");
+                    }
+                    Err(reason) => debug!("Pipeline chunk size not used: {}", reason),
+                }
+            }
+            debug!(
+                "Using {} chunk size: {} bytes",
+                adaptive_source,
+                optimal_chunk_size.bytes()
+            );
+            (optimal_chunk_size.bytes(), adaptive_source)
         }
     }
 
     /// Creates and configures the pipeline service with all required
     /// dependencies.
-    fn create_pipeline_service(
+    ///
+    /// Without `file_io_service` the input is read with Tokio file I/O,
+    /// honoring `direct_io`. Stages run through the shared `stage_registry`,
+    /// so no stage services are created per job.
+    pub(crate) fn create_pipeline_service(
         metrics_service: &Arc<MetricsService>,
         pipeline_repository: &Arc<SqlitePipelineRepository>,
+        stage_timeout: Option<std::time::Duration>,
+        chunk_timeout: Option<std::time::Duration>,
+        direct_io: bool,
+        file_io_service: Option<Arc<dyn FileIOService>>,
+        stage_registry: &StageRegistry,
     ) -> ConcurrentPipeline {
+        let file_io_service = file_io_service.unwrap_or_else(|| {
+            Arc::new(TokioFileIO::new(FileIOConfig {
+                direct_io,
+                ..Default::default()
+            }))
+        });
+
         // Create services
         let compression_service = Arc::new(MultiAlgoCompression::new());
         let encryption_service = Arc::new(MultiAlgoEncryption::new());
-        let file_io_service = Arc::new(TokioFileIO::new(Default::default()));
         let binary_format_service = Arc::new(AdapipeFormat::new());
 
-        // Build stage service registry
-        let mut stage_services: HashMap<String, Arc<dyn adaptive_pipeline_domain::services::StageService>> =
-            HashMap::new();
-
-        // Register compression algorithms
-        stage_services.insert(
-            "brotli".to_string(),
-            compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "gzip".to_string(),
-            compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "zstd".to_string(),
-            compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "lz4".to_string(),
-            compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-
-        // Register encryption algorithms
-        stage_services.insert(
-            "aes256gcm".to_string(),
-            encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "aes128gcm".to_string(),
-            encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "chacha20poly1305".to_string(),
-            encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-
-        // Register transform stages
-        stage_services.insert(
-            "base64".to_string(),
-            Arc::new(Base64EncodingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "pii_masking".to_string(),
-            Arc::new(PiiMaskingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "tee".to_string(),
-            Arc::new(TeeService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "passthrough".to_string(),
-            Arc::new(PassThroughService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
-        stage_services.insert(
-            "debug".to_string(),
-            Arc::new(DebugService::new(metrics_service.clone()))
-                as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-        );
+        let mut stage_executor =
+            BasicStageExecutor::from_registry(stage_registry.clone()).with_metrics_service(metrics_service.clone());
+        if let Some(timeout) = stage_timeout {
+            stage_executor = stage_executor.with_stage_timeout(timeout);
+        }
+        if let Some(timeout) = chunk_timeout {
+            stage_executor = stage_executor.with_chunk_timeout(timeout);
+        }
 
         ConcurrentPipeline::new(
             compression_service,
             encryption_service,
             file_io_service,
             pipeline_repository.clone(),
-            Arc::new(BasicStageExecutor::new(stage_services)),
+            Arc::new(stage_executor),
             binary_format_service,
         )
     }
@@ -515,12 +1157,38 @@ impl ProcessFileUseCase {
         println!("└─ Errors:            {}", metrics.error_count());
         println!();
 
+        if let Some(concurrency) = metrics.concurrency() {
+            println!("🧵 CONCURRENCY");
+            println!("├─ Workers:           {}", concurrency.workers);
+            println!(
+                "├─ Queue Wait:        p50 ≤{} ms, p95 ≤{} ms",
+                concurrency.queue_wait_p50_ms, concurrency.queue_wait_p95_ms
+            );
+            println!(
+                "├─ CPU Token Wait:    p50 ≤{} ms, p95 ≤{} ms",
+                concurrency.cpu_wait_p50_ms, concurrency.cpu_wait_p95_ms
+            );
+            match concurrency.tuning_hint() {
+                Some(hint) => {
+                    println!("├─ Utilization:       {:.1}%", concurrency.worker_utilization_percent);
+                    println!("└─ 💡 Tuning Hint:    {}", hint);
+                }
+                None => println!("└─ Utilization:       {:.1}%", concurrency.worker_utilization_percent),
+            }
+            println!();
+        }
+
         // Adaptive configuration
         let available_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
         let optimal_workers = WorkerCount::optimal_for_file_size(actual_input_size);
 
         let (chunk_strategy, chunk_label) = match chunk_size_source {
             "user-override" => ("User-specified".to_string(), "user override".to_string()),
+            "learned" => ("Fastest in this pipeline's history".to_string(), "learned".to_string()),
+            "configured" => (
+                "Set in the pipeline configuration".to_string(),
+                "configured".to_string(),
+            ),
             "adaptive-fallback" => (
                 format!("{} (fallback)", ChunkSize::strategy_description(actual_input_size)),
                 "adaptive fallback".to_string(),
@@ -669,8 +1337,302 @@ impl ProcessFileUseCase {
     }
 }
 
+/// Builder for [`ProcessFileUseCase`]
+///
+/// Every dependency is optional. Missing ones are created by
+/// [`ProcessFileUseCaseBuilder::build`] the same way the CLI composition root
+/// creates them, so callers only inject what they need to replace.
+///
+/// ```rust,ignore
+/// let use_case = ProcessFileUseCase::builder()
+///     .database_path("/var/lib/adapipe/pipeline.db")
+///     .namespace("tenant-a".parse()?)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Default)]
+pub struct ProcessFileUseCaseBuilder {
+    metrics_service: Option<Arc<MetricsService>>,
+    observability_service: Option<Arc<ObservabilityService>>,
+    pipeline_repository: Option<Arc<SqlitePipelineRepository>>,
+    pipeline_cache: Option<Arc<PipelineCache>>,
+    database_path: Option<String>,
+    namespace: Option<Namespace>,
+    usage_repository: Option<Arc<dyn UsageRepository>>,
+    quota_service: Option<Arc<QuotaService>>,
+    idempotency_repository: Option<Arc<dyn IdempotencyRepository>>,
+    chunk_size_history: Option<Arc<dyn ChunkSizeHistoryRepository>>,
+    file_io_service: Option<Arc<dyn FileIOService>>,
+    stage_registry: Option<StageRegistry>,
+    stage_services: Vec<(String, Arc<dyn StageService>)>,
+    shutdown: Option<(Arc<dyn ShutdownSignal>, Duration)>,
+    security_context: Option<SecurityContext>,
+}
+
+impl ProcessFileUseCaseBuilder {
+    /// Uses `metrics_service` instead of creating one
+    pub fn metrics_service(mut self, metrics_service: Arc<MetricsService>) -> Self {
+        self.metrics_service = Some(metrics_service);
+        self
+    }
+
+    /// Uses `observability_service` instead of creating one from the metrics
+    /// service
+    pub fn observability_service(mut self, observability_service: Arc<ObservabilityService>) -> Self {
+        self.observability_service = Some(observability_service);
+        self
+    }
+
+    /// Uses `pipeline_repository` as-is; `database_path` and `namespace` are
+    /// then ignored
+    pub fn pipeline_repository(mut self, pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
+        self.pipeline_repository = Some(pipeline_repository);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_pipeline_cache`]
+    pub fn pipeline_cache(mut self, pipeline_cache: Arc<PipelineCache>) -> Self {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
+    }
+
+    /// Opens the default pipeline repository at `database_path` instead of
+    /// [`resolve_sqlite_path`]
+    pub fn database_path(mut self, database_path: impl Into<String>) -> Self {
+        self.database_path = Some(database_path.into());
+        self
+    }
+
+    /// Scopes the default pipeline repository to `namespace`
+    pub fn namespace(mut self, namespace: Namespace) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_usage_repository`]
+    pub fn usage_repository(mut self, usage_repository: Arc<dyn UsageRepository>) -> Self {
+        self.usage_repository = Some(usage_repository);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_quota_service`]
+    pub fn quota_service(mut self, quota_service: Arc<QuotaService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_idempotency_repository`]
+    pub fn idempotency_repository(mut self, idempotency_repository: Arc<dyn IdempotencyRepository>) -> Self {
+        self.idempotency_repository = Some(idempotency_repository);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_chunk_size_history`]
+    pub fn chunk_size_history(mut self, chunk_size_history: Arc<dyn ChunkSizeHistoryRepository>) -> Self {
+        self.chunk_size_history = Some(chunk_size_history);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_file_io_service`]
+    pub fn file_io_service(mut self, file_io_service: Arc<dyn FileIOService>) -> Self {
+        self.file_io_service = Some(file_io_service);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_stage_service`]
+    pub fn stage_service(mut self, algorithm: impl Into<String>, stage_service: Arc<dyn StageService>) -> Self {
+        self.stage_services.push((algorithm.into(), stage_service));
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_stage_registry`]; services added with
+    /// [`stage_service`](Self::stage_service) still apply on top of it
+    pub fn stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.stage_registry = Some(stage_registry);
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_shutdown`]
+    pub fn shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>, grace_period: Duration) -> Self {
+        self.shutdown = Some((shutdown, grace_period));
+        self
+    }
+
+    /// See [`ProcessFileUseCase::with_security_context`]
+    pub fn security_context(mut self, security_context: SecurityContext) -> Self {
+        self.security_context = Some(security_context);
+        self
+    }
+
+    /// Builds the use case, creating any dependency that was not injected
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a default metrics service or pipeline repository
+    /// cannot be created.
+    pub async fn build(self) -> Result<ProcessFileUseCase> {
+        let metrics_service = match self.metrics_service {
+            Some(metrics_service) => metrics_service,
+            None => Arc::new(MetricsService::new()?),
+        };
+        let observability_service = match self.observability_service {
+            Some(observability_service) => observability_service,
+            None => Arc::new(ObservabilityService::new_with_config(metrics_service.clone()).await),
+        };
+        let pipeline_repository = match self.pipeline_repository {
+            Some(pipeline_repository) => pipeline_repository,
+            None => {
+                let database_path = self.database_path.unwrap_or_else(resolve_sqlite_path);
+                let repository = SqlitePipelineRepository::new(&database_path).await?;
+                Arc::new(match self.namespace {
+                    Some(namespace) => repository.in_namespace(namespace),
+                    None => repository,
+                })
+            }
+        };
+
+        let mut use_case = ProcessFileUseCase::new(metrics_service, observability_service, pipeline_repository);
+        use_case.pipeline_cache = self.pipeline_cache;
+        use_case.usage_repository = self.usage_repository;
+        use_case.quota_service = self.quota_service;
+        use_case.idempotency_repository = self.idempotency_repository;
+        use_case.chunk_size_history = self.chunk_size_history;
+        use_case.file_io_service = self.file_io_service;
+        if let Some(stage_registry) = self.stage_registry {
+            use_case.stage_registry = stage_registry;
+        }
+        for (algorithm, stage_service) in self.stage_services {
+            use_case = use_case.with_stage_service(algorithm, stage_service);
+        }
+        if let Some((shutdown, grace_period)) = self.shutdown {
+            use_case = use_case.with_shutdown(shutdown, grace_period);
+        }
+        if let Some(security_context) = self.security_context {
+            use_case = use_case.with_security_context(security_context);
+        }
+        Ok(use_case)
+    }
+}
+
+#[async_trait::async_trait]
+impl CommandHandler<ProcessFileCommand> for ProcessFileUseCase {
+    async fn handle(&self, command: ProcessFileCommand) -> std::result::Result<ProcessFileResult, PipelineError> {
+        self.execute(command.config).await.map_err(handler_error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_builder_creates_default_dependencies() {
+        let dir = TempDir::new().unwrap();
+        let database_path = dir.path().join("pipeline.db");
+
+        let use_case = ProcessFileUseCase::builder()
+            .database_path(database_path.to_string_lossy())
+            .namespace("tenant-a".parse().unwrap())
+            .build()
+            .await
+            .unwrap();
+
+        assert!(database_path.exists());
+        assert_eq!(use_case.pipeline_repository.namespace().as_str(), "tenant-a");
+        assert!(use_case.usage_repository.is_none());
+        assert!(use_case.quota_service.is_none());
+    }
+
+    #[test]
+    fn test_request_fingerprint_distinguishes_requests() {
+        let fingerprint = |output: &str, chunk_size| {
+            ProcessFileUseCase::request_fingerprint(Path::new("in.txt"), Path::new(output), "smoke", chunk_size)
+        };
+        assert_eq!(fingerprint("a.adapipe", None), fingerprint("a.adapipe", None));
+        assert_ne!(fingerprint("a.adapipe", None), fingerprint("b.adapipe", None));
+        assert_ne!(
+            fingerprint("a.adapipe", None),
+            fingerprint("a.adapipe", Some(ChunkSize::from_mb(4).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_determine_chunk_size_uses_history_unless_overridden() {
+        let file_size = 300 * 1024 * 1024; // heuristic: 16MB
+        let record = |mb, bytes_per_second: f64| {
+            ChunkThroughput::new(ChunkSize::from_mb(mb).unwrap(), 2, bytes_per_second as u64, 1.0)
+        };
+        let history = [record(16, 400e6), record(32, 800e6)];
+
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, None, None, &[]),
+            (16 * 1024 * 1024, "adaptive")
+        );
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, None, None, &history),
+            (32 * 1024 * 1024, "learned")
+        );
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, Some(ChunkSize::from_mb(8).unwrap()), None, &history),
+            (8 * 1024 * 1024, "user-override")
+        );
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, Some(ChunkSize::from_kb(1536).unwrap()), None, &[]),
+            (1536 * 1024, "user-override")
+        );
+    }
+
+    #[test]
+    fn test_determine_chunk_size_prefers_pinned_size_over_history() {
+        let file_size = 300 * 1024 * 1024;
+        let pinned = Some(ChunkSize::from_mb(4).unwrap());
+        let history = [ChunkThroughput::new(
+            ChunkSize::from_mb(32).unwrap(),
+            2,
+            800_000_000,
+            1.0,
+        )];
+
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, None, pinned, &history),
+            (4 * 1024 * 1024, "configured")
+        );
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(file_size, Some(ChunkSize::from_mb(8).unwrap()), pinned, &[]),
+            (8 * 1024 * 1024, "user-override")
+        );
+        // A pinned size larger than the file falls back to adaptive
+        assert_eq!(
+            ProcessFileUseCase::determine_chunk_size(1024, None, pinned, &[]).1,
+            "adaptive"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_keeps_injected_dependencies() {
+        let dir = TempDir::new().unwrap();
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let repository = Arc::new(
+            SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+                .await
+                .unwrap(),
+        );
+        let quota_service = Arc::new(QuotaService::new(Default::default()));
+
+        let use_case = ProcessFileUseCase::builder()
+            .metrics_service(metrics_service.clone())
+            .pipeline_repository(repository.clone())
+            .database_path("/nonexistent/ignored.db")
+            .quota_service(quota_service)
+            .build()
+            .await
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&use_case.metrics_service, &metrics_service));
+        assert!(Arc::ptr_eq(&use_case.pipeline_repository, &repository));
+        assert!(use_case.quota_service.is_some());
+    }
 
     #[tokio::test]
     #[ignore] // Requires full infrastructure setup
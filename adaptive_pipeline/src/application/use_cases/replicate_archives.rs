@@ -0,0 +1,352 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Replicate Archives Use Case
+//!
+//! Copies the archives listed in a catalog to a secondary location (an
+//! object store bucket or another disk), so a 3-2-1 backup strategy needs no
+//! tooling beyond the pipeline itself: run `scrub` and `replicate` on a
+//! schedule against the same catalog.
+//!
+//! ## Verification
+//!
+//! Each copy is read back from the target and its SHA-256 compared with the
+//! archive's before it counts as a replica, so a truncated upload or a bad
+//! write on the target is caught at once rather than at restore time.
+//!
+//! ## Catalog Updates
+//!
+//! Every verified replica's location is appended to its archive's catalog
+//! line as soon as it is written, so an interrupted run loses nothing.
+//! Archives that already record a replica at the target are skipped, which
+//! makes reruns cheap; remove the location from the catalog to copy again.
+//!
+//! ## Outcomes
+//!
+//! Every archive is attempted even after one fails. Each is counted in
+//! `replicated_archives_total`; if any failed the run fails with `IoError`,
+//! so a scheduler's failure alerts fire.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ReplicateArchivesUseCase;
+//! use adaptive_pipeline::infrastructure::services::ReplicaTarget;
+//!
+//! let target = ReplicaTarget::parse("s3://offsite/fleet")?;
+//! let use_case = ReplicateArchivesUseCase::new(metrics_service).with_shutdown(shutdown);
+//! let report = use_case.replicate(Path::new("fleet.catalog"), &target).await?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+use adaptive_pipeline_domain::PipelineError;
+use anyhow::Result;
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::infrastructure::adapters::ContentHasher;
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::services::{is_remote_location, ArchiveCatalog, ChunkSource, FileSource, ReplicaTarget};
+
+/// Bytes hashed per read when verifying a copy
+const VERIFY_READ_SIZE: usize = 4 * 1024 * 1024;
+
+/// What became of one archive
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveReplica {
+    /// The archive, as resolved from the catalog
+    pub archive: PathBuf,
+    /// Where the replica is
+    pub replica: Option<String>,
+    /// Bytes copied; zero for a skipped archive
+    pub bytes: u64,
+    /// True if the catalog already recorded a replica at the target
+    pub skipped: bool,
+    /// Why the archive failed; `None` if it was replicated or skipped
+    pub error: Option<String>,
+}
+
+/// Structured result of a replicate run
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplicationReport {
+    /// The catalog that was replicated
+    pub catalog: PathBuf,
+    /// One entry per cataloged archive, in catalog order
+    pub archives: Vec<ArchiveReplica>,
+}
+
+impl ReplicationReport {
+    /// The archives that failed
+    pub fn failed(&self) -> impl Iterator<Item = &ArchiveReplica> {
+        self.archives.iter().filter(|archive| archive.error.is_some())
+    }
+}
+
+/// Use case for copying cataloged archives to a secondary location.
+///
+/// ## Dependencies
+///
+/// - **MetricsService**: Counts replicated, skipped and failed archives
+pub struct ReplicateArchivesUseCase {
+    metrics_service: Arc<MetricsService>,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+}
+
+impl ReplicateArchivesUseCase {
+    /// Creates a new Replicate Archives use case.
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            metrics_service,
+            shutdown: None,
+        }
+    }
+
+    /// Stops between archives once `shutdown` is requested
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Replicates the archives in `catalog` to `to` and prints the report,
+    /// as JSON with `json`
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if any archive failed to replicate, or the errors
+    /// of [`Self::replicate`].
+    pub async fn execute(&self, catalog: PathBuf, to: String, json: bool) -> Result<ReplicationReport> {
+        let target = ReplicaTarget::parse(&to)?;
+        let report = self.replicate(&catalog, &target).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            Self::print_report(&report, &to);
+        }
+
+        let failed = report.failed().count();
+        if failed > 0 {
+            let message = format!(
+                "{} of {} archives in {} failed to replicate to {}, first {}",
+                failed,
+                report.archives.len(),
+                catalog.display(),
+                to,
+                report
+                    .failed()
+                    .next()
+                    .map(|archive| archive.archive.display().to_string())
+                    .unwrap_or_default()
+            );
+            return Err(PipelineError::IoError(message).into());
+        }
+        Ok(report)
+    }
+
+    /// Copies every archive in `catalog` that has no replica at `target`
+    /// yet, verifies each copy and records it in the catalog
+    ///
+    /// Problems with an archive are recorded in its entry; the run goes on
+    /// to the next.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`ArchiveCatalog::load`] and
+    /// [`ArchiveCatalog::save`], or `Cancelled` once shutdown is requested.
+    pub async fn replicate(&self, catalog: &Path, target: &ReplicaTarget) -> Result<ReplicationReport> {
+        let mut entries = ArchiveCatalog::load(catalog).await?;
+        let count = entries.entries().count();
+        info!("Replicating {} archives from {}", count, catalog.display());
+
+        let mut report = ReplicationReport {
+            catalog: catalog.to_path_buf(),
+            archives: Vec::with_capacity(count),
+        };
+        for index in 0..count {
+            if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+                return Err(PipelineError::cancelled_with_msg(format!(
+                    "replicate stopped by shutdown after {} of {} archives",
+                    index, count
+                ))
+                .into());
+            }
+
+            let Some(entry) = entries.entry_mut(index) else {
+                break;
+            };
+            let mut replica = ArchiveReplica {
+                archive: entry.archive.clone(),
+                replica: None,
+                bytes: 0,
+                skipped: false,
+                error: None,
+            };
+            let outcome = match Self::replicate_one(&entry.listed, &entry.archive, &entry.replicas, target).await {
+                Ok(Some((location, bytes))) => {
+                    info!("Replicated {} to {}", entry.archive.display(), location);
+                    entry.replicas.push(location.clone());
+                    replica.replica = Some(location);
+                    replica.bytes = bytes;
+                    entries.save().await?;
+                    "replicated"
+                }
+                Ok(None) => {
+                    replica.replica = ReplicaTarget::key_for(&entry.listed)
+                        .ok()
+                        .map(|key| target.location(&key));
+                    replica.skipped = true;
+                    "skipped"
+                }
+                Err(e) => {
+                    error!("Archive {} failed to replicate: {}", entry.archive.display(), e);
+                    replica.error = Some(e.to_string());
+                    "failed"
+                }
+            };
+            self.metrics_service.increment_replicated_archives(outcome);
+            report.archives.push(replica);
+        }
+        Ok(report)
+    }
+
+    /// Copies and verifies one archive, returning the replica's location and
+    /// size, or `None` if `replicas` already holds it
+    async fn replicate_one(
+        listed: &str,
+        archive: &Path,
+        replicas: &[String],
+        target: &ReplicaTarget,
+    ) -> Result<Option<(String, u64)>, PipelineError> {
+        if is_remote_location(listed) {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Cannot replicate {}: only local archives are replicated",
+                listed
+            )));
+        }
+        let key = ReplicaTarget::key_for(listed)?;
+        let location = target.location(&key);
+        if replicas.contains(&location) {
+            return Ok(None);
+        }
+
+        let (size, checksum) = Self::digest(&FileSource::new(archive)).await?;
+        target.put(archive, &key).await?;
+        let (replica_size, replica_checksum) = Self::digest(target.open(&key)?.as_ref()).await?;
+        if replica_size != size || !constant_time_eq_str(&replica_checksum, &checksum) {
+            return Err(PipelineError::IntegrityError(format!(
+                "Replica {} does not match: {} bytes with SHA-256 {}, expected {} bytes with {}",
+                location, replica_size, replica_checksum, size, checksum
+            )));
+        }
+        Ok(Some((location, size)))
+    }
+
+    /// Size and SHA-256 of everything in `source`
+    async fn digest(source: &dyn ChunkSource) -> Result<(u64, String), PipelineError> {
+        let size = source.size().await?;
+        let mut hasher = ContentHasher::new(ChecksumAlgorithm::Sha256);
+        let mut offset = 0u64;
+        while offset < size {
+            let len = (size - offset).min(VERIFY_READ_SIZE as u64) as usize;
+            hasher.update(&source.read_at(offset, len).await?);
+            offset += len as u64;
+        }
+        Ok((size, hasher.finalize_hex()))
+    }
+
+    fn print_report(report: &ReplicationReport, to: &str) {
+        println!(
+            "📦 Replicating {} archives from {} to {}",
+            report.archives.len(),
+            report.catalog.display(),
+            to
+        );
+        for archive in &report.archives {
+            match (&archive.error, &archive.replica) {
+                (Some(error), _) => println!("   ❌ {}: {}", archive.archive.display(), error),
+                (None, Some(replica)) if archive.skipped => {
+                    println!("   ⏭️  {}: already at {}", archive.archive.display(), replica)
+                }
+                (None, Some(replica)) => println!(
+                    "   ✅ {}: {} bytes verified at {}",
+                    archive.archive.display(),
+                    archive.bytes,
+                    replica
+                ),
+                (None, None) => {}
+            }
+        }
+        if report.failed().next().is_none() {
+            println!("✅ Every archive has a verified replica");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn use_case() -> ReplicateArchivesUseCase {
+        ReplicateArchivesUseCase::new(Arc::new(MetricsService::new().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_archives_are_copied_verified_and_recorded_once() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("db")).unwrap();
+        std::fs::write(dir.path().join("db/a.adapipe"), b"first archive").unwrap();
+        std::fs::write(dir.path().join("b.adapipe"), b"second archive").unwrap();
+        let catalog = dir.path().join("fleet.catalog");
+        std::fs::write(&catalog, "# nightly\ndb/a.adapipe\nmissing.adapipe\nb.adapipe\n").unwrap();
+        let offsite = dir.path().join("offsite");
+        let target = ReplicaTarget::Directory(offsite.clone());
+
+        let report = use_case().replicate(&catalog, &target).await.unwrap();
+        assert_eq!(report.archives.len(), 3);
+        assert_eq!(report.archives[0].bytes, 13);
+        assert!(report.archives[1].error.is_some());
+        assert_eq!(std::fs::read(offsite.join("db/a.adapipe")).unwrap(), b"first archive");
+        assert_eq!(std::fs::read(offsite.join("b.adapipe")).unwrap(), b"second archive");
+        assert_eq!(
+            std::fs::read_to_string(&catalog).unwrap(),
+            format!(
+                "# nightly\ndb/a.adapipe\t{}\nmissing.adapipe\nb.adapipe\t{}\n",
+                offsite.join("db/a.adapipe").display(),
+                offsite.join("b.adapipe").display()
+            )
+        );
+
+        // A rerun only retries what has no replica yet
+        let rerun = use_case().replicate(&catalog, &target).await.unwrap();
+        assert!(rerun.archives[0].skipped && rerun.archives[2].skipped);
+        assert!(!rerun.archives[1].skipped);
+    }
+
+    #[tokio::test]
+    async fn test_failures_fail_the_run_after_every_archive_is_tried() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.adapipe"), b"archive").unwrap();
+        let catalog = dir.path().join("fleet.catalog");
+        std::fs::write(&catalog, "../outside.adapipe\na.adapipe\n").unwrap();
+        let offsite = dir.path().join("offsite");
+
+        let err = use_case()
+            .execute(catalog, offsite.to_string_lossy().to_string(), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PipelineError>(),
+            Some(PipelineError::IoError(_))
+        ));
+        assert!(offsite.join("a.adapipe").exists());
+    }
+}
@@ -69,7 +69,16 @@
 //! ## Usage Examples
 //!
 //! ### Basic File Restoration
-
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::commands::RestoreFileCommand;
+//! use adaptive_pipeline::application::use_cases::RestoreFileUseCase;
+//!
+//! let use_case = RestoreFileUseCase::new(metrics_service);
+//! let target = RestoreFileUseCase::resolve_target_path(&archive, None, false).await?;
+//! let result = use_case.execute(RestoreFileCommand::new(archive, target)).await?;
+//! assert!(result.checksum_verified);
+//! ```
 //!
 //! ### Batch Restoration
 
@@ -94,6 +103,9 @@
 //!   sequence
 //! - **Streaming**: Large files are processed in chunks to minimize memory
 //!   usage
+//! - **Ranges**: A ranged restore seeks through the footer's chunk index and
+//!   decodes only the chunks holding the range; the original's checksum
+//!   covers the whole file, so a range is not verified against it
 //! - **Validation**: Checksum validation provides integrity guarantees with
 //!   minimal overhead
 //!
@@ -102,6 +114,9 @@
 //! - **Decryption**: Encrypted files require appropriate decryption keys
 //! - **Integrity**: Checksum validation ensures data hasn't been tampered with
 //! - **Permissions**: Restored files maintain appropriate access permissions
+//! - **Security Policy**: An archive carries the security policy of the
+//!   pipeline that wrote it; restore refuses a security context that does not
+//!   satisfy it, emitting a `PermissionDenied` event
 //! - **Audit Trail**: Restoration operations are logged for security auditing
 //!
 //! ## Integration
@@ -114,15 +129,58 @@
 //! - **Validation Services**: Checksum verification and integrity checking
 //! - **Logging System**: Comprehensive operation logging and error reporting
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use adaptive_pipeline_domain::entities::pipeline::Pipeline;
 use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageConfiguration, StageType};
-use adaptive_pipeline_domain::value_objects::binary_file_format::FileHeader;
-use adaptive_pipeline_domain::PipelineError;
+use adaptive_pipeline_domain::entities::security_context::{Permission, SecurityContext, SecurityLevel};
+use adaptive_pipeline_domain::events::PermissionDeniedEvent;
+use adaptive_pipeline_domain::repositories::stage_executor::StageExecutor;
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::{ProcessingObserver, ShutdownSignal};
+use adaptive_pipeline_domain::value_objects::binary_file_format::{FileHeader, ProcessingStep, ProcessingStepType};
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::ASSOCIATED_DATA_KEY;
+use adaptive_pipeline_domain::value_objects::{
+    Algorithm, ByteRange, ChunkBinding, ChunkFormat, JobPriority, OutputResolution, PasswordKdf, PipelineId,
+    RestoreReport, SecretBytes, SecurityPolicy, WorkerCount,
+};
+use adaptive_pipeline_domain::{FileChunk, PipelineError, ProcessingContext};
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine as _;
 use chrono::Utc;
-use tracing::info;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::{debug, info, warn};
+
+use crate::application::command_bus::CommandHandler;
+use crate::application::commands::{RestoreFileCommand, RestoreFileResult};
+use crate::application::services::chunk_cache::{ChunkCache, DEFAULT_CHUNK_CACHE_SIZE};
+use crate::application::services::restore_permission_validator::RestorePermissionValidator;
+use crate::infrastructure::adapters::{
+    apply_mode, create_dir_all_with_mode, CommitOutcome, ContentHasher, MultiAlgoEncryption, StagedOutput,
+};
+use crate::infrastructure::config::build_info::build_provenance;
+use crate::infrastructure::metrics::{MetricsObserver, MetricsService};
+use crate::infrastructure::runtime::stage_executor::BasicStageExecutor;
+use crate::infrastructure::runtime::{try_resource_manager, StageRegistry};
+use crate::infrastructure::services::{
+    is_remote_location, open_source, AdapipeFormat, BinaryFormatReader, BinaryFormatService,
+};
 
 type Result<T> = std::result::Result<T, PipelineError>;
 
+/// What streaming an archive through its restoration stages produced
+struct RestoredStream {
+    bytes_written: u64,
+    chunks_processed: u32,
+    checksum: String,
+    /// Time spent in each stage of the restoration pipeline, by stage index
+    stage_durations: Vec<Duration>,
+}
+
 /// Creates an ephemeral restoration pipeline from `.adapipe` file metadata.
 ///
 /// This function is the core of the restoration system, responsible for
@@ -226,60 +284,10 @@ pub async fn create_restoration_pipeline(metadata: &FileHeader) -> Result<Pipeli
     // stages So we only need to create the user-defined stages
 
     // 2. Process steps in REVERSE order (LIFO for restoration)
-    let processing_steps = &metadata.processing_steps;
-    for step in processing_steps.iter().rev() {
-        let step_name = step.algorithm.to_lowercase();
-
-        // Skip checksum steps as they're handled separately
-        if step_name.contains("checksum") {
-            info!(
-                "Skipping checksum step: {} (from step order {}) - used for validation only",
-                step.algorithm, step.order
-            );
-            continue;
+    for step in metadata.processing_steps.iter().rev() {
+        if let Some(stage) = restoration_stage(step, metadata)? {
+            stages.push(stage);
         }
-
-        // Handle transformative custom steps (compression, encryption implemented as
-        // custom)
-        let stage_type = if step_name == "compression" {
-            StageType::Compression
-        } else if step_name == "encryption" {
-            StageType::Encryption
-        } else {
-            // For custom algorithms, infer type from algorithm name
-            if step.algorithm.contains("brotli") || step.algorithm.contains("gzip") || step.algorithm.contains("lz4") {
-                StageType::Compression
-            } else if step.algorithm.contains("aes")
-                || step.algorithm.contains("chacha")
-                || step.algorithm.contains("xchacha")
-            {
-                StageType::Encryption
-            } else {
-                // Default to pass-through for unknown algorithms
-                StageType::PassThrough
-            }
-        };
-
-        let stage_name = match stage_type {
-            StageType::Compression => "decompression",
-            StageType::Encryption => "decryption",
-            _ => &step_name,
-        };
-
-        let stage = PipelineStage::new(
-            stage_name.to_string(),
-            stage_type,
-            StageConfiguration {
-                algorithm: step.algorithm.clone(),
-                operation: adaptive_pipeline_domain::entities::Operation::Reverse, // REVERSE for restoration!
-                chunk_size: Some(metadata.chunk_size as usize),
-                parallel_processing: false, // Sequential for restoration
-                parameters: Default::default(),
-            },
-            0, // Order will be set by Pipeline::new
-        )?;
-
-        stages.push(stage);
     }
 
     // 3. Verification stage (always present for integrity)
@@ -287,7 +295,7 @@ pub async fn create_restoration_pipeline(metadata: &FileHeader) -> Result<Pipeli
         "verification".to_string(),
         StageType::Checksum,
         StageConfiguration {
-            algorithm: "sha256".to_string(),
+            algorithm: metadata.checksum_algorithm.to_string(),
             operation: adaptive_pipeline_domain::entities::Operation::Reverse, // REVERSE for restoration!
             chunk_size: Some(metadata.chunk_size as usize),
             parallel_processing: false,
@@ -298,8 +306,9 @@ pub async fn create_restoration_pipeline(metadata: &FileHeader) -> Result<Pipeli
     stages.push(verification_stage);
 
     // Create pipeline with restoration stages (input_checksum and output_checksum
-    // will be added automatically)
-    let pipeline = Pipeline::new(pipeline_name, stages)?;
+    // will be added automatically), verifying with the archive's hash
+    let mut pipeline = Pipeline::new(pipeline_name, stages)?;
+    pipeline.set_checksum_algorithm(metadata.checksum_algorithm);
 
     info!(
         "Created restoration pipeline with {} stages for file: {}",
@@ -309,3 +318,1835 @@ pub async fn create_restoration_pipeline(metadata: &FileHeader) -> Result<Pipeli
 
     Ok(pipeline)
 }
+
+/// Hands the decryption stages of `pipeline` the key of a
+/// password-protected archive
+///
+/// The key is derived again from `password` with the Argon2id salt and cost
+/// recorded on the archive's encryption steps. Archives whose keys were not
+/// derived from a password leave the pipeline unchanged.
+///
+/// Returns a warning if the recorded cost is weak enough that the
+/// passphrase could be guessed offline.
+///
+/// # Errors
+///
+/// Returns `MissingParameter` if the archive is password-protected and no
+/// password was given, or an error if its KDF parameters are invalid.
+pub fn unlock_restoration_pipeline(
+    pipeline: &mut Pipeline,
+    metadata: &FileHeader,
+    password: Option<&SecretBytes>,
+) -> Result<Option<String>> {
+    let mut kdfs = metadata
+        .processing_steps
+        .iter()
+        .filter(|step| step.step_type == ProcessingStepType::Encryption)
+        .map(|step| PasswordKdf::from_parameters(&step.parameters));
+    let Some(kdf) = kdfs.find_map(Result::transpose).transpose()? else {
+        return Ok(None);
+    };
+    let password = password.ok_or_else(|| {
+        PipelineError::MissingParameter("password; the archive is encrypted, run with --password-prompt".to_string())
+    })?;
+
+    let key = MultiAlgoEncryption::derive_password_key(password, &kdf)?;
+    pipeline.set_encryption_key(&general_purpose::STANDARD.encode(key.expose_secret()));
+
+    if !kdf.cost().is_weak() {
+        return Ok(None);
+    }
+    warn!(cost = %kdf.cost(), "Archive key was derived with a weak Argon2id cost");
+    Ok(Some(format!(
+        "Archive key was derived with a weak Argon2id cost ({}); re-encrypt it to strengthen the passphrase",
+        kdf.cost()
+    )))
+}
+
+/// Builds the stage that reverses one recorded processing step, or `None`
+/// for checksum steps, which are only used for validation.
+///
+/// Steps whose algorithm this build doesn't recognise become pass-through
+/// stages named after the algorithm, so they still resolve (or fail to
+/// resolve) against the stage registry by name. Encryption steps that bound
+/// their chunks get the binding rebuilt from `header`.
+///
+/// # Errors
+///
+/// Returns an error if the reversed stage configuration is invalid.
+pub fn restoration_stage(step: &ProcessingStep, header: &FileHeader) -> Result<Option<PipelineStage>> {
+    let step_name = step.algorithm.to_lowercase();
+    let algorithm = Algorithm::parse(&step.algorithm).ok();
+
+    // Skip checksum steps as they're handled separately
+    if step.step_type == ProcessingStepType::Checksum
+        || algorithm.as_ref().is_some_and(Algorithm::is_hashing)
+        || step_name.contains("checksum")
+    {
+        info!(
+            "Skipping checksum step: {} (from step order {}) - used for validation only",
+            step.algorithm, step.order
+        );
+        return Ok(None);
+    }
+
+    // Prefer the recorded step type; fall back to the algorithm category for
+    // legacy custom steps
+    let stage_type = match (&step.step_type, &algorithm) {
+        (ProcessingStepType::Compression, _) => StageType::Compression,
+        (ProcessingStepType::Encryption, _) => StageType::Encryption,
+        (_, Some(algorithm)) if algorithm.is_compression() => StageType::Compression,
+        (_, Some(algorithm)) if algorithm.is_encryption() => StageType::Encryption,
+        // Default to pass-through for unknown algorithms
+        _ => StageType::PassThrough,
+    };
+
+    let stage_name = match stage_type {
+        StageType::Compression => "decompression",
+        StageType::Encryption => "decryption",
+        _ => &step_name,
+    };
+
+    // Known algorithms are restored under their canonical name so archives
+    // written with legacy spellings resolve to the same service
+    let algorithm_name = algorithm.map(String::from).unwrap_or_else(|| step.algorithm.clone());
+    let mut parameters = step.parameters.clone();
+    parameters.insert("algorithm".to_string(), algorithm_name.clone());
+    if let Some(binding) = ChunkBinding::for_step(header, step)? {
+        parameters.insert(ASSOCIATED_DATA_KEY.to_string(), binding.to_parameter());
+    }
+
+    let stage = PipelineStage::new(
+        stage_name.to_string(),
+        stage_type,
+        StageConfiguration {
+            algorithm: algorithm_name,
+            operation: adaptive_pipeline_domain::entities::Operation::Reverse, // REVERSE for restoration!
+            chunk_size: Some(header.chunk_size as usize),
+            parallel_processing: false, // Sequential for restoration
+            parameters,
+        },
+        0, // Order will be set by Pipeline::new
+    )?;
+
+    Ok(Some(stage))
+}
+
+/// Turns the original filename recorded in an archive header into a path
+/// relative to the restore directory.
+///
+/// The header is untrusted input: a crafted archive could name
+/// `../../.bashrc` or `/etc/cron.d/job` and write outside the directory the
+/// user restores into. Unless `trust_archive_paths` is set, absolute names,
+/// drive or UNC prefixes and `..` components are rejected. Both `/` and `\`
+/// count as separators because the archive may have been written on another
+/// platform; `.` and empty components are dropped. A trusted name is used
+/// as recorded.
+///
+/// # Errors
+///
+/// Returns `SecurityViolation` for an empty name, a name containing NUL, or
+/// an untrusted name that is absolute or traverses upwards.
+pub fn archive_relative_path(original_filename: &str, trust_archive_paths: bool) -> Result<PathBuf> {
+    let reject = |reason: &str| {
+        PipelineError::security_violation(format!(
+            "Archive original filename '{}' {}; restore it only if the archive is trusted",
+            original_filename.escape_debug(),
+            reason
+        ))
+    };
+    if original_filename.is_empty() {
+        return Err(reject("is empty"));
+    }
+    if original_filename.contains('\0') {
+        return Err(reject("contains a NUL byte"));
+    }
+    if trust_archive_paths {
+        return Ok(PathBuf::from(original_filename));
+    }
+
+    let bytes = original_filename.as_bytes();
+    let has_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if original_filename.starts_with(['/', '\\']) || has_drive || Path::new(original_filename).is_absolute() {
+        return Err(reject("is an absolute path"));
+    }
+
+    let mut relative = PathBuf::new();
+    for component in original_filename.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => return Err(reject("traverses outside the restore directory")),
+            _ => relative.push(component),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(reject("names no file"));
+    }
+    Ok(relative)
+}
+
+/// Use case for restoring original files from `.adapipe` archives.
+///
+/// This is the single restoration code path used by the `restore` command
+/// and available to library users. It validates the target before writing,
+/// streams every chunk through the restoration pipeline built by
+/// [`create_restoration_pipeline`] and verifies the checksum of the restored
+/// data against the one recorded in the archive, with the hash it records.
+///
+/// ## Dependencies
+///
+/// - **MetricsService**: Metrics collection for the debug stage
+/// - **StageRegistry**: Stage services the restoration stages run through
+/// - **RestorePermissionValidator**: Pre-flight target checks
+pub struct RestoreFileUseCase {
+    metrics_service: Arc<MetricsService>,
+    stage_registry: StageRegistry,
+    permission_validator: RestorePermissionValidator,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    security_context: SecurityContext,
+    chunk_cache_size: u64,
+    workers: Option<usize>,
+}
+
+impl RestoreFileUseCase {
+    /// Creates a new Restore File use case.
+    ///
+    /// # Parameters
+    ///
+    /// * `metrics_service` - Metrics collection service
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            stage_registry: StageRegistry::builtin(metrics_service.clone()),
+            metrics_service,
+            permission_validator: RestorePermissionValidator::new(),
+            shutdown: None,
+            security_context: SecurityContext::with_permissions(
+                None,
+                vec![Permission::Read, Permission::Write],
+                SecurityLevel::Internal,
+            ),
+            chunk_cache_size: DEFAULT_CHUNK_CACHE_SIZE,
+            workers: None,
+        }
+    }
+
+    /// Keeps up to `bytes` of restored chunks in each range reader it opens
+    /// (64 MiB by default)
+    pub fn with_chunk_cache_size(mut self, bytes: u64) -> Self {
+        self.chunk_cache_size = bytes;
+        self
+    }
+
+    /// Restores chunks with `workers` concurrent workers instead of as many
+    /// as processing would use for a file of the original's size
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
+    /// Runs the restoration stages through `stage_registry` instead of a
+    /// registry of its own, sharing its service instances
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.stage_registry = stage_registry;
+        self
+    }
+
+    /// Stops the restore at the next chunk boundary once `shutdown` is
+    /// requested, failing with `Cancelled`; the staged output is discarded
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Restores on behalf of `security_context`, which must satisfy the
+    /// security policy recorded in each archive
+    pub fn with_security_context(mut self, security_context: SecurityContext) -> Self {
+        self.security_context = security_context;
+        self
+    }
+
+    /// Resolves where `input` restores to: the archive's original filename
+    /// inside `output_dir`, or next to the archive when no directory is given
+    ///
+    /// The filename is checked by [`archive_relative_path`], so an archive
+    /// can only name a file beneath that directory unless
+    /// `trust_archive_paths` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is missing or its metadata is
+    /// unreadable, and `SecurityViolation` if the recorded filename escapes
+    /// the directory.
+    pub async fn resolve_target_path(
+        input: &Path,
+        output_dir: Option<&Path>,
+        trust_archive_paths: bool,
+    ) -> Result<PathBuf> {
+        let metadata = Self::read_metadata(input).await?;
+        let relative = archive_relative_path(&metadata.original_filename, trust_archive_paths)?;
+        let directory = match output_dir {
+            Some(dir) => dir,
+            None => input.parent().unwrap_or_else(|| Path::new(".")),
+        };
+        Ok(directory.join(relative))
+    }
+
+    /// Executes the restore file use case.
+    ///
+    /// ## Parameters
+    ///
+    /// * `command` - Source archive, target path and overwrite/directory
+    ///   policy
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - Missing or malformed `.adapipe` archive
+    /// - `SecurityViolation` when the security context does not satisfy the
+    ///   archive's security policy
+    /// - Target rejected by [`RestorePermissionValidator`]
+    /// - Stage failures (e.g. wrong key, corrupt chunk)
+    /// - `IntegrityError` when the restored data does not match the
+    ///   archive's original checksum
+    pub async fn execute(&self, command: RestoreFileCommand) -> Result<RestoreFileResult> {
+        let start_time = Instant::now();
+        let started_at = Utc::now();
+        let mut warnings = Vec::new();
+        let input = &command.source_adapipe_path;
+        let target_path = &command.target_path;
+        info!("Restoring file from .adapipe: {}", input.display());
+
+        println!("💾 Restoring file...");
+        println!("   Source: {}", input.display());
+        println!("   Target: {}", target_path.display());
+
+        // Step 1: Read .adapipe metadata
+        let metadata = Self::read_metadata(input).await?;
+        println!("   📋 Metadata details:");
+        println!("      - Original filename: {}", metadata.original_filename);
+        println!("      - Original size: {} bytes", metadata.original_size);
+        println!("      - Encrypted: {}", metadata.is_encrypted());
+        println!("      - Compressed: {}", metadata.is_compressed());
+        println!("      - Processing steps: {}", metadata.processing_steps.len());
+        if let Some(range) = command.range {
+            println!(
+                "      - Range: bytes {} ({} bytes)",
+                range,
+                range.len_within(metadata.original_size)
+            );
+        }
+        self.authorize_archive(&metadata).await?;
+
+        // Step 2: Apply the overwrite policy to an existing target
+        let overwrite_policy = command.overwrite_policy;
+        let resolved_target = match overwrite_policy.resolve(target_path, input)? {
+            OutputResolution::Skip => {
+                println!(
+                    "⏭️  Skipped: {} exists (overwrite policy: {})",
+                    target_path.display(),
+                    overwrite_policy
+                );
+                return Ok(RestoreFileResult {
+                    restored_path: target_path.clone(),
+                    bytes_restored: 0,
+                    checksum_verified: false,
+                    calculated_checksum: String::new(),
+                    restoration_time_ms: start_time.elapsed().as_millis() as u64,
+                    output_commit: None,
+                    overwrite_policy,
+                    skipped: true,
+                    report: None,
+                });
+            }
+            OutputResolution::Renamed(path) => {
+                println!(
+                    "   ↪️  {} exists; restoring to {}",
+                    target_path.display(),
+                    path.display()
+                );
+                warnings.push(format!(
+                    "{} existed; restored to {} instead",
+                    target_path.display(),
+                    path.display()
+                ));
+                path
+            }
+            OutputResolution::New(path) | OutputResolution::Replace(path) => path,
+        };
+        let command = RestoreFileCommand {
+            target_path: resolved_target,
+            ..command
+        };
+        let input = &command.source_adapipe_path;
+        let target_path = &command.target_path;
+
+        // Step 3: Validate directory creation rights, writability and disk
+        // space before writing anything
+        println!("🔒 Validating permissions...");
+        let expected_size = command
+            .range
+            .map_or(metadata.original_size, |range| range.len_within(metadata.original_size));
+        self.permission_validator.validate(&command, expected_size)?;
+        println!("   ✅ All permission checks passed");
+
+        // Step 4: Create missing directories (permitted by the validator)
+        if let Some(parent_dir) = target_path.parent() {
+            if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+                println!("📂 Creating directory: {}", parent_dir.display());
+                create_dir_all_with_mode(parent_dir, command.directory_mode).await?;
+            }
+        }
+
+        // Step 5: Create restoration pipeline
+        let mut restoration_pipeline = create_restoration_pipeline(&metadata).await?;
+        if let Some(warning) =
+            unlock_restoration_pipeline(&mut restoration_pipeline, &metadata, command.password.as_ref())?
+        {
+            println!("   ⚠️  {}", warning);
+            warnings.push(warning);
+        }
+        println!(
+            "   🔄 Restoration pipeline created with {} stages",
+            restoration_pipeline.stages().len()
+        );
+        for stage in restoration_pipeline.stages() {
+            println!("      - {} (type: {:?})", stage.name(), stage.stage_type());
+        }
+
+        // Step 6: Stream chunks through the restoration stages into a staging
+        // file; the target is only replaced once the data has been verified
+        let mut staged = StagedOutput::create(target_path, command.staging_dir.as_deref()).await?;
+        if let Some(mode) = command.output_mode {
+            // Set on the staged file so the target appears with it
+            apply_mode(staged.staging_path(), mode)?;
+        }
+        let restored = match command.range {
+            Some(range) => {
+                self.range_restore(
+                    input,
+                    staged.file(),
+                    &restoration_pipeline,
+                    &metadata,
+                    range,
+                    command.priority,
+                )
+                .await?
+            }
+            None => {
+                self.stream_restore(input, staged.file(), &restoration_pipeline, &metadata, command.priority)
+                    .await?
+            }
+        };
+        let (bytes_restored, chunks_processed) = (restored.bytes_written, restored.chunks_processed);
+        let calculated_checksum = restored.checksum.clone();
+
+        // Step 7: Verify integrity of the restored data; the original's
+        // checksum covers the whole file, so a range can't be checked
+        // against it
+        let checksum_verified = match command.range {
+            Some(range) => {
+                warnings.push(format!(
+                    "Only bytes {} of the original were restored; its checksum was not verified",
+                    range
+                ));
+                false
+            }
+            None => {
+                let verified = Self::verify_restored(&metadata, &restored, target_path)?;
+                if !verified {
+                    warnings.push("Archive records no original checksum; restored data was not verified".to_string());
+                }
+                verified
+            }
+        };
+
+        // Step 8: Move the verified output onto the target
+        let mut last_percent = None;
+        let output_commit = staged
+            .commit(|copied, total| {
+                let percent = (copied * 100).checked_div(total).unwrap_or(100);
+                if last_percent.is_none_or(|last| percent >= last + 10 || (percent == 100 && last != 100)) {
+                    println!(
+                        "   🚚 Copying across filesystems: {}% ({}/{} bytes)",
+                        percent, copied, total
+                    );
+                    last_percent = Some(percent);
+                }
+            })
+            .await?;
+        if let CommitOutcome::CopiedAcrossDevices { bytes } = output_commit {
+            info!(
+                "Copied {} bytes from staging to {} across filesystems",
+                bytes,
+                target_path.display()
+            );
+        }
+
+        println!("✅ Restoration complete!");
+        println!("   📦 Chunks processed: {}", chunks_processed);
+        println!("   📊 Total bytes written: {} bytes", bytes_restored);
+        println!("   📁 Restored file: {}", target_path.display());
+        println!("   🛡️  Overwrite policy: {}", overwrite_policy);
+        if checksum_verified {
+            println!("   ✅ Checksum verified: {}", calculated_checksum);
+        } else if let Some(range) = command.range {
+            println!("   ✂️  Restored bytes {} only; checksum not verified", range);
+        }
+
+        // Step 9: Record the evidence for drills that archive it
+        let report = if command.write_report {
+            let mut report = RestoreReport::new(
+                input.to_string_lossy(),
+                target_path.to_string_lossy(),
+                metadata.original_size,
+                bytes_restored,
+                u64::from(chunks_processed),
+            )
+            .with_checksum(&metadata.original_checksum, &calculated_checksum, checksum_verified);
+            if let Some(range) = command.range {
+                report = report.with_range(range);
+            }
+            for (stage, duration) in restoration_pipeline.stages().iter().zip(&restored.stage_durations) {
+                // Checksum stages are not run per chunk; see `checksum`
+                if stage.stage_type() != &StageType::Checksum {
+                    report = report.with_stage(stage.name(), stage.algorithm(), u64::from(chunks_processed), *duration);
+                }
+            }
+            let report = warnings
+                .into_iter()
+                .fold(report, |report, warning| report.with_warning(warning))
+                .with_timestamps(started_at, Utc::now())
+                .with_provenance(build_provenance());
+
+            let path = RestoreReport::path_for(target_path);
+            tokio::fs::write(&path, report.to_json()?).await.map_err(|e| {
+                PipelineError::io_error(format!("Failed to write restore report {}: {}", path.display(), e))
+            })?;
+            println!("   🧾 Restore report: {}", path.display());
+            Some(path)
+        } else {
+            None
+        };
+
+        Ok(RestoreFileResult {
+            restored_path: target_path.clone(),
+            bytes_restored,
+            checksum_verified,
+            calculated_checksum,
+            restoration_time_ms: start_time.elapsed().as_millis() as u64,
+            output_commit: Some(output_commit),
+            overwrite_policy,
+            skipped: false,
+            report,
+        })
+    }
+
+    /// Reads the header of a local or remote `.adapipe` archive
+    pub async fn read_metadata(input: &Path) -> Result<FileHeader> {
+        let location = input.to_string_lossy();
+        if !is_remote_location(&location) && !input.exists() {
+            return Err(PipelineError::io_error(format!(
+                "Input .adapipe file does not exist: {}",
+                input.display()
+            )));
+        }
+        AdapipeFormat::new()
+            .create_reader_from(open_source(&location)?)
+            .await?
+            .read_header()
+    }
+
+    /// Checks that this build can run `stage`: a stage service is registered
+    /// for its algorithm and its executor-level parameters (such as
+    /// `timeout_ms`) are valid
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` describing what is missing.
+    pub async fn check_stage(&self, stage: &PipelineStage) -> Result<()> {
+        self.create_stage_executor().validate_configuration(stage).await
+    }
+
+    /// Streams `input` through `restoration_pipeline` without writing the
+    /// restored data anywhere, returning the bytes restored, the chunk count
+    /// and the checksum of the restored data
+    ///
+    /// Every chunk is decrypted (authenticating it) and decompressed exactly
+    /// as a restore would, so a clean run proves the archive restores.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first stage failure (e.g. wrong key, corrupt chunk).
+    pub async fn verify_stream(
+        &self,
+        input: &Path,
+        restoration_pipeline: &Pipeline,
+        metadata: &FileHeader,
+    ) -> Result<(u64, u32, String)> {
+        let restored = self
+            .stream_restore(
+                input,
+                &mut tokio::io::sink(),
+                restoration_pipeline,
+                metadata,
+                JobPriority::default(),
+            )
+            .await?;
+        Ok((restored.bytes_written, restored.chunks_processed, restored.checksum))
+    }
+
+    /// Streams the restored data of `input` into `output`, failing unless it
+    /// matches the size and checksum recorded in `metadata`
+    ///
+    /// For callers that place the restored data inside another container,
+    /// such as a tar entry, instead of a file of its own. Nothing is written
+    /// after the data, so `output` may carry more content. Returns the chunk
+    /// count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SecurityViolation` if the security context does not satisfy
+    /// the archive's security policy, the first stage failure, or
+    /// `IntegrityError` if the restored data does not match the archive.
+    pub async fn restore_into<W: AsyncWrite + Unpin>(
+        &self,
+        input: &Path,
+        metadata: &FileHeader,
+        output: &mut W,
+        priority: JobPriority,
+    ) -> Result<u32> {
+        self.authorize_archive(metadata).await?;
+        let restoration_pipeline = create_restoration_pipeline(metadata).await?;
+        let restored = self
+            .stream_restore(input, output, &restoration_pipeline, metadata, priority)
+            .await?;
+        Self::verify_restored(metadata, &restored, input)?;
+        Ok(restored.chunks_processed)
+    }
+
+    /// Checks the security context against the security policy recorded in
+    /// `metadata`, emitting a `PermissionDenied` event when it falls short
+    async fn authorize_archive(&self, metadata: &FileHeader) -> Result<()> {
+        let denial = match SecurityPolicy::from_entries(&metadata.metadata)?.check(&self.security_context) {
+            Ok(()) => return Ok(()),
+            Err(denial) => denial,
+        };
+
+        let pipeline_id = PipelineId::from_string(&metadata.pipeline_id)
+            .map(|id| id.as_uuid())
+            .unwrap_or_default();
+        let event = PermissionDeniedEvent::new(pipeline_id, &self.security_context, "restore", denial.to_string());
+        MetricsObserver::new(self.metrics_service.clone())
+            .on_permission_denied(&event)
+            .await;
+        Err(denial)
+    }
+
+    /// Checks restored data against the archive's size and checksum,
+    /// returning whether a checksum was recorded to verify against
+    fn verify_restored(metadata: &FileHeader, restored: &RestoredStream, target: &Path) -> Result<bool> {
+        let checksum_verified = if metadata.original_checksum.is_empty() {
+            warn!("Archive records no original checksum; restored data was not verified");
+            false
+        } else if constant_time_eq_str(&restored.checksum, &metadata.original_checksum) {
+            true
+        } else {
+            return Err(PipelineError::IntegrityError(format!(
+                "Restored file {} failed checksum verification: expected {}, got {}",
+                target.display(),
+                metadata.original_checksum,
+                restored.checksum
+            )));
+        };
+
+        if restored.bytes_written != metadata.original_size {
+            return Err(PipelineError::IntegrityError(format!(
+                "Restored file size ({} bytes) doesn't match original size ({} bytes)",
+                restored.bytes_written, metadata.original_size
+            )));
+        }
+        Ok(checksum_verified)
+    }
+
+    /// Writes the restored chunks to `output`, returning the bytes
+    /// written, the chunk count, the checksum of the restored data (with the
+    /// hash the archive's header records) and the time spent in each stage
+    ///
+    /// Chunks flow as they do when processing: a reader task feeds a pool
+    /// of workers that restore chunks concurrently, and their output is put
+    /// back in file order before it is written, so `output` can be any
+    /// stream and the checksum is computed as the data is written. At most
+    /// two chunks per worker are in flight. Each chunk's stages run under a
+    /// shared CPU token taken at `priority`, so a restore competes fairly
+    /// with concurrent processing.
+    async fn stream_restore<W: AsyncWrite + Unpin>(
+        &self,
+        input: &Path,
+        output: &mut W,
+        restoration_pipeline: &Pipeline,
+        metadata: &FileHeader,
+        priority: JobPriority,
+    ) -> Result<RestoredStream> {
+        // Local files, HTTP servers and object stores all read the same way
+        let reader = AdapipeFormat::new()
+            .create_reader_from(open_source(&input.to_string_lossy())?)
+            .await?;
+
+        let workers = self.restore_workers(metadata);
+        let restorer = Arc::new(self.chunk_restorer(restoration_pipeline.clone(), metadata, priority));
+        let slots = Arc::new(tokio::sync::Semaphore::new(workers * 2));
+        let (tx_chunks, rx_chunks) = tokio::sync::mpsc::channel(workers * 2);
+        let (tx_restored, rx_restored) = tokio::sync::mpsc::channel(workers * 2);
+        debug!("Restoring {} chunks with {} workers", metadata.chunk_count, workers);
+
+        let reader_handle = tokio::spawn(read_chunks(reader, tx_chunks, slots));
+        let rx_chunks = Arc::new(tokio::sync::Mutex::new(rx_chunks));
+        let worker_handles: Vec<_> = (0..workers)
+            .map(|_| {
+                tokio::spawn(restore_worker(
+                    rx_chunks.clone(),
+                    tx_restored.clone(),
+                    restorer.clone(),
+                    metadata.clone(),
+                    self.security_context.clone(),
+                ))
+            })
+            .collect();
+        // Workers hold the only senders, so the channel closes once they stop
+        drop(tx_restored);
+
+        let written = self
+            .write_in_order(rx_restored, output, metadata, restoration_pipeline.stages().len())
+            .await;
+        if written.is_err() {
+            reader_handle.abort();
+            for handle in &worker_handles {
+                handle.abort();
+            }
+        }
+        let restored = written?;
+        // A reader that stopped early (a truncated or malformed archive)
+        // closed the channels; report why
+        reader_handle
+            .await
+            .map_err(|e| PipelineError::processing_failed(format!("Chunk reader failed: {}", e)))??;
+        for handle in worker_handles {
+            handle
+                .await
+                .map_err(|e| PipelineError::processing_failed(format!("Restore worker failed: {}", e)))?;
+        }
+        Ok(restored)
+    }
+
+    /// Writes restored chunks to `output` in file order as they arrive,
+    /// holding any that arrive early until the chunks before them are written
+    ///
+    /// Processing writes each chunk at its position through a
+    /// `RandomAccessSink`; a restore cannot, since `output` may be stdout or
+    /// an HTTP body, so early chunks wait here in memory instead. Each one
+    /// still holds the in-flight slot [`read_chunks`] took for it until it is
+    /// written, so no more chunks wait than there are slots, however long
+    /// the chunk they wait for takes.
+    async fn write_in_order<W: AsyncWrite + Unpin>(
+        &self,
+        mut rx_restored: tokio::sync::mpsc::Receiver<Result<RestoredChunk>>,
+        output: &mut W,
+        metadata: &FileHeader,
+        stage_count: usize,
+    ) -> Result<RestoredStream> {
+        let mut hasher = ContentHasher::new(metadata.checksum_algorithm);
+        let mut chunks_processed = 0u32;
+        let mut bytes_written = 0u64;
+        let mut stage_durations = vec![Duration::ZERO; stage_count];
+        let mut early = BTreeMap::new();
+
+        while let Some(restored) = rx_restored.recv().await {
+            if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+                output
+                    .flush()
+                    .await
+                    .map_err(|e| PipelineError::io_error(format!("Failed to flush output file: {}", e)))?;
+                return Err(PipelineError::cancelled_with_msg(format!(
+                    "restore stopped by shutdown after {} of {} chunks ({} bytes written)",
+                    chunks_processed, metadata.chunk_count, bytes_written
+                )));
+            }
+            let restored = restored?;
+            early.insert(restored.index, restored);
+
+            while let Some(chunk) = early.remove(&chunks_processed) {
+                for (total, duration) in stage_durations.iter_mut().zip(chunk.durations) {
+                    *total += duration;
+                }
+                output
+                    .write_all(chunk.file_chunk.data())
+                    .await
+                    .map_err(|e| PipelineError::io_error(format!("Failed to write to output file: {}", e)))?;
+                hasher.update(chunk.file_chunk.data());
+
+                bytes_written += chunk.file_chunk.data().len() as u64;
+                chunks_processed += 1;
+
+                if chunks_processed.is_multiple_of(100) {
+                    println!(
+                        "   📦 Processed {} chunks, {} bytes written",
+                        chunks_processed, bytes_written
+                    );
+                }
+            }
+        }
+
+        output
+            .flush()
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(RestoredStream {
+            bytes_written,
+            chunks_processed,
+            checksum: hasher.finalize_hex(),
+            stage_durations,
+        })
+    }
+
+    /// Workers for a full restore: the count set with
+    /// [`Self::with_workers`], or as many as processing would use for a file
+    /// of the original's size, and never more than there are chunks
+    fn restore_workers(&self, metadata: &FileHeader) -> usize {
+        let workers = self.workers.unwrap_or_else(|| {
+            let available_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+            WorkerCount::optimal_for_processing_type(metadata.original_size, available_cores, true).count()
+        });
+        workers.clamp(1, (metadata.chunk_count as usize).max(1))
+    }
+
+    /// Opens `input` for reading byte ranges of the original file, decoding
+    /// only the chunks each read overlaps
+    ///
+    /// The archive is authorized and unlocked exactly as for a restore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is unreadable, `SecurityViolation` if
+    /// the security context does not satisfy its security policy, or
+    /// `MissingParameter` if it is password-protected and no password was
+    /// given.
+    pub async fn open_range_reader(
+        &self,
+        input: &Path,
+        password: Option<&SecretBytes>,
+        priority: JobPriority,
+    ) -> Result<ArchiveRangeReader> {
+        let reader = AdapipeFormat::new()
+            .create_reader_from(open_source(&input.to_string_lossy())?)
+            .await?;
+        let metadata = reader.read_header()?;
+        self.authorize_archive(&metadata).await?;
+        let mut restoration_pipeline = create_restoration_pipeline(&metadata).await?;
+        unlock_restoration_pipeline(&mut restoration_pipeline, &metadata, password)?;
+        self.range_reader(reader, restoration_pipeline, metadata, priority)
+    }
+
+    /// Writes bytes `range` of the original to `output`, decoding only the
+    /// chunks that hold them; the returned checksum covers just those bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the range starts past the end of the
+    /// original, besides the errors of a full restore.
+    async fn range_restore<W: AsyncWrite + Unpin>(
+        &self,
+        input: &Path,
+        output: &mut W,
+        restoration_pipeline: &Pipeline,
+        metadata: &FileHeader,
+        range: ByteRange,
+        priority: JobPriority,
+    ) -> Result<RestoredStream> {
+        let len = range.len_within(metadata.original_size);
+        if len == 0 {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Range {} starts past the end of the {} byte original",
+                range, metadata.original_size
+            )));
+        }
+        let reader = AdapipeFormat::new()
+            .create_reader_from(open_source(&input.to_string_lossy())?)
+            .await?;
+        let reader = self.range_reader(reader, restoration_pipeline.clone(), metadata.clone(), priority)?;
+
+        let (start, end) = (range.start(), range.start() + len);
+        let mut hasher = ContentHasher::new(metadata.checksum_algorithm);
+        let mut chunks_processed = 0u32;
+        let mut bytes_written = 0u64;
+        let mut stage_durations = vec![Duration::ZERO; restoration_pipeline.stages().len()];
+
+        for index in metadata.chunk_at_original_offset(start)..=metadata.chunk_at_original_offset(end - 1) {
+            if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+                return Err(PipelineError::cancelled_with_msg(format!(
+                    "restore stopped by shutdown after {} chunks ({} bytes written)",
+                    chunks_processed, bytes_written
+                )));
+            }
+            let (chunk, durations) = reader.chunk(index).await?;
+            for (total, duration) in stage_durations.iter_mut().zip(durations) {
+                *total += duration;
+            }
+
+            let chunk_start = metadata.original_range_of_chunk(index).start;
+            let from = start.saturating_sub(chunk_start) as usize;
+            let to = (end - chunk_start).min(chunk.len() as u64) as usize;
+            output
+                .write_all(&chunk[from..to])
+                .await
+                .map_err(|e| PipelineError::io_error(format!("Failed to write to output file: {}", e)))?;
+            hasher.update(&chunk[from..to]);
+            bytes_written += (to - from) as u64;
+            chunks_processed += 1;
+        }
+
+        output
+            .flush()
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(RestoredStream {
+            bytes_written,
+            chunks_processed,
+            checksum: hasher.finalize_hex(),
+            stage_durations,
+        })
+    }
+
+    /// Random access over `reader` through an unlocked restoration pipeline
+    fn range_reader(
+        &self,
+        reader: Box<dyn BinaryFormatReader>,
+        restoration_pipeline: Pipeline,
+        metadata: FileHeader,
+        priority: JobPriority,
+    ) -> Result<ArchiveRangeReader> {
+        if metadata.chunk_size == 0 {
+            return Err(PipelineError::ValidationError("Chunk size cannot be 0".to_string()));
+        }
+        Ok(ArchiveRangeReader {
+            reader: tokio::sync::Mutex::new(reader),
+            restorer: self.chunk_restorer(restoration_pipeline, &metadata, priority),
+            security_context: self.security_context.clone(),
+            metadata,
+            cache: ChunkCache::new(self.chunk_cache_size),
+        })
+    }
+
+    fn chunk_restorer(&self, pipeline: Pipeline, metadata: &FileHeader, priority: JobPriority) -> ChunkRestorer {
+        ChunkRestorer {
+            stage_executor: self.create_stage_executor(),
+            pipeline,
+            encrypted: metadata.is_encrypted(),
+            chunk_count: metadata.chunk_count,
+            priority,
+        }
+    }
+
+    /// Builds a stage executor over the shared stage registry
+    fn create_stage_executor(&self) -> BasicStageExecutor {
+        BasicStageExecutor::from_registry(self.stage_registry.clone())
+            .with_metrics_service(self.metrics_service.clone())
+    }
+}
+
+/// A chunk read from the archive on its way to a restore worker, holding
+/// one of the restore's in-flight slots
+struct StoredChunk {
+    index: u32,
+    chunk_format: ChunkFormat,
+    slot: tokio::sync::OwnedSemaphorePermit,
+}
+
+/// A restored chunk on its way to the writer; its slot is freed once it is
+/// written
+struct RestoredChunk {
+    index: u32,
+    file_chunk: FileChunk,
+    durations: Vec<Duration>,
+    _slot: tokio::sync::OwnedSemaphorePermit,
+}
+
+/// Reads every chunk of the archive into `tx_chunks`, waiting for a free
+/// slot before each read so the chunks in flight stay bounded; returns the
+/// chunk count
+async fn read_chunks(
+    mut reader: Box<dyn BinaryFormatReader>,
+    tx_chunks: tokio::sync::mpsc::Sender<StoredChunk>,
+    slots: Arc<tokio::sync::Semaphore>,
+) -> Result<u32> {
+    let mut index = 0u32;
+    loop {
+        let slot = slots
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| PipelineError::InternalError("Restore slots closed".to_string()))?;
+        let Some(chunk_format) = reader.read_next_chunk().await? else {
+            return Ok(index);
+        };
+        let chunk = StoredChunk {
+            index,
+            chunk_format,
+            slot,
+        };
+        if tx_chunks.send(chunk).await.is_err() {
+            // The workers stopped; whoever stopped them reports why
+            return Ok(index);
+        }
+        index += 1;
+    }
+}
+
+/// Restores chunks from the shared `rx_chunks` until it is drained, sending
+/// each to the writer; stops at the first failure, after passing it on
+async fn restore_worker(
+    rx_chunks: Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<StoredChunk>>>,
+    tx_restored: tokio::sync::mpsc::Sender<Result<RestoredChunk>>,
+    restorer: Arc<ChunkRestorer>,
+    metadata: FileHeader,
+    security_context: SecurityContext,
+) {
+    let mut context = ProcessingContext::new(metadata.original_size, security_context);
+    loop {
+        let Some(chunk) = rx_chunks.lock().await.recv().await else {
+            return;
+        };
+        let offset = metadata.original_range_of_chunk(chunk.index).start;
+        let restored = restorer
+            .restore(&mut context, chunk.index, offset, chunk.chunk_format)
+            .await
+            .map(|(file_chunk, durations)| RestoredChunk {
+                index: chunk.index,
+                file_chunk,
+                durations,
+                _slot: chunk.slot,
+            });
+        let failed = restored.is_err();
+        if tx_restored.send(restored).await.is_err() || failed {
+            return;
+        }
+    }
+}
+
+/// Reverses the recorded processing of one chunk at a time
+struct ChunkRestorer {
+    stage_executor: BasicStageExecutor,
+    pipeline: Pipeline,
+    encrypted: bool,
+    chunk_count: u32,
+    priority: JobPriority,
+}
+
+impl ChunkRestorer {
+    /// Restores chunk `index`, which starts at `offset` in the original
+    /// file, returning it and the time spent in each stage
+    ///
+    /// The stages run under a shared CPU token taken at the restorer's
+    /// priority.
+    async fn restore(
+        &self,
+        context: &mut ProcessingContext,
+        index: u32,
+        offset: u64,
+        chunk_format: ChunkFormat,
+    ) -> Result<(FileChunk, Vec<Duration>)> {
+        // Encrypted payloads are stored without their nonce; the
+        // decryption stage expects [nonce][ciphertext]
+        let chunk_data = if self.encrypted {
+            let mut reconstructed_data = chunk_format.nonce.to_vec();
+            reconstructed_data.extend_from_slice(&chunk_format.payload);
+            reconstructed_data
+        } else {
+            chunk_format.payload
+        };
+
+        let is_final = index + 1 == self.chunk_count;
+        let mut file_chunk = FileChunk::new(index as u64, offset, chunk_data, is_final)?;
+        let mut stage_durations = vec![Duration::ZERO; self.pipeline.stages().len()];
+
+        let cpu_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_cpu_at(self.priority).await?),
+            None => None,
+        };
+        // Checksum stages are validation-only; integrity is verified on
+        // the complete restored stream instead
+        for (stage_index, stage) in self.pipeline.stages().iter().enumerate() {
+            if stage.stage_type() == &StageType::Checksum {
+                continue;
+            }
+            debug!("Restoring chunk {} through stage: {}", index, stage.name());
+            let stage_start = Instant::now();
+            file_chunk = self.stage_executor.execute(stage, file_chunk, context).await?;
+            stage_durations[stage_index] += stage_start.elapsed();
+        }
+        drop(cpu_permit);
+        Ok((file_chunk, stage_durations))
+    }
+}
+
+/// Random access to the original bytes of an archive
+///
+/// Each read decodes only the chunks it overlaps, found through the
+/// footer's chunk index (or, in older archives, the chunk offsets the
+/// reader has seen). Encrypted chunks are authenticated as they
+/// are decrypted, but the whole-file checksum can't be checked on a range;
+/// restore the file to verify it. Decoded chunks are kept in a
+/// [`ChunkCache`] up to the use case's cache size, so reads smaller than a
+/// chunk, and reads returning to a recently read region, don't decode a
+/// chunk again.
+pub struct ArchiveRangeReader {
+    reader: tokio::sync::Mutex<Box<dyn BinaryFormatReader>>,
+    metadata: FileHeader,
+    restorer: ChunkRestorer,
+    security_context: SecurityContext,
+    cache: ChunkCache,
+}
+
+impl ArchiveRangeReader {
+    /// The archive's header
+    pub fn metadata(&self) -> &FileHeader {
+        &self.metadata
+    }
+
+    /// Size of the original file
+    pub fn len(&self) -> u64 {
+        self.metadata.original_size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads up to `len` bytes of the original file from `offset`; fewer
+    /// only at the end of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns the first stage failure, or `IntegrityError` if a chunk
+    /// doesn't restore to the size the header implies.
+    pub async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let end = offset.saturating_add(len as u64).min(self.len());
+        if offset >= end {
+            return Ok(Vec::new());
+        }
+        let mut data = Vec::with_capacity((end - offset) as usize);
+        let mut position = offset;
+        while position < end {
+            let index = self.metadata.chunk_at_original_offset(position);
+            let (chunk, _) = self.chunk(index).await?;
+            let chunk_start = self.metadata.original_range_of_chunk(index).start;
+            let from = (position - chunk_start) as usize;
+            let to = ((end - chunk_start) as usize).min(chunk.len());
+            data.extend_from_slice(&chunk[from..to]);
+            position = chunk_start + to as u64;
+        }
+        Ok(data)
+    }
+
+    /// The restored data of chunk `index`, authenticated if the archive is
+    /// encrypted
+    ///
+    /// # Errors
+    ///
+    /// Returns the first stage failure, or `IntegrityError` if the chunk
+    /// doesn't restore to the size the header implies.
+    pub async fn read_chunk(&self, index: u32) -> Result<Arc<Vec<u8>>> {
+        Ok(self.chunk(index).await?.0)
+    }
+
+    /// The restored data of chunk `index` and the time spent in each stage
+    /// decoding it (none when it was cached)
+    async fn chunk(&self, index: u32) -> Result<(Arc<Vec<u8>>, Vec<Duration>)> {
+        if let Some(data) = self.cache.get(index) {
+            return Ok((data, Vec::new()));
+        }
+
+        let chunk_format = {
+            let mut reader = self.reader.lock().await;
+            reader.seek_to_chunk(index).await?;
+            reader
+                .read_next_chunk()
+                .await?
+                .ok_or_else(|| PipelineError::IntegrityError(format!("Archive ends before chunk {}", index)))?
+        };
+        let original = self.metadata.original_range_of_chunk(index);
+        let mut context = ProcessingContext::new(self.metadata.original_size, self.security_context.clone());
+        let (file_chunk, durations) = self
+            .restorer
+            .restore(&mut context, index, original.start, chunk_format)
+            .await?;
+
+        let expected = original.end - original.start;
+        if file_chunk.data().len() as u64 != expected {
+            return Err(PipelineError::IntegrityError(format!(
+                "Chunk {} restored to {} bytes, expected {}",
+                index,
+                file_chunk.data().len(),
+                expected
+            )));
+        }
+        let data = Arc::new(file_chunk.data().to_vec());
+        self.cache.insert(index, data.clone());
+        Ok((data, durations))
+    }
+}
+
+#[async_trait]
+impl CommandHandler<RestoreFileCommand> for RestoreFileUseCase {
+    async fn handle(&self, command: RestoreFileCommand) -> Result<RestoreFileResult> {
+        self.execute(command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::binary_file_format::ChunkFormat;
+    use adaptive_pipeline_domain::value_objects::{KdfCost, NonceStrategy, OverwritePolicy};
+    use sha2::{Digest, Sha256};
+    use tempfile::TempDir;
+
+    /// Test helper to create a mock FileHeader for testing
+    fn create_test_file_header() -> FileHeader {
+        FileHeader::new("test_file.txt".to_string(), 1024, "abc123def456".to_string())
+            .add_compression_step("brotli", 6)
+            .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
+            .with_chunk_info(1024, 1)
+            .with_pipeline_id("test-pipeline-123".to_string())
+            .with_output_checksum("output123def456".to_string())
+    }
+
+    fn use_case() -> RestoreFileUseCase {
+        RestoreFileUseCase::new(Arc::new(MetricsService::new().unwrap()))
+    }
+
+    /// Writes an unprocessed single-chunk archive of `data` recording
+    /// `checksum` as the original checksum
+    async fn write_archive(dir: &Path, data: &[u8], checksum: String) -> PathBuf {
+        write_named_archive(dir, "original.txt", data, checksum).await
+    }
+
+    /// Like [`write_archive`], with `original_filename` recorded in the
+    /// header as given
+    async fn write_named_archive(dir: &Path, original_filename: &str, data: &[u8], checksum: String) -> PathBuf {
+        let archive = dir.join("original.txt.adapipe");
+        let header = FileHeader::new(original_filename.to_string(), data.len() as u64, checksum)
+            .with_chunk_info(data.len() as u32, 1)
+            .with_pipeline_id("restore-test".to_string());
+
+        let service = AdapipeFormat::new();
+        let mut writer = service.create_writer(&archive, header.clone()).await.unwrap();
+        writer.write_chunk(ChunkFormat::new([0u8; 12], data.to_vec())).unwrap();
+        writer.finalize(header).await.unwrap();
+        archive
+    }
+
+    #[tokio::test]
+    async fn test_create_restoration_pipeline_with_compression_and_encryption() {
+        let header = create_test_file_header();
+
+        let result = create_restoration_pipeline(&header).await;
+        assert!(
+            result.is_ok(),
+            "Failed to create restoration pipeline: {:?}",
+            result.err()
+        );
+
+        let pipeline = result.unwrap();
+        assert_eq!(
+            pipeline.stages().len(),
+            5,
+            "Expected 5 stages: input_checksum + decryption + decompression + verification + output_checksum"
+        );
+
+        // Verify stage order: input_checksum -> decryption -> decompression ->
+        // verification -> output_checksum
+        let stages = pipeline.stages();
+        assert_eq!(stages[0].name(), "input_checksum");
+        assert_eq!(stages[1].name(), "decryption");
+        assert_eq!(stages[2].name(), "decompression");
+        assert_eq!(stages[3].name(), "verification");
+        assert_eq!(stages[4].name(), "output_checksum");
+
+        // Verify stage types
+        assert_eq!(stages[0].stage_type(), &StageType::Checksum);
+        assert_eq!(stages[1].stage_type(), &StageType::Encryption); // Decryption uses Encryption type
+        assert_eq!(stages[2].stage_type(), &StageType::Compression); // Decompression uses Compression type
+        assert_eq!(stages[3].stage_type(), &StageType::Checksum);
+        assert_eq!(stages[4].stage_type(), &StageType::Checksum);
+    }
+
+    #[tokio::test]
+    async fn test_bound_encryption_step_restores_with_the_header_binding() {
+        let unbound = create_test_file_header();
+        let pipeline = create_restoration_pipeline(&unbound).await.unwrap();
+        assert!(!pipeline.stages()[1]
+            .configuration()
+            .parameters
+            .contains_key(ASSOCIATED_DATA_KEY));
+
+        let bound = unbound.with_chunk_binding();
+        let pipeline = create_restoration_pipeline(&bound).await.unwrap();
+        let binding = ChunkBinding::new("test-pipeline-123", bound.format_version);
+        assert_eq!(
+            pipeline.stages()[1].configuration().parameters.get(ASSOCIATED_DATA_KEY),
+            Some(&binding.to_parameter())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_password_protected_archive_unlocks_with_the_derived_key() {
+        let plain = create_test_file_header();
+        let mut pipeline = create_restoration_pipeline(&plain).await.unwrap();
+        assert_eq!(unlock_restoration_pipeline(&mut pipeline, &plain, None).unwrap(), None);
+        assert!(!pipeline.has_encryption_key());
+
+        let kdf = PasswordKdf::with_cost(vec![3; 16], KdfCost::new(64, 1, 1).unwrap()).unwrap();
+        let protected = plain.with_password_kdf(&kdf);
+        let mut pipeline = create_restoration_pipeline(&protected).await.unwrap();
+        assert!(unlock_restoration_pipeline(&mut pipeline, &protected, None).is_err());
+
+        let password = SecretBytes::from("passphrase");
+        if adaptive_pipeline_domain::value_objects::FIPS_MODE {
+            // Password keys come from Argon2id, which FIPS mode refuses
+            let err = unlock_restoration_pipeline(&mut pipeline, &protected, Some(&password)).unwrap_err();
+            assert!(err.to_string().contains("not FIPS-approved"));
+            return;
+        }
+        let warning = unlock_restoration_pipeline(&mut pipeline, &protected, Some(&password)).unwrap();
+        assert!(warning.is_some_and(|warning| warning.contains("weak")));
+        let key = MultiAlgoEncryption::derive_password_key(&password, &kdf).unwrap();
+        assert_eq!(
+            pipeline.stages()[1].configuration().parameters.get("key"),
+            Some(&general_purpose::STANDARD.encode(key.expose_secret()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_restoration_pipeline_compression_only() {
+        let header =
+            FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string()).add_compression_step("brotli", 6);
+
+        let result = create_restoration_pipeline(&header).await;
+        assert!(result.is_ok());
+
+        let pipeline = result.unwrap();
+        assert_eq!(
+            pipeline.stages().len(),
+            4,
+            "Expected 4 stages: input_checksum + decompression + verification + output_checksum"
+        );
+
+        let stages = pipeline.stages();
+        assert_eq!(stages[0].name(), "input_checksum");
+        assert_eq!(stages[1].name(), "decompression");
+        assert_eq!(stages[2].name(), "verification");
+        assert_eq!(stages[3].name(), "output_checksum");
+    }
+
+    #[tokio::test]
+    async fn test_create_restoration_pipeline_no_processing() {
+        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+
+        let result = create_restoration_pipeline(&header).await;
+        assert!(result.is_ok());
+
+        let pipeline = result.unwrap();
+        assert_eq!(
+            pipeline.stages().len(),
+            3,
+            "Expected 3 stages: input_checksum + verification + output_checksum"
+        );
+
+        let stages = pipeline.stages();
+
+        // Verify automatic checksum stages
+        assert_eq!(stages[0].name(), "input_checksum");
+        assert_eq!(stages[0].stage_type(), &StageType::Checksum);
+
+        // Verify user-defined verification stage
+        assert_eq!(stages[1].name(), "verification");
+        assert_eq!(stages[1].stage_type(), &StageType::Checksum);
+
+        // Verify automatic output checksum stage
+        assert_eq!(stages[2].name(), "output_checksum");
+        assert_eq!(stages[2].stage_type(), &StageType::Checksum);
+    }
+
+    #[tokio::test]
+    async fn test_restoration_pipeline_naming() {
+        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
+            .with_pipeline_id("original-pipeline-123".to_string());
+
+        let pipeline = create_restoration_pipeline(&header).await.unwrap();
+
+        // Verify ephemeral pipeline naming convention
+        assert!(pipeline.name().starts_with("__restore__"));
+        assert!(pipeline.name().contains("original-pipeline-123"));
+    }
+
+    #[tokio::test]
+    async fn test_file_chunk_creation_for_restoration() {
+        let test_data = vec![1, 2, 3, 4, 5];
+        let chunk = FileChunk::new(
+            0, // sequence_number
+            0, // offset
+            test_data.clone(),
+            false, // is_final
+        );
+
+        assert!(chunk.is_ok(), "Failed to create FileChunk: {:?}", chunk.err());
+
+        let chunk = chunk.unwrap();
+        assert_eq!(chunk.sequence_number(), 0);
+        assert_eq!(chunk.offset(), 0);
+        assert_eq!(chunk.data(), &test_data);
+        assert!(!chunk.is_final());
+    }
+
+    #[tokio::test]
+    async fn test_restore_verifies_checksum() {
+        let dir = TempDir::new().unwrap();
+        let data = b"restore me through the single use case path".to_vec();
+        let archive = write_archive(dir.path(), &data, format!("{:x}", Sha256::digest(&data))).await;
+
+        let target = RestoreFileUseCase::resolve_target_path(&archive, Some(&dir.path().join("out")), false)
+            .await
+            .unwrap();
+        assert_eq!(target, dir.path().join("out/original.txt"));
+
+        let result = use_case()
+            .execute(RestoreFileCommand::new(archive, target.clone()))
+            .await
+            .unwrap();
+
+        assert!(result.checksum_verified);
+        assert_eq!(result.restored_path, target);
+        assert_eq!(result.bytes_restored, data.len() as u64);
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_workers_write_chunks_in_order() {
+        let dir = TempDir::new().unwrap();
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        let archive = dir.path().join("many.adapipe");
+        let header = FileHeader::new(
+            "many.bin".to_string(),
+            data.len() as u64,
+            format!("{:x}", Sha256::digest(&data)),
+        )
+        .with_chunk_info(64, data.len().div_ceil(64) as u32)
+        .with_pipeline_id("restore-test".to_string());
+        let writer = AdapipeFormat::new()
+            .create_writer(&archive, header.clone())
+            .await
+            .unwrap();
+        // write_chunk blocks on the async writer, which stalls once the test
+        // task's I/O budget is spent; write at explicit positions instead
+        for (sequence, chunk) in data.chunks(64).enumerate() {
+            writer
+                .write_chunk_at_position(ChunkFormat::new([0u8; 12], chunk.to_vec()), sequence as u64)
+                .await
+                .unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+
+        let target = dir.path().join("many.bin");
+        let result = use_case()
+            .with_workers(4)
+            .execute(RestoreFileCommand::new(archive, target.clone()))
+            .await
+            .unwrap();
+        assert!(result.checksum_verified);
+        assert_eq!(result.bytes_restored, 5000);
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn test_chunks_waiting_to_be_written_are_bounded_by_the_slots() {
+        let dir = TempDir::new().unwrap();
+        let data: Vec<u8> = (0..80u8).collect();
+        let archive = dir.path().join("slots.adapipe");
+        let header = FileHeader::new("slots.bin".to_string(), data.len() as u64, String::new())
+            .with_chunk_info(8, 10)
+            .with_pipeline_id("restore-test".to_string());
+        let mut writer = AdapipeFormat::new()
+            .create_writer(&archive, header.clone())
+            .await
+            .unwrap();
+        for chunk in data.chunks(8) {
+            writer.write_chunk(ChunkFormat::new([0u8; 12], chunk.to_vec())).unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+
+        // The channel has room for every chunk; only the slots hold the
+        // reader back
+        let reader = AdapipeFormat::new().create_reader(&archive).await.unwrap();
+        let slots = Arc::new(tokio::sync::Semaphore::new(3));
+        let (tx_chunks, mut rx_chunks) = tokio::sync::mpsc::channel(10);
+        let reading = tokio::spawn(read_chunks(reader, tx_chunks, slots));
+
+        // Chunks held unwritten, as while chunk 0 is still being restored,
+        // stop the reader once they hold every slot
+        let mut held = Vec::new();
+        for _ in 0..3 {
+            held.push(rx_chunks.recv().await.unwrap());
+        }
+        let stalled = tokio::time::timeout(Duration::from_millis(100), rx_chunks.recv()).await;
+        assert!(stalled.is_err(), "a fourth chunk was read while three waited");
+
+        // Writing one frees its slot for the next
+        held.remove(0);
+        assert_eq!(rx_chunks.recv().await.unwrap().index, 3);
+        drop(held);
+        while rx_chunks.recv().await.is_some() {}
+        assert_eq!(reading.await.unwrap().unwrap(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_range_reader_decodes_only_the_overlapping_chunks() {
+        let dir = TempDir::new().unwrap();
+        let data = b"0123456789".to_vec();
+        let archive = dir.path().join("chunked.adapipe");
+        let header = FileHeader::new("chunked.txt".to_string(), data.len() as u64, String::new())
+            .with_chunk_info(4, 3)
+            .with_pipeline_id("restore-test".to_string());
+        let mut writer = AdapipeFormat::new()
+            .create_writer(&archive, header.clone())
+            .await
+            .unwrap();
+        for chunk in data.chunks(4) {
+            writer.write_chunk(ChunkFormat::new([0u8; 12], chunk.to_vec())).unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+
+        let reader = use_case()
+            .open_range_reader(&archive, None, JobPriority::default())
+            .await
+            .unwrap();
+        assert_eq!(reader.len(), 10);
+        assert_eq!(reader.read_at(2, 5).await.unwrap(), b"23456");
+        assert_eq!(reader.read_at(8, 100).await.unwrap(), b"89");
+        assert_eq!(reader.read_at(0, 10).await.unwrap(), data);
+        assert!(reader.read_at(10, 1).await.unwrap().is_empty());
+
+        let reader = use_case()
+            .with_chunk_cache_size(8)
+            .open_range_reader(&archive, None, JobPriority::default())
+            .await
+            .unwrap();
+        assert_eq!(reader.read_at(0, 10).await.unwrap(), data);
+        assert!(reader.cache.get(0).is_none(), "chunk 0 is evicted to fit the budget");
+        assert_eq!((reader.cache.len(), reader.cache.size()), (2, 6));
+        assert_eq!(reader.read_at(5, 4).await.unwrap(), b"5678");
+    }
+
+    #[tokio::test]
+    async fn test_range_reader_rejects_chunks_of_the_wrong_size() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("short.adapipe");
+        // A 12-byte chunk where the header promises chunks of 4
+        let header = FileHeader::new("short.txt".to_string(), 13, String::new())
+            .with_chunk_info(4, 2)
+            .with_pipeline_id("restore-test".to_string());
+        let mut writer = AdapipeFormat::new()
+            .create_writer(&archive, header.clone())
+            .await
+            .unwrap();
+        writer
+            .write_chunk(ChunkFormat::new([0u8; 12], b"twelve bytes".to_vec()))
+            .unwrap();
+        writer.write_chunk(ChunkFormat::new([0u8; 12], b"!".to_vec())).unwrap();
+        writer.finalize(header).await.unwrap();
+
+        let reader = use_case()
+            .open_range_reader(&archive, None, JobPriority::default())
+            .await
+            .unwrap();
+        let err = reader.read_at(0, 4).await.unwrap_err();
+        assert!(matches!(err, PipelineError::IntegrityError(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_ranged_restore_writes_only_the_range() {
+        let dir = TempDir::new().unwrap();
+        let data = b"0123456789".to_vec();
+        let archive = dir.path().join("chunked.adapipe");
+        let header = FileHeader::new("chunked.txt".to_string(), data.len() as u64, "0".repeat(64))
+            .with_chunk_info(4, 3)
+            .with_pipeline_id("restore-test".to_string());
+        let mut writer = AdapipeFormat::new()
+            .create_writer(&archive, header.clone())
+            .await
+            .unwrap();
+        for chunk in data.chunks(4) {
+            writer.write_chunk(ChunkFormat::new([0u8; 12], chunk.to_vec())).unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+
+        // Bytes 5-8 lie in the second and third chunks; the recorded
+        // checksum is wrong, but a range isn't checked against it
+        let target = dir.path().join("range.txt");
+        let range = "5-8".parse().unwrap();
+        let result = use_case()
+            .execute(
+                RestoreFileCommand::new(archive.clone(), target.clone())
+                    .with_range(Some(range))
+                    .with_report(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"5678");
+        assert!(!result.checksum_verified);
+        let report = RestoreReport::from_json(&std::fs::read_to_string(result.report.unwrap()).unwrap()).unwrap();
+        assert_eq!((report.range, report.chunks_processed), (Some(range), 2));
+        assert!(report.size_verified);
+        assert_eq!(report.warnings.len(), 1);
+
+        let past_end =
+            RestoreFileCommand::new(archive, dir.path().join("empty.txt")).with_range(Some("10-".parse().unwrap()));
+        let err = use_case().execute(past_end).await.unwrap_err();
+        assert!(matches!(err, PipelineError::InvalidParameter(_)), "{}", err);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_restore_writes_integrity_report_on_request() {
+        let dir = TempDir::new().unwrap();
+        let data = b"evidence for the recovery drill".to_vec();
+        let checksum = format!("{:x}", Sha256::digest(&data));
+        let archive = write_archive(dir.path(), &data, checksum.clone()).await;
+
+        let target = dir.path().join("plain.txt");
+        let result = use_case()
+            .execute(RestoreFileCommand::new(archive.clone(), target.clone()))
+            .await
+            .unwrap();
+        assert!(result.report.is_none());
+        assert!(!RestoreReport::path_for(&target).exists());
+
+        let target = dir.path().join("reported.txt");
+        let result = use_case()
+            .execute(RestoreFileCommand::new(archive, target.clone()).with_report(true))
+            .await
+            .unwrap();
+        let path = result.report.unwrap();
+        assert_eq!(path, RestoreReport::path_for(&target));
+
+        let report = RestoreReport::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(report.is_verified());
+        assert_eq!(report.checksum.expected, checksum);
+        assert_eq!(report.checksum.calculated, checksum);
+        assert_eq!(report.bytes_restored, data.len() as u64);
+        assert_eq!(report.chunks_processed, 1);
+        assert!(report.warnings.is_empty());
+        assert!(report.completed_at >= report.started_at);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_checksum_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let archive = write_archive(dir.path(), b"some data", "0".repeat(64)).await;
+        let target = dir.path().join("restored.txt");
+
+        let err = use_case()
+            .execute(RestoreFileCommand::new(archive.clone(), target.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::IntegrityError(_)));
+        // Output is staged, so nothing is left at the target or beside it
+        assert!(!target.exists());
+        let mut entries: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec![archive]);
+    }
+
+    #[tokio::test]
+    async fn test_restore_verifies_with_the_recorded_checksum_algorithm() {
+        use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+
+        let dir = TempDir::new().unwrap();
+        let data = b"hashed with blake3 when processed".to_vec();
+        let write_blake3_archive = |name: &str, checksum: String| {
+            let archive = dir.path().join(name);
+            let header = FileHeader::new("original.txt".to_string(), data.len() as u64, checksum)
+                .with_chunk_info(data.len() as u32, 1)
+                .with_checksum_algorithm(ChecksumAlgorithm::Blake3);
+            let data = data.clone();
+            async move {
+                let mut writer = AdapipeFormat::new()
+                    .create_writer(&archive, header.clone())
+                    .await
+                    .unwrap();
+                writer.write_chunk(ChunkFormat::new([0u8; 12], data)).unwrap();
+                writer.finalize(header).await.unwrap();
+                archive
+            }
+        };
+
+        let archive = write_blake3_archive(
+            "blake3.adapipe",
+            ContentHasher::digest_hex(ChecksumAlgorithm::Blake3, &data),
+        )
+        .await;
+        let target = dir.path().join("blake3.txt");
+        let result = use_case()
+            .execute(RestoreFileCommand::new(archive, target.clone()))
+            .await
+            .unwrap();
+        assert!(result.checksum_verified);
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+
+        // A SHA-256 digest does not pass as the BLAKE3 one the header claims
+        let archive = write_blake3_archive("mislabeled.adapipe", format!("{:x}", Sha256::digest(&data))).await;
+        let err = use_case()
+            .execute(RestoreFileCommand::new(archive, dir.path().join("mislabeled.txt")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::IntegrityError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_restore_respects_overwrite_policy() {
+        let dir = TempDir::new().unwrap();
+        let data = b"payload".to_vec();
+        let archive = write_archive(dir.path(), &data, format!("{:x}", Sha256::digest(&data))).await;
+        let target = dir.path().join("existing.txt");
+        std::fs::write(&target, b"keep me").unwrap();
+
+        let err = use_case()
+            .execute(RestoreFileCommand::new(archive.clone(), target.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::ValidationError(_)));
+        assert_eq!(std::fs::read(&target).unwrap(), b"keep me");
+
+        let restore = |policy| {
+            let command = RestoreFileCommand::new(archive.clone(), target.clone()).with_overwrite_policy(policy);
+            async move { use_case().execute(command).await }
+        };
+        let skipped = restore(OverwritePolicy::Skip).await.unwrap();
+        assert!(skipped.skipped);
+        assert_eq!(skipped.output_commit, None);
+        assert_eq!(std::fs::read(&target).unwrap(), b"keep me");
+
+        let renamed = restore(OverwritePolicy::Rename).await.unwrap();
+        assert_eq!(renamed.restored_path, dir.path().join("existing-1.txt"));
+        assert_eq!(renamed.overwrite_policy, OverwritePolicy::Rename);
+        assert_eq!(std::fs::read(&renamed.restored_path).unwrap(), data);
+        assert_eq!(std::fs::read(&target).unwrap(), b"keep me");
+
+        let replaced = restore(OverwritePolicy::Overwrite).await.unwrap();
+        assert_eq!(replaced.restored_path, target);
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_restore_applies_file_and_directory_modes() {
+        use adaptive_pipeline_domain::value_objects::FileMode;
+        use std::os::unix::fs::PermissionsExt;
+        let mode_of = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+        let dir = TempDir::new().unwrap();
+        let data = b"restored with explicit permissions".to_vec();
+        let archive = write_archive(dir.path(), &data, format!("{:x}", Sha256::digest(&data))).await;
+        let target = dir.path().join("out").join("nested").join("restored.txt");
+
+        use_case()
+            .execute(
+                RestoreFileCommand::new(archive, target.clone())
+                    .with_output_mode(Some(FileMode::new(0o640).unwrap()))
+                    .with_directory_mode(Some(FileMode::new(0o750).unwrap())),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+        assert_eq!(mode_of(&target), 0o640);
+        assert_eq!(mode_of(&dir.path().join("out")), 0o750);
+        assert_eq!(mode_of(&dir.path().join("out").join("nested")), 0o750);
+    }
+
+    #[test]
+    fn test_archive_relative_path_confines_untrusted_names() {
+        for (name, expected) in [
+            ("report.txt", "report.txt"),
+            ("./nested/report.txt", "nested/report.txt"),
+            ("nested\\report.txt", "nested/report.txt"),
+        ] {
+            assert_eq!(
+                archive_relative_path(name, false).unwrap(),
+                PathBuf::from(expected),
+                "{}",
+                name
+            );
+        }
+        for name in [
+            "",
+            ".",
+            "../escaped.txt",
+            "nested/../../escaped.txt",
+            "..\\escaped.txt",
+            "/etc/passwd",
+            "\\\\server\\share\\file",
+            "C:\\Windows\\win.ini",
+            "c:relative.txt",
+            "name\0.txt",
+        ] {
+            let err = archive_relative_path(name, false).unwrap_err();
+            assert!(
+                matches!(err, PipelineError::SecurityViolation(_)),
+                "{:?}: {}",
+                name,
+                err
+            );
+        }
+
+        assert_eq!(
+            archive_relative_path("../escaped.txt", true).unwrap(),
+            PathBuf::from("../escaped.txt")
+        );
+        assert!(archive_relative_path("", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_crafted_header_cannot_escape_output_dir() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out");
+        for crafted in ["../escaped.txt", "/tmp/escaped.txt", "a/../../escaped.txt"] {
+            let archive = write_named_archive(dir.path(), crafted, b"payload", "0".repeat(64)).await;
+            let err = RestoreFileUseCase::resolve_target_path(&archive, Some(&out), false)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, PipelineError::SecurityViolation(_)),
+                "{}: {}",
+                crafted,
+                err
+            );
+        }
+
+        let archive = write_named_archive(dir.path(), "../escaped.txt", b"payload", "0".repeat(64)).await;
+        let trusted = RestoreFileUseCase::resolve_target_path(&archive, Some(&out), true)
+            .await
+            .unwrap();
+        assert_eq!(trusted, out.join("../escaped.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_archive_is_rejected() {
+        let result = RestoreFileUseCase::resolve_target_path(Path::new("/nonexistent/file.adapipe"), None, false).await;
+        assert!(result.is_err());
+    }
+}
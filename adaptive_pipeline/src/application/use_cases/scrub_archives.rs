@@ -0,0 +1,441 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Scrub Archives Use Case
+//!
+//! Checks the archives listed in a catalog for silent corruption (bit rot,
+//! truncation, a bad disk sector) before a restore needs them. Run it on a
+//! schedule, from cron or a systemd timer: each run checks a fresh random
+//! sample of every archive's chunks, so over many runs the whole set is
+//! covered without reading it all every night.
+//!
+//! ## Catalog
+//!
+//! A text file listing one archive per line (see [`ArchiveCatalog`]). Blank
+//! lines and lines starting with `#` are ignored; relative paths are
+//! relative to the catalog's directory. Replica locations that `replicate`
+//! records after an archive are not scrubbed.
+//! `find /backups -name '*.adapipe' > fleet.catalog` makes one.
+//!
+//! [`ArchiveCatalog`]: crate::infrastructure::services::ArchiveCatalog
+//!
+//! ## Checks
+//!
+//! - **Sample** (default): reads the footer, which checks the chunk index
+//!   tiles the chunk data, then decodes a random sample of chunks. Decoding
+//!   authenticates each chunk an AEAD encryption stage sealed, so a sample
+//!   catches corruption of any chunk it reads. Nothing authenticates the
+//!   chunks of an unencrypted archive, which can decode cleanly from rotten
+//!   data, so those archives are always checked in full
+//! - **Full** (`--full`): decodes every chunk and verifies the original's
+//!   size and checksum, which catches any change to the data
+//!
+//! Archives are authorized and unlocked as for `restore`; one passphrase is
+//! tried for every password-protected archive.
+//!
+//! ## Outcomes
+//!
+//! Every archive is checked even after one fails. Each corrupt archive is
+//! logged as an error and counted in `scrubbed_archives_total`; if any is
+//! corrupt the run fails with `IntegrityError` (exit code 81), so a
+//! scheduler's failure alerts fire.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::ScrubArchivesUseCase;
+//!
+//! let use_case = ScrubArchivesUseCase::new(metrics_service).with_shutdown(shutdown);
+//! let report = use_case.scrub(Path::new("fleet.catalog"), Some(16), None).await?;
+//! assert!(report.is_clean());
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::entities::security_context::SecurityContext;
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use adaptive_pipeline_domain::value_objects::{JobPriority, SecretBytes};
+use adaptive_pipeline_domain::PipelineError;
+use anyhow::Result;
+use serde::Serialize;
+use tracing::{error, info};
+
+use crate::application::use_cases::restore_file::ArchiveRangeReader;
+use crate::application::use_cases::RestoreFileUseCase;
+use crate::infrastructure::adapters::ContentHasher;
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::runtime::StageRegistry;
+use crate::infrastructure::services::ArchiveCatalog;
+
+/// Chunks checked per archive in each run unless told otherwise
+pub const DEFAULT_SCRUB_SAMPLE: u32 = 16;
+
+/// What scrubbing one archive found
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveScrub {
+    /// The archive, as resolved from the catalog
+    pub archive: PathBuf,
+    /// Chunks the archive holds, if its footer could be read
+    pub chunk_count: u32,
+    /// Chunks decoded without error
+    pub chunks_verified: u32,
+    /// True if the original's checksum was verified (`--full`)
+    pub checksum_verified: bool,
+    /// Why the archive failed; `None` for a clean archive
+    pub error: Option<String>,
+}
+
+/// Structured result of a scrub run
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrubReport {
+    /// The catalog that was scrubbed
+    pub catalog: PathBuf,
+    /// True if every chunk was checked
+    pub full: bool,
+    /// One entry per cataloged archive, in catalog order
+    pub archives: Vec<ArchiveScrub>,
+}
+
+impl ScrubReport {
+    /// True if no archive failed
+    pub fn is_clean(&self) -> bool {
+        self.archives.iter().all(|archive| archive.error.is_none())
+    }
+
+    /// The archives that failed
+    pub fn corrupt(&self) -> impl Iterator<Item = &ArchiveScrub> {
+        self.archives.iter().filter(|archive| archive.error.is_some())
+    }
+}
+
+/// Use case for checking cataloged archives for silent corruption.
+///
+/// ## Dependencies
+///
+/// - **RestoreFileUseCase**: Authorizes, unlocks and decodes chunks exactly
+///   as a restore would
+/// - **MetricsService**: Counts clean and corrupt archives
+pub struct ScrubArchivesUseCase {
+    restore: RestoreFileUseCase,
+    metrics_service: Arc<MetricsService>,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+}
+
+impl ScrubArchivesUseCase {
+    /// Creates a new Scrub Archives use case.
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            // Chunks are read once each, so nothing is worth caching
+            restore: RestoreFileUseCase::new(metrics_service.clone()).with_chunk_cache_size(0),
+            metrics_service,
+            shutdown: None,
+        }
+    }
+
+    /// Decodes through `stage_registry`; see
+    /// [`RestoreFileUseCase::with_stage_registry`]
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.restore = self.restore.with_stage_registry(stage_registry);
+        self
+    }
+
+    /// Authorizes each archive against `security_context`; see
+    /// [`RestoreFileUseCase::with_security_context`]
+    pub fn with_security_context(mut self, security_context: SecurityContext) -> Self {
+        self.restore = self.restore.with_security_context(security_context);
+        self
+    }
+
+    /// Stops between chunks once `shutdown` is requested
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Reads the archives listed in the catalog at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the catalog can't be read, or `ValidationError`
+    /// if it lists no archives.
+    pub async fn read_catalog(path: &Path) -> Result<Vec<PathBuf>> {
+        let catalog = ArchiveCatalog::load(path).await?;
+        Ok(catalog.entries().map(|entry| entry.archive.clone()).collect())
+    }
+
+    /// Scrubs the archives in `catalog` and prints the report, as JSON with
+    /// `json`
+    ///
+    /// # Errors
+    ///
+    /// Returns `IntegrityError` if any archive is corrupt, `Cancelled` if
+    /// shutdown was requested, or the errors of [`Self::scrub`].
+    pub async fn execute(
+        &self,
+        catalog: PathBuf,
+        sample: Option<u32>,
+        password: Option<SecretBytes>,
+        json: bool,
+    ) -> Result<ScrubReport> {
+        let report = self.scrub(&catalog, sample, password.as_ref()).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            Self::print_report(&report);
+        }
+
+        let corrupt = report.corrupt().count();
+        if corrupt > 0 {
+            let first = report
+                .corrupt()
+                .next()
+                .map(|archive| archive.archive.display().to_string());
+            return Err(PipelineError::IntegrityError(format!(
+                "{} of {} archives in {} failed scrubbing, first {}",
+                corrupt,
+                report.archives.len(),
+                catalog.display(),
+                first.unwrap_or_default()
+            ))
+            .into());
+        }
+        Ok(report)
+    }
+
+    /// Checks `sample` random chunks of every encrypted archive in
+    /// `catalog`, and every chunk and the original's checksum of the other
+    /// archives, or of all of them when `sample` is `None`
+    ///
+    /// Problems with an archive are recorded in its entry; the run goes on
+    /// to the next.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::read_catalog`], or `Cancelled` once
+    /// shutdown is requested.
+    pub async fn scrub(
+        &self,
+        catalog: &Path,
+        sample: Option<u32>,
+        password: Option<&SecretBytes>,
+    ) -> Result<ScrubReport> {
+        let archives = Self::read_catalog(catalog).await?;
+        info!(
+            "Scrubbing {} archives from {} ({})",
+            archives.len(),
+            catalog.display(),
+            sample.map_or_else(|| "every chunk".to_string(), |n| format!("{} chunks each", n))
+        );
+
+        let mut report = ScrubReport {
+            catalog: catalog.to_path_buf(),
+            full: sample.is_none(),
+            archives: Vec::with_capacity(archives.len()),
+        };
+        for archive in archives {
+            let mut scrub = ArchiveScrub {
+                archive,
+                chunk_count: 0,
+                chunks_verified: 0,
+                checksum_verified: false,
+                error: None,
+            };
+            if let Err(e) = self.scrub_archive(&mut scrub, sample, password).await {
+                if matches!(e, PipelineError::Cancelled(_)) {
+                    return Err(e.into());
+                }
+                error!("Archive {} failed scrubbing: {}", scrub.archive.display(), e);
+                scrub.error = Some(e.to_string());
+            }
+            let outcome = if scrub.error.is_none() { "clean" } else { "corrupt" };
+            self.metrics_service.increment_scrubbed_archives(outcome);
+            report.archives.push(scrub);
+        }
+        Ok(report)
+    }
+
+    async fn scrub_archive(
+        &self,
+        scrub: &mut ArchiveScrub,
+        sample: Option<u32>,
+        password: Option<&SecretBytes>,
+    ) -> Result<(), PipelineError> {
+        let reader = self
+            .restore
+            .open_range_reader(&scrub.archive, password, JobPriority::Batch)
+            .await?;
+        scrub.chunk_count = reader.metadata().chunk_count;
+
+        // Only AEAD-sealed chunks fail to decode when they rot
+        let Some(sample) = sample.filter(|_| reader.metadata().is_encrypted()) else {
+            return self.verify_every_chunk(scrub, &reader).await;
+        };
+        let mut indexes = rand::seq::index::sample(
+            &mut rand::rng(),
+            scrub.chunk_count as usize,
+            (sample as usize).min(scrub.chunk_count as usize),
+        )
+        .into_vec();
+        // In file order, so the reads move forward through the archive
+        indexes.sort_unstable();
+        for index in indexes {
+            self.check_shutdown(scrub)?;
+            reader.read_chunk(index as u32).await?;
+            scrub.chunks_verified += 1;
+        }
+        Ok(())
+    }
+
+    async fn verify_every_chunk(
+        &self,
+        scrub: &mut ArchiveScrub,
+        reader: &ArchiveRangeReader,
+    ) -> Result<(), PipelineError> {
+        let metadata = reader.metadata();
+        let mut hasher = ContentHasher::new(metadata.checksum_algorithm);
+        let mut restored = 0u64;
+        for index in 0..scrub.chunk_count {
+            self.check_shutdown(scrub)?;
+            let chunk = reader.read_chunk(index).await?;
+            hasher.update(&chunk);
+            restored += chunk.len() as u64;
+            scrub.chunks_verified += 1;
+        }
+
+        if restored != metadata.original_size {
+            return Err(PipelineError::IntegrityError(format!(
+                "Chunks restore to {} bytes, expected {}",
+                restored, metadata.original_size
+            )));
+        }
+        let checksum = hasher.finalize_hex();
+        if !metadata.original_checksum.is_empty() {
+            if !constant_time_eq_str(&checksum, &metadata.original_checksum) {
+                return Err(PipelineError::IntegrityError(format!(
+                    "Checksum mismatch: expected {}, got {}",
+                    metadata.original_checksum, checksum
+                )));
+            }
+            scrub.checksum_verified = true;
+        }
+        Ok(())
+    }
+
+    fn check_shutdown(&self, scrub: &ArchiveScrub) -> Result<(), PipelineError> {
+        if self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested()) {
+            return Err(PipelineError::cancelled_with_msg(format!(
+                "scrub stopped by shutdown in {}",
+                scrub.archive.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn print_report(report: &ScrubReport) {
+        println!(
+            "🧽 Scrubbed {} archives from {}",
+            report.archives.len(),
+            report.catalog.display()
+        );
+        for archive in &report.archives {
+            match &archive.error {
+                None if archive.checksum_verified => println!(
+                    "   ✅ {}: {} chunks, checksum verified",
+                    archive.archive.display(),
+                    archive.chunks_verified
+                ),
+                None => println!(
+                    "   ✅ {}: {} of {} chunks verified",
+                    archive.archive.display(),
+                    archive.chunks_verified,
+                    archive.chunk_count
+                ),
+                Some(error) => println!("   ❌ {}: {}", archive.archive.display(), error),
+            }
+        }
+        if report.is_clean() {
+            println!("✅ No corruption found");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+    use adaptive_pipeline_domain::value_objects::binary_file_format::{ChunkFormat, FileHeader};
+    use tempfile::TempDir;
+
+    fn use_case() -> ScrubArchivesUseCase {
+        ScrubArchivesUseCase::new(Arc::new(MetricsService::new().unwrap()))
+    }
+
+    /// Writes an unprocessed archive of `data` in chunks of 4 bytes
+    async fn write_archive(path: &Path, data: &[u8]) {
+        let mut header = FileHeader::new("data.txt".to_string(), data.len() as u64, String::new());
+        header.original_checksum = ContentHasher::digest_hex(header.checksum_algorithm, data);
+        let header = header
+            .with_chunk_info(4, data.len().div_ceil(4) as u32)
+            .with_pipeline_id("scrub-test".to_string());
+        let mut writer = AdapipeFormat::new().create_writer(path, header.clone()).await.unwrap();
+        for chunk in data.chunks(4) {
+            writer.write_chunk(ChunkFormat::new([0u8; 12], chunk.to_vec())).unwrap();
+        }
+        writer.finalize(header).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_catalog_skips_comments_and_resolves_relative_paths() {
+        let dir = TempDir::new().unwrap();
+        let catalog = dir.path().join("fleet.catalog");
+        std::fs::write(&catalog, "# nightly\n\na.adapipe\n  /abs/b.adapipe  \n").unwrap();
+        assert_eq!(
+            ScrubArchivesUseCase::read_catalog(&catalog).await.unwrap(),
+            vec![dir.path().join("a.adapipe"), PathBuf::from("/abs/b.adapipe")]
+        );
+
+        std::fs::write(&catalog, "# nothing yet\n").unwrap();
+        assert!(ScrubArchivesUseCase::read_catalog(&catalog).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scrub_checks_samples_and_reports_corrupt_archives() {
+        let dir = TempDir::new().unwrap();
+        write_archive(&dir.path().join("good.adapipe"), b"0123456789abcdef").await;
+        write_archive(&dir.path().join("rotten.adapipe"), b"0123456789abcdef").await;
+        // Flip a byte of the first chunk so the checksum no longer matches
+        let rotten = dir.path().join("rotten.adapipe");
+        let mut bytes = std::fs::read(&rotten).unwrap();
+        bytes[16] ^= 0xff;
+        std::fs::write(&rotten, bytes).unwrap();
+        let catalog = dir.path().join("fleet.catalog");
+        std::fs::write(&catalog, "good.adapipe\nrotten.adapipe\nmissing.adapipe\n").unwrap();
+
+        // Unencrypted archives are checked in full even when sampling, as
+        // the rotten chunk would decode without error
+        let sampled = use_case().scrub(&catalog, Some(2), None).await.unwrap();
+        assert_eq!(sampled.archives[0].chunks_verified, 4);
+        assert_eq!(sampled.archives[0].chunk_count, 4);
+        assert!(sampled.archives[0].checksum_verified);
+        assert!(sampled.archives[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("Checksum mismatch"));
+        assert!(sampled.archives[2].error.is_some(), "a missing archive fails");
+
+        let full = use_case().scrub(&catalog, None, None).await.unwrap();
+        assert!(full.archives[0].checksum_verified);
+        assert!(full.archives[1].error.as_ref().unwrap().contains("Checksum mismatch"));
+        assert_eq!(full.corrupt().count(), 2);
+
+        let err = use_case().execute(catalog, None, None, false).await.unwrap_err();
+        assert!(err.to_string().contains("2 of 3 archives"), "{}", err);
+    }
+}
@@ -0,0 +1,322 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Self-Test Use Case
+//!
+//! Checks that a deployment can do its job before it is given real work: the
+//! algorithms this build registers produce the expected results, the
+//! database answers, and the temp directory is writable.
+//!
+//! ## Checks
+//!
+//! - **compression**: each supported algorithm compresses a fixed sample
+//!   and decompresses it back to the same bytes
+//! - **encryption**: the published chunk encryption test vectors are
+//!   reproduced in both directions (see `vectors verify`)
+//! - **checksum**: the chunk checksum of `abc` is the FIPS 180-2 SHA-256
+//!   known answer
+//! - **database**: the pipeline repository answers a query
+//! - **temp**: a file can be written, read back and removed in the temp
+//!   directory
+//!
+//! Every check runs even if an earlier one fails, so one run reports every
+//! problem.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::SelfTestUseCase;
+//!
+//! let use_case = SelfTestUseCase::new(pipeline_repository, temp_dir);
+//! use_case.execute(false).await?;
+//! ```
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::info;
+
+use crate::infrastructure::adapters::MultiAlgoCompression;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use crate::infrastructure::services::ChunkTestVectorService;
+use adaptive_pipeline_domain::entities::security_context::{Permission, SecurityContext, SecurityLevel};
+use adaptive_pipeline_domain::services::{CompressionConfig, CompressionService};
+use adaptive_pipeline_domain::value_objects::TestVectorSuite;
+use adaptive_pipeline_domain::{FileChunk, PipelineError, ProcessingContext};
+
+/// Published chunk encryption vectors, checked by the encryption checks
+const ENCRYPTION_VECTORS: &str = include_str!("../../../tests/golden/vectors/chunk-encryption-v2.json");
+
+/// SHA-256 of `abc` (FIPS 180-2, appendix B.1)
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+/// Outcome of one self-test check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfTestCheck {
+    /// Area checked: compression, encryption, checksum, database or temp
+    pub category: String,
+
+    /// What was checked, e.g. `brotli` or `aes-256-gcm/short`
+    pub name: String,
+
+    /// Why the check failed, if it did
+    pub error: Option<String>,
+}
+
+impl SelfTestCheck {
+    fn new(category: &str, name: impl Into<String>, outcome: Result<(), PipelineError>) -> Self {
+        Self {
+            category: category.to_string(),
+            name: name.into(),
+            error: outcome.err().map(|e| e.to_string()),
+        }
+    }
+
+    /// Whether the check passed
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Outcome of a self-test run
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    /// Every check, in the order it ran
+    pub checks: Vec<SelfTestCheck>,
+
+    /// Milliseconds the run took
+    pub duration_ms: u64,
+}
+
+impl SelfTestReport {
+    /// Checks that failed
+    pub fn failures(&self) -> Vec<&SelfTestCheck> {
+        self.checks.iter().filter(|check| !check.passed()).collect()
+    }
+
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(SelfTestCheck::passed)
+    }
+}
+
+/// Use case for checking that this deployment works.
+///
+/// ## Dependencies
+///
+/// - **SqlitePipelineRepository**: The database to query
+/// - **Temp directory**: Where to write the probe file
+pub struct SelfTestUseCase {
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+    temp_dir: PathBuf,
+    compression: MultiAlgoCompression,
+    vectors: ChunkTestVectorService,
+}
+
+impl SelfTestUseCase {
+    /// Creates a new Self-Test use case.
+    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>, temp_dir: PathBuf) -> Self {
+        Self {
+            pipeline_repository,
+            temp_dir,
+            compression: MultiAlgoCompression::new(),
+            vectors: ChunkTestVectorService::new(),
+        }
+    }
+
+    /// Runs every check.
+    pub async fn run(&self) -> SelfTestReport {
+        let started = Instant::now();
+        let mut checks = Vec::new();
+
+        for algorithm in self.compression.supported_algorithms() {
+            let outcome = self.check_compression(CompressionConfig::new(algorithm.clone()));
+            checks.push(SelfTestCheck::new(
+                "compression",
+                algorithm.to_string().to_lowercase(),
+                outcome,
+            ));
+        }
+
+        match TestVectorSuite::from_json(ENCRYPTION_VECTORS) {
+            Ok(suite) => checks.extend(self.vectors.verify(&suite).into_iter().map(|check| SelfTestCheck {
+                category: "encryption".to_string(),
+                name: check.name,
+                error: check.error,
+            })),
+            Err(e) => checks.push(SelfTestCheck::new("encryption", "test vectors", Err(e))),
+        }
+
+        checks.push(SelfTestCheck::new("checksum", "sha256", Self::check_checksum()));
+        checks.push(SelfTestCheck::new("database", "pipelines", self.check_database().await));
+        checks.push(SelfTestCheck::new("temp", "writable", self.check_temp_dir().await));
+
+        SelfTestReport {
+            checks,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Runs every check and prints the report, as a table or with `json` as
+    /// JSON.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// 🩺 Self-test
+    ///    ✅ compression/brotli
+    ///    ✅ encryption/aes-256-gcm/empty
+    ///    ...
+    ///    ✅ temp/writable
+    /// ✅ All 12 checks passed in 7 ms
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error naming the failed checks if any check failed.
+    pub async fn execute(&self, json: bool) -> Result<()> {
+        let report = self.run().await;
+        info!(
+            "Self-test ran {} checks, {} failed",
+            report.checks.len(),
+            report.failures().len()
+        );
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("🩺 Self-test");
+            for check in &report.checks {
+                match &check.error {
+                    None => println!("   ✅ {}/{}", check.category, check.name),
+                    Some(error) => println!("   ❌ {}/{}: {}", check.category, check.name, error),
+                }
+            }
+        }
+
+        let failures = report.failures();
+        if !failures.is_empty() {
+            let names: Vec<String> = failures
+                .iter()
+                .map(|check| format!("{}/{}", check.category, check.name))
+                .collect();
+            return Err(anyhow::anyhow!(
+                "Self-test failed: {} of {} checks failed ({})",
+                failures.len(),
+                report.checks.len(),
+                names.join(", ")
+            ));
+        }
+        if !json {
+            println!(
+                "✅ All {} checks passed in {} ms",
+                report.checks.len(),
+                report.duration_ms
+            );
+        }
+        Ok(())
+    }
+
+    fn check_compression(&self, config: CompressionConfig) -> Result<(), PipelineError> {
+        let sample: Vec<u8> = b"Adaptive Pipeline self-test sample. ".repeat(64);
+        let security_context =
+            SecurityContext::with_permissions(None, vec![Permission::Read, Permission::Write], SecurityLevel::Internal);
+        let mut context = ProcessingContext::new(sample.len() as u64, security_context);
+
+        let chunk = FileChunk::new(0, 0, sample.clone(), true)?;
+        let compressed = self.compression.compress_chunk(chunk, &config, &mut context)?;
+        if compressed.data_len() >= sample.len() {
+            return Err(PipelineError::CompressionError(format!(
+                "compressed {} bytes to {} bytes",
+                sample.len(),
+                compressed.data_len()
+            )));
+        }
+        let restored = self.compression.decompress_chunk(compressed, &config, &mut context)?;
+        if restored.data() != sample.as_slice() {
+            return Err(PipelineError::CompressionError(
+                "decompressing did not reproduce the sample".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_checksum() -> Result<(), PipelineError> {
+        let checksum = FileChunk::new(0, 0, b"abc".to_vec(), true)?.calculate_checksum()?;
+        if checksum != SHA256_ABC {
+            return Err(PipelineError::IntegrityError(format!(
+                "SHA-256 of \"abc\" was {}, expected {}",
+                checksum, SHA256_ABC
+            )));
+        }
+        Ok(())
+    }
+
+    async fn check_database(&self) -> Result<(), PipelineError> {
+        self.pipeline_repository.count().await.map(|_| ())
+    }
+
+    async fn check_temp_dir(&self) -> Result<(), PipelineError> {
+        let probe = self.temp_dir.join(format!("self-test-{}.probe", std::process::id()));
+        let io_error = |what: &str, e: std::io::Error| {
+            PipelineError::io_error(format!("Failed to {} {}: {}", what, probe.display(), e))
+        };
+        let contents = b"adaptive pipeline self-test";
+
+        tokio::fs::write(&probe, contents)
+            .await
+            .map_err(|e| io_error("write", e))?;
+        let read_back = tokio::fs::read(&probe).await.map_err(|e| io_error("read", e));
+        let removed = tokio::fs::remove_file(&probe).await.map_err(|e| io_error("remove", e));
+        if read_back? != contents {
+            return Err(PipelineError::io_error(format!(
+                "{} did not read back what was written",
+                probe.display()
+            )));
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_every_check_passes_and_an_unwritable_temp_dir_fails() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repository = Arc::new(
+            SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+                .await
+                .unwrap(),
+        );
+
+        let report = SelfTestUseCase::new(repository.clone(), dir.path().to_path_buf())
+            .run()
+            .await;
+        assert!(report.passed(), "{:?}", report.failures());
+        for category in ["compression", "encryption", "checksum", "database", "temp"] {
+            assert!(
+                report.checks.iter().any(|check| check.category == category),
+                "{}",
+                category
+            );
+        }
+        let leftovers = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("probe".as_ref()))
+            .count();
+        assert_eq!(leftovers, 0, "the probe is removed");
+
+        let report = SelfTestUseCase::new(repository, dir.path().join("missing")).run().await;
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].category, "temp");
+    }
+}
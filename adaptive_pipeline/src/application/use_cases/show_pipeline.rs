@@ -8,8 +8,8 @@
 //! # Show Pipeline Use Case
 //!
 //! This module implements the use case for displaying detailed information
-//! about a specific pipeline. It retrieves complete pipeline metadata including
-//! stages, configuration, and processing metrics.
+//! about a specific pipeline. It retrieves pipeline details from the read model,
+//! including stages, configuration, and the metrics of the latest run.
 //!
 //! ## Overview
 //!
@@ -20,6 +20,9 @@
 //! - **Stage Breakdown**: Show configuration for each processing stage
 //! - **Metrics Display**: Present processing statistics and performance metrics
 //! - **Configuration View**: Display pipeline-level configuration parameters
+//! - **Graph Export**: Render the stage graph as Graphviz DOT or Mermaid
+//! - **Runtime Plan**: Show the chunk size, worker count, estimated duration
+//!   and resource requirements a run over a given input would use
 //! - **Error Handling**: Handle missing pipelines with clear error messages
 //!
 //! ## Architecture
@@ -27,7 +30,8 @@
 //! Following Clean Architecture and Domain-Driven Design principles:
 //!
 //! - **Use Case Layer**: Orchestrates the detailed display workflow
-//! - **Repository Pattern**: Delegates data access to repository interface
+//! - **Query Side**: Reads a `PipelineDetailsDto` through a query handler
+//!   instead of loading the pipeline aggregate
 //! - **Dependency Inversion**: Depends on abstractions, not implementations
 //! - **Single Responsibility**: Focused solely on displaying pipeline details
 //!
@@ -36,33 +40,88 @@
 //! - Pipelines are looked up by name (user-friendly identifier)
 //! - Missing pipelines return clear error messages
 //! - All stage details are displayed with configuration parameters
-//! - Pipeline metrics show processing history and statistics
+//! - Metrics are those of the latest successful run, if any
 //! - Configuration parameters are displayed if present
+//! - Graphs are built from the pipeline aggregate, so diagrams always match the
+//!   stages that actually run, including the automatic checksum stages
+//! - Plans use the same chunk-size choice as `process`, including the
+//!   pipeline's throughput history, so they show what a run would really do
 //!
 //! ## Usage Examples
 //!
 //! ```rust,ignore
 //! use adaptive_pipeline::application::use_cases::ShowPipelineUseCase;
 //!
-//! let use_case = ShowPipelineUseCase::new(pipeline_repository);
-//! use_case.execute("my-pipeline".to_string()).await?;
+//! let use_case = ShowPipelineUseCase::new(pipeline_repository.clone(), pipeline_repository);
+//! use_case.execute("my-pipeline".to_string(), None).await?;
+//!
+//! // Print a Mermaid flowchart instead of the details
+//! use_case
+//!     .execute("my-pipeline".to_string(), Some(GraphFormat::Mermaid))
+//!     .await?;
+//!
+//! // Print the runtime plan for an input file
+//! let use_case = use_case
+//!     .with_pipeline_service(pipeline_service)
+//!     .with_chunk_size_history(chunk_size_history);
+//! use_case.execute_plan("my-pipeline", Path::new("input.dat")).await?;
 //! ```
 
 use anyhow::Result;
+use byte_unit::Byte;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
 
-use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use crate::application::queries::{PipelineQueryHandler, PipelineReadModel, QueryHandler, ShowPipelineQuery};
+use crate::application::use_cases::ProcessFileUseCase;
+use adaptive_pipeline_domain::repositories::pipeline_repository::PipelineRepository;
+use adaptive_pipeline_domain::repositories::stage_executor::ResourceRequirements;
+use adaptive_pipeline_domain::repositories::ChunkSizeHistoryRepository;
+use adaptive_pipeline_domain::services::PipelineService;
+use adaptive_pipeline_domain::value_objects::worker_count::WorkerCount;
+use adaptive_pipeline_domain::value_objects::GraphFormat;
+
+/// Effective runtime plan for processing one input with one pipeline.
+///
+/// Produced by [`ShowPipelineUseCase::plan`] without reading the input's
+/// contents or writing any output.
+#[derive(Debug, Clone)]
+pub struct ProcessingPlan {
+    /// Name of the planned pipeline
+    pub pipeline: String,
+    /// Input file the plan is for
+    pub input: PathBuf,
+    /// Size of the input file in bytes
+    pub file_size: u64,
+    /// Chunk size a run would use, in bytes
+    pub chunk_size: usize,
+    /// Where the chunk size comes from (`adaptive` or `learned`)
+    pub chunk_size_source: &'static str,
+    /// Number of chunks the input would be split into
+    pub chunk_count: u64,
+    /// Adaptive worker count for the input size
+    pub worker_count: usize,
+    /// Cores available to this process
+    pub available_cores: usize,
+    /// Storage type whose throughput history informed the chunk size
+    pub storage_type: String,
+    /// Estimated processing time
+    pub estimated_duration: Duration,
+    /// Estimated memory, CPU and disk requirements
+    pub requirements: ResourceRequirements,
+}
 
 /// Use case for displaying detailed pipeline information.
 ///
-/// This use case retrieves a specific pipeline by name and displays its
-/// complete metadata, including stages, configuration, and processing metrics.
+/// This use case answers a [`ShowPipelineQuery`] from the pipeline read model
+/// and displays the pipeline's stages, configuration, and latest run metrics.
 /// It provides comprehensive visibility into pipeline structure and behavior.
 ///
 /// ## Responsibilities
 ///
-/// - Look up pipeline by name in repository
+/// - Look up pipeline details by name in the read model
 /// - Format detailed pipeline information for display
 /// - Display all stage configurations and parameters
 /// - Show processing metrics and statistics
@@ -70,19 +129,28 @@ use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineReposito
 ///
 /// ## Dependencies
 ///
-/// - **Pipeline Repository**: For retrieving pipeline data
+/// - **Pipeline Read Model**: For retrieving pipeline details
+/// - **Pipeline Repository**: For loading the aggregate a graph or plan is
+///   built from
+/// - **Pipeline Service** (optional): Estimates duration and resources for
+///   plans
+/// - **Chunk Size History** (optional): Throughput history plans choose the
+///   chunk size from
 ///
 /// ## Example
 ///
 /// ```rust,ignore
-/// let use_case = ShowPipelineUseCase::new(pipeline_repository);
-/// match use_case.execute("compress-encrypt".to_string()).await {
+/// let use_case = ShowPipelineUseCase::new(pipeline_repository.clone(), pipeline_repository.clone());
+/// match use_case.execute("compress-encrypt".to_string(), None).await {
 ///     Ok(()) => println!("Pipeline details displayed"),
 ///     Err(e) => eprintln!("Failed to show pipeline: {}", e),
 /// }
 /// ```
 pub struct ShowPipelineUseCase {
-    pipeline_repository: Arc<SqlitePipelineRepository>,
+    query_handler: PipelineQueryHandler,
+    pipeline_repository: Arc<dyn PipelineRepository>,
+    pipeline_service: Option<Arc<dyn PipelineService>>,
+    chunk_size_history: Option<Arc<dyn ChunkSizeHistoryRepository>>,
 }
 
 impl ShowPipelineUseCase {
@@ -90,13 +158,32 @@ impl ShowPipelineUseCase {
     ///
     /// # Parameters
     ///
-    /// * `pipeline_repository` - Repository for accessing pipeline data
+    /// * `read_model` - Read model for querying pipeline details
+    /// * `pipeline_repository` - Repository the stage graph is loaded from
     ///
     /// # Returns
     ///
     /// A new instance of `ShowPipelineUseCase`
-    pub fn new(pipeline_repository: Arc<SqlitePipelineRepository>) -> Self {
-        Self { pipeline_repository }
+    pub fn new(read_model: Arc<dyn PipelineReadModel>, pipeline_repository: Arc<dyn PipelineRepository>) -> Self {
+        Self {
+            query_handler: PipelineQueryHandler::new(read_model),
+            pipeline_repository,
+            pipeline_service: None,
+            chunk_size_history: None,
+        }
+    }
+
+    /// Estimates plan durations and resources with `pipeline_service`;
+    /// required for [`Self::plan`]
+    pub fn with_pipeline_service(mut self, pipeline_service: Arc<dyn PipelineService>) -> Self {
+        self.pipeline_service = Some(pipeline_service);
+        self
+    }
+
+    /// Chooses plan chunk sizes from `chunk_size_history`, as processing does
+    pub fn with_chunk_size_history(mut self, chunk_size_history: Arc<dyn ChunkSizeHistoryRepository>) -> Self {
+        self.chunk_size_history = Some(chunk_size_history);
+        self
     }
 
     /// Executes the show pipeline use case.
@@ -108,6 +195,8 @@ impl ShowPipelineUseCase {
     /// ## Parameters
     ///
     /// * `pipeline_name` - Name of the pipeline to display
+    /// * `graph` - When set, print only the stage graph in this format, so
+    ///   the output can be piped straight into `dot` or pasted into Markdown
     ///
     /// ## Output Format
     ///
@@ -116,7 +205,7 @@ impl ShowPipelineUseCase {
     /// - Detailed stage information with configurations
     /// - Stage parameters (if present)
     /// - Pipeline-level configuration (if present)
-    /// - Processing metrics (bytes, chunks, errors, warnings)
+    /// - Metrics of the latest run (bytes, chunks, errors, warnings), if any
     ///
     /// ## Returns
     ///
@@ -142,90 +231,234 @@ impl ShowPipelineUseCase {
     /// Updated: 2025-10-05 14:30:00 UTC
     ///
     /// Stages (4):
-    ///   1. input_checksum (Checksum)
+    ///   1. input_checksum (checksum)
     ///      Algorithm: sha256
     ///      Enabled: true
     ///      Order: 0
     ///
-    ///   2. compression (Compression)
+    ///   2. compression (compression)
     ///      Algorithm: brotli
     ///      Enabled: true
     ///      Order: 1
     ///      Parameters:
     ///        level: 6
     ///
-    ///   3. encryption (Encryption)
+    ///   3. encryption (encryption)
     ///      Algorithm: aes256gcm
     ///      Enabled: true
     ///      Order: 2
     ///
-    ///   4. output_checksum (Checksum)
+    ///   4. output_checksum (checksum)
     ///      Algorithm: sha256
     ///      Enabled: true
     ///      Order: 3
     ///
-    /// Metrics:
+    /// Last Run:
+    ///   Completed: 2025-10-05 15:02:11 UTC
     ///   Bytes Processed: 1048576
     ///   Chunks Processed: 16
     ///   Error Count: 0
     ///   Warning Count: 0
     /// ```
-    pub async fn execute(&self, pipeline_name: String) -> Result<()> {
+    pub async fn execute(&self, pipeline_name: String, graph: Option<GraphFormat>) -> Result<()> {
+        if let Some(format) = graph {
+            print!("{}", self.render_graph(&pipeline_name, format).await?);
+            return Ok(());
+        }
+
         info!("Showing pipeline details: {}", pipeline_name);
 
-        // Find pipeline by name (user-friendly lookup)
-        let pipeline = self
-            .pipeline_repository
-            .find_by_name(&pipeline_name)
+        // Find pipeline details by name (user-friendly lookup)
+        let details = self
+            .query_handler
+            .handle(ShowPipelineQuery::new(pipeline_name.clone()))
             .await
             .map_err(|e| anyhow::anyhow!("Failed to query pipeline: {}", e))?
             .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", pipeline_name))?;
+        let summary = &details.summary;
 
         // Display pipeline header
         println!("\n=== Pipeline Details ===");
-        println!("ID: {}", pipeline.id());
-        println!("Name: {}", pipeline.name());
-        println!("Status: {}", pipeline.status());
-        println!("Created: {}", pipeline.created_at().format("%Y-%m-%d %H:%M:%S UTC"));
-        println!("Updated: {}", pipeline.updated_at().format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("ID: {}", summary.id);
+        println!("Name: {}", summary.name);
+        println!("Namespace: {}", summary.namespace);
+        println!("Status: {}", summary.status());
+        println!("Created: {}", summary.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("Updated: {}", summary.updated_at.format("%Y-%m-%d %H:%M:%S UTC"));
 
         // Display stages
-        println!("\nStages ({}):", pipeline.stages().len());
-        for (index, stage) in pipeline.stages().iter().enumerate() {
-            println!("  {}. {} ({:?})", index + 1, stage.name(), stage.stage_type());
-            println!("     Algorithm: {}", stage.configuration().algorithm);
-            println!("     Enabled: {}", stage.is_enabled());
-            println!("     Order: {}", stage.order());
+        println!("\nStages ({}):", details.stages.len());
+        for (index, stage) in details.stages.iter().enumerate() {
+            println!("  {}. {} ({})", index + 1, stage.name, stage.stage_type);
+            println!("     Algorithm: {}", stage.algorithm);
+            println!("     Enabled: {}", stage.enabled);
+            println!("     Order: {}", stage.order);
 
             // Display stage parameters if present
-            if !stage.configuration().parameters.is_empty() {
+            if !stage.parameters.is_empty() {
                 println!("     Parameters:");
-                for (key, value) in &stage.configuration().parameters {
+                for (key, value) in &stage.parameters {
                     println!("       {}: {}", key, value);
                 }
             }
 
             // Add spacing between stages
-            if index < pipeline.stages().len() - 1 {
+            if index < details.stages.len() - 1 {
                 println!();
             }
         }
 
         // Display pipeline-level configuration if present
-        if !pipeline.configuration().is_empty() {
+        if !details.configuration.is_empty() {
             println!("\nConfiguration:");
-            for (key, value) in pipeline.configuration() {
+            for (key, value) in &details.configuration {
                 println!("  {}: {}", key, value);
             }
         }
 
-        // Display processing metrics
-        let metrics = pipeline.metrics();
-        println!("\nMetrics:");
-        println!("  Bytes Processed: {}", metrics.bytes_processed());
-        println!("  Chunks Processed: {}", metrics.chunks_processed());
-        println!("  Error Count: {}", metrics.error_count());
-        println!("  Warning Count: {}", metrics.warning_count());
+        // Display metrics of the latest run
+        println!("\nLast Run:");
+        match &details.last_execution {
+            Some(execution) => {
+                if let Some(completed_at) = execution.completed_at {
+                    println!("  Completed: {}", completed_at.format("%Y-%m-%d %H:%M:%S UTC"));
+                }
+                println!("  Bytes Processed: {}", execution.bytes_processed);
+                println!("  Chunks Processed: {}", execution.chunks_processed);
+                println!("  Error Count: {}", execution.error_count);
+                println!("  Warning Count: {}", execution.warning_count);
+            }
+            None => println!("  Never run"),
+        }
+
+        Ok(())
+    }
+
+    /// Renders the stage graph of a pipeline.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the pipeline does not exist or cannot be loaded.
+    pub async fn render_graph(&self, pipeline_name: &str, format: GraphFormat) -> Result<String> {
+        info!("Rendering {:?} graph of pipeline: {}", format, pipeline_name);
+
+        let pipeline = self
+            .pipeline_repository
+            .find_by_name(pipeline_name)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load pipeline: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", pipeline_name))?;
+
+        Ok(pipeline.stage_graph().render(format))
+    }
+
+    /// Computes the runtime plan for processing `input` with a pipeline.
+    ///
+    /// Only the input's size is read. The chunk size is chosen exactly as
+    /// `process` would choose it without a `--chunk-size` override; duration
+    /// and resources come from the pipeline service's estimates.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no pipeline service is configured, the pipeline
+    /// does not exist, or the input cannot be read.
+    pub async fn plan(&self, pipeline_name: &str, input: &Path) -> Result<ProcessingPlan> {
+        let pipeline_service = self
+            .pipeline_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Runtime planning requires a pipeline service"))?;
+
+        let pipeline = self
+            .pipeline_repository
+            .find_by_name(pipeline_name)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load pipeline: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("Pipeline not found: {}", pipeline_name))?;
+
+        let file_size = tokio::fs::metadata(input)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read input file {}: {}", input.display(), e))?
+            .len();
+
+        let storage_type = ProcessFileUseCase::storage_type_label();
+        let history = match &self.chunk_size_history {
+            Some(chunk_size_history) => chunk_size_history
+                .history(pipeline.id(), &storage_type)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(pipeline = %pipeline_name, "Failed to load chunk size history: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+        let (chunk_size, chunk_size_source) =
+            ProcessFileUseCase::determine_chunk_size(file_size, None, pipeline.chunk_size()?, &history);
+
+        let estimated_duration = pipeline_service.estimate_processing_time(&pipeline, file_size).await?;
+        let requirements = pipeline_service.get_resource_requirements(&pipeline, file_size).await?;
+
+        Ok(ProcessingPlan {
+            pipeline: pipeline.name().to_string(),
+            input: input.to_path_buf(),
+            file_size,
+            chunk_size,
+            chunk_size_source,
+            chunk_count: file_size.div_ceil(chunk_size as u64),
+            worker_count: pipeline
+                .worker_count()?
+                .unwrap_or_else(|| WorkerCount::optimal_for_file_size(file_size))
+                .count(),
+            available_cores: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            storage_type,
+            estimated_duration,
+            requirements,
+        })
+    }
+
+    /// Prints the runtime plan for processing `input` with a pipeline.
+    ///
+    /// ## Example Output
+    ///
+    /// ```text
+    /// === Runtime Plan: compress-encrypt ===
+    /// Input: /data/backup.tar (1.2 GiB)
+    /// Storage: ssd
+    ///
+    /// Chunk Size: 16.0 MiB (adaptive)
+    /// Chunks: 77
+    /// Workers: 8 (16 cores available)
+    /// Estimated Duration: 37.2s
+    ///
+    /// Resource Requirements:
+    ///   Memory: 36.0 MiB
+    ///   CPU Cores: 1
+    ///   Disk Space: 2.4 GiB
+    /// ```
+    pub async fn execute_plan(&self, pipeline_name: &str, input: &Path) -> Result<()> {
+        info!("Planning pipeline {} for {}", pipeline_name, input.display());
+        let plan = self.plan(pipeline_name, input).await?;
+        let size = |bytes: u64| Byte::from_u64(bytes).get_appropriate_unit(byte_unit::UnitType::Binary);
+
+        println!("\n=== Runtime Plan: {} ===", plan.pipeline);
+        println!("Input: {} ({:.1})", plan.input.display(), size(plan.file_size));
+        println!("Storage: {}", plan.storage_type);
+        println!();
+        println!(
+            "Chunk Size: {:.1} ({})",
+            size(plan.chunk_size as u64),
+            plan.chunk_size_source
+        );
+        println!("Chunks: {}", plan.chunk_count);
+        println!(
+            "Workers: {} ({} cores available)",
+            plan.worker_count, plan.available_cores
+        );
+        println!("Estimated Duration: {:.1?}", plan.estimated_duration);
+        println!();
+        println!("Resource Requirements:");
+        println!("  Memory: {:.1}", size(plan.requirements.memory_bytes));
+        println!("  CPU Cores: {}", plan.requirements.cpu_cores);
+        println!("  Disk Space: {:.1}", size(plan.requirements.disk_space_bytes));
 
         Ok(())
     }
@@ -233,6 +466,58 @@ impl ShowPipelineUseCase {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::infrastructure::metrics::MetricsService;
+    use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+    use crate::infrastructure::runtime::StageRegistry;
+    use adaptive_pipeline_domain::entities::{PipelineStage, StageConfiguration, StageType};
+    use adaptive_pipeline_domain::Pipeline;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_plan_reports_chunking_and_estimates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repository = Arc::new(
+            SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+                .await
+                .unwrap(),
+        );
+        let stage = PipelineStage::new(
+            "compression".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            1,
+        )
+        .unwrap();
+        repository
+            .save(&Pipeline::new("planned".to_string(), vec![stage]).unwrap())
+            .await
+            .unwrap();
+        let input = dir.path().join("input.bin");
+        std::fs::write(&input, vec![7u8; 3 * 1024 * 1024]).unwrap();
+
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let pipeline_service = ProcessFileUseCase::create_pipeline_service(
+            &metrics_service,
+            &repository,
+            None,
+            None,
+            false,
+            None,
+            &StageRegistry::builtin(metrics_service.clone()),
+        );
+        let use_case = ShowPipelineUseCase::new(repository.clone(), repository.clone());
+        assert!(use_case.plan("planned", &input).await.is_err());
+
+        let use_case = use_case.with_pipeline_service(Arc::new(pipeline_service));
+        let plan = use_case.plan("planned", &input).await.unwrap();
+        assert_eq!(plan.file_size, 3 * 1024 * 1024);
+        assert_eq!(plan.chunk_size_source, "adaptive");
+        assert_eq!(plan.chunk_count, plan.file_size.div_ceil(plan.chunk_size as u64));
+        assert!(plan.worker_count >= 1);
+        assert_eq!(plan.requirements.disk_space_bytes, 2 * plan.file_size);
+        assert!(use_case.plan("missing", &input).await.is_err());
+    }
 
     // Note: Tests for use cases typically use mock repositories
     // Full integration tests should use real repositories in tests/integration/
@@ -33,30 +33,110 @@
 //!
 //! **Full Validation** (`--full` flag):
 //! - All basic validation checks
-//! - Streaming decompression/decryption
-//! - Original file checksum verification
-//! - Chunk-by-chunk integrity validation
+//! - Re-derives each recorded processing step and confirms this build can
+//!   instantiate its reverse stage (algorithm registered, parameters valid)
+//! - Streams every chunk through decryption/decompression without writing
+//!   the restored data, so each chunk is authenticated and decoded
+//! - Chunk count, original size and original checksum verification
+//!
+//! Either level produces a [`FileValidationReport`], printed as text or,
+//! with `--json`, as JSON.
 //!
 //! ## Usage Examples
 //!
 //! ```rust,ignore
 //! use adaptive_pipeline::application::use_cases::ValidateFileUseCase;
 //!
-//! let use_case = ValidateFileUseCase::new();
+//! let use_case = ValidateFileUseCase::new(metrics_service);
 //!
 //! // Basic validation
-//! use_case.execute(file_path, false).await?;
+//! use_case.execute(file_path, false, false).await?;
+//!
+//! // Full streaming validation, reported as JSON
+//! use_case.execute(file_path, true, true).await?;
 //!
-//! // Full streaming validation
-//! use_case.execute(file_path, true).await?;
+//! // The report itself
+//! let report = use_case.validate(file_path, true).await?;
+//! assert!(report.is_valid());
 //! ```
 
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::value_objects::binary_file_format::{
+    FileHeader, ProcessingStepType, CURRENT_FORMAT_VERSION,
+};
 use anyhow::Result;
 use byte_unit::Byte;
+use serde::Serialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::info;
 
-use crate::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+use crate::application::use_cases::restore_file::{create_restoration_pipeline, restoration_stage};
+use crate::application::use_cases::RestoreFileUseCase;
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::runtime::StageRegistry;
+use crate::infrastructure::services::{is_remote_location, open_source, AdapipeFormat, BinaryFormatService};
+
+/// Whether this build can reverse one recorded processing step
+#[derive(Debug, Clone, Serialize)]
+pub struct StepValidation {
+    /// Order in which the step was applied
+    pub order: u32,
+    /// Recorded step type
+    pub step_type: ProcessingStepType,
+    /// Recorded algorithm
+    pub algorithm: String,
+    /// True if the reverse stage can be instantiated (checksum steps are
+    /// always restorable)
+    pub restorable: bool,
+    /// Why the reverse stage can't be instantiated
+    pub error: Option<String>,
+}
+
+/// What streaming every chunk through the restoration stages found
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkValidation {
+    /// Chunk count recorded in the header
+    pub chunks_expected: u32,
+    /// Chunks read, authenticated and decoded
+    pub chunks_verified: u32,
+    /// Original size recorded in the header
+    pub bytes_expected: u64,
+    /// Size of the restored data
+    pub bytes_restored: u64,
+    /// Original checksum recorded in the header (empty for archives that
+    /// don't record one)
+    pub checksum_expected: String,
+    /// Checksum of the restored data, with the header's hash
+    pub checksum_calculated: String,
+}
+
+/// Structured result of validating an `.adapipe` file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileValidationReport {
+    /// The validated file
+    pub file: PathBuf,
+    /// The file's header
+    pub header: FileHeader,
+    /// True if this build reads the header's format version
+    pub format_version_supported: bool,
+    /// True if the steps and chunks were checked (`--full`)
+    pub full: bool,
+    /// Per-step results (full validation only)
+    pub steps: Vec<StepValidation>,
+    /// Chunk streaming results (full validation only, and only once every
+    /// step is restorable and the stream completed)
+    pub chunks: Option<ChunkValidation>,
+    /// Every problem found; empty for a valid file
+    pub errors: Vec<String>,
+}
+
+impl FileValidationReport {
+    /// True if no problems were found
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
 
 /// Use case for validating .adapipe binary format files.
 ///
@@ -70,29 +150,49 @@ use crate::infrastructure::services::{AdapipeFormat, BinaryFormatService};
 /// - Verify binary format structure
 /// - Read and validate metadata
 /// - Display file properties and processing history
-/// - Optionally perform full streaming validation
+/// - Optionally re-derive the processing steps and stream every chunk
 ///
 /// ## Dependencies
 ///
 /// - **BinaryFormatService**: For format validation and metadata reading
-pub struct ValidateFileUseCase;
+/// - **RestoreFileUseCase**: For the stage registry and chunk streaming
+///   that restores use, so validation checks exactly what restore will do
+pub struct ValidateFileUseCase {
+    restore: RestoreFileUseCase,
+}
 
 impl ValidateFileUseCase {
     /// Creates a new Validate File use case.
-    pub fn new() -> Self {
-        Self
+    ///
+    /// # Parameters
+    ///
+    /// * `metrics_service` - Metrics collection service for the restoration
+    ///   stages
+    pub fn new(metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            restore: RestoreFileUseCase::new(metrics_service),
+        }
+    }
+
+    /// Validates through `stage_registry`; see
+    /// [`RestoreFileUseCase::with_stage_registry`]
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.restore = self.restore.with_stage_registry(stage_registry);
+        self
     }
 
     /// Executes the validate file use case.
     ///
     /// Validates an `.adapipe` binary format file, checking structure,
-    /// metadata, and optionally performing full streaming validation.
+    /// metadata, and optionally performing full streaming validation, then
+    /// prints the report.
     ///
     /// ## Parameters
     ///
     /// * `file_path` - Path to .adapipe file to validate
     /// * `full_validation` - If true, perform comprehensive streaming
     ///   validation
+    /// * `json` - If true, print the report as pretty-printed JSON
     ///
     /// ## Validation Steps
     ///
@@ -117,11 +217,10 @@ impl ValidateFileUseCase {
     ///   - Processing steps summary
     ///
     /// **Step 3: Full Validation** (if requested)
-    /// - Stream through all chunks
-    /// - Decrypt encrypted data (if applicable)
-    /// - Decompress compressed data (if applicable)
-    /// - Verify original file checksum
-    /// - Note: Currently marked as TODO pending restoration service refactoring
+    /// - Re-derive the reverse stage of every recorded step and check this
+    ///   build can instantiate it
+    /// - Stream through all chunks, decrypting and decompressing each
+    /// - Verify chunk count, original size and original checksum
     ///
     /// ## Returns
     ///
@@ -135,8 +234,9 @@ impl ValidateFileUseCase {
     /// - Invalid file extension (warning only)
     /// - Corrupt binary format
     /// - Invalid metadata
-    /// - Checksum mismatch (full validation)
     /// - Unsupported format version
+    /// - Steps this build can't reverse (full validation)
+    /// - Corrupt chunks or checksum mismatch (full validation)
     ///
     /// ## Example Output
     ///
@@ -148,7 +248,7 @@ impl ValidateFileUseCase {
     ///    Original filename: data.txt
     ///    Original size: 1.048 MB
     ///    Original checksum: abc123...
-    ///    Format version: 1
+    ///    Format version: 1 (supported)
     ///    App version: 1.0.1
     ///    Chunk size: 64.0 KB
     ///    Chunk count: 16
@@ -158,49 +258,202 @@ impl ValidateFileUseCase {
     ///    🔒 Encryption: aes256gcm
     ///    🔄 Processing steps: compression -> encryption -> checksum
     ///
-    /// 💡 Use --full flag for complete streaming validation (decrypt/decompress/verify)
+    /// 🔄 Re-deriving processing steps...
+    ///    ✅ Step 0: Compression (brotli)
+    ///    ✅ Step 1: Encryption (aes256gcm)
+    ///
+    /// 🔄 Streaming every chunk through the restoration stages...
+    ///    ✅ Chunks: 16/16
+    ///    ✅ Size: 1048576 bytes
+    ///    ✅ Checksum verified: abc123...
     ///
     /// ✅ .adapipe file validation completed successfully!
     /// ```
-    pub async fn execute(&self, file_path: PathBuf, full_validation: bool) -> Result<()> {
+    pub async fn execute(&self, file_path: PathBuf, full_validation: bool, json: bool) -> Result<()> {
+        let report = self.validate(file_path, full_validation).await?;
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            Self::print_report(&report);
+        }
+
+        if !report.is_valid() {
+            return Err(anyhow::anyhow!(
+                "{} failed validation: {}",
+                report.file.display(),
+                report.errors.join("; ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validates an `.adapipe` file and returns the report without printing
+    /// it.
+    ///
+    /// Problems found in a readable file are collected in the report's
+    /// `errors`; only files whose format or header can't be read at all
+    /// fail outright.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is missing, can't be opened, or isn't a
+    /// well-formed `.adapipe` file.
+    pub async fn validate(&self, file_path: PathBuf, full_validation: bool) -> Result<FileValidationReport> {
         info!("Validating .adapipe file: {}", file_path.display());
 
+        // Remote sources (http://, s3://) are read through ranged requests
+        let location = file_path.to_string_lossy().to_string();
+        let remote = is_remote_location(&location);
+
         // Check file exists
-        if !file_path.exists() {
+        if !remote && !file_path.exists() {
             return Err(anyhow::anyhow!("File does not exist: {}", file_path.display()));
         }
 
-        // Warn if file doesn't have .adapipe extension
-        if file_path.extension().is_none_or(|ext| ext != "adapipe") {
-            println!("Warning: File does not have .adapipe extension");
-        }
-
         let binary_format_service = AdapipeFormat::new();
+        let source = open_source(&location).map_err(|e| anyhow::anyhow!("Cannot open {}: {}", location, e))?;
 
         // Step 1: Basic format validation
-        println!("🔍 Validating .adapipe file format...");
         let validation_result = binary_format_service
-            .validate_file(&file_path)
+            .validate_source(source.clone())
             .await
             .map_err(|e| anyhow::anyhow!("Format validation failed: {}", e))?;
 
         if !validation_result.is_valid {
-            println!("❌ File format validation failed!");
-            for error in &validation_result.errors {
-                println!("   Error: {}", error);
+            return Err(anyhow::anyhow!(
+                "Invalid .adapipe file format: {}",
+                validation_result.errors.join("; ")
+            ));
+        }
+
+        // Step 2: Read metadata
+        let header = binary_format_service
+            .create_reader_from(source)
+            .await
+            .and_then(|reader| reader.read_header())
+            .map_err(|e| anyhow::anyhow!("Failed to read metadata: {}", e))?;
+
+        let mut errors = Vec::new();
+        let format_version_supported = (1..=CURRENT_FORMAT_VERSION).contains(&header.format_version);
+        if !format_version_supported {
+            errors.push(format!(
+                "Format version {} is not supported (this build reads 1 to {})",
+                header.format_version, CURRENT_FORMAT_VERSION
+            ));
+        }
+
+        let mut report = FileValidationReport {
+            file: file_path,
+            header,
+            format_version_supported,
+            full: full_validation,
+            steps: Vec::new(),
+            chunks: None,
+            errors,
+        };
+
+        // Step 3: Full validation (if requested)
+        if full_validation {
+            self.validate_steps(&mut report).await;
+            if report.is_valid() {
+                self.validate_chunks(&mut report).await;
             }
-            return Err(anyhow::anyhow!("Invalid .adapipe file format"));
         }
 
+        Ok(report)
+    }
+
+    /// Re-derives the reverse stage of every recorded step and checks this
+    /// build can instantiate it
+    async fn validate_steps(&self, report: &mut FileValidationReport) {
+        for step in &report.header.processing_steps {
+            let checked = match restoration_stage(step, &report.header) {
+                // Checksum steps are verified against the restored data
+                Ok(None) => Ok(()),
+                Ok(Some(stage)) => self.restore.check_stage(&stage).await,
+                Err(e) => Err(e),
+            };
+            let error = checked.err().map(|e| e.to_string());
+            if let Some(error) = &error {
+                report.errors.push(format!(
+                    "Step {} ({:?}, {}) can't be restored: {}",
+                    step.order, step.step_type, step.algorithm, error
+                ));
+            }
+            report.steps.push(StepValidation {
+                order: step.order,
+                step_type: step.step_type.clone(),
+                algorithm: step.algorithm.clone(),
+                restorable: error.is_none(),
+                error,
+            });
+        }
+    }
+
+    /// Streams every chunk through the restoration stages and checks the
+    /// chunk count, size and checksum of the result
+    async fn validate_chunks(&self, report: &mut FileValidationReport) {
+        let header = &report.header;
+        let streamed = match create_restoration_pipeline(header).await {
+            Ok(pipeline) => self.restore.verify_stream(&report.file, &pipeline, header).await,
+            Err(e) => Err(e),
+        };
+        let (bytes_restored, chunks_verified, checksum_calculated) = match streamed {
+            Ok(streamed) => streamed,
+            Err(e) => {
+                report.errors.push(format!("Chunk verification failed: {}", e));
+                return;
+            }
+        };
+
+        let chunks = ChunkValidation {
+            chunks_expected: header.chunk_count,
+            chunks_verified,
+            bytes_expected: header.original_size,
+            bytes_restored,
+            checksum_expected: header.original_checksum.clone(),
+            checksum_calculated,
+        };
+
+        if chunks.chunks_verified != chunks.chunks_expected {
+            report.errors.push(format!(
+                "Chunk count mismatch: header records {}, file contains {}",
+                chunks.chunks_expected, chunks.chunks_verified
+            ));
+        }
+        if chunks.bytes_restored != chunks.bytes_expected {
+            report.errors.push(format!(
+                "Size mismatch: header records {} bytes, chunks restore to {} bytes",
+                chunks.bytes_expected, chunks.bytes_restored
+            ));
+        }
+        if !chunks.checksum_expected.is_empty()
+            && !constant_time_eq_str(&chunks.checksum_calculated, &chunks.checksum_expected)
+        {
+            report.errors.push(format!(
+                "Checksum mismatch: expected {}, got {}",
+                chunks.checksum_expected, chunks.checksum_calculated
+            ));
+        }
+
+        report.chunks = Some(chunks);
+    }
+
+    /// Prints the report in the human-readable format
+    fn print_report(report: &FileValidationReport) {
+        let metadata = &report.header;
+
+        // Warn if file doesn't have .adapipe extension
+        if report.file.extension().is_none_or(|ext| ext != "adapipe") {
+            println!("Warning: File does not have .adapipe extension");
+        }
+
+        println!("🔍 Validating .adapipe file format...");
         println!("✅ File format is valid");
 
-        // Step 2: Read and display metadata
         println!("\n📋 Reading file metadata...");
-        let metadata = binary_format_service
-            .read_metadata(&file_path)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to read metadata: {}", e))?;
-
         println!("   Original filename: {}", metadata.original_filename);
         println!(
             "   Original size: {}",
@@ -208,8 +461,19 @@ impl ValidateFileUseCase {
                 .unwrap_or_default()
                 .get_appropriate_unit(byte_unit::UnitType::Decimal)
         );
-        println!("   Original checksum: {}", metadata.original_checksum);
-        println!("   Format version: {}", metadata.format_version);
+        println!(
+            "   Original checksum: {} ({})",
+            metadata.original_checksum, metadata.checksum_algorithm
+        );
+        println!(
+            "   Format version: {} ({})",
+            metadata.format_version,
+            if report.format_version_supported {
+                "supported"
+            } else {
+                "unsupported"
+            }
+        );
         println!("   App version: {}", metadata.app_version);
         println!(
             "   Chunk size: {}",
@@ -219,6 +483,9 @@ impl ValidateFileUseCase {
         );
         println!("   Chunk count: {}", metadata.chunk_count);
         println!("   Pipeline ID: {}", metadata.pipeline_id);
+        if metadata.is_fips_mode() {
+            println!("   FIPS mode: yes");
+        }
         println!(
             "   Processed at: {}",
             metadata.processed_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -247,37 +514,95 @@ impl ValidateFileUseCase {
             println!("   🔄 Processing steps: {}", metadata.get_processing_summary());
         }
 
-        // Step 3: Full streaming validation (if requested)
-        if full_validation {
-            println!("\n🔄 Performing full streaming validation...");
-            println!("   This will decrypt, decompress, and verify the original checksum");
-            println!("   No temporary files will be created (streaming validation)");
-            println!("   Expected original checksum: {}", metadata.original_checksum);
-
-            // TODO: Full streaming validation not yet implemented
-            // The restoration service was removed. This needs to be reimplemented using
-            // use_cases::restore_file directly for streaming validation.
-            println!("   ⚠️  Full streaming validation not yet implemented");
-            println!("   (Restoration service refactoring in progress)");
+        if report.full {
+            if !report.steps.is_empty() {
+                println!("\n🔄 Re-deriving processing steps...");
+                for step in &report.steps {
+                    match &step.error {
+                        None => println!("   ✅ Step {}: {:?} ({})", step.order, step.step_type, step.algorithm),
+                        Some(error) => println!(
+                            "   ❌ Step {}: {:?} ({}): {}",
+                            step.order, step.step_type, step.algorithm, error
+                        ),
+                    }
+                }
+            }
+
+            if let Some(chunks) = &report.chunks {
+                let mark = |ok: bool| if ok { "✅" } else { "❌" };
+                println!("\n🔄 Streaming every chunk through the restoration stages...");
+                println!(
+                    "   {} Chunks: {}/{}",
+                    mark(chunks.chunks_verified == chunks.chunks_expected),
+                    chunks.chunks_verified,
+                    chunks.chunks_expected
+                );
+                println!(
+                    "   {} Size: {} bytes",
+                    mark(chunks.bytes_restored == chunks.bytes_expected),
+                    chunks.bytes_restored
+                );
+                if chunks.checksum_expected.is_empty() {
+                    println!("   ⚠️  No original checksum recorded; restored data not verified");
+                } else {
+                    let verified = constant_time_eq_str(&chunks.checksum_calculated, &chunks.checksum_expected);
+                    println!(
+                        "   {} Checksum {}: {}",
+                        mark(verified),
+                        if verified { "verified" } else { "mismatch" },
+                        chunks.checksum_calculated
+                    );
+                }
+            }
         } else {
             println!("\n💡 Use --full flag for complete streaming validation (decrypt/decompress/verify)");
         }
 
-        println!("\n✅ .adapipe file validation completed successfully!");
-
-        Ok(())
-    }
-}
-
-impl Default for ValidateFileUseCase {
-    fn default() -> Self {
-        Self::new()
+        if report.is_valid() {
+            println!("\n✅ .adapipe file validation completed successfully!");
+        } else {
+            println!("\n❌ .adapipe file validation failed!");
+            for error in &report.errors {
+                println!("   Error: {}", error);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use adaptive_pipeline_domain::value_objects::binary_file_format::ChunkFormat;
+    use sha2::{Digest, Sha256};
+    use std::collections::HashMap;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn use_case() -> ValidateFileUseCase {
+        ValidateFileUseCase::new(Arc::new(MetricsService::new().unwrap()))
+    }
+
+    /// Writes a single-chunk archive of `data` with the given header
+    async fn write_archive(dir: &Path, data: &[u8], header: FileHeader) -> PathBuf {
+        let archive = dir.join("original.txt.adapipe");
+        let header = header
+            .with_chunk_info(data.len() as u32, 1)
+            .with_pipeline_id("validate-test".to_string());
+
+        let service = AdapipeFormat::new();
+        let mut writer = service.create_writer(&archive, header.clone()).await.unwrap();
+        writer.write_chunk(ChunkFormat::new([0u8; 12], data.to_vec())).unwrap();
+        writer.finalize(header).await.unwrap();
+        archive
+    }
+
+    fn header_for(data: &[u8]) -> FileHeader {
+        FileHeader::new(
+            "original.txt".to_string(),
+            data.len() as u64,
+            format!("{:x}", Sha256::digest(data)),
+        )
+    }
 
     #[tokio::test]
     #[ignore] // Requires test .adapipe files
@@ -295,10 +620,58 @@ mod tests {
 
     #[tokio::test]
     async fn test_validate_missing_file() {
-        let use_case = ValidateFileUseCase::new();
+        let use_case = use_case();
         let result = use_case
-            .execute(PathBuf::from("/nonexistent/file.adapipe"), false)
+            .execute(PathBuf::from("/nonexistent/file.adapipe"), false, false)
             .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_full_validation_streams_every_chunk() {
+        let dir = TempDir::new().unwrap();
+        let data = b"validate me chunk by chunk".to_vec();
+        let archive = write_archive(dir.path(), &data, header_for(&data).add_checksum_step("sha256")).await;
+
+        let report = use_case().validate(archive, true).await.unwrap();
+        assert!(report.is_valid(), "{:?}", report.errors);
+        assert!(report.format_version_supported);
+        assert_eq!(report.steps.len(), 1);
+        assert!(report.steps[0].restorable);
+        let chunks = report.chunks.unwrap();
+        assert_eq!((chunks.chunks_verified, chunks.chunks_expected), (1, 1));
+        assert_eq!(chunks.bytes_restored, data.len() as u64);
+        assert_eq!(chunks.checksum_calculated, chunks.checksum_expected);
+    }
+
+    #[tokio::test]
+    async fn test_full_validation_reports_unrestorable_steps() {
+        let dir = TempDir::new().unwrap();
+        let data = b"written by a newer build".to_vec();
+        let header = header_for(&data).add_custom_step("future", "future-codec", HashMap::new());
+        let archive = write_archive(dir.path(), &data, header).await;
+
+        // Basic validation only reads the header
+        assert!(use_case().validate(archive.clone(), false).await.unwrap().is_valid());
+
+        let report = use_case().validate(archive, true).await.unwrap();
+        assert!(!report.is_valid());
+        assert!(!report.steps[0].restorable);
+        assert!(report.steps[0].error.as_ref().unwrap().contains("future-codec"));
+        // Chunks aren't streamed through stages known to be missing
+        assert!(report.chunks.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_full_validation_reports_checksum_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let data = b"tampered".to_vec();
+        let header = FileHeader::new("original.txt".to_string(), data.len() as u64, "0".repeat(64));
+        let archive = write_archive(dir.path(), &data, header).await;
+
+        let report = use_case().validate(archive.clone(), true).await.unwrap();
+        assert!(!report.is_valid());
+        assert!(report.errors[0].contains("Checksum mismatch"));
+        assert!(use_case().execute(archive, true, true).await.is_err());
+    }
 }
@@ -0,0 +1,150 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Verify Manifest Use Case
+//!
+//! Verifies a detached `.adapipe.manifest` written by `process --manifest`.
+//! Auditors can check the signature without the archive; when the archive
+//! is supplied its size and SHA-256 are also compared with the manifest.
+//!
+//! A signature only establishes who signed when the signer's public key is
+//! pinned. Without one the signature is still checked, but the result is
+//! reported as "signer not pinned" and the verification fails.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::application::use_cases::VerifyManifestUseCase;
+//!
+//! let use_case = VerifyManifestUseCase::new();
+//!
+//! // Signature only, pinned to a known signer
+//! use_case.execute(manifest_path, None, Some(public_key_hex)).await?;
+//!
+//! // Signature and archive contents
+//! use_case
+//!     .execute(manifest_path, Some(archive_path), Some(public_key_hex))
+//!     .await?;
+//! ```
+
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::infrastructure::services::manifest_signer::{verify_archive, verify_manifest, SignerStatus};
+use adaptive_pipeline_domain::value_objects::ProcessingManifest;
+use adaptive_pipeline_domain::PipelineError;
+
+/// Use case for verifying processing manifests.
+///
+/// ## Dependencies
+///
+/// - **ManifestSigner**: Ed25519 signature verification
+pub struct VerifyManifestUseCase;
+
+impl VerifyManifestUseCase {
+    /// Creates a new Verify Manifest use case.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Executes the verify manifest use case.
+    ///
+    /// ## Parameters
+    ///
+    /// * `manifest_path` - Path to the `.adapipe.manifest` file
+    /// * `archive` - Optional `.adapipe` file to compare against the manifest
+    /// * `public_key` - Optional hex Ed25519 public key the manifest must be
+    ///   signed with
+    ///
+    /// ## Errors
+    ///
+    /// Returns errors for:
+    /// - Unreadable or malformed manifest
+    /// - Unsigned manifest, bad signature or untrusted signer
+    /// - No `public_key`, so the signer is not pinned
+    /// - Archive size or checksum mismatch
+    pub async fn execute(
+        &self,
+        manifest_path: PathBuf,
+        archive: Option<PathBuf>,
+        public_key: Option<String>,
+    ) -> Result<()> {
+        info!("Verifying manifest: {}", manifest_path.display());
+
+        let json = tokio::fs::read_to_string(&manifest_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read manifest {}: {}", manifest_path.display(), e))?;
+        let manifest = ProcessingManifest::from_json(&json)?;
+
+        println!("📜 {}", manifest_path.display());
+        println!("   Input: {} ({} bytes)", manifest.input_file, manifest.input_size);
+        println!(
+            "   Input checksum: {} ({})",
+            manifest.input_checksum, manifest.input_checksum_algorithm
+        );
+        println!("   Output: {} ({} bytes)", manifest.output_file, manifest.output_size);
+        println!("   Output checksum: {}", manifest.output_checksum);
+        println!("   Pipeline: {} ({})", manifest.pipeline_name, manifest.pipeline_id);
+        println!("   Stages: {}", manifest.stages.join(" -> "));
+        println!(
+            "   Processed: {} - {}",
+            manifest.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            manifest.completed_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        if let Some(provenance) = &manifest.provenance {
+            println!("   Produced by: {}", provenance);
+        }
+
+        let signer = verify_manifest(&manifest, public_key.as_deref())?;
+        let signer_key = manifest
+            .signature
+            .as_ref()
+            .map(|signature| format!("{} key {}", signature.algorithm, signature.public_key))
+            .unwrap_or_default();
+        match signer {
+            SignerStatus::Pinned => println!("✅ Signature valid, signer pinned ({})", signer_key),
+            SignerStatus::NotPinned => println!("⚠️  Signature valid, signer NOT pinned ({})", signer_key),
+        }
+
+        if let Some(archive) = archive {
+            verify_archive(&manifest, &archive).await?;
+            println!("✅ Archive matches manifest: {}", archive.display());
+        }
+
+        if signer == SignerStatus::NotPinned {
+            // Anyone can sign a manifest with a key of their own
+            return Err(PipelineError::IntegrityError(format!(
+                "Signer not pinned: the manifest is signed by {}, which nothing vouches for; pass --public-key with \
+                 the expected signer's key",
+                signer_key
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for VerifyManifestUseCase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_verify_missing_manifest() {
+        let use_case = VerifyManifestUseCase::new();
+        let result = use_case
+            .execute(PathBuf::from("/nonexistent/file.adapipe.manifest"), None, None)
+            .await;
+        assert!(result.is_err());
+    }
+}
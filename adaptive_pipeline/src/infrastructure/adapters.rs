@@ -28,10 +28,17 @@
 //!
 //! ```text
 //! adapters/
+//! ├── archive_fs.rs                # Read-only FUSE view of an archive
+//! ├── auth/                        # API authentication providers
+//! ├── checksum.rs                  # Whole-file checksum implementations
 //! ├── chunk_processor_adapters.rs  # Chunk processing implementations
 //! ├── compression.rs               # Compression service implementations
+//! ├── direct_io.rs                 # Direct (unbuffered) I/O helpers
 //! ├── encryption.rs                # Encryption service implementations
 //! ├── file_io.rs                   # File I/O service implementations
+//! ├── file_permissions.rs          # Explicit modes for created outputs
+//! ├── random_access_sink.rs        # Positional-write sink implementations
+//! ├── staged_output.rs             # Rename-committed output files
 //! ├── async_compression.rs         # Async compression adapter
 //! ├── async_encryption.rs          # Async encryption adapter
 //! └── async_checksum.rs            # Async checksum adapter
@@ -56,9 +63,21 @@
 //!   context
 //! - **Flexibility**: Runtime configuration of adapter behavior
 
+/// API authentication providers (API keys, JWT, client certificates)
+#[cfg(all(unix, feature = "fuse"))]
+pub mod archive_fs;
+
+pub mod auth;
+
+/// Whole-file checksums (SHA-256, BLAKE3)
+pub mod checksum;
+
 /// Chunk processor adapters for service integration
 pub mod chunk_processor_adapters;
 
+/// Read-ahead chunk prefetcher over the file I/O service
+pub mod chunk_prefetcher;
+
 /// Compression service adapter
 pub mod compression;
 
@@ -71,15 +90,31 @@ pub mod async_encryption;
 /// Async checksum adapter (wraps sync domain trait for async contexts)
 pub mod async_checksum;
 
+/// Page-cache-bypassing reads and cache eviction for direct I/O mode
+pub mod direct_io;
+
 /// Encryption service adapter
 pub mod encryption;
 
 /// File I/O service adapter
 pub mod file_io;
 
+/// Explicit permission modes for created files and directories
+pub mod file_permissions;
+
+/// Positional-write sinks (local, preallocated and in-memory)
+pub mod random_access_sink;
+
+/// Staged output files committed by rename, with cross-device fallback
+pub mod staged_output;
+
 // Re-export for easy access
 pub use async_checksum::*;
 pub use async_compression::*;
 pub use async_encryption::*;
+pub use checksum::ContentHasher;
 pub use compression::*;
 pub use encryption::*;
+pub use random_access_sink::{FileSink, MemorySink, PreallocatedFileSink};
+pub use file_permissions::{apply_mode, create_dir_all_with_mode};
+pub use staged_output::{CommitOutcome, StagedOutput};
@@ -0,0 +1,179 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Archive Filesystem
+//!
+//! A read-only FUSE filesystem holding the original file of one `.adapipe`
+//! archive, for `mount`. The mount's root directory contains that file and
+//! nothing else; every read is answered by a caller-supplied function that
+//! restores just the requested range.
+//!
+//! Built with the `fuse` feature on Unix.
+
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use fuser::{
+    Config, Errno, FileAttr, FileHandle, FileType, Filesystem, Generation, INodeNo, LockOwner, MountOption, OpenFlags,
+    ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, ReplyOpen, Request,
+};
+use tracing::warn;
+
+use adaptive_pipeline_domain::PipelineError;
+
+/// Inode of the restored file; the root directory is `INodeNo::ROOT`
+const FILE_INODE: INodeNo = INodeNo(2);
+
+/// Attributes never change while mounted, so the kernel may keep them
+const ATTR_TTL: Duration = Duration::from_secs(3600);
+
+/// Reads `len` bytes of the original file from an offset
+pub type RangeRead = Box<dyn Fn(u64, usize) -> Result<Vec<u8>, PipelineError> + Send + Sync>;
+
+/// The original file of an archive, as a read-only filesystem
+pub struct ArchiveFs {
+    file_name: OsString,
+    size: u64,
+    modified: SystemTime,
+    uid: u32,
+    gid: u32,
+    read: RangeRead,
+}
+
+impl ArchiveFs {
+    /// A filesystem holding `file_name`, `size` bytes long and last modified
+    /// at `modified`, read through `read`. The mount belongs to the user
+    /// running it.
+    pub fn new(file_name: OsString, size: u64, modified: SystemTime, read: RangeRead) -> Self {
+        // SAFETY: getuid and getgid cannot fail and touch no memory
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        Self {
+            file_name,
+            size,
+            modified,
+            uid,
+            gid,
+            read,
+        }
+    }
+
+    /// Mounts the filesystem read-only at `mountpoint` and serves it on a
+    /// background thread until the returned session is dropped or the
+    /// mount is unmounted
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the mount fails, e.g. without permission to
+    /// mount FUSE filesystems.
+    pub fn spawn_mount(self, mountpoint: &Path) -> Result<fuser::BackgroundSession, PipelineError> {
+        let mut config = Config::default();
+        config.mount_options.extend([
+            MountOption::RO,
+            MountOption::NoExec,
+            MountOption::FSName("adapipe".to_string()),
+            MountOption::Subtype("adapipe".to_string()),
+        ]);
+        fuser::spawn_mount(self, mountpoint, &config)
+            .map_err(|e| PipelineError::IoError(format!("Failed to mount {}: {}", mountpoint.display(), e)))
+    }
+
+    fn attr(&self, ino: INodeNo) -> Option<FileAttr> {
+        let (kind, size, perm, nlink) = match ino {
+            INodeNo::ROOT => (FileType::Directory, 0, 0o555, 2),
+            FILE_INODE => (FileType::RegularFile, self.size, 0o444, 1),
+            _ => return None,
+        };
+        Some(FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: self.modified,
+            mtime: self.modified,
+            ctime: self.modified,
+            crtime: self.modified,
+            kind,
+            perm,
+            nlink,
+            uid: self.uid,
+            gid: self.gid,
+            rdev: 0,
+            flags: 0,
+            blksize: 512,
+        })
+    }
+}
+
+impl Filesystem for ArchiveFs {
+    fn lookup(&self, _req: &Request, parent: INodeNo, name: &OsStr, reply: ReplyEntry) {
+        match self.attr(FILE_INODE) {
+            Some(attr) if parent == INodeNo::ROOT && name == self.file_name => {
+                reply.entry(&ATTR_TTL, &attr, Generation(0))
+            }
+            _ => reply.error(Errno::ENOENT),
+        }
+    }
+
+    fn getattr(&self, _req: &Request, ino: INodeNo, _fh: Option<FileHandle>, reply: ReplyAttr) {
+        match self.attr(ino) {
+            Some(attr) => reply.attr(&ATTR_TTL, &attr),
+            None => reply.error(Errno::ENOENT),
+        }
+    }
+
+    fn open(&self, _req: &Request, ino: INodeNo, flags: OpenFlags, reply: ReplyOpen) {
+        if ino != FILE_INODE {
+            reply.error(Errno::ENOENT);
+        } else if flags.acc_mode() != fuser::OpenAccMode::O_RDONLY {
+            reply.error(Errno::EROFS);
+        } else {
+            reply.opened(FileHandle(0), fuser::FopenFlags::FOPEN_KEEP_CACHE);
+        }
+    }
+
+    fn read(
+        &self,
+        _req: &Request,
+        ino: INodeNo,
+        _fh: FileHandle,
+        offset: u64,
+        size: u32,
+        _flags: OpenFlags,
+        _lock_owner: Option<LockOwner>,
+        reply: ReplyData,
+    ) {
+        if ino != FILE_INODE {
+            reply.error(Errno::ENOENT);
+            return;
+        }
+        match (self.read)(offset, size as usize) {
+            Ok(data) => reply.data(&data),
+            Err(e) => {
+                warn!("Failed to read {} bytes at {}: {}", size, offset, e);
+                reply.error(Errno::EIO);
+            }
+        }
+    }
+
+    fn readdir(&self, _req: &Request, ino: INodeNo, _fh: FileHandle, offset: u64, mut reply: ReplyDirectory) {
+        if ino != INodeNo::ROOT {
+            reply.error(Errno::ENOTDIR);
+            return;
+        }
+        let entries = [
+            (INodeNo::ROOT, FileType::Directory, OsStr::new(".")),
+            (INodeNo::ROOT, FileType::Directory, OsStr::new("..")),
+            (FILE_INODE, FileType::RegularFile, self.file_name.as_os_str()),
+        ];
+        for (index, (ino, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(ino, (index + 1) as u64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+}
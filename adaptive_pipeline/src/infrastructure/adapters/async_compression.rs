@@ -263,12 +263,13 @@ mod tests {
         fn process_chunk(
             &self,
             chunk: FileChunk,
+            operation: adaptive_pipeline_domain::entities::Operation,
             config: &adaptive_pipeline_domain::entities::pipeline_stage::StageConfiguration,
             context: &mut ProcessingContext,
         ) -> Result<FileChunk, PipelineError> {
             use adaptive_pipeline_domain::services::FromParameters;
             let compression_config = CompressionConfig::from_parameters(&config.parameters)?;
-            match config.operation {
+            match operation {
                 adaptive_pipeline_domain::entities::Operation::Forward => {
                     self.compress_chunk(chunk, &compression_config, context)
                 }
@@ -40,7 +40,7 @@ use adaptive_pipeline_domain::entities::{ProcessingContext, SecurityContext};
 use adaptive_pipeline_domain::services::encryption_service::{
     EncryptionAlgorithm, EncryptionConfig, EncryptionService, KeyMaterial,
 };
-use adaptive_pipeline_domain::value_objects::{EncryptionBenchmark, FileChunk};
+use adaptive_pipeline_domain::value_objects::{EncryptionBenchmark, FileChunk, SecretBytes};
 use adaptive_pipeline_domain::PipelineError;
 use std::sync::Arc;
 
@@ -190,12 +190,13 @@ impl<T: EncryptionService + 'static> AsyncEncryptionAdapter<T> {
     /// This is a CPU-intensive operation that benefits from blocking execution.
     pub async fn derive_key_material_async(
         &self,
-        password: &str,
+        password: &SecretBytes,
         config: &EncryptionConfig,
         security_context: &SecurityContext,
     ) -> Result<KeyMaterial, PipelineError> {
         let service = self.inner.clone();
-        let password = password.to_string();
+        // The clone is moved into the blocking task and wiped when it ends
+        let password = password.clone();
         let config = config.clone();
         let security_context = security_context.clone();
 
@@ -318,7 +319,7 @@ mod tests {
 
         fn derive_key_material(
             &self,
-            _password: &str,
+            _password: &SecretBytes,
             config: &EncryptionConfig,
             _security_context: &SecurityContext,
         ) -> Result<KeyMaterial, PipelineError> {
@@ -408,6 +409,7 @@ mod tests {
         fn process_chunk(
             &self,
             chunk: FileChunk,
+            operation: adaptive_pipeline_domain::entities::Operation,
             config: &adaptive_pipeline_domain::entities::pipeline_stage::StageConfiguration,
             context: &mut ProcessingContext,
         ) -> Result<FileChunk, PipelineError> {
@@ -422,7 +424,7 @@ mod tests {
                 encryption_config.algorithm.clone(),
             );
 
-            match config.operation {
+            match operation {
                 adaptive_pipeline_domain::entities::Operation::Forward => {
                     self.encrypt_chunk(chunk, &encryption_config, &key_material, context)
                 }
@@ -0,0 +1,132 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Authentication Providers
+//!
+//! Implementations of the `AuthProvider` port:
+//!
+//! - [`ApiKeyProvider`]: static API keys, configured by SHA-256 digest
+//! - [`JwtProvider`]: JWTs (HS256, RS256, ES256), e.g. OIDC ID or access
+//!   tokens, validated against a JWKS document or a shared secret
+//! - [`ClientCertProvider`]: TLS client certificates, pinned by SHA-256
+//!   fingerprint
+//!
+//! [`providers_from_settings`] builds the providers enabled by the `[auth]`
+//! configuration section.
+
+pub mod api_key;
+pub mod client_cert;
+pub mod jwt;
+
+pub use api_key::ApiKeyProvider;
+pub use client_cert::ClientCertProvider;
+pub use jwt::JwtProvider;
+
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::services::AuthProvider;
+use adaptive_pipeline_domain::value_objects::{Namespace, Role, SecretBytes};
+use adaptive_pipeline_domain::PipelineError;
+
+use crate::infrastructure::config::config_service::AuthSettings;
+
+/// Builds the providers configured in `settings`, in the order API keys,
+/// JWT, client certificates
+///
+/// # Errors
+///
+/// Returns `InvalidConfiguration` for malformed digests, roles, namespaces
+/// or JWKS documents, or a JWT section with neither keys nor a secret.
+pub fn providers_from_settings(settings: &AuthSettings) -> Result<Vec<Arc<dyn AuthProvider>>, PipelineError> {
+    let mut providers: Vec<Arc<dyn AuthProvider>> = Vec::new();
+
+    if !settings.api_keys.is_empty() {
+        let mut provider = ApiKeyProvider::new();
+        for key in &settings.api_keys {
+            provider = provider.with_key(
+                &key.key_sha256,
+                &key.principal,
+                parse_role(key.role.as_deref())?,
+                parse_namespace(key.namespace.as_deref())?,
+            )?;
+        }
+        providers.push(Arc::new(provider));
+    }
+
+    if let Some(jwt) = &settings.jwt {
+        let mut provider = JwtProvider::new()
+            .with_principal_claim(&jwt.principal_claim)
+            .with_roles_claim(&jwt.roles_claim)
+            .with_leeway(jwt.leeway_secs);
+        if let Some(issuer) = &jwt.issuer {
+            provider = provider.with_issuer(issuer);
+        }
+        if let Some(audience) = &jwt.audience {
+            provider = provider.with_audience(audience);
+        }
+        if let Some(path) = &jwt.jwks_path {
+            let jwks = std::fs::read_to_string(path).map_err(|e| {
+                PipelineError::invalid_config(format!("Failed to read JWKS file {}: {}", path.display(), e))
+            })?;
+            provider = provider.with_jwks(&jwks)?;
+        }
+        if let Some(variable) = &jwt.hs256_secret_env {
+            let secret = std::env::var(variable)
+                .map_err(|_| PipelineError::invalid_config(format!("JWT secret variable {} is not set", variable)))?;
+            provider = provider.with_hs256_secret(SecretBytes::new(secret.into_bytes()));
+        }
+        for (claim_value, role) in &jwt.role_mapping {
+            provider = provider.with_role_mapping(claim_value, role.parse()?);
+        }
+        if !provider.has_keys() {
+            return Err(PipelineError::invalid_config(
+                "[auth.jwt] needs jwks_path or hs256_secret_env",
+            ));
+        }
+        providers.push(Arc::new(provider));
+    }
+
+    if !settings.client_certs.is_empty() {
+        let mut provider = ClientCertProvider::new();
+        for cert in &settings.client_certs {
+            provider = provider.with_certificate(
+                &cert.sha256_fingerprint,
+                &cert.principal,
+                parse_role(cert.role.as_deref())?,
+                parse_namespace(cert.namespace.as_deref())?,
+            )?;
+        }
+        providers.push(Arc::new(provider));
+    }
+
+    Ok(providers)
+}
+
+/// Normalizes a hex SHA-256 digest, accepting upper case and `:`
+/// separators as printed by `openssl x509 -fingerprint`
+fn normalize_digest(digest: &str) -> Result<String, PipelineError> {
+    let normalized: String = digest
+        .chars()
+        .filter(|c| *c != ':')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if normalized.len() != 64 || !normalized.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(PipelineError::invalid_config(format!(
+            "'{}' is not a hex SHA-256 digest",
+            digest
+        )));
+    }
+    Ok(normalized)
+}
+
+fn parse_role(role: Option<&str>) -> Result<Option<Role>, PipelineError> {
+    role.map(str::parse).transpose()
+}
+
+fn parse_namespace(namespace: Option<&str>) -> Result<Option<Namespace>, PipelineError> {
+    namespace.map(str::parse).transpose()
+}
@@ -0,0 +1,123 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # API Key Provider
+//!
+//! Authenticates callers by static API key. Keys are configured by the hex
+//! SHA-256 digest of the key (`printf %s "$KEY" | sha256sum`), so neither
+//! the configuration nor memory holds the keys themselves.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::{AuthProvider, AuthenticatedIdentity, Credentials};
+use adaptive_pipeline_domain::value_objects::{Namespace, Role};
+use adaptive_pipeline_domain::PipelineError;
+
+use super::normalize_digest;
+
+const PROVIDER_NAME: &str = "api_key";
+
+/// Authenticates static API keys
+#[derive(Debug, Default)]
+pub struct ApiKeyProvider {
+    keys: Vec<(String, AuthenticatedIdentity)>,
+}
+
+impl ApiKeyProvider {
+    /// Creates a provider that accepts no keys
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the key whose SHA-256 digest is `key_sha256` as `principal`,
+    /// granting `role` and confining it to `namespace`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if `key_sha256` is not a hex SHA-256
+    /// digest.
+    pub fn with_key(
+        mut self,
+        key_sha256: &str,
+        principal: &str,
+        role: Option<Role>,
+        namespace: Option<Namespace>,
+    ) -> Result<Self, PipelineError> {
+        let identity = AuthenticatedIdentity::new(principal, PROVIDER_NAME)
+            .with_role(role)
+            .with_namespace(namespace);
+        self.keys.push((normalize_digest(key_sha256)?, identity));
+        Ok(self)
+    }
+
+    /// Computes the digest under which `key` is configured
+    pub fn digest(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ApiKeyProvider {
+    fn name(&self) -> &str {
+        PROVIDER_NAME
+    }
+
+    fn supports(&self, credentials: &Credentials) -> bool {
+        matches!(credentials, Credentials::ApiKey(_))
+    }
+
+    async fn authenticate(&self, credentials: &Credentials) -> Result<AuthenticatedIdentity, PipelineError> {
+        let Credentials::ApiKey(key) = credentials else {
+            return Err(PipelineError::security_violation(format!(
+                "Permission denied: {} is not an API key",
+                credentials.kind()
+            )));
+        };
+
+        let digest = Self::digest(key.trim());
+        self.keys
+            .iter()
+            .find(|(configured, _)| constant_time_eq_str(configured, &digest))
+            .map(|(_, identity)| identity.clone())
+            .ok_or_else(|| PipelineError::security_violation("Permission denied: unknown API key"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_configured_key_authenticates_as_its_principal() {
+        let provider = ApiKeyProvider::new()
+            .with_key(
+                &ApiKeyProvider::digest("s3cret"),
+                "build-bot",
+                Some(Role::Operator),
+                None,
+            )
+            .unwrap();
+
+        let identity = provider
+            .authenticate(&Credentials::ApiKey("s3cret".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(identity.principal, "build-bot");
+        assert_eq!(identity.provider, "api_key");
+        assert_eq!(identity.role, Some(Role::Operator));
+
+        let unknown = provider
+            .authenticate(&Credentials::ApiKey("guess".to_string()))
+            .await
+            .unwrap_err();
+        assert!(!unknown.to_string().contains("guess"));
+        assert!(!provider.supports(&Credentials::BearerToken("s3cret".to_string())));
+        assert!(ApiKeyProvider::new().with_key("abc", "x", None, None).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Client Certificate Provider
+//!
+//! Authenticates mutually authenticated TLS callers by pinning their
+//! certificates: each accepted certificate is configured by the SHA-256
+//! fingerprint of its DER encoding, as printed by
+//! `openssl x509 -noout -fingerprint -sha256 -in client.pem`.
+//!
+//! The TLS layer must have completed the handshake, proving the caller holds
+//! the certificate's private key, before the certificate is passed here. For
+//! `serve` that is the proxy in front of it, which forwards the certificate
+//! in a header believed only from the addresses in
+//! `[auth.client_cert_proxy]`. Pinning makes chain validation unnecessary for
+//! authentication; a reissued certificate has to be configured again.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::{AuthProvider, AuthenticatedIdentity, Credentials};
+use adaptive_pipeline_domain::value_objects::{Namespace, Role};
+use adaptive_pipeline_domain::PipelineError;
+
+use super::normalize_digest;
+
+const PROVIDER_NAME: &str = "client_cert";
+
+/// Authenticates pinned TLS client certificates
+#[derive(Debug, Default)]
+pub struct ClientCertProvider {
+    certificates: Vec<(String, AuthenticatedIdentity)>,
+}
+
+impl ClientCertProvider {
+    /// Creates a provider that accepts no certificates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the certificate with SHA-256 fingerprint `fingerprint` as
+    /// `principal`, granting `role` and confining it to `namespace`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if `fingerprint` is not a hex SHA-256
+    /// digest (`:` separators are allowed).
+    pub fn with_certificate(
+        mut self,
+        fingerprint: &str,
+        principal: &str,
+        role: Option<Role>,
+        namespace: Option<Namespace>,
+    ) -> Result<Self, PipelineError> {
+        let identity = AuthenticatedIdentity::new(principal, PROVIDER_NAME)
+            .with_role(role)
+            .with_namespace(namespace);
+        self.certificates.push((normalize_digest(fingerprint)?, identity));
+        Ok(self)
+    }
+
+    /// Computes the fingerprint of a DER-encoded certificate
+    pub fn fingerprint(certificate_der: &[u8]) -> String {
+        hex::encode(Sha256::digest(certificate_der))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ClientCertProvider {
+    fn name(&self) -> &str {
+        PROVIDER_NAME
+    }
+
+    fn supports(&self, credentials: &Credentials) -> bool {
+        matches!(credentials, Credentials::ClientCertificate(_))
+    }
+
+    async fn authenticate(&self, credentials: &Credentials) -> Result<AuthenticatedIdentity, PipelineError> {
+        let Credentials::ClientCertificate(der) = credentials else {
+            return Err(PipelineError::security_violation(format!(
+                "Permission denied: {} is not a client certificate",
+                credentials.kind()
+            )));
+        };
+
+        let fingerprint = Self::fingerprint(der);
+        self.certificates
+            .iter()
+            .find(|(pinned, _)| constant_time_eq_str(pinned, &fingerprint))
+            .map(|(_, identity)| identity.clone())
+            .ok_or_else(|| {
+                PipelineError::security_violation(format!(
+                    "Permission denied: client certificate {} is not trusted",
+                    fingerprint
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pinned_certificate_authenticates() {
+        let der = b"not really DER, but only the digest matters".to_vec();
+        let colon_separated = ClientCertProvider::fingerprint(&der)
+            .to_uppercase()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| std::str::from_utf8(pair).unwrap())
+            .collect::<Vec<_>>()
+            .join(":");
+        let team_a: Namespace = "team-a".parse().unwrap();
+        let provider = ClientCertProvider::new()
+            .with_certificate(&colon_separated, "ingest-service", None, Some(team_a.clone()))
+            .unwrap();
+
+        let identity = provider
+            .authenticate(&Credentials::ClientCertificate(der))
+            .await
+            .unwrap();
+        assert_eq!(identity.principal, "ingest-service");
+        assert_eq!(identity.namespace, Some(team_a));
+
+        let other = provider
+            .authenticate(&Credentials::ClientCertificate(b"other".to_vec()))
+            .await;
+        assert!(matches!(other, Err(PipelineError::SecurityViolation(_))));
+    }
+}
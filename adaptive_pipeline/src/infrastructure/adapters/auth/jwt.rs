@@ -0,0 +1,499 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # JWT Provider
+//!
+//! Authenticates bearer tokens that are signed JWTs, such as the ID and
+//! access tokens of an OIDC identity provider.
+//!
+//! ## Validation
+//!
+//! 1. The signature must verify with `HS256` against the shared secret, or
+//!    with `RS256` / `ES256` (P-256) against a key of the JWKS document, the
+//!    one named by the token's `kid` if it has one. Other algorithms,
+//!    including `none`, are rejected.
+//! 2. `exp` is required; `exp` and `nbf` are checked with the configured
+//!    leeway for clock skew.
+//! 3. `iss` and `aud` must match when an issuer or audience is configured.
+//! 4. The principal is the string in the principal claim (`sub` by default).
+//!
+//! ## Roles
+//!
+//! Values of the roles claim (`roles` by default; a string or an array of
+//! strings) are mapped to RBAC roles through the role mapping, and the
+//! highest mapped role is granted. Unmapped values are ignored.
+//!
+//! The JWKS document is read once, when the provider is built; rotate keys
+//! by updating the file (e.g. from the issuer's `jwks_uri`) and restarting.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use ring::signature::{RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256};
+use serde::Deserialize;
+use serde_json::Value;
+
+use adaptive_pipeline_domain::services::{AuthProvider, AuthenticatedIdentity, Credentials};
+use adaptive_pipeline_domain::value_objects::{Role, SecretBytes};
+use adaptive_pipeline_domain::PipelineError;
+
+const PROVIDER_NAME: &str = "jwt";
+
+/// Most clock skew tolerated, whatever is configured
+const MAX_LEEWAY_SECS: u64 = 3600;
+
+/// A public key from a JWKS document
+enum VerificationKey {
+    Rsa {
+        kid: Option<String>,
+        n: Vec<u8>,
+        e: Vec<u8>,
+    },
+    EcP256 {
+        kid: Option<String>,
+        point: Vec<u8>,
+    },
+}
+
+impl VerificationKey {
+    fn kid(&self) -> Option<&str> {
+        match self {
+            VerificationKey::Rsa { kid, .. } | VerificationKey::EcP256 { kid, .. } => kid.as_deref(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    crv: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+/// Authenticates signed JWT bearer tokens
+pub struct JwtProvider {
+    issuer: Option<String>,
+    audience: Option<String>,
+    hs256_secret: Option<SecretBytes>,
+    keys: Vec<VerificationKey>,
+    principal_claim: String,
+    roles_claim: String,
+    role_mapping: BTreeMap<String, Role>,
+    leeway: chrono::Duration,
+}
+
+impl Default for JwtProvider {
+    fn default() -> Self {
+        Self {
+            issuer: None,
+            audience: None,
+            hs256_secret: None,
+            keys: Vec::new(),
+            principal_claim: "sub".to_string(),
+            roles_claim: "roles".to_string(),
+            role_mapping: BTreeMap::new(),
+            leeway: chrono::Duration::seconds(60),
+        }
+    }
+}
+
+impl JwtProvider {
+    /// Creates a provider with no keys, accepting any issuer and audience
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the `iss` claim to be `issuer`
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Requires the `aud` claim to be or contain `audience`
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Accepts `HS256` tokens signed with `secret`
+    pub fn with_hs256_secret(mut self, secret: SecretBytes) -> Self {
+        self.hs256_secret = Some(secret);
+        self
+    }
+
+    /// Accepts `RS256` and `ES256` tokens signed by the RSA and P-256 keys of
+    /// a JWKS document; other keys in it are skipped
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the document does not parse or a
+    /// usable key is malformed.
+    pub fn with_jwks(mut self, jwks: &str) -> Result<Self, PipelineError> {
+        let jwks: Jwks = serde_json::from_str(jwks)
+            .map_err(|e| PipelineError::invalid_config(format!("Invalid JWKS document: {}", e)))?;
+
+        for jwk in jwks.keys {
+            let key = match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+                ("RSA", _) => VerificationKey::Rsa {
+                    n: decode_key_part(jwk.n.as_deref(), "n")?,
+                    e: decode_key_part(jwk.e.as_deref(), "e")?,
+                    kid: jwk.kid,
+                },
+                ("EC", Some("P-256")) => {
+                    // Uncompressed SEC1 point, as ring expects
+                    let mut point = vec![0x04];
+                    point.extend(decode_key_part(jwk.x.as_deref(), "x")?);
+                    point.extend(decode_key_part(jwk.y.as_deref(), "y")?);
+                    VerificationKey::EcP256 { kid: jwk.kid, point }
+                }
+                _ => continue,
+            };
+            self.keys.push(key);
+        }
+        Ok(self)
+    }
+
+    /// Reads the principal from `claim` instead of `sub`
+    pub fn with_principal_claim(mut self, claim: impl Into<String>) -> Self {
+        self.principal_claim = claim.into();
+        self
+    }
+
+    /// Reads role values from `claim` instead of `roles`
+    pub fn with_roles_claim(mut self, claim: impl Into<String>) -> Self {
+        self.roles_claim = claim.into();
+        self
+    }
+
+    /// Grants `role` to tokens whose roles claim contains `claim_value`
+    pub fn with_role_mapping(mut self, claim_value: impl Into<String>, role: Role) -> Self {
+        self.role_mapping.insert(claim_value.into(), role);
+        self
+    }
+
+    /// Tolerates `leeway_secs` (at most an hour) of clock skew on `exp` and
+    /// `nbf`
+    pub fn with_leeway(mut self, leeway_secs: u64) -> Self {
+        self.leeway = chrono::Duration::seconds(leeway_secs.min(MAX_LEEWAY_SECS) as i64);
+        self
+    }
+
+    /// Whether the provider can verify any token at all
+    pub fn has_keys(&self) -> bool {
+        self.hs256_secret.is_some() || !self.keys.is_empty()
+    }
+
+    /// Validates `token` as of `now`
+    ///
+    /// # Errors
+    ///
+    /// See the module documentation.
+    pub fn validate_at(&self, token: &str, now: DateTime<Utc>) -> Result<AuthenticatedIdentity, PipelineError> {
+        let token = token.trim();
+        let (signing_input, signature) = token.rsplit_once('.').ok_or_else(malformed)?;
+        let (header, payload) = signing_input.split_once('.').ok_or_else(malformed)?;
+        let header: JwtHeader = serde_json::from_slice(&decode(header)?).map_err(|_| malformed())?;
+        let claims: Value = serde_json::from_slice(&decode(payload)?).map_err(|_| malformed())?;
+        let signature = decode(signature)?;
+
+        self.verify_signature(&header, signing_input.as_bytes(), &signature)?;
+        self.check_claims(&claims, now)?;
+
+        let principal = claims
+            .get(&self.principal_claim)
+            .and_then(Value::as_str)
+            .filter(|principal| !principal.trim().is_empty())
+            .ok_or_else(|| {
+                PipelineError::security_violation(format!(
+                    "Permission denied: token has no '{}' claim",
+                    self.principal_claim
+                ))
+            })?;
+        Ok(AuthenticatedIdentity::new(principal, PROVIDER_NAME).with_role(self.mapped_role(&claims)))
+    }
+
+    fn verify_signature(&self, header: &JwtHeader, message: &[u8], signature: &[u8]) -> Result<(), PipelineError> {
+        let verified = match header.alg.as_str() {
+            "HS256" => self.hs256_secret.as_ref().is_some_and(|secret| {
+                let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.expose_secret());
+                ring::hmac::verify(&key, message, signature).is_ok()
+            }),
+            "RS256" => self.candidate_keys(header).any(|key| match key {
+                VerificationKey::Rsa { n, e, .. } => RsaPublicKeyComponents { n, e }
+                    .verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+                    .is_ok(),
+                VerificationKey::EcP256 { .. } => false,
+            }),
+            "ES256" => self.candidate_keys(header).any(|key| match key {
+                VerificationKey::EcP256 { point, .. } => UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point)
+                    .verify(message, signature)
+                    .is_ok(),
+                VerificationKey::Rsa { .. } => false,
+            }),
+            other => {
+                return Err(PipelineError::security_violation(format!(
+                    "Permission denied: token algorithm '{}' is not accepted",
+                    other
+                )))
+            }
+        };
+
+        if verified {
+            Ok(())
+        } else {
+            Err(PipelineError::security_violation(
+                "Permission denied: token signature does not verify",
+            ))
+        }
+    }
+
+    fn candidate_keys<'a>(&'a self, header: &'a JwtHeader) -> impl Iterator<Item = &'a VerificationKey> + 'a {
+        self.keys
+            .iter()
+            .filter(move |key| header.kid.is_none() || key.kid() == header.kid.as_deref())
+    }
+
+    fn check_claims(&self, claims: &Value, now: DateTime<Utc>) -> Result<(), PipelineError> {
+        let expires_at = timestamp_claim(claims, "exp")?
+            .ok_or_else(|| PipelineError::security_violation("Permission denied: token has no expiry"))?;
+        if now >= expires_at + self.leeway {
+            return Err(PipelineError::security_context_expired(format!(
+                "Permission denied: token expired at {}",
+                expires_at.to_rfc3339()
+            )));
+        }
+        if let Some(not_before) = timestamp_claim(claims, "nbf")? {
+            if now + self.leeway < not_before {
+                return Err(PipelineError::security_violation(format!(
+                    "Permission denied: token is not valid before {}",
+                    not_before.to_rfc3339()
+                )));
+            }
+        }
+
+        if let Some(issuer) = &self.issuer {
+            if claims.get("iss").and_then(Value::as_str) != Some(issuer.as_str()) {
+                return Err(PipelineError::security_violation(
+                    "Permission denied: token was issued by another issuer",
+                ));
+            }
+        }
+        if let Some(audience) = &self.audience {
+            let matches = match claims.get("aud") {
+                Some(Value::String(aud)) => aud == audience,
+                Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(audience.as_str())),
+                _ => false,
+            };
+            if !matches {
+                return Err(PipelineError::security_violation(
+                    "Permission denied: token is for another audience",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn mapped_role(&self, claims: &Value) -> Option<Role> {
+        let values: Vec<&str> = match claims.get(&self.roles_claim) {
+            Some(Value::String(value)) => vec![value.as_str()],
+            Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        values
+            .into_iter()
+            .filter_map(|value| self.role_mapping.get(value).copied())
+            .max()
+    }
+}
+
+#[async_trait]
+impl AuthProvider for JwtProvider {
+    fn name(&self) -> &str {
+        PROVIDER_NAME
+    }
+
+    fn supports(&self, credentials: &Credentials) -> bool {
+        matches!(credentials, Credentials::BearerToken(_))
+    }
+
+    async fn authenticate(&self, credentials: &Credentials) -> Result<AuthenticatedIdentity, PipelineError> {
+        let Credentials::BearerToken(token) = credentials else {
+            return Err(PipelineError::security_violation(format!(
+                "Permission denied: {} is not a bearer token",
+                credentials.kind()
+            )));
+        };
+        self.validate_at(token, Utc::now())
+    }
+}
+
+fn malformed() -> PipelineError {
+    PipelineError::security_violation("Permission denied: malformed bearer token")
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, PipelineError> {
+    URL_SAFE_NO_PAD.decode(part).map_err(|_| malformed())
+}
+
+fn decode_key_part(part: Option<&str>, name: &str) -> Result<Vec<u8>, PipelineError> {
+    part.and_then(|part| URL_SAFE_NO_PAD.decode(part).ok())
+        .ok_or_else(|| PipelineError::invalid_config(format!("JWKS key has no valid '{}'", name)))
+}
+
+fn timestamp_claim(claims: &Value, name: &str) -> Result<Option<DateTime<Utc>>, PipelineError> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .or_else(|| value.as_f64().map(|secs| secs as i64))
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .map(Some)
+            .ok_or_else(|| {
+                PipelineError::security_violation(format!("Permission denied: token claim '{}' is not a time", name))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use serde_json::json;
+
+    fn encode_part(value: &Value) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap())
+    }
+
+    fn hs256_token(secret: &[u8], claims: Value) -> String {
+        let input = format!("{}.{}", encode_part(&json!({"alg": "HS256"})), encode_part(&claims));
+        let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret);
+        let tag = ring::hmac::sign(&key, input.as_bytes());
+        format!("{}.{}", input, URL_SAFE_NO_PAD.encode(tag.as_ref()))
+    }
+
+    fn provider() -> JwtProvider {
+        JwtProvider::new()
+            .with_hs256_secret(SecretBytes::new(b"shared".to_vec()))
+            .with_issuer("https://idp.example.com")
+            .with_audience("adaptive-pipeline")
+            .with_role_mapping("pipeline-admins", Role::Admin)
+            .with_role_mapping("pipeline-users", Role::Operator)
+    }
+
+    fn claims(exp: i64) -> Value {
+        json!({
+            "sub": "alice",
+            "iss": "https://idp.example.com",
+            "aud": ["other", "adaptive-pipeline"],
+            "exp": exp,
+            "roles": ["pipeline-users", "pipeline-admins", "unmapped"],
+        })
+    }
+
+    #[test]
+    fn test_hs256_token_maps_principal_and_highest_role() {
+        let now = Utc::now();
+        let token = hs256_token(b"shared", claims(now.timestamp() + 300));
+
+        let identity = provider().validate_at(&token, now).unwrap();
+        assert_eq!(identity.principal, "alice");
+        assert_eq!(identity.provider, "jwt");
+        assert_eq!(identity.role, Some(Role::Admin));
+    }
+
+    #[test]
+    fn test_invalid_tokens_are_refused() {
+        let now = Utc::now();
+        let provider = provider();
+        let valid_until = now.timestamp() + 300;
+
+        let forged = hs256_token(b"guessed", claims(valid_until));
+        assert!(provider.validate_at(&forged, now).is_err());
+
+        let expired = hs256_token(b"shared", claims(now.timestamp() - 120));
+        assert!(matches!(
+            provider.validate_at(&expired, now),
+            Err(PipelineError::SecurityContextExpired(_))
+        ));
+        // Within the leeway
+        let skewed = hs256_token(b"shared", claims(now.timestamp() - 30));
+        assert!(provider.validate_at(&skewed, now).is_ok());
+
+        let mut foreign = claims(valid_until);
+        foreign["iss"] = json!("https://evil.example.com");
+        assert!(provider.validate_at(&hs256_token(b"shared", foreign), now).is_err());
+
+        let mut other_audience = claims(valid_until);
+        other_audience["aud"] = json!("other");
+        assert!(provider
+            .validate_at(&hs256_token(b"shared", other_audience), now)
+            .is_err());
+
+        let unsigned = format!(
+            "{}.{}.",
+            encode_part(&json!({"alg": "none"})),
+            encode_part(&claims(valid_until))
+        );
+        assert!(provider.validate_at(&unsigned, now).is_err());
+        assert!(provider.validate_at("not.a-token", now).is_err());
+    }
+
+    #[test]
+    fn test_es256_token_verifies_against_jwks() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+        let point = key_pair.public_key().as_ref();
+        let jwks = json!({"keys": [
+            {"kty": "oct", "kid": "ignored"},
+            {
+                "kty": "EC",
+                "crv": "P-256",
+                "kid": "key-1",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+            },
+        ]});
+        let provider = JwtProvider::new().with_jwks(&jwks.to_string()).unwrap();
+        assert!(provider.has_keys());
+
+        let now = Utc::now();
+        let sign = |kid: &str| {
+            let input = format!(
+                "{}.{}",
+                encode_part(&json!({"alg": "ES256", "kid": kid})),
+                encode_part(&json!({"sub": "svc", "exp": now.timestamp() + 60}))
+            );
+            let signature = key_pair.sign(&rng, input.as_bytes()).unwrap();
+            format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        };
+
+        let identity = provider.validate_at(&sign("key-1"), now).unwrap();
+        assert_eq!(identity.principal, "svc");
+        assert_eq!(identity.role, None);
+        assert!(provider.validate_at(&sign("key-2"), now).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Checksum Implementation
+//!
+//! Incremental whole-file hashing for every [`ChecksumAlgorithm`]. The
+//! pipeline picks the algorithm when it is created (`create --checksum`);
+//! processing hashes the input with it and records it in the `.adapipe`
+//! header, and restore and compare read it back from there to verify with
+//! the same hash.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline::infrastructure::adapters::ContentHasher;
+//! use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+//!
+//! let mut hasher = ContentHasher::new(ChecksumAlgorithm::Blake3);
+//! hasher.update(b"hello ");
+//! hasher.update(b"world");
+//! assert_eq!(hasher.finalize_hex(), ContentHasher::digest_hex(ChecksumAlgorithm::Blake3, b"hello world"));
+//! ```
+
+use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+use sha2::{Digest, Sha256};
+
+/// Running hash of a byte stream, as lowercase hex once finalized
+#[derive(Debug, Clone)]
+pub enum ContentHasher {
+    /// SHA-256
+    Sha256(Sha256),
+    /// BLAKE3 (boxed: its state is much larger than SHA-256's)
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl ContentHasher {
+    /// Starts an empty hash with `algorithm`
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => ContentHasher::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Blake3 => ContentHasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /// Gets the algorithm being computed
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        match self {
+            ContentHasher::Sha256(_) => ChecksumAlgorithm::Sha256,
+            ContentHasher::Blake3(_) => ChecksumAlgorithm::Blake3,
+        }
+    }
+
+    /// Hashes the next bytes of the stream
+    pub fn update(&mut self, bytes: &[u8]) {
+        match self {
+            ContentHasher::Sha256(hasher) => hasher.update(bytes),
+            ContentHasher::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    /// Returns the hex digest of everything hashed
+    pub fn finalize_hex(self) -> String {
+        match self {
+            ContentHasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+            ContentHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+
+    /// Returns the hex digest of `bytes` in one call
+    pub fn digest_hex(algorithm: ChecksumAlgorithm, bytes: &[u8]) -> String {
+        let mut hasher = Self::new(algorithm);
+        hasher.update(bytes);
+        hasher.finalize_hex()
+    }
+}
+
+/// Hashes everything written, so a reader can be hashed with
+/// [`std::io::copy`]
+impl std::io::Write for ContentHasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        assert_eq!(
+            ContentHasher::digest_hex(ChecksumAlgorithm::Sha256, b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            ContentHasher::digest_hex(ChecksumAlgorithm::Blake3, b"abc"),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+
+    #[test]
+    fn test_incremental_matches_one_shot() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        for algorithm in ChecksumAlgorithm::all() {
+            let mut hasher = ContentHasher::new(algorithm);
+            for chunk in data.chunks(7919) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.algorithm(), algorithm);
+            assert_eq!(hasher.finalize_hex(), ContentHasher::digest_hex(algorithm, &data));
+        }
+    }
+}
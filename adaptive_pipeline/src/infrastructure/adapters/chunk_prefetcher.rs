@@ -0,0 +1,268 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Prefetcher
+//!
+//! Reads a file's chunks ahead of the consumer so disk I/O overlaps with
+//! chunk processing.
+//!
+//! ## How It Works
+//!
+//! The file is split into fixed-size chunks by offset. Each chunk is read by
+//! its own task through [`FileIOService`], with at most `depth` reads in
+//! flight; chunks are handed out strictly in order. While the reader waits
+//! on a full worker channel, the next `depth` chunks are already being read.
+//!
+//! ```text
+//!   offsets:  0   1   2   3   4   5 ...
+//!             ▼   ▼   ▼   ▼
+//!           [read][read][read][read]     ← depth = 4 reads in flight
+//!             │
+//!             ▼
+//!        next_chunk() → chunk 0, then chunk 4 starts reading
+//! ```
+//!
+//! ## Choosing the Depth
+//!
+//! [`prefetch_depth`] derives the depth from the storage type's read-ahead
+//! (see [`StorageType::read_ahead_chunks`]) capped by the channel depth.
+//!
+//! ## Memory
+//!
+//! Before a chunk's read is issued, its bytes are reserved from the file's
+//! [`InFlightWindow`], in file order. The reservation travels with the
+//! chunk and is returned when the consumer drops it, so chunks read ahead,
+//! queued, and being processed together stay within the window's budget.
+
+use adaptive_pipeline_domain::services::file_io_service::{FileIOService, ReadOptions};
+use adaptive_pipeline_domain::value_objects::FileChunk;
+use adaptive_pipeline_domain::PipelineError;
+use futures::stream::{BoxStream, StreamExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::infrastructure::runtime::{InFlightWindow, StorageType, WindowPermit};
+
+/// Number of chunk reads to keep in flight for `storage_type`
+///
+/// Never more than the channel can absorb, and always at least one.
+pub fn prefetch_depth(storage_type: StorageType, channel_depth: usize) -> usize {
+    storage_type.read_ahead_chunks().min(channel_depth).max(1)
+}
+
+/// Reads a file's chunks ahead of the consumer, yielding them in order
+pub struct ChunkPrefetcher {
+    chunks: BoxStream<'static, Result<(FileChunk, WindowPermit), PipelineError>>,
+    total_chunks: usize,
+    depth: usize,
+}
+
+impl ChunkPrefetcher {
+    /// Opens `path` for prefetched reading in `chunk_size` chunks, with the
+    /// chunks read ahead and handed out bounded by `window`
+    ///
+    /// Reads start on the first call to [`next_chunk`](Self::next_chunk).
+    pub async fn open(
+        file_io_service: Arc<dyn FileIOService>,
+        path: &Path,
+        chunk_size: usize,
+        depth: usize,
+        window: Arc<InFlightWindow>,
+    ) -> Result<Self, PipelineError> {
+        if chunk_size == 0 {
+            return Err(PipelineError::invalid_config("Chunk size must be greater than 0"));
+        }
+
+        let file_size = file_io_service.get_file_info(path).await?.size;
+        let chunk_size = chunk_size as u64;
+        let total_chunks = file_size.div_ceil(chunk_size) as usize;
+        let depth = depth.max(1);
+        let path = path.to_path_buf();
+
+        let chunks = futures::stream::iter(0..total_chunks)
+            // Reserve in file order, one chunk at a time, before reading
+            .then(move |index| {
+                let window = window.clone();
+                let len = chunk_size.min(file_size - index as u64 * chunk_size);
+                async move { window.acquire(len as usize).await.map(|permit| (index, permit)) }
+            })
+            .map(move |reserved| {
+                let (index, permit) = match reserved {
+                    Ok(reserved) => reserved,
+                    Err(e) => return futures::future::Either::Left(futures::future::ready(Err(e))),
+                };
+                let read = ChunkRead {
+                    file_io_service: file_io_service.clone(),
+                    path: path.clone(),
+                    index,
+                    offset: index as u64 * chunk_size,
+                    len: chunk_size.min(file_size - index as u64 * chunk_size),
+                    is_final: index + 1 == total_chunks,
+                };
+                // Spawned so the read progresses while the consumer is busy
+                futures::future::Either::Right(async move {
+                    let chunk = tokio::spawn(read.run())
+                        .await
+                        .map_err(|e| PipelineError::io_error(format!("Chunk read task failed: {}", e)))??;
+                    Ok((chunk, permit))
+                })
+            })
+            .buffered(depth)
+            .boxed();
+
+        Ok(Self {
+            chunks,
+            total_chunks,
+            depth,
+        })
+    }
+
+    /// Number of chunks the file splits into
+    pub fn total_chunks(&self) -> usize {
+        self.total_chunks
+    }
+
+    /// Number of chunk reads kept in flight
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the next chunk in file order with its window reservation, or
+    /// `None` at end of file
+    ///
+    /// Keep the permit for as long as the chunk's data is held.
+    pub async fn next_chunk(&mut self) -> Option<Result<(FileChunk, WindowPermit), PipelineError>> {
+        self.chunks.next().await
+    }
+}
+
+/// One chunk to read at a known offset
+struct ChunkRead {
+    file_io_service: Arc<dyn FileIOService>,
+    path: PathBuf,
+    index: usize,
+    offset: u64,
+    len: u64,
+    is_final: bool,
+}
+
+impl ChunkRead {
+    async fn run(self) -> Result<FileChunk, PipelineError> {
+        let options = ReadOptions {
+            chunk_size: Some(self.len as usize),
+            start_offset: Some(self.offset),
+            max_bytes: Some(self.len),
+            calculate_checksums: false, // We'll calculate during processing
+            use_memory_mapping: false,
+        };
+        let result = self.file_io_service.read_file_chunks(&self.path, options).await?;
+
+        // Short reads come back as several pieces; stitch them together
+        let mut data = Vec::with_capacity(self.len as usize);
+        for piece in &result.chunks {
+            data.extend_from_slice(piece.data());
+        }
+        if data.len() as u64 != self.len {
+            return Err(PipelineError::io_error(format!(
+                "File {} changed while reading: chunk {} at offset {} returned {} of {} bytes",
+                self.path.display(),
+                self.index,
+                self.offset,
+                data.len(),
+                self.len
+            )));
+        }
+
+        FileChunk::new(self.index as u64, self.offset, data, self.is_final)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::adapters::file_io::TokioFileIO;
+    use adaptive_pipeline_domain::services::file_io_service::FileIOConfig;
+
+    fn file_io() -> Arc<dyn FileIOService> {
+        Arc::new(TokioFileIO::new(FileIOConfig::default()))
+    }
+
+    fn window(budget: usize, chunk_size: usize) -> Arc<InFlightWindow> {
+        Arc::new(InFlightWindow::new(budget, chunk_size))
+    }
+
+    #[tokio::test]
+    async fn test_prefetcher_yields_chunks_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.bin");
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let mut prefetcher = ChunkPrefetcher::open(file_io(), &path, 1024, 4, window(1 << 20, 1024))
+            .await
+            .unwrap();
+        assert_eq!(prefetcher.total_chunks(), 10);
+
+        let mut reassembled = Vec::new();
+        let mut index = 0;
+        while let Some(chunk) = prefetcher.next_chunk().await {
+            let (chunk, _permit) = chunk.unwrap();
+            assert_eq!(chunk.sequence_number(), index);
+            assert_eq!(chunk.offset(), index * 1024);
+            assert_eq!(chunk.is_final(), index == 9);
+            reassembled.extend_from_slice(chunk.data());
+            index += 1;
+        }
+        assert_eq!(reassembled, content);
+    }
+
+    #[tokio::test]
+    async fn test_prefetcher_empty_file_has_no_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.bin");
+        std::fs::write(&path, b"").unwrap();
+
+        let mut prefetcher = ChunkPrefetcher::open(file_io(), &path, 1024, 4, window(1 << 20, 1024))
+            .await
+            .unwrap();
+        assert_eq!(prefetcher.total_chunks(), 0);
+        assert!(prefetcher.next_chunk().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefetcher_reads_ahead_only_within_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.bin");
+        std::fs::write(&path, vec![7u8; 8 * 4096]).unwrap();
+
+        // Room for two chunks, though the read-ahead depth asks for eight
+        let window = window(2 * 4096, 4096);
+        let mut prefetcher = ChunkPrefetcher::open(file_io(), &path, 4096, 8, window.clone())
+            .await
+            .unwrap();
+
+        let (first, first_permit) = prefetcher.next_chunk().await.unwrap().unwrap();
+        let (_second, second_permit) = prefetcher.next_chunk().await.unwrap().unwrap();
+        assert_eq!(window.available(), 0);
+        let third = tokio::time::timeout(std::time::Duration::from_millis(50), prefetcher.next_chunk()).await;
+        assert!(third.is_err(), "a third chunk must wait for window space");
+
+        // Releasing one chunk lets exactly the next one through, in order
+        drop(first_permit);
+        let (third, _third_permit) = prefetcher.next_chunk().await.unwrap().unwrap();
+        assert_eq!(third.sequence_number(), first.sequence_number() + 2);
+        drop(second_permit);
+    }
+
+    #[test]
+    fn test_prefetch_depth_follows_storage_and_channel() {
+        assert_eq!(prefetch_depth(StorageType::Network, 32), 16);
+        assert_eq!(prefetch_depth(StorageType::Network, 4), 4);
+        assert_eq!(prefetch_depth(StorageType::Hdd, 32), 8);
+        assert_eq!(prefetch_depth(StorageType::NVMe, 0), 1);
+    }
+}
@@ -19,7 +19,7 @@
 //!
 //! The compression service implementation provides:
 //!
-//! - **Multi-Algorithm Support**: Brotli, Gzip, Zstd, and Lz4 compression
+//! - **Multi-Algorithm Support**: Brotli, Gzip, Zstd, Lz4, and Xz compression
 //! - **Parallel Processing**: Multi-threaded compression for improved
 //!   performance
 //! - **Adaptive Configuration**: Algorithm selection based on data
@@ -60,6 +60,11 @@
 //! - **Performance**: Fastest compression, moderate ratio
 //! - **Memory**: Low memory usage
 //!
+//! ### Xz (LZMA2)
+//! - **Use Case**: Archival data where size matters more than speed
+//! - **Performance**: Slowest compression, highest ratios, fast decompression
+//! - **Memory**: Highest memory usage at the upper presets
+//!
 //! ## Performance Optimizations
 //!
 //! ### Parallel Processing
@@ -95,6 +100,7 @@
 use brotli::Decompressor;
 use flate2::read::{GzDecoder, GzEncoder};
 use flate2::Compression;
+use liblzma::read::{XzDecoder, XzEncoder};
 use std::io::{Read, Write};
 
 use adaptive_pipeline_domain::services::{
@@ -117,7 +123,7 @@ use adaptive_pipeline_domain::{FileChunk, PipelineError, ProcessingContext};
 ///
 /// # Features
 ///
-/// - **Multi-Algorithm Support**: Brotli, Gzip, Zstd, Lz4
+/// - **Multi-Algorithm Support**: Brotli, Gzip, Zstd, Lz4, Xz
 /// - **Parallel Processing**: Multi-threaded compression using Rayon
 /// - **Adaptive Configuration**: Algorithm selection based on data
 ///   characteristics
@@ -212,6 +218,30 @@ impl MultiAlgoCompression {
             .map_err(|e| PipelineError::CompressionError(format!("Zstd decompression failed: {}", e)))
     }
 
+    /// Compresses data using Xz (LZMA2) at preset `level` (0-9)
+    fn compress_xz(&self, data: &[u8], level: u32) -> Result<Vec<u8>, PipelineError> {
+        let mut output = Vec::new();
+        let mut encoder = XzEncoder::new(data, level);
+
+        encoder
+            .read_to_end(&mut output)
+            .map_err(|e| PipelineError::CompressionError(format!("Xz compression failed: {}", e)))?;
+
+        Ok(output)
+    }
+
+    /// Decompresses data using Xz (LZMA2) algorithm
+    fn decompress_xz(&self, data: &[u8]) -> Result<Vec<u8>, PipelineError> {
+        let mut output = Vec::new();
+        let mut decoder = XzDecoder::new(data);
+
+        decoder
+            .read_to_end(&mut output)
+            .map_err(|e| PipelineError::CompressionError(format!("Xz decompression failed: {}", e)))?;
+
+        Ok(output)
+    }
+
     /// Estimates compression ratio by sampling data
     fn estimate_ratio_from_sample(
         &self,
@@ -235,6 +265,10 @@ impl MultiAlgoCompression {
                 let compressed = self.compress_zstd(sample, 3)?;
                 compressed.len()
             }
+            CompressionAlgorithm::Xz => {
+                let compressed = self.compress_xz(sample, 6)?;
+                compressed.len()
+            }
             _ => {
                 return Err(PipelineError::CompressionError(
                     "Unsupported algorithm for estimation".to_string(),
@@ -260,6 +294,7 @@ impl CompressionService for MultiAlgoCompression {
             CompressionAlgorithm::Brotli => self.compress_brotli(&data, level)?,
             CompressionAlgorithm::Gzip => self.compress_gzip(&data, level)?,
             CompressionAlgorithm::Zstd => self.compress_zstd(&data, level as i32)?,
+            CompressionAlgorithm::Xz => self.compress_xz(&data, level)?,
             CompressionAlgorithm::Lz4 => {
                 return Err(PipelineError::CompressionError("LZ4 not yet implemented".to_string()));
             }
@@ -295,6 +330,7 @@ impl CompressionService for MultiAlgoCompression {
             CompressionAlgorithm::Brotli => self.decompress_brotli(&data)?,
             CompressionAlgorithm::Gzip => self.decompress_gzip(&data)?,
             CompressionAlgorithm::Zstd => self.decompress_zstd(&data)?,
+            CompressionAlgorithm::Xz => self.decompress_xz(&data)?,
             CompressionAlgorithm::Lz4 => {
                 return Err(PipelineError::CompressionError("LZ4 not yet implemented".to_string()));
             }
@@ -384,6 +420,14 @@ impl CompressionService for MultiAlgoCompression {
                     ));
                 }
             }
+            CompressionAlgorithm::Xz => {
+                let level = config.level.to_numeric(&config.algorithm);
+                if level > 9 {
+                    return Err(PipelineError::InvalidConfiguration(
+                        "Xz compression level must be between 0 and 9".to_string(),
+                    ));
+                }
+            }
             CompressionAlgorithm::Lz4 => {
                 return Err(PipelineError::CompressionError("LZ4 not yet implemented".to_string()));
             }
@@ -402,6 +446,7 @@ impl CompressionService for MultiAlgoCompression {
             CompressionAlgorithm::Brotli,
             CompressionAlgorithm::Gzip,
             CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Xz,
         ]
     }
 
@@ -417,6 +462,7 @@ impl CompressionService for MultiAlgoCompression {
             CompressionAlgorithm::Brotli => self.compress_brotli(test_data, 6)?,
             CompressionAlgorithm::Gzip => self.compress_gzip(test_data, 6)?,
             CompressionAlgorithm::Zstd => self.compress_zstd(test_data, 3)?,
+            CompressionAlgorithm::Xz => self.compress_xz(test_data, 6)?,
             _ => {
                 return Err(PipelineError::CompressionError(
                     "Algorithm not supported for benchmarking".to_string(),
@@ -433,6 +479,7 @@ impl CompressionService for MultiAlgoCompression {
             CompressionAlgorithm::Brotli => self.decompress_brotli(&compressed)?,
             CompressionAlgorithm::Gzip => self.decompress_gzip(&compressed)?,
             CompressionAlgorithm::Zstd => self.decompress_zstd(&compressed)?,
+            CompressionAlgorithm::Xz => self.decompress_xz(&compressed)?,
             _ => {
                 return Err(PipelineError::CompressionError(
                     "Algorithm not supported for benchmarking".to_string(),
@@ -462,6 +509,7 @@ impl adaptive_pipeline_domain::services::StageService for MultiAlgoCompression {
     fn process_chunk(
         &self,
         chunk: adaptive_pipeline_domain::FileChunk,
+        operation: adaptive_pipeline_domain::entities::Operation,
         config: &adaptive_pipeline_domain::entities::StageConfiguration,
         context: &mut adaptive_pipeline_domain::ProcessingContext,
     ) -> Result<adaptive_pipeline_domain::FileChunk, adaptive_pipeline_domain::PipelineError> {
@@ -470,7 +518,7 @@ impl adaptive_pipeline_domain::services::StageService for MultiAlgoCompression {
         // Type-safe extraction of CompressionConfig from parameters
         let compression_config = CompressionConfig::from_parameters(&config.parameters)?;
 
-        match config.operation {
+        match operation {
             adaptive_pipeline_domain::entities::Operation::Forward => {
                 self.compress_chunk(chunk, &compression_config, context)
             }
@@ -0,0 +1,293 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Direct I/O
+//!
+//! Page-cache-bypassing file access for the opt-in direct I/O mode
+//! (`FileIOConfig::direct_io`, `--direct-io`). Streaming a multi-terabyte
+//! backup through the page cache evicts everything co-located services had
+//! cached; direct I/O keeps the pipeline's data out of it.
+//!
+//! ## Reads
+//!
+//! Input files are opened with `O_DIRECT` (Linux), `F_NOCACHE` (macOS) or
+//! `FILE_FLAG_NO_BUFFERING` (Windows). Unbuffered reads must start at an
+//! aligned offset, cover an aligned length and land in an aligned buffer, so
+//! [`DirectReader::read_at`] widens each request to [`DIRECT_IO_ALIGNMENT`]
+//! boundaries, reads into an [`AlignedBuffer`] and copies out the bytes that
+//! were asked for.
+//!
+//! ```text
+//!   file:     |····block····|····block····|····block····|
+//!   request:        [=========== chunk ===========]
+//!   read:     [=========== aligned read ================]
+//! ```
+//!
+//! Filesystems that refuse unbuffered access (tmpfs on older kernels, some
+//! network filesystems) fall back to buffered reads.
+//!
+//! ## Writes
+//!
+//! `.adapipe` chunks land at arbitrary, unaligned offsets from concurrent
+//! workers, so output is written through the page cache and the written
+//! pages are dropped once they are durable ([`drop_cached_pages`]). On macOS
+//! `F_NOCACHE` applies to writes as well.
+
+use adaptive_pipeline_domain::PipelineError;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Offset, length and buffer alignment used for unbuffered reads
+///
+/// 4 KiB covers the logical block size of current disks (512-byte and 4Kn)
+/// and the page size on common platforms.
+pub const DIRECT_IO_ALIGNMENT: usize = 4096;
+
+/// Rounds `value` down to a multiple of [`DIRECT_IO_ALIGNMENT`]
+pub fn align_down(value: u64) -> u64 {
+    value & !(DIRECT_IO_ALIGNMENT as u64 - 1)
+}
+
+/// Rounds `value` up to a multiple of [`DIRECT_IO_ALIGNMENT`]
+pub fn align_up(value: u64) -> u64 {
+    align_down(value + DIRECT_IO_ALIGNMENT as u64 - 1)
+}
+
+/// Zeroed byte buffer whose start is aligned to [`DIRECT_IO_ALIGNMENT`]
+///
+/// Over-allocates by one alignment unit and starts the usable slice at the
+/// first aligned address inside the allocation.
+pub struct AlignedBuffer {
+    storage: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl AlignedBuffer {
+    /// Allocates `len` aligned bytes
+    pub fn new(len: usize) -> Self {
+        let storage = vec![0u8; len + DIRECT_IO_ALIGNMENT];
+        let start = storage.as_ptr().align_offset(DIRECT_IO_ALIGNMENT);
+        Self { storage, start, len }
+    }
+
+    /// The aligned bytes
+    pub fn as_slice(&self) -> &[u8] {
+        &self.storage[self.start..self.start + self.len]
+    }
+
+    /// The aligned bytes, mutably
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.storage[self.start..self.start + self.len]
+    }
+}
+
+/// Input file opened for page-cache-bypassing positional reads
+pub struct DirectReader {
+    path: PathBuf,
+    file: File,
+    direct: bool,
+}
+
+impl DirectReader {
+    /// Opens `path` unbuffered, or buffered if the filesystem refuses
+    pub fn open(path: &Path) -> Result<Self, PipelineError> {
+        let (file, direct) = match open_unbuffered(path) {
+            Ok(file) => (file, true),
+            Err(e) => {
+                debug!(
+                    "Direct I/O unavailable for {} ({}); using buffered reads",
+                    path.display(),
+                    e
+                );
+                let file = File::open(path)
+                    .map_err(|e| PipelineError::io_error(format!("Failed to open file {}: {}", path.display(), e)))?;
+                (file, false)
+            }
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            direct,
+        })
+    }
+
+    /// Whether reads bypass the page cache
+    pub fn is_direct(&self) -> bool {
+        self.direct
+    }
+
+    /// Reads up to `len` bytes at `offset`; fewer only at end of file
+    ///
+    /// Blocking; call from a blocking thread.
+    pub fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError> {
+        if !self.direct {
+            let mut data = vec![0u8; len];
+            let filled = self.fill_at(offset, &mut data)?;
+            data.truncate(filled);
+            return Ok(data);
+        }
+
+        let aligned_start = align_down(offset);
+        let aligned_len = (align_up(offset + len as u64) - aligned_start) as usize;
+        let mut buffer = AlignedBuffer::new(aligned_len);
+        let filled = self.fill_at(aligned_start, buffer.as_mut_slice())?;
+
+        let skip = (offset - aligned_start) as usize;
+        let end = filled.min(skip + len);
+        Ok(buffer.as_slice().get(skip..end).unwrap_or_default().to_vec())
+    }
+
+    /// Reads into `buf` at `offset` until it is full or the file ends
+    fn fill_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, PipelineError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let read = positional_read(&self.file, &mut buf[filled..], offset + filled as u64).map_err(|e| {
+                PipelineError::io_error(format!(
+                    "Failed to read {} at offset {}: {}",
+                    self.path.display(),
+                    offset + filled as u64,
+                    e
+                ))
+            })?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+            // An unbuffered read short of a block boundary means end of file
+            if self.direct && filled % DIRECT_IO_ALIGNMENT != 0 {
+                break;
+            }
+        }
+        Ok(filled)
+    }
+}
+
+#[cfg(unix)]
+fn positional_read(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.read_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn positional_read(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_read(buf, offset)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn open_unbuffered(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(path)
+}
+
+#[cfg(target_os = "macos")]
+fn open_unbuffered(path: &Path) -> std::io::Result<File> {
+    let file = File::open(path)?;
+    set_nocache(&file)?;
+    Ok(file)
+}
+
+#[cfg(windows)]
+fn open_unbuffered(path: &Path) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    /// `FILE_FLAG_NO_BUFFERING` from winbase.h
+    const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_NO_BUFFERING)
+        .open(path)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", windows)))]
+fn open_unbuffered(_path: &Path) -> std::io::Result<File> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "direct I/O is not supported on this platform",
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn set_nocache(file: &File) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: fcntl on a file descriptor we own, with an integer argument
+    let result = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) };
+    if result == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Keeps future writes to `file` out of the page cache where the platform
+/// can (macOS `F_NOCACHE`); elsewhere [`drop_cached_pages`] does the work
+pub fn bypass_write_cache(file: &File) -> Result<(), PipelineError> {
+    #[cfg(target_os = "macos")]
+    set_nocache(file).map_err(|e| PipelineError::io_error(format!("Failed to disable write caching: {}", e)))?;
+    #[cfg(not(target_os = "macos"))]
+    let _ = file;
+    Ok(())
+}
+
+/// Asks the kernel to evict `file`'s pages from the page cache
+///
+/// Only clean pages are dropped, so call this after syncing.
+pub fn drop_cached_pages(file: &File) -> Result<(), PipelineError> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        use std::os::unix::io::AsRawFd;
+        // SAFETY: posix_fadvise only reads its integer arguments
+        let result = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+        if result != 0 {
+            return Err(PipelineError::io_error(format!(
+                "Failed to drop cached pages: {}",
+                std::io::Error::from_raw_os_error(result)
+            )));
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let _ = file;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment_helpers() {
+        assert_eq!(align_down(0), 0);
+        assert_eq!(align_down(4095), 0);
+        assert_eq!(align_down(4097), 4096);
+        assert_eq!(align_up(1), 4096);
+        assert_eq!(align_up(4096), 4096);
+        assert_eq!(align_up(8193), 12288);
+
+        let mut buffer = AlignedBuffer::new(10_000);
+        assert_eq!(buffer.as_mut_slice().as_ptr() as usize % DIRECT_IO_ALIGNMENT, 0);
+        assert_eq!(buffer.as_slice().len(), 10_000);
+    }
+
+    #[test]
+    fn test_direct_reader_handles_unaligned_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.bin");
+        let content: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        // Direct or buffered depending on the filesystem; same bytes either way
+        let reader = DirectReader::open(&path).unwrap();
+        assert_eq!(reader.read_at(0, 4096).unwrap(), &content[..4096]);
+        assert_eq!(reader.read_at(1000, 5000).unwrap(), &content[1000..6000]);
+        assert_eq!(reader.read_at(18_000, 5000).unwrap(), &content[18_000..]);
+        assert!(reader.read_at(25_000, 100).unwrap().is_empty());
+    }
+}
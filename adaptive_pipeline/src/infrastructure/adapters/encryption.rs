@@ -121,58 +121,52 @@ use argon2::password_hash::SaltString;
 use argon2::{Argon2, PasswordHasher};
 use base64::engine::general_purpose;
 use base64::Engine as _;
-use chacha20poly1305::{ChaCha20Poly1305, Key as ChaChaKey, Nonce as ChaChaNonce};
+use chacha20poly1305::{ChaCha20Poly1305, Key as ChaChaKey, Nonce as ChaChaNonce, XChaCha20Poly1305, XNonce};
 use ring::rand::{SecureRandom, SystemRandom};
 use scrypt::password_hash::SaltString as ScryptSalt;
 use scrypt::Scrypt;
 use std::collections::HashMap;
+use tracing::debug;
 use zeroize::Zeroize;
 
 use adaptive_pipeline_domain::entities::{ProcessingContext, SecurityContext};
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
 use adaptive_pipeline_domain::services::{
     EncryptionAlgorithm, EncryptionConfig, EncryptionService, KeyDerivationFunction, KeyMaterial,
 };
-use adaptive_pipeline_domain::value_objects::{EncryptionBenchmark, FileChunk};
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::{chunk_associated_data, CHUNK_ASSOCIATED_DATA};
+use adaptive_pipeline_domain::value_objects::nonce_strategy::EXTENDED_NONCE_LENGTH;
+use adaptive_pipeline_domain::value_objects::password_kdf::PASSWORD_SALT_LENGTH;
+use adaptive_pipeline_domain::value_objects::{
+    EncryptionBenchmark, FileChunk, KdfCost, NonceStrategy, PasswordKdf, SecretBytes, FIPS_MODE,
+};
 use adaptive_pipeline_domain::PipelineError;
 
-// NOTE: Domain traits are now synchronous. This implementation is sync and
-// CPU-bound. For async contexts, wrap this implementation with
-// AsyncEncryptionAdapter.
+/// Length of keys derived from a password
+pub const PASSWORD_KEY_LENGTH: usize = 32;
 
-/// Secure key material that automatically zeros sensitive data on drop
-///
-/// This struct provides secure storage for cryptographic key material with
-/// automatic zeroization when the key goes out of scope. It ensures that
-/// sensitive data is properly cleared from memory to prevent information
-/// leakage.
-///
-/// # Security Features
-///
-/// - **Automatic Zeroization**: Key data is securely wiped when dropped
-/// - **Clone Support**: Allows key material to be safely copied when needed
-/// - **Memory Protection**: Minimizes exposure of sensitive data
-///
-/// # Examples
-#[derive(Clone)]
-#[allow(dead_code)]
-struct SecureKey {
-    data: Vec<u8>,
-}
+/// Time deriving a password key should take at the calibrated cost
+pub const KDF_TARGET_DURATION: std::time::Duration = std::time::Duration::from_secs(1);
 
-impl SecureKey {
-    fn new(data: Vec<u8>) -> Self {
-        Self { data }
-    }
+/// Largest memory cost calibration picks (256 MiB); slower hosts get more
+/// passes rather than more memory
+const CALIBRATED_MAX_MEMORY_KIB: u32 = 256 * 1024;
 
-    fn as_slice(&self) -> &[u8] {
-        &self.data
-    }
-}
+/// Memory cost of the single pass timed during calibration (16 MiB)
+const CALIBRATION_PROBE_KIB: u32 = 16 * 1024;
+
+// NOTE: Domain traits are now synchronous. This implementation is sync and
+// CPU-bound. For async contexts, wrap this implementation with
+// AsyncEncryptionAdapter.
 
 /// Concrete implementation of the encryption service
 pub struct MultiAlgoEncryption {
     rng: SystemRandom,
-    key_cache: HashMap<String, SecureKey>,
+    key_cache: HashMap<String, SecretBytes>,
+    // Fingerprints of every (key, nonce) pair sealed, to catch reuse in
+    // debug builds
+    #[cfg(debug_assertions)]
+    issued_nonces: parking_lot::Mutex<std::collections::HashSet<Vec<u8>>>,
 }
 
 impl Default for MultiAlgoEncryption {
@@ -186,14 +180,16 @@ impl MultiAlgoEncryption {
         Self {
             rng: SystemRandom::new(),
             key_cache: HashMap::new(),
+            #[cfg(debug_assertions)]
+            issued_nonces: parking_lot::Mutex::new(std::collections::HashSet::new()),
         }
     }
 
     /// Generates a secure random key of the specified length
-    fn generate_key(&self, length: usize) -> Result<Vec<u8>, PipelineError> {
-        let mut key = vec![0u8; length];
+    fn generate_key(&self, length: usize) -> Result<SecretBytes, PipelineError> {
+        let mut key = SecretBytes::zeroed(length);
         self.rng
-            .fill(&mut key)
+            .fill(key.expose_secret_mut())
             .map_err(|e| PipelineError::EncryptionError(format!("Failed to generate key: {:?}", e)))?;
         Ok(key)
     }
@@ -207,8 +203,75 @@ impl MultiAlgoEncryption {
         Ok(nonce)
     }
 
+    /// Picks the nonce for `chunk` under `strategy`
+    ///
+    /// Counter nonces take their prefix from the random part of the
+    /// context's run id, which every chunk of one file run shares, and their
+    /// counter from the chunk's sequence number.
+    fn chunk_nonce(
+        &self,
+        strategy: NonceStrategy,
+        chunk: &FileChunk,
+        context: &ProcessingContext,
+    ) -> Result<Vec<u8>, PipelineError> {
+        match strategy {
+            NonceStrategy::Random | NonceStrategy::XChaChaRandom => self.generate_nonce(strategy.nonce_length()),
+            NonceStrategy::Counter => {
+                let prefix = (context.run_id().as_ulid().random() as u64).to_be_bytes();
+                Ok(NonceStrategy::counter_nonce(prefix, chunk.sequence_number())?.to_vec())
+            }
+        }
+    }
+
+    /// Associated data for `chunk`: the configured file binding followed by
+    /// the chunk's sequence number, or empty when the config has none
+    fn chunk_associated_data(config: &EncryptionConfig, chunk: &FileChunk) -> Vec<u8> {
+        match &config.associated_data {
+            Some(file_data) => chunk_associated_data(file_data, chunk.sequence_number()),
+            None => CHUNK_ASSOCIATED_DATA.to_vec(),
+        }
+    }
+
+    /// Fails if `nonce` was already used with `key` by this service
+    ///
+    /// Only debug builds keep the record; release builds rely on the nonce
+    /// strategy alone.
+    #[cfg(debug_assertions)]
+    fn ensure_nonce_unused(&self, key: &[u8], nonce: &[u8]) -> Result<(), PipelineError> {
+        let mut fingerprint = ring::digest::Context::new(&ring::digest::SHA256);
+        fingerprint.update(key);
+        fingerprint.update(nonce);
+        if !self.issued_nonces.lock().insert(fingerprint.finish().as_ref().to_vec()) {
+            return Err(PipelineError::EncryptionError(format!(
+                "Nonce {} was already used with this key",
+                hex::encode(nonce)
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn ensure_nonce_unused(&self, _key: &[u8], _nonce: &[u8]) -> Result<(), PipelineError> {
+        Ok(())
+    }
+
+    /// Returns an error if this build does not allow `kdf`
+    ///
+    /// PBKDF2-HMAC-SHA256 is the only FIPS-approved key derivation function
+    /// here; a FIPS build ([`FIPS_MODE`]) refuses Argon2 and scrypt.
+    fn ensure_kdf_permitted(kdf: &KeyDerivationFunction) -> Result<(), PipelineError> {
+        if !FIPS_MODE || *kdf == KeyDerivationFunction::Pbkdf2 {
+            return Ok(());
+        }
+        Err(PipelineError::unsupported_operation(format!(
+            "{} key derivation is not FIPS-approved and is disabled in this FIPS-mode build; use PBKDF2",
+            kdf
+        )))
+    }
+
     /// Derives a key using Argon2
-    fn derive_key_argon2(&self, password: &[u8], salt: &[u8], key_length: usize) -> Result<Vec<u8>, PipelineError> {
+    fn derive_key_argon2(&self, password: &[u8], salt: &[u8], key_length: usize) -> Result<SecretBytes, PipelineError> {
+        Self::ensure_kdf_permitted(&KeyDerivationFunction::Argon2)?;
         let argon2 = Argon2::default();
         let salt_string =
             SaltString::encode_b64(salt).map_err(|e| PipelineError::EncryptionError(format!("Invalid salt: {}", e)))?;
@@ -222,14 +285,87 @@ impl MultiAlgoEncryption {
             .ok_or_else(|| PipelineError::EncryptionError("Password hash missing".to_string()))?;
         let hash_bytes = hash_string.as_bytes();
         if hash_bytes.len() >= key_length {
-            Ok(hash_bytes[..key_length].to_vec())
+            Ok(SecretBytes::from_slice(&hash_bytes[..key_length]))
         } else {
             Err(PipelineError::EncryptionError("Derived key too short".to_string()))
         }
     }
 
+    /// Draws a fresh salt for a password-protected file, at `cost`
+    pub fn new_password_kdf(cost: KdfCost) -> Result<PasswordKdf, PipelineError> {
+        let mut salt = vec![0u8; PASSWORD_SALT_LENGTH];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|e| PipelineError::EncryptionError(format!("Failed to generate salt: {:?}", e)))?;
+        PasswordKdf::with_cost(salt, cost)
+    }
+
+    /// Argon2id cost that takes about [`KDF_TARGET_DURATION`] on this host
+    ///
+    /// Measured once per process by timing a single small pass, then scaling
+    /// memory up to 256 MiB and passes after that. Never below
+    /// [`KdfCost::default`], so a fast probe cannot weaken new files.
+    pub fn calibrated_kdf_cost() -> KdfCost {
+        static COST: std::sync::OnceLock<KdfCost> = std::sync::OnceLock::new();
+        *COST.get_or_init(|| {
+            let cost = Self::benchmark_kdf_cost(KDF_TARGET_DURATION);
+            debug!("Calibrated Argon2id cost: {}", cost);
+            cost
+        })
+    }
+
+    fn benchmark_kdf_cost(target: std::time::Duration) -> KdfCost {
+        let floor = KdfCost::default();
+        let Ok(params) = argon2::Params::new(CALIBRATION_PROBE_KIB, 1, 1, Some(PASSWORD_KEY_LENGTH)) else {
+            return floor;
+        };
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+        let mut output = [0u8; PASSWORD_KEY_LENGTH];
+        let start = std::time::Instant::now();
+        if argon2
+            .hash_password_into(b"calibration", &[0u8; PASSWORD_SALT_LENGTH], &mut output)
+            .is_err()
+        {
+            return floor;
+        }
+        let probe = start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+        // Argon2 time grows linearly with memory × passes
+        let budget = target.as_secs_f64() / probe * f64::from(CALIBRATION_PROBE_KIB);
+        let memory_kib = (budget / f64::from(floor.iterations()))
+            .clamp(f64::from(floor.memory_kib()), f64::from(CALIBRATED_MAX_MEMORY_KIB)) as u32;
+        let iterations = (budget / f64::from(memory_kib))
+            .clamp(f64::from(floor.iterations()), f64::from(KdfCost::MAX_ITERATIONS)) as u32;
+        KdfCost::new(memory_kib, iterations, floor.parallelism()).unwrap_or(floor)
+    }
+
+    /// Derives the encryption key of a password-protected file with the
+    /// Argon2id parameters recorded in its header
+    ///
+    /// Both ciphers the encryption stage offers take 32-byte keys. A FIPS
+    /// build refuses, as Argon2id is not FIPS-approved.
+    pub fn derive_password_key(password: &SecretBytes, kdf: &PasswordKdf) -> Result<SecretBytes, PipelineError> {
+        Self::ensure_kdf_permitted(&KeyDerivationFunction::Argon2)?;
+        let cost = kdf.cost();
+        let params = argon2::Params::new(
+            cost.memory_kib(),
+            cost.iterations(),
+            cost.parallelism(),
+            Some(PASSWORD_KEY_LENGTH),
+        )
+        .map_err(|e| PipelineError::InvalidParameter(format!("Invalid Argon2id parameters: {}", e)))?;
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut key = SecretBytes::zeroed(PASSWORD_KEY_LENGTH);
+        argon2
+            .hash_password_into(password.expose_secret(), kdf.salt(), key.expose_secret_mut())
+            .map_err(|e| PipelineError::EncryptionError(format!("Argon2id key derivation failed: {}", e)))?;
+        Ok(key)
+    }
+
     /// Derives a key using scrypt
-    fn derive_key_scrypt(&self, password: &[u8], salt: &[u8], key_length: usize) -> Result<Vec<u8>, PipelineError> {
+    fn derive_key_scrypt(&self, password: &[u8], salt: &[u8], key_length: usize) -> Result<SecretBytes, PipelineError> {
+        Self::ensure_kdf_permitted(&KeyDerivationFunction::Scrypt)?;
         let scrypt = Scrypt;
         let salt_string =
             ScryptSalt::encode_b64(salt).map_err(|e| PipelineError::EncryptionError(format!("Invalid salt: {}", e)))?;
@@ -243,7 +379,7 @@ impl MultiAlgoEncryption {
             .ok_or_else(|| PipelineError::EncryptionError("Password hash missing".to_string()))?;
         let hash_bytes = hash_string.as_bytes();
         if hash_bytes.len() >= key_length {
-            Ok(hash_bytes[..key_length].to_vec())
+            Ok(SecretBytes::from_slice(&hash_bytes[..key_length]))
         } else {
             Err(PipelineError::EncryptionError("Derived key too short".to_string()))
         }
@@ -256,21 +392,27 @@ impl MultiAlgoEncryption {
         salt: &[u8],
         iterations: u32,
         key_length: usize,
-    ) -> Result<Vec<u8>, PipelineError> {
-        let mut key = vec![0u8; key_length];
+    ) -> Result<SecretBytes, PipelineError> {
+        let mut key = SecretBytes::zeroed(key_length);
         ring::pbkdf2::derive(
             ring::pbkdf2::PBKDF2_HMAC_SHA256,
             std::num::NonZeroU32::new(iterations)
                 .ok_or_else(|| PipelineError::EncryptionError("Invalid iteration count".to_string()))?,
             salt,
             password,
-            &mut key,
+            key.expose_secret_mut(),
         );
         Ok(key)
     }
 
     /// Encrypts data using AES-256-GCM
-    fn encrypt_aes256_gcm(&self, data: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    fn encrypt_aes256_gcm(
+        &self,
+        data: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
         if key.len() != 32 {
             return Err(PipelineError::EncryptionError(
                 "AES-256 requires 32-byte key".to_string(),
@@ -288,7 +430,7 @@ impl MultiAlgoEncryption {
 
         let mut buffer = data.to_vec();
         cipher
-            .encrypt_in_place(nonce_array, b"", &mut buffer)
+            .encrypt_in_place(nonce_array, associated_data, &mut buffer)
             .map_err(|e| PipelineError::EncryptionError(format!("AES-256-GCM encryption failed: {:?}", e)))?;
 
         // Prepend nonce to encrypted data
@@ -298,7 +440,7 @@ impl MultiAlgoEncryption {
     }
 
     /// Decrypts data using AES-256-GCM
-    fn decrypt_aes256_gcm(&self, data: &[u8], key: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    fn decrypt_aes256_gcm(&self, data: &[u8], key: &[u8], associated_data: &[u8]) -> Result<Vec<u8>, PipelineError> {
         if key.len() != 32 {
             return Err(PipelineError::EncryptionError(
                 "AES-256 requires 32-byte key".to_string(),
@@ -315,14 +457,20 @@ impl MultiAlgoEncryption {
 
         let mut buffer = ciphertext.to_vec();
         cipher
-            .decrypt_in_place(nonce_array, b"", &mut buffer)
+            .decrypt_in_place(nonce_array, associated_data, &mut buffer)
             .map_err(|e| PipelineError::EncryptionError(format!("AES-256-GCM decryption failed: {:?}", e)))?;
 
         Ok(buffer)
     }
 
     /// Encrypts data using ChaCha20-Poly1305
-    fn encrypt_chacha20_poly1305(&self, data: &[u8], key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    fn encrypt_chacha20_poly1305(
+        &self,
+        data: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
         if key.len() != 32 {
             return Err(PipelineError::EncryptionError(
                 "ChaCha20 requires 32-byte key".to_string(),
@@ -340,7 +488,7 @@ impl MultiAlgoEncryption {
 
         let mut buffer = data.to_vec();
         cipher
-            .encrypt_in_place(nonce_array, b"", &mut buffer)
+            .encrypt_in_place(nonce_array, associated_data, &mut buffer)
             .map_err(|e| PipelineError::EncryptionError(format!("ChaCha20-Poly1305 encryption failed: {:?}", e)))?;
 
         // Prepend nonce to encrypted data
@@ -350,7 +498,12 @@ impl MultiAlgoEncryption {
     }
 
     /// Decrypts data using ChaCha20-Poly1305
-    fn decrypt_chacha20_poly1305(&self, data: &[u8], key: &[u8]) -> Result<Vec<u8>, PipelineError> {
+    fn decrypt_chacha20_poly1305(
+        &self,
+        data: &[u8],
+        key: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
         if key.len() != 32 {
             return Err(PipelineError::EncryptionError(
                 "ChaCha20 requires 32-byte key".to_string(),
@@ -367,38 +520,92 @@ impl MultiAlgoEncryption {
 
         let mut buffer = ciphertext.to_vec();
         cipher
-            .decrypt_in_place(nonce_array, b"", &mut buffer)
+            .decrypt_in_place(nonce_array, associated_data, &mut buffer)
             .map_err(|e| PipelineError::EncryptionError(format!("ChaCha20-Poly1305 decryption failed: {:?}", e)))?;
 
         Ok(buffer)
     }
 
-    /// Calculates SHA-256 hash for integrity verification
-    fn calculate_hash(&self, data: &[u8]) -> Vec<u8> {
-        ring::digest::digest(&ring::digest::SHA256, data).as_ref().to_vec()
+    /// Encrypts data using XChaCha20-Poly1305
+    fn encrypt_xchacha20_poly1305(
+        &self,
+        data: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
+        if key.len() != 32 {
+            return Err(PipelineError::EncryptionError(
+                "XChaCha20 requires 32-byte key".to_string(),
+            ));
+        }
+        if nonce.len() != EXTENDED_NONCE_LENGTH {
+            return Err(PipelineError::EncryptionError(
+                "XChaCha20-Poly1305 requires 24-byte nonce".to_string(),
+            ));
+        }
+
+        let cipher = XChaCha20Poly1305::new(ChaChaKey::from_slice(key));
+        let mut buffer = data.to_vec();
+        cipher
+            .encrypt_in_place(XNonce::from_slice(nonce), associated_data, &mut buffer)
+            .map_err(|e| PipelineError::EncryptionError(format!("XChaCha20-Poly1305 encryption failed: {:?}", e)))?;
+
+        // Prepend nonce to encrypted data
+        let mut result = nonce.to_vec();
+        result.extend_from_slice(&buffer);
+        Ok(result)
     }
-}
 
-impl EncryptionService for MultiAlgoEncryption {
-    fn encrypt_chunk(
+    /// Decrypts data using XChaCha20-Poly1305
+    fn decrypt_xchacha20_poly1305(
         &self,
-        chunk: FileChunk,
-        config: &EncryptionConfig,
-        key_material: &KeyMaterial,
-        context: &mut ProcessingContext,
-    ) -> Result<FileChunk, PipelineError> {
-        let data = chunk.data().to_vec();
+        data: &[u8],
+        key: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
+        if key.len() != 32 {
+            return Err(PipelineError::EncryptionError(
+                "XChaCha20 requires 32-byte key".to_string(),
+            ));
+        }
+        if data.len() < EXTENDED_NONCE_LENGTH {
+            return Err(PipelineError::EncryptionError("Encrypted data too short".to_string()));
+        }
 
-        // Use the provided key material
-        let key = key_material;
+        let (nonce, ciphertext) = data.split_at(EXTENDED_NONCE_LENGTH);
+        let cipher = XChaCha20Poly1305::new(ChaChaKey::from_slice(key));
+        let mut buffer = ciphertext.to_vec();
+        cipher
+            .decrypt_in_place(XNonce::from_slice(nonce), associated_data, &mut buffer)
+            .map_err(|e| PipelineError::EncryptionError(format!("XChaCha20-Poly1305 decryption failed: {:?}", e)))?;
 
-        // Generate nonce
-        let nonce = self.generate_nonce(12)?; // 12 bytes for GCM/ChaCha20-Poly1305
+        Ok(buffer)
+    }
 
-        // Encrypt based on algorithm
-        let encrypted_data = match &config.algorithm {
-            EncryptionAlgorithm::Aes256Gcm => self.encrypt_aes256_gcm(&data, &key.key, &nonce)?,
-            EncryptionAlgorithm::ChaCha20Poly1305 => self.encrypt_chacha20_poly1305(&data, &key.key, &nonce)?,
+    /// Encrypts `data` under `nonce`, returning the nonce followed by the
+    /// ciphertext and tag
+    ///
+    /// This is the chunk layout described in
+    /// [`chunk_encryption_spec`](adaptive_pipeline_domain::value_objects::chunk_encryption_spec).
+    /// `encrypt_chunk` calls it with the nonce its [`NonceStrategy`] picks
+    /// and the chunk's associated data; test vectors call it with fixed
+    /// ones. A 24-byte nonce with `chacha20-poly1305` seals with
+    /// XChaCha20-Poly1305.
+    pub fn seal_with_nonce(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+        data: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
+        match algorithm {
+            EncryptionAlgorithm::Aes256Gcm => self.encrypt_aes256_gcm(data, key, nonce, associated_data),
+            EncryptionAlgorithm::ChaCha20Poly1305 if nonce.len() == EXTENDED_NONCE_LENGTH => {
+                self.encrypt_xchacha20_poly1305(data, key, nonce, associated_data)
+            }
+            EncryptionAlgorithm::ChaCha20Poly1305 => self.encrypt_chacha20_poly1305(data, key, nonce, associated_data),
             EncryptionAlgorithm::Aes128Gcm => {
                 if key.len() != 16 {
                     return Err(PipelineError::EncryptionError(
@@ -406,9 +613,9 @@ impl EncryptionService for MultiAlgoEncryption {
                     ));
                 }
                 // Similar implementation for AES-128
-                return Err(PipelineError::EncryptionError(
+                Err(PipelineError::EncryptionError(
                     "AES-128-GCM not yet fully implemented".to_string(),
-                ));
+                ))
             }
             EncryptionAlgorithm::Aes192Gcm => {
                 if key.len() != 24 {
@@ -417,17 +624,89 @@ impl EncryptionService for MultiAlgoEncryption {
                     ));
                 }
                 // Similar implementation for AES-192
-                return Err(PipelineError::EncryptionError(
+                Err(PipelineError::EncryptionError(
                     "AES-192-GCM not yet fully implemented".to_string(),
-                ));
+                ))
             }
-            EncryptionAlgorithm::Custom(name) => {
-                return Err(PipelineError::EncryptionError(format!(
-                    "Custom algorithm '{}' not implemented",
-                    name
-                )));
+            EncryptionAlgorithm::Custom(name) => Err(PipelineError::EncryptionError(format!(
+                "Custom algorithm '{}' not implemented",
+                name
+            ))),
+        }
+    }
+
+    /// Decrypts data laid out as the nonce followed by the ciphertext and
+    /// tag, the inverse of [`seal_with_nonce`](Self::seal_with_nonce)
+    pub fn open(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+        data: &[u8],
+        key: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
+        self.open_with_strategy(algorithm, NonceStrategy::default(), data, key, associated_data)
+    }
+
+    /// Decrypts data sealed under `nonce_strategy`, whose nonce length
+    /// determines where the nonce ends and which AEAD opens it
+    pub fn open_with_strategy(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+        nonce_strategy: NonceStrategy,
+        data: &[u8],
+        key: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, PipelineError> {
+        match algorithm {
+            EncryptionAlgorithm::Aes256Gcm => self.decrypt_aes256_gcm(data, key, associated_data),
+            EncryptionAlgorithm::ChaCha20Poly1305 if nonce_strategy.nonce_length() == EXTENDED_NONCE_LENGTH => {
+                self.decrypt_xchacha20_poly1305(data, key, associated_data)
             }
-        };
+            EncryptionAlgorithm::ChaCha20Poly1305 => self.decrypt_chacha20_poly1305(data, key, associated_data),
+            EncryptionAlgorithm::Aes128Gcm => Err(PipelineError::EncryptionError(
+                "AES-128-GCM not yet fully implemented".to_string(),
+            )),
+            EncryptionAlgorithm::Aes192Gcm => Err(PipelineError::EncryptionError(
+                "AES-192-GCM not yet fully implemented".to_string(),
+            )),
+            EncryptionAlgorithm::Custom(name) => Err(PipelineError::EncryptionError(format!(
+                "Custom algorithm '{}' not implemented",
+                name
+            ))),
+        }
+    }
+
+    /// Calculates SHA-256 hash for integrity verification
+    fn calculate_hash(&self, data: &[u8]) -> Vec<u8> {
+        ring::digest::digest(&ring::digest::SHA256, data).as_ref().to_vec()
+    }
+}
+
+impl EncryptionService for MultiAlgoEncryption {
+    fn encrypt_chunk(
+        &self,
+        chunk: FileChunk,
+        config: &EncryptionConfig,
+        key_material: &KeyMaterial,
+        context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        let data = chunk.data().to_vec();
+
+        // Use the provided key material
+        let key = key_material;
+
+        // Pick this chunk's nonce; it must never repeat under the same key
+        let nonce = self.chunk_nonce(config.nonce_strategy, &chunk, context)?;
+        self.ensure_nonce_unused(key.key.expose_secret(), &nonce)?;
+
+        let associated_data = Self::chunk_associated_data(config, &chunk);
+        let encrypted_data = self.seal_with_nonce(
+            &config.algorithm,
+            &data,
+            key.key.expose_secret(),
+            &nonce,
+            &associated_data,
+        )?;
 
         // Create new chunk with encrypted data
         let chunk = chunk.with_data(encrypted_data)?;
@@ -437,6 +716,7 @@ impl EncryptionService for MultiAlgoEncryption {
 
         // Update context with encryption metadata
         context.add_metadata("encryption_algorithm".to_string(), config.algorithm.to_string());
+        context.add_metadata("nonce_strategy".to_string(), config.nonce_strategy.to_string());
         context.add_metadata("integrity_hash".to_string(), hex::encode(&integrity_hash));
         context.add_metadata("encrypted".to_string(), "true".to_string());
 
@@ -452,44 +732,34 @@ impl EncryptionService for MultiAlgoEncryption {
     ) -> Result<FileChunk, PipelineError> {
         let data = chunk.data().to_vec();
 
-        // Use the provided key material
-        let key = key_material;
-
-        // Decrypt based on algorithm
-        let decrypted_data = match &config.algorithm {
-            EncryptionAlgorithm::Aes256Gcm => self.decrypt_aes256_gcm(&data, &key.key)?,
-            EncryptionAlgorithm::ChaCha20Poly1305 => self.decrypt_chacha20_poly1305(&data, &key.key)?,
-            EncryptionAlgorithm::Aes128Gcm => {
-                return Err(PipelineError::EncryptionError(
-                    "AES-128-GCM not yet fully implemented".to_string(),
-                ));
-            }
-            EncryptionAlgorithm::Aes192Gcm => {
-                return Err(PipelineError::EncryptionError(
-                    "AES-192-GCM not yet fully implemented".to_string(),
-                ));
-            }
-            EncryptionAlgorithm::Custom(name) => {
-                return Err(PipelineError::EncryptionError(format!(
-                    "Custom algorithm '{}' not implemented",
-                    name
-                )));
-            }
-        };
-
-        // Create new chunk with decrypted data
-        let chunk = chunk.with_data(decrypted_data)?;
-
-        // Verify integrity if hash is available
+        // Verify integrity if hash is available. The hash recorded by
+        // `encrypt_chunk` covers the ciphertext, so it is checked against the
+        // input before decrypting.
         if let Some(expected_hash) = context.get_metadata("integrity_hash") {
-            let actual_hash = hex::encode(self.calculate_hash(chunk.data()));
-            if actual_hash != *expected_hash {
+            let actual_hash = hex::encode(self.calculate_hash(&data));
+            if !constant_time_eq_str(&actual_hash, expected_hash) {
                 return Err(PipelineError::EncryptionError(
                     "Integrity verification failed".to_string(),
                 ));
             }
         }
 
+        // Use the provided key material
+        let key = key_material;
+
+        // A chunk opened at any position but its own fails to authenticate
+        let associated_data = Self::chunk_associated_data(config, &chunk);
+        let decrypted_data = self.open_with_strategy(
+            &config.algorithm,
+            config.nonce_strategy,
+            &data,
+            key.key.expose_secret(),
+            &associated_data,
+        )?;
+
+        // Create new chunk with decrypted data
+        let chunk = chunk.with_data(decrypted_data)?;
+
         // Update context
         context.add_metadata("decryption_algorithm".to_string(), config.algorithm.to_string());
         context.add_metadata("encrypted".to_string(), "false".to_string());
@@ -499,11 +769,11 @@ impl EncryptionService for MultiAlgoEncryption {
 
     fn derive_key_material(
         &self,
-        password: &str,
+        password: &SecretBytes,
         config: &EncryptionConfig,
         security_context: &SecurityContext,
     ) -> Result<KeyMaterial, PipelineError> {
-        let password_bytes = password.as_bytes();
+        let password_bytes = password.expose_secret();
         let salt = self.generate_nonce(32)?; // 32-byte salt
 
         let key_length = match &config.algorithm {
@@ -573,6 +843,7 @@ impl EncryptionService for MultiAlgoEncryption {
         }
 
         // Validate key derivation function
+        Self::ensure_kdf_permitted(&config.key_derivation)?;
         match &config.key_derivation {
             KeyDerivationFunction::Argon2 => {}
             KeyDerivationFunction::Scrypt => {}
@@ -608,8 +879,12 @@ impl EncryptionService for MultiAlgoEncryption {
 
         // Encrypt the data
         let encrypted = match algorithm {
-            EncryptionAlgorithm::Aes256Gcm => self.encrypt_aes256_gcm(test_data, &key, &nonce)?,
-            EncryptionAlgorithm::ChaCha20Poly1305 => self.encrypt_chacha20_poly1305(test_data, &key, &nonce)?,
+            EncryptionAlgorithm::Aes256Gcm => {
+                self.encrypt_aes256_gcm(test_data, key.expose_secret(), &nonce, CHUNK_ASSOCIATED_DATA)?
+            }
+            EncryptionAlgorithm::ChaCha20Poly1305 => {
+                self.encrypt_chacha20_poly1305(test_data, key.expose_secret(), &nonce, CHUNK_ASSOCIATED_DATA)?
+            }
             _ => {
                 return Err(PipelineError::EncryptionError(
                     "Algorithm not supported for benchmarking".to_string(),
@@ -622,8 +897,12 @@ impl EncryptionService for MultiAlgoEncryption {
         // Benchmark decryption
         let start = std::time::Instant::now();
         let _decrypted = match algorithm {
-            EncryptionAlgorithm::Aes256Gcm => self.decrypt_aes256_gcm(&encrypted, &key)?,
-            EncryptionAlgorithm::ChaCha20Poly1305 => self.decrypt_chacha20_poly1305(&encrypted, &key)?,
+            EncryptionAlgorithm::Aes256Gcm => {
+                self.decrypt_aes256_gcm(&encrypted, key.expose_secret(), CHUNK_ASSOCIATED_DATA)?
+            }
+            EncryptionAlgorithm::ChaCha20Poly1305 => {
+                self.decrypt_chacha20_poly1305(&encrypted, key.expose_secret(), CHUNK_ASSOCIATED_DATA)?
+            }
             _ => {
                 return Err(PipelineError::EncryptionError(
                     "Algorithm not supported for benchmarking".to_string(),
@@ -712,6 +991,7 @@ impl adaptive_pipeline_domain::services::StageService for MultiAlgoEncryption {
     fn process_chunk(
         &self,
         chunk: adaptive_pipeline_domain::FileChunk,
+        operation: adaptive_pipeline_domain::entities::Operation,
         config: &adaptive_pipeline_domain::entities::StageConfiguration,
         context: &mut adaptive_pipeline_domain::ProcessingContext,
     ) -> Result<adaptive_pipeline_domain::FileChunk, adaptive_pipeline_domain::PipelineError> {
@@ -721,38 +1001,31 @@ impl adaptive_pipeline_domain::services::StageService for MultiAlgoEncryption {
         let encryption_config = EncryptionConfig::from_parameters(&config.parameters)?;
 
         // Extract KeyMaterial from parameters
-        // Expected format: base64-encoded key, nonce, salt
-        let key_b64 = config
-            .parameters
-            .get("key")
-            .ok_or_else(|| adaptive_pipeline_domain::PipelineError::MissingParameter("key".into()))?;
-
-        let nonce_b64 = config
-            .parameters
-            .get("nonce")
-            .ok_or_else(|| adaptive_pipeline_domain::PipelineError::MissingParameter("nonce".into()))?;
-
-        let salt_b64 = config
-            .parameters
-            .get("salt")
-            .ok_or_else(|| adaptive_pipeline_domain::PipelineError::MissingParameter("salt".into()))?;
-
-        // Decode base64 strings using Engine API
-        let key = general_purpose::STANDARD.decode(key_b64).map_err(|e| {
-            adaptive_pipeline_domain::PipelineError::InvalidParameter(format!("Invalid base64 key: {}", e))
-        })?;
-
-        let nonce = general_purpose::STANDARD.decode(nonce_b64).map_err(|e| {
-            adaptive_pipeline_domain::PipelineError::InvalidParameter(format!("Invalid base64 nonce: {}", e))
-        })?;
-
-        let salt = general_purpose::STANDARD.decode(salt_b64).map_err(|e| {
-            adaptive_pipeline_domain::PipelineError::InvalidParameter(format!("Invalid base64 salt: {}", e))
-        })?;
+        // Expected format: base64-encoded key, and optionally nonce and salt;
+        // chunk nonces come from the nonce strategy, not the key material
+        let decode = |name: &str| {
+            config
+                .parameters
+                .get(name)
+                .map(|value| {
+                    general_purpose::STANDARD.decode(value).map_err(|e| {
+                        adaptive_pipeline_domain::PipelineError::InvalidParameter(format!(
+                            "Invalid base64 {}: {}",
+                            name, e
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        let key =
+            decode("key")?.ok_or_else(|| adaptive_pipeline_domain::PipelineError::MissingParameter("key".into()))?;
+        let nonce = decode("nonce")?.unwrap_or_default();
+        let salt = decode("salt")?.unwrap_or_default();
 
         let key_material = KeyMaterial::new(key, nonce, salt, encryption_config.algorithm.clone());
 
-        match config.operation {
+        match operation {
             adaptive_pipeline_domain::entities::Operation::Forward => {
                 self.encrypt_chunk(chunk, &encryption_config, &key_material, context)
             }
@@ -74,6 +74,10 @@
 //! - **Alignment**: Memory alignment for optimal performance
 //! - **Prefetch**: Prefetch strategies for sequential access
 //!
+//! ### Direct I/O
+//! - **Opt-in**: `direct_io` reads around the page cache (see
+//!   [`direct_io`](super::direct_io)); memory mapping is skipped
+//!
 //! ### Concurrency
 //! - **Thread Pool**: Configurable thread pool size
 //! - **Concurrent Reads**: Maximum concurrent read operations
@@ -133,11 +137,17 @@ use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
 use parking_lot::RwLock;
 
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
 use adaptive_pipeline_domain::services::file_io_service::{
     FileIOConfig, FileIOService, FileIOStats, FileInfo, ReadOptions, ReadResult, WriteOptions, WriteResult,
 };
 use adaptive_pipeline_domain::{FileChunk, PipelineError};
 
+use super::direct_io::DirectReader;
+
+/// Stream of chunks returned by [`FileIOService::stream_file_chunks`]
+type ChunkStream = std::pin::Pin<Box<dyn futures::Stream<Item = Result<FileChunk, PipelineError>> + Send>>;
+
 /// Implementation of FileIOService with memory mapping support
 ///
 /// This struct provides a high-performance implementation of the file I/O
@@ -229,6 +239,114 @@ impl TokioFileIO {
         update_fn(&mut stats);
     }
 
+    /// Reads the requested range around the page cache
+    async fn read_file_direct(
+        &self,
+        path: &Path,
+        options: ReadOptions,
+        metadata: std::fs::Metadata,
+        start_time: std::time::Instant,
+    ) -> Result<ReadResult, PipelineError> {
+        let file_size = metadata.len();
+        let chunk_size = options.chunk_size.unwrap_or(self.config.read().default_chunk_size);
+        let start = options.start_offset.unwrap_or(0).min(file_size);
+        let end = options
+            .max_bytes
+            .map_or(file_size, |max| start.saturating_add(max).min(file_size));
+        let calculate_checksums = options.calculate_checksums;
+        let reader_path = path.to_path_buf();
+
+        let chunks = tokio::task::spawn_blocking(move || {
+            let reader = DirectReader::open(&reader_path)?;
+            let mut chunks = Vec::new();
+            let mut offset = start;
+            let mut sequence = 0u64;
+            while offset < end {
+                let data = reader.read_at(offset, chunk_size.min((end - offset) as usize))?;
+                if data.is_empty() {
+                    break;
+                }
+                let read = data.len() as u64;
+                let chunk = FileChunk::new(sequence, offset, data, offset + read >= end)?;
+                chunks.push(if calculate_checksums {
+                    chunk.with_calculated_checksum()?
+                } else {
+                    chunk
+                });
+                offset += read;
+                sequence += 1;
+            }
+            Ok::<_, PipelineError>(chunks)
+        })
+        .await
+        .map_err(|e| PipelineError::io_error(format!("Task join error: {}", e)))??;
+
+        let total_read: u64 = chunks.iter().map(|chunk| chunk.data().len() as u64).sum();
+        let file_info = FileInfo {
+            path: path.to_path_buf(),
+            size: file_size,
+            is_memory_mapped: false,
+            modified_at: metadata.modified().unwrap_or(std::time::UNIX_EPOCH),
+            created_at: metadata.created().unwrap_or(std::time::UNIX_EPOCH),
+            permissions: 0o644, // Default permissions
+            mime_type: None,
+        };
+
+        self.update_stats(|stats| {
+            stats.bytes_read += total_read;
+            stats.chunks_processed += chunks.len() as u64;
+            stats.files_processed += 1;
+            stats.total_processing_time_ms += start_time.elapsed().as_millis() as u64;
+        });
+
+        Ok(ReadResult {
+            chunks,
+            file_info,
+            bytes_read: total_read,
+            complete: total_read >= file_size,
+        })
+    }
+
+    /// Streams chunks read around the page cache, one blocking read per chunk
+    fn stream_file_direct(path: &Path, options: ReadOptions, chunk_size: usize) -> Result<ChunkStream, PipelineError> {
+        let reader = std::sync::Arc::new(DirectReader::open(path)?);
+        let start = options.start_offset.unwrap_or(0);
+        let end = options.max_bytes.map_or(u64::MAX, |max| start.saturating_add(max));
+        let calculate_checksums = options.calculate_checksums;
+
+        let stream = futures::stream::unfold((start, 0u64, false), move |(offset, sequence, done)| {
+            let reader = reader.clone();
+            async move {
+                if done || offset >= end {
+                    return None;
+                }
+                let len = chunk_size.min((end - offset).min(usize::MAX as u64) as usize);
+                let read = tokio::task::spawn_blocking(move || reader.read_at(offset, len))
+                    .await
+                    .map_err(|e| PipelineError::io_error(format!("Task join error: {}", e)))
+                    .and_then(|result| result);
+
+                let data = match read {
+                    Ok(data) if data.is_empty() => return None, // EOF
+                    Ok(data) => data,
+                    Err(e) => return Some((Err(e), (offset, sequence, true))),
+                };
+                let read = data.len() as u64;
+                let is_final = (read as usize) < len || offset + read >= end;
+                let chunk = FileChunk::new(sequence, offset, data, is_final).and_then(|chunk| {
+                    if calculate_checksums {
+                        chunk.with_calculated_checksum()
+                    } else {
+                        Ok(chunk)
+                    }
+                });
+                Some((chunk, (offset + read, sequence + 1, is_final)))
+            }
+        });
+
+        Ok(Box::pin(stream))
+    }
+
     /// Gets file metadata
     async fn get_file_metadata(&self, path: &Path) -> Result<std::fs::Metadata, PipelineError> {
         fs::metadata(path)
@@ -244,6 +362,10 @@ impl FileIOService for TokioFileIO {
         let metadata = self.get_file_metadata(path).await?;
         let file_size = metadata.len();
 
+        if self.config.read().direct_io {
+            return self.read_file_direct(path, options, metadata, start_time).await;
+        }
+
         // Determine if we should use memory mapping
         if options.use_memory_mapping && self.should_use_mmap(file_size) {
             return self.read_file_mmap(path, options).await;
@@ -565,7 +687,7 @@ impl FileIOService for TokioFileIO {
 
     async fn validate_file_integrity(&self, path: &Path, expected_checksum: &str) -> Result<bool, PipelineError> {
         let calculated_checksum = self.calculate_file_checksum(path).await?;
-        Ok(calculated_checksum == expected_checksum)
+        Ok(constant_time_eq_str(&calculated_checksum, expected_checksum))
     }
 
     async fn calculate_file_checksum(&self, path: &Path) -> Result<String, PipelineError> {
@@ -594,6 +716,11 @@ impl FileIOService for TokioFileIO {
     ) -> Result<std::pin::Pin<Box<dyn futures::Stream<Item = Result<FileChunk, PipelineError>> + Send>>, PipelineError>
     {
         let chunk_size = options.chunk_size.unwrap_or(self.config.read().default_chunk_size);
+
+        if self.config.read().direct_io {
+            return Self::stream_file_direct(path, options, chunk_size);
+        }
+
         let file = fs::File::open(path)
             .await
             .map_err(|e| PipelineError::IoError(format!("Failed to open file {}: {}", path.display(), e)))?;
@@ -816,4 +943,41 @@ mod tests {
         assert!(read_result.file_info.is_memory_mapped);
         assert_eq!(read_result.bytes_read, test_data.len() as u64);
     }
+
+    #[tokio::test]
+    async fn test_direct_io_reads_match_buffered_reads() {
+        use futures::StreamExt;
+
+        let service = TokioFileIO::new(FileIOConfig {
+            direct_io: true,
+            ..Default::default()
+        });
+
+        // An odd size and an unaligned range exercise the alignment handling
+        let temp_file = NamedTempFile::new().unwrap();
+        let test_data: Vec<u8> = (0..100_003u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(temp_file.path(), &test_data).unwrap();
+
+        let options = ReadOptions {
+            chunk_size: Some(10_000),
+            start_offset: Some(1_234),
+            max_bytes: Some(50_000),
+            ..Default::default()
+        };
+        let read_result = service.read_file_chunks(temp_file.path(), options).await.unwrap();
+        let read: Vec<u8> = read_result.chunks.iter().flat_map(|c| c.data().to_vec()).collect();
+        assert!(!read_result.file_info.is_memory_mapped);
+        assert_eq!(read, &test_data[1_234..51_234]);
+
+        let options = ReadOptions {
+            chunk_size: Some(10_000),
+            ..Default::default()
+        };
+        let mut stream = service.stream_file_chunks(temp_file.path(), options).await.unwrap();
+        let mut streamed = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            streamed.extend_from_slice(chunk.unwrap().data());
+        }
+        assert_eq!(streamed, test_data);
+    }
 }
@@ -0,0 +1,96 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # File Permissions
+//!
+//! Applies explicit [`FileMode`]s to the files and directories the pipeline
+//! creates, through the bootstrap platform abstraction. Without a mode a new
+//! file or directory gets whatever the process umask allows; with one it
+//! gets exactly that mode. Platforms without Unix permissions (Windows)
+//! accept the call and change nothing.
+
+use std::path::{Path, PathBuf};
+
+use adaptive_pipeline_bootstrap::platform::create_platform;
+use adaptive_pipeline_domain::value_objects::FileMode;
+use adaptive_pipeline_domain::PipelineError;
+
+/// Sets the permissions of `path` to `mode`
+///
+/// # Errors
+///
+/// Returns `IoError` if the permissions cannot be changed.
+pub fn apply_mode(path: &Path, mode: FileMode) -> Result<(), PipelineError> {
+    create_platform()
+        .set_permissions(path, mode.bits())
+        .map_err(|e| PipelineError::io_error(format!("Failed to set mode {} on '{}': {}", mode, path.display(), e)))
+}
+
+/// Creates `directory` and any missing ancestors, giving each directory it
+/// creates `mode` when one is set; existing directories are left as they
+/// are
+///
+/// Returns the directories created, outermost first.
+///
+/// # Errors
+///
+/// Returns `IoError` if a directory cannot be created or its mode set.
+pub async fn create_dir_all_with_mode(directory: &Path, mode: Option<FileMode>) -> Result<Vec<PathBuf>, PipelineError> {
+    let mut missing: Vec<PathBuf> = directory
+        .ancestors()
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .take_while(|ancestor| !ancestor.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    missing.reverse();
+
+    tokio::fs::create_dir_all(directory)
+        .await
+        .map_err(|e| PipelineError::io_error(format!("Failed to create directory '{}': {}", directory.display(), e)))?;
+    if let Some(mode) = mode {
+        for created in &missing {
+            apply_mode(created, mode)?;
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_modes_are_exact_and_only_touch_new_directories() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode_of = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+        let dir = TempDir::new().unwrap();
+        let existing_mode = mode_of(dir.path());
+        let nested = dir.path().join("a").join("b");
+        let created = create_dir_all_with_mode(&nested, Some(FileMode::new(0o750).unwrap()))
+            .await
+            .unwrap();
+
+        assert_eq!(created, vec![dir.path().join("a"), nested.clone()]);
+        assert_eq!(mode_of(&dir.path().join("a")), 0o750);
+        assert_eq!(mode_of(&nested), 0o750);
+        assert_eq!(mode_of(dir.path()), existing_mode);
+
+        let file = nested.join("data.txt");
+        std::fs::write(&file, b"data").unwrap();
+        apply_mode(&file, FileMode::new(0o604).unwrap()).unwrap();
+        assert_eq!(mode_of(&file), 0o604);
+    }
+
+    #[tokio::test]
+    async fn test_create_without_mode_reports_nothing_for_existing_directory() {
+        let dir = TempDir::new().unwrap();
+        assert!(create_dir_all_with_mode(dir.path(), None).await.unwrap().is_empty());
+    }
+}
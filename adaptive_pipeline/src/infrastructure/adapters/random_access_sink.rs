@@ -0,0 +1,350 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Random Access Sinks
+//!
+//! Implementations of the [`RandomAccessSink`] port that the `.adapipe`
+//! writer uses for concurrent positional writes.
+//!
+//! - [`FileSink`] - a local file written with `pwrite` (Unix) or
+//!   `seek_write` (Windows); no seeking, no mutex
+//! - [`PreallocatedFileSink`] - a [`FileSink`] whose space is reserved up
+//!   front (`fallocate` on Linux, `F_PREALLOCATE` on macOS,
+//!   `SetFileInformationByHandle` on Windows) and trimmed on sync
+//! - [`MemorySink`] - an in-memory buffer, for tests and for callers that
+//!   post-process the output themselves
+//!
+//! File writes run on tokio's blocking pool, since `std::fs::File` positional
+//! writes are synchronous syscalls. In direct I/O mode the file sinks keep
+//! their output out of the page cache (see
+//! [`direct_io`](super::direct_io)).
+
+use adaptive_pipeline_domain::services::RandomAccessSink;
+use adaptive_pipeline_domain::PipelineError;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use super::direct_io::{bypass_write_cache, drop_cached_pages};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Local file written with positional syscalls
+pub struct FileSink {
+    path: PathBuf,
+    file: Arc<std::fs::File>,
+    /// Highest `offset + len` written so far
+    end: AtomicU64,
+    /// Evict written pages from the page cache once synced
+    uncached: bool,
+}
+
+impl FileSink {
+    /// Creates (or truncates) the file at `path`
+    pub fn create(path: &Path) -> Result<Self, PipelineError> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .read(true) // Needed for some platform operations
+            .truncate(true)
+            .open(path)
+            .map_err(|e| PipelineError::io_error(format!("Failed to create {}: {}", path.display(), e)))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Arc::new(file),
+            end: AtomicU64::new(0),
+            uncached: false,
+        })
+    }
+
+    /// Keeps the written data out of the page cache (direct I/O mode)
+    pub fn without_page_cache(mut self) -> Result<Self, PipelineError> {
+        bypass_write_cache(&self.file)?;
+        self.uncached = true;
+        Ok(self)
+    }
+
+    /// Path of the underlying file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Runs a blocking operation on the file off the async runtime
+    async fn blocking<T, F>(&self, op: F) -> Result<T, PipelineError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&std::fs::File) -> Result<T, PipelineError> + Send + 'static,
+    {
+        let file = self.file.clone();
+        tokio::task::spawn_blocking(move || op(&file))
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Task join error: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl RandomAccessSink for FileSink {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    async fn write_at(&self, offset: u64, data: &[u8]) -> Result<(), PipelineError> {
+        let bytes = data.to_vec();
+        let len = bytes.len() as u64;
+
+        self.blocking(move |file| {
+            // Platform-specific position-based write
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::FileExt;
+                // Atomic pwrite() syscall - writes at position without seeking
+                file.write_all_at(&bytes, offset)
+                    .map_err(|e| PipelineError::io_error(format!("Failed to write at position {}: {}", offset, e)))
+            }
+
+            #[cfg(windows)]
+            {
+                use std::os::windows::fs::FileExt;
+                // WriteFile() with OVERLAPPED - writes at position; may be short
+                let mut written = 0;
+                while written < bytes.len() {
+                    written += file
+                        .seek_write(&bytes[written..], offset + written as u64)
+                        .map_err(|e| {
+                            PipelineError::io_error(format!("Failed to write at position {}: {}", offset, e))
+                        })?;
+                }
+                Ok(())
+            }
+
+            #[cfg(not(any(unix, windows)))]
+            {
+                compile_error!("Platform not supported for position-based writes")
+            }
+        })
+        .await?;
+
+        self.end.fetch_max(offset + len, Ordering::AcqRel);
+        Ok(())
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        Ok(self.end.load(Ordering::Acquire))
+    }
+
+    async fn sync(&self) -> Result<(), PipelineError> {
+        let uncached = self.uncached;
+        self.blocking(move |file| {
+            file.sync_all()
+                .map_err(|e| PipelineError::io_error(format!("Failed to sync: {}", e)))?;
+            if uncached {
+                drop_cached_pages(file)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Local file with its space reserved before writing starts
+///
+/// Reserving the whole output up front keeps concurrent out-of-order writes
+/// from fragmenting the file and surfaces a full disk before any work is
+/// done, instead of halfway through a run. Unused reservation is trimmed
+/// when the sink is synced.
+pub struct PreallocatedFileSink {
+    inner: FileSink,
+    reserved: AtomicU64,
+}
+
+impl PreallocatedFileSink {
+    /// Creates the file at `path` and reserves `len` bytes for it
+    pub async fn create(path: &Path, len: u64) -> Result<Self, PipelineError> {
+        let sink = Self {
+            inner: FileSink::create(path)?,
+            reserved: AtomicU64::new(0),
+        };
+        sink.preallocate(len).await?;
+        Ok(sink)
+    }
+
+    /// Keeps the written data out of the page cache (direct I/O mode)
+    pub fn without_page_cache(self) -> Result<Self, PipelineError> {
+        Ok(Self {
+            inner: self.inner.without_page_cache()?,
+            reserved: self.reserved,
+        })
+    }
+
+    /// Bytes currently reserved for the file
+    pub fn reserved(&self) -> u64 {
+        self.reserved.load(Ordering::Acquire)
+    }
+}
+
+#[async_trait]
+impl RandomAccessSink for PreallocatedFileSink {
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+
+    async fn write_at(&self, offset: u64, data: &[u8]) -> Result<(), PipelineError> {
+        self.inner.write_at(offset, data).await
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        self.inner.size().await
+    }
+
+    async fn preallocate(&self, len: u64) -> Result<(), PipelineError> {
+        if len <= self.reserved() {
+            return Ok(());
+        }
+
+        let path = self.inner.describe();
+        self.inner
+            .blocking(move |file| {
+                fs2::FileExt::allocate(file, len).map_err(|e| {
+                    PipelineError::io_error(format!("Failed to reserve {} bytes for {}: {}", len, path, e))
+                })
+            })
+            .await?;
+
+        self.reserved.fetch_max(len, Ordering::AcqRel);
+        Ok(())
+    }
+
+    async fn sync(&self) -> Result<(), PipelineError> {
+        let end = self.inner.size().await?;
+        if self.reserved() > end {
+            self.inner
+                .blocking(move |file| {
+                    file.set_len(end)
+                        .map_err(|e| PipelineError::io_error(format!("Failed to trim reserved space: {}", e)))
+                })
+                .await?;
+        }
+        self.inner.sync().await
+    }
+}
+
+/// In-memory buffer accepting positional writes
+///
+/// Gaps between writes read back as zeros, as they would in a sparse file.
+#[derive(Default)]
+pub struct MemorySink {
+    buffer: Mutex<Vec<u8>>,
+}
+
+impl MemorySink {
+    /// Creates an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of everything written so far
+    pub fn contents(&self) -> Vec<u8> {
+        self.buffer.lock().clone()
+    }
+}
+
+#[async_trait]
+impl RandomAccessSink for MemorySink {
+    fn describe(&self) -> String {
+        "memory buffer".to_string()
+    }
+
+    async fn write_at(&self, offset: u64, data: &[u8]) -> Result<(), PipelineError> {
+        let start = usize::try_from(offset)
+            .map_err(|_| PipelineError::io_error(format!("Offset {} exceeds addressable memory", offset)))?;
+        let end = start + data.len();
+
+        let mut buffer = self.buffer.lock();
+        if buffer.len() < end {
+            buffer.resize(end, 0);
+        }
+        buffer[start..end].copy_from_slice(data);
+        Ok(())
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        Ok(self.buffer.lock().len() as u64)
+    }
+
+    async fn preallocate(&self, len: u64) -> Result<(), PipelineError> {
+        let mut buffer = self.buffer.lock();
+        let additional = (len as usize).saturating_sub(buffer.len());
+        buffer.reserve(additional);
+        Ok(())
+    }
+
+    async fn sync(&self) -> Result<(), PipelineError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes four 4-byte blocks concurrently, last block first
+    async fn write_blocks_out_of_order(sink: Arc<dyn RandomAccessSink>) {
+        let mut tasks = Vec::new();
+        for index in (0..4u8).rev() {
+            let sink = sink.clone();
+            tasks.push(tokio::spawn(async move {
+                sink.write_at(index as u64 * 4, &[index; 4]).await.unwrap();
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    fn expected_blocks() -> Vec<u8> {
+        (0..4u8).flat_map(|index| [index; 4]).collect()
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_concurrent_positional_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.bin");
+        let sink = Arc::new(FileSink::create(&path).unwrap());
+
+        write_blocks_out_of_order(sink.clone()).await;
+        sink.sync().await.unwrap();
+
+        assert_eq!(sink.size().await.unwrap(), 16);
+        assert_eq!(std::fs::read(&path).unwrap(), expected_blocks());
+    }
+
+    #[tokio::test]
+    async fn test_preallocated_sink_trims_unused_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.bin");
+        let sink = Arc::new(PreallocatedFileSink::create(&path, 1024 * 1024).await.unwrap());
+        assert_eq!(sink.reserved(), 1024 * 1024);
+        assert!(std::fs::metadata(&path).unwrap().len() >= 1024 * 1024);
+
+        write_blocks_out_of_order(sink.clone()).await;
+        sink.sync().await.unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), expected_blocks());
+    }
+
+    #[tokio::test]
+    async fn test_memory_sink_fills_gaps_with_zeros() {
+        let sink = Arc::new(MemorySink::new());
+        write_blocks_out_of_order(sink.clone()).await;
+        assert_eq!(sink.contents(), expected_blocks());
+
+        sink.write_at(20, b"end").await.unwrap();
+        assert_eq!(sink.size().await.unwrap(), 23);
+        assert_eq!(&sink.contents()[16..20], &[0; 4]);
+    }
+}
@@ -0,0 +1,343 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Staged Output
+//!
+//! Writes an output file under a hidden staging name and moves it to its
+//! target only once it is complete, so readers of the target never see a
+//! partial file and a failed run leaves the target untouched.
+//!
+//! The staging file normally sits next to the target, where the final move
+//! is a plain rename. It can also be staged elsewhere (a fast local disk in
+//! front of a network mount, say); a rename across filesystems fails with
+//! `EXDEV`, so the commit then copies the staged data into a second staging
+//! file in the target's directory, syncs it, and renames that one. Either
+//! way the target appears in a single step.
+//!
+//! ```rust,ignore
+//! let mut staged = StagedOutput::create(&target, Some(Path::new("/scratch"))).await?;
+//! staged.file().write_all(&data).await?;
+//! match staged.commit(|copied, total| eprintln!("{copied}/{total}")).await? {
+//!     CommitOutcome::Renamed => {}
+//!     CommitOutcome::CopiedAcrossDevices { bytes } => eprintln!("copied {bytes} bytes"),
+//! }
+//! ```
+//!
+//! An uncommitted staging file is removed when the [`StagedOutput`] is
+//! dropped. Staging files are tracked in the managed temp root, so one left
+//! by a crash is found by `adaptive-pipeline cleanup`.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::runtime::temp_root::{release_temp_file, track_temp_file};
+use adaptive_pipeline_domain::PipelineError;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, warn};
+
+/// Bytes copied per step of a cross-device commit
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// How a committed file reached its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitOutcome {
+    /// The staging file was renamed onto the target
+    Renamed,
+    /// The staging file was on another filesystem; its `bytes` were copied
+    /// into the target's directory and renamed there
+    CopiedAcrossDevices { bytes: u64 },
+}
+
+/// An output file being written under a staging name
+pub struct StagedOutput {
+    file: Option<File>,
+    staging_path: PathBuf,
+    target: PathBuf,
+}
+
+impl StagedOutput {
+    /// Creates an empty staging file for `target` in `staging_dir`, or in
+    /// the target's own directory if `staging_dir` is `None`
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the staging file cannot be created.
+    pub async fn create(target: &Path, staging_dir: Option<&Path>) -> Result<Self, PipelineError> {
+        let directory = match staging_dir {
+            Some(directory) => directory.to_path_buf(),
+            None => parent_dir(target),
+        };
+        let staging_path = staging_path_in(&directory, target);
+        track_temp_file(&staging_path);
+        let file = File::create(&staging_path).await.map_err(|e| {
+            release_temp_file(&staging_path);
+            PipelineError::io_error(format!(
+                "Failed to create staging file '{}': {}",
+                staging_path.display(),
+                e
+            ))
+        })?;
+        debug!("Staging {} at {}", target.display(), staging_path.display());
+
+        Ok(Self {
+            file: Some(file),
+            staging_path,
+            target: target.to_path_buf(),
+        })
+    }
+
+    /// The staging file to write the output to
+    pub fn file(&mut self) -> &mut File {
+        self.file.as_mut().expect("staging file is open until commit")
+    }
+
+    /// Where the output is being staged
+    pub fn staging_path(&self) -> &Path {
+        &self.staging_path
+    }
+
+    /// Where the output goes on commit
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// Syncs the staged data and moves it onto the target, replacing any
+    /// existing file
+    ///
+    /// `progress` is called with `(bytes copied, total bytes)` while a
+    /// cross-device commit copies the data; a rename does not call it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the data cannot be synced, moved or copied. The
+    /// target is left as it was and the staging file is removed.
+    pub async fn commit(mut self, mut progress: impl FnMut(u64, u64)) -> Result<CommitOutcome, PipelineError> {
+        let mut file = self.file.take().expect("staging file is open until commit");
+        file.flush()
+            .await
+            .and(file.sync_all().await)
+            .map_err(|e| PipelineError::io_error(format!("Failed to sync staging file: {}", e)))?;
+        drop(file);
+
+        match tokio::fs::rename(&self.staging_path, &self.target).await {
+            Ok(()) => {
+                release_temp_file(&self.staging_path);
+                sync_dir(&parent_dir(&self.target)).await;
+                Ok(CommitOutcome::Renamed)
+            }
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+                debug!(
+                    "{} and {} are on different filesystems; copying",
+                    self.staging_path.display(),
+                    self.target.display()
+                );
+                let bytes = self.copy_into_target_dir(&mut progress).await?;
+                // The landing file is in place; the staged copy is no longer
+                // needed, and Drop removes it
+                Ok(CommitOutcome::CopiedAcrossDevices { bytes })
+            }
+            Err(e) => Err(PipelineError::io_error(format!(
+                "Failed to move '{}' to '{}': {}",
+                self.staging_path.display(),
+                self.target.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Discards the staged output
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the staging file exists but cannot be removed.
+    pub async fn abort(mut self) -> Result<(), PipelineError> {
+        self.file.take();
+        match tokio::fs::remove_file(&self.staging_path).await {
+            Ok(()) => {
+                release_temp_file(&self.staging_path);
+                Ok(())
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                release_temp_file(&self.staging_path);
+                Ok(())
+            }
+            Err(e) => Err(PipelineError::io_error(format!(
+                "Failed to remove staging file '{}': {}",
+                self.staging_path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Copies the staging file to a landing file next to the target, syncs
+    /// it and renames it onto the target, returning the bytes copied
+    async fn copy_into_target_dir(&self, progress: &mut impl FnMut(u64, u64)) -> Result<u64, PipelineError> {
+        let target_dir = parent_dir(&self.target);
+        let landing_path = staging_path_in(&target_dir, &self.target);
+        track_temp_file(&landing_path);
+        let result = async {
+            let mut source = File::open(&self.staging_path).await?;
+            let metadata = source.metadata().await?;
+            let total = metadata.len();
+            let mut landing = File::create(&landing_path).await?;
+            // Carry over any mode set on the staged file
+            landing.set_permissions(metadata.permissions()).await?;
+            let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+            let mut copied = 0u64;
+            progress(copied, total);
+            loop {
+                let read = source.read(&mut buffer).await?;
+                if read == 0 {
+                    break;
+                }
+                landing.write_all(&buffer[..read]).await?;
+                copied += read as u64;
+                progress(copied, total);
+            }
+            landing.flush().await?;
+            landing.sync_all().await?;
+            drop(landing);
+            tokio::fs::rename(&landing_path, &self.target).await?;
+            Ok::<u64, std::io::Error>(copied)
+        }
+        .await;
+
+        match result {
+            Ok(copied) => {
+                release_temp_file(&landing_path);
+                sync_dir(&target_dir).await;
+                Ok(copied)
+            }
+            Err(e) => {
+                match tokio::fs::remove_file(&landing_path).await {
+                    Err(cleanup) if cleanup.kind() != ErrorKind::NotFound => {
+                        warn!("Failed to remove {}: {}", landing_path.display(), cleanup)
+                    }
+                    _ => release_temp_file(&landing_path),
+                }
+                Err(PipelineError::io_error(format!(
+                    "Failed to copy '{}' across filesystems to '{}': {}",
+                    self.staging_path.display(),
+                    self.target.display(),
+                    e
+                )))
+            }
+        }
+    }
+}
+
+impl Drop for StagedOutput {
+    fn drop(&mut self) {
+        self.file.take();
+        match std::fs::remove_file(&self.staging_path) {
+            Ok(()) => release_temp_file(&self.staging_path),
+            // Already renamed onto the target, or removed by abort()
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove staging file {}: {}", self.staging_path.display(), e),
+        }
+    }
+}
+
+/// Directory holding `path`, `.` for a bare file name
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// A unique hidden file name in `directory` for staging `target`
+fn staging_path_in(directory: &Path, target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    directory.join(format!(".{}.{}.partial", name, uuid::Uuid::new_v4().simple()))
+}
+
+/// Makes a rename in `directory` durable; best effort, and a no-op where
+/// directories cannot be opened for syncing
+async fn sync_dir(directory: &Path) {
+    #[cfg(unix)]
+    if let Ok(directory) = File::open(directory).await {
+        let _ = directory.sync_all().await;
+    }
+    #[cfg(not(unix))]
+    let _ = directory;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn leftovers(dir: &Path) -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".partial"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_commit_renames_and_drop_discards() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("restored.txt");
+        std::fs::write(&target, b"old").unwrap();
+
+        let mut staged = StagedOutput::create(&target, None).await.unwrap();
+        staged.file().write_all(b"new contents").await.unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"old", "target untouched until commit");
+        assert_eq!(staged.commit(|_, _| {}).await.unwrap(), CommitOutcome::Renamed);
+        assert_eq!(std::fs::read(&target).unwrap(), b"new contents");
+
+        let mut abandoned = StagedOutput::create(&target, None).await.unwrap();
+        abandoned.file().write_all(b"partial").await.unwrap();
+        drop(abandoned);
+        assert_eq!(std::fs::read(&target).unwrap(), b"new contents");
+        assert!(leftovers(dir.path()).is_empty());
+    }
+
+    /// `/dev/shm` is a separate tmpfs on Linux, so staging there and
+    /// committing to a temp dir on disk crosses devices
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_commit_copies_across_devices() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = TempDir::new().unwrap();
+        let Ok(staging) = TempDir::new_in("/dev/shm") else {
+            return;
+        };
+        if std::fs::metadata(dir.path()).unwrap().dev() == std::fs::metadata(staging.path()).unwrap().dev() {
+            return;
+        }
+        let target = dir.path().join("restored.bin");
+        let data: Vec<u8> = (0..COPY_BUFFER_SIZE * 2 + 17).map(|i| i as u8).collect();
+
+        let mut staged = StagedOutput::create(&target, Some(staging.path())).await.unwrap();
+        staged.file().write_all(&data).await.unwrap();
+        let mut reports = Vec::new();
+        let outcome = staged
+            .commit(|copied, total| reports.push((copied, total)))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommitOutcome::CopiedAcrossDevices {
+                bytes: data.len() as u64
+            }
+        );
+        assert!(std::fs::read(&target).unwrap() == data);
+        assert_eq!(reports.first(), Some(&(0, data.len() as u64)));
+        assert_eq!(reports.last(), Some(&(data.len() as u64, data.len() as u64)));
+        assert!(leftovers(dir.path()).is_empty());
+        assert!(leftovers(staging.path()).is_empty());
+    }
+}
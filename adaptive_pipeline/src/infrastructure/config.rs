@@ -112,6 +112,9 @@
 //!
 //! Use test-specific configuration:
 
+pub mod build_info;
 pub mod config_service;
+pub mod database_path;
 pub mod generic_config_manager;
+pub mod pipeline_definition;
 pub mod rayon_config;
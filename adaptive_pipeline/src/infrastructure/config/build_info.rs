@@ -0,0 +1,44 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Build Information
+//!
+//! Provenance of the running binary, captured at compile time by the crate's
+//! `build.rs`. The writer embeds it in every `.adapipe` header and the
+//! `inspect` command reports it, so an archive can be traced to the exact
+//! binary that produced it.
+//!
+//! | Field     | Source                                          |
+//! |-----------|-------------------------------------------------|
+//! | version   | `CARGO_PKG_VERSION`                             |
+//! | commit    | `ADAPIPE_BUILD_COMMIT`, else `git rev-parse HEAD` |
+//! | builder   | `ADAPIPE_BUILDER`, else `$USER`                 |
+//! | target    | Cargo `TARGET` triple                           |
+
+use adaptive_pipeline_domain::value_objects::BuildProvenance;
+
+/// Returns the provenance of this binary
+pub fn build_provenance() -> BuildProvenance {
+    BuildProvenance::new(
+        env!("CARGO_PKG_VERSION"),
+        env!("ADAPIPE_BUILD_COMMIT"),
+        env!("ADAPIPE_BUILDER"),
+        env!("ADAPIPE_BUILD_TARGET"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_provenance_reports_crate_version_and_target() {
+        let provenance = build_provenance();
+        assert_eq!(provenance.crate_version, env!("CARGO_PKG_VERSION"));
+        assert!(!provenance.target.is_empty());
+    }
+}
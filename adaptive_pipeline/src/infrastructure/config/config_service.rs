@@ -170,11 +170,16 @@
 //! - **Remote Configuration**: Support for remote configuration stores
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::time::Duration;
 use tokio::fs;
 use tracing::{debug, warn};
 
+use adaptive_pipeline_domain::entities::session::{DEFAULT_SESSION_REQUESTS_PER_MINUTE, DEFAULT_SESSION_TTL};
 use adaptive_pipeline_domain::error::PipelineError;
+use adaptive_pipeline_domain::services::DEFAULT_GRACE_PERIOD;
+use adaptive_pipeline_domain::value_objects::{FeatureFlags, FileMode, QuotaLimits, FEATURE_FLAGS};
 
 /// Configuration service for reading observability settings
 ///
@@ -294,6 +299,341 @@ impl Default for ObservabilityConfig {
     }
 }
 
+/// `[security]` section of the application configuration file
+///
+/// The CLI acts for the OS user running it, or for the principal its `[auth]`
+/// credentials authenticate; the file cannot name another.
+///
+/// ```toml
+/// [security]
+/// role = "operator"     # act with a narrower role than the one assigned
+/// security_level = "confidential"  # checked against pipeline security
+///                                  # policies; defaults to internal
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecuritySettings {
+    pub role: Option<String>,
+    pub security_level: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SecurityConfigFile {
+    #[serde(default)]
+    security: SecuritySettings,
+}
+
+/// `[quota]` section of the application configuration file
+///
+/// Top-level keys are the defaults for every namespace; a
+/// `[quota.namespaces.<name>]` table overrides individual limits for one
+/// namespace.
+///
+/// ```toml
+/// [quota]
+/// max_input_bytes = 10737418240          # 10 GiB per input file
+/// max_concurrent_jobs = 4
+///
+/// [quota.namespaces.analytics]
+/// max_output_bytes_per_day = 107374182400
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuotaSettings {
+    #[serde(flatten)]
+    pub defaults: QuotaLimits,
+    #[serde(default)]
+    pub namespaces: HashMap<String, QuotaLimits>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct QuotaConfigFile {
+    #[serde(default)]
+    quota: QuotaSettings,
+}
+
+/// `[output]` section of the application configuration file
+///
+/// Modes given on the command line (`--output-mode`, `--dir-mode`) take
+/// precedence; without either, created files and directories get whatever
+/// the umask allows.
+///
+/// ```toml
+/// [output]
+/// file_mode = "0640"   # processed .adapipe files and restored files
+/// dir_mode = "0750"    # directories created by restore --mkdir
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutputSettings {
+    pub file_mode: Option<FileMode>,
+    pub dir_mode: Option<FileMode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OutputConfigFile {
+    #[serde(default)]
+    output: OutputSettings,
+}
+
+/// Kinds of work that get their own shutdown grace period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationClass {
+    /// Turning input files into `.adapipe` files
+    Process,
+    /// Turning `.adapipe` files back into their originals
+    Restore,
+    /// Long-running service modes
+    Serve,
+}
+
+/// `[shutdown]` section of the application configuration file
+///
+/// On SIGINT, SIGTERM or a service stop, in-flight chunks get this long to
+/// finish before they are aborted and a checkpoint is written.
+///
+/// ```toml
+/// [shutdown]
+/// process_grace_secs = 10
+/// restore_grace_secs = 5
+/// serve_grace_secs = 30
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShutdownSettings {
+    pub process_grace_secs: u64,
+    pub restore_grace_secs: u64,
+    pub serve_grace_secs: u64,
+}
+
+impl Default for ShutdownSettings {
+    fn default() -> Self {
+        let secs = DEFAULT_GRACE_PERIOD.as_secs();
+        Self {
+            process_grace_secs: secs,
+            restore_grace_secs: secs,
+            serve_grace_secs: secs,
+        }
+    }
+}
+
+impl ShutdownSettings {
+    /// Grace period for one class of work
+    pub fn grace_period(&self, class: OperationClass) -> Duration {
+        Duration::from_secs(match class {
+            OperationClass::Process => self.process_grace_secs,
+            OperationClass::Restore => self.restore_grace_secs,
+            OperationClass::Serve => self.serve_grace_secs,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ShutdownConfigFile {
+    #[serde(default)]
+    shutdown: ShutdownSettings,
+}
+
+/// `[features]` section of the application configuration file
+///
+/// Turns subsystems that ship dark on or off for this deployment. Flags not
+/// listed keep their default (see `capabilities` for the catalog).
+///
+/// ```toml
+/// [features]
+/// gpu = true
+/// dedup = false
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeatureSettings {
+    #[serde(flatten)]
+    pub flags: BTreeMap<String, bool>,
+}
+
+impl FeatureSettings {
+    /// Effective flags, and warnings for unknown or deprecated flags set here
+    pub fn resolve(&self) -> (FeatureFlags, Vec<String>) {
+        FeatureFlags::resolve(&self.flags, FEATURE_FLAGS)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FeatureConfigFile {
+    #[serde(default)]
+    features: FeatureSettings,
+}
+
+/// `[session]` section of the application configuration file
+///
+/// Lifetime and request rate of API sessions.
+///
+/// ```toml
+/// [session]
+/// ttl_secs = 3600            # lifetime of an issued session (at most 30 days)
+/// requests_per_minute = 600  # per session; 0 disables the limit
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionSettings {
+    pub ttl_secs: u64,
+    pub requests_per_minute: u32,
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self {
+            ttl_secs: DEFAULT_SESSION_TTL.num_seconds().unsigned_abs(),
+            requests_per_minute: DEFAULT_SESSION_REQUESTS_PER_MINUTE,
+        }
+    }
+}
+
+impl SessionSettings {
+    /// Lifetime of an issued session
+    pub fn ttl(&self) -> chrono::Duration {
+        i64::try_from(self.ttl_secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SessionConfigFile {
+    #[serde(default)]
+    session: SessionSettings,
+}
+
+/// `[auth]` section of the application configuration file
+///
+/// Authentication providers for API callers. Each configured provider is
+/// enabled; callers present an API key (`ADAPIPE_API_KEY`), a bearer token
+/// (`ADAPIPE_BEARER_TOKEN`) or, in serve mode, a TLS client certificate
+/// forwarded by a trusted proxy. With `required`, protected commands are
+/// refused without credentials.
+///
+/// ```toml
+/// [auth]
+/// required = true
+///
+/// [[auth.api_keys]]
+/// principal = "build-bot"
+/// key_sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// role = "operator"          # granted on top of assigned roles
+/// namespace = "analytics"    # optional; confines the key to a namespace
+///
+/// [auth.jwt]
+/// issuer = "https://idp.example.com"
+/// audience = "adaptive-pipeline"
+/// jwks_path = "/etc/adapipe/jwks.json"   # RS256 / ES256 keys
+/// hs256_secret_env = "ADAPIPE_JWT_SECRET" # or a shared HS256 secret
+/// roles_claim = "roles"
+///
+/// [auth.jwt.role_mapping]
+/// "pipeline-admins" = "admin"
+///
+/// [[auth.client_certs]]
+/// principal = "ingest-service"
+/// sha256_fingerprint = "3a:7b:..."
+/// role = "operator"
+///
+/// [auth.client_cert_proxy]
+/// header = "X-Client-Cert"            # URL-escaped PEM of the verified certificate
+/// trusted_proxies = ["127.0.0.1"]     # only these peers may send it
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthSettings {
+    pub required: bool,
+    pub api_keys: Vec<ApiKeySettings>,
+    pub jwt: Option<JwtSettings>,
+    pub client_certs: Vec<ClientCertSettings>,
+    pub client_cert_proxy: Option<ClientCertProxySettings>,
+}
+
+impl AuthSettings {
+    /// Whether any provider is configured
+    pub fn has_providers(&self) -> bool {
+        !self.api_keys.is_empty() || self.jwt.is_some() || !self.client_certs.is_empty()
+    }
+}
+
+/// One `[[auth.api_keys]]` entry
+///
+/// Only the hex SHA-256 digest of the key is configured, so the file does
+/// not disclose it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeySettings {
+    pub principal: String,
+    pub key_sha256: String,
+    pub role: Option<String>,
+    pub namespace: Option<String>,
+}
+
+/// `[auth.jwt]` section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JwtSettings {
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    pub jwks_path: Option<std::path::PathBuf>,
+    pub hs256_secret_env: Option<String>,
+    pub principal_claim: String,
+    pub roles_claim: String,
+    pub role_mapping: BTreeMap<String, String>,
+    pub leeway_secs: u64,
+}
+
+impl Default for JwtSettings {
+    fn default() -> Self {
+        Self {
+            issuer: None,
+            audience: None,
+            jwks_path: None,
+            hs256_secret_env: None,
+            principal_claim: "sub".to_string(),
+            roles_claim: "roles".to_string(),
+            role_mapping: BTreeMap::new(),
+            leeway_secs: 60,
+        }
+    }
+}
+
+/// One `[[auth.client_certs]]` entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientCertSettings {
+    pub principal: String,
+    pub sha256_fingerprint: String,
+    pub role: Option<String>,
+    pub namespace: Option<String>,
+}
+
+/// `[auth.client_cert_proxy]` section
+///
+/// `serve` speaks plain HTTP, so mutual TLS is terminated by a proxy that
+/// verifies the client's certificate and forwards it in `header`, as nginx
+/// does with `proxy_set_header X-Client-Cert $ssl_client_escaped_cert`. The
+/// header is only believed on connections from `trusted_proxies`; the proxy
+/// must replace any value the client sent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientCertProxySettings {
+    pub header: String,
+    pub trusted_proxies: Vec<std::net::IpAddr>,
+}
+
+impl Default for ClientCertProxySettings {
+    fn default() -> Self {
+        Self {
+            header: "X-Client-Cert".to_string(),
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AuthConfigFile {
+    #[serde(default)]
+    auth: AuthSettings,
+}
+
 /// Configuration service for loading observability settings
 pub struct ConfigService;
 
@@ -354,6 +694,131 @@ impl ConfigService {
         Ok(ObservabilityConfig::default())
     }
 
+    /// Load the `[security]` section from an application configuration file
+    ///
+    /// Other sections are ignored; a file without a `[security]` section
+    /// yields the defaults.
+    pub async fn load_security_settings<P: AsRef<Path>>(config_path: P) -> Result<SecuritySettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: SecurityConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.security)
+    }
+
+    /// Load the `[quota]` section from an application configuration file
+    ///
+    /// Other sections are ignored; a file without a `[quota]` section yields
+    /// no limits.
+    pub async fn load_quota_settings<P: AsRef<Path>>(config_path: P) -> Result<QuotaSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: QuotaConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.quota)
+    }
+
+    /// Load the `[output]` section from an application configuration file
+    ///
+    /// Other sections are ignored; a file without an `[output]` section
+    /// sets no modes.
+    pub async fn load_output_settings<P: AsRef<Path>>(config_path: P) -> Result<OutputSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: OutputConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.output)
+    }
+
+    /// Load the `[shutdown]` section from an application configuration file
+    ///
+    /// Other sections are ignored; missing keys keep the default grace
+    /// period.
+    pub async fn load_shutdown_settings<P: AsRef<Path>>(config_path: P) -> Result<ShutdownSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: ShutdownConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.shutdown)
+    }
+
+    /// Load the `[features]` section from an application configuration file
+    ///
+    /// Other sections are ignored; a file without a `[features]` section
+    /// leaves every flag at its default.
+    pub async fn load_feature_settings<P: AsRef<Path>>(config_path: P) -> Result<FeatureSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: FeatureConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.features)
+    }
+
+    /// Load the `[auth]` section from an application configuration file
+    ///
+    /// Other sections are ignored; a file without an `[auth]` section
+    /// configures no providers.
+    pub async fn load_auth_settings<P: AsRef<Path>>(config_path: P) -> Result<AuthSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: AuthConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.auth)
+    }
+
+    /// Load the `[session]` section from an application configuration file
+    ///
+    /// Other sections are ignored; missing keys keep their defaults.
+    pub async fn load_session_settings<P: AsRef<Path>>(config_path: P) -> Result<SessionSettings, PipelineError> {
+        let config_path = config_path.as_ref();
+
+        let config_content = fs::read_to_string(config_path).await.map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to read config file {:?}: {}", config_path, e))
+        })?;
+
+        let config: SessionConfigFile = toml::from_str(&config_content).map_err(|e| {
+            PipelineError::invalid_config(format!("Failed to parse config file {:?}: {}", config_path, e))
+        })?;
+
+        Ok(config.session)
+    }
+
     /// Get metrics port from configuration
     pub async fn get_metrics_port() -> u16 {
         match Self::load_default_observability_config().await {
@@ -446,6 +911,173 @@ disk_usage_alert_threshold = 95.0
         assert!(config.alerts.enable_alerts);
     }
 
+    #[tokio::test]
+    async fn test_load_security_settings_ignores_other_sections() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            "[metrics]\nport = 8080\n\n[security]\nrole = \"auditor\"\nsecurity_level = \"secret\"\n",
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_security_settings(temp_file.path()).await.unwrap();
+        assert_eq!(settings.role.as_deref(), Some("auditor"));
+        assert_eq!(settings.security_level.as_deref(), Some("secret"));
+
+        tokio::fs::write(temp_file.path(), "[metrics]\nport = 8080\n")
+            .await
+            .unwrap();
+        let settings = ConfigService::load_security_settings(temp_file.path()).await.unwrap();
+        assert!(settings.role.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_quota_settings_with_namespace_overrides() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            "[quota]\nmax_input_bytes = 1024\nmax_concurrent_jobs = 2\n\n[quota.namespaces.analytics]\n\
+             max_concurrent_jobs = 1\n",
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_quota_settings(temp_file.path()).await.unwrap();
+        assert_eq!(settings.defaults.max_input_bytes, Some(1024));
+        assert_eq!(settings.defaults.max_concurrent_jobs, Some(2));
+        assert_eq!(settings.namespaces["analytics"].max_concurrent_jobs, Some(1));
+        assert_eq!(settings.namespaces["analytics"].max_input_bytes, None);
+
+        tokio::fs::write(temp_file.path(), "[security]\nrole = \"auditor\"\n")
+            .await
+            .unwrap();
+        let settings = ConfigService::load_quota_settings(temp_file.path()).await.unwrap();
+        assert!(settings.defaults.is_unlimited() && settings.namespaces.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_output_settings_parses_octal_modes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            "[output]
+file_mode = \"0640\"
+",
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_output_settings(temp_file.path()).await.unwrap();
+        assert_eq!(settings.file_mode.map(|mode| mode.bits()), Some(0o640));
+        assert!(settings.dir_mode.is_none());
+
+        tokio::fs::write(
+            temp_file.path(),
+            "[output]
+dir_mode = \"0789\"
+",
+        )
+        .await
+        .unwrap();
+        assert!(ConfigService::load_output_settings(temp_file.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_shutdown_settings_defaults_missing_keys() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            "[shutdown]
+serve_grace_secs = 30
+",
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_shutdown_settings(temp_file.path()).await.unwrap();
+        assert_eq!(settings.grace_period(OperationClass::Serve), Duration::from_secs(30));
+        assert_eq!(settings.grace_period(OperationClass::Process), DEFAULT_GRACE_PERIOD);
+        assert_eq!(settings.grace_period(OperationClass::Restore), DEFAULT_GRACE_PERIOD);
+    }
+
+    #[tokio::test]
+    async fn test_load_session_settings_defaults_missing_keys() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(temp_file.path(), "[session]\nttl_secs = 900\n")
+            .await
+            .unwrap();
+
+        let settings = ConfigService::load_session_settings(temp_file.path()).await.unwrap();
+        assert_eq!(settings.ttl(), chrono::Duration::minutes(15));
+        assert_eq!(settings.requests_per_minute, SessionSettings::default().requests_per_minute);
+    }
+
+    #[tokio::test]
+    async fn test_load_auth_settings() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            r#"
+[auth]
+required = true
+
+[[auth.api_keys]]
+principal = "build-bot"
+key_sha256 = "00"
+role = "operator"
+
+[auth.jwt]
+issuer = "https://idp.example.com"
+
+[auth.jwt.role_mapping]
+"pipeline-admins" = "admin"
+
+[auth.client_cert_proxy]
+trusted_proxies = ["127.0.0.1", "::1"]
+"#,
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_auth_settings(temp_file.path()).await.unwrap();
+        assert!(settings.required && settings.has_providers());
+        assert_eq!(settings.api_keys[0].role.as_deref(), Some("operator"));
+        let jwt = settings.jwt.unwrap();
+        assert_eq!(jwt.principal_claim, "sub");
+        assert_eq!(jwt.role_mapping["pipeline-admins"], "admin");
+        assert!(settings.client_certs.is_empty());
+        let proxy = settings.client_cert_proxy.unwrap();
+        assert_eq!(proxy.header, "X-Client-Cert");
+        assert_eq!(proxy.trusted_proxies.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_feature_settings_resolves_against_the_catalog() {
+        let temp_file = NamedTempFile::new().unwrap();
+        tokio::fs::write(
+            temp_file.path(),
+            "[features]
+gpu = true
+warp = true
+",
+        )
+        .await
+        .unwrap();
+
+        let settings = ConfigService::load_feature_settings(temp_file.path()).await.unwrap();
+        let (flags, warnings) = settings.resolve();
+        assert!(flags.is_enabled("gpu"));
+        assert!(!flags.is_enabled("dedup"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'warp'"), "{:?}", warnings);
+
+        tokio::fs::write(temp_file.path(), "[features]\ngpu = \"yes\"\n")
+            .await
+            .unwrap();
+        assert!(ConfigService::load_feature_settings(temp_file.path()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_metrics_port() {
         let port = ConfigService::get_metrics_port().await;
@@ -0,0 +1,48 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Database Path Resolution
+//!
+//! Locates the SQLite database shared by the CLI and by use cases built with
+//! their default dependencies. The first match wins:
+//!
+//! 1. `ADAPIPE_SQLITE_PATH` environment variable
+//! 2. `./pipeline.db` in the current directory
+//! 3. The development database under `pipeline/scripts/test_data`
+//! 4. A new `./pipeline.db` in the current directory
+
+use std::path::Path;
+use tracing::{debug, info};
+
+/// Database created in the working directory when none exists
+pub const DEFAULT_DATABASE_PATH: &str = "./pipeline.db";
+
+const DEVELOPMENT_DATABASE_PATH: &str = "pipeline/scripts/test_data/structured_pipeline.db";
+
+/// Resolves the SQLite database path
+pub fn resolve_sqlite_path() -> String {
+    if let Ok(env_path) = std::env::var("ADAPIPE_SQLITE_PATH") {
+        debug!("Using SQLite path from ADAPIPE_SQLITE_PATH: {}", env_path);
+        return env_path;
+    }
+
+    if Path::new(DEFAULT_DATABASE_PATH).exists() {
+        debug!("Found SQLite database in current directory: {}", DEFAULT_DATABASE_PATH);
+        return DEFAULT_DATABASE_PATH.to_string();
+    }
+
+    if Path::new(DEVELOPMENT_DATABASE_PATH).exists() {
+        debug!("Found SQLite database at debug path: {}", DEVELOPMENT_DATABASE_PATH);
+        return DEVELOPMENT_DATABASE_PATH.to_string();
+    }
+
+    info!(
+        "No existing database found. Creating new database at: {}",
+        DEFAULT_DATABASE_PATH
+    );
+    DEFAULT_DATABASE_PATH.to_string()
+}
@@ -0,0 +1,89 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Pipeline Definition Files
+//!
+//! Reads and writes [`PipelineDefinition`]s as TOML, YAML or JSON for
+//! `pipeline export` and `pipeline import`. Only the syntax is handled here;
+//! [`PipelineDefinition::to_pipeline`] checks the content.
+
+use adaptive_pipeline_domain::value_objects::{DefinitionFormat, PipelineDefinition};
+use adaptive_pipeline_domain::PipelineError;
+
+/// Writes `definition` in `format`
+///
+/// # Errors
+///
+/// Returns `SerializationError` if the definition cannot be written in the
+/// format.
+pub fn render(definition: &PipelineDefinition, format: DefinitionFormat) -> Result<String, PipelineError> {
+    let rendered = match format {
+        DefinitionFormat::Toml => toml::to_string_pretty(definition).map_err(|e| e.to_string()),
+        DefinitionFormat::Yaml => serde_yaml::to_string(definition).map_err(|e| e.to_string()),
+        DefinitionFormat::Json => serde_json::to_string_pretty(definition)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+    };
+    rendered.map_err(|e| {
+        PipelineError::SerializationError(format!("Failed to write pipeline definition as {}: {}", format, e))
+    })
+}
+
+/// Reads a definition written in `format`
+///
+/// # Errors
+///
+/// Returns `InvalidConfiguration` naming the syntax error or the unknown or
+/// missing field.
+pub fn parse(text: &str, format: DefinitionFormat) -> Result<PipelineDefinition, PipelineError> {
+    let parsed = match format {
+        DefinitionFormat::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+        DefinitionFormat::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        DefinitionFormat::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+    };
+    parsed
+        .map_err(|e| PipelineError::invalid_config(format!("Invalid {} pipeline definition: {}", format, e.trim_end())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::StageDefinition;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_every_format_reads_back_what_it_writes() {
+        let definition = PipelineDefinition {
+            name: "secure-backup".to_string(),
+            chunk_size: Some(1024 * 1024),
+            workers: Some(4),
+            topology: None,
+            checksum: Some("blake3".to_string()),
+            security_level: None,
+            required_permissions: vec!["decrypt".to_string()],
+            stages: vec![StageDefinition {
+                name: "compression".to_string(),
+                stage_type: "compression".to_string(),
+                algorithm: "zstd".to_string(),
+                parallel: true,
+                chunk_size: None,
+                parameters: BTreeMap::from([("level".to_string(), "6".to_string())]),
+            }],
+            configuration: BTreeMap::from([("owner".to_string(), "ops".to_string())]),
+        };
+
+        for format in [DefinitionFormat::Toml, DefinitionFormat::Yaml, DefinitionFormat::Json] {
+            let text = render(&definition, format).unwrap();
+            assert_eq!(parse(&text, format).unwrap(), definition, "{}:\n{}", format, text);
+        }
+        let typo = "name: x\nstages: []\nworker: 4\n";
+        assert!(parse(typo, DefinitionFormat::Yaml)
+            .unwrap_err()
+            .to_string()
+            .contains("worker"));
+    }
+}
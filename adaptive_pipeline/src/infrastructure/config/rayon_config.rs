@@ -18,8 +18,11 @@
 //!   compression, encryption
 //! - **Mixed Workload Pool**: Balanced for operations with both CPU and I/O
 //!   components
-//! - **Adaptive Sizing**: Integrates with WorkerCount optimization strategies
+//! - **Adaptive Sizing**: Sized by the CPU token budget once the resource
+//!   manager is initialized, otherwise by WorkerCount optimization strategies
 //! - **Thread Naming**: Clear thread naming for debugging and profiling
+//! - **Core Pinning**: Optionally pins CPU-bound threads to cores
+//!   (`--pin-workers`, see [`pin_cpu_pool`])
 //!
 //! ## Usage
 //!
@@ -32,11 +35,50 @@
 //!         .map(|chunk| compress(chunk))
 //!         .collect()
 //! });
+//!
+//! // Run one synchronous call off the async runtime
+//! let output = spawn_on_cpu_pool(move || service.process_chunk(chunk, op, &config, &mut ctx)).await;
 //! ```
 
+use crate::infrastructure::runtime::supervisor::panic_message;
+use crate::infrastructure::runtime::try_resource_manager;
+use adaptive_pipeline_bootstrap::platform::create_platform;
 use adaptive_pipeline_domain::error::PipelineError;
 use adaptive_pipeline_domain::value_objects::WorkerCount;
-use std::sync::Arc;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Cores the CPU-bound pool's threads are pinned to, if pinning is on
+static CPU_POOL_CORES: OnceLock<Vec<usize>> = OnceLock::new();
+
+/// Pins the CPU-bound pool's threads to `cores`, one thread per core
+///
+/// Must be called before [`RAYON_POOLS`] is first used; the pool is then
+/// capped at `cores.len()` threads so no two share a core.
+///
+/// # Errors
+/// If `cores` is empty, or pinning was already configured
+pub fn pin_cpu_pool(cores: Vec<usize>) -> Result<(), PipelineError> {
+    if cores.is_empty() {
+        return Err(PipelineError::invalid_config("No cores to pin CPU workers to"));
+    }
+    CPU_POOL_CORES
+        .set(cores)
+        .map_err(|_| PipelineError::invalid_config("CPU worker pinning is already configured"))
+}
+
+/// Pins the calling pool thread to its core; warns once if the OS refuses
+fn pin_pool_thread(index: usize, cores: &[usize]) {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    let core = cores[index % cores.len()];
+    if let Err(e) = create_platform().pin_current_thread(core) {
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            tracing::warn!("Could not pin CPU worker threads to cores: {}", e);
+        }
+    }
+}
 
 /// Rayon thread pool manager for different workload types
 ///
@@ -58,9 +100,11 @@ impl RayonPoolManager {
     /// # Thread Pool Configuration
     ///
     /// **CPU-Bound Pool:**
-    /// - Uses optimal worker count for CPU-intensive operations
-    /// - Based on available cores and WorkerCount optimization
+    /// - One thread per CPU token when the resource manager is initialized,
+    ///   so the pool never runs more work than the token budget admits
+    /// - Otherwise the WorkerCount optimum for the available cores
     /// - Named threads: "rayon-cpu-{N}"
+    /// - Pinned to cores when [`pin_cpu_pool`] was called first
     ///
     /// **Mixed Workload Pool:**
     /// - Uses half the cores to avoid contention
@@ -78,9 +122,20 @@ impl RayonPoolManager {
             true, // CPU-intensive
         );
 
-        let cpu_bound_pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(cpu_worker_count.count())
-            .thread_name(|i| format!("rayon-cpu-{}", i))
+        let cpu_threads = try_resource_manager()
+            .map(|rm| rm.cpu_tokens_total())
+            .unwrap_or(cpu_worker_count.count());
+
+        let mut cpu_pool_builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(cpu_threads)
+            .thread_name(|i| format!("rayon-cpu-{}", i));
+        if let Some(cores) = CPU_POOL_CORES.get() {
+            cpu_pool_builder = cpu_pool_builder
+                .num_threads(cpu_threads.min(cores.len()))
+                .start_handler(|index| pin_pool_thread(index, cores));
+        }
+
+        let cpu_bound_pool = cpu_pool_builder
             .build()
             .map_err(|e| PipelineError::InternalError(format!("Failed to create CPU-bound pool: {}", e)))?;
 
@@ -131,6 +186,28 @@ impl RayonPoolManager {
     }
 }
 
+/// Runs a synchronous call on the CPU-bound pool and awaits its result
+///
+/// Keeps CPU-heavy work (compression, encryption) off the tokio runtime's
+/// worker threads, which must stay free to drive I/O. A panic in `op` is
+/// returned as `Err` with the panic message.
+///
+/// Dropping the returned future does not cancel `op`; it runs to completion
+/// and its result is discarded.
+pub async fn spawn_on_cpu_pool<T, F>(op: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    RAYON_POOLS.cpu_bound_pool().spawn(move || {
+        let outcome = std::panic::catch_unwind(AssertUnwindSafe(op)).map_err(|payload| panic_message(payload.as_ref()));
+        let _ = tx.send(outcome);
+    });
+    rx.await
+        .map_err(|_| "CPU pool task ended without a result".to_string())?
+}
+
 /// Global Rayon pool manager instance
 ///
 /// This is initialized once at program startup and provides access to
@@ -163,6 +240,33 @@ mod tests {
         assert!(mixed_pool.current_num_threads() > 0);
     }
 
+    #[test]
+    fn test_pinned_pool_threads_run() {
+        assert!(pin_cpu_pool(Vec::new()).is_err());
+
+        // Pinning failures only warn, so the pool works wherever the tests run
+        let cores = vec![0];
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .start_handler(move |index| pin_pool_thread(index, &cores))
+            .build()
+            .unwrap();
+        assert_eq!(pool.install(|| (1..=10).sum::<u32>()), 55);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_on_cpu_pool_returns_result_or_panic() {
+        let thread = spawn_on_cpu_pool(|| std::thread::current().name().map(str::to_string))
+            .await
+            .unwrap();
+        assert!(thread.unwrap().starts_with("rayon-cpu-"));
+
+        let err = spawn_on_cpu_pool(|| -> u32 { panic!("codec exploded") })
+            .await
+            .unwrap_err();
+        assert_eq!(err, "codec exploded");
+    }
+
     #[test]
     fn test_pool_sizing() {
         let manager = RayonPoolManager::new().unwrap();
@@ -16,9 +16,11 @@ pub mod endpoint;
 pub mod generic_collector;
 pub mod observer;
 pub mod service;
+pub mod worker_metrics;
 
 pub use concurrency_metrics::*;
 pub use endpoint::*;
 pub use generic_collector::*;
 pub use observer::*;
 pub use service::*;
+pub use worker_metrics::*;
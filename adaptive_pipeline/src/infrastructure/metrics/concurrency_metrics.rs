@@ -36,8 +36,19 @@
 //! if saturation > 80.0 {
 //!     println!("CPU-saturated: consider increasing workers");
 //! }
+//!
+//! // Workers or disk? Compare queue, worker and writer gauges
+//! let pressure = CONCURRENCY_METRICS.pipeline_pressure();
+//! println!("{pressure}"); // queue 4 | busy 6 | writing 5 | unfinalized 120 | disk-bound
+//!
+//! // Waits and worker utilization of one run, for its completion summary
+//! let start = CONCURRENCY_METRICS.wait_snapshot();
+//! // ... run ...
+//! let summary = CONCURRENCY_METRICS.summary_since(&start, workers, busy, elapsed);
 //! ```
 
+use adaptive_pipeline_domain::value_objects::ConcurrencySummary;
+use std::fmt;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -106,16 +117,39 @@ impl Histogram {
     /// True percentiles require sorting all values. Histograms trade
     /// precision for memory efficiency by bucketing values.
     pub fn percentile(&self, p: f64) -> u64 {
-        let total = self.count();
+        self.percentile_of(&self.counts(), p)
+    }
+
+    /// Get the count of each bucket, the +inf bucket last
+    pub fn counts(&self) -> Vec<u64> {
+        self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect()
+    }
+
+    /// Get a rough percentile estimate of the values recorded since
+    /// `earlier` was taken with [`Histogram::counts`]
+    pub fn percentile_since(&self, earlier: &[u64], p: f64) -> u64 {
+        let counts: Vec<u64> = self
+            .counts()
+            .iter()
+            .zip(earlier.iter().chain(std::iter::repeat(&0)))
+            .map(|(now, before)| now.saturating_sub(*before))
+            .collect();
+        self.percentile_of(&counts, p)
+    }
+
+    fn percentile_of(&self, counts: &[u64], p: f64) -> u64 {
+        let total: u64 = counts.iter().sum();
         if total == 0 {
             return 0;
         }
 
-        let target = (((total as f64) * p) / 100.0) as u64;
+        // Rank of the percentile value; at least the first value, so empty
+        // leading buckets are never reported
+        let target = (((total as f64) * p) / 100.0).ceil().max(1.0) as u64;
         let mut cumulative = 0u64;
 
-        for (i, bucket) in self.buckets.iter().enumerate() {
-            cumulative += bucket.load(Ordering::Relaxed);
+        for (i, count) in counts.iter().enumerate() {
+            cumulative += count;
             if cumulative >= target {
                 return if i < self.bucket_boundaries.len() {
                     self.bucket_boundaries[i]
@@ -192,6 +226,12 @@ pub struct ConcurrencyMetrics {
     /// Total number of tasks completed (counter)
     tasks_completed: AtomicU64,
 
+    /// Total number of stage panics caught at the worker boundary (counter)
+    worker_panics: AtomicU64,
+
+    /// Total number of workers replaced after a panic (counter)
+    worker_restarts: AtomicU64,
+
     // === Channel Queue Metrics ===
     /// Current depth of CPU worker channel (gauge)
     /// Educational: Reveals backpressure - high depth means workers can't keep
@@ -205,6 +245,80 @@ pub struct ConcurrencyMetrics {
     /// Histogram of time chunks wait in CPU queue
     /// Educational: Queue wait time indicates worker saturation
     cpu_queue_wait_histogram: Mutex<Histogram>,
+
+    // === Writer Metrics ===
+    /// Chunk writes currently in progress (gauge)
+    /// Educational: Busy workers stuck here mean the disk is the bottleneck
+    writer_in_flight: AtomicUsize,
+
+    /// Maximum chunk writes in progress at once (gauge)
+    writer_in_flight_max: AtomicUsize,
+
+    /// Chunks written whose output has not been finalized yet (gauge)
+    /// Educational: Data that still depends on the footer write and flush
+    writer_pending_finalize: AtomicUsize,
+
+    /// Histogram of chunk write times
+    writer_write_histogram: Mutex<Histogram>,
+}
+
+/// Point-in-time view of where chunks are in the pipeline
+///
+/// ## Educational: Workers slow or disk slow?
+///
+/// Every busy worker is either running stages or writing its chunk:
+/// - Few writes in flight, queue full: stages are the bottleneck
+///   (CPU-bound)
+/// - Most busy workers writing: the output disk is the bottleneck
+///   (disk-bound)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelinePressure {
+    /// Chunks waiting in the CPU worker channel
+    pub cpu_queue_depth: usize,
+    /// Workers processing or writing a chunk
+    pub active_workers: usize,
+    /// Of those, workers writing their chunk
+    pub writer_in_flight: usize,
+    /// Chunks written but not yet finalized
+    pub writer_pending_finalize: usize,
+}
+
+impl PipelinePressure {
+    /// Which side is holding throughput back, if either clearly is
+    pub fn bottleneck(&self) -> Option<&'static str> {
+        if self.writer_in_flight > 0 && self.writer_in_flight * 2 >= self.active_workers {
+            Some("disk-bound")
+        } else if self.active_workers > 0 && self.cpu_queue_depth > 0 {
+            Some("cpu-bound")
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for PipelinePressure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "queue {} | busy {} | writing {} | unfinalized {}",
+            self.cpu_queue_depth, self.active_workers, self.writer_in_flight, self.writer_pending_finalize
+        )?;
+        if let Some(bottleneck) = self.bottleneck() {
+            write!(f, " | {}", bottleneck)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bucket counts of the queue and CPU token wait histograms at one moment
+///
+/// Taken when a run starts, so that [`ConcurrencyMetrics::summary_since`]
+/// reports only the waits recorded since. Runs in the same process that
+/// overlap share the histograms, so their waits count toward each other.
+#[derive(Debug, Clone, Default)]
+pub struct WaitSnapshot {
+    cpu_queue_wait: Vec<u64>,
+    cpu_wait: Vec<u64>,
 }
 
 impl ConcurrencyMetrics {
@@ -226,11 +340,19 @@ impl ConcurrencyMetrics {
             active_workers: AtomicUsize::new(0),
             tasks_spawned: AtomicU64::new(0),
             tasks_completed: AtomicU64::new(0),
+            worker_panics: AtomicU64::new(0),
+            worker_restarts: AtomicU64::new(0),
 
             // Queue metrics
             cpu_queue_depth: AtomicUsize::new(0),
             cpu_queue_depth_max: AtomicUsize::new(0),
             cpu_queue_wait_histogram: Mutex::new(Histogram::new()),
+
+            // Writer metrics
+            writer_in_flight: AtomicUsize::new(0),
+            writer_in_flight_max: AtomicUsize::new(0),
+            writer_pending_finalize: AtomicUsize::new(0),
+            writer_write_histogram: Mutex::new(Histogram::new()),
         }
     }
 
@@ -369,6 +491,24 @@ impl ConcurrencyMetrics {
         self.tasks_completed.load(Ordering::Relaxed)
     }
 
+    /// Record a stage panic caught at the worker boundary
+    pub fn record_worker_panic(&self) {
+        self.worker_panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a worker replaced after a panic
+    pub fn record_worker_restart(&self) {
+        self.worker_restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn worker_panics(&self) -> u64 {
+        self.worker_panics.load(Ordering::Relaxed)
+    }
+
+    pub fn worker_restarts(&self) -> u64 {
+        self.worker_restarts.load(Ordering::Relaxed)
+    }
+
     // === Channel Queue Metrics ===
 
     /// Update CPU queue depth
@@ -444,17 +584,131 @@ impl ConcurrencyMetrics {
             .unwrap_or(0)
     }
 
+    // === Writer Metrics ===
+
+    /// Record a chunk write starting
+    pub fn writer_write_started(&self) {
+        let in_flight = self.writer_in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.writer_in_flight_max.fetch_max(in_flight, Ordering::Relaxed);
+    }
+
+    /// Record a chunk write ending; a successful write leaves its chunk
+    /// pending finalize
+    pub fn writer_write_completed(&self, duration: Duration, written: bool) {
+        self.writer_in_flight.fetch_sub(1, Ordering::Relaxed);
+        if written {
+            self.writer_pending_finalize.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Ok(hist) = self.writer_write_histogram.lock() {
+            hist.record(duration.as_millis() as u64);
+        }
+    }
+
+    /// Record the end of an output file's finalize, successful or not,
+    /// releasing its `chunks` from pending finalize
+    pub fn writer_finalized(&self, chunks: usize) {
+        let _ = self
+            .writer_pending_finalize
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |pending| {
+                Some(pending.saturating_sub(chunks))
+            });
+    }
+
+    /// Get chunk writes currently in progress
+    pub fn writer_in_flight(&self) -> usize {
+        self.writer_in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Get maximum chunk writes in progress at once
+    pub fn writer_in_flight_max(&self) -> usize {
+        self.writer_in_flight_max.load(Ordering::Relaxed)
+    }
+
+    /// Get chunks written but not yet finalized
+    pub fn writer_pending_finalize(&self) -> usize {
+        self.writer_pending_finalize.load(Ordering::Relaxed)
+    }
+
+    /// Get P95 chunk write time in milliseconds
+    pub fn writer_write_p95(&self) -> u64 {
+        self.writer_write_histogram
+            .lock()
+            .map(|h| h.percentile(95.0))
+            .unwrap_or(0)
+    }
+
+    // === Run Summary ===
+
+    /// Snapshot of the wait histograms, to summarize a run from
+    pub fn wait_snapshot(&self) -> WaitSnapshot {
+        WaitSnapshot {
+            cpu_queue_wait: self
+                .cpu_queue_wait_histogram
+                .lock()
+                .map(|h| h.counts())
+                .unwrap_or_default(),
+            cpu_wait: self.cpu_wait_histogram.lock().map(|h| h.counts()).unwrap_or_default(),
+        }
+    }
+
+    /// Summarizes a run that started at `start`: its wait percentiles, and
+    /// the utilization of `workers` that were busy for `busy` in total over
+    /// `elapsed` of wall time
+    pub fn summary_since(
+        &self,
+        start: &WaitSnapshot,
+        workers: usize,
+        busy: Duration,
+        elapsed: Duration,
+    ) -> ConcurrencySummary {
+        let percentile = |histogram: &Mutex<Histogram>, earlier: &[u64], p: f64| {
+            histogram.lock().map(|h| h.percentile_since(earlier, p)).unwrap_or(0)
+        };
+        let capacity = elapsed.as_secs_f64() * (workers as f64);
+        let worker_utilization_percent = if capacity > 0.0 {
+            (busy.as_secs_f64() / capacity * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+
+        ConcurrencySummary {
+            workers,
+            queue_wait_p50_ms: percentile(&self.cpu_queue_wait_histogram, &start.cpu_queue_wait, 50.0),
+            queue_wait_p95_ms: percentile(&self.cpu_queue_wait_histogram, &start.cpu_queue_wait, 95.0),
+            cpu_wait_p50_ms: percentile(&self.cpu_wait_histogram, &start.cpu_wait, 50.0),
+            cpu_wait_p95_ms: percentile(&self.cpu_wait_histogram, &start.cpu_wait, 95.0),
+            worker_utilization_percent,
+        }
+    }
+
+    /// Current queue, worker and writer gauges together
+    pub fn pipeline_pressure(&self) -> PipelinePressure {
+        PipelinePressure {
+            cpu_queue_depth: self.cpu_queue_depth(),
+            active_workers: self.active_workers(),
+            writer_in_flight: self.writer_in_flight(),
+            writer_pending_finalize: self.writer_pending_finalize(),
+        }
+    }
+
     /// Reset all metrics (for testing/benchmarking)
     pub fn reset(&self) {
         self.cpu_wait_total_ms.store(0, Ordering::Relaxed);
         self.io_wait_total_ms.store(0, Ordering::Relaxed);
         self.tasks_spawned.store(0, Ordering::Relaxed);
         self.tasks_completed.store(0, Ordering::Relaxed);
+        self.worker_panics.store(0, Ordering::Relaxed);
+        self.worker_restarts.store(0, Ordering::Relaxed);
 
         // Reset queue metrics
         self.cpu_queue_depth.store(0, Ordering::Relaxed);
         self.cpu_queue_depth_max.store(0, Ordering::Relaxed);
 
+        // Reset writer metrics
+        self.writer_in_flight.store(0, Ordering::Relaxed);
+        self.writer_in_flight_max.store(0, Ordering::Relaxed);
+        self.writer_pending_finalize.store(0, Ordering::Relaxed);
+
         if let Ok(hist) = self.cpu_wait_histogram.lock() {
             hist.reset();
         }
@@ -464,6 +718,9 @@ impl ConcurrencyMetrics {
         if let Ok(hist) = self.cpu_queue_wait_histogram.lock() {
             hist.reset();
         }
+        if let Ok(hist) = self.writer_write_histogram.lock() {
+            hist.reset();
+        }
     }
 }
 
@@ -534,6 +791,30 @@ mod tests {
         assert!(metrics.cpu_wait_p50() > 0);
     }
 
+    #[test]
+    fn test_summary_covers_only_waits_since_snapshot() {
+        let metrics = ConcurrencyMetrics::new(8, 24, 1024);
+        for _ in 0..10 {
+            metrics.record_cpu_queue_wait(Duration::from_millis(70));
+            metrics.record_cpu_wait(Duration::from_millis(70));
+        }
+
+        let start = metrics.wait_snapshot();
+        for _ in 0..10 {
+            metrics.record_cpu_queue_wait(Duration::from_millis(2));
+        }
+        metrics.record_cpu_wait(Duration::from_millis(7));
+
+        let summary = metrics.summary_since(&start, 4, Duration::from_secs(6), Duration::from_secs(2));
+        assert_eq!(summary.queue_wait_p50_ms, 5);
+        assert_eq!(summary.queue_wait_p95_ms, 5);
+        assert_eq!(summary.cpu_wait_p95_ms, 10);
+        assert_eq!(summary.worker_utilization_percent, 75.0);
+
+        // The process-wide percentiles still include the earlier waits
+        assert_eq!(metrics.cpu_queue_wait_p95(), 100);
+    }
+
     #[test]
     fn test_worker_tracking() {
         let metrics = ConcurrencyMetrics::new(8, 24, 1024);
@@ -548,4 +829,33 @@ mod tests {
         assert_eq!(metrics.active_workers(), 0);
         assert_eq!(metrics.tasks_completed(), 1);
     }
+
+    #[test]
+    fn test_writer_tracking_and_pressure() {
+        let metrics = ConcurrencyMetrics::new(8, 24, 1024);
+
+        metrics.worker_started();
+        metrics.worker_started();
+        metrics.writer_write_started();
+        metrics.writer_write_started();
+        assert_eq!(metrics.writer_in_flight(), 2);
+        assert_eq!(metrics.pipeline_pressure().bottleneck(), Some("disk-bound"));
+
+        metrics.writer_write_completed(Duration::from_millis(3), true);
+        metrics.writer_write_completed(Duration::from_millis(3), false);
+        assert_eq!(metrics.writer_in_flight(), 0);
+        assert_eq!(metrics.writer_in_flight_max(), 2);
+        assert_eq!(metrics.writer_pending_finalize(), 1);
+
+        metrics.update_cpu_queue_depth(4);
+        let pressure = metrics.pipeline_pressure();
+        assert_eq!(pressure.bottleneck(), Some("cpu-bound"));
+        assert_eq!(
+            pressure.to_string(),
+            "queue 4 | busy 2 | writing 0 | unfinalized 1 | cpu-bound"
+        );
+
+        metrics.writer_finalized(5);
+        assert_eq!(metrics.writer_pending_finalize(), 0);
+    }
 }
@@ -116,9 +116,10 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::infrastructure::metrics::service::MetricsService;
+use adaptive_pipeline_domain::events::PermissionDeniedEvent;
 use adaptive_pipeline_domain::services::pipeline_service::ProcessingObserver;
 use adaptive_pipeline_domain::ProcessingMetrics;
 
@@ -325,10 +326,6 @@ impl ProcessingObserver for MetricsObserver {
         self.metrics_service
             .update_throughput(calculated_throughput.max(throughput_mbps));
 
-        eprintln!(
-            "📊 MetricsObserver: Progress update - {} bytes processed, {:.2} MB/s",
-            bytes_processed, calculated_throughput
-        );
         debug!(
             "MetricsObserver: Progress update - {} bytes processed, {:.2} MB/s",
             bytes_processed, calculated_throughput
@@ -370,4 +367,14 @@ impl ProcessingObserver for MetricsObserver {
             total_duration, final_throughput
         );
     }
+    async fn on_permission_denied(&self, event: &PermissionDeniedEvent) {
+        self.metrics_service.increment_permission_denials(&event.operation);
+        warn!(
+            operation = %event.operation,
+            user = event.user_id.as_deref().unwrap_or("unknown"),
+            pipeline_id = %event.pipeline_id,
+            "{}",
+            event.reason
+        );
+    }
 }
@@ -14,7 +14,9 @@
 //! low overhead. See mdBook for detailed metric catalog and integration guide.
 
 use byte_unit::Byte;
-use prometheus::{Gauge, GaugeVec, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry};
+use prometheus::{
+    Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+};
 use std::sync::Arc;
 use tracing::debug;
 
@@ -64,9 +66,31 @@ pub struct MetricsService {
     // System metrics
     active_pipelines: IntGauge,
 
+    // Quota metrics
+    quota_rejections_total: IntCounterVec,
+
+    // Security policy metrics
+    permission_denials_total: IntCounterVec,
+
     // Debug stage metrics (for diagnostic stages)
     debug_stage_bytes: GaugeVec,
     debug_stage_chunks_total: IntCounterVec,
+
+    // Command bus metrics
+    commands_total: IntCounterVec,
+    command_duration: HistogramVec,
+
+    // Stage deadline metrics
+    stage_timeouts_total: IntCounterVec,
+
+    // Directory processing metrics
+    directory_files_total: IntCounterVec,
+
+    // Scrub metrics
+    scrubbed_archives_total: IntCounterVec,
+
+    // Replication metrics
+    replicated_archives_total: IntCounterVec,
 }
 
 impl MetricsService {
@@ -141,6 +165,27 @@ impl MetricsService {
         )
         .map_err(|e| PipelineError::metrics_error(format!("Failed to create active_pipelines metric: {}", e)))?;
 
+        // Create quota metrics (labelled by namespace and exceeded limit)
+        let quota_rejections_total = IntCounterVec::new(
+            Opts::new("quota_rejections_total", "Jobs rejected for exceeding a quota limit")
+                .namespace("adaptive_pipeline"),
+            &["namespace", "limit"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create quota_rejections_total metric: {}", e)))?;
+
+        // Create security policy metrics (labelled by denied operation)
+        let permission_denials_total = IntCounterVec::new(
+            Opts::new(
+                "permission_denials_total",
+                "Operations refused because the security context did not satisfy a pipeline's security policy",
+            )
+            .namespace("adaptive_pipeline"),
+            &["operation"],
+        )
+        .map_err(|e| {
+            PipelineError::metrics_error(format!("Failed to create permission_denials_total metric: {}", e))
+        })?;
+
         // Create debug stage metrics (with labels for stage identification)
         let debug_stage_bytes = GaugeVec::new(
             Opts::new("debug_stage_bytes", "Bytes processed by debug stage per chunk").namespace("adaptive_pipeline"),
@@ -157,6 +202,59 @@ impl MetricsService {
             PipelineError::metrics_error(format!("Failed to create debug_stage_chunks_total metric: {}", e))
         })?;
 
+        let commands_total = IntCounterVec::new(
+            Opts::new("commands_total", "Commands dispatched through the command bus").namespace("adaptive_pipeline"),
+            &["command", "outcome"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create commands_total metric: {}", e)))?;
+
+        let command_duration = HistogramVec::new(
+            HistogramOpts::new("command_duration_seconds", "Time spent handling dispatched commands")
+                .namespace("adaptive_pipeline"),
+            &["command"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create command_duration metric: {}", e)))?;
+
+        // Labelled by stage name and which deadline (stage or chunk) expired
+        let stage_timeouts_total = IntCounterVec::new(
+            Opts::new("stage_timeouts_total", "Chunks failed for exceeding a stage deadline")
+                .namespace("adaptive_pipeline"),
+            &["stage", "deadline"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create stage_timeouts_total metric: {}", e)))?;
+
+        // Labelled by outcome: processed, skipped or failed
+        let directory_files_total = IntCounterVec::new(
+            Opts::new(
+                "directory_files_total",
+                "Files handled by recursive directory processing",
+            )
+            .namespace("adaptive_pipeline"),
+            &["outcome"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create directory_files_total metric: {}", e)))?;
+
+        // Labelled by outcome: clean or corrupt
+        let scrubbed_archives_total = IntCounterVec::new(
+            Opts::new("scrubbed_archives_total", "Archives checked for corruption by scrub")
+                .namespace("adaptive_pipeline"),
+            &["outcome"],
+        )
+        .map_err(|e| PipelineError::metrics_error(format!("Failed to create scrubbed_archives_total metric: {}", e)))?;
+
+        // Labelled by outcome: replicated, skipped or failed
+        let replicated_archives_total = IntCounterVec::new(
+            Opts::new(
+                "replicated_archives_total",
+                "Archives copied to a secondary location by replicate",
+            )
+            .namespace("adaptive_pipeline"),
+            &["outcome"],
+        )
+        .map_err(|e| {
+            PipelineError::metrics_error(format!("Failed to create replicated_archives_total metric: {}", e))
+        })?;
+
         // Register all metrics
         registry
             .register(Box::new(pipelines_processed_total.clone()))
@@ -193,12 +291,38 @@ impl MetricsService {
         registry
             .register(Box::new(active_pipelines.clone()))
             .map_err(|e| PipelineError::metrics_error(format!("Failed to register active_pipelines: {}", e)))?;
+        registry
+            .register(Box::new(quota_rejections_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register quota_rejections_total: {}", e)))?;
+        registry
+            .register(Box::new(permission_denials_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register permission_denials_total: {}", e)))?;
         registry
             .register(Box::new(debug_stage_bytes.clone()))
             .map_err(|e| PipelineError::metrics_error(format!("Failed to register debug_stage_bytes: {}", e)))?;
         registry
             .register(Box::new(debug_stage_chunks_total.clone()))
             .map_err(|e| PipelineError::metrics_error(format!("Failed to register debug_stage_chunks_total: {}", e)))?;
+        registry
+            .register(Box::new(commands_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register commands_total: {}", e)))?;
+        registry
+            .register(Box::new(command_duration.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register command_duration: {}", e)))?;
+        registry
+            .register(Box::new(stage_timeouts_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register stage_timeouts_total: {}", e)))?;
+        registry
+            .register(Box::new(directory_files_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register directory_files_total: {}", e)))?;
+        registry
+            .register(Box::new(scrubbed_archives_total.clone()))
+            .map_err(|e| PipelineError::metrics_error(format!("Failed to register scrubbed_archives_total: {}", e)))?;
+        registry
+            .register(Box::new(replicated_archives_total.clone()))
+            .map_err(|e| {
+                PipelineError::metrics_error(format!("Failed to register replicated_archives_total: {}", e))
+            })?;
 
         debug!("MetricsService initialized with Prometheus registry");
 
@@ -213,8 +337,16 @@ impl MetricsService {
             throughput_mbps,
             compression_ratio,
             active_pipelines,
+            quota_rejections_total,
+            permission_denials_total,
             debug_stage_bytes,
             debug_stage_chunks_total,
+            commands_total,
+            command_duration,
+            stage_timeouts_total,
+            directory_files_total,
+            scrubbed_archives_total,
+            replicated_archives_total,
         })
     }
 
@@ -304,6 +436,16 @@ impl MetricsService {
         self.pipeline_chunks_processed_total.inc();
     }
 
+    /// Increment the quota rejection counter for a namespace and limit
+    pub fn increment_quota_rejections(&self, namespace: &str, limit: &str) {
+        self.quota_rejections_total.with_label_values(&[namespace, limit]).inc();
+    }
+
+    /// Increment the permission denial counter for an operation
+    pub fn increment_permission_denials(&self, operation: &str) {
+        self.permission_denials_total.with_label_values(&[operation]).inc();
+    }
+
     /// Record bytes processed by a debug stage for a specific chunk
     pub fn record_debug_stage_bytes(&self, label: &str, chunk_id: u64, bytes: u64) {
         self.debug_stage_bytes
@@ -321,6 +463,37 @@ impl MetricsService {
         debug!("Incremented debug stage chunks: label={}", label);
     }
 
+    /// Record a dispatched command's outcome (`ok` or an error category) and
+    /// handling time
+    pub fn record_command(&self, command: &str, outcome: &str, duration: std::time::Duration) {
+        self.commands_total.with_label_values(&[command, outcome]).inc();
+        self.command_duration
+            .with_label_values(&[command])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Count a chunk that exceeded `stage`'s deadline (`stage` or `chunk`)
+    pub fn increment_stage_timeouts(&self, stage: &str, deadline: &str) {
+        self.stage_timeouts_total.with_label_values(&[stage, deadline]).inc();
+    }
+
+    /// Count a file handled by directory processing (`processed`, `skipped`
+    /// or `failed`)
+    pub fn increment_directory_files(&self, outcome: &str) {
+        self.directory_files_total.with_label_values(&[outcome]).inc();
+    }
+
+    /// Count an archive checked by scrub (`clean` or `corrupt`)
+    pub fn increment_scrubbed_archives(&self, outcome: &str) {
+        self.scrubbed_archives_total.with_label_values(&[outcome]).inc();
+    }
+
+    /// Count an archive handled by replicate (`replicated`, `skipped` or
+    /// `failed`)
+    pub fn increment_replicated_archives(&self, outcome: &str) {
+        self.replicated_archives_total.with_label_values(&[outcome]).inc();
+    }
+
     /// Get Prometheus metrics in text format for scraping
     pub fn get_metrics(&self) -> Result<String, PipelineError> {
         let encoder = prometheus::TextEncoder::new();
@@ -0,0 +1,203 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Sharded Worker Metrics
+//!
+//! Lock-free accumulation of per-chunk statistics from the CPU worker pool.
+//!
+//! Each worker owns one cell and is its only writer, so recording a chunk is
+//! a handful of uncontended relaxed atomic adds with no lock and no shared
+//! cache line (cells are cache-padded). Readers never block writers:
+//!
+//! - A **sampler** calls [`WorkerMetricsShards::snapshot`] periodically to
+//!   drive the progress display and live Prometheus gauges while the file is
+//!   still being processed.
+//! - At **finalize** the same fold, taken after every worker has been
+//!   joined, gives the exact totals.
+//!
+//! Snapshots are ordered: every counter in a later snapshot is at least its
+//! value in an earlier one, and [`WorkerMetricsSnapshot::sequence`]
+//! increases with each snapshot taken. A snapshot taken while workers are
+//! running may see one counter of a chunk updated before another (bytes
+//! before the chunk count, say); the final snapshot is exact.
+//!
+//! ```rust,ignore
+//! let shards = Arc::new(WorkerMetricsShards::new(worker_count));
+//! // in worker `id`, after writing a chunk
+//! shards.record_chunk(id, chunk_index, bytes_in, bytes_out, busy);
+//! // in the sampler
+//! let snapshot = shards.snapshot();
+//! if let Some(last_chunk) = snapshot.last_chunk {
+//!     progress.show_sample(snapshot.chunks, last_chunk).await;
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crossbeam::utils::CachePadded;
+
+/// One worker's counters
+#[derive(Debug, Default)]
+struct WorkerCell {
+    chunks: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    busy_nanos: AtomicU64,
+    /// Highest chunk index written, plus one (zero: none yet)
+    last_chunk: AtomicU64,
+}
+
+/// Per-worker metric cells, folded on demand
+#[derive(Debug)]
+pub struct WorkerMetricsShards {
+    cells: Box<[CachePadded<WorkerCell>]>,
+    snapshots: AtomicU64,
+}
+
+/// Totals across all workers at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkerMetricsSnapshot {
+    /// Increases with every snapshot taken from the same shards
+    pub sequence: u64,
+    /// Chunks processed and written
+    pub chunks: u64,
+    /// Input bytes of those chunks
+    pub bytes_in: u64,
+    /// Bytes written for those chunks
+    pub bytes_out: u64,
+    /// Time workers spent running stages, summed over workers
+    pub busy: Duration,
+    /// Highest chunk index written, if any
+    pub last_chunk: Option<u64>,
+    /// Chunks processed by each worker, by worker ID
+    pub per_worker_chunks: Vec<u64>,
+}
+
+impl WorkerMetricsShards {
+    /// Creates one cell per worker
+    pub fn new(workers: usize) -> Self {
+        Self {
+            cells: (0..workers.max(1))
+                .map(|_| CachePadded::new(WorkerCell::default()))
+                .collect(),
+            snapshots: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of cells
+    pub fn workers(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Records a chunk completed by `worker_id`
+    ///
+    /// Worker IDs beyond the number of cells wrap around onto existing
+    /// cells; totals stay exact, only the per-worker split blurs.
+    pub fn record_chunk(&self, worker_id: usize, chunk_index: u64, bytes_in: u64, bytes_out: u64, busy: Duration) {
+        let cell = &self.cells[worker_id % self.cells.len()];
+        cell.bytes_in.fetch_add(bytes_in, Ordering::Relaxed);
+        cell.bytes_out.fetch_add(bytes_out, Ordering::Relaxed);
+        cell.busy_nanos
+            .fetch_add(u64::try_from(busy.as_nanos()).unwrap_or(u64::MAX), Ordering::Relaxed);
+        cell.last_chunk.fetch_max(chunk_index + 1, Ordering::Relaxed);
+        // Released last, so a snapshot that sees this chunk counted also
+        // sees its bytes
+        cell.chunks.fetch_add(1, Ordering::Release);
+    }
+
+    /// Folds every cell into totals
+    pub fn snapshot(&self) -> WorkerMetricsSnapshot {
+        let mut snapshot = WorkerMetricsSnapshot {
+            sequence: self.snapshots.fetch_add(1, Ordering::Relaxed) + 1,
+            per_worker_chunks: Vec::with_capacity(self.cells.len()),
+            ..Default::default()
+        };
+        let mut busy_nanos = 0u64;
+        let mut last_chunk = 0u64;
+        for cell in self.cells.iter() {
+            let chunks = cell.chunks.load(Ordering::Acquire);
+            snapshot.chunks += chunks;
+            snapshot.per_worker_chunks.push(chunks);
+            snapshot.bytes_in += cell.bytes_in.load(Ordering::Relaxed);
+            snapshot.bytes_out += cell.bytes_out.load(Ordering::Relaxed);
+            busy_nanos = busy_nanos.saturating_add(cell.busy_nanos.load(Ordering::Relaxed));
+            last_chunk = last_chunk.max(cell.last_chunk.load(Ordering::Relaxed));
+        }
+        snapshot.busy = Duration::from_nanos(busy_nanos);
+        snapshot.last_chunk = last_chunk.checked_sub(1);
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_snapshot_folds_every_worker() {
+        let shards = WorkerMetricsShards::new(3);
+        assert_eq!(shards.snapshot().last_chunk, None);
+
+        shards.record_chunk(0, 4, 100, 60, Duration::from_millis(2));
+        shards.record_chunk(2, 1, 50, 30, Duration::from_millis(1));
+        shards.record_chunk(2, 7, 50, 30, Duration::from_millis(1));
+
+        let snapshot = shards.snapshot();
+        assert_eq!(snapshot.chunks, 3);
+        assert_eq!(snapshot.bytes_in, 200);
+        assert_eq!(snapshot.bytes_out, 120);
+        assert_eq!(snapshot.busy, Duration::from_millis(4));
+        assert_eq!(snapshot.last_chunk, Some(7));
+        assert_eq!(snapshot.per_worker_chunks, vec![1, 0, 2]);
+        assert!(shards.snapshot().sequence > snapshot.sequence);
+    }
+
+    #[test]
+    fn test_concurrent_samples_are_monotonic_and_final_fold_is_exact() {
+        const WORKERS: usize = 4;
+        const CHUNKS_PER_WORKER: u64 = 5_000;
+        let shards = Arc::new(WorkerMetricsShards::new(WORKERS));
+
+        let writers: Vec<_> = (0..WORKERS)
+            .map(|worker| {
+                let shards = shards.clone();
+                std::thread::spawn(move || {
+                    for i in 0..CHUNKS_PER_WORKER {
+                        let index = i * WORKERS as u64 + worker as u64;
+                        shards.record_chunk(worker, index, 10, 5, Duration::from_nanos(1));
+                    }
+                })
+            })
+            .collect();
+
+        let mut previous = WorkerMetricsSnapshot::default();
+        while writers.iter().any(|writer| !writer.is_finished()) {
+            let sample = shards.snapshot();
+            assert!(sample.sequence > previous.sequence);
+            assert!(sample.chunks >= previous.chunks && sample.bytes_in >= previous.bytes_in);
+            assert!(
+                sample.bytes_in >= sample.chunks * 10,
+                "bytes are visible before the chunk count"
+            );
+            previous = sample;
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let total = shards.snapshot();
+        assert_eq!(total.chunks, WORKERS as u64 * CHUNKS_PER_WORKER);
+        assert_eq!(total.bytes_in, total.chunks * 10);
+        assert_eq!(total.last_chunk, Some(total.chunks - 1));
+        assert!(total
+            .per_worker_chunks
+            .iter()
+            .all(|&chunks| chunks == CHUNKS_PER_WORKER));
+    }
+}
@@ -35,10 +35,12 @@
 //! - **Repository implementations**: Concrete implementations of domain
 //!   interfaces
 //!
-//! ### Crate-visible Utilities (Infrastructure Sharing)
-//! - **Generic repository utilities**: Shared within infrastructure layer
-//! - **Database adapters**: Common database interaction patterns
-//! - **Implementation helpers**: Utilities for repository implementations
+//! ### Generic Repositories (Downstream Crates)
+//! - **`generic`**: The `Repository` interface and an in-memory implementation
+//! - **`sqlite`**: `SqliteRepository`, storing any serializable aggregate in
+//!   the pipeline database, with per-table migrations and transactions
+//! - **`sqlite_adapter`**: `Repository` on `SqliteRepository`, and factories
+//!   choosing between the two
 //!
 //! ### Private Implementation Details
 //! - **Internal data structures**: Hidden from other layers
@@ -122,15 +124,24 @@
 //! - **Backward Compatibility**: Support for schema evolution
 //! - **Data Migration**: Safe data transformation during updates
 // DOMAIN-SPECIFIC REPOSITORIES (PUBLIC - for dependency injection)
+pub mod sqlite_backup;
+pub mod sqlite_chunk_history;
+pub mod sqlite_idempotency;
+pub mod sqlite_job;
 pub mod sqlite_pipeline;
+pub mod sqlite_role;
+pub mod sqlite_running_job;
+pub mod sqlite_session;
+pub mod sqlite_unit_of_work;
+pub mod sqlite_usage;
 
 // SCHEMA MANAGEMENT (PUBLIC - for database initialization)
 pub mod schema;
 
-// INFRASTRUCTURE UTILITIES (CRATE-VISIBLE - internal infrastructure sharing)
-pub(crate) mod generic;
-pub(crate) mod sqlite;
-pub(crate) mod sqlite_adapter;
+// GENERIC REPOSITORIES (PUBLIC - for aggregates of downstream crates)
+pub mod generic;
+pub mod sqlite;
+pub mod sqlite_adapter;
 
 // CLEAN ARCHITECTURE EXPORTS - Only domain-specific implementations
 // Following DIP: Export concrete implementations for dependency injection
@@ -5,130 +5,54 @@
 // See LICENSE file in the project root.
 // /////////////////////////////////////////////////////////////////////////////
 
-// Infrastructure module - contains future features not yet fully utilized
-#![allow(dead_code, unused_imports, unused_variables)]
-//! # Generic Repository Adapter
-//!
-//! This module provides a generic, in-memory repository adapter implementation
-//! that can be used with any domain entity. It's designed for testing,
-//! prototyping, and scenarios where persistent storage is not required. This
-//! adapter follows Hexagonal Architecture principles.
-//!
-//! ## Overview
-//!
-//! The generic repository provides:
-//!
-//! - **Type Safety**: Generic implementation that works with any entity type
-//! - **In-Memory Storage**: Fast, in-memory storage using HashMap
-//! - **Thread Safety**: Concurrent access using async RwLock
-//! - **Simple Interface**: Clean, simple interface for basic CRUD operations
-//! - **Testing Support**: Ideal for unit testing and integration testing
-//!
-//! ## Architecture
-//!
-//! The implementation follows the Repository pattern:
-//!
-//! - **Generic Design**: Works with any entity implementing `RepositoryEntity`
-//! - **Async Operations**: All operations are async for consistency
-//! - **Memory-Based**: Uses HashMap for fast in-memory storage
-//! - **Thread-Safe**: Uses RwLock for concurrent access protection
-//!
-//! ## Entity Requirements
-//!
-//! Entities must implement the `RepositoryEntity` trait:
-
-//!
-//! ## Usage Examples
-//!
-//! ### Basic Repository Operations
-
-//!
-//! ### Testing with Generic Repository
-
-//!
-//! ### Advanced Query Operations
-
-//!
-//! ## Performance Characteristics
-//!
-//! ### Memory Usage
-//!
-//! - **In-Memory Storage**: All data stored in memory using HashMap
-//! - **Cloning Overhead**: Entities are cloned when stored and retrieved
-//! - **Memory Growth**: Memory usage grows linearly with entity count
-//! - **No Persistence**: Data is lost when the repository is dropped
-//!
-//! ### Access Performance
-//!
-//! - **O(1) Lookups**: HashMap provides O(1) average case lookups
-//! - **Fast Iteration**: Fast iteration over all entities
-//! - **Concurrent Access**: RwLock allows multiple concurrent readers
-//! - **Write Blocking**: Write operations block all access temporarily
-//!
-//! ### Scalability Limits
-//!
-//! - **Memory Bound**: Limited by available system memory
-//! - **Single Process**: Cannot be shared across processes
-//! - **No Persistence**: No durability guarantees
-//! - **Testing Focus**: Designed for testing, not production use
-//!
-//! ## Thread Safety
-//!
-//! The repository is fully thread-safe:
-//!
-//! - **Concurrent Reads**: Multiple threads can read simultaneously
-//! - **Exclusive Writes**: Write operations are mutually exclusive
-//! - **Async Operations**: All operations are async and non-blocking
-//! - **Deadlock Prevention**: RwLock prevents deadlocks
-//!
-//! ## Error Handling
-//!
-//! ### Common Error Scenarios
-//!
-//! - **Entity Not Found**: Attempting to access non-existent entities
-//! - **Duplicate Keys**: Attempting to save entities with duplicate IDs
-//! - **Lock Contention**: High contention scenarios with many writers
-//! - **Memory Exhaustion**: Running out of memory with large datasets
-//!
-//! ### Error Recovery
-//!
-//! - **Graceful Degradation**: Operations fail gracefully with clear errors
-//! - **Consistent State**: Repository maintains consistent state on errors
-//! - **Retry Safety**: Operations are safe to retry on failure
-//!
-//! ## Integration
-//!
-//! The generic repository integrates with:
-//!
-//! - **Domain Entities**: Any entity implementing `RepositoryEntity`
-//! - **Testing Framework**: Ideal for unit and integration testing
-//! - **Application Services**: Can be used by application layer services
-//! - **Dependency Injection**: Supports dependency injection patterns
-//!
-//! ## Use Cases
-//!
-//! ### Primary Use Cases
-//!
-//! - **Unit Testing**: Fast, isolated testing of business logic
-//! - **Integration Testing**: Testing without external dependencies
-//! - **Prototyping**: Rapid prototyping without database setup
-//! - **Development**: Local development without persistent storage
-//!
-//! ### Not Recommended For
-//!
-//! - **Production Systems**: Not suitable for production use
-//! - **Large Datasets**: Memory limitations with large datasets
-//! - **Distributed Systems**: Cannot be shared across processes
-//! - **Data Persistence**: No durability guarantees
-//!
-//! ## Future Enhancements
-//!
-//! Planned enhancements include:
-//!
-//! - **Query Builder**: More sophisticated query capabilities
-//! - **Indexing**: Secondary indexes for faster queries
-//! - **Serialization**: Optional persistence to disk
-//! - **Metrics**: Built-in performance metrics and monitoring
+//! # Generic Repository
+//!
+//! The storage-independent repository interface for aggregates that are not
+//! pipelines, and an in-memory implementation of it.
+//!
+//! - [`RepositoryEntity`]: an aggregate's identity, implemented by any type
+//!   stored in a repository
+//! - [`Repository`]: CRUD plus soft delete (archive and restore) for one
+//!   aggregate type
+//! - [`InMemoryRepository`]: a `HashMap`-backed [`Repository`] for tests and
+//!   prototypes; nothing survives the process
+//!
+//! [`super::sqlite_adapter::SqliteRepositoryAdapter`] implements
+//! [`Repository`] on the pipeline database, so code written against
+//! `Arc<dyn Repository<T>>` runs against either.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use adaptive_pipeline::infrastructure::repositories::generic::{
+//!     InMemoryRepository, Repository, RepositoryEntity,
+//! };
+//!
+//! #[derive(Clone)]
+//! struct Tag {
+//!     id: u32,
+//!     label: String,
+//! }
+//!
+//! impl RepositoryEntity for Tag {
+//!     type Id = u32;
+//!
+//!     fn id(&self) -> u32 {
+//!         self.id
+//!     }
+//!
+//!     fn name(&self) -> Option<&str> {
+//!         Some(&self.label)
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let tags = InMemoryRepository::new();
+//! tags.save(&Tag { id: 1, label: "nightly".to_string() }).await.unwrap();
+//! assert!(tags.find_by_name("nightly").await.unwrap().is_some());
+//! # }
+//! ```
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -153,14 +77,6 @@ use adaptive_pipeline_domain::PipelineError;
 /// - Have a stable lifetime (`'static`)
 /// - Provide a unique identifier that is hashable and comparable
 ///
-/// # Examples
-///
-///
-/// # Developer Notes
-///
-/// This trait enables any domain entity to be used with the generic repository.
-/// Entities must provide their unique identifier and support cloning for
-/// storage.
 pub trait RepositoryEntity: Clone + Send + Sync + 'static {
     /// The type used as the unique identifier for this entity
     type Id: Clone + Hash + Eq + Debug + Send + Sync + 'static;
@@ -184,8 +100,6 @@ pub trait RepositoryEntity: Clone + Send + Sync + 'static {
 /// reused for any domain entity. It maintains Clean Architecture principles by
 /// depending only on domain abstractions and provides consistent behavior
 /// across all entity types.
-///
-/// # Examples
 pub struct InMemoryRepository<T: RepositoryEntity> {
     /// Active entities storage
     entities: Arc<RwLock<HashMap<T::Id, T>>>,
@@ -388,7 +302,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_generic_repository_crud_operations() {
-        // let repo = InMemoryRepository::<TestEntity>::new();
+        let repo = InMemoryRepository::<TestEntity>::new();
         let entity_id = Uuid::new_v4();
         let entity = TestEntity {
             id: entity_id,
@@ -396,52 +310,31 @@ mod tests {
             value: 42,
         };
 
-        // Test save
-        // assert!(repo.save(&entity).is_ok());
-
-        // Test find_by_id
-        let found = Some(entity.clone()); // repo.find_by_id(entity_id)?;
-        assert_eq!(found, Some(entity.clone()));
-
-        // Test find_by_name
-        let found_by_name = Some(entity.clone()); // repo.find_by_name("test_entity")?;
-        assert_eq!(found_by_name, Some(entity.clone()));
-
-        // Test exists
-        // assert!(repo.exists(entity_id).unwrap());
+        // Save and find
+        repo.save(&entity).await.unwrap();
+        assert_eq!(repo.find_by_id(entity_id).await.unwrap(), Some(entity.clone()));
+        assert_eq!(repo.find_by_name("test_entity").await.unwrap(), Some(entity.clone()));
+        assert!(repo.exists(entity_id).await.unwrap());
+        assert_eq!(repo.count().await.unwrap(), 1);
+        assert_eq!(repo.list_all().await.unwrap(), vec![entity.clone()]);
 
-        // Test count
-        // assert_eq!(repo.count().unwrap(), 1);
-
-        // Test list_all
-        let all_entities = [entity.clone()]; // repo.list_all()?;
-        assert_eq!(all_entities.len(), 1);
-        assert_eq!(all_entities[0], entity);
-
-        // Test update
+        // Update
         let mut updated_entity = entity.clone();
         updated_entity.value = 100;
-        // assert!(repo.update(&updated_entity).is_ok());
-
-        let found_updated = updated_entity.clone(); // repo.find_by_id(entity_id)?;
-        assert_eq!(found_updated.value, 100);
-
-        // Test archive
-        // assert!(repo.archive(entity_id).unwrap());
-        // assert!(!repo.exists(entity_id).unwrap());
-        // assert_eq!(repo.count().unwrap(), 0);
-
-        let archived: Vec<TestEntity> = Vec::new(); // repo.list_archived()?;
-        assert_eq!(archived.len(), 0);
-
-        // Test restore
-        // assert!(repo.restore(entity_id).unwrap());
-        // assert!(repo.exists(entity_id).unwrap());
-        // assert_eq!(repo.count().unwrap(), 1);
-
-        // Test delete
-        // assert!(repo.delete(entity_id).unwrap());
-        // assert!(!repo.exists(entity_id).unwrap());
-        // assert_eq!(repo.count().unwrap(), 0);
+        repo.update(&updated_entity).await.unwrap();
+        assert_eq!(repo.find_by_id(entity_id).await.unwrap().unwrap().value, 100);
+
+        // Archive and restore
+        assert!(repo.archive(entity_id).await.unwrap());
+        assert!(!repo.exists(entity_id).await.unwrap());
+        assert_eq!(repo.count().await.unwrap(), 0);
+        assert_eq!(repo.list_archived().await.unwrap().len(), 1);
+        assert!(repo.restore(entity_id).await.unwrap());
+        assert!(repo.exists(entity_id).await.unwrap());
+
+        // Delete
+        assert!(repo.delete(entity_id).await.unwrap());
+        assert!(!repo.exists(entity_id).await.unwrap());
+        assert!(repo.update(&updated_entity).await.is_err());
     }
 }
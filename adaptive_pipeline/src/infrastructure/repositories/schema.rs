@@ -9,17 +9,77 @@
 //!
 //! Applies migrations on start-up so integration tests and services see a
 //! consistent database.
+//!
+//! ## Concurrent Access
+//!
+//! Several processes may open the same database at once: a long-running job
+//! and the CLI commands an operator runs beside it. Connections are opened
+//! with [`connect_options`], which puts the database in WAL mode, so readers
+//! never block on a writer, and waits up to [`BUSY_TIMEOUT`] for another
+//! process's write lock instead of failing with "database is locked".
+//! Processes that find the same migrations pending race to apply them; the
+//! losers retry and find them applied.
+//!
+//! ## Pre-Migration Backups
+//!
+//! Before applying migrations to a database that already has some, the
+//! database is copied to `<database>.pre-migration-<version>`; see
+//! [`super::sqlite_backup`].
+
+use std::str::FromStr;
+use std::time::Duration;
 
 use sqlx::migrate::MigrateDatabase;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqliteSynchronous};
 use sqlx::SqlitePool;
 use tracing::{debug, info};
 
+use adaptive_pipeline_domain::PipelineError;
+
+/// How long a connection waits for another process's write lock
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Times migrations are attempted while other processes apply them too
+const MIGRATION_ATTEMPTS: u32 = 5;
+
+/// Connection options for `database_url` suited to access by several
+/// processes: WAL journaling, `synchronous = NORMAL` (durable in WAL mode
+/// except for the last commits before a power loss), foreign keys enforced
+/// and a [`BUSY_TIMEOUT`] on locks
+///
+/// # Errors
+///
+/// Returns an error if `database_url` is not a SQLite URL.
+pub fn connect_options(database_url: &str) -> Result<SqliteConnectOptions, sqlx::Error> {
+    Ok(SqliteConnectOptions::from_str(database_url)?
+        .journal_mode(SqliteJournalMode::Wal)
+        .synchronous(SqliteSynchronous::Normal)
+        .foreign_keys(true)
+        .busy_timeout(BUSY_TIMEOUT))
+}
+
 /// Runs pending migrations against the provided SQLite pool.
+///
+/// Migrations run on a single connection with foreign keys disabled, since
+/// SQLite table rebuilds (needed to change constraints) would otherwise
+/// cascade deletes into child tables. Foreign keys are checked once the
+/// migrations have been applied.
 pub async fn ensure_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     debug!("Ensuring database schema is up to date");
 
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+
     // Run migrations - sqlx will automatically track what's been applied
-    sqlx::migrate!("./migrations").run(pool).await?;
+    let migrated = sqlx::migrate!("./migrations").run(&mut *conn).await;
+    let violations = sqlx::query("PRAGMA foreign_key_check").fetch_all(&mut *conn).await;
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    migrated?;
+    if !violations?.is_empty() {
+        return Err(sqlx::Error::Protocol(
+            "Foreign key violations found after applying migrations".to_string(),
+        ));
+    }
 
     info!("Database schema is up to date");
     Ok(())
@@ -89,12 +149,82 @@ pub async fn initialize_database(database_url: &str) -> Result<SqlitePool, sqlx:
     create_database_if_missing(database_url).await?;
 
     // Connect to database
-    let pool = SqlitePool::connect(database_url).await?;
+    let options = connect_options(database_url)?;
+    let database_file = options.get_filename().to_path_buf();
+    let pool = SqlitePool::connect_with(options).await?;
+
+    // Keep a copy of an existing database before its schema changes
+    if database_file.is_file() {
+        if let Some(backup) = super::sqlite_backup::backup_before_migrating(&pool, &database_file).await? {
+            info!("Backed up database to {} before migrating", backup.display());
+        }
+    }
 
-    // Run migrations
-    ensure_schema(&pool).await?;
+    // Run migrations, retrying if another process applied them first
+    let mut attempt = 1;
+    loop {
+        let applied_before = applied_migrations(&pool).await;
+        match ensure_schema(&pool).await {
+            Err(e)
+                if attempt < MIGRATION_ATTEMPTS
+                    && (is_busy(&e) || applied_migrations(&pool).await > applied_before) =>
+            {
+                debug!("Migrations raced another process (attempt {}): {}", attempt, e);
+                tokio::time::sleep(Duration::from_millis(50 * u64::from(attempt))).await;
+                attempt += 1;
+            }
+            result => return result.map(|()| pool),
+        }
+    }
+}
 
-    Ok(pool)
+/// Opens the database at `database_path`, creating and migrating it if
+/// needed
+///
+/// `database_path` is a file path, or `:memory:` (also `sqlite::memory:`)
+/// for a private in-memory database.
+///
+/// # Errors
+///
+/// Returns `PipelineError::DatabaseError` if the database cannot be created,
+/// opened or migrated.
+pub async fn open_database(database_path: &str) -> Result<SqlitePool, PipelineError> {
+    initialize_database(&database_url(database_path))
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to initialize database '{}': {}", database_path, e)))
+}
+
+/// SQLite connection URL for `database_path`: `sqlite::memory:` for an
+/// in-memory database, otherwise `sqlite://<path>`
+pub fn database_url(database_path: &str) -> String {
+    if database_path == ":memory:" || database_path == "sqlite::memory:" {
+        "sqlite::memory:".to_string()
+    } else {
+        format!("sqlite://{}", database_path)
+    }
+}
+
+/// Number of migrations recorded as applied, or 0 before the first one
+///
+/// A failed migration that another process has meanwhile recorded as applied
+/// raced that process, whatever the failure said.
+async fn applied_migrations(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE success = 1")
+        .fetch_one(pool)
+        .await
+        .unwrap_or(0)
+}
+
+/// Whether `error` is SQLite giving up waiting for another connection's lock
+fn is_busy(error: &sqlx::Error) -> bool {
+    // Extended result codes keep the primary code in the low byte
+    const SQLITE_BUSY: i64 = 5;
+    const SQLITE_LOCKED: i64 = 6;
+    error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i64>().ok())
+        .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED))
 }
 
 #[cfg(test)]
@@ -138,6 +268,29 @@ mod tests {
         assert_eq!(result, 1, "Pipelines table should exist");
     }
 
+    #[tokio::test]
+    async fn test_applied_migrations_counts_recorded_migrations() {
+        let temp = NamedTempFile::new().unwrap();
+        let db_url = format!("sqlite://{}", temp.path().to_str().unwrap());
+        let pool = SqlitePool::connect(&db_url).await.unwrap();
+        assert_eq!(applied_migrations(&pool).await, 0);
+
+        ensure_schema(&pool).await.unwrap();
+        let applied = applied_migrations(&pool).await;
+        assert_eq!(applied, sqlx::migrate!("./migrations").iter().count() as i64);
+
+        // A migration that fails while no other process records one is not
+        // a race, whatever the failure says
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = 20250109000000")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let before = applied_migrations(&pool).await;
+        assert!(ensure_schema(&pool).await.is_err());
+        assert!(!is_busy(&ensure_schema(&pool).await.unwrap_err()));
+        assert_eq!(applied_migrations(&pool).await, before);
+    }
+
     #[tokio::test]
     async fn test_ensure_schema_idempotent() {
         let temp = NamedTempFile::new().unwrap();
@@ -153,4 +306,36 @@ mod tests {
         ensure_schema(&pool).await.unwrap();
         ensure_schema(&pool).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_initialize_database_switches_existing_database_to_wal() {
+        // An existing empty file starts with the rollback journal
+        let temp = NamedTempFile::new().unwrap();
+        let db_url = format!("sqlite://{}", temp.path().to_str().unwrap());
+
+        let pool = initialize_database(&db_url).await.unwrap();
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(busy_timeout, BUSY_TIMEOUT.as_millis() as i64);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_initialization_of_a_new_database() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_url = format!("sqlite://{}", dir.path().join("pipeline.db").display());
+
+        // Separate pools race to create and migrate the database, as
+        // separate processes would
+        let results = futures::future::join_all((0..8).map(|_| initialize_database(&db_url))).await;
+        for result in results {
+            result.unwrap();
+        }
+    }
 }
@@ -5,310 +5,328 @@
 // See LICENSE file in the project root.
 // /////////////////////////////////////////////////////////////////////////////
 
-// Infrastructure module - contains future features not yet fully utilized
-#![allow(dead_code, unused_imports, unused_variables)]
-//! # SQLite Base Repository Adapter
+//! # SQLite Generic Repository
 //!
-//! This module provides a generic SQLite-based repository adapter
-//! implementation that serves as the foundation for persistent data storage in
-//! the adaptive pipeline system. It implements the Repository pattern with
-//! SQLite as the underlying database technology, following
-//! Hexagonal Architecture principles.
-//!
-//! ## Overview
-//!
-//! The SQLite repository provides:
-//!
-//! - **Generic Entity Storage**: Type-safe storage for any entity implementing
-//!   `SqliteEntity`
-//! - **ACID Transactions**: Full transactional support for data consistency
-//! - **Connection Pooling**: Efficient database connection management
-//! - **Schema Management**: Automatic table creation and schema validation
-//! - **JSON Serialization**: Flexible entity serialization using JSON columns
-//! - **Query Optimization**: Efficient querying with prepared statements
-//!
-//! ## Architecture
-//!
-//! The repository follows Clean Architecture principles:
-//!
-//! ```text
-//! ┌─────────────────────────────────────────┐
-//! │         Domain Layer                  │
-//! │  ┌─────────────────────────────────┐    │
-//! │  │    Repository Interface       │    │
-//! │  └─────────────────────────────────┘    │
-//! └─────────────────┬───────────────────────┘
-//!                   │ implements
-//! ┌─────────────────▼───────────────────────┐
-//! │      Infrastructure Layer             │
-//! │  ┌─────────────────────────────────┐    │
-//! │  │   SQLite Repository Adapter   │    │
-//! │  └─────────────────┬───────────────┘    │
-//! │                    │ uses              │
-//! │  ┌─────────────────▼───────────────┐    │
-//! │  │    SQLite Repository Base    │    │
-//! │  └─────────────────────────────────┘    │
-//! └─────────────────────────────────────────┘
-//! ```
+//! Stores any serializable aggregate in the pipeline database, one table per
+//! aggregate type, so that crates built on this one (custom stages,
+//! catalogs) can persist their own data next to the pipelines, share its
+//! backups and write to it in the same transactions.
 //!
 //! ## Entity Requirements
 //!
-//! Entities stored in SQLite repositories must implement the `SqliteEntity`
-//! trait:
-
-//!
-//! ## Usage Examples
-//!
-//! ### Basic Repository Operations
-
-//!
-//! ### Transaction Support
-//!
-//!
-//! ## Database Schema
+//! An aggregate implements [`RepositoryEntity`] for its identity and
+//! [`SqliteEntity`] for its table. Both its ID and the aggregate itself must
+//! serialize with serde; the aggregate is stored as JSON in the `data`
+//! column.
+//!
+//! ```rust
+//! use adaptive_pipeline::infrastructure::repositories::generic::RepositoryEntity;
+//! use adaptive_pipeline::infrastructure::repositories::sqlite::SqliteEntity;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct CatalogEntry {
+//!     id: String,
+//!     title: String,
+//! }
+//!
+//! impl RepositoryEntity for CatalogEntry {
+//!     type Id = String;
+//!
+//!     fn id(&self) -> String {
+//!         self.id.clone()
+//!     }
+//!
+//!     fn name(&self) -> Option<&str> {
+//!         Some(&self.title)
+//!     }
+//! }
+//!
+//! impl SqliteEntity for CatalogEntry {
+//!     fn table_name() -> &'static str {
+//!         "catalog_entries"
+//!     }
+//!
+//!     fn migrations() -> &'static [&'static str] {
+//!         &["CREATE INDEX IF NOT EXISTS idx_catalog_entries_updated ON catalog_entries(updated_at)"]
+//!     }
+//! }
+//! ```
 //!
-//! ### Standard Table Structure
+//! ## Table Layout
 //!
-//! All SQLite repository tables follow a standard structure:
+//! The default [`SqliteEntity::table_schema`] creates:
 //!
 //! ```sql
-//! CREATE TABLE IF NOT EXISTS entity_table (
-//!     id TEXT PRIMARY KEY,           -- Entity identifier
-//!     data TEXT NOT NULL,            -- JSON-serialized entity data
-//!     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-//!     updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-//! );
-//!
-//! -- Indexes for performance
-//! CREATE INDEX IF NOT EXISTS idx_entity_table_created_at ON entity_table(created_at);
-//! CREATE INDEX IF NOT EXISTS idx_entity_table_updated_at ON entity_table(updated_at);
+//! CREATE TABLE IF NOT EXISTS <table_name> (
+//!     id TEXT PRIMARY KEY,        -- SqliteEntity::id_to_sql
+//!     name TEXT,                  -- RepositoryEntity::name
+//!     data TEXT NOT NULL,         -- the aggregate as JSON
+//!     created_at TEXT NOT NULL,   -- RFC 3339
+//!     updated_at TEXT NOT NULL,   -- RFC 3339
+//!     archived BOOLEAN NOT NULL DEFAULT false
+//! )
 //! ```
 //!
-//! ### Schema Migration
+//! A custom schema may add columns and indexes but must keep these.
+//! Prefix table names with the owning crate's name to stay clear of this
+//! crate's tables.
 //!
-//! The repository automatically handles schema creation and migration:
+//! ## Migrations
 //!
+//! [`SqliteEntity::migrations`] lists SQL scripts applied in order after the
+//! table is created. The number applied is recorded per table in
+//! `entity_schema_versions`, so each runs once per database. Never edit or
+//! reorder a released script; append a new one. These are tracked apart
+//! from this crate's own migrations, which a newer release may add to.
 //!
-//! ## Performance Optimization
+//! ## Transactions
 //!
-//! ### Connection Pooling
+//! [`SqliteRepository::begin`] starts a write transaction. The `*_in`
+//! methods take its connection, so saves of several aggregates, of
+//! different types or repositories on the same database, commit together:
 //!
-//! The repository uses SQLite connection pooling for optimal performance:
-//!
-//!
-//! ### Query Optimization
-//!
-//! - **Prepared Statements**: All queries use prepared statements for
-//!   performance
-//! - **Indexing**: Automatic creation of indexes on commonly queried columns
-//! - **Batch Operations**: Support for bulk insert/update operations
-//! - **Query Planning**: SQLite query planner optimization
-//!
-//! ### Caching Strategy
-//!
-//!
-//! ## Error Handling
-//!
-//! The repository provides comprehensive error handling:
+//! ```rust,ignore
+//! let mut tx = entries.begin().await?;
+//! entries.save_in(&mut tx, &entry).await?;
+//! tags.save_in(&mut tx, &tag).await?;
+//! tx.commit().await?;
+//! ```
 
-//!
-//! ## Security Considerations
-//!
-//! - **SQL Injection Prevention**: All queries use parameterized statements
-//! - **Data Encryption**: Support for transparent data encryption at rest
-//! - **Access Control**: Integration with security context for permission
-//!   checks
-//! - **Audit Logging**: Comprehensive logging of all database operations
-//!
-//! ## Testing Support
-//!
-//! ### In-Memory Testing
-//!
-//!
-//! ## Monitoring and Observability
-//!
-//! - **Metrics Collection**: Performance metrics for query execution times
-//! - **Health Checks**: Database connectivity and performance monitoring
-//! - **Distributed Tracing**: Integration with tracing systems
-//! - **Logging**: Comprehensive operation logging with structured data
-
-use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool};
-use std::fmt::Debug;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, Sqlite, SqliteConnection, SqlitePool, Transaction};
 use std::marker::PhantomData;
+use tracing::{debug, info};
 
+use crate::infrastructure::repositories::generic::RepositoryEntity;
 use adaptive_pipeline_domain::PipelineError;
 
-/// Generic SQLite repository implementation for any entity type
-///
-/// # Type Parameters
-/// * `T` - The entity type that implements `SqliteEntity`
-///
-/// # Developer Notes
-/// This repository provides persistent storage using SQLite database.
-/// It requires entities to implement serialization for storage and
-/// provides the same interface as the in-memory repository through adapters.
-///
-/// # Architecture
-/// This is an infrastructure layer component that depends on domain
-/// abstractions. It should be wrapped by an adapter to implement the domain
-/// Repository trait.
-pub struct SqliteRepository<T> {
-    pool: SqlitePool,
-    table_name: String,
-    _phantom: PhantomData<T>,
-}
-
-/// Trait for entities that can be stored in SQLite
-///
-/// # Developer Notes
-/// This trait extends the basic repository entity with SQLite-specific
-/// requirements:
-/// - Serialization for JSON storage
-/// - Table name for dynamic table operations
-/// - SQL schema definition for table creation
-pub trait SqliteEntity: Clone + Send + Sync + 'static + Serialize + for<'de> Deserialize<'de> {
-    /// The type used as the unique identifier for this entity
-    type Id: Clone + Debug + Send + Sync + 'static + Serialize + for<'de> Deserialize<'de>;
-
-    /// Returns the unique identifier for this entity
-    fn id(&self) -> Self::Id;
-
-    /// Returns the table name for this entity type
+/// An aggregate stored by [`SqliteRepository`]
+pub trait SqliteEntity: RepositoryEntity<Id: Serialize + DeserializeOwned> + Serialize + DeserializeOwned {
+    /// Table holding this aggregate: ASCII letters, digits and underscores,
+    /// not starting with a digit
     fn table_name() -> &'static str;
 
-    /// Returns the SQL schema for creating the table
-    /// Should include the table creation SQL with appropriate columns
-    fn table_schema() -> &'static str;
-
-    /// Optional: Returns a human-readable name for searching (default: None)
-    fn name(&self) -> Option<&str> {
-        None
-    }
-
-    /// Converts the entity ID to a string for SQL queries
-    fn id_to_string(&self) -> String {
-        serde_json::to_string(&self.id()).unwrap_or_default()
+    /// SQL creating the table if it does not exist
+    ///
+    /// Defaults to the layout described in the module documentation.
+    fn table_schema() -> String {
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+                id TEXT PRIMARY KEY, \
+                name TEXT, \
+                data TEXT NOT NULL, \
+                created_at TEXT NOT NULL, \
+                updated_at TEXT NOT NULL, \
+                archived BOOLEAN NOT NULL DEFAULT false\
+             )",
+            Self::table_name()
+        )
     }
 
-    /// Parses an ID from a string (inverse of id_to_string)
-    fn id_from_string(s: &str) -> Result<Self::Id, PipelineError> {
-        serde_json::from_str(s).map_err(|e| PipelineError::SerializationError(format!("Failed to parse ID: {}", e)))
+    /// Migration scripts applied in order after the table is created
+    ///
+    /// Only ever append to this list.
+    fn migrations() -> &'static [&'static str] {
+        &[]
     }
 
-    /// Converts an ID to string format (static version of id_to_string)
-    fn id_to_string_static(id: &Self::Id) -> String {
+    /// How `id` is stored in the `id` column
+    ///
+    /// Defaults to its JSON encoding. Must not change once rows are stored.
+    fn id_to_sql(id: &Self::Id) -> String {
         serde_json::to_string(id).unwrap_or_default()
     }
 }
 
+/// Repository storing aggregates of type `T` in one SQLite table
+pub struct SqliteRepository<T> {
+    pool: SqlitePool,
+    table_name: &'static str,
+    _phantom: PhantomData<T>,
+}
+
 impl<T: SqliteEntity> SqliteRepository<T> {
-    /// Creates a new SQLite repository with the given database pool
+    /// Creates a repository on `pool`, creating `T`'s table and applying its
+    /// pending migrations
+    ///
+    /// Share the pool of another repository, e.g.
+    /// `SqlitePipelineRepository::pool`, to use the pipeline database.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the table name is not a plain identifier or a migration fails;
+    /// a failed migration is rolled back with those before it in this call.
     pub async fn new(pool: SqlitePool) -> Result<Self, PipelineError> {
-        let table_name = T::table_name().to_string();
-        let repo = Self {
+        let table_name = T::table_name();
+        if !is_identifier(table_name) {
+            return Err(PipelineError::invalid_config(format!(
+                "Invalid table name '{}': use ASCII letters, digits and underscores",
+                table_name
+            )));
+        }
+
+        let repository = Self {
             pool,
             table_name,
             _phantom: PhantomData,
         };
-
-        // Ensure table exists
-        // repo.ensure_table_exists()?;
-
-        Ok(repo)
+        repository.ensure_schema().await?;
+        Ok(repository)
     }
 
-    /// Creates a new SQLite repository with a database file path
+    /// Creates a repository on the database at `database_path`, creating the
+    /// database and applying this crate's migrations if needed
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
     pub async fn from_file(database_path: &str) -> Result<Self, PipelineError> {
-        let pool = SqlitePool::connect(database_path)
-            .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to connect to database: {}", e)))?;
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
 
         Self::new(pool).await
     }
 
-    /// Creates an in-memory SQLite database (useful for testing)
+    /// Creates a repository on a new in-memory database (useful for testing)
     pub async fn in_memory() -> Result<Self, PipelineError> {
-        let pool = SqlitePool::connect(":memory:")
-            .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to create in-memory database: {}", e)))?;
-
-        Self::new(pool).await
+        Self::from_file(":memory:").await
     }
 
-    /// Ensures the table exists, creating it if necessary
-    pub async fn ensure_table_exists(&self) -> Result<(), PipelineError> {
-        let schema = T::table_schema();
-        sqlx::query(schema)
-            .execute(&self.pool)
+    /// Creates the table and applies pending migrations in one transaction
+    async fn ensure_schema(&self) -> Result<(), PipelineError> {
+        let mut tx = self.begin().await?;
+        sqlx::raw_sql(
+            "CREATE TABLE IF NOT EXISTS entity_schema_versions (\
+                table_name TEXT PRIMARY KEY, \
+                version INTEGER NOT NULL\
+             )",
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to create schema version table: {}", e)))?;
+        sqlx::raw_sql(&T::table_schema())
+            .execute(&mut *tx)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to create table: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to create table {}: {}", self.table_name, e)))?;
+
+        let applied: Option<i64> =
+            sqlx::query_scalar("SELECT version FROM entity_schema_versions WHERE table_name = ?")
+                .bind(self.table_name)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| PipelineError::database_error(format!("Failed to read schema version: {}", e)))?;
+        let applied = applied.unwrap_or(0).max(0) as usize;
+        let migrations = T::migrations();
+        if applied > migrations.len() {
+            return Err(PipelineError::database_error(format!(
+                "Table {} is at schema version {}, newer than the {} migrations this build knows",
+                self.table_name,
+                applied,
+                migrations.len()
+            )));
+        }
 
+        for (index, script) in migrations.iter().enumerate().skip(applied) {
+            sqlx::raw_sql(script).execute(&mut *tx).await.map_err(|e| {
+                PipelineError::database_error(format!(
+                    "Migration {} of table {} failed: {}",
+                    index + 1,
+                    self.table_name,
+                    e
+                ))
+            })?;
+            debug!("Applied migration {} of table {}", index + 1, self.table_name);
+        }
+        sqlx::query(
+            "INSERT INTO entity_schema_versions (table_name, version) VALUES (?, ?) \
+             ON CONFLICT(table_name) DO UPDATE SET version = excluded.version",
+        )
+        .bind(self.table_name)
+        .bind(migrations.len() as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to record schema version: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to commit schema changes: {}", e)))?;
+        if migrations.len() > applied {
+            info!(
+                "Table {} migrated to schema version {}",
+                self.table_name,
+                migrations.len()
+            );
+        }
         Ok(())
     }
 
-    /// Helper method to convert ID to string using the same format as save
-    fn id_to_string_format(&self, id: &T::Id) -> Result<String, PipelineError> {
-        Ok(T::id_to_string_static(id))
+    /// Starts a write transaction on this repository's database
+    ///
+    /// The write lock is taken up front. Pass the transaction to the `*_in`
+    /// methods of any repository on the same database, then commit it;
+    /// dropping it rolls back.
+    pub async fn begin(&self) -> Result<Transaction<'static, Sqlite>, PipelineError> {
+        self.pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to begin transaction: {}", e)))
     }
 
-    /// Saves an entity to the database
+    /// Saves `entity`, inserting it or replacing the stored one with the
+    /// same ID, which is un-archived
     pub async fn save(&self, entity: &T) -> Result<(), PipelineError> {
-        let id_str = entity.id_to_string();
-        let data = serde_json::to_string(entity)
-            .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize entity: {}", e)))?;
+        let mut conn = self.acquire().await?;
+        self.save_in(&mut conn, entity).await
+    }
 
+    /// [`Self::save`] on `conn`, e.g. inside a transaction
+    pub async fn save_in(&self, conn: &mut SqliteConnection, entity: &T) -> Result<(), PipelineError> {
         let now = chrono::Utc::now().to_rfc3339();
-
-        // Get entity name if available
-        let entity_name = entity.name().unwrap_or("unknown");
-
         let query = format!(
-            "INSERT OR REPLACE INTO {} (id, name, data, created_at, updated_at, archived) VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO {} (id, name, data, created_at, updated_at, archived) VALUES (?, ?, ?, ?, ?, false) \
+             ON CONFLICT(id) DO UPDATE SET \
+             name = excluded.name, data = excluded.data, updated_at = excluded.updated_at, archived = false",
             self.table_name
         );
 
         sqlx::query(&query)
-            .bind(&id_str)
-            .bind(entity_name)
-            .bind(&data)
+            .bind(T::id_to_sql(&entity.id()))
+            .bind(entity.name())
+            .bind(serialize(entity)?)
             .bind(&now)
             .bind(&now)
-            .bind(false)
-            .execute(&self.pool)
+            .execute(conn)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to save entity: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to save entity: {}", e)))?;
 
         Ok(())
     }
 
-    /// Finds an entity by its unique identifier
+    /// Finds an active entity by its ID
     pub async fn find_by_id(&self, id: T::Id) -> Result<Option<T>, PipelineError> {
-        // Use the same ID format as save method
-        let id_str = self.id_to_string_format(&id)?;
+        let mut conn = self.acquire().await?;
+        self.find_by_id_in(&mut conn, id).await
+    }
 
+    /// [`Self::find_by_id`] on `conn`, e.g. to read inside a transaction
+    pub async fn find_by_id_in(&self, conn: &mut SqliteConnection, id: T::Id) -> Result<Option<T>, PipelineError> {
         let query = format!("SELECT data FROM {} WHERE id = ? AND archived = false", self.table_name);
 
         let row = sqlx::query(&query)
-            .bind(&id_str)
-            .fetch_optional(&self.pool)
+            .bind(T::id_to_sql(&id))
+            .fetch_optional(conn)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to query entity: {}", e)))?;
-
-        if let Some(row) = row {
-            let data: String = row.get("data");
-            let entity: T = serde_json::from_str(&data)
-                .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))?;
-            Ok(Some(entity))
-        } else {
-            Ok(None)
-        }
+            .map_err(|e| PipelineError::database_error(format!("Failed to query entity: {}", e)))?;
+
+        row.as_ref().map(deserialize).transpose()
     }
 
-    /// Finds an entity by name
+    /// Finds the first active entity whose [`RepositoryEntity::name`] is
+    /// `name`
     pub async fn find_by_name(&self, name: &str) -> Result<Option<T>, PipelineError> {
         let query = format!(
-            "SELECT data FROM {} WHERE name = ? AND archived = false LIMIT 1",
+            "SELECT data FROM {} WHERE name = ? AND archived = false ORDER BY created_at LIMIT 1",
             self.table_name
         );
 
@@ -316,19 +334,12 @@ impl<T: SqliteEntity> SqliteRepository<T> {
             .bind(name)
             .fetch_optional(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to query entity by name: {}", e)))?;
-
-        if let Some(row) = row {
-            let data: String = row.get("data");
-            let entity: T = serde_json::from_str(&data)
-                .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))?;
-            Ok(Some(entity))
-        } else {
-            Ok(None)
-        }
+            .map_err(|e| PipelineError::database_error(format!("Failed to query entity by name: {}", e)))?;
+
+        row.as_ref().map(deserialize).transpose()
     }
 
-    /// Lists all entities
+    /// Lists active entities, oldest first
     pub async fn list_all(&self) -> Result<Vec<T>, PipelineError> {
         let query = format!(
             "SELECT data FROM {} WHERE archived = false ORDER BY created_at",
@@ -338,20 +349,13 @@ impl<T: SqliteEntity> SqliteRepository<T> {
         let rows = sqlx::query(&query)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to list entities: {}", e)))?;
-
-        let mut entities = Vec::new();
-        for row in rows {
-            let data: String = row.get("data");
-            let entity: T = serde_json::from_str(&data)
-                .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))?;
-            entities.push(entity);
-        }
+            .map_err(|e| PipelineError::database_error(format!("Failed to list entities: {}", e)))?;
 
-        Ok(entities)
+        rows.iter().map(deserialize).collect()
     }
 
-    /// Lists entities with pagination
+    /// Lists at most `limit` active entities, oldest first, skipping the
+    /// first `offset`
     pub async fn list_paginated(&self, offset: usize, limit: usize) -> Result<Vec<T>, PipelineError> {
         let query = format!(
             "SELECT data FROM {} WHERE archived = false ORDER BY created_at LIMIT ? OFFSET ?",
@@ -363,143 +367,139 @@ impl<T: SqliteEntity> SqliteRepository<T> {
             .bind(offset as i64)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to list paginated entities: {}", e)))?;
-
-        let mut entities = Vec::new();
-        for row in rows {
-            let data: String = row.get("data");
-            let entity: T = serde_json::from_str(&data)
-                .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))?;
-            entities.push(entity);
-        }
+            .map_err(|e| PipelineError::database_error(format!("Failed to list paginated entities: {}", e)))?;
 
-        Ok(entities)
+        rows.iter().map(deserialize).collect()
     }
 
-    /// Updates an existing entity
+    /// Replaces a stored active entity
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::PipelineNotFound` if no active entity has
+    /// `entity`'s ID.
     pub async fn update(&self, entity: &T) -> Result<(), PipelineError> {
-        let id_str = entity.id_to_string();
-        let data = serde_json::to_string(entity)
-            .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize entity: {}", e)))?;
-
-        let name = entity.name().unwrap_or("");
-        let now = chrono::Utc::now().to_rfc3339();
+        let mut conn = self.acquire().await?;
+        self.update_in(&mut conn, entity).await
+    }
 
+    /// [`Self::update`] on `conn`, e.g. inside a transaction
+    pub async fn update_in(&self, conn: &mut SqliteConnection, entity: &T) -> Result<(), PipelineError> {
         let query = format!(
             "UPDATE {} SET name = ?, data = ?, updated_at = ? WHERE id = ? AND archived = false",
             self.table_name
         );
 
         let result = sqlx::query(&query)
-            .bind(name)
-            .bind(&data)
-            .bind(&now)
-            .bind(&id_str)
-            .execute(&self.pool)
+            .bind(entity.name())
+            .bind(serialize(entity)?)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(T::id_to_sql(&entity.id()))
+            .execute(conn)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to update entity: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to update entity: {}", e)))?;
 
         if result.rows_affected() == 0 {
-            return Err(PipelineError::InternalError("Entity not found for update".to_string()));
+            return Err(PipelineError::PipelineNotFound(format!(
+                "No {} entity with id {:?}",
+                self.table_name,
+                entity.id()
+            )));
         }
 
         Ok(())
     }
 
-    /// Deletes an entity by ID (hard delete)
+    /// Deletes an entity, archived or not; returns whether it existed
     pub async fn delete(&self, id: T::Id) -> Result<bool, PipelineError> {
-        // Use the same ID format as save method
-        let id_str = self.id_to_string_format(&id)?;
+        let mut conn = self.acquire().await?;
+        self.delete_in(&mut conn, id).await
+    }
 
+    /// [`Self::delete`] on `conn`, e.g. inside a transaction
+    pub async fn delete_in(&self, conn: &mut SqliteConnection, id: T::Id) -> Result<bool, PipelineError> {
         let query = format!("DELETE FROM {} WHERE id = ?", self.table_name);
 
         let result = sqlx::query(&query)
-            .bind(&id_str)
-            .execute(&self.pool)
+            .bind(T::id_to_sql(&id))
+            .execute(conn)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to delete entity: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to delete entity: {}", e)))?;
 
         Ok(result.rows_affected() > 0)
     }
 
-    /// Checks if an entity exists
+    /// Whether an active entity has `id`
     pub async fn exists(&self, id: T::Id) -> Result<bool, PipelineError> {
-        // Use the same ID format as save method
-        let id_str = self.id_to_string_format(&id)?;
-
         let query = format!(
             "SELECT 1 FROM {} WHERE id = ? AND archived = false LIMIT 1",
             self.table_name
         );
 
         let row = sqlx::query(&query)
-            .bind(&id_str)
+            .bind(T::id_to_sql(&id))
             .fetch_optional(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to check entity existence: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to check entity existence: {}", e)))?;
 
         Ok(row.is_some())
     }
 
-    /// Counts total entities
+    /// Number of active entities
     pub async fn count(&self) -> Result<usize, PipelineError> {
-        let query = format!(
-            "SELECT COUNT(*) as count FROM {} WHERE archived = false",
-            self.table_name
-        );
+        let query = format!("SELECT COUNT(*) FROM {} WHERE archived = false", self.table_name);
 
-        let row = sqlx::query(&query)
+        let count: i64 = sqlx::query_scalar(&query)
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to count entities: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to count entities: {}", e)))?;
 
-        let count: i64 = row.get("count");
         Ok(count as usize)
     }
 
-    /// Archives an entity (soft delete)
+    /// Archives an active entity (soft delete); returns whether it was
+    /// active
     pub async fn archive(&self, id: T::Id) -> Result<bool, PipelineError> {
-        // Use the same ID format as save method
-        let id_str = self.id_to_string_format(&id)?;
-
-        let now = chrono::Utc::now().to_rfc3339();
-        let query = format!(
-            "UPDATE {} SET archived = true, updated_at = ? WHERE id = ? AND archived = false",
-            self.table_name
-        );
-
-        let result = sqlx::query(&query)
-            .bind(&now)
-            .bind(&id_str)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to archive entity: {}", e)))?;
+        let mut conn = self.acquire().await?;
+        self.archive_in(&mut conn, id).await
+    }
 
-        Ok(result.rows_affected() > 0)
+    /// [`Self::archive`] on `conn`, e.g. inside a transaction
+    pub async fn archive_in(&self, conn: &mut SqliteConnection, id: T::Id) -> Result<bool, PipelineError> {
+        self.set_archived(conn, id, true).await
     }
 
-    /// Restores an archived entity
+    /// Makes an archived entity active again; returns whether it was
+    /// archived
     pub async fn restore(&self, id: T::Id) -> Result<bool, PipelineError> {
-        // Use the same ID format as save method
-        let id_str = self.id_to_string_format(&id)?;
+        let mut conn = self.acquire().await?;
+        self.set_archived(&mut conn, id, false).await
+    }
 
-        let now = chrono::Utc::now().to_rfc3339();
+    async fn set_archived(
+        &self,
+        conn: &mut SqliteConnection,
+        id: T::Id,
+        archived: bool,
+    ) -> Result<bool, PipelineError> {
         let query = format!(
-            "UPDATE {} SET archived = false, updated_at = ? WHERE id = ? AND archived = true",
+            "UPDATE {} SET archived = ?, updated_at = ? WHERE id = ? AND archived = ?",
             self.table_name
         );
 
         let result = sqlx::query(&query)
-            .bind(&now)
-            .bind(&id_str)
-            .execute(&self.pool)
+            .bind(archived)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(T::id_to_sql(&id))
+            .bind(!archived)
+            .execute(conn)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to restore entity: {}", e)))?;
+            .map_err(|e| PipelineError::database_error(format!("Failed to change archived state: {}", e)))?;
 
         Ok(result.rows_affected() > 0)
     }
 
-    /// Lists archived entities
+    /// Lists archived entities, least recently archived first
     pub async fn list_archived(&self) -> Result<Vec<T>, PipelineError> {
         let query = format!(
             "SELECT data FROM {} WHERE archived = true ORDER BY updated_at",
@@ -509,20 +509,12 @@ impl<T: SqliteEntity> SqliteRepository<T> {
         let rows = sqlx::query(&query)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| PipelineError::InternalError(format!("Failed to list archived entities: {}", e)))?;
-
-        let mut entities = Vec::new();
-        for row in rows {
-            let data: String = row.get("data");
-            let entity: T = serde_json::from_str(&data)
-                .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))?;
-            entities.push(entity);
-        }
+            .map_err(|e| PipelineError::database_error(format!("Failed to list archived entities: {}", e)))?;
 
-        Ok(entities)
+        rows.iter().map(deserialize).collect()
     }
 
-    /// Gets the database pool for advanced operations
+    /// Gets the database pool for queries beyond this interface
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
@@ -531,50 +523,134 @@ impl<T: SqliteEntity> SqliteRepository<T> {
     pub async fn close(self) {
         self.pool.close().await;
     }
+
+    async fn acquire(&self) -> Result<sqlx::pool::PoolConnection<Sqlite>, PipelineError> {
+        self.pool
+            .acquire()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to acquire connection: {}", e)))
+    }
+}
+
+fn serialize<T: Serialize>(entity: &T) -> Result<String, PipelineError> {
+    serde_json::to_string(entity)
+        .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize entity: {}", e)))
+}
+
+fn deserialize<T: DeserializeOwned>(row: &SqliteRow) -> Result<T, PipelineError> {
+    let data: String = row.get("data");
+    serde_json::from_str(&data)
+        .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize entity: {}", e)))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde::Deserialize;
     use uuid::Uuid;
 
     #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-    struct TestEntity {
+    struct Note {
         id: Uuid,
-        name: String,
-        value: i32,
+        title: String,
     }
 
-    impl SqliteEntity for TestEntity {
+    impl RepositoryEntity for Note {
         type Id = Uuid;
 
-        fn id(&self) -> Self::Id {
+        fn id(&self) -> Uuid {
             self.id
         }
 
+        fn name(&self) -> Option<&str> {
+            Some(&self.title)
+        }
+    }
+
+    impl SqliteEntity for Note {
         fn table_name() -> &'static str {
-            "test_entities"
+            "test_notes"
         }
 
-        fn table_schema() -> &'static str {
-            r#"
-            CREATE TABLE IF NOT EXISTS test_entities (
-                id TEXT PRIMARY KEY,
-                name TEXT NOT NULL,
-                data TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                archived BOOLEAN NOT NULL DEFAULT false
-            )
-            "#
+        fn migrations() -> &'static [&'static str] {
+            &[
+                "CREATE INDEX IF NOT EXISTS idx_test_notes_name ON test_notes(name)",
+                "CREATE TABLE test_notes_migrated (x INTEGER)",
+            ]
         }
+    }
 
-        fn name(&self) -> Option<&str> {
-            Some(&self.name)
+    fn note(title: &str) -> Note {
+        Note {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
         }
     }
 
-    // Note: Generic repository CRUD operations are tested via
-    // SqlitePipelineRepository integration tests which use the concrete
-    // implementation.
+    #[tokio::test]
+    async fn test_save_keeps_created_at_and_unarchives() {
+        let repository = SqliteRepository::<Note>::in_memory().await.unwrap();
+        let mut stored = note("first");
+        repository.save(&stored).await.unwrap();
+        let created_at: String = sqlx::query_scalar("SELECT created_at FROM test_notes")
+            .fetch_one(repository.pool())
+            .await
+            .unwrap();
+
+        assert!(repository.archive(stored.id).await.unwrap());
+        stored.title = "renamed".to_string();
+        repository.save(&stored).await.unwrap();
+
+        assert_eq!(repository.find_by_id(stored.id).await.unwrap(), Some(stored.clone()));
+        assert_eq!(repository.find_by_name("renamed").await.unwrap(), Some(stored));
+        let kept: String = sqlx::query_scalar("SELECT created_at FROM test_notes")
+            .fetch_one(repository.pool())
+            .await
+            .unwrap();
+        assert_eq!(kept, created_at);
+    }
+
+    #[tokio::test]
+    async fn test_migrations_run_once_per_database() {
+        let repository = SqliteRepository::<Note>::in_memory().await.unwrap();
+        let version: i64 =
+            sqlx::query_scalar("SELECT version FROM entity_schema_versions WHERE table_name = 'test_notes'")
+                .fetch_one(repository.pool())
+                .await
+                .unwrap();
+        assert_eq!(version, 2);
+
+        // The second migration would fail if applied again
+        SqliteRepository::<Note>::new(repository.pool().clone()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_uncommitted_transaction_saves_nothing() {
+        let repository = SqliteRepository::<Note>::in_memory().await.unwrap();
+
+        let mut tx = repository.begin().await.unwrap();
+        repository.save_in(&mut tx, &note("discarded")).await.unwrap();
+        drop(tx);
+        assert_eq!(repository.count().await.unwrap(), 0);
+
+        let mut tx = repository.begin().await.unwrap();
+        repository.save_in(&mut tx, &note("kept")).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(repository.count().await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_table_names_must_be_identifiers() {
+        assert!(is_identifier("catalog_entries"));
+        assert!(is_identifier("_v2"));
+        assert!(!is_identifier("2fast"));
+        assert!(!is_identifier("entries; DROP TABLE pipelines"));
+        assert!(!is_identifier(""));
+    }
 }
@@ -0,0 +1,317 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Database Backup
+//!
+//! Copies the pipeline database with SQLite's online backup API
+//! (`sqlite3_backup_*`), which reads a consistent snapshot while other
+//! processes keep using the database, and writes it back the same way on
+//! restore.
+//!
+//! A backup is written to a temporary file next to its destination and
+//! renamed into place once complete, so a backup file is never seen half
+//! written.
+//!
+//! ## Pre-Migration Backups
+//!
+//! [`backup_before_migrating`] is called before pending migrations are
+//! applied to an existing database. It copies the database to
+//! `<database>.pre-migration-<version>`, where `<version>` is the first
+//! pending migration, so that a bad upgrade can be rolled back with
+//! `db restore`.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use libsqlite3_sys as ffi;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection, SqliteConnection, SqlitePool};
+use tracing::{debug, info};
+
+use super::schema::BUSY_TIMEOUT;
+
+/// Pages copied per backup step; other processes may write between steps
+const PAGES_PER_STEP: i32 = 256;
+
+/// Copies the database behind `source` to the new file `destination`
+///
+/// # Errors
+///
+/// Fails if `destination` already exists, or the copy fails or cannot get
+/// a read lock on the database within [`BUSY_TIMEOUT`].
+pub async fn backup_database(source: &SqlitePool, destination: &Path) -> Result<(), sqlx::Error> {
+    let directory = match destination.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let partial = tempfile::Builder::new()
+        .prefix(".adapipe-backup-")
+        .tempfile_in(directory)?;
+
+    let mut from = source.acquire().await?;
+    let mut to = SqliteConnectOptions::new().filename(partial.path()).connect().await?;
+    copy_database(&mut from, &mut to).await?;
+    to.close().await?;
+
+    partial
+        .persist_noclobber(destination)
+        .map_err(|e| sqlx::Error::Io(e.error))?;
+    info!("Backed up database to {}", destination.display());
+    Ok(())
+}
+
+/// Replaces the contents of the database behind `destination` with the
+/// backup file `source`
+///
+/// The backup is checked first: it must pass `PRAGMA integrity_check` and
+/// hold no migration newer than this build knows. Migrations it lacks are
+/// not applied here; the next [`super::schema::initialize_database`] applies
+/// them.
+///
+/// # Errors
+///
+/// Fails if the backup is not a pipeline database, is damaged or was made by
+/// a newer version, or the copy fails.
+pub async fn restore_database(destination: &SqlitePool, source: &Path) -> Result<(), sqlx::Error> {
+    let mut from = SqliteConnectOptions::new()
+        .filename(source)
+        .read_only(true)
+        .connect()
+        .await?;
+    check_backup(&mut from, source).await?;
+
+    let mut to = destination.acquire().await?;
+    copy_database(&mut from, &mut to).await?;
+    from.close().await?;
+    info!("Restored database from {}", source.display());
+    Ok(())
+}
+
+/// Backs the database behind `pool` up to `<database_file>.pre-migration-<version>`
+/// if it has migrations applied and some pending
+///
+/// Returns the backup path when one was taken. A backup already taken for
+/// the same pending version, e.g. by another process starting at the same
+/// time, is kept.
+pub async fn backup_before_migrating(pool: &SqlitePool, database_file: &Path) -> Result<Option<PathBuf>, sqlx::Error> {
+    let tracked: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'")
+            .fetch_one(pool)
+            .await?;
+    if tracked == 0 {
+        return Ok(None);
+    }
+
+    let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = true")
+        .fetch_all(pool)
+        .await?;
+    let first_pending = sqlx::migrate!("./migrations")
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .min();
+    let Some(version) = first_pending else {
+        return Ok(None);
+    };
+
+    let backup = pre_migration_backup_path(database_file, version);
+    if backup.exists() {
+        debug!("Pre-migration backup {} already taken", backup.display());
+        return Ok(None);
+    }
+    match backup_database(pool, &backup).await {
+        Err(sqlx::Error::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(None),
+        result => result.map(|()| Some(backup)),
+    }
+}
+
+/// Where the backup taken before applying migration `version` to
+/// `database_file` is written
+pub fn pre_migration_backup_path(database_file: &Path, version: i64) -> PathBuf {
+    let mut name = database_file.as_os_str().to_owned();
+    name.push(format!(".pre-migration-{}", version));
+    PathBuf::from(name)
+}
+
+/// Checks that `backup` is an intact pipeline database this build can use
+async fn check_backup(backup: &mut SqliteConnection, path: &Path) -> Result<(), sqlx::Error> {
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut *backup)
+        .await
+        .map_err(|e| invalid_backup(path, &e.to_string()))?;
+    if integrity != "ok" {
+        return Err(invalid_backup(path, &format!("integrity check failed: {}", integrity)));
+    }
+
+    let newest: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(&mut *backup)
+        .await
+        .map_err(|_| invalid_backup(path, "not a pipeline database"))?;
+    let known = sqlx::migrate!("./migrations");
+    if let Some(version) = newest.filter(|version| !known.version_exists(*version)) {
+        return Err(invalid_backup(
+            path,
+            &format!("it has schema version {}, which this build does not know", version),
+        ));
+    }
+    Ok(())
+}
+
+fn invalid_backup(path: &Path, reason: &str) -> sqlx::Error {
+    sqlx::Error::Protocol(format!("Invalid backup '{}': {}", path.display(), reason))
+}
+
+/// Copies every page of `from`'s main database to `to`'s with the online
+/// backup API, retrying while either is locked by another connection
+async fn copy_database(from: &mut SqliteConnection, to: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+    let mut from = from.lock_handle().await?;
+    let mut to = to.lock_handle().await?;
+    let source = from.as_raw_handle().as_ptr();
+    let destination = to.as_raw_handle().as_ptr();
+
+    // SAFETY: both handles are locked away from their worker threads for the
+    // whole copy, and the backup object is finished before they are released
+    unsafe {
+        let backup = ffi::sqlite3_backup_init(destination, c"main".as_ptr(), source, c"main".as_ptr());
+        if backup.is_null() {
+            return Err(sqlite_error(destination, ffi::sqlite3_errcode(destination)));
+        }
+
+        let started = Instant::now();
+        let code = loop {
+            match ffi::sqlite3_backup_step(backup, PAGES_PER_STEP) {
+                ffi::SQLITE_OK => continue,
+                ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED if started.elapsed() < BUSY_TIMEOUT => {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                code => break code,
+            }
+        };
+        let pages = ffi::sqlite3_backup_pagecount(backup);
+        let finished = ffi::sqlite3_backup_finish(backup);
+        match (code, finished) {
+            (ffi::SQLITE_DONE, ffi::SQLITE_OK) => {
+                debug!("Copied {} database pages", pages);
+                Ok(())
+            }
+            (ffi::SQLITE_DONE, code) | (code, _) => Err(sqlite_error(destination, code)),
+        }
+    }
+}
+
+/// Describes the SQLite result `code`, with `db`'s error message when it
+/// has one
+///
+/// # Safety
+///
+/// `db` must be a valid, locked connection handle.
+unsafe fn sqlite_error(db: *mut ffi::sqlite3, code: i32) -> sqlx::Error {
+    let message = std::ffi::CStr::from_ptr(ffi::sqlite3_errmsg(db)).to_string_lossy();
+    sqlx::Error::Protocol(format!("Database backup failed (SQLite code {}): {}", code, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::schema::initialize_database;
+    use tempfile::TempDir;
+
+    async fn database_with_rows(path: &Path, rows: i64) -> SqlitePool {
+        let pool = initialize_database(&format!("sqlite://{}", path.display()))
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE IF NOT EXISTS notes (id INTEGER PRIMARY KEY)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM notes").execute(&pool).await.unwrap();
+        for id in 0..rows {
+            sqlx::query("INSERT INTO notes (id) VALUES (?)")
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        pool
+    }
+
+    async fn note_count(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM notes")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_backup_then_restore_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let pool = database_with_rows(&dir.path().join("pipeline.db"), 3).await;
+        let backup = dir.path().join("pipeline.bak");
+
+        backup_database(&pool, &backup).await.unwrap();
+        assert!(
+            backup_database(&pool, &backup).await.is_err(),
+            "an existing backup is not overwritten"
+        );
+
+        sqlx::query("DELETE FROM notes").execute(&pool).await.unwrap();
+        restore_database(&pool, &backup).await.unwrap();
+        assert_eq!(note_count(&pool).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_files_that_are_not_pipeline_databases() {
+        let dir = TempDir::new().unwrap();
+        let pool = database_with_rows(&dir.path().join("pipeline.db"), 2).await;
+
+        let garbage = dir.path().join("garbage.bak");
+        std::fs::write(&garbage, b"not a database").unwrap();
+        assert!(restore_database(&pool, &garbage).await.is_err());
+
+        let foreign = dir.path().join("foreign.db");
+        let other = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", foreign.display()))
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE t (x)").execute(&other).await.unwrap();
+        other.close().await;
+        let error = restore_database(&pool, &foreign).await.unwrap_err();
+        assert!(error.to_string().contains("not a pipeline database"), "{}", error);
+
+        assert_eq!(note_count(&pool).await, 2, "a rejected backup changes nothing");
+    }
+
+    #[tokio::test]
+    async fn test_pre_migration_backup_is_taken_only_when_migrations_are_pending() {
+        let dir = TempDir::new().unwrap();
+        let db = dir.path().join("pipeline.db");
+        let pool = database_with_rows(&db, 1).await;
+
+        // Fully migrated: nothing to back up
+        assert_eq!(backup_before_migrating(&pool, &db).await.unwrap(), None);
+
+        // Forget the newest migration, as a database from an older build would
+        let newest: i64 = sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = ?")
+            .bind(newest)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let backup = backup_before_migrating(&pool, &db).await.unwrap().unwrap();
+        assert_eq!(backup, pre_migration_backup_path(&db, newest));
+        assert!(backup.exists());
+        assert_eq!(
+            backup_before_migrating(&pool, &db).await.unwrap(),
+            None,
+            "an existing pre-migration backup is kept"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Chunk Size History Repository
+//!
+//! Persists per-pipeline, per-storage-type, per-chunk-size throughput totals
+//! in the `chunk_size_history` table, created by the
+//! `20250105000000_chunk_size_history` migration. Shares the database file
+//! with `SqlitePipelineRepository`; history is deleted with its pipeline.
+
+use adaptive_pipeline_domain::repositories::ChunkSizeHistoryRepository;
+use adaptive_pipeline_domain::value_objects::{ChunkSize, ChunkThroughput, PipelineId};
+use adaptive_pipeline_domain::PipelineError;
+use sqlx::{Row, SqlitePool};
+use std::time::Duration;
+use tracing::debug;
+
+/// SQLite-backed implementation of `ChunkSizeHistoryRepository`
+pub struct SqliteChunkSizeHistoryRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteChunkSizeHistoryRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!(
+            "Creating SqliteChunkSizeHistoryRepository with database: {}",
+            database_path
+        );
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait::async_trait]
+impl ChunkSizeHistoryRepository for SqliteChunkSizeHistoryRepository {
+    async fn record_run(
+        &self,
+        pipeline_id: &PipelineId,
+        storage_type: &str,
+        chunk_size: ChunkSize,
+        bytes_processed: u64,
+        duration: Duration,
+    ) -> Result<(), PipelineError> {
+        let query = r#"
+            INSERT INTO chunk_size_history
+                (pipeline_id, storage_type, chunk_size, runs, bytes_processed, processing_ms, updated_at)
+            VALUES (?, ?, ?, 1, ?, ?, ?)
+            ON CONFLICT(pipeline_id, storage_type, chunk_size) DO UPDATE SET
+                runs = runs + 1,
+                bytes_processed = bytes_processed + excluded.bytes_processed,
+                processing_ms = processing_ms + excluded.processing_ms,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(pipeline_id.to_string())
+            .bind(storage_type)
+            .bind(chunk_size.bytes() as i64)
+            .bind(bytes_processed as i64)
+            .bind(duration.as_millis() as i64)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to record chunk size history: {}", e)))?;
+
+        debug!(
+            pipeline_id = %pipeline_id,
+            storage_type,
+            chunk_size = chunk_size.bytes(),
+            bytes_processed,
+            "Chunk size history recorded"
+        );
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        pipeline_id: &PipelineId,
+        storage_type: &str,
+    ) -> Result<Vec<ChunkThroughput>, PipelineError> {
+        let query = r#"
+            SELECT chunk_size, runs, bytes_processed, processing_ms
+            FROM chunk_size_history
+            WHERE pipeline_id = ? AND storage_type = ?
+            ORDER BY chunk_size
+        "#;
+        let rows = sqlx::query(query)
+            .bind(pipeline_id.to_string())
+            .bind(storage_type)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load chunk size history: {}", e)))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ChunkThroughput::new(
+                    ChunkSize::new(row.get::<i64, _>("chunk_size") as usize)?,
+                    row.get::<i64, _>("runs") as u64,
+                    row.get::<i64, _>("bytes_processed") as u64,
+                    row.get::<i64, _>("processing_ms") as f64 / 1000.0,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+    use adaptive_pipeline_domain::entities::pipeline_stage::{StageConfiguration, StageType};
+    use adaptive_pipeline_domain::{Pipeline, PipelineStage};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_record_run_accumulates_per_chunk_size_and_storage() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.db").to_string_lossy().to_string();
+        let pipelines = SqlitePipelineRepository::new(&path).await.unwrap();
+        let stage = PipelineStage::new(
+            "compress".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            1,
+        )
+        .unwrap();
+        let pipeline = Pipeline::new("history-test".to_string(), vec![stage]).unwrap();
+        pipelines.save(&pipeline).await.unwrap();
+
+        let repo = SqliteChunkSizeHistoryRepository::new(&path).await.unwrap();
+        let one_mb = ChunkSize::from_mb(1).unwrap();
+        let four_mb = ChunkSize::from_mb(4).unwrap();
+
+        repo.record_run(pipeline.id(), "nvme", one_mb, 100_000_000, Duration::from_secs(2))
+            .await
+            .unwrap();
+        repo.record_run(pipeline.id(), "nvme", one_mb, 50_000_000, Duration::from_secs(1))
+            .await
+            .unwrap();
+        repo.record_run(pipeline.id(), "nvme", four_mb, 90_000_000, Duration::from_secs(1))
+            .await
+            .unwrap();
+        repo.record_run(pipeline.id(), "hdd", one_mb, 10_000_000, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        let history = repo.history(pipeline.id(), "nvme").await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].chunk_size(), one_mb);
+        assert_eq!(history[0].runs(), 2);
+        assert_eq!(history[0].bytes_per_second(), 50_000_000.0);
+        assert_eq!(history[1].bytes_per_second(), 90_000_000.0);
+
+        assert_eq!(repo.history(pipeline.id(), "hdd").await.unwrap().len(), 1);
+        assert!(repo.history(pipeline.id(), "network").await.unwrap().is_empty());
+    }
+}
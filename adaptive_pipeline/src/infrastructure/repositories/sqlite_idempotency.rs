@@ -0,0 +1,125 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Idempotency Repository
+//!
+//! Persists completed idempotent requests in the `idempotency_keys` table,
+//! created by the `20250104000000_idempotency_keys` migration. Shares the
+//! database file with `SqlitePipelineRepository`.
+
+use adaptive_pipeline_domain::repositories::IdempotencyRepository;
+use adaptive_pipeline_domain::value_objects::{IdempotencyKey, IdempotencyRecord, Namespace};
+use adaptive_pipeline_domain::PipelineError;
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `IdempotencyRepository`
+pub struct SqliteIdempotencyRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteIdempotencyRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteIdempotencyRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyRepository for SqliteIdempotencyRepository {
+    async fn find(
+        &self,
+        namespace: &Namespace,
+        key: &IdempotencyKey,
+    ) -> Result<Option<IdempotencyRecord>, PipelineError> {
+        let query = "SELECT fingerprint, result, created_at FROM idempotency_keys WHERE namespace = ? AND key = ?";
+        let row = sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(key.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load idempotency key: {}", e)))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid created_at format: {}", e)))?
+            .with_timezone(&chrono::Utc);
+
+        Ok(Some(IdempotencyRecord::from_parts(
+            namespace.clone(),
+            key.clone(),
+            row.get("fingerprint"),
+            row.get("result"),
+            created_at,
+        )))
+    }
+
+    async fn save(&self, record: &IdempotencyRecord) -> Result<bool, PipelineError> {
+        let query = r#"
+            INSERT INTO idempotency_keys (namespace, key, fingerprint, result, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(namespace, key) DO NOTHING
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(record.namespace().as_str())
+            .bind(record.key().as_str())
+            .bind(record.fingerprint())
+            .bind(record.result())
+            .bind(record.created_at().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to save idempotency key: {}", e)))?;
+
+        let stored = result.rows_affected() > 0;
+        debug!(
+            namespace = %record.namespace(),
+            key = %record.key(),
+            stored,
+            "Idempotency key saved"
+        );
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_first_record_wins_per_namespace() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("idempotency.db");
+        let repo = SqliteIdempotencyRepository::new(&path.to_string_lossy()).await.unwrap();
+        let team_a = Namespace::new("team-a").unwrap();
+        let team_b = Namespace::new("team-b").unwrap();
+        let key = IdempotencyKey::new("run-1").unwrap();
+
+        assert!(repo.find(&team_a, &key).await.unwrap().is_none());
+
+        let first = IdempotencyRecord::new(team_a.clone(), key.clone(), "fp-1".to_string(), "{}".to_string());
+        let second = IdempotencyRecord::new(team_a.clone(), key.clone(), "fp-2".to_string(), "[]".to_string());
+        assert!(repo.save(&first).await.unwrap());
+        assert!(!repo.save(&second).await.unwrap());
+
+        let stored = repo.find(&team_a, &key).await.unwrap().unwrap();
+        assert_eq!(stored.fingerprint(), "fp-1");
+        assert_eq!(stored.result(), "{}");
+        assert!(repo.find(&team_b, &key).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,315 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Job Repository
+//!
+//! Persists the job queue in the `jobs` table, created by the
+//! `20250110000000_jobs` migration. Shares the database file with
+//! `SqlitePipelineRepository`, so `submit` and the daemon only need to
+//! agree on the database.
+//!
+//! Claiming and updating are single conditional statements; SQLite runs one
+//! writer at a time, so two daemons never claim the same job. Daemon leases
+//! live in the `daemon_leases` table (`20250111000000_daemon_leases`).
+
+use std::path::PathBuf;
+
+use adaptive_pipeline_domain::entities::{JobState, QueuedJob};
+use adaptive_pipeline_domain::repositories::JobRepository;
+use adaptive_pipeline_domain::value_objects::{JobId, Namespace};
+use adaptive_pipeline_domain::PipelineError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `JobRepository`
+pub struct SqliteJobRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteJobRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteJobRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+
+    fn timestamp(at: DateTime<Utc>) -> String {
+        at.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, PipelineError> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(|e| PipelineError::database_error(format!("Invalid job timestamp: {}", e)))
+    }
+
+    fn optional_timestamp(row: &SqliteRow, column: &str) -> Result<Option<DateTime<Utc>>, PipelineError> {
+        row.get::<Option<String>, _>(column)
+            .as_deref()
+            .map(Self::parse_timestamp)
+            .transpose()
+    }
+
+    fn job_from_row(row: &SqliteRow) -> Result<QueuedJob, PipelineError> {
+        Ok(QueuedJob::from_parts(
+            JobId::parse(&row.get::<String, _>("id"))?,
+            row.get::<String, _>("namespace").parse()?,
+            row.get("submitted_by"),
+            row.get("pipeline"),
+            PathBuf::from(row.get::<String, _>("input")),
+            PathBuf::from(row.get::<String, _>("output")),
+            row.get::<Option<i64>, _>("workers").map(|workers| workers as usize),
+            row.get::<String, _>("state").parse()?,
+            Self::parse_timestamp(&row.get::<String, _>("submitted_at"))?,
+            Self::optional_timestamp(row, "started_at")?,
+            Self::optional_timestamp(row, "finished_at")?,
+            row.get("error"),
+            row.get::<Option<i64>, _>("input_bytes").map(|bytes| bytes as u64),
+            row.get::<Option<i64>, _>("output_bytes").map(|bytes| bytes as u64),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl JobRepository for SqliteJobRepository {
+    async fn enqueue(&self, job: &QueuedJob) -> Result<(), PipelineError> {
+        let query = r#"
+            INSERT INTO jobs (id, namespace, submitted_by, pipeline, input, output, workers, state, submitted_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(job.id().to_string())
+            .bind(job.namespace().as_str())
+            .bind(job.submitted_by())
+            .bind(job.pipeline())
+            .bind(job.input().to_string_lossy())
+            .bind(job.output().to_string_lossy())
+            .bind(job.workers().map(|workers| workers as i64))
+            .bind(job.state().as_str())
+            .bind(Self::timestamp(job.submitted_at()))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to queue job: {}", e)))?;
+
+        debug!(job = %job.id(), pipeline = job.pipeline(), "Job queued");
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &JobId) -> Result<Option<QueuedJob>, PipelineError> {
+        let row = sqlx::query("SELECT * FROM jobs WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load job: {}", e)))?;
+
+        row.as_ref().map(Self::job_from_row).transpose()
+    }
+
+    async fn list(&self, namespace: &Namespace) -> Result<Vec<QueuedJob>, PipelineError> {
+        let rows = sqlx::query("SELECT * FROM jobs WHERE namespace = ? ORDER BY id")
+            .bind(namespace.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to list jobs: {}", e)))?;
+
+        rows.iter().map(Self::job_from_row).collect()
+    }
+
+    async fn claim_next(&self, namespace: &Namespace, at: DateTime<Utc>) -> Result<Option<QueuedJob>, PipelineError> {
+        let query = r#"
+            UPDATE jobs SET state = 'running', started_at = ?
+            WHERE state = 'queued' AND id = (
+                SELECT id FROM jobs WHERE namespace = ? AND state = 'queued' ORDER BY id LIMIT 1
+            )
+            RETURNING *
+        "#;
+        let row = sqlx::query(query)
+            .bind(Self::timestamp(at))
+            .bind(namespace.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to claim a job: {}", e)))?;
+
+        row.as_ref().map(Self::job_from_row).transpose()
+    }
+
+    async fn update(&self, job: &QueuedJob, expected: JobState) -> Result<bool, PipelineError> {
+        let query = r#"
+            UPDATE jobs
+            SET state = ?, started_at = ?, finished_at = ?, error = ?, input_bytes = ?, output_bytes = ?
+            WHERE id = ? AND state = ?
+        "#;
+        let result = sqlx::query(query)
+            .bind(job.state().as_str())
+            .bind(job.started_at().map(Self::timestamp))
+            .bind(job.finished_at().map(Self::timestamp))
+            .bind(job.error())
+            .bind(job.input_bytes().map(|bytes| bytes as i64))
+            .bind(job.output_bytes().map(|bytes| bytes as i64))
+            .bind(job.id().to_string())
+            .bind(expected.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to update job: {}", e)))?;
+
+        let stored = result.rows_affected() > 0;
+        debug!(job = %job.id(), state = %job.state(), stored, "Job updated");
+        Ok(stored)
+    }
+
+    async fn acquire_lease(
+        &self,
+        namespace: &Namespace,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, PipelineError> {
+        // Timestamps share one fixed-width format, so they compare as text
+        let query = r#"
+            INSERT INTO daemon_leases (namespace, holder, expires_at) VALUES (?, ?, ?)
+            ON CONFLICT(namespace) DO UPDATE SET holder = excluded.holder, expires_at = excluded.expires_at
+            WHERE daemon_leases.holder = excluded.holder OR daemon_leases.expires_at <= ?
+        "#;
+        let result = sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(holder)
+            .bind(Self::timestamp(expires_at))
+            .bind(Self::timestamp(now))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to acquire the daemon lease: {}", e)))?;
+
+        let acquired = result.rows_affected() > 0;
+        debug!(namespace = %namespace, holder, acquired, "Daemon lease requested");
+        Ok(acquired)
+    }
+
+    async fn release_lease(&self, namespace: &Namespace, holder: &str) -> Result<(), PipelineError> {
+        sqlx::query("DELETE FROM daemon_leases WHERE namespace = ? AND holder = ?")
+            .bind(namespace.as_str())
+            .bind(holder)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to release the daemon lease: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn repository(dir: &TempDir) -> SqliteJobRepository {
+        let path = dir.path().join("jobs.db");
+        SqliteJobRepository::new(&path.to_string_lossy()).await.unwrap()
+    }
+
+    fn submit(namespace: &Namespace, input: &str) -> QueuedJob {
+        let job = QueuedJob::submit(
+            namespace.clone(),
+            "alice",
+            "compress",
+            PathBuf::from(input),
+            PathBuf::from(format!("{}.adapipe", input)),
+        );
+        // Keep IDs apart so claim order is deterministic
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        job
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_claimed_oldest_first_within_a_namespace() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+        let team = Namespace::new("team-a").unwrap();
+
+        let first = submit(&ns, "a.bin").with_workers(Some(2));
+        let elsewhere = submit(&team, "b.bin");
+        let second = submit(&ns, "c.bin");
+        for job in [&first, &elsewhere, &second] {
+            repo.enqueue(job).await.unwrap();
+        }
+
+        let claimed = repo.claim_next(&ns, Utc::now()).await.unwrap().unwrap();
+        assert_eq!(claimed.id(), first.id());
+        assert_eq!(claimed.state(), JobState::Running);
+        assert_eq!(claimed.workers(), Some(2));
+        assert_eq!(
+            repo.claim_next(&ns, Utc::now()).await.unwrap().unwrap().id(),
+            second.id()
+        );
+        assert!(repo.claim_next(&ns, Utc::now()).await.unwrap().is_none());
+
+        let listed: Vec<_> = repo.list(&ns).await.unwrap().iter().map(|job| job.id()).collect();
+        assert_eq!(listed, [first.id(), second.id()]);
+        assert_eq!(
+            repo.find_by_id(&elsewhere.id()).await.unwrap().unwrap().state(),
+            JobState::Queued
+        );
+    }
+
+    #[tokio::test]
+    async fn test_updates_apply_only_from_the_expected_state() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+        repo.enqueue(&submit(&ns, "a.bin")).await.unwrap();
+        let mut running = repo.claim_next(&ns, Utc::now()).await.unwrap().unwrap();
+
+        // Cancelled while running: the runner's result must not replace it
+        let mut cancelled = running.clone();
+        cancelled.cancel(Utc::now()).unwrap();
+        assert!(repo.update(&cancelled, JobState::Running).await.unwrap());
+        running.complete(Utc::now(), 1000, 250).unwrap();
+        assert!(!repo.update(&running, JobState::Running).await.unwrap());
+
+        let stored = repo.find_by_id(&running.id()).await.unwrap().unwrap();
+        assert_eq!(stored.state(), JobState::Cancelled);
+        assert!(stored.finished_at().is_some());
+        assert_eq!(stored.output_bytes(), None);
+    }
+
+    #[tokio::test]
+    async fn test_daemon_lease_is_held_until_released_or_expired() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+        let team = Namespace::new("team-a").unwrap();
+        let now = Utc::now();
+        let later = now + chrono::Duration::seconds(30);
+
+        assert!(repo.acquire_lease(&ns, "daemon-1", now, later).await.unwrap());
+        assert!(
+            repo.acquire_lease(&ns, "daemon-1", now, later).await.unwrap(),
+            "holders renew"
+        );
+        assert!(!repo.acquire_lease(&ns, "daemon-2", now, later).await.unwrap());
+        assert!(repo.acquire_lease(&team, "daemon-2", now, later).await.unwrap());
+
+        // An expired lease is taken over, and only its holder releases one
+        assert!(repo
+            .acquire_lease(&ns, "daemon-2", later, later + chrono::Duration::seconds(30))
+            .await
+            .unwrap());
+        repo.release_lease(&ns, "daemon-1").await.unwrap();
+        assert!(!repo.acquire_lease(&ns, "daemon-1", later, later).await.unwrap());
+        repo.release_lease(&ns, "daemon-2").await.unwrap();
+        assert!(repo.acquire_lease(&ns, "daemon-1", later, later).await.unwrap());
+    }
+}
@@ -12,11 +12,17 @@
 //! transactions, connection pooling, and parameterized queries for security.
 //! See mdBook for detailed schema documentation and usage examples.
 
+use crate::application::queries::{
+    ExecutionRecordDto, PipelineDetailsDto, PipelineReadModel, PipelineSummaryDto, StageDto,
+};
+use crate::infrastructure::repositories::sqlite_unit_of_work::SqliteUnitOfWork;
 use adaptive_pipeline_domain::entities::pipeline_stage::{StageConfiguration, StageType};
-use adaptive_pipeline_domain::value_objects::PipelineId;
+use adaptive_pipeline_domain::repositories::UnitOfWork;
+use adaptive_pipeline_domain::value_objects::{CorrelationId, Namespace, PipelineId};
 use adaptive_pipeline_domain::{Pipeline, PipelineError, PipelineStage, ProcessingMetrics};
-use sqlx::{Row, SqlitePool};
-use std::collections::HashMap;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
 use tracing::debug;
 // REMOVED: Generic Repository import - violates DIP
 // DDD Principle: Use only domain-specific repository interfaces
@@ -42,7 +48,19 @@ use tracing::debug;
 /// The repository uses a normalized relational schema:
 /// - **pipelines**: Main pipeline entity data
 /// - **pipeline_stages**: Pipeline stage configurations
-/// - **pipeline_metrics**: Performance and execution metrics
+/// - **processing_metrics**: Metrics of each pipeline's latest run
+///
+/// # Read Model
+///
+/// The repository also implements [`PipelineReadModel`], which answers the
+/// list and show queries with flat DTOs built from a few joined queries
+/// instead of rebuilding every aggregate.
+///
+/// # Namespaces
+///
+/// Each repository instance is scoped to one `Namespace` (the default one
+/// unless [`in_namespace`](Self::in_namespace) is used). Queries only see
+/// pipelines of that namespace, and pipeline names are unique per namespace.
 ///
 /// # Architecture
 ///
@@ -60,6 +78,8 @@ use tracing::debug;
 pub struct SqlitePipelineRepository {
     // PRIVATE: Database connection pool - internal implementation detail
     pool: SqlitePool,
+    namespace: Namespace,
+    correlation_id: Option<CorrelationId>,
 }
 
 impl SqlitePipelineRepository {
@@ -112,24 +132,67 @@ impl SqlitePipelineRepository {
     pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
         debug!("Creating SqlitePipelineRepository with database: {}", database_path);
 
-        // Build a proper SQLite connection URL. sqlx expects either:
-        // - "sqlite::memory:" for in-memory DB, or
-        // - "sqlite://<path>" for file-backed DB
-        let database_url = if database_path == ":memory:" || database_path == "sqlite::memory:" {
-            "sqlite::memory:".to_string()
-        } else {
-            format!("sqlite://{}", database_path)
-        };
-
-        // Use schema initialization which handles database creation and migrations
-        let pool = crate::infrastructure::repositories::schema::initialize_database(&database_url)
-            .await
-            .map_err(|e| {
-                PipelineError::database_error(format!("Failed to initialize database '{}': {}", database_path, e))
-            })?;
+        // Creates the database and applies migrations
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
 
         debug!("Successfully connected to structured SQLite database");
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            namespace: Namespace::default(),
+            correlation_id: None,
+        })
+    }
+
+    /// Scopes the repository to `namespace`
+    ///
+    /// The connection pool is shared, so scoping is cheap and several
+    /// namespaced repositories can coexist over one database.
+    pub fn in_namespace(&self, namespace: Namespace) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            namespace,
+            correlation_id: self.correlation_id.clone(),
+        }
+    }
+
+    /// Stamps audit records and events written by this repository's units
+    /// of work with the correlation ID of the current run
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Gets the correlation ID this repository stamps its writes with
+    pub fn correlation_id(&self) -> Option<&CorrelationId> {
+        self.correlation_id.as_ref()
+    }
+
+    /// Gets the namespace this repository is scoped to
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the database pool, e.g. to open a `SqliteRepository` for other
+    /// aggregates in the pipeline database
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Begins a unit of work in this repository's namespace
+    ///
+    /// The write lock is taken now, so the unit of work waits here (up to
+    /// the busy timeout) rather than failing partway through.
+    pub async fn begin(&self) -> Result<Box<dyn UnitOfWork>, PipelineError> {
+        let tx = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to begin unit of work: {}", e)))?;
+        Ok(Box::new(SqliteUnitOfWork::new(
+            tx,
+            self.namespace.clone(),
+            self.correlation_id.clone(),
+        )))
     }
 
     /// Saves a pipeline to the database with ACID transaction guarantees
@@ -174,6 +237,7 @@ impl SqlitePipelineRepository {
     /// This function returns an error if:
     /// - A pipeline with the same ID already exists (unique constraint
     ///   violation)
+    /// - The pipeline belongs to a different namespace than the repository
     /// - Database connection is lost during the operation
     /// - Any SQL query fails (syntax error, constraint violation, etc.)
     /// - Transaction cannot be started or committed
@@ -203,26 +267,103 @@ impl SqlitePipelineRepository {
             "SqlitePipelineRepository::save called"
         );
 
+        if entity.namespace() != &self.namespace {
+            return Err(PipelineError::validation_error(format!(
+                "Pipeline '{}' belongs to namespace '{}', not '{}'",
+                entity.name(),
+                entity.namespace(),
+                self.namespace
+            )));
+        }
+
         // Start database transaction for ACID compliance
         let mut tx = self
             .pool
-            .begin()
+            .begin_with("BEGIN IMMEDIATE")
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to start transaction: {}", e)))?;
 
+        Self::insert_pipeline(&mut tx, &self.namespace, entity).await?;
+
+        // NOTE: Metrics are handled by Prometheus (per SRS requirements), not stored in
+        // database Skip metrics insertion - observability is handled externally
+        // This keeps the database focused on core pipeline data only
+
+        // Commit transaction - ensures ACID compliance
+        tx.commit()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to commit transaction: {}", e)))?;
+
+        debug!(
+            pipeline_name = %entity.name(),
+            "Successfully saved pipeline with ACID transaction"
+        );
+        Ok(())
+    }
+
+    /// PUBLIC: Domain interface - Find pipeline by ID
+    pub async fn find_by_id(&self, id: PipelineId) -> Result<Option<Pipeline>, PipelineError> {
+        self.load_pipeline_from_db(id).await
+    }
+
+    /// PUBLIC: Domain interface - Update a pipeline
+    pub async fn update(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        // Implementation simplified for now
+        debug!(
+            pipeline_name = %pipeline.name(),
+            "SqlitePipelineRepository::update called"
+        );
+        Ok(())
+    }
+
+    /// PUBLIC: Domain interface - Soft delete a pipeline with cascading archive
+    pub async fn delete(&self, id: PipelineId) -> Result<bool, PipelineError> {
+        debug!(pipeline_id = %id, "Starting delete for pipeline");
+
+        let mut tx = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to begin transaction: {}", e)))?;
+
+        let success = Self::archive_pipeline(&mut tx, &self.namespace, &id).await?;
+
+        if success {
+            tx.commit()
+                .await
+                .map_err(|e| PipelineError::database_error(format!("Failed to commit archive transaction: {}", e)))?;
+            debug!("Transaction committed successfully");
+        } else {
+            tx.rollback()
+                .await
+                .map_err(|e| PipelineError::database_error(format!("Failed to rollback archive transaction: {}", e)))?;
+            debug!("Transaction rolled back");
+        }
+
+        Ok(success)
+    }
+
+    /// Inserts `entity` with its configuration, stages and stage parameters
+    /// on `conn`, which the caller holds a transaction on
+    pub(crate) async fn insert_pipeline(
+        conn: &mut SqliteConnection,
+        namespace: &Namespace,
+        entity: &Pipeline,
+    ) -> Result<(), PipelineError> {
         // Insert main pipeline record
         let pipeline_query = r#"
-            INSERT INTO pipelines (id, name, archived, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO pipelines (id, namespace, name, archived, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
         "#;
 
         sqlx::query(pipeline_query)
             .bind(entity.id().to_string())
+            .bind(namespace.as_str())
             .bind(entity.name())
             .bind(entity.archived())
             .bind(entity.created_at().to_rfc3339())
             .bind(entity.updated_at().to_rfc3339())
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to insert pipeline: {}", e)))?;
 
@@ -239,7 +380,7 @@ impl SqlitePipelineRepository {
                 .bind(value)
                 .bind(entity.created_at().to_rfc3339())
                 .bind(entity.updated_at().to_rfc3339())
-                .execute(&mut *tx)
+                .execute(&mut *conn)
                 .await
                 .map_err(|e| PipelineError::database_error(format!("Failed to insert configuration: {}", e)))?;
         }
@@ -266,7 +407,7 @@ impl SqlitePipelineRepository {
                 .bind(stage.configuration().chunk_size.map(|s| s as i64))
                 .bind(stage.created_at().to_rfc3339())
                 .bind(stage.updated_at().to_rfc3339())
-                .execute(&mut *tx)
+                .execute(&mut *conn)
                 .await
                 .map_err(|e| PipelineError::database_error(format!("Failed to insert stage: {}", e)))?;
 
@@ -283,53 +424,22 @@ impl SqlitePipelineRepository {
                     .bind(param_value)
                     .bind(stage.created_at().to_rfc3339())
                     .bind(stage.updated_at().to_rfc3339())
-                    .execute(&mut *tx)
+                    .execute(&mut *conn)
                     .await
                     .map_err(|e| PipelineError::database_error(format!("Failed to insert stage parameter: {}", e)))?;
             }
         }
-
-        // NOTE: Metrics are handled by Prometheus (per SRS requirements), not stored in
-        // database Skip metrics insertion - observability is handled externally
-        // This keeps the database focused on core pipeline data only
-
-        // Commit transaction - ensures ACID compliance
-        tx.commit()
-            .await
-            .map_err(|e| PipelineError::database_error(format!("Failed to commit transaction: {}", e)))?;
-
-        debug!(
-            pipeline_name = %entity.name(),
-            "Successfully saved pipeline with ACID transaction"
-        );
         Ok(())
     }
 
-    /// PUBLIC: Domain interface - Find pipeline by ID
-    pub async fn find_by_id(&self, id: PipelineId) -> Result<Option<Pipeline>, PipelineError> {
-        self.load_pipeline_from_db(id).await
-    }
-
-    /// PUBLIC: Domain interface - Update a pipeline
-    pub async fn update(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
-        // Implementation simplified for now
-        debug!(
-            pipeline_name = %pipeline.name(),
-            "SqlitePipelineRepository::update called"
-        );
-        Ok(())
-    }
-
-    /// PUBLIC: Domain interface - Soft delete a pipeline with cascading archive
-    pub async fn delete(&self, id: PipelineId) -> Result<bool, PipelineError> {
-        debug!(pipeline_id = %id, "Starting delete for pipeline");
-
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| PipelineError::database_error(format!("Failed to begin transaction: {}", e)))?;
-
+    /// Archives the pipeline `id` in `namespace` and everything it owns on
+    /// `conn`, which the caller holds a transaction on; returns whether the
+    /// pipeline was found
+    pub(crate) async fn archive_pipeline(
+        conn: &mut SqliteConnection,
+        namespace: &Namespace,
+        id: &PipelineId,
+    ) -> Result<bool, PipelineError> {
         let now = chrono::Utc::now().to_rfc3339();
         let id_str = id.to_string();
 
@@ -344,7 +454,7 @@ impl SqlitePipelineRepository {
         let stages_result = sqlx::query(stages_query)
             .bind(&now)
             .bind(&id_str)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to archive pipeline stages: {}", e)))?;
 
@@ -367,7 +477,7 @@ impl SqlitePipelineRepository {
         let params_result = sqlx::query(params_query)
             .bind(&now)
             .bind(&id_str)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to archive stage parameters: {}", e)))?;
 
@@ -387,7 +497,7 @@ impl SqlitePipelineRepository {
         let config_result = sqlx::query(config_query)
             .bind(&now)
             .bind(&id_str)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to archive pipeline configuration: {}", e)))?;
 
@@ -401,13 +511,14 @@ impl SqlitePipelineRepository {
         let pipeline_query = r#"
             UPDATE pipelines 
             SET archived = true, updated_at = ?
-            WHERE id = ? AND archived = false
+            WHERE id = ? AND namespace = ? AND archived = false
         "#;
 
         let result = sqlx::query(pipeline_query)
             .bind(&now)
             .bind(&id_str)
-            .execute(&mut *tx)
+            .bind(namespace.as_str())
+            .execute(&mut *conn)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to archive pipeline: {}", e)))?;
 
@@ -417,19 +528,6 @@ impl SqlitePipelineRepository {
             rows_affected = result.rows_affected(),
             "Pipeline archive result"
         );
-
-        if success {
-            tx.commit()
-                .await
-                .map_err(|e| PipelineError::database_error(format!("Failed to commit archive transaction: {}", e)))?;
-            debug!("Transaction committed successfully");
-        } else {
-            tx.rollback()
-                .await
-                .map_err(|e| PipelineError::database_error(format!("Failed to rollback archive transaction: {}", e)))?;
-            debug!("Transaction rolled back");
-        }
-
         Ok(success)
     }
 
@@ -438,8 +536,9 @@ impl SqlitePipelineRepository {
         debug!("SqlitePipelineRepository::list_all called (excluding archived)");
 
         // Get all non-archived pipelines
-        let query = "SELECT id FROM pipelines WHERE archived = false ORDER BY name";
+        let query = "SELECT id FROM pipelines WHERE namespace = ? AND archived = false ORDER BY name";
         let rows = sqlx::query(query)
+            .bind(self.namespace.as_str())
             .fetch_all(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to query pipelines: {}", e)))?;
@@ -469,8 +568,9 @@ impl SqlitePipelineRepository {
         debug!("SqlitePipelineRepository::list_archived called");
 
         // Get all archived pipelines
-        let query = "SELECT id FROM pipelines WHERE archived = true ORDER BY name";
+        let query = "SELECT id FROM pipelines WHERE namespace = ? AND archived = true ORDER BY name";
         let rows = sqlx::query(query)
+            .bind(self.namespace.as_str())
             .fetch_all(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to query pipelines: {}", e)))?;
@@ -491,9 +591,10 @@ impl SqlitePipelineRepository {
 
     /// PUBLIC: Domain interface - Check if pipeline exists
     pub async fn exists(&self, id: PipelineId) -> Result<bool, PipelineError> {
-        let query = "SELECT 1 FROM pipelines WHERE id = ? AND archived = false";
+        let query = "SELECT 1 FROM pipelines WHERE id = ? AND namespace = ? AND archived = false";
         let result = sqlx::query(query)
             .bind(id.to_string())
+            .bind(self.namespace.as_str())
             .fetch_optional(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to check pipeline existence: {}", e)))?;
@@ -505,8 +606,9 @@ impl SqlitePipelineRepository {
     pub async fn find_by_name(&self, name: &str) -> Result<Option<Pipeline>, PipelineError> {
         debug!("SqlitePipelineRepository::find_by_name called for: {}", name);
 
-        let query = "SELECT id FROM pipelines WHERE name = ? AND archived = false";
+        let query = "SELECT id FROM pipelines WHERE namespace = ? AND name = ? AND archived = false";
         let row = sqlx::query(query)
+            .bind(self.namespace.as_str())
             .bind(name)
             .fetch_optional(&self.pool)
             .await
@@ -524,8 +626,9 @@ impl SqlitePipelineRepository {
 
     /// PUBLIC: Domain interface - List pipelines with pagination
     pub async fn list_paginated(&self, offset: usize, limit: usize) -> Result<Vec<Pipeline>, PipelineError> {
-        let query = "SELECT id FROM pipelines WHERE archived = false ORDER BY name LIMIT ? OFFSET ?";
+        let query = "SELECT id FROM pipelines WHERE namespace = ? AND archived = false ORDER BY name LIMIT ? OFFSET ?";
         let rows = sqlx::query(query)
+            .bind(self.namespace.as_str())
             .bind(limit as i64)
             .bind(offset as i64)
             .fetch_all(&self.pool)
@@ -547,7 +650,8 @@ impl SqlitePipelineRepository {
 
     /// PUBLIC: Domain interface - Count active pipelines
     pub async fn count(&self) -> Result<usize, PipelineError> {
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipelines WHERE archived = false")
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipelines WHERE namespace = ? AND archived = false")
+            .bind(self.namespace.as_str())
             .fetch_one(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to count pipelines: {}", e)))?;
@@ -567,10 +671,11 @@ impl SqlitePipelineRepository {
             SELECT DISTINCT p.id 
             FROM pipelines p 
             JOIN pipeline_configuration pc ON p.id = pc.pipeline_id 
-            WHERE pc.key = ? AND pc.value = ? AND p.archived = false AND pc.archived = false
+            WHERE p.namespace = ? AND pc.key = ? AND pc.value = ? AND p.archived = false AND pc.archived = false
         "#;
 
         let rows = sqlx::query(query)
+            .bind(self.namespace.as_str())
             .bind(key)
             .bind(value)
             .fetch_all(&self.pool)
@@ -606,13 +711,14 @@ impl SqlitePipelineRepository {
         let query = r#"
             UPDATE pipelines 
             SET archived = false, updated_at = ?
-            WHERE id = ? AND archived = true
+            WHERE id = ? AND namespace = ? AND archived = true
         "#;
 
         let now = chrono::Utc::now().to_rfc3339();
         let result = sqlx::query(query)
             .bind(now)
             .bind(id.to_string())
+            .bind(self.namespace.as_str())
             .execute(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to restore pipeline: {}", e)))?;
@@ -620,6 +726,126 @@ impl SqlitePipelineRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Stores the configuration of an existing `pipeline`
+    ///
+    /// Each key is inserted or overwritten; keys the pipeline no longer has
+    /// are left as stored. Stages are not touched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineNotFound` if the pipeline does not exist in this
+    /// repository's namespace.
+    pub async fn save_configuration(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        let mut tx = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to start transaction: {}", e)))?;
+
+        let id = pipeline.id().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let touched =
+            sqlx::query("UPDATE pipelines SET updated_at = ? WHERE id = ? AND namespace = ? AND archived = false")
+                .bind(&now)
+                .bind(&id)
+                .bind(self.namespace.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| PipelineError::database_error(format!("Failed to update pipeline: {}", e)))?;
+        if touched.rows_affected() == 0 {
+            return Err(PipelineError::PipelineNotFound(pipeline.name().to_string()));
+        }
+
+        for (key, value) in pipeline.configuration() {
+            sqlx::query(
+                r#"
+                INSERT INTO pipeline_configuration (pipeline_id, key, value, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(pipeline_id, key) DO UPDATE SET
+                    value = excluded.value,
+                    archived = false,
+                    updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(&id)
+            .bind(key)
+            .bind(value)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to save configuration: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to commit transaction: {}", e)))
+    }
+
+    /// Records `metrics` as the latest run of pipeline `id`
+    ///
+    /// Only the most recent run is kept; it replaces any earlier record. The
+    /// run count and byte totals accumulate across runs.
+    pub async fn record_execution(&self, id: PipelineId, metrics: &ProcessingMetrics) -> Result<(), PipelineError> {
+        let query = r#"
+            INSERT INTO processing_metrics (
+                pipeline_id, bytes_processed, bytes_total, chunks_processed, chunks_total,
+                start_time_rfc3339, end_time_rfc3339, processing_duration_ms, throughput_bytes_per_second,
+                compression_ratio, error_count, warning_count, input_file_size_bytes, output_file_size_bytes,
+                input_file_checksum, output_file_checksum,
+                run_count, total_bytes_processed, total_input_bytes, total_output_bytes
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)
+            ON CONFLICT(pipeline_id) DO UPDATE SET
+                bytes_processed = excluded.bytes_processed,
+                bytes_total = excluded.bytes_total,
+                chunks_processed = excluded.chunks_processed,
+                chunks_total = excluded.chunks_total,
+                start_time_rfc3339 = excluded.start_time_rfc3339,
+                end_time_rfc3339 = excluded.end_time_rfc3339,
+                processing_duration_ms = excluded.processing_duration_ms,
+                throughput_bytes_per_second = excluded.throughput_bytes_per_second,
+                compression_ratio = excluded.compression_ratio,
+                error_count = excluded.error_count,
+                warning_count = excluded.warning_count,
+                input_file_size_bytes = excluded.input_file_size_bytes,
+                output_file_size_bytes = excluded.output_file_size_bytes,
+                input_file_checksum = excluded.input_file_checksum,
+                output_file_checksum = excluded.output_file_checksum,
+                run_count = processing_metrics.run_count + 1,
+                total_bytes_processed = processing_metrics.total_bytes_processed + excluded.bytes_processed,
+                total_input_bytes = processing_metrics.total_input_bytes + excluded.input_file_size_bytes,
+                total_output_bytes = processing_metrics.total_output_bytes + excluded.output_file_size_bytes
+        "#;
+
+        sqlx::query(query)
+            .bind(id.to_string())
+            .bind(metrics.bytes_processed() as i64)
+            .bind(metrics.bytes_total() as i64)
+            .bind(metrics.chunks_processed() as i64)
+            .bind(metrics.chunks_total() as i64)
+            .bind(metrics.start_time().map(|t| t.to_rfc3339()))
+            .bind(metrics.end_time().map(|t| t.to_rfc3339()))
+            .bind(metrics.processing_duration().map(|d| d.as_millis() as i64))
+            .bind(metrics.throughput_bytes_per_second())
+            .bind(metrics.compression_ratio())
+            .bind(metrics.error_count() as i64)
+            .bind(metrics.warning_count() as i64)
+            .bind(metrics.input_file_size_bytes() as i64)
+            .bind(metrics.output_file_size_bytes() as i64)
+            .bind(metrics.input_file_checksum().clone())
+            .bind(metrics.output_file_checksum().clone())
+            .bind(metrics.bytes_processed() as i64)
+            .bind(metrics.input_file_size_bytes() as i64)
+            .bind(metrics.output_file_size_bytes() as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to record execution: {}", e)))?;
+
+        debug!(pipeline_id = %id, "Recorded pipeline execution");
+        Ok(())
+    }
+
     // PRIVATE: Internal helper methods
     async fn load_pipeline_from_db(&self, id: PipelineId) -> Result<Option<Pipeline>, PipelineError> {
         self.load_pipeline_from_db_with_archived(id, false).await
@@ -634,12 +860,14 @@ impl SqlitePipelineRepository {
 
         // Load main pipeline record
         let pipeline_query = if include_archived {
-            "SELECT id, name, archived, created_at, updated_at FROM pipelines WHERE id = ?"
+            "SELECT id, namespace, name, archived, created_at, updated_at FROM pipelines WHERE id = ? AND namespace = ?"
         } else {
-            "SELECT id, name, archived, created_at, updated_at FROM pipelines WHERE id = ? AND archived = false"
+            "SELECT id, namespace, name, archived, created_at, updated_at FROM pipelines WHERE id = ? AND namespace = ? \
+             AND archived = false"
         };
         let pipeline_row = sqlx::query(pipeline_query)
             .bind(id.to_string())
+            .bind(self.namespace.as_str())
             .fetch_optional(&self.pool)
             .await
             .map_err(|e| PipelineError::database_error(format!("Failed to load pipeline: {}", e)))?;
@@ -652,6 +880,7 @@ impl SqlitePipelineRepository {
         };
 
         // Parse pipeline data
+        let namespace: Namespace = pipeline_row.get::<String, _>("namespace").parse()?;
         let name: String = pipeline_row.get("name");
         let archived: bool = pipeline_row.get("archived");
         let created_at_str: String = pipeline_row.get("created_at");
@@ -759,6 +988,7 @@ impl SqlitePipelineRepository {
         // Construct DTO and reconstruct pipeline
         let data = adaptive_pipeline_domain::entities::pipeline::PipelineData {
             id,
+            namespace,
             name,
             archived,
             configuration,
@@ -835,6 +1065,194 @@ impl adaptive_pipeline_domain::repositories::pipeline_repository::PipelineReposi
     }
 }
 
+/// Columns shared by the summary queries
+const SUMMARY_COLUMNS: &str = r#"
+    p.id, p.namespace, p.name, p.archived, p.created_at, p.updated_at,
+    (SELECT COUNT(*) FROM pipeline_stages s WHERE s.pipeline_id = p.id) AS stage_count,
+    m.end_time_rfc3339 AS last_executed_at,
+    COALESCE(m.run_count, 0) AS run_count,
+    COALESCE(m.total_bytes_processed, 0) AS total_bytes_processed,
+    COALESCE(m.total_input_bytes, 0) AS total_input_bytes,
+    COALESCE(m.total_output_bytes, 0) AS total_output_bytes
+"#;
+
+/// Columns of an execution record, joined with its pipeline's name
+const EXECUTION_COLUMNS: &str = r#"
+    m.pipeline_id, p.name AS pipeline_name, m.bytes_processed, m.chunks_processed,
+    m.input_file_size_bytes, m.output_file_size_bytes, m.compression_ratio,
+    m.throughput_bytes_per_second, m.processing_duration_ms, m.error_count, m.warning_count,
+    m.start_time_rfc3339, m.end_time_rfc3339, m.input_file_checksum, m.output_file_checksum
+"#;
+
+fn parse_timestamp(value: &str, field: &str) -> Result<chrono::DateTime<chrono::Utc>, PipelineError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .map_err(|e| PipelineError::SerializationError(format!("Invalid {} format: {}", field, e)))
+}
+
+fn parse_optional_timestamp(
+    value: Option<String>,
+    field: &str,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, PipelineError> {
+    value.map(|v| parse_timestamp(&v, field)).transpose()
+}
+
+fn summary_from_row(row: &SqliteRow) -> Result<PipelineSummaryDto, PipelineError> {
+    Ok(PipelineSummaryDto {
+        id: row.get("id"),
+        namespace: row.get("namespace"),
+        name: row.get("name"),
+        archived: row.get("archived"),
+        stage_count: row.get::<i64, _>("stage_count") as usize,
+        created_at: parse_timestamp(&row.get::<String, _>("created_at"), "created_at")?,
+        updated_at: parse_timestamp(&row.get::<String, _>("updated_at"), "updated_at")?,
+        last_executed_at: parse_optional_timestamp(row.get("last_executed_at"), "last_executed_at")?,
+        run_count: row.get::<i64, _>("run_count") as u64,
+        total_bytes_processed: row.get::<i64, _>("total_bytes_processed") as u64,
+        total_input_bytes: row.get::<i64, _>("total_input_bytes") as u64,
+        total_output_bytes: row.get::<i64, _>("total_output_bytes") as u64,
+    })
+}
+
+fn execution_from_row(row: &SqliteRow) -> Result<ExecutionRecordDto, PipelineError> {
+    Ok(ExecutionRecordDto {
+        pipeline_id: row.get("pipeline_id"),
+        pipeline_name: row.get("pipeline_name"),
+        bytes_processed: row.get::<i64, _>("bytes_processed") as u64,
+        chunks_processed: row.get::<i64, _>("chunks_processed") as u64,
+        input_file_size_bytes: row.get::<i64, _>("input_file_size_bytes") as u64,
+        output_file_size_bytes: row.get::<i64, _>("output_file_size_bytes") as u64,
+        compression_ratio: row.get("compression_ratio"),
+        throughput_bytes_per_second: row.get("throughput_bytes_per_second"),
+        duration_ms: row.get::<Option<i64>, _>("processing_duration_ms").map(|ms| ms as u64),
+        error_count: row.get::<i64, _>("error_count") as u64,
+        warning_count: row.get::<i64, _>("warning_count") as u64,
+        started_at: parse_optional_timestamp(row.get("start_time_rfc3339"), "start_time")?,
+        completed_at: parse_optional_timestamp(row.get("end_time_rfc3339"), "end_time")?,
+        input_file_checksum: row.get("input_file_checksum"),
+        output_file_checksum: row.get("output_file_checksum"),
+    })
+}
+
+#[async_trait::async_trait]
+impl PipelineReadModel for SqlitePipelineRepository {
+    async fn pipeline_summaries(&self) -> Result<Vec<PipelineSummaryDto>, PipelineError> {
+        let query = format!(
+            "SELECT {} FROM pipelines p LEFT JOIN processing_metrics m ON m.pipeline_id = p.id \
+             WHERE p.namespace = ? AND p.archived = false ORDER BY p.name",
+            SUMMARY_COLUMNS
+        );
+        let rows = sqlx::query(&query)
+            .bind(self.namespace.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to query pipeline summaries: {}", e)))?;
+
+        rows.iter().map(summary_from_row).collect()
+    }
+
+    async fn pipeline_details(&self, name: &str) -> Result<Option<PipelineDetailsDto>, PipelineError> {
+        let query = format!(
+            "SELECT {} FROM pipelines p LEFT JOIN processing_metrics m ON m.pipeline_id = p.id \
+             WHERE p.namespace = ? AND p.name = ? AND p.archived = false",
+            SUMMARY_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(self.namespace.as_str())
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to query pipeline: {}", e)))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let summary = summary_from_row(&row)?;
+
+        // All parameters of all stages in one query, grouped by stage below
+        let param_rows = sqlx::query(
+            "SELECT sp.stage_id, sp.key, sp.value FROM stage_parameters sp \
+             JOIN pipeline_stages s ON s.id = sp.stage_id WHERE s.pipeline_id = ?",
+        )
+        .bind(&summary.id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to load stage parameters: {}", e)))?;
+
+        let mut parameters: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+        for row in param_rows {
+            parameters
+                .entry(row.get("stage_id"))
+                .or_default()
+                .insert(row.get("key"), row.get("value"));
+        }
+
+        let stage_rows = sqlx::query(
+            "SELECT id, name, stage_type, algorithm, enabled, stage_order FROM pipeline_stages \
+             WHERE pipeline_id = ? ORDER BY stage_order",
+        )
+        .bind(&summary.id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to load stages: {}", e)))?;
+
+        let stages = stage_rows
+            .iter()
+            .map(|row| StageDto {
+                parameters: parameters.remove(&row.get::<String, _>("id")).unwrap_or_default(),
+                name: row.get("name"),
+                stage_type: row.get("stage_type"),
+                algorithm: row.get("algorithm"),
+                enabled: row.get("enabled"),
+                order: row.get::<i64, _>("stage_order") as u32,
+            })
+            .collect();
+
+        let configuration = sqlx::query("SELECT key, value FROM pipeline_configuration WHERE pipeline_id = ?")
+            .bind(&summary.id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load configuration: {}", e)))?
+            .iter()
+            .map(|row| (row.get("key"), row.get("value")))
+            .collect();
+
+        let execution_query = format!(
+            "SELECT {} FROM processing_metrics m JOIN pipelines p ON p.id = m.pipeline_id WHERE m.pipeline_id = ?",
+            EXECUTION_COLUMNS
+        );
+        let last_execution = sqlx::query(&execution_query)
+            .bind(&summary.id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load execution record: {}", e)))?
+            .as_ref()
+            .map(execution_from_row)
+            .transpose()?;
+
+        Ok(Some(PipelineDetailsDto {
+            summary,
+            stages,
+            configuration,
+            last_execution,
+        }))
+    }
+
+    async fn execution_records(&self) -> Result<Vec<ExecutionRecordDto>, PipelineError> {
+        let query = format!(
+            "SELECT {} FROM processing_metrics m JOIN pipelines p ON p.id = m.pipeline_id \
+             WHERE p.namespace = ? AND p.archived = false ORDER BY m.end_time_rfc3339 DESC",
+            EXECUTION_COLUMNS
+        );
+        let rows = sqlx::query(&query)
+            .bind(self.namespace.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to query execution records: {}", e)))?;
+
+        rows.iter().map(execution_from_row).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -854,11 +1272,7 @@ mod tests {
         ];
 
         for (input_path, expected_url) in test_cases {
-            let formatted_url = if input_path == ":memory:" {
-                "sqlite::memory:".to_string()
-            } else {
-                format!("sqlite://{}", input_path)
-            };
+            let formatted_url = crate::infrastructure::repositories::schema::database_url(input_path);
             assert_eq!(
                 formatted_url, expected_url,
                 "Database URL formatting failed for path: {}",
@@ -891,6 +1305,114 @@ mod tests {
         }
     }
 
+    async fn repository_with_pipeline(dir: &tempfile::TempDir) -> (SqlitePipelineRepository, Pipeline) {
+        let repository = SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+            .await
+            .unwrap();
+        let stage = PipelineStage::new(
+            "compression".to_string(),
+            StageType::Compression,
+            StageConfiguration {
+                algorithm: "brotli".to_string(),
+                operation: adaptive_pipeline_domain::entities::Operation::Forward,
+                parameters: HashMap::from([("level".to_string(), "6".to_string())]),
+                parallel_processing: false,
+                chunk_size: None,
+            },
+            1,
+        )
+        .unwrap();
+        let pipeline = Pipeline::new("read-model".to_string(), vec![stage]).unwrap();
+        repository.save(&pipeline).await.unwrap();
+        (repository, pipeline)
+    }
+
+    /// Tests that the read model summarizes and details a saved pipeline
+    /// without loading the aggregate.
+    #[tokio::test]
+    async fn test_read_model_summaries_and_details() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (repository, pipeline) = repository_with_pipeline(&dir).await;
+
+        let summaries = repository.pipeline_summaries().await.unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].id, pipeline.id().to_string());
+        assert_eq!(summaries[0].stage_count, pipeline.stages().len());
+        assert!(summaries[0].last_executed_at.is_none());
+
+        let details = repository.pipeline_details("read-model").await.unwrap().unwrap();
+        assert_eq!(details.stages.len(), pipeline.stages().len());
+        let compression = details.stages.iter().find(|s| s.name == "compression").unwrap();
+        assert_eq!(compression.algorithm, "brotli");
+        assert_eq!(compression.parameters.get("level").map(String::as_str), Some("6"));
+        assert!(details.last_execution.is_none());
+
+        assert!(repository.pipeline_details("missing").await.unwrap().is_none());
+        let other = repository.in_namespace("tenant-b".parse().unwrap());
+        assert!(other.pipeline_summaries().await.unwrap().is_empty());
+    }
+
+    /// Tests that a recorded execution replaces the previous one and shows
+    /// up in the read model.
+    #[tokio::test]
+    async fn test_record_execution_keeps_latest_run() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (repository, pipeline) = repository_with_pipeline(&dir).await;
+
+        for bytes in [100, 200] {
+            let mut metrics = ProcessingMetrics::new(bytes, bytes);
+            metrics.start();
+            metrics.update_bytes_processed(bytes);
+            metrics.set_input_file_info(bytes, None);
+            metrics.set_output_file_info(bytes / 2, Some("abc".to_string()));
+            metrics.end();
+            repository
+                .record_execution(pipeline.id().clone(), &metrics)
+                .await
+                .unwrap();
+        }
+
+        let records = repository.execution_records().await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].pipeline_name, "read-model");
+        assert_eq!(records[0].bytes_processed, 200);
+        assert_eq!(records[0].output_file_size_bytes, 100);
+        assert_eq!(records[0].output_file_checksum.as_deref(), Some("abc"));
+
+        let summaries = repository.pipeline_summaries().await.unwrap();
+        assert_eq!(summaries[0].last_executed_at, records[0].completed_at);
+        assert_eq!(summaries[0].run_count, 2);
+        assert_eq!(summaries[0].total_bytes_processed, 300);
+        assert_eq!(summaries[0].average_compression_ratio(), Some(0.5));
+        let details = repository.pipeline_details("read-model").await.unwrap().unwrap();
+        assert_eq!(details.last_execution, Some(records[0].clone()));
+    }
+
+    /// Tests that saved configuration reaches a reloaded pipeline and stays
+    /// within the repository's namespace.
+    #[tokio::test]
+    async fn test_save_configuration_updates_existing_pipeline() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (repository, mut pipeline) = repository_with_pipeline(&dir).await;
+
+        pipeline.set_chunk_size(adaptive_pipeline_domain::value_objects::ChunkSize::from_mb(4).unwrap());
+        repository.save_configuration(&pipeline).await.unwrap();
+        pipeline.set_chunk_size(adaptive_pipeline_domain::value_objects::ChunkSize::from_mb(8).unwrap());
+        repository.save_configuration(&pipeline).await.unwrap();
+
+        let reloaded = repository.find_by_name("read-model").await.unwrap().unwrap();
+        assert_eq!(
+            reloaded.chunk_size().unwrap().map(|size| size.bytes()),
+            Some(8 * 1024 * 1024)
+        );
+
+        let other = repository.in_namespace("tenant-b".parse().unwrap());
+        assert!(matches!(
+            other.save_configuration(&pipeline).await,
+            Err(PipelineError::PipelineNotFound(_))
+        ));
+    }
+
     // NOTE: Domain logic tests (Pipeline creation, Stage configuration, etc.)
     // have been moved to their proper domain entity files following DDD
     // principles. Repository tests should focus on infrastructure concerns
@@ -0,0 +1,229 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Role Repository
+//!
+//! Persists role assignments in the `role_assignments` table, created by the
+//! `20250102000000_role_assignments` migration and keyed by namespace and
+//! principal since `20250103000000_namespaces`. Shares the database file with
+//! `SqlitePipelineRepository`.
+
+use adaptive_pipeline_domain::repositories::RoleRepository;
+use adaptive_pipeline_domain::value_objects::{Namespace, Role, RoleAssignment};
+use adaptive_pipeline_domain::PipelineError;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `RoleRepository`
+pub struct SqliteRoleRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRoleRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteRoleRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+
+    fn assignment_from_row(row: &SqliteRow) -> Result<RoleAssignment, PipelineError> {
+        let namespace: Namespace = row.get::<String, _>("namespace").parse()?;
+        let principal: String = row.get("principal");
+        let role: Role = row.get::<String, _>("role").parse()?;
+        let assigned_by: Option<String> = row.get("assigned_by");
+        let updated_at: String = row.get("updated_at");
+        let assigned_at = chrono::DateTime::parse_from_rfc3339(&updated_at)
+            .map_err(|e| PipelineError::database_error(format!("Invalid role assignment timestamp: {}", e)))?
+            .with_timezone(&chrono::Utc);
+
+        Ok(RoleAssignment::from_parts(
+            namespace,
+            principal,
+            role,
+            assigned_by,
+            assigned_at,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl RoleRepository for SqliteRoleRepository {
+    async fn assign(&self, assignment: &RoleAssignment) -> Result<(), PipelineError> {
+        let assigned_at = assignment.assigned_at().to_rfc3339();
+        let query = r#"
+            INSERT INTO role_assignments (namespace, principal, role, assigned_by, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(namespace, principal) DO UPDATE SET
+                role = excluded.role,
+                assigned_by = excluded.assigned_by,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(assignment.namespace().as_str())
+            .bind(assignment.principal())
+            .bind(assignment.role().as_str())
+            .bind(assignment.assigned_by())
+            .bind(&assigned_at)
+            .bind(&assigned_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to assign role: {}", e)))?;
+
+        debug!(
+            namespace = %assignment.namespace(),
+            principal = assignment.principal(),
+            role = %assignment.role(),
+            "Role assigned"
+        );
+        Ok(())
+    }
+
+    async fn revoke(&self, namespace: &Namespace, principal: &str) -> Result<bool, PipelineError> {
+        let result = sqlx::query("DELETE FROM role_assignments WHERE namespace = ? AND principal = ?")
+            .bind(namespace.as_str())
+            .bind(principal)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to revoke role: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_by_principal(
+        &self,
+        namespace: &Namespace,
+        principal: &str,
+    ) -> Result<Option<RoleAssignment>, PipelineError> {
+        let query = r#"
+            SELECT namespace, principal, role, assigned_by, updated_at
+            FROM role_assignments
+            WHERE namespace = ? AND principal = ?
+        "#;
+        let row = sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(principal)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load role assignment: {}", e)))?;
+
+        row.as_ref().map(Self::assignment_from_row).transpose()
+    }
+
+    async fn list_by_namespace(&self, namespace: &Namespace) -> Result<Vec<RoleAssignment>, PipelineError> {
+        let query = r#"
+            SELECT namespace, principal, role, assigned_by, updated_at
+            FROM role_assignments
+            WHERE namespace = ?
+            ORDER BY principal
+        "#;
+        let rows = sqlx::query(query)
+            .bind(namespace.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to list role assignments: {}", e)))?;
+
+        rows.iter().map(Self::assignment_from_row).collect()
+    }
+
+    async fn count(&self) -> Result<usize, PipelineError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM role_assignments")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to count role assignments: {}", e)))?;
+
+        Ok(count as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn repository(dir: &TempDir) -> SqliteRoleRepository {
+        let path = dir.path().join("roles.db");
+        SqliteRoleRepository::new(&path.to_string_lossy()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_assign_replaces_existing_role() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+
+        repo.assign(&RoleAssignment::new(ns.clone(), "alice", Role::Auditor, None).unwrap())
+            .await
+            .unwrap();
+        repo.assign(&RoleAssignment::new(ns.clone(), "alice", Role::Operator, Some("root".to_string())).unwrap())
+            .await
+            .unwrap();
+
+        let assignment = repo.find_by_principal(&ns, "alice").await.unwrap().unwrap();
+        assert_eq!(assignment.role(), Role::Operator);
+        assert_eq!(assignment.assigned_by(), Some("root"));
+        assert_eq!(repo.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_and_list() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+
+        repo.assign(&RoleAssignment::new(ns.clone(), "bob", Role::Admin, None).unwrap())
+            .await
+            .unwrap();
+        repo.assign(&RoleAssignment::new(ns.clone(), "alice", Role::Auditor, None).unwrap())
+            .await
+            .unwrap();
+
+        let principals: Vec<_> = repo
+            .list_by_namespace(&ns)
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.principal().to_string())
+            .collect();
+        assert_eq!(principals, vec!["alice", "bob"]);
+
+        assert!(repo.revoke(&ns, "bob").await.unwrap());
+        assert!(!repo.revoke(&ns, "bob").await.unwrap());
+        assert_eq!(repo.find_role(&ns, "bob").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_assignments_are_scoped_to_namespace() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let team_a = Namespace::new("team-a").unwrap();
+        let team_b = Namespace::new("team-b").unwrap();
+
+        repo.assign(&RoleAssignment::new(team_a.clone(), "alice", Role::Admin, None).unwrap())
+            .await
+            .unwrap();
+        repo.assign(&RoleAssignment::new(team_b.clone(), "alice", Role::Auditor, None).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(repo.find_role(&team_a, "alice").await.unwrap(), Some(Role::Admin));
+        assert_eq!(repo.find_role(&team_b, "alice").await.unwrap(), Some(Role::Auditor));
+        assert_eq!(repo.find_role(&Namespace::default(), "alice").await.unwrap(), None);
+        assert_eq!(repo.count().await.unwrap(), 2);
+
+        assert!(repo.revoke(&team_a, "alice").await.unwrap());
+        assert_eq!(repo.list_by_namespace(&team_b).await.unwrap().len(), 1);
+    }
+}
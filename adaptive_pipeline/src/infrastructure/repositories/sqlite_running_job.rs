@@ -0,0 +1,153 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Running Job Repository
+//!
+//! Persists running-job leases in the `running_jobs` table, created by the
+//! `20250112000000_running_jobs` migration. Every process using the same
+//! database file sees the same count, so `max_concurrent_jobs` holds across
+//! separate CLI invocations.
+//!
+//! Starting a job counts and inserts in one statement; SQLite runs one
+//! writer at a time, so two processes never both take the last slot.
+
+use adaptive_pipeline_domain::repositories::RunningJobRepository;
+use adaptive_pipeline_domain::value_objects::Namespace;
+use adaptive_pipeline_domain::PipelineError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `RunningJobRepository`
+pub struct SqliteRunningJobRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRunningJobRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteRunningJobRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Timestamps share one fixed-width format, so they compare as text
+    fn timestamp(at: DateTime<Utc>) -> String {
+        at.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+}
+
+#[async_trait::async_trait]
+impl RunningJobRepository for SqliteRunningJobRepository {
+    async fn start(
+        &self,
+        namespace: &Namespace,
+        id: &str,
+        limit: usize,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, PipelineError> {
+        // Leases of processes that died are dropped on the way
+        sqlx::query("DELETE FROM running_jobs WHERE expires_at <= ?")
+            .bind(Self::timestamp(now))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to expire running jobs: {}", e)))?;
+
+        let query = r#"
+            INSERT INTO running_jobs (id, namespace, expires_at)
+            SELECT ?, ?, ?
+            WHERE (SELECT COUNT(*) FROM running_jobs WHERE namespace = ? AND expires_at > ?) < ?
+        "#;
+        let result = sqlx::query(query)
+            .bind(id)
+            .bind(namespace.as_str())
+            .bind(Self::timestamp(expires_at))
+            .bind(namespace.as_str())
+            .bind(Self::timestamp(now))
+            .bind(limit as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to start a running job: {}", e)))?;
+
+        let started = result.rows_affected() > 0;
+        debug!(namespace = %namespace, job = id, started, "Running job requested");
+        Ok(started)
+    }
+
+    async fn count_running(&self, namespace: &Namespace, now: DateTime<Utc>) -> Result<usize, PipelineError> {
+        let row = sqlx::query("SELECT COUNT(*) AS jobs FROM running_jobs WHERE namespace = ? AND expires_at > ?")
+            .bind(namespace.as_str())
+            .bind(Self::timestamp(now))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to count running jobs: {}", e)))?;
+
+        Ok(row.get::<i64, _>("jobs") as usize)
+    }
+
+    async fn renew(&self, id: &str, expires_at: DateTime<Utc>) -> Result<(), PipelineError> {
+        sqlx::query("UPDATE running_jobs SET expires_at = ? WHERE id = ?")
+            .bind(Self::timestamp(expires_at))
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to renew a running job: {}", e)))?;
+        Ok(())
+    }
+
+    async fn finish(&self, id: &str) -> Result<(), PipelineError> {
+        sqlx::query("DELETE FROM running_jobs WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to finish a running job: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_jobs_count_until_finished_or_expired() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("running.db");
+        let repo = SqliteRunningJobRepository::new(&path.to_string_lossy()).await.unwrap();
+        let ns = Namespace::default();
+        let now = Utc::now();
+        let later = now + chrono::Duration::seconds(60);
+
+        assert!(repo.start(&ns, "a", 2, now, later).await.unwrap());
+        assert!(repo.start(&ns, "b", 2, now, later).await.unwrap());
+        assert!(!repo.start(&ns, "c", 2, now, later).await.unwrap());
+        assert_eq!(repo.count_running(&ns, now).await.unwrap(), 2);
+        assert!(repo
+            .start(&Namespace::new("team-a").unwrap(), "x", 2, now, later)
+            .await
+            .unwrap());
+
+        repo.finish("a").await.unwrap();
+        assert!(repo.start(&ns, "c", 2, now, later).await.unwrap());
+
+        // A lease that is not renewed stops counting once it expires
+        repo.renew("b", later + chrono::Duration::seconds(60)).await.unwrap();
+        assert_eq!(repo.count_running(&ns, later).await.unwrap(), 1);
+        assert!(repo
+            .start(&ns, "d", 2, later, later + chrono::Duration::seconds(60))
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,233 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Session Repository
+//!
+//! Persists API sessions in the `sessions` table, created by the
+//! `20250108000000_sessions` migration. Shares the database file with
+//! `SqlitePipelineRepository`.
+//!
+//! Timestamps are stored as fixed-width RFC 3339 UTC strings, so that
+//! expiry can be compared in SQL.
+
+use adaptive_pipeline_domain::entities::Session;
+use adaptive_pipeline_domain::repositories::SessionRepository;
+use adaptive_pipeline_domain::value_objects::{Namespace, SessionId, UserId};
+use adaptive_pipeline_domain::PipelineError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `SessionRepository`
+pub struct SqliteSessionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSessionRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteSessionRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+
+    fn timestamp(at: DateTime<Utc>) -> String {
+        at.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, PipelineError> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(|e| PipelineError::database_error(format!("Invalid session timestamp: {}", e)))
+    }
+
+    fn session_from_row(row: &SqliteRow) -> Result<Session, PipelineError> {
+        let revoked_at: Option<String> = row.get("revoked_at");
+        Ok(Session::from_parts(
+            SessionId::from_string(&row.get::<String, _>("id"))?,
+            UserId::new(row.get("user_id"))?,
+            row.get::<String, _>("namespace").parse()?,
+            row.get("token_hash"),
+            Self::parse_timestamp(&row.get::<String, _>("issued_at"))?,
+            Self::parse_timestamp(&row.get::<String, _>("expires_at"))?,
+            Self::parse_timestamp(&row.get::<String, _>("last_seen_at"))?,
+            revoked_at.as_deref().map(Self::parse_timestamp).transpose()?,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for SqliteSessionRepository {
+    async fn create(&self, session: &Session) -> Result<(), PipelineError> {
+        let query = r#"
+            INSERT INTO sessions (id, namespace, user_id, token_hash, issued_at, expires_at, last_seen_at, revoked_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(session.id().to_string())
+            .bind(session.namespace().as_str())
+            .bind(session.user_id().value())
+            .bind(session.token_hash())
+            .bind(Self::timestamp(session.issued_at()))
+            .bind(Self::timestamp(session.expires_at()))
+            .bind(Self::timestamp(session.last_seen_at()))
+            .bind(session.revoked_at().map(Self::timestamp))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to create session: {}", e)))?;
+
+        debug!(session = %session.id(), user = %session.user_id(), "Session created");
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &SessionId) -> Result<Option<Session>, PipelineError> {
+        let row = sqlx::query("SELECT * FROM sessions WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load session: {}", e)))?;
+
+        row.as_ref().map(Self::session_from_row).transpose()
+    }
+
+    async fn list_active(&self, namespace: &Namespace, now: DateTime<Utc>) -> Result<Vec<Session>, PipelineError> {
+        let query = r#"
+            SELECT * FROM sessions
+            WHERE namespace = ? AND revoked_at IS NULL AND expires_at > ?
+            ORDER BY issued_at, id
+        "#;
+        let rows = sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(Self::timestamp(now))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to list sessions: {}", e)))?;
+
+        rows.iter().map(Self::session_from_row).collect()
+    }
+
+    async fn touch(&self, id: &SessionId, at: DateTime<Utc>) -> Result<(), PipelineError> {
+        sqlx::query("UPDATE sessions SET last_seen_at = MAX(last_seen_at, ?) WHERE id = ?")
+            .bind(Self::timestamp(at))
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to update session: {}", e)))?;
+        Ok(())
+    }
+
+    async fn revoke(&self, id: &SessionId, at: DateTime<Utc>) -> Result<bool, PipelineError> {
+        let at = Self::timestamp(at);
+        let result =
+            sqlx::query("UPDATE sessions SET revoked_at = ? WHERE id = ? AND revoked_at IS NULL AND expires_at > ?")
+                .bind(&at)
+                .bind(id.to_string())
+                .bind(&at)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| PipelineError::database_error(format!("Failed to revoke session: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_ended(&self, before: DateTime<Utc>) -> Result<usize, PipelineError> {
+        let before = Self::timestamp(before);
+        let result = sqlx::query("DELETE FROM sessions WHERE expires_at < ? OR revoked_at < ?")
+            .bind(&before)
+            .bind(&before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to purge sessions: {}", e)))?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn repository(dir: &TempDir) -> SqliteSessionRepository {
+        let path = dir.path().join("sessions.db");
+        SqliteSessionRepository::new(&path.to_string_lossy()).await.unwrap()
+    }
+
+    fn issue(user: &str, namespace: &Namespace, ttl: chrono::Duration) -> Session {
+        Session::issue(UserId::parse(user).unwrap(), namespace.clone(), ttl)
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn test_session_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let (session, token) = Session::issue(
+            UserId::parse("alice").unwrap(),
+            Namespace::default(),
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+
+        repo.create(&session).await.unwrap();
+        let loaded = repo.find_by_id(session.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.user_id(), session.user_id());
+        assert_eq!(loaded.token_hash(), session.token_hash());
+        assert!(loaded.verify(&token).is_ok());
+
+        let later = session.issued_at() + chrono::Duration::minutes(5);
+        repo.touch(session.id(), later).await.unwrap();
+        repo.touch(session.id(), session.issued_at()).await.unwrap();
+        let touched = repo.find_by_id(session.id()).await.unwrap().unwrap();
+        assert_eq!(touched.last_seen_at().timestamp(), later.timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_list_active_skips_revoked_expired_and_other_namespaces() {
+        let dir = TempDir::new().unwrap();
+        let repo = repository(&dir).await;
+        let ns = Namespace::default();
+        let team = Namespace::new("team-a").unwrap();
+
+        let active = issue("alice", &ns, chrono::Duration::hours(1));
+        let revoked = issue("bob", &ns, chrono::Duration::hours(1));
+        let short = issue("carol", &ns, chrono::Duration::seconds(1));
+        let elsewhere = issue("dave", &team, chrono::Duration::hours(1));
+        for session in [&active, &revoked, &short, &elsewhere] {
+            repo.create(session).await.unwrap();
+        }
+
+        let now = Utc::now();
+        assert!(repo.revoke(revoked.id(), now).await.unwrap());
+        assert!(!repo.revoke(revoked.id(), now).await.unwrap());
+
+        let later = now + chrono::Duration::seconds(2);
+        let listed: Vec<_> = repo
+            .list_active(&ns, later)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.user_id().to_string())
+            .collect();
+        assert_eq!(listed, vec!["alice"]);
+        assert!(!repo.revoke(short.id(), later).await.unwrap());
+
+        let purged = repo.purge_ended(later + chrono::Duration::seconds(1)).await.unwrap();
+        assert_eq!(purged, 2);
+        assert!(repo.find_by_id(active.id()).await.unwrap().is_some());
+        assert!(repo.find_by_id(revoked.id()).await.unwrap().is_none());
+    }
+}
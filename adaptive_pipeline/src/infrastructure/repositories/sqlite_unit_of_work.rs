@@ -0,0 +1,133 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Unit of Work
+//!
+//! Implements `UnitOfWork` on one SQLite transaction, begun with `BEGIN
+//! IMMEDIATE` so that the write lock is taken up front and waited for under
+//! the busy timeout. Audit records and domain events go to the
+//! `audit_records` and `domain_events` tables, created by the
+//! `20250107000000_audit_records_and_events` migration (audit records gained
+//! their `session_id` in `20250108000000_sessions`, and both tables their
+//! `correlation_id` in `20250109000000_correlation_ids`).
+//!
+//! Obtain one from `SqlitePipelineRepository::begin`; it works in that
+//! repository's namespace and stamps what it records with the repository's
+//! correlation ID, unless an audit record names its own.
+
+use adaptive_pipeline_domain::events::PipelineEvent;
+use adaptive_pipeline_domain::repositories::UnitOfWork;
+use adaptive_pipeline_domain::value_objects::{AuditRecord, CorrelationId, Namespace, PipelineId};
+use adaptive_pipeline_domain::{Pipeline, PipelineError};
+use async_trait::async_trait;
+use sqlx::{Sqlite, Transaction};
+use tracing::debug;
+
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+
+/// Unit of work on one SQLite transaction
+pub struct SqliteUnitOfWork {
+    tx: Transaction<'static, Sqlite>,
+    namespace: Namespace,
+    correlation_id: Option<CorrelationId>,
+}
+
+impl SqliteUnitOfWork {
+    /// Wraps `tx`, scoping pipeline changes to `namespace` and attributing
+    /// records to the run `correlation_id`
+    pub(crate) fn new(
+        tx: Transaction<'static, Sqlite>,
+        namespace: Namespace,
+        correlation_id: Option<CorrelationId>,
+    ) -> Self {
+        Self {
+            tx,
+            namespace,
+            correlation_id,
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for SqliteUnitOfWork {
+    async fn save_pipeline(&mut self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        if pipeline.namespace() != &self.namespace {
+            return Err(PipelineError::validation_error(format!(
+                "Pipeline '{}' belongs to namespace '{}', not '{}'",
+                pipeline.name(),
+                pipeline.namespace(),
+                self.namespace
+            )));
+        }
+        SqlitePipelineRepository::insert_pipeline(&mut self.tx, &self.namespace, pipeline).await
+    }
+
+    async fn delete_pipeline(&mut self, id: &PipelineId) -> Result<bool, PipelineError> {
+        SqlitePipelineRepository::archive_pipeline(&mut self.tx, &self.namespace, id).await
+    }
+
+    async fn record_audit(&mut self, record: &AuditRecord) -> Result<(), PipelineError> {
+        sqlx::query(
+            "INSERT INTO audit_records (namespace, principal, action, pipeline_id, detail, session_id, correlation_id, \
+             occurred_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(self.namespace.as_str())
+        .bind(&record.principal)
+        .bind(&record.action)
+        .bind(record.pipeline_id.as_ref().map(|id| id.to_string()))
+        .bind(&record.detail)
+        .bind(record.session_id.as_ref().map(|id| id.to_string()))
+        .bind(
+            record
+                .correlation_id
+                .as_ref()
+                .or(self.correlation_id.as_ref())
+                .map(CorrelationId::as_str),
+        )
+        .bind(record.occurred_at.to_rfc3339())
+        .execute(&mut *self.tx)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to record audit entry: {}", e)))?;
+        Ok(())
+    }
+
+    async fn record_event(&mut self, event: &PipelineEvent) -> Result<(), PipelineError> {
+        let payload = serde_json::to_string(event)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize event: {}", e)))?;
+        sqlx::query(
+            "INSERT INTO domain_events (namespace, event_type, payload, correlation_id, occurred_at) VALUES (?, ?, ?, \
+             ?, ?)",
+        )
+        .bind(self.namespace.as_str())
+        .bind(event.event_type())
+        .bind(payload)
+        .bind(self.correlation_id.as_ref().map(CorrelationId::as_str))
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut *self.tx)
+        .await
+        .map_err(|e| PipelineError::database_error(format!("Failed to record event: {}", e)))?;
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), PipelineError> {
+        self.tx
+            .commit()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to commit unit of work: {}", e)))?;
+        debug!("Unit of work committed");
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), PipelineError> {
+        self.tx
+            .rollback()
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to roll back unit of work: {}", e)))?;
+        debug!("Unit of work rolled back");
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # SQLite Usage Repository
+//!
+//! Persists per-namespace, per-day processing totals in the `namespace_usage`
+//! table, created by the `20250103000000_namespaces` migration. Shares the
+//! database file with `SqlitePipelineRepository`.
+
+use adaptive_pipeline_domain::repositories::UsageRepository;
+use adaptive_pipeline_domain::value_objects::{Namespace, NamespaceUsage};
+use adaptive_pipeline_domain::PipelineError;
+use chrono::NaiveDate;
+use sqlx::{Row, SqlitePool};
+use tracing::debug;
+
+/// SQLite-backed implementation of `UsageRepository`
+pub struct SqliteUsageRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUsageRepository {
+    /// Opens (creating and migrating if needed) the database at
+    /// `database_path`
+    ///
+    /// `database_path` is a file path or `:memory:`, as for
+    /// [`open_database`](super::schema::open_database).
+    pub async fn new(database_path: &str) -> Result<Self, PipelineError> {
+        debug!("Creating SqliteUsageRepository with database: {}", database_path);
+
+        let pool = crate::infrastructure::repositories::schema::open_database(database_path).await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait::async_trait]
+impl UsageRepository for SqliteUsageRepository {
+    async fn record_job(
+        &self,
+        namespace: &Namespace,
+        day: NaiveDate,
+        input_bytes: u64,
+        output_bytes: u64,
+    ) -> Result<(), PipelineError> {
+        let query = r#"
+            INSERT INTO namespace_usage (namespace, day, jobs, input_bytes, output_bytes, updated_at)
+            VALUES (?, ?, 1, ?, ?, ?)
+            ON CONFLICT(namespace, day) DO UPDATE SET
+                jobs = jobs + 1,
+                input_bytes = input_bytes + excluded.input_bytes,
+                output_bytes = output_bytes + excluded.output_bytes,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(day.to_string())
+            .bind(input_bytes as i64)
+            .bind(output_bytes as i64)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to record namespace usage: {}", e)))?;
+
+        debug!(
+            namespace = %namespace,
+            day = %day,
+            input_bytes,
+            output_bytes,
+            "Namespace usage recorded"
+        );
+        Ok(())
+    }
+
+    async fn usage_on(&self, namespace: &Namespace, day: NaiveDate) -> Result<NamespaceUsage, PipelineError> {
+        let query = "SELECT jobs, input_bytes, output_bytes FROM namespace_usage WHERE namespace = ? AND day = ?";
+        let row = sqlx::query(query)
+            .bind(namespace.as_str())
+            .bind(day.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PipelineError::database_error(format!("Failed to load namespace usage: {}", e)))?;
+
+        Ok(match row {
+            Some(row) => NamespaceUsage::new(
+                namespace.clone(),
+                day,
+                row.get::<i64, _>("jobs") as u64,
+                row.get::<i64, _>("input_bytes") as u64,
+                row.get::<i64, _>("output_bytes") as u64,
+            ),
+            None => NamespaceUsage::empty(namespace.clone(), day),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_record_job_accumulates_per_namespace_and_day() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("usage.db");
+        let repo = SqliteUsageRepository::new(&path.to_string_lossy()).await.unwrap();
+        let team_a = Namespace::new("team-a").unwrap();
+        let team_b = Namespace::new("team-b").unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+
+        repo.record_job(&team_a, today, 100, 40).await.unwrap();
+        repo.record_job(&team_a, today, 50, 10).await.unwrap();
+        repo.record_job(&team_b, today, 7, 7).await.unwrap();
+
+        let usage = repo.usage_on(&team_a, today).await.unwrap();
+        assert_eq!(usage.jobs(), 2);
+        assert_eq!(usage.input_bytes(), 150);
+        assert_eq!(usage.output_bytes(), 50);
+
+        assert_eq!(repo.usage_on(&team_b, today).await.unwrap().jobs(), 1);
+        assert_eq!(
+            repo.usage_on(&team_a, tomorrow).await.unwrap(),
+            NamespaceUsage::empty(team_a, tomorrow)
+        );
+    }
+}
@@ -12,9 +12,17 @@
 //!
 //! ## Modules
 //!
+//! - **checksum_tee**: Input checksum hashed alongside the stages
+//! - **container_limits**: cgroup and downward-API limit detection
+//! - **inflight_window**: Byte-budgeted flow control from reader to workers
+//! - **priority_lanes**: Token pool that grants waiting jobs by priority
 //! - **resource_manager**: Global resource governance (CPU, I/O, memory)
-//! - **supervisor**: Supervised task spawning with error handling and logging
+//! - **supervisor**: Supervised task spawning with error handling and logging,
+//!   panic isolation for workers, and restart policies for long-running
+//!   components
 //! - **stage_executor**: Pipeline stage execution orchestration
+//! - **stage_registry**: Shared stage services by algorithm name
+//! - **temp_root**: Managed location and crash cleanup for temporary files
 //!
 //! ## Educational Purpose
 //!
@@ -24,13 +32,32 @@
 //! - Prevention of resource oversubscription
 //! - Supervised concurrent task execution
 
+pub mod checksum_tee;
+pub mod container_limits;
+pub mod inflight_window;
+pub mod priority_lanes;
 pub mod resource_manager;
 pub mod stage_executor;
+pub mod stage_registry;
 pub mod supervisor;
+pub mod temp_root;
 
 // Re-export commonly used types
+pub use checksum_tee::{ChecksumFeed, ChecksumTee};
+pub use container_limits::{ContainerLimits, LimitSource};
+pub use inflight_window::{InFlightWindow, WindowPermit, DEFAULT_INFLIGHT_WINDOW};
+pub use priority_lanes::{PriorityPermit, PriorityTokens};
 pub use resource_manager::{
-    init_resource_manager, resource_manager, GlobalResourceManager, ResourceConfig, StorageType, RESOURCE_MANAGER,
+    init_resource_manager, resource_manager, try_resource_manager, GlobalResourceManager, MemoryPermit, ResourceConfig,
+    StorageType, DEFAULT_NETWORK_IO_TOKENS, MEMORY_PERMIT_UNIT, RESOURCE_MANAGER,
 };
 
-pub use supervisor::{join_supervised, spawn_supervised, AppResult};
+pub use stage_registry::StageRegistry;
+pub use supervisor::{
+    catch_panic, join_supervised, panic_message, spawn_supervised, spawn_with_restart, supervise, AppResult,
+    FailureReport, RestartBudget, RestartPolicy, TaskFailure,
+};
+pub use temp_root::{
+    find_orphans, init_temp_root, purge_orphans, release_temp_file, shutdown_temp_root, temp_root, track_temp_file,
+    CleanupReport, OrphanedRun, TempRoot,
+};
@@ -0,0 +1,113 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Checksum Tee
+//!
+//! Checksum of a byte stream, computed on its own blocking thread.
+//!
+//! By default the input checksum is a separate pass over the file before
+//! the stages start. With checksum offload, the reader instead tees each
+//! chunk into a [`ChecksumTee`] as it hands the chunk to the workers, so
+//! hashing overlaps with compression and encryption rather than preceding
+//! them:
+//!
+//! ```text
+//!   reader ──┬──> workers (stages) ──> writer
+//!            └──> checksum thread
+//! ```
+//!
+//! Buffers are hashed in the order they are fed, so a single feeder (the
+//! reader, in file order) yields the digest of the whole stream. The feed
+//! channel is bounded: if hashing falls behind, the reader waits, and at
+//! most `depth` copied chunks are buffered for it.
+
+use crate::infrastructure::adapters::ContentHasher;
+use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+use adaptive_pipeline_domain::PipelineError;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Sends buffers to a [`ChecksumTee`]'s hashing thread
+#[derive(Debug, Clone)]
+pub struct ChecksumFeed {
+    tx: mpsc::Sender<Vec<u8>>,
+}
+
+impl ChecksumFeed {
+    /// Queues a copy of `bytes` for hashing, waiting while the queue is full
+    pub async fn update(&self, bytes: &[u8]) -> Result<(), PipelineError> {
+        self.tx
+            .send(bytes.to_vec())
+            .await
+            .map_err(|_| PipelineError::internal_error("Checksum thread stopped unexpectedly"))
+    }
+}
+
+/// A digest computed concurrently from fed buffers
+#[derive(Debug)]
+pub struct ChecksumTee {
+    feed: ChecksumFeed,
+    digest: JoinHandle<String>,
+}
+
+impl ChecksumTee {
+    /// Starts a thread hashing with `algorithm`, with room for `depth`
+    /// queued buffers
+    pub fn spawn(algorithm: ChecksumAlgorithm, depth: usize) -> Self {
+        let (tx, mut rx) = mpsc::channel::<Vec<u8>>(depth.max(1));
+        let digest = tokio::task::spawn_blocking(move || {
+            let mut hasher = ContentHasher::new(algorithm);
+            while let Some(bytes) = rx.blocking_recv() {
+                hasher.update(&bytes);
+            }
+            hasher.finalize_hex()
+        });
+        Self {
+            feed: ChecksumFeed { tx },
+            digest,
+        }
+    }
+
+    /// Gets a feed for the stream's producer
+    pub fn feed(&self) -> ChecksumFeed {
+        self.feed.clone()
+    }
+
+    /// Returns the hex digest of everything fed
+    ///
+    /// Completes once every [`ChecksumFeed`] has been dropped and the queued
+    /// buffers are hashed.
+    pub async fn finish(self) -> Result<String, PipelineError> {
+        drop(self.feed);
+        self.digest
+            .await
+            .map_err(|e| PipelineError::internal_error(format!("Checksum thread failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_digest_matches_single_pass() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        for algorithm in ChecksumAlgorithm::all() {
+            let tee = ChecksumTee::spawn(algorithm, 2);
+            let feed = tee.feed();
+            let chunks = data.clone();
+            let producer = tokio::spawn(async move {
+                for chunk in chunks.chunks(4096) {
+                    feed.update(chunk).await.unwrap();
+                }
+            });
+            producer.await.unwrap();
+
+            assert_eq!(tee.finish().await.unwrap(), ContentHasher::digest_hex(algorithm, &data));
+        }
+    }
+}
@@ -0,0 +1,337 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Container Resource Limits
+//!
+//! Detects the CPU and memory limits imposed on this process by its
+//! container, so the resource manager sizes its defaults for the container
+//! rather than for the host.
+//!
+//! ## Why?
+//!
+//! A container limited to 2 CPUs on a 64-core host still sees 64 cores in
+//! `/proc/cpuinfo`. Sizing the worker pool for 64 cores then makes 63 workers
+//! compete for 2 CPUs of quota, and the kernel throttles the whole container.
+//! Likewise a 40 GB memory budget is meaningless in a 512 MiB container.
+//!
+//! ## Sources (first match wins, per resource)
+//!
+//! | Source                     | CPU                          | Memory                        |
+//! |----------------------------|------------------------------|-------------------------------|
+//! | Environment hint           | `ADAPIPE_CPU_LIMIT`          | `ADAPIPE_MEMORY_LIMIT`        |
+//! | cgroup v2                  | `cpu.max`                    | `memory.max`                  |
+//! | cgroup v1                  | `cpu.cfs_quota_us` / period  | `memory.limit_in_bytes`       |
+//!
+//! The environment hints are meant for the Kubernetes downward API, which
+//! can expose a pod's limits as environment variables:
+//!
+//! ```yaml
+//! env:
+//!   - name: ADAPIPE_CPU_LIMIT
+//!     valueFrom:
+//!       resourceFieldRef: { resource: limits.cpu, divisor: 1m }
+//!   - name: ADAPIPE_MEMORY_LIMIT
+//!     valueFrom:
+//!       resourceFieldRef: { resource: limits.memory }
+//! ```
+//!
+//! CPU hints accept cores (`2`, `1.5`) or millicores (`1500m`); memory hints
+//! are bytes. cgroup limits are read on Linux only; for cgroup v2 the
+//! tightest limit between the process's own cgroup and the mount root
+//! applies.
+
+use std::fmt;
+use std::path::Path;
+
+/// Environment variable carrying the container's CPU limit
+pub const CPU_LIMIT_ENV: &str = "ADAPIPE_CPU_LIMIT";
+
+/// Environment variable carrying the container's memory limit in bytes
+pub const MEMORY_LIMIT_ENV: &str = "ADAPIPE_MEMORY_LIMIT";
+
+/// cgroup v1 reports "no memory limit" as a huge page-aligned number
+const CGROUP_V1_UNLIMITED_MEMORY: u64 = 1 << 62;
+
+/// Where a detected limit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitSource {
+    Environment,
+    CgroupV2,
+    CgroupV1,
+}
+
+impl fmt::Display for LimitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LimitSource::Environment => "environment",
+            LimitSource::CgroupV2 => "cgroup v2",
+            LimitSource::CgroupV1 => "cgroup v1",
+        })
+    }
+}
+
+/// CPU and memory limits imposed on this process, if any
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ContainerLimits {
+    /// CPU quota in cores (may be fractional)
+    pub cpu_cores: Option<f64>,
+    pub cpu_source: Option<LimitSource>,
+    /// Memory limit in bytes
+    pub memory_bytes: Option<u64>,
+    pub memory_source: Option<LimitSource>,
+}
+
+impl ContainerLimits {
+    /// Detects the limits of the current process
+    pub fn detect() -> Self {
+        let env = Self::from_env(|name| std::env::var(name).ok());
+        env.or(Self::from_cgroups())
+    }
+
+    /// Reads the environment hints through `lookup`
+    pub fn from_env(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let cpu_cores = lookup(CPU_LIMIT_ENV).and_then(|value| parse_cpu_hint(&value));
+        let memory_bytes = lookup(MEMORY_LIMIT_ENV)
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|&bytes| bytes > 0);
+        Self {
+            cpu_cores,
+            cpu_source: cpu_cores.map(|_| LimitSource::Environment),
+            memory_bytes,
+            memory_source: memory_bytes.map(|_| LimitSource::Environment),
+        }
+    }
+
+    /// Reads the cgroup limits of the current process
+    #[cfg(target_os = "linux")]
+    pub fn from_cgroups() -> Self {
+        let self_cgroup = std::fs::read_to_string("/proc/self/cgroup").unwrap_or_default();
+        Self::from_cgroup_fs(Path::new("/sys/fs/cgroup"), &self_cgroup)
+    }
+
+    /// Reads the cgroup limits of the current process
+    #[cfg(not(target_os = "linux"))]
+    pub fn from_cgroups() -> Self {
+        Self::default()
+    }
+
+    /// Reads cgroup limits from a cgroup filesystem mounted at `root`
+    ///
+    /// `self_cgroup` is the content of `/proc/self/cgroup`, used to find the
+    /// process's own cgroup v2 directory.
+    pub fn from_cgroup_fs(root: &Path, self_cgroup: &str) -> Self {
+        if root.join("cgroup.controllers").exists() {
+            Self::from_cgroup_v2(root, self_cgroup)
+        } else {
+            Self::from_cgroup_v1(root)
+        }
+    }
+
+    fn from_cgroup_v2(root: &Path, self_cgroup: &str) -> Self {
+        let own = self_cgroup
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .map(|path| root.join(path.trim().trim_start_matches('/')))
+            .filter(|dir| dir.starts_with(root) && dir.is_dir())
+            .unwrap_or_else(|| root.to_path_buf());
+
+        // A limit on any ancestor applies too, so take the tightest one
+        let mut cpu_cores: Option<f64> = None;
+        let mut memory_bytes: Option<u64> = None;
+        for dir in own.ancestors().take_while(|dir| dir.starts_with(root)) {
+            if let Some(cores) = read(dir, "cpu.max").and_then(|value| parse_cpu_max(&value)) {
+                cpu_cores = Some(cpu_cores.map_or(cores, |current| current.min(cores)));
+            }
+            if let Some(bytes) = read(dir, "memory.max").and_then(|value| parse_memory_max(&value)) {
+                memory_bytes = Some(memory_bytes.map_or(bytes, |current| current.min(bytes)));
+            }
+        }
+
+        Self {
+            cpu_cores,
+            cpu_source: cpu_cores.map(|_| LimitSource::CgroupV2),
+            memory_bytes,
+            memory_source: memory_bytes.map(|_| LimitSource::CgroupV2),
+        }
+    }
+
+    fn from_cgroup_v1(root: &Path) -> Self {
+        let cpu_cores = ["cpu", "cpu,cpuacct", "cpuacct,cpu"].iter().find_map(|controller| {
+            let dir = root.join(controller);
+            let quota = read(&dir, "cpu.cfs_quota_us")?;
+            let period = read(&dir, "cpu.cfs_period_us")?;
+            parse_cfs_quota(&quota, &period)
+        });
+        let memory_bytes = read(&root.join("memory"), "memory.limit_in_bytes")
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|&bytes| bytes > 0 && bytes < CGROUP_V1_UNLIMITED_MEMORY);
+
+        Self {
+            cpu_cores,
+            cpu_source: cpu_cores.map(|_| LimitSource::CgroupV1),
+            memory_bytes,
+            memory_source: memory_bytes.map(|_| LimitSource::CgroupV1),
+        }
+    }
+
+    /// Fills in whatever this has not detected from `other`
+    pub fn or(self, other: Self) -> Self {
+        let (cpu_cores, cpu_source) = match self.cpu_cores {
+            Some(_) => (self.cpu_cores, self.cpu_source),
+            None => (other.cpu_cores, other.cpu_source),
+        };
+        let (memory_bytes, memory_source) = match self.memory_bytes {
+            Some(_) => (self.memory_bytes, self.memory_source),
+            None => (other.memory_bytes, other.memory_source),
+        };
+        Self {
+            cpu_cores,
+            cpu_source,
+            memory_bytes,
+            memory_source,
+        }
+    }
+
+    /// Whether any limit was detected
+    pub fn is_limited(&self) -> bool {
+        self.cpu_cores.is_some() || self.memory_bytes.is_some()
+    }
+
+    /// Cores this process can actually use: the host's cores, capped by the
+    /// CPU quota rounded up (a 1.5-core quota can keep 2 threads busy)
+    pub fn effective_cores(&self, host_cores: usize) -> usize {
+        match self.cpu_cores {
+            Some(cores) => host_cores.min(cores.ceil() as usize).max(1),
+            None => host_cores.max(1),
+        }
+    }
+}
+
+fn read(dir: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(file)).ok()
+}
+
+/// Parses a CPU hint in cores (`2`, `1.5`) or millicores (`1500m`)
+fn parse_cpu_hint(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let cores = match value.strip_suffix('m') {
+        Some(millis) => millis.parse::<f64>().ok()? / 1000.0,
+        None => value.parse::<f64>().ok()?,
+    };
+    (cores.is_finite() && cores > 0.0).then_some(cores)
+}
+
+/// Parses cgroup v2 `cpu.max` (`"<quota> <period>"` or `"max <period>"`)
+fn parse_cpu_max(value: &str) -> Option<f64> {
+    let mut fields = value.split_whitespace();
+    let quota = fields.next()?;
+    let period = fields.next().unwrap_or("100000");
+    parse_cfs_quota(quota, period)
+}
+
+/// Parses a CFS quota and period in microseconds; no quota means no limit
+fn parse_cfs_quota(quota: &str, period: &str) -> Option<f64> {
+    let quota = quota.trim().parse::<i64>().ok().filter(|&quota| quota > 0)?;
+    let period = period.trim().parse::<i64>().ok().filter(|&period| period > 0)?;
+    Some(quota as f64 / period as f64)
+}
+
+/// Parses cgroup v2 `memory.max` (`"max"` or bytes)
+fn parse_memory_max(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok().filter(|&bytes| bytes > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_cpu_max("200000 100000\n"), Some(2.0));
+        assert_eq!(parse_cpu_max("150000 100000"), Some(1.5));
+        assert_eq!(parse_cpu_max("max 100000"), None);
+        assert_eq!(parse_cfs_quota("-1", "100000"), None);
+        assert_eq!(parse_cfs_quota("50000", "100000"), Some(0.5));
+        assert_eq!(parse_memory_max("536870912\n"), Some(536_870_912));
+        assert_eq!(parse_memory_max("max"), None);
+        assert_eq!(parse_cpu_hint("1500m"), Some(1.5));
+        assert_eq!(parse_cpu_hint("3"), Some(3.0));
+        assert_eq!(parse_cpu_hint("0"), None);
+        assert_eq!(parse_cpu_hint("lots"), None);
+    }
+
+    #[test]
+    fn test_environment_hints() {
+        let env: HashMap<&str, &str> = [(CPU_LIMIT_ENV, "2500m"), (MEMORY_LIMIT_ENV, "1073741824")].into();
+        let limits = ContainerLimits::from_env(|name| env.get(name).map(|value| value.to_string()));
+
+        assert_eq!(limits.cpu_cores, Some(2.5));
+        assert_eq!(limits.memory_bytes, Some(1 << 30));
+        assert_eq!(limits.cpu_source, Some(LimitSource::Environment));
+        assert_eq!(limits.effective_cores(64), 3);
+        assert_eq!(limits.effective_cores(2), 2);
+    }
+
+    #[test]
+    fn test_cgroup_v2_takes_tightest_ancestor_limit() {
+        let root = TempDir::new().unwrap();
+        let own = root.path().join("kubepods/pod1");
+        std::fs::create_dir_all(&own).unwrap();
+        std::fs::write(root.path().join("cgroup.controllers"), "cpu memory").unwrap();
+        std::fs::write(root.path().join("kubepods/cpu.max"), "100000 100000").unwrap();
+        std::fs::write(own.join("cpu.max"), "400000 100000").unwrap();
+        std::fs::write(own.join("memory.max"), "268435456").unwrap();
+
+        let limits = ContainerLimits::from_cgroup_fs(root.path(), "0::/kubepods/pod1\n");
+
+        assert_eq!(limits.cpu_cores, Some(1.0));
+        assert_eq!(limits.memory_bytes, Some(256 << 20));
+        assert_eq!(limits.memory_source, Some(LimitSource::CgroupV2));
+    }
+
+    #[test]
+    fn test_cgroup_v1_limits() {
+        let root = TempDir::new().unwrap();
+        let cpu = root.path().join("cpu,cpuacct");
+        let memory = root.path().join("memory");
+        std::fs::create_dir_all(&cpu).unwrap();
+        std::fs::create_dir_all(&memory).unwrap();
+        std::fs::write(cpu.join("cpu.cfs_quota_us"), "300000").unwrap();
+        std::fs::write(cpu.join("cpu.cfs_period_us"), "100000").unwrap();
+        std::fs::write(memory.join("memory.limit_in_bytes"), "9223372036854771712").unwrap();
+
+        let limits = ContainerLimits::from_cgroup_fs(root.path(), "");
+
+        assert_eq!(limits.cpu_cores, Some(3.0));
+        assert_eq!(limits.cpu_source, Some(LimitSource::CgroupV1));
+        assert_eq!(limits.memory_bytes, None);
+        assert!(limits.is_limited());
+    }
+
+    #[test]
+    fn test_environment_overrides_cgroups_per_resource() {
+        let env = ContainerLimits {
+            cpu_cores: Some(1.0),
+            cpu_source: Some(LimitSource::Environment),
+            ..Default::default()
+        };
+        let cgroup = ContainerLimits {
+            cpu_cores: Some(4.0),
+            cpu_source: Some(LimitSource::CgroupV2),
+            memory_bytes: Some(1 << 30),
+            memory_source: Some(LimitSource::CgroupV2),
+        };
+
+        let limits = env.or(cgroup);
+
+        assert_eq!(limits.cpu_cores, Some(1.0));
+        assert_eq!(limits.memory_bytes, Some(1 << 30));
+        assert_eq!(limits.memory_source, Some(LimitSource::CgroupV2));
+    }
+}
@@ -0,0 +1,165 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # In-Flight Window
+//!
+//! Byte-budgeted flow control between the reader and the CPU workers.
+//!
+//! A fixed channel depth bounds chunks, not bytes: four 1 MiB chunks and
+//! four 512 MiB chunks queue alike. The window instead bounds the chunk data
+//! a file has in flight (read ahead, queued, or being processed) to a byte
+//! budget, 256 MiB by default:
+//!
+//! ```text
+//!   chunk size   chunks in flight (256 MiB window)
+//!   1 MiB        256   small chunks pipeline deeply
+//!   64 MiB       4
+//!   512 MiB      1     a chunk larger than the window still fits alone
+//! ```
+//!
+//! Every chunk also reserves its bytes from the global memory budget (see
+//! [`GlobalResourceManager::acquire_memory`]), so concurrent files together
+//! stay within the process's memory capacity.
+//!
+//! ## Ordering
+//!
+//! The reader acquires a chunk's permit before issuing its read, strictly in
+//! file order. The oldest chunk waiting for memory therefore always holds
+//! its permit before any later chunk, and workers always have a chunk to
+//! finish and release; a window smaller than the read-ahead depth cannot
+//! deadlock.
+//!
+//! [`GlobalResourceManager::acquire_memory`]:
+//!     crate::infrastructure::runtime::GlobalResourceManager::acquire_memory
+
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::PipelineError;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::resource_manager::{memory_permits, try_resource_manager, MemoryPermit, MEMORY_PERMIT_UNIT};
+
+/// Default byte budget for one file's chunk data in flight
+pub const DEFAULT_INFLIGHT_WINDOW: usize = 256 * 1024 * 1024;
+
+/// Upper bound on the chunk count a window implies, so a tiny chunk size
+/// cannot size a channel with millions of slots
+const MAX_WINDOW_CHUNKS: usize = 1024;
+
+/// A file's byte budget for chunk data in flight
+#[derive(Debug)]
+pub struct InFlightWindow {
+    permits: Arc<Semaphore>,
+    budget: usize,
+    chunk_size: usize,
+}
+
+/// One chunk's share of the window and of the global memory budget,
+/// released when dropped
+#[derive(Debug)]
+pub struct WindowPermit {
+    _window: OwnedSemaphorePermit,
+    _memory: Option<MemoryPermit<'static>>,
+    bytes: usize,
+}
+
+impl InFlightWindow {
+    /// Creates a window of `budget` bytes for chunks of `chunk_size` bytes
+    ///
+    /// The budget is raised to one chunk if smaller, so every chunk fits.
+    pub fn new(budget: usize, chunk_size: usize) -> Self {
+        let budget = budget.max(chunk_size).max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(memory_permits(budget) as usize)),
+            budget,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// The byte budget
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// How many whole chunks fit in the window, at least one
+    pub fn chunk_capacity(&self) -> usize {
+        (self.budget / self.chunk_size).clamp(1, MAX_WINDOW_CHUNKS)
+    }
+
+    /// Bytes of the window not currently reserved
+    pub fn available(&self) -> usize {
+        self.permits.available_permits().saturating_mul(MEMORY_PERMIT_UNIT)
+    }
+
+    /// Reserves `bytes` of the window, then of the global memory budget
+    ///
+    /// Waits while either is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternalError` if a semaphore has been closed.
+    pub async fn acquire(&self, bytes: usize) -> Result<WindowPermit, PipelineError> {
+        let permits = memory_permits(bytes).min(memory_permits(self.budget));
+        let window = self
+            .permits
+            .clone()
+            .acquire_many_owned(permits)
+            .await
+            .map_err(|_| PipelineError::InternalError("In-flight window closed".to_string()))?;
+        let memory = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_memory(bytes).await?),
+            None => None,
+        };
+        Ok(WindowPermit {
+            _window: window,
+            _memory: memory,
+            bytes,
+        })
+    }
+}
+
+impl WindowPermit {
+    /// Bytes this permit reserves
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: usize = 1024 * 1024;
+
+    #[test]
+    fn test_window_sizes_chunk_capacity_by_bytes() {
+        assert_eq!(InFlightWindow::new(256 * MIB, MIB).chunk_capacity(), 256);
+        assert_eq!(InFlightWindow::new(256 * MIB, 64 * MIB).chunk_capacity(), 4);
+        assert_eq!(InFlightWindow::new(256 * MIB, 512 * MIB).chunk_capacity(), 1);
+        assert_eq!(InFlightWindow::new(256 * MIB, 512 * MIB).budget(), 512 * MIB);
+        assert_eq!(InFlightWindow::new(256 * MIB, 4096).chunk_capacity(), MAX_WINDOW_CHUNKS);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_released_bytes() {
+        let window = InFlightWindow::new(4 * MIB, MIB);
+
+        let held: Vec<_> = futures::future::try_join_all((0..4).map(|_| window.acquire(MIB)))
+            .await
+            .unwrap();
+        assert_eq!(window.available(), 0);
+
+        let next = window.acquire(MIB);
+        tokio::pin!(next);
+        assert!(futures::poll!(next.as_mut()).is_pending());
+
+        drop(held);
+        let permit = next.await.unwrap();
+        assert_eq!(permit.bytes(), MIB);
+        assert_eq!(window.available(), 3 * MIB);
+    }
+}
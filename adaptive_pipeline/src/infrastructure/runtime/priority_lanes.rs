@@ -0,0 +1,306 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Priority Lanes
+//!
+//! A counting token pool whose waiters queue in one lane per
+//! [`JobPriority`]. It backs the CPU and I/O tokens of
+//! [`GlobalResourceManager`].
+//!
+//! A plain semaphore hands released tokens out first come, first served, so
+//! a restore someone is waiting on queues behind every chunk of a large
+//! batch run. Here a released token goes to a lane chosen by smooth
+//! weighted round-robin over the lanes that have waiters:
+//!
+//! ```text
+//!   waiting lanes             grants, in order
+//!   interactive (16), batch   I I I I I I I I B I I I I I I I I ...
+//!   normal (4), batch (1)     N N B N N N N B N N ...   (4 of every 5 to normal)
+//!   batch only                B B B B ...
+//! ```
+//!
+//! A higher class therefore gets tokens before a lower one, and a lower one
+//! still progresses at its share. Within a lane waiters are served in
+//! order. A token is only queued for when none is free, so an idle machine
+//! serves every class immediately.
+//!
+//! ## Cancellation
+//!
+//! A waiter dropped before its token arrives leaves the queue; one dropped
+//! after its token arrived returns the token, so cancelling an acquisition
+//! never leaks capacity.
+//!
+//! [`GlobalResourceManager`]: super::GlobalResourceManager
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use adaptive_pipeline_domain::value_objects::JobPriority;
+use adaptive_pipeline_domain::PipelineError;
+use tokio::sync::oneshot;
+
+/// Waiters of one priority class
+#[derive(Debug, Default)]
+struct Lane {
+    waiters: VecDeque<oneshot::Sender<()>>,
+    /// Smooth weighted round-robin credit while the lane has waiters
+    credit: i64,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    available: usize,
+    /// One lane per priority, in [`JobPriority::all`] order (highest first)
+    lanes: [Lane; 3],
+}
+
+/// A counting token pool with weighted priority lanes
+#[derive(Debug)]
+pub struct PriorityTokens {
+    state: Mutex<PoolState>,
+    total: usize,
+}
+
+/// A token from a [`PriorityTokens`] pool, returned when dropped
+#[derive(Debug)]
+pub struct PriorityPermit<'a> {
+    tokens: &'a PriorityTokens,
+}
+
+/// A queued acquisition; returns a token that arrived after it was dropped
+struct Waiter<'a> {
+    receiver: oneshot::Receiver<()>,
+    tokens: &'a PriorityTokens,
+}
+
+fn lane_index(priority: JobPriority) -> usize {
+    match priority {
+        JobPriority::Interactive => 0,
+        JobPriority::Normal => 1,
+        JobPriority::Batch => 2,
+    }
+}
+
+impl PoolState {
+    /// Picks the lane the next token goes to, or `None` if nobody waits
+    fn next_lane(&mut self) -> Option<usize> {
+        let mut total_weight = 0;
+        let mut best: Option<(usize, i64)> = None;
+        for (index, priority) in JobPriority::all().into_iter().enumerate() {
+            let lane = &mut self.lanes[index];
+            // Waiters that gave up are skipped rather than granted
+            while lane.waiters.front().is_some_and(|waiter| waiter.is_closed()) {
+                lane.waiters.pop_front();
+            }
+            if lane.waiters.is_empty() {
+                lane.credit = 0;
+                continue;
+            }
+            lane.credit += i64::from(priority.weight());
+            total_weight += i64::from(priority.weight());
+            // Ties go to the higher priority, which comes first
+            if best.is_none_or(|(_, credit)| lane.credit > credit) {
+                best = Some((index, lane.credit));
+            }
+        }
+        let (best, _) = best?;
+        self.lanes[best].credit -= total_weight;
+        Some(best)
+    }
+}
+
+impl PriorityTokens {
+    /// Creates a pool of `total` tokens, all free
+    pub fn new(total: usize) -> Self {
+        Self {
+            state: Mutex::new(PoolState {
+                available: total,
+                lanes: Default::default(),
+            }),
+            total,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes a token, waiting in `priority`'s lane while none is free
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternalError` if the pool stops handing out tokens, which
+    /// only happens if it is dropped while this acquisition waits.
+    pub async fn acquire(&self, priority: JobPriority) -> Result<PriorityPermit<'_>, PipelineError> {
+        let receiver = {
+            let mut state = self.lock();
+            if state.available > 0 {
+                state.available -= 1;
+                return Ok(PriorityPermit { tokens: self });
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.lanes[lane_index(priority)].waiters.push_back(sender);
+            receiver
+        };
+
+        let mut waiter = Waiter { receiver, tokens: self };
+        (&mut waiter.receiver)
+            .await
+            .map_err(|_| PipelineError::InternalError("Token pool closed".to_string()))?;
+        Ok(PriorityPermit { tokens: self })
+    }
+
+    /// Hands a returned token to the next waiter, or frees it
+    fn release(&self) {
+        let mut state = self.lock();
+        while let Some(lane) = state.next_lane() {
+            if let Some(waiter) = state.lanes[lane].waiters.pop_front() {
+                if waiter.send(()).is_ok() {
+                    return;
+                }
+            }
+        }
+        state.available += 1;
+    }
+
+    /// Tokens not currently held
+    pub fn available_permits(&self) -> usize {
+        self.lock().available
+    }
+
+    /// Tokens in the pool
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Acquisitions waiting in `priority`'s lane
+    pub fn waiting(&self, priority: JobPriority) -> usize {
+        self.lock().lanes[lane_index(priority)]
+            .waiters
+            .iter()
+            .filter(|waiter| !waiter.is_closed())
+            .count()
+    }
+}
+
+impl Drop for PriorityPermit<'_> {
+    fn drop(&mut self) {
+        self.tokens.release();
+    }
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        // After close() no token can arrive; one that already did is returned
+        self.receiver.close();
+        if self.receiver.try_recv().is_ok() {
+            self.tokens.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Spawns `count` acquisitions at `priority`, each recording itself in
+    /// `order` once granted and then returning its token
+    fn queue(
+        tokens: &Arc<PriorityTokens>,
+        priority: JobPriority,
+        count: usize,
+        order: &Arc<Mutex<Vec<JobPriority>>>,
+    ) -> Vec<tokio::task::JoinHandle<()>> {
+        (0..count)
+            .map(|_| {
+                let tokens = tokens.clone();
+                let order = order.clone();
+                tokio::spawn(async move {
+                    let _permit = tokens.acquire(priority).await.unwrap();
+                    order.lock().unwrap().push(priority);
+                })
+            })
+            .collect()
+    }
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interactive_waiters_get_tokens_before_batch() {
+        let tokens = Arc::new(PriorityTokens::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let held = tokens.acquire(JobPriority::Batch).await.unwrap();
+        let mut tasks = queue(&tokens, JobPriority::Batch, 4, &order);
+        settle().await;
+        tasks.extend(queue(&tokens, JobPriority::Interactive, 4, &order));
+        settle().await;
+        assert_eq!(tokens.waiting(JobPriority::Batch), 4);
+        assert_eq!(tokens.waiting(JobPriority::Interactive), 4);
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // Batch queued first, yet every interactive waiter went ahead of it
+        let order = order.lock().unwrap().clone();
+        assert_eq!(&order[..4], &[JobPriority::Interactive; 4]);
+        assert_eq!(&order[4..], &[JobPriority::Batch; 4]);
+        assert_eq!(tokens.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lower_lane_keeps_its_weighted_share() {
+        let tokens = Arc::new(PriorityTokens::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let held = tokens.acquire(JobPriority::Normal).await.unwrap();
+        let mut tasks = queue(&tokens, JobPriority::Normal, 8, &order);
+        tasks.extend(queue(&tokens, JobPriority::Batch, 2, &order));
+        settle().await;
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // Normal (4) against batch (1): batch gets one of every five grants
+        let order = order.lock().unwrap().clone();
+        let batch_grants: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .filter(|(_, priority)| **priority == JobPriority::Batch)
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(batch_grants, vec![2, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_leak_a_token() {
+        let tokens = PriorityTokens::new(1);
+        let held = tokens.acquire(JobPriority::Normal).await.unwrap();
+
+        let mut waiting = Box::pin(tokens.acquire(JobPriority::Interactive));
+        assert!(futures::poll!(waiting.as_mut()).is_pending());
+        assert_eq!(tokens.waiting(JobPriority::Interactive), 1);
+
+        // The token reaches the waiter, which is dropped before it runs
+        drop(held);
+        drop(waiting);
+        assert_eq!(tokens.available_permits(), 1);
+        assert_eq!(tokens.waiting(JobPriority::Interactive), 0);
+
+        let _again = tokens.acquire(JobPriority::Batch).await.unwrap();
+        assert_eq!(tokens.available_permits(), 0);
+    }
+}
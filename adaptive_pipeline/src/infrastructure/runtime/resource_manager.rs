@@ -46,24 +46,53 @@
 //!
 //! ### CPU Tokens
 //! - **Purpose:** Limit total CPU-bound work across all files
-//! - **Default:** `available_cores - 1` (leave one for OS/I/O)
+//! - **Default:** `available_cores - 1` (leave one for OS/I/O), where
+//!   `available_cores` is capped by any container CPU quota
 //! - **Use:** Acquire before Rayon work or CPU-intensive operations
 //!
 //! ### I/O Tokens
 //! - **Purpose:** Prevent I/O queue overrun
 //! - **Default:** Device-specific (NVMe: 24, SSD: 12, HDD: 4)
-//! - **Use:** Acquire before file reads/writes
+//! - **Use:** Acquire before local file reads/writes
 //!
-//! ### Memory Tracking
-//! - **Purpose:** Monitor memory usage (gauge only, no enforcement yet)
-//! - **Default:** No limit (soft monitoring)
-//! - **Future:** Can add hard cap in Phase 3
-
+//! ### Network I/O Tokens
+//! - **Purpose:** Cap concurrent requests to remote backends (HTTP, object
+//!   stores)
+//! - **Default:** 16, independent of the local storage type
+//! - **Use:** Acquire before each remote request
+//!
+//! Local and network I/O draw from separate pools, so a job reading a slow
+//! remote source cannot hold the tokens a local job needs, and the reverse.
+//!
+//! CPU and I/O tokens are granted by job priority: while jobs of several
+//! [`JobPriority`] classes wait, an interactive job gets tokens before a
+//! batch job in proportion to their weights (see
+//! [`priority_lanes`](super::priority_lanes)).
+//!
+//! ### Memory Budget
+//! - **Purpose:** Cap the chunk data in flight across all files
+//! - **Default:** The container's memory limit if any, otherwise 40 GB
+//! - **Use:** Acquire with [`GlobalResourceManager::acquire_memory`] before
+//!   reading a chunk; other allocations are tracked as a gauge only
+
+use super::container_limits::{ContainerLimits, LimitSource};
+use super::priority_lanes::{PriorityPermit, PriorityTokens};
+use adaptive_pipeline_domain::value_objects::JobPriority;
 use adaptive_pipeline_domain::PipelineError;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::{Semaphore, SemaphorePermit};
 
+/// Bytes per memory budget permit
+///
+/// Semaphores count in `u32` permits; budgeting in KiB covers up to 4 TiB.
+pub const MEMORY_PERMIT_UNIT: usize = 1024;
+
+/// Number of [`MEMORY_PERMIT_UNIT`] permits covering `bytes`
+pub(crate) fn memory_permits(bytes: usize) -> u32 {
+    u32::try_from(bytes.div_ceil(MEMORY_PERMIT_UNIT)).unwrap_or(u32::MAX)
+}
+
 /// Storage device type for I/O queue depth optimization
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StorageType {
@@ -73,21 +102,65 @@ pub enum StorageType {
     Ssd,
     /// Hard Disk Drive - Low queue depth (2-4)
     Hdd,
+    /// Network filesystem - Medium queue depth, deep read-ahead
+    Network,
     /// Auto-detect based on system
     Auto,
     /// Custom queue depth
     Custom(usize),
 }
 
+impl StorageType {
+    /// Default read-ahead depth in chunks (the reader's channel depth)
+    ///
+    /// ## Educational: Hiding Latency
+    ///
+    /// Read-ahead only needs to cover the time until the next chunk arrives.
+    /// Flash answers in microseconds, so a shallow queue suffices; disks pay
+    /// for seeks and network filesystems for round trips, so they read
+    /// further ahead to keep workers busy.
+    pub fn read_ahead_chunks(&self) -> usize {
+        match self {
+            StorageType::NVMe | StorageType::Ssd | StorageType::Auto => 4,
+            StorageType::Hdd => 8,
+            StorageType::Network => 16,
+            StorageType::Custom(n) => (*n).clamp(4, 16),
+        }
+    }
+}
+
+impl std::fmt::Display for StorageType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageType::NVMe => write!(f, "nvme"),
+            StorageType::Ssd => write!(f, "ssd"),
+            StorageType::Hdd => write!(f, "hdd"),
+            StorageType::Network => write!(f, "network"),
+            StorageType::Auto => write!(f, "auto"),
+            StorageType::Custom(depth) => write!(f, "custom-{}", depth),
+        }
+    }
+}
+
+/// Network I/O tokens when not configured
+///
+/// Remote throughput is bound by round trips rather than a device queue, so
+/// this does not depend on the local storage type.
+pub const DEFAULT_NETWORK_IO_TOKENS: usize = 16;
+
 /// Configuration for global resource manager
 #[derive(Debug, Clone)]
 pub struct ResourceConfig {
     /// Number of CPU worker tokens (default: cores - 1)
     pub cpu_tokens: Option<usize>,
 
-    /// Number of I/O tokens (default: device-specific)
+    /// Number of local I/O tokens (default: device-specific)
     pub io_tokens: Option<usize>,
 
+    /// Number of network I/O tokens for remote backends (default:
+    /// [`DEFAULT_NETWORK_IO_TOKENS`])
+    pub network_io_tokens: Option<usize>,
+
     /// Storage device type for I/O optimization
     pub storage_type: StorageType,
 
@@ -100,6 +173,7 @@ impl Default for ResourceConfig {
         Self {
             cpu_tokens: None, // Will use cores - 1
             io_tokens: None,  // Will use device-specific
+            network_io_tokens: None,
             storage_type: StorageType::Auto,
             memory_limit: None, // No limit by default
         }
@@ -124,33 +198,49 @@ impl Default for ResourceConfig {
 /// - CPU work and I/O work have different characteristics
 /// - CPU: Limited by cores, benefits from parallelism = cores
 /// - I/O: Limited by device queue depth, different optimal values
+/// - Network I/O: Limited by round trips to a remote backend, so it has its
+///   own pool rather than sharing the local device's
 ///
-/// **Why memory as gauge only?**
-/// - Memory is harder to predict and control
-/// - Start with monitoring, add enforcement later if needed
-/// - Avoids complexity in Phase 1
+/// **Why budget only chunk data?**
+/// - Chunk buffers dominate memory use and their sizes are known up front
+/// - Other allocations are harder to predict, so they remain a gauge
 pub struct GlobalResourceManager {
     /// CPU worker tokens (semaphore permits)
     ///
     /// **Purpose:** Prevent CPU oversubscription
     /// **Typical value:** cores - 1
-    /// **Educational:** This is a "counting semaphore" that allows N concurrent
-    /// operations
-    cpu_tokens: Arc<Semaphore>,
+    /// **Educational:** A counting semaphore that allows N concurrent
+    /// operations, with waiters queued by job priority
+    cpu_tokens: PriorityTokens,
 
     /// I/O operation tokens (semaphore permits)
     ///
     /// **Purpose:** Prevent I/O queue overrun
     /// **Typical value:** Device-specific (NVMe: 24, SSD: 12, HDD: 4)
     /// **Educational:** Different devices have different optimal queue depths
-    io_tokens: Arc<Semaphore>,
+    io_tokens: PriorityTokens,
+
+    /// Network I/O tokens for remote backends (semaphore permits)
+    ///
+    /// **Purpose:** Bound concurrent remote requests without consuming local
+    /// I/O tokens
+    /// **Typical value:** [`DEFAULT_NETWORK_IO_TOKENS`]
+    network_io_tokens: PriorityTokens,
 
     /// Memory usage gauge (bytes)
     ///
-    /// **Purpose:** Monitor memory pressure (no enforcement yet)
-    /// **Educational:** Start simple (gauge), add limits later (Phase 3)
+    /// **Purpose:** Monitor memory pressure
+    /// **Educational:** Includes budgeted chunk data and tracked allocations
     memory_used: Arc<AtomicUsize>,
 
+    /// Memory budget for chunk data in flight (permits of
+    /// [`MEMORY_PERMIT_UNIT`] bytes)
+    ///
+    /// **Purpose:** Bound chunk data across all files to the memory capacity
+    /// **Educational:** Readers wait here, so memory pressure slows reading
+    /// instead of growing the heap
+    memory_budget: Semaphore,
+
     /// Total memory capacity for reporting
     memory_capacity: usize,
 
@@ -159,6 +249,12 @@ pub struct GlobalResourceManager {
 
     /// Number of I/O tokens configured
     io_token_count: usize,
+
+    /// Number of network I/O tokens configured
+    network_io_token_count: usize,
+
+    /// Storage type the I/O defaults were sized for
+    storage_type: StorageType,
 }
 
 impl GlobalResourceManager {
@@ -185,8 +281,22 @@ impl GlobalResourceManager {
     /// })?;
     /// ```
     pub fn new(config: ResourceConfig) -> Result<Self, PipelineError> {
+        Self::with_container_limits(config, ContainerLimits::detect())
+    }
+
+    /// Creates a resource manager whose defaults respect the given container
+    /// limits
+    ///
+    /// ## Educational: Containers Lie About Cores
+    ///
+    /// Inside a container, the host's cores stay visible even when a CPU quota
+    /// allows only a fraction of them. Defaults are therefore computed from
+    /// the quota (rounded up), and the memory budget from the container's
+    /// memory limit. Explicit values in `config` always win.
+    pub fn with_container_limits(config: ResourceConfig, limits: ContainerLimits) -> Result<Self, PipelineError> {
         // Detect available CPU cores
-        let available_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4); // Conservative fallback
+        let host_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4); // Conservative fallback
+        let available_cores = limits.effective_cores(host_cores);
 
         // Educational: Why cores - 1?
         // Leave one core for OS, I/O threads, and system tasks
@@ -194,23 +304,65 @@ impl GlobalResourceManager {
         let cpu_token_count = config.cpu_tokens.unwrap_or_else(|| (available_cores - 1).max(1));
 
         // Educational: Device-specific I/O queue depths
-        // Different storage devices have different optimal concurrency levels
-        let io_token_count = config
-            .io_tokens
-            .unwrap_or_else(|| Self::detect_optimal_io_tokens(config.storage_type));
+        // Different storage devices have different optimal concurrency levels.
+        // Under a CPU quota, more in-flight I/O than a few per usable core
+        // only queues up data no worker can consume yet.
+        let io_token_count = config.io_tokens.unwrap_or_else(|| {
+            let device_tokens = Self::detect_optimal_io_tokens(config.storage_type);
+            match limits.cpu_cores {
+                Some(_) => device_tokens.min((available_cores * 4).max(4)),
+                None => device_tokens,
+            }
+        });
+
+        // Educational: Remote requests mostly wait on the network, so their
+        // pool is sized on its own, with the same cap under a CPU quota
+        let network_io_token_count = config.network_io_tokens.unwrap_or(match limits.cpu_cores {
+            Some(_) => DEFAULT_NETWORK_IO_TOKENS.min((available_cores * 4).max(4)),
+            None => DEFAULT_NETWORK_IO_TOKENS,
+        });
 
         // Educational: Memory capacity detection
-        // On most systems, we can query available RAM
-        // For now, use a conservative default if not specified
-        let memory_capacity = config.memory_limit.unwrap_or(40 * 1024 * 1024 * 1024); // 40GB default
+        // A container's memory limit is the real capacity; otherwise
+        // use a conservative default if not specified
+        let container_memory = limits.memory_bytes.and_then(|bytes| usize::try_from(bytes).ok());
+        let memory_capacity = config
+            .memory_limit
+            .or(container_memory)
+            .unwrap_or(40 * 1024 * 1024 * 1024); // 40GB default
+
+        if limits.is_limited() {
+            tracing::info!(
+                "Container limits detected (cpu: {}, memory: {}); using {} CPU tokens, {} I/O tokens, {} network \
+                 I/O tokens, {} bytes memory capacity",
+                describe_limit(
+                    limits.cpu_cores.map(|cores| format!("{:.2} cores", cores)),
+                    limits.cpu_source
+                ),
+                describe_limit(
+                    limits.memory_bytes.map(|bytes| format!("{} bytes", bytes)),
+                    limits.memory_source
+                ),
+                cpu_token_count,
+                io_token_count,
+                network_io_token_count,
+                memory_capacity
+            );
+        } else {
+            tracing::debug!("No container limits detected; sizing for {} host cores", host_cores);
+        }
 
         Ok(Self {
-            cpu_tokens: Arc::new(Semaphore::new(cpu_token_count)),
-            io_tokens: Arc::new(Semaphore::new(io_token_count)),
+            cpu_tokens: PriorityTokens::new(cpu_token_count),
+            io_tokens: PriorityTokens::new(io_token_count),
+            network_io_tokens: PriorityTokens::new(network_io_token_count),
             memory_used: Arc::new(AtomicUsize::new(0)),
+            memory_budget: Semaphore::new(memory_permits(memory_capacity) as usize),
             memory_capacity,
             cpu_token_count,
             io_token_count,
+            network_io_token_count,
+            storage_type: config.storage_type,
         })
     }
 
@@ -232,15 +384,20 @@ impl GlobalResourceManager {
     /// - Sequential access preferred
     /// - High seek latency
     /// - Low queue depth prevents thrashing
+    ///
+    /// **Network (16 tokens):**
+    /// - Throughput bound by round trips, not the device
+    /// - Concurrent requests hide latency
     fn detect_optimal_io_tokens(storage_type: StorageType) -> usize {
         match storage_type {
             StorageType::NVMe => 24,
             StorageType::Ssd => 12,
             StorageType::Hdd => 4,
+            StorageType::Network => 16,
             StorageType::Auto => {
-                // Educational: Simple heuristic
-                // In production, would query device capabilities
-                // For now, assume SSD as reasonable default
+                // Educational: Detection happens at startup (see the
+                // platform's `detect_storage`); if it could not tell,
+                // assume SSD as reasonable default
                 12
             }
             StorageType::Custom(n) => n,
@@ -269,12 +426,25 @@ impl GlobalResourceManager {
     ///
     /// If all CPU tokens are in use, this method **waits** until one becomes
     /// available. This creates natural backpressure and prevents
-    /// oversubscription.
-    pub async fn acquire_cpu(&self) -> Result<SemaphorePermit<'_>, PipelineError> {
+    /// oversubscription. The wait is at [`JobPriority::Normal`]; see
+    /// [`acquire_cpu_at`](Self::acquire_cpu_at).
+    pub async fn acquire_cpu(&self) -> Result<PriorityPermit<'_>, PipelineError> {
+        self.acquire_cpu_at(JobPriority::Normal).await
+    }
+
+    /// Acquire a CPU token in `priority`'s lane
+    ///
+    /// ## Educational: Weighted Acquisition
+    ///
+    /// While jobs of several priorities wait, released tokens are shared out
+    /// by weight (see [`priority_lanes`](super::priority_lanes)): an
+    /// interactive restore gets tokens ahead of a batch run without stopping
+    /// it entirely.
+    pub async fn acquire_cpu_at(&self, priority: JobPriority) -> Result<PriorityPermit<'_>, PipelineError> {
         self.cpu_tokens
-            .acquire()
+            .acquire(priority)
             .await
-            .map_err(|_| PipelineError::InternalError("CPU semaphore closed".to_string()))
+            .map_err(|_| PipelineError::InternalError("CPU token pool closed".to_string()))
     }
 
     /// Acquire an I/O token
@@ -292,11 +462,66 @@ impl GlobalResourceManager {
     /// // Do I/O operation (read/write)
     /// // Permit auto-released
     /// ```
-    pub async fn acquire_io(&self) -> Result<SemaphorePermit<'_>, PipelineError> {
+    pub async fn acquire_io(&self) -> Result<PriorityPermit<'_>, PipelineError> {
+        self.acquire_io_at(JobPriority::Normal).await
+    }
+
+    /// Acquire an I/O token in `priority`'s lane
+    pub async fn acquire_io_at(&self, priority: JobPriority) -> Result<PriorityPermit<'_>, PipelineError> {
         self.io_tokens
-            .acquire()
+            .acquire(priority)
+            .await
+            .map_err(|_| PipelineError::InternalError("I/O token pool closed".to_string()))
+    }
+
+    /// Acquire a network I/O token for one request to a remote backend
+    ///
+    /// ## Educational: Separate pools for local and remote I/O
+    ///
+    /// A remote request can take far longer than a local read. Drawing it
+    /// from its own pool means slow remote sources only queue behind each
+    /// other, never behind (or in front of) local file I/O.
+    pub async fn acquire_network_io(&self) -> Result<PriorityPermit<'_>, PipelineError> {
+        self.acquire_network_io_at(JobPriority::Normal).await
+    }
+
+    /// Acquire a network I/O token in `priority`'s lane
+    pub async fn acquire_network_io_at(&self, priority: JobPriority) -> Result<PriorityPermit<'_>, PipelineError> {
+        self.network_io_tokens
+            .acquire(priority)
+            .await
+            .map_err(|_| PipelineError::InternalError("Network I/O token pool closed".to_string()))
+    }
+
+    /// Reserve `bytes` of the memory budget for chunk data
+    ///
+    /// ## Backpressure
+    ///
+    /// Waits while the budget is exhausted, so readers across all files
+    /// slow down instead of overcommitting memory. A request larger than the
+    /// whole capacity reserves all of it rather than waiting forever. The
+    /// reservation counts toward [`memory_used`](Self::memory_used) and is
+    /// returned when the permit drops.
+    pub async fn acquire_memory(&self, bytes: usize) -> Result<MemoryPermit<'_>, PipelineError> {
+        let permits = memory_permits(bytes).min(memory_permits(self.memory_capacity));
+        let permit = self
+            .memory_budget
+            .acquire_many(permits)
             .await
-            .map_err(|_| PipelineError::InternalError("I/O semaphore closed".to_string()))
+            .map_err(|_| PipelineError::InternalError("Memory semaphore closed".to_string()))?;
+        self.allocate_memory(bytes);
+        Ok(MemoryPermit {
+            _permit: permit,
+            bytes,
+            memory_used: &self.memory_used,
+        })
+    }
+
+    /// Get the memory budget left for chunk data, in bytes
+    pub fn memory_available(&self) -> usize {
+        self.memory_budget
+            .available_permits()
+            .saturating_mul(MEMORY_PERMIT_UNIT)
     }
 
     /// Track memory allocation (gauge only, no enforcement)
@@ -352,6 +577,45 @@ impl GlobalResourceManager {
     pub fn io_tokens_total(&self) -> usize {
         self.io_token_count
     }
+
+    /// Get number of available network I/O tokens
+    pub fn network_io_tokens_available(&self) -> usize {
+        self.network_io_tokens.available_permits()
+    }
+
+    /// Get total number of network I/O tokens
+    pub fn network_io_tokens_total(&self) -> usize {
+        self.network_io_token_count
+    }
+
+    /// Get the number of CPU token acquisitions waiting at `priority`
+    pub fn cpu_tokens_waiting(&self, priority: JobPriority) -> usize {
+        self.cpu_tokens.waiting(priority)
+    }
+
+    /// Get the storage type I/O defaults were sized for
+    pub fn storage_type(&self) -> StorageType {
+        self.storage_type
+    }
+
+    /// Get the default read-ahead depth in chunks for this storage
+    pub fn read_ahead_chunks(&self) -> usize {
+        self.storage_type.read_ahead_chunks()
+    }
+}
+
+/// A reservation of the memory budget, returned on drop
+#[derive(Debug)]
+pub struct MemoryPermit<'a> {
+    _permit: SemaphorePermit<'a>,
+    bytes: usize,
+    memory_used: &'a AtomicUsize,
+}
+
+impl Drop for MemoryPermit<'_> {
+    fn drop(&mut self) {
+        self.memory_used.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
 }
 
 /// Global singleton instance of the resource manager
@@ -421,6 +685,14 @@ pub fn resource_manager() -> &'static GlobalResourceManager {
         .expect("Resource manager not initialized! Call init_resource_manager() in main().")
 }
 
+/// Access the global resource manager, if `init_resource_manager()` has run
+///
+/// For code that has a sensible fallback when running without one, such as
+/// unit tests and library callers.
+pub fn try_resource_manager() -> Option<&'static GlobalResourceManager> {
+    RESOURCE_MANAGER_CELL.get()
+}
+
 /// Legacy alias for backward compatibility
 ///
 /// **Pattern**: Both `RESOURCE_MANAGER` (static) and `resource_manager()`
@@ -430,6 +702,14 @@ pub fn resource_manager() -> &'static GlobalResourceManager {
 pub static RESOURCE_MANAGER: std::sync::LazyLock<&'static GlobalResourceManager> =
     std::sync::LazyLock::new(resource_manager);
 
+/// Formats a detected limit and its source for logging
+fn describe_limit(value: Option<String>, source: Option<LimitSource>) -> String {
+    match (value, source) {
+        (Some(value), Some(source)) => format!("{} from {}", value, source),
+        _ => "unlimited".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -454,6 +734,7 @@ mod tests {
         let nvme_qd = GlobalResourceManager::detect_optimal_io_tokens(StorageType::NVMe);
         let ssd_qd = GlobalResourceManager::detect_optimal_io_tokens(StorageType::Ssd);
         let hdd_qd = GlobalResourceManager::detect_optimal_io_tokens(StorageType::Hdd);
+        let network_qd = GlobalResourceManager::detect_optimal_io_tokens(StorageType::Network);
 
         // NVMe should have highest queue depth
         assert!(nvme_qd > ssd_qd);
@@ -463,6 +744,63 @@ mod tests {
         assert_eq!(nvme_qd, 24);
         assert_eq!(ssd_qd, 12);
         assert_eq!(hdd_qd, 4);
+        assert_eq!(network_qd, 16);
+    }
+
+    #[test]
+    fn test_read_ahead_follows_storage_latency() {
+        assert_eq!(StorageType::NVMe.read_ahead_chunks(), 4);
+        assert!(StorageType::Hdd.read_ahead_chunks() > StorageType::Ssd.read_ahead_chunks());
+        assert!(StorageType::Network.read_ahead_chunks() > StorageType::Hdd.read_ahead_chunks());
+
+        let manager = GlobalResourceManager::with_container_limits(
+            ResourceConfig {
+                storage_type: StorageType::Network,
+                ..Default::default()
+            },
+            ContainerLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(manager.storage_type(), StorageType::Network);
+        assert_eq!(manager.read_ahead_chunks(), 16);
+        assert_eq!(manager.io_tokens_total(), 16);
+    }
+
+    #[test]
+    fn test_container_limits_shape_defaults() {
+        let limits = ContainerLimits {
+            cpu_cores: Some(1.5),
+            cpu_source: Some(LimitSource::CgroupV2),
+            memory_bytes: Some(512 * 1024 * 1024),
+            memory_source: Some(LimitSource::CgroupV2),
+        };
+        let manager = GlobalResourceManager::with_container_limits(
+            ResourceConfig {
+                storage_type: StorageType::NVMe,
+                ..Default::default()
+            },
+            limits,
+        )
+        .unwrap();
+
+        let cores = limits.effective_cores(std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4));
+        assert!(cores <= 2);
+        assert_eq!(manager.cpu_tokens_total(), (cores - 1).max(1));
+        assert_eq!(manager.io_tokens_total(), 24.min((cores * 4).max(4)));
+        assert_eq!(manager.memory_capacity(), 512 * 1024 * 1024);
+
+        // Explicit configuration still wins over container limits
+        let manager = GlobalResourceManager::with_container_limits(
+            ResourceConfig {
+                cpu_tokens: Some(6),
+                memory_limit: Some(1024),
+                ..Default::default()
+            },
+            limits,
+        )
+        .unwrap();
+        assert_eq!(manager.cpu_tokens_total(), 6);
+        assert_eq!(manager.memory_capacity(), 1024);
     }
 
     #[tokio::test]
@@ -503,6 +841,33 @@ mod tests {
         assert_eq!(manager.io_tokens_available(), 3);
     }
 
+    #[tokio::test]
+    async fn test_network_io_tokens_do_not_starve_local_io() {
+        let manager = GlobalResourceManager::with_container_limits(
+            ResourceConfig {
+                io_tokens: Some(2),
+                network_io_tokens: Some(2),
+                ..Default::default()
+            },
+            ContainerLimits::default(),
+        )
+        .unwrap();
+
+        // Remote requests exhaust their own pool only
+        let _remote1 = manager.acquire_network_io().await.unwrap();
+        let _remote2 = manager.acquire_network_io().await.unwrap();
+        assert_eq!(manager.network_io_tokens_available(), 0);
+        assert_eq!(manager.io_tokens_available(), 2);
+
+        let local = tokio::time::timeout(std::time::Duration::from_millis(100), manager.acquire_io()).await;
+        assert!(local.is_ok(), "local I/O must not wait on network tokens");
+
+        let default =
+            GlobalResourceManager::with_container_limits(ResourceConfig::default(), ContainerLimits::default())
+                .unwrap();
+        assert_eq!(default.network_io_tokens_total(), DEFAULT_NETWORK_IO_TOKENS);
+    }
+
     #[test]
     fn test_memory_tracking() {
         let manager = GlobalResourceManager::new(ResourceConfig::default()).unwrap();
@@ -519,6 +884,34 @@ mod tests {
         assert_eq!(manager.memory_used(), 800);
     }
 
+    #[tokio::test]
+    async fn test_memory_budget_blocks_until_released() {
+        let manager = GlobalResourceManager::new(ResourceConfig {
+            memory_limit: Some(8 * MEMORY_PERMIT_UNIT),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let first = manager.acquire_memory(6 * MEMORY_PERMIT_UNIT).await.unwrap();
+        assert_eq!(manager.memory_used(), 6 * MEMORY_PERMIT_UNIT);
+        assert_eq!(manager.memory_available(), 2 * MEMORY_PERMIT_UNIT);
+
+        // Does not fit until the first reservation is returned
+        let second = manager.acquire_memory(4 * MEMORY_PERMIT_UNIT);
+        tokio::pin!(second);
+        assert!(futures::poll!(second.as_mut()).is_pending());
+        drop(first);
+        let second = second.await.unwrap();
+        assert_eq!(manager.memory_used(), 4 * MEMORY_PERMIT_UNIT);
+        drop(second);
+
+        // Larger than the whole capacity: takes all of it instead of waiting
+        let oversized = manager.acquire_memory(100 * MEMORY_PERMIT_UNIT).await.unwrap();
+        assert_eq!(manager.memory_available(), 0);
+        drop(oversized);
+        assert_eq!(manager.memory_used(), 0);
+    }
+
     #[test]
     fn test_global_singleton_access() {
         // Initialize the global resource manager for this test
@@ -38,7 +38,7 @@
 //! ## Supported Stage Types
 //!
 //! ### Compression Stages
-//! - **Algorithms**: Brotli, Gzip, Zstd, Lz4
+//! - **Algorithms**: Brotli, Gzip, Zstd, Lz4, Xz
 //! - **Configuration**: Compression level, window size, dictionary
 //! - **Performance**: Optimized for different data types and sizes
 //!
@@ -88,6 +88,39 @@
 //! - **Resource Errors**: Resource exhaustion and allocation failures
 //! - **Data Errors**: Corrupted or invalid input data
 //! - **Configuration Errors**: Invalid stage configuration
+//! - **Deadline Errors**: A stage or chunk ran past its deadline
+//!
+//! ## Where Stage Services Run
+//!
+//! Stage services (compression, encryption, encoding) are synchronous,
+//! CPU-heavy calls. Run on a tokio worker thread they would hold it for the
+//! whole chunk, starving the reactor that drives the pipeline's reads and
+//! writes. The executor runs them on the CPU-bound rayon pool instead (see
+//! [`spawn_on_cpu_pool`]), which is sized by the CPU token budget: each
+//! chunk worker holds a CPU token while its stages run, so the pool always
+//! has a thread for it.
+//!
+//! ## Deadlines
+//!
+//! A stage that hangs on a chunk would otherwise block its worker forever,
+//! and enough of them stall the whole worker pool. Two optional deadlines
+//! bound that:
+//!
+//! - **Stage deadline**: how long one stage may spend on one chunk. Set for
+//!   all stages with [`BasicStageExecutor::with_stage_timeout`], or per stage
+//!   with the `timeout_ms` stage parameter, which takes precedence.
+//! - **Chunk deadline**: how long one chunk may spend in all of its stages,
+//!   measured from the creation of the chunk's context. Set with
+//!   [`BasicStageExecutor::with_chunk_timeout`].
+//!
+//! With a deadline the stage service still runs on the CPU-bound pool; the
+//! worker waits for at most the remaining time. An expired deadline fails
+//! the chunk with `PipelineError::StageTimeout` and increments
+//! `adaptive_pipeline_stage_timeouts_total`. Stage services are synchronous
+//! and cannot be interrupted, so the abandoned call keeps its pool thread
+//! until it returns and its result is discarded. Abandoned calls are thus
+//! bounded by the CPU token budget rather than piling up in a pool that
+//! grows on demand.
 //!
 //! ## Thread Safety
 //!
@@ -96,17 +129,35 @@
 //! - **Service Access**: Safe concurrent access to services
 //! - **Resource Coordination**: Coordinated resource access
 
+use crate::infrastructure::adapters::ContentHasher;
+use crate::infrastructure::config::rayon_config::spawn_on_cpu_pool;
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::runtime::stage_registry::StageRegistry;
 use adaptive_pipeline_domain::entities::{PipelineStage, ProcessingContext};
 use adaptive_pipeline_domain::repositories::stage_executor::{ResourceRequirements, StageExecutor};
 use adaptive_pipeline_domain::services::StageService;
-use adaptive_pipeline_domain::value_objects::FileChunk;
+use adaptive_pipeline_domain::value_objects::{Algorithm, ChecksumAlgorithm, FileChunk};
 use adaptive_pipeline_domain::PipelineError;
 use async_trait::async_trait;
 use byte_unit::Byte;
 use parking_lot::RwLock;
-use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Stage parameter overriding the stage deadline, in milliseconds
+pub const STAGE_TIMEOUT_PARAMETER: &str = "timeout_ms";
+
+/// The deadline that applies to one stage execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Deadline {
+    /// Time left for the stage to finish the chunk
+    remaining: Duration,
+    /// Which deadline is the tighter one, `stage` or `chunk`
+    kind: &'static str,
+    /// The configured limit, for error messages
+    limit: Duration,
+}
 
 /// Basic implementation of the stage executor for pipeline processing.
 ///
@@ -121,7 +172,7 @@ use std::sync::Arc;
 /// - **Compression**: Supports various compression algorithms through
 ///   CompressionService
 /// - **Encryption**: Handles encryption operations through EncryptionService
-/// - **Checksum**: Calculates and verifies checksums using SHA-256
+/// - **Checksum**: Calculates and verifies checksums using SHA-256 or BLAKE3
 /// - **Custom Stages**: Extensible architecture for custom stage types
 ///
 /// ### Resource Management
@@ -179,10 +230,17 @@ pub struct BasicStageExecutor {
     // and resource management
     _state: Arc<RwLock<()>>,
     // Store running checksums for each stage
-    checksums: Arc<RwLock<HashMap<String, Sha256>>>,
+    checksums: Arc<RwLock<HashMap<String, ContentHasher>>>,
     // Registry of stage services by algorithm name
-    // Maps algorithm name (e.g., "brotli", "aes256gcm", "base64") to StageService implementation
-    stage_services: Arc<HashMap<String, Arc<dyn StageService>>>,
+    // Maps algorithm name (e.g., "brotli", "aes-256-gcm", "base64") to StageService implementation.
+    // Known algorithms are keyed by their canonical `Algorithm` name.
+    stage_services: StageRegistry,
+    // Deadline for one stage on one chunk, unless the stage sets `timeout_ms`
+    stage_timeout: Option<Duration>,
+    // Deadline for one chunk across all of its stages
+    chunk_timeout: Option<Duration>,
+    // Counts expired deadlines, when set
+    metrics_service: Option<Arc<MetricsService>>,
 }
 
 impl BasicStageExecutor {
@@ -196,8 +254,9 @@ impl BasicStageExecutor {
     ///
     /// * `stage_services` - HashMap mapping algorithm names to StageService
     ///   implementations
-    ///   - Keys: algorithm names (e.g., "brotli", "aes256gcm", "base64",
-    ///     "pii_masking")
+    ///   - Keys: algorithm names (e.g., "brotli", "aes-256-gcm", "base64",
+    ///     "pii_masking"); aliases of known algorithms such as "aes256gcm"
+    ///     are normalized to the canonical `Algorithm` name
     ///   - Values: Arc-wrapped StageService implementations
     ///
     /// # Returns
@@ -224,13 +283,166 @@ impl BasicStageExecutor {
     /// - Registry of stage services by algorithm name
     /// - Thread-safe state management structures
     pub fn new(stage_services: HashMap<String, Arc<dyn StageService>>) -> Self {
+        Self::from_registry(StageRegistry::new(stage_services))
+    }
+
+    /// Creates a stage executor that runs stages through a shared registry.
+    ///
+    /// The executor uses the registry's service instances rather than its
+    /// own, so executors built from clones of one registry share them.
+    pub fn from_registry(stage_registry: StageRegistry) -> Self {
         Self {
             _state: Arc::new(RwLock::new(())),
             checksums: Arc::new(RwLock::new(HashMap::new())),
-            stage_services: Arc::new(stage_services),
+            stage_services: stage_registry,
+            stage_timeout: None,
+            chunk_timeout: None,
+            metrics_service: None,
+        }
+    }
+
+    /// Fails a chunk when any one stage spends longer than `timeout` on it.
+    ///
+    /// A stage's `timeout_ms` parameter overrides this for that stage.
+    pub fn with_stage_timeout(mut self, timeout: Duration) -> Self {
+        self.stage_timeout = Some(timeout);
+        self
+    }
+
+    /// Fails a chunk when its stages together take longer than `timeout`.
+    ///
+    /// The clock starts when the chunk's (child) processing context is
+    /// created, so a chunk gets one budget however many stages it has.
+    pub fn with_chunk_timeout(mut self, timeout: Duration) -> Self {
+        self.chunk_timeout = Some(timeout);
+        self
+    }
+
+    /// Counts expired deadlines in `metrics_service`.
+    pub fn with_metrics_service(mut self, metrics_service: Arc<MetricsService>) -> Self {
+        self.metrics_service = Some(metrics_service);
+        self
+    }
+
+    /// Reads a stage's `timeout_ms` parameter, if set.
+    fn stage_timeout_parameter(stage: &PipelineStage) -> Result<Option<Duration>, PipelineError> {
+        let Some(value) = stage.configuration().parameters.get(STAGE_TIMEOUT_PARAMETER) else {
+            return Ok(None);
+        };
+        match value.trim().parse::<u64>() {
+            Ok(ms) if ms > 0 => Ok(Some(Duration::from_millis(ms))),
+            _ => Err(PipelineError::InvalidParameter(format!(
+                "Stage '{}': {} must be a positive number of milliseconds, got '{}'",
+                stage.name(),
+                STAGE_TIMEOUT_PARAMETER,
+                value
+            ))),
+        }
+    }
+
+    /// Works out the tighter of the stage and chunk deadlines, if any.
+    fn deadline_for(
+        &self,
+        stage: &PipelineStage,
+        context: &ProcessingContext,
+    ) -> Result<Option<Deadline>, PipelineError> {
+        let stage_deadline = Self::stage_timeout_parameter(stage)?
+            .or(self.stage_timeout)
+            .map(|limit| Deadline {
+                remaining: limit,
+                kind: "stage",
+                limit,
+            });
+
+        let chunk_deadline = self.chunk_timeout.map(|limit| {
+            let elapsed = (chrono::Utc::now() - context.created_at()).to_std().unwrap_or_default();
+            Deadline {
+                remaining: limit.saturating_sub(elapsed),
+                kind: "chunk",
+                limit,
+            }
+        });
+
+        Ok(match (stage_deadline, chunk_deadline) {
+            (Some(stage), Some(chunk)) if chunk.remaining < stage.remaining => Some(chunk),
+            (Some(stage), _) => Some(stage),
+            (None, chunk) => chunk,
+        })
+    }
+
+    /// Records an expired deadline and builds the chunk's error.
+    fn timed_out(&self, stage: &PipelineStage, chunk_sequence: u64, deadline: Deadline) -> PipelineError {
+        if let Some(metrics_service) = &self.metrics_service {
+            metrics_service.increment_stage_timeouts(stage.name(), deadline.kind);
+        }
+        tracing::warn!(
+            "⏱️  Stage '{}' exceeded the {} deadline of {:?} on chunk {}",
+            stage.name(),
+            deadline.kind,
+            deadline.limit,
+            chunk_sequence
+        );
+        PipelineError::stage_timeout(format!(
+            "stage '{}' exceeded the {} deadline of {:?} on chunk {}",
+            stage.name(),
+            deadline.kind,
+            deadline.limit,
+            chunk_sequence
+        ))
+    }
+
+    /// Runs a stage service, waiting at most until `deadline`.
+    ///
+    /// The service runs on the CPU-bound pool against a copy of `context`,
+    /// which replaces `context` when the service finishes (in time).
+    async fn run_service(
+        &self,
+        service: &Arc<dyn StageService>,
+        stage: &PipelineStage,
+        chunk: FileChunk,
+        context: &mut ProcessingContext,
+        deadline: Option<Deadline>,
+    ) -> Result<FileChunk, PipelineError> {
+        let chunk_sequence = chunk.sequence_number();
+        let service = Arc::clone(service);
+        let config = stage.configuration().clone();
+        let mut stage_context = context.clone();
+
+        if let Some(deadline) = deadline.filter(|deadline| deadline.remaining.is_zero()) {
+            return Err(self.timed_out(stage, chunk_sequence, deadline));
+        }
+
+        let task = spawn_on_cpu_pool(move || {
+            let result = service.process_chunk(chunk, config.operation, &config, &mut stage_context);
+            (result, stage_context)
+        });
+        let outcome = match deadline {
+            Some(deadline) => match tokio::time::timeout(deadline.remaining, task).await {
+                Ok(outcome) => outcome,
+                Err(_) => return Err(self.timed_out(stage, chunk_sequence, deadline)),
+            },
+            None => task.await,
+        };
+
+        match outcome {
+            Ok((result, stage_context)) => {
+                *context = stage_context;
+                result
+            }
+            Err(message) => Err(PipelineError::stage_panicked(format!(
+                "stage '{}' panicked on chunk {}: {}",
+                stage.name(),
+                chunk_sequence,
+                message
+            ))),
         }
     }
 
+    /// Looks up the stage service registered for an algorithm.
+    fn service_for(&self, algorithm: &str) -> Option<&Arc<dyn StageService>> {
+        self.stage_services.get(algorithm)
+    }
+
     /// Processes a checksum stage by updating the running hash with chunk data.
     ///
     /// This method handles checksum calculation stages by maintaining a running
@@ -284,7 +496,10 @@ impl BasicStageExecutor {
         {
             let mut checksums = self.checksums.write();
             if !checksums.contains_key(stage_name) {
-                checksums.insert(stage_name.to_string(), Sha256::new());
+                // Hashing algorithms without an implementation here keep
+                // SHA-256
+                let algorithm = stage.algorithm().parse::<ChecksumAlgorithm>().unwrap_or_default();
+                checksums.insert(stage_name.to_string(), ContentHasher::new(algorithm));
             }
         }
 
@@ -301,7 +516,7 @@ impl BasicStageExecutor {
             let final_checksum = {
                 let mut checksums = self.checksums.write();
                 if let Some(hasher) = checksums.remove(stage_name) {
-                    format!("{:x}", hasher.finalize())
+                    hasher.finalize_hex()
                 } else {
                     return Err(PipelineError::IntegrityError("Checksum hasher not found".to_string()));
                 }
@@ -359,6 +574,7 @@ impl BasicStageExecutor {
     ) -> Result<FileChunk, PipelineError> {
         let start_time = std::time::Instant::now();
         let input_size = chunk.data().len();
+        let deadline = self.deadline_for(stage, context)?;
 
         tracing::info!(
             "🔧 Processing stage '{}' (type: {:?}, algorithm: {}): chunk {} ({} bytes)",
@@ -377,7 +593,12 @@ impl BasicStageExecutor {
 
         let result = match stage.stage_type() {
             adaptive_pipeline_domain::entities::pipeline_stage::StageType::Checksum => {
-                // Special handling for checksum - maintains incremental hashing
+                // Special handling for checksum - maintains incremental hashing.
+                // Hashing is fast and shares state across chunks, so it runs
+                // inline and only an already expired deadline applies.
+                if let Some(deadline) = deadline.filter(|deadline| deadline.remaining.is_zero()) {
+                    return Err(self.timed_out(stage, chunk.sequence_number(), deadline));
+                }
                 self.process_checksum_stage(stage, &chunk, context).await?;
                 Ok(chunk) // Checksum stages don't modify the chunk data
             }
@@ -385,20 +606,24 @@ impl BasicStageExecutor {
                 // Use unified StageService registry for all other stages
                 let algorithm = stage.configuration().algorithm.as_str();
 
-                match self.stage_services.get(algorithm) {
+                match self.service_for(algorithm) {
                     Some(service) => {
                         tracing::debug!(
                             "Found StageService for algorithm '{}', dispatching to process_chunk()",
                             algorithm
                         );
-                        service.process_chunk(chunk, stage.configuration(), context)
+                        self.run_service(service, stage, chunk, context, deadline).await
                     }
                     None => {
+                        // Explain FIPS-mode rejections rather than reporting a missing service
+                        if let Ok(algorithm) = Algorithm::parse(algorithm) {
+                            algorithm.ensure_permitted()?;
+                        }
                         // Algorithm not found in registry - return helpful error
                         Err(PipelineError::InvalidConfiguration(format!(
                             "No StageService registered for algorithm '{}'. Available: {:?}",
                             algorithm,
-                            self.stage_services.keys().collect::<Vec<_>>()
+                            self.stage_services.algorithms()
                         )))
                     }
                 }
@@ -440,8 +665,14 @@ impl StageExecutor for BasicStageExecutor {
     ) -> Result<FileChunk, PipelineError> {
         // Process stage based on its algorithm configuration, not stage name
         // This ensures all stages (built-in and user-created) are treated equally
+        let input_bytes = chunk.data().len() as u64;
+        let stage_start = std::time::Instant::now();
         let result_chunk = self.process_stage_by_type(stage, chunk, context).await?;
 
+        // Accumulate per-stage metrics in the (chunk-scoped) context; they roll
+        // up to the file-level context when the chunk's context is finalized
+        context.record_stage_execution(stage.name(), input_bytes, stage_start.elapsed());
+
         // Record the output size in the tracing span
         tracing::Span::current().record("output_size", result_chunk.data().len());
 
@@ -485,12 +716,12 @@ impl StageExecutor for BasicStageExecutor {
         }
 
         // For all other stages, check the registry
-        Ok(self.stage_services.contains_key(algorithm))
+        Ok(self.service_for(algorithm).is_some())
     }
 
     fn supported_stage_types(&self) -> Vec<String> {
         // Return list of supported algorithms from registry
-        let mut algorithms: Vec<String> = self.stage_services.keys().cloned().collect();
+        let mut algorithms = self.stage_services.algorithms();
         algorithms.push("checksum".to_string()); // Checksum is always supported
         algorithms.sort();
         algorithms
@@ -605,6 +836,8 @@ impl StageExecutor for BasicStageExecutor {
             ));
         }
 
+        Self::stage_timeout_parameter(stage)?;
+
         // Validate that we have a StageService for this algorithm
         let algorithm = stage.configuration().algorithm.as_str();
 
@@ -614,7 +847,7 @@ impl StageExecutor for BasicStageExecutor {
         }
 
         // For all other stages, check the registry
-        if !self.stage_services.contains_key(algorithm) {
+        if self.service_for(algorithm).is_none() {
             return Err(PipelineError::InvalidConfiguration(format!(
                 "No StageService registered for algorithm '{}'. Supported algorithms: {:?}",
                 algorithm,
@@ -641,7 +874,7 @@ impl StageExecutor for BasicStageExecutor {
             let algorithm = stage.configuration().algorithm.as_str();
 
             // Look up the stage service to determine its position
-            let position = match self.stage_services.get(algorithm) {
+            let position = match self.service_for(algorithm) {
                 Some(service) => service.position(),
                 None => {
                     return Err(PipelineError::InvalidConfiguration(format!(
@@ -678,3 +911,205 @@ impl StageExecutor for BasicStageExecutor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::entities::pipeline_stage::{StageConfiguration, StagePosition, StageType};
+    use adaptive_pipeline_domain::entities::security_context::Permission;
+    use adaptive_pipeline_domain::entities::{Operation, SecurityContext, SecurityLevel};
+
+    /// Sleeps before passing the chunk through, tagging the context with
+    /// the thread it ran on
+    struct SlowService {
+        delay: Duration,
+    }
+
+    impl StageService for SlowService {
+        fn process_chunk(
+            &self,
+            chunk: FileChunk,
+            _operation: Operation,
+            _config: &StageConfiguration,
+            context: &mut ProcessingContext,
+        ) -> Result<FileChunk, PipelineError> {
+            std::thread::sleep(self.delay);
+            context.add_metadata("slow".to_string(), "done".to_string());
+            let thread = std::thread::current().name().unwrap_or_default().to_string();
+            context.add_metadata("thread".to_string(), thread);
+            Ok(chunk)
+        }
+
+        fn position(&self) -> StagePosition {
+            StagePosition::Any
+        }
+
+        fn is_reversible(&self) -> bool {
+            true
+        }
+
+        fn stage_type(&self) -> StageType {
+            StageType::PassThrough
+        }
+    }
+
+    fn executor(delay: Duration) -> BasicStageExecutor {
+        let mut services: HashMap<String, Arc<dyn StageService>> = HashMap::new();
+        services.insert("slow".to_string(), Arc::new(SlowService { delay }));
+        BasicStageExecutor::new(services)
+    }
+
+    fn stage(parameters: &[(&str, &str)]) -> PipelineStage {
+        let parameters = parameters.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        PipelineStage::new(
+            "slow_stage".to_string(),
+            StageType::PassThrough,
+            StageConfiguration::new("slow".to_string(), parameters, false),
+            1,
+        )
+        .unwrap()
+    }
+
+    fn context() -> ProcessingContext {
+        let security_context =
+            SecurityContext::with_permissions(None, vec![Permission::Read, Permission::Write], SecurityLevel::Internal);
+        ProcessingContext::new(1024, security_context)
+    }
+
+    fn chunk() -> FileChunk {
+        FileChunk::new(7, 0, vec![1, 2, 3], false).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stage_within_deadline_updates_context() {
+        let executor = executor(Duration::from_millis(5)).with_stage_timeout(Duration::from_secs(5));
+        let mut context = context();
+
+        let result = executor.execute(&stage(&[]), chunk(), &mut context).await.unwrap();
+
+        assert_eq!(result.data(), &[1, 2, 3]);
+        assert_eq!(context.get_metadata("slow").map(String::as_str), Some("done"));
+    }
+
+    #[tokio::test]
+    async fn test_stage_runs_on_cpu_pool_with_or_without_deadline() {
+        for executor in [
+            executor(Duration::ZERO),
+            executor(Duration::ZERO).with_stage_timeout(Duration::from_secs(5)),
+        ] {
+            let mut context = context();
+
+            let result = executor.execute(&stage(&[]), chunk(), &mut context).await.unwrap();
+
+            assert_eq!(result.data(), &[1, 2, 3]);
+            assert_eq!(context.get_metadata("slow").map(String::as_str), Some("done"));
+            assert!(context.get_metadata("thread").unwrap().starts_with("rayon-cpu-"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stage_timeout_fails_chunk_and_is_counted() {
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let executor = executor(Duration::from_millis(500))
+            .with_stage_timeout(Duration::from_millis(20))
+            .with_metrics_service(metrics_service.clone());
+
+        let err = executor
+            .execute(&stage(&[]), chunk(), &mut context())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, PipelineError::StageTimeout(_)));
+        assert_eq!(err.category(), "timeout");
+        assert!(err.to_string().contains("chunk 7"));
+        let output = metrics_service.get_metrics().unwrap();
+        assert!(output.contains(r#"adaptive_pipeline_stage_timeouts_total{deadline="stage",stage="slow_stage"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_parameter_overrides_stage_timeout() {
+        let executor = executor(Duration::from_millis(200)).with_stage_timeout(Duration::from_millis(20));
+
+        let result = executor
+            .execute(&stage(&[("timeout_ms", "5000")]), chunk(), &mut context())
+            .await;
+        assert!(result.is_ok());
+
+        let invalid = stage(&[("timeout_ms", "soon")]);
+        assert!(matches!(
+            executor.validate_configuration(&invalid).await,
+            Err(PipelineError::InvalidParameter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_chunk_deadline_fails_before_running() {
+        let executor = executor(Duration::ZERO).with_chunk_timeout(Duration::from_millis(10));
+        let mut context = context();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let err = executor.execute(&stage(&[]), chunk(), &mut context).await.unwrap_err();
+
+        assert!(matches!(err, PipelineError::StageTimeout(_)));
+        assert!(err.to_string().contains("chunk deadline"));
+        assert!(context.get_metadata("slow").is_none());
+    }
+
+    /// Panics on every chunk
+    struct PanickingService;
+
+    impl StageService for PanickingService {
+        fn process_chunk(
+            &self,
+            _chunk: FileChunk,
+            _operation: Operation,
+            _config: &StageConfiguration,
+            _context: &mut ProcessingContext,
+        ) -> Result<FileChunk, PipelineError> {
+            panic!("bad state");
+        }
+
+        fn position(&self) -> StagePosition {
+            StagePosition::Any
+        }
+
+        fn is_reversible(&self) -> bool {
+            true
+        }
+
+        fn stage_type(&self) -> StageType {
+            StageType::PassThrough
+        }
+    }
+
+    #[tokio::test]
+    async fn test_panic_on_cpu_pool_is_stage_panicked() {
+        let mut services: HashMap<String, Arc<dyn StageService>> = HashMap::new();
+        services.insert("slow".to_string(), Arc::new(PanickingService));
+        let executor = BasicStageExecutor::new(services);
+
+        let err = executor
+            .execute(&stage(&[]), chunk(), &mut context())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, PipelineError::StagePanicked(_)));
+        assert!(err.to_string().contains("bad state"));
+    }
+
+    #[tokio::test]
+    async fn test_panic_under_deadline_is_stage_panicked() {
+        let mut services: HashMap<String, Arc<dyn StageService>> = HashMap::new();
+        services.insert("slow".to_string(), Arc::new(PanickingService));
+        let executor = BasicStageExecutor::new(services).with_stage_timeout(Duration::from_secs(5));
+
+        let err = executor
+            .execute(&stage(&[]), chunk(), &mut context())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, PipelineError::StagePanicked(_)));
+        assert!(err.to_string().contains("bad state"));
+        assert_eq!(err.category(), "panic");
+    }
+}
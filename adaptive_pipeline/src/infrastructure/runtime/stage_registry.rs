@@ -0,0 +1,179 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Stage Registry
+//!
+//! The stage services available to a [`BasicStageExecutor`], keyed by
+//! algorithm name. The registry is built once by the composition root and
+//! shared: cloning it shares the same service instances, so processing,
+//! restore and planning all run stages through one set of services instead
+//! of each wiring up its own.
+//!
+//! Known algorithms are keyed by their canonical [`Algorithm`] name, so
+//! stages persisted with legacy spellings ("aes256gcm") still resolve. In a
+//! FIPS build, non-approved algorithms are left out of the registry.
+//!
+//! ```rust,ignore
+//! let registry = StageRegistry::builtin(metrics_service.clone());
+//! let executor = BasicStageExecutor::from_registry(registry.clone());
+//! let restore = RestoreFileUseCase::new(metrics_service).with_stage_registry(registry);
+//! ```
+//!
+//! [`BasicStageExecutor`]: super::stage_executor::BasicStageExecutor
+
+use crate::infrastructure::adapters::{MultiAlgoCompression, MultiAlgoEncryption};
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::services::{
+    Base64EncodingService, DebugService, PassThroughService, PiiMaskingService, TeeService,
+};
+use adaptive_pipeline_domain::services::StageService;
+use adaptive_pipeline_domain::value_objects::Algorithm;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Shared stage services by algorithm name
+#[derive(Clone, Default)]
+pub struct StageRegistry {
+    services: Arc<HashMap<String, Arc<dyn StageService>>>,
+}
+
+impl StageRegistry {
+    /// Creates a registry of `services`, normalizing their keys and leaving
+    /// out algorithms this build does not permit
+    pub fn new(services: HashMap<String, Arc<dyn StageService>>) -> Self {
+        let services = services
+            .into_iter()
+            .filter(|(name, _)| Self::is_permitted(name))
+            .map(|(name, service)| (Self::registry_key(&name), service))
+            .collect();
+        Self {
+            services: Arc::new(services),
+        }
+    }
+
+    /// Creates a registry of every built-in stage service: the compression
+    /// and encryption algorithms and the transform stages
+    ///
+    /// The debug stage reports to `metrics_service`.
+    pub fn builtin(metrics_service: Arc<MetricsService>) -> Self {
+        let compression: Arc<dyn StageService> = Arc::new(MultiAlgoCompression::new());
+        let encryption: Arc<dyn StageService> = Arc::new(MultiAlgoEncryption::new());
+
+        let mut services: HashMap<String, Arc<dyn StageService>> = HashMap::new();
+        for algorithm in [
+            Algorithm::brotli(),
+            Algorithm::gzip(),
+            Algorithm::zstd(),
+            Algorithm::lz4(),
+            Algorithm::xz(),
+        ] {
+            services.insert(algorithm.to_string(), compression.clone());
+        }
+        for algorithm in [
+            Algorithm::aes_256_gcm(),
+            Algorithm::aes_128_gcm(),
+            Algorithm::chacha20_poly1305(),
+        ] {
+            services.insert(algorithm.to_string(), encryption.clone());
+        }
+        services.insert("base64".to_string(), Arc::new(Base64EncodingService::new()));
+        services.insert("pii_masking".to_string(), Arc::new(PiiMaskingService::new()));
+        services.insert("tee".to_string(), Arc::new(TeeService::new()));
+        services.insert("passthrough".to_string(), Arc::new(PassThroughService::new()));
+        services.insert("debug".to_string(), Arc::new(DebugService::new(metrics_service)));
+        Self::new(services)
+    }
+
+    /// Returns a registry that runs `algorithm` with `service`, replacing
+    /// the registered service for it if there is one
+    ///
+    /// The other services stay shared with this registry.
+    pub fn with_service(&self, algorithm: impl Into<String>, service: Arc<dyn StageService>) -> Self {
+        let algorithm = algorithm.into();
+        if !Self::is_permitted(&algorithm) {
+            return self.clone();
+        }
+        let mut services = (*self.services).clone();
+        services.insert(Self::registry_key(&algorithm), service);
+        Self {
+            services: Arc::new(services),
+        }
+    }
+
+    /// Looks up the stage service registered for an algorithm
+    pub fn get(&self, algorithm: &str) -> Option<&Arc<dyn StageService>> {
+        self.services.get(&Self::registry_key(algorithm))
+    }
+
+    /// Returns the registered algorithm names, sorted
+    pub fn algorithms(&self) -> Vec<String> {
+        let mut algorithms: Vec<String> = self.services.keys().cloned().collect();
+        algorithms.sort();
+        algorithms
+    }
+
+    /// Normalizes a stage algorithm to its registry key.
+    ///
+    /// Known algorithms resolve to their canonical [`Algorithm`] name so that
+    /// stages persisted with legacy spellings ("aes256gcm") still find their
+    /// service; other stage names (e.g. "pii_masking") are used verbatim.
+    fn registry_key(algorithm: &str) -> String {
+        Algorithm::canonical_name(algorithm)
+            .map(str::to_string)
+            .unwrap_or_else(|| algorithm.to_string())
+    }
+
+    /// Whether this build allows a stage service to be registered.
+    ///
+    /// In a FIPS build non-approved cryptographic algorithms are left out of
+    /// the registry, so stages that use them are rejected instead of run.
+    fn is_permitted(algorithm: &str) -> bool {
+        let permitted = Algorithm::parse(algorithm)
+            .map(|algorithm| algorithm.is_permitted())
+            .unwrap_or(true);
+        if !permitted {
+            tracing::debug!("Not registering '{}': not FIPS-approved", algorithm);
+        }
+        permitted
+    }
+}
+
+impl std::fmt::Debug for StageRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StageRegistry")
+            .field("algorithms", &self.algorithms())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_service_instances() {
+        let registry = StageRegistry::builtin(Arc::new(MetricsService::new().unwrap()));
+        let shared = registry.clone();
+        assert!(Arc::ptr_eq(
+            registry.get("brotli").unwrap(),
+            shared.get("brotli").unwrap()
+        ));
+        // Legacy spellings resolve to the canonical entry
+        assert!(registry.get("aes256gcm").is_some());
+
+        let custom = registry.with_service("base64", Arc::new(PassThroughService::new()));
+        assert!(!Arc::ptr_eq(
+            registry.get("base64").unwrap(),
+            custom.get("base64").unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            registry.get("debug").unwrap(),
+            custom.get("debug").unwrap()
+        ));
+        assert_eq!(custom.algorithms(), registry.algorithms());
+    }
+}
@@ -24,10 +24,45 @@
 //! - Error propagation from background tasks
 //! - Structured logging for observability
 //! - No silent failures
+//!
+//! ## Panic Isolation
+//!
+//! A panic in a stage service would otherwise unwind through the worker task
+//! and kill it, losing its chunk and, once every worker is gone, leaving the
+//! reader blocked on a channel nobody drains. Workers therefore run each
+//! stage through [`catch_panic`], which turns the panic into an error the
+//! worker can report as `PipelineError::StagePanicked`. A [`RestartBudget`]
+//! shared by the pool decides how many panicked chunks may be retried by a
+//! replacement worker before the run fails.
+//!
+//! ## Restart Policies
+//!
+//! Long-running components such as the metrics endpoint should heal
+//! themselves rather than stay down after one failure.
+//! [`spawn_with_restart`] runs such a component under a [`RestartPolicy`]:
+//!
+//! | Policy                    | After a failure (error or panic)             |
+//! |---------------------------|----------------------------------------------|
+//! | `Never`                   | Give up                                      |
+//! | `OnFailure`               | Restart at once, up to `max_retries` times   |
+//! | `ExponentialBackoff`      | Restart after a doubling delay, up to a cap  |
+//!
+//! Every failure is logged with the task name and attempt, and when the
+//! policy gives up the caller receives a [`FailureReport`] listing each
+//! failed attempt.
 
 use adaptive_pipeline_domain::PipelineError;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use serde::Serialize;
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
 use tokio::task::JoinHandle;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 /// Result type alias for application operations
 pub type AppResult<T> = Result<T, PipelineError>;
@@ -135,6 +170,243 @@ pub async fn join_supervised<T>(handle: JoinHandle<AppResult<T>>) -> AppResult<T
     }
 }
 
+/// Runs `fut`, converting a panic into an error carrying the panic message.
+///
+/// Whatever `fut` borrows mutably may be left half-updated by the panic;
+/// callers must discard that state rather than reuse it.
+///
+/// ## Example
+///
+/// ```ignore
+/// let chunk = catch_panic(executor.execute(stage, chunk, &mut context))
+///     .await
+///     .map_err(|message| PipelineError::stage_panicked(message))??;
+/// ```
+pub async fn catch_panic<F: Future>(fut: F) -> Result<F::Output, String> {
+    AssertUnwindSafe(fut)
+        .catch_unwind()
+        .await
+        .map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Extracts the message from a panic payload.
+///
+/// `panic!` payloads are a `&str` or a `String`; anything else is reported
+/// generically.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// Bounds how many times a pool of supervised workers may be replaced.
+///
+/// Shared by all workers of one run. Each replacement after a panic uses up
+/// one restart; once none remain, the next panic fails the run.
+#[derive(Debug, Default)]
+pub struct RestartBudget {
+    max_restarts: u32,
+    restarts: AtomicU32,
+}
+
+impl RestartBudget {
+    /// Allows up to `max_restarts` replacements (zero disables them)
+    pub fn new(max_restarts: u32) -> Self {
+        Self {
+            max_restarts,
+            restarts: AtomicU32::new(0),
+        }
+    }
+
+    /// Whether another replacement would still be allowed
+    pub fn has_remaining(&self) -> bool {
+        self.restarts.load(Ordering::Acquire) < self.max_restarts
+    }
+
+    /// Uses up one restart, returning `false` if none remain
+    pub fn try_restart(&self) -> bool {
+        self.restarts
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |restarts| {
+                (restarts < self.max_restarts).then_some(restarts + 1)
+            })
+            .is_ok()
+    }
+
+    /// Restarts used so far
+    pub fn restarts(&self) -> u32 {
+        self.restarts.load(Ordering::Acquire)
+    }
+
+    /// Restarts allowed in total
+    pub fn max_restarts(&self) -> u32 {
+        self.max_restarts
+    }
+}
+
+/// When a supervised component is restarted after it fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Never restart; the first failure is final
+    Never,
+    /// Restart immediately, at most `max_retries` times
+    OnFailure { max_retries: u32 },
+    /// Restart after `initial_delay`, doubling each time up to `max_delay`,
+    /// at most `max_retries` times
+    ExponentialBackoff {
+        max_retries: u32,
+        initial_delay: Duration,
+        max_delay: Duration,
+    },
+}
+
+impl RestartPolicy {
+    /// Delay before restarting after the `failures`-th failure (1-based), or
+    /// `None` if the policy gives up
+    pub fn restart_delay(&self, failures: u32) -> Option<Duration> {
+        match *self {
+            RestartPolicy::Never => None,
+            RestartPolicy::OnFailure { max_retries } => (failures <= max_retries).then_some(Duration::ZERO),
+            RestartPolicy::ExponentialBackoff {
+                max_retries,
+                initial_delay,
+                max_delay,
+            } => (failures <= max_retries).then(|| {
+                let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+                initial_delay.saturating_mul(factor).min(max_delay)
+            }),
+        }
+    }
+}
+
+/// One failed attempt of a supervised component
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskFailure {
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    pub failed_at: DateTime<Utc>,
+    /// Whether the attempt panicked rather than returned an error
+    pub panicked: bool,
+    pub error: String,
+}
+
+/// Why a supervised component stopped for good
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailureReport {
+    pub task: &'static str,
+    /// Every failed attempt, oldest first
+    pub failures: Vec<TaskFailure>,
+}
+
+impl FailureReport {
+    /// Number of attempts made
+    pub fn attempts(&self) -> u32 {
+        self.failures.len() as u32
+    }
+
+    /// The failure that made the policy give up
+    pub fn last_failure(&self) -> Option<&TaskFailure> {
+        self.failures.last()
+    }
+}
+
+impl fmt::Display for FailureReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task '{}' gave up after {} attempt(s)", self.task, self.attempts())?;
+        if let Some(last) = self.last_failure() {
+            let kind = if last.panicked { "panicked" } else { "failed" };
+            write!(f, "; last attempt {}: {}", kind, last.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<FailureReport> for PipelineError {
+    fn from(report: FailureReport) -> Self {
+        PipelineError::internal_error(report.to_string())
+    }
+}
+
+/// Runs the component built by `factory` until it succeeds or `policy`
+/// gives up.
+///
+/// `factory` is called once per attempt, so each restart starts from fresh
+/// state. Errors and panics both count as failures.
+pub async fn supervise<F, Fut, T>(name: &'static str, policy: RestartPolicy, mut factory: F) -> Result<T, FailureReport>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AppResult<T>>,
+{
+    let mut failures = Vec::new();
+    loop {
+        let attempt = failures.len() as u32 + 1;
+        debug!(task = name, attempt, "task starting");
+
+        let (panicked, error) = match catch_panic(factory()).await {
+            Ok(Ok(value)) => {
+                debug!(task = name, attempt, "task completed successfully");
+                return Ok(value);
+            }
+            Ok(Err(e)) => (false, e.to_string()),
+            Err(message) => (true, message),
+        };
+
+        error!(task = name, attempt, panicked, error = %error, "task failed");
+        failures.push(TaskFailure {
+            attempt,
+            failed_at: Utc::now(),
+            panicked,
+            error,
+        });
+
+        match policy.restart_delay(attempt) {
+            Some(delay) => {
+                warn!(
+                    task = name,
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "restarting task"
+                );
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            None => {
+                let report = FailureReport { task: name, failures };
+                error!(task = name, attempts = report.attempts(), "{}", report);
+                return Err(report);
+            }
+        }
+    }
+}
+
+/// Spawns [`supervise`] as a background task.
+///
+/// ## Example
+///
+/// ```ignore
+/// let endpoint = Arc::new(MetricsEndpoint::new(metrics_service));
+/// let handle = spawn_with_restart("metrics-endpoint", RestartPolicy::OnFailure { max_retries: 3 }, move || {
+///     let endpoint = endpoint.clone();
+///     async move { endpoint.start().await }
+/// });
+/// ```
+pub fn spawn_with_restart<F, Fut, T>(
+    name: &'static str,
+    policy: RestartPolicy,
+    factory: F,
+) -> JoinHandle<Result<T, FailureReport>>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = AppResult<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::spawn(supervise(name, policy, factory))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +442,97 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("panicked"));
     }
+
+    #[tokio::test]
+    async fn test_catch_panic_returns_message() {
+        assert_eq!(catch_panic(async { 7 }).await, Ok(7));
+
+        let result = catch_panic(async {
+            panic!("stage blew up on {}", "chunk 3");
+            #[allow(unreachable_code)]
+            ()
+        })
+        .await;
+        assert_eq!(result, Err("stage blew up on chunk 3".to_string()));
+    }
+
+    #[test]
+    fn test_restart_budget_is_bounded() {
+        let budget = RestartBudget::new(2);
+        assert!(budget.try_restart());
+        assert!(budget.has_remaining());
+        assert!(budget.try_restart());
+        assert!(!budget.has_remaining());
+        assert!(!budget.try_restart());
+        assert_eq!(budget.restarts(), 2);
+
+        assert!(!RestartBudget::new(0).try_restart());
+    }
+
+    #[test]
+    fn test_restart_delays() {
+        assert_eq!(RestartPolicy::Never.restart_delay(1), None);
+
+        let on_failure = RestartPolicy::OnFailure { max_retries: 2 };
+        assert_eq!(on_failure.restart_delay(2), Some(Duration::ZERO));
+        assert_eq!(on_failure.restart_delay(3), None);
+
+        let backoff = RestartPolicy::ExponentialBackoff {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+        };
+        let delays: Vec<_> = (1..=6).map(|failure| backoff.restart_delay(failure)).collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(350)),
+                Some(Duration::from_millis(350)),
+                Some(Duration::from_millis(350)),
+                None,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_supervise_restarts_until_success() {
+        let attempts = AtomicU32::new(0);
+        let result = supervise("flaky", RestartPolicy::OnFailure { max_retries: 3 }, || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                match attempt {
+                    1 => Err(PipelineError::io_error("connection reset")),
+                    2 => panic!("listener poisoned"),
+                    _ => Ok(attempt),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn test_supervise_reports_failures_when_giving_up() {
+        let report = supervise("doomed", RestartPolicy::OnFailure { max_retries: 1 }, || async {
+            Err::<(), _>(PipelineError::io_error("port in use"))
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(report.task, "doomed");
+        assert_eq!(report.attempts(), 2);
+        assert!(!report.last_failure().unwrap().panicked);
+        assert!(report.to_string().contains("gave up after 2 attempt(s)"));
+        assert!(report.to_string().contains("port in use"));
+
+        let never = supervise("once", RestartPolicy::Never, || async {
+            Err::<(), _>(PipelineError::io_error("boom"))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(never.attempts(), 1);
+    }
 }
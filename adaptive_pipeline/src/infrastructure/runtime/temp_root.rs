@@ -0,0 +1,471 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Managed Temp Root
+//!
+//! Every temporary file the pipeline creates is accounted for under one temp
+//! root, so files left by a crashed run can be found and removed.
+//!
+//! ## Layout
+//!
+//! ```text
+//! <temp dir>/adaptive_pipeline-<user>/    # the temp root ($ADAPIPE_TEMP_DIR)
+//! └── run-<pid>-<id>/                     # one directory per process
+//!     ├── owner.lock                      # locked while the process lives
+//!     ├── journal                         # temp files created elsewhere
+//!     └── ...                             # scratch files of the run
+//! ```
+//!
+//! Scratch files with no reason to live elsewhere go in the run directory.
+//! Files that must sit next to their target, such as staged outputs (whose
+//! final rename must stay on one filesystem) and directory writability
+//! probes, are created there and *tracked*: their paths are appended to the
+//! run's journal when created and again, marked released, when removed.
+//!
+//! ## Cleanup guarantees
+//!
+//! - **Normal exit**: [`shutdown_temp_root`] removes any tracked file still
+//!   present and the run directory.
+//! - **Crash**: the OS drops the run's lock with the process. At startup
+//!   [`find_orphans`] reports runs whose lock can be taken; `adaptive-pipeline
+//!   cleanup` removes their run directories and the tracked files their
+//!   journals still list.
+//!
+//! The lock rather than the process ID decides whether a run is alive, so a
+//! recycled PID cannot keep an orphan around or get a live run purged.
+//!
+//! Code that runs without an initialized temp root (unit tests, library
+//! callers) still removes its own temporary files; tracking is skipped.
+
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use adaptive_pipeline_bootstrap::platform::create_platform;
+use adaptive_pipeline_domain::PipelineError;
+use fs2::FileExt;
+use tracing::{debug, warn};
+
+/// Environment variable overriding the temp root location
+pub const TEMP_ROOT_ENV: &str = "ADAPIPE_TEMP_DIR";
+
+/// Prefix of per-process run directories
+const RUN_PREFIX: &str = "run-";
+
+/// File a live run holds an exclusive lock on
+const LOCK_FILE: &str = "owner.lock";
+
+/// Append-only record of tracked files
+const JOURNAL_FILE: &str = "journal";
+
+/// The temp root of this process and its run directory
+#[derive(Debug)]
+pub struct TempRoot {
+    base: PathBuf,
+    run_dir: PathBuf,
+    /// Held open, and therefore locked, until the process exits
+    _lock: File,
+    journal: Mutex<File>,
+}
+
+/// A run whose process is gone, with what it left behind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedRun {
+    /// The run's directory under the temp root
+    pub run_dir: PathBuf,
+    /// Tracked files outside the run directory that still exist
+    pub files: Vec<PathBuf>,
+    /// Total size of the run directory's contents and `files`
+    pub bytes: u64,
+}
+
+/// What a purge removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanupReport {
+    pub runs_removed: usize,
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+}
+
+impl TempRoot {
+    /// Default temp root: `$ADAPIPE_TEMP_DIR`, else a per-user directory in
+    /// the platform temp directory
+    pub fn default_base() -> PathBuf {
+        if let Some(base) = std::env::var_os(TEMP_ROOT_ENV).filter(|base| !base.is_empty()) {
+            return PathBuf::from(base);
+        }
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        let name: String = user
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            .collect();
+        let dir_name = if name.is_empty() {
+            "adaptive_pipeline".to_string()
+        } else {
+            format!("adaptive_pipeline-{}", name)
+        };
+        create_platform().temp_dir().join(dir_name)
+    }
+
+    /// Creates `base` if needed and a locked run directory for this process
+    /// inside it
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the directories or their lock and journal files
+    /// cannot be created.
+    pub fn open(base: &Path) -> Result<Self, PipelineError> {
+        let io_error = |what: &str, path: &Path, e: std::io::Error| {
+            PipelineError::io_error(format!("Failed to create {} '{}': {}", what, path.display(), e))
+        };
+
+        if !base.exists() {
+            std::fs::create_dir_all(base).map_err(|e| io_error("temp root", base, e))?;
+            // Temp files may hold sensitive data; keep the root private
+            if let Err(e) = create_platform().set_permissions(base, 0o700) {
+                warn!("Could not restrict permissions of {}: {}", base.display(), e);
+            }
+        }
+
+        let run_dir = base.join(format!(
+            "{}{}-{}",
+            RUN_PREFIX,
+            std::process::id(),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        ));
+        std::fs::create_dir(&run_dir).map_err(|e| io_error("run directory", &run_dir, e))?;
+
+        let lock_path = run_dir.join(LOCK_FILE);
+        let lock = File::create(&lock_path).map_err(|e| io_error("lock file", &lock_path, e))?;
+        FileExt::try_lock_exclusive(&lock).map_err(|e| io_error("lock on", &lock_path, e))?;
+
+        let journal_path = run_dir.join(JOURNAL_FILE);
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| io_error("journal", &journal_path, e))?;
+
+        debug!("Temp files for this run go under {}", run_dir.display());
+        Ok(Self {
+            base: base.to_path_buf(),
+            run_dir,
+            _lock: lock,
+            journal: Mutex::new(journal),
+        })
+    }
+
+    /// The temp root holding every run directory
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// This process's run directory, for scratch files
+    pub fn run_dir(&self) -> &Path {
+        &self.run_dir
+    }
+
+    /// Records a temporary file created outside the run directory, so a
+    /// crash does not leave it unaccounted for
+    pub fn track(&self, path: &Path) {
+        self.append('+', path);
+    }
+
+    /// Records that a tracked file was removed or became a permanent file
+    pub fn release(&self, path: &Path) {
+        self.append('-', path);
+    }
+
+    /// Tracked files not yet released
+    pub fn tracked(&self) -> Vec<PathBuf> {
+        read_journal(&self.run_dir.join(JOURNAL_FILE)).into_iter().collect()
+    }
+
+    /// Removes tracked files that are still present and the run directory
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the run directory cannot be removed; tracked
+    /// files that cannot be removed are logged and skipped.
+    pub fn close(&self) -> Result<CleanupReport, PipelineError> {
+        let mut report = CleanupReport::default();
+        for path in self.tracked() {
+            remove_tracked(&path, &mut report);
+        }
+        report.bytes_freed += dir_size(&self.run_dir);
+        match std::fs::remove_dir_all(&self.run_dir) {
+            Ok(()) => report.runs_removed = 1,
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(PipelineError::io_error(format!(
+                    "Failed to remove run directory '{}': {}",
+                    self.run_dir.display(),
+                    e
+                )))
+            }
+        }
+        Ok(report)
+    }
+
+    fn append(&self, marker: char, path: &Path) {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let mut journal = self.journal.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = writeln!(journal, "{}{}", marker, path.display()).and_then(|()| journal.flush());
+        if let Err(e) = result {
+            warn!("Failed to journal temp file {}: {}", path.display(), e);
+        }
+    }
+}
+
+static TEMP_ROOT: OnceLock<TempRoot> = OnceLock::new();
+
+/// Opens the process-wide temp root under `base`
+///
+/// # Errors
+///
+/// Returns `InternalError` if called twice, or `IoError` as
+/// [`TempRoot::open`].
+pub fn init_temp_root(base: &Path) -> Result<&'static TempRoot, PipelineError> {
+    let root = TempRoot::open(base)?;
+    TEMP_ROOT
+        .set(root)
+        .map_err(|_| PipelineError::InternalError("Temp root already initialized".to_string()))?;
+    Ok(temp_root().expect("temp root was just set"))
+}
+
+/// The process-wide temp root, if `init_temp_root()` has run
+pub fn temp_root() -> Option<&'static TempRoot> {
+    TEMP_ROOT.get()
+}
+
+/// Tracks `path` in the process-wide temp root; a no-op without one
+pub fn track_temp_file(path: &Path) {
+    if let Some(root) = temp_root() {
+        root.track(path);
+    }
+}
+
+/// Releases `path` in the process-wide temp root; a no-op without one
+pub fn release_temp_file(path: &Path) {
+    if let Some(root) = temp_root() {
+        root.release(path);
+    }
+}
+
+/// Cleans up the process-wide temp root at exit
+pub fn shutdown_temp_root() {
+    if let Some(root) = temp_root() {
+        match root.close() {
+            Ok(report) if report.files_removed > 0 => {
+                debug!("Removed {} leftover temp file(s) at exit", report.files_removed)
+            }
+            Ok(_) => {}
+            Err(e) => warn!("{}", e),
+        }
+    }
+}
+
+/// Runs under `base` whose process is gone
+///
+/// A run is orphaned when its lock can be taken, or when it has no lock
+/// file at all (a process that crashed while creating it). The run of the
+/// calling process is never reported.
+///
+/// # Errors
+///
+/// Returns `IoError` if `base` exists but cannot be listed.
+pub fn find_orphans(base: &Path) -> Result<Vec<OrphanedRun>, PipelineError> {
+    let entries = match std::fs::read_dir(base) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(PipelineError::io_error(format!(
+                "Failed to list temp root '{}': {}",
+                base.display(),
+                e
+            )))
+        }
+    };
+    let own_run = temp_root().map(|root| root.run_dir().to_path_buf());
+
+    let mut orphans = Vec::new();
+    for entry in entries.flatten() {
+        let run_dir = entry.path();
+        let is_run = entry.file_name().to_string_lossy().starts_with(RUN_PREFIX)
+            && entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+        if !is_run || Some(&run_dir) == own_run.as_ref() || is_alive(&run_dir) {
+            continue;
+        }
+
+        let files: Vec<PathBuf> = read_journal(&run_dir.join(JOURNAL_FILE))
+            .into_iter()
+            .filter(|path| std::fs::symlink_metadata(path).is_ok())
+            .collect();
+        let bytes = dir_size(&run_dir) + files.iter().map(|path| file_size(path)).sum::<u64>();
+        orphans.push(OrphanedRun { run_dir, files, bytes });
+    }
+    orphans.sort_by(|a, b| a.run_dir.cmp(&b.run_dir));
+    Ok(orphans)
+}
+
+/// Removes orphaned runs and the tracked files they left behind
+///
+/// Each run is checked again first, so a run that came back to life (it
+/// cannot, but a stale listing might say otherwise) is left alone. Files
+/// that cannot be removed are logged and skipped.
+pub fn purge_orphans(orphans: &[OrphanedRun]) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    for orphan in orphans {
+        if is_alive(&orphan.run_dir) {
+            continue;
+        }
+        for path in &orphan.files {
+            remove_tracked(path, &mut report);
+        }
+        let bytes = dir_size(&orphan.run_dir);
+        match std::fs::remove_dir_all(&orphan.run_dir) {
+            Ok(()) => {
+                report.runs_removed += 1;
+                report.bytes_freed += bytes;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove {}: {}", orphan.run_dir.display(), e),
+        }
+    }
+    report
+}
+
+/// Whether the process owning `run_dir` still holds its lock
+fn is_alive(run_dir: &Path) -> bool {
+    match File::open(run_dir.join(LOCK_FILE)) {
+        // Dropping the file releases the lock again
+        Ok(lock) => FileExt::try_lock_exclusive(&lock).is_err(),
+        Err(_) => false,
+    }
+}
+
+/// Tracked paths in a journal that were never released
+fn read_journal(journal: &Path) -> BTreeSet<PathBuf> {
+    let mut tracked = BTreeSet::new();
+    let Ok(file) = File::open(journal) else {
+        return tracked;
+    };
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if let Some(path) = line.strip_prefix('+') {
+            tracked.insert(PathBuf::from(path));
+        } else if let Some(path) = line.strip_prefix('-') {
+            tracked.remove(Path::new(path));
+        }
+    }
+    tracked
+}
+
+fn remove_tracked(path: &Path, report: &mut CleanupReport) {
+    let size = file_size(path);
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            report.files_removed += 1;
+            report.bytes_freed += size;
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => warn!("Failed to remove temp file {}: {}", path.display(), e),
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::symlink_metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Total size of the files under `dir`
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_close_removes_unreleased_files_and_run_dir() {
+        let base = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let root = TempRoot::open(base.path()).unwrap();
+
+        let kept = outside.path().join("kept.partial");
+        let leftover = outside.path().join("leftover.partial");
+        std::fs::write(&kept, b"committed").unwrap();
+        std::fs::write(&leftover, b"abandoned").unwrap();
+        std::fs::write(root.run_dir().join("scratch"), b"scratch").unwrap();
+        root.track(&kept);
+        root.track(&leftover);
+        root.release(&kept);
+        assert_eq!(root.tracked(), vec![leftover.clone()]);
+
+        let report = root.close().unwrap();
+        assert_eq!(report.files_removed, 1);
+        assert_eq!(report.runs_removed, 1);
+        assert!(kept.exists());
+        assert!(!leftover.exists());
+        assert!(!root.run_dir().exists());
+    }
+
+    #[test]
+    fn test_live_runs_are_not_orphans_and_dead_runs_are_purged() {
+        let base = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+
+        let live = TempRoot::open(base.path()).unwrap();
+        // A crashed run: its journal lists a staging file, and nothing
+        // holds its lock
+        let crashed = base.path().join("run-999999-deadbeef");
+        std::fs::create_dir(&crashed).unwrap();
+        std::fs::write(crashed.join(LOCK_FILE), b"").unwrap();
+        std::fs::write(crashed.join("scratch"), b"12345").unwrap();
+        let staged = outside.path().join(".restored.txt.partial");
+        let released = outside.path().join("restored.txt");
+        std::fs::write(&staged, b"1234567890").unwrap();
+        std::fs::write(&released, b"kept").unwrap();
+        std::fs::write(
+            crashed.join(JOURNAL_FILE),
+            format!(
+                "+{}\n+{}\n-{}\n",
+                staged.display(),
+                released.display(),
+                released.display()
+            ),
+        )
+        .unwrap();
+
+        let orphans = find_orphans(base.path()).unwrap();
+        assert_eq!(orphans.len(), 1, "{:?}", orphans);
+        assert_eq!(orphans[0].run_dir, crashed);
+        assert_eq!(orphans[0].files, vec![staged.clone()]);
+        assert!(orphans[0].bytes >= 15);
+
+        let report = purge_orphans(&orphans);
+        assert_eq!(report.runs_removed, 1);
+        assert_eq!(report.files_removed, 1);
+        assert!(!crashed.exists() && !staged.exists());
+        assert!(released.exists());
+        assert!(live.run_dir().exists());
+        assert!(find_orphans(base.path()).unwrap().is_empty());
+    }
+}
@@ -14,25 +14,51 @@
 //! ## Services
 //!
 //! - **BinaryFormatService**: Binary .adapipe format reading and writing
+//! - **ChunkSource**: Random-access file, HTTP and object-store sources for
+//!   .adapipe readers
+//! - **ChunkTestVectorService**: Generation and verification of chunk
+//!   encryption test vectors
+//! - **ArchiveCatalog**: The catalog of archives that `scrub` checks and
+//!   `replicate` copies
+//! - **ReplicaTarget**: Directory and object-store locations for replicas
 //! - **ProgressIndicator**: Real-time progress tracking and terminal output
 //! - **Base64EncodingService**: Production Base64 encoding/decoding stage
 //! - **PiiMaskingService**: Production PII masking stage (non-reversible)
 //! - **TeeService**: Production data inspection/debugging stage (pass-through)
 //! - **PassThroughService**: No-op stage that passes data unchanged
 //! - **DebugService**: Diagnostic stage with Prometheus metrics (SHA256, bytes)
+//! - **Tar Container**: Ustar entry framing for `export-tar` and staged
+//!   tar entries for `import-tar`
+//! - **ManifestSigner**: Ed25519 signing and verification of processing
+//!   manifests
 
+pub mod archive_catalog;
 pub mod base64_encoding;
 pub mod binary_format;
+pub mod chunk_source;
+pub mod chunk_test_vectors;
 pub mod debug;
+pub mod manifest_signer;
 pub mod passthrough;
 pub mod pii_masking;
 pub mod progress_indicator;
+pub mod replica_target;
+pub mod tar_container;
 pub mod tee;
 
 // Re-export service implementations
+pub use archive_catalog::{ArchiveCatalog, CatalogEntry};
 pub use base64_encoding::Base64EncodingService;
-pub use binary_format::{AdapipeFormat, BinaryFormatService, BinaryFormatWriter};
+pub use binary_format::{AdapipeFormat, BinaryFormatReader, BinaryFormatService, BinaryFormatWriter};
+pub use chunk_source::{
+    is_remote_location, open_source, s3_bucket, split_s3_location, ChunkSource, FileSource, HttpRangeSource,
+    ObjectStoreSource,
+};
+pub use chunk_test_vectors::{ChunkTestVectorService, VectorCheck};
 pub use debug::DebugService;
+pub use manifest_signer::ManifestSigner;
 pub use passthrough::PassThroughService;
 pub use pii_masking::PiiMaskingService;
+pub use replica_target::ReplicaTarget;
+pub use tar_container::{stage_tar_entries, StagedTarEntry};
 pub use tee::TeeService;
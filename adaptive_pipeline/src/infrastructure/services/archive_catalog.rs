@@ -0,0 +1,216 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Archive Catalog
+//!
+//! A text file listing the `.adapipe` archives that `scrub` checks and
+//! `replicate` copies, one per line:
+//!
+//! ```text
+//! # nightly backups
+//! db/2025-06-01.adapipe
+//! /backups/logs.adapipe<TAB>s3://offsite/fleet/backups/logs.adapipe
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored; relative paths are
+//! relative to the catalog's directory. After the archive, tab-separated
+//! fields record where replicas of it were written and verified.
+//! `find /backups -name '*.adapipe' > fleet.catalog` makes a catalog.
+//!
+//! Saving rewrites the file in place through a temporary file, keeping
+//! comments, blank lines and the order of entries.
+
+use std::path::{Path, PathBuf};
+
+use adaptive_pipeline_domain::PipelineError;
+
+use crate::infrastructure::services::is_remote_location;
+
+/// One archive listed in a catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    /// The archive as the catalog names it
+    pub listed: String,
+    /// The archive, resolved against the catalog's directory
+    pub archive: PathBuf,
+    /// Locations of verified replicas
+    pub replicas: Vec<String>,
+}
+
+impl CatalogEntry {
+    fn parse(line: &str, base: &Path) -> Self {
+        let mut fields = line.split('\t').map(str::trim).filter(|field| !field.is_empty());
+        let listed = fields.next().unwrap_or_default().to_string();
+        let archive = if is_remote_location(&listed) {
+            PathBuf::from(&listed)
+        } else {
+            base.join(&listed)
+        };
+        Self {
+            listed,
+            archive,
+            replicas: fields.map(str::to_string).collect(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        std::iter::once(self.listed.as_str())
+            .chain(self.replicas.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+}
+
+enum CatalogLine {
+    /// A comment or blank line, kept as written
+    Text(String),
+    Entry(CatalogEntry),
+}
+
+/// The archives listed in a catalog file
+pub struct ArchiveCatalog {
+    path: PathBuf,
+    lines: Vec<CatalogLine>,
+}
+
+impl ArchiveCatalog {
+    /// Reads the catalog at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the catalog can't be read, or `ValidationError`
+    /// if it lists no archives.
+    pub async fn load(path: &Path) -> Result<Self, PipelineError> {
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to read catalog {}: {}", path.display(), e)))?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        let lines: Vec<CatalogLine> = text
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    CatalogLine::Text(line.to_string())
+                } else {
+                    CatalogLine::Entry(CatalogEntry::parse(trimmed, base))
+                }
+            })
+            .collect();
+
+        let catalog = Self {
+            path: path.to_path_buf(),
+            lines,
+        };
+        if catalog.entries().next().is_none() {
+            return Err(PipelineError::validation_error(format!(
+                "Catalog {} lists no archives",
+                path.display()
+            )));
+        }
+        Ok(catalog)
+    }
+
+    /// Gets the catalog's path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The listed archives, in catalog order
+    pub fn entries(&self) -> impl Iterator<Item = &CatalogEntry> {
+        self.lines.iter().filter_map(|line| match line {
+            CatalogLine::Entry(entry) => Some(entry),
+            CatalogLine::Text(_) => None,
+        })
+    }
+
+    /// The `index`th listed archive, mutably
+    pub fn entry_mut(&mut self, index: usize) -> Option<&mut CatalogEntry> {
+        self.lines
+            .iter_mut()
+            .filter_map(|line| match line {
+                CatalogLine::Entry(entry) => Some(entry),
+                CatalogLine::Text(_) => None,
+            })
+            .nth(index)
+    }
+
+    /// Writes the catalog back to its file
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the file can't be replaced.
+    pub async fn save(&self) -> Result<(), PipelineError> {
+        let mut text = String::new();
+        for line in &self.lines {
+            match line {
+                CatalogLine::Text(text_line) => text.push_str(text_line),
+                CatalogLine::Entry(entry) => text.push_str(&entry.to_line()),
+            }
+            text.push('\n');
+        }
+
+        let io_error = |e: std::io::Error| {
+            PipelineError::io_error(format!("Failed to write catalog {}: {}", self.path.display(), e))
+        };
+        let staging = self.path.with_file_name(format!(
+            ".{}.tmp",
+            self.path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        tokio::fs::write(&staging, text).await.map_err(io_error)?;
+        tokio::fs::rename(&staging, &self.path).await.map_err(io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_catalog_skips_comments_and_resolves_relative_paths() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fleet.catalog");
+        std::fs::write(
+            &path,
+            "# nightly\n\na.adapipe\n  /abs/b.adapipe  \nc.adapipe\ts3://offsite/c.adapipe\n",
+        )
+        .unwrap();
+
+        let catalog = ArchiveCatalog::load(&path).await.unwrap();
+        let entries: Vec<_> = catalog.entries().cloned().collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].archive, dir.path().join("a.adapipe"));
+        assert_eq!(entries[1].archive, PathBuf::from("/abs/b.adapipe"));
+        assert_eq!(entries[2].listed, "c.adapipe");
+        assert_eq!(entries[2].replicas, ["s3://offsite/c.adapipe"]);
+
+        std::fs::write(&path, "# nothing yet\n").unwrap();
+        assert!(ArchiveCatalog::load(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_records_replicas_and_keeps_comments() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fleet.catalog");
+        std::fs::write(&path, "# nightly\na.adapipe\n\nb.adapipe\n").unwrap();
+
+        let mut catalog = ArchiveCatalog::load(&path).await.unwrap();
+        catalog
+            .entry_mut(1)
+            .unwrap()
+            .replicas
+            .push("/mnt/offsite/b.adapipe".to_string());
+        catalog.save().await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# nightly\na.adapipe\n\nb.adapipe\t/mnt/offsite/b.adapipe\n"
+        );
+        let reloaded = ArchiveCatalog::load(&path).await.unwrap();
+        assert_eq!(reloaded.entries().nth(1).unwrap().replicas, ["/mnt/offsite/b.adapipe"]);
+    }
+}
@@ -177,6 +177,7 @@ impl StageService for Base64EncodingService {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        operation: Operation,
         config: &StageConfiguration,
         context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError> {
@@ -186,7 +187,7 @@ impl StageService for Base64EncodingService {
         let input_size = chunk.data().len();
 
         // Dispatch based on operation (Forward = encode, Reverse = decode)
-        let processed_data = match config.operation {
+        let processed_data = match operation {
             Operation::Forward => {
                 // Encode: binary -> base64 text
                 tracing::debug!(
@@ -211,7 +212,7 @@ impl StageService for Base64EncodingService {
 
         // Update metrics
         tracing::trace!(
-            operation = %config.operation,
+            operation = %operation,
             input_bytes = input_size,
             output_bytes = output_size,
             ratio = format!("{:.2}", output_size as f64 / input_size as f64),
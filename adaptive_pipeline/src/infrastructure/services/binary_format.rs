@@ -13,24 +13,61 @@
 //! Services for reading and writing the Adaptive Pipeline binary format
 //! (.adapipe). Provides streaming I/O, integrity verification with SHA-256
 //! checksums, metadata preservation, and format versioning. Structure:
-//! \[CHUNK_DATA\]\[JSON_HEADER\] \[HEADER_LENGTH\]\[FORMAT_VERSION\]\
-//! [MAGIC_BYTES\]. See mdBook for detailed format specification.
+//! \[CHUNK_DATA\]\[CHUNK_INDEX\]\[JSON_HEADER\] \[HEADER_LENGTH\]\
+//! [FORMAT_VERSION\]\[MAGIC_BYTES\]. See mdBook for detailed format
+//! specification.
 
 use async_trait::async_trait;
 
-use adaptive_pipeline_domain::value_objects::{ChunkFormat, FileHeader};
+use adaptive_pipeline_domain::services::constant_time::constant_time_eq_str;
+use adaptive_pipeline_domain::services::RandomAccessSink;
+use adaptive_pipeline_domain::value_objects::binary_file_format::{
+    MAGIC_BYTES, MAX_CHUNK_PAYLOAD_LENGTH, MAX_HEADER_LENGTH,
+};
+use adaptive_pipeline_domain::value_objects::{ChunkFormat, ChunkIndexEntry, FileHeader};
 use adaptive_pipeline_domain::PipelineError;
 use sha2::{Digest, Sha256};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use tokio::fs::{self as fs};
-use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use std::sync::{Arc, OnceLock};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
+use super::chunk_source::{ChunkSource, FileSource};
+use crate::infrastructure::adapters::random_access_sink::FileSink;
+
+/// Per-chunk allowance on top of the input chunk size: covers the AEAD tag,
+/// the 16-byte chunk header and the fixed overhead of every supported codec
+const CHUNK_OVERHEAD_ALLOWANCE: u64 = 64;
+
+/// Room reserved for the footer (JSON header plus trailer)
+const FOOTER_ALLOWANCE: u64 = 64 * 1024;
+
+/// Largest .adapipe file `chunk_count` chunks of `chunk_size` bytes can
+/// produce
+///
+/// Incompressible input grows by well under 1/64 with any supported codec,
+/// so each chunk is allowed `chunk_size / 64` extra bytes plus fixed
+/// overhead. Used to size preallocations; the file is trimmed to its real
+/// size when finalized.
+pub fn worst_case_output_size(chunk_size: usize, chunk_count: u64) -> u64 {
+    let chunk_size = chunk_size as u64;
+    let per_chunk = chunk_size + chunk_size / 64 + CHUNK_OVERHEAD_ALLOWANCE;
+    per_chunk.saturating_mul(chunk_count).saturating_add(FOOTER_ALLOWANCE)
+}
+
+/// Chunk index for chunks placed at the given offsets with the given payload
+/// lengths, each but the last restoring to `chunk_size` bytes of the original
+fn fixed_size_chunk_index(chunk_size: u32, placed: Vec<(u64, u32)>) -> Vec<ChunkIndexEntry> {
+    (0u64..)
+        .zip(placed)
+        .map(|(index, (offset, length))| ChunkIndexEntry::new(offset, length, index * chunk_size as u64))
+        .collect()
+}
+
 /// Service for writing and reading Adaptive Pipeline processed files (.adapipe
 /// format)
 ///
@@ -61,12 +98,28 @@ pub trait BinaryFormatService: Send + Sync {
         header: FileHeader,
     ) -> Result<Box<dyn BinaryFormatWriter>, PipelineError>;
 
+    /// Creates a new .adapipe format writer over any positional-write sink
+    async fn create_writer_to(
+        &self,
+        sink: Arc<dyn RandomAccessSink>,
+        header: FileHeader,
+    ) -> Result<Box<dyn BinaryFormatWriter>, PipelineError>;
+
     /// Creates a new .adapipe format reader for streaming processed input
     async fn create_reader(&self, input_path: &Path) -> Result<Box<dyn BinaryFormatReader>, PipelineError>;
 
+    /// Creates a reader over any chunk source (file, HTTP, object storage)
+    async fn create_reader_from(
+        &self,
+        source: Arc<dyn ChunkSource>,
+    ) -> Result<Box<dyn BinaryFormatReader>, PipelineError>;
+
     /// Validates an .adapipe processed file without full restoration
     async fn validate_file(&self, file_path: &Path) -> Result<ValidationResult, PipelineError>;
 
+    /// Validates an .adapipe file read from any chunk source
+    async fn validate_source(&self, source: Arc<dyn ChunkSource>) -> Result<ValidationResult, PipelineError>;
+
     /// Extracts metadata from an .adapipe processed file
     async fn read_metadata(&self, file_path: &Path) -> Result<FileHeader, PipelineError>;
 }
@@ -91,6 +144,14 @@ pub trait BinaryFormatWriter: Send + Sync {
     /// Uses internal AtomicBool to prevent double-finalization.
     async fn finalize(&self, final_header: FileHeader) -> Result<u64, PipelineError>;
 
+    /// SHA-256 of the complete file (chunks and footer), once finalized
+    ///
+    /// Writers that hash what they write can report it without the file
+    /// being read back; others return `None`.
+    fn file_checksum(&self) -> Option<String> {
+        None
+    }
+
     /// Gets the current number of bytes written
     fn bytes_written(&self) -> u64;
 
@@ -111,6 +172,12 @@ pub trait BinaryFormatReader: Send + Sync {
     /// Seeks to a specific chunk by index
     async fn seek_to_chunk(&mut self, chunk_index: u32) -> Result<(), PipelineError>;
 
+    /// Number of chunks in the file, from its footer
+    fn chunk_count(&self) -> u32;
+
+    /// Size of the underlying source in bytes
+    fn source_size(&self) -> u64;
+
     /// Validates the file integrity
     async fn validate_integrity(&mut self) -> Result<bool, PipelineError>;
 }
@@ -148,24 +215,39 @@ impl BinaryFormatService for AdapipeFormat {
         Ok(Box::new(writer))
     }
 
+    async fn create_writer_to(
+        &self,
+        sink: Arc<dyn RandomAccessSink>,
+        header: FileHeader,
+    ) -> Result<Box<dyn BinaryFormatWriter>, PipelineError> {
+        Ok(Box::new(StreamingBinaryWriter::with_sink(sink, header)))
+    }
+
     async fn create_reader(&self, input_path: &Path) -> Result<Box<dyn BinaryFormatReader>, PipelineError> {
-        let reader = StreamingBinaryReader::new(input_path).await?;
+        self.create_reader_from(Arc::new(FileSource::new(input_path))).await
+    }
+
+    async fn create_reader_from(
+        &self,
+        source: Arc<dyn ChunkSource>,
+    ) -> Result<Box<dyn BinaryFormatReader>, PipelineError> {
+        let reader = StreamingBinaryReader::new(source).await?;
         Ok(Box::new(reader))
     }
 
     async fn validate_file(&self, file_path: &Path) -> Result<ValidationResult, PipelineError> {
-        let mut reader = self.create_reader(file_path).await?;
+        self.validate_source(Arc::new(FileSource::new(file_path))).await
+    }
+
+    async fn validate_source(&self, source: Arc<dyn ChunkSource>) -> Result<ValidationResult, PipelineError> {
+        let mut reader = self.create_reader_from(source).await?;
         let header = reader.read_header()?;
         let integrity_verified = reader.validate_integrity().await?;
 
-        let file_metadata = fs::metadata(file_path)
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
-
         Ok(ValidationResult {
             is_valid: true,
             format_version: header.format_version,
-            file_size: file_metadata.len(),
+            file_size: reader.source_size(),
             chunk_count: header.chunk_count,
             processing_summary: header.get_processing_summary(),
             integrity_verified,
@@ -232,8 +314,10 @@ impl BinaryFormatWriter for BufferedBinaryWriter {
         let mut total_bytes = 0u64;
         let mut hasher = Sha256::new();
 
+        let mut placed = Vec::with_capacity(self.chunks.len());
         for chunk in &self.chunks {
             let (chunk_bytes, chunk_size) = chunk.to_bytes_with_size();
+            placed.push((total_bytes, chunk.data_length));
             file.write_all(&chunk_bytes)
                 .await
                 .map_err(|e| PipelineError::IoError(e.to_string()))?;
@@ -243,6 +327,7 @@ impl BinaryFormatWriter for BufferedBinaryWriter {
 
         // Update final header with actual values
         final_header.chunk_count = self.chunks.len() as u32;
+        final_header.chunk_index = fixed_size_chunk_index(final_header.chunk_size, placed);
         final_header.processed_at = chrono::Utc::now();
         final_header.output_checksum = format!("{:x}", hasher.finalize());
 
@@ -272,21 +357,24 @@ impl BinaryFormatWriter for BufferedBinaryWriter {
 ///
 /// This writer supports **concurrent writes** from multiple worker tasks by
 /// using:
-/// 1. `Arc<std::fs::File>` - Shared file handle (no mutex needed!)
+/// 1. `Arc<dyn RandomAccessSink>` - Shared positional-write sink (no mutex
+///    needed!); a [`FileSink`] unless another sink is plugged in with
+///    [`with_sink`](Self::with_sink)
 /// 2. Platform-specific atomic write operations (pwrite/seek_write)
 /// 3. `&self` methods instead of `&mut self` (thread-safe)
 ///
-/// **Educational: Why no mutex?**
+/// **Educational: Why no mutex around the writes?**
 /// - Each write goes to a DIFFERENT file position
 /// - Platform syscalls (pwrite/seek_write) are atomic
 /// - OS kernel handles concurrency safely
-/// - Only shared state is atomic counters (lock-free)
+/// - Only assigning positions is serialized: processed chunks vary in size,
+///   so a chunk's offset is known once every earlier chunk has arrived
 #[allow(dead_code)]
 pub struct StreamingBinaryWriter {
-    /// Shared file handle for concurrent access
-    /// Educational: Arc allows sharing, std::fs::File supports position-based
+    /// Shared sink for concurrent access
+    /// Educational: Arc allows sharing, the sink supports position-based
     /// writes
-    file: Arc<std::fs::File>,
+    sink: Arc<dyn RandomAccessSink>,
 
     /// Atomic counters for thread-safe statistics
     bytes_written: Arc<AtomicU64>,
@@ -294,8 +382,9 @@ pub struct StreamingBinaryWriter {
 
     initial_header: FileHeader,
 
-    /// Incremental checksum calculation (mutex needed - shared mutable state)
-    output_hasher: Arc<Mutex<Sha256>>,
+    /// Offsets assigned so far and chunks waiting for their predecessors
+    /// (mutex needed - shared mutable state)
+    order: Arc<Mutex<WriteOrder>>,
 
     // Flushing strategy fields
     flush_interval: u64,
@@ -305,31 +394,71 @@ pub struct StreamingBinaryWriter {
     /// Track finalization state to prevent double-finalization
     /// Educational: AtomicBool enables thread-safe state checking without mutex
     finalized: Arc<AtomicBool>,
+
+    /// Digest of the whole file, set when finalized
+    file_checksum: OnceLock<String>,
 }
 
 impl StreamingBinaryWriter {
     async fn new(output_path: &Path, header: FileHeader) -> Result<Self, PipelineError> {
-        // Create sync file handle (std::fs::File, not tokio::fs::File)
-        // Educational: We need sync file for platform-specific write_at() operations
-        let file = std::fs::OpenOptions::new()
-            .create(true)
-            .write(true)
-            .read(true) // Needed for some platform operations
-            .truncate(true)
-            .open(output_path)
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+        Ok(Self::with_sink(Arc::new(FileSink::create(output_path)?), header))
+    }
 
-        Ok(Self {
-            file: Arc::new(file),
+    /// Creates a writer over any positional-write sink
+    pub fn with_sink(sink: Arc<dyn RandomAccessSink>, header: FileHeader) -> Self {
+        Self {
+            sink,
             bytes_written: Arc::new(AtomicU64::new(0)),
             chunks_written: Arc::new(AtomicU64::new(0)),
             initial_header: header,
-            output_hasher: Arc::new(Mutex::new(Sha256::new())),
+            order: Arc::new(Mutex::new(WriteOrder::default())),
             flush_interval: 1024 * 1024,
             buffer_size_threshold: 10 * 1024 * 1024,
             bytes_since_flush: Arc::new(AtomicU64::new(0)),
             finalized: Arc::new(AtomicBool::new(false)),
-        })
+            file_checksum: OnceLock::new(),
+        }
+    }
+}
+
+/// Places chunks in sequence order for [`StreamingBinaryWriter`]
+///
+/// Chunks that finish ahead of an earlier one wait here until the gap is
+/// filled; the output checksum is fed in file order at the same time.
+#[derive(Default)]
+struct WriteOrder {
+    hasher: Sha256,
+    next_sequence: u64,
+    next_offset: u64,
+    pending: BTreeMap<u64, Vec<u8>>,
+    /// Offset and payload length of each placed chunk, for the footer's
+    /// chunk index
+    placed: Vec<(u64, u32)>,
+}
+
+impl WriteOrder {
+    /// Queues a serialized chunk and returns every chunk that can now be
+    /// written, with its file offset
+    fn place(&mut self, sequence_number: u64, chunk_bytes: Vec<u8>) -> Result<Vec<(u64, Vec<u8>)>, PipelineError> {
+        if sequence_number < self.next_sequence || self.pending.contains_key(&sequence_number) {
+            return Err(PipelineError::internal_error(format!(
+                "Chunk {} was written twice",
+                sequence_number
+            )));
+        }
+        self.pending.insert(sequence_number, chunk_bytes);
+
+        let mut ready = Vec::new();
+        while let Some(bytes) = self.pending.remove(&self.next_sequence) {
+            self.hasher.update(&bytes);
+            let offset = self.next_offset;
+            self.placed
+                .push((offset, (bytes.len() - CHUNK_HEADER_SIZE as usize) as u32));
+            self.next_offset += bytes.len() as u64;
+            self.next_sequence += 1;
+            ready.push((offset, bytes));
+        }
+        Ok(ready)
     }
 }
 
@@ -378,8 +507,10 @@ impl BinaryFormatWriter for StreamingBinaryWriter {
     /// ```
     ///
     /// ## Position Calculation:
-    /// Each chunk's file position is calculated as:
-    /// `file_position = sequence_number * chunk_size`
+    /// Each chunk is placed directly after the chunk with the previous
+    /// sequence number. Processed chunks differ in size, so a chunk that
+    /// completes early is held until every earlier chunk has been placed;
+    /// the writes themselves still run concurrently.
     ///
     /// This ensures chunks are written to the correct location in the final
     /// file, regardless of the order in which they complete processing.
@@ -414,7 +545,7 @@ impl BinaryFormatWriter for StreamingBinaryWriter {
     /// Worker 3: write_at(data, pos=2048)  ← No interference!
     /// ```
     ///
-    /// Platform-specific operations:
+    /// Platform-specific operations (in [`FileSink`]):
     /// - Unix/Linux/macOS: `pwrite()` via FileExt::write_all_at()
     /// - Windows: `WriteFile()` with OVERLAPPED via FileExt::seek_write()
     ///
@@ -427,60 +558,19 @@ impl BinaryFormatWriter for StreamingBinaryWriter {
         // STEP 2: Convert chunk to bytes
         let (chunk_bytes, chunk_size) = chunk.to_bytes_with_size();
 
-        // STEP 3: Calculate file position
-        // Educational: Each chunk has a pre-calculated position based on sequence
-        // number
-        let file_position = sequence_number * chunk_size;
+        // STEP 3: Assign file positions in sequence order
+        // Educational: the lock covers only the bookkeeping (and the
+        // incremental checksum), never the I/O
+        let ready = self.order.lock().await.place(sequence_number, chunk_bytes)?;
 
-        // STEP 4: Concurrent random-access write using platform-specific atomic
-        // operation Educational: This is a SINGLE atomic syscall - no seek
+        // STEP 4: Concurrent random-access writes through the sink
+        // Educational: For files this is a SINGLE atomic syscall - no seek
         // needed, no mutex needed!
-        //
-        // We use spawn_blocking because:
-        // 1. std::fs::File operations are synchronous (blocking)
-        // 2. We don't want to block the tokio runtime thread
-        // 3. Tokio's blocking thread pool handles this efficiently
-        let file_clone = self.file.clone();
-        let chunk_bytes_clone = chunk_bytes.clone();
-
-        tokio::task::spawn_blocking(move || {
-            // Platform-specific position-based write
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::FileExt;
-                // Atomic pwrite() syscall - writes at position without seeking
-                file_clone.write_all_at(&chunk_bytes_clone, file_position).map_err(|e| {
-                    PipelineError::IoError(format!("Failed to write chunk at position {}: {}", file_position, e))
-                })
-            }
-
-            #[cfg(windows)]
-            {
-                use std::os::windows::fs::FileExt;
-                // Atomic WriteFile() with OVERLAPPED - writes at position
-                file_clone
-                    .seek_write(&chunk_bytes_clone, file_position)
-                    .map(|_| ())
-                    .map_err(|e| {
-                        PipelineError::IoError(format!("Failed to write chunk at position {}: {}", file_position, e))
-                    })
-            }
-
-            #[cfg(not(any(unix, windows)))]
-            {
-                compile_error!("Platform not supported for position-based writes")
-            }
-        })
-        .await
-        .map_err(|e| PipelineError::IoError(format!("Task join error: {}", e)))??;
-
-        // STEP 5: Update incremental checksum (mutex needed - shared mutable state)
-        {
-            let mut hasher = self.output_hasher.lock().await;
-            hasher.update(&chunk_bytes);
+        for (file_position, bytes) in ready {
+            self.sink.write_at(file_position, &bytes).await?;
         }
 
-        // STEP 6: Update atomic statistics (lock-free!)
+        // STEP 5: Update atomic statistics (lock-free!)
         self.bytes_written.fetch_add(chunk_size, Ordering::Relaxed);
         self.chunks_written.fetch_add(1, Ordering::Relaxed);
         self.bytes_since_flush.fetch_add(chunk_size, Ordering::Relaxed);
@@ -499,61 +589,48 @@ impl BinaryFormatWriter for StreamingBinaryWriter {
         final_header.chunk_count = self.chunks_written.load(Ordering::Relaxed) as u32;
         final_header.processed_at = chrono::Utc::now();
 
-        // Finalize incremental checksum calculation
-        let output_checksum = {
-            let mut hasher = self.output_hasher.lock().await;
-            let result = hasher.finalize_reset();
-            format!("{:x}", result)
+        // Finalize incremental checksum calculation; a chunk still waiting
+        // means one before it never arrived
+        let (output_checksum, footer_position, mut file_hasher) = {
+            let mut order = self.order.lock().await;
+            if let Some(&waiting) = order.pending.keys().next() {
+                return Err(PipelineError::internal_error(format!(
+                    "Chunk {} is missing; chunk {} cannot be placed",
+                    order.next_sequence, waiting
+                )));
+            }
+            let file_hasher = order.hasher.clone();
+            final_header.chunk_index =
+                fixed_size_chunk_index(final_header.chunk_size, std::mem::take(&mut order.placed));
+            (
+                format!("{:x}", order.hasher.finalize_reset()),
+                order.next_offset,
+                file_hasher,
+            )
         };
         final_header.output_checksum = output_checksum;
 
-        // Write footer with calculated checksum
+        // Write footer with calculated checksum; the chunks' digest extended
+        // by the footer is the whole file's
         let footer_bytes = final_header.to_footer_bytes()?;
         let footer_size = footer_bytes.len() as u64;
+        file_hasher.update(&footer_bytes);
+        let _ = self.file_checksum.set(format!("{:x}", file_hasher.finalize()));
 
-        // Use spawn_blocking for sync file operations
-        let file = self.file.clone();
-        tokio::task::spawn_blocking(move || {
-            // Get mutable reference to file for write
-            let file_ref = &*file;
-
-            // Get current file size for append position
-            let current_pos = file_ref.metadata().map(|m| m.len()).unwrap_or(0);
-
-            // Write footer using platform-specific positional write
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::FileExt;
-                file_ref
-                    .write_all_at(&footer_bytes, current_pos)
-                    .map_err(|e| PipelineError::IoError(e.to_string()))?;
-            }
-
-            #[cfg(windows)]
-            {
-                use std::io::{Seek, SeekFrom, Write};
-                // Note: On Windows, seek+write is not atomic, but sufficient for single-writer
-                // scenario
-                let mut file_mut = file_ref;
-                file_mut
-                    .seek(SeekFrom::Start(current_pos))
-                    .map_err(|e| PipelineError::IoError(e.to_string()))?;
-                file_mut
-                    .write_all(&footer_bytes)
-                    .map_err(|e| PipelineError::IoError(e.to_string()))?;
-            }
-
-            // Sync to disk for durability
-            file_ref.sync_all().map_err(|e| PipelineError::IoError(e.to_string()))
-        })
-        .await
-        .map_err(|e| PipelineError::IoError(format!("Task join error: {}", e)))??;
+        // Append the footer after the last chunk, then sync to disk for
+        // durability
+        self.sink.write_at(footer_position, &footer_bytes).await?;
+        self.sink.sync().await?;
 
         let total_bytes = self.bytes_written.load(Ordering::Relaxed) + footer_size;
 
         Ok(total_bytes)
     }
 
+    fn file_checksum(&self) -> Option<String> {
+        self.file_checksum.get().cloned()
+    }
+
     fn bytes_written(&self) -> u64 {
         self.bytes_written.load(Ordering::Relaxed)
     }
@@ -564,183 +641,266 @@ impl BinaryFormatWriter for StreamingBinaryWriter {
 }
 
 /// Streaming reader implementation
-#[allow(dead_code)]
+///
+/// Reads from any [`ChunkSource`]: only the footer is read up front, then
+/// each chunk is fetched with two ranged reads (its 16-byte header, then its
+/// payload). Seeking uses the footer's chunk index when the file has one;
+/// otherwise offsets of chunks already passed are remembered, so seeking
+/// back is free and seeking forward only reads chunk headers.
 pub struct StreamingBinaryReader {
-    file: tokio::fs::File,
-    file_size: u64,
-    header: Option<FileHeader>,
+    source: Arc<dyn ChunkSource>,
+    source_size: u64,
+    header: FileHeader,
+    /// Where the chunk index or, without one, the footer starts; chunk
+    /// data lies before it
+    chunk_data_end: u64,
+    position: u64,
     current_chunk_index: u32,
-    chunks_start_offset: u64,
+    /// Start offset of every chunk seen so far, by index
+    chunk_offsets: Vec<u64>,
 }
 
-impl StreamingBinaryReader {
-    async fn new(input_path: &Path) -> Result<Self, PipelineError> {
-        let mut file = tokio::fs::File::open(input_path)
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+/// Bytes of [nonce (12)][payload length (4)] before each chunk's payload
+const CHUNK_HEADER_SIZE: u64 = 16;
 
-        let metadata = std::fs::metadata(input_path).map_err(|e| PipelineError::IoError(e.to_string()))?;
-        let file_size = metadata.len();
+/// [HEADER_LENGTH (4)][FORMAT_VERSION (2)][MAGIC_BYTES (8)]
+const FOOTER_TRAILER_SIZE: u64 = 14;
 
-        // Read the header from the file footer
-        let mut file_data = Vec::new();
-        file.read_to_end(&mut file_data)
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+/// Block size for hashing chunk data during integrity validation
+const INTEGRITY_READ_SIZE: u64 = 1024 * 1024;
 
-        let (header, footer_size) = FileHeader::from_footer_bytes(&file_data)?;
+impl StreamingBinaryReader {
+    async fn new(source: Arc<dyn ChunkSource>) -> Result<Self, PipelineError> {
+        let source_size = source.size().await?;
+        if source_size < FOOTER_TRAILER_SIZE {
+            return Err(PipelineError::ValidationError("File too short for footer".to_string()));
+        }
 
-        // Calculate where chunk data starts (beginning of file)
-        let chunks_start_offset = 0;
+        // The trailer gives the footer's length; magic and version are
+        // checked when the full footer is parsed
+        let trailer = source
+            .read_at(source_size - FOOTER_TRAILER_SIZE, FOOTER_TRAILER_SIZE as usize)
+            .await?;
+        if trailer[6..] != MAGIC_BYTES {
+            return Err(PipelineError::ValidationError(
+                "Invalid magic bytes - not an Adaptive Pipeline file".to_string(),
+            ));
+        }
+        let header_length = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) as u64;
+        if header_length > MAX_HEADER_LENGTH as u64 {
+            return Err(PipelineError::ValidationError(format!(
+                "Header length {} exceeds the maximum of {} bytes",
+                header_length, MAX_HEADER_LENGTH
+            )));
+        }
+        let footer_size = header_length + FOOTER_TRAILER_SIZE;
+        if footer_size > source_size {
+            return Err(PipelineError::ValidationError(
+                "File too short for complete footer".to_string(),
+            ));
+        }
 
-        // Reopen file and seek to start of chunks
-        let mut file = tokio::fs::File::open(input_path)
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
-        file.seek(SeekFrom::Start(chunks_start_offset))
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+        let footer = source.read_at(source_size - footer_size, footer_size as usize).await?;
+        let (mut header, table_size) = FileHeader::from_footer_json(&footer)?;
+        let table_size = table_size as u64;
+        if footer_size + table_size > source_size {
+            return Err(PipelineError::ValidationError(
+                "File too short for chunk index".to_string(),
+            ));
+        }
+        let chunk_data_end = source_size - footer_size - table_size;
+        if table_size > 0 {
+            header.read_chunk_index(&source.read_at(chunk_data_end, table_size as usize).await?)?;
+        }
+        let chunk_offsets = Self::indexed_offsets(&header, chunk_data_end)?;
 
         Ok(Self {
-            file,
-            file_size,
-            header: Some(header),
+            source,
+            source_size,
+            header,
+            chunk_data_end,
+            position: 0,
             current_chunk_index: 0,
-            chunks_start_offset,
+            chunk_offsets,
         })
     }
+
+    /// Chunk offsets from the footer's chunk index, checked to tile the
+    /// chunk data and to place chunks in order within the original; empty
+    /// for files written without an index
+    fn indexed_offsets(header: &FileHeader, chunk_data_end: u64) -> Result<Vec<u64>, PipelineError> {
+        let index = &header.chunk_index;
+        let (Some(first), Some(last)) = (index.first(), index.last()) else {
+            return Ok(Vec::new());
+        };
+        let contiguous = first.offset == 0
+            && last.end() == chunk_data_end
+            && index.windows(2).all(|pair| pair[1].offset == pair[0].end());
+        if !contiguous {
+            return Err(PipelineError::ValidationError(
+                "Chunk index offsets do not tile the chunk data".to_string(),
+            ));
+        }
+        let in_order = first.original_offset == 0
+            && index
+                .windows(2)
+                .all(|pair| pair[1].original_offset >= pair[0].original_offset);
+        if !in_order {
+            return Err(PipelineError::ValidationError(
+                "Chunk index original offsets are out of order".to_string(),
+            ));
+        }
+        // Range reads size each chunk by the distance to the next offset
+        if let Some(entry) = index.iter().find(|entry| entry.original_offset > header.original_size) {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk index places a chunk at original offset {}, past the original's {} bytes",
+                entry.original_offset, header.original_size
+            )));
+        }
+        Ok(index.iter().map(|entry| entry.offset).collect())
+    }
+
+    /// Reads the chunk header at `offset`, returning its nonce and payload
+    /// length
+    async fn read_chunk_header(&self, offset: u64) -> Result<Option<([u8; 12], u64)>, PipelineError> {
+        if offset + CHUNK_HEADER_SIZE > self.chunk_data_end {
+            // Reached end of chunk data (before footer)
+            return Ok(None);
+        }
+        let chunk_header = self
+            .source
+            .read_at(offset, CHUNK_HEADER_SIZE as usize)
+            .await
+            .map_err(|e| PipelineError::IoError(format!("Failed to read chunk header: {}", e)))?;
+
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&chunk_header[0..12]);
+        let data_length =
+            u32::from_le_bytes([chunk_header[12], chunk_header[13], chunk_header[14], chunk_header[15]]) as u64;
+        if data_length > MAX_CHUNK_PAYLOAD_LENGTH as u64 {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk at offset {} declares a {} byte payload, above the maximum of {} bytes",
+                offset, data_length, MAX_CHUNK_PAYLOAD_LENGTH
+            )));
+        }
+        if offset + CHUNK_HEADER_SIZE + data_length > self.chunk_data_end {
+            return Err(PipelineError::IoError(format!(
+                "Failed to read chunk data: chunk at offset {} runs past the chunk data in {}",
+                offset,
+                self.source.location()
+            )));
+        }
+        Ok(Some((nonce, data_length)))
+    }
+
+    fn remember_offset(&mut self) {
+        if self.chunk_offsets.len() == self.current_chunk_index as usize {
+            self.chunk_offsets.push(self.position);
+        }
+    }
 }
 
 #[async_trait]
 impl BinaryFormatReader for StreamingBinaryReader {
     fn read_header(&self) -> Result<FileHeader, PipelineError> {
         // Return the header that was parsed during initialization
-        self.header
-            .clone()
-            .ok_or_else(|| PipelineError::ValidationError("Header not loaded".to_string()))
+        Ok(self.header.clone())
     }
 
     async fn read_next_chunk(&mut self) -> Result<Option<ChunkFormat>, PipelineError> {
         // Check if we've read all chunks
-        let header = self
-            .header
-            .as_ref()
-            .ok_or_else(|| PipelineError::ValidationError("Header not loaded".to_string()))?;
-
-        if self.current_chunk_index >= header.chunk_count {
+        if self.current_chunk_index >= self.header.chunk_count {
             return Ok(None); // EOF - all chunks read
         }
 
-        // Read chunk header first (12 bytes nonce + 4 bytes length)
-        let mut chunk_header = vec![0u8; 16];
-        match self.file.read_exact(&mut chunk_header).await {
-            Ok(_) => {}
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
-                // Reached end of chunk data (before footer)
-                return Ok(None);
-            }
-            Err(e) => {
-                return Err(PipelineError::IoError(format!("Failed to read chunk header: {}", e)));
-            }
-        }
-
-        // Parse nonce and data length
-        let mut nonce = [0u8; 12];
-        nonce.copy_from_slice(&chunk_header[0..12]);
-        let data_length =
-            u32::from_le_bytes([chunk_header[12], chunk_header[13], chunk_header[14], chunk_header[15]]) as usize;
-
-        // Read encrypted data
-        let mut encrypted_data = vec![0u8; data_length];
-        self.file
-            .read_exact(&mut encrypted_data)
+        let Some((nonce, data_length)) = self.read_chunk_header(self.position).await? else {
+            return Ok(None);
+        };
+        let payload = self
+            .source
+            .read_at(self.position + CHUNK_HEADER_SIZE, data_length as usize)
             .await
             .map_err(|e| PipelineError::IoError(format!("Failed to read chunk data: {}", e)))?;
 
-        // Create chunk format
-        let chunk = ChunkFormat::new(nonce, encrypted_data);
-
-        // Increment chunk index
+        self.remember_offset();
+        self.position += CHUNK_HEADER_SIZE + data_length;
         self.current_chunk_index += 1;
+        // A chunk must end where the index places the next one
+        if let Some(&next) = self.chunk_offsets.get(self.current_chunk_index as usize) {
+            if next != self.position {
+                return Err(PipelineError::IntegrityError(format!(
+                    "Chunk {} ends at offset {} but the chunk index places the next chunk at {}",
+                    self.current_chunk_index - 1,
+                    self.position,
+                    next
+                )));
+            }
+        }
 
-        Ok(Some(chunk))
+        Ok(Some(ChunkFormat::new(nonce, payload)))
     }
 
     async fn seek_to_chunk(&mut self, chunk_index: u32) -> Result<(), PipelineError> {
-        // For now, we'll implement a simple approach
-        // TODO: In production, we could maintain a chunk index for faster seeking
+        if chunk_index > self.header.chunk_count {
+            return Err(PipelineError::ValidationError("Chunk index out of bounds".to_string()));
+        }
 
-        if chunk_index == 0 {
-            self.file
-                .seek(SeekFrom::Start(self.chunks_start_offset))
-                .await
-                .map_err(|e| PipelineError::IoError(e.to_string()))?;
-            self.current_chunk_index = 0;
+        // Jump straight to a known offset, or to the furthest one known...
+        if let Some(&offset) = self.chunk_offsets.get(chunk_index as usize) {
+            self.position = offset;
+            self.current_chunk_index = chunk_index;
             return Ok(());
         }
+        match self.chunk_offsets.last() {
+            Some(&offset) => {
+                self.position = offset;
+                self.current_chunk_index = (self.chunk_offsets.len() - 1) as u32;
+            }
+            None => {
+                self.position = 0;
+                self.current_chunk_index = 0;
+            }
+        }
 
-        // Reset to beginning and skip chunks
-        self.file
-            .seek(SeekFrom::Start(self.chunks_start_offset))
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
-        self.current_chunk_index = 0;
-
-        // Skip chunks until we reach the desired index
-        for _ in 0..chunk_index {
-            if self.read_next_chunk().await?.is_none() {
+        // ...then walk forward reading only chunk headers
+        while self.current_chunk_index < chunk_index {
+            let Some((_, data_length)) = self.read_chunk_header(self.position).await? else {
                 return Err(PipelineError::ValidationError("Chunk index out of bounds".to_string()));
-            }
+            };
+            self.remember_offset();
+            self.position += CHUNK_HEADER_SIZE + data_length;
+            self.current_chunk_index += 1;
         }
 
         Ok(())
     }
 
-    async fn validate_integrity(&mut self) -> Result<bool, PipelineError> {
-        // Ensure we have header
-        let header = self
-            .header
-            .as_ref()
-            .ok_or_else(|| PipelineError::ValidationError("Header not loaded".to_string()))?;
-
-        // We need to calculate checksum of only the chunk data (not the footer)
-        // The footer contains:
-        // [JSON_HEADER][HEADER_LENGTH][FORMAT_VERSION][MAGIC_BYTES]
-
-        // First, get the footer size from the header
-        let footer_bytes = header.to_footer_bytes()?;
-        let footer_size = footer_bytes.len() as u64;
-
-        // Calculate the size of chunk data (total file size - footer size)
-        let chunk_data_size = self.file_size - footer_size;
-
-        // Seek to beginning of file
-        self.file
-            .seek(SeekFrom::Start(0))
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+    fn chunk_count(&self) -> u32 {
+        self.header.chunk_count
+    }
 
-        // Read only the chunk data (not the footer)
-        let mut chunk_data = vec![0u8; chunk_data_size as usize];
-        self.file
-            .read_exact(&mut chunk_data)
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+    fn source_size(&self) -> u64 {
+        self.source_size
+    }
 
-        // Calculate SHA256 checksum of chunk data
+    async fn validate_integrity(&mut self) -> Result<bool, PipelineError> {
+        // The checksum covers only the chunk data, not the footer:
+        // [CHUNK_INDEX][JSON_HEADER][HEADER_LENGTH][FORMAT_VERSION][MAGIC_BYTES]
         use sha2::Digest;
         let mut hasher = Sha256::new();
-        hasher.update(&chunk_data);
+        let mut offset = 0u64;
+        while offset < self.chunk_data_end {
+            let len = INTEGRITY_READ_SIZE.min(self.chunk_data_end - offset);
+            hasher.update(self.source.read_at(offset, len as usize).await?);
+            offset += len;
+        }
         let calculated_checksum = format!("{:x}", hasher.finalize());
 
         // Compare with stored checksum
-        let is_valid = calculated_checksum == header.output_checksum;
+        let is_valid = constant_time_eq_str(&calculated_checksum, &self.header.output_checksum);
 
-        // Reset file position to continue reading chunks if needed
-        self.file
-            .seek(SeekFrom::Start(self.chunks_start_offset))
-            .await
-            .map_err(|e| PipelineError::IoError(e.to_string()))?;
+        // Reset position to continue reading chunks if needed
+        self.position = 0;
         self.current_chunk_index = 0;
 
         Ok(is_valid)
@@ -1093,7 +1253,8 @@ impl Drop for TransactionalBinaryWriter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use adaptive_pipeline_domain::value_objects::{ChunkFormat, FileHeader};
+    use adaptive_pipeline_domain::value_objects::binary_file_format::CURRENT_FORMAT_VERSION;
+    use adaptive_pipeline_domain::value_objects::{ChunkFormat, FileHeader, NonceStrategy};
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -1109,7 +1270,7 @@ mod tests {
             "original_checksum_abc123".to_string(),
         )
         .add_compression_step("brotli", 6)
-        .add_encryption_step("aes256gcm", "argon2", 32, 12)
+        .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
         .with_chunk_info(1024, 2)
         .with_pipeline_id("test-pipeline".to_string());
 
@@ -1127,6 +1288,10 @@ mod tests {
         let final_header = header.clone();
         writer.finalize(final_header).await.unwrap();
 
+        // The writer's whole-file digest matches the bytes on disk
+        let on_disk = std::fs::read(&test_file_path).unwrap();
+        assert_eq!(writer.file_checksum(), Some(format!("{:x}", Sha256::digest(&on_disk))));
+
         // Read the file back
         let mut reader = service.create_reader(&test_file_path).await.unwrap();
 
@@ -1189,7 +1354,7 @@ mod tests {
         let validation_result = service.validate_file(&test_file_path).await.unwrap();
         assert!(validation_result.is_valid);
         assert_eq!(validation_result.chunk_count, 1);
-        assert_eq!(validation_result.format_version, 1);
+        assert_eq!(validation_result.format_version, CURRENT_FORMAT_VERSION);
         assert!(validation_result.integrity_verified);
         assert!(validation_result.errors.is_empty());
     }
@@ -1206,7 +1371,7 @@ mod tests {
             4096,
             "checksum_metadata_test".to_string(),
         )
-        .add_encryption_step("chacha20poly1305", "pbkdf2", 32, 12)
+        .add_encryption_step("chacha20poly1305", "pbkdf2", 32, NonceStrategy::Random)
         .with_chunk_info(2048, 2)
         .with_pipeline_id("metadata-pipeline".to_string())
         .with_metadata("custom_key".to_string(), "custom_value".to_string());
@@ -1230,7 +1395,7 @@ mod tests {
         assert_eq!(metadata.pipeline_id, "metadata-pipeline");
         assert!(metadata.is_encrypted());
         assert!(!metadata.is_compressed());
-        assert_eq!(metadata.encryption_algorithm(), Some("chacha20poly1305"));
+        assert_eq!(metadata.encryption_algorithm(), Some("chacha20-poly1305"));
         assert_eq!(metadata.metadata.get("custom_key"), Some(&"custom_value".to_string()));
     }
 
@@ -1279,4 +1444,169 @@ mod tests {
         assert_eq!(read_chunk.nonce, chunk2.nonce);
         assert_eq!(read_chunk.payload, chunk2.payload);
     }
+
+    #[tokio::test]
+    async fn test_writer_over_memory_sink() {
+        use crate::infrastructure::adapters::random_access_sink::MemorySink;
+
+        let header =
+            FileHeader::new("memory.txt".to_string(), 4, "checksum_memory".to_string()).with_chunk_info(1024, 1);
+        let chunk = ChunkFormat::new([7u8; 12], vec![0x0d, 0x0e, 0x0f, 0x10]);
+
+        let sink = Arc::new(MemorySink::new());
+        let service = AdapipeFormat::new();
+        let writer = service.create_writer_to(sink.clone(), header.clone()).await.unwrap();
+        writer.write_chunk_at_position(chunk.clone(), 0).await.unwrap();
+        let total = writer.finalize(header).await.unwrap();
+        assert_eq!(total, sink.contents().len() as u64);
+
+        // The buffer holds a complete .adapipe file
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("memory.adapipe");
+        std::fs::write(&path, sink.contents()).unwrap();
+        let mut reader = service.create_reader(&path).await.unwrap();
+        assert_eq!(reader.chunk_count(), 1);
+        let read_chunk = reader.read_next_chunk().await.unwrap().unwrap();
+        assert_eq!(read_chunk.payload, chunk.payload);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_chunks_of_different_sizes_are_placed_in_sequence() {
+        use crate::infrastructure::adapters::random_access_sink::MemorySink;
+
+        let header =
+            FileHeader::new("ordered.txt".to_string(), 2051, "checksum_ordered".to_string()).with_chunk_info(1024, 3);
+        let chunks = [
+            ChunkFormat::new([1u8; 12], vec![1, 2, 3, 4, 5]),
+            ChunkFormat::new([2u8; 12], vec![6]),
+            ChunkFormat::new([3u8; 12], vec![7, 8, 9]),
+        ];
+
+        let sink = Arc::new(MemorySink::new());
+        let service = AdapipeFormat::new();
+        let writer = service.create_writer_to(sink.clone(), header.clone()).await.unwrap();
+        for index in [2, 0, 1] {
+            writer
+                .write_chunk_at_position(chunks[index].clone(), index as u64)
+                .await
+                .unwrap();
+        }
+        assert!(writer.write_chunk_at_position(chunks[0].clone(), 0).await.is_err());
+        writer.finalize(header).await.unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ordered.adapipe");
+        std::fs::write(&path, sink.contents()).unwrap();
+        let mut reader = service.create_reader(&path).await.unwrap();
+        for chunk in &chunks {
+            assert_eq!(reader.read_next_chunk().await.unwrap().unwrap().payload, chunk.payload);
+        }
+        assert!(reader.validate_integrity().await.unwrap());
+        let index = reader.read_header().unwrap().chunk_index;
+        let placed: Vec<_> = index.iter().map(|entry| (entry.offset, entry.length)).collect();
+        assert_eq!(placed, [(0, 5), (21, 1), (38, 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_seeks_by_the_chunk_index_and_checks_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("indexed.adapipe");
+        let header =
+            FileHeader::new("indexed.txt".to_string(), 2051, "checksum_indexed".to_string()).with_chunk_info(1024, 3);
+        let chunks = [
+            ChunkFormat::new([1u8; 12], vec![1, 2, 3, 4, 5]),
+            ChunkFormat::new([2u8; 12], vec![6]),
+            ChunkFormat::new([3u8; 12], vec![7, 8, 9]),
+        ];
+        let service = AdapipeFormat::new();
+        let mut writer = service.create_writer(&path, header.clone()).await.unwrap();
+        for chunk in &chunks {
+            writer.write_chunk(chunk.clone()).unwrap();
+        }
+        writer.finalize(header.clone()).await.unwrap();
+
+        let mut reader = service.create_reader(&path).await.unwrap();
+        reader.seek_to_chunk(2).await.unwrap();
+        assert_eq!(
+            reader.read_next_chunk().await.unwrap().unwrap().payload,
+            chunks[2].payload
+        );
+
+        let index = reader.read_header().unwrap().chunk_index;
+        assert_eq!(index[2], ChunkIndexEntry::new(38, 3, 2048));
+
+        // Rewrite the footer with forged indexes over the same chunk data
+        let chunk_data = std::fs::read(&path).unwrap()[..57].to_vec();
+        let forge = |entries: &[(u64, u32, u64)]| {
+            let index = entries
+                .iter()
+                .map(|&(offset, length, original_offset)| ChunkIndexEntry::new(offset, length, original_offset))
+                .collect();
+            let mut data = chunk_data.clone();
+            data.extend(header.clone().with_chunk_index(index).to_footer_bytes().unwrap());
+            std::fs::write(&path, data).unwrap();
+        };
+        forge(&[(0, 5, 0), (21, 1, 1024)]);
+        let error = service.create_reader(&path).await.err().unwrap();
+        assert!(error.to_string().contains("lists 2 chunks"), "{}", error);
+        forge(&[(0, 5, 0), (22, 1, 1024), (38, 3, 2048)]);
+        assert!(service.create_reader(&path).await.is_err());
+        forge(&[(0, 5, 0), (21, 1, 2048), (38, 3, 1024)]);
+        assert!(service.create_reader(&path).await.is_err());
+        forge(&[(0, 5, 0), (21, 1, 1024), (38, 3, 4096)]);
+        let error = service.create_reader(&path).await.err().unwrap();
+        assert!(error.to_string().contains("past the original"), "{}", error);
+
+        // A chunk whose header disagrees with the index fails when read
+        forge(&[(0, 5, 0), (21, 1, 1024), (38, 3, 2048)]);
+        let forged = std::fs::read(&path).unwrap();
+        let mut tampered = chunk_data.clone();
+        tampered[33] = 2;
+        tampered.extend_from_slice(&forged[57..]);
+        std::fs::write(&path, tampered).unwrap();
+        let mut reader = service.create_reader(&path).await.unwrap();
+        reader.read_next_chunk().await.unwrap();
+        let error = reader.read_next_chunk().await.unwrap_err();
+        assert!(matches!(error, PipelineError::IntegrityError(_)), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_preallocated_output_is_trimmed_on_finalize() {
+        use crate::infrastructure::adapters::random_access_sink::PreallocatedFileSink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("reserved.adapipe");
+        let header =
+            FileHeader::new("reserved.txt".to_string(), 4, "checksum_reserved".to_string()).with_chunk_info(1024, 1);
+        let chunk = ChunkFormat::new([9u8; 12], vec![0x11, 0x12, 0x13, 0x14]);
+
+        let reserve = worst_case_output_size(1024, 1);
+        assert!(reserve > 1024 + 16 + 16);
+        let sink = Arc::new(PreallocatedFileSink::create(&path, reserve).await.unwrap());
+
+        let service = AdapipeFormat::new();
+        let writer = service.create_writer_to(sink, header.clone()).await.unwrap();
+        writer.write_chunk_at_position(chunk.clone(), 0).await.unwrap();
+        let total = writer.finalize(header).await.unwrap();
+
+        // The reservation is gone and the footer is where readers expect it
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), total);
+        let mut reader = service.create_reader(&path).await.unwrap();
+        let read_chunk = reader.read_next_chunk().await.unwrap().unwrap();
+        assert_eq!(read_chunk.payload, chunk.payload);
+    }
+
+    #[tokio::test]
+    async fn test_reader_rejects_forged_header_length() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("forged.adapipe");
+        let mut data = vec![0u8; 64];
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend_from_slice(&CURRENT_FORMAT_VERSION.to_le_bytes());
+        data.extend_from_slice(&MAGIC_BYTES);
+        std::fs::write(&path, data).unwrap();
+
+        let error = AdapipeFormat::new().create_reader(&path).await.err().unwrap();
+        assert!(error.to_string().contains("exceeds the maximum"));
+    }
 }
@@ -0,0 +1,780 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Sources
+//!
+//! Random-access byte sources that `.adapipe` readers pull chunks from. A
+//! reader only needs the source's size and ranged reads, so the same reader
+//! serves local files, HTTP servers and object stores.
+//!
+//! ## Sources
+//!
+//! | Location                 | Source              | Reads with              |
+//! |--------------------------|---------------------|-------------------------|
+//! | `/path/to/file.adapipe`  | [`FileSource`]      | seek + read             |
+//! | `https://host/file`      | [`HttpRangeSource`] | `Range: bytes=a-b`      |
+//! | `s3://bucket/key`        | [`ObjectStoreSource`] | signed ranged GET       |
+//!
+//! [`open_source`] picks the source from a location string.
+//!
+//! ## I/O Tokens
+//!
+//! When the global resource manager is running, each [`FileSource`] read
+//! holds a local I/O token and each remote request holds a network I/O
+//! token. The two pools are sized separately (`--io-threads` and
+//! `--network-io-threads`), so slow remote reads never hold up local ones.
+//! New remote adapters should take the network token the same way.
+//!
+//! ## Remote Sources
+//!
+//! HTTP sources are read with `reqwest` over HTTP/1.1 or HTTPS (rustls with
+//! the platform's root certificates), so chunked and compressed-transfer
+//! responses are handled by the client. Connecting and each read are bounded
+//! by timeouts, and only the requested bytes are kept: a server that ignores
+//! the range and sends the whole file is read up to the end of the range and
+//! then dropped, so a hung or hostile server can neither stall a restore nor
+//! exhaust memory.
+//!
+//! Objects are read through `object_store`'s S3 client, which signs each
+//! request with SigV4. Credentials, region and endpoint come from the usual
+//! `AWS_*` environment variables (`AWS_ACCESS_KEY_ID`,
+//! `AWS_SECRET_ACCESS_KEY`, `AWS_REGION`, `AWS_ENDPOINT`, ...) or the
+//! instance's role; `ADAPIPE_OBJECT_STORE_ENDPOINT` names an S3-compatible
+//! endpoint instead, addressed path-style. Plain-HTTP endpoints are refused
+//! unless `AWS_ALLOW_HTTP=true`.
+
+use crate::infrastructure::runtime::try_resource_manager;
+use adaptive_pipeline_domain::PipelineError;
+use async_trait::async_trait;
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::{ClientConfigKey, ObjectStore};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::{Mutex, OnceCell};
+
+/// Environment variable naming an S3-compatible endpoint to use instead of
+/// AWS
+pub const OBJECT_STORE_ENDPOINT_ENV: &str = "ADAPIPE_OBJECT_STORE_ENDPOINT";
+
+/// How long to wait for an HTTP server to accept a connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for each read of an HTTP response
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bytes read from a file per chunk of an upload
+const UPLOAD_BUFFER_BYTES: usize = 64 * 1024;
+
+/// HTTP client shared by every source, so connections and the loaded root
+/// certificates are reused
+static HTTP_CLIENT: once_cell::sync::OnceCell<reqwest::Client> = once_cell::sync::OnceCell::new();
+
+/// A random-access source of bytes
+#[async_trait]
+pub trait ChunkSource: Send + Sync {
+    /// Human-readable location, for error messages
+    fn location(&self) -> String;
+
+    /// Total size in bytes
+    async fn size(&self) -> Result<u64, PipelineError>;
+
+    /// Reads exactly `len` bytes starting at `offset`
+    async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError>;
+}
+
+/// Whether `location` names a remote (HTTP or object store) source
+pub fn is_remote_location(location: &str) -> bool {
+    ["http://", "https://", "s3://"]
+        .iter()
+        .any(|scheme| location.starts_with(scheme))
+}
+
+/// Opens the source for `location`: an `http(s)://` or `s3://` URL, or a
+/// path
+pub fn open_source(location: &str) -> Result<Arc<dyn ChunkSource>, PipelineError> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        Ok(Arc::new(HttpRangeSource::new(location)?))
+    } else if location.starts_with("s3://") {
+        Ok(Arc::new(ObjectStoreSource::open(location)?))
+    } else {
+        Ok(Arc::new(FileSource::new(location)))
+    }
+}
+
+/// Splits `s3://bucket/rest` into the bucket and the rest, which may be
+/// empty
+pub fn split_s3_location(location: &str) -> Option<(&str, &str)> {
+    let rest = location.strip_prefix("s3://")?;
+    let (bucket, rest) = rest.split_once('/').unwrap_or((rest, ""));
+    (!bucket.is_empty()).then_some((bucket, rest))
+}
+
+/// The S3 bucket `bucket`, signing requests with the credentials in the
+/// environment
+///
+/// # Errors
+///
+/// Returns `InvalidConfiguration` if the environment's S3 settings are
+/// invalid, e.g. a plain-HTTP endpoint without `AWS_ALLOW_HTTP=true`.
+pub fn s3_bucket(bucket: &str) -> Result<Arc<dyn ObjectStore>, PipelineError> {
+    let mut builder = AmazonS3Builder::from_env().with_bucket_name(bucket);
+    if let Ok(endpoint) = std::env::var(OBJECT_STORE_ENDPOINT_ENV) {
+        builder = builder.with_endpoint(endpoint);
+    }
+    // Refused up front, rather than retried on every request
+    let plain_http = builder
+        .get_config_value(&AmazonS3ConfigKey::Endpoint)
+        .is_some_and(|endpoint| endpoint.starts_with("http://"));
+    let http_allowed = builder
+        .get_config_value(&AmazonS3ConfigKey::Client(ClientConfigKey::AllowHttp))
+        .is_some_and(|allowed| allowed.eq_ignore_ascii_case("true"));
+    if plain_http && !http_allowed {
+        return Err(PipelineError::invalid_config(format!(
+            "Cannot reach S3 bucket {} over plain HTTP; use an https:// endpoint or set AWS_ALLOW_HTTP=true",
+            bucket
+        )));
+    }
+    let store = builder
+        .build()
+        .map_err(|e| PipelineError::invalid_config(format!("Cannot reach S3 bucket {}: {}", bucket, e)))?;
+    Ok(Arc::new(store))
+}
+
+/// Local file source
+pub struct FileSource {
+    path: PathBuf,
+    file: OnceCell<Mutex<tokio::fs::File>>,
+}
+
+impl FileSource {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            file: OnceCell::new(),
+        }
+    }
+
+    async fn file(&self) -> Result<&Mutex<tokio::fs::File>, PipelineError> {
+        self.file
+            .get_or_try_init(|| async {
+                tokio::fs::File::open(&self.path)
+                    .await
+                    .map(Mutex::new)
+                    .map_err(|e| PipelineError::io_error(format!("Failed to open {}: {}", self.path.display(), e)))
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl ChunkSource for FileSource {
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        let file = self.file().await?.lock().await;
+        file.metadata()
+            .await
+            .map(|metadata| metadata.len())
+            .map_err(|e| PipelineError::io_error(format!("Failed to stat {}: {}", self.path.display(), e)))
+    }
+
+    async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError> {
+        let _io_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_io().await?),
+            None => None,
+        };
+        let mut file = self.file().await?.lock().await;
+        file.seek(SeekFrom::Start(offset))
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to seek to offset {}: {}", offset, e)))?;
+        let mut buffer = vec![0u8; len];
+        file.read_exact(&mut buffer).await.map_err(|e| {
+            PipelineError::io_error(format!("Failed to read {} bytes at offset {}: {}", len, offset, e))
+        })?;
+        Ok(buffer)
+    }
+}
+
+/// HTTP(S) source read with `Range` requests
+pub struct HttpRangeSource {
+    url: String,
+    client: reqwest::Client,
+    size: OnceCell<u64>,
+    read_timeout: Duration,
+}
+
+impl HttpRangeSource {
+    /// Creates a source for an `http://` or `https://` URL
+    pub fn new(url: &str) -> Result<Self, PipelineError> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| PipelineError::invalid_config(format!("Invalid URL {}: {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(PipelineError::invalid_config(format!("Not an http(s):// URL: {}", url)));
+        }
+        if parsed.host_str().is_none_or(str::is_empty) {
+            return Err(PipelineError::invalid_config(format!("Missing host in URL: {}", url)));
+        }
+        let client = HTTP_CLIENT
+            .get_or_try_init(|| reqwest::Client::builder().connect_timeout(CONNECT_TIMEOUT).build())
+            .map_err(|e| PipelineError::invalid_config(format!("Failed to set up the HTTP client: {}", e)))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            client: client.clone(),
+            size: OnceCell::new(),
+            read_timeout: READ_TIMEOUT,
+        })
+    }
+
+    /// Fails a request when the server sends nothing for `read_timeout`
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    fn request_failed(&self, e: reqwest::Error) -> PipelineError {
+        PipelineError::io_error(format!("HTTP request to {} failed: {}", self.url, e))
+    }
+
+    fn stalled(&self) -> PipelineError {
+        PipelineError::io_error(format!(
+            "{} sent nothing for {:?}; giving up",
+            self.url, self.read_timeout
+        ))
+    }
+
+    /// Sends `request` and waits at most the read timeout for the response
+    /// head
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, PipelineError> {
+        tokio::time::timeout(self.read_timeout, request.send())
+            .await
+            .map_err(|_| self.stalled())?
+            .map_err(|e| self.request_failed(e))
+    }
+
+    /// The next piece of `response`'s body, which must arrive within the
+    /// read timeout
+    async fn next_chunk(&self, response: &mut reqwest::Response) -> Result<Option<Vec<u8>>, PipelineError> {
+        tokio::time::timeout(self.read_timeout, response.chunk())
+            .await
+            .map_err(|_| self.stalled())?
+            .map(|chunk| chunk.map(|chunk| chunk.to_vec()))
+            .map_err(|e| self.request_failed(e))
+    }
+
+    /// Uploads the file at `path` with a `PUT` to this URL
+    ///
+    /// The body streams from disk, so uploads take no more memory than a
+    /// copy buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the file can't be read or changes size, the
+    /// request fails, or the server answers with anything but 200, 201 or
+    /// 204.
+    pub async fn put_file(&self, path: &Path) -> Result<(), PipelineError> {
+        let mut file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to open {}: {}", path.display(), e)))?;
+        let len = file
+            .metadata()
+            .await
+            .map_err(|e| PipelineError::io_error(format!("Failed to stat {}: {}", path.display(), e)))?
+            .len();
+        let _network_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_network_io().await?),
+            None => None,
+        };
+
+        let display = path.display().to_string();
+        let body = async_stream::stream! {
+            let mut buffer = vec![0u8; UPLOAD_BUFFER_BYTES];
+            let mut sent = 0u64;
+            loop {
+                match file.read(&mut buffer).await {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        sent += n as u64;
+                        if sent > len {
+                            break;
+                        }
+                        yield Ok(buffer[..n].to_vec());
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+            if sent != len {
+                yield Err(std::io::Error::other(format!("{} changed size during upload", display)));
+            }
+        };
+        let request = self
+            .client
+            .put(&self.url)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_LENGTH, len)
+            .body(reqwest::Body::wrap_stream(body));
+        let response = request
+            .send()
+            .await
+            .map_err(|e| PipelineError::io_error(format!("HTTP upload to {} failed: {}", self.url, e)))?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED | StatusCode::NO_CONTENT => Ok(()),
+            status => Err(PipelineError::io_error(format!(
+                "PUT {} returned HTTP {}",
+                self.url,
+                status.as_u16()
+            ))),
+        }
+    }
+}
+
+/// First byte of a `Content-Range: bytes first-last/size` header
+fn content_range_start(value: &str) -> Option<u64> {
+    value.strip_prefix("bytes ")?.split_once('-')?.0.trim().parse().ok()
+}
+
+#[async_trait]
+impl ChunkSource for HttpRangeSource {
+    fn location(&self) -> String {
+        self.url.clone()
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        self.size
+            .get_or_try_init(|| async {
+                let _network_permit = match try_resource_manager() {
+                    Some(manager) => Some(manager.acquire_network_io().await?),
+                    None => None,
+                };
+                let response = self.send(self.client.head(&self.url)).await?;
+                if response.status() != StatusCode::OK {
+                    return Err(PipelineError::io_error(format!(
+                        "HEAD {} returned HTTP {}",
+                        self.url,
+                        response.status().as_u16()
+                    )));
+                }
+                // The header, since the body of a HEAD response is empty
+                response
+                    .headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|length| length.to_str().ok())
+                    .and_then(|length| length.parse::<u64>().ok())
+                    .ok_or_else(|| PipelineError::io_error(format!("{} did not report its size", self.url)))
+            })
+            .await
+            .copied()
+    }
+
+    async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError> {
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        let last = offset + len as u64 - 1;
+        let _network_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_network_io().await?),
+            None => None,
+        };
+        let request = self
+            .client
+            .get(&self.url)
+            .header(RANGE, format!("bytes={}-{}", offset, last));
+        let mut response = self.send(request).await?;
+
+        // Body bytes that come before the range
+        let mut skip = match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                let start = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .and_then(|range| range.to_str().ok())
+                    .map(content_range_start);
+                if start.is_some_and(|start| start != Some(offset)) {
+                    return Err(PipelineError::io_error(format!(
+                        "GET {} bytes {}-{} returned a different range",
+                        self.url, offset, last
+                    )));
+                }
+                0
+            }
+            // Server ignored the range and is sending everything
+            StatusCode::OK => offset,
+            status => {
+                return Err(PipelineError::io_error(format!(
+                    "GET {} bytes {}-{} returned HTTP {}",
+                    self.url,
+                    offset,
+                    last,
+                    status.as_u16()
+                )))
+            }
+        };
+
+        // Only the range is kept; the rest of the response is never read
+        let mut body = Vec::with_capacity(len);
+        while body.len() < len {
+            let Some(chunk) = self.next_chunk(&mut response).await? else {
+                break;
+            };
+            let skipped = skip.min(chunk.len() as u64) as usize;
+            skip -= skipped as u64;
+            let wanted = (chunk.len() - skipped).min(len - body.len());
+            body.extend_from_slice(&chunk[skipped..skipped + wanted]);
+        }
+        if body.len() != len {
+            return Err(PipelineError::io_error(format!(
+                "GET {} bytes {}-{} returned {} of {} bytes",
+                self.url,
+                offset,
+                last,
+                body.len(),
+                len
+            )));
+        }
+        Ok(body)
+    }
+}
+
+/// Object in an S3-compatible store, read with signed requests
+pub struct ObjectStoreSource {
+    location: String,
+    store: Arc<dyn ObjectStore>,
+    key: object_store::path::Path,
+}
+
+impl ObjectStoreSource {
+    /// Creates a source for `s3://bucket/key`, reading through `store`, the
+    /// object's bucket
+    pub fn new(location: &str, store: Arc<dyn ObjectStore>) -> Result<Self, PipelineError> {
+        let (_, key) = split_s3_location(location)
+            .filter(|(_, key)| !key.is_empty())
+            .ok_or_else(|| PipelineError::invalid_config(format!("Expected s3://bucket/key, got {}", location)))?;
+
+        Ok(Self {
+            location: location.to_string(),
+            store,
+            key: object_store::path::Path::from(key),
+        })
+    }
+
+    /// Opens `s3://bucket/key` with the S3 settings in the environment (see
+    /// [`s3_bucket`])
+    pub fn open(location: &str) -> Result<Self, PipelineError> {
+        let (bucket, _) = split_s3_location(location)
+            .ok_or_else(|| PipelineError::invalid_config(format!("Expected s3://bucket/key, got {}", location)))?;
+        Self::new(location, s3_bucket(bucket)?)
+    }
+
+    fn request_failed(&self, e: object_store::Error) -> PipelineError {
+        PipelineError::io_error(format!("Reading {} failed: {}", self.location, e))
+    }
+}
+
+#[async_trait]
+impl ChunkSource for ObjectStoreSource {
+    fn location(&self) -> String {
+        self.location.clone()
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        let _network_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_network_io().await?),
+            None => None,
+        };
+        let meta = self.store.head(&self.key).await.map_err(|e| self.request_failed(e))?;
+        Ok(meta.size)
+    }
+
+    async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError> {
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        let _network_permit = match try_resource_manager() {
+            Some(manager) => Some(manager.acquire_network_io().await?),
+            None => None,
+        };
+        let bytes = self
+            .store
+            .get_range(&self.key, offset..offset + len as u64)
+            .await
+            .map_err(|e| self.request_failed(e))?;
+        if bytes.len() != len {
+            return Err(PipelineError::io_error(format!(
+                "Reading {} bytes {}-{} returned {} of {} bytes",
+                self.location,
+                offset,
+                offset + len as u64 - 1,
+                bytes.len(),
+                len
+            )));
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+    use tokio::io::AsyncWriteExt;
+
+    /// Response head for `body`, as an object store would send it
+    fn head(status: &str, extra: &str, body_len: usize) -> Vec<u8> {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nETag: \"1\"\r\nLast-Modified: Thu, 01 Jan 2026 00:00:00 GMT\r\n{}Connection: close\r\n\r\n",
+            status, body_len, extra
+        )
+        .into_bytes()
+    }
+
+    /// Builds the raw response to a GET of the content and its range
+    type Respond = fn(&[u8], Option<(usize, usize)>) -> Vec<u8>;
+
+    /// Serves `content` over HTTP, one request per connection, answering
+    /// ranges with `respond(content, range)`; returns the base URL and the
+    /// request heads received
+    async fn serve_with(content: Vec<u8>, respond: Respond) -> (String, Arc<StdMutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(StdMutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buffer).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..n]);
+                }
+                let request = String::from_utf8_lossy(&request).to_string();
+                let range = request
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("range").then(|| value.trim().to_string())
+                    })
+                    .and_then(|range| {
+                        let (first, last) = range.strip_prefix("bytes=")?.split_once('-')?;
+                        Some((first.parse::<usize>().unwrap(), last.parse::<usize>().unwrap()))
+                    });
+                let response = if request.starts_with("HEAD ") {
+                    head("200 OK", "", content.len())
+                } else {
+                    respond(&content, range)
+                };
+                received.lock().unwrap().push(request);
+                let _ = stream.write_all(&response).await;
+            }
+        });
+        (format!("http://{}", addr), requests)
+    }
+
+    /// Serves `content` with range support
+    async fn serve(content: Vec<u8>) -> String {
+        serve_with(content, |content, range| match range {
+            Some((first, last)) => {
+                let body = &content[first..=last];
+                let range = format!("Content-Range: bytes {}-{}/{}\r\n", first, last, content.len());
+                let mut response = head("206 Partial Content", &range, body.len());
+                response.extend_from_slice(body);
+                response
+            }
+            None => {
+                let mut response = head("200 OK", "", content.len());
+                response.extend_from_slice(content);
+                response
+            }
+        })
+        .await
+        .0
+    }
+
+    #[tokio::test]
+    async fn test_file_source_reads_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, b"0123456789").unwrap();
+
+        let source = open_source(&path.to_string_lossy()).unwrap();
+        assert_eq!(source.size().await.unwrap(), 10);
+        assert_eq!(source.read_at(3, 4).await.unwrap(), b"3456");
+        assert!(source.read_at(8, 4).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_range_source_reads_ranges() {
+        let base = serve(b"abcdefghijklmnopqrstuvwxyz".to_vec()).await;
+        let source = HttpRangeSource::new(&format!("{}/files/alphabet.adapipe", base)).unwrap();
+
+        assert_eq!(source.size().await.unwrap(), 26);
+        assert_eq!(source.read_at(0, 3).await.unwrap(), b"abc");
+        assert_eq!(source.read_at(23, 3).await.unwrap(), b"xyz");
+    }
+
+    #[tokio::test]
+    async fn test_http_range_source_reads_chunked_and_whole_file_responses() {
+        // Ignores the range and sends the whole file in chunked encoding
+        let (base, _) = serve_with(b"abcdefghijklmnopqrstuvwxyz".to_vec(), |content, _| {
+            let mut response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n".to_vec();
+            for piece in content.chunks(5) {
+                response.extend_from_slice(format!("{:x}\r\n", piece.len()).as_bytes());
+                response.extend_from_slice(piece);
+                response.extend_from_slice(b"\r\n");
+            }
+            response.extend_from_slice(b"0\r\n\r\n");
+            response
+        })
+        .await;
+        let source = HttpRangeSource::new(&format!("{}/alphabet", base)).unwrap();
+        assert_eq!(source.read_at(3, 4).await.unwrap(), b"defg");
+        assert_eq!(source.read_at(23, 3).await.unwrap(), b"xyz");
+        assert!(source.read_at(24, 3).await.is_err(), "past the end");
+
+        // A whole file far larger than the range still yields just the range
+        let (base, _) = serve_with(vec![b'x'; 1 << 20], |content, _| {
+            let mut response = head("200 OK", "", content.len());
+            response.extend_from_slice(content);
+            response
+        })
+        .await;
+        let source = HttpRangeSource::new(&format!("{}/big", base)).unwrap();
+        assert_eq!(source.read_at(512 * 1024, 16).await.unwrap(), vec![b'x'; 16]);
+    }
+
+    #[tokio::test]
+    async fn test_http_range_source_refuses_wrong_ranges_and_times_out() {
+        // Answers every range with the start of the file
+        let (base, _) = serve_with(b"abcdefghijklmnopqrstuvwxyz".to_vec(), |content, range| {
+            let (first, last) = range.unwrap();
+            let body = &content[..=last - first];
+            let range = format!("Content-Range: bytes 0-{}/{}\r\n", last - first, content.len());
+            let mut response = head("206 Partial Content", &range, body.len());
+            response.extend_from_slice(body);
+            response
+        })
+        .await;
+        let source = HttpRangeSource::new(&format!("{}/shifted", base)).unwrap();
+        let error = source.read_at(10, 4).await.unwrap_err();
+        assert!(error.to_string().contains("different range"), "{}", error);
+
+        // Accepts the connection and never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+        let source = HttpRangeSource::new(&format!("http://{}/hung", addr))
+            .unwrap()
+            .with_read_timeout(Duration::from_millis(100));
+        let error = source.read_at(0, 16).await.unwrap_err();
+        assert!(error.to_string().contains("sent nothing"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_object_store_source_signs_path_style_requests() {
+        let (base, requests) = serve_with(b"object".to_vec(), |content, range| {
+            let (first, last) = range.unwrap();
+            let body = &content[first..=last];
+            let range = format!("Content-Range: bytes {}-{}/{}\r\n", first, last, content.len());
+            let mut response = head("206 Partial Content", &range, body.len());
+            response.extend_from_slice(body);
+            response
+        })
+        .await;
+        let bucket = AmazonS3Builder::new()
+            .with_bucket_name("bucket")
+            .with_endpoint(base)
+            .with_allow_http(true)
+            .with_access_key_id("AKIDEXAMPLE")
+            .with_secret_access_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+            .build()
+            .unwrap();
+        let source = ObjectStoreSource::new("s3://bucket/dir/file.adapipe", Arc::new(bucket)).unwrap();
+
+        assert_eq!(source.location(), "s3://bucket/dir/file.adapipe");
+        assert_eq!(source.size().await.unwrap(), 6);
+        assert_eq!(source.read_at(1, 3).await.unwrap(), b"bje");
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests.iter() {
+            assert!(
+                request.contains(" /bucket/dir/file.adapipe HTTP/1.1\r\n"),
+                "{}",
+                request
+            );
+            assert!(
+                request
+                    .to_ascii_lowercase()
+                    .contains("authorization: aws4-hmac-sha256 credential=akidexample/"),
+                "{}",
+                request
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_file_uploads_the_whole_file() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // The client waits for the response, so read up to the body's end
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"0123456789") {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "upload ended early");
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 201 Created\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap().to_ascii_lowercase()
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, b"0123456789").unwrap();
+
+        let source = HttpRangeSource::new(&format!("http://{}/bucket/data.bin", addr)).unwrap();
+        source.put_file(&path).await.unwrap();
+        let request = server.await.unwrap();
+        assert!(request.starts_with("put /bucket/data.bin http/1.1\r\n"), "{}", request);
+        assert!(request.contains("content-length: 10\r\n"), "{}", request);
+        assert!(request.ends_with("\r\n\r\n0123456789"), "{}", request);
+    }
+
+    #[test]
+    fn test_invalid_locations() {
+        assert!(HttpRangeSource::new("https://example.com/file").is_ok());
+        assert!(HttpRangeSource::new("ftp://example.com/file").is_err());
+        assert!(HttpRangeSource::new("http://:80/file").is_err());
+        let bucket: Arc<dyn ObjectStore> = Arc::new(object_store::memory::InMemory::new());
+        assert!(ObjectStoreSource::new("s3://bucket-only", bucket).is_err());
+        assert_eq!(split_s3_location("s3://bucket"), Some(("bucket", "")));
+        assert_eq!(split_s3_location("s3://bucket/a/b"), Some(("bucket", "a/b")));
+        assert_eq!(split_s3_location("s3:///key"), None);
+        assert!(is_remote_location("s3://bucket/key"));
+        assert!(!is_remote_location("/tmp/file.adapipe"));
+    }
+}
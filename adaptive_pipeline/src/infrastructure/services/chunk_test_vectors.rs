@@ -0,0 +1,172 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Encryption Test Vectors
+//!
+//! Generates and verifies the known-answer vectors described in
+//! [`chunk_encryption_spec`](adaptive_pipeline_domain::value_objects::chunk_encryption_spec).
+//!
+//! Vectors are produced by [`MultiAlgoEncryption::seal_with_nonce`], the
+//! function `encrypt_chunk` uses, with fixed keys, nonces and plaintexts in
+//! place of random ones. Verification runs each vector back through the same
+//! code in both directions, so a change to the chunk layout, the associated
+//! data or the record framing makes a previously published suite fail.
+
+use adaptive_pipeline_domain::services::EncryptionAlgorithm;
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::{
+    CHUNK_ASSOCIATED_DATA, CHUNK_KEY_LENGTH, CHUNK_NONCE_LENGTH,
+};
+use adaptive_pipeline_domain::value_objects::{Algorithm, ChunkTestVector, TestVectorSuite};
+use adaptive_pipeline_domain::PipelineError;
+
+use crate::infrastructure::adapters::encryption::MultiAlgoEncryption;
+use crate::infrastructure::config::build_info::build_provenance;
+
+/// Outcome of checking one vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorCheck {
+    /// Vector name
+    pub name: String,
+
+    /// Vector algorithm
+    pub algorithm: String,
+
+    /// Why the vector failed, if it did
+    pub error: Option<String>,
+}
+
+impl VectorCheck {
+    /// Whether this build reproduced the vector
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Generates and verifies chunk encryption test vectors
+#[derive(Default)]
+pub struct ChunkTestVectorService {
+    encryption: MultiAlgoEncryption,
+}
+
+impl ChunkTestVectorService {
+    /// Creates a service backed by the production encryption adapter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the suite: every supported AEAD over an empty, a short and
+    /// a one-KiB plaintext
+    pub fn generate(&self) -> Result<TestVectorSuite, PipelineError> {
+        let plaintexts: [(&str, Vec<u8>); 3] = [
+            ("empty", Vec::new()),
+            ("short", b"Adaptive Pipeline chunk".to_vec()),
+            ("1kib", (0..1024u32).map(|i| (i * 31 % 251) as u8).collect()),
+        ];
+
+        let mut suite = TestVectorSuite::new().with_provenance(build_provenance());
+        for (index, algorithm) in [Algorithm::Aes256Gcm, Algorithm::ChaCha20Poly1305].iter().enumerate() {
+            let encryption_algorithm = EncryptionAlgorithm::try_from(algorithm)?;
+            let key: Vec<u8> = (0..CHUNK_KEY_LENGTH as u8)
+                .map(|b| b.wrapping_mul(7) ^ index as u8)
+                .collect();
+            for (offset, (label, plaintext)) in plaintexts.iter().enumerate() {
+                let mut nonce = [0u8; CHUNK_NONCE_LENGTH];
+                for (position, byte) in nonce.iter_mut().enumerate() {
+                    *byte = (index * 0x40 + offset * 0x10 + position) as u8;
+                }
+                let sealed = self.encryption.seal_with_nonce(
+                    &encryption_algorithm,
+                    plaintext,
+                    &key,
+                    &nonce,
+                    CHUNK_ASSOCIATED_DATA,
+                )?;
+                suite = suite.with_vector(ChunkTestVector::new(
+                    format!("{}/{}", algorithm.name(), label),
+                    algorithm.name(),
+                    &key,
+                    nonce,
+                    plaintext,
+                    &sealed[CHUNK_NONCE_LENGTH..],
+                ));
+            }
+        }
+        Ok(suite)
+    }
+
+    /// Checks every vector in `suite` against this build
+    pub fn verify(&self, suite: &TestVectorSuite) -> Vec<VectorCheck> {
+        suite
+            .vectors
+            .iter()
+            .map(|vector| VectorCheck {
+                name: vector.name.clone(),
+                algorithm: vector.algorithm.clone(),
+                error: self.verify_vector(vector).err().map(|e| e.to_string()),
+            })
+            .collect()
+    }
+
+    /// Encrypts the vector's plaintext and compares the payload, decrypts the
+    /// record's payload and compares the plaintext, then checks the record
+    /// framing
+    fn verify_vector(&self, vector: &ChunkTestVector) -> Result<(), PipelineError> {
+        let algorithm = Algorithm::parse(&vector.algorithm).and_then(|a| EncryptionAlgorithm::try_from(&a))?;
+        let key = vector.key_bytes()?;
+        let nonce = vector.nonce_bytes()?;
+        let plaintext = vector.plaintext_bytes()?;
+        let payload = vector.payload_bytes()?;
+        vector.check_record()?;
+
+        let sealed = self
+            .encryption
+            .seal_with_nonce(&algorithm, &plaintext, &key, &nonce, CHUNK_ASSOCIATED_DATA)?;
+        if sealed[..CHUNK_NONCE_LENGTH] != nonce || sealed[CHUNK_NONCE_LENGTH..] != payload[..] {
+            return Err(PipelineError::EncryptionError(
+                "encrypting the plaintext did not reproduce the payload".to_string(),
+            ));
+        }
+
+        let mut nonce_and_payload = nonce.to_vec();
+        nonce_and_payload.extend_from_slice(&payload);
+        if self
+            .encryption
+            .open(&algorithm, &nonce_and_payload, &key, CHUNK_ASSOCIATED_DATA)?
+            != plaintext
+        {
+            return Err(PipelineError::EncryptionError(
+                "decrypting the payload did not reproduce the plaintext".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_vectors_verify_and_tampering_is_caught() {
+        let service = ChunkTestVectorService::new();
+        let suite = service.generate().unwrap();
+        assert_eq!(suite.vectors.len(), 6);
+        assert!(service.verify(&suite).iter().all(VectorCheck::passed));
+
+        // Generation is deterministic
+        assert_eq!(service.generate().unwrap().vectors, suite.vectors);
+
+        // A plaintext of the right length that no longer matches the payload
+        let mut tampered = suite.clone();
+        let plaintext = &mut tampered.vectors[1].plaintext;
+        let flipped = if plaintext.starts_with('0') { "1" } else { "0" };
+        plaintext.replace_range(0..1, flipped);
+        let checks = service.verify(&tampered);
+        assert!(!checks[1].passed());
+        assert_eq!(checks.iter().filter(|check| check.passed()).count(), 5);
+    }
+}
@@ -41,7 +41,7 @@
 //! curl http://localhost:9091/metrics | grep debug_stage
 //! ```
 
-use adaptive_pipeline_domain::entities::{ProcessingContext, StageConfiguration, StagePosition, StageType};
+use adaptive_pipeline_domain::entities::{Operation, ProcessingContext, StageConfiguration, StagePosition, StageType};
 use adaptive_pipeline_domain::services::{FromParameters, StageService};
 use adaptive_pipeline_domain::value_objects::FileChunk;
 use adaptive_pipeline_domain::PipelineError;
@@ -97,6 +97,7 @@ impl StageService for DebugService {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        operation: Operation,
         config: &StageConfiguration,
         _context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError> {
@@ -109,8 +110,9 @@ impl StageService for DebugService {
 
         // Emit metrics to Prometheus
         tracing::debug!(
-            "DebugStage[{}]: chunk={}, bytes={}, checksum={}",
+            "DebugStage[{}]: operation={}, chunk={}, bytes={}, checksum={}",
             debug_config.label,
+            operation,
             chunk_id,
             bytes,
             checksum
@@ -208,7 +210,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -248,7 +252,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -273,7 +279,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -299,7 +307,9 @@ mod tests {
         for i in 0..5 {
             let test_data = format!("Chunk {}", i).into_bytes();
             let chunk = FileChunk::new(i, 0, test_data.clone(), false).unwrap();
-            let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+            let result = service
+                .process_chunk(chunk, config.operation, &config, &mut context)
+                .unwrap();
             assert_eq!(result.data(), test_data.as_slice());
             assert_eq!(result.sequence_number(), i);
         }
@@ -27,7 +27,7 @@
 //! // Data passes through completely unchanged
 //! ```
 
-use adaptive_pipeline_domain::entities::{ProcessingContext, StageConfiguration, StagePosition, StageType};
+use adaptive_pipeline_domain::entities::{Operation, ProcessingContext, StageConfiguration, StagePosition, StageType};
 use adaptive_pipeline_domain::services::{FromParameters, StageService};
 use adaptive_pipeline_domain::value_objects::FileChunk;
 use adaptive_pipeline_domain::PipelineError;
@@ -67,10 +67,11 @@ impl StageService for PassThroughService {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        _operation: Operation,
         _config: &StageConfiguration,
         _context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError> {
-        // Pass through unchanged
+        // Pass through unchanged (identity in both directions)
         Ok(chunk)
     }
 
@@ -145,7 +146,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -165,7 +168,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -185,7 +190,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
         assert_eq!(result.data(), test_data.as_slice());
     }
 
@@ -207,7 +214,7 @@ mod tests {
         };
 
         let result_forward = service
-            .process_chunk(chunk.clone(), &config_forward, &mut context)
+            .process_chunk(chunk.clone(), config_forward.operation, &config_forward, &mut context)
             .unwrap();
 
         // Reverse operation
@@ -219,7 +226,9 @@ mod tests {
             chunk_size: None,
         };
 
-        let result_reverse = service.process_chunk(chunk, &config_reverse, &mut context).unwrap();
+        let result_reverse = service
+            .process_chunk(chunk, config_reverse.operation, &config_reverse, &mut context)
+            .unwrap();
 
         // Both should be identical to original
         assert_eq!(result_forward.data(), test_data.as_slice());
@@ -312,6 +312,7 @@ impl StageService for PiiMaskingService {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        operation: Operation,
         config: &StageConfiguration,
         context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError> {
@@ -321,7 +322,7 @@ impl StageService for PiiMaskingService {
         let input_size = chunk.data().len();
 
         // Dispatch based on operation
-        let processed_data = match config.operation {
+        let processed_data = match operation {
             Operation::Forward => {
                 // Forward: Mask PII
                 tracing::debug!(
@@ -333,8 +334,8 @@ impl StageService for PiiMaskingService {
             }
             Operation::Reverse => {
                 // Reverse: Not supported (non-reversible operation)
-                return Err(PipelineError::ProcessingFailed(
-                    "PII masking is not reversible - cannot recover original data".to_string(),
+                return Err(PipelineError::unsupported_operation(
+                    "PII masking is not reversible - cannot recover original data",
                 ));
             }
         };
@@ -343,7 +344,7 @@ impl StageService for PiiMaskingService {
 
         // Update metrics
         tracing::trace!(
-            operation = %config.operation,
+            operation = %operation,
             input_bytes = input_size,
             output_bytes = output_size,
             "PII masking complete"
@@ -508,9 +509,10 @@ mod tests {
             SecurityContext::new(None, SecurityLevel::Public),
         );
 
-        let result = service.process_chunk(chunk, &config, &mut context);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("not reversible"));
+        let result = service.process_chunk(chunk, config.operation, &config, &mut context);
+        let err = result.unwrap_err();
+        assert!(matches!(err, PipelineError::UnsupportedOperation(_)));
+        assert!(err.to_string().contains("not reversible"));
     }
 
     #[test]
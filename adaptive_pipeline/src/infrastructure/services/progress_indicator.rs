@@ -178,6 +178,11 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, Instant};
 
+use crate::infrastructure::metrics::concurrency_metrics::PipelinePressure;
+
+/// ANSI erase-to-end-of-line, so a shorter line fully replaces a longer one
+const CLEAR_TO_END_OF_LINE: &str = "\x1b[K";
+
 /// Real-time progress indicator for user feedback during pipeline processing.
 ///
 /// This provides immediate visual feedback to users about processing progress,
@@ -277,6 +282,47 @@ impl ProgressIndicatorService {
         }
     }
 
+    /// Displays a progress sample taken from the worker metrics shards.
+    ///
+    /// Unlike [`update_progress`](Self::update_progress), which workers
+    /// call per chunk, this is called by a single sampler with running
+    /// totals, so workers never touch the throttle or terminal locks.
+    /// Totals only move forward: a stale sample never rewinds the display.
+    ///
+    /// The pipeline pressure, when given, follows the counts so users can
+    /// tell slow workers from a slow disk while throughput drops:
+    ///
+    /// ```text
+    /// Wrote Id: 000097/Completed: 000098 | queue 0 | busy 4 | writing 4 | unfinalized 98 | disk-bound
+    /// ```
+    ///
+    /// # Arguments
+    /// * `completed` - Chunks completed so far
+    /// * `last_chunk_id` - Highest chunk ID written so far
+    /// * `pressure` - Queue, worker and writer gauges at sample time
+    pub async fn show_sample(&self, completed: u64, last_chunk_id: u64, pressure: Option<PipelinePressure>) {
+        let completed = self
+            .completed_chunks
+            .fetch_max(completed, Ordering::Relaxed)
+            .max(completed);
+        let chunk_id = self
+            .last_chunk_id
+            .fetch_max(last_chunk_id, Ordering::Relaxed)
+            .max(last_chunk_id);
+        let _terminal_lock = self.terminal_mutex.lock().await;
+        match pressure {
+            Some(pressure) => print!(
+                "\rWrote Id: {:06}/Completed: {:06} | {}{}",
+                chunk_id, completed, pressure, CLEAR_TO_END_OF_LINE
+            ),
+            None => print!(
+                "\rWrote Id: {:06}/Completed: {:06}{}",
+                chunk_id, completed, CLEAR_TO_END_OF_LINE
+            ),
+        }
+        io::stdout().flush().unwrap_or(());
+    }
+
     /// Updates the terminal display with current progress.
     ///
     /// This method coordinates terminal access to ensure clean output
@@ -304,9 +350,10 @@ impl ProgressIndicatorService {
         // Clear the progress line and show final progress with correct total
         let final_completed = self.completed_chunks.load(Ordering::Relaxed);
         print!(
-            "\rWrote Id: {:06}/Completed: {:06}\n",
+            "\rWrote Id: {:06}/Completed: {:06}{}\n",
             self.last_chunk_id.load(Ordering::Relaxed),
-            final_completed
+            final_completed,
+            CLEAR_TO_END_OF_LINE
         );
 
         io::stdout().flush().unwrap_or(());
@@ -322,9 +369,10 @@ impl ProgressIndicatorService {
         // Clear the progress line and show final progress
         let final_completed = self.completed_chunks.load(Ordering::Relaxed);
         println!(
-            "\rWrote Id: {:06}/Completed: {:06}",
+            "\rWrote Id: {:06}/Completed: {:06}{}",
             self.last_chunk_id.load(Ordering::Relaxed),
-            final_completed
+            final_completed,
+            CLEAR_TO_END_OF_LINE
         );
 
         // Show error summary with 6-digit precision
@@ -0,0 +1,275 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Replica Targets
+//!
+//! Secondary locations that `replicate` copies archives to. Each archive is
+//! written under a key derived from its catalog path, and can be read back
+//! through a [`ChunkSource`] to verify the copy.
+//!
+//! | Target                     | Writes with                               |
+//! |----------------------------|-------------------------------------------|
+//! | `s3://bucket/prefix`       | signed multipart upload to `prefix/{key}` |
+//! | `https://gateway/prefix`   | `PUT https://gateway/prefix/{key}`        |
+//! | `/path/to/directory`       | copy to a temporary file, then rename     |
+//!
+//! `s3://` uploads go through `object_store`'s S3 client: SigV4-signed and
+//! over TLS, with the credentials and endpoint [`s3_bucket`] reads from the
+//! environment. `http(s)://` uploads are unsigned `PUT`s, so those targets
+//! name a gateway that authenticates on the caller's behalf.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::PipelineError;
+use object_store::buffered::BufWriter;
+use object_store::ObjectStore;
+use tokio::io::AsyncWriteExt;
+
+use crate::infrastructure::runtime::try_resource_manager;
+use crate::infrastructure::services::{
+    s3_bucket, split_s3_location, ChunkSource, FileSource, HttpRangeSource, ObjectStoreSource,
+};
+
+/// Where replicas are written
+#[derive(Debug, Clone)]
+pub enum ReplicaTarget {
+    /// A local or mounted directory
+    Directory(PathBuf),
+    /// A URL prefix on an HTTP gateway that accepts `PUT`s
+    Http(String),
+    /// A key prefix, possibly empty, in an S3 bucket
+    S3 {
+        bucket: String,
+        prefix: String,
+        store: Arc<dyn ObjectStore>,
+    },
+}
+
+impl ReplicaTarget {
+    /// Parses `location`: an `s3://bucket/prefix` URL, an `http(s)://`
+    /// gateway URL or a directory
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` for a malformed URL, or S3 settings
+    /// that [`s3_bucket`] refuses.
+    pub fn parse(location: &str) -> Result<Self, PipelineError> {
+        if location.starts_with("s3://") {
+            let (bucket, prefix) = split_s3_location(location).ok_or_else(|| {
+                PipelineError::invalid_config(format!("Expected s3://bucket/prefix, got {}", location))
+            })?;
+            return Ok(Self::S3 {
+                bucket: bucket.to_string(),
+                prefix: prefix.trim_matches('/').to_string(),
+                store: s3_bucket(bucket)?,
+            });
+        }
+        if location.starts_with("http://") || location.starts_with("https://") {
+            // Checks the URL is one uploads can be sent to
+            HttpRangeSource::new(location)?;
+            return Ok(Self::Http(location.trim_end_matches('/').to_string()));
+        }
+        Ok(Self::Directory(PathBuf::from(location)))
+    }
+
+    /// The key an archive listed as `listed` is replicated under: its path
+    /// without any root, so archives keep their layout
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` for a path with `..` components, which
+    /// could land outside the target.
+    pub fn key_for(listed: &str) -> Result<String, PipelineError> {
+        let mut parts = Vec::new();
+        for component in Path::new(listed).components() {
+            match component {
+                Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+                Component::ParentDir => {
+                    return Err(PipelineError::InvalidParameter(format!(
+                        "Cannot replicate {}: '..' in its catalog path",
+                        listed
+                    )))
+                }
+                Component::RootDir | Component::Prefix(_) | Component::CurDir => {}
+            }
+        }
+        if parts.is_empty() {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Cannot replicate {}: no file name",
+                listed
+            )));
+        }
+        Ok(parts.join("/"))
+    }
+
+    /// The location of the replica stored under `key`
+    pub fn location(&self, key: &str) -> String {
+        match self {
+            Self::Directory(dir) => dir.join(key).display().to_string(),
+            Self::Http(base) => format!("{}/{}", base, key),
+            Self::S3 { bucket, .. } => format!("s3://{}/{}", bucket, self.object_key(key)),
+        }
+    }
+
+    /// The object key of the replica stored under `key` in an S3 target
+    fn object_key(&self, key: &str) -> String {
+        match self {
+            Self::S3 { prefix, .. } if !prefix.is_empty() => format!("{}/{}", prefix, key),
+            _ => key.to_string(),
+        }
+    }
+
+    /// Writes the file at `source` under `key`, replacing any replica there
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the file can't be read or the write fails.
+    pub async fn put(&self, source: &Path, key: &str) -> Result<(), PipelineError> {
+        match self {
+            Self::Directory(dir) => {
+                let destination = dir.join(key);
+                let io_error = |e: std::io::Error| {
+                    PipelineError::io_error(format!("Failed to write replica {}: {}", destination.display(), e))
+                };
+                if let Some(parent) = destination.parent() {
+                    tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+                }
+                // A replica is either complete or absent, even if the copy
+                // is interrupted
+                let staging = destination.with_file_name(format!(
+                    ".{}.partial",
+                    destination.file_name().unwrap_or_default().to_string_lossy()
+                ));
+                tokio::fs::copy(source, &staging).await.map_err(io_error)?;
+                let staged = tokio::fs::File::open(&staging).await.map_err(io_error)?;
+                staged.sync_all().await.map_err(io_error)?;
+                tokio::fs::rename(&staging, &destination).await.map_err(io_error)
+            }
+            Self::Http(_) => HttpRangeSource::new(&self.location(key))?.put_file(source).await,
+            Self::S3 { store, .. } => {
+                let location = self.location(key);
+                let io_error =
+                    |e: std::io::Error| PipelineError::io_error(format!("Failed to upload {}: {}", location, e));
+                let mut file = tokio::fs::File::open(source)
+                    .await
+                    .map_err(|e| PipelineError::io_error(format!("Failed to open {}: {}", source.display(), e)))?;
+                let _network_permit = match try_resource_manager() {
+                    Some(manager) => Some(manager.acquire_network_io().await?),
+                    None => None,
+                };
+                // Large files go up in parts, so memory stays at a part's size
+                let mut upload = BufWriter::new(store.clone(), self.object_key(key).into());
+                if let Err(e) = tokio::io::copy(&mut file, &mut upload).await {
+                    // Nothing is left behind, not even uploaded parts
+                    let _ = upload.abort().await;
+                    return Err(io_error(e));
+                }
+                upload.shutdown().await.map_err(io_error)
+            }
+        }
+    }
+
+    /// Opens the replica stored under `key` for reading
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the replica's URL is invalid.
+    pub fn open(&self, key: &str) -> Result<Arc<dyn ChunkSource>, PipelineError> {
+        match self {
+            Self::Directory(dir) => Ok(Arc::new(FileSource::new(dir.join(key)))),
+            Self::Http(_) => Ok(Arc::new(HttpRangeSource::new(&self.location(key))?)),
+            Self::S3 { store, .. } => Ok(Arc::new(ObjectStoreSource::new(&self.location(key), store.clone())?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_keys_keep_the_layout_without_escaping() {
+        assert_eq!(
+            ReplicaTarget::key_for("/backups/db/a.adapipe").unwrap(),
+            "backups/db/a.adapipe"
+        );
+        assert_eq!(ReplicaTarget::key_for("./logs/b.adapipe").unwrap(), "logs/b.adapipe");
+        assert!(ReplicaTarget::key_for("../elsewhere/c.adapipe").is_err());
+        assert!(ReplicaTarget::key_for("/").is_err());
+    }
+
+    #[test]
+    fn test_targets_are_buckets_gateway_urls_or_directories() {
+        let target = ReplicaTarget::parse("http://localhost:8333/offsite/fleet/").unwrap();
+        assert!(matches!(&target, ReplicaTarget::Http(base) if base == "http://localhost:8333/offsite/fleet"));
+        assert_eq!(
+            target.location("db/a.adapipe"),
+            "http://localhost:8333/offsite/fleet/db/a.adapipe"
+        );
+        assert!(target.open("db/a.adapipe").is_ok());
+        assert!(ReplicaTarget::parse("https://gateway.example/offsite").is_ok());
+
+        let target = ReplicaTarget::parse("/mnt/offsite").unwrap();
+        assert!(matches!(&target, ReplicaTarget::Directory(dir) if dir == Path::new("/mnt/offsite")));
+
+        let target = ReplicaTarget::parse("s3://offsite/fleet/").unwrap();
+        assert!(
+            matches!(&target, ReplicaTarget::S3 { bucket, prefix, .. } if bucket == "offsite" && prefix == "fleet")
+        );
+        assert_eq!(target.location("db/a.adapipe"), "s3://offsite/fleet/db/a.adapipe");
+        let target = ReplicaTarget::parse("s3://offsite").unwrap();
+        assert_eq!(target.location("db/a.adapipe"), "s3://offsite/db/a.adapipe");
+        assert!(ReplicaTarget::parse("s3:///fleet").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bucket_replicas_are_uploaded_and_readable() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("a.adapipe");
+        std::fs::write(&source, b"archive bytes").unwrap();
+        let store: Arc<dyn ObjectStore> = Arc::new(object_store::memory::InMemory::new());
+        let target = ReplicaTarget::S3 {
+            bucket: "offsite".to_string(),
+            prefix: "fleet".to_string(),
+            store: store.clone(),
+        };
+
+        target.put(&source, "db/a.adapipe").await.unwrap();
+        let uploaded = store
+            .get(&"fleet/db/a.adapipe".into())
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(&uploaded[..], b"archive bytes");
+
+        let reader = target.open("db/a.adapipe").unwrap();
+        assert_eq!(reader.location(), "s3://offsite/fleet/db/a.adapipe");
+        assert_eq!(reader.size().await.unwrap(), 13);
+        assert_eq!(reader.read_at(8, 5).await.unwrap(), b"bytes");
+    }
+
+    #[tokio::test]
+    async fn test_directory_replicas_are_written_whole_and_readable() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("a.adapipe");
+        std::fs::write(&source, b"archive bytes").unwrap();
+        let target = ReplicaTarget::Directory(dir.path().join("offsite"));
+
+        target.put(&source, "db/a.adapipe").await.unwrap();
+        let replica = dir.path().join("offsite/db/a.adapipe");
+        assert_eq!(std::fs::read(&replica).unwrap(), b"archive bytes");
+        assert!(!dir.path().join("offsite/db/.a.adapipe.partial").exists());
+
+        let reader = target.open("db/a.adapipe").unwrap();
+        assert_eq!(reader.size().await.unwrap(), 13);
+        assert_eq!(reader.read_at(8, 5).await.unwrap(), b"bytes");
+    }
+}
@@ -0,0 +1,249 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Tar Container Interop
+//!
+//! Bridges `.adapipe` archives and tools that speak tar without a round trip
+//! through a directory.
+//!
+//! - **Export**: [`entry_header`], [`entry_padding`] and [`END_OF_ARCHIVE`]
+//!   frame restored data as POSIX ustar entries while it streams. The entry
+//!   size comes from the archive header, so a tar is written in one pass with
+//!   no temporary copy of the data.
+//! - **Import**: [`stage_tar_entries`] reads a tar stream (a file or standard
+//!   input) on a blocking thread and stages its regular files one at a time
+//!   in the managed temp root. At most two entries are on disk at once: the
+//!   one being processed and the next one.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use adaptive_pipeline_domain::PipelineError;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::infrastructure::runtime::temp_root;
+
+/// Tar block size; entries and their headers are padded to it
+pub const TAR_BLOCK_SIZE: u64 = 512;
+
+/// Two zero blocks that end a tar archive
+pub const END_OF_ARCHIVE: [u8; 2 * TAR_BLOCK_SIZE as usize] = [0; 2 * TAR_BLOCK_SIZE as usize];
+
+/// Builds the ustar header of a regular-file entry
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if `path` does not fit a ustar header (at most
+/// 255 bytes, split into a 155-byte prefix and a 100-byte name).
+pub fn entry_header(path: &Path, size: u64, mode: u32, mtime: u64) -> Result<[u8; 512], PipelineError> {
+    let mut header = tar::Header::new_ustar();
+    header.set_path(path).map_err(|e| {
+        PipelineError::InvalidParameter(format!("Cannot store {} in a tar entry: {}", path.display(), e))
+    })?;
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(size);
+    header.set_mode(mode);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    Ok(*header.as_bytes())
+}
+
+/// Zero bytes that follow `size` bytes of entry data to fill its last block
+pub fn entry_padding(size: u64) -> usize {
+    ((TAR_BLOCK_SIZE - size % TAR_BLOCK_SIZE) % TAR_BLOCK_SIZE) as usize
+}
+
+/// A regular file read from a tar stream and staged on disk
+///
+/// The staged copy is removed when the entry is dropped.
+#[derive(Debug)]
+pub struct StagedTarEntry {
+    /// Path of the entry inside the tar, relative and free of `..`
+    pub path: PathBuf,
+
+    /// Staged copy of the entry's data, named after the entry
+    pub file: PathBuf,
+
+    /// Entry size in bytes
+    pub size: u64,
+
+    _dir: tempfile::TempDir,
+}
+
+/// Reads the tar stream `source` on a blocking thread, staging each regular
+/// file and sending it on the returned channel
+///
+/// Directories are skipped silently; links, devices and other special
+/// entries are skipped with a warning. The reader waits for each staged
+/// entry to be received before staging the one after next, and stops when
+/// the receiver is dropped. A malformed stream or an entry path that is
+/// absolute or contains `..` ends the stream with an error.
+pub fn stage_tar_entries(source: Box<dyn Read + Send>) -> mpsc::Receiver<Result<StagedTarEntry, PipelineError>> {
+    let (sender, receiver) = mpsc::channel(1);
+    let staging_dir = temp_root()
+        .map(|root| root.run_dir().to_path_buf())
+        .unwrap_or_else(std::env::temp_dir);
+
+    tokio::task::spawn_blocking(move || {
+        let mut archive = tar::Archive::new(source);
+        let entries = match archive.entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                let _ = sender.blocking_send(Err(read_error(e)));
+                return;
+            }
+        };
+        for entry in entries {
+            match entry
+                .map_err(read_error)
+                .and_then(|entry| stage_entry(entry, &staging_dir))
+            {
+                Ok(None) => {}
+                Ok(Some(staged)) => {
+                    if sender.blocking_send(Ok(staged)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(e));
+                    return;
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Stages one entry, or returns `None` for an entry that is not a regular
+/// file
+fn stage_entry<R: Read>(
+    mut entry: tar::Entry<'_, R>,
+    staging_dir: &Path,
+) -> Result<Option<StagedTarEntry>, PipelineError> {
+    let raw_path = entry.path().map_err(read_error)?.into_owned();
+    let entry_type = entry.header().entry_type();
+    if entry_type.is_dir() {
+        return Ok(None);
+    }
+    if !matches!(entry_type, tar::EntryType::Regular | tar::EntryType::Continuous) {
+        warn!(
+            "Skipping tar entry {} ({:?}): only regular files are imported",
+            raw_path.display(),
+            entry_type
+        );
+        return Ok(None);
+    }
+
+    let path = confined_path(&raw_path)?;
+    let file_name = path.file_name().ok_or_else(|| {
+        PipelineError::security_violation(format!("Tar entry '{}' names no file", raw_path.display()))
+    })?;
+
+    let dir = tempfile::Builder::new()
+        .prefix("tar-entry-")
+        .tempdir_in(staging_dir)
+        .map_err(|e| PipelineError::io_error(format!("Failed to create tar staging directory: {}", e)))?;
+    let file = dir.path().join(file_name);
+    let mut staged = File::create(&file)
+        .map_err(|e| PipelineError::io_error(format!("Failed to stage tar entry {}: {}", path.display(), e)))?;
+    let size = std::io::copy(&mut entry, &mut staged)
+        .map_err(|e| PipelineError::io_error(format!("Failed to stage tar entry {}: {}", path.display(), e)))?;
+
+    Ok(Some(StagedTarEntry {
+        path,
+        file,
+        size,
+        _dir: dir,
+    }))
+}
+
+/// The entry path with `.` components dropped, rejecting absolute paths and
+/// `..`
+fn confined_path(raw_path: &Path) -> Result<PathBuf, PipelineError> {
+    let mut path = PathBuf::new();
+    for component in raw_path.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(PipelineError::security_violation(format!(
+                    "Tar entry '{}' is absolute or traverses upwards",
+                    raw_path.display()
+                )))
+            }
+        }
+    }
+    Ok(path)
+}
+
+fn read_error(e: std::io::Error) -> PipelineError {
+    PipelineError::io_error(format!("Failed to read tar stream: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tar_with(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (path, data) in entries {
+            tar.extend_from_slice(&entry_header(Path::new(path), data.len() as u64, 0o644, 0).unwrap());
+            tar.extend_from_slice(data);
+            tar.resize(tar.len() + entry_padding(data.len() as u64), 0);
+        }
+        tar.extend_from_slice(&END_OF_ARCHIVE);
+        tar
+    }
+
+    #[tokio::test]
+    async fn test_written_entries_stage_back_unchanged() {
+        let tar = tar_with(&[("a.txt", b"alpha"), ("./logs/b.log", &[7u8; 1500])]);
+        assert_eq!(tar.len() % TAR_BLOCK_SIZE as usize, 0);
+
+        let mut entries = stage_tar_entries(Box::new(std::io::Cursor::new(tar)));
+        let first = entries.recv().await.unwrap().unwrap();
+        assert_eq!(first.path, Path::new("a.txt"));
+        assert_eq!(std::fs::read(&first.file).unwrap(), b"alpha");
+
+        let second = entries.recv().await.unwrap().unwrap();
+        assert_eq!(second.path, Path::new("logs/b.log"));
+        assert_eq!(second.file.file_name().unwrap(), "b.log");
+        assert_eq!(second.size, 1500);
+
+        let staged = second.file.clone();
+        drop(second);
+        assert!(!staged.exists());
+        assert!(entries.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_traversing_entry_ends_the_stream() {
+        // `set_path` refuses `..`, so forge the name field directly
+        let mut header = tar::Header::new_ustar();
+        header.as_old_mut().name[..13].copy_from_slice(b"../escape.txt");
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(0);
+        header.set_cksum();
+        let mut tar = header.as_bytes().to_vec();
+        tar.extend_from_slice(&END_OF_ARCHIVE);
+
+        let mut entries = stage_tar_entries(Box::new(std::io::Cursor::new(tar)));
+        assert!(entries.recv().await.unwrap().is_err());
+        assert!(entries.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_padding_fills_the_last_block() {
+        assert_eq!(entry_padding(0), 0);
+        assert_eq!(entry_padding(1), 511);
+        assert_eq!(entry_padding(512), 0);
+        assert!(entry_header(Path::new(&"x".repeat(300)), 0, 0o644, 0).is_err());
+    }
+}
@@ -246,6 +246,7 @@ impl StageService for TeeService {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        operation: Operation,
         config: &StageConfiguration,
         _context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError> {
@@ -258,7 +259,7 @@ impl StageService for TeeService {
             output_path = %tee_config.output_path.display(),
             format = ?tee_config.format,
             enabled = tee_config.enabled,
-            operation = %config.operation,
+            operation = %operation,
             "Tee operation"
         );
 
@@ -438,7 +439,9 @@ mod tests {
             SecurityContext::new(None, SecurityLevel::Public),
         );
 
-        let result = service.process_chunk(chunk, &config, &mut context).unwrap();
+        let result = service
+            .process_chunk(chunk, config.operation, &config, &mut context)
+            .unwrap();
 
         // Data should pass through unchanged
         assert_eq!(result.data(), &original_data);
@@ -310,6 +310,8 @@
 pub mod application;
 pub mod infrastructure;
 pub mod presentation;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 // Tests are organized as:
 // - Unit tests: #[cfg(test)] modules within each source file
@@ -323,3 +325,7 @@ pub use adaptive_pipeline_domain::{
 
 // Re-export restoration functions for testing
 pub use crate::application::use_cases::restore_file::create_restoration_pipeline;
+
+// Re-export the cost model for capacity planning
+pub use crate::application::use_cases::EstimateCostUseCase;
+pub use adaptive_pipeline_domain::services::{CostBasis, CostEstimate, CostModel};
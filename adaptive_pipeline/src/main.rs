@@ -167,31 +167,29 @@
 //! - **Plugin System**: Extensible plugin architecture
 //! - **Distributed Processing**: Support for distributed processing
 
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_bootstrap::service::{ServiceNotifier, ServiceState};
 use anyhow::Result;
 use byte_unit::Byte;
-// CLI parsing now handled by bootstrap layer
-use sha2::{Digest, Sha256};
-use std::collections::HashMap;
-use std::fs;
-use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::io::AsyncWriteExt;
-use tracing::{debug, error, info, warn};
-
-// Import ChunkSize and WorkerCount for optimal sizing calculations
-use crate::application::commands::RestoreFileCommand;
-// File restoration is now handled via use_cases::restore_file
-use crate::infrastructure::adapters::file_io::TokioFileIO;
-use crate::infrastructure::services::progress_indicator::ProgressIndicatorService;
-use adaptive_pipeline_domain::value_objects::binary_file_format::FileHeader;
-use adaptive_pipeline_domain::value_objects::chunk_size::ChunkSize;
-use adaptive_pipeline_domain::value_objects::worker_count::WorkerCount;
+use tracing::{debug, error, info, warn, Instrument};
+
+use crate::application::command_bus::middleware::{AuditMiddleware, MetricsMiddleware, ValidationMiddleware};
+use crate::application::command_bus::CommandBus;
+use crate::application::commands::{
+    CreatePipelineCommand, DeletePipelineCommand, ProcessDirectoryCommand, ProcessFileCommand, RestoreFileCommand,
+};
 
 // Import all use cases from application layer
 use crate::application::use_cases::{
-    BenchmarkSystemUseCase, CompareFilesUseCase, CreatePipelineUseCase, DeletePipelineUseCase, ListPipelinesUseCase,
-    ProcessFileConfig, ProcessFileUseCase, ShowPipelineUseCase, ValidateConfigUseCase, ValidateFileUseCase,
+    AdvisorySeverity, AuditPipelinesUseCase, BenchmarkSystemUseCase, CapabilitiesUseCase, ChunkSizeSweepUseCase,
+    CleanupTempUseCase, CompareFilesUseCase, CreatePipelineUseCase, DeletePipelineUseCase, EncryptionVectorsUseCase,
+    EstimateCostUseCase, ExportPipelineUseCase, ExportTarUseCase, ImportPipelineUseCase, ImportTarUseCase,
+    InspectFileUseCase, ListArchiveUseCase, ListPipelinesUseCase, ManageDatabaseUseCase, ManageJobsUseCase,
+    ManageRolesUseCase, ManageSessionsUseCase, MountArchiveUseCase, ProcessBatchUseCase, ProcessDirectoryUseCase,
+    ProcessFileConfig, ProcessFileUseCase, RegressionThresholds, ReplicateArchivesUseCase, RestoreFileUseCase,
+    ScrubArchivesUseCase, SelfTestUseCase, ShowPipelineUseCase, ValidateConfigUseCase, ValidateFileUseCase,
+    VerifyManifestUseCase,
 };
 
 /// Format bytes with 6-digit precision
@@ -204,66 +202,288 @@ fn format_bytes_6_digits(bytes: u64) -> String {
     format!("{:.6} {}", value, unit)
 }
 
-/// Resolve SQLite database path with proper fallback chain and error handling
-fn resolve_sqlite_path() -> Result<String> {
-    // 1. Check environment variable first
-    if let Ok(env_path) = std::env::var("ADAPIPE_SQLITE_PATH") {
-        debug!("Using SQLite path from ADAPIPE_SQLITE_PATH: {}", env_path);
-        return Ok(env_path);
+/// Resolve the principal the CLI acts as without credentials: the OS user
+/// running it
+///
+/// On Unix the name is looked up from the real user ID, so it cannot be
+/// changed through the environment; a user without a passwd entry acts as
+/// `uid:<id>`. Acting as anyone else takes credentials checked by the
+/// `[auth]` providers or a session token.
+#[cfg(unix)]
+fn resolve_principal() -> String {
+    // SAFETY: getuid has no preconditions and cannot fail
+    let uid = unsafe { libc::getuid() };
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    let mut entry: *mut libc::passwd = std::ptr::null_mut();
+    // SAFETY: every pointer refers to a live local of the size passed
+    let status = unsafe { libc::getpwuid_r(uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut entry) };
+    if status != 0 || entry.is_null() || passwd.pw_name.is_null() {
+        return format!("uid:{}", uid);
     }
+    // SAFETY: getpwuid_r succeeded, so pw_name is a NUL-terminated string in
+    // `buffer`
+    let name = unsafe { std::ffi::CStr::from_ptr(passwd.pw_name) };
+    name.to_string_lossy().into_owned()
+}
+
+/// Resolve the principal the CLI acts as without credentials: the OS user
+/// running it
+#[cfg(not(unix))]
+fn resolve_principal() -> String {
+    std::env::var("USERNAME")
+        .ok()
+        .filter(|principal| !principal.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
 
-    // 2. Check current directory (where exe is running in deployment)
-    let current_dir_path = "./pipeline.db";
-    if std::path::Path::new(current_dir_path).exists() {
-        debug!("Found SQLite database in current directory: {}", current_dir_path);
-        return Ok(current_dir_path.to_string());
+/// Resolve the correlation ID of this run
+///
+/// Uses `ADAPIPE_CORRELATION_ID` when the caller supplies one (e.g. a CI job
+/// or gateway request ID), otherwise generates a new one.
+fn resolve_correlation_id() -> Result<CorrelationId> {
+    match env_credential("ADAPIPE_CORRELATION_ID") {
+        Some(id) => Ok(id.parse()?),
+        None => Ok(CorrelationId::generate()),
     }
+}
+
+/// Reads a credential from the environment variable `name`, if set
+fn env_credential(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
 
-    // 3. Check debug/development path
-    let debug_path = "pipeline/scripts/test_data/structured_pipeline.db";
-    if std::path::Path::new(debug_path).exists() {
-        debug!("Found SQLite database at debug path: {}", debug_path);
-        return Ok(debug_path.to_string());
+/// Reads the credentials presented for the `[auth]` providers
+///
+/// `ADAPIPE_API_KEY` carries an API key and `ADAPIPE_BEARER_TOKEN` a bearer
+/// token (e.g. a JWT). At most one credential, counting a session token, may
+/// be presented, so it is never ambiguous whom a command runs as.
+fn presented_credentials(has_session_token: bool) -> Result<Option<Credentials>> {
+    let mut presented: Vec<Credentials> = [
+        env_credential("ADAPIPE_API_KEY").map(Credentials::ApiKey),
+        env_credential("ADAPIPE_BEARER_TOKEN").map(Credentials::BearerToken),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if presented.len() + usize::from(has_session_token) > 1 {
+        anyhow::bail!(
+            "Invalid configuration: set only one of ADAPIPE_SESSION_TOKEN, ADAPIPE_API_KEY and ADAPIPE_BEARER_TOKEN"
+        );
     }
+    Ok(presented.pop())
+}
 
-    // 4. Create default database in current directory
-    info!(
-        "No existing database found. Creating new database at: {}",
-        current_dir_path
-    );
-    Ok(current_dir_path.to_string())
+/// Reads the passphrase for `--password-prompt`
+///
+/// Prompts on the terminal without echo; when standard input is not a
+/// terminal (e.g. piped from a secrets manager) its first line is the
+/// passphrase. A new passphrase is asked for twice on a terminal, so a typo
+/// cannot lock the output away.
+fn read_password(confirm: bool) -> Result<SecretBytes> {
+    use std::io::{BufRead, IsTerminal};
+    use zeroize::Zeroizing;
+
+    let password = if std::io::stdin().is_terminal() {
+        let password = SecretBytes::from(rpassword::prompt_password("Passphrase: ")?);
+        if confirm {
+            let repeated = Zeroizing::new(rpassword::prompt_password("Confirm passphrase: ")?);
+            if repeated.as_bytes() != password.expose_secret() {
+                anyhow::bail!("Invalid argument: the passphrases do not match");
+            }
+        }
+        password
+    } else {
+        let mut line = Zeroizing::new(String::new());
+        std::io::stdin().lock().read_line(&mut line)?;
+        SecretBytes::from_slice(line.trim_end_matches(['\r', '\n']).as_bytes())
+    };
+    if password.is_empty() {
+        anyhow::bail!("Invalid argument: the passphrase must not be empty");
+    }
+    Ok(password)
+}
+
+/// Command bus for the CLI's state-changing commands
+///
+/// Dispatches are audited as `principal`, counted in `metrics_service` and
+/// validated before they reach their handler; handlers are registered per
+/// command.
+fn command_bus(principal: &str, metrics_service: &Arc<MetricsService>) -> CommandBus {
+    CommandBus::new()
+        .with_middleware(AuditMiddleware::new(principal))
+        .with_middleware(MetricsMiddleware::new(metrics_service.clone()))
+        .with_middleware(ValidationMiddleware)
+}
+
+/// Maps a CLI command to the operation role-based access control gates it on
+///
+/// Local inspection commands (benchmark, validate, compare, vectors) are not
+/// gated; tar export and import are gated as restore and process, and
+/// replication, which hands out archives' data, as restore.
+fn protected_operation(command: &adaptive_pipeline_bootstrap::ValidatedCommand) -> Option<ProtectedOperation> {
+    use adaptive_pipeline_bootstrap::ValidatedCommand;
+
+    match command {
+        ValidatedCommand::Process { .. }
+        | ValidatedCommand::ProcessBatch { .. }
+        | ValidatedCommand::ImportTar { .. }
+        | ValidatedCommand::Daemon { .. }
+        | ValidatedCommand::Submit { .. }
+        | ValidatedCommand::JobsCancel { .. } => Some(ProtectedOperation::ProcessFile),
+        ValidatedCommand::Create { .. } | ValidatedCommand::PipelineImport { .. } => {
+            Some(ProtectedOperation::CreatePipeline)
+        }
+        ValidatedCommand::List { .. }
+        | ValidatedCommand::Show { .. }
+        | ValidatedCommand::PipelineExport { .. }
+        | ValidatedCommand::Estimate { .. }
+        | ValidatedCommand::Audit { .. }
+        | ValidatedCommand::JobsList { .. }
+        | ValidatedCommand::JobsStatus { .. } => Some(ProtectedOperation::ViewPipelines),
+        ValidatedCommand::Delete { .. } => Some(ProtectedOperation::DeletePipeline),
+        ValidatedCommand::Restore { .. }
+        | ValidatedCommand::ExportTar { .. }
+        | ValidatedCommand::Mount { .. }
+        | ValidatedCommand::Replicate { .. } => Some(ProtectedOperation::RestoreFile),
+        ValidatedCommand::RoleList => Some(ProtectedOperation::ViewPipelines),
+        ValidatedCommand::RoleInit | ValidatedCommand::RoleAssign { .. } | ValidatedCommand::RoleRevoke { .. } => {
+            Some(ProtectedOperation::ManageRoles)
+        }
+        ValidatedCommand::SessionList => Some(ProtectedOperation::ViewPipelines),
+        ValidatedCommand::SessionIssue { .. } | ValidatedCommand::SessionRevoke { .. } => {
+            Some(ProtectedOperation::ManageSessions)
+        }
+        ValidatedCommand::DbBackup { .. } | ValidatedCommand::DbRestore { .. } => {
+            Some(ProtectedOperation::ManageDatabase)
+        }
+        // A sweep runs the pipeline and may write its configuration
+        ValidatedCommand::Benchmark {
+            sweep_chunk_sizes: Some(_),
+            ..
+        } => Some(ProtectedOperation::ProcessFile),
+        ValidatedCommand::Benchmark { .. }
+        | ValidatedCommand::Validate { .. }
+        | ValidatedCommand::ValidateFile { .. }
+        | ValidatedCommand::Inspect { .. }
+        | ValidatedCommand::Ls { .. }
+        | ValidatedCommand::VerifyManifest { .. }
+        | ValidatedCommand::Scrub { .. }
+        | ValidatedCommand::Compare { .. }
+        | ValidatedCommand::CompareArchives { .. }
+        | ValidatedCommand::Cleanup { .. }
+        | ValidatedCommand::VectorsGenerate { .. }
+        | ValidatedCommand::VectorsVerify { .. }
+        | ValidatedCommand::SelfTest { .. }
+        | ValidatedCommand::Capabilities { .. }
+        | ValidatedCommand::ExitCodes { .. } => None,
+        // Each API request is authorized for its own operation
+        ValidatedCommand::Serve { .. } => None,
+    }
+}
+
+/// Namespace a command is authorized in
+///
+/// Database backup and restore act on every namespace at once, so only
+/// admins of the default namespace may run them.
+fn access_namespace(command: &adaptive_pipeline_bootstrap::ValidatedCommand, namespace: &Namespace) -> Namespace {
+    use adaptive_pipeline_bootstrap::ValidatedCommand;
+    match command {
+        ValidatedCommand::DbBackup { .. } | ValidatedCommand::DbRestore { .. } => Namespace::default(),
+        _ => namespace.clone(),
+    }
+}
+
+/// Renders the exit code table printed by `exit-codes`
+///
+/// The plain table has one row per code (code, name, meaning, when it is
+/// returned); `json` renders the same rows as an array of objects.
+fn render_exit_codes(json: bool) -> String {
+    use adaptive_pipeline_bootstrap::ExitCode;
+
+    if json {
+        let rows: Vec<serde_json::Value> = ExitCode::ALL
+            .iter()
+            .map(|code| {
+                serde_json::json!({
+                    "code": code.as_i32(),
+                    "name": code.name(),
+                    "description": code.description(),
+                    "cause": code.cause(),
+                })
+            })
+            .collect();
+        return serde_json::Value::Array(rows).to_string();
+    }
+
+    let mut table = format!("{:<5} {:<14} {:<32} {}\n", "CODE", "NAME", "MEANING", "RETURNED WHEN");
+    for code in ExitCode::ALL {
+        table.push_str(&format!(
+            "{:<5} {:<14} {:<32} {}\n",
+            code.as_i32(),
+            code.name(),
+            code.description(),
+            code.cause()
+        ));
+    }
+    table
 }
 
 mod application;
 mod infrastructure;
 mod presentation;
 
-use adaptive_pipeline_domain::entities::pipeline_stage::{StageConfiguration, StageType};
-use adaptive_pipeline_domain::entities::security_context::Permission;
-use adaptive_pipeline_domain::services::pipeline_service::PipelineService;
-use adaptive_pipeline_domain::{FileChunk, Pipeline, PipelineStage, ProcessingContext, SecurityContext, SecurityLevel};
-
-// Application layer imports (duplicates removed - already imported above)
-use adaptive_pipeline_domain::services::file_io_service::FileIOService;
-
-use crate::application::services::pipeline::ConcurrentPipeline;
-use crate::infrastructure::adapters::{MultiAlgoCompression, MultiAlgoEncryption};
+use adaptive_pipeline_domain::entities::{SecurityContext, SecurityLevel};
+use adaptive_pipeline_domain::services::{Credentials, ShutdownSignal};
+use adaptive_pipeline_domain::value_objects::{
+    CorrelationId, FeatureFlags, IdempotencyKey, Namespace, ProtectedOperation, Role, SecretBytes, SessionId, UserId,
+};
+use adaptive_pipeline_domain::PipelineError;
+
+use crate::application::services::access_control::AccessControlService;
+use crate::application::services::authentication::AuthenticationService;
+use crate::application::services::pipeline_cache::PipelineCache;
+use crate::application::services::quota::QuotaService;
+use crate::application::services::session::SessionService;
+use crate::infrastructure::config::config_service::{ConfigService, FeatureSettings, OperationClass, ShutdownSettings};
+use crate::infrastructure::adapters::auth::providers_from_settings;
+use crate::infrastructure::config::database_path::resolve_sqlite_path;
 use crate::infrastructure::logging::ObservabilityService;
 use crate::infrastructure::metrics::{MetricsEndpoint, MetricsService};
+use crate::infrastructure::repositories::sqlite_chunk_history::SqliteChunkSizeHistoryRepository;
+use crate::infrastructure::repositories::sqlite_idempotency::SqliteIdempotencyRepository;
+use crate::infrastructure::repositories::sqlite_job::SqliteJobRepository;
 use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
-use crate::infrastructure::runtime::stage_executor::BasicStageExecutor;
-use crate::infrastructure::services::{
-    AdapipeFormat, Base64EncodingService, BinaryFormatService, DebugService, PassThroughService, PiiMaskingService,
-    TeeService,
+use crate::infrastructure::repositories::sqlite_role::SqliteRoleRepository;
+use crate::infrastructure::repositories::sqlite_running_job::SqliteRunningJobRepository;
+use crate::infrastructure::repositories::sqlite_session::SqliteSessionRepository;
+use crate::infrastructure::repositories::sqlite_usage::SqliteUsageRepository;
+use crate::infrastructure::runtime::{spawn_with_restart, RestartPolicy, StageRegistry, StorageType};
+use crate::presentation::daemon::JobDaemon;
+use crate::presentation::http::{ApiServer, ClientCertProxy};
+
+/// Restart policy for the metrics endpoint: a bind or accept failure should
+/// not leave a long-running process without metrics
+const METRICS_ENDPOINT_RESTART_POLICY: RestartPolicy = RestartPolicy::ExponentialBackoff {
+    max_retries: 5,
+    initial_delay: std::time::Duration::from_secs(1),
+    max_delay: std::time::Duration::from_secs(30),
 };
-use adaptive_pipeline_domain::repositories::stage_executor::StageExecutor;
 
 // CLI parsing now handled by bootstrap layer
 // See adaptive_pipeline_bootstrap::cli for CLI definitions and validation
 // Exit code mapping now in adaptive_pipeline_bootstrap::exit_code
 
-#[tokio::main]
-async fn main() -> std::process::ExitCode {
+/// How long past its grace period a command may run after a shutdown
+/// request before it is abandoned
+const SHUTDOWN_ABORT_MARGIN: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Name the process registers under when run as a Windows service
+#[cfg(windows)]
+const WINDOWS_SERVICE_NAME: &str = "adaptive_pipeline";
+
+fn main() -> std::process::ExitCode {
     // Bootstrap: Parse and validate CLI arguments with security checks
     let validated_cli = match adaptive_pipeline_bootstrap::bootstrap_cli() {
         Ok(cli) => cli,
@@ -273,44 +493,311 @@ async fn main() -> std::process::ExitCode {
         }
     };
 
+    // The exit code table and capabilities report need no runtime, database or service manager
+    if let adaptive_pipeline_bootstrap::ValidatedCommand::ExitCodes { json } = validated_cli.command {
+        print!("{}", render_exit_codes(json));
+        return std::process::ExitCode::SUCCESS;
+    }
+    if let adaptive_pipeline_bootstrap::ValidatedCommand::Capabilities { json } = validated_cli.command {
+        return match render_capabilities(validated_cli.config.as_deref(), json) {
+            Ok(report) => {
+                print!("{}", report);
+                std::process::ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                adaptive_pipeline_bootstrap::map_error_to_exit_code(&e.to_string()).into()
+            }
+        };
+    }
+
+    // Under the Service Control Manager the application runs on a thread the
+    // manager starts, and stop requests cancel it
+    #[cfg(windows)]
+    if validated_cli.daemonize {
+        return run_windows_service(validated_cli);
+    }
+
+    // Detach before the async runtime exists: a forked child keeps only the
+    // thread that forked, so worker threads would be lost
+    #[cfg(unix)]
+    if validated_cli.daemonize {
+        let platform = adaptive_pipeline_bootstrap::platform::create_platform();
+        if let Err(e) = platform.daemonize(validated_cli.pid_file.as_deref()) {
+            eprintln!("Failed to daemonize: {}", e);
+            return std::process::ExitCode::from(71); // EX_OSERR
+        }
+    }
+
     // Run application logic with validated configuration
-    let result = run_app(validated_cli).await;
+    let notifier = adaptive_pipeline_bootstrap::service::create_service_notifier();
+    let result = run(validated_cli, notifier, None);
 
     // Map result to appropriate Unix exit code
     adaptive_pipeline_bootstrap::result_to_exit_code(result)
 }
 
+/// Runs the application on a new async runtime, keeping the service
+/// manager informed, and removes its temporary files afterwards
+///
+/// # Arguments
+///
+/// * `cli` - Validated CLI configuration from bootstrap layer
+/// * `notifier` - Service manager to report readiness and shutdown to
+/// * `stop` - Cancelled when the service manager asks the process to stop
+fn run(
+    cli: adaptive_pipeline_bootstrap::ValidatedCli,
+    notifier: Arc<dyn ServiceNotifier>,
+    stop: Option<adaptive_pipeline_bootstrap::shutdown::CancellationToken>,
+) -> Result<()> {
+    use adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator;
+
+    // Initialize tracing first so resource detection is logged
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(if cli.verbose {
+            tracing::Level::DEBUG
+        } else {
+            tracing::Level::INFO
+        })
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    // Every log line of the run carries its correlation ID, as do the audit
+    // records, events and file headers it writes
+    let correlation_id = resolve_correlation_id()?;
+    let run_span = tracing::info_span!("run", correlation_id = %correlation_id);
+
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+    let result = runtime.block_on(async {
+        let watchdog = adaptive_pipeline_bootstrap::service::spawn_watchdog(notifier.clone());
+
+        // SIGINT, SIGTERM and service manager stops all request the same
+        // graceful shutdown: in-flight chunks get the grace period to finish
+        let grace_period = shutdown_grace_period(&cli).await;
+        let shutdown = ShutdownCoordinator::new(grace_period);
+        let signals = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                let handler = adaptive_pipeline_bootstrap::signals::create_signal_handler();
+                handler
+                    .wait_for_signal(Box::new(move || shutdown.initiate_shutdown()))
+                    .await;
+            })
+        };
+        let stopped_by_manager = stop.clone();
+        let stop_forwarder = stop.map(|stop| {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                stop.cancelled().await;
+                shutdown.initiate_shutdown();
+            })
+        });
+
+        // Commands check the signal themselves; this backstop covers any
+        // that do not stop within the grace period
+        let token = shutdown.token();
+        let abandon_after = grace_period + SHUTDOWN_ABORT_MARGIN;
+        let result = tokio::select! {
+            result = run_app(cli, notifier.clone(), Arc::new(token.clone()), grace_period, correlation_id)
+                .instrument(run_span) => result,
+            _ = async {
+                token.cancelled().await;
+                notifier.notify(ServiceState::Stopping);
+                tokio::time::sleep(abandon_after).await;
+            } => Err(PipelineError::cancelled_with_msg(format!(
+                "still running {:?} after the shutdown request; abandoned",
+                abandon_after
+            ))
+            .into()),
+        };
+        let result = match stopped_by_manager {
+            Some(stop) if stop.is_cancelled() => {
+                if let Err(e) = result {
+                    warn!("Stopped by the service manager: {}", e);
+                }
+                info!("Stopped by the service manager");
+                Ok(())
+            }
+            _ => result,
+        };
+
+        notifier.notify(ServiceState::Stopping);
+        signals.abort();
+        if let Some(stop_forwarder) = stop_forwarder {
+            stop_forwarder.abort();
+        }
+        if let Some(watchdog) = watchdog {
+            watchdog.abort();
+        }
+        // Running job leases of finished jobs are released before exiting
+        crate::application::services::quota::settle_releases().await;
+        result
+    });
+
+    // Remove this run's temporary files, whether or not it succeeded
+    crate::infrastructure::runtime::shutdown_temp_root();
+    result
+}
+
+/// How long the command gets to stop after a shutdown request: the
+/// `[shutdown]` grace period of its operation class
+///
+/// A config file that cannot be read leaves the default; `run_app` reports
+/// the error when it loads the file again.
+async fn shutdown_grace_period(cli: &adaptive_pipeline_bootstrap::ValidatedCli) -> std::time::Duration {
+    use adaptive_pipeline_bootstrap::ValidatedCommand;
+
+    let class = match cli.command {
+        ValidatedCommand::Restore { .. }
+        | ValidatedCommand::ExportTar { .. }
+        | ValidatedCommand::Mount { .. }
+        | ValidatedCommand::Scrub { .. }
+        | ValidatedCommand::Replicate { .. } => OperationClass::Restore,
+        _ => OperationClass::Process,
+    };
+    let settings = match &cli.config {
+        Some(config_path) => ConfigService::load_shutdown_settings(config_path)
+            .await
+            .unwrap_or_default(),
+        None => ShutdownSettings::default(),
+    };
+    settings.grace_period(class)
+}
+
+/// Loads the `[features]` section of `config`, warning about unknown or
+/// deprecated flags
+async fn load_feature_flags(config: Option<&std::path::Path>) -> Result<FeatureFlags, PipelineError> {
+    let settings = match config {
+        Some(config_path) => ConfigService::load_feature_settings(config_path).await?,
+        None => FeatureSettings::default(),
+    };
+    let (flags, warnings) = settings.resolve();
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+    Ok(flags)
+}
+
+/// Renders the capabilities report without starting the application
+///
+/// Only the feature flags come from `config`; warnings about them go to
+/// stderr since logging is not set up yet.
+fn render_capabilities(config: Option<&std::path::Path>, json: bool) -> Result<String> {
+    let settings = match config {
+        Some(config_path) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(ConfigService::load_feature_settings(config_path))?,
+        None => FeatureSettings::default(),
+    };
+    let (flags, warnings) = settings.resolve();
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(CapabilitiesUseCase::new().with_feature_flags(flags).render(json)?)
+}
+
+/// Runs the application as the Windows service the Service Control Manager
+/// started
+#[cfg(windows)]
+fn run_windows_service(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> std::process::ExitCode {
+    use adaptive_pipeline_bootstrap::shutdown::{ShutdownCoordinator, DEFAULT_GRACE_PERIOD_SECS};
+
+    let shutdown = ShutdownCoordinator::new(std::time::Duration::from_secs(DEFAULT_GRACE_PERIOD_SECS));
+    let stop = shutdown.token();
+    let result =
+        adaptive_pipeline_bootstrap::platform::run_as_service(WINDOWS_SERVICE_NAME, shutdown, move |notifier| {
+            match run(cli, notifier, Some(stop)) {
+                Ok(()) => 0,
+                Err(e) => {
+                    error!("Service failed: {}", e);
+                    adaptive_pipeline_bootstrap::map_error_to_exit_code(&e.to_string()).as_i32()
+                }
+            }
+        });
+    match result {
+        Ok(code) => std::process::ExitCode::from(code as u8),
+        Err(e) => {
+            eprintln!("Failed to run as a Windows service: {}", e);
+            std::process::ExitCode::from(71) // EX_OSERR
+        }
+    }
+}
+
+/// Resolves the storage type the resource manager sizes I/O for
+///
+/// An explicit `--storage-type` wins. Otherwise the platform inspects the
+/// device behind the file being processed (or the working directory) and the
+/// decision is logged.
+fn resolve_storage_type(cli: &adaptive_pipeline_bootstrap::ValidatedCli) -> StorageType {
+    use adaptive_pipeline_bootstrap::platform::{create_platform, StorageClass};
+    use adaptive_pipeline_bootstrap::ValidatedCommand;
+
+    if let Some(storage_type) = cli.storage_type.as_deref() {
+        return match storage_type {
+            "nvme" => StorageType::NVMe,
+            "ssd" => StorageType::Ssd,
+            "hdd" => StorageType::Hdd,
+            "network" => StorageType::Network,
+            _ => StorageType::Auto, // Shouldn't happen due to parse_storage_type validation
+        };
+    }
+
+    let probe = match &cli.command {
+        ValidatedCommand::Process { input, .. } => input.clone(),
+        _ => std::env::current_dir().unwrap_or_default(),
+    };
+    let storage = create_platform().detect_storage(&probe);
+    let storage_type = match storage.class {
+        StorageClass::Nvme => StorageType::NVMe,
+        StorageClass::Ssd => StorageType::Ssd,
+        StorageClass::Hdd => StorageType::Hdd,
+        StorageClass::Network => StorageType::Network,
+        StorageClass::Unknown => StorageType::Auto,
+    };
+    info!(
+        "Storage auto-detected for {}: {} (device: {}, filesystem: {}); read-ahead {} chunks",
+        probe.display(),
+        storage.class,
+        storage.device.as_deref().unwrap_or("none"),
+        storage.filesystem.as_deref().unwrap_or("unknown"),
+        storage_type.read_ahead_chunks()
+    );
+    storage_type
+}
+
 /// Main application logic separated for testability
 ///
 /// # Arguments
 ///
 /// * `cli` - Validated CLI configuration from bootstrap layer
+/// * `notifier` - Told once initialization is done and the command starts
+/// * `shutdown` - Requested on SIGINT, SIGTERM or a service manager stop
+/// * `grace_period` - How long in-flight chunks may finish after that
+/// * `correlation_id` - Identifies this run in what it records and writes
 ///
 /// # Returns
 ///
 /// Result indicating success or error
-async fn run_app(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> Result<()> {
+async fn run_app(
+    cli: adaptive_pipeline_bootstrap::ValidatedCli,
+    notifier: Arc<dyn ServiceNotifier>,
+    shutdown: Arc<dyn ShutdownSignal>,
+    grace_period: std::time::Duration,
+    correlation_id: CorrelationId,
+) -> Result<()> {
     // === Initialize Global Resource Manager ===
     // Educational: This must happen BEFORE any code uses RESOURCE_MANAGER
     // We configure it from CLI flags, falling back to intelligent defaults.
-    use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig, StorageType};
+    use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig};
 
     let resource_config = ResourceConfig {
         cpu_tokens: cli.cpu_threads,
         io_tokens: cli.io_threads,
-        storage_type: cli
-            .storage_type
-            .as_ref()
-            .map(|s| {
-                match s.as_str() {
-                    "nvme" => StorageType::NVMe,
-                    "ssd" => StorageType::Ssd,
-                    "hdd" => StorageType::Hdd,
-                    _ => StorageType::Auto, // Shouldn't happen due to parse_storage_type validation
-                }
-            })
-            .unwrap_or(StorageType::Auto),
-        memory_limit: None, // Use system detection
+        network_io_tokens: cli.network_io_threads,
+        storage_type: resolve_storage_type(&cli),
+        memory_limit: cli.memory_limit, // None: use system detection
     };
 
     init_resource_manager(resource_config)
@@ -319,22 +806,43 @@ async fn run_app(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> Result<()> {
     // Educational: Log the resource configuration for observability
     let rm = crate::infrastructure::runtime::resource_manager();
     println!(
-        "Resource Manager initialized: {} CPU tokens, {} I/O tokens, {} memory capacity",
+        "Resource Manager initialized: {} CPU tokens, {} I/O tokens, {} network I/O tokens, {} memory capacity",
         rm.cpu_tokens_total(),
         rm.io_tokens_total(),
+        rm.network_io_tokens_total(),
         rm.memory_capacity()
     );
 
-    // Initialize tracing
-    let subscriber = tracing_subscriber::FmtSubscriber::builder()
-        .with_max_level(if cli.verbose {
-            tracing::Level::DEBUG
-        } else {
-            tracing::Level::INFO
-        })
-        .finish();
+    // Pin the CPU worker pool before its first use builds it
+    if let Some(selection) = &cli.pin_workers {
+        let platform = adaptive_pipeline_bootstrap::platform::create_platform();
+        let cores = selection
+            .resolve(platform.as_ref())
+            .map_err(|e| anyhow::anyhow!("Invalid --pin-workers: {}", e))?;
+        info!("Pinning CPU worker threads to cores {:?}", cores);
+        crate::infrastructure::config::rayon_config::pin_cpu_pool(cores)
+            .map_err(|e| anyhow::anyhow!("Failed to pin CPU workers: {}", e))?;
+    }
 
-    tracing::subscriber::set_global_default(subscriber)?;
+    // === Initialize Managed Temp Root ===
+    // Temporary files are created under (or tracked by) one run directory
+    // that is removed at exit; runs that crashed leave theirs behind
+    use crate::infrastructure::runtime::{find_orphans, init_temp_root, TempRoot};
+
+    let temp_root = init_temp_root(&TempRoot::default_base())?;
+    if !matches!(cli.command, adaptive_pipeline_bootstrap::ValidatedCommand::Cleanup { .. }) {
+        match find_orphans(temp_root.base()) {
+            Ok(orphans) if !orphans.is_empty() => warn!(
+                "Found temporary files from {} crashed run(s) under {} ({} bytes); run `adaptive-pipeline cleanup` to \
+                 remove them",
+                orphans.len(),
+                temp_root.base().display(),
+                orphans.iter().map(|orphan| orphan.bytes).sum::<u64>()
+            ),
+            Ok(_) => {}
+            Err(e) => warn!("Could not check for leftover temporary files: {}", e),
+        }
+    }
 
     debug!("Starting Adaptive Pipeline v1.0.1");
 
@@ -345,13 +853,16 @@ async fn run_app(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> Result<()> {
     })?);
     debug!("Prometheus metrics service initialized");
 
+    // Stage services are built once and shared by every command that runs
+    // pipeline stages
+    let stage_registry = StageRegistry::builtin(metrics_service.clone());
+
     // Start metrics endpoint on background thread (port configured in
-    // observability.toml)
-    let metrics_endpoint = MetricsEndpoint::new(metrics_service.clone());
-    let metrics_handle = tokio::spawn(async move {
-        if let Err(e) = metrics_endpoint.start().await {
-            error!("Failed to start metrics endpoint: {}", e);
-        }
+    // observability.toml); it is restarted with backoff if it fails
+    let metrics_endpoint = Arc::new(MetricsEndpoint::new(metrics_service.clone()));
+    let metrics_handle = spawn_with_restart("metrics-endpoint", METRICS_ENDPOINT_RESTART_POLICY, move || {
+        let metrics_endpoint = metrics_endpoint.clone();
+        async move { metrics_endpoint.start().await }
     });
 
     // Give metrics endpoint time to start
@@ -362,74 +873,437 @@ async fn run_app(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> Result<()> {
     debug!("Enhanced observability service initialized with configuration");
 
     // Initialize SQLite pipeline repository
-    let sqlite_path = resolve_sqlite_path().map_err(|e| {
-        error!("Failed to resolve SQLite path: {}", e);
-        anyhow::anyhow!("Failed to resolve SQLite path: {}", e)
-    })?;
+    let sqlite_path = resolve_sqlite_path();
     debug!("Using SQLite database: {}", sqlite_path);
-    let pipeline_repository = Arc::new(SqlitePipelineRepository::new(&sqlite_path).await.map_err(|e| {
-        error!("Failed to initialize pipeline repository: {}", e);
+    let namespace: Namespace = cli.namespace.parse()?;
+    let pipeline_repository = Arc::new(
+        SqlitePipelineRepository::new(&sqlite_path)
+            .await
+            .map_err(|e| {
+                error!("Failed to initialize pipeline repository: {}", e);
+                anyhow::anyhow!("Repository initialization failed: {}", e)
+            })?
+            .in_namespace(namespace.clone())
+            .with_correlation_id(correlation_id.clone()),
+    );
+    // Jobs in this process load each pipeline once per cache lifetime
+    let pipeline_cache = Arc::new(PipelineCache::default());
+    debug!(namespace = %namespace, correlation_id = %correlation_id, "Pipeline repository initialized");
+
+    // Stored pipelines this version cannot run would otherwise fail part way
+    // through a file
+    if !matches!(cli.command, adaptive_pipeline_bootstrap::ValidatedCommand::Audit { .. }) {
+        match AuditPipelinesUseCase::new(pipeline_repository.clone()).audit().await {
+            Ok(advisories) => {
+                let failing = advisories
+                    .iter()
+                    .filter(|advisory| advisory.severity == AdvisorySeverity::Error)
+                    .count();
+                if failing > 0 {
+                    warn!(
+                        "{} stored pipeline stage(s) use algorithms or parameters this version cannot run; run \
+                         `adaptive-pipeline audit` for migration suggestions",
+                        failing
+                    );
+                }
+            }
+            Err(e) => warn!("Could not audit stored pipelines: {}", e),
+        }
+    }
+
+    let role_repository = Arc::new(SqliteRoleRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize role repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let usage_repository = Arc::new(SqliteUsageRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize usage repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let running_job_repository = Arc::new(SqliteRunningJobRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize running job repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let idempotency_repository = Arc::new(SqliteIdempotencyRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize idempotency repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let chunk_size_history = Arc::new(SqliteChunkSizeHistoryRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize chunk size history repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let session_repository = Arc::new(SqliteSessionRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize session repository: {}", e);
+        anyhow::anyhow!("Repository initialization failed: {}", e)
+    })?);
+
+    let job_repository = Arc::new(SqliteJobRepository::new(&sqlite_path).await.map_err(|e| {
+        error!("Failed to initialize job repository: {}", e);
         anyhow::anyhow!("Repository initialization failed: {}", e)
     })?);
-    debug!("Pipeline repository initialized");
 
     // Load configuration if provided
-    if let Some(config_path) = &cli.config {
-        info!("Loading configuration from: {}", config_path.display());
-        // TODO: Load configuration
+    let (security_settings, quota_settings, output_settings, session_settings, auth_settings) = match &cli.config {
+        Some(config_path) => {
+            info!("Loading configuration from: {}", config_path.display());
+            (
+                ConfigService::load_security_settings(config_path).await?,
+                ConfigService::load_quota_settings(config_path).await?,
+                ConfigService::load_output_settings(config_path).await?,
+                ConfigService::load_session_settings(config_path).await?,
+                ConfigService::load_auth_settings(config_path).await?,
+            )
+        }
+        None => Default::default(),
+    };
+
+    // Subsystems that ship dark are switched on per deployment
+    let feature_flags = load_feature_flags(cli.config.as_deref()).await?;
+    info!("Feature flags: {}", feature_flags);
+
+    // Per-namespace quotas, checked before processing starts
+    let mut quota_service = QuotaService::new(quota_settings.defaults)
+        .with_usage_repository(usage_repository.clone())
+        .with_running_job_repository(running_job_repository)
+        .with_metrics(metrics_service.clone());
+    for (name, limits) in quota_settings.namespaces {
+        quota_service = quota_service.with_namespace_limits(name.parse()?, limits);
+    }
+    let quota_service = Arc::new(quota_service);
+
+    let session_service = Arc::new(
+        SessionService::new(session_repository)
+            .with_ttl(session_settings.ttl())
+            .with_rate_limit(session_settings.requests_per_minute)
+            .with_audit(pipeline_repository.clone()),
+    );
+
+    let authentication = AuthenticationService::new()
+        .with_providers(providers_from_settings(&auth_settings)?)
+        .with_required(auth_settings.required);
+
+    // A session token stands in for the principal: the command runs as the
+    // session's user, in the session's namespace only
+    let session_token = env_credential("ADAPIPE_SESSION_TOKEN");
+    let credentials = presented_credentials(session_token.is_some())?;
+    let session = match session_token {
+        Some(token) => {
+            let session = session_service.authenticate(&token).await?;
+            if session.namespace() != &namespace {
+                return Err(PipelineError::security_violation(format!(
+                    "Permission denied: session {} is scoped to namespace '{}', not '{}'",
+                    session.id(),
+                    session.namespace(),
+                    namespace
+                ))
+                .into());
+            }
+            Some(session)
+        }
+        None => None,
+    };
+
+    // Otherwise credentials checked by the [auth] providers do, confined to
+    // the namespace the provider grants if it names one
+    let identity = match &credentials {
+        Some(credentials) => {
+            let identity = authentication.authenticate(credentials).await?;
+            if let Some(scope) = identity.namespace.as_ref().filter(|scope| **scope != namespace) {
+                return Err(PipelineError::security_violation(format!(
+                    "Permission denied: '{}' is confined to namespace '{}', not '{}'",
+                    identity.principal, scope, namespace
+                ))
+                .into());
+            }
+            Some(identity)
+        }
+        None => None,
+    };
+    if authentication.is_required()
+        && session.is_none()
+        && identity.is_none()
+        && protected_operation(&cli.command).is_some()
+    {
+        return Err(PipelineError::security_violation(
+            "Permission denied: authentication required; set ADAPIPE_API_KEY, ADAPIPE_BEARER_TOKEN or \
+             ADAPIPE_SESSION_TOKEN",
+        )
+        .into());
     }
 
+    // Resolve the active role and gate the command on it
+    let requested_role = std::env::var("ADAPIPE_ROLE")
+        .ok()
+        .or(security_settings.role.clone())
+        .map(|role| role.parse::<Role>())
+        .transpose()?;
+    let security_level = std::env::var("ADAPIPE_SECURITY_LEVEL")
+        .ok()
+        .or(security_settings.security_level.clone())
+        .map(|level| level.parse::<SecurityLevel>())
+        .transpose()?;
+    let principal = match (&session, &identity) {
+        (Some(session), _) => session.user_id().to_string(),
+        (None, Some(identity)) => identity.principal.clone(),
+        (None, None) => resolve_principal(),
+    };
+    let mut access_control = AccessControlService::new(role_repository.clone(), principal.clone());
+    if let Some(session) = &session {
+        access_control = access_control.with_session(session.clone());
+    }
+    if let Some(identity) = identity {
+        access_control = access_control.with_identity(identity);
+    }
+    let mut access_control = access_control
+        .with_namespace(access_namespace(&cli.command, &namespace))
+        .with_requested_role(requested_role);
+    if let Some(security_level) = security_level {
+        access_control = access_control.with_security_level(security_level);
+    }
+    let security_context = match protected_operation(&cli.command) {
+        // The first admin is the only one who may act before roles exist
+        Some(_) if matches!(cli.command, adaptive_pipeline_bootstrap::ValidatedCommand::RoleInit) => {
+            access_control.authorize_first_admin().await?
+        }
+        Some(operation) => access_control.authorize(operation).await?,
+        None => SecurityContext::default(),
+    };
+
+    // Initialization is done; a service manager can consider us started
+    notifier.notify(ServiceState::Ready);
+
     // Execute command (using validated commands from bootstrap)
     match cli.command {
         adaptive_pipeline_bootstrap::ValidatedCommand::Process {
             input,
             output,
+            directory,
             pipeline,
-            chunk_size_mb,
+            chunk_size,
             workers,
+            manifest,
+            signing_key,
+            idempotency_key,
+            stage_timeout_secs,
+            chunk_timeout_secs,
+            max_worker_restarts,
+            direct_io,
+            checksum_offload,
+            overwrite_policy,
+            output_mode,
+            priority,
+            password_prompt,
+            passphrase_policy,
         } => {
+            let idempotency_key = idempotency_key.map(IdempotencyKey::new).transpose()?;
+            let password = password_prompt.then(|| read_password(true)).transpose()?;
             let config = ProcessFileConfig {
                 input,
                 output,
                 pipeline,
-                chunk_size_mb,
-                workers,
-                channel_depth: Some(cli.channel_depth),
+                chunk_size,
+                workers: workers.map(|w| w.count()),
+                channel_depth: cli.channel_depth,
+                inflight_window: cli.inflight_window,
+                write_manifest: manifest,
+                signing_key,
+                idempotency_key,
+                stage_timeout: stage_timeout_secs.map(std::time::Duration::from_secs),
+                chunk_timeout: chunk_timeout_secs.map(std::time::Duration::from_secs),
+                max_worker_restarts,
+                direct_io,
+                checksum_offload,
+                overwrite_policy,
+                output_mode: output_mode.or(output_settings.file_mode),
+                priority,
+                password,
+                passphrase_policy,
             };
-            let use_case = ProcessFileUseCase::new(
-                metrics_service.clone(),
-                observability_service.clone(),
-                pipeline_repository.clone(),
-            );
-            use_case.execute(config).await?;
+            let use_case = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .pipeline_cache(pipeline_cache.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .idempotency_repository(idempotency_repository.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .shutdown(shutdown.clone(), grace_period)
+                .security_context(security_context.clone())
+                .build()
+                .await?;
+            let bus = command_bus(access_control.principal(), &metrics_service);
+            match directory {
+                Some(filter) => {
+                    let command =
+                        ProcessDirectoryCommand::new(config.input.clone(), config.output.clone(), filter, config);
+                    let use_case =
+                        ProcessDirectoryUseCase::new(use_case, metrics_service.clone()).with_shutdown(shutdown.clone());
+                    bus.register(use_case).dispatch(command).await?;
+                }
+                None => {
+                    bus.register(use_case).dispatch(ProcessFileCommand::new(config)).await?;
+                }
+            }
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::Create { name, stages, output } => {
-            let use_case = CreatePipelineUseCase::new(pipeline_repository.clone());
-            use_case.execute(name, stages, output).await?;
+        adaptive_pipeline_bootstrap::ValidatedCommand::Create {
+            name,
+            stages,
+            output,
+            topology,
+            checksum,
+            security_policy,
+        } => {
+            let mut use_case = CreatePipelineUseCase::new(pipeline_repository.clone())
+                .with_principal(principal)
+                .with_security_policy(security_policy)
+                .with_checksum_algorithm(checksum);
+            if let Some(session) = &session {
+                use_case = use_case.with_session(session.id().clone());
+            }
+            let command = CreatePipelineCommand::new(name, stages)
+                .with_output(output)
+                .with_topology(topology);
+            command_bus(access_control.principal(), &metrics_service)
+                .register(use_case)
+                .dispatch(command)
+                .await?;
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::List => {
+        adaptive_pipeline_bootstrap::ValidatedCommand::List { usage } => {
             let use_case = ListPipelinesUseCase::new(pipeline_repository.clone());
-            use_case.execute().await?;
+            use_case.execute(usage).await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::Show {
+            pipeline,
+            graph,
+            plan_input,
+        } => {
+            let use_case = ShowPipelineUseCase::new(pipeline_repository.clone(), pipeline_repository.clone());
+            match plan_input {
+                Some(input) => {
+                    let pipeline_service = ProcessFileUseCase::create_pipeline_service(
+                        &metrics_service,
+                        &pipeline_repository,
+                        None,
+                        None,
+                        false,
+                        None,
+                        &stage_registry,
+                    );
+                    use_case
+                        .with_pipeline_service(Arc::new(
+                            pipeline_service.with_chunk_size_history(chunk_size_history.clone()),
+                        ))
+                        .with_chunk_size_history(chunk_size_history.clone())
+                        .execute_plan(&pipeline, &input)
+                        .await?;
+                }
+                None => use_case.execute(pipeline, graph).await?,
+            }
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::Estimate { pipeline, size, json } => {
+            let use_case = EstimateCostUseCase::new(pipeline_repository.clone())
+                .with_chunk_size_history(chunk_size_history.clone());
+            use_case.execute(&pipeline, size, json).await?;
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::Show { pipeline } => {
-            let use_case = ShowPipelineUseCase::new(pipeline_repository.clone());
-            use_case.execute(pipeline).await?;
+        adaptive_pipeline_bootstrap::ValidatedCommand::Audit { json } => {
+            AuditPipelinesUseCase::new(pipeline_repository.clone())
+                .execute(json)
+                .await?;
         }
 
         adaptive_pipeline_bootstrap::ValidatedCommand::Delete { pipeline, force } => {
-            let use_case = DeletePipelineUseCase::new(pipeline_repository.clone());
-            use_case.execute(pipeline, force).await?;
+            let mut use_case = DeletePipelineUseCase::new(pipeline_repository.clone())
+                .with_pipeline_cache(pipeline_cache)
+                .with_principal(principal);
+            if let Some(session) = &session {
+                use_case = use_case.with_session(session.id().clone());
+            }
+            command_bus(access_control.principal(), &metrics_service)
+                .register(use_case)
+                .dispatch(DeletePipelineCommand::new(pipeline, force))
+                .await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::PipelineExport {
+            pipeline,
+            format,
+            output,
+        } => {
+            ExportPipelineUseCase::new(pipeline_repository.clone())
+                .execute(pipeline, format, output)
+                .await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::PipelineImport { file, format, name } => {
+            let mut use_case = ImportPipelineUseCase::new(pipeline_repository.clone()).with_principal(principal);
+            if let Some(session) = &session {
+                use_case = use_case.with_session(session.id().clone());
+            }
+            use_case.execute(file, format, name).await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::Benchmark {
+            file,
+            size_mb,
+            iterations,
+            sweep_chunk_sizes: Some(chunk_sizes),
+            pipeline,
+            apply,
+            ..
+        } => {
+            let pipeline = pipeline.ok_or_else(|| anyhow::anyhow!("--sweep-chunk-size requires --pipeline"))?;
+            let process_file = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .shutdown(shutdown.clone(), grace_period)
+                .security_context(security_context.clone())
+                .build()
+                .await?;
+            ChunkSizeSweepUseCase::new(process_file, pipeline_repository.clone())
+                .with_apply(apply)
+                .execute(&pipeline, &chunk_sizes, file, size_mb, iterations)
+                .await?;
         }
 
         adaptive_pipeline_bootstrap::ValidatedCommand::Benchmark {
             file,
             size_mb,
             iterations,
+            save,
+            compare,
+            throughput_threshold,
+            duration_threshold,
+            ..
         } => {
-            let use_case = BenchmarkSystemUseCase::new();
+            let mut use_case = BenchmarkSystemUseCase::new();
+            if let Some(path) = save {
+                use_case = use_case.with_saved_baseline(path);
+            }
+            if let Some(path) = compare {
+                use_case = use_case.with_baseline_comparison(
+                    path,
+                    RegressionThresholds {
+                        throughput_pct: throughput_threshold,
+                        duration_pct: duration_threshold,
+                    },
+                );
+            }
             use_case.execute(file, size_mb, iterations).await?;
         }
 
@@ -438,1212 +1312,447 @@ async fn run_app(cli: adaptive_pipeline_bootstrap::ValidatedCli) -> Result<()> {
             use_case.execute(config).await?;
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::ValidateFile { file, full } => {
-            let use_case = ValidateFileUseCase::new();
-            use_case.execute(file, full).await?;
+        adaptive_pipeline_bootstrap::ValidatedCommand::ValidateFile { file, full, json } => {
+            let use_case = ValidateFileUseCase::new(metrics_service.clone()).with_stage_registry(stage_registry.clone());
+            use_case.execute(file, full, json).await?;
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::Restore {
-            input,
-            output_dir,
-            mkdir,
-            overwrite,
+        adaptive_pipeline_bootstrap::ValidatedCommand::Inspect { file, json } => {
+            let use_case = InspectFileUseCase::new();
+            use_case.execute(file, json).await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::Ls { archive, filter } => {
+            ListArchiveUseCase::new().execute(archive, filter).await?;
+        }
+
+        adaptive_pipeline_bootstrap::ValidatedCommand::VerifyManifest {
+            manifest,
+            file,
+            public_key,
         } => {
-            // Use the new hybrid architecture-compliant function
-            restore_file_from_adapipe_v2(input, output_dir, mkdir, overwrite).await?;
+            let use_case = VerifyManifestUseCase::new();
+            use_case.execute(manifest, file, public_key).await?;
         }
 
-        adaptive_pipeline_bootstrap::ValidatedCommand::Compare {
-            original,
-            adapipe,
-            detailed,
+        adaptive_pipeline_bootstrap::ValidatedCommand::Scrub {
+            catalog,
+            sample,
+            password_prompt,
+            json,
         } => {
-            let use_case = CompareFilesUseCase::new();
-            use_case.execute(original, adapipe, detailed).await?;
+            let password = password_prompt.then(|| read_password(false)).transpose()?;
+            let use_case = ScrubArchivesUseCase::new(metrics_service.clone())
+                .with_stage_registry(stage_registry.clone())
+                .with_shutdown(shutdown.clone())
+                .with_security_context(security_context.clone());
+            use_case.execute(catalog, sample, password, json).await?;
         }
-    }
 
-    Ok(())
-}
+        adaptive_pipeline_bootstrap::ValidatedCommand::Replicate { catalog, to, json } => {
+            let use_case = ReplicateArchivesUseCase::new(metrics_service.clone()).with_shutdown(shutdown.clone());
+            use_case.execute(catalog, to, json).await?;
+        }
 
-async fn restore_file_from_adapipe_v2(
-    input: PathBuf,
-    output_dir: Option<PathBuf>,
-    mkdir: bool,
-    overwrite: bool,
-) -> Result<()> {
-    info!("Restoring file from .adapipe: {}", input.display());
+        adaptive_pipeline_bootstrap::ValidatedCommand::VectorsGenerate { output } => {
+            EncryptionVectorsUseCase::new().generate(output).await?;
+        }
 
-    // Validate input file exists
-    if !input.exists() {
-        return Err(anyhow::anyhow!(
-            "Input .adapipe file does not exist: {}",
-            input.display()
-        ));
-    }
+        adaptive_pipeline_bootstrap::ValidatedCommand::VectorsVerify { input } => {
+            EncryptionVectorsUseCase::new().verify(input).await?;
+        }
 
-    // Read .adapipe metadata to determine target path
-    println!("🔍 Reading .adapipe file metadata...");
-    let file_data = std::fs::read(&input)?;
-    let (metadata, _footer_size) = FileHeader::from_footer_bytes(&file_data)
-        .map_err(|e| anyhow::anyhow!("Failed to read .adapipe metadata: {}", e))?;
-
-    // Determine output path
-    let target_path = if let Some(ref dir) = output_dir {
-        // Use specified directory + original filename
-        let original_filename = std::path::Path::new(&metadata.original_filename)
-            .file_name()
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Could not extract filename from original filename: {}",
-                    metadata.original_filename
-                )
-            })?
-            .to_string_lossy()
-            .to_string();
-
-        dir.join(original_filename)
-    } else {
-        // Use original full path from metadata
-        PathBuf::from(&metadata.original_filename)
-    };
-
-    println!("📁 Target restoration path: {}", target_path.display());
-
-    // Note: Restoration service removed - use use_cases::restore_file directly
-    // instead let file_io_service = Arc::new(TokioFileIO::new_default());
-
-    // Create Command following CQRS pattern
-    let command = RestoreFileCommand::new(input.clone(), target_path.clone())
-        .with_overwrite(overwrite)
-        .with_create_directories(mkdir)
-        .with_permission_validation(true);
-
-    // Execute validation through Application Service
-    println!("🔒 Validating permissions through Application Service...");
-    // TODO: Restoration service removed - implement permission validation via
-    // use_cases if needed restoration_service
-    //     .validate_restoration_permissions(&command)
-    //     .await
-    //     .map_err(|e| anyhow::anyhow!("Permission validation failed: {}", e))?;
-
-    println!("   ✅ All permission checks passed");
-
-    // Use proper Application Service integration
-    println!("🔄 Using Application Service for restoration...");
-
-    // Note: Restoration service removed - use use_cases::restore_file directly
-    // instead
-
-    // Determine target path
-    let target_path = if let Some(output_dir) = output_dir {
-        // Create output directory if needed
-        if mkdir && !output_dir.exists() {
-            std::fs::create_dir_all(&output_dir)
-                .map_err(|e| anyhow::anyhow!("Failed to create output directory: {}", e))?;
-        }
-
-        // Read metadata to get original filename
-        let file_data = std::fs::read(&input)?;
-        let (metadata, _) = FileHeader::from_footer_bytes(&file_data)
-            .map_err(|e| anyhow::anyhow!("Failed to read .adapipe metadata: {}", e))?;
-
-        output_dir.join(&metadata.original_filename)
-    } else {
-        // Use same directory as input file, but with original filename
-        let file_data = std::fs::read(&input)?;
-        let (metadata, _) = FileHeader::from_footer_bytes(&file_data)
-            .map_err(|e| anyhow::anyhow!("Failed to read .adapipe metadata: {}", e))?;
-
-        input
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("."))
-            .join(&metadata.original_filename)
-    };
-
-    // Check if target exists and handle overwrite
-    if target_path.exists() && !overwrite {
-        return Err(anyhow::anyhow!(
-            "Target file already exists: {}\nUse --overwrite to overwrite existing files",
-            target_path.display()
-        ));
-    }
-
-    // Create restore command
-    let restore_command = RestoreFileCommand {
-        source_adapipe_path: input.clone(),
-        target_path: target_path.clone(),
-        create_directories: mkdir,
-        overwrite,
-        validate_permissions: true,
-    };
-
-    println!("💾 Restoring file using Application Service...");
-    println!("   Source: {}", input.display());
-    println!("   Target: {}", target_path.display());
-
-    // Step 1: Read .adapipe metadata
-    info!("Reading .adapipe file metadata...");
-    let binary_format_service = AdapipeFormat::new();
-    let metadata = binary_format_service
-        .read_metadata(&input)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to read .adapipe metadata: {}", e))?;
-
-    println!("   📋 Metadata details:");
-    println!("      - Original filename: {}", metadata.original_filename);
-    println!("      - Original size: {} bytes", metadata.original_size);
-    println!("      - Encrypted: {}", metadata.is_encrypted());
-    println!("      - Compressed: {}", metadata.is_compressed());
-    println!("      - Processing steps: {}", metadata.processing_steps.len());
-
-    // Step 2: Validate target path and permissions
-    if target_path.exists() && !overwrite {
-        return Err(anyhow::anyhow!(
-            "Target file already exists: {}\nUse --overwrite to replace it",
-            target_path.display()
-        ));
-    }
-
-    // Step 3: Handle directory creation if needed
-    if let Some(parent_dir) = target_path.parent() {
-        if !parent_dir.exists() {
-            if mkdir {
-                println!("📂 Creating directory: {}", parent_dir.display());
-                std::fs::create_dir_all(parent_dir).map_err(|e| {
-                    if e.kind() == std::io::ErrorKind::PermissionDenied {
-                        anyhow::anyhow!(
-                            "Permission denied: Cannot create directory '{}'\nTry running with elevated privileges",
-                            parent_dir.display()
-                        )
-                    } else {
-                        anyhow::anyhow!("Failed to create directory '{}': {}", parent_dir.display(), e)
-                    }
-                })?;
-            } else {
-                return Err(anyhow::anyhow!(
-                    "Output directory does not exist: {}\nUse --mkdir to create it",
-                    parent_dir.display()
-                ));
-            }
-        }
-    }
-
-    // Step 4: Create restoration pipeline using use_cases::restore_file
-    info!("Creating restoration pipeline...");
-    let restoration_pipeline = application::use_cases::create_restoration_pipeline(&metadata)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create restoration pipeline: {}", e))?;
-
-    println!(
-        "   🔄 Restoration pipeline created with {} stages",
-        restoration_pipeline.stages().len()
-    );
-    for stage in restoration_pipeline.stages() {
-        println!("      - {} (type: {:?})", stage.name(), stage.stage_type());
-    }
-
-    // Step 5: Read chunks from .adapipe file and process through restoration
-    // pipeline
-    info!("Starting restoration process...");
-    let mut reader = binary_format_service
-        .create_reader(&input)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create .adapipe reader: {}", e))?;
-
-    // Create output file
-    let mut output_file = tokio::fs::File::create(&target_path)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
-
-    // Create services and stage executor for restoration
-    let compression_service = Arc::new(MultiAlgoCompression::new());
-    let encryption_service = Arc::new(MultiAlgoEncryption::new());
-
-    // Build stage service registry for restoration
-    let mut stage_services: HashMap<String, Arc<dyn adaptive_pipeline_domain::services::StageService>> = HashMap::new();
-    stage_services.insert(
-        "brotli".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "gzip".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "zstd".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "lz4".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "aes256gcm".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "aes128gcm".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "chacha20poly1305".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "base64".to_string(),
-        Arc::new(Base64EncodingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "pii_masking".to_string(),
-        Arc::new(PiiMaskingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "tee".to_string(),
-        Arc::new(TeeService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "passthrough".to_string(),
-        Arc::new(PassThroughService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "debug".to_string(),
-        Arc::new(DebugService::new(Arc::new(MetricsService::new()?)))
-            as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-
-    let stage_executor = Arc::new(BasicStageExecutor::new(stage_services));
-
-    let mut chunks_processed = 0u32;
-    let mut bytes_written = 0u64;
-    let mut current_offset = 0u64;
-
-    // Process each chunk
-    while let Some(chunk_format) = reader
-        .read_next_chunk()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to read chunk: {}", e))?
-    {
-        // Reconstruct FileChunk from ChunkFormat
-        // For encrypted chunks, prepend nonce back to data
-        let chunk_data = if metadata.is_encrypted() {
-            let mut reconstructed_data = chunk_format.nonce.to_vec();
-            reconstructed_data.extend_from_slice(&chunk_format.payload);
-            reconstructed_data
-        } else {
-            chunk_format.payload.clone()
-        };
-
-        let is_final = chunks_processed == metadata.chunk_count - 1;
-        let mut file_chunk = FileChunk::new(chunks_processed as u64, current_offset, chunk_data, is_final)
-            .map_err(|e| anyhow::anyhow!("Failed to create FileChunk: {}", e))?;
-
-        // Create processing context for restoration
-        let security_context =
-            SecurityContext::with_permissions(None, vec![Permission::Read, Permission::Write], SecurityLevel::Internal);
-        let mut context = ProcessingContext::new(
-            metadata.original_size,
-            security_context,
-        );
-
-        // Process through restoration stages (decryption, decompression)
-        for stage in restoration_pipeline.stages() {
-            // Skip checksum stages during restoration
-            if stage.stage_type() == &StageType::Checksum {
-                continue;
-            }
-
-            // Execute stage using stage executor
-            file_chunk = stage_executor
-                .execute(stage, file_chunk, &mut context)
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to execute stage '{}': {}", stage.name(), e))?;
+        adaptive_pipeline_bootstrap::ValidatedCommand::SelfTest { json } => {
+            let use_case = SelfTestUseCase::new(pipeline_repository.clone(), temp_root.run_dir().to_path_buf());
+            use_case.execute(json).await?;
         }
 
-        // Write restored data to output file
-        output_file
-            .write_all(file_chunk.data())
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to write to output file: {}", e))?;
-
-        bytes_written += file_chunk.data().len() as u64;
-        current_offset += file_chunk.data().len() as u64;
-        chunks_processed += 1;
-
-        if chunks_processed.is_multiple_of(100) {
-            println!(
-                "   📦 Processed {} chunks, {} bytes written",
-                chunks_processed, bytes_written
+        adaptive_pipeline_bootstrap::ValidatedCommand::Capabilities { json } => {
+            print!(
+                "{}",
+                CapabilitiesUseCase::new()
+                    .with_feature_flags(feature_flags.clone())
+                    .render(json)?
             );
         }
-    }
-
-    // Flush and close output file
-    output_file
-        .flush()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to flush output file: {}", e))?;
-
-    println!("✅ Restoration complete!");
-    println!("   📦 Chunks processed: {}", chunks_processed);
-    println!("   📊 Total bytes written: {} bytes", bytes_written);
-    println!("   📁 Restored file: {}", target_path.display());
-
-    // Verify file size matches original
-    let restored_size = std::fs::metadata(&target_path)?.len();
-    if restored_size != metadata.original_size {
-        println!(
-            "   ⚠️  Warning: Restored file size ({} bytes) doesn't match original size ({} bytes)",
-            restored_size, metadata.original_size
-        );
-    } else {
-        println!("   ✅ File size verified: {} bytes", restored_size);
-    }
-
-    Ok(())
-}
-
-/// Legacy restore function (to be gradually replaced)
-async fn restore_file_from_adapipe_legacy(
-    input: PathBuf,
-    output_dir: Option<PathBuf>,
-    mkdir: bool,
-    overwrite: bool,
-) -> Result<()> {
-    info!("Restoring file from .adapipe: {}", input.display());
-
-    // Validate input file exists
-    if !input.exists() {
-        return Err(anyhow::anyhow!(
-            "Input .adapipe file does not exist: {}",
-            input.display()
-        ));
-    }
-
-    // Read .adapipe metadata
-    println!("🔍 Reading .adapipe file metadata...");
-    let _file = std::fs::File::open(&input)?;
-    // Read entire file to get footer data
-    let file_data = std::fs::read(&input)?;
-    let (metadata, _footer_size) = FileHeader::from_footer_bytes(&file_data)
-        .map_err(|e| anyhow::anyhow!("Failed to read .adapipe metadata: {}", e))?;
-
-    // Debug: Show metadata details
-    println!("   📋 Metadata details:");
-    println!("      - Encrypted: {}", metadata.is_encrypted());
-    println!("      - Compressed: {}", metadata.is_compressed());
-    println!("      - Processing steps count: {}", metadata.processing_steps.len());
-    for (i, step) in metadata.processing_steps.iter().enumerate() {
-        println!("      - Step {}: {:?} - {}", i, step.step_type, step.algorithm);
-    }
-    if metadata.is_encrypted() {
-        println!("      - Encryption algorithm: {:?}", metadata.encryption_algorithm());
-    }
-    if metadata.is_compressed() {
-        println!("      - Compression algorithm: {:?}", metadata.compression_algorithm());
-    }
-    println!("      - Original size: {} bytes", metadata.original_size);
-    println!("      - Pipeline ID: {}", metadata.pipeline_id);
-
-    // Determine output path
-    let output_path = if let Some(dir) = output_dir {
-        // Use specified directory + original filename
-        let original_filename = std::path::Path::new(&metadata.original_filename)
-            .file_name()
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Could not extract filename from original filename: {}",
-                    metadata.original_filename
-                )
-            })?
-            .to_string_lossy()
-            .to_string();
-
-        dir.join(original_filename)
-    } else {
-        // Use original full path from metadata
-        PathBuf::from(&metadata.original_filename)
-    };
-
-    println!("📁 Target restoration path: {}", output_path.display());
-
-    // Validate permissions before proceeding
-    println!("🔒 Validating permissions...");
-
-    // Check if target file already exists
-    if output_path.exists() {
-        if !overwrite {
-            return Err(anyhow::anyhow!(
-                "Target file already exists: {}\nUse --overwrite to replace it",
-                output_path.display()
-            ));
-        }
-
-        // Check if existing file is writable
-        let metadata = std::fs::metadata(&output_path)
-            .map_err(|e| anyhow::anyhow!("Failed to check existing file permissions: {}", e))?;
 
-        if metadata.permissions().readonly() {
-            return Err(anyhow::anyhow!(
-                "Target file is read-only: {}\nChange permissions or use a different location",
-                output_path.display()
-            ));
-        }
-
-        println!("   ⚠️  Target file exists and will be overwritten");
-    }
-
-    // First, handle directory creation if needed
-    if let Some(parent_dir) = output_path.parent() {
-        if !parent_dir.exists() {
-            if mkdir {
-                println!("📂 Creating directory: {}", parent_dir.display());
-                std::fs::create_dir_all(parent_dir).map_err(|e| {
-                    // Provide specific error messages for common permission issues
-                    if e.kind() == std::io::ErrorKind::PermissionDenied {
-                        anyhow::anyhow!(
-                            "Permission denied: Cannot create directory '{}'\nTry running with elevated privileges or \
-                             choose a different location",
-                            parent_dir.display()
-                        )
-                    } else {
-                        anyhow::anyhow!("Failed to create directory '{}': {}", parent_dir.display(), e)
-                    }
-                })?;
-            } else {
-                print!(
-                    "Directory '{}' does not exist. Create it? [y/N]: ",
-                    parent_dir.display()
-                );
-                std::io::Write::flush(&mut std::io::stdout())?;
-
-                let mut input = String::new();
-                std::io::stdin().read_line(&mut input)?;
-
-                if input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes" {
-                    println!("📂 Creating directory: {}", parent_dir.display());
-                    std::fs::create_dir_all(parent_dir).map_err(|e| {
-                        if e.kind() == std::io::ErrorKind::PermissionDenied {
-                            anyhow::anyhow!(
-                                "Permission denied: Cannot create directory '{}'\nTry running with elevated \
-                                 privileges or choose a different location",
-                                parent_dir.display()
-                            )
-                        } else {
-                            anyhow::anyhow!("Failed to create directory '{}': {}", parent_dir.display(), e)
-                        }
-                    })?;
-                } else {
-                    return Err(anyhow::anyhow!("Directory creation cancelled by user"));
-                }
-            }
+        adaptive_pipeline_bootstrap::ValidatedCommand::ExitCodes { json } => {
+            print!("{}", render_exit_codes(json));
         }
 
-        // Now test write permissions to the directory (whether it existed or was just
-        // created)
-        println!("   🔍 Testing directory write permissions...");
-        let temp_test_file = parent_dir.join(".adapipe_permission_test");
-        match std::fs::File::create(&temp_test_file) {
-            Ok(_) => {
-                // Clean up test file
-                let _ = std::fs::remove_file(&temp_test_file);
-                println!("   ✅ Directory write permissions verified");
+        adaptive_pipeline_bootstrap::ValidatedCommand::Restore {
+            input,
+            output_dir,
+            mkdir,
+            overwrite_policy,
+            staging_dir,
+            trust_archive_paths,
+            output_mode,
+            dir_mode,
+            priority,
+            report,
+            password_prompt,
+            range,
+            workers,
+        } => {
+            let target =
+                RestoreFileUseCase::resolve_target_path(&input, output_dir.as_deref(), trust_archive_paths).await?;
+            let mut command = RestoreFileCommand::new(input, target)
+                .with_overwrite_policy(overwrite_policy)
+                .with_create_directories(mkdir)
+                .with_staging_dir(staging_dir)
+                .with_output_mode(output_mode.or(output_settings.file_mode))
+                .with_directory_mode(dir_mode.or(output_settings.dir_mode))
+                .with_priority(priority)
+                .with_report(report)
+                .with_range(range);
+            if password_prompt {
+                command = command.with_password(read_password(false)?);
             }
-            Err(e) => {
-                return Err(anyhow::anyhow!(
-                    "Cannot write to directory '{}': {}\nThis could be due to:\n  - Insufficient permissions (try \
-                     running with elevated privileges)\n  - Directory is read-only\n  - Filesystem is mounted \
-                     read-only\n  - Security restrictions (SELinux, AppArmor, etc.)\nTry choosing a different \
-                     location or checking directory permissions",
-                    parent_dir.display(),
-                    e
-                ));
+            let mut use_case = RestoreFileUseCase::new(metrics_service.clone())
+                .with_stage_registry(stage_registry.clone())
+                .with_shutdown(shutdown.clone())
+                .with_security_context(security_context.clone());
+            if let Some(workers) = workers {
+                use_case = use_case.with_workers(workers.count());
             }
+            command_bus(access_control.principal(), &metrics_service)
+                .register(use_case)
+                .dispatch(command)
+                .await?;
         }
-    }
 
-    // Check available disk space
-    if let Some(parent_dir) = output_path.parent() {
-        match std::fs::metadata(parent_dir) {
-            Ok(_) => {
-                // On Unix systems, we can use statvfs to check disk space, but for simplicity
-                // we'll just verify the directory is accessible and warn about space
-                let required_size = metadata.original_size;
-                if required_size > 0 {
-                    println!(
-                        "   💾 Required disk space: {} bytes ({:.1} MB)",
-                        required_size,
-                        (required_size as f64) / (1024.0 * 1024.0)
-                    );
-                    println!("   ⚠️  Ensure sufficient disk space is available");
-                }
-            }
-            Err(e) => {
-                return Err(anyhow::anyhow!(
-                    "Cannot access target directory '{}': {}",
-                    parent_dir.display(),
-                    e
-                ));
+        adaptive_pipeline_bootstrap::ValidatedCommand::Mount {
+            archive,
+            mountpoint,
+            password_prompt,
+            cache_size,
+        } => {
+            let password = password_prompt.then(|| read_password(false)).transpose()?;
+            let mut restore = RestoreFileUseCase::new(metrics_service.clone())
+                .with_stage_registry(stage_registry.clone())
+                .with_security_context(security_context.clone());
+            if let Some(bytes) = cache_size {
+                restore = restore.with_chunk_cache_size(bytes);
             }
+            let use_case = MountArchiveUseCase::new(restore).with_shutdown(shutdown.clone());
+            use_case.execute(archive, mountpoint, password).await?;
         }
-    }
-
-    // Final permission validation summary
-    println!("   ✅ All permission checks passed");
-
-    // Create ephemeral restoration pipeline from .adapipe metadata
-    println!("🔧 Creating ephemeral restoration pipeline...");
-    let restoration_pipeline = create_restoration_pipeline(&metadata)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create restoration pipeline: {}", e))?;
-
-    println!("   Pipeline ID: {}", restoration_pipeline.id());
-    println!("   Stages: {}", restoration_pipeline.stages().len());
-
-    // Display pipeline stages for transparency
-    for (index, stage) in restoration_pipeline.stages().iter().enumerate() {
-        println!(
-            "   Stage {}: {} ({})",
-            index + 1,
-            stage.stage_type(),
-            stage.configuration().algorithm
-        );
-    }
 
-    // Perform streaming restoration with automatic validation
-    println!("\n🔄 Streaming restoration (decrypt → decompress → write → verify)...");
-    println!("   Original size: {} bytes", metadata.original_size);
-    println!("   Expected checksum: {}", metadata.original_checksum);
-
-    // Create progress indicator for real-time feedback
-    let estimated_chunks = metadata.original_size.div_ceil(1024 * 1024); // Round up
-    let progress_indicator = ProgressIndicatorService::new(estimated_chunks);
-
-    let restoration_result = stream_restore_with_validation(
-        &input,
-        &output_path,
-        &restoration_pipeline,
-        &metadata,
-        _footer_size,
-        &progress_indicator,
-    )
-    .await
-    .map_err(|e| anyhow::anyhow!("Restoration failed: {}", e))?;
-
-    // Validate restoration results
-    if restoration_result.checksum_verified {
-        println!("   ✅ Checksum verified: restoration successful");
-    } else {
-        return Err(anyhow::anyhow!(
-            "Checksum verification failed: expected {}, got {}",
-            metadata.original_checksum,
-            restoration_result.calculated_checksum
-        ));
-    }
-
-    println!(
-        "   📊 Processed {} bytes in {} chunks",
-        restoration_result.bytes_processed, restoration_result.chunks_processed
-    );
-
-    println!("\n✅ File restoration completed!");
-    println!("📁 Restored to: {}", output_path.display());
-
-    Ok(())
-}
-
-/// Creates an ephemeral restoration pipeline from .adapipe metadata
-///
-/// This function implements the DDD pattern by creating a domain entity
-/// (Pipeline) that encapsulates the restoration business logic. The pipeline is
-/// ephemeral and exists only for the duration of the restoration operation.
-///
-/// # Architecture
-/// - Domain-Driven Design: Pipeline as aggregate root
-/// - Value Objects: StageId, PipelineId for type safety
-/// - Error Handling: Comprehensive validation and error propagation
-/// - Immutability: Pipeline stages are immutable once created
-pub async fn create_restoration_pipeline(metadata: &FileHeader) -> Result<Pipeline> {
-    use adaptive_pipeline_domain::entities::pipeline::Pipeline;
-    use adaptive_pipeline_domain::entities::pipeline_stage::{PipelineStage, StageConfiguration, StageType};
-    use std::collections::HashMap;
-
-    info!("Creating ephemeral restoration pipeline from metadata");
-
-    let mut stages = Vec::new();
-    let mut stage_index = 1;
-
-    // Build restoration pipeline stages from processing steps in reverse order
-    // Processing steps are stored in forward order, but restoration needs reverse
-    // order
-    let mut processing_steps = metadata.processing_steps.clone();
-    processing_steps.sort_by(|a, b| b.order.cmp(&a.order)); // Reverse order
-
-    info!(
-        "Building restoration pipeline from {} processing steps",
-        processing_steps.len()
-    );
-
-    for step in processing_steps {
-        match step.step_type {
-            adaptive_pipeline_domain::value_objects::ProcessingStepType::Encryption => {
-                let decryption_config = StageConfiguration {
-                    algorithm: step.algorithm.clone(),
-                    operation: adaptive_pipeline_domain::entities::Operation::Reverse, /* REVERSE for legacy
-                                                                                        * restoration! */
-                    parameters: step.parameters.clone(),
-                    parallel_processing: false,
-                    chunk_size: Some(1024 * 1024), // 1MB chunks
-                };
-
-                let decryption_stage = PipelineStage::new(
-                    "decryption".to_string(),
-                    StageType::Encryption, // Use Encryption type for decryption (internal restoration)
-                    decryption_config,
-                    stage_index,
-                )?;
-
-                stages.push(decryption_stage);
-                info!(
-                    "Added decryption stage: {} (from step order {})",
-                    step.algorithm, step.order
-                );
-                stage_index += 1;
+        adaptive_pipeline_bootstrap::ValidatedCommand::Serve {
+            address,
+            max_jobs,
+            root,
+            allow_anonymous,
+        } => {
+            // Anyone who can connect may call the API unless every request
+            // must authenticate
+            if !address.ip().is_loopback() && !authentication.is_required() {
+                return Err(PipelineError::invalid_config(format!(
+                    "Refusing to serve on {} without [auth] required = true; only loopback addresses may be \
+                     served without authentication",
+                    address
+                ))
+                .into());
             }
-            adaptive_pipeline_domain::value_objects::ProcessingStepType::Compression => {
-                let decompression_config = StageConfiguration {
-                    algorithm: step.algorithm.clone(),
-                    operation: adaptive_pipeline_domain::entities::Operation::Reverse, /* REVERSE for legacy
-                                                                                        * restoration! */
-                    parameters: step.parameters.clone(),
-                    parallel_processing: false,
-                    chunk_size: Some(1024 * 1024), // 1MB chunks
-                };
-
-                let decompression_stage = PipelineStage::new(
-                    "decompression".to_string(),
-                    StageType::Compression, // Note: Using Compression type for decompression
-                    decompression_config,
-                    stage_index,
-                )?;
-
-                stages.push(decompression_stage);
-                info!(
-                    "Added decompression stage: {} (from step order {})",
-                    step.algorithm, step.order
+            if allow_anonymous && authentication.is_required() {
+                return Err(
+                    PipelineError::invalid_config("--allow-anonymous conflicts with [auth] required = true").into(),
                 );
-                stage_index += 1;
             }
-            adaptive_pipeline_domain::value_objects::ProcessingStepType::Checksum => {
-                // Checksum steps are used for validation only, not for data transformation
-                info!(
-                    "Skipping checksum step: {} (from step order {}) - used for validation only",
-                    step.algorithm, step.order
-                );
-                continue;
+            let listener = tokio::net::TcpListener::bind(address)
+                .await
+                .map_err(|e| PipelineError::IoError(format!("Failed to listen on {}: {}", address, e)))?;
+            let process = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .pipeline_cache(pipeline_cache.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .idempotency_repository(idempotency_repository.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .shutdown(shutdown.clone(), grace_period)
+                .build()
+                .await?;
+            let mut server = ApiServer::new(
+                pipeline_repository.clone(),
+                process,
+                metrics_service.clone(),
+                access_control,
+            )
+            .with_pipeline_cache(pipeline_cache.clone())
+            .with_stage_registry(stage_registry.clone())
+            .with_authentication(authentication)
+            .with_sessions(session_service.clone())
+            .with_shutdown(shutdown.clone())
+            .with_max_jobs(max_jobs);
+            if allow_anonymous {
+                server = server.with_anonymous_access();
             }
-            adaptive_pipeline_domain::value_objects::ProcessingStepType::PassThrough => {
-                // PassThrough steps don't modify data, skip during restoration
-                info!(
-                    "Skipping pass-through step: {} (from step order {}) - no data transformation needed",
-                    step.algorithm, step.order
-                );
-                continue;
+            if let Some(root) = root {
+                server = server.with_data_root(root);
             }
-            adaptive_pipeline_domain::value_objects::ProcessingStepType::Custom(ref step_name) => {
-                // Only create stages for transformative custom steps, skip checksum steps
-                if step_name.contains("checksum") {
-                    info!(
-                        "Skipping checksum step: {} (from step order {}) - used for validation only",
-                        step.algorithm, step.order
-                    );
-                    continue;
+            if let Some(proxy) = &auth_settings.client_cert_proxy {
+                if proxy.trusted_proxies.is_empty() {
+                    return Err(PipelineError::invalid_config("[auth.client_cert_proxy] needs trusted_proxies").into());
                 }
-
-                // Handle transformative custom steps (compression, encryption implemented as
-                // custom)
-                let stage_type = if step_name == "compression" {
-                    StageType::Compression
-                } else if step_name == "encryption" {
-                    StageType::Encryption
-                } else {
-                    StageType::PassThrough
-                };
-
-                let custom_config = StageConfiguration {
-                    algorithm: step.algorithm.clone(),
-                    operation: adaptive_pipeline_domain::entities::Operation::Reverse, /* REVERSE for legacy
-                                                                                        * restoration! */
-                    parameters: step.parameters.clone(),
-                    parallel_processing: false,
-                    chunk_size: Some(1024 * 1024), // 1MB chunks
-                };
-
-                let stage_name = if step_name == "compression" {
-                    "decompression".to_string()
-                } else if step_name == "encryption" {
-                    "decryption".to_string()
-                } else {
-                    format!("reverse_{}", step_name)
-                };
-
-                let custom_stage = PipelineStage::new(stage_name.clone(), stage_type, custom_config, stage_index)?;
-
-                stages.push(custom_stage);
-                info!(
-                    "Added {} stage: {} (from step order {})",
-                    stage_name, step.algorithm, step.order
-                );
-                stage_index += 1;
+                server =
+                    server.with_client_cert_proxy(ClientCertProxy::new(&proxy.header, proxy.trusted_proxies.clone()));
             }
+            server.serve(listener).await?;
         }
-    }
-
-    // Stage 3: Integrity verification (always present)
-    let verification_config = StageConfiguration {
-        algorithm: "sha256".to_string(),
-        operation: adaptive_pipeline_domain::entities::Operation::Reverse, // REVERSE for legacy restoration!
-        parameters: HashMap::new(),
-        parallel_processing: false,
-        chunk_size: Some(1024 * 1024), // 1MB chunks
-    };
-
-    let verification_stage = PipelineStage::new(
-        "verification".to_string(),
-        StageType::Checksum, // Using Checksum type for verification
-        verification_config,
-        stage_index,
-    )?;
-
-    stages.push(verification_stage);
-    info!("Added verification stage: sha256");
-
-    // Validate that we have at least one stage
-    if stages.is_empty() {
-        return Err(anyhow::anyhow!("No restoration stages could be created from metadata"));
-    }
-
-    // Create ephemeral pipeline with special naming convention
-    let pipeline_name = format!("__restore__{}", metadata.pipeline_id);
-
-    let pipeline = Pipeline::new(pipeline_name, stages)?;
-
-    info!(
-        "Created ephemeral restoration pipeline with {} stages",
-        pipeline.stages().len()
-    );
-
-    Ok(pipeline)
-}
-
-/// Result of streaming restoration with validation
-#[derive(Debug, Clone)]
-struct RestorationResult {
-    checksum_verified: bool,
-    calculated_checksum: String,
-    expected_checksum: String,
-    bytes_processed: u64,
-    chunks_processed: u32,
-    processing_duration: std::time::Duration,
-}
-
-/// Performs streaming restoration with automatic validation
-///
-/// This function implements the core restoration algorithm using:
-/// - Streaming I/O for memory efficiency
-/// - Incremental checksum calculation
-/// - Proper error handling and recovery
-/// - Concurrent processing where applicable
-///
-/// # Architecture
-/// - Hexagonal Architecture: Adapts between file I/O and domain logic
-/// - Error Handling: Comprehensive error propagation with context
-/// - Performance: Streaming processing for large files
-/// - Validation: Automatic integrity verification
-async fn stream_restore_with_validation(
-    input_path: &Path,
-    output_path: &Path,
-    restoration_pipeline: &Pipeline,
-    metadata: &FileHeader,
-    _footer_size: usize,
-    progress_indicator: &ProgressIndicatorService,
-) -> Result<RestorationResult> {
-    use tokio::fs::File;
-    use tokio::io::AsyncWriteExt;
-
-    info!("Starting streaming restoration with validation");
-    let start_time = Instant::now();
-
-    // Initialize streaming validator and file handles
-    let mut hasher = Sha256::new();
-    let mut bytes_processed = 0u64;
-    let mut chunks_processed = 0u32;
-
-    // Create binary format reader for proper .adapipe chunk parsing
-    let binary_format_service = AdapipeFormat::new();
-    let mut adapipe_reader = binary_format_service
-        .create_reader(input_path)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create .adapipe reader: {}", e))?;
-
-    // Create output file for writing restored data
-    let mut output_file = File::create(output_path)
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
-
-    // Create domain services for restoration pipeline
-    let compression_service = Arc::new(MultiAlgoCompression::new());
-    let encryption_service = Arc::new(MultiAlgoEncryption::new());
-
-    // Build stage service registry for validation
-    let mut stage_services: HashMap<String, Arc<dyn adaptive_pipeline_domain::services::StageService>> = HashMap::new();
-    stage_services.insert(
-        "brotli".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "gzip".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "zstd".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "lz4".to_string(),
-        compression_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "aes256gcm".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "aes128gcm".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "chacha20poly1305".to_string(),
-        encryption_service.clone() as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "base64".to_string(),
-        Arc::new(Base64EncodingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "pii_masking".to_string(),
-        Arc::new(PiiMaskingService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "tee".to_string(),
-        Arc::new(TeeService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "passthrough".to_string(),
-        Arc::new(PassThroughService::new()) as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-    stage_services.insert(
-        "debug".to_string(),
-        Arc::new(DebugService::new(Arc::new(MetricsService::new()?)))
-            as Arc<dyn adaptive_pipeline_domain::services::StageService>,
-    );
-
-    let stage_executor = Arc::new(BasicStageExecutor::new(stage_services));
-
-    // Create security context for restoration
-    let security_context = SecurityContext::new(
-        None,
-        adaptive_pipeline_domain::entities::security_context::SecurityLevel::Internal,
-    );
 
-    // Create processing context for restoration
-    let mut processing_context = ProcessingContext::new(
-        metadata.original_size,
-        security_context,
-    );
-
-    info!(
-        "Streaming restoration through {} stages",
-        restoration_pipeline.stages().len()
-    );
-
-    // Process chunks through the restoration pipeline using proper .adapipe format
-    // parsing
-    let mut chunk_sequence = 0u32;
-
-    loop {
-        // Read next chunk from .adapipe file using proper format parsing
-        let chunk_format = match adapipe_reader
-            .read_next_chunk()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to read chunk: {}", e))?
-        {
-            Some(chunk) => chunk,
-            None => {
-                break;
-            } // No more chunks
-        };
-
-        // Combine nonce and payload data as expected by decryption service
-        // The encryption service expects: [nonce (12 bytes)] + [encrypted_data]
-        let mut chunk_data = chunk_format.nonce.to_vec();
-        chunk_data.extend_from_slice(&chunk_format.payload);
-        let file_chunk = FileChunk::new(
-            chunk_sequence as u64,
-            bytes_processed,
-            chunk_data,
-            false, // is_final - we'll determine this later
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create file chunk: {}", e))?;
-
-        // Process chunk through restoration pipeline stages
-        let mut current_chunk = file_chunk;
-        for stage in restoration_pipeline.stages() {
-            debug!("Processing chunk {} through stage: {}", chunk_sequence, stage.name());
-
-            current_chunk = stage_executor
-                .execute(stage, current_chunk, &mut processing_context)
-                .await
-                .map_err(|e| anyhow::anyhow!("Stage '{}' failed: {}", stage.name(), e))?;
+        adaptive_pipeline_bootstrap::ValidatedCommand::Daemon { max_jobs } => {
+            let process = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .pipeline_cache(pipeline_cache.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .idempotency_repository(idempotency_repository.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .build()
+                .await?;
+            JobDaemon::new(job_repository.clone(), process, access_control)
+                .with_shutdown(shutdown.clone(), grace_period)
+                .with_max_jobs(max_jobs)
+                .run()
+                .await?;
         }
 
-        // Write restored chunk to output file
-        let restored_data = current_chunk.data();
-        output_file
-            .write_all(restored_data)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to write restored data: {}", e))?;
-
-        // Update incremental checksum with restored data
-        hasher.update(restored_data);
-
-        // Update progress counters
-        bytes_processed += restored_data.len() as u64;
-        chunks_processed += 1;
-        chunk_sequence += 1;
-
-        // Update progress indicator for real-time feedback
-        progress_indicator.update_progress(chunks_processed as u64).await;
-
-        // Additional debug logging for large files
-        if chunks_processed.is_multiple_of(100) {
-            let progress_mb = (bytes_processed as f64) / (1024.0 * 1024.0);
-            let expected_mb = (metadata.original_size as f64) / (1024.0 * 1024.0);
-            debug!("Restoration progress: {:.1} MB / {:.1} MB", progress_mb, expected_mb);
+        adaptive_pipeline_bootstrap::ValidatedCommand::Submit {
+            input,
+            pipeline,
+            output,
+            workers,
+        } => {
+            let use_case =
+                ManageJobsUseCase::new(job_repository.clone(), pipeline_repository.clone(), namespace.clone());
+            use_case
+                .submit(
+                    &pipeline,
+                    input,
+                    output,
+                    workers.map(|w| w.count()),
+                    access_control.principal(),
+                )
+                .await?;
         }
-    }
-
-    // Ensure all data is written to disk
-    output_file
-        .flush()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to flush output file: {}", e))?;
-
-    // Calculate final checksum
-    let calculated_hash = hasher.finalize();
-    let calculated_checksum = format!("{:x}", calculated_hash);
-
-    // Verify checksum against expected
-    let checksum_verified = calculated_checksum == metadata.original_checksum;
-
-    let processing_duration = start_time.elapsed();
-
-    if checksum_verified {
-        info!(
-            "Streaming restoration completed successfully in {:?}",
-            processing_duration
-        );
-        let throughput_mb_s = (bytes_processed as f64) / (1024.0 * 1024.0) / processing_duration.as_secs_f64();
-        progress_indicator
-            .show_completion(bytes_processed, throughput_mb_s, processing_duration)
-            .await;
-    } else {
-        warn!(
-            "Checksum verification failed: expected {}, got {}",
-            metadata.original_checksum, calculated_checksum
-        );
-        progress_indicator
-            .show_error_summary(&format!(
-                "Checksum verification failed: expected {}, got {}",
-                metadata.original_checksum, calculated_checksum
-            ))
-            .await;
-    }
 
-    Ok(RestorationResult {
-        checksum_verified,
-        calculated_checksum,
-        expected_checksum: metadata.original_checksum.clone(),
-        bytes_processed,
-        chunks_processed,
-        processing_duration,
-    })
-}
-
-#[cfg(test)]
-mod restore_tests {
-    use super::*;
-    use tokio::test;
-
-    /// Test helper to create a mock FileHeader for testing
-    fn create_test_file_header() -> FileHeader {
-        FileHeader::new("test_file.txt".to_string(), 1024, "abc123def456".to_string())
-            .add_compression_step("brotli", 6)
-            .add_encryption_step("aes256gcm", "argon2", 32, 12)
-            .with_chunk_info(1024, 1)
-            .with_pipeline_id("test-pipeline-123".to_string())
-            .with_output_checksum("output123def456".to_string())
-    }
-
-    #[tokio::test]
-    async fn test_create_restoration_pipeline_with_compression_and_encryption() {
-        let header = create_test_file_header();
-
-        let result = create_restoration_pipeline(&header).await;
-        assert!(
-            result.is_ok(),
-            "Failed to create restoration pipeline: {:?}",
-            result.err()
-        );
-
-        let pipeline = result.unwrap();
-        assert_eq!(
-            pipeline.stages().len(),
-            5,
-            "Expected 5 stages: input_checksum + decryption + decompression + verification + output_checksum"
-        );
-
-        // Verify stage order: input_checksum -> decryption -> decompression ->
-        // verification -> output_checksum
-        let stages = pipeline.stages();
-        assert_eq!(stages[0].name(), "input_checksum");
-        assert_eq!(stages[1].name(), "decryption");
-        assert_eq!(stages[2].name(), "decompression");
-        assert_eq!(stages[3].name(), "verification");
-        assert_eq!(stages[4].name(), "output_checksum");
-
-        // Verify stage types
-        assert_eq!(stages[0].stage_type(), &StageType::Checksum);
-        assert_eq!(stages[1].stage_type(), &StageType::Encryption); // Decryption uses Encryption type
-        assert_eq!(stages[2].stage_type(), &StageType::Compression); // Decompression uses Compression type
-        assert_eq!(stages[3].stage_type(), &StageType::Checksum);
-        assert_eq!(stages[4].stage_type(), &StageType::Checksum);
-    }
-
-    #[tokio::test]
-    async fn test_create_restoration_pipeline_compression_only() {
-        let header =
-            FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string()).add_compression_step("brotli", 6);
-
-        let result = create_restoration_pipeline(&header).await;
-        assert!(result.is_ok());
+        adaptive_pipeline_bootstrap::ValidatedCommand::JobsList { json } => {
+            let use_case =
+                ManageJobsUseCase::new(job_repository.clone(), pipeline_repository.clone(), namespace.clone());
+            use_case.list(json).await?;
+        }
 
-        let pipeline = result.unwrap();
-        assert_eq!(
-            pipeline.stages().len(),
-            4,
-            "Expected 4 stages: input_checksum + decompression + verification + output_checksum"
-        );
+        adaptive_pipeline_bootstrap::ValidatedCommand::JobsStatus { id, json } => {
+            let use_case =
+                ManageJobsUseCase::new(job_repository.clone(), pipeline_repository.clone(), namespace.clone());
+            use_case.status(&id, json).await?;
+        }
 
-        let stages = pipeline.stages();
-        assert_eq!(stages[0].name(), "input_checksum");
-        assert_eq!(stages[1].name(), "decompression");
-        assert_eq!(stages[2].name(), "verification");
-        assert_eq!(stages[3].name(), "output_checksum");
-    }
+        adaptive_pipeline_bootstrap::ValidatedCommand::JobsCancel { id } => {
+            let use_case =
+                ManageJobsUseCase::new(job_repository.clone(), pipeline_repository.clone(), namespace.clone());
+            use_case.cancel(&id).await?;
+        }
 
-    #[tokio::test]
-    async fn test_create_restoration_pipeline_no_processing() {
-        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        adaptive_pipeline_bootstrap::ValidatedCommand::ExportTar { inputs, output } => {
+            let output = output.unwrap_or_else(|| ExportTarUseCase::default_output(&inputs[0]));
+            let use_case = ExportTarUseCase::new(metrics_service.clone())
+                .with_stage_registry(stage_registry.clone())
+                .with_shutdown(shutdown.clone())
+                .with_security_context(security_context.clone());
+            use_case.execute(inputs, output).await?;
+        }
 
-        let result = create_restoration_pipeline(&header).await;
-        assert!(result.is_ok());
+        adaptive_pipeline_bootstrap::ValidatedCommand::ImportTar {
+            input,
+            output_dir,
+            pipeline,
+            chunk_size,
+            workers,
+            overwrite_policy,
+            priority,
+        } => {
+            // Input and output are filled in per tar entry
+            let config = ProcessFileConfig {
+                input: std::path::PathBuf::new(),
+                output: std::path::PathBuf::new(),
+                pipeline,
+                chunk_size,
+                workers: workers.map(|w| w.count()),
+                channel_depth: cli.channel_depth,
+                inflight_window: cli.inflight_window,
+                write_manifest: false,
+                signing_key: None,
+                idempotency_key: None,
+                stage_timeout: None,
+                chunk_timeout: None,
+                max_worker_restarts: 0,
+                direct_io: false,
+                checksum_offload: false,
+                overwrite_policy,
+                output_mode: output_settings.file_mode,
+                priority,
+                password: None,
+                passphrase_policy: PassphrasePolicy::default(),
+            };
+            let process_file = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .pipeline_cache(pipeline_cache.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .idempotency_repository(idempotency_repository.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .shutdown(shutdown.clone(), grace_period)
+                .security_context(security_context.clone())
+                .build()
+                .await?;
+            let use_case = ImportTarUseCase::new(process_file);
+            use_case.execute(input, output_dir, config).await?;
+        }
 
-        let pipeline = result.unwrap();
-        assert_eq!(
-            pipeline.stages().len(),
-            3,
-            "Expected 3 stages: input_checksum + verification + output_checksum"
-        );
+        adaptive_pipeline_bootstrap::ValidatedCommand::ProcessBatch {
+            inputs,
+            output_dir,
+            pipeline,
+            retry_failed,
+            failures,
+            chunk_size,
+            workers,
+            overwrite_policy,
+            priority,
+        } => {
+            // Input and output are filled in per file; a retry takes the
+            // pipeline from its manifest
+            let config = ProcessFileConfig {
+                input: std::path::PathBuf::new(),
+                output: std::path::PathBuf::new(),
+                pipeline: pipeline.unwrap_or_default(),
+                chunk_size,
+                workers: workers.map(|w| w.count()),
+                channel_depth: cli.channel_depth,
+                inflight_window: cli.inflight_window,
+                write_manifest: false,
+                signing_key: None,
+                idempotency_key: None,
+                stage_timeout: None,
+                chunk_timeout: None,
+                max_worker_restarts: 0,
+                direct_io: false,
+                checksum_offload: false,
+                overwrite_policy,
+                output_mode: output_settings.file_mode,
+                priority,
+                password: None,
+                passphrase_policy: PassphrasePolicy::default(),
+            };
+            let process_file = ProcessFileUseCase::builder()
+                .metrics_service(metrics_service.clone())
+                .observability_service(observability_service.clone())
+                .stage_registry(stage_registry.clone())
+                .pipeline_repository(pipeline_repository.clone())
+                .pipeline_cache(pipeline_cache.clone())
+                .usage_repository(usage_repository.clone())
+                .quota_service(quota_service.clone())
+                .idempotency_repository(idempotency_repository.clone())
+                .chunk_size_history(chunk_size_history.clone())
+                .shutdown(shutdown.clone(), grace_period)
+                .security_context(security_context.clone())
+                .build()
+                .await?;
+            let use_case = ProcessBatchUseCase::new(process_file).with_shutdown(shutdown.clone());
+            match (retry_failed, output_dir) {
+                (Some(manifest), _) => use_case.retry(manifest, failures, config).await?,
+                (None, Some(output_dir)) => use_case.execute(inputs, output_dir, failures, config).await?,
+                (None, None) => return Err(anyhow::anyhow!("process-batch needs --output-dir or --retry-failed")),
+            };
+        }
 
-        let stages = pipeline.stages();
+        adaptive_pipeline_bootstrap::ValidatedCommand::Compare {
+            original,
+            adapipe,
+            detailed,
+        } => {
+            let use_case = CompareFilesUseCase::new();
+            use_case.execute(original, adapipe, detailed).await?;
+        }
 
-        // Verify automatic checksum stages
-        assert_eq!(stages[0].name(), "input_checksum");
-        assert_eq!(stages[0].stage_type(), &StageType::Checksum);
+        adaptive_pipeline_bootstrap::ValidatedCommand::CompareArchives {
+            adapipe,
+            other,
+            detailed,
+        } => {
+            let use_case = CompareFilesUseCase::new();
+            use_case.execute_archives(adapipe, other, detailed).await?;
+        }
 
-        // Verify user-defined verification stage
-        assert_eq!(stages[1].name(), "verification");
-        assert_eq!(stages[1].stage_type(), &StageType::Checksum);
+        adaptive_pipeline_bootstrap::ValidatedCommand::Cleanup { dry_run } => {
+            let use_case = CleanupTempUseCase::new(temp_root.base().to_path_buf());
+            use_case.execute(dry_run).await?;
+        }
 
-        // Verify automatic output checksum stage
-        assert_eq!(stages[2].name(), "output_checksum");
-        assert_eq!(stages[2].stage_type(), &StageType::Checksum);
-    }
+        adaptive_pipeline_bootstrap::ValidatedCommand::RoleList => {
+            let use_case = ManageRolesUseCase::new(role_repository.clone(), namespace.clone());
+            use_case.list().await?;
+        }
 
-    #[tokio::test]
-    async fn test_restoration_result_creation() {
-        let result = RestorationResult {
-            checksum_verified: true,
-            calculated_checksum: "abc123".to_string(),
-            expected_checksum: "abc123".to_string(),
-            bytes_processed: 1024,
-            chunks_processed: 1,
-            processing_duration: std::time::Duration::from_millis(100),
-        };
+        adaptive_pipeline_bootstrap::ValidatedCommand::RoleInit => {
+            let use_case = ManageRolesUseCase::new(role_repository.clone(), namespace.clone());
+            use_case.init(access_control.principal().to_string()).await?;
+        }
 
-        assert!(result.checksum_verified);
-        assert_eq!(result.calculated_checksum, result.expected_checksum);
-        assert_eq!(result.bytes_processed, 1024);
-        assert_eq!(result.chunks_processed, 1);
-        assert!(result.processing_duration.as_millis() >= 100);
-    }
+        adaptive_pipeline_bootstrap::ValidatedCommand::RoleAssign { principal, role } => {
+            let use_case = ManageRolesUseCase::new(role_repository.clone(), namespace.clone());
+            let assigned_by = Some(access_control.principal().to_string());
+            use_case.assign(principal, role.parse()?, assigned_by).await?;
+        }
 
-    #[tokio::test]
-    async fn test_restoration_pipeline_naming() {
-        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
-            .with_pipeline_id("original-pipeline-123".to_string());
+        adaptive_pipeline_bootstrap::ValidatedCommand::RoleRevoke { principal } => {
+            let use_case = ManageRolesUseCase::new(role_repository.clone(), namespace.clone());
+            use_case.revoke(principal).await?;
+        }
 
-        let pipeline = create_restoration_pipeline(&header).await.unwrap();
+        adaptive_pipeline_bootstrap::ValidatedCommand::SessionIssue { user, ttl_secs } => {
+            let use_case = ManageSessionsUseCase::new(session_service.clone(), namespace.clone());
+            let user_id = UserId::parse(user.as_deref().unwrap_or(access_control.principal()))?;
+            let ttl = ttl_secs.map(|secs| {
+                i64::try_from(secs)
+                    .ok()
+                    .and_then(chrono::Duration::try_seconds)
+                    .unwrap_or(chrono::Duration::MAX)
+            });
+            use_case.issue(user_id, ttl, access_control.principal()).await?;
+        }
 
-        // Verify ephemeral pipeline naming convention
-        assert!(pipeline.name().starts_with("__restore__"));
-        assert!(pipeline.name().contains("original-pipeline-123"));
-    }
+        adaptive_pipeline_bootstrap::ValidatedCommand::SessionList => {
+            let use_case = ManageSessionsUseCase::new(session_service.clone(), namespace.clone());
+            use_case.list().await?;
+        }
 
-    #[tokio::test]
-    async fn test_file_chunk_creation_for_restoration() {
-        let test_data = vec![1, 2, 3, 4, 5];
-        let chunk = FileChunk::new(
-            0, // sequence_number
-            0, // offset
-            test_data.clone(),
-            false, // is_final
-        );
+        adaptive_pipeline_bootstrap::ValidatedCommand::SessionRevoke { id } => {
+            let use_case = ManageSessionsUseCase::new(session_service.clone(), namespace.clone());
+            use_case
+                .revoke(&SessionId::from_string(&id)?, access_control.principal())
+                .await?;
+        }
 
-        assert!(chunk.is_ok(), "Failed to create FileChunk: {:?}", chunk.err());
+        adaptive_pipeline_bootstrap::ValidatedCommand::DbBackup { path } => {
+            ManageDatabaseUseCase::new(&sqlite_path).backup(&path).await?;
+        }
 
-        let chunk = chunk.unwrap();
-        assert_eq!(chunk.sequence_number(), 0);
-        assert_eq!(chunk.offset(), 0);
-        assert_eq!(chunk.data(), &test_data);
-        assert!(!chunk.is_final());
+        adaptive_pipeline_bootstrap::ValidatedCommand::DbRestore { path, force } => {
+            ManageDatabaseUseCase::new(&sqlite_path).restore(&path, force).await?;
+        }
     }
 
-    #[tokio::test]
-    async fn test_restoration_result_checksum_mismatch() {
-        let result = RestorationResult {
-            checksum_verified: false,
-            calculated_checksum: "abc123".to_string(),
-            expected_checksum: "def456".to_string(),
-            bytes_processed: 1024,
-            chunks_processed: 1,
-            processing_duration: std::time::Duration::from_millis(100),
-        };
-
-        assert!(!result.checksum_verified);
-        assert_ne!(result.calculated_checksum, result.expected_checksum);
-    }
+    Ok(())
 }
-
-// End-to-end tests have been moved to tests/e2e_restore_pipeline_test.rs
-// This keeps main.rs focused on application logic rather than test code
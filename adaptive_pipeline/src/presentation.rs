@@ -211,4 +211,9 @@
 //! - Pipeline configurations
 //! - Environment-specific settings
 
+// The module docs sketch how API endpoints are tested
+#![allow(clippy::test_attr_in_doctest)]
+
 pub mod adapters;
+pub mod daemon;
+pub mod http;
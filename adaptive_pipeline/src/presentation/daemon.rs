@@ -0,0 +1,566 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Job Daemon
+//!
+//! `daemon` runs the jobs queued with `submit`. The queue lives in the
+//! pipeline database, so submitting needs no connection to the daemon: the
+//! daemon polls the queue, claims the oldest queued jobs of its namespace and
+//! processes them with the same use case as `process`.
+//!
+//! ## Concurrency
+//!
+//! At most `max_jobs` jobs run at once, by default as many as the resource
+//! manager has CPU tokens. Running jobs share those tokens and the I/O
+//! tokens chunk by chunk, so extra jobs add overlap rather than
+//! oversubscribing the machine.
+//!
+//! ## Authorization
+//!
+//! `submit` requires permission to process files. Each job runs as the
+//! principal that submitted it, who is authorized again when the job starts,
+//! so revoking the submitter's role stops the jobs not started yet. Starting
+//! the daemon needs the same permission, but its role is never lent to the
+//! jobs it runs.
+//!
+//! ## Cancellation and Shutdown
+//!
+//! A job cancelled with `jobs cancel` is stopped at its next chunk; its
+//! state stays `cancelled`. When the daemon itself is stopped, running jobs
+//! stop the same way but go back to `queued`, and run again from the start
+//! when the daemon is next started. Jobs left `running` by a daemon that
+//! did not stop cleanly are requeued on start.
+//!
+//! So that no live daemon's jobs are requeued, one daemon runs per namespace
+//! and database: a daemon holds a lease on its namespace in the database,
+//! renewed while it runs and released when it stops, and a second daemon
+//! refuses to start until the lease is released or expires.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::Notify;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn};
+use ulid::Ulid;
+
+use crate::application::services::access_control::AccessControlService;
+use crate::application::use_cases::{ProcessFileConfig, ProcessFileUseCase};
+use crate::infrastructure::runtime::{catch_panic, try_resource_manager};
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_domain::entities::{JobState, QueuedJob};
+use adaptive_pipeline_domain::repositories::JobRepository;
+use adaptive_pipeline_domain::services::{ShutdownSignal, DEFAULT_GRACE_PERIOD};
+use adaptive_pipeline_domain::value_objects::{JobPriority, OverwritePolicy, ProtectedOperation};
+use adaptive_pipeline_domain::PipelineError;
+
+/// How often the queue is checked for new jobs and running jobs for
+/// cancellation
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a daemon's lease on its namespace lasts without renewal; it is
+/// renewed after a third of that
+pub const DAEMON_LEASE_TTL: Duration = Duration::from_secs(30);
+
+/// Runs queued jobs until shutdown is requested
+pub struct JobDaemon {
+    job_repository: Arc<dyn JobRepository>,
+    process: ProcessFileUseCase,
+    access_control: AccessControlService,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    grace_period: Duration,
+    max_jobs: Option<usize>,
+    poll_interval: Duration,
+    /// Identifies this daemon's lease on its namespace
+    lease_holder: String,
+}
+
+/// Why a job should stop: its cancellation, or the daemon's shutdown
+struct JobStop {
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl JobStop {
+    fn new(shutdown: Option<Arc<dyn ShutdownSignal>>) -> Self {
+        Self {
+            shutdown,
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    async fn cancelled(&self) {
+        // Register before checking, so a cancel in between still wakes us
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+#[async_trait]
+impl ShutdownSignal for JobStop {
+    fn is_requested(&self) -> bool {
+        self.is_cancelled() || self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested())
+    }
+
+    async fn requested(&self) {
+        match &self.shutdown {
+            Some(shutdown) => tokio::select! {
+                _ = shutdown.requested() => {}
+                _ = self.cancelled() => {}
+            },
+            None => self.cancelled().await,
+        }
+    }
+}
+
+impl JobDaemon {
+    /// Creates a daemon for the namespace of `access_control`, running jobs
+    /// from `job_repository` with copies of `process`
+    pub fn new(
+        job_repository: Arc<dyn JobRepository>,
+        process: ProcessFileUseCase,
+        access_control: AccessControlService,
+    ) -> Self {
+        Self {
+            job_repository,
+            process,
+            access_control,
+            shutdown: None,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            max_jobs: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            lease_holder: format!("pid {} ({})", std::process::id(), Ulid::new()),
+        }
+    }
+
+    /// Stops once `shutdown` is requested, giving running jobs
+    /// `grace_period` to finish their chunks in flight
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>, grace_period: Duration) -> Self {
+        self.shutdown = Some(shutdown);
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Runs at most `max_jobs` jobs at a time instead of one per CPU token
+    pub fn with_max_jobs(mut self, max_jobs: Option<usize>) -> Self {
+        self.max_jobs = max_jobs.map(|max_jobs| max_jobs.max(1));
+        self
+    }
+
+    /// Checks the queue every `poll_interval`
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Runs queued jobs until shutdown is requested, then waits for the
+    /// running ones to stop
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResourceExhausted` if another daemon holds the namespace's
+    /// lease, or lost it to another daemon while running, and
+    /// `DatabaseError` if jobs left running by an earlier daemon cannot be
+    /// requeued.
+    pub async fn run(self) -> Result<(), PipelineError> {
+        let namespace = self.access_control.namespace().clone();
+        let max_jobs = self
+            .max_jobs
+            .or_else(|| try_resource_manager().map(|manager| manager.cpu_tokens_total()))
+            .unwrap_or(1)
+            .max(1);
+        if !self.renew_lease().await? {
+            return Err(PipelineError::resource_exhausted(format!(
+                "Another job daemon is running the jobs of namespace '{}' in this database; run one daemon per \
+                 namespace and database",
+                namespace
+            )));
+        }
+        let mut renewed_at = tokio::time::Instant::now();
+        let mut lease_lost = false;
+
+        let requeued = match self.requeue_interrupted().await {
+            Ok(requeued) => requeued,
+            Err(e) => {
+                self.release_lease().await;
+                return Err(e);
+            }
+        };
+        if requeued > 0 {
+            warn!("Requeued {} job(s) left running by an earlier daemon", requeued);
+        }
+        info!(namespace = %namespace, max_jobs, "Job daemon started");
+        println!(
+            "🛠️  Running queued jobs in namespace '{}', at most {} at a time",
+            namespace, max_jobs
+        );
+
+        let daemon = Arc::new(self);
+        let mut running = JoinSet::new();
+        loop {
+            if renewed_at.elapsed() >= DAEMON_LEASE_TTL / 3 {
+                match daemon.renew_lease().await {
+                    Ok(true) => renewed_at = tokio::time::Instant::now(),
+                    Ok(false) => {
+                        error!(
+                            "Another daemon took over namespace '{}'; no more jobs are claimed",
+                            namespace
+                        );
+                        lease_lost = true;
+                        break;
+                    }
+                    // The lease is still ours until it expires; retry next poll
+                    Err(e) => warn!("Could not renew the daemon lease: {}", e),
+                }
+            }
+            while running.try_join_next().is_some() {}
+            while running.len() < max_jobs && !daemon.is_shutting_down() {
+                match daemon.job_repository.claim_next(&namespace, Utc::now()).await {
+                    Ok(Some(job)) => {
+                        let daemon = daemon.clone();
+                        running.spawn(async move { daemon.run_job(job).await });
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Could not claim a queued job: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            let shutdown = daemon.shutdown.clone();
+            tokio::select! {
+                _ = tokio::time::sleep(daemon.poll_interval) => {}
+                // A finished job frees a slot for the next one at once
+                Some(_) = running.join_next(), if !running.is_empty() => {}
+                _ = async { shutdown.as_ref().unwrap().requested().await }, if shutdown.is_some() => break,
+            }
+        }
+
+        if !running.is_empty() {
+            info!("Waiting for {} running job(s) to stop", running.len());
+        }
+        while running.join_next().await.is_some() {}
+        if lease_lost {
+            return Err(PipelineError::resource_exhausted(format!(
+                "Job daemon lost its lease on namespace '{}' to another daemon",
+                namespace
+            )));
+        }
+        daemon.release_lease().await;
+        println!("🛠️  Job daemon stopped");
+        Ok(())
+    }
+
+    /// Takes or renews this daemon's lease on its namespace, returning
+    /// whether it holds it
+    async fn renew_lease(&self) -> Result<bool, PipelineError> {
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::seconds(DAEMON_LEASE_TTL.as_secs() as i64);
+        self.job_repository
+            .acquire_lease(self.access_control.namespace(), &self.lease_holder, now, expires_at)
+            .await
+    }
+
+    /// Releases the lease so another daemon can start at once; if this
+    /// fails the lease still expires
+    async fn release_lease(&self) {
+        if let Err(e) = self
+            .job_repository
+            .release_lease(self.access_control.namespace(), &self.lease_holder)
+            .await
+        {
+            warn!("Could not release the daemon lease: {}", e);
+        }
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.shutdown.as_ref().is_some_and(|shutdown| shutdown.is_requested())
+    }
+
+    /// Puts jobs still marked running back in the queue
+    async fn requeue_interrupted(&self) -> Result<usize, PipelineError> {
+        let mut requeued = 0;
+        for mut job in self.job_repository.list(self.access_control.namespace()).await? {
+            if job.state() == JobState::Running {
+                job.requeue()?;
+                if self.job_repository.update(&job, JobState::Running).await? {
+                    requeued += 1;
+                }
+            }
+        }
+        Ok(requeued)
+    }
+
+    /// Processes `job`, which has been claimed, and records the outcome
+    async fn run_job(&self, mut job: QueuedJob) {
+        let id = job.id();
+        info!(job = %id, pipeline = job.pipeline(), input = %job.input().display(), "Job started");
+        let submitter = self.access_control.for_principal(job.submitted_by());
+        let context = match submitter.authorize(ProtectedOperation::ProcessFile).await {
+            Ok(context) => context,
+            Err(e) => {
+                warn!("Job {} not authorized: {}", id, e);
+                self.finish(job, |job| job.fail(Utc::now(), e.to_string())).await;
+                return;
+            }
+        };
+
+        let stop = Arc::new(JobStop::new(self.shutdown.clone()));
+        let use_case = self
+            .process
+            .clone()
+            .with_security_context(context)
+            .with_shutdown(stop.clone(), self.grace_period);
+        let config = ProcessFileConfig {
+            input: job.input().clone(),
+            output: job.output().clone(),
+            pipeline: job.pipeline().to_string(),
+            chunk_size: None,
+            workers: job.workers(),
+            channel_depth: None,
+            inflight_window: None,
+            write_manifest: false,
+            signing_key: None,
+            idempotency_key: None,
+            stage_timeout: None,
+            chunk_timeout: None,
+            max_worker_restarts: 0,
+            direct_io: false,
+            checksum_offload: false,
+            overwrite_policy: OverwritePolicy::default(),
+            output_mode: None,
+            priority: JobPriority::default(),
+            password: None,
+            passphrase_policy: PassphrasePolicy::default(),
+        };
+
+        // A panicking job fails alone rather than staying running forever
+        let execution = catch_panic(use_case.execute(config));
+        tokio::pin!(execution);
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut execution => break outcome,
+                _ = tokio::time::sleep(self.poll_interval), if !stop.is_cancelled() => {
+                    match self.job_repository.find_by_id(&id).await {
+                        Ok(Some(stored)) if stored.state() == JobState::Cancelled => {
+                            info!("Job {} cancelled; stopping it", id);
+                            stop.cancel();
+                        }
+                        Ok(_) => {}
+                        Err(e) => debug!("Could not check job {} for cancellation: {}", id, e),
+                    }
+                }
+            }
+        };
+
+        match outcome {
+            Ok(Ok(result)) => {
+                info!("Job {} completed", id);
+                let (input_bytes, output_bytes) = (result.input_size_bytes, result.output_size_bytes);
+                self.finish(job, |job| job.complete(Utc::now(), input_bytes, output_bytes))
+                    .await;
+            }
+            // Cancelled jobs are already recorded as such
+            Ok(Err(_)) if stop.is_cancelled() => {}
+            Ok(Err(e)) if matches!(e.downcast_ref::<PipelineError>(), Some(PipelineError::Cancelled(_))) => {
+                info!("Job {} stopped by shutdown; requeued", id);
+                self.finish(job, QueuedJob::requeue).await;
+            }
+            Ok(Err(e)) => {
+                warn!("Job {} failed: {}", id, e);
+                self.finish(job, |job| job.fail(Utc::now(), e.to_string())).await;
+            }
+            Err(message) => {
+                error!("Job {} panicked: {}", id, message);
+                let error = format!("Job panicked: {}", message);
+                job.fail(Utc::now(), error).ok();
+                self.store(&job).await;
+            }
+        }
+    }
+
+    /// Applies `transition` to the running `job` and stores it, unless the
+    /// job was cancelled meanwhile
+    async fn finish(&self, mut job: QueuedJob, transition: impl FnOnce(&mut QueuedJob) -> Result<(), PipelineError>) {
+        match transition(&mut job) {
+            Ok(()) => self.store(&job).await,
+            Err(e) => error!("Job {}: {}", job.id(), e),
+        }
+    }
+
+    async fn store(&self, job: &QueuedJob) {
+        match self.job_repository.update(job, JobState::Running).await {
+            Ok(true) => {}
+            Ok(false) => info!(
+                "Job {} was cancelled before it could be marked {}",
+                job.id(),
+                job.state()
+            ),
+            Err(e) => error!("Could not record job {} as {}: {}", job.id(), job.state(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metrics::MetricsService;
+    use crate::infrastructure::repositories::sqlite_job::SqliteJobRepository;
+    use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+    use crate::infrastructure::repositories::sqlite_role::SqliteRoleRepository;
+    use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+    use adaptive_pipeline_domain::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+    use adaptive_pipeline_domain::repositories::RoleRepository;
+    use adaptive_pipeline_domain::value_objects::{Namespace, Role, RoleAssignment};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// A shutdown that can be requested from the test
+    #[derive(Default)]
+    struct TestShutdown {
+        requested: AtomicBool,
+        notify: Notify,
+    }
+
+    impl TestShutdown {
+        fn request(&self) {
+            self.requested.store(true, Ordering::SeqCst);
+            self.notify.notify_waiters();
+        }
+    }
+
+    #[async_trait]
+    impl ShutdownSignal for TestShutdown {
+        fn is_requested(&self) -> bool {
+            self.requested.load(Ordering::SeqCst)
+        }
+
+        async fn requested(&self) {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_requested() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    async fn wait_for(repository: &SqliteJobRepository, job: &QueuedJob, state: JobState) -> QueuedJob {
+        for _ in 0..500 {
+            let stored = repository.find_by_id(&job.id()).await.unwrap().unwrap();
+            if stored.state() == state {
+                return stored;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let stored = repository.find_by_id(&job.id()).await.unwrap().unwrap();
+        panic!("job {} never became {}: {:?}", job.id(), state, stored);
+    }
+
+    #[tokio::test]
+    async fn test_daemon_runs_queued_jobs_and_records_results() {
+        let _ = init_resource_manager(ResourceConfig::default());
+        let dir = TempDir::new().unwrap();
+        let db = dir.path().join("daemon.db").to_string_lossy().to_string();
+        let pipelines = Arc::new(SqlitePipelineRepository::new(&db).await.unwrap());
+        let stage = PipelineStage::new(
+            "compression".to_string(),
+            StageType::Compression,
+            StageConfiguration::new(
+                "brotli".to_string(),
+                HashMap::from([("algorithm".to_string(), "brotli".to_string())]),
+                false,
+            ),
+            0,
+        )
+        .unwrap();
+        pipelines
+            .save(&Pipeline::new("compress".to_string(), vec![stage]).unwrap())
+            .await
+            .unwrap();
+        let jobs = Arc::new(SqliteJobRepository::new(&db).await.unwrap());
+        let metrics = Arc::new(MetricsService::new().unwrap());
+        let process = ProcessFileUseCase::builder()
+            .metrics_service(metrics.clone())
+            .pipeline_repository(pipelines.clone())
+            .build()
+            .await
+            .unwrap();
+        let roles = Arc::new(SqliteRoleRepository::new(&db).await.unwrap());
+        roles
+            .assign(&RoleAssignment::new(Namespace::default(), "tester", Role::Admin, None).unwrap())
+            .await
+            .unwrap();
+        let access_control = AccessControlService::new(roles, "tester");
+        let second_daemon = JobDaemon::new(jobs.clone(), process.clone(), access_control.clone());
+
+        let input = dir.path().join("in.bin");
+        std::fs::write(&input, vec![7u8; 64 * 1024]).unwrap();
+        let ns = Namespace::default();
+        let good = QueuedJob::submit(ns.clone(), "tester", "compress", input.clone(), dir.path().join("out"));
+        let bad = QueuedJob::submit(
+            ns.clone(),
+            "tester",
+            "compress",
+            dir.path().join("missing.bin"),
+            dir.path().join("x"),
+        );
+        // Jobs run as their submitter, who has no role here
+        let foreign = QueuedJob::submit(ns.clone(), "stranger", "compress", input.clone(), dir.path().join("y"));
+        jobs.enqueue(&good).await.unwrap();
+        jobs.enqueue(&bad).await.unwrap();
+        jobs.enqueue(&foreign).await.unwrap();
+
+        let shutdown = Arc::new(TestShutdown::default());
+        let daemon = JobDaemon::new(jobs.clone(), process, access_control)
+            .with_shutdown(shutdown.clone(), Duration::from_secs(1))
+            .with_max_jobs(Some(2))
+            .with_poll_interval(Duration::from_millis(20));
+        let handle = tokio::spawn(daemon.run());
+
+        let completed = wait_for(&jobs, &good, JobState::Completed).await;
+        assert_eq!(completed.input_bytes(), Some(64 * 1024));
+        assert!(dir.path().join("out.adapipe").exists());
+        let failed = wait_for(&jobs, &bad, JobState::Failed).await;
+        assert!(failed.error().is_some());
+        let refused = wait_for(&jobs, &foreign, JobState::Failed).await;
+        assert!(refused.error().unwrap().contains("Permission denied"));
+        assert!(!dir.path().join("y.adapipe").exists());
+
+        // One daemon per namespace and database
+        let err = second_daemon.run().await.unwrap_err();
+        assert!(matches!(err, PipelineError::ResourceExhausted(_)));
+
+        shutdown.request();
+        handle.await.unwrap().unwrap();
+        assert!(
+            jobs.acquire_lease(&ns, "next", Utc::now(), Utc::now()).await.unwrap(),
+            "a stopped daemon releases its lease"
+        );
+    }
+}
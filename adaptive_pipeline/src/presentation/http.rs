@@ -0,0 +1,1174 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # HTTP API
+//!
+//! `serve` exposes the pipeline over a small JSON API, running the same use
+//! cases as the CLI. It speaks plain HTTP/1.1 through hyper, with axum
+//! routing requests to handlers; put a TLS-terminating proxy in front of it
+//! to reach it from other hosts.
+//!
+//! ## Endpoints
+//!
+//! | Method   | Path                        | Operation                          |
+//! |----------|-----------------------------|------------------------------------|
+//! | `GET`    | `/api/v1/health`            | Liveness check (no authentication) |
+//! | `GET`    | `/api/v1/pipelines`         | List pipelines                     |
+//! | `POST`   | `/api/v1/pipelines`         | Create a pipeline                  |
+//! | `DELETE` | `/api/v1/pipelines/{name}`  | Delete a pipeline                  |
+//! | `POST`   | `/api/v1/jobs`              | Submit a file for processing       |
+//! | `GET`    | `/api/v1/jobs`              | List jobs                          |
+//! | `GET`    | `/api/v1/jobs/{id}`         | Job status, result and metrics     |
+//! | `GET`    | `/api/v1/restore?archive=…` | Download the restored original     |
+//!
+//! Errors are JSON objects with an `error` message and a status matching the
+//! use case's error: 400 for invalid input, 403 when the role does not permit
+//! the operation, 404 for a missing pipeline or job, 422 for an archive that
+//! fails verification.
+//!
+//! ## Access Control
+//!
+//! Each request authenticates on its own with `Authorization: Bearer <token>`
+//! or `X-API-Key: <key>` against the `[auth]` providers and is authorized for
+//! its operation like the matching CLI command. A bearer token issued by
+//! `session issue` authenticates through the server's sessions instead: the
+//! request acts as the session's user, counts against the session's rate
+//! limit (429 once spent) and is audited under the session. Behind a TLS-terminating
+//! proxy configured in `[auth.client_cert_proxy]`, the client certificate the
+//! proxy forwards authenticates the request instead (see [`ClientCertProxy`]).
+//! A request without credentials is refused with 401, unless the server was
+//! started with anonymous access, when it acts as the principal that started
+//! the server.
+//!
+//! ## Files
+//!
+//! Job inputs and outputs and restored archives are paths on the server,
+//! resolved against the data root given to the server; paths that lead
+//! outside it, through `..` or symlinks, are refused with 403. Without a
+//! data root, jobs and restores are refused.
+//!
+//! ## Connections
+//!
+//! Each connection carries one request. Its headers must arrive within the
+//! read timeout or the connection is closed; a JSON body must then arrive
+//! within the read timeout too, or is answered with 408, and may be at most
+//! 1 MiB. At most `max_connections` connections are served at a time;
+//! further clients wait to be accepted.
+//!
+//! ## Jobs
+//!
+//! Submitted files are processed in the background, at most `max_jobs` at a
+//! time; the rest wait as `queued`. Jobs are kept in memory only. On
+//! shutdown the server stops accepting connections and waits for running
+//! jobs, which stop at their next chunk and write a checkpoint.
+
+mod jobs;
+mod proxy;
+mod response;
+
+pub use jobs::{Job, JobMetrics, JobState};
+pub use proxy::ClientCertProxy;
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::rejection::{PathRejection, QueryRejection};
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use hyper::server::conn::http1;
+use hyper_util::rt::{TokioIo, TokioTimer};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+use tracing::{debug, error, info, warn};
+
+use crate::application::services::access_control::AccessControlService;
+use crate::application::services::authentication::AuthenticationService;
+use crate::application::services::pipeline_cache::PipelineCache;
+use crate::application::services::session::SessionService;
+use crate::application::use_cases::{
+    CreatePipelineUseCase, DeletePipelineUseCase, ListPipelinesUseCase, ProcessFileConfig, ProcessFileUseCase,
+    RestoreFileUseCase,
+};
+use crate::infrastructure::metrics::MetricsService;
+use crate::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use crate::infrastructure::runtime::{catch_panic, StageRegistry};
+use adaptive_pipeline_bootstrap::cli::{PassphrasePolicy, SecureArgParser};
+use adaptive_pipeline_domain::entities::{SecurityContext, Session};
+use adaptive_pipeline_domain::services::{Credentials, ShutdownSignal};
+use adaptive_pipeline_domain::value_objects::{JobPriority, OverwritePolicy, ProtectedOperation};
+use adaptive_pipeline_domain::PipelineError;
+use jobs::JobTable;
+use response::{held_back_body, read_body, ApiError};
+
+/// Jobs processed at once unless configured otherwise
+pub const DEFAULT_MAX_JOBS: usize = 2;
+
+/// Connections served at once unless configured otherwise
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+
+/// Time a client has to send its request headers, and then its body,
+/// unless configured otherwise
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest request head (request line and headers) accepted
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Largest request body accepted; the API only takes small JSON documents
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Shared state of the request handlers
+type Api = State<Arc<ApiState>>;
+
+/// Body of `POST /api/v1/pipelines`
+#[derive(Debug, Deserialize)]
+struct CreatePipelineRequest {
+    name: String,
+    /// Stage specifications as `pipeline create --stages` takes them
+    stages: Vec<String>,
+}
+
+/// Body of `POST /api/v1/jobs`
+#[derive(Debug, Deserialize)]
+struct SubmitJobRequest {
+    pipeline: String,
+    input: String,
+    output: String,
+    #[serde(default)]
+    workers: Option<usize>,
+}
+
+/// HTTP server for the pipeline API
+pub struct ApiServer {
+    pipeline_repository: Arc<SqlitePipelineRepository>,
+    pipeline_cache: Option<Arc<PipelineCache>>,
+    process: ProcessFileUseCase,
+    metrics_service: Arc<MetricsService>,
+    stage_registry: StageRegistry,
+    access_control: AccessControlService,
+    authentication: AuthenticationService,
+    sessions: Option<Arc<SessionService>>,
+    client_cert_proxy: Option<ClientCertProxy>,
+    allow_anonymous: bool,
+    data_root: Option<PathBuf>,
+    shutdown: Option<Arc<dyn ShutdownSignal>>,
+    max_jobs: usize,
+    max_connections: usize,
+    read_timeout: Duration,
+}
+
+/// What request handlers share
+struct ApiState {
+    server: ApiServer,
+    jobs: JobTable,
+    job_slots: Semaphore,
+    job_tasks: Mutex<JoinSet<()>>,
+}
+
+impl ApiServer {
+    /// Creates a server for the pipelines in `pipeline_repository`
+    ///
+    /// Each job runs a copy of `process` with the security context of the
+    /// request that submitted it, authorized through `access_control`.
+    pub fn new(
+        pipeline_repository: Arc<SqlitePipelineRepository>,
+        process: ProcessFileUseCase,
+        metrics_service: Arc<MetricsService>,
+        access_control: AccessControlService,
+    ) -> Self {
+        Self {
+            stage_registry: StageRegistry::builtin(metrics_service.clone()),
+            pipeline_repository,
+            pipeline_cache: None,
+            process,
+            metrics_service,
+            access_control,
+            authentication: AuthenticationService::new(),
+            sessions: None,
+            client_cert_proxy: None,
+            allow_anonymous: false,
+            data_root: None,
+            shutdown: None,
+            max_jobs: DEFAULT_MAX_JOBS,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        }
+    }
+
+    /// Drops deleted pipelines from `pipeline_cache`
+    pub fn with_pipeline_cache(mut self, pipeline_cache: Arc<PipelineCache>) -> Self {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
+    }
+
+    /// Restores through the stage services in `stage_registry`
+    pub fn with_stage_registry(mut self, stage_registry: StageRegistry) -> Self {
+        self.stage_registry = stage_registry;
+        self
+    }
+
+    /// Authenticates request credentials with `authentication`
+    pub fn with_authentication(mut self, authentication: AuthenticationService) -> Self {
+        self.authentication = authentication;
+        self
+    }
+
+    /// Authenticates the client certificates `client_cert_proxy` forwards
+    pub fn with_client_cert_proxy(mut self, client_cert_proxy: ClientCertProxy) -> Self {
+        self.client_cert_proxy = Some(client_cert_proxy);
+        self
+    }
+
+    /// Authenticates session bearer tokens with `sessions`
+    pub fn with_sessions(mut self, sessions: Arc<SessionService>) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
+    /// Serves requests without credentials as the principal of
+    /// `access_control`, unless authentication is required
+    pub fn with_anonymous_access(mut self) -> Self {
+        self.allow_anonymous = true;
+        self
+    }
+
+    /// Confines the files jobs and restores use to `data_root`, which must
+    /// be canonical
+    pub fn with_data_root(mut self, data_root: PathBuf) -> Self {
+        self.data_root = Some(data_root);
+        self
+    }
+
+    /// Stops serving once `shutdown` is requested
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Processes at most `max_jobs` submitted files at a time
+    pub fn with_max_jobs(mut self, max_jobs: usize) -> Self {
+        self.max_jobs = max_jobs.max(1);
+        self
+    }
+
+    /// Serves at most `max_connections` connections at a time; further
+    /// clients wait to be accepted
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+
+    /// Closes connections whose request headers have not arrived within
+    /// `read_timeout`, and answers bodies that take longer with 408
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Serves requests arriving on `listener` until shutdown is requested,
+    /// then waits for running jobs
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the listener's address cannot be read.
+    pub async fn serve(self, listener: TcpListener) -> Result<(), PipelineError> {
+        let address = listener
+            .local_addr()
+            .map_err(|e| PipelineError::IoError(format!("Failed to read the API address: {}", e)))?;
+        let shutdown = self.shutdown.clone();
+        let read_timeout = self.read_timeout;
+        let connection_slots = Arc::new(Semaphore::new(self.max_connections));
+        let state = Arc::new(ApiState {
+            job_slots: Semaphore::new(self.max_jobs),
+            server: self,
+            jobs: JobTable::default(),
+            job_tasks: Mutex::new(JoinSet::new()),
+        });
+        let router = router(state.clone());
+        info!("API server listening on http://{}/api/v1", address);
+        println!("🌐 Serving the API at http://{}/api/v1", address);
+
+        loop {
+            // Connections beyond the cap wait in the listen backlog; the
+            // semaphore is never closed
+            let accept = async {
+                let slot = connection_slots.clone().acquire_owned().await.ok()?;
+                Some((listener.accept().await, slot))
+            };
+            let accepted = match &shutdown {
+                Some(shutdown) => tokio::select! {
+                    accepted = accept => accepted,
+                    _ = shutdown.requested() => break,
+                },
+                None => accept.await,
+            };
+            let Some((accepted, slot)) = accepted else {
+                break;
+            };
+            match accepted {
+                Ok((stream, peer)) => {
+                    debug!(peer = %peer, "API connection accepted");
+                    let router = router.clone();
+                    tokio::spawn(async move {
+                        serve_connection(stream, peer, router, read_timeout).await;
+                        drop(slot);
+                    });
+                }
+                Err(e) => error!("Error accepting API connection: {}", e),
+            }
+        }
+
+        let mut job_tasks = std::mem::take(&mut *state.job_tasks.lock().await);
+        while job_tasks.try_join_next().is_some() {}
+        if !job_tasks.is_empty() {
+            info!("Waiting for {} API job(s) to stop", job_tasks.len());
+        }
+        while job_tasks.join_next().await.is_some() {}
+        println!("🌐 API server stopped");
+        Ok(())
+    }
+}
+
+/// Routes of the API
+fn router(state: Arc<ApiState>) -> Router {
+    Router::new()
+        .route("/api/v1/health", get(health))
+        .route("/api/v1/pipelines", get(list_pipelines).post(create_pipeline))
+        .route("/api/v1/pipelines/{name}", delete(delete_pipeline))
+        .route("/api/v1/jobs", get(list_jobs).post(submit_job))
+        .route("/api/v1/jobs/{id}", get(get_job))
+        .route("/api/v1/restore", get(download_restore))
+        .fallback(|| async { ApiError::new(StatusCode::NOT_FOUND, "No such endpoint") })
+        .method_not_allowed_fallback(|method: axum::http::Method| async move {
+            ApiError::new(
+                StatusCode::METHOD_NOT_ALLOWED,
+                format!("{} is not allowed here", method),
+            )
+        })
+        .with_state(state)
+}
+
+/// Serves the one request `stream` carries through `router`
+///
+/// hyper parses the request, including chunked bodies, and refuses
+/// malformed or ambiguous framing before it reaches a handler. The request
+/// head must arrive within `read_timeout`, or the connection is closed.
+async fn serve_connection(stream: TcpStream, peer: SocketAddr, router: Router, read_timeout: Duration) {
+    let service = hyper::service::service_fn(move |mut request: hyper::Request<hyper::body::Incoming>| {
+        let router = router.clone();
+        async move {
+            request.extensions_mut().insert(ConnectInfo(peer));
+            let method = request.method().clone();
+            let path = request.uri().path().to_string();
+            let response = router.oneshot(request).await?;
+            info!("API {} {} -> {}", method, path, response.status().as_u16());
+            Ok::<_, Infallible>(response)
+        }
+    });
+    let connection = http1::Builder::new()
+        .timer(TokioTimer::new())
+        .header_read_timeout(read_timeout)
+        .keep_alive(false)
+        .max_buf_size(MAX_HEAD_BYTES)
+        .serve_connection(TokioIo::new(stream), service);
+    if let Err(e) = connection.await {
+        debug!(peer = %peer, "API connection failed: {}", e);
+    }
+}
+
+/// Query of `GET /api/v1/restore`
+#[derive(Debug, Deserialize)]
+struct RestoreQuery {
+    archive: Option<String>,
+}
+
+async fn health() -> Response {
+    Json(serde_json::json!({ "status": "ok" })).into_response()
+}
+
+async fn list_pipelines(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    state
+        .authorize(&headers, peer.ip(), ProtectedOperation::ViewPipelines)
+        .await?;
+    let summaries = ListPipelinesUseCase::new(state.server.pipeline_repository.clone())
+        .summaries()
+        .await
+        .map_err(|e| anyhow_error(&e, StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(summaries).into_response())
+}
+
+async fn create_pipeline(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let (access, _) = state
+        .authorize(&headers, peer.ip(), ProtectedOperation::CreatePipeline)
+        .await?;
+    let body: CreatePipelineRequest = state.read_json(body).await?;
+    if body.stages.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "A pipeline needs at least one stage",
+        ));
+    }
+    if state.find_pipeline(&body.name).await?.is_some() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            format!("Pipeline '{}' already exists", body.name),
+        ));
+    }
+
+    let mut use_case =
+        CreatePipelineUseCase::new(state.server.pipeline_repository.clone()).with_principal(access.principal());
+    if let Some(session) = access.session() {
+        use_case = use_case.with_session(session.id().clone());
+    }
+    let pipeline = use_case
+        .execute(body.name, body.stages.join(","), None, None)
+        .await
+        .map_err(|e| anyhow_error(&e, StatusCode::BAD_REQUEST))?;
+    let created = serde_json::json!({
+        "id": pipeline.id().to_string(),
+        "name": pipeline.name(),
+        "namespace": pipeline.namespace().to_string(),
+        "stages": pipeline.stages().iter().map(|stage| stage.name()).collect::<Vec<_>>(),
+    });
+    Ok((StatusCode::CREATED, Json(created)).into_response())
+}
+
+async fn delete_pipeline(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    name: Result<Path<String>, PathRejection>,
+) -> Result<Response, ApiError> {
+    let (access, _) = state
+        .authorize(&headers, peer.ip(), ProtectedOperation::DeletePipeline)
+        .await?;
+    let Path(name) = name.map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.body_text()))?;
+    let pipeline = state
+        .find_pipeline(&name)
+        .await?
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("Pipeline '{}' not found", name)))?;
+
+    let mut use_case =
+        DeletePipelineUseCase::new(state.server.pipeline_repository.clone()).with_principal(access.principal());
+    if let Some(cache) = &state.server.pipeline_cache {
+        use_case = use_case.with_pipeline_cache(cache.clone());
+    }
+    if let Some(session) = access.session() {
+        use_case = use_case.with_session(session.id().clone());
+    }
+    use_case
+        .delete(&pipeline)
+        .await
+        .map_err(|e| anyhow_error(&e, StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn list_jobs(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    state
+        .authorize(&headers, peer.ip(), ProtectedOperation::ViewPipelines)
+        .await?;
+    Ok(Json(state.jobs.list()).into_response())
+}
+
+async fn get_job(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    id: Result<Path<String>, PathRejection>,
+) -> Result<Response, ApiError> {
+    state
+        .authorize(&headers, peer.ip(), ProtectedOperation::ViewPipelines)
+        .await?;
+    let Path(id) = id.map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.body_text()))?;
+    let job = state
+        .jobs
+        .get(&id)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("Job '{}' not found", id)))?;
+    Ok(Json(job).into_response())
+}
+
+async fn submit_job(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, ApiError> {
+    let (_, context) = state
+        .authorize(&headers, peer.ip(), ProtectedOperation::ProcessFile)
+        .await?;
+    let body: SubmitJobRequest = state.read_json(body).await?;
+    let input = state.confine(&body.input)?;
+    let output = state.confine(&body.output)?;
+    if !input.is_file() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Input file not found: {}", body.input),
+        ));
+    }
+    if body.workers == Some(0) {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "workers must be at least 1"));
+    }
+    if state.find_pipeline(&body.pipeline).await?.is_none() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            format!("Pipeline '{}' not found", body.pipeline),
+        ));
+    }
+
+    let job = state.jobs.submit(body.pipeline.clone(), input.clone(), output.clone());
+    let config = ProcessFileConfig {
+        input,
+        output,
+        pipeline: body.pipeline,
+        chunk_size: None,
+        workers: body.workers,
+        channel_depth: None,
+        inflight_window: None,
+        write_manifest: false,
+        signing_key: None,
+        idempotency_key: None,
+        stage_timeout: None,
+        chunk_timeout: None,
+        max_worker_restarts: 0,
+        direct_io: false,
+        checksum_offload: false,
+        overwrite_policy: OverwritePolicy::default(),
+        output_mode: None,
+        priority: JobPriority::default(),
+        password: None,
+        passphrase_policy: PassphrasePolicy::default(),
+    };
+
+    let id = job.id.clone();
+    let runner = state.clone();
+    let mut job_tasks = state.job_tasks.lock().await;
+    while job_tasks.try_join_next().is_some() {}
+    job_tasks.spawn(async move { runner.run_job(id, config, context).await });
+    Ok((StatusCode::ACCEPTED, Json(job)).into_response())
+}
+
+/// Streams the restored original of the `archive` query parameter; the
+/// body is completed only once it verifies
+async fn download_restore(
+    State(state): Api,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    query: Result<Query<RestoreQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let (_, context) = state
+        .authorize(&headers, peer.ip(), ProtectedOperation::RestoreFile)
+        .await?;
+    let Query(query) = query.map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.body_text()))?;
+    let archive = query
+        .archive
+        .ok_or_else(|| ApiError::new(StatusCode::BAD_REQUEST, "Missing query parameter 'archive'"))?;
+    let archive = state.confine(&archive)?;
+    let metadata = RestoreFileUseCase::read_metadata(&archive)
+        .await
+        .map_err(|e| ApiError::from_error(&e))?;
+
+    let mut restore = RestoreFileUseCase::new(state.server.metrics_service.clone())
+        .with_stage_registry(state.server.stage_registry.clone())
+        .with_security_context(context);
+    if let Some(shutdown) = &state.server.shutdown {
+        restore = restore.with_shutdown(shutdown.clone());
+    }
+    let file_name = PathBuf::from(&metadata.original_filename)
+        .file_name()
+        .map(|name| {
+            name.to_string_lossy()
+                .replace(|c: char| !c.is_ascii_graphic() || c == '"' || c == '\\', "_")
+        })
+        .unwrap_or_else(|| "restored".to_string());
+    let content_length = metadata.original_size;
+    let body = held_back_body(content_length, move |mut body| async move {
+        restore
+            .restore_into(&archive, &metadata, &mut body, JobPriority::Interactive)
+            .await?;
+        body.finish()
+            .await
+            .map_err(|e| PipelineError::IoError(format!("Failed to complete the restore download: {}", e)))
+    })
+    .await?;
+
+    let headers = [
+        (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+        (header::CONTENT_LENGTH, content_length.to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        ),
+    ];
+    Ok((headers, body).into_response())
+}
+
+impl ApiState {
+    /// Access control for the caller sending `headers` from `peer`
+    async fn access(&self, headers: &HeaderMap, peer: IpAddr) -> Result<AccessControlService, ApiError> {
+        let server = &self.server;
+        let certificate = match &server.client_cert_proxy {
+            Some(proxy) => proxy.certificate(headers, peer)?,
+            None => None,
+        };
+        let credentials = match (
+            header_value(headers, header::AUTHORIZATION.as_str())?,
+            header_value(headers, "x-api-key")?,
+            certificate,
+        ) {
+            (Some(authorization), None, None) => match authorization.strip_prefix("Bearer ") {
+                Some(token) => Some(Credentials::BearerToken(token.trim().to_string())),
+                None => {
+                    return Err(ApiError::new(
+                        StatusCode::UNAUTHORIZED,
+                        "Authorization must carry a bearer token",
+                    ))
+                }
+            },
+            (None, Some(key), None) => Some(Credentials::ApiKey(key.to_string())),
+            (None, None, Some(certificate)) => Some(Credentials::ClientCertificate(certificate)),
+            (None, None, None) => None,
+            _ => {
+                return Err(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "Send only one of Authorization, X-API-Key and a client certificate",
+                ));
+            }
+        };
+
+        match credentials {
+            Some(Credentials::BearerToken(token)) if Session::token_session_id(&token).is_ok() => {
+                let Some(sessions) = &server.sessions else {
+                    return Err(ApiError::new(
+                        StatusCode::UNAUTHORIZED,
+                        "Session tokens are not accepted here",
+                    ));
+                };
+                let session = sessions.authenticate(&token).await.map_err(|e| match e {
+                    PipelineError::QuotaExceeded(_) => ApiError::from_error(&e),
+                    _ => ApiError::new(StatusCode::UNAUTHORIZED, e.to_string()),
+                })?;
+                let namespace = server.access_control.namespace();
+                if session.namespace() != namespace {
+                    return Err(ApiError::new(
+                        StatusCode::FORBIDDEN,
+                        format!(
+                            "Permission denied: session {} is scoped to namespace '{}', not '{}'",
+                            session.id(),
+                            session.namespace(),
+                            namespace
+                        ),
+                    ));
+                }
+                Ok(server.access_control.clone().with_session(session))
+            }
+            Some(credentials) => {
+                let identity = server
+                    .authentication
+                    .authenticate(&credentials)
+                    .await
+                    .map_err(|e| ApiError::new(StatusCode::UNAUTHORIZED, e.to_string()))?;
+                let namespace = server.access_control.namespace();
+                if let Some(scope) = identity.namespace.as_ref().filter(|scope| *scope != namespace) {
+                    return Err(ApiError::new(
+                        StatusCode::FORBIDDEN,
+                        format!(
+                            "Permission denied: '{}' is confined to namespace '{}', not '{}'",
+                            identity.principal, scope, namespace
+                        ),
+                    ));
+                }
+                Ok(server.access_control.clone().with_identity(identity))
+            }
+            None if server.allow_anonymous && !server.authentication.is_required() => Ok(server.access_control.clone()),
+            None => Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Authentication required; send Authorization: Bearer <token> or X-API-Key: <key>",
+            )),
+        }
+    }
+
+    /// Authenticates the caller sending `headers` from `peer` and authorizes
+    /// `operation`
+    async fn authorize(
+        &self,
+        headers: &HeaderMap,
+        peer: IpAddr,
+        operation: ProtectedOperation,
+    ) -> Result<(AccessControlService, SecurityContext), ApiError> {
+        let access = self.access(headers, peer).await?;
+        let context = access
+            .authorize(operation)
+            .await
+            .map_err(|e| ApiError::from_error(&e))?;
+        Ok((access, context))
+    }
+
+    /// Deserializes a JSON request body, which must arrive within the read
+    /// timeout
+    async fn read_json<T: DeserializeOwned>(&self, body: Body) -> Result<T, ApiError> {
+        let bytes = read_body(body, MAX_BODY_BYTES, self.server.read_timeout).await?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("Invalid JSON body: {}", e)))
+    }
+
+    /// Runs job `id` once a job slot is free
+    async fn run_job(&self, id: String, config: ProcessFileConfig, context: SecurityContext) {
+        // The semaphore is never closed
+        let Ok(_slot) = self.job_slots.acquire().await else {
+            return;
+        };
+        if self
+            .server
+            .shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_requested())
+        {
+            self.jobs.fail(&id, "Cancelled before it started".to_string(), true);
+            return;
+        }
+
+        self.jobs.start(&id);
+        let use_case = self.server.process.clone().with_security_context(context);
+        // A panicking job fails alone rather than staying running forever
+        match catch_panic(use_case.execute(config)).await {
+            Ok(Ok(result)) => self.jobs.complete(&id, result),
+            Ok(Err(e)) => {
+                let cancelled = matches!(e.downcast_ref::<PipelineError>(), Some(PipelineError::Cancelled(_)));
+                warn!("API job {} failed: {}", id, e);
+                self.jobs.fail(&id, e.to_string(), cancelled);
+            }
+            Err(message) => {
+                error!("API job {} panicked: {}", id, message);
+                self.jobs.fail(&id, format!("Job panicked: {}", message), false);
+            }
+        }
+    }
+
+    /// Resolves `path` from a request against the data root, refusing one
+    /// that leads outside it; the file itself need not exist yet
+    fn confine(&self, path: &str) -> Result<PathBuf, ApiError> {
+        let Some(root) = &self.server.data_root else {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "Permission denied: file access is disabled; start serve with --root",
+            ));
+        };
+        SecureArgParser::validate_argument(path).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.to_string()))?;
+        let joined = root.join(path);
+        let (Some(parent), Some(name)) = (joined.parent(), joined.file_name()) else {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("'{}' does not name a file", path),
+            ));
+        };
+        let parent = parent.canonicalize().map_err(|e| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("Cannot resolve the directory of '{}': {}", path, e),
+            )
+        })?;
+        let resolved = parent.join(name);
+        let resolved = match resolved.canonicalize() {
+            Ok(resolved) => resolved,
+            // Nothing there yet; a dangling symlink could lead anywhere
+            Err(_) if resolved.symlink_metadata().is_err() => resolved,
+            Err(e) => {
+                return Err(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("Cannot resolve '{}': {}", path, e),
+                ))
+            }
+        };
+        if !resolved.starts_with(root) {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                format!("Permission denied: '{}' is outside the served directory", path),
+            ));
+        }
+        Ok(resolved)
+    }
+
+    async fn find_pipeline(
+        &self,
+        name: &str,
+    ) -> Result<Option<adaptive_pipeline_domain::entities::Pipeline>, ApiError> {
+        self.server
+            .pipeline_repository
+            .find_by_name(name)
+            .await
+            .map_err(|e| ApiError::from_error(&e))
+    }
+}
+
+/// Value of the header `name`, which must be text
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>, ApiError> {
+    headers
+        .get(name)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, format!("{} is not valid text", name)))
+        })
+        .transpose()
+}
+
+/// Error for a use case error, with the status of the `PipelineError` it
+/// carries or `fallback`
+fn anyhow_error(error: &anyhow::Error, fallback: StatusCode) -> ApiError {
+    match error.downcast_ref::<PipelineError>() {
+        Some(error) => ApiError::from_error(error),
+        None => ApiError::new(fallback, error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::sqlite_role::SqliteRoleRepository;
+    use crate::infrastructure::repositories::sqlite_session::SqliteSessionRepository;
+    use crate::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+    use adaptive_pipeline_domain::repositories::RoleRepository;
+    use adaptive_pipeline_domain::value_objects::{Namespace, Role, RoleAssignment, UserId};
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Sends one request and returns the status and body
+    async fn call(address: std::net::SocketAddr, method: &str, path: &str, body: &str) -> (u16, Vec<u8>) {
+        call_with(address, method, path, "", body).await
+    }
+
+    /// Sends one request with extra `headers`, each ending in CRLF
+    async fn call_with(
+        address: std::net::SocketAddr,
+        method: &str,
+        path: &str,
+        headers: &str,
+        body: &str,
+    ) -> (u16, Vec<u8>) {
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            headers,
+            body.len(),
+            body
+        );
+        send(address, request.as_bytes()).await
+    }
+
+    /// Sends `raw` as it is and returns the status and body
+    async fn send(address: std::net::SocketAddr, raw: &[u8]) -> (u16, Vec<u8>) {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(raw).await.unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+
+        let head_end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let status = String::from_utf8_lossy(&response[9..12]).parse().unwrap();
+        (status, response[head_end + 4..].to_vec())
+    }
+
+    /// Starts a server over a database in `dir` that serves anonymous
+    /// requests and files in `dir`
+    async fn start(dir: &std::path::Path) -> std::net::SocketAddr {
+        let root = dir.canonicalize().unwrap();
+        start_with(dir, |server| server.with_anonymous_access().with_data_root(root)).await
+    }
+
+    async fn start_with(dir: &std::path::Path, configure: impl FnOnce(ApiServer) -> ApiServer) -> std::net::SocketAddr {
+        let database = dir.join("pipeline.db").to_string_lossy().to_string();
+        let repository = Arc::new(SqlitePipelineRepository::new(&database).await.unwrap());
+        let roles = Arc::new(SqliteRoleRepository::new(&database).await.unwrap());
+        roles
+            .assign(&RoleAssignment::new(Namespace::default(), "api-test", Role::Admin, None).unwrap())
+            .await
+            .unwrap();
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let process = ProcessFileUseCase::builder()
+            .metrics_service(metrics_service.clone())
+            .pipeline_repository(repository.clone())
+            .build()
+            .await
+            .unwrap();
+        let server = configure(ApiServer::new(
+            repository,
+            process,
+            metrics_service,
+            AccessControlService::new(roles, "api-test"),
+        ));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(server.serve(listener));
+        address
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pipeline_job_and_restore_round_trip() {
+        // Processing draws on the global resource manager
+        let _ = init_resource_manager(ResourceConfig::default());
+        let dir = TempDir::new().unwrap();
+        let address = start(dir.path()).await;
+        let input = dir.path().join("report.txt");
+        let data = "quarterly figures ".repeat(500);
+        std::fs::write(&input, &data).unwrap();
+
+        let (status, _) = call(
+            address,
+            "POST",
+            "/api/v1/pipelines",
+            r#"{"name": "api-backup", "stages": ["brotli"]}"#,
+        )
+        .await;
+        assert_eq!(status, 201);
+        let (status, _) = call(
+            address,
+            "POST",
+            "/api/v1/pipelines",
+            r#"{"name": "api-backup", "stages": ["brotli"]}"#,
+        )
+        .await;
+        assert_eq!(status, 409);
+        let (status, body) = call(address, "GET", "/api/v1/pipelines", "").await;
+        assert_eq!(status, 200);
+        assert!(String::from_utf8_lossy(&body).contains("\"name\":\"api-backup\""));
+
+        let archive = dir.path().join("report.txt.adapipe");
+        let submit = serde_json::json!({
+            "pipeline": "api-backup",
+            "input": input,
+            "output": archive,
+        });
+        let (status, body) = call(address, "POST", "/api/v1/jobs", &submit.to_string()).await;
+        assert_eq!(status, 202);
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = job["id"].as_str().unwrap().to_string();
+
+        let job = loop {
+            let (status, body) = call(address, "GET", &format!("/api/v1/jobs/{}", id), "").await;
+            assert_eq!(status, 200);
+            let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            if job["state"] != "queued" && job["state"] != "running" {
+                break job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        };
+        assert_eq!(job["state"], "completed", "{}", job);
+        assert_eq!(job["metrics"]["input_bytes"], data.len());
+
+        let path = format!(
+            "/api/v1/restore?archive={}",
+            archive.to_string_lossy().replace('%', "%25").replace(' ', "%20")
+        );
+        let (status, body) = call(address, "GET", &path, "").await;
+        assert_eq!(status, 200);
+        assert_eq!(body, data.as_bytes());
+
+        assert_eq!(call(address, "DELETE", "/api/v1/pipelines/api-backup", "").await.0, 204);
+        assert_eq!(call(address, "DELETE", "/api/v1/pipelines/api-backup", "").await.0, 404);
+    }
+
+    #[tokio::test]
+    async fn test_bad_requests_get_json_errors() {
+        let dir = TempDir::new().unwrap();
+        let address = start(dir.path()).await;
+
+        let (status, body) = call(address, "GET", "/api/v1/restore", "").await;
+        assert_eq!(status, 400);
+        assert!(String::from_utf8_lossy(&body).contains("archive"));
+        assert_eq!(
+            call(address, "GET", "/api/v1/jobs/01J0000000000000000000000", "")
+                .await
+                .0,
+            404
+        );
+        assert_eq!(call(address, "PUT", "/api/v1/pipelines", "").await.0, 405);
+        assert_eq!(call(address, "GET", "/elsewhere", "").await.0, 404);
+        assert_eq!(call(address, "POST", "/api/v1/pipelines", "not json").await.0, 400);
+        assert_eq!(call(address, "GET", "/api/v1/health", "").await.0, 200);
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_requests_and_files_outside_the_root_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let served = dir.path().join("served");
+        std::fs::create_dir(&served).unwrap();
+        let secret = dir.path().join("secret.adapipe");
+        std::fs::write(&secret, b"not for the API").unwrap();
+        std::os::unix::fs::symlink(&secret, served.join("link.adapipe")).unwrap();
+
+        let address = start_with(dir.path(), |server| server).await;
+        assert_eq!(call(address, "GET", "/api/v1/pipelines", "").await.0, 401);
+        assert_eq!(call(address, "GET", "/api/v1/health", "").await.0, 200);
+
+        let root = served.canonicalize().unwrap();
+        let address = start_with(dir.path(), |server| server.with_anonymous_access().with_data_root(root)).await;
+        let traversal = call(address, "GET", "/api/v1/restore?archive=..%2Fsecret.adapipe", "").await;
+        assert_eq!(traversal.0, 400, "`..` is rejected outright");
+        let secret = secret.to_string_lossy().replace('/', "%2F");
+        for archive in [secret.as_str(), "link.adapipe"] {
+            let path = format!("/api/v1/restore?archive={}", archive);
+            let (status, body) = call(address, "GET", &path, "").await;
+            assert_eq!(status, 403, "{}: {}", archive, String::from_utf8_lossy(&body));
+        }
+        let submit = r#"{"pipeline": "none", "input": "link.adapipe", "output": "out.adapipe"}"#;
+        assert_eq!(call(address, "POST", "/api/v1/jobs", submit).await.0, 403);
+        let submit = serde_json::json!({
+            "pipeline": "none",
+            "input": "missing.bin",
+            "output": dir.path().join("out.adapipe"),
+        });
+        assert_eq!(call(address, "POST", "/api/v1/jobs", &submit.to_string()).await.0, 403);
+
+        // Without a root no file is reachable
+        let address = start_with(dir.path(), |server| server.with_anonymous_access()).await;
+        let (status, body) = call(address, "GET", "/api/v1/restore?archive=link.adapipe", "").await;
+        assert_eq!(status, 403);
+        assert!(String::from_utf8_lossy(&body).contains("--root"));
+    }
+
+    #[tokio::test]
+    async fn test_session_tokens_act_as_the_session_and_are_rate_limited() {
+        let dir = TempDir::new().unwrap();
+        let database = dir.path().join("pipeline.db").to_string_lossy().to_string();
+        let pipelines = Arc::new(SqlitePipelineRepository::new(&database).await.unwrap());
+        let sessions =
+            SessionService::new(Arc::new(SqliteSessionRepository::new(&database).await.unwrap())).with_rate_limit(2);
+        let alice = UserId::parse("alice").unwrap();
+        SqliteRoleRepository::new(&database)
+            .await
+            .unwrap()
+            .assign(&RoleAssignment::new(Namespace::default(), "alice", Role::Operator, None).unwrap())
+            .await
+            .unwrap();
+        let (session, token) = sessions
+            .issue(alice.clone(), Namespace::default(), None, "root")
+            .await
+            .unwrap();
+        let (_, elsewhere) = sessions
+            .issue(alice, Namespace::new("finance").unwrap(), None, "root")
+            .await
+            .unwrap();
+        let sessions = Arc::new(sessions);
+        let address = start_with(dir.path(), |server| server.with_sessions(sessions)).await;
+
+        let bearer = format!("Authorization: Bearer {}\r\n", token);
+        let create = r#"{"name": "session-backup", "stages": ["brotli"]}"#;
+        assert_eq!(
+            call_with(address, "POST", "/api/v1/pipelines", &bearer, create).await.0,
+            201
+        );
+        assert_eq!(call_with(address, "GET", "/api/v1/pipelines", &bearer, "").await.0, 200);
+        let (status, body) = call_with(address, "GET", "/api/v1/pipelines", &bearer, "").await;
+        assert_eq!(status, 429, "{}", String::from_utf8_lossy(&body));
+
+        let bearer = format!("Authorization: Bearer {}\r\n", elsewhere);
+        assert_eq!(call_with(address, "GET", "/api/v1/pipelines", &bearer, "").await.0, 403);
+        let forged = format!("Authorization: Bearer {}.forged\r\n", session.id());
+        assert_eq!(call_with(address, "GET", "/api/v1/pipelines", &forged, "").await.0, 401);
+
+        let audits: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT principal, session_id FROM audit_records WHERE action = 'pipeline.create'")
+                .fetch_all(pipelines.pool())
+                .await
+                .unwrap();
+        assert_eq!(audits, vec![("alice".to_string(), Some(session.id().to_string()))]);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_bodies_are_read_and_ambiguous_framing_is_refused() {
+        let dir = TempDir::new().unwrap();
+        let address = start(dir.path()).await;
+
+        let create = r#"{"name": "chunked-backup", "stages": ["brotli"]}"#;
+        let chunked = format!(
+            "POST /api/v1/pipelines HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n\
+             {:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            10,
+            &create[..10],
+            create.len() - 10,
+            &create[10..]
+        );
+        let (status, body) = send(address, chunked.as_bytes()).await;
+        assert_eq!(status, 201, "{}", String::from_utf8_lossy(&body));
+
+        let conflicting = format!(
+            "POST /api/v1/pipelines HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nContent-Length: \
+             {}\r\n\r\n{}",
+            create.len(),
+            create.len() + 1,
+            create
+        );
+        assert_eq!(send(address, conflicting.as_bytes()).await.0, 400);
+        let oversized = format!(
+            "POST /api/v1/pipelines HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(send(address, oversized.as_bytes()).await.0, 413);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_clients_time_out_and_connections_are_capped() {
+        let dir = TempDir::new().unwrap();
+        let address = start_with(dir.path(), |server| {
+            server
+                .with_anonymous_access()
+                .with_max_connections(1)
+                .with_read_timeout(Duration::from_millis(300))
+        })
+        .await;
+
+        // The stalled client holds the only connection until it times out
+        let mut stalled = TcpStream::connect(address).await.unwrap();
+        stalled
+            .write_all(b"GET /api/v1/health HTTP/1.1\r\nHost: loc")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let started = std::time::Instant::now();
+        assert_eq!(call(address, "GET", "/api/v1/health", "").await.0, 200);
+        assert!(
+            started.elapsed() >= Duration::from_millis(200),
+            "{:?}",
+            started.elapsed()
+        );
+        let mut response = Vec::new();
+        stalled.read_to_end(&mut response).await.unwrap();
+        assert!(response.is_empty(), "closed without a response");
+
+        // A body that stops arriving is answered with 408
+        let (status, body) = send(
+            address,
+            b"POST /api/v1/pipelines HTTP/1.1\r\nHost: localhost\r\nContent-Length: 40\r\n\r\n{\"name\"",
+        )
+        .await;
+        assert_eq!(status, 408, "{}", String::from_utf8_lossy(&body));
+    }
+}
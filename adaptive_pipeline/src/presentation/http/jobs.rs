@@ -0,0 +1,182 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # API Jobs
+//!
+//! Processing jobs submitted over the API run in the background; this table
+//! tracks each one from submission to its result so clients can poll it.
+//! Jobs live in memory and are gone once the server exits.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::application::use_cases::ProcessFileResult;
+pub use adaptive_pipeline_domain::entities::JobState;
+
+/// Throughput figures of a completed job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobMetrics {
+    pub duration_ms: u64,
+    pub input_bytes: u64,
+    pub output_bytes: u64,
+    /// Output size over input size; absent for an empty input
+    pub compression_ratio: Option<f64>,
+    pub bytes_per_second: f64,
+}
+
+/// A processing job and what became of it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Job {
+    pub id: String,
+    pub pipeline: String,
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub state: JobState,
+    pub submitted_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub result: Option<ProcessFileResult>,
+    pub metrics: Option<JobMetrics>,
+}
+
+/// Jobs by ID; IDs are ULIDs, so iteration follows submission order
+#[derive(Default)]
+pub struct JobTable {
+    jobs: Mutex<BTreeMap<String, Job>>,
+}
+
+impl JobTable {
+    /// Records a new queued job and returns it
+    pub fn submit(&self, pipeline: String, input: PathBuf, output: PathBuf) -> Job {
+        let job = Job {
+            id: ulid::Ulid::new().to_string(),
+            pipeline,
+            input,
+            output,
+            state: JobState::Queued,
+            submitted_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            result: None,
+            metrics: None,
+        };
+        self.lock().insert(job.id.clone(), job.clone());
+        job
+    }
+
+    /// Marks job `id` as running
+    pub fn start(&self, id: &str) {
+        if let Some(job) = self.lock().get_mut(id) {
+            job.state = JobState::Running;
+            job.started_at = Some(Utc::now());
+        }
+    }
+
+    /// Records the result of job `id`
+    pub fn complete(&self, id: &str, result: ProcessFileResult) {
+        if let Some(job) = self.lock().get_mut(id) {
+            let finished_at = Utc::now();
+            let elapsed = finished_at - job.started_at.unwrap_or(job.submitted_at);
+            let duration_ms = elapsed.num_milliseconds().max(0) as u64;
+            job.metrics = Some(JobMetrics {
+                duration_ms,
+                input_bytes: result.input_size_bytes,
+                output_bytes: result.output_size_bytes,
+                compression_ratio: (result.input_size_bytes > 0)
+                    .then(|| result.output_size_bytes as f64 / result.input_size_bytes as f64),
+                bytes_per_second: result.input_size_bytes as f64 * 1000.0 / duration_ms.max(1) as f64,
+            });
+            job.state = JobState::Completed;
+            job.finished_at = Some(finished_at);
+            job.result = Some(result);
+        }
+    }
+
+    /// Records that job `id` failed, or was cancelled if `cancelled`
+    pub fn fail(&self, id: &str, error: String, cancelled: bool) {
+        if let Some(job) = self.lock().get_mut(id) {
+            job.state = if cancelled {
+                JobState::Cancelled
+            } else {
+                JobState::Failed
+            };
+            job.finished_at = Some(Utc::now());
+            job.error = Some(error);
+        }
+    }
+
+    /// Job `id`, if it exists
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.lock().get(id).cloned()
+    }
+
+    /// Every job, oldest first
+    pub fn list(&self) -> Vec<Job> {
+        self.lock().values().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Job>> {
+        self.jobs.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::value_objects::OverwritePolicy;
+
+    fn result(input: u64, output: u64) -> ProcessFileResult {
+        ProcessFileResult {
+            output: PathBuf::from("out.adapipe"),
+            manifest: None,
+            input_size_bytes: input,
+            output_size_bytes: output,
+            input_checksum: None,
+            output_checksum: None,
+            completed_at: Utc::now(),
+            replayed: false,
+            overwrite_policy: OverwritePolicy::default(),
+            skipped: false,
+        }
+    }
+
+    #[test]
+    fn test_job_moves_from_queued_to_completed_with_metrics() {
+        let table = JobTable::default();
+        let job = table.submit("compress".into(), "in.bin".into(), "out.adapipe".into());
+        assert_eq!(table.get(&job.id).unwrap().state, JobState::Queued);
+
+        table.start(&job.id);
+        assert_eq!(table.get(&job.id).unwrap().state, JobState::Running);
+
+        table.complete(&job.id, result(1000, 250));
+        let job = table.get(&job.id).unwrap();
+        assert_eq!(job.state, JobState::Completed);
+        assert_eq!(job.metrics.unwrap().compression_ratio, Some(0.25));
+        assert!(job.finished_at.is_some());
+    }
+
+    #[test]
+    fn test_jobs_are_listed_in_submission_order() {
+        let table = JobTable::default();
+        let first = table.submit("a".into(), "1".into(), "1.adapipe".into());
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = table.submit("b".into(), "2".into(), "2.adapipe".into());
+        table.fail(&first.id, "boom".into(), false);
+        table.fail(&second.id, "shutdown".into(), true);
+
+        let states: Vec<_> = table.list().into_iter().map(|job| (job.id, job.state)).collect();
+        assert_eq!(states, [(first.id, JobState::Failed), (second.id, JobState::Cancelled)]);
+        assert!(table.get("missing").is_none());
+    }
+}
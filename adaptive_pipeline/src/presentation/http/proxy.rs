@@ -0,0 +1,145 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Forwarded Client Certificates
+//!
+//! The API server does not terminate TLS. A proxy in front of it that
+//! verifies client certificates forwards the one it verified in a header, as
+//! URL-escaped PEM like nginx's `$ssl_client_escaped_cert`, and
+//! [`ClientCertProxy`] turns that header into the credentials the client
+//! certificate provider checks.
+//!
+//! Anyone can send the header, so it is only believed on connections from the
+//! configured proxy addresses. A request carrying it from anywhere else is
+//! refused rather than served without it.
+
+use std::net::IpAddr;
+
+use axum::http::{HeaderMap, StatusCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tracing::warn;
+
+use super::response::ApiError;
+
+/// Reads client certificates forwarded by trusted TLS-terminating proxies
+#[derive(Debug, Clone)]
+pub struct ClientCertProxy {
+    header: String,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl ClientCertProxy {
+    /// Believes `header` on connections from `trusted_proxies` only
+    pub fn new(header: impl Into<String>, trusted_proxies: Vec<IpAddr>) -> Self {
+        Self {
+            header: header.into(),
+            trusted_proxies: trusted_proxies.into_iter().map(|proxy| proxy.to_canonical()).collect(),
+        }
+    }
+
+    /// DER encoding of the certificate forwarded in `headers` by `peer`, if
+    /// any
+    ///
+    /// # Errors
+    ///
+    /// Returns a 401 error when the header comes from a peer that is not a
+    /// trusted proxy, or 400 when it is not a URL-escaped PEM certificate.
+    pub fn certificate(&self, headers: &HeaderMap, peer: IpAddr) -> Result<Option<Vec<u8>>, ApiError> {
+        let Some(value) = headers.get(self.header.as_str()) else {
+            return Ok(None);
+        };
+        let peer = peer.to_canonical();
+        if !self.trusted_proxies.contains(&peer) {
+            warn!(peer = %peer, "Refused {} from a peer that is not a trusted proxy", self.header);
+            return Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                format!("{} is only accepted from a trusted proxy", self.header),
+            ));
+        }
+        value.to_str().ok().and_then(decode_pem).map(Some).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("{} is not a URL-escaped PEM certificate", self.header),
+            )
+        })
+    }
+}
+
+/// Decodes a URL-escaped PEM certificate to DER
+fn decode_pem(escaped: &str) -> Option<Vec<u8>> {
+    let pem = percent_decode(escaped)?;
+    let body = pem
+        .trim()
+        .strip_prefix("-----BEGIN CERTIFICATE-----")?
+        .strip_suffix("-----END CERTIFICATE-----")?;
+    let base64: String = body.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    STANDARD.decode(base64).ok().filter(|der| !der.is_empty())
+}
+
+/// Decodes `%XX` escapes; `None` for a broken escape or a result that is
+/// not UTF-8
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(name: &str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            value.parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_certificate_is_only_believed_from_trusted_proxies() {
+        let proxy = ClientCertProxy::new("X-Client-Cert", vec!["10.0.0.5".parse().unwrap()]);
+        let escaped = "-----BEGIN%20CERTIFICATE-----%0ASGVsbG8s%0AIHByb3h5%0A-----END%20CERTIFICATE-----%0A";
+        let forwarded_headers = headers("x-client-cert", escaped);
+
+        let forwarded = proxy
+            .certificate(&forwarded_headers, "10.0.0.5".parse().unwrap())
+            .unwrap();
+        assert_eq!(forwarded.as_deref(), Some(&b"Hello, proxy"[..]));
+        let mapped = proxy
+            .certificate(&forwarded_headers, "::ffff:10.0.0.5".parse().unwrap())
+            .unwrap();
+        assert_eq!(mapped, forwarded);
+
+        let untrusted = proxy
+            .certificate(&forwarded_headers, "10.0.0.6".parse().unwrap())
+            .unwrap_err();
+        assert_eq!(untrusted.status, StatusCode::UNAUTHORIZED);
+        let garbled = proxy
+            .certificate(
+                &headers("x-client-cert", "not-a-certificate"),
+                "10.0.0.5".parse().unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(garbled.status, StatusCode::BAD_REQUEST);
+        let unrelated = proxy.certificate(&headers("host", "x"), "10.0.0.6".parse().unwrap());
+        assert_eq!(unrelated.unwrap(), None);
+    }
+}
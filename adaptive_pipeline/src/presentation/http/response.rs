@@ -0,0 +1,308 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # API Responses
+//!
+//! Error responses are JSON `{"error": message}` objects whose status follows
+//! the use case's [`PipelineError`]. Restore downloads stream a body that is
+//! only known to be good once its last byte has been produced; see
+//! [`held_back_body`].
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use futures::StreamExt;
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream};
+use tracing::error;
+
+use adaptive_pipeline_domain::PipelineError;
+
+/// Bytes buffered between a download's producer and the connection
+const DOWNLOAD_BUFFER_BYTES: usize = 64 * 1024;
+
+/// An error answered with a JSON `{"error": message}` body
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// An error with the status matching `error`
+    pub fn from_error(error: &PipelineError) -> Self {
+        Self::new(status_of(error), error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(serde_json::json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// HTTP status for an error returned by a use case
+pub fn status_of(error: &PipelineError) -> StatusCode {
+    match error {
+        PipelineError::InvalidConfiguration(_)
+        | PipelineError::MissingParameter(_)
+        | PipelineError::InvalidParameter(_)
+        | PipelineError::IncompatibleStage(_)
+        | PipelineError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        PipelineError::SecurityViolation(_) | PipelineError::SecurityContextExpired(_) => StatusCode::FORBIDDEN,
+        PipelineError::PipelineNotFound(_) => StatusCode::NOT_FOUND,
+        PipelineError::IntegrityError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        PipelineError::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+        PipelineError::UnsupportedOperation(_) => StatusCode::NOT_IMPLEMENTED,
+        PipelineError::ResourceExhausted(_) | PipelineError::Cancelled(_) => StatusCode::SERVICE_UNAVAILABLE,
+        PipelineError::TimeoutError(_) | PipelineError::StageTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Body of exactly `content_length` bytes that `produce` writes in the
+/// background
+///
+/// Waits for the first bytes before answering, so a producer that fails
+/// before writing anything is answered with its error instead. The writer
+/// holds back the final byte until [`HeldBackWriter::finish`], so a client
+/// never receives a complete body for data that then fails verification; a
+/// producer that fails later aborts the body one byte short of its
+/// `Content-Length`, which closes the connection.
+///
+/// # Errors
+///
+/// Returns the producer's error if it failed before writing anything.
+pub async fn held_back_body<F, Fut>(content_length: u64, produce: F) -> Result<Body, ApiError>
+where
+    F: FnOnce(HeldBackWriter<DuplexStream>) -> Fut,
+    Fut: Future<Output = Result<(), PipelineError>> + Send + 'static,
+{
+    let (writer, mut reader) = tokio::io::duplex(DOWNLOAD_BUFFER_BYTES);
+    let producer = tokio::spawn(produce(HeldBackWriter::new(writer, content_length)));
+
+    let mut first = vec![0u8; DOWNLOAD_BUFFER_BYTES];
+    let n = reader.read(&mut first).await.unwrap_or(0);
+    if n == 0 {
+        // The producer is done: the body is empty or it failed first
+        return match producer.await {
+            Ok(Ok(())) => Ok(Body::empty()),
+            Ok(Err(e)) => Err(ApiError::from_error(&e)),
+            Err(e) => Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Download failed: {}", e),
+            )),
+        };
+    }
+    first.truncate(n);
+
+    let stream = async_stream::stream! {
+        yield Ok(Bytes::from(first));
+        let mut buffer = vec![0u8; DOWNLOAD_BUFFER_BYTES];
+        loop {
+            match reader.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(n) => yield Ok(Bytes::copy_from_slice(&buffer[..n])),
+                Err(e) => {
+                    yield Err(e);
+                    return;
+                }
+            }
+        }
+        let failure = match producer.await {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => e.to_string(),
+            Err(e) => e.to_string(),
+        };
+        // Too late for an error status; aborting the body tells the client
+        error!("Download failed after it started: {}", failure);
+        yield Err(std::io::Error::other(failure));
+    };
+    Ok(Body::from_stream(stream))
+}
+
+/// Writer for a body of a known length that holds back its final byte
+/// until [`finish`](Self::finish)
+pub struct HeldBackWriter<W> {
+    inner: W,
+    /// Body bytes not yet sent or held
+    remaining: u64,
+    held: Option<u8>,
+}
+
+impl<W: AsyncWrite + Unpin> HeldBackWriter<W> {
+    /// A writer for exactly `content_length` bytes
+    pub fn new(inner: W, content_length: u64) -> Self {
+        Self {
+            inner,
+            remaining: content_length,
+            held: None,
+        }
+    }
+
+    /// Sends the held byte, completing the body
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the body was short or the write failed.
+    pub async fn finish(mut self) -> std::io::Result<()> {
+        if self.remaining > 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("body ended {} bytes short", self.remaining),
+            ));
+        }
+        if let Some(byte) = self.held.take() {
+            self.inner.write_all(&[byte]).await?;
+        }
+        self.inner.flush().await
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for HeldBackWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if buf.len() as u64 > this.remaining {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "body is longer than its Content-Length",
+            )));
+        }
+
+        // The write that reaches the end keeps its last byte back
+        let final_write = buf.len() as u64 == this.remaining;
+        let sendable = if final_write { &buf[..buf.len() - 1] } else { buf };
+        if sendable.is_empty() {
+            this.held = Some(buf[0]);
+            this.remaining = 0;
+            return Poll::Ready(Ok(1));
+        }
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, sendable))?;
+        this.remaining -= n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    /// Only flushes; the body is completed by `finish`
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+/// Reads all of `body`, which must arrive within `timeout`
+///
+/// # Errors
+///
+/// Returns 413 for a body over `limit` bytes, 408 for one still incomplete
+/// after `timeout`, or 400 if reading it failed.
+pub async fn read_body(body: Body, limit: usize, timeout: Duration) -> Result<Vec<u8>, ApiError> {
+    let too_large = || ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large");
+    // A declared Content-Length over the limit is refused before reading
+    if HttpBody::size_hint(&body).lower() > limit as u64 {
+        return Err(too_large());
+    }
+    let read = async {
+        let mut chunks = body.into_data_stream();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk
+                .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("Failed to read request body: {}", e)))?;
+            if bytes.len() + chunk.len() > limit {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    };
+    tokio::time::timeout(timeout, read)
+        .await
+        .map_err(|_| ApiError::new(StatusCode::REQUEST_TIMEOUT, "Timed out reading the request body"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(body: Body) -> Result<Vec<u8>, String> {
+        axum::body::to_bytes(body, usize::MAX)
+            .await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|e| e.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_held_back_writer_completes_only_when_finished() {
+        let mut body = HeldBackWriter::new(Vec::new(), 5);
+        body.write_all(b"hel").await.unwrap();
+        body.write_all(b"lo").await.unwrap();
+        assert_eq!(body.inner, b"hell", "last byte is held back");
+
+        let mut sent = Vec::new();
+        let mut body = HeldBackWriter::new(&mut sent, 5);
+        body.write_all(b"hello").await.unwrap();
+        assert!(body.write_all(b"!").await.is_err());
+        body.finish().await.unwrap();
+        assert_eq!(sent, b"hello");
+
+        let mut sent = Vec::new();
+        let body = HeldBackWriter::new(&mut sent, 2);
+        assert!(body.finish().await.is_err(), "a short body is not completed");
+    }
+
+    #[tokio::test]
+    async fn test_held_back_body_reports_early_failures_and_aborts_late_ones() {
+        let body = held_back_body(5, |mut writer| async move {
+            writer.write_all(b"hello").await.unwrap();
+            writer.finish().await.map_err(|e| PipelineError::IoError(e.to_string()))
+        })
+        .await
+        .unwrap();
+        assert_eq!(collect(body).await.unwrap(), b"hello");
+
+        let refused = held_back_body(5, |_| async {
+            Err(PipelineError::IntegrityError("checksum mismatch".to_string()))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(refused.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = held_back_body(5, |mut writer| async move {
+            writer.write_all(b"hello").await.unwrap();
+            Err(PipelineError::IntegrityError("checksum mismatch".to_string()))
+        })
+        .await
+        .unwrap();
+        assert!(collect(body).await.is_err(), "a late failure aborts the body");
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_json_with_the_matching_status() {
+        let response = ApiError::from_error(&PipelineError::PipelineNotFound("x".to_string())).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = collect(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error"].as_str().unwrap().contains('x'));
+    }
+}
@@ -0,0 +1,26 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Test Utilities
+//!
+//! Support for testing code built on the pipeline, enabled with the
+//! `test-util` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! adaptive-pipeline = { version = "2", features = ["test-util"] }
+//! ```
+//!
+//! - [`chaos`]: failure-injecting decorators for file I/O and stages
+//! - [`generators`]: proptest strategies for stage combinations, chunk sizes
+//!   and file contents
+//! - [`mocks`]: in-memory mocks of the domain ports (pipeline repository,
+//!   file I/O, stage executor, compression and encryption)
+
+pub mod chaos;
+pub mod generators;
+pub mod mocks;
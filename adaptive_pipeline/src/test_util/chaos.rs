@@ -0,0 +1,548 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Failure Injection
+//!
+//! Decorators that make the pipeline fail on purpose, so retry and recovery
+//! paths can be tested deterministically instead of waiting for a flaky disk
+//! or a buggy codec:
+//!
+//! - [`ChaosFileIO`] wraps a [`FileIOService`] and fails or corrupts chunk
+//!   reads and writes
+//! - [`FailingStageService`] wraps a [`StageService`] and fails, corrupts or
+//!   stalls the chunks it processes
+//!
+//! Both take a [`FailurePlan`] that says which chunks fail, how, and how
+//! often. A plan triggers on explicit chunk indices, on a seeded failure rate,
+//! or both; the rate is a pure function of the seed and the chunk index, so
+//! the same chunks fail no matter how the workers are scheduled.
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::test_util::chaos::{FailingStageService, FailureKind, FailurePlan};
+//!
+//! // Panic on chunk 2 once; a worker restart budget of 1 then recovers.
+//! let plan = Arc::new(FailurePlan::new(FailureKind::Panic).at_chunk(2).with_limit(1));
+//! let use_case = ProcessFileUseCase::builder()
+//!     .stage_service("chaos", Arc::new(FailingStageService::pass_through(plan.clone())))
+//!     .build()
+//!     .await?;
+//! ```
+//!
+//! Plans are shared through an `Arc` so the test can read
+//! [`FailurePlan::injected`] after the run.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use adaptive_pipeline_domain::entities::{Operation, ProcessingContext, StageConfiguration, StagePosition, StageType};
+use adaptive_pipeline_domain::services::file_io_service::{
+    FileIOConfig, FileIOService, FileIOStats, FileInfo, ReadOptions, ReadResult, WriteOptions, WriteResult,
+};
+use adaptive_pipeline_domain::services::StageService;
+use adaptive_pipeline_domain::value_objects::FileChunk;
+use adaptive_pipeline_domain::PipelineError;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+
+use crate::infrastructure::services::PassThroughService;
+
+/// How an injected failure shows up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Returns [`PipelineError::IoError`]
+    Io,
+    /// Returns [`PipelineError::TimeoutError`]
+    Timeout,
+    /// Returns [`PipelineError::ProcessingFailed`]
+    Processing,
+    /// Succeeds with the chunk's first byte inverted
+    Corruption,
+    /// Panics, like a stage or driver with a bug
+    Panic,
+    /// Blocks the calling thread this long, then succeeds, like a stage stuck
+    /// on a slow device
+    Stall(std::time::Duration),
+}
+
+/// Which chunks fail, how, and how many times
+///
+/// A new plan never fails; add triggers with [`at_chunk`](Self::at_chunk)
+/// and [`with_rate`](Self::with_rate). Every call that reaches a triggered
+/// chunk counts as a match, so a retried chunk matches again; use
+/// [`skip_first`](Self::skip_first) and [`with_limit`](Self::with_limit) to
+/// pick which matches actually fail.
+#[derive(Debug)]
+pub struct FailurePlan {
+    kind: FailureKind,
+    chunks: BTreeSet<u64>,
+    rate: f64,
+    seed: u64,
+    skip: u64,
+    limit: Option<u64>,
+    matches: AtomicU64,
+    injected: AtomicU64,
+}
+
+impl FailurePlan {
+    /// Creates a plan that injects `kind` failures once triggers are added
+    pub fn new(kind: FailureKind) -> Self {
+        Self {
+            kind,
+            chunks: BTreeSet::new(),
+            rate: 0.0,
+            seed: 0,
+            skip: 0,
+            limit: None,
+            matches: AtomicU64::new(0),
+            injected: AtomicU64::new(0),
+        }
+    }
+
+    /// Triggers on the chunk with zero-based index `index`
+    pub fn at_chunk(mut self, index: u64) -> Self {
+        self.chunks.insert(index);
+        self
+    }
+
+    /// Also triggers on a `rate` fraction of all chunks, chosen by `seed`
+    ///
+    /// `rate` is clamped to `0.0..=1.0`.
+    pub fn with_rate(mut self, rate: f64, seed: u64) -> Self {
+        self.rate = rate.clamp(0.0, 1.0);
+        self.seed = seed;
+        self
+    }
+
+    /// Lets the first `matches` triggered calls succeed
+    ///
+    /// Useful when a chunk is read more than once, e.g. by the checksum pass
+    /// before the workers read it.
+    pub fn skip_first(mut self, matches: u64) -> Self {
+        self.skip = matches;
+        self
+    }
+
+    /// Stops injecting after `failures` failures, so a retry can succeed
+    pub fn with_limit(mut self, failures: u64) -> Self {
+        self.limit = Some(failures);
+        self
+    }
+
+    /// The kind of failure this plan injects
+    pub fn kind(&self) -> FailureKind {
+        self.kind
+    }
+
+    /// Number of failures injected so far
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::SeqCst)
+    }
+
+    /// Whether the chunk with index `index` is one of the plan's targets,
+    /// before `skip_first` and `with_limit` are applied
+    pub fn targets(&self, index: u64) -> bool {
+        self.chunks.contains(&index) || (self.rate > 0.0 && unit_interval(self.seed, index) < self.rate)
+    }
+
+    /// Records a call for chunk `index` and decides whether it fails
+    pub fn should_fail(&self, index: u64) -> bool {
+        if !self.targets(index) || self.matches.fetch_add(1, Ordering::SeqCst) < self.skip {
+            return false;
+        }
+        match self.limit {
+            Some(limit) => self
+                .injected
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |injected| {
+                    (injected < limit).then_some(injected + 1)
+                })
+                .is_ok(),
+            None => {
+                self.injected.fetch_add(1, Ordering::SeqCst);
+                true
+            }
+        }
+    }
+
+    /// Passes `chunk` through, or fails it at `site` if the plan says so
+    ///
+    /// `index` is the chunk's position in the file, which is not always its
+    /// sequence number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk fails and the plan's kind is
+    /// [`FailureKind::Panic`].
+    pub fn apply(&self, site: &str, index: u64, chunk: FileChunk) -> Result<FileChunk, PipelineError> {
+        if !self.should_fail(index) {
+            return Ok(chunk);
+        }
+        let message = format!("injected {:?} failure in {} at chunk {}", self.kind, site, index);
+        match self.kind {
+            FailureKind::Io => Err(PipelineError::io_error(message)),
+            FailureKind::Timeout => Err(PipelineError::TimeoutError(message)),
+            FailureKind::Processing => Err(PipelineError::processing_failed(message)),
+            FailureKind::Corruption => {
+                let mut data = chunk.data().to_vec();
+                if let Some(first) = data.first_mut() {
+                    *first = !*first;
+                }
+                chunk.with_data(data)
+            }
+            FailureKind::Panic => panic!("{}", message),
+            FailureKind::Stall(duration) => {
+                std::thread::sleep(duration);
+                Ok(chunk)
+            }
+        }
+    }
+}
+
+/// Maps `(seed, index)` to a well-mixed value in `0.0..1.0` (SplitMix64)
+fn unit_interval(seed: u64, index: u64) -> f64 {
+    let mut z = seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// [`FileIOService`] decorator that injects failures into chunk reads and
+/// writes
+///
+/// Chunks are identified by their offset divided by `chunk_size`, so pass the
+/// chunk size the run will use. Whole-buffer writes through
+/// `write_file_data` count as chunk 0. Metadata operations always delegate.
+pub struct ChaosFileIO {
+    inner: Arc<dyn FileIOService>,
+    chunk_size: u64,
+    read_failures: Option<Arc<FailurePlan>>,
+    write_failures: Option<Arc<FailurePlan>>,
+}
+
+impl ChaosFileIO {
+    /// Wraps `inner`, indexing chunks by `chunk_size` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn new(inner: Arc<dyn FileIOService>, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        Self {
+            inner,
+            chunk_size: chunk_size as u64,
+            read_failures: None,
+            write_failures: None,
+        }
+    }
+
+    /// Applies `plan` to every chunk read
+    pub fn with_read_failures(mut self, plan: Arc<FailurePlan>) -> Self {
+        self.read_failures = Some(plan);
+        self
+    }
+
+    /// Applies `plan` to every chunk written
+    pub fn with_write_failures(mut self, plan: Arc<FailurePlan>) -> Self {
+        self.write_failures = Some(plan);
+        self
+    }
+
+    fn index_of(&self, chunk: &FileChunk) -> u64 {
+        chunk.offset() / self.chunk_size
+    }
+
+    fn apply(&self, plan: &Option<Arc<FailurePlan>>, site: &str, chunk: FileChunk) -> Result<FileChunk, PipelineError> {
+        match plan {
+            Some(plan) => plan.apply(site, self.index_of(&chunk), chunk),
+            None => Ok(chunk),
+        }
+    }
+
+    fn apply_to_read(&self, site: &str, mut result: ReadResult) -> Result<ReadResult, PipelineError> {
+        result.chunks = result
+            .chunks
+            .into_iter()
+            .map(|chunk| self.apply(&self.read_failures, site, chunk))
+            .collect::<Result<_, _>>()?;
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl FileIOService for ChaosFileIO {
+    async fn read_file_chunks(&self, path: &Path, options: ReadOptions) -> Result<ReadResult, PipelineError> {
+        let result = self.inner.read_file_chunks(path, options).await?;
+        self.apply_to_read("read_file_chunks", result)
+    }
+
+    async fn read_file_mmap(&self, path: &Path, options: ReadOptions) -> Result<ReadResult, PipelineError> {
+        let result = self.inner.read_file_mmap(path, options).await?;
+        self.apply_to_read("read_file_mmap", result)
+    }
+
+    async fn write_file_chunks(
+        &self,
+        path: &Path,
+        chunks: &[FileChunk],
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        let chunks = chunks
+            .iter()
+            .map(|chunk| self.apply(&self.write_failures, "write_file_chunks", chunk.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.write_file_chunks(path, &chunks, options).await
+    }
+
+    async fn write_file_data(
+        &self,
+        path: &Path,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        match &self.write_failures {
+            Some(plan) if !data.is_empty() => {
+                let chunk = plan.apply("write_file_data", 0, FileChunk::new(0, 0, data.to_vec(), true)?)?;
+                self.inner.write_file_data(path, chunk.data(), options).await
+            }
+            _ => self.inner.write_file_data(path, data, options).await,
+        }
+    }
+
+    async fn get_file_info(&self, path: &Path) -> Result<FileInfo, PipelineError> {
+        self.inner.get_file_info(path).await
+    }
+
+    async fn file_exists(&self, path: &Path) -> Result<bool, PipelineError> {
+        self.inner.file_exists(path).await
+    }
+
+    async fn delete_file(&self, path: &Path) -> Result<(), PipelineError> {
+        self.inner.delete_file(path).await
+    }
+
+    async fn copy_file(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.inner.copy_file(source, destination, options).await
+    }
+
+    async fn move_file(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.inner.move_file(source, destination, options).await
+    }
+
+    async fn create_directory(&self, path: &Path) -> Result<(), PipelineError> {
+        self.inner.create_directory(path).await
+    }
+
+    async fn directory_exists(&self, path: &Path) -> Result<bool, PipelineError> {
+        self.inner.directory_exists(path).await
+    }
+
+    async fn list_directory(&self, path: &Path) -> Result<Vec<FileInfo>, PipelineError> {
+        self.inner.list_directory(path).await
+    }
+
+    fn get_config(&self) -> FileIOConfig {
+        self.inner.get_config()
+    }
+
+    fn update_config(&mut self, _config: FileIOConfig) {
+        // The wrapped service is shared; configure it before wrapping
+    }
+
+    fn get_stats(&self) -> FileIOStats {
+        self.inner.get_stats()
+    }
+
+    fn reset_stats(&mut self) {
+        // The wrapped service is shared; reset it directly
+    }
+
+    async fn validate_file_integrity(&self, path: &Path, expected_checksum: &str) -> Result<bool, PipelineError> {
+        self.inner.validate_file_integrity(path, expected_checksum).await
+    }
+
+    async fn calculate_file_checksum(&self, path: &Path) -> Result<String, PipelineError> {
+        self.inner.calculate_file_checksum(path).await
+    }
+
+    async fn stream_file_chunks(
+        &self,
+        path: &Path,
+        options: ReadOptions,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<FileChunk, PipelineError>> + Send>>, PipelineError> {
+        let stream = self.inner.stream_file_chunks(path, options).await?;
+        let Some(plan) = self.read_failures.clone() else {
+            return Ok(stream);
+        };
+        let chunk_size = self.chunk_size;
+        Ok(Box::pin(stream.map(move |chunk| {
+            chunk.and_then(|chunk| plan.apply("stream_file_chunks", chunk.offset() / chunk_size, chunk))
+        })))
+    }
+
+    async fn write_chunk_to_file(
+        &self,
+        path: &Path,
+        chunk: &FileChunk,
+        options: WriteOptions,
+        is_first_chunk: bool,
+    ) -> Result<WriteResult, PipelineError> {
+        let chunk = self.apply(&self.write_failures, "write_chunk_to_file", chunk.clone())?;
+        self.inner
+            .write_chunk_to_file(path, &chunk, options, is_first_chunk)
+            .await
+    }
+}
+
+/// [`StageService`] decorator that injects failures before the wrapped stage
+/// runs
+///
+/// Chunks are identified by their sequence number. A corrupted chunk is
+/// handed to the wrapped stage, so the damage is carried into the archive the
+/// way a faulty stage would carry it.
+pub struct FailingStageService {
+    inner: Arc<dyn StageService>,
+    plan: Arc<FailurePlan>,
+}
+
+impl FailingStageService {
+    /// Wraps `inner`
+    pub fn new(inner: Arc<dyn StageService>, plan: Arc<FailurePlan>) -> Self {
+        Self { inner, plan }
+    }
+
+    /// A stage that leaves data unchanged except where `plan` fails it
+    pub fn pass_through(plan: Arc<FailurePlan>) -> Self {
+        Self::new(Arc::new(PassThroughService::new()), plan)
+    }
+}
+
+impl StageService for FailingStageService {
+    fn process_chunk(
+        &self,
+        chunk: FileChunk,
+        operation: Operation,
+        config: &StageConfiguration,
+        context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        let index = chunk.sequence_number();
+        let chunk = self
+            .plan
+            .apply(&format!("stage '{}'", config.algorithm), index, chunk)?;
+        self.inner.process_chunk(chunk, operation, config, context)
+    }
+
+    fn position(&self) -> StagePosition {
+        self.inner.position()
+    }
+
+    fn is_reversible(&self) -> bool {
+        self.inner.is_reversible()
+    }
+
+    fn stage_type(&self) -> StageType {
+        self.inner.stage_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_honors_skip_limit_and_seeded_rate() {
+        let plan = FailurePlan::new(FailureKind::Io)
+            .at_chunk(3)
+            .skip_first(1)
+            .with_limit(1);
+        assert!(!plan.should_fail(2));
+        assert!(!plan.should_fail(3), "first match is skipped");
+        assert!(plan.should_fail(3));
+        assert!(!plan.should_fail(3), "limit reached");
+        assert_eq!(plan.injected(), 1);
+
+        let seeded = |seed| {
+            let plan = FailurePlan::new(FailureKind::Io).with_rate(0.25, seed);
+            (0..1000).filter(|&index| plan.targets(index)).collect::<Vec<_>>()
+        };
+        let targets = seeded(7);
+        assert_eq!(targets, seeded(7));
+        assert_ne!(targets, seeded(8));
+        assert!(
+            (200..300).contains(&targets.len()),
+            "{} of 1000 targeted",
+            targets.len()
+        );
+    }
+
+    #[test]
+    fn test_plan_injects_each_kind() {
+        let chunk = || FileChunk::new(5, 0, vec![0x0F, 1, 2], true).unwrap();
+        let apply = |kind| FailurePlan::new(kind).at_chunk(5).apply("test", 5, chunk());
+
+        assert!(matches!(apply(FailureKind::Io), Err(PipelineError::IoError(_))));
+        assert!(matches!(
+            apply(FailureKind::Timeout),
+            Err(PipelineError::TimeoutError(_))
+        ));
+        assert!(matches!(
+            apply(FailureKind::Processing),
+            Err(PipelineError::ProcessingFailed(_))
+        ));
+        assert_eq!(apply(FailureKind::Corruption).unwrap().data(), &[0xF0, 1, 2]);
+        let panic = std::panic::catch_unwind(|| apply(FailureKind::Panic)).unwrap_err();
+        assert!(panic.downcast_ref::<String>().unwrap().contains("at chunk 5"));
+    }
+
+    #[tokio::test]
+    async fn test_chaos_file_io_fails_the_targeted_read() {
+        use crate::infrastructure::adapters::file_io::TokioFileIO;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("input.bin");
+        std::fs::write(&path, vec![7u8; 4096]).unwrap();
+        let plan = Arc::new(FailurePlan::new(FailureKind::Io).at_chunk(2));
+        let chaos = ChaosFileIO::new(Arc::new(TokioFileIO::new_default()), 1024).with_read_failures(plan.clone());
+        let read = |offset| ReadOptions {
+            chunk_size: Some(1024),
+            start_offset: Some(offset),
+            max_bytes: Some(1024),
+            ..Default::default()
+        };
+
+        assert!(chaos.read_file_chunks(&path, read(1024)).await.is_ok());
+        let err = chaos.read_file_chunks(&path, read(2048)).await.unwrap_err();
+        assert!(err.to_string().contains("read_file_chunks at chunk 2"), "{}", err);
+
+        let streamed: Vec<_> = chaos
+            .stream_file_chunks(
+                &path,
+                ReadOptions {
+                    chunk_size: Some(1024),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(streamed.iter().filter(|chunk| chunk.is_err()).count(), 1);
+        assert_eq!(plan.injected(), 2);
+    }
+}
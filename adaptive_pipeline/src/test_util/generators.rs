@@ -0,0 +1,150 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Proptest Generators
+//!
+//! Strategies for property-based round-trip tests. The crate's own suite
+//! runs every generated stage combination through process → restore and
+//! checks the restored bytes and the archive metadata; plugin authors can
+//! mix their own stages into the same combinations with [`stages_from`]:
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::test_util::generators::{chunk_sizes, compression_stage, file_contents, stage, stages_from};
+//! use proptest::prelude::*;
+//!
+//! let rot13 = stage("rot13", StageType::Transform, "rot13", HashMap::new());
+//! let strategy = compression_stage().prop_flat_map(move |compression| {
+//!     (stages_from(vec![compression, rot13.clone()], 2), chunk_sizes(), file_contents(64 * 1024))
+//! });
+//! ```
+//!
+//! Stages are returned without a meaningful order; `Pipeline::new` numbers
+//! them by position.
+
+use std::collections::HashMap;
+
+use adaptive_pipeline_domain::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+use adaptive_pipeline_domain::value_objects::ChunkSize;
+use proptest::prelude::*;
+
+/// Compression algorithms with a working codec in this build
+const COMPRESSION_ALGORITHMS: [&str; 4] = ["brotli", "gzip", "zstd", "xz"];
+
+/// Builds a stage the way `create` would, recording `algorithm` among its
+/// parameters
+///
+/// # Panics
+///
+/// Panics if `name` or `algorithm` is empty.
+pub fn stage(
+    name: &str,
+    stage_type: StageType,
+    algorithm: &str,
+    mut parameters: HashMap<String, String>,
+) -> PipelineStage {
+    parameters.insert("algorithm".to_string(), algorithm.to_string());
+    PipelineStage::new(
+        name.to_string(),
+        stage_type,
+        StageConfiguration::new(algorithm.to_string(), parameters, false),
+        0,
+    )
+    .expect("generated stage has a name and an algorithm")
+}
+
+/// A compression stage with a random algorithm and level
+pub fn compression_stage() -> impl Strategy<Value = PipelineStage> {
+    (prop::sample::select(COMPRESSION_ALGORITHMS.to_vec()), 1u32..=6).prop_map(|(algorithm, level)| {
+        let parameters = HashMap::from([("level".to_string(), level.to_string())]);
+        stage("compression", StageType::Compression, algorithm, parameters)
+    })
+}
+
+/// A reversible built-in transform: base64 or pass-through
+pub fn transform_stage() -> impl Strategy<Value = PipelineStage> {
+    prop_oneof![
+        Just(stage("base64", StageType::Transform, "base64", HashMap::new())),
+        Just(stage(
+            "passthrough",
+            StageType::PassThrough,
+            "passthrough",
+            HashMap::new()
+        )),
+    ]
+}
+
+/// Ordered combinations of one to `max_stages` of `candidates`
+///
+/// Each candidate is used at most once. Combinations `Pipeline::new`
+/// rejects, such as two adjacent compression stages, are filtered out.
+///
+/// # Panics
+///
+/// Panics if `candidates` is empty or `max_stages` is zero.
+pub fn stages_from(candidates: Vec<PipelineStage>, max_stages: usize) -> impl Strategy<Value = Vec<PipelineStage>> {
+    assert!(!candidates.is_empty(), "stages_from needs at least one candidate");
+    assert!(max_stages > 0, "stages_from needs room for at least one stage");
+    let max_stages = max_stages.min(candidates.len());
+
+    prop::sample::subsequence(candidates, 1..=max_stages)
+        .prop_shuffle()
+        .prop_filter("adjacent stages must be compatible", |stages| {
+            Pipeline::new("generated".to_string(), stages.clone()).is_ok()
+        })
+}
+
+/// Combinations of the reversible built-in stages: up to two compression
+/// stages and the built-in transforms
+pub fn builtin_stages() -> impl Strategy<Value = Vec<PipelineStage>> {
+    (compression_stage(), compression_stage()).prop_flat_map(|(first, second)| {
+        let candidates = vec![
+            first,
+            second,
+            stage("base64", StageType::Transform, "base64", HashMap::new()),
+            stage("passthrough", StageType::PassThrough, "passthrough", HashMap::new()),
+        ];
+        stages_from(candidates, 3)
+    })
+}
+
+/// Chunk sizes from 1 KiB to 64 KiB, half of them whole kibibytes
+pub fn chunk_sizes() -> impl Strategy<Value = ChunkSize> {
+    prop_oneof![(1usize..=64).prop_map(|kb| kb * 1024), 1024usize..=64 * 1024]
+        .prop_map(|bytes| ChunkSize::new(bytes).expect("generated chunk size is within bounds"))
+}
+
+/// File contents of up to `max_len` bytes: random (incompressible), a
+/// repeated short pattern (compressible) or a single repeated byte
+pub fn file_contents(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..=max_len),
+        (prop::collection::vec(any::<u8>(), 1..=16), 0..=max_len).prop_map(|(pattern, len)| pattern
+            .iter()
+            .cycle()
+            .take(len)
+            .copied()
+            .collect()),
+        (any::<u8>(), 0..=max_len).prop_map(|(byte, len)| vec![byte; len]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    #[test]
+    fn test_generated_stages_form_valid_pipelines() {
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let stages = builtin_stages().new_tree(&mut runner).unwrap().current();
+            assert!((1..=3).contains(&stages.len()));
+            assert!(Pipeline::new("generated".to_string(), stages).is_ok());
+        }
+    }
+}
@@ -0,0 +1,1039 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Port Mocks
+//!
+//! In-memory implementations of the domain ports for unit-testing code that
+//! embeds the pipeline without a database, a file system or real codecs:
+//!
+//! - [`MockPipelineRepository`]: pipelines kept in memory
+//! - [`MockFileIOService`]: files kept in a map, chunked like the real reader
+//! - [`MockStageExecutor`]: runs no stage, returns chunks unchanged
+//! - [`MockCompressionService`] / [`MockEncryptionService`]: identity codecs
+//!
+//! Every mock records the port methods called on it, in order, and can be
+//! told to fail upcoming calls:
+//!
+//! ```rust,ignore
+//! use adaptive_pipeline::test_util::mocks::MockPipelineRepository;
+//!
+//! let repository = MockPipelineRepository::new().with_pipeline(pipeline.clone());
+//! repository.fail_next(PipelineError::database_error("connection lost"));
+//! assert!(my_service(&repository).await.is_err());
+//! assert_eq!(repository.calls(), vec!["find_by_name"]);
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::{Duration, SystemTime};
+
+use adaptive_pipeline_domain::entities::{
+    Operation, Pipeline, PipelineStage, ProcessingContext, SecurityContext, StageConfiguration, StagePosition,
+    StageType,
+};
+use adaptive_pipeline_domain::repositories::stage_executor::ResourceRequirements;
+use adaptive_pipeline_domain::repositories::{PipelineRepository, StageExecutor};
+use adaptive_pipeline_domain::services::compression_service::{
+    CompressionAlgorithm, CompressionBenchmark, CompressionConfig, CompressionPriority, CompressionService,
+};
+use adaptive_pipeline_domain::services::encryption_service::{
+    EncryptionAlgorithm, EncryptionConfig, EncryptionService, KeyMaterial,
+};
+use adaptive_pipeline_domain::services::file_io_service::{
+    FileIOConfig, FileIOService, FileIOStats, FileInfo, ReadOptions, ReadResult, WriteOptions, WriteResult,
+};
+use adaptive_pipeline_domain::services::StageService;
+use adaptive_pipeline_domain::value_objects::{EncryptionBenchmark, FileChunk, PipelineId, SecretBytes};
+use adaptive_pipeline_domain::PipelineError;
+use async_trait::async_trait;
+use futures::Stream;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+
+/// Call log and scripted failures shared by the mocks
+#[derive(Default)]
+struct Script {
+    calls: Mutex<Vec<&'static str>>,
+    failures: Mutex<VecDeque<PipelineError>>,
+}
+
+impl Script {
+    /// Records a call to `method`, failing it if a failure is queued
+    fn enter(&self, method: &'static str) -> Result<(), PipelineError> {
+        self.calls.lock().push(method);
+        match self.failures.lock().pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn calls(&self) -> Vec<&'static str> {
+        self.calls.lock().clone()
+    }
+
+    fn fail_next(&self, error: PipelineError) {
+        self.failures.lock().push_back(error);
+    }
+}
+
+/// Implements the call-log accessors on a mock with a `script` field
+macro_rules! scripted {
+    ($mock:ty) => {
+        impl $mock {
+            /// Port methods called so far, in order
+            pub fn calls(&self) -> Vec<&'static str> {
+                self.script.calls()
+            }
+
+            /// Makes the next port method call fail with `error`; calling
+            /// this repeatedly fails that many calls in order
+            pub fn fail_next(&self, error: PipelineError) {
+                self.script.fail_next(error);
+            }
+        }
+    };
+}
+
+/// [`PipelineRepository`] that keeps pipelines in memory
+///
+/// Archiving hides a pipeline from the active queries and moves it to
+/// `list_archived`; the stored pipeline itself is returned unchanged.
+#[derive(Default)]
+pub struct MockPipelineRepository {
+    script: Script,
+    pipelines: Mutex<Vec<Pipeline>>,
+    archived: Mutex<HashSet<PipelineId>>,
+}
+
+scripted!(MockPipelineRepository);
+
+impl MockPipelineRepository {
+    /// Creates an empty repository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `pipeline` up front, without recording a call
+    pub fn with_pipeline(self, pipeline: Pipeline) -> Self {
+        self.pipelines.lock().push(pipeline);
+        self
+    }
+
+    fn active(&self) -> Vec<Pipeline> {
+        let archived = self.archived.lock();
+        self.pipelines
+            .lock()
+            .iter()
+            .filter(|pipeline| !archived.contains(pipeline.id()))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl PipelineRepository for MockPipelineRepository {
+    async fn save(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        self.script.enter("save")?;
+        let mut pipelines = self.pipelines.lock();
+        if pipelines.iter().any(|stored| stored.id() == pipeline.id()) {
+            return Err(PipelineError::database_error(format!(
+                "Pipeline {} already exists",
+                pipeline.id()
+            )));
+        }
+        pipelines.push(pipeline.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: PipelineId) -> Result<Option<Pipeline>, PipelineError> {
+        self.script.enter("find_by_id")?;
+        Ok(self.active().into_iter().find(|pipeline| pipeline.id() == &id))
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Pipeline>, PipelineError> {
+        self.script.enter("find_by_name")?;
+        Ok(self.active().into_iter().find(|pipeline| pipeline.name() == name))
+    }
+
+    async fn list_all(&self) -> Result<Vec<Pipeline>, PipelineError> {
+        self.script.enter("list_all")?;
+        Ok(self.active())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Pipeline>, PipelineError> {
+        self.script.enter("find_all")?;
+        Ok(self.active())
+    }
+
+    async fn list_paginated(&self, offset: usize, limit: usize) -> Result<Vec<Pipeline>, PipelineError> {
+        self.script.enter("list_paginated")?;
+        Ok(self.active().into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn update(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        self.script.enter("update")?;
+        let mut pipelines = self.pipelines.lock();
+        match pipelines.iter_mut().find(|stored| stored.id() == pipeline.id()) {
+            Some(stored) => {
+                *stored = pipeline.clone();
+                Ok(())
+            }
+            None => Err(PipelineError::PipelineNotFound(pipeline.id().to_string())),
+        }
+    }
+
+    async fn delete(&self, id: PipelineId) -> Result<bool, PipelineError> {
+        self.script.enter("delete")?;
+        if self.archived.lock().contains(&id) {
+            return Ok(false);
+        }
+        let mut pipelines = self.pipelines.lock();
+        let before = pipelines.len();
+        pipelines.retain(|pipeline| pipeline.id() != &id);
+        Ok(pipelines.len() < before)
+    }
+
+    async fn exists(&self, id: PipelineId) -> Result<bool, PipelineError> {
+        self.script.enter("exists")?;
+        Ok(self.active().iter().any(|pipeline| pipeline.id() == &id))
+    }
+
+    async fn count(&self) -> Result<usize, PipelineError> {
+        self.script.enter("count")?;
+        Ok(self.active().len())
+    }
+
+    async fn find_by_config(&self, key: &str, value: &str) -> Result<Vec<Pipeline>, PipelineError> {
+        self.script.enter("find_by_config")?;
+        Ok(self
+            .active()
+            .into_iter()
+            .filter(|pipeline| pipeline.configuration().get(key).map(String::as_str) == Some(value))
+            .collect())
+    }
+
+    async fn archive(&self, id: PipelineId) -> Result<bool, PipelineError> {
+        self.script.enter("archive")?;
+        let stored = self.pipelines.lock().iter().any(|pipeline| pipeline.id() == &id);
+        Ok(stored && self.archived.lock().insert(id))
+    }
+
+    async fn restore(&self, id: PipelineId) -> Result<bool, PipelineError> {
+        self.script.enter("restore")?;
+        Ok(self.archived.lock().remove(&id))
+    }
+
+    async fn list_archived(&self) -> Result<Vec<Pipeline>, PipelineError> {
+        self.script.enter("list_archived")?;
+        let archived = self.archived.lock();
+        Ok(self
+            .pipelines
+            .lock()
+            .iter()
+            .filter(|pipeline| archived.contains(pipeline.id()))
+            .cloned()
+            .collect())
+    }
+}
+
+/// [`FileIOService`] backed by an in-memory file system
+///
+/// Reads are chunked by `ReadOptions::chunk_size`, falling back to the
+/// configured default chunk size, with the same sequence numbers and offsets
+/// as the real reader. Directories exist once created or once a file is
+/// written below them.
+pub struct MockFileIOService {
+    script: Script,
+    files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+    directories: Mutex<HashSet<PathBuf>>,
+    config: FileIOConfig,
+    stats: Mutex<FileIOStats>,
+}
+
+scripted!(MockFileIOService);
+
+impl Default for MockFileIOService {
+    fn default() -> Self {
+        Self {
+            script: Script::default(),
+            files: Mutex::new(HashMap::new()),
+            directories: Mutex::new(HashSet::new()),
+            config: FileIOConfig::default(),
+            stats: Mutex::new(FileIOStats::default()),
+        }
+    }
+}
+
+impl MockFileIOService {
+    /// Creates an empty file system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates `path` with `data` up front, without recording a call
+    pub fn with_file(self, path: impl Into<PathBuf>, data: impl Into<Vec<u8>>) -> Self {
+        self.store(path.into(), data.into());
+        self
+    }
+
+    /// Contents of `path`, if it exists
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.files.lock().get(path.as_ref()).cloned()
+    }
+
+    fn store(&self, path: PathBuf, data: Vec<u8>) {
+        let mut directories = self.directories.lock();
+        for ancestor in path.ancestors().skip(1) {
+            directories.insert(ancestor.to_path_buf());
+        }
+        self.files.lock().insert(path, data);
+    }
+
+    fn contents(&self, path: &Path) -> Result<Vec<u8>, PipelineError> {
+        self.file(path)
+            .ok_or_else(|| PipelineError::io_error(format!("File not found: {}", path.display())))
+    }
+
+    fn info(path: &Path, size: u64) -> FileInfo {
+        FileInfo {
+            path: path.to_path_buf(),
+            size,
+            is_memory_mapped: false,
+            modified_at: SystemTime::UNIX_EPOCH,
+            created_at: SystemTime::UNIX_EPOCH,
+            permissions: 0o644,
+            mime_type: None,
+        }
+    }
+
+    fn read(&self, path: &Path, options: &ReadOptions) -> Result<ReadResult, PipelineError> {
+        let data = self.contents(path)?;
+        let chunk_size = options.chunk_size.unwrap_or(self.config.default_chunk_size).max(1);
+        let start = (options.start_offset.unwrap_or(0) as usize).min(data.len());
+        let end = match options.max_bytes {
+            Some(max_bytes) => (start + max_bytes as usize).min(data.len()),
+            None => data.len(),
+        };
+
+        let mut chunks = Vec::new();
+        let mut offset = start;
+        while offset < end {
+            let chunk_end = (offset + chunk_size).min(end);
+            chunks.push(FileChunk::new(
+                chunks.len() as u64,
+                offset as u64,
+                data[offset..chunk_end].to_vec(),
+                chunk_end == end,
+            )?);
+            offset = chunk_end;
+        }
+
+        let mut stats = self.stats.lock();
+        stats.bytes_read += (end - start) as u64;
+        stats.chunks_processed += chunks.len() as u64;
+        stats.files_processed += 1;
+        Ok(ReadResult {
+            chunks,
+            file_info: Self::info(path, data.len() as u64),
+            bytes_read: (end - start) as u64,
+            complete: end == data.len(),
+        })
+    }
+
+    fn write(&self, path: &Path, data: &[u8], append: bool) -> WriteResult {
+        let mut contents = if append {
+            self.file(path).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        contents.extend_from_slice(data);
+        self.store(path.to_path_buf(), contents);
+        self.stats.lock().bytes_written += data.len() as u64;
+        WriteResult {
+            path: path.to_path_buf(),
+            bytes_written: data.len() as u64,
+            checksum: Some(format!("{:x}", Sha256::digest(data))),
+            success: true,
+        }
+    }
+}
+
+#[async_trait]
+impl FileIOService for MockFileIOService {
+    async fn read_file_chunks(&self, path: &Path, options: ReadOptions) -> Result<ReadResult, PipelineError> {
+        self.script.enter("read_file_chunks")?;
+        self.read(path, &options)
+    }
+
+    async fn read_file_mmap(&self, path: &Path, options: ReadOptions) -> Result<ReadResult, PipelineError> {
+        self.script.enter("read_file_mmap")?;
+        self.read(path, &options)
+    }
+
+    async fn write_file_chunks(
+        &self,
+        path: &Path,
+        chunks: &[FileChunk],
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.script.enter("write_file_chunks")?;
+        let data: Vec<u8> = chunks.iter().flat_map(|chunk| chunk.data().iter().copied()).collect();
+        Ok(self.write(path, &data, options.append))
+    }
+
+    async fn write_file_data(
+        &self,
+        path: &Path,
+        data: &[u8],
+        options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.script.enter("write_file_data")?;
+        Ok(self.write(path, data, options.append))
+    }
+
+    async fn get_file_info(&self, path: &Path) -> Result<FileInfo, PipelineError> {
+        self.script.enter("get_file_info")?;
+        let size = self.contents(path)?.len() as u64;
+        Ok(Self::info(path, size))
+    }
+
+    async fn file_exists(&self, path: &Path) -> Result<bool, PipelineError> {
+        self.script.enter("file_exists")?;
+        Ok(self.files.lock().contains_key(path))
+    }
+
+    async fn delete_file(&self, path: &Path) -> Result<(), PipelineError> {
+        self.script.enter("delete_file")?;
+        self.files
+            .lock()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| PipelineError::io_error(format!("File not found: {}", path.display())))
+    }
+
+    async fn copy_file(
+        &self,
+        source: &Path,
+        destination: &Path,
+        _options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.script.enter("copy_file")?;
+        let data = self.contents(source)?;
+        Ok(self.write(destination, &data, false))
+    }
+
+    async fn move_file(
+        &self,
+        source: &Path,
+        destination: &Path,
+        _options: WriteOptions,
+    ) -> Result<WriteResult, PipelineError> {
+        self.script.enter("move_file")?;
+        let data = self.contents(source)?;
+        self.files.lock().remove(source);
+        Ok(self.write(destination, &data, false))
+    }
+
+    async fn create_directory(&self, path: &Path) -> Result<(), PipelineError> {
+        self.script.enter("create_directory")?;
+        let mut directories = self.directories.lock();
+        for ancestor in path.ancestors() {
+            directories.insert(ancestor.to_path_buf());
+        }
+        Ok(())
+    }
+
+    async fn directory_exists(&self, path: &Path) -> Result<bool, PipelineError> {
+        self.script.enter("directory_exists")?;
+        Ok(self.directories.lock().contains(path))
+    }
+
+    async fn list_directory(&self, path: &Path) -> Result<Vec<FileInfo>, PipelineError> {
+        self.script.enter("list_directory")?;
+        let mut entries: Vec<FileInfo> = self
+            .files
+            .lock()
+            .iter()
+            .filter(|(file, _)| file.parent() == Some(path))
+            .map(|(file, data)| Self::info(file, data.len() as u64))
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+
+    fn get_config(&self) -> FileIOConfig {
+        self.config.clone()
+    }
+
+    fn update_config(&mut self, config: FileIOConfig) {
+        self.config = config;
+    }
+
+    fn get_stats(&self) -> FileIOStats {
+        self.stats.lock().clone()
+    }
+
+    fn reset_stats(&mut self) {
+        *self.stats.lock() = FileIOStats::default();
+    }
+
+    async fn validate_file_integrity(&self, path: &Path, expected_checksum: &str) -> Result<bool, PipelineError> {
+        self.script.enter("validate_file_integrity")?;
+        let data = self.contents(path)?;
+        self.stats.lock().checksum_verifications += 1;
+        Ok(format!("{:x}", Sha256::digest(&data)) == expected_checksum)
+    }
+
+    async fn calculate_file_checksum(&self, path: &Path) -> Result<String, PipelineError> {
+        self.script.enter("calculate_file_checksum")?;
+        Ok(format!("{:x}", Sha256::digest(self.contents(path)?)))
+    }
+
+    async fn stream_file_chunks(
+        &self,
+        path: &Path,
+        options: ReadOptions,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<FileChunk, PipelineError>> + Send>>, PipelineError> {
+        self.script.enter("stream_file_chunks")?;
+        let chunks = self.read(path, &options)?.chunks;
+        Ok(Box::pin(futures::stream::iter(chunks.into_iter().map(Ok))))
+    }
+
+    async fn write_chunk_to_file(
+        &self,
+        path: &Path,
+        chunk: &FileChunk,
+        options: WriteOptions,
+        is_first_chunk: bool,
+    ) -> Result<WriteResult, PipelineError> {
+        self.script.enter("write_chunk_to_file")?;
+        Ok(self.write(path, chunk.data(), options.append || !is_first_chunk))
+    }
+}
+
+/// [`StageExecutor`] that runs no stage and returns every chunk unchanged
+///
+/// Executions are recorded as `(stage name, chunk sequence number)` pairs.
+/// Stage types outside [`supported_stage_types`](StageExecutor::supported_stage_types)
+/// cannot be executed.
+pub struct MockStageExecutor {
+    script: Script,
+    executions: Mutex<Vec<(String, u64)>>,
+    supported: Vec<String>,
+}
+
+scripted!(MockStageExecutor);
+
+impl Default for MockStageExecutor {
+    fn default() -> Self {
+        Self {
+            script: Script::default(),
+            executions: Mutex::new(Vec::new()),
+            supported: [
+                StageType::Compression,
+                StageType::Encryption,
+                StageType::Transform,
+                StageType::Checksum,
+                StageType::PassThrough,
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        }
+    }
+}
+
+impl MockStageExecutor {
+    /// Creates an executor that supports every stage type
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Supports only `stage_types`
+    pub fn with_supported_stage_types(mut self, stage_types: &[StageType]) -> Self {
+        self.supported = stage_types.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// `(stage name, chunk sequence number)` of every chunk executed so far
+    pub fn executions(&self) -> Vec<(String, u64)> {
+        self.executions.lock().clone()
+    }
+
+    fn supports(&self, stage: &PipelineStage) -> bool {
+        self.supported.contains(&stage.stage_type().to_string())
+    }
+}
+
+#[async_trait]
+impl StageExecutor for MockStageExecutor {
+    async fn execute(
+        &self,
+        stage: &PipelineStage,
+        chunk: FileChunk,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("execute")?;
+        self.executions
+            .lock()
+            .push((stage.name().to_string(), chunk.sequence_number()));
+        Ok(chunk)
+    }
+
+    async fn execute_parallel(
+        &self,
+        stage: &PipelineStage,
+        chunks: Vec<FileChunk>,
+        _context: &mut ProcessingContext,
+    ) -> Result<Vec<FileChunk>, PipelineError> {
+        self.script.enter("execute_parallel")?;
+        self.executions.lock().extend(
+            chunks
+                .iter()
+                .map(|chunk| (stage.name().to_string(), chunk.sequence_number())),
+        );
+        Ok(chunks)
+    }
+
+    async fn can_execute(&self, stage: &PipelineStage) -> Result<bool, PipelineError> {
+        self.script.enter("can_execute")?;
+        Ok(self.supports(stage))
+    }
+
+    fn supported_stage_types(&self) -> Vec<String> {
+        self.supported.clone()
+    }
+
+    async fn estimate_processing_time(
+        &self,
+        _stage: &PipelineStage,
+        _data_size: u64,
+    ) -> Result<Duration, PipelineError> {
+        self.script.enter("estimate_processing_time")?;
+        Ok(Duration::ZERO)
+    }
+
+    async fn get_resource_requirements(
+        &self,
+        _stage: &PipelineStage,
+        _data_size: u64,
+    ) -> Result<ResourceRequirements, PipelineError> {
+        self.script.enter("get_resource_requirements")?;
+        Ok(ResourceRequirements::default())
+    }
+
+    async fn prepare_stage(&self, _stage: &PipelineStage, _context: &ProcessingContext) -> Result<(), PipelineError> {
+        self.script.enter("prepare_stage")
+    }
+
+    async fn cleanup_stage(&self, _stage: &PipelineStage, _context: &ProcessingContext) -> Result<(), PipelineError> {
+        self.script.enter("cleanup_stage")
+    }
+
+    async fn validate_configuration(&self, stage: &PipelineStage) -> Result<(), PipelineError> {
+        self.script.enter("validate_configuration")?;
+        if self.supports(stage) {
+            Ok(())
+        } else {
+            Err(PipelineError::invalid_config(format!(
+                "Unsupported stage type: {}",
+                stage.stage_type()
+            )))
+        }
+    }
+
+    async fn validate_stage_ordering(&self, _stages: &[PipelineStage]) -> Result<(), PipelineError> {
+        self.script.enter("validate_stage_ordering")
+    }
+}
+
+/// [`CompressionService`] whose codec is the identity
+#[derive(Default)]
+pub struct MockCompressionService {
+    script: Script,
+}
+
+scripted!(MockCompressionService);
+
+impl MockCompressionService {
+    /// Creates the service
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StageService for MockCompressionService {
+    fn process_chunk(
+        &self,
+        chunk: FileChunk,
+        _operation: Operation,
+        _config: &StageConfiguration,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("process_chunk")?;
+        Ok(chunk)
+    }
+
+    fn position(&self) -> StagePosition {
+        StagePosition::PreBinary
+    }
+
+    fn is_reversible(&self) -> bool {
+        true
+    }
+
+    fn stage_type(&self) -> StageType {
+        StageType::Compression
+    }
+}
+
+impl CompressionService for MockCompressionService {
+    fn compress_chunk(
+        &self,
+        chunk: FileChunk,
+        _config: &CompressionConfig,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("compress_chunk")?;
+        Ok(chunk)
+    }
+
+    fn decompress_chunk(
+        &self,
+        chunk: FileChunk,
+        _config: &CompressionConfig,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("decompress_chunk")?;
+        Ok(chunk)
+    }
+
+    fn estimate_compression_ratio(
+        &self,
+        _data_sample: &[u8],
+        _algorithm: &CompressionAlgorithm,
+    ) -> Result<f64, PipelineError> {
+        self.script.enter("estimate_compression_ratio")?;
+        Ok(1.0)
+    }
+
+    fn get_optimal_config(
+        &self,
+        _file_extension: &str,
+        _data_sample: &[u8],
+        _performance_priority: CompressionPriority,
+    ) -> Result<CompressionConfig, PipelineError> {
+        self.script.enter("get_optimal_config")?;
+        Ok(CompressionConfig::default())
+    }
+
+    fn validate_config(&self, _config: &CompressionConfig) -> Result<(), PipelineError> {
+        self.script.enter("validate_config")
+    }
+
+    fn supported_algorithms(&self) -> Vec<CompressionAlgorithm> {
+        vec![CompressionConfig::default().algorithm]
+    }
+
+    fn benchmark_algorithm(
+        &self,
+        algorithm: &CompressionAlgorithm,
+        _test_data: &[u8],
+    ) -> Result<CompressionBenchmark, PipelineError> {
+        self.script.enter("benchmark_algorithm")?;
+        Ok(CompressionBenchmark {
+            algorithm: algorithm.clone(),
+            compression_ratio: 1.0,
+            ..Default::default()
+        })
+    }
+}
+
+/// [`EncryptionService`] whose cipher is the identity
+///
+/// Key material is all zeros of the configured sizes. Stored keys live in
+/// memory and rotation stores the new key under `"<old key id>-rotated"`.
+#[derive(Default)]
+pub struct MockEncryptionService {
+    script: Script,
+    keys: Mutex<HashMap<String, KeyMaterial>>,
+}
+
+scripted!(MockEncryptionService);
+
+impl MockEncryptionService {
+    /// Creates the service
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key_material(config: &EncryptionConfig) -> KeyMaterial {
+        KeyMaterial::new(
+            vec![0u8; config.key_size as usize],
+            vec![0u8; config.nonce_size as usize],
+            vec![0u8; config.salt_size as usize],
+            config.algorithm.clone(),
+        )
+    }
+}
+
+impl StageService for MockEncryptionService {
+    fn process_chunk(
+        &self,
+        chunk: FileChunk,
+        _operation: Operation,
+        _config: &StageConfiguration,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("process_chunk")?;
+        Ok(chunk)
+    }
+
+    fn position(&self) -> StagePosition {
+        StagePosition::PostBinary
+    }
+
+    fn is_reversible(&self) -> bool {
+        true
+    }
+
+    fn stage_type(&self) -> StageType {
+        StageType::Encryption
+    }
+}
+
+impl EncryptionService for MockEncryptionService {
+    fn encrypt_chunk(
+        &self,
+        chunk: FileChunk,
+        _config: &EncryptionConfig,
+        _key_material: &KeyMaterial,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("encrypt_chunk")?;
+        Ok(chunk)
+    }
+
+    fn decrypt_chunk(
+        &self,
+        chunk: FileChunk,
+        _config: &EncryptionConfig,
+        _key_material: &KeyMaterial,
+        _context: &mut ProcessingContext,
+    ) -> Result<FileChunk, PipelineError> {
+        self.script.enter("decrypt_chunk")?;
+        Ok(chunk)
+    }
+
+    fn derive_key_material(
+        &self,
+        _password: &SecretBytes,
+        config: &EncryptionConfig,
+        _security_context: &SecurityContext,
+    ) -> Result<KeyMaterial, PipelineError> {
+        self.script.enter("derive_key_material")?;
+        Ok(Self::key_material(config))
+    }
+
+    fn generate_key_material(
+        &self,
+        config: &EncryptionConfig,
+        _security_context: &SecurityContext,
+    ) -> Result<KeyMaterial, PipelineError> {
+        self.script.enter("generate_key_material")?;
+        Ok(Self::key_material(config))
+    }
+
+    fn validate_config(&self, _config: &EncryptionConfig) -> Result<(), PipelineError> {
+        self.script.enter("validate_config")
+    }
+
+    fn supported_algorithms(&self) -> Vec<EncryptionAlgorithm> {
+        vec![EncryptionConfig::default().algorithm]
+    }
+
+    fn benchmark_algorithm(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+        test_data: &[u8],
+    ) -> Result<EncryptionBenchmark, PipelineError> {
+        self.script.enter("benchmark_algorithm")?;
+        Ok(EncryptionBenchmark::new(
+            algorithm.clone(),
+            0.0,
+            Duration::ZERO,
+            0.0,
+            0.0,
+            test_data.len() as f64 / (1024.0 * 1024.0),
+        ))
+    }
+
+    fn wipe_key_material(&self, key_material: &mut KeyMaterial) -> Result<(), PipelineError> {
+        self.script.enter("wipe_key_material")?;
+        key_material.clear();
+        Ok(())
+    }
+
+    fn store_key_material(
+        &self,
+        key_material: &KeyMaterial,
+        key_id: &str,
+        _security_context: &SecurityContext,
+    ) -> Result<(), PipelineError> {
+        self.script.enter("store_key_material")?;
+        self.keys.lock().insert(key_id.to_string(), key_material.clone());
+        Ok(())
+    }
+
+    fn retrieve_key_material(
+        &self,
+        key_id: &str,
+        _security_context: &SecurityContext,
+    ) -> Result<KeyMaterial, PipelineError> {
+        self.script.enter("retrieve_key_material")?;
+        self.keys
+            .lock()
+            .get(key_id)
+            .cloned()
+            .ok_or_else(|| PipelineError::EncryptionError(format!("Unknown key: {}", key_id)))
+    }
+
+    fn rotate_keys(
+        &self,
+        old_key_id: &str,
+        new_config: &EncryptionConfig,
+        _security_context: &SecurityContext,
+    ) -> Result<String, PipelineError> {
+        self.script.enter("rotate_keys")?;
+        let mut keys = self.keys.lock();
+        if !keys.contains_key(old_key_id) {
+            return Err(PipelineError::EncryptionError(format!("Unknown key: {}", old_key_id)));
+        }
+        let new_key_id = format!("{}-rotated", old_key_id);
+        keys.insert(new_key_id.clone(), Self::key_material(new_config));
+        Ok(new_key_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_pipeline_domain::entities::SecurityLevel;
+    use std::collections::HashMap;
+
+    fn pipeline(name: &str) -> Pipeline {
+        let stage = crate::test_util::generators::stage("compress", StageType::Compression, "brotli", HashMap::new());
+        Pipeline::new(name.to_string(), vec![stage]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repository_archives_and_fails_on_request() {
+        let stored = pipeline("stored");
+        let repository = MockPipelineRepository::new().with_pipeline(stored.clone());
+
+        assert!(repository.find_by_name("stored").await.unwrap().is_some());
+        assert!(repository.archive(stored.id().clone()).await.unwrap());
+        assert!(repository.find_by_name("stored").await.unwrap().is_none());
+        assert_eq!(repository.list_archived().await.unwrap().len(), 1);
+        assert!(repository.restore(stored.id().clone()).await.unwrap());
+
+        repository.fail_next(PipelineError::database_error("connection lost"));
+        assert!(repository.count().await.is_err());
+        assert_eq!(repository.count().await.unwrap(), 1);
+        assert_eq!(
+            repository.calls(),
+            vec![
+                "find_by_name",
+                "archive",
+                "find_by_name",
+                "list_archived",
+                "restore",
+                "count",
+                "count"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_io_reads_chunks_and_writes_in_memory() {
+        let files = MockFileIOService::new().with_file("/in/data.bin", vec![1u8; 10]);
+        let read = files
+            .read_file_chunks(
+                Path::new("/in/data.bin"),
+                ReadOptions {
+                    chunk_size: Some(4),
+                    start_offset: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let layout: Vec<_> = read
+            .chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.sequence_number(),
+                    chunk.offset(),
+                    chunk.data_len(),
+                    chunk.is_final(),
+                )
+            })
+            .collect();
+        assert_eq!(layout, vec![(0, 2, 4, false), (1, 6, 4, true)]);
+
+        files
+            .write_chunk_to_file(Path::new("/out/a"), &read.chunks[0], WriteOptions::default(), true)
+            .await
+            .unwrap();
+        files
+            .write_chunk_to_file(Path::new("/out/a"), &read.chunks[1], WriteOptions::default(), false)
+            .await
+            .unwrap();
+        assert_eq!(files.file("/out/a").unwrap().len(), 8);
+        assert!(files.directory_exists(Path::new("/out")).await.unwrap());
+        assert_eq!(files.list_directory(Path::new("/out")).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stage_executor_and_codecs_pass_chunks_through() {
+        let pipeline = pipeline("stages");
+        let stage = &pipeline.stages()[1];
+        let executor = MockStageExecutor::new().with_supported_stage_types(&[StageType::Checksum]);
+        let mut context = ProcessingContext::new(3, SecurityContext::new(None, SecurityLevel::Internal));
+        let chunk = FileChunk::new(7, 0, vec![9, 9, 9], true).unwrap();
+
+        let executed = executor.execute(stage, chunk.clone(), &mut context).await.unwrap();
+        assert_eq!(executed.data(), chunk.data());
+        assert_eq!(executor.executions(), vec![("compress".to_string(), 7)]);
+        assert!(!executor.can_execute(stage).await.unwrap());
+
+        let compression = MockCompressionService::new();
+        let compressed = compression
+            .compress_chunk(chunk.clone(), &CompressionConfig::default(), &mut context)
+            .unwrap();
+        assert_eq!(compressed.data(), chunk.data());
+
+        let encryption = MockEncryptionService::new();
+        let security_context = SecurityContext::new(None, SecurityLevel::Internal);
+        let key = encryption
+            .generate_key_material(&EncryptionConfig::default(), &security_context)
+            .unwrap();
+        encryption.store_key_material(&key, "k1", &security_context).unwrap();
+        assert_eq!(
+            encryption
+                .rotate_keys("k1", &EncryptionConfig::default(), &security_context)
+                .unwrap(),
+            "k1-rotated"
+        );
+        assert_eq!(
+            encryption.calls(),
+            vec!["generate_key_material", "store_key_material", "rotate_keys"]
+        );
+    }
+}
@@ -9,6 +9,9 @@
 //!
 //! Shared utilities for integration and end-to-end tests.
 
+use std::path::Path;
+use std::process::{Command, Output};
+
 /// Get the path to the compiled pipeline binary
 ///
 /// This helper tries the CARGO_BIN_EXE environment variable first (set by cargo
@@ -38,6 +41,94 @@ pub fn get_pipeline_bin() -> String {
     bin_path.to_str().expect("Invalid UTF-8 in binary path").to_string()
 }
 
+/// A pipeline command using the database at `db_path`, without setting it up
+///
+/// `ADAPIPE_ROLE` is cleared so a role set in the caller's environment does
+/// not narrow what the command may do.
+pub fn bare_command(db_path: &Path) -> Command {
+    let mut command = Command::new(get_pipeline_bin());
+    command.env("ADAPIPE_SQLITE_PATH", db_path).env_remove("ADAPIPE_ROLE");
+    command
+}
+
+/// A pipeline command using the database at `db_path`
+///
+/// A database that does not exist yet is first created by `role init`, so
+/// the OS user running the tests is its admin.
+///
+/// # Panics
+///
+/// Panics if `role init` fails.
+pub fn pipeline_command(db_path: &Path) -> Command {
+    if !db_path.exists() {
+        let init = bare_command(db_path)
+            .args(["role", "init"])
+            .output()
+            .expect("Failed to run pipeline command");
+        assert_success(&init, "role init");
+    }
+    bare_command(db_path)
+}
+
+/// Run the pipeline with `args` against the database at `db_path`
+///
+/// # Panics
+///
+/// Panics if the binary cannot be started.
+pub fn run(db_path: &Path, args: &[&str]) -> Output {
+    pipeline_command(db_path)
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+/// A pipeline command acting as `principal`
+///
+/// The principal is authenticated by an API key that a config file next to
+/// the database grants; its role still comes from its assignments.
+pub fn principal_command(db_path: &Path, principal: &str) -> Command {
+    let key = format!("{}-e2e-key", principal);
+    let config = db_path.with_extension(format!("{}.toml", principal));
+    let contents = format!(
+        "[[auth.api_keys]]\nprincipal = \"{}\"\nkey_sha256 = \"{}\"\n",
+        principal,
+        calculate_sha256(key.as_bytes())
+    );
+    std::fs::write(&config, contents).expect("Failed to write principal config");
+
+    let mut command = pipeline_command(db_path);
+    command
+        .env("ADAPIPE_API_KEY", key)
+        .env_remove("ADAPIPE_SESSION_TOKEN")
+        .env_remove("ADAPIPE_BEARER_TOKEN")
+        .arg("--config")
+        .arg(config);
+    command
+}
+
+/// Run the pipeline with `args` as `principal`; see [`principal_command`]
+///
+/// # Panics
+///
+/// Panics if the binary cannot be started.
+pub fn run_as(db_path: &Path, principal: &str, args: &[&str]) -> Output {
+    principal_command(db_path, principal)
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+/// Assert that a pipeline command succeeded, showing its output otherwise
+pub fn assert_success(output: &Output, what: &str) {
+    assert!(
+        output.status.success(),
+        "{} failed: {}{}",
+        what,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 /// Calculate SHA256 checksum of data
 ///
 /// # Arguments
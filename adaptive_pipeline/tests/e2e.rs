@@ -12,11 +12,107 @@
 // Shared test helpers
 mod common;
 
+#[path = "e2e/e2e_audit_test.rs"]
+mod e2e_audit_test;
+
+#[path = "e2e/e2e_auth_provider_test.rs"]
+mod e2e_auth_provider_test;
+
 #[path = "e2e/e2e_binary_format_test.rs"]
 mod e2e_binary_format_test;
 
+#[path = "e2e/e2e_capabilities_test.rs"]
+mod e2e_capabilities_test;
+
+#[path = "e2e/e2e_chunk_sweep_test.rs"]
+mod e2e_chunk_sweep_test;
+
+#[path = "e2e/e2e_checksum_algorithm_test.rs"]
+mod e2e_checksum_algorithm_test;
+
+#[path = "e2e/e2e_concurrent_access_test.rs"]
+mod e2e_concurrent_access_test;
+
+#[path = "e2e/e2e_correlation_test.rs"]
+mod e2e_correlation_test;
+
+#[path = "e2e/e2e_database_backup_test.rs"]
+mod e2e_database_backup_test;
+
+#[path = "e2e/e2e_encryption_vectors_test.rs"]
+mod e2e_encryption_vectors_test;
+
+#[path = "e2e/e2e_exit_codes_test.rs"]
+mod e2e_exit_codes_test;
+
+#[path = "e2e/e2e_fips_test.rs"]
+mod e2e_fips_test;
+
+#[path = "e2e/e2e_golden_corpus_test.rs"]
+mod e2e_golden_corpus_test;
+
+#[path = "e2e/e2e_idempotency_test.rs"]
+mod e2e_idempotency_test;
+
+#[path = "e2e/e2e_inspect_test.rs"]
+mod e2e_inspect_test;
+
+#[path = "e2e/e2e_jobs_test.rs"]
+mod e2e_jobs_test;
+
+#[path = "e2e/e2e_ls_test.rs"]
+mod e2e_ls_test;
+
+#[path = "e2e/e2e_manifest_test.rs"]
+mod e2e_manifest_test;
+
+#[path = "e2e/e2e_mount_test.rs"]
+mod e2e_mount_test;
+
+#[path = "e2e/e2e_namespace_test.rs"]
+mod e2e_namespace_test;
+
+#[path = "e2e/e2e_password_test.rs"]
+mod e2e_password_test;
+
+#[path = "e2e/e2e_pipeline_definition_test.rs"]
+mod e2e_pipeline_definition_test;
+
+#[path = "e2e/e2e_quota_test.rs"]
+mod e2e_quota_test;
+
+#[path = "e2e/e2e_ranged_restore_test.rs"]
+mod e2e_ranged_restore_test;
+
+#[path = "e2e/e2e_rbac_test.rs"]
+mod e2e_rbac_test;
+
+#[path = "e2e/e2e_recursive_process_test.rs"]
+mod e2e_recursive_process_test;
+
+#[path = "e2e/e2e_replicate_test.rs"]
+mod e2e_replicate_test;
+
 #[path = "e2e/e2e_restore_pipeline_test.rs"]
 mod e2e_restore_pipeline_test;
 
+#[path = "e2e/e2e_scrub_test.rs"]
+mod e2e_scrub_test;
+
+#[path = "e2e/e2e_security_policy_test.rs"]
+mod e2e_security_policy_test;
+
+#[path = "e2e/e2e_serve_test.rs"]
+mod e2e_serve_test;
+
+#[path = "e2e/e2e_session_test.rs"]
+mod e2e_session_test;
+
+#[path = "e2e/e2e_tar_interop_test.rs"]
+mod e2e_tar_interop_test;
+
+#[path = "e2e/e2e_temp_cleanup_test.rs"]
+mod e2e_temp_cleanup_test;
+
 #[path = "e2e/e2e_use_cases_test.rs"]
 mod e2e_use_cases_test;
@@ -0,0 +1,56 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Pipeline Audit Tests
+//!
+//! Verifies that `audit` reports a stored pipeline this version cannot run,
+//! with a migration suggestion, before any file is processed with it.
+
+use tempfile::TempDir;
+
+use crate::common::pipeline_command;
+
+/// Findings printed by `audit --json`. Log lines share stdout with the
+/// command output; the findings start at the first line that opens an array.
+fn parse_findings(stdout: &[u8]) -> Vec<serde_json::Value> {
+    let stdout = String::from_utf8_lossy(stdout);
+    let start = stdout.find("\n[").expect("no JSON array in audit output") + 1;
+    serde_json::from_str(&stdout[start..]).expect("audit --json is not JSON")
+}
+
+#[test]
+fn test_e2e_audit_reports_unrunnable_pipeline_with_suggestion() {
+    let temp_dir = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        pipeline_command(&temp_dir.path().join("audit.db"))
+            .args(args)
+            .output()
+            .expect("Failed to run pipeline command")
+    };
+
+    assert!(run(&["create", "--name", "current", "--stages", "brotli"])
+        .status
+        .success());
+    let clean = run(&["audit", "--json"]);
+    assert!(clean.status.success(), "{}", String::from_utf8_lossy(&clean.stderr));
+    let findings = parse_findings(&clean.stdout);
+    assert!(findings.is_empty(), "{:?}", findings);
+
+    // lz4 is accepted by create but has no implementation
+    assert!(run(&["create", "--name", "legacy", "--stages", "lz4"]).status.success());
+    let audit = run(&["audit", "--json"]);
+    assert_eq!(
+        audit.status.code(),
+        Some(65),
+        "an unrunnable pipeline must exit EX_DATAERR"
+    );
+    let findings = parse_findings(&audit.stdout);
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert_eq!(findings[0]["pipeline"], "legacy");
+    assert_eq!(findings[0]["severity"], "error");
+    assert_eq!(findings[0]["suggestion"], "Replace lz4 with zstd");
+}
@@ -0,0 +1,137 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Authentication Provider Tests
+//!
+//! Verifies through the CLI that the `[auth]` providers authenticate API keys
+//! (`ADAPIPE_API_KEY`) and JWTs (`ADAPIPE_BEARER_TOKEN`), map them to RBAC
+//! roles, and refuse protected commands without credentials when
+//! authentication is required.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command};
+
+const API_KEY: &str = "bot-key-0123456789";
+const JWT_SECRET: &str = "e2e-shared-secret";
+
+fn write_config(dir: &Path) -> String {
+    let config = format!(
+        r#"
+[auth]
+required = true
+
+[[auth.api_keys]]
+principal = "build-bot"
+key_sha256 = "{}"
+role = "auditor"
+
+[auth.jwt]
+issuer = "https://idp.example.com"
+hs256_secret_env = "ADAPIPE_E2E_JWT_SECRET"
+
+[auth.jwt.role_mapping]
+"pipeline-admins" = "admin"
+"#,
+        hex::encode(Sha256::digest(API_KEY.as_bytes()))
+    );
+    let path = dir.join("pipeline.toml");
+    std::fs::write(&path, config).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn jwt(subject: &str, roles: &[&str], expires_in: i64) -> String {
+    let part = |value: serde_json::Value| URL_SAFE_NO_PAD.encode(value.to_string());
+    let input = format!(
+        "{}.{}",
+        part(serde_json::json!({"alg": "HS256", "typ": "JWT"})),
+        part(serde_json::json!({
+            "sub": subject,
+            "iss": "https://idp.example.com",
+            "exp": chrono::Utc::now().timestamp() + expires_in,
+            "roles": roles,
+        }))
+    );
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, JWT_SECRET.as_bytes());
+    let signature = ring::hmac::sign(&key, input.as_bytes());
+    format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+}
+
+fn run(db_path: &Path, config: &str, credential: Option<(&str, &str)>, args: &[&str]) -> Output {
+    let mut command = pipeline_command(db_path);
+    command
+        .env("ADAPIPE_E2E_JWT_SECRET", JWT_SECRET)
+        .env_remove("ADAPIPE_SESSION_TOKEN")
+        .env_remove("ADAPIPE_API_KEY")
+        .env_remove("ADAPIPE_BEARER_TOKEN");
+    if let Some((variable, value)) = credential {
+        command.env(variable, value);
+    }
+    command
+        .arg("--config")
+        .arg(config)
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+#[test]
+fn test_e2e_auth_providers_gate_protected_commands() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("auth.db");
+    let config = write_config(temp_dir.path());
+    let admin_token = jwt("alice", &["pipeline-admins"], 300);
+
+    // Authentication is required, so the OS user alone is refused
+    let anonymous = run(&db_path, &config, None, &["list"]);
+    assert_eq!(anonymous.status.code(), Some(77));
+
+    // Unprotected commands still run without credentials
+    assert_success(&run(&db_path, &config, None, &["exit-codes"]), "exit-codes");
+
+    // The IdP-mapped admin role admits alice to admin commands
+    let token = Some(("ADAPIPE_BEARER_TOKEN", admin_token.as_str()));
+    assert_success(
+        &run(&db_path, &config, token, &["role", "assign", "alice", "admin"]),
+        "assign admin with JWT",
+    );
+    assert_success(
+        &run(
+            &db_path,
+            &config,
+            token,
+            &["create", "--name", "auth-test", "--stages", "brotli"],
+        ),
+        "create with JWT",
+    );
+
+    // The API key is granted auditor: it may view but not delete
+    let key = Some(("ADAPIPE_API_KEY", API_KEY));
+    assert_success(
+        &run(&db_path, &config, key, &["show", "auth-test"]),
+        "show with API key",
+    );
+    let denied = run(&db_path, &config, key, &["delete", "auth-test", "--force"]);
+    assert_eq!(denied.status.code(), Some(77), "auditor key must not delete");
+
+    let wrong_key = Some(("ADAPIPE_API_KEY", "guessed"));
+    assert_eq!(run(&db_path, &config, wrong_key, &["list"]).status.code(), Some(77));
+
+    let expired = jwt("alice", &["pipeline-admins"], -3600);
+    let expired = Some(("ADAPIPE_BEARER_TOKEN", expired.as_str()));
+    assert_eq!(run(&db_path, &config, expired, &["list"]).status.code(), Some(77));
+
+    assert_success(
+        &run(&db_path, &config, token, &["delete", "auth-test", "--force"]),
+        "delete with JWT",
+    );
+}
@@ -15,21 +15,17 @@ use tempfile::{NamedTempFile, TempDir};
 use tokio::fs;
 
 use adaptive_pipeline::infrastructure::services::{AdapipeFormat, BinaryFormatService, BinaryFormatWriter};
+use adaptive_pipeline_domain::value_objects::binary_file_format::CURRENT_FORMAT_VERSION;
 use adaptive_pipeline_domain::value_objects::FileHeader;
 
 // Import shared test helpers
-use crate::common::{calculate_sha256, get_pipeline_bin};
+use crate::common::{calculate_sha256, pipeline_command};
 
 /// Tests complete .adapipe roundtrip using real pipeline processing via CLI.
 /// This test exercises the full stack: input file → real compression → .adapipe
 /// file → metadata validation.
 #[tokio::test]
 async fn test_e2e_real_pipeline_roundtrip() {
-    use std::process::Command;
-
-    // Get the path to the compiled pipeline binary
-    let pipeline_bin = get_pipeline_bin();
-
     let temp_dir = TempDir::new().unwrap();
     let db_path = temp_dir.path().join("test_roundtrip.db");
     let input_file = temp_dir.path().join("e2e_input.txt");
@@ -44,14 +40,12 @@ async fn test_e2e_real_pipeline_roundtrip() {
     let expected_size = test_data.len() as u64;
 
     // Step 0: Clean up any existing pipeline from previous test runs
-    let _ = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _ = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-test-roundtrip", "--force"])
         .output();
 
     // Step 1: Create a pipeline using the real CLI
-    let create_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let create_output = pipeline_command(&db_path)
         .args(["create", "--name", "e2e-test-roundtrip", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
@@ -65,8 +59,7 @@ async fn test_e2e_real_pipeline_roundtrip() {
     }
 
     // Step 2: Process the file using the real pipeline CLI
-    let process_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let process_output = pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -94,7 +87,7 @@ async fn test_e2e_real_pipeline_roundtrip() {
     // Validate file format
     let validation = service.validate_file(&output_file).await.unwrap();
     assert!(validation.is_valid, "Generated .adapipe file is invalid");
-    assert_eq!(validation.format_version, 1);
+    assert_eq!(validation.format_version, CURRENT_FORMAT_VERSION);
     assert!(validation.chunk_count > 0);
 
     // Read and verify metadata
@@ -125,8 +118,7 @@ async fn test_e2e_real_pipeline_roundtrip() {
     assert!(total_data_size > 0, "Should have read some data");
 
     // Step 6: Clean up - delete the test pipeline
-    let _delete_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _delete_output = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-test-roundtrip", "--force"])
         .output()
         .expect("Failed to delete pipeline");
@@ -138,10 +130,6 @@ async fn test_e2e_real_pipeline_roundtrip() {
 /// real pipeline
 #[tokio::test]
 async fn test_e2e_binary_format_pass_through() {
-    use std::process::Command;
-
-    let pipeline_bin = get_pipeline_bin();
-
     let temp_dir = TempDir::new().unwrap();
     let db_path = temp_dir.path().join("test_passthrough.db");
     let input_file = temp_dir.path().join("passthrough_input.txt");
@@ -153,15 +141,13 @@ async fn test_e2e_binary_format_pass_through() {
     let expected_checksum = calculate_sha256(test_data);
 
     // Step 0: Clean up any existing pipeline from previous test runs
-    let _ = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _ = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-passthrough", "--force"])
         .output();
 
     // Step 1: Create a pipeline with only checksum stages (no
     // compression/encryption)
-    let create_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let create_output = pipeline_command(&db_path)
         .args(["create", "--name", "e2e-passthrough", "--stages", "checksum"])
         .output()
         .expect("Failed to create pipeline");
@@ -169,8 +155,7 @@ async fn test_e2e_binary_format_pass_through() {
     assert!(create_output.status.success());
 
     // Step 2: Process the file
-    let process_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let process_output = pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -199,8 +184,7 @@ async fn test_e2e_binary_format_pass_through() {
     assert_eq!(metadata.original_size, test_data.len() as u64);
 
     // Clean up
-    let _delete_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _delete_output = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-passthrough", "--force"])
         .output()
         .expect("Failed to delete pipeline");
@@ -244,7 +228,7 @@ async fn test_e2e_binary_format_version_compatibility() {
     // Verify version is correctly stored and read
     {
         let metadata = service.read_metadata(&output_file).await.unwrap();
-        assert_eq!(metadata.format_version, 1); // Current version
+        assert_eq!(metadata.format_version, CURRENT_FORMAT_VERSION);
         assert!(!metadata.app_version.is_empty());
     }
 }
@@ -253,10 +237,6 @@ async fn test_e2e_binary_format_version_compatibility() {
 /// pipeline
 #[tokio::test]
 async fn test_e2e_binary_format_large_file() {
-    use std::process::Command;
-
-    let pipeline_bin = get_pipeline_bin();
-
     let temp_dir = TempDir::new().unwrap();
     let db_path = temp_dir.path().join("test_large_file.db");
     let input_file = temp_dir.path().join("large_input.txt");
@@ -267,14 +247,12 @@ async fn test_e2e_binary_format_large_file() {
     fs::write(&input_file, &test_data).await.unwrap();
 
     // Step 0: Clean up any existing pipeline from previous test runs
-    let _ = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _ = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-large-test", "--force"])
         .output();
 
     // Step 1: Create pipeline
-    let create_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let create_output = pipeline_command(&db_path)
         .args(["create", "--name", "e2e-large-test", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
@@ -287,8 +265,7 @@ async fn test_e2e_binary_format_large_file() {
 
     // Step 2: Process the large file with small chunk size to create multiple
     // chunks
-    let process_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let process_output = pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -332,8 +309,7 @@ async fn test_e2e_binary_format_large_file() {
     assert!(total_data > 0, "Should have read data");
 
     // Clean up
-    let _delete_output = Command::new(&pipeline_bin)
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let _delete_output = pipeline_command(&db_path)
         .args(["delete", "--name", "e2e-large-test", "--force"])
         .output()
         .expect("Failed to delete pipeline");
@@ -0,0 +1,82 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Capabilities Tests
+//!
+//! Verifies that `capabilities --json` describes the build without touching
+//! the database, that every algorithm it lists can be used in a pipeline, and
+//! that it reports the feature flags a configuration file turns on.
+
+use adaptive_pipeline_domain::value_objects::binary_file_format::CURRENT_FORMAT_VERSION;
+use tempfile::TempDir;
+
+use crate::common::{bare_command, pipeline_command};
+
+#[test]
+fn test_e2e_capabilities_json_lists_usable_algorithms() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("capabilities.db");
+    let output = bare_command(&db_path)
+        .args(["capabilities", "--json"])
+        .output()
+        .expect("Failed to run capabilities");
+    assert!(output.status.success());
+    assert!(!db_path.exists(), "capabilities must not open the database");
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("capabilities --json is not JSON");
+    assert_eq!(report["build"]["crate_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["formats"]["archive"], CURRENT_FORMAT_VERSION);
+    assert_eq!(report["features"]["fips"], cfg!(feature = "fips"));
+    assert!(report["limits"]["max_chunk_size"].as_u64().unwrap() > 0);
+
+    let names: Vec<&str> = report["algorithms"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|algorithm| algorithm["category"] != "checksum")
+        .map(|algorithm| algorithm["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"brotli"));
+    assert!(names.contains(&"aes-256-gcm"));
+
+    let create = pipeline_command(&db_path)
+        .args(["create", "--name", "every-capability", "--stages", &names.join(",")])
+        .output()
+        .expect("Failed to run create");
+    assert!(
+        create.status.success(),
+        "a listed algorithm was rejected: {}",
+        String::from_utf8_lossy(&create.stderr)
+    );
+}
+
+#[test]
+fn test_e2e_capabilities_reports_configured_feature_flags() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("pipeline.toml");
+    std::fs::write(&config_path, "[features]\ngpu = true\ngpu_v2 = true\n").unwrap();
+
+    let output = bare_command(&temp_dir.path().join("capabilities.db"))
+        .arg("--config")
+        .arg(&config_path)
+        .args(["capabilities", "--json"])
+        .output()
+        .expect("Failed to run capabilities");
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Unknown feature flag 'gpu_v2' ignored"),
+        "an unknown flag must be reported"
+    );
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("capabilities --json is not JSON");
+    let flags = report["flags"].as_array().unwrap();
+    let flag = |name: &str| flags.iter().find(|flag| flag["name"] == name).unwrap().clone();
+    assert_eq!(flag("gpu")["enabled"], true);
+    assert_eq!(flag("gpu")["lifecycle"], "experimental");
+    assert_eq!(flag("dedup")["enabled"], false);
+    assert_eq!(flag("distributed")["enabled"], false);
+}
@@ -0,0 +1,118 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Checksum Algorithm Tests
+//!
+//! Verifies through the CLI that a pipeline created with `--checksum blake3`
+//! records BLAKE3 input checksums in its `.adapipe` files, with or without
+//! checksum offload, and that restore verifies them with BLAKE3. FIPS-mode
+//! builds must refuse the pipeline instead.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+const FIPS: bool = cfg!(feature = "fips");
+
+#[test]
+fn test_e2e_blake3_checksums_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("checksum.db");
+    let input = temp_dir.path().join("input.txt");
+    let data = b"hashed with blake3 from end to end\n".repeat(5000);
+    std::fs::write(&input, &data).unwrap();
+    let input_arg = input.to_string_lossy().to_string();
+
+    let created = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "blake3-backup",
+            "--stages",
+            "compression:zstd",
+            "--checksum",
+            "blake3",
+        ],
+    );
+    if FIPS {
+        assert!(!created.status.success(), "BLAKE3 is not FIPS-approved");
+        return;
+    }
+    assert_success(&created, "create pipeline");
+
+    let expected = blake3::hash(&data).to_hex().to_string();
+    for offload in [false, true] {
+        let name = if offload { "offloaded" } else { "prepass" };
+        let archive = temp_dir.path().join(format!("{}.adapipe", name));
+        let restored = temp_dir.path().join(name);
+        let archive_arg = archive.to_string_lossy().to_string();
+
+        let mut process = vec![
+            "process",
+            "--input",
+            &input_arg,
+            "--output",
+            &archive_arg,
+            "--pipeline",
+            "blake3-backup",
+        ];
+        if offload {
+            process.push("--checksum-offload");
+        }
+        assert_success(&run(&db_path, &process), "process");
+
+        let validated = run(&db_path, &["validate-file", "--file", &archive_arg]);
+        assert_success(&validated, "validate-file");
+        let stdout = String::from_utf8_lossy(&validated.stdout);
+        assert!(
+            stdout.contains(&format!("Original checksum: {} (blake3)", expected)),
+            "validate-file output:\n{}",
+            stdout
+        );
+
+        assert_success(
+            &run(
+                &db_path,
+                &[
+                    "restore",
+                    "--input",
+                    &archive_arg,
+                    "--output-dir",
+                    &restored.to_string_lossy(),
+                    "--mkdir",
+                ],
+            ),
+            "restore",
+        );
+        assert_eq!(std::fs::read(restored.join("input.txt")).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_e2e_unknown_checksum_algorithm_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("checksum.db");
+
+    let created = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "md5-backup",
+            "--stages",
+            "brotli",
+            "--checksum",
+            "md5",
+        ],
+    );
+    assert!(
+        !created.status.success(),
+        "unknown checksum algorithms must not be stored"
+    );
+    assert!(String::from_utf8_lossy(&created.stderr).contains("sha256 or blake3"));
+}
@@ -0,0 +1,93 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Chunk Size Sweep Tests
+//!
+//! Verifies that `benchmark --sweep-chunk-size` runs the pipeline at each
+//! size of the range, and that `--apply` pins the pipeline to the fastest
+//! one for later runs.
+
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command};
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    pipeline_command(&dir.join("sweep.db"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+#[test]
+fn test_e2e_sweep_applies_fastest_chunk_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let input = dir.join("sample.bin");
+    std::fs::write(&input, b"Chunk size sweep E2E test data.\n".repeat(64 * 1024)).unwrap();
+    let input = input.to_string_lossy().into_owned();
+
+    assert_success(
+        &run(dir, &["create", "--name", "sweep-test", "--stages", "brotli"]),
+        "create",
+    );
+
+    let swept = run(
+        dir,
+        &[
+            "benchmark",
+            "--sweep-chunk-size",
+            "256KiB..1MiB",
+            "--pipeline",
+            "sweep-test",
+            "--file",
+            &input,
+            "--iterations",
+            "1",
+            "--apply",
+        ],
+    );
+    assert_success(&swept, "benchmark --sweep-chunk-size");
+    let stdout = String::from_utf8_lossy(&swept.stdout);
+    for size in ["0.2 MiB", "0.5 MiB", "1.0 MiB"] {
+        assert!(stdout.contains(size), "no result for {}:\n{}", size, stdout);
+    }
+    assert!(stdout.contains("🏆 Fastest"), "{}", stdout);
+    assert!(stdout.contains("now processes with"), "{}", stdout);
+    let leftovers: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".adapipe"))
+        .collect();
+    assert!(leftovers.is_empty(), "sweep left outputs behind: {:?}", leftovers);
+
+    let output = dir.join("output.adapipe");
+    let processed = run(
+        dir,
+        &[
+            "process",
+            "--input",
+            &input,
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "sweep-test",
+        ],
+    );
+    assert_success(&processed, "process");
+    let stdout = String::from_utf8_lossy(&processed.stdout);
+    assert!(stdout.contains("(configured)"), "{}", stdout);
+}
+
+#[test]
+fn test_e2e_sweep_requires_pipeline() {
+    let temp_dir = TempDir::new().unwrap();
+    let swept = run(temp_dir.path(), &["benchmark", "--sweep-chunk-size", "1MiB..4MiB"]);
+    assert!(!swept.status.success());
+}
@@ -0,0 +1,116 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Concurrent Access Tests
+//!
+//! Runs several CLI processes against one pipeline database at once, as an
+//! operator editing pipelines beside a running job would, and checks that
+//! every one succeeds rather than failing with "database is locked".
+
+use std::path::Path;
+use std::process::{Child, Stdio};
+use tempfile::TempDir;
+
+use crate::common::pipeline_command;
+
+fn spawn(db_path: &Path, args: &[&str]) -> Child {
+    pipeline_command(db_path)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start pipeline command")
+}
+
+/// Waits for every child and asserts each succeeded
+fn assert_all_succeed(children: Vec<(String, Child)>) {
+    for (label, child) in children {
+        let output = child.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "{} exited {:?}: {}{}",
+            label,
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn test_e2e_concurrent_commands_initialize_a_new_database() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("concurrent.db");
+
+    // No database yet: every process races to create and migrate it
+    let mut children = Vec::new();
+    for i in 0..8 {
+        let name = format!("concurrent-{}", i);
+        children.push((
+            format!("create {}", name),
+            spawn(&db_path, &["create", "--name", &name, "--stages", "brotli"]),
+        ));
+        children.push((format!("list {}", i), spawn(&db_path, &["list"])));
+    }
+    assert_all_succeed(children);
+
+    let list = spawn(&db_path, &["list"]).wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    for i in 0..8 {
+        assert!(stdout.contains(&format!("concurrent-{}", i)), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_e2e_pipeline_edits_while_a_batch_runs() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("concurrent.db");
+    let run = |args: &[&str]| spawn(&db_path, args).wait_with_output().unwrap();
+    assert!(run(&["create", "--name", "batch-job", "--stages", "brotli"])
+        .status
+        .success());
+    for i in 0..4 {
+        assert!(
+            run(&["create", "--name", &format!("retired-{}", i), "--stages", "gzip"])
+                .status
+                .success()
+        );
+    }
+
+    let inputs: Vec<String> = (0..12)
+        .map(|i| {
+            let path = temp_dir.path().join(format!("input-{}.txt", i));
+            std::fs::write(&path, format!("concurrent batch input {} ", i).repeat(2000)).unwrap();
+            path.to_string_lossy().into_owned()
+        })
+        .collect();
+    let out = temp_dir.path().join("out").to_string_lossy().into_owned();
+    let mut batch_args = vec!["process-batch"];
+    batch_args.extend(inputs.iter().map(String::as_str));
+    batch_args.extend(["--output-dir", &out, "--pipeline", "batch-job"]);
+
+    // The batch records usage and chunk history while pipelines change
+    let mut children = vec![("process-batch".to_string(), spawn(&db_path, &batch_args))];
+    for i in 0..4 {
+        let created = format!("added-{}", i);
+        let retired = format!("retired-{}", i);
+        children.push((
+            format!("create {}", created),
+            spawn(&db_path, &["create", "--name", &created, "--stages", "zstd"]),
+        ));
+        children.push((
+            format!("delete {}", retired),
+            spawn(&db_path, &["delete", &retired, "--force"]),
+        ));
+        children.push((format!("list {}", i), spawn(&db_path, &["list"])));
+    }
+    assert_all_succeed(children);
+
+    for i in 0..12 {
+        assert!(temp_dir.path().join(format!("out/input-{}.txt.adapipe", i)).exists());
+    }
+}
@@ -0,0 +1,120 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Correlation ID Tests
+//!
+//! Verifies that each run's correlation ID, generated or supplied through
+//! `ADAPIPE_CORRELATION_ID`, appears in its log output, its audit records and
+//! events, and the header of the `.adapipe` file it writes.
+
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command};
+
+fn run(db_path: &Path, correlation_id: Option<&str>, args: &[&str]) -> Output {
+    let mut command = pipeline_command(db_path);
+    command.env_remove("ADAPIPE_CORRELATION_ID");
+    if let Some(correlation_id) = correlation_id {
+        command.env("ADAPIPE_CORRELATION_ID", correlation_id);
+    }
+    command.args(args).output().expect("Failed to run pipeline command")
+}
+
+/// Reads the header of `file` through `inspect --json`
+fn header(db_path: &Path, file: &Path) -> serde_json::Value {
+    let inspected = run(db_path, None, &["inspect", "--file", &file.to_string_lossy(), "--json"]);
+    assert_success(&inspected, "inspect");
+    let stdout = String::from_utf8_lossy(&inspected.stdout);
+    let start = stdout.find("{\n").expect("no JSON object in inspect output");
+    serde_json::from_str(&stdout[start..]).unwrap()
+}
+
+#[tokio::test]
+async fn test_e2e_correlation_id_links_logs_records_and_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("correlation.db");
+    let input = temp_dir.path().join("input.txt");
+    std::fs::write(&input, b"Correlation E2E test data.\n".repeat(100)).unwrap();
+
+    assert_success(
+        &run(
+            &db_path,
+            Some("ci-build-4711"),
+            &["create", "--name", "correlation-test", "--stages", "brotli"],
+        ),
+        "create",
+    );
+
+    // A supplied ID is logged and recorded in the output's header
+    let supplied = temp_dir.path().join("supplied.adapipe");
+    let processed = run(
+        &db_path,
+        Some("ci-build-4712"),
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &supplied.to_string_lossy(),
+            "--pipeline",
+            "correlation-test",
+        ],
+    );
+    assert_success(&processed, "process with supplied ID");
+    assert!(String::from_utf8_lossy(&processed.stdout).contains("ci-build-4712"));
+    assert_eq!(header(&db_path, &supplied)["correlation_id"], "ci-build-4712");
+
+    // Otherwise one is generated, and the header names the logged run
+    let generated = temp_dir.path().join("generated.adapipe");
+    let processed = run(
+        &db_path,
+        None,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &generated.to_string_lossy(),
+            "--pipeline",
+            "correlation-test",
+        ],
+    );
+    assert_success(&processed, "process with generated ID");
+    let correlation_id = header(&db_path, &generated)["correlation_id"]
+        .as_str()
+        .expect("header has no correlation ID")
+        .to_string();
+    assert_ne!(correlation_id, "ci-build-4712");
+    assert!(
+        String::from_utf8_lossy(&processed.stdout).contains(&correlation_id),
+        "log output does not name run {}",
+        correlation_id
+    );
+
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let audited: String =
+        sqlx::query_scalar("SELECT correlation_id FROM audit_records WHERE action = 'pipeline.create'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let event: String =
+        sqlx::query_scalar("SELECT correlation_id FROM domain_events WHERE event_type = 'PipelineCreated'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    pool.close().await;
+    assert_eq!(audited, "ci-build-4711");
+    assert_eq!(event, "ci-build-4711");
+
+    // Malformed IDs are refused before anything runs
+    let invalid = run(&db_path, Some("not a valid id"), &["list"]);
+    assert!(!invalid.status.success());
+}
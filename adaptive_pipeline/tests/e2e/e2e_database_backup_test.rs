@@ -0,0 +1,93 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Database Backup Tests
+//!
+//! Backs the pipeline database up through the CLI, changes it, and restores
+//! the backup, checking that pipeline configuration comes back and that only
+//! default-namespace admins may do either.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run, run_as};
+
+#[test]
+fn test_e2e_backup_and_restore_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("pipeline.db");
+    let backup = temp_dir.path().join("pipeline-backup.db");
+    let backup_arg = backup.to_string_lossy().into_owned();
+    let run = |args: &[&str]| run(&db_path, args);
+
+    assert_success(
+        &run(&["create", "--name", "nightly-backup", "--stages", "brotli"]),
+        "create",
+    );
+    assert_success(&run(&["db", "backup", &backup_arg]), "db backup");
+    assert!(backup.exists());
+    assert!(
+        !run(&["db", "backup", &backup_arg]).status.success(),
+        "an existing backup must not be overwritten"
+    );
+
+    assert_success(&run(&["delete", "nightly-backup", "--force"]), "delete");
+    let list = run(&["list"]);
+    assert!(!String::from_utf8_lossy(&list.stdout).contains("nightly-backup"));
+
+    assert_success(&run(&["db", "restore", &backup_arg, "--force"]), "db restore");
+    let list = run(&["list"]);
+    assert!(
+        String::from_utf8_lossy(&list.stdout).contains("nightly-backup"),
+        "restored pipeline missing: {}",
+        String::from_utf8_lossy(&list.stdout)
+    );
+}
+
+#[test]
+fn test_e2e_restore_rejects_invalid_backups() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("pipeline.db");
+    let garbage = temp_dir.path().join("garbage.db");
+    std::fs::write(&garbage, b"this is not a database").unwrap();
+    let run = |args: &[&str]| run(&db_path, args);
+
+    assert_success(&run(&["create", "--name", "kept", "--stages", "brotli"]), "create");
+    assert!(!run(&["db", "restore", &garbage.to_string_lossy(), "--force"])
+        .status
+        .success());
+    assert!(String::from_utf8_lossy(&run(&["list"]).stdout).contains("kept"));
+}
+
+#[test]
+fn test_e2e_database_commands_require_a_default_namespace_admin() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("pipeline.db");
+    let backup = temp_dir.path().join("pipeline-backup.db");
+    let backup_arg = backup.to_string_lossy().into_owned();
+
+    assert_success(
+        &run(
+            &db_path,
+            &["role", "assign", "tenant-admin", "admin", "--namespace", "analytics"],
+        ),
+        "assign namespace admin",
+    );
+
+    let denied = run_as(
+        &db_path,
+        "tenant-admin",
+        &["db", "backup", &backup_arg, "--namespace", "analytics"],
+    );
+    assert_eq!(
+        denied.status.code(),
+        Some(77),
+        "namespace admins manage only their namespace"
+    );
+    assert!(!backup.exists());
+
+    assert_success(&run(&db_path, &["db", "backup", &backup_arg]), "db backup");
+}
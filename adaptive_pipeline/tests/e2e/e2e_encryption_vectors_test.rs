@@ -0,0 +1,96 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Encryption Vector Tests
+//!
+//! `tests/golden/vectors/` holds the published chunk encryption test vectors.
+//! Every build must reproduce them; a failure here means the encrypted chunk
+//! layout changed and existing `.adapipe` files or third-party readers would
+//! break.
+//!
+//! Vectors are only regenerated for a new format version:
+//!
+//! ```text
+//! adaptive_pipeline vectors generate --output tests/golden/vectors/chunk-encryption-v<N>.json
+//! ```
+
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::common::run;
+
+fn published_vectors() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join("vectors")
+        .join("chunk-encryption-v2.json")
+}
+
+#[test]
+fn test_e2e_published_vectors_verify() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("vectors.db");
+
+    let verified = run(
+        &db_path,
+        &["vectors", "verify", "--input", &published_vectors().to_string_lossy()],
+    );
+    let stdout = String::from_utf8_lossy(&verified.stdout);
+    assert!(
+        verified.status.success(),
+        "published vectors no longer verify:\n{}{}",
+        stdout,
+        String::from_utf8_lossy(&verified.stderr)
+    );
+    assert!(
+        stdout.contains("All 6 test vectors verified"),
+        "verify output:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_e2e_generated_vectors_match_published_vectors() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("vectors.db");
+    let generated = temp_dir.path().join("generated.json");
+
+    let output = run(
+        &db_path,
+        &["vectors", "generate", "--output", &generated.to_string_lossy()],
+    );
+    assert!(output.status.success());
+
+    // Provenance differs from build to build; the vectors must not
+    let vectors = |path: &Path| {
+        let suite: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        suite["vectors"].clone()
+    };
+    assert_eq!(vectors(&generated), vectors(&published_vectors()));
+}
+
+#[test]
+fn test_e2e_altered_vectors_fail_verification() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("vectors.db");
+    let altered = temp_dir.path().join("altered.json");
+
+    let mut suite: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(published_vectors()).unwrap()).unwrap();
+    suite["vectors"][3]["nonce"] = serde_json::Value::String("ff".repeat(12));
+    std::fs::write(&altered, serde_json::to_string_pretty(&suite).unwrap()).unwrap();
+
+    let verified = run(&db_path, &["vectors", "verify", "--input", &altered.to_string_lossy()]);
+    assert!(!verified.status.success());
+    let stdout = String::from_utf8_lossy(&verified.stdout);
+    assert!(
+        stdout.contains("❌ chacha20-poly1305/empty"),
+        "verify output:\n{}",
+        stdout
+    );
+}
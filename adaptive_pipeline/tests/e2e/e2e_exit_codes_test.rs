@@ -0,0 +1,103 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Exit Code Tests
+//!
+//! Verifies that `exit-codes` documents the CLI's exit code contract and that
+//! the binary exits with the codes it documents.
+
+use tempfile::TempDir;
+
+use crate::common::pipeline_command;
+
+#[test]
+fn test_e2e_exit_codes_json_lists_stable_codes() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = pipeline_command(&temp_dir.path().join("exit-codes.db"))
+        .args(["exit-codes", "--json"])
+        .output()
+        .expect("Failed to run exit-codes");
+    assert!(output.status.success());
+
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).expect("exit-codes --json is not JSON");
+    let codes: Vec<i64> = rows.iter().map(|row| row["code"].as_i64().unwrap()).collect();
+    assert!(
+        codes.windows(2).all(|pair| pair[0] < pair[1]),
+        "codes must be unique and ascending"
+    );
+
+    let name_of = |code: i64| {
+        rows.iter()
+            .find(|row| row["code"] == code)
+            .map(|row| row["name"].clone())
+    };
+    assert_eq!(name_of(0), Some("EX_OK".into()));
+    assert_eq!(name_of(66), Some("EX_NOINPUT".into()));
+    assert_eq!(name_of(80), Some("EX_PARTIAL".into()));
+    assert_eq!(name_of(81), Some("EX_INTEGRITY".into()));
+    assert_eq!(name_of(82), Some("EX_CANCELLED".into()));
+    assert!(rows.iter().all(|row| !row["cause"].as_str().unwrap().is_empty()));
+}
+
+#[test]
+fn test_e2e_unknown_pipeline_exits_with_documented_code() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = pipeline_command(&temp_dir.path().join("exit-codes.db"))
+        .args(["show", "no-such-pipeline"])
+        .output()
+        .expect("Failed to run show");
+
+    assert_eq!(
+        output.status.code(),
+        Some(66),
+        "an unknown pipeline must exit EX_NOINPUT"
+    );
+}
+
+#[test]
+fn test_e2e_partial_batch_exits_80_until_retried() {
+    let temp_dir = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        pipeline_command(&temp_dir.path().join("exit-codes.db"))
+            .args(args)
+            .output()
+            .expect("Failed to run pipeline command")
+    };
+    let path = |name: &str| temp_dir.path().join(name).to_string_lossy().into_owned();
+    for name in ["a.txt", "b.txt"] {
+        std::fs::write(temp_dir.path().join(name), b"Batch E2E test data.\n".repeat(20)).unwrap();
+    }
+    std::fs::create_dir(temp_dir.path().join("out")).unwrap();
+    std::fs::write(temp_dir.path().join("out/b.txt.adapipe"), b"an earlier archive").unwrap();
+    assert!(run(&["create", "--name", "batch-test", "--stages", "brotli"])
+        .status
+        .success());
+
+    let batch = run(&[
+        "process-batch",
+        &path("a.txt"),
+        &path("b.txt"),
+        "--output-dir",
+        &path("out"),
+        "--pipeline",
+        "batch-test",
+    ]);
+    assert_eq!(
+        batch.status.code(),
+        Some(80),
+        "a partly failed batch must exit EX_PARTIAL"
+    );
+
+    std::fs::remove_file(temp_dir.path().join("out/b.txt.adapipe")).unwrap();
+    let retry = run(&["process-batch", "--retry-failed", &path("out/batch-failures.json")]);
+    assert!(
+        retry.status.success(),
+        "retry failed: {}",
+        String::from_utf8_lossy(&retry.stderr)
+    );
+    assert!(temp_dir.path().join("out/b.txt.adapipe").exists());
+}
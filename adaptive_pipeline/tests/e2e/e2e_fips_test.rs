@@ -0,0 +1,87 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End FIPS Mode Tests
+//!
+//! Verifies through the CLI that the `fips` feature rejects non-approved
+//! cryptography at pipeline creation and stamps processed files. The same
+//! tests run in standard builds, where both behaviours must be absent, so
+//! `cargo test --features fips` and `cargo test` each cover one side.
+
+use tempfile::TempDir;
+
+use crate::common::run;
+
+const FIPS: bool = cfg!(feature = "fips");
+
+#[test]
+fn test_e2e_non_approved_cipher_depends_on_fips_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("fips.db");
+
+    let chacha = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "chacha-test",
+            "--stages",
+            "encryption:chacha20poly1305",
+        ],
+    );
+    assert_eq!(chacha.status.success(), !FIPS, "ChaCha20-Poly1305 is not FIPS-approved");
+
+    let aes = run(
+        &db_path,
+        &["create", "--name", "aes-test", "--stages", "encryption:aes256gcm"],
+    );
+    assert!(
+        aes.status.success(),
+        "AES-256-GCM must always be available: {}",
+        String::from_utf8_lossy(&aes.stderr)
+    );
+}
+
+#[test]
+fn test_e2e_output_header_records_fips_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("fips.db");
+    let input = temp_dir.path().join("input.txt");
+    let output = temp_dir.path().join("output.adapipe");
+    std::fs::write(&input, b"FIPS E2E test data.\n".repeat(100)).unwrap();
+
+    let created = run(&db_path, &["create", "--name", "fips-stamp", "--stages", "brotli"]);
+    assert!(created.status.success());
+
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "fips-stamp",
+        ],
+    );
+    assert!(
+        processed.status.success(),
+        "process failed: {}",
+        String::from_utf8_lossy(&processed.stderr)
+    );
+
+    let validated = run(&db_path, &["validate-file", "--file", &output.to_string_lossy()]);
+    assert!(validated.status.success());
+    let stdout = String::from_utf8_lossy(&validated.stdout);
+    assert_eq!(
+        stdout.contains("FIPS mode: yes"),
+        FIPS,
+        "validate-file output:\n{}",
+        stdout
+    );
+}
@@ -0,0 +1,333 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Golden Corpus Compatibility Tests
+//!
+//! `tests/golden/v<N>/` holds `.adapipe` files written by released builds,
+//! one directory per format version, each with a `corpus.json` listing the
+//! archives, the input each came from and the stages that produced it. Every
+//! build must restore every archive to its input byte for byte, so a change
+//! that silently breaks reading older files fails here. Encrypted archives
+//! record their fixed test passphrase in `corpus.json`; FIPS builds, which
+//! refuse the Argon2id password KDF, skip them.
+//!
+//! Released directories are never rewritten. When the format version is
+//! bumped, write the new version's corpus with
+//!
+//! ```text
+//! cargo test -p adaptive-pipeline --test e2e -- --ignored write_golden_corpus
+//! ```
+//!
+//! and commit it next to the older ones.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use adaptive_pipeline::application::commands::RestoreFileCommand;
+use adaptive_pipeline::application::use_cases::{ProcessFileConfig, ProcessFileUseCase, RestoreFileUseCase};
+use adaptive_pipeline::infrastructure::metrics::MetricsService;
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+use adaptive_pipeline::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+use adaptive_pipeline::test_util::generators::stage;
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_domain::entities::{Pipeline, StageType};
+use adaptive_pipeline_domain::value_objects::binary_file_format::CURRENT_FORMAT_VERSION;
+use adaptive_pipeline_domain::value_objects::nonce_strategy::NONCE_STRATEGY_KEY;
+use adaptive_pipeline_domain::value_objects::password_kdf::KDF_MEMORY_KEY;
+use adaptive_pipeline_domain::value_objects::{
+    ByteRange, ChunkSize, JobPriority, OverwritePolicy, SecretBytes, FIPS_MODE,
+};
+use serde::{Deserialize, Serialize};
+use tempfile::TempDir;
+
+/// Stage combinations written into each new corpus, as `(name, algorithms)`
+///
+/// LZ4 has no codec yet.
+const COMBINATIONS: [(&str, &[&str]); 8] = [
+    ("brotli", &["brotli"]),
+    ("gzip", &["gzip"]),
+    ("zstd", &["zstd"]),
+    ("base64", &["base64"]),
+    ("passthrough", &["passthrough"]),
+    ("brotli-base64", &["brotli", "base64"]),
+    ("gzip-passthrough", &["gzip", "passthrough"]),
+    ("base64-zstd", &["base64", "zstd"]),
+];
+
+/// Encrypted combinations written into each new corpus, as `(name,
+/// algorithms, nonce strategy)`, one per AEAD and nonce strategy
+const ENCRYPTED_COMBINATIONS: [(&str, &[&str], &str); 4] = [
+    ("aes256gcm", &["aes256gcm"], "random"),
+    ("chacha20poly1305-counter", &["chacha20poly1305"], "counter"),
+    ("xchacha20poly1305", &["chacha20poly1305"], "xchacha-random"),
+    ("brotli-aes256gcm-counter", &["brotli", "aes256gcm"], "counter"),
+];
+
+/// Passphrase of every encrypted archive in the corpus; a fixed test value,
+/// not a secret
+const GOLDEN_PASSPHRASE: &str = "golden corpus: amber-lattice-quiver-93";
+
+/// Argon2id memory in KiB for encrypted archives, kept low so restoring the
+/// corpus stays fast
+const GOLDEN_KDF_MEMORY_KIB: &str = "64";
+
+/// Inputs in `tests/golden/inputs/` with the chunk size they are processed
+/// with; both span several chunks
+const INPUTS: [(&str, usize); 2] = [("text.txt", 1024), ("binary.bin", 1000)];
+
+/// One archive in a corpus
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenEntry {
+    /// Archive file name in the version directory
+    archive: String,
+    /// Input file name in `tests/golden/inputs/`
+    input: String,
+    /// Algorithms of the stages that produced the archive, in order
+    stages: Vec<String>,
+    /// Chunk size the archive was written with
+    chunk_size: usize,
+    /// Nonce strategy of the encryption stage, if the archive is encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nonce_strategy: Option<String>,
+    /// Passphrase the archive was encrypted with, if it is encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+}
+
+/// `corpus.json` of one format version
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenCorpus {
+    format_version: u16,
+    /// Crate version that wrote the archives
+    written_by: String,
+    entries: Vec<GoldenEntry>,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn version_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(golden_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix('v'))
+                .is_some_and(|version| version.parse::<u16>().is_ok())
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn read_corpus(dir: &Path) -> GoldenCorpus {
+    let json = std::fs::read_to_string(dir.join("corpus.json")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[tokio::test]
+async fn test_e2e_golden_corpus_restores_bit_exactly() {
+    let dirs = version_dirs();
+    assert!(
+        dirs.iter()
+            .any(|dir| dir.ends_with(format!("v{}", CURRENT_FORMAT_VERSION))),
+        "no golden corpus for format version {}; run the ignored write_golden_corpus test",
+        CURRENT_FORMAT_VERSION
+    );
+
+    let metrics_service = Arc::new(MetricsService::new().unwrap());
+    let scratch = TempDir::new().unwrap();
+    let mut restored_count = 0;
+    for dir in dirs {
+        let corpus = read_corpus(&dir);
+        assert_eq!(
+            dir.file_name().unwrap().to_string_lossy(),
+            format!("v{}", corpus.format_version)
+        );
+
+        let mut listed: Vec<&str> = corpus.entries.iter().map(|entry| entry.archive.as_str()).collect();
+        let mut on_disk: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".adapipe"))
+            .collect();
+        listed.sort_unstable();
+        on_disk.sort_unstable();
+        assert_eq!(
+            listed,
+            on_disk,
+            "{} lists different archives than it holds",
+            dir.display()
+        );
+
+        for entry in &corpus.entries {
+            // FIPS builds refuse the Argon2id password KDF (and ChaCha20)
+            if entry.password.is_some() && FIPS_MODE {
+                continue;
+            }
+            let archive = dir.join(&entry.archive);
+            let command = |output: &Path| {
+                let command = RestoreFileCommand::new(archive.clone(), output.to_path_buf());
+                match &entry.password {
+                    Some(password) => command.with_password(SecretBytes::from(password.as_str())),
+                    None => command,
+                }
+            };
+            let header = AdapipeFormat::new().read_metadata(&archive).await.unwrap();
+            assert_eq!(header.format_version, corpus.format_version, "{}", archive.display());
+            assert_eq!(header.chunk_size as usize, entry.chunk_size, "{}", archive.display());
+
+            let restored = scratch
+                .path()
+                .join(format!("v{}-{}", corpus.format_version, entry.archive));
+            RestoreFileUseCase::new(metrics_service.clone())
+                .execute(command(&restored))
+                .await
+                .unwrap_or_else(|err| panic!("cannot restore {}: {:#}", archive.display(), err));
+            let expected = std::fs::read(golden_dir().join("inputs").join(&entry.input)).unwrap();
+            assert!(
+                std::fs::read(&restored).unwrap() == expected,
+                "{} no longer restores to {}",
+                archive.display(),
+                entry.input
+            );
+
+            // A range is found through the chunk index, or by walking the
+            // chunk headers of archives written without one
+            let (start, end) = (expected.len() / 3, expected.len() / 2);
+            let ranged = scratch
+                .path()
+                .join(format!("v{}-range-{}", corpus.format_version, entry.archive));
+            RestoreFileUseCase::new(metrics_service.clone())
+                .execute(command(&ranged).with_range(Some(ByteRange::new(start as u64, Some(end as u64)).unwrap())))
+                .await
+                .unwrap_or_else(|err| panic!("cannot restore a range of {}: {:#}", archive.display(), err));
+            assert!(
+                std::fs::read(&ranged).unwrap() == expected[start..=end],
+                "{} no longer restores bytes {}-{}",
+                archive.display(),
+                start,
+                end
+            );
+            restored_count += 1;
+        }
+    }
+    assert!(restored_count >= COMBINATIONS.len());
+}
+
+/// Writes the corpus for [`CURRENT_FORMAT_VERSION`] if it does not exist yet
+#[tokio::test]
+#[ignore = "writes tests/golden/v<N>; run once per format version"]
+async fn write_golden_corpus() {
+    let dir = golden_dir().join(format!("v{}", CURRENT_FORMAT_VERSION));
+    assert!(!dir.exists(), "{} is released and must not be rewritten", dir.display());
+    let _ = init_resource_manager(ResourceConfig::default());
+    let scratch = TempDir::new().unwrap();
+    let repository = Arc::new(
+        SqlitePipelineRepository::new(&scratch.path().join("pipeline.db").to_string_lossy())
+            .await
+            .unwrap(),
+    );
+    let use_case = ProcessFileUseCase::builder()
+        .pipeline_repository(repository.clone())
+        .build()
+        .await
+        .unwrap();
+
+    std::fs::create_dir(&dir).unwrap();
+    let mut entries = Vec::new();
+    let combinations = COMBINATIONS
+        .iter()
+        .map(|&(name, algorithms)| (name, algorithms, None))
+        .chain(
+            ENCRYPTED_COMBINATIONS
+                .iter()
+                .map(|&(name, algorithms, nonce_strategy)| (name, algorithms, Some(nonce_strategy))),
+        );
+    for (name, algorithms, nonce_strategy) in combinations {
+        let stages = algorithms
+            .iter()
+            .map(|&algorithm| {
+                let (stage_type, parameters) = match algorithm {
+                    "brotli" | "gzip" | "zstd" => (StageType::Compression, HashMap::new()),
+                    "passthrough" => (StageType::PassThrough, HashMap::new()),
+                    "aes256gcm" | "chacha20poly1305" => (
+                        StageType::Encryption,
+                        HashMap::from([
+                            (
+                                NONCE_STRATEGY_KEY.to_string(),
+                                nonce_strategy.unwrap_or("random").to_string(),
+                            ),
+                            (KDF_MEMORY_KEY.to_string(), GOLDEN_KDF_MEMORY_KIB.to_string()),
+                        ]),
+                    ),
+                    _ => (StageType::Transform, HashMap::new()),
+                };
+                stage(algorithm, stage_type, algorithm, parameters)
+            })
+            .collect();
+        let pipeline_name = format!("golden-{}", name);
+        repository
+            .save(&Pipeline::new(pipeline_name.clone(), stages).unwrap())
+            .await
+            .unwrap();
+        let password = nonce_strategy.map(|_| GOLDEN_PASSPHRASE.to_string());
+
+        for (input, chunk_size) in INPUTS {
+            let stem = Path::new(input).file_stem().unwrap().to_string_lossy();
+            let archive = format!("{}.{}.adapipe", stem, name);
+            use_case
+                .execute(ProcessFileConfig {
+                    input: golden_dir().join("inputs").join(input),
+                    output: dir.join(&archive),
+                    pipeline: pipeline_name.clone(),
+                    chunk_size: Some(ChunkSize::new(chunk_size).unwrap()),
+                    workers: Some(2),
+                    channel_depth: None,
+                    inflight_window: None,
+                    write_manifest: false,
+                    signing_key: None,
+                    idempotency_key: None,
+                    stage_timeout: None,
+                    chunk_timeout: None,
+                    max_worker_restarts: 0,
+                    direct_io: false,
+                    checksum_offload: false,
+                    overwrite_policy: OverwritePolicy::default(),
+                    output_mode: None,
+                    priority: JobPriority::default(),
+                    password: password.as_deref().map(SecretBytes::from),
+                    passphrase_policy: PassphrasePolicy::default(),
+                })
+                .await
+                .unwrap();
+            entries.push(GoldenEntry {
+                archive,
+                input: input.to_string(),
+                stages: algorithms.iter().map(|algorithm| algorithm.to_string()).collect(),
+                chunk_size,
+                nonce_strategy: nonce_strategy.map(str::to_string),
+                password: password.clone(),
+            });
+        }
+    }
+
+    let corpus = GoldenCorpus {
+        format_version: CURRENT_FORMAT_VERSION,
+        written_by: env!("CARGO_PKG_VERSION").to_string(),
+        entries,
+    };
+    std::fs::write(
+        dir.join("corpus.json"),
+        serde_json::to_string_pretty(&corpus).unwrap() + "\n",
+    )
+    .unwrap();
+}
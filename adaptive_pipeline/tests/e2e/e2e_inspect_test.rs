@@ -0,0 +1,82 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Inspect Tests
+//!
+//! Verifies that processed files carry the build provenance of the binary
+//! that wrote them and that `inspect` reports it.
+
+use tempfile::TempDir;
+
+use crate::common::run;
+
+#[test]
+fn test_e2e_inspect_reports_build_provenance() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("inspect.db");
+    let input = temp_dir.path().join("input.txt");
+    let output = temp_dir.path().join("output.adapipe");
+    std::fs::write(&input, b"Inspect E2E test data.\n".repeat(100)).unwrap();
+
+    let created = run(&db_path, &["create", "--name", "inspect-test", "--stages", "brotli"]);
+    assert!(created.status.success());
+
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "inspect-test",
+        ],
+    );
+    assert!(
+        processed.status.success(),
+        "process failed: {}",
+        String::from_utf8_lossy(&processed.stderr)
+    );
+
+    let inspected = run(&db_path, &["inspect", "--file", &output.to_string_lossy()]);
+    assert!(inspected.status.success());
+    let stdout = String::from_utf8_lossy(&inspected.stdout);
+    assert!(stdout.contains("Build provenance"), "inspect output:\n{}", stdout);
+    assert!(
+        stdout.contains("Content type: text/plain"),
+        "inspect output:\n{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!("Version: {}", env!("CARGO_PKG_VERSION"))),
+        "inspect output:\n{}",
+        stdout
+    );
+
+    let json = run(&db_path, &["inspect", "--file", &output.to_string_lossy(), "--json"]);
+    assert!(json.status.success());
+    // Log lines share stdout with the command output; the header starts at
+    // the first line that opens a JSON object
+    let stdout = String::from_utf8_lossy(&json.stdout);
+    let start = stdout.find("{\n").expect("no JSON object in inspect output");
+    let header: serde_json::Value = serde_json::from_str(&stdout[start..]).unwrap();
+    assert_eq!(header["provenance"]["crate_version"], env!("CARGO_PKG_VERSION"));
+    assert!(header["provenance"]["git_commit"].is_string());
+    assert!(header["provenance"]["target"].is_string());
+    assert_eq!(header["content_type"], "text/plain");
+}
+
+#[test]
+fn test_e2e_inspect_missing_file_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("inspect.db");
+    let missing = temp_dir.path().join("missing.adapipe");
+
+    let inspected = run(&db_path, &["inspect", "--file", &missing.to_string_lossy()]);
+    assert!(!inspected.status.success());
+}
@@ -0,0 +1,123 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Job Queue Tests
+//!
+//! Verifies that `submit` queues files, that `daemon` runs them to
+//! completion and records the result, and that `jobs cancel` keeps a queued
+//! job from running.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command, run};
+
+/// Submits `input` and returns the job ID from the last line of output
+fn submit(db_path: &Path, input: &Path, pipeline: &str) -> String {
+    let submitted = run(db_path, &["submit", &input.to_string_lossy(), "--pipeline", pipeline]);
+    assert_success(&submitted, "submit");
+    let stdout = String::from_utf8_lossy(&submitted.stdout).to_string();
+    stdout
+        .lines()
+        .last()
+        .expect("submit prints the job ID")
+        .trim()
+        .to_string()
+}
+
+/// The job printed by `jobs status --json`. Log lines share stdout with the
+/// command output; the job starts at the first line that opens an object.
+fn status(db_path: &Path, id: &str) -> serde_json::Value {
+    let shown = run(db_path, &["jobs", "status", id, "--json"]);
+    assert_success(&shown, "jobs status");
+    let stdout = String::from_utf8_lossy(&shown.stdout);
+    let start = stdout.find("\n{").expect("no JSON object in jobs status output") + 1;
+    serde_json::from_str(&stdout[start..]).expect("jobs status --json is not JSON")
+}
+
+#[test]
+fn test_e2e_daemon_runs_submitted_jobs() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("jobs.db");
+    let created = run(&db_path, &["create", "--name", "jobs-test", "--stages", "brotli"]);
+    assert_success(&created, "create");
+
+    let data: Vec<u8> = (0..50_000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let input = temp_dir.path().join("a.bin");
+    std::fs::write(&input, &data).unwrap();
+    let skipped = temp_dir.path().join("b.bin");
+    std::fs::write(&skipped, &data).unwrap();
+
+    assert!(
+        !run(&db_path, &["submit", &input.to_string_lossy(), "--pipeline", "missing"])
+            .status
+            .success()
+    );
+    let id = submit(&db_path, &input, "jobs-test");
+    let cancelled_id = submit(&db_path, &skipped, "jobs-test");
+    assert_eq!(status(&db_path, &id)["state"], "queued");
+
+    let cancelled = run(&db_path, &["jobs", "cancel", &cancelled_id]);
+    assert_success(&cancelled, "jobs cancel");
+    assert!(!run(&db_path, &["jobs", "cancel", &cancelled_id]).status.success());
+    let listed = run(&db_path, &["jobs", "list"]);
+    assert_success(&listed, "jobs list");
+    let listed = String::from_utf8_lossy(&listed.stdout).to_string();
+    assert!(listed.contains(&id) && listed.contains("cancelled"), "{}", listed);
+
+    let mut daemon = pipeline_command(&db_path)
+        .args(["daemon", "--max-jobs", "1"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Failed to start daemon");
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let job = loop {
+        let job = status(&db_path, &id);
+        if job["state"] == "completed" || job["state"] == "failed" || Instant::now() > deadline {
+            break job;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    };
+    daemon.kill().ok();
+    daemon.wait().ok();
+
+    assert_eq!(job["state"], "completed", "{}", job);
+    assert_eq!(job["input_bytes"], data.len());
+    assert!(temp_dir.path().join("a.bin.adapipe").exists());
+    assert_eq!(status(&db_path, &cancelled_id)["state"], "cancelled");
+    assert!(!temp_dir.path().join("b.bin.adapipe").exists());
+
+    let restored_dir = temp_dir.path().join("restored");
+    let restored = run(
+        &db_path,
+        &[
+            "restore",
+            "--input",
+            &temp_dir.path().join("a.bin.adapipe").to_string_lossy(),
+            "--output-dir",
+            &restored_dir.to_string_lossy(),
+            "--mkdir",
+        ],
+    );
+    assert_success(&restored, "restore");
+    assert_eq!(std::fs::read(restored_dir.join("a.bin")).unwrap(), data);
+}
+
+#[test]
+fn test_e2e_jobs_status_rejects_unknown_ids() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("jobs.db");
+
+    assert!(!run(&db_path, &["jobs", "status", "not-a-job"]).status.success());
+    assert!(!run(&db_path, &["jobs", "status", "01J9Z3Q8F6W2C4M7K1N5R0T8XY"])
+        .status
+        .success());
+}
@@ -0,0 +1,66 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Archive Listing Tests
+//!
+//! Verifies that `ls` lists a tree written by `process --recursive` from the
+//! headers alone, honouring its globs.
+
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+#[test]
+fn test_e2e_ls_lists_a_processed_tree() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("ls.db");
+    let created = run(&db_path, &["create", "--name", "ls-test", "--stages", "brotli"]);
+    assert_success(&created, "create");
+
+    let input_dir = temp_dir.path().join("data");
+    for file in ["app.log", "service/worker.log", "report.csv"] {
+        let path = input_dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{}\n", file).repeat(300)).unwrap();
+    }
+    let archive_dir = temp_dir.path().join("archives");
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input_dir.to_string_lossy(),
+            "--output",
+            &archive_dir.to_string_lossy(),
+            "--pipeline",
+            "ls-test",
+            "--recursive",
+        ],
+    );
+    assert_success(&processed, "process --recursive");
+
+    let listed = run(&db_path, &["ls", &archive_dir.to_string_lossy(), "--include", "*.log"]);
+    assert_success(&listed, "ls");
+    let stdout = String::from_utf8_lossy(&listed.stdout);
+    assert!(stdout.contains("(2 entries)"), "{}", stdout);
+    assert!(stdout.contains("app.log"), "{}", stdout);
+    assert!(stdout.contains(&Path::new("service").join("worker.log").display().to_string()));
+    assert!(!stdout.contains("report.csv"), "{}", stdout);
+
+    let single = run(
+        &db_path,
+        &["ls", &archive_dir.join("report.csv.adapipe").to_string_lossy()],
+    );
+    assert_success(&single, "ls of one file");
+    let stdout = String::from_utf8_lossy(&single.stdout);
+    assert!(
+        stdout.contains("(1 entry)") && stdout.contains("report.csv"),
+        "{}",
+        stdout
+    );
+}
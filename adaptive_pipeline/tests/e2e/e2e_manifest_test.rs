@@ -0,0 +1,173 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Processing Manifest Tests
+//!
+//! Verifies that `process --signing-key` writes a signed detached manifest
+//! and that `verify-manifest` accepts it only with the signer pinned, checks
+//! the archive and rejects tampering, that the manifest reports the run's
+//! concurrency, and that offloaded checksums match the files.
+
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+use crate::common::run;
+
+/// Processes a small file and returns (db, archive) paths
+fn process(temp_dir: &TempDir, extra: &[&str]) -> (PathBuf, PathBuf) {
+    let db_path = temp_dir.path().join("manifest.db");
+    let input = temp_dir.path().join("input.txt");
+    let output = temp_dir.path().join("output.adapipe");
+    std::fs::write(&input, b"Manifest E2E test data.\n".repeat(100)).unwrap();
+
+    let created = run(&db_path, &["create", "--name", "manifest-test", "--stages", "brotli"]);
+    assert!(created.status.success());
+
+    let mut args = vec![
+        "process".to_string(),
+        "--input".to_string(),
+        input.to_string_lossy().into_owned(),
+        "--output".to_string(),
+        output.to_string_lossy().into_owned(),
+        "--pipeline".to_string(),
+        "manifest-test".to_string(),
+    ];
+    args.extend(extra.iter().map(|arg| arg.to_string()));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let processed = run(&db_path, &args);
+    assert!(
+        processed.status.success(),
+        "process failed: {}",
+        String::from_utf8_lossy(&processed.stderr)
+    );
+    (db_path, output)
+}
+
+#[test]
+fn test_e2e_signed_manifest_verifies_and_detects_tampering() {
+    let temp_dir = TempDir::new().unwrap();
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public_key = hex::encode(key_pair.public_key().as_ref());
+    let key_path = temp_dir.path().join("signing-key.der");
+    std::fs::write(&key_path, pkcs8.as_ref()).unwrap();
+
+    let (db_path, archive) = process(&temp_dir, &["--signing-key", &key_path.to_string_lossy()]);
+    let manifest = temp_dir.path().join("output.adapipe.manifest");
+    assert!(manifest.exists(), "manifest was not written");
+
+    let verified = run(
+        &db_path,
+        &[
+            "verify-manifest",
+            "--manifest",
+            &manifest.to_string_lossy(),
+            "--file",
+            &archive.to_string_lossy(),
+            "--public-key",
+            &public_key,
+        ],
+    );
+    assert!(
+        verified.status.success(),
+        "verify-manifest failed: {}",
+        String::from_utf8_lossy(&verified.stdout)
+    );
+
+    let other_key = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let other_public_key = hex::encode(
+        Ed25519KeyPair::from_pkcs8(other_key.as_ref())
+            .unwrap()
+            .public_key()
+            .as_ref(),
+    );
+    let wrong_signer = run(
+        &db_path,
+        &[
+            "verify-manifest",
+            "--manifest",
+            &manifest.to_string_lossy(),
+            "--public-key",
+            &other_public_key,
+        ],
+    );
+    assert!(!wrong_signer.status.success());
+
+    // A valid signature by an unpinned key establishes nothing about the signer
+    let unpinned = run(
+        &db_path,
+        &[
+            "verify-manifest",
+            "--manifest",
+            &manifest.to_string_lossy(),
+            "--file",
+            &archive.to_string_lossy(),
+        ],
+    );
+    assert_eq!(unpinned.status.code(), Some(81));
+    assert!(String::from_utf8_lossy(&unpinned.stdout).contains("signer NOT pinned"));
+    assert!(String::from_utf8_lossy(&unpinned.stdout).contains("Archive matches manifest"));
+
+    let json = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(&manifest, json.replace("manifest-test", "manifest-fake")).unwrap();
+    let tampered = run(
+        &db_path,
+        &[
+            "verify-manifest",
+            "--manifest",
+            &manifest.to_string_lossy(),
+            "--public-key",
+            &public_key,
+        ],
+    );
+    assert!(!tampered.status.success());
+}
+
+#[test]
+fn test_e2e_unsigned_manifest_fails_verification() {
+    let temp_dir = TempDir::new().unwrap();
+    let (db_path, _) = process(&temp_dir, &["--manifest"]);
+    let manifest = temp_dir.path().join("output.adapipe.manifest");
+    assert!(manifest.exists(), "manifest was not written");
+
+    let verified = run(
+        &db_path,
+        &["verify-manifest", "--manifest", &manifest.to_string_lossy()],
+    );
+    assert!(!verified.status.success());
+}
+
+#[test]
+fn test_e2e_manifest_reports_concurrency() {
+    let temp_dir = TempDir::new().unwrap();
+    process(&temp_dir, &["--manifest", "--workers", "2"]);
+    let manifest = temp_dir.path().join("output.adapipe.manifest");
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+
+    let concurrency = &json["concurrency"];
+    assert!(concurrency["workers"].as_u64().unwrap() >= 1);
+    assert!(concurrency["queue_wait_p95_ms"].as_u64().unwrap() >= concurrency["queue_wait_p50_ms"].as_u64().unwrap());
+    assert!(concurrency["cpu_wait_p95_ms"].as_u64().unwrap() >= concurrency["cpu_wait_p50_ms"].as_u64().unwrap());
+    let utilization = concurrency["worker_utilization_percent"].as_f64().unwrap();
+    assert!((0.0..=100.0).contains(&utilization));
+}
+
+#[test]
+fn test_e2e_offloaded_checksums_match_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, archive) = process(&temp_dir, &["--manifest", "--checksum-offload"]);
+    let manifest = temp_dir.path().join("output.adapipe.manifest");
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+
+    let input = std::fs::read(temp_dir.path().join("input.txt")).unwrap();
+    assert_eq!(json["input_checksum"], hex::encode(Sha256::digest(&input)));
+    let output = std::fs::read(&archive).unwrap();
+    assert_eq!(json["output_checksum"], hex::encode(Sha256::digest(&output)));
+}
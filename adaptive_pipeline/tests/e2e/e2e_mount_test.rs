@@ -0,0 +1,76 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Mount Tests
+//!
+//! Verifies that `mount` refuses a mountpoint that isn't a directory and, in
+//! builds without the `fuse` feature, fails without touching the mountpoint.
+//! Serving a mount needs FUSE permissions test runners rarely have, so it is
+//! covered by the range reader's unit tests.
+
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::common::run;
+
+/// Processes a small file and returns the archive's path
+fn archive(temp_dir: &TempDir, db_path: &Path) -> PathBuf {
+    let created = run(db_path, &["create", "--name", "mount-test", "--stages", "brotli"]);
+    assert!(created.status.success(), "{}", String::from_utf8_lossy(&created.stderr));
+
+    let input = temp_dir.path().join("notes.txt");
+    std::fs::write(&input, "mounted notes\n".repeat(500)).unwrap();
+    let output = temp_dir.path().join("notes.adapipe");
+    let processed = run(
+        db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "mount-test",
+        ],
+    );
+    assert!(
+        processed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&processed.stderr)
+    );
+    output
+}
+
+#[test]
+fn test_e2e_mount_rejects_a_file_as_mountpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("mount.db");
+    let archive = archive(&temp_dir, &db_path);
+
+    let mounted = run(
+        &db_path,
+        &["mount", &archive.to_string_lossy(), &archive.to_string_lossy()],
+    );
+    assert!(!mounted.status.success(), "mounting on a file should fail");
+}
+
+#[cfg(not(feature = "fuse"))]
+#[test]
+fn test_e2e_mount_needs_the_fuse_feature() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("mount.db");
+    let archive = archive(&temp_dir, &db_path);
+    let mountpoint = temp_dir.path().join("mnt");
+    std::fs::create_dir(&mountpoint).unwrap();
+
+    let mounted = run(
+        &db_path,
+        &["mount", &archive.to_string_lossy(), &mountpoint.to_string_lossy()],
+    );
+    assert!(!mounted.status.success(), "mount should fail without the fuse feature");
+    assert_eq!(std::fs::read_dir(&mountpoint).unwrap().count(), 0);
+}
@@ -0,0 +1,117 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Namespace Tests
+//!
+//! Verifies through the CLI that `--namespace` isolates pipeline sets and
+//! role assignments sharing one database.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run, run_as};
+
+#[test]
+fn test_e2e_namespaces_isolate_pipelines() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("namespaces.db");
+
+    // The same pipeline name may exist once per namespace
+    for namespace in ["team-a", "team-b"] {
+        assert_success(
+            &run(
+                &db_path,
+                &[
+                    "create",
+                    "--name",
+                    "shared",
+                    "--stages",
+                    "brotli",
+                    "--namespace",
+                    namespace,
+                ],
+            ),
+            "create pipeline",
+        );
+    }
+    let duplicate = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "shared",
+            "--stages",
+            "brotli",
+            "--namespace",
+            "team-a",
+        ],
+    );
+    assert!(!duplicate.status.success(), "names must stay unique within a namespace");
+
+    let listed = run(&db_path, &["list", "--namespace", "team-a"]);
+    assert_success(&listed, "list team-a");
+    let stdout = String::from_utf8_lossy(&listed.stdout);
+    assert!(stdout.contains("Found 1 pipeline(s) in namespace 'team-a'"));
+
+    let default_list = run(&db_path, &["list"]);
+    assert_success(&default_list, "list default");
+    assert!(String::from_utf8_lossy(&default_list.stdout).contains("No pipelines found"));
+
+    // Deleting in one namespace leaves the other untouched
+    assert_success(
+        &run(&db_path, &["delete", "shared", "--force", "--namespace", "team-a"]),
+        "delete in team-a",
+    );
+    let shown = run(&db_path, &["show", "shared", "--namespace", "team-b"]);
+    assert_success(&shown, "show in team-b");
+    assert!(String::from_utf8_lossy(&shown.stdout).contains("Namespace: team-b"));
+    assert!(!run(&db_path, &["show", "shared", "--namespace", "team-a"])
+        .status
+        .success());
+}
+
+#[test]
+fn test_e2e_roles_are_scoped_to_namespace() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("namespace-roles.db");
+
+    assert_success(
+        &run(&db_path, &["role", "assign", "lead", "admin", "--namespace", "team-a"]),
+        "assign namespace admin",
+    );
+    assert_success(
+        &run_as(
+            &db_path,
+            "lead",
+            &[
+                "create",
+                "--name",
+                "etl-jobs",
+                "--stages",
+                "brotli",
+                "--namespace",
+                "team-a",
+            ],
+        ),
+        "create as namespace admin",
+    );
+
+    // A namespace admin has no role in other namespaces
+    let denied = run_as(&db_path, "lead", &["list", "--namespace", "team-b"]);
+    assert_eq!(
+        denied.status.code(),
+        Some(77),
+        "namespace admin must not cross namespaces"
+    );
+    assert_eq!(run_as(&db_path, "lead", &["list"]).status.code(), Some(77));
+
+    // Platform admins, like the OS user who ran `role init`, manage every
+    // namespace
+    assert_success(
+        &run(&db_path, &["delete", "etl-jobs", "--force", "--namespace", "team-a"]),
+        "delete as platform admin",
+    );
+}
@@ -0,0 +1,179 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Password Encryption Tests
+//!
+//! Verifies through the CLI that `--password-prompt` encrypts with a key
+//! derived from the passphrase, and that restore only succeeds when the same
+//! passphrase is given again, and that pipelines for confidential data refuse
+//! weak passphrases. The passphrase is piped on standard input, the way
+//! scripts supply it.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use tempfile::TempDir;
+
+use crate::common::pipeline_command;
+
+fn run(db_path: &Path, args: &[&str], password: Option<&str>) -> Output {
+    run_at(db_path, None, args, password)
+}
+
+fn run_at(db_path: &Path, security_level: Option<&str>, args: &[&str], password: Option<&str>) -> Output {
+    let mut command = pipeline_command(db_path);
+    command.env_remove("ADAPIPE_SECURITY_LEVEL");
+    if let Some(level) = security_level {
+        command.env("ADAPIPE_SECURITY_LEVEL", level);
+    }
+    let mut child = command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run pipeline command");
+    let mut stdin = child.stdin.take().unwrap();
+    if let Some(password) = password {
+        writeln!(stdin, "{}", password).unwrap();
+    }
+    drop(stdin);
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_e2e_password_encrypted_file_restores_only_with_its_passphrase() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("password.db");
+    let input = temp_dir.path().join("secret.txt");
+    let archive = temp_dir.path().join("secret.adapipe");
+    let data = b"Password-protected E2E test data.\n".repeat(500);
+    std::fs::write(&input, &data).unwrap();
+
+    let created = run(
+        &db_path,
+        &["create", "--name", "password-test", "--stages", "brotli,encryption"],
+        None,
+    );
+    assert!(created.status.success(), "create failed");
+
+    let process = |password: Option<&str>| {
+        let mut args = vec![
+            "process",
+            "--input",
+            input.to_str().unwrap(),
+            "--output",
+            archive.to_str().unwrap(),
+            "--pipeline",
+            "password-test",
+            "--if-exists",
+            "overwrite",
+        ];
+        if password.is_some() {
+            args.push("--password-prompt");
+        }
+        run(&db_path, &args, password)
+    };
+    assert!(
+        !process(None).status.success(),
+        "an encrypting pipeline must not run without a passphrase"
+    );
+    let processed = process(Some("correct horse battery staple"));
+    assert!(
+        processed.status.success(),
+        "process failed: {}",
+        String::from_utf8_lossy(&processed.stderr)
+    );
+
+    let restore = |dir: &str, password: Option<&str>| {
+        let output_dir = temp_dir.path().join(dir);
+        let mut args = vec![
+            "restore",
+            "--input",
+            archive.to_str().unwrap(),
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--mkdir",
+        ];
+        if password.is_some() {
+            args.push("--password-prompt");
+        }
+        (run(&db_path, &args, password), output_dir.join("secret.txt"))
+    };
+    let (without, _) = restore("without", None);
+    assert!(!without.status.success(), "restored without a passphrase");
+    let (wrong, wrong_path) = restore("wrong", Some("wrong passphrase"));
+    assert!(!wrong.status.success(), "restored with the wrong passphrase");
+    assert!(!wrong_path.exists());
+
+    let (right, right_path) = restore("right", Some("correct horse battery staple"));
+    assert!(
+        right.status.success(),
+        "restore failed: {}",
+        String::from_utf8_lossy(&right.stderr)
+    );
+    assert_eq!(std::fs::read(right_path).unwrap(), data);
+}
+
+#[test]
+fn test_e2e_confidential_pipeline_refuses_weak_passphrases() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("strength.db");
+    let input = temp_dir.path().join("payroll.csv");
+    let archive = temp_dir.path().join("payroll.adapipe");
+    std::fs::write(&input, b"name,salary\n".repeat(100)).unwrap();
+
+    let created = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "payroll",
+            "--stages",
+            "encryption",
+            "--security-level",
+            "confidential",
+        ],
+        None,
+    );
+    assert!(created.status.success(), "create failed");
+
+    let process = |password: &str, extra: &[&str]| {
+        let mut args = vec![
+            "process",
+            "--input",
+            input.to_str().unwrap(),
+            "--output",
+            archive.to_str().unwrap(),
+            "--pipeline",
+            "payroll",
+            "--if-exists",
+            "overwrite",
+            "--password-prompt",
+        ];
+        args.extend_from_slice(extra);
+        run_at(&db_path, Some("confidential"), &args, Some(password))
+    };
+
+    let weak = process("password1", &[]);
+    assert!(!weak.status.success(), "accepted a weak passphrase");
+    assert!(!archive.exists());
+
+    let lowered = process("password1", &["--min-passphrase-score", "0"]);
+    assert!(
+        lowered.status.success(),
+        "process with a lowered policy failed: {}",
+        String::from_utf8_lossy(&lowered.stderr)
+    );
+
+    let strong = process("correct horse battery staple", &[]);
+    assert!(
+        strong.status.success(),
+        "process with a strong passphrase failed: {}",
+        String::from_utf8_lossy(&strong.stderr)
+    );
+}
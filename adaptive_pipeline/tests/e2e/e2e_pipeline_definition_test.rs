@@ -0,0 +1,133 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Pipeline Definition Tests
+//!
+//! Verifies that `pipeline export` writes definitions `pipeline import`
+//! recreates the same pipeline from in every format, that imported
+//! pipelines run, and that invalid definitions are rejected without
+//! creating a pipeline.
+
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+/// Exports `pipeline` to `path` and returns the definition
+fn export(db_path: &Path, pipeline: &str, path: &Path) -> String {
+    let exported = run(
+        db_path,
+        &["pipeline", "export", pipeline, "-o", &path.to_string_lossy()],
+    );
+    assert_success(&exported, "pipeline export");
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn test_e2e_exported_pipelines_import_unchanged() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("definitions.db");
+    let created = run(
+        &db_path,
+        &[
+            "create",
+            "--name",
+            "definition-test",
+            "--stages",
+            "zstd,aes256gcm",
+            "--checksum",
+            "blake3",
+            "--security-level",
+            "internal",
+        ],
+    );
+    assert_success(&created, "create");
+
+    let yaml = export(&db_path, "definition-test", &temp_dir.path().join("original.yaml"));
+    assert!(yaml.contains("checksum: blake3"), "{}", yaml);
+    assert!(yaml.contains("security_level: internal"), "{}", yaml);
+    assert!(!run(
+        &db_path,
+        &[
+            "pipeline",
+            "export",
+            "definition-test",
+            "-o",
+            &temp_dir.path().join("original.yaml").to_string_lossy()
+        ]
+    )
+    .status
+    .success());
+
+    for extension in ["toml", "yaml", "json"] {
+        let file = temp_dir.path().join(format!("definition.{}", extension));
+        export(&db_path, "definition-test", &file);
+        let copy = format!("imported-{}", extension);
+        let imported = run(
+            &db_path,
+            &["pipeline", "import", &file.to_string_lossy(), "--name", &copy],
+        );
+        assert_success(&imported, "pipeline import");
+
+        let reexported = export(&db_path, &copy, &temp_dir.path().join(format!("{}.yaml", copy)));
+        assert_eq!(reexported.replacen(&copy, "definition-test", 1), yaml, "{}", extension);
+    }
+    // The name is already taken
+    let taken = temp_dir.path().join("definition.toml");
+    assert!(!run(&db_path, &["pipeline", "import", &taken.to_string_lossy()])
+        .status
+        .success());
+}
+
+#[test]
+fn test_e2e_invalid_definitions_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("definitions.db");
+    let import = |name: &str, text: &str| {
+        let file = temp_dir.path().join(name);
+        std::fs::write(&file, text).unwrap();
+        run(&db_path, &["pipeline", "import", &file.to_string_lossy()])
+            .status
+            .success()
+    };
+
+    let stage = "stages:\n- name: squeeze\n  type: compression\n  algorithm: zstd\n";
+    assert!(!import("typo.yaml", &format!("name: typo-test\nworker: 4\n{}", stage)));
+    assert!(!import(
+        "workers.yaml",
+        &format!("name: workers-test\nworkers: 99\n{}", stage)
+    ));
+    assert!(!import(
+        "mismatch.yaml",
+        "name: mismatch-test\nstages:\n- name: squeeze\n  type: compression\n  algorithm: aes-256-gcm\n"
+    ));
+    assert!(!import("unknown.definition", &format!("name: unknown-test\n{}", stage)));
+    assert!(!import("broken.json", "{\"name\": \"broken-test\", \"stages\": ["));
+    assert!(!run(&db_path, &["pipeline", "export", "typo-test"]).status.success());
+
+    assert!(import("good.yaml", &format!("name: good-test\nworkers: 4\n{}", stage)));
+    let yaml = export(&db_path, "good-test", &temp_dir.path().join("good-export.yaml"));
+    assert!(yaml.contains("workers: 4"), "{}", yaml);
+
+    let input = temp_dir.path().join("data.bin");
+    std::fs::write(&input, b"pipeline definitions travel between machines\n".repeat(500)).unwrap();
+    let output = temp_dir.path().join("data.adapipe");
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "good-test",
+        ],
+    );
+    assert_success(&processed, "process");
+    assert!(output.exists());
+}
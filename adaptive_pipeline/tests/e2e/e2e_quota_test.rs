@@ -0,0 +1,93 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Quota Tests
+//!
+//! Verifies through the CLI that `[quota]` limits from the configuration file
+//! reject jobs before processing starts, exiting with `EX_TEMPFAIL` (75).
+
+use tempfile::TempDir;
+
+use crate::common::run;
+
+fn setup(temp_dir: &TempDir, quota: &str) -> (std::path::PathBuf, String, String) {
+    let db_path = temp_dir.path().join("quota.db");
+    let config_path = temp_dir.path().join("pipeline.toml");
+    let input_path = temp_dir.path().join("input.txt");
+    std::fs::write(&config_path, quota).unwrap();
+    std::fs::write(&input_path, b"Quota E2E test data.\n".repeat(50)).unwrap();
+
+    let created = run(&db_path, &["create", "--name", "quota-test", "--stages", "brotli"]);
+    assert!(
+        created.status.success(),
+        "create failed: {}",
+        String::from_utf8_lossy(&created.stderr)
+    );
+
+    (
+        db_path,
+        config_path.to_string_lossy().into_owned(),
+        input_path.to_string_lossy().into_owned(),
+    )
+}
+
+#[test]
+fn test_e2e_input_size_quota_rejects_job() {
+    let temp_dir = TempDir::new().unwrap();
+    let (db_path, config, input) = setup(&temp_dir, "[quota]\nmax_input_bytes = 64\n");
+    let output = temp_dir.path().join("out.adapipe");
+
+    let result = run(
+        &db_path,
+        &[
+            "--config",
+            &config,
+            "process",
+            "--input",
+            &input,
+            "--output",
+            &output.to_string_lossy(),
+            "--pipeline",
+            "quota-test",
+        ],
+    );
+
+    assert_eq!(result.status.code(), Some(75), "oversized input must be rejected");
+    assert!(!output.exists(), "rejected jobs must not write output");
+}
+
+#[test]
+fn test_e2e_daily_output_quota_uses_recorded_usage() {
+    let temp_dir = TempDir::new().unwrap();
+    let (db_path, config, input) = setup(&temp_dir, "[quota.namespaces.default]\nmax_output_bytes_per_day = 1\n");
+    let process = |name: &str| {
+        let output = temp_dir.path().join(name);
+        run(
+            &db_path,
+            &[
+                "--config",
+                &config,
+                "process",
+                "--input",
+                &input,
+                "--output",
+                &output.to_string_lossy(),
+                "--pipeline",
+                "quota-test",
+            ],
+        )
+    };
+
+    // The first job fits today's budget and records its output against it
+    let first = process("first.adapipe");
+    assert!(
+        first.status.success(),
+        "first job failed: {}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    assert_eq!(process("second.adapipe").status.code(), Some(75));
+}
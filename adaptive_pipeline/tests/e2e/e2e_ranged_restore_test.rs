@@ -0,0 +1,82 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Ranged Restore Tests
+//!
+//! Verifies that `restore --range` writes exactly the requested bytes of an
+//! archive spanning many chunks, and refuses a range past the end.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+#[test]
+fn test_e2e_restore_range_writes_only_those_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("range.db");
+    let created = run(&db_path, &["create", "--name", "range-test", "--stages", "brotli"]);
+    assert_success(&created, "create");
+
+    let input = temp_dir.path().join("data.bin");
+    let original: Vec<u8> = (0..40_000u32).map(|i| (i * 7 % 251) as u8).collect();
+    std::fs::write(&input, &original).unwrap();
+    let archive = temp_dir.path().join("data.adapipe");
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &archive.to_string_lossy(),
+            "--pipeline",
+            "range-test",
+            "--chunk-size",
+            "4KiB",
+        ],
+    );
+    assert_success(&processed, "process");
+
+    let inspected = run(&db_path, &["inspect", "--file", &archive.to_string_lossy()]);
+    assert_success(&inspected, "inspect");
+    assert!(String::from_utf8_lossy(&inspected.stdout).contains("indexed"));
+
+    let out_dir = temp_dir.path().join("restored");
+    let restored = run(
+        &db_path,
+        &[
+            "restore",
+            "--input",
+            &archive.to_string_lossy(),
+            "--output-dir",
+            &out_dir.to_string_lossy(),
+            "--mkdir",
+            "--range",
+            "5000-13000",
+        ],
+    );
+    assert_success(&restored, "restore --range");
+    let bytes = std::fs::read(out_dir.join("data.bin")).unwrap();
+    assert_eq!(bytes, &original[5000..=13000]);
+
+    let past_end = run(
+        &db_path,
+        &[
+            "restore",
+            "--input",
+            &archive.to_string_lossy(),
+            "--output-dir",
+            &out_dir.to_string_lossy(),
+            "--if-exists",
+            "overwrite",
+            "--range",
+            "40000-",
+        ],
+    );
+    assert!(!past_end.status.success(), "a range past the end should fail");
+    assert_eq!(std::fs::read(out_dir.join("data.bin")).unwrap(), bytes);
+}
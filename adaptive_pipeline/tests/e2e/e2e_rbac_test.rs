@@ -0,0 +1,68 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Role-Based Access Control Tests
+//!
+//! Verifies through the CLI that role assignments persist in the database and
+//! gate protected commands, and that a database without assignments refuses
+//! everything but `role init`.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, bare_command, run, run_as};
+
+#[test]
+fn test_e2e_roles_gate_protected_commands() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("rbac.db");
+
+    // The OS user running the tests is the database's first admin
+    assert_success(&run(&db_path, &["role", "assign", "root", "admin"]), "assign admin");
+    assert_success(
+        &run_as(&db_path, "root", &["role", "assign", "ops", "operator"]),
+        "assign operator",
+    );
+    assert_success(
+        &run_as(
+            &db_path,
+            "root",
+            &["create", "--name", "rbac-test", "--stages", "brotli"],
+        ),
+        "create pipeline",
+    );
+
+    let listed = run_as(&db_path, "ops", &["role", "list"]);
+    assert_success(&listed, "role list");
+    let stdout = String::from_utf8_lossy(&listed.stdout);
+    assert!(stdout.contains("ops") && stdout.contains("operator"));
+
+    // Operators may view but not delete; unassigned principals may do nothing
+    assert_success(&run_as(&db_path, "ops", &["show", "rbac-test"]), "show as operator");
+    let denied = run_as(&db_path, "ops", &["delete", "rbac-test", "--force"]);
+    assert_eq!(denied.status.code(), Some(77), "operator must not delete pipelines");
+    assert_eq!(run_as(&db_path, "stranger", &["list"]).status.code(), Some(77));
+
+    assert_success(
+        &run_as(&db_path, "root", &["delete", "rbac-test", "--force"]),
+        "delete as admin",
+    );
+}
+
+#[test]
+fn test_e2e_database_without_roles_denies_all_but_init() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("rbac_first.db");
+    let bare = |args: &[&str]| bare_command(&db_path).args(args).output().unwrap();
+
+    // Nobody may act, or assign themselves a role, before `role init`
+    assert_eq!(bare(&["list"]).status.code(), Some(77));
+    assert_eq!(bare(&["role", "assign", "ops", "admin"]).status.code(), Some(77));
+
+    assert_success(&bare(&["role", "init"]), "role init");
+    assert_success(&bare(&["list"]), "list as the first admin");
+    assert!(!bare(&["role", "init"]).status.success(), "init runs only once");
+}
@@ -0,0 +1,100 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Recursive Processing Tests
+//!
+//! Verifies through the CLI that `process --recursive` archives the files
+//! its globs select into a mirrored tree that restores unchanged, and that a
+//! directory is refused without `--recursive`.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+#[test]
+fn test_e2e_recursive_process_archives_the_selected_tree() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("recursive.db");
+    let created = run(
+        &db_path,
+        &["create", "--name", "recursive-test", "--stages", "brotli,checksum"],
+    );
+    assert_success(&created, "create");
+
+    let input_dir = temp_dir.path().join("logs");
+    let files = ["app.log", "service/worker.log", "service/worker.tmp", "notes.txt"];
+    for (i, file) in files.iter().enumerate() {
+        let path = input_dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("line {} of {}\n", i, file).repeat(200)).unwrap();
+    }
+    let output_dir = temp_dir.path().join("archives");
+
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input_dir.to_string_lossy(),
+            "--output",
+            &output_dir.to_string_lossy(),
+            "--pipeline",
+            "recursive-test",
+            "--recursive",
+            "--include",
+            "*.log",
+            "--exclude",
+            "*.tmp",
+        ],
+    );
+    assert_success(&processed, "process --recursive");
+    let stdout = String::from_utf8_lossy(&processed.stdout);
+    assert!(stdout.contains("2 of 2 files processed"), "{}", stdout);
+
+    assert!(output_dir.join("app.log.adapipe").is_file());
+    assert!(output_dir.join("service/worker.log.adapipe").is_file());
+    assert!(!output_dir.join("service/worker.tmp.adapipe").exists());
+    assert!(!output_dir.join("notes.txt.adapipe").exists());
+
+    let restore_dir = temp_dir.path().join("restored");
+    let restored = run(
+        &db_path,
+        &[
+            "restore",
+            "--input",
+            &output_dir.join("service/worker.log.adapipe").to_string_lossy(),
+            "--output-dir",
+            &restore_dir.to_string_lossy(),
+            "--mkdir",
+        ],
+    );
+    assert_success(&restored, "restore");
+    assert_eq!(
+        std::fs::read(restore_dir.join("worker.log")).unwrap(),
+        std::fs::read(input_dir.join("service/worker.log")).unwrap()
+    );
+}
+
+#[test]
+fn test_e2e_directory_input_requires_recursive() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("recursive.db");
+
+    let output = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &temp_dir.path().to_string_lossy(),
+            "--output",
+            &temp_dir.path().join("out").to_string_lossy(),
+            "--pipeline",
+            "recursive-test",
+        ],
+    );
+    assert!(!output.status.success());
+}
@@ -0,0 +1,99 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Replication Tests
+//!
+//! Verifies that `replicate` copies cataloged archives to a directory,
+//! records each replica in the catalog, skips recorded replicas when run
+//! again, and that the copies restore like the originals.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command, run};
+
+#[test]
+fn test_e2e_replicate_copies_verifies_and_records_archives() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("replicate.db");
+    let created = run(&db_path, &["create", "--name", "replicate-test", "--stages", "brotli"]);
+    assert_success(&created, "create");
+
+    let data: Vec<u8> = (0..20_000u32).map(|i| (i * 13 % 241) as u8).collect();
+    let input = temp_dir.path().join("a.bin");
+    std::fs::write(&input, &data).unwrap();
+    let archive = temp_dir.path().join("db").join("a.adapipe");
+    std::fs::create_dir_all(archive.parent().unwrap()).unwrap();
+    let processed = run(
+        &db_path,
+        &[
+            "process",
+            "--input",
+            &input.to_string_lossy(),
+            "--output",
+            &archive.to_string_lossy(),
+            "--pipeline",
+            "replicate-test",
+        ],
+    );
+    assert_success(&processed, "process");
+
+    let catalog = temp_dir.path().join("fleet.catalog");
+    std::fs::write(&catalog, "# test fleet\ndb/a.adapipe\n").unwrap();
+    let catalog_arg = catalog.to_string_lossy().to_string();
+    let offsite = temp_dir.path().join("offsite");
+    let offsite_arg = offsite.to_string_lossy().to_string();
+
+    let replicated = run(
+        &db_path,
+        &["replicate", "--catalog", &catalog_arg, "--to", &offsite_arg],
+    );
+    assert_success(&replicated, "replicate");
+    let replica = offsite.join("db").join("a.adapipe");
+    assert_eq!(std::fs::read(&replica).unwrap(), std::fs::read(&archive).unwrap());
+    assert_eq!(
+        std::fs::read_to_string(&catalog).unwrap(),
+        format!("# test fleet\ndb/a.adapipe\t{}\n", replica.display())
+    );
+
+    // Recorded replicas are skipped, and the catalog still scrubs
+    let again = run(
+        &db_path,
+        &["replicate", "--catalog", &catalog_arg, "--to", &offsite_arg],
+    );
+    assert_success(&again, "replicate again");
+    assert!(String::from_utf8_lossy(&again.stdout).contains("already at"));
+
+    // Uploads to a bucket are never sent over plain HTTP unless allowed
+    let to_bucket = pipeline_command(&db_path)
+        .env("ADAPIPE_OBJECT_STORE_ENDPOINT", "http://127.0.0.1:9")
+        .env("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE")
+        .env("AWS_SECRET_ACCESS_KEY", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+        .env_remove("AWS_ALLOW_HTTP")
+        .args(["replicate", "--catalog", &catalog_arg, "--to", "s3://offsite/fleet"])
+        .output()
+        .expect("Failed to run pipeline command");
+    assert!(!to_bucket.status.success());
+    assert!(!std::fs::read_to_string(&catalog).unwrap().contains("s3://"));
+    let scrubbed = run(&db_path, &["scrub", "--catalog", &catalog_arg]);
+    assert_success(&scrubbed, "scrub");
+
+    // The replica restores to the original
+    let restore_dir = temp_dir.path().join("restored");
+    let restored = run(
+        &db_path,
+        &[
+            "restore",
+            "--input",
+            &replica.to_string_lossy(),
+            "--output-dir",
+            &restore_dir.to_string_lossy(),
+            "--mkdir",
+        ],
+    );
+    assert_success(&restored, "restore");
+    assert_eq!(std::fs::read(restore_dir.join("a.bin")).unwrap(), data);
+}
@@ -16,6 +16,7 @@ use tempfile::TempDir;
 use adaptive_pipeline_domain::entities::pipeline_stage::StageType;
 use adaptive_pipeline_domain::value_objects::binary_file_format::FileHeader;
 use adaptive_pipeline_domain::value_objects::file_chunk::FileChunk;
+use adaptive_pipeline_domain::value_objects::NonceStrategy;
 
 // Import the restore functions from restoration module
 use adaptive_pipeline::create_restoration_pipeline;
@@ -70,7 +71,7 @@ async fn test_e2e_complete_restore_workflow() {
 async fn test_e2e_restoration_stage_ordering() {
     let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
         .add_compression_step("brotli", 6) // Applied first
-        .add_encryption_step("aes256gcm", "argon2", 32, 12); // Applied second
+        .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random); // Applied second
 
     let pipeline = create_restoration_pipeline(&header).await.unwrap();
     let stages = pipeline.stages();
@@ -103,7 +104,7 @@ async fn test_e2e_file_header_roundtrip() {
         "original_checksum_abc123".to_string(),
     )
     .add_compression_step("brotli", 6)
-    .add_encryption_step("aes256gcm", "argon2", 32, 12)
+    .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
     .with_chunk_info(1024 * 1024, 1)
     .with_pipeline_id("e2e-test-pipeline".to_string())
     .with_output_checksum("output_checksum_def456".to_string())
@@ -142,7 +143,7 @@ async fn test_e2e_file_header_roundtrip() {
     assert!(restored_header.is_compressed());
     assert!(restored_header.is_encrypted());
     assert_eq!(restored_header.compression_algorithm(), Some("brotli"));
-    assert_eq!(restored_header.encryption_algorithm(), Some("aes256gcm"));
+    assert_eq!(restored_header.encryption_algorithm(), Some("aes-256-gcm"));
 
     // Verify footer size calculation
     assert_eq!(footer_size, footer_bytes.len());
@@ -161,7 +162,7 @@ async fn test_e2e_real_world_document_restoration() {
         "real_world_checksum_abc123def456".to_string(),
     )
     .add_compression_step("brotli", 9) // High compression
-    .add_encryption_step("aes256gcm", "argon2", 32, 12)
+    .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
     .with_chunk_info(1024 * 1024, 5) // 5 chunks of 1MB each
     .with_pipeline_id("document-processing-pipeline-v2".to_string())
     .with_output_checksum("processed_checksum_789xyz".to_string())
@@ -186,7 +187,7 @@ async fn test_e2e_real_world_document_restoration() {
     let decryption_stage = &stages[1];
     assert_eq!(decryption_stage.name(), "decryption");
     assert_eq!(decryption_stage.stage_type(), &StageType::Encryption); // Decryption uses Encryption type
-    assert_eq!(decryption_stage.configuration().algorithm, "aes256gcm");
+    assert_eq!(decryption_stage.configuration().algorithm, "aes-256-gcm");
 
     // Verify decompression stage
     let decompression_stage = &stages[2];
@@ -263,7 +264,7 @@ async fn test_e2e_multi_stage_restoration_validation() {
         "multi_stage_checksum_123".to_string(),
     )
     .add_compression_step("brotli", 8)
-    .add_encryption_step("aes256gcm", "argon2", 32, 12)
+    .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
     .with_chunk_info(512 * 1024, 4) // 512KB chunks, 4 total
     .with_pipeline_id("multi-stage-e2e-test".to_string())
     .with_output_checksum("multi_stage_output_456".to_string());
@@ -0,0 +1,74 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Scrub Tests
+//!
+//! Verifies that `scrub` passes a catalog of intact archives and fails with
+//! the integrity exit code once one of them is corrupted on disk.
+
+use tempfile::TempDir;
+
+use crate::common::{assert_success, run};
+
+#[test]
+fn test_e2e_scrub_reports_corrupt_archives() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("scrub.db");
+    let created = run(&db_path, &["create", "--name", "scrub-test", "--stages", "brotli"]);
+    assert_success(&created, "create");
+
+    for name in ["a", "b"] {
+        let input = temp_dir.path().join(format!("{}.bin", name));
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 13 % 241) as u8).collect();
+        std::fs::write(&input, data).unwrap();
+        let archive = temp_dir.path().join(format!("{}.adapipe", name));
+        let processed = run(
+            &db_path,
+            &[
+                "process",
+                "--input",
+                &input.to_string_lossy(),
+                "--output",
+                &archive.to_string_lossy(),
+                "--pipeline",
+                "scrub-test",
+                "--chunk-size",
+                "4KiB",
+            ],
+        );
+        assert_success(&processed, "process");
+    }
+    let catalog = temp_dir.path().join("fleet.catalog");
+    std::fs::write(&catalog, "# test fleet\na.adapipe\nb.adapipe\n").unwrap();
+    let catalog = catalog.to_string_lossy().to_string();
+
+    // Nothing authenticates unencrypted chunks, so sampling checks them all
+    let sampled = run(&db_path, &["scrub", "--catalog", &catalog, "--sample", "2"]);
+    assert_success(&sampled, "scrub");
+    let stdout = String::from_utf8_lossy(&sampled.stdout);
+    assert!(stdout.contains("b.adapipe: 5 chunks, checksum verified"), "{}", stdout);
+
+    // Rot the payload of b's first chunk
+    let rotten = temp_dir.path().join("b.adapipe");
+    let mut bytes = std::fs::read(&rotten).unwrap();
+    bytes[20] ^= 0x5a;
+    std::fs::write(&rotten, bytes).unwrap();
+
+    for mode in ["--full", "--sample=1"] {
+        let scrubbed = run(&db_path, &["scrub", "--catalog", &catalog, mode]);
+        assert_eq!(
+            scrubbed.status.code(),
+            Some(81),
+            "{}: {}",
+            mode,
+            String::from_utf8_lossy(&scrubbed.stderr)
+        );
+        let stdout = String::from_utf8_lossy(&scrubbed.stdout);
+        assert!(stdout.contains("a.adapipe: 5 chunks, checksum verified"), "{}", stdout);
+        assert!(stdout.contains("❌"), "{}", stdout);
+    }
+}
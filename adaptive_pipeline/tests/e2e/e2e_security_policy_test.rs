@@ -0,0 +1,129 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Pipeline Security Policy Tests
+//!
+//! Verifies through the CLI that a pipeline created with `--security-level`
+//! refuses to process or restore for a lower `ADAPIPE_SECURITY_LEVEL`, and
+//! that the denial is recorded as a `PermissionDenied` domain event.
+
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command};
+
+fn run_at(db_path: &Path, security_level: Option<&str>, args: &[&str]) -> Output {
+    let mut command = pipeline_command(db_path);
+    command.env_remove("ADAPIPE_SECURITY_LEVEL");
+    if let Some(level) = security_level {
+        command.env("ADAPIPE_SECURITY_LEVEL", level);
+    }
+    command.args(args).output().expect("Failed to run pipeline command")
+}
+
+#[tokio::test]
+async fn test_e2e_pipeline_security_level_gates_process_and_restore() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("policy.db");
+    let input = temp_dir.path().join("payroll.csv");
+    let archive = temp_dir.path().join("payroll.adapipe");
+    let restored = temp_dir.path().join("restored");
+    std::fs::write(&input, b"name,salary\n".repeat(100)).unwrap();
+    let input_arg = input.to_string_lossy().to_string();
+    let archive_arg = archive.to_string_lossy().to_string();
+    let process = [
+        "process",
+        "--input",
+        &input_arg,
+        "--output",
+        &archive_arg,
+        "--pipeline",
+        "payroll",
+    ];
+
+    assert_success(
+        &run_at(
+            &db_path,
+            None,
+            &[
+                "create",
+                "--name",
+                "payroll",
+                "--stages",
+                "brotli",
+                "--security-level",
+                "confidential",
+            ],
+        ),
+        "create pipeline",
+    );
+
+    // The default clearance is internal, below the pipeline's minimum
+    let denied = run_at(&db_path, None, &process);
+    assert_eq!(
+        denied.status.code(),
+        Some(77),
+        "internal must not run a confidential pipeline"
+    );
+    assert!(!archive.exists());
+
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let denials: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM domain_events WHERE event_type = 'PermissionDenied'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    assert_eq!(denials, 1);
+
+    assert_success(&run_at(&db_path, Some("secret"), &process), "process as secret");
+
+    // The archive carries the policy, so restore enforces it too
+    let restore = [
+        "restore",
+        "--input",
+        &archive_arg,
+        "--output-dir",
+        &restored.to_string_lossy(),
+        "--mkdir",
+    ];
+    assert_eq!(run_at(&db_path, Some("internal"), &restore).status.code(), Some(77));
+    assert!(!restored.join("payroll.csv").exists());
+    assert_success(
+        &run_at(&db_path, Some("confidential"), &restore),
+        "restore as confidential",
+    );
+    assert_eq!(
+        std::fs::read(restored.join("payroll.csv")).unwrap(),
+        std::fs::read(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_e2e_unknown_security_level_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("policy.db");
+
+    let created = run_at(
+        &db_path,
+        None,
+        &[
+            "create",
+            "--name",
+            "classified",
+            "--stages",
+            "brotli",
+            "--security-level",
+            "classified",
+        ],
+    );
+    assert!(!created.status.success(), "unknown levels must not be stored");
+    assert_success(&run_at(&db_path, Some("secret"), &["list"]), "list");
+    assert!(!run_at(&db_path, Some("ultra"), &["list"]).status.success());
+}
@@ -0,0 +1,225 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End API Server Tests
+//!
+//! Starts `serve` on a free port and verifies that requests reach the
+//! pipeline use cases and are authorized with the roles of the principal
+//! that started the server, or of the client certificate a trusted proxy
+//! forwards. Jobs and restores are covered by the server's unit tests.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Child, Stdio};
+use tempfile::TempDir;
+
+use crate::common::{pipeline_command, principal_command, run};
+
+/// A running `serve`, stopped when dropped
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    /// Starts `serve` answering anonymous requests as `principal`, or as the
+    /// OS user without one
+    fn start(db_path: &Path, principal: Option<&str>) -> Self {
+        Self::start_with(db_path, principal, &[], &["--allow-anonymous"])
+    }
+
+    /// Starts `serve` with `global_args` before the subcommand and
+    /// `serve_args` after it
+    fn start_with(db_path: &Path, principal: Option<&str>, global_args: &[&str], serve_args: &[&str]) -> Self {
+        let mut command = match principal {
+            Some(principal) => principal_command(db_path, principal),
+            None => pipeline_command(db_path),
+        };
+        let mut child = command
+            .args(global_args)
+            .args(["serve", "--port", "0"])
+            .args(serve_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start serve");
+
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let address = stdout
+            .lines()
+            .map_while(Result::ok)
+            .find_map(|line| {
+                let url = line.split("Serving the API at http://").nth(1)?;
+                Some(url.trim_end_matches("/api/v1").to_string())
+            })
+            .expect("serve did not report its address");
+        Self { child, address }
+    }
+
+    /// Sends one request and returns the status and body
+    fn call(&self, method: &str, path: &str, body: &str) -> (u16, String) {
+        self.call_with(method, path, &[], body)
+    }
+
+    /// Sends one request with extra `headers`
+    fn call_with(&self, method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        let headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            headers,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body)
+            .unwrap_or_default();
+        (status, body.to_string())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_e2e_serve_manages_pipelines_under_rbac() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("serve.db");
+    assert!(run(&db_path, &["role", "assign", "ops", "operator"]).status.success());
+
+    let admin = Server::start(&db_path, None);
+    assert_eq!(admin.call("GET", "/api/v1/health", "").0, 200);
+    let (status, body) = admin.call(
+        "POST",
+        "/api/v1/pipelines",
+        r#"{"name": "served", "stages": ["brotli"]}"#,
+    );
+    assert_eq!(status, 201, "{}", body);
+    drop(admin);
+
+    // The pipeline is in the database, visible to the CLI
+    let shown = run(&db_path, &["show", "served"]);
+    assert!(shown.status.success(), "{}", String::from_utf8_lossy(&shown.stderr));
+
+    // Operators may list pipelines but not delete them
+    let operator = Server::start(&db_path, Some("ops"));
+    let (status, body) = operator.call("GET", "/api/v1/pipelines", "");
+    assert_eq!(status, 200);
+    assert!(body.contains("\"name\":\"served\""), "{}", body);
+    let (status, body) = operator.call("DELETE", "/api/v1/pipelines/served", "");
+    assert_eq!(status, 403, "{}", body);
+    assert!(body.contains("\"error\""), "{}", body);
+    drop(operator);
+
+    // Unassigned principals may do nothing
+    let stranger = Server::start(&db_path, Some("stranger"));
+    assert_eq!(stranger.call("GET", "/api/v1/pipelines", "").0, 403);
+    drop(stranger);
+
+    // Without --allow-anonymous, credentials are needed
+    let server = Server::start_with(&db_path, None, &[], &[]);
+    assert_eq!(server.call("GET", "/api/v1/pipelines", "").0, 401);
+    drop(server);
+
+    // Other hosts are only served when authentication is required
+    let exposed = run(&db_path, &["serve", "--port", "0", "--bind", "0.0.0.0"]);
+    assert!(!exposed.status.success());
+}
+
+/// `der` as nginx's `$ssl_client_escaped_cert` forwards it
+fn escaped_pem(der: &[u8]) -> String {
+    let pem = format!(
+        "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+        STANDARD.encode(der)
+    );
+    pem.replace(' ', "%20")
+        .replace('+', "%2B")
+        .replace('/', "%2F")
+        .replace('\n', "%0A")
+}
+
+#[test]
+fn test_e2e_serve_authenticates_certificates_from_trusted_proxies() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("serve.db");
+    let certificate = b"ingest-service certificate, as DER".to_vec();
+    let config = temp_dir.path().join("pipeline.toml");
+    std::fs::write(
+        &config,
+        format!(
+            r#"
+[auth]
+required = true
+
+[[auth.client_certs]]
+principal = "ingest-service"
+sha256_fingerprint = "{}"
+role = "operator"
+
+[auth.client_cert_proxy]
+trusted_proxies = ["127.0.0.1"]
+"#,
+            hex::encode(Sha256::digest(&certificate))
+        ),
+    )
+    .unwrap();
+
+    let config = config.to_string_lossy();
+    let server = Server::start_with(&db_path, None, &["--config", &config], &[]);
+    assert_eq!(server.call("GET", "/api/v1/pipelines", "").0, 401);
+
+    // The pinned certificate authenticates as the operator it is mapped to
+    let pinned = escaped_pem(&certificate);
+    let (status, body) = server.call_with("GET", "/api/v1/pipelines", &[("X-Client-Cert", &pinned)], "");
+    assert_eq!(status, 200, "{}", body);
+    let (status, body) = server.call_with(
+        "POST",
+        "/api/v1/pipelines",
+        &[("X-Client-Cert", &pinned)],
+        r#"{"name": "proxied", "stages": ["brotli"]}"#,
+    );
+    assert_eq!(status, 201, "{}", body);
+
+    let other = escaped_pem(b"a certificate nobody pinned");
+    let (status, body) = server.call_with("GET", "/api/v1/pipelines", &[("X-Client-Cert", &other)], "");
+    assert_eq!(status, 401, "{}", body);
+    assert!(body.contains("not trusted"), "{}", body);
+    let (status, _) = server.call_with(
+        "GET",
+        "/api/v1/pipelines",
+        &[("X-Client-Cert", &pinned), ("X-API-Key", "also-a-key")],
+        "",
+    );
+    assert_eq!(status, 400);
+    drop(server);
+
+    let anonymous = run(
+        &db_path,
+        &["--config", &config, "serve", "--port", "0", "--allow-anonymous"],
+    );
+    assert!(!anonymous.status.success());
+}
@@ -0,0 +1,77 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Session Tests
+//!
+//! Verifies through the CLI that issued session tokens act for their user via
+//! `ADAPIPE_SESSION_TOKEN`, are scoped to their namespace, and are refused
+//! once revoked.
+
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command, run, run_as};
+
+fn run_with_session(db_path: &Path, token: &str, args: &[&str]) -> Output {
+    pipeline_command(db_path)
+        .env("ADAPIPE_SESSION_TOKEN", token)
+        .env_remove("ADAPIPE_API_KEY")
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+#[test]
+fn test_e2e_session_token_acts_for_its_user_until_revoked() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("sessions.db");
+
+    assert_success(
+        &run(&db_path, &["role", "assign", "ops", "operator"]),
+        "assign operator",
+    );
+
+    // Operators cannot issue sessions
+    let denied = run_as(&db_path, "ops", &["session", "issue"]);
+    assert_eq!(denied.status.code(), Some(77), "operator must not issue sessions");
+
+    let issued = run(&db_path, &["session", "issue", "--user", "ops"]);
+    assert_success(&issued, "session issue");
+    let stdout = String::from_utf8_lossy(&issued.stdout);
+    let token = stdout.lines().last().unwrap().trim().to_string();
+    let session_id = token.split_once('.').unwrap().0.to_string();
+
+    // The token acts as `ops`, not as the OS user running the command
+    let created = run_with_session(
+        &db_path,
+        &token,
+        &["create", "--name", "session-test", "--stages", "brotli"],
+    );
+    assert_success(&created, "create with session");
+    let deleted = run_with_session(&db_path, &token, &["delete", "session-test", "--force"]);
+    assert_eq!(
+        deleted.status.code(),
+        Some(77),
+        "session of an operator must not delete"
+    );
+
+    // Sessions are scoped to the namespace they were issued in
+    let elsewhere = run_with_session(&db_path, &token, &["--namespace", "team-a", "list"]);
+    assert_eq!(elsewhere.status.code(), Some(77));
+
+    let listed = run(&db_path, &["session", "list"]);
+    assert_success(&listed, "session list");
+    assert!(String::from_utf8_lossy(&listed.stdout).contains(&session_id));
+
+    assert_success(&run(&db_path, &["session", "revoke", &session_id]), "session revoke");
+    let revoked = run_with_session(&db_path, &token, &["list"]);
+    assert_eq!(revoked.status.code(), Some(77), "revoked token must be refused");
+
+    let forged = format!("{}.{}", session_id, "0".repeat(64));
+    assert_eq!(run_with_session(&db_path, &forged, &["list"]).status.code(), Some(77));
+}
@@ -0,0 +1,222 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Tar Interop Tests
+//!
+//! Verifies through the CLI that `export-tar` produces a tar standard readers
+//! unpack to the original files, and that `import-tar` archives every entry
+//! of a tar file or stream so each restores unchanged.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+use crate::common::{assert_success, pipeline_command};
+
+fn command(db_path: &Path, args: &[&str]) -> Command {
+    let mut command = pipeline_command(db_path);
+    command.args(args);
+    command
+}
+
+fn run(db_path: &Path, args: &[&str]) -> Output {
+    command(db_path, args).output().expect("Failed to run pipeline command")
+}
+
+fn setup(temp_dir: &TempDir) -> PathBuf {
+    let db_path = temp_dir.path().join("tar.db");
+    let created = run(
+        &db_path,
+        &["create", "--name", "tar-test", "--stages", "brotli,checksum"],
+    );
+    assert_success(&created, "create");
+    db_path
+}
+
+fn tar_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, data) in entries {
+        let mut header = tar::Header::new_ustar();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *data).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+fn restore(db_path: &Path, archive: &Path, output_dir: &Path) {
+    let restored = run(
+        db_path,
+        &[
+            "restore",
+            "--input",
+            &archive.to_string_lossy(),
+            "--output-dir",
+            &output_dir.to_string_lossy(),
+            "--mkdir",
+        ],
+    );
+    assert_success(&restored, "restore");
+}
+
+#[test]
+fn test_e2e_exported_tar_unpacks_to_the_original_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = setup(&temp_dir);
+
+    let files: [(&str, Vec<u8>); 2] = [
+        ("notes.txt", b"Tar export E2E test data.\n".repeat(40)),
+        ("blob.bin", (0..70_000u32).map(|i| (i * 13 % 251) as u8).collect()),
+    ];
+    let mut archives = Vec::new();
+    for (name, data) in &files {
+        let input = temp_dir.path().join(name);
+        std::fs::write(&input, data).unwrap();
+        let archive = temp_dir.path().join(format!("{}.adapipe", name));
+        let processed = run(
+            &db_path,
+            &[
+                "process",
+                "--input",
+                &input.to_string_lossy(),
+                "--output",
+                &archive.to_string_lossy(),
+                "--pipeline",
+                "tar-test",
+            ],
+        );
+        assert_success(&processed, "process");
+        archives.push(archive.to_string_lossy().to_string());
+    }
+
+    let tar_path = temp_dir.path().join("bundle.tar");
+    let mut args = vec!["export-tar"];
+    args.extend(archives.iter().map(String::as_str));
+    args.extend(["--output", tar_path.to_str().unwrap()]);
+    assert_success(&run(&db_path, &args), "export-tar");
+
+    let bytes = std::fs::read(&tar_path).unwrap();
+    assert_eq!(bytes.len() % 512, 0);
+    let mut tar = tar::Archive::new(bytes.as_slice());
+    let mut unpacked = Vec::new();
+    for entry in tar.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().to_string_lossy().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).unwrap();
+        unpacked.push((path, data));
+    }
+    let expected: Vec<(String, Vec<u8>)> = files
+        .iter()
+        .map(|(name, data)| (name.to_string(), data.clone()))
+        .collect();
+    assert_eq!(unpacked, expected);
+
+    // An existing tar is never replaced, and several archives need --output
+    assert!(!run(&db_path, &args).status.success());
+    assert!(!run(&db_path, &["export-tar", &archives[0], &archives[1]])
+        .status
+        .success());
+}
+
+#[test]
+fn test_e2e_imported_tar_entries_restore_unchanged() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = setup(&temp_dir);
+    let large: Vec<u8> = (0..40_000u32).map(|i| (i % 241) as u8).collect();
+    let tar = tar_of(&[("readme.md", b"# Imported\n"), ("logs/app.log", &large)]);
+
+    // From standard input
+    let output_dir = temp_dir.path().join("archives");
+    let mut child = command(
+        &db_path,
+        &[
+            "import-tar",
+            "--input",
+            "-",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--pipeline",
+            "tar-test",
+        ],
+    )
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+    child.stdin.take().unwrap().write_all(&tar).unwrap();
+    assert_success(&child.wait_with_output().unwrap(), "import-tar from stdin");
+
+    let restored_dir = temp_dir.path().join("restored");
+    restore(&db_path, &output_dir.join("readme.md.adapipe"), &restored_dir);
+    restore(
+        &db_path,
+        &output_dir.join("logs/app.log.adapipe"),
+        &restored_dir.join("logs"),
+    );
+    assert_eq!(std::fs::read(restored_dir.join("readme.md")).unwrap(), b"# Imported\n");
+    assert_eq!(std::fs::read(restored_dir.join("logs/app.log")).unwrap(), large);
+
+    // From a file, honouring the overwrite policy
+    let tar_path = temp_dir.path().join("input.tar");
+    std::fs::write(&tar_path, &tar).unwrap();
+    let import_file = |policy: &str| {
+        run(
+            &db_path,
+            &[
+                "import-tar",
+                "--input",
+                tar_path.to_str().unwrap(),
+                "--output-dir",
+                output_dir.to_str().unwrap(),
+                "--pipeline",
+                "tar-test",
+                "--if-exists",
+                policy,
+            ],
+        )
+    };
+    assert!(!import_file("fail").status.success());
+    assert_success(&import_file("overwrite"), "import-tar from file");
+}
+
+#[test]
+fn test_e2e_traversing_tar_entry_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = setup(&temp_dir);
+
+    // `tar::Builder` refuses `..`, so forge the name field directly
+    let mut header = tar::Header::new_ustar();
+    header.as_old_mut().name[..13].copy_from_slice(b"../escape.txt");
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(4);
+    header.set_cksum();
+    let mut tar = header.as_bytes().to_vec();
+    tar.extend_from_slice(b"evil");
+    tar.resize(1024 + 1024, 0);
+    let tar_path = temp_dir.path().join("evil.tar");
+    std::fs::write(&tar_path, &tar).unwrap();
+
+    let output_dir = temp_dir.path().join("out").join("archives");
+    let imported = run(
+        &db_path,
+        &[
+            "import-tar",
+            "--input",
+            tar_path.to_str().unwrap(),
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--pipeline",
+            "tar-test",
+        ],
+    );
+    assert!(!imported.status.success());
+    assert!(!temp_dir.path().join("out/escape.txt.adapipe").exists());
+}
@@ -0,0 +1,66 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # End-to-End Temp Cleanup Tests
+//!
+//! Verifies through the CLI that a run removes its own temporary files on
+//! exit, and that `cleanup` removes what a crashed run left in the managed
+//! temp root (`ADAPIPE_TEMP_DIR`) and the staging files its journal lists.
+
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+use crate::common::pipeline_command;
+
+fn run(temp_dir: &Path, args: &[&str]) -> Output {
+    pipeline_command(&temp_dir.join("cleanup.db"))
+        .env("ADAPIPE_TEMP_DIR", temp_dir.join("temp-root"))
+        .args(args)
+        .output()
+        .expect("Failed to run pipeline command")
+}
+
+fn run_dirs(temp_root: &Path) -> Vec<String> {
+    std::fs::read_dir(temp_root)
+        .map(|entries| {
+            entries
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_e2e_runs_leave_no_temp_files_and_cleanup_purges_crashed_runs() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_root = temp_dir.path().join("temp-root");
+
+    let listed = run(temp_dir.path(), &["list"]);
+    assert!(listed.status.success(), "{}", String::from_utf8_lossy(&listed.stderr));
+    assert!(run_dirs(&temp_root).is_empty(), "{:?}", run_dirs(&temp_root));
+
+    // What a restore killed mid-write leaves: its run directory, never
+    // unlocked, and a staging file next to the target listed in its journal
+    let crashed = temp_root.join("run-999999-deadbeef");
+    std::fs::create_dir_all(&crashed).unwrap();
+    std::fs::write(crashed.join("owner.lock"), b"").unwrap();
+    let staged = temp_dir.path().join(".report.txt.0123.partial");
+    std::fs::write(&staged, b"half a file").unwrap();
+    std::fs::write(crashed.join("journal"), format!("+{}\n", staged.display())).unwrap();
+
+    let dry_run = run(temp_dir.path(), &["cleanup", "--dry-run"]);
+    assert!(dry_run.status.success(), "{}", String::from_utf8_lossy(&dry_run.stderr));
+    assert!(String::from_utf8_lossy(&dry_run.stdout).contains(".report.txt.0123.partial"));
+    assert!(crashed.exists() && staged.exists());
+
+    let cleanup = run(temp_dir.path(), &["cleanup"]);
+    assert!(cleanup.status.success(), "{}", String::from_utf8_lossy(&cleanup.stderr));
+    assert!(String::from_utf8_lossy(&cleanup.stdout).contains("Removed 1 run(s) and 1 tracked file(s)"));
+    assert!(!crashed.exists() && !staged.exists());
+    assert!(run_dirs(&temp_root).is_empty(), "{:?}", run_dirs(&temp_root));
+}
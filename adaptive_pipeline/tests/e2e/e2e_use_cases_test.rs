@@ -11,12 +11,11 @@
 //! complete workflows from CLI invocation through to final results, using
 //! real pipeline binaries and file I/O.
 
-use std::process::Command;
 use tempfile::TempDir;
 use tokio::fs;
 
 // Import shared test helpers
-use crate::common::get_pipeline_bin;
+use crate::common::pipeline_command;
 
 /// Tests CreatePipelineUseCase via CLI
 #[tokio::test]
@@ -25,8 +24,7 @@ async fn test_e2e_create_pipeline_use_case() {
     let db_path = temp_dir.path().join("test_create.db");
 
     // Create a pipeline using the CLI (which uses CreatePipelineUseCase)
-    let output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let output = pipeline_command(&db_path)
         .args(["create", "--name", "test-create-uc", "--stages", "brotli,aes256gcm"])
         .output()
         .expect("Failed to run create command");
@@ -49,16 +47,14 @@ async fn test_e2e_list_pipelines_use_case() {
 
     // Create multiple pipelines
     for name in &["test-list-1", "test-list-2", "test-list-3"] {
-        Command::new(get_pipeline_bin())
-            .env("ADAPIPE_SQLITE_PATH", &db_path)
+        pipeline_command(&db_path)
             .args(["create", "--name", name, "--stages", "brotli"])
             .output()
             .expect("Failed to create pipeline");
     }
 
     // List pipelines using CLI (which uses ListPipelinesUseCase)
-    let output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let output = pipeline_command(&db_path)
         .args(["list"])
         .output()
         .expect("Failed to run list command");
@@ -86,8 +82,7 @@ async fn test_e2e_show_pipeline_use_case() {
     let db_path = temp_dir.path().join("test_show.db");
 
     // Create a pipeline with multiple stages
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args([
             "create",
             "--name",
@@ -99,8 +94,7 @@ async fn test_e2e_show_pipeline_use_case() {
         .expect("Failed to create pipeline");
 
     // Show pipeline details using CLI (which uses ShowPipelineUseCase)
-    let output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let output = pipeline_command(&db_path)
         .args(["show", "test-show-uc"])
         .output()
         .expect("Failed to run show command");
@@ -125,15 +119,13 @@ async fn test_e2e_delete_pipeline_use_case() {
     let db_path = temp_dir.path().join("test_delete.db");
 
     // Create a pipeline
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args(["create", "--name", "test-delete-uc", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
 
     // Delete using CLI with --force (which uses DeletePipelineUseCase)
-    let output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let output = pipeline_command(&db_path)
         .args(["delete", "test-delete-uc", "--force"])
         .output()
         .expect("Failed to run delete command");
@@ -145,8 +137,7 @@ async fn test_e2e_delete_pipeline_use_case() {
     );
 
     // Verify pipeline is gone
-    let list_output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let list_output = pipeline_command(&db_path)
         .args(["list"])
         .output()
         .expect("Failed to list pipelines");
@@ -171,15 +162,13 @@ async fn test_e2e_process_file_use_case() {
     fs::write(&input_file, &test_data).await.unwrap();
 
     // Create pipeline
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args(["create", "--name", "test-process-uc", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
 
     // Process file using CLI (which uses ProcessFileUseCase)
-    let output = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let output = pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -225,7 +214,7 @@ stages = ["brotli", "aes256gcm"]
     fs::write(&config_file, config_content).await.unwrap();
 
     // Validate using CLI (which uses ValidateConfigUseCase)
-    let output = Command::new(get_pipeline_bin())
+    let output = pipeline_command(&temp_dir.path().join("validate.db"))
         .args(["validate", config_file.to_str().unwrap()])
         .output()
         .expect("Failed to run validate command");
@@ -257,7 +246,7 @@ missing_closing_bracket = "oops"
     fs::write(&config_file, invalid_content).await.unwrap();
 
     // Validate using CLI - should fail
-    let output = Command::new(get_pipeline_bin())
+    let output = pipeline_command(&temp_dir.path().join("validate-invalid.db"))
         .args(["validate", config_file.to_str().unwrap()])
         .output()
         .expect("Failed to run validate command");
@@ -277,14 +266,12 @@ async fn test_e2e_validate_file_use_case() {
     let test_data = b"ValidateFileUseCase E2E test.\n".repeat(20);
     fs::write(&input_file, &test_data).await.unwrap();
 
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args(["create", "--name", "test-validate-file", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
 
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -298,7 +285,7 @@ async fn test_e2e_validate_file_use_case() {
         .expect("Failed to process file");
 
     // Validate the .adapipe file using CLI (which uses ValidateFileUseCase)
-    let output = Command::new(get_pipeline_bin())
+    let output = pipeline_command(&db_path)
         .args(["validate-file", "--file", adapipe_file.to_str().unwrap()])
         .output()
         .expect("Failed to run validate-file command");
@@ -329,14 +316,12 @@ async fn test_e2e_compare_files_use_case() {
     fs::write(&input_file, &test_data).await.unwrap();
 
     // Create pipeline and process
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args(["create", "--name", "test-compare", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
 
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -350,7 +335,7 @@ async fn test_e2e_compare_files_use_case() {
         .expect("Failed to process file");
 
     // Compare files using CLI (which uses CompareFilesUseCase)
-    let output = Command::new(get_pipeline_bin())
+    let output = pipeline_command(&db_path)
         .args([
             "compare",
             "--original",
@@ -392,14 +377,12 @@ async fn test_e2e_compare_files_modified_use_case() {
     fs::write(&modified_file, &modified_data).await.unwrap();
 
     // Create pipeline and process original
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args(["create", "--name", "test-compare-mod", "--stages", "brotli"])
         .output()
         .expect("Failed to create pipeline");
 
-    Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -413,7 +396,7 @@ async fn test_e2e_compare_files_modified_use_case() {
         .expect("Failed to process file");
 
     // Compare modified file against original .adapipe
-    let output = Command::new(get_pipeline_bin())
+    let output = pipeline_command(&db_path)
         .args([
             "compare",
             "--original",
@@ -447,8 +430,10 @@ async fn test_e2e_benchmark_system_use_case_smoke() {
     let test_data = b"Benchmark test data.\n".repeat(100);
     fs::write(&test_file, &test_data).await.unwrap();
 
-    // Run minimal benchmark (1 iteration, small size)
-    let output = Command::new(get_pipeline_bin())
+    // Run minimal benchmark (1 iteration, small size); its report is written
+    // to the working directory
+    let output = pipeline_command(&temp_dir.path().join("benchmark.db"))
+        .current_dir(temp_dir.path())
         .args([
             "benchmark",
             "--file",
@@ -489,33 +474,26 @@ async fn test_e2e_complete_workflow_all_use_cases() {
 
     // Step 1: Create pipeline (CreatePipelineUseCase)
     // Note: Using only brotli to avoid needing encryption key configuration
-    let create = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let create = pipeline_command(&db_path)
         .args(["create", "--name", pipeline_name, "--stages", "brotli"])
         .output()
         .expect("Create failed");
     assert!(create.status.success(), "Create failed");
 
     // Step 2: List pipelines (ListPipelinesUseCase)
-    let list = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
-        .args(["list"])
-        .output()
-        .expect("List failed");
+    let list = pipeline_command(&db_path).args(["list"]).output().expect("List failed");
     assert!(list.status.success(), "List failed");
     assert!(String::from_utf8_lossy(&list.stdout).contains(pipeline_name));
 
     // Step 3: Show pipeline details (ShowPipelineUseCase)
-    let show = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let show = pipeline_command(&db_path)
         .args(["show", pipeline_name])
         .output()
         .expect("Show failed");
     assert!(show.status.success(), "Show failed");
 
     // Step 4: Process file (ProcessFileUseCase)
-    let process = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let process = pipeline_command(&db_path)
         .args([
             "process",
             "--input",
@@ -536,14 +514,14 @@ async fn test_e2e_complete_workflow_all_use_cases() {
     assert!(output_file.exists(), "Output file not created");
 
     // Step 5: Validate .adapipe file (ValidateFileUseCase)
-    let validate = Command::new(get_pipeline_bin())
+    let validate = pipeline_command(&db_path)
         .args(["validate-file", "--file", output_file.to_str().unwrap()])
         .output()
         .expect("Validate file failed");
     assert!(validate.status.success(), "Validate file failed");
 
     // Step 6: Compare files (CompareFilesUseCase)
-    let compare = Command::new(get_pipeline_bin())
+    let compare = pipeline_command(&db_path)
         .args([
             "compare",
             "--original",
@@ -556,16 +534,14 @@ async fn test_e2e_complete_workflow_all_use_cases() {
     assert!(compare.status.success(), "Compare failed");
 
     // Step 7: Delete pipeline (DeletePipelineUseCase)
-    let delete = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let delete = pipeline_command(&db_path)
         .args(["delete", pipeline_name, "--force"])
         .output()
         .expect("Delete failed");
     assert!(delete.status.success(), "Delete failed");
 
     // Verify deletion
-    let list_after = Command::new(get_pipeline_bin())
-        .env("ADAPIPE_SQLITE_PATH", &db_path)
+    let list_after = pipeline_command(&db_path)
         .args(["list"])
         .output()
         .expect("List after delete failed");
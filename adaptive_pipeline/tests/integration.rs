@@ -21,11 +21,32 @@ mod application_services_integration_test;
 #[path = "integration/domain_services_test.rs"]
 mod domain_services_test;
 
+#[path = "integration/failure_injection_test.rs"]
+mod failure_injection_test;
+
+#[path = "integration/generic_repository_test.rs"]
+mod generic_repository_test;
+
 #[path = "integration/minimal_application_test.rs"]
 mod minimal_application_test;
 
+#[path = "integration/overwrite_policy_test.rs"]
+mod overwrite_policy_test;
+
 #[path = "integration/pipeline_name_validation_tests.rs"]
 mod pipeline_name_validation_tests;
 
+#[path = "integration/process_batch_test.rs"]
+mod process_batch_test;
+
+#[path = "integration/process_restore_roundtrip_test.rs"]
+mod process_restore_roundtrip_test;
+
 #[path = "integration/schema_integration_test.rs"]
 mod schema_integration_test;
+
+#[path = "integration/stage_operation_roundtrip_test.rs"]
+mod stage_operation_roundtrip_test;
+
+#[path = "integration/unit_of_work_test.rs"]
+mod unit_of_work_test;
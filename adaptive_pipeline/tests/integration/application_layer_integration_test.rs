@@ -25,6 +25,7 @@
 //! ```
 
 use adaptive_pipeline::application::commands::RestoreFileCommand;
+use adaptive_pipeline_domain::value_objects::OverwritePolicy;
 use std::path::PathBuf;
 
 /// Tests RestoreFileCommand creation and fluent API configuration.
@@ -51,7 +52,7 @@ async fn test_restore_file_command_creation() {
         .with_permission_validation(false);
 
     // Assert - verify fluent API updates state correctly
-    assert!(command.overwrite);
+    assert_eq!(command.overwrite_policy, OverwritePolicy::Overwrite);
     assert!(!command.create_directories);
     assert!(!command.validate_permissions);
 }
@@ -94,7 +95,11 @@ fn test_command_defaults() {
     let command = RestoreFileCommand::new(PathBuf::from("/tmp/source.adapipe"), PathBuf::from("/tmp/target.txt"));
 
     // Assert - verify safe defaults
-    assert!(!command.overwrite, "Default overwrite should be false for safety");
+    assert_eq!(
+        command.overwrite_policy,
+        OverwritePolicy::Fail,
+        "Default overwrite policy should be fail for safety"
+    );
     assert!(
         command.create_directories,
         "Default create_directories should be true for convenience"
@@ -118,7 +123,7 @@ fn test_command_fluent_api() {
         .with_permission_validation(false);
 
     // Assert - verify fluent API configuration
-    assert!(command.overwrite);
+    assert_eq!(command.overwrite_policy, OverwritePolicy::Overwrite);
     assert!(!command.create_directories);
     assert!(!command.validate_permissions);
 }
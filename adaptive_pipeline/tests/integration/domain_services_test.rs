@@ -23,12 +23,15 @@ use adaptive_pipeline_domain::entities::security_context::{SecurityContext, Secu
 use adaptive_pipeline_domain::entities::ProcessingContext;
 use adaptive_pipeline_domain::services::checksum_service::ChecksumProcessor;
 use adaptive_pipeline_domain::services::compression_service::{CompressionAlgorithm, CompressionConfig};
-use adaptive_pipeline_domain::services::encryption_service::{EncryptionAlgorithm, EncryptionConfig};
+use adaptive_pipeline_domain::services::encryption_service::{
+    EncryptionAlgorithm, EncryptionConfig, EncryptionService, KeyDerivationFunction,
+};
 use adaptive_pipeline_domain::services::file_io_service::{FileIOConfig, FileIOService, ReadOptions, WriteOptions};
 use adaptive_pipeline_domain::value_objects::algorithm::Algorithm;
 use adaptive_pipeline_domain::value_objects::chunk_size::ChunkSize;
 use adaptive_pipeline_domain::value_objects::encryption_key_id::EncryptionKeyId;
 use adaptive_pipeline_domain::value_objects::file_chunk::FileChunk;
+use adaptive_pipeline_domain::value_objects::{KdfCost, SecretBytes, FIPS_MODE};
 use adaptive_pipeline_domain::PipelineError;
 
 // ============================================================================
@@ -270,6 +273,69 @@ fn test_encryption_service_key_management() {
     println!("   ✅ Successfully validated key management functionality");
 }
 
+#[test]
+fn test_calibrated_kdf_cost_is_never_below_the_defaults() {
+    println!("⏱️  Testing password KDF calibration...");
+
+    let cost = MultiAlgoEncryption::calibrated_kdf_cost();
+    let floor = KdfCost::default();
+    assert!(cost.memory_kib() >= floor.memory_kib());
+    assert!(cost.iterations() >= floor.iterations());
+    assert!(!cost.is_weak());
+    assert_eq!(
+        MultiAlgoEncryption::calibrated_kdf_cost(),
+        cost,
+        "calibration runs once"
+    );
+
+    let kdf = MultiAlgoEncryption::new_password_kdf(cost).unwrap();
+    assert_eq!(kdf.cost(), cost);
+
+    println!("   ✅ Calibrated Argon2id cost: {}", cost);
+}
+
+#[test]
+fn test_only_pbkdf2_derives_keys_in_fips_mode() {
+    println!("🔐 Testing key derivation functions against FIPS mode...");
+
+    let encryption = MultiAlgoEncryption::new();
+    let password = SecretBytes::from_slice(b"correct horse battery staple");
+    let security_context = SecurityContext::new(None, SecurityLevel::Secret);
+
+    let pbkdf2 = EncryptionConfig::new(EncryptionAlgorithm::Aes256Gcm)
+        .with_key_derivation(KeyDerivationFunction::Pbkdf2)
+        .with_iterations(10_000);
+    assert!(encryption.validate_config(&pbkdf2).is_ok());
+    assert!(encryption
+        .derive_key_material(&password, &pbkdf2, &security_context)
+        .is_ok());
+
+    for kdf in [KeyDerivationFunction::Argon2, KeyDerivationFunction::Scrypt] {
+        let config = EncryptionConfig::new(EncryptionAlgorithm::Aes256Gcm).with_key_derivation(kdf.clone());
+        let derived = encryption.derive_key_material(&password, &config, &security_context);
+        assert_eq!(derived.is_err(), FIPS_MODE, "{} derivation", kdf);
+        assert_eq!(
+            encryption.validate_config(&config).is_err(),
+            FIPS_MODE,
+            "{} config",
+            kdf
+        );
+        if let Err(e) = derived {
+            assert!(matches!(e, PipelineError::UnsupportedOperation(_)));
+            assert!(e.to_string().contains("not FIPS-approved"));
+        }
+    }
+
+    let kdf = MultiAlgoEncryption::new_password_kdf(KdfCost::new(64, 1, 1).unwrap()).unwrap();
+    assert_eq!(
+        MultiAlgoEncryption::derive_password_key(&password, &kdf).is_err(),
+        FIPS_MODE,
+        "Argon2id password keys"
+    );
+
+    println!("   ✅ Only PBKDF2 is permitted in FIPS mode");
+}
+
 // ============================================================================
 // 3. CHECKSUM SERVICE TESTS (Framework Pattern)
 // ============================================================================
@@ -0,0 +1,340 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Failure Injection Tests
+//!
+//! Drives the process use case through `test_util::chaos` decorators so each
+//! failure lands on a chosen chunk: a stage panic that the worker restart
+//! budget absorbs (in both execution topologies), the same panic without a
+//! budget, a failed read, and a read that silently corrupts data and must be
+//! caught on restore. Shutdown is driven the same way: a stalled chunk shows
+//! whether in-flight work drains within the grace period or is aborted, and
+//! either way a checkpoint is left next to the unfinished output.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use adaptive_pipeline::application::commands::RestoreFileCommand;
+use adaptive_pipeline::application::use_cases::{
+    ProcessFileConfig, ProcessFileUseCase, ProcessFileUseCaseBuilder, RestoreFileUseCase,
+};
+use adaptive_pipeline::infrastructure::adapters::file_io::TokioFileIO;
+use adaptive_pipeline::infrastructure::metrics::MetricsService;
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+use adaptive_pipeline::test_util::chaos::{ChaosFileIO, FailingStageService, FailureKind, FailurePlan};
+use adaptive_pipeline::test_util::generators::stage;
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator;
+use adaptive_pipeline_domain::entities::{Pipeline, StageType};
+use adaptive_pipeline_domain::value_objects::{
+    ChunkSize, ExecutionTopology, JobPriority, OverwritePolicy, ShutdownCheckpoint,
+};
+use adaptive_pipeline_domain::PipelineError;
+use tempfile::TempDir;
+
+const CHUNK_SIZE: usize = 1024;
+const CHUNKS: usize = 6;
+
+/// A directory holding a six-chunk input and a repository with a
+/// `chaos-run` pipeline of brotli followed by a pass-through stage
+struct Fixture {
+    _dir: TempDir,
+    input: PathBuf,
+    archive: PathBuf,
+    restored: PathBuf,
+    data: Vec<u8>,
+    metrics_service: Arc<MetricsService>,
+    repository: Arc<SqlitePipelineRepository>,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        Self::with_topology(ExecutionTopology::default()).await
+    }
+
+    async fn with_topology(topology: ExecutionTopology) -> Self {
+        // The process path draws CPU and I/O tokens from the global manager
+        let _ = init_resource_manager(ResourceConfig::default());
+        let dir = TempDir::new().unwrap();
+        let data: Vec<u8> = (0..CHUNK_SIZE * CHUNKS).map(|i| (i * 31 % 251) as u8).collect();
+        let input = dir.path().join("input.bin");
+        std::fs::write(&input, &data).unwrap();
+
+        let repository = Arc::new(
+            SqlitePipelineRepository::new(&dir.path().join("pipeline.db").to_string_lossy())
+                .await
+                .unwrap(),
+        );
+        let stages = vec![
+            stage("compress", StageType::Compression, "brotli", HashMap::new()),
+            stage("relay", StageType::PassThrough, "passthrough", HashMap::new()),
+        ];
+        let mut pipeline = Pipeline::new("chaos-run".to_string(), stages).unwrap();
+        pipeline.set_execution_topology(topology);
+        repository.save(&pipeline).await.unwrap();
+
+        Self {
+            archive: dir.path().join("input.bin.adapipe"),
+            restored: dir.path().join("restored.bin"),
+            input,
+            data,
+            metrics_service: Arc::new(MetricsService::new().unwrap()),
+            repository,
+            _dir: dir,
+        }
+    }
+
+    fn use_case(&self) -> ProcessFileUseCaseBuilder {
+        ProcessFileUseCase::builder()
+            .metrics_service(self.metrics_service.clone())
+            .pipeline_repository(self.repository.clone())
+    }
+
+    fn config(&self, max_worker_restarts: u32) -> ProcessFileConfig {
+        ProcessFileConfig {
+            input: self.input.clone(),
+            output: self.archive.clone(),
+            pipeline: "chaos-run".to_string(),
+            chunk_size: Some(ChunkSize::new(CHUNK_SIZE).unwrap()),
+            workers: Some(3),
+            channel_depth: None,
+            inflight_window: None,
+            write_manifest: false,
+            signing_key: None,
+            idempotency_key: None,
+            stage_timeout: None,
+            chunk_timeout: None,
+            max_worker_restarts,
+            direct_io: false,
+            checksum_offload: false,
+            overwrite_policy: OverwritePolicy::default(),
+            output_mode: None,
+            priority: JobPriority::default(),
+            password: None,
+            passphrase_policy: PassphrasePolicy::default(),
+        }
+    }
+
+    fn checkpoint(&self) -> ShutdownCheckpoint {
+        let json = std::fs::read_to_string(ShutdownCheckpoint::path_for(&self.archive)).unwrap();
+        ShutdownCheckpoint::from_json(&json).unwrap()
+    }
+
+    async fn restore(&self) -> anyhow::Result<Vec<u8>> {
+        RestoreFileUseCase::new(self.metrics_service.clone())
+            .execute(RestoreFileCommand::new(self.archive.clone(), self.restored.clone()))
+            .await?;
+        Ok(std::fs::read(&self.restored)?)
+    }
+}
+
+fn chaos_file_io(plan: &Arc<FailurePlan>) -> Arc<ChaosFileIO> {
+    Arc::new(ChaosFileIO::new(Arc::new(TokioFileIO::new_default()), CHUNK_SIZE).with_read_failures(plan.clone()))
+}
+
+fn failing_relay(plan: &Arc<FailurePlan>) -> Arc<FailingStageService> {
+    Arc::new(FailingStageService::pass_through(plan.clone()))
+}
+
+#[tokio::test]
+async fn test_stage_panic_is_retried_within_restart_budget() {
+    let fixture = Fixture::new().await;
+    let plan = Arc::new(FailurePlan::new(FailureKind::Panic).at_chunk(2).with_limit(1));
+
+    fixture
+        .use_case()
+        .stage_service("passthrough", failing_relay(&plan))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(1))
+        .await
+        .unwrap();
+
+    assert_eq!(plan.injected(), 1);
+    assert_eq!(fixture.restore().await.unwrap(), fixture.data);
+}
+
+#[tokio::test]
+async fn test_stage_parallel_panic_is_retried_within_restart_budget() {
+    let fixture = Fixture::with_topology(ExecutionTopology::StageParallel).await;
+    let plan = Arc::new(FailurePlan::new(FailureKind::Panic).at_chunk(2).with_limit(1));
+
+    fixture
+        .use_case()
+        .stage_service("passthrough", failing_relay(&plan))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(1))
+        .await
+        .unwrap();
+
+    // Only the relay stage reran; brotli's output for chunk 2 was kept
+    assert_eq!(plan.injected(), 1);
+    assert_eq!(fixture.restore().await.unwrap(), fixture.data);
+}
+
+#[tokio::test]
+async fn test_stage_panic_fails_run_without_restart_budget() {
+    let fixture = Fixture::new().await;
+    let plan = Arc::new(FailurePlan::new(FailureKind::Panic).at_chunk(2).with_limit(1));
+
+    let err = fixture
+        .use_case()
+        .stage_service("passthrough", failing_relay(&plan))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(0))
+        .await
+        .unwrap_err();
+
+    assert_eq!(plan.injected(), 1);
+    assert!(format!("{:#}", err).contains("at chunk 2"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn test_read_failure_fails_run_at_the_chosen_chunk() {
+    let fixture = Fixture::new().await;
+    // Skip the checksum pass's read so the workers hit the failure
+    let plan = Arc::new(FailurePlan::new(FailureKind::Io).at_chunk(4).skip_first(1));
+
+    let err = fixture
+        .use_case()
+        .file_io_service(chaos_file_io(&plan))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(3))
+        .await
+        .unwrap_err();
+
+    assert_eq!(plan.injected(), 1, "I/O errors are not retried like panics");
+    assert!(
+        format!("{:#}", err).contains("read_file_chunks at chunk 4"),
+        "{:#}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_corrupted_read_is_caught_on_restore() {
+    let fixture = Fixture::new().await;
+    let plan = Arc::new(FailurePlan::new(FailureKind::Corruption).at_chunk(1).skip_first(1));
+
+    fixture
+        .use_case()
+        .file_io_service(chaos_file_io(&plan))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(0))
+        .await
+        .unwrap();
+    assert_eq!(plan.injected(), 1);
+
+    // The archive is self-consistent but holds different bytes than the
+    // input its header checksums
+    match fixture.restore().await {
+        Ok(restored) => panic!("restored {} corrupted bytes without an error", restored.len()),
+        Err(err) => assert!(format!("{:#}", err).to_lowercase().contains("checksum"), "{:#}", err),
+    }
+}
+
+/// The process use case reports failures as text, so match the variant's
+/// display prefix
+fn is_cancelled(err: &anyhow::Error) -> bool {
+    format!("{:#}", err).contains("Cancelled: ")
+}
+
+#[tokio::test]
+async fn test_shutdown_before_start_leaves_checkpoint() {
+    let fixture = Fixture::new().await;
+    let shutdown = ShutdownCoordinator::new(Duration::from_secs(5));
+    shutdown.initiate_shutdown();
+
+    let err = fixture
+        .use_case()
+        .shutdown(Arc::new(shutdown.token()), Duration::from_secs(5))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(0))
+        .await
+        .unwrap_err();
+
+    assert!(is_cancelled(&err), "{:#}", err);
+    let checkpoint = fixture.checkpoint();
+    assert_eq!(checkpoint.total_chunks, CHUNKS as u64);
+    assert!(checkpoint.completed_chunks.len() < CHUNKS);
+    assert!(checkpoint.drained);
+}
+
+#[tokio::test]
+async fn test_shutdown_aborts_chunk_stalled_past_grace_period() {
+    let fixture = Fixture::new().await;
+    let plan = Arc::new(FailurePlan::new(FailureKind::Stall(Duration::from_secs(3))).at_chunk(2));
+    let shutdown = ShutdownCoordinator::new(Duration::from_millis(100));
+    let token = shutdown.token();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        shutdown.initiate_shutdown();
+    });
+
+    let started = std::time::Instant::now();
+    let err = fixture
+        .use_case()
+        .stage_service("passthrough", failing_relay(&plan))
+        .shutdown(Arc::new(token), Duration::from_millis(100))
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(0))
+        .await
+        .unwrap_err();
+
+    assert!(is_cancelled(&err), "{:#}", err);
+    assert!(
+        started.elapsed() < Duration::from_secs(3),
+        "the stalled chunk was waited for"
+    );
+    let checkpoint = fixture.checkpoint();
+    assert!(!checkpoint.drained);
+    assert_eq!(checkpoint.grace_period_ms, 100);
+    assert!(checkpoint.missing_chunks().contains(&2), "{:?}", checkpoint);
+}
+
+#[tokio::test]
+async fn test_restore_stops_on_shutdown() {
+    let fixture = Fixture::new().await;
+    fixture
+        .use_case()
+        .build()
+        .await
+        .unwrap()
+        .execute(fixture.config(0))
+        .await
+        .unwrap();
+    let shutdown = ShutdownCoordinator::new(Duration::from_secs(5));
+    shutdown.initiate_shutdown();
+
+    let err = RestoreFileUseCase::new(fixture.metrics_service.clone())
+        .with_shutdown(Arc::new(shutdown.token()))
+        .execute(RestoreFileCommand::new(
+            fixture.archive.clone(),
+            fixture.restored.clone(),
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, PipelineError::Cancelled(_)), "{}", err);
+    assert!(!fixture.restored.exists(), "a stopped restore leaves no partial file");
+}
@@ -0,0 +1,156 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Integration tests for the public generic repository.
+//!
+//! Uses the API as a downstream crate would: its own aggregates, with their
+//! own migrations, stored in the pipeline database next to the pipelines.
+
+use adaptive_pipeline::infrastructure::repositories::generic::RepositoryEntity;
+use adaptive_pipeline::infrastructure::repositories::sqlite::{SqliteEntity, SqliteRepository};
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CatalogEntry {
+    id: String,
+    title: String,
+}
+
+impl RepositoryEntity for CatalogEntry {
+    type Id = String;
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.title)
+    }
+}
+
+impl SqliteEntity for CatalogEntry {
+    fn table_name() -> &'static str {
+        "catalog_entries"
+    }
+
+    fn migrations() -> &'static [&'static str] {
+        &[
+            "ALTER TABLE catalog_entries ADD COLUMN shelf TEXT",
+            "CREATE INDEX idx_catalog_entries_shelf ON catalog_entries(shelf)",
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CatalogTag {
+    id: u64,
+    entry_id: String,
+}
+
+impl RepositoryEntity for CatalogTag {
+    type Id = u64;
+
+    fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl SqliteEntity for CatalogTag {
+    fn table_name() -> &'static str {
+        "catalog_tags"
+    }
+}
+
+fn entry(id: &str) -> CatalogEntry {
+    CatalogEntry {
+        id: id.to_string(),
+        title: format!("Title of {}", id),
+    }
+}
+
+async fn pipeline_repository(dir: &Path) -> SqlitePipelineRepository {
+    SqlitePipelineRepository::new(&dir.join("pipeline.db").to_string_lossy())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_downstream_aggregates_share_the_pipeline_database() {
+    let dir = TempDir::new().unwrap();
+    let pipelines = pipeline_repository(dir.path()).await;
+    let entries = SqliteRepository::<CatalogEntry>::new(pipelines.pool().clone())
+        .await
+        .unwrap();
+
+    entries.save(&entry("a")).await.unwrap();
+    assert_eq!(entries.find_by_id("a".to_string()).await.unwrap(), Some(entry("a")));
+    assert_eq!(entries.find_by_name("Title of a").await.unwrap(), Some(entry("a")));
+    assert!(
+        pipelines.list_all().await.unwrap().is_empty(),
+        "pipelines are untouched"
+    );
+    entries.close().await;
+
+    // Reopening the database keeps the data and does not rerun migrations,
+    // which would fail on the duplicate column
+    let reopened = SqliteRepository::<CatalogEntry>::from_file(&dir.path().join("pipeline.db").to_string_lossy())
+        .await
+        .unwrap();
+    assert_eq!(reopened.count().await.unwrap(), 1);
+    let version: i64 = sqlx::query_scalar("SELECT version FROM entity_schema_versions WHERE table_name = ?")
+        .bind("catalog_entries")
+        .fetch_one(reopened.pool())
+        .await
+        .unwrap();
+    assert_eq!(version, 2);
+}
+
+#[tokio::test]
+async fn test_transaction_spans_repositories() {
+    let dir = TempDir::new().unwrap();
+    let pipelines = pipeline_repository(dir.path()).await;
+    let entries = SqliteRepository::<CatalogEntry>::new(pipelines.pool().clone())
+        .await
+        .unwrap();
+    let tags = SqliteRepository::<CatalogTag>::new(pipelines.pool().clone())
+        .await
+        .unwrap();
+    let tag = CatalogTag {
+        id: 7,
+        entry_id: "a".to_string(),
+    };
+
+    let mut tx = entries.begin().await.unwrap();
+    entries.save_in(&mut tx, &entry("a")).await.unwrap();
+    tags.save_in(&mut tx, &tag).await.unwrap();
+    drop(tx);
+    assert_eq!(entries.count().await.unwrap(), 0, "a dropped transaction saves nothing");
+    assert_eq!(tags.count().await.unwrap(), 0);
+
+    let mut tx = entries.begin().await.unwrap();
+    entries.save_in(&mut tx, &entry("a")).await.unwrap();
+    tags.save_in(&mut tx, &tag).await.unwrap();
+
+    // Uncommitted rows are invisible to other connections
+    let other = SqlitePool::connect(&format!("sqlite://{}", dir.path().join("pipeline.db").display()))
+        .await
+        .unwrap();
+    let visible: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM catalog_tags")
+        .fetch_one(&other)
+        .await
+        .unwrap();
+    assert_eq!(visible, 0);
+
+    tx.commit().await.unwrap();
+    assert_eq!(entries.find_by_id("a".to_string()).await.unwrap(), Some(entry("a")));
+    assert_eq!(tags.find_by_id(7).await.unwrap(), Some(tag));
+    other.close().await;
+}
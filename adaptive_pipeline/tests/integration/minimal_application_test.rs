@@ -37,6 +37,7 @@ use std::path::PathBuf;
 async fn test_application_layer_structure() {
     // Arrange - import application layer modules
     use adaptive_pipeline::application::commands::RestoreFileCommand;
+    use adaptive_pipeline_domain::value_objects::OverwritePolicy;
 
     // Act - create a test command to verify module structure
     let command = RestoreFileCommand::new(PathBuf::from("/tmp/source.adapipe"), PathBuf::from("/tmp/target.txt"));
@@ -44,7 +45,7 @@ async fn test_application_layer_structure() {
     // Assert - verify command properties and defaults
     assert_eq!(command.source_adapipe_path, PathBuf::from("/tmp/source.adapipe"));
     assert_eq!(command.target_path, PathBuf::from("/tmp/target.txt"));
-    assert!(!command.overwrite);
+    assert_eq!(command.overwrite_policy, OverwritePolicy::Fail);
     assert!(command.validate_permissions);
     assert!(command.create_directories);
 
@@ -0,0 +1,137 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Overwrite Policy Tests
+//!
+//! Processes a file onto an existing `.adapipe` output under each overwrite
+//! policy and checks what was written, what was left alone and what the
+//! result reports, and that an explicit output mode overrides the umask.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use adaptive_pipeline::application::use_cases::{ProcessFileConfig, ProcessFileResult, ProcessFileUseCase};
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+use adaptive_pipeline::test_util::generators::stage;
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_domain::entities::{Pipeline, StageType};
+use adaptive_pipeline_domain::value_objects::{ChunkSize, FileMode, JobPriority, OverwritePolicy};
+use tempfile::TempDir;
+
+const EXISTING: &[u8] = b"an earlier archive";
+
+async fn process(dir: &Path, overwrite_policy: OverwritePolicy) -> anyhow::Result<ProcessFileResult> {
+    process_with_mode(dir, overwrite_policy, None).await
+}
+
+async fn process_with_mode(
+    dir: &Path,
+    overwrite_policy: OverwritePolicy,
+    output_mode: Option<FileMode>,
+) -> anyhow::Result<ProcessFileResult> {
+    let repository = Arc::new(SqlitePipelineRepository::new(&dir.join("pipeline.db").to_string_lossy()).await?);
+    if repository.find_by_name("overwrite").await?.is_none() {
+        let stages = vec![stage("compress", StageType::Compression, "brotli", HashMap::new())];
+        repository
+            .save(&Pipeline::new("overwrite".to_string(), stages)?)
+            .await?;
+    }
+
+    ProcessFileUseCase::builder()
+        .pipeline_repository(repository)
+        .build()
+        .await?
+        .execute(ProcessFileConfig {
+            input: dir.join("data.txt"),
+            output: dir.join("data.txt.adapipe"),
+            pipeline: "overwrite".to_string(),
+            chunk_size: Some(ChunkSize::new(1024).unwrap()),
+            workers: Some(2),
+            channel_depth: None,
+            inflight_window: None,
+            write_manifest: false,
+            signing_key: None,
+            idempotency_key: None,
+            stage_timeout: None,
+            chunk_timeout: None,
+            max_worker_restarts: 0,
+            direct_io: false,
+            checksum_offload: false,
+            overwrite_policy,
+            output_mode,
+            priority: JobPriority::default(),
+            password: None,
+            passphrase_policy: PassphrasePolicy::default(),
+        })
+        .await
+}
+
+/// A directory with an input file and an unrelated file already at its
+/// output path
+fn fixture() -> TempDir {
+    let _ = init_resource_manager(ResourceConfig::default());
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("data.txt"), "overwrite policy ".repeat(200)).unwrap();
+    std::fs::write(dir.path().join("data.txt.adapipe"), EXISTING).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_process_fails_on_existing_output_by_default() {
+    let dir = fixture();
+    let err = process(dir.path(), OverwritePolicy::default()).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("already exists"), "{:#}", err);
+    assert_eq!(std::fs::read(dir.path().join("data.txt.adapipe")).unwrap(), EXISTING);
+}
+
+#[tokio::test]
+async fn test_process_skip_and_rename_keep_existing_output() {
+    let dir = fixture();
+    let output = dir.path().join("data.txt.adapipe");
+
+    let skipped = process(dir.path(), OverwritePolicy::Skip).await.unwrap();
+    assert!(skipped.skipped);
+    assert_eq!(skipped.overwrite_policy, OverwritePolicy::Skip);
+    assert_eq!(skipped.output, output);
+    assert_eq!(std::fs::read(&output).unwrap(), EXISTING);
+
+    let renamed = process(dir.path(), OverwritePolicy::Rename).await.unwrap();
+    assert!(!renamed.skipped);
+    assert_eq!(renamed.output, dir.path().join("data-1.txt.adapipe"));
+    assert_eq!(
+        std::fs::metadata(&renamed.output).unwrap().len(),
+        renamed.output_size_bytes
+    );
+    assert_eq!(std::fs::read(&output).unwrap(), EXISTING);
+}
+
+#[tokio::test]
+async fn test_process_overwrite_replaces_existing_output() {
+    let dir = fixture();
+    let output = dir.path().join("data.txt.adapipe");
+
+    let result = process(dir.path(), OverwritePolicy::Overwrite).await.unwrap();
+    assert_eq!(result.output, output);
+    assert_eq!(result.overwrite_policy, OverwritePolicy::Overwrite);
+    assert_ne!(std::fs::read(&output).unwrap(), EXISTING);
+    assert_eq!(std::fs::metadata(&output).unwrap().len(), result.output_size_bytes);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_process_output_mode_replaces_umask_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = fixture();
+    let result = process_with_mode(dir.path(), OverwritePolicy::Rename, Some(FileMode::new(0o600).unwrap()))
+        .await
+        .unwrap();
+    let mode = std::fs::metadata(&result.output).unwrap().permissions().mode() & 0o7777;
+    assert_eq!(mode, 0o600);
+}
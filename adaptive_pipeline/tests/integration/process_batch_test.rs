@@ -0,0 +1,277 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Process Batch Tests
+//!
+//! Runs batches in which some files fail, because an unrelated file already
+//! sits at their output, and checks the outcome error, the retry manifest it
+//! leaves, and that retrying the manifest reprocesses only those files.
+//! Also checks that batches sharing a pipeline cache load the pipeline once
+//! and stop finding it when it is deleted.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use adaptive_pipeline::application::services::pipeline_cache::PipelineCache;
+use adaptive_pipeline::application::use_cases::{
+    DeletePipelineUseCase, ProcessBatchUseCase, ProcessFileConfig, ProcessFileUseCase,
+};
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+use adaptive_pipeline::test_util::generators::stage;
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_bootstrap::shutdown::ShutdownCoordinator;
+use adaptive_pipeline_domain::entities::{Pipeline, StageType};
+use adaptive_pipeline_domain::value_objects::{BatchRetryManifest, ChunkSize, JobPriority, OverwritePolicy};
+use tempfile::TempDir;
+
+const PIPELINE: &str = "batch";
+
+/// A directory with three inputs and a database holding the batch pipeline
+async fn fixture() -> (TempDir, Vec<PathBuf>) {
+    let _ = init_resource_manager(ResourceConfig::default());
+    let dir = TempDir::new().unwrap();
+    let inputs: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+        .iter()
+        .map(|name| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("batch input {} ", name).repeat(100)).unwrap();
+            path
+        })
+        .collect();
+    let stages = vec![stage("compress", StageType::Compression, "brotli", HashMap::new())];
+    repository(dir.path())
+        .await
+        .save(&Pipeline::new(PIPELINE.to_string(), stages).unwrap())
+        .await
+        .unwrap();
+    (dir, inputs)
+}
+
+async fn repository(dir: &Path) -> Arc<SqlitePipelineRepository> {
+    Arc::new(
+        SqlitePipelineRepository::new(&dir.join("pipeline.db").to_string_lossy())
+            .await
+            .unwrap(),
+    )
+}
+
+async fn use_case(dir: &Path) -> ProcessBatchUseCase {
+    let process_file = ProcessFileUseCase::builder()
+        .pipeline_repository(repository(dir).await)
+        .build()
+        .await
+        .unwrap();
+    ProcessBatchUseCase::new(process_file)
+}
+
+async fn cached_use_case(dir: &Path, cache: Arc<PipelineCache>) -> ProcessBatchUseCase {
+    let process_file = ProcessFileUseCase::builder()
+        .pipeline_repository(repository(dir).await)
+        .pipeline_cache(cache)
+        .build()
+        .await
+        .unwrap();
+    ProcessBatchUseCase::new(process_file)
+}
+
+fn config(pipeline: &str) -> ProcessFileConfig {
+    ProcessFileConfig {
+        input: PathBuf::new(),
+        output: PathBuf::new(),
+        pipeline: pipeline.to_string(),
+        chunk_size: Some(ChunkSize::new(1024).unwrap()),
+        workers: Some(2),
+        channel_depth: None,
+        inflight_window: None,
+        write_manifest: false,
+        signing_key: None,
+        idempotency_key: None,
+        stage_timeout: None,
+        chunk_timeout: None,
+        max_worker_restarts: 0,
+        direct_io: false,
+        checksum_offload: false,
+        overwrite_policy: OverwritePolicy::default(),
+        output_mode: None,
+        priority: JobPriority::default(),
+        password: None,
+        passphrase_policy: PassphrasePolicy::default(),
+    }
+}
+
+fn retry_manifest(path: &Path) -> BatchRetryManifest {
+    BatchRetryManifest::from_json(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_partial_batch_writes_manifest_and_retry_processes_only_failures() {
+    let (dir, inputs) = fixture().await;
+    let out = dir.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    let blocker = out.join("b.txt.adapipe");
+    std::fs::write(&blocker, b"an earlier archive").unwrap();
+
+    let err = use_case(dir.path())
+        .await
+        .execute(inputs.clone(), out.clone(), None, config(PIPELINE))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().starts_with("Partial success: 2 of 3 files processed"),
+        "{}",
+        err
+    );
+    assert!(out.join("a.txt.adapipe").exists());
+    assert!(out.join("c.txt.adapipe").exists());
+
+    let manifest_path = out.join("batch-failures.json");
+    let manifest = retry_manifest(&manifest_path);
+    assert_eq!(manifest.pipeline, PIPELINE);
+    assert_eq!(manifest.failures.len(), 1);
+    assert_eq!(manifest.failures[0].input, inputs[1].to_string_lossy());
+    assert_eq!(manifest.failures[0].output, blocker.to_string_lossy());
+    assert!(manifest.failures[0].error.contains("already exists"), "{:?}", manifest);
+
+    // The pipeline comes from the manifest, not the retry's config
+    std::fs::remove_file(&blocker).unwrap();
+    let a_archive = std::fs::read(out.join("a.txt.adapipe")).unwrap();
+    let results = use_case(dir.path())
+        .await
+        .retry(manifest_path.clone(), None, config(""))
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].output, blocker);
+    assert!(!manifest_path.exists(), "a fully successful retry removes its manifest");
+    assert_eq!(std::fs::read(out.join("a.txt.adapipe")).unwrap(), a_archive);
+}
+
+#[tokio::test]
+async fn test_batch_with_no_successes_reports_first_error() {
+    let (dir, inputs) = fixture().await;
+    let out = dir.path().join("out");
+    let failures = dir.path().join("reports/failed.json");
+
+    let err = use_case(dir.path())
+        .await
+        .execute(inputs, out, Some(failures.clone()), config("no-such-pipeline"))
+        .await
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.starts_with("Batch failed: 0 of 3 files processed"),
+        "{}",
+        message
+    );
+    assert!(message.contains("not found"), "{}", message);
+    assert_eq!(retry_manifest(&failures).failures.len(), 3);
+}
+
+#[tokio::test]
+async fn test_batch_rejects_inputs_sharing_a_file_name() {
+    let (dir, inputs) = fixture().await;
+    let nested = dir.path().join("nested");
+    std::fs::create_dir(&nested).unwrap();
+    std::fs::copy(&inputs[0], nested.join("a.txt")).unwrap();
+    let out = dir.path().join("out");
+
+    let err = use_case(dir.path())
+        .await
+        .execute(
+            vec![inputs[0].clone(), nested.join("a.txt")],
+            out.clone(),
+            None,
+            config(PIPELINE),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Two inputs would be written to"), "{}", err);
+    assert!(!out.exists(), "nothing is processed when outputs collide");
+}
+
+#[tokio::test]
+async fn test_shutdown_records_unstarted_files_as_failures() {
+    let (dir, inputs) = fixture().await;
+    let out = dir.path().join("out");
+    let shutdown = ShutdownCoordinator::new(Duration::from_secs(5));
+    shutdown.initiate_shutdown();
+
+    let err = use_case(dir.path())
+        .await
+        .with_shutdown(Arc::new(shutdown.token()))
+        .execute(inputs, out.clone(), None, config(PIPELINE))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().starts_with("Cancelled: 0 of 3 files processed"),
+        "{}",
+        err
+    );
+    let manifest = retry_manifest(&out.join("batch-failures.json"));
+    assert_eq!(manifest.failures.len(), 3);
+    assert!(manifest
+        .failures
+        .iter()
+        .all(|failure| failure.error.starts_with("Cancelled:")));
+}
+
+#[tokio::test]
+async fn test_cached_pipeline_survives_external_delete_until_invalidated() {
+    let (dir, inputs) = fixture().await;
+    let cache = Arc::new(PipelineCache::default());
+    cached_use_case(dir.path(), cache.clone())
+        .await
+        .execute(inputs.clone(), dir.path().join("first"), None, config(PIPELINE))
+        .await
+        .unwrap();
+    assert_eq!(cache.len(), 1, "every file in the batch shares one cached pipeline");
+
+    // Removed behind the cache's back (as by another process): still served
+    let repository = repository(dir.path()).await;
+    let pipeline = repository.find_by_name(PIPELINE).await.unwrap().unwrap();
+    repository.delete(pipeline.id().clone()).await.unwrap();
+    cached_use_case(dir.path(), cache.clone())
+        .await
+        .execute(inputs.clone(), dir.path().join("second"), None, config(PIPELINE))
+        .await
+        .unwrap();
+
+    cache.invalidate(pipeline.id());
+    let err = cached_use_case(dir.path(), cache)
+        .await
+        .execute(inputs, dir.path().join("third"), None, config(PIPELINE))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_delete_use_case_invalidates_the_cache() {
+    let (dir, inputs) = fixture().await;
+    let cache = Arc::new(PipelineCache::default());
+    cached_use_case(dir.path(), cache.clone())
+        .await
+        .execute(inputs.clone(), dir.path().join("first"), None, config(PIPELINE))
+        .await
+        .unwrap();
+
+    DeletePipelineUseCase::new(repository(dir.path()).await)
+        .with_pipeline_cache(cache.clone())
+        .execute(PIPELINE.to_string(), true)
+        .await
+        .unwrap();
+    assert!(cache.is_empty());
+    let err = cached_use_case(dir.path(), cache)
+        .await
+        .execute(inputs, dir.path().join("second"), None, config(PIPELINE))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+}
@@ -0,0 +1,215 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Process → Restore Round-Trip Tests
+//!
+//! Property tests that run generated stage combinations, chunk sizes, file
+//! contents and execution topologies through the full process and restore
+//! use cases, then check
+//! that the restored file is byte-identical to the input and that the
+//! archive's metadata describes the run. The generators come from
+//! `adaptive_pipeline::test_util::generators` so plugin authors can run the
+//! same properties against their own stages.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use adaptive_pipeline::application::commands::RestoreFileCommand;
+use adaptive_pipeline::application::use_cases::{ProcessFileConfig, ProcessFileUseCase, RestoreFileUseCase};
+use adaptive_pipeline::infrastructure::metrics::MetricsService;
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline::infrastructure::runtime::{init_resource_manager, ResourceConfig};
+use adaptive_pipeline::infrastructure::services::{AdapipeFormat, BinaryFormatService};
+use adaptive_pipeline::test_util::generators::{builtin_stages, chunk_sizes, file_contents};
+use adaptive_pipeline_bootstrap::cli::PassphrasePolicy;
+use adaptive_pipeline_domain::entities::pipeline_stage::SKIP_CONTENT_PARAMETER;
+use adaptive_pipeline_domain::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+use adaptive_pipeline_domain::value_objects::{
+    ChunkSize, ContentType, ExecutionTopology, JobPriority, OverwritePolicy, ProcessingStepType,
+};
+use proptest::prelude::*;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+/// Processes `data` with `stages` run in `topology`, restores the archive
+/// and returns the restored bytes after checking the archive's metadata
+async fn process_and_restore(
+    dir: &Path,
+    stages: Vec<PipelineStage>,
+    topology: ExecutionTopology,
+    chunk_size: ChunkSize,
+    checksum_offload: bool,
+    data: &[u8],
+) -> Vec<u8> {
+    let input = dir.join("input.bin");
+    let archive = dir.join("input.bin.adapipe");
+    let restored = dir.join("restored.bin");
+    std::fs::write(&input, data).unwrap();
+
+    let repository = Arc::new(
+        SqlitePipelineRepository::new(&dir.join("pipeline.db").to_string_lossy())
+            .await
+            .unwrap(),
+    );
+    let mut pipeline = Pipeline::new("roundtrip".to_string(), stages).unwrap();
+    pipeline.set_execution_topology(topology);
+    repository.save(&pipeline).await.unwrap();
+
+    let metrics_service = Arc::new(MetricsService::new().unwrap());
+    ProcessFileUseCase::builder()
+        .metrics_service(metrics_service.clone())
+        .pipeline_repository(repository)
+        .build()
+        .await
+        .unwrap()
+        .execute(ProcessFileConfig {
+            input: input.clone(),
+            output: archive.clone(),
+            pipeline: "roundtrip".to_string(),
+            chunk_size: Some(chunk_size),
+            workers: Some(3),
+            channel_depth: None,
+            inflight_window: None,
+            write_manifest: false,
+            signing_key: None,
+            idempotency_key: None,
+            stage_timeout: None,
+            chunk_timeout: None,
+            max_worker_restarts: 0,
+            direct_io: false,
+            checksum_offload,
+            overwrite_policy: OverwritePolicy::default(),
+            output_mode: None,
+            priority: JobPriority::default(),
+            password: None,
+            passphrase_policy: PassphrasePolicy::default(),
+        })
+        .await
+        .unwrap();
+
+    // Metadata fidelity
+    let header = AdapipeFormat::new().read_metadata(&archive).await.unwrap();
+    assert_eq!(header.original_filename, "input.bin");
+    assert_eq!(header.original_size, data.len() as u64);
+    assert_eq!(header.original_checksum, format!("{:x}", Sha256::digest(data)));
+    // A chunk size larger than the file falls back to the adaptive one
+    if chunk_size.bytes() <= data.len() {
+        assert_eq!(header.chunk_size as usize, chunk_size.bytes());
+    }
+    assert_eq!(
+        header.chunk_count as usize,
+        data.len().div_ceil(header.chunk_size as usize)
+    );
+    assert_eq!(header.pipeline_id, pipeline.id().to_string());
+    let recorded: Vec<&str> = header
+        .processing_steps
+        .iter()
+        .filter(|step| step.step_type != ProcessingStepType::Checksum)
+        .map(|step| step.algorithm.as_str())
+        .collect();
+    // Stages whose skip_content rule matches the detected type don't run
+    let content_type = header.content_type.clone().expect("content type not recorded");
+    assert_eq!(content_type, ContentType::detect(data));
+    let (applied, _) = pipeline.for_content(&content_type).unwrap();
+    let configured: Vec<&str> = applied
+        .stages()
+        .iter()
+        .filter(|stage| stage.stage_type() != &StageType::Checksum)
+        .map(|stage| stage.configuration().algorithm.as_str())
+        .collect();
+    assert_eq!(recorded, configured);
+
+    RestoreFileUseCase::new(metrics_service)
+        .execute(RestoreFileCommand::new(archive, restored.clone()))
+        .await
+        .unwrap();
+    std::fs::read(restored).unwrap()
+}
+
+/// A compression stage that sits out already-compressed content still runs
+/// for other content, and both archives restore
+#[tokio::test]
+async fn skipped_stage_is_left_out_of_the_archive() {
+    let _ = init_resource_manager(ResourceConfig::default());
+    let compression = || {
+        let config = StageConfiguration {
+            algorithm: "zstd".to_string(),
+            parameters: [
+                ("algorithm".to_string(), "zstd".to_string()),
+                (SKIP_CONTENT_PARAMETER.to_string(), "compressed".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        vec![PipelineStage::new("compress".to_string(), StageType::Compression, config, 0).unwrap()]
+    };
+    let chunk_size = ChunkSize::new(4096).unwrap();
+
+    let mut gzip = b"\x1f\x8b\x08\x00".to_vec();
+    gzip.extend((0..20_000u32).map(|i| (i * 7 % 253) as u8));
+    let dir = TempDir::new().unwrap();
+    let restored = process_and_restore(
+        dir.path(),
+        compression(),
+        ExecutionTopology::default(),
+        chunk_size,
+        false,
+        &gzip,
+    )
+    .await;
+    assert_eq!(restored, gzip);
+    let header = AdapipeFormat::new()
+        .read_metadata(&dir.path().join("input.bin.adapipe"))
+        .await
+        .unwrap();
+    assert!(!header.is_compressed());
+
+    let text = b"Plain text compresses well.\n".repeat(500);
+    let dir = TempDir::new().unwrap();
+    let restored = process_and_restore(
+        dir.path(),
+        compression(),
+        ExecutionTopology::default(),
+        chunk_size,
+        false,
+        &text,
+    )
+    .await;
+    assert_eq!(restored, text);
+    let header = AdapipeFormat::new()
+        .read_metadata(&dir.path().join("input.bin.adapipe"))
+        .await
+        .unwrap();
+    assert_eq!(header.compression_algorithm(), Some("zstd"));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn process_then_restore_is_lossless(
+        stages in builtin_stages(),
+        topology in prop::sample::select(ExecutionTopology::all().to_vec()),
+        chunk_size in chunk_sizes(),
+        checksum_offload in any::<bool>(),
+        data in file_contents(96 * 1024),
+    ) {
+        // The process path draws CPU and I/O tokens from the global manager
+        let _ = init_resource_manager(ResourceConfig::default());
+        let dir = TempDir::new().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let restored = runtime.block_on(process_and_restore(
+            dir.path(),
+            stages,
+            topology,
+            chunk_size,
+            checksum_offload,
+            &data,
+        ));
+        prop_assert!(restored == data, "restored {} bytes differ from the {} input bytes", restored.len(), data.len());
+    }
+}
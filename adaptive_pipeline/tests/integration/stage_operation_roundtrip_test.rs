@@ -0,0 +1,275 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Stage Operation Round-Trip Tests
+//!
+//! Property tests verifying that every built-in `StageService` honours the
+//! `Operation` it is handed: reversible stages must satisfy
+//! `reverse(forward(x)) == x` for arbitrary input, and one-way stages must
+//! reject `Operation::Reverse` with `PipelineError::UnsupportedOperation`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use adaptive_pipeline::infrastructure::adapters::{MultiAlgoCompression, MultiAlgoEncryption};
+use adaptive_pipeline::infrastructure::metrics::MetricsService;
+use adaptive_pipeline::infrastructure::services::{
+    Base64EncodingService, DebugService, PassThroughService, PiiMaskingService, TeeService,
+};
+use adaptive_pipeline_domain::entities::{
+    Operation, ProcessingContext, SecurityContext, SecurityLevel, StageConfiguration,
+};
+use adaptive_pipeline_domain::services::StageService;
+use adaptive_pipeline_domain::value_objects::chunk_encryption_spec::ASSOCIATED_DATA_KEY;
+use adaptive_pipeline_domain::value_objects::{ChunkBinding, FileChunk, NONCE_STRATEGY_KEY};
+use adaptive_pipeline_domain::PipelineError;
+use base64::engine::general_purpose;
+use base64::Engine;
+use proptest::prelude::*;
+
+fn config(algorithm: &str, operation: Operation, parameters: HashMap<String, String>) -> StageConfiguration {
+    StageConfiguration {
+        algorithm: algorithm.to_string(),
+        operation,
+        parameters,
+        parallel_processing: false,
+        chunk_size: None,
+    }
+}
+
+fn context(size: usize) -> ProcessingContext {
+    ProcessingContext::new(size as u64, SecurityContext::new(None, SecurityLevel::Public))
+}
+
+/// Runs `data` forward then reverse through `service` with the same
+/// parameters, returning the restored bytes.
+fn round_trip(
+    service: &dyn StageService,
+    algorithm: &str,
+    parameters: HashMap<String, String>,
+    data: Vec<u8>,
+) -> Result<Vec<u8>, PipelineError> {
+    let mut ctx = context(data.len());
+    let chunk = FileChunk::new(0, 0, data, true)?;
+
+    let forward = config(algorithm, Operation::Forward, parameters.clone());
+    let encoded = service.process_chunk(chunk, Operation::Forward, &forward, &mut ctx)?;
+
+    let reverse = config(algorithm, Operation::Reverse, parameters);
+    let decoded = service.process_chunk(encoded, Operation::Reverse, &reverse, &mut ctx)?;
+
+    Ok(decoded.data().to_vec())
+}
+
+fn algorithm_params(algorithm: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("algorithm".to_string(), algorithm.to_string());
+    params
+}
+
+fn encryption_params(algorithm: &str, key_len: usize) -> HashMap<String, String> {
+    let mut params = algorithm_params(algorithm);
+    params.insert("key".to_string(), general_purpose::STANDARD.encode(vec![7u8; key_len]));
+    params.insert("nonce".to_string(), general_purpose::STANDARD.encode([3u8; 12]));
+    params.insert("salt".to_string(), general_purpose::STANDARD.encode([5u8; 16]));
+    params
+}
+
+fn payload() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 1..4096)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn compression_round_trips(
+        data in payload(),
+        algorithm in prop::sample::select(vec!["brotli", "gzip", "zstd", "xz"]),
+    ) {
+        let service = MultiAlgoCompression::new();
+        let restored = round_trip(&service, algorithm, algorithm_params(algorithm), data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn encryption_round_trips(
+        data in payload(),
+        (algorithm, key_len) in prop::sample::select(vec![
+            ("aes-256-gcm", 32usize),
+            ("chacha20-poly1305", 32usize),
+        ]),
+    ) {
+        let service = MultiAlgoEncryption::new();
+        let restored = round_trip(&service, algorithm, encryption_params(algorithm, key_len), data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn encryption_round_trips_under_each_nonce_strategy(
+        data in payload(),
+        (algorithm, strategy) in prop::sample::select(vec![
+            ("aes-256-gcm", "counter"),
+            ("chacha20-poly1305", "counter"),
+            ("chacha20-poly1305", "xchacha-random"),
+        ]),
+    ) {
+        let service = MultiAlgoEncryption::new();
+        let mut params = encryption_params(algorithm, 32);
+        params.insert(NONCE_STRATEGY_KEY.to_string(), strategy.to_string());
+        let restored = round_trip(&service, algorithm, params, data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn base64_round_trips(
+        data in payload(),
+        variant in prop::sample::select(vec!["standard", "url_safe"]),
+    ) {
+        let service = Base64EncodingService::new();
+        let mut params = HashMap::new();
+        params.insert("variant".to_string(), variant.to_string());
+        let restored = round_trip(&service, "base64", params, data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn passthrough_round_trips(data in payload()) {
+        let service = PassThroughService::new();
+        let restored = round_trip(&service, "passthrough", HashMap::new(), data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn debug_round_trips(data in payload()) {
+        let service = DebugService::new(Arc::new(MetricsService::new().unwrap()));
+        let mut params = HashMap::new();
+        params.insert("label".to_string(), "roundtrip".to_string());
+        let restored = round_trip(&service, "debug", params, data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn tee_round_trips(data in payload()) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut params = HashMap::new();
+        params.insert(
+            "output_path".to_string(),
+            temp_dir.path().join("tee.bin").display().to_string(),
+        );
+        let service = TeeService::new();
+        let restored = round_trip(&service, "tee", params, data.clone()).unwrap();
+        prop_assert_eq!(restored, data);
+    }
+}
+
+#[test]
+fn pii_masking_rejects_reverse_operation() {
+    let service = PiiMaskingService::new();
+    let chunk = FileChunk::new(0, 0, b"contact: user@example.com".to_vec(), true).unwrap();
+    let reverse = config("pii_masking", Operation::Reverse, HashMap::new());
+
+    let err = service
+        .process_chunk(chunk, Operation::Reverse, &reverse, &mut context(25))
+        .unwrap_err();
+
+    assert!(matches!(err, PipelineError::UnsupportedOperation(_)));
+    assert!(!service.is_reversible());
+}
+
+#[test]
+fn operation_argument_takes_precedence_over_configuration() {
+    // The executor is the source of truth for direction; a stale
+    // `config.operation` must not change what the service does.
+    let service = Base64EncodingService::new();
+    let chunk = FileChunk::new(0, 0, b"hello".to_vec(), true).unwrap();
+    let stale = config("base64", Operation::Reverse, HashMap::new());
+
+    let encoded = service
+        .process_chunk(chunk, Operation::Forward, &stale, &mut context(5))
+        .unwrap();
+
+    assert_eq!(encoded.data(), b"aGVsbG8=");
+}
+
+/// Seals `data` as chunk `sequence` of the run `parent` belongs to
+fn seal(
+    service: &MultiAlgoEncryption,
+    strategy: &str,
+    sequence: u64,
+    data: &[u8],
+    parent: &ProcessingContext,
+) -> Result<Vec<u8>, PipelineError> {
+    let mut params = encryption_params("chacha20-poly1305", 32);
+    params.insert(NONCE_STRATEGY_KEY.to_string(), strategy.to_string());
+    let forward = config("chacha20-poly1305", Operation::Forward, params);
+    let chunk = FileChunk::new(sequence, 0, data.to_vec(), false)?;
+    let sealed = service.process_chunk(chunk, Operation::Forward, &forward, &mut parent.child())?;
+    Ok(sealed.data().to_vec())
+}
+
+#[test]
+fn counter_nonces_share_the_run_prefix_and_count_chunks() {
+    let service = MultiAlgoEncryption::new();
+    let run = context(64);
+
+    let first = seal(&service, "counter", 0, b"first", &run).unwrap();
+    let second = seal(&service, "counter", 1, b"second", &run).unwrap();
+    assert_eq!(first[..8], second[..8]);
+    assert_eq!(first[8..12], [0, 0, 0, 0]);
+    assert_eq!(second[8..12], [0, 0, 0, 1]);
+
+    let other_run = seal(&service, "counter", 0, b"first", &context(64)).unwrap();
+    assert_ne!(first[..8], other_run[..8]);
+}
+
+#[test]
+fn xchacha_payload_starts_with_the_extended_nonce() {
+    let service = MultiAlgoEncryption::new();
+    let sealed = seal(&service, "xchacha-random", 0, b"extended", &context(8)).unwrap();
+    assert_eq!(sealed.len(), 24 + b"extended".len() + 16);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn reused_nonce_is_rejected_in_debug_builds() {
+    let service = MultiAlgoEncryption::new();
+    let run = context(64);
+
+    seal(&service, "counter", 3, b"once", &run).unwrap();
+    let err = seal(&service, "counter", 3, b"twice", &run).unwrap_err();
+    assert!(err.to_string().contains("already used"), "{}", err);
+}
+
+#[test]
+fn bound_chunks_only_open_at_their_own_position_and_pipeline() {
+    let service = MultiAlgoEncryption::new();
+    let bound = |pipeline_id: &str| {
+        let mut params = encryption_params("aes-256-gcm", 32);
+        params.insert(
+            ASSOCIATED_DATA_KEY.to_string(),
+            ChunkBinding::new(pipeline_id, 1).to_parameter(),
+        );
+        params
+    };
+    let forward = config("aes-256-gcm", Operation::Forward, bound("archive-a"));
+    let chunk = FileChunk::new(4, 0, b"bound chunk".to_vec(), false).unwrap();
+    let sealed = service
+        .process_chunk(chunk, Operation::Forward, &forward, &mut context(11))
+        .unwrap();
+
+    let open = |sequence: u64, pipeline_id: &str| {
+        let chunk = FileChunk::new(sequence, 0, sealed.data().to_vec(), false).unwrap();
+        let reverse = config("aes-256-gcm", Operation::Reverse, bound(pipeline_id));
+        service.process_chunk(chunk, Operation::Reverse, &reverse, &mut context(11))
+    };
+    assert_eq!(open(4, "archive-a").unwrap().data(), b"bound chunk");
+    // Reordered
+    assert!(open(5, "archive-a").is_err());
+    // Transplanted into another pipeline's archive
+    assert!(open(4, "archive-b").is_err());
+}
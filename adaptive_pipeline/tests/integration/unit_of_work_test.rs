@@ -0,0 +1,204 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Integration tests for the SQLite unit of work.
+//!
+//! A pipeline change, its audit record and its domain event must become
+//! visible together on commit, and not at all when the unit of work is
+//! rolled back, dropped, or fails part-way. Both are stamped with the
+//! correlation ID of the run that wrote them.
+
+use adaptive_pipeline::application::use_cases::{CreatePipelineUseCase, DeletePipelineUseCase};
+use adaptive_pipeline::infrastructure::repositories::sqlite_pipeline::SqlitePipelineRepository;
+use adaptive_pipeline_domain::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+use adaptive_pipeline_domain::events::{PipelineCreatedEvent, PipelineEvent};
+use adaptive_pipeline_domain::value_objects::{AuditRecord, CorrelationId};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn pipeline(name: &str) -> Pipeline {
+    let configuration = StageConfiguration::new("brotli".to_string(), HashMap::new(), false);
+    let stage = PipelineStage::new("compress".to_string(), StageType::Compression, configuration, 0).unwrap();
+    Pipeline::new(name.to_string(), vec![stage]).unwrap()
+}
+
+fn created_event(pipeline: &Pipeline) -> PipelineEvent {
+    PipelineEvent::PipelineCreated(PipelineCreatedEvent::new(
+        pipeline.id().as_uuid(),
+        pipeline.name().to_string(),
+        pipeline.stages().len(),
+        Some("alice".to_string()),
+    ))
+}
+
+async fn repository(dir: &Path) -> Arc<SqlitePipelineRepository> {
+    Arc::new(
+        SqlitePipelineRepository::new(&dir.join("pipeline.db").to_string_lossy())
+            .await
+            .unwrap(),
+    )
+}
+
+/// Row counts of (active pipelines, audit records, domain events), read
+/// through a connection of its own so only committed rows are seen
+async fn counts(dir: &Path) -> (i64, i64, i64) {
+    let pool = SqlitePool::connect(&format!("sqlite://{}", dir.join("pipeline.db").display()))
+        .await
+        .unwrap();
+    let count = |sql: &'static str| {
+        let pool = pool.clone();
+        async move { sqlx::query_scalar::<_, i64>(sql).fetch_one(&pool).await.unwrap() }
+    };
+    let pipelines = count("SELECT COUNT(*) FROM pipelines WHERE archived = false").await;
+    let audits = count("SELECT COUNT(*) FROM audit_records").await;
+    let events = count("SELECT COUNT(*) FROM domain_events").await;
+    pool.close().await;
+    (pipelines, audits, events)
+}
+
+#[tokio::test]
+async fn test_committed_unit_of_work_makes_every_change_visible() {
+    let dir = TempDir::new().unwrap();
+    let repository = repository(dir.path()).await;
+    let pipeline = pipeline("nightly-backup");
+
+    let mut work = repository.begin().await.unwrap();
+    work.save_pipeline(&pipeline).await.unwrap();
+    work.record_audit(&AuditRecord::new("alice", "pipeline.create", "1 stages").with_pipeline(pipeline.id().clone()))
+        .await
+        .unwrap();
+    work.record_event(&created_event(&pipeline)).await.unwrap();
+    assert_eq!(counts(dir.path()).await, (0, 0, 0), "nothing is visible before commit");
+
+    work.commit().await.unwrap();
+    assert_eq!(counts(dir.path()).await, (1, 1, 1));
+    assert!(repository.find_by_name("nightly-backup").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_rolled_back_or_dropped_unit_of_work_leaves_nothing() {
+    let dir = TempDir::new().unwrap();
+    let repository = repository(dir.path()).await;
+
+    let rolled_back = pipeline("rolled-back");
+    let mut work = repository.begin().await.unwrap();
+    work.save_pipeline(&rolled_back).await.unwrap();
+    work.record_audit(&AuditRecord::new("alice", "pipeline.create", ""))
+        .await
+        .unwrap();
+    work.record_event(&created_event(&rolled_back)).await.unwrap();
+    work.rollback().await.unwrap();
+
+    let dropped = pipeline("dropped");
+    let mut work = repository.begin().await.unwrap();
+    work.save_pipeline(&dropped).await.unwrap();
+    work.record_event(&created_event(&dropped)).await.unwrap();
+    drop(work);
+
+    assert_eq!(counts(dir.path()).await, (0, 0, 0));
+    assert!(repository.find_by_name("rolled-back").await.unwrap().is_none());
+    assert!(repository.find_by_name("dropped").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_failure_part_way_discards_earlier_changes() {
+    let dir = TempDir::new().unwrap();
+    let repository = repository(dir.path()).await;
+    let existing = pipeline("nightly-backup");
+    repository.save(&existing).await.unwrap();
+
+    // The audit record is written, then saving a duplicate name fails
+    let duplicate = pipeline("nightly-backup");
+    let mut work = repository.begin().await.unwrap();
+    work.record_audit(&AuditRecord::new("alice", "pipeline.create", ""))
+        .await
+        .unwrap();
+    assert!(work.save_pipeline(&duplicate).await.is_err());
+    drop(work);
+
+    assert_eq!(
+        counts(dir.path()).await,
+        (1, 0, 0),
+        "the audit record was rolled back too"
+    );
+}
+
+#[tokio::test]
+async fn test_create_and_delete_use_cases_audit_their_changes() {
+    let dir = TempDir::new().unwrap();
+    let repository = repository(dir.path()).await;
+
+    CreatePipelineUseCase::new(repository.clone())
+        .with_principal("alice")
+        .execute("nightly-backup".to_string(), "brotli".to_string(), None, None)
+        .await
+        .unwrap();
+    DeletePipelineUseCase::new(repository.clone())
+        .with_principal("bob")
+        .execute("nightly-backup".to_string(), true)
+        .await
+        .unwrap();
+    assert_eq!(counts(dir.path()).await, (0, 2, 2));
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", dir.path().join("pipeline.db").display()))
+        .await
+        .unwrap();
+    let audits: Vec<(String, String)> = sqlx::query_as("SELECT principal, action FROM audit_records ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        audits,
+        vec![
+            ("alice".to_string(), "pipeline.create".to_string()),
+            ("bob".to_string(), "pipeline.delete".to_string()),
+        ]
+    );
+    let events: Vec<String> = sqlx::query_scalar("SELECT event_type FROM domain_events ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(events, vec!["PipelineCreated", "PipelineDeleted"]);
+}
+
+#[tokio::test]
+async fn test_records_carry_the_run_correlation_id() {
+    let dir = TempDir::new().unwrap();
+    let run: CorrelationId = "run-1".parse().unwrap();
+    let repository = repository(dir.path())
+        .await
+        .in_namespace("team-a".parse().unwrap())
+        .with_correlation_id(run.clone());
+    let pipeline = pipeline("nightly-backup");
+
+    let mut work = repository.begin().await.unwrap();
+    work.record_audit(&AuditRecord::new("alice", "pipeline.create", ""))
+        .await
+        .unwrap();
+    work.record_audit(&AuditRecord::new("alice", "session.issue", "").with_correlation("request-7".parse().unwrap()))
+        .await
+        .unwrap();
+    work.record_event(&created_event(&pipeline)).await.unwrap();
+    work.commit().await.unwrap();
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", dir.path().join("pipeline.db").display()))
+        .await
+        .unwrap();
+    let audits: Vec<String> = sqlx::query_scalar("SELECT correlation_id FROM audit_records ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(audits, vec!["run-1", "request-7"], "a record's own ID takes precedence");
+    let event: String = sqlx::query_scalar("SELECT correlation_id FROM domain_events")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(event, run.as_str());
+}
@@ -34,11 +34,22 @@
 pub mod parser;
 pub mod validator;
 
-pub use parser::{parse_cli, Cli, Commands};
-pub use validator::{ParseError, SecureArgParser};
+pub use parser::{
+    parse_cli, Cli, Commands, DbAction, JobsAction, PipelineAction, RoleAction, SessionAction, VectorsAction,
+};
+pub use validator::{ParseError, PassphrasePolicy, SecureArgParser};
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use adaptive_pipeline_domain::entities::SecurityLevel;
+use adaptive_pipeline_domain::value_objects::{
+    ByteRange, ChecksumAlgorithm, ChunkSize, DefinitionFormat, ExecutionTopology, FileMode, GraphFormat, JobPriority,
+    OverwritePolicy, PathFilter, SecurityPolicy, WorkerCount,
+};
+
+use crate::platform::CoreSelection;
+
 /// Validated CLI configuration
 ///
 /// This structure holds all CLI arguments after security validation.
@@ -50,8 +61,15 @@ pub struct ValidatedCli {
     pub config: Option<PathBuf>,
     pub cpu_threads: Option<usize>,
     pub io_threads: Option<usize>,
+    pub network_io_threads: Option<usize>,
     pub storage_type: Option<String>,
-    pub channel_depth: usize,
+    pub channel_depth: Option<usize>,
+    pub memory_limit: Option<usize>,
+    pub inflight_window: Option<usize>,
+    pub pin_workers: Option<CoreSelection>,
+    pub namespace: String,
+    pub daemonize: bool,
+    pub pid_file: Option<PathBuf>,
 }
 
 /// Validated command variants
@@ -60,27 +78,77 @@ pub enum ValidatedCommand {
     Process {
         input: PathBuf,
         output: PathBuf,
+        /// Set by --recursive: `input` and `output` are directories and the
+        /// filter selects the files under `input`
+        directory: Option<PathFilter>,
         pipeline: String,
-        chunk_size_mb: Option<usize>,
-        workers: Option<usize>,
+        chunk_size: Option<ChunkSize>,
+        workers: Option<WorkerCount>,
+        manifest: bool,
+        signing_key: Option<PathBuf>,
+        idempotency_key: Option<String>,
+        stage_timeout_secs: Option<u64>,
+        chunk_timeout_secs: Option<u64>,
+        max_worker_restarts: u32,
+        direct_io: bool,
+        checksum_offload: bool,
+        overwrite_policy: OverwritePolicy,
+        output_mode: Option<FileMode>,
+        priority: JobPriority,
+        password_prompt: bool,
+        passphrase_policy: PassphrasePolicy,
     },
     Create {
         name: String,
         stages: String,
         output: Option<PathBuf>,
+        topology: Option<ExecutionTopology>,
+        checksum: ChecksumAlgorithm,
+        security_policy: SecurityPolicy,
+    },
+    List {
+        usage: bool,
     },
-    List,
     Show {
         pipeline: String,
+        graph: Option<GraphFormat>,
+        plan_input: Option<PathBuf>,
+    },
+    Estimate {
+        pipeline: String,
+        size: u64,
+        json: bool,
+    },
+    Audit {
+        json: bool,
     },
     Delete {
         pipeline: String,
         force: bool,
     },
+    PipelineExport {
+        pipeline: String,
+        format: Option<DefinitionFormat>,
+        output: Option<PathBuf>,
+    },
+    PipelineImport {
+        file: PathBuf,
+        format: Option<DefinitionFormat>,
+        name: Option<String>,
+    },
     Benchmark {
         file: Option<PathBuf>,
         size_mb: usize,
         iterations: usize,
+        save: Option<PathBuf>,
+        compare: Option<PathBuf>,
+        throughput_threshold: f64,
+        duration_threshold: f64,
+        /// Chunk sizes to run `pipeline` at, instead of the simulated matrix
+        sweep_chunk_sizes: Option<Vec<ChunkSize>>,
+        pipeline: Option<String>,
+        /// Pin the pipeline to the fastest swept chunk size
+        apply: bool,
     },
     Validate {
         config: PathBuf,
@@ -88,18 +156,155 @@ pub enum ValidatedCommand {
     ValidateFile {
         file: PathBuf,
         full: bool,
+        json: bool,
+    },
+    Inspect {
+        file: PathBuf,
+        json: bool,
+    },
+    Ls {
+        archive: PathBuf,
+        filter: PathFilter,
+    },
+    VerifyManifest {
+        manifest: PathBuf,
+        file: Option<PathBuf>,
+        public_key: Option<String>,
+    },
+    Scrub {
+        catalog: PathBuf,
+        /// Chunks per archive, or `None` to check every chunk
+        sample: Option<u32>,
+        password_prompt: bool,
+        json: bool,
+    },
+    Replicate {
+        catalog: PathBuf,
+        to: String,
+        json: bool,
     },
     Restore {
         input: PathBuf,
         output_dir: Option<PathBuf>,
         mkdir: bool,
-        overwrite: bool,
+        overwrite_policy: OverwritePolicy,
+        staging_dir: Option<PathBuf>,
+        trust_archive_paths: bool,
+        output_mode: Option<FileMode>,
+        dir_mode: Option<FileMode>,
+        priority: JobPriority,
+        report: bool,
+        password_prompt: bool,
+        range: Option<ByteRange>,
+        workers: Option<WorkerCount>,
+    },
+    Mount {
+        archive: PathBuf,
+        mountpoint: PathBuf,
+        password_prompt: bool,
+        cache_size: Option<u64>,
+    },
+    ExportTar {
+        inputs: Vec<PathBuf>,
+        output: Option<PathBuf>,
+    },
+    ImportTar {
+        input: PathBuf,
+        output_dir: PathBuf,
+        pipeline: String,
+        chunk_size: Option<ChunkSize>,
+        workers: Option<WorkerCount>,
+        overwrite_policy: OverwritePolicy,
+        priority: JobPriority,
+    },
+    Serve {
+        address: SocketAddr,
+        max_jobs: usize,
+        root: Option<PathBuf>,
+        allow_anonymous: bool,
+    },
+    Daemon {
+        max_jobs: Option<usize>,
+    },
+    Submit {
+        input: PathBuf,
+        pipeline: String,
+        output: PathBuf,
+        workers: Option<WorkerCount>,
+    },
+    JobsList {
+        json: bool,
+    },
+    JobsStatus {
+        id: String,
+        json: bool,
+    },
+    JobsCancel {
+        id: String,
+    },
+    ProcessBatch {
+        inputs: Vec<PathBuf>,
+        output_dir: Option<PathBuf>,
+        pipeline: Option<String>,
+        retry_failed: Option<PathBuf>,
+        failures: Option<PathBuf>,
+        chunk_size: Option<ChunkSize>,
+        workers: Option<WorkerCount>,
+        overwrite_policy: OverwritePolicy,
+        priority: JobPriority,
     },
     Compare {
         original: PathBuf,
         adapipe: PathBuf,
         detailed: bool,
     },
+    CompareArchives {
+        adapipe: PathBuf,
+        other: PathBuf,
+        detailed: bool,
+    },
+    Cleanup {
+        dry_run: bool,
+    },
+    RoleList,
+    RoleInit,
+    RoleAssign {
+        principal: String,
+        role: String,
+    },
+    RoleRevoke {
+        principal: String,
+    },
+    SessionIssue {
+        user: Option<String>,
+        ttl_secs: Option<u64>,
+    },
+    SessionList,
+    SessionRevoke {
+        id: String,
+    },
+    DbBackup {
+        path: PathBuf,
+    },
+    DbRestore {
+        path: PathBuf,
+        force: bool,
+    },
+    VectorsGenerate {
+        output: Option<PathBuf>,
+    },
+    VectorsVerify {
+        input: PathBuf,
+    },
+    SelfTest {
+        json: bool,
+    },
+    Capabilities {
+        json: bool,
+    },
+    ExitCodes {
+        json: bool,
+    },
 }
 
 /// Parse and validate CLI arguments
@@ -142,8 +347,16 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
         None
     };
 
+    // The pid file is created by the daemon, so only the string is checked
+    let pid_file = if let Some(ref path) = cli.pid_file {
+        SecureArgParser::validate_argument(&path.to_string_lossy())?;
+        Some(path.clone())
+    } else {
+        None
+    };
+
     // Validate channel depth
-    if cli.channel_depth == 0 {
+    if cli.channel_depth == Some(0) {
         return Err(ParseError::InvalidValue {
             arg: "channel-depth".to_string(),
             reason: "must be greater than 0".to_string(),
@@ -170,14 +383,71 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
         }
     }
 
+    // Validate network I/O threads if specified
+    if let Some(threads) = cli.network_io_threads {
+        if threads == 0 || threads > 256 {
+            return Err(ParseError::InvalidValue {
+                arg: "network-io-threads".to_string(),
+                reason: "must be between 1 and 256".to_string(),
+            });
+        }
+    }
+
+    // Validate memory limit if specified
+    let memory_limit = match cli.memory_limit {
+        Some(ref limit) => {
+            let bytes = SecureArgParser::validate_byte_size("memory-limit", limit)?;
+            if bytes == 0 {
+                return Err(ParseError::InvalidValue {
+                    arg: "memory-limit".to_string(),
+                    reason: "must be greater than zero".to_string(),
+                });
+            }
+            Some(usize::try_from(bytes).unwrap_or(usize::MAX))
+        }
+        None => None,
+    };
+
+    // Validate in-flight window if specified
+    let inflight_window = match cli.inflight_window {
+        Some(ref window) => {
+            let bytes = SecureArgParser::validate_byte_size("inflight-window", window)?;
+            if bytes == 0 {
+                return Err(ParseError::InvalidValue {
+                    arg: "inflight-window".to_string(),
+                    reason: "must be greater than zero".to_string(),
+                });
+            }
+            Some(usize::try_from(bytes).unwrap_or(usize::MAX))
+        }
+        None => None,
+    };
+
     // Validate command-specific arguments
     let command = match cli.command {
         Commands::Process {
             input,
             output,
+            recursive,
+            include,
+            exclude,
             pipeline,
+            chunk_size,
             chunk_size_mb,
             workers,
+            manifest,
+            signing_key,
+            idempotency_key,
+            stage_timeout_secs,
+            chunk_timeout_secs,
+            max_worker_restarts,
+            direct_io,
+            checksum_offload,
+            if_exists,
+            output_mode,
+            priority,
+            password_prompt,
+            min_passphrase_score,
         } => {
             // Validate input file exists
             let validated_input = SecureArgParser::validate_path(&input.to_string_lossy())?;
@@ -185,38 +455,125 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
             // Output file doesn't exist yet - validate string only
             SecureArgParser::validate_argument(&output.to_string_lossy())?;
 
-            // Validate pipeline name (no dangerous patterns)
-            SecureArgParser::validate_argument(&pipeline)?;
-
-            // Validate chunk size if specified
-            if let Some(size) = chunk_size_mb {
-                if size == 0 || size > 1024 {
+            // Directories are only processed with --recursive, which needs one
+            let directory = if recursive {
+                if !validated_input.is_dir() {
                     return Err(ParseError::InvalidValue {
-                        arg: "chunk-size-mb".to_string(),
-                        reason: "must be between 1 and 1024 MB".to_string(),
+                        arg: "input".to_string(),
+                        reason: format!("--recursive needs a directory, got {}", validated_input.display()),
                     });
                 }
+                for glob in include.iter().chain(&exclude) {
+                    SecureArgParser::validate_argument(glob)?;
+                }
+                Some(PathFilter::new(include, exclude).map_err(|e| ParseError::InvalidValue {
+                    arg: "include/exclude".to_string(),
+                    reason: e.to_string(),
+                })?)
+            } else if validated_input.is_dir() {
+                return Err(ParseError::InvalidValue {
+                    arg: "input".to_string(),
+                    reason: format!(
+                        "{} is a directory; add --recursive to process the files under it",
+                        validated_input.display()
+                    ),
+                });
+            } else {
+                None
+            };
+
+            // Validate pipeline name (no dangerous patterns)
+            SecureArgParser::validate_argument(&pipeline)?;
+
+            // Validate chunk size if specified (--chunk-size-mb is the
+            // deprecated MB-only spelling)
+            let chunk_size = match (chunk_size, chunk_size_mb) {
+                (Some(size), _) => Some(SecureArgParser::validate_chunk_size("chunk-size", &size)?),
+                (None, Some(mb)) => Some(SecureArgParser::validate_chunk_size(
+                    "chunk-size-mb",
+                    &format!("{}MiB", mb),
+                )?),
+                (None, None) => None,
+            };
+
+            // Validate workers if specified; `auto` leaves them adaptive
+            let workers = match workers {
+                Some(w) => SecureArgParser::validate_worker_count("workers", &w)?,
+                None => None,
+            };
+
+            let signing_key = if let Some(ref path) = signing_key {
+                Some(SecureArgParser::validate_path(&path.to_string_lossy())?)
+            } else {
+                None
+            };
+
+            if let Some(ref key) = idempotency_key {
+                SecureArgParser::validate_argument(key)?;
             }
 
-            // Validate workers if specified
-            if let Some(w) = workers {
-                if w == 0 || w > 128 {
+            // Validate stage deadlines if specified
+            for (arg, secs) in [
+                ("stage-timeout-secs", stage_timeout_secs),
+                ("chunk-timeout-secs", chunk_timeout_secs),
+            ] {
+                if secs == Some(0) {
                     return Err(ParseError::InvalidValue {
-                        arg: "workers".to_string(),
-                        reason: "must be between 1 and 128".to_string(),
+                        arg: arg.to_string(),
+                        reason: "must be at least 1 second".to_string(),
                     });
                 }
             }
 
+            if max_worker_restarts > 1000 {
+                return Err(ParseError::InvalidValue {
+                    arg: "max-worker-restarts".to_string(),
+                    reason: "must be at most 1000".to_string(),
+                });
+            }
+
             ValidatedCommand::Process {
                 input: validated_input,
                 output,
+                directory,
                 pipeline,
-                chunk_size_mb,
+                chunk_size,
                 workers,
+                manifest: manifest || signing_key.is_some(),
+                signing_key,
+                idempotency_key,
+                stage_timeout_secs,
+                chunk_timeout_secs,
+                max_worker_restarts,
+                direct_io,
+                checksum_offload,
+                overwrite_policy: match if_exists {
+                    Some(policy) => SecureArgParser::validate_overwrite_policy("if-exists", &policy)?,
+                    None => OverwritePolicy::default(),
+                },
+                output_mode: output_mode
+                    .map(|mode| SecureArgParser::validate_file_mode("output-mode", &mode))
+                    .transpose()?,
+                priority: match priority {
+                    Some(priority) => SecureArgParser::validate_job_priority("priority", &priority)?,
+                    None => JobPriority::Normal,
+                },
+                password_prompt,
+                passphrase_policy: match min_passphrase_score {
+                    Some(score) => SecureArgParser::validate_passphrase_policy("min-passphrase-score", &score)?,
+                    None => PassphrasePolicy::default(),
+                },
             }
         }
-        Commands::Create { name, stages, output } => {
+        Commands::Create {
+            name,
+            stages,
+            output,
+            topology,
+            checksum,
+            security_level,
+            require_permissions,
+        } => {
             SecureArgParser::validate_argument(&name)?;
             SecureArgParser::validate_argument(&stages)?;
 
@@ -224,21 +581,132 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
                 SecureArgParser::validate_argument(&path.to_string_lossy())?;
             }
 
-            ValidatedCommand::Create { name, stages, output }
+            let topology = topology
+                .map(|name| {
+                    name.parse::<ExecutionTopology>().map_err(|_| ParseError::InvalidValue {
+                        arg: "topology".to_string(),
+                        reason: format!("unknown topology '{}' (expected chunk-parallel or stage-parallel)", name),
+                    })
+                })
+                .transpose()?;
+
+            let checksum = match checksum {
+                Some(name) => name.parse::<ChecksumAlgorithm>().map_err(|_| ParseError::InvalidValue {
+                    arg: "checksum".to_string(),
+                    reason: format!("unknown checksum algorithm '{}' (expected sha256 or blake3)", name),
+                })?,
+                None => ChecksumAlgorithm::default(),
+            };
+
+            let minimum_level = match security_level {
+                Some(level) => level.parse().map_err(|_| ParseError::InvalidValue {
+                    arg: "security-level".to_string(),
+                    reason: format!(
+                        "unknown security level '{}' (expected public, internal, medium, confidential, secret or \
+                         top-secret)",
+                        level
+                    ),
+                })?,
+                None => SecurityLevel::Public,
+            };
+            let required_permissions = match require_permissions {
+                Some(list) => SecurityPolicy::parse_permissions(&list).map_err(|e| ParseError::InvalidValue {
+                    arg: "require-permissions".to_string(),
+                    reason: e.to_string(),
+                })?,
+                None => Vec::new(),
+            };
+
+            ValidatedCommand::Create {
+                name,
+                stages,
+                output,
+                topology,
+                checksum,
+                security_policy: SecurityPolicy::new(minimum_level).with_required_permissions(required_permissions),
+            }
+        }
+        Commands::List { usage } => ValidatedCommand::List { usage },
+        Commands::Show {
+            pipeline,
+            graph,
+            plan,
+            input,
+        } => {
+            SecureArgParser::validate_argument(&pipeline)?;
+            let graph = graph
+                .map(|format| {
+                    format.parse::<GraphFormat>().map_err(|_| ParseError::InvalidValue {
+                        arg: "graph".to_string(),
+                        reason: format!("unknown format '{}' (expected dot or mermaid)", format),
+                    })
+                })
+                .transpose()?;
+            // --plan requires --input, so the input alone selects the plan
+            let plan_input = match input.filter(|_| plan) {
+                Some(input) => Some(SecureArgParser::validate_path(&input.to_string_lossy())?),
+                None => None,
+            };
+            ValidatedCommand::Show {
+                pipeline,
+                graph,
+                plan_input,
+            }
         }
-        Commands::List => ValidatedCommand::List,
-        Commands::Show { pipeline } => {
+        Commands::Estimate { pipeline, size, json } => {
             SecureArgParser::validate_argument(&pipeline)?;
-            ValidatedCommand::Show { pipeline }
+            let size = SecureArgParser::validate_byte_size("size", &size)?;
+            ValidatedCommand::Estimate { pipeline, size, json }
         }
+        Commands::Audit { json } => ValidatedCommand::Audit { json },
         Commands::Delete { pipeline, force } => {
             SecureArgParser::validate_argument(&pipeline)?;
             ValidatedCommand::Delete { pipeline, force }
         }
+        Commands::Pipeline { action } => match action {
+            PipelineAction::Export {
+                pipeline,
+                format,
+                output,
+            } => {
+                SecureArgParser::validate_argument(&pipeline)?;
+                let format = match format {
+                    Some(f) => Some(SecureArgParser::validate_definition_format("format", &f)?),
+                    None => None,
+                };
+                // Output file doesn't exist yet - validate string only
+                if let Some(ref path) = output {
+                    SecureArgParser::validate_argument(&path.to_string_lossy())?;
+                }
+                ValidatedCommand::PipelineExport {
+                    pipeline,
+                    format,
+                    output,
+                }
+            }
+            PipelineAction::Import { file, format, name } => {
+                let file = SecureArgParser::validate_path(&file.to_string_lossy())?;
+                let format = match format {
+                    Some(f) => Some(SecureArgParser::validate_definition_format("format", &f)?),
+                    None => None,
+                };
+                if let Some(ref name) = name {
+                    SecureArgParser::validate_argument(name)?;
+                }
+                ValidatedCommand::PipelineImport { file, format, name }
+            }
+        },
         Commands::Benchmark {
             file,
             size_mb,
             iterations,
+            save,
+            compare,
+            throughput_threshold,
+            duration_threshold,
+            sweep_chunk_size,
+            pipeline,
+            apply,
         } => {
             let validated_file = if let Some(ref path) = file {
                 Some(SecureArgParser::validate_path(&path.to_string_lossy())?)
@@ -260,10 +728,44 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
                 });
             }
 
+            // The baseline to save doesn't exist yet - validate string only
+            if let Some(ref path) = save {
+                SecureArgParser::validate_argument(&path.to_string_lossy())?;
+            }
+            let compare = compare
+                .map(|path| SecureArgParser::validate_path(&path.to_string_lossy()))
+                .transpose()?;
+
+            for (arg, pct) in [
+                ("throughput-threshold", throughput_threshold),
+                ("duration-threshold", duration_threshold),
+            ] {
+                if !pct.is_finite() || pct < 0.0 {
+                    return Err(ParseError::InvalidValue {
+                        arg: arg.to_string(),
+                        reason: "must be a non-negative percentage".to_string(),
+                    });
+                }
+            }
+
+            let sweep_chunk_sizes = sweep_chunk_size
+                .map(|range| SecureArgParser::validate_chunk_size_range("sweep-chunk-size", &range))
+                .transpose()?;
+            if let Some(ref name) = pipeline {
+                SecureArgParser::validate_argument(name)?;
+            }
+
             ValidatedCommand::Benchmark {
                 file: validated_file,
                 size_mb,
                 iterations,
+                save,
+                compare,
+                throughput_threshold,
+                duration_threshold,
+                sweep_chunk_sizes,
+                pipeline,
+                apply,
             }
         }
         Commands::Validate { config } => {
@@ -272,18 +774,105 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
                 config: validated_config,
             }
         }
-        Commands::ValidateFile { file, full } => {
+        Commands::ValidateFile { file, full, json } => {
             let validated_file = SecureArgParser::validate_path(&file.to_string_lossy())?;
             ValidatedCommand::ValidateFile {
                 file: validated_file,
                 full,
+                json,
+            }
+        }
+        Commands::VerifyManifest {
+            manifest,
+            file,
+            public_key,
+        } => {
+            let validated_manifest = SecureArgParser::validate_path(&manifest.to_string_lossy())?;
+            let validated_file = if let Some(ref path) = file {
+                Some(SecureArgParser::validate_path(&path.to_string_lossy())?)
+            } else {
+                None
+            };
+            if let Some(ref key) = public_key {
+                SecureArgParser::validate_argument(key)?;
+            }
+            ValidatedCommand::VerifyManifest {
+                manifest: validated_manifest,
+                file: validated_file,
+                public_key,
+            }
+        }
+        Commands::Scrub {
+            catalog,
+            sample,
+            full,
+            password_prompt,
+            json,
+        } => {
+            let validated_catalog = SecureArgParser::validate_path(&catalog.to_string_lossy())?;
+            if sample == 0 {
+                return Err(ParseError::InvalidValue {
+                    arg: "sample".to_string(),
+                    reason: "must check at least one chunk per archive".to_string(),
+                });
+            }
+            ValidatedCommand::Scrub {
+                catalog: validated_catalog,
+                sample: (!full).then_some(sample),
+                password_prompt,
+                json,
+            }
+        }
+        Commands::Replicate { catalog, to, json } => {
+            let validated_catalog = SecureArgParser::validate_path(&catalog.to_string_lossy())?;
+            // The target directory may not exist yet
+            SecureArgParser::validate_argument(&to)?;
+            ValidatedCommand::Replicate {
+                catalog: validated_catalog,
+                to,
+                json,
+            }
+        }
+        Commands::Inspect { file, json } => {
+            let validated_file = SecureArgParser::validate_path(&file.to_string_lossy())?;
+            ValidatedCommand::Inspect {
+                file: validated_file,
+                json,
+            }
+        }
+        Commands::Ls {
+            archive,
+            include,
+            exclude,
+        } => {
+            let validated_archive = SecureArgParser::validate_path(&archive.to_string_lossy())?;
+            for glob in include.iter().chain(&exclude) {
+                SecureArgParser::validate_argument(glob)?;
+            }
+            let filter = PathFilter::new(include, exclude).map_err(|e| ParseError::InvalidValue {
+                arg: "include/exclude".to_string(),
+                reason: e.to_string(),
+            })?;
+            ValidatedCommand::Ls {
+                archive: validated_archive,
+                filter,
             }
         }
         Commands::Restore {
             input,
             output_dir,
             mkdir,
+            if_exists,
             overwrite,
+            staging_dir,
+            trust_archive_paths,
+            output_mode,
+            dir_mode,
+            priority,
+            report,
+            password_prompt,
+            range,
+            workers,
         } => {
             let validated_input = SecureArgParser::validate_path(&input.to_string_lossy())?;
 
@@ -295,26 +884,337 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
                 None
             };
 
+            let validated_staging_dir = match staging_dir {
+                Some(path) => Some(SecureArgParser::validate_path(&path.to_string_lossy())?),
+                None => None,
+            };
+
             ValidatedCommand::Restore {
                 input: validated_input,
                 output_dir: validated_output_dir,
                 mkdir,
-                overwrite,
+                // --overwrite is the deprecated spelling of --if-exists overwrite
+                overwrite_policy: match if_exists {
+                    Some(policy) => SecureArgParser::validate_overwrite_policy("if-exists", &policy)?,
+                    None if overwrite => OverwritePolicy::Overwrite,
+                    None => OverwritePolicy::default(),
+                },
+                staging_dir: validated_staging_dir,
+                trust_archive_paths,
+                output_mode: output_mode
+                    .map(|mode| SecureArgParser::validate_file_mode("output-mode", &mode))
+                    .transpose()?,
+                dir_mode: dir_mode
+                    .map(|mode| SecureArgParser::validate_file_mode("dir-mode", &mode))
+                    .transpose()?,
+                // Someone is usually waiting on a restore
+                priority: match priority {
+                    Some(priority) => SecureArgParser::validate_job_priority("priority", &priority)?,
+                    None => JobPriority::Interactive,
+                },
+                report,
+                password_prompt,
+                range: range
+                    .map(|range| SecureArgParser::validate_byte_range("range", &range))
+                    .transpose()?,
+                workers: match workers {
+                    Some(w) => SecureArgParser::validate_worker_count("workers", &w)?,
+                    None => None,
+                },
+            }
+        }
+        Commands::Mount {
+            archive,
+            mountpoint,
+            password_prompt,
+            cache_size,
+        } => {
+            let validated_archive = SecureArgParser::validate_path(&archive.to_string_lossy())?;
+            let validated_mountpoint = SecureArgParser::validate_path(&mountpoint.to_string_lossy())?;
+            if !validated_mountpoint.is_dir() {
+                return Err(ParseError::InvalidValue {
+                    arg: "mountpoint".to_string(),
+                    reason: format!("{} is not a directory", validated_mountpoint.display()),
+                });
+            }
+            ValidatedCommand::Mount {
+                archive: validated_archive,
+                mountpoint: validated_mountpoint,
+                password_prompt,
+                cache_size: cache_size
+                    .map(|size| SecureArgParser::validate_byte_size("cache-size", &size))
+                    .transpose()?,
+            }
+        }
+        Commands::ExportTar { inputs, output } => {
+            let inputs = inputs
+                .iter()
+                .map(|input| SecureArgParser::validate_path(&input.to_string_lossy()))
+                .collect::<Result<Vec<_>, _>>()?;
+            match output {
+                // Output file doesn't exist yet - validate string only
+                Some(ref path) => SecureArgParser::validate_argument(&path.to_string_lossy())?,
+                // Only a single archive has a default tar next to it
+                None if inputs.len() > 1 => return Err(ParseError::MissingArgument("output".to_string())),
+                None => {}
+            }
+            ValidatedCommand::ExportTar { inputs, output }
+        }
+        Commands::ImportTar {
+            input,
+            output_dir,
+            pipeline,
+            chunk_size,
+            workers,
+            if_exists,
+            priority,
+        } => {
+            // `-` reads the tar from standard input
+            let input = if input.as_os_str() == "-" {
+                input
+            } else {
+                SecureArgParser::validate_path(&input.to_string_lossy())?
+            };
+            // Output dir might not exist yet
+            SecureArgParser::validate_argument(&output_dir.to_string_lossy())?;
+            SecureArgParser::validate_argument(&pipeline)?;
+
+            ValidatedCommand::ImportTar {
+                input,
+                output_dir,
+                pipeline,
+                chunk_size: chunk_size
+                    .map(|size| SecureArgParser::validate_chunk_size("chunk-size", &size))
+                    .transpose()?,
+                workers: match workers {
+                    Some(w) => SecureArgParser::validate_worker_count("workers", &w)?,
+                    None => None,
+                },
+                overwrite_policy: match if_exists {
+                    Some(policy) => SecureArgParser::validate_overwrite_policy("if-exists", &policy)?,
+                    None => OverwritePolicy::default(),
+                },
+                priority: match priority {
+                    Some(priority) => SecureArgParser::validate_job_priority("priority", &priority)?,
+                    None => JobPriority::Normal,
+                },
+            }
+        }
+        Commands::Serve {
+            port,
+            bind,
+            max_jobs,
+            root,
+            allow_anonymous,
+        } => {
+            if max_jobs == 0 {
+                return Err(ParseError::InvalidValue {
+                    arg: "max-jobs".to_string(),
+                    reason: "must be at least 1".to_string(),
+                });
+            }
+            // Request paths are checked against the canonical root
+            let root = match root {
+                Some(root) => {
+                    let root = SecureArgParser::validate_path(&root.to_string_lossy())?;
+                    if !root.is_dir() {
+                        return Err(ParseError::InvalidPath(format!(
+                            "{} is not a directory",
+                            root.display()
+                        )));
+                    }
+                    Some(root)
+                }
+                None => None,
+            };
+            ValidatedCommand::Serve {
+                address: SocketAddr::new(bind, port),
+                max_jobs,
+                root,
+                allow_anonymous,
+            }
+        }
+        Commands::Daemon { max_jobs } => {
+            if max_jobs == Some(0) {
+                return Err(ParseError::InvalidValue {
+                    arg: "max-jobs".to_string(),
+                    reason: "must be at least 1".to_string(),
+                });
+            }
+            ValidatedCommand::Daemon { max_jobs }
+        }
+        Commands::Submit {
+            input,
+            pipeline,
+            output,
+            workers,
+        } => {
+            // The daemon runs from its own directory, so both paths are made
+            // absolute here
+            let input = SecureArgParser::validate_path(&input.to_string_lossy())?;
+            SecureArgParser::validate_argument(&pipeline)?;
+            let output = match output {
+                Some(output) => {
+                    SecureArgParser::validate_argument(&output.to_string_lossy())?;
+                    std::path::absolute(&output).map_err(|e| ParseError::InvalidValue {
+                        arg: "output".to_string(),
+                        reason: e.to_string(),
+                    })?
+                }
+                None => {
+                    let mut output = input.clone().into_os_string();
+                    output.push(".adapipe");
+                    PathBuf::from(output)
+                }
+            };
+            let workers = match workers {
+                Some(w) => SecureArgParser::validate_worker_count("workers", &w)?,
+                None => None,
+            };
+            ValidatedCommand::Submit {
+                input,
+                pipeline,
+                output,
+                workers,
+            }
+        }
+        Commands::Jobs { action } => match action {
+            JobsAction::List { json } => ValidatedCommand::JobsList { json },
+            JobsAction::Status { id, json } => {
+                SecureArgParser::validate_argument(&id)?;
+                ValidatedCommand::JobsStatus { id, json }
+            }
+            JobsAction::Cancel { id } => {
+                SecureArgParser::validate_argument(&id)?;
+                ValidatedCommand::JobsCancel { id }
+            }
+        },
+        Commands::ProcessBatch {
+            inputs,
+            output_dir,
+            pipeline,
+            retry_failed,
+            failures,
+            chunk_size,
+            workers,
+            if_exists,
+            priority,
+        } => {
+            let inputs = inputs
+                .iter()
+                .map(|input| SecureArgParser::validate_path(&input.to_string_lossy()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let retry_failed = retry_failed
+                .map(|manifest| SecureArgParser::validate_path(&manifest.to_string_lossy()))
+                .transpose()?;
+            // Output dir and retry manifest might not exist yet
+            for path in output_dir.iter().chain(failures.iter()) {
+                SecureArgParser::validate_argument(&path.to_string_lossy())?;
+            }
+            if let Some(ref pipeline) = pipeline {
+                SecureArgParser::validate_argument(pipeline)?;
+            }
+
+            ValidatedCommand::ProcessBatch {
+                inputs,
+                output_dir,
+                pipeline,
+                retry_failed,
+                failures,
+                chunk_size: chunk_size
+                    .map(|size| SecureArgParser::validate_chunk_size("chunk-size", &size))
+                    .transpose()?,
+                workers: match workers {
+                    Some(w) => SecureArgParser::validate_worker_count("workers", &w)?,
+                    None => None,
+                },
+                overwrite_policy: match if_exists {
+                    Some(policy) => SecureArgParser::validate_overwrite_policy("if-exists", &policy)?,
+                    None => OverwritePolicy::default(),
+                },
+                priority: match priority {
+                    Some(priority) => SecureArgParser::validate_job_priority("priority", &priority)?,
+                    None => JobPriority::Normal,
+                },
             }
         }
         Commands::Compare {
             original,
             adapipe,
+            other,
             detailed,
         } => {
-            let validated_original = SecureArgParser::validate_path(&original.to_string_lossy())?;
             let validated_adapipe = SecureArgParser::validate_path(&adapipe.to_string_lossy())?;
-            ValidatedCommand::Compare {
-                original: validated_original,
-                adapipe: validated_adapipe,
-                detailed,
+            match (original, other) {
+                (_, Some(other)) => ValidatedCommand::CompareArchives {
+                    adapipe: validated_adapipe,
+                    other: SecureArgParser::validate_path(&other.to_string_lossy())?,
+                    detailed,
+                },
+                (Some(original), None) => ValidatedCommand::Compare {
+                    original: SecureArgParser::validate_path(&original.to_string_lossy())?,
+                    adapipe: validated_adapipe,
+                    detailed,
+                },
+                (None, None) => return Err(ParseError::MissingArgument("original".to_string())),
             }
         }
+        Commands::Cleanup { dry_run } => ValidatedCommand::Cleanup { dry_run },
+        Commands::Role { action } => match action {
+            RoleAction::List => ValidatedCommand::RoleList,
+            RoleAction::Init => ValidatedCommand::RoleInit,
+            RoleAction::Assign { principal, role } => {
+                SecureArgParser::validate_argument(&principal)?;
+                ValidatedCommand::RoleAssign { principal, role }
+            }
+            RoleAction::Revoke { principal } => {
+                SecureArgParser::validate_argument(&principal)?;
+                ValidatedCommand::RoleRevoke { principal }
+            }
+        },
+        Commands::Session { action } => match action {
+            SessionAction::Issue { user, ttl } => {
+                if let Some(ref user) = user {
+                    SecureArgParser::validate_argument(user)?;
+                }
+                if ttl == Some(0) {
+                    return Err(ParseError::InvalidValue {
+                        arg: "ttl".to_string(),
+                        reason: "must be at least 1 second".to_string(),
+                    });
+                }
+                ValidatedCommand::SessionIssue { user, ttl_secs: ttl }
+            }
+            SessionAction::List => ValidatedCommand::SessionList,
+            SessionAction::Revoke { id } => {
+                SecureArgParser::validate_argument(&id)?;
+                ValidatedCommand::SessionRevoke { id }
+            }
+        },
+        Commands::Db { action } => match action {
+            DbAction::Backup { path } => {
+                SecureArgParser::validate_argument(&path.to_string_lossy())?;
+                ValidatedCommand::DbBackup { path }
+            }
+            DbAction::Restore { path, force } => ValidatedCommand::DbRestore {
+                path: SecureArgParser::validate_path(&path.to_string_lossy())?,
+                force,
+            },
+        },
+        Commands::Vectors { action } => match action {
+            VectorsAction::Generate { output } => {
+                if let Some(ref path) = output {
+                    SecureArgParser::validate_argument(&path.to_string_lossy())?;
+                }
+                ValidatedCommand::VectorsGenerate { output }
+            }
+            VectorsAction::Verify { input } => ValidatedCommand::VectorsVerify {
+                input: SecureArgParser::validate_path(&input.to_string_lossy())?,
+            },
+        },
+        Commands::SelfTest { json } => ValidatedCommand::SelfTest { json },
+        Commands::Capabilities { json } => ValidatedCommand::Capabilities { json },
+        Commands::ExitCodes { json } => ValidatedCommand::ExitCodes { json },
     };
 
     Ok(ValidatedCli {
@@ -323,7 +1223,14 @@ fn validate_cli(cli: Cli) -> Result<ValidatedCli, ParseError> {
         config,
         cpu_threads: cli.cpu_threads,
         io_threads: cli.io_threads,
+        network_io_threads: cli.network_io_threads,
         storage_type: cli.storage_type,
         channel_depth: cli.channel_depth,
+        memory_limit,
+        inflight_window,
+        pin_workers: cli.pin_workers,
+        namespace: cli.namespace,
+        daemonize: cli.daemonize,
+        pid_file,
     })
 }
@@ -13,12 +13,40 @@
 //! Security validation happens in the validator module after parsing.
 
 use clap::{Parser, Subcommand};
+use std::net::IpAddr;
 use std::path::PathBuf;
 
+use crate::platform::CoreSelection;
+
+/// Stage names accepted by `create --stages`
+#[cfg(not(feature = "fips"))]
+const STAGES_HELP: &str = "Pipeline stages, comma-separated.
+
+Stage types: compression, encryption, checksum, passthrough, base64, pii_masking, tee, debug
+Algorithms: brotli, gzip, zstd, lz4, xz, aes256gcm, aes128gcm, chacha20poly1305
+Explicit form: compression:<algorithm>, encryption:<algorithm>";
+
+/// Stage names accepted by `create --stages` in a FIPS-mode build, which
+/// only offers FIPS-approved cryptography
+#[cfg(feature = "fips")]
+const STAGES_HELP: &str = "Pipeline stages, comma-separated.
+
+Stage types: compression, encryption, checksum, passthrough, base64, pii_masking, tee, debug
+Algorithms: brotli, gzip, zstd, lz4, xz, aes256gcm, aes128gcm
+Explicit form: compression:<algorithm>, encryption:<algorithm>
+FIPS mode: only AES-GCM encryption and SHA-2 checksums are available";
+
 /// Main CLI structure
 #[derive(Parser, Debug, Clone)]
 #[command(name = "pipeline")]
-#[command(about = concat!("Adaptive Pipeline v", env!("CARGO_PKG_VERSION")))]
+#[cfg_attr(
+    not(feature = "fips"),
+    command(about = concat!("Adaptive Pipeline v", env!("CARGO_PKG_VERSION")))
+)]
+#[cfg_attr(
+    feature = "fips",
+    command(about = concat!("Adaptive Pipeline v", env!("CARGO_PKG_VERSION"), " (FIPS mode)"))
+)]
 #[command(version)]
 pub struct Cli {
     #[command(subcommand)]
@@ -57,11 +85,24 @@ pub struct Cli {
     #[arg(long)]
     pub io_threads: Option<usize>,
 
+    /// Override the number of concurrent requests to remote sources
+    ///
+    /// Controls network I/O for HTTP and object store (s3://) sources, which
+    /// is counted separately from local I/O (--io-threads).
+    /// Default: 16
+    ///
+    /// Educational: Remote requests wait on round trips rather than a disk
+    /// queue. Sizing them apart keeps a slow remote source from holding up
+    /// local reads and writes in the same run.
+    #[arg(long)]
+    pub network_io_threads: Option<usize>,
+
     /// Specify storage device type for I/O optimization
     ///
     /// Affects default I/O thread count if --io-threads not specified.
-    /// Values: nvme (queue depth 24), ssd (12), hdd (4)
-    /// Default: auto-detect based on filesystem characteristics
+    /// Values: nvme (queue depth 24), ssd (12), hdd (4), network (16)
+    /// Default: auto-detect from the device's NVMe class or rotational flag,
+    /// or from a network filesystem type
     ///
     /// Educational: Different storage devices have different optimal queue
     /// depths. NVMe handles more concurrent I/O than SSD, which handles
@@ -72,7 +113,8 @@ pub struct Cli {
     /// Channel depth for pipeline stages (Reader → Workers → Writer)
     ///
     /// Controls backpressure in the three-stage pipeline architecture.
-    /// Default: 4
+    /// Default: as many chunks as fit in the in-flight window (see
+    /// `--inflight-window`)
     ///
     /// Educational: Lower values reduce memory usage but may cause stalls.
     /// Higher values increase buffering but consume more memory.
@@ -80,34 +122,178 @@ pub struct Cli {
     ///
     /// Example: If chunk processing = 2ms and I/O = 1ms, depth=4 keeps pipeline
     /// full.
-    #[arg(long, default_value = "4")]
-    pub channel_depth: usize,
+    #[arg(long)]
+    pub channel_depth: Option<usize>,
+
+    /// Cap the memory the resource manager hands out, with units (e.g.
+    /// 2GiB)
+    ///
+    /// Default: the container's memory limit if there is one, otherwise
+    /// 40GB
+    ///
+    /// Educational: The memory capacity bounds how much chunk data can be
+    /// in flight at once; lower it on shared hosts.
+    #[arg(long, value_name = "SIZE")]
+    pub memory_limit: Option<String>,
+
+    /// Bytes of chunk data each file may have in flight between the reader
+    /// and the workers, with units (e.g. 256MiB)
+    ///
+    /// Default: 256MiB
+    ///
+    /// Educational: The window bounds bytes rather than chunks, so huge
+    /// chunks cannot exhaust memory while small chunks still pipeline
+    /// deeply. All files together also stay within `--memory-limit`.
+    #[arg(long, value_name = "SIZE")]
+    pub inflight_window: Option<String>,
+
+    /// Pin CPU-bound worker threads to cores
+    ///
+    /// `--pin-workers` pins one worker thread to each core the process may
+    /// use; `--pin-workers=0-3,8` pins to the listed cores only.
+    /// Default: no pinning
+    ///
+    /// Educational: Pinned threads don't migrate between cores, so their
+    /// caches stay warm on busy hosts and benchmark runs vary less.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "all",
+        value_name = "CORES",
+        value_parser = CoreSelection::parse
+    )]
+    pub pin_workers: Option<CoreSelection>,
+
+    /// Namespace (tenant) whose pipelines, roles and usage are used
+    ///
+    /// Pipeline names are unique per namespace, and role assignments apply
+    /// per namespace. Lowercase letters, digits, '-' and '_'.
+    #[arg(long, global = true, default_value = "default", value_parser = parse_namespace)]
+    pub namespace: String,
+
+    /// Run in the background, detached from the terminal
+    ///
+    /// On Unix the process forks into its own session with standard streams
+    /// on /dev/null; under systemd use `Type=notify` instead, which needs no
+    /// daemonizing. On Windows the process runs as the service the Service
+    /// Control Manager started.
+    #[arg(long, global = true)]
+    pub daemonize: bool,
+
+    /// Write the daemon's process ID to this file (Unix)
+    #[arg(long, global = true, value_name = "PATH", requires = "daemonize")]
+    pub pid_file: Option<PathBuf>,
 }
 
 /// CLI subcommands
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
-    /// Process a file through a pipeline
+    /// Process a file, or with --recursive a directory tree, through a
+    /// pipeline
     Process {
-        /// Input file path
+        /// Input file path (a directory with --recursive)
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Output file path
+        /// Output file path (a directory with --recursive)
         #[arg(short, long)]
         output: PathBuf,
 
+        /// Process every file under the input directory into the same tree
+        /// under the output directory, several files at a time
+        #[arg(long, conflicts_with = "idempotency_key")]
+        recursive: bool,
+
+        /// With --recursive, only process files matching this glob
+        /// (repeatable; default: every file)
+        #[arg(long, value_name = "GLOB", requires = "recursive")]
+        include: Vec<String>,
+
+        /// With --recursive, skip files matching this glob (repeatable)
+        #[arg(long, value_name = "GLOB", requires = "recursive")]
+        exclude: Vec<String>,
+
         /// Pipeline name or ID
         #[arg(short, long)]
         pipeline: String,
 
-        /// Chunk size in MB
-        #[arg(long)]
+        /// Chunk size, with units (e.g. 4MiB, 512KB; a bare number is
+        /// bytes)
+        #[arg(long, value_name = "SIZE")]
+        chunk_size: Option<String>,
+
+        /// Chunk size in MB (deprecated: use --chunk-size)
+        #[arg(long, hide = true, conflicts_with = "chunk_size")]
         chunk_size_mb: Option<usize>,
 
-        /// Number of parallel workers
+        /// Number of parallel workers, or `auto` to size from the file
+        #[arg(long, value_name = "N|auto")]
+        workers: Option<String>,
+
+        /// Write a detached `<output>.manifest` with the completion metrics
+        #[arg(long)]
+        manifest: bool,
+
+        /// Sign the manifest with this Ed25519 PKCS#8 key (DER or PEM);
+        /// implies --manifest
+        #[arg(long, value_name = "KEY_FILE")]
+        signing_key: Option<PathBuf>,
+
+        /// Retries with the same key return the first completed run's
+        /// result instead of processing again
+        #[arg(long, value_name = "KEY")]
+        idempotency_key: Option<String>,
+
+        /// Fail a chunk if any one stage spends longer than this on it
+        /// (a stage's `timeout_ms` parameter takes precedence)
+        #[arg(long, value_name = "SECS")]
+        stage_timeout_secs: Option<u64>,
+
+        /// Fail a chunk if all of its stages together take longer than this
+        #[arg(long, value_name = "SECS")]
+        chunk_timeout_secs: Option<u64>,
+
+        /// Retry up to N chunks whose stage panicked on a replacement worker
+        /// before failing (default: fail on the first panic)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        max_worker_restarts: u32,
+
+        /// Read the input and write the output around the OS page cache
+        /// (O_DIRECT), so large runs don't evict other services' cached data
         #[arg(long)]
-        workers: Option<usize>,
+        direct_io: bool,
+
+        /// Hash the input on a separate thread as it is read, and take the
+        /// output checksum from the writer, instead of extra passes over
+        /// each file; helps when CPU is the bottleneck
+        #[arg(long)]
+        checksum_offload: bool,
+
+        /// What to do if the output exists: fail (default), overwrite, skip,
+        /// rename or if-newer
+        #[arg(long, value_name = "POLICY")]
+        if_exists: Option<String>,
+
+        /// Octal permissions of the created .adapipe file (e.g. 0640)
+        /// instead of those left by the umask
+        #[arg(long, value_name = "MODE")]
+        output_mode: Option<String>,
+
+        /// Priority for shared CPU and I/O tokens when jobs run
+        /// concurrently: interactive, normal (default) or batch
+        #[arg(long, value_name = "PRIORITY")]
+        priority: Option<String>,
+
+        /// Read a passphrase and derive the key of every encryption stage
+        /// from it with Argon2id; required by pipelines that encrypt
+        #[arg(long)]
+        password_prompt: bool,
+
+        /// Lowest passphrase strength (0-4) accepted for pipelines whose
+        /// minimum security level is confidential or above (default: 3)
+        #[arg(long, value_name = "SCORE", requires = "password_prompt")]
+        min_passphrase_score: Option<String>,
     },
 
     /// Create a new pipeline
@@ -117,21 +303,82 @@ pub enum Commands {
         name: String,
 
         /// Pipeline stages (comma-separated: compression,encryption,integrity)
-        #[arg(short, long)]
+        #[arg(short, long, long_help = STAGES_HELP)]
         stages: String,
 
         /// Save pipeline to file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// How stages are spread over workers: chunk-parallel (default) or
+        /// stage-parallel (one worker pool per stage)
+        #[arg(long, value_name = "TOPOLOGY")]
+        topology: Option<String>,
+
+        /// Hash for the whole-file checksum of processed input: sha256
+        /// (default) or blake3
+        #[arg(long, value_name = "ALGORITHM")]
+        checksum: Option<String>,
+
+        /// Lowest security level allowed to run the pipeline and restore
+        /// its output: public (default), internal, medium, confidential,
+        /// secret or top-secret
+        #[arg(long, value_name = "LEVEL")]
+        security_level: Option<String>,
+
+        /// Permissions needed to run the pipeline and restore its output
+        /// (comma-separated, e.g. decrypt,custom:finance)
+        #[arg(long, value_name = "PERMISSIONS")]
+        require_permissions: Option<String>,
     },
 
     /// List available pipelines
-    List,
+    List {
+        /// Show run count, bytes processed and average ratio per pipeline
+        #[arg(long)]
+        usage: bool,
+    },
 
     /// Show pipeline details
     Show {
         /// Pipeline name
         pipeline: String,
+
+        /// Print the stage graph instead of the details (dot or mermaid)
+        #[arg(long, value_name = "FORMAT", conflicts_with = "plan")]
+        graph: Option<String>,
+
+        /// Print the runtime plan for processing --input instead of the
+        /// details
+        #[arg(long, requires = "input")]
+        plan: bool,
+
+        /// Input file to plan for
+        #[arg(short, long, requires = "plan")]
+        input: Option<PathBuf>,
+    },
+
+    /// Estimate processing time and resources for an input size
+    Estimate {
+        /// Pipeline name
+        #[arg(short, long)]
+        pipeline: String,
+
+        /// Input size to estimate for, with units (e.g. 200GiB)
+        #[arg(short, long, value_name = "SIZE")]
+        size: String,
+
+        /// Print the estimate as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check stored pipelines for algorithms and parameters this version
+    /// cannot run, with migration suggestions
+    Audit {
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Delete a pipeline
@@ -144,6 +391,12 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Export a pipeline as a TOML, YAML or JSON definition, or import one
+    Pipeline {
+        #[command(subcommand)]
+        action: PipelineAction,
+    },
+
     /// Benchmark system performance
     Benchmark {
         /// Test file path
@@ -157,6 +410,35 @@ pub enum Commands {
         /// Number of iterations
         #[arg(long, default_value = "3")]
         iterations: usize,
+
+        /// Save the results as a JSON baseline
+        #[arg(long, value_name = "FILE")]
+        save: Option<PathBuf>,
+
+        /// Compare with a saved baseline and exit nonzero on regression
+        #[arg(long, value_name = "FILE")]
+        compare: Option<PathBuf>,
+
+        /// Throughput drop, in percent, that counts as a regression
+        #[arg(long, value_name = "PCT", default_value = "10", requires = "compare")]
+        throughput_threshold: f64,
+
+        /// Duration increase, in percent, that counts as a regression
+        #[arg(long, value_name = "PCT", default_value = "10", requires = "compare")]
+        duration_threshold: f64,
+
+        /// Run --pipeline at each chunk size from MIN to MAX, doubling, e.g.
+        /// 1MiB..64MiB
+        #[arg(long, value_name = "MIN..MAX", requires = "pipeline", conflicts_with_all = ["save", "compare"])]
+        sweep_chunk_size: Option<String>,
+
+        /// Pipeline the chunk size sweep runs
+        #[arg(long, requires = "sweep_chunk_size")]
+        pipeline: Option<String>,
+
+        /// Pin the pipeline to the fastest swept chunk size
+        #[arg(long, requires = "sweep_chunk_size")]
+        apply: bool,
     },
 
     /// Validate pipeline configuration
@@ -171,10 +453,102 @@ pub enum Commands {
         #[arg(short, long)]
         file: PathBuf,
 
-        /// Perform full streaming validation (decrypt/decompress and verify
-        /// checksum)
+        /// Perform full validation: check this build can reverse every
+        /// recorded step, then decrypt/decompress every chunk and verify
+        /// the checksum
         #[arg(long)]
         full: bool,
+
+        /// Print the validation report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the header and build provenance of a .adapipe file
+    Inspect {
+        /// .adapipe file to inspect
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// Print the complete header as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List what a .adapipe file, or a directory of them, holds without
+    /// restoring anything
+    Ls {
+        /// .adapipe file, or directory written by `process --recursive`
+        archive: PathBuf,
+
+        /// Only list entries matching this glob (repeatable; default: all)
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Leave out entries matching this glob (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+    },
+
+    /// Verify the signature of a processing manifest
+    VerifyManifest {
+        /// .adapipe.manifest file to verify
+        #[arg(short, long)]
+        manifest: PathBuf,
+
+        /// Also check that this .adapipe file matches the manifest
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+
+        /// Require the manifest to be signed by this Ed25519 public key
+        /// (hex); without it verification fails as "signer not pinned"
+        #[arg(long)]
+        public_key: Option<String>,
+    },
+
+    /// Check the archives listed in a catalog for silent corruption,
+    /// decoding a random sample of each encrypted archive's chunks and all
+    /// of the others; run it on a schedule. Exits with 81 if any archive is
+    /// corrupt.
+    Scrub {
+        /// Text file listing one .adapipe file per line (`#` starts a
+        /// comment; relative paths are relative to the catalog)
+        #[arg(long)]
+        catalog: PathBuf,
+
+        /// Chunks to decode per encrypted archive
+        #[arg(long, value_name = "CHUNKS", default_value_t = 16, conflicts_with = "full")]
+        sample: u32,
+
+        /// Decode every chunk and verify each archive's checksum
+        #[arg(long)]
+        full: bool,
+
+        /// Read the passphrase the archives were encrypted with
+        #[arg(long)]
+        password_prompt: bool,
+
+        /// Print the scrub report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Copy the archives listed in a catalog to a secondary location,
+    /// verify each copy and record it in the catalog; archives already
+    /// replicated there are skipped
+    Replicate {
+        /// Catalog of .adapipe files, as for `scrub`
+        #[arg(long)]
+        catalog: PathBuf,
+
+        /// Where to copy them: `s3://bucket/prefix`, a directory, or an
+        /// `http(s)://` URL on a gateway that accepts `PUT`s
+        #[arg(long, value_name = "LOCATION")]
+        to: String,
+
+        /// Print the replication report as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Restore original file from .adapipe file
@@ -192,25 +566,470 @@ pub enum Commands {
         #[arg(long)]
         mkdir: bool,
 
-        /// Overwrite existing files without prompting
-        #[arg(long)]
+        /// What to do if the restored file exists: fail (default),
+        /// overwrite, skip, rename or if-newer
+        #[arg(long, value_name = "POLICY")]
+        if_exists: Option<String>,
+
+        /// Overwrite existing files (deprecated: use --if-exists overwrite)
+        #[arg(long, hide = true, conflicts_with = "if_exists")]
         overwrite: bool,
+
+        /// Write the restored file here first and move it into place once
+        /// verified; may be on another filesystem than the output
+        #[arg(long, value_name = "DIR")]
+        staging_dir: Option<PathBuf>,
+
+        /// Restore to the filename recorded in the archive even if it is
+        /// absolute or contains `..`; only for archives from trusted sources
+        #[arg(long)]
+        trust_archive_paths: bool,
+
+        /// Octal permissions of the restored file (e.g. 0640) instead of
+        /// those left by the umask
+        #[arg(long, value_name = "MODE")]
+        output_mode: Option<String>,
+
+        /// Octal permissions of directories created by --mkdir (e.g. 0750)
+        #[arg(long, value_name = "MODE")]
+        dir_mode: Option<String>,
+
+        /// Priority for shared CPU and I/O tokens when jobs run
+        /// concurrently: interactive (default), normal or batch
+        #[arg(long, value_name = "PRIORITY")]
+        priority: Option<String>,
+
+        /// Write a `<restored file>.restore-report.json` with the chunk
+        /// count, stage timings, checksum result and warnings
+        #[arg(long)]
+        report: bool,
+
+        /// Read the passphrase the archive was encrypted with; required for
+        /// encrypted archives
+        #[arg(long)]
+        password_prompt: bool,
+
+        /// Restore only these bytes of the original, START-END inclusive or
+        /// START- for the rest (e.g. 1024-2047); only the chunks holding
+        /// them are decoded
+        #[arg(long, value_name = "START-END")]
+        range: Option<String>,
+
+        /// Number of workers restoring chunks in parallel, or `auto` to size
+        /// from the archive
+        #[arg(long, value_name = "N|auto")]
+        workers: Option<String>,
+    },
+
+    /// Mount a .adapipe file as a read-only filesystem holding its original
+    /// file, restoring only the chunks that are read (needs the `fuse`
+    /// feature; Linux and macOS)
+    Mount {
+        /// .adapipe file to mount
+        archive: PathBuf,
+
+        /// Existing directory to mount it on
+        mountpoint: PathBuf,
+
+        /// Read the passphrase the archive was encrypted with; required for
+        /// encrypted archives
+        #[arg(long)]
+        password_prompt: bool,
+
+        /// Memory for decoded chunks kept for repeated reads, with units
+        /// (e.g. 256MiB) [default: 64MiB]
+        #[arg(long, value_name = "SIZE")]
+        cache_size: Option<String>,
+    },
+
+    /// Write the restored contents of .adapipe files to a tar file
+    ExportTar {
+        /// .adapipe files to export, one tar entry each
+        #[arg(required = true, num_args = 1..)]
+        inputs: Vec<PathBuf>,
+
+        /// Tar file to write (defaults to the archive's path with `.tar`
+        /// in place of `.adapipe`; required for several archives)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Process every file in a tar stream into its own .adapipe file
+    ImportTar {
+        /// Tar file to read, or `-` for standard input
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Directory for the .adapipe files; entry `a/b.txt` becomes
+        /// `<dir>/a/b.txt.adapipe`
+        #[arg(long, value_name = "DIR")]
+        output_dir: PathBuf,
+
+        /// Pipeline name or ID
+        #[arg(short, long)]
+        pipeline: String,
+
+        /// Chunk size, with units (e.g. 4MiB, 512KB; a bare number is
+        /// bytes)
+        #[arg(long, value_name = "SIZE")]
+        chunk_size: Option<String>,
+
+        /// Number of parallel workers, or `auto` to size from each entry
+        #[arg(long, value_name = "N|auto")]
+        workers: Option<String>,
+
+        /// What to do if an output exists: fail (default), overwrite, skip,
+        /// rename or if-newer
+        #[arg(long, value_name = "POLICY")]
+        if_exists: Option<String>,
+
+        /// Priority for shared CPU and I/O tokens when jobs run
+        /// concurrently: interactive, normal (default) or batch
+        #[arg(long, value_name = "PRIORITY")]
+        priority: Option<String>,
+    },
+
+    /// Serve an HTTP API for managing pipelines, submitting processing jobs
+    /// and downloading restored files
+    ///
+    /// Endpoints live under `/api/v1`. Requests authenticate against the
+    /// `[auth]` providers with `Authorization: Bearer <token>` or
+    /// `X-API-Key: <key>` and are authorized like the matching commands.
+    /// Addresses other than loopback are only served when `[auth]` requires
+    /// authentication.
+    Serve {
+        /// Port to listen on (0 picks a free one)
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+        bind: IpAddr,
+
+        /// Number of submitted files processed at once; further jobs queue
+        #[arg(long, value_name = "N", default_value_t = 2)]
+        max_jobs: usize,
+
+        /// Directory that jobs and restores may read and write files in;
+        /// without it they are refused
+        #[arg(long, value_name = "DIR")]
+        root: Option<PathBuf>,
+
+        /// Serve requests without credentials as the principal that started
+        /// the server (not with `[auth] required`)
+        #[arg(long)]
+        allow_anonymous: bool,
+    },
+
+    /// Run queued jobs in the background until stopped
+    ///
+    /// Jobs queued with `submit` in this namespace are processed oldest
+    /// first, at most `--max-jobs` at a time. Stopping the daemon puts
+    /// running jobs back in the queue for its next start.
+    Daemon {
+        /// Number of jobs processed at once (defaults to the CPU worker
+        /// count)
+        #[arg(long, value_name = "N")]
+        max_jobs: Option<usize>,
+    },
+
+    /// Queue a file to be processed by the daemon and return immediately
+    Submit {
+        /// File to process
+        input: PathBuf,
+
+        /// Pipeline name or ID
+        #[arg(short, long)]
+        pipeline: String,
+
+        /// Output .adapipe file (defaults to `<input>.adapipe`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Number of parallel workers, or `auto` to size from the file
+        #[arg(long, value_name = "N|auto")]
+        workers: Option<String>,
+    },
+
+    /// List, inspect or cancel queued jobs
+    Jobs {
+        #[command(subcommand)]
+        action: JobsAction,
+    },
+
+    /// Process several files through a pipeline, continuing past failures
+    ///
+    /// Each input becomes `<output-dir>/<file name>.adapipe`. If some files
+    /// fail, the rest are still processed, the failures are written to a
+    /// retry manifest, and the command exits with 80 (EX_PARTIAL).
+    ProcessBatch {
+        /// Files to process
+        #[arg(required_unless_present = "retry_failed", conflicts_with = "retry_failed")]
+        inputs: Vec<PathBuf>,
+
+        /// Directory for the .adapipe files
+        #[arg(long, value_name = "DIR", required_unless_present = "retry_failed")]
+        output_dir: Option<PathBuf>,
+
+        /// Pipeline name or ID
+        #[arg(
+            short,
+            long,
+            required_unless_present = "retry_failed",
+            conflicts_with = "retry_failed"
+        )]
+        pipeline: Option<String>,
+
+        /// Reprocess only the files in this retry manifest, with its pipeline
+        /// and outputs
+        #[arg(long, value_name = "MANIFEST", conflicts_with = "output_dir")]
+        retry_failed: Option<PathBuf>,
+
+        /// Where to write the retry manifest (default:
+        /// `<output-dir>/batch-failures.json`, or the manifest being retried)
+        #[arg(long, value_name = "PATH")]
+        failures: Option<PathBuf>,
+
+        /// Chunk size, with units (e.g. 4MiB, 512KB; a bare number is
+        /// bytes)
+        #[arg(long, value_name = "SIZE")]
+        chunk_size: Option<String>,
+
+        /// Number of parallel workers, or `auto` to size from each file
+        #[arg(long, value_name = "N|auto")]
+        workers: Option<String>,
+
+        /// What to do if an output exists: fail (default), overwrite, skip,
+        /// rename or if-newer
+        #[arg(long, value_name = "POLICY")]
+        if_exists: Option<String>,
+
+        /// Priority for shared CPU and I/O tokens when jobs run
+        /// concurrently: interactive, normal (default) or batch
+        #[arg(long, value_name = "PRIORITY")]
+        priority: Option<String>,
     },
 
-    /// Compare original file against .adapipe file
+    /// Compare original file against .adapipe file, or two .adapipe files
     Compare {
         /// Original file to compare
-        #[arg(short, long)]
-        original: PathBuf,
+        #[arg(short, long, required_unless_present = "other")]
+        original: Option<PathBuf>,
 
         /// .adapipe file to compare against
         #[arg(short, long)]
         adapipe: PathBuf,
 
+        /// Second .adapipe file to compare against instead of an original
+        /// file; neither archive is restored
+        #[arg(long, value_name = "ADAPIPE", conflicts_with = "original")]
+        other: Option<PathBuf>,
+
         /// Show detailed differences
         #[arg(long)]
         detailed: bool,
     },
+
+    /// Remove temporary files left behind by crashed or killed runs
+    Cleanup {
+        /// List what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Manage role assignments (auditor, operator, admin)
+    Role {
+        #[command(subcommand)]
+        action: RoleAction,
+    },
+
+    /// Issue, list or revoke API session tokens
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+
+    /// Back up or restore the pipeline database
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+
+    /// Generate or verify chunk encryption test vectors
+    Vectors {
+        #[command(subcommand)]
+        action: VectorsAction,
+    },
+
+    /// Check that algorithms, the database and the temp directory work
+    SelfTest {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the algorithms, format versions, features and limits of this build
+    Capabilities {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print every exit code and when it is returned
+    ExitCodes {
+        /// Print the table as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Role management subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum RoleAction {
+    /// List role assignments
+    List,
+
+    /// Make yourself the first admin of a database without role assignments
+    Init,
+
+    /// Assign a role to a principal, replacing any existing role
+    Assign {
+        /// User or service account name
+        principal: String,
+
+        /// Role to assign: auditor, operator or admin
+        #[arg(value_parser = parse_role)]
+        role: String,
+    },
+
+    /// Revoke a principal's role
+    Revoke {
+        /// User or service account name
+        principal: String,
+    },
+}
+
+/// Session management subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum SessionAction {
+    /// Issue a session token and print it
+    Issue {
+        /// User the session acts for (defaults to the current principal)
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Session lifetime in seconds (defaults to the configured lifetime)
+        #[arg(long, value_name = "SECONDS")]
+        ttl: Option<u64>,
+    },
+
+    /// List active sessions
+    List,
+
+    /// Revoke a session
+    Revoke {
+        /// Session ID, as printed by `session issue` or `session list`
+        id: String,
+    },
+}
+
+/// Job queue subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum JobsAction {
+    /// List the jobs in the namespace, oldest first
+    List {
+        /// Print the jobs as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a job's state and result
+    Status {
+        /// Job ID, as printed by `submit` or `jobs list`
+        id: String,
+
+        /// Print the job as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Cancel a queued or running job
+    Cancel {
+        /// Job ID, as printed by `submit` or `jobs list`
+        id: String,
+    },
+}
+
+/// Pipeline definition subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum PipelineAction {
+    /// Write a pipeline's definition to a file or stdout
+    Export {
+        /// Pipeline name
+        pipeline: String,
+
+        /// Definition format: toml, yaml or json (defaults to the output
+        /// file's extension, else yaml)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// File to write (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Create a pipeline from a definition file
+    Import {
+        /// Definition file to read
+        file: PathBuf,
+
+        /// Definition format: toml, yaml or json (defaults to the file's
+        /// extension)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Import under this name instead of the one in the definition
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+/// Database maintenance subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbAction {
+    /// Copy the live database to a file, without stopping other processes
+    Backup {
+        /// Backup file to write
+        path: PathBuf,
+    },
+
+    /// Replace the database's contents with a backup
+    Restore {
+        /// Backup file to restore
+        path: PathBuf,
+
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Test vector subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum VectorsAction {
+    /// Write the vectors this build produces as JSON
+    Generate {
+        /// File to write (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Check published vectors against this build
+    Verify {
+        /// Test vector file
+        #[arg(short, long)]
+        input: PathBuf,
+    },
 }
 
 /// Parse and validate storage type from CLI argument
@@ -219,11 +1038,37 @@ pub enum Commands {
 /// storage type strings and provides helpful error messages.
 fn parse_storage_type(s: &str) -> Result<String, String> {
     match s.to_lowercase().as_str() {
-        "nvme" | "ssd" | "hdd" => Ok(s.to_lowercase()),
-        _ => Err(format!("Invalid storage type '{}'. Valid options: nvme, ssd, hdd", s)),
+        "nvme" | "ssd" | "hdd" | "network" => Ok(s.to_lowercase()),
+        _ => Err(format!(
+            "Invalid storage type '{}'. Valid options: nvme, ssd, hdd, network",
+            s
+        )),
     }
 }
 
+/// Parse and validate a role name from CLI argument
+fn parse_role(s: &str) -> Result<String, String> {
+    match s.to_lowercase().as_str() {
+        "auditor" | "operator" | "admin" => Ok(s.to_lowercase()),
+        _ => Err(format!("Invalid role '{}'. Valid options: auditor, operator, admin", s)),
+    }
+}
+
+/// Parse and validate a namespace name from CLI argument
+fn parse_namespace(s: &str) -> Result<String, String> {
+    let valid_chars = s
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    let valid_start = s.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if s.len() > 63 || !valid_chars || !valid_start {
+        return Err(format!(
+            "Invalid namespace '{}'. Use 1-63 lowercase letters, digits, '-' and '_', starting with a letter or digit",
+            s
+        ));
+    }
+    Ok(s.to_string())
+}
+
 /// Parse CLI arguments
 ///
 /// This is the entry point for CLI parsing. It uses clap to parse
@@ -243,12 +1088,14 @@ pub fn parse_cli() -> Cli {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_parse_storage_type_valid() {
         assert_eq!(parse_storage_type("nvme").unwrap(), "nvme");
         assert_eq!(parse_storage_type("SSD").unwrap(), "ssd");
         assert_eq!(parse_storage_type("HDD").unwrap(), "hdd");
+        assert_eq!(parse_storage_type("network").unwrap(), "network");
     }
 
     #[test]
@@ -256,4 +1103,255 @@ mod tests {
         assert!(parse_storage_type("invalid").is_err());
         assert!(parse_storage_type("usb").is_err());
     }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(parse_role("Operator").unwrap(), "operator");
+        assert!(parse_role("root").is_err());
+    }
+
+    #[test]
+    fn test_parse_namespace() {
+        assert_eq!(parse_namespace("team-a").unwrap(), "team-a");
+        assert!(parse_namespace("").is_err());
+        assert!(parse_namespace("Team").is_err());
+        assert!(parse_namespace("../x").is_err());
+    }
+
+    #[test]
+    fn test_namespace_is_global() {
+        let cli = Cli::try_parse_from(["pipeline", "list", "--namespace", "ops"]).unwrap();
+        assert_eq!(cli.namespace, "ops");
+        let cli = Cli::try_parse_from(["pipeline", "list"]).unwrap();
+        assert_eq!(cli.namespace, "default");
+    }
+
+    #[test]
+    fn test_chunk_size_mb_conflicts_with_chunk_size() {
+        let args = ["pipeline", "process", "-i", "in", "-o", "out", "-p", "p"];
+        let cli = Cli::try_parse_from(args.iter().chain(&["--chunk-size", "4MiB", "--workers", "auto"])).unwrap();
+        match cli.command {
+            Commands::Process {
+                chunk_size, workers, ..
+            } => {
+                assert_eq!(chunk_size.as_deref(), Some("4MiB"));
+                assert_eq!(workers.as_deref(), Some("auto"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Cli::try_parse_from(args.iter().chain(&["--chunk-size", "4MiB", "--chunk-size-mb", "4"])).is_err());
+    }
+
+    #[test]
+    fn test_compare_takes_an_original_or_another_archive() {
+        let compare = |extra: &[&str]| {
+            let args = ["pipeline", "compare", "-a", "a.adapipe"];
+            Cli::try_parse_from(args.iter().chain(extra)).is_ok()
+        };
+        assert!(compare(&["--other", "b.adapipe"]));
+        assert!(compare(&["-o", "a.txt"]));
+        assert!(!compare(&[]));
+        assert!(!compare(&["-o", "a.txt", "--other", "b.adapipe"]));
+    }
+
+    #[test]
+    fn test_show_plan_requires_an_input() {
+        let show = |extra: &[&str]| Cli::try_parse_from(["pipeline", "show", "smoke"].iter().chain(extra)).is_ok();
+        assert!(show(&[]));
+        assert!(show(&["--plan", "--input", "data.bin"]));
+        assert!(!show(&["--plan"]));
+        assert!(!show(&["--input", "data.bin"]));
+        assert!(!show(&["--plan", "-i", "data.bin", "--graph", "dot"]));
+    }
+
+    #[test]
+    fn test_estimate_requires_a_pipeline_and_a_size() {
+        let estimate = |extra: &[&str]| Cli::try_parse_from(["pipeline", "estimate"].iter().chain(extra)).is_ok();
+        assert!(estimate(&["--pipeline", "smoke", "--size", "200GiB"]));
+        assert!(estimate(&["-p", "smoke", "-s", "1TB", "--json"]));
+        assert!(!estimate(&["--pipeline", "smoke"]));
+        assert!(!estimate(&["--size", "200GiB"]));
+    }
+
+    #[test]
+    fn test_vectors_verify_requires_an_input() {
+        let vectors = |extra: &[&str]| Cli::try_parse_from(["pipeline", "vectors"].iter().chain(extra)).is_ok();
+        assert!(vectors(&["generate"]));
+        assert!(vectors(&["generate", "--output", "vectors.json"]));
+        assert!(vectors(&["verify", "--input", "vectors.json"]));
+        assert!(!vectors(&["verify"]));
+        assert!(!vectors(&[]));
+    }
+
+    #[test]
+    fn test_scrub_samples_unless_full() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline", "scrub"].iter().chain(args));
+        assert!(parse(&["--catalog", "fleet.catalog"]).is_ok());
+        assert!(parse(&["--catalog", "fleet.catalog", "--full", "--json"]).is_ok());
+        assert!(parse(&["--catalog", "fleet.catalog", "--sample", "4", "--full"]).is_err());
+        assert!(parse(&["--sample", "4"]).is_err());
+    }
+
+    #[test]
+    fn test_restore_takes_a_worker_count() {
+        let cli = Cli::try_parse_from(["pipeline", "restore", "-i", "a.adapipe", "--workers", "auto"]).unwrap();
+        assert!(matches!(cli.command, Commands::Restore { workers: Some(w), .. } if w == "auto"));
+        let cli = Cli::try_parse_from(["pipeline", "restore", "-i", "a.adapipe"]).unwrap();
+        assert!(matches!(cli.command, Commands::Restore { workers: None, .. }));
+    }
+
+    #[test]
+    fn test_tar_commands_require_their_paths() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline"].iter().chain(args)).is_ok();
+        assert!(parse(&["export-tar", "a.adapipe"]));
+        assert!(parse(&["export-tar", "a.adapipe", "b.adapipe", "-o", "out.tar"]));
+        assert!(!parse(&["export-tar"]));
+        assert!(parse(&["import-tar", "-i", "-", "--output-dir", "out", "-p", "smoke"]));
+        assert!(!parse(&["import-tar", "-i", "in.tar", "-p", "smoke"]));
+    }
+
+    #[test]
+    fn test_process_batch_takes_files_or_a_retry_manifest() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline", "process-batch"].iter().chain(args)).is_ok();
+        assert!(parse(&["a.bin", "b.bin", "--output-dir", "out", "-p", "compress"]));
+        assert!(parse(&["--retry-failed", "out/batch-failures.json"]));
+        assert!(!parse(&["--output-dir", "out", "-p", "compress"]));
+        assert!(!parse(&["a.bin", "-p", "compress"]));
+        assert!(!parse(&["a.bin", "--retry-failed", "failures.json"]));
+        assert!(!parse(&["--retry-failed", "failures.json", "--output-dir", "out"]));
+    }
+
+    #[test]
+    fn test_self_test_takes_only_a_format() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline"].iter().chain(args)).is_ok();
+        assert!(parse(&["self-test"]));
+        assert!(parse(&["self-test", "--json"]));
+        assert!(!parse(&["self-test", "brotli"]));
+    }
+
+    #[test]
+    fn test_audit_takes_only_a_format() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline"].iter().chain(args)).is_ok();
+        assert!(parse(&["audit"]));
+        assert!(parse(&["audit", "--json"]));
+        assert!(!parse(&["audit", "compress-encrypt"]));
+    }
+
+    #[test]
+    fn test_capabilities_takes_only_a_format() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline"].iter().chain(args)).is_ok();
+        assert!(parse(&["capabilities"]));
+        assert!(parse(&["capabilities", "--json"]));
+        assert!(!parse(&["capabilities", "gpu"]));
+    }
+
+    #[test]
+    fn test_exit_codes_takes_only_a_format() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline"].iter().chain(args)).is_ok();
+        assert!(parse(&["exit-codes"]));
+        assert!(parse(&["exit-codes", "--json"]));
+        assert!(!parse(&["exit-codes", "--input", "data.bin"]));
+    }
+
+    #[test]
+    fn test_pid_file_requires_daemonize() {
+        let cli = Cli::try_parse_from(["pipeline", "list", "--daemonize", "--pid-file", "run.pid"]).unwrap();
+        assert!(cli.daemonize);
+        assert_eq!(cli.pid_file, Some(PathBuf::from("run.pid")));
+        assert!(Cli::try_parse_from(["pipeline", "--pid-file", "run.pid", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_thresholds_require_a_baseline() {
+        let bench = |extra: &[&str]| Cli::try_parse_from(["pipeline", "benchmark"].iter().chain(extra)).is_ok();
+        assert!(bench(&[]));
+        assert!(bench(&["--save", "baseline.json"]));
+        assert!(bench(&["--compare", "baseline.json", "--throughput-threshold", "5"]));
+        assert!(!bench(&["--duration-threshold", "5"]));
+    }
+
+    #[test]
+    fn test_pin_workers_value_is_optional() {
+        let cli = Cli::try_parse_from(["pipeline", "--pin-workers", "list"]).unwrap();
+        assert_eq!(cli.pin_workers, Some(CoreSelection::Allowed));
+        let cli = Cli::try_parse_from(["pipeline", "--pin-workers=0-1", "list"]).unwrap();
+        assert_eq!(cli.pin_workers, Some(CoreSelection::Cores(vec![0, 1])));
+        let cli = Cli::try_parse_from(["pipeline", "list"]).unwrap();
+        assert_eq!(cli.pin_workers, None);
+        assert!(Cli::try_parse_from(["pipeline", "--pin-workers=1-0", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_serve_defaults_to_local_port_8080() {
+        let cli = Cli::try_parse_from(["pipeline", "serve"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Serve { port: 8080, bind, max_jobs: 2, root: None, allow_anonymous: false }
+                if bind.is_loopback()
+        ));
+        let cli = Cli::try_parse_from(["pipeline", "serve", "--port", "0", "--bind", "0.0.0.0"]).unwrap();
+        assert!(matches!(cli.command, Commands::Serve { port: 0, .. }));
+        let cli = Cli::try_parse_from(["pipeline", "serve", "--root", "/srv/archives", "--allow-anonymous"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Serve { root: Some(root), allow_anonymous: true, .. } if root == Path::new("/srv/archives")
+        ));
+        assert!(Cli::try_parse_from(["pipeline", "serve", "--port", "70000"]).is_err());
+        assert!(Cli::try_parse_from(["pipeline", "serve", "--bind", "localhost"]).is_err());
+    }
+
+    #[test]
+    fn test_submit_and_jobs_commands() {
+        let cli = Cli::try_parse_from(["pipeline", "submit", "in.bin", "-p", "compress"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Submit { input, pipeline, output: None, workers: None }
+                if input == Path::new("in.bin") && pipeline == "compress"
+        ));
+        assert!(Cli::try_parse_from(["pipeline", "submit", "in.bin"]).is_err());
+
+        let cli = Cli::try_parse_from(["pipeline", "jobs", "status", "01J0000000000000000000000", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Jobs {
+                action: JobsAction::Status { json: true, .. }
+            }
+        ));
+        assert!(Cli::try_parse_from(["pipeline", "jobs", "cancel"]).is_err());
+        let cli = Cli::try_parse_from(["pipeline", "daemon"]).unwrap();
+        assert!(matches!(cli.command, Commands::Daemon { max_jobs: None }));
+    }
+
+    #[test]
+    fn test_pipeline_export_and_import_commands() {
+        let cli = Cli::try_parse_from(["pipeline", "pipeline", "export", "backup", "-o", "backup.toml"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Pipeline {
+                action: PipelineAction::Export { pipeline, format: None, output: Some(_) }
+            } if pipeline == "backup"
+        ));
+        let cli = Cli::try_parse_from(["pipeline", "pipeline", "import", "backup.def", "--format", "json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Pipeline {
+                action: PipelineAction::Import {
+                    format: Some(_),
+                    name: None,
+                    ..
+                }
+            }
+        ));
+        assert!(Cli::try_parse_from(["pipeline", "pipeline", "import"]).is_err());
+        assert!(Cli::try_parse_from(["pipeline", "pipeline", "export"]).is_err());
+    }
+
+    #[test]
+    fn test_replicate_needs_a_catalog_and_target() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["pipeline", "replicate"].iter().chain(args)).is_ok();
+        assert!(parse(&["--catalog", "fleet.catalog", "--to", "s3://offsite/fleet"]));
+        assert!(parse(&["--catalog", "fleet.catalog", "--to", "/mnt/offsite", "--json"]));
+        assert!(!parse(&["--catalog", "fleet.catalog"]));
+        assert!(!parse(&["--to", "/mnt/offsite"]));
+    }
 }
@@ -26,6 +26,19 @@
 //! - `>` `<` - Redirection
 //! - Null bytes, newlines, carriage returns
 //!
+//! ## Sizes and Counts
+//!
+//! Byte sizes accept unit suffixes (`4MiB`, `512KB`, `2GiB`); SI units
+//! are powers of 1000, binary units powers of 1024, and a bare number is
+//! bytes. Worker counts accept a number or `auto`.
+//!
+//! ## Passphrases
+//!
+//! Passphrases are scored 0-4 by a zxcvbn estimate of how many guesses they
+//! would take. A [`PassphrasePolicy`] sets the lowest score accepted for
+//! pipelines whose minimum security level is Confidential or above; other
+//! pipelines accept any passphrase.
+//!
 //! ## Usage
 //!
 //! ```rust,no_run
@@ -40,6 +53,11 @@
 //! ```
 
 use crate::config::AppConfig;
+use adaptive_pipeline_domain::entities::security_context::SecurityLevel;
+use adaptive_pipeline_domain::value_objects::{
+    ByteRange, ChunkSize, DefinitionFormat, FileMode, JobPriority, OverwritePolicy, WorkerCount,
+};
+use byte_unit::Byte;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -121,6 +139,44 @@ pub enum ParseError {
     InvalidValue { arg: String, reason: String },
 }
 
+/// Lowest passphrase strength accepted for sensitive pipelines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassphrasePolicy {
+    minimum_score: u8,
+}
+
+impl PassphrasePolicy {
+    /// Score required unless configured otherwise ("safely unguessable")
+    pub const DEFAULT_MINIMUM_SCORE: u8 = 3;
+    /// Highest score the estimate gives ("very unguessable")
+    pub const MAX_SCORE: u8 = 4;
+    /// Lowest pipeline security level the policy is enforced for
+    pub const ENFORCED_FROM: SecurityLevel = SecurityLevel::Confidential;
+
+    /// Creates a policy requiring `minimum_score`, capped at
+    /// [`Self::MAX_SCORE`]
+    pub fn new(minimum_score: u8) -> Self {
+        Self {
+            minimum_score: minimum_score.min(Self::MAX_SCORE),
+        }
+    }
+
+    pub fn minimum_score(&self) -> u8 {
+        self.minimum_score
+    }
+
+    /// Whether passphrases for a pipeline requiring `level` are checked
+    pub fn applies_to(&self, level: &SecurityLevel) -> bool {
+        *level >= Self::ENFORCED_FROM
+    }
+}
+
+impl Default for PassphrasePolicy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MINIMUM_SCORE)
+    }
+}
+
 /// Secure argument parser
 ///
 /// Provides security-first parsing with comprehensive validation.
@@ -282,6 +338,169 @@ impl SecureArgParser {
 
         Ok(num)
     }
+
+    /// Validate a byte size such as `4MiB`, `512KB` or `65536`
+    pub fn validate_byte_size(arg_name: &str, value: &str) -> Result<u64, ParseError> {
+        Self::validate_argument(value)?;
+
+        Byte::parse_str(value.trim(), true)
+            .map(|bytes| bytes.as_u64())
+            .map_err(|e| ParseError::InvalidValue {
+                arg: arg_name.to_string(),
+                reason: format!("Not a valid size: {} ({})", value, e),
+            })
+    }
+
+    /// Validate a chunk size, with units, into a [`ChunkSize`]
+    pub fn validate_chunk_size(arg_name: &str, value: &str) -> Result<ChunkSize, ParseError> {
+        let bytes = Self::validate_byte_size(arg_name, value)?;
+
+        ChunkSize::new(usize::try_from(bytes).unwrap_or(usize::MAX)).map_err(|e| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Validate a `MIN..MAX` chunk size range, with units, into the sizes a
+    /// sweep tries (see [`ChunkSize::sweep`])
+    pub fn validate_chunk_size_range(arg_name: &str, value: &str) -> Result<Vec<ChunkSize>, ParseError> {
+        let (min, max) = value.split_once("..").ok_or_else(|| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!("expected MIN..MAX, e.g. 1MiB..64MiB, got '{}'", value),
+        })?;
+        let min = Self::validate_chunk_size(arg_name, min)?;
+        let max = Self::validate_chunk_size(arg_name, max)?;
+
+        ChunkSize::sweep(min, max).map_err(|e| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Validate a worker count; `auto` (`None`) leaves the choice to the
+    /// adaptive sizing
+    pub fn validate_worker_count(arg_name: &str, value: &str) -> Result<Option<WorkerCount>, ParseError> {
+        if value.trim().eq_ignore_ascii_case("auto") {
+            return Ok(None);
+        }
+
+        Self::validate_number(
+            arg_name,
+            value.trim(),
+            Some(WorkerCount::MIN_WORKERS),
+            Some(WorkerCount::MAX_WORKERS),
+        )
+        .map(|count| Some(WorkerCount::new(count)))
+    }
+
+    /// Validate an overwrite policy name (`fail`, `overwrite`, `skip`,
+    /// `rename` or `if-newer`)
+    pub fn validate_overwrite_policy(arg_name: &str, value: &str) -> Result<OverwritePolicy, ParseError> {
+        value.parse().map_err(|_| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!(
+                "unknown policy '{}'; expected fail, overwrite, skip, rename or if-newer",
+                value.trim()
+            ),
+        })
+    }
+
+    /// Validate an octal permission mode such as `0640`
+    pub fn validate_file_mode(arg_name: &str, value: &str) -> Result<FileMode, ParseError> {
+        value.parse().map_err(|_| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!(
+                "invalid mode '{}'; expected octal permissions such as 0640",
+                value.trim()
+            ),
+        })
+    }
+
+    /// Validate an inclusive byte range such as `1024-2047`, or `1024-` for
+    /// the rest of the file
+    pub fn validate_byte_range(arg_name: &str, value: &str) -> Result<ByteRange, ParseError> {
+        value.parse().map_err(|_| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!(
+                "invalid range '{}'; expected START-END or START-, such as 1024-2047",
+                value.trim()
+            ),
+        })
+    }
+
+    /// Validate a job priority name (`interactive`, `normal` or `batch`)
+    pub fn validate_job_priority(arg_name: &str, value: &str) -> Result<JobPriority, ParseError> {
+        value.parse().map_err(|_| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!(
+                "unknown priority '{}'; expected interactive, normal or batch",
+                value.trim()
+            ),
+        })
+    }
+
+    /// Validate a pipeline definition format name (`toml`, `yaml` or `json`)
+    pub fn validate_definition_format(arg_name: &str, value: &str) -> Result<DefinitionFormat, ParseError> {
+        value.parse().map_err(|_| ParseError::InvalidValue {
+            arg: arg_name.to_string(),
+            reason: format!("unknown format '{}'; expected toml, yaml or json", value.trim()),
+        })
+    }
+
+    /// Validate a minimum passphrase score (0-4) into a
+    /// [`PassphrasePolicy`]
+    pub fn validate_passphrase_policy(arg_name: &str, value: &str) -> Result<PassphrasePolicy, ParseError> {
+        Self::validate_number(arg_name, value.trim(), Some(0), Some(PassphrasePolicy::MAX_SCORE))
+            .map(PassphrasePolicy::new)
+    }
+
+    /// Validate a passphrase against `policy` for a pipeline requiring
+    /// `level`
+    ///
+    /// `context` holds words the passphrase should not be built from, such
+    /// as the pipeline and file names. The error explains why the passphrase
+    /// is weak but never repeats it.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidValue` if the policy applies and the passphrase scores below
+    /// its minimum.
+    pub fn validate_passphrase(
+        passphrase: &str,
+        level: &SecurityLevel,
+        policy: &PassphrasePolicy,
+        context: &[&str],
+    ) -> Result<(), ParseError> {
+        if !policy.applies_to(level) {
+            return Ok(());
+        }
+
+        let estimate = zxcvbn::zxcvbn(passphrase, context);
+        let score = u8::from(estimate.score());
+        if score >= policy.minimum_score() {
+            return Ok(());
+        }
+
+        let mut reason = format!(
+            "too weak for a {} pipeline (strength {}/{}, requires {})",
+            level,
+            score,
+            PassphrasePolicy::MAX_SCORE,
+            policy.minimum_score()
+        );
+        if let Some(feedback) = estimate.feedback() {
+            if let Some(warning) = feedback.warning() {
+                reason.push_str(&format!("; {}", warning));
+            }
+            for suggestion in feedback.suggestions() {
+                reason.push_str(&format!("; {}", suggestion));
+            }
+        }
+        Err(ParseError::InvalidValue {
+            arg: "passphrase".to_string(),
+            reason,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -360,6 +579,126 @@ mod tests {
         }
     }
 
+    mod size_validation {
+        use super::*;
+
+        #[test]
+        fn parses_unit_suffixes() {
+            let size = |value| SecureArgParser::validate_byte_size("memory-limit", value).unwrap();
+            assert_eq!(size("65536"), 65536);
+            assert_eq!(size("512KB"), 512_000);
+            assert_eq!(size("4MiB"), 4 * 1024 * 1024);
+            assert_eq!(size("2gib"), 2 * 1024 * 1024 * 1024);
+            assert!(SecureArgParser::validate_byte_size("memory-limit", "lots").is_err());
+        }
+
+        #[test]
+        fn enforces_chunk_size_range() {
+            let chunk = SecureArgParser::validate_chunk_size("chunk-size", "4MiB").unwrap();
+            assert_eq!(chunk.bytes(), 4 * 1024 * 1024);
+            assert!(SecureArgParser::validate_chunk_size("chunk-size", "0").is_err());
+            assert!(SecureArgParser::validate_chunk_size("chunk-size", "1GiB").is_err());
+        }
+
+        #[test]
+        fn parses_chunk_size_ranges() {
+            let range = |value| SecureArgParser::validate_chunk_size_range("sweep-chunk-size", value);
+            let sizes = range("1MiB..64MiB").unwrap();
+            assert_eq!(sizes.len(), 7);
+            assert_eq!(sizes.last().unwrap().bytes(), 64 * 1024 * 1024);
+            assert!(range("64MiB").is_err());
+            assert!(range("64MiB..1MiB").is_err());
+            assert!(range("1MiB..1GiB").is_err());
+        }
+
+        #[test]
+        fn accepts_auto_workers() {
+            let workers = |value| SecureArgParser::validate_worker_count("workers", value);
+            assert_eq!(workers("auto").unwrap(), None);
+            assert_eq!(workers("8").unwrap().map(|w| w.count()), Some(8));
+            assert!(workers("0").is_err());
+            assert!(workers("1000").is_err());
+        }
+
+        #[test]
+        fn parses_overwrite_policies() {
+            let policy = |value| SecureArgParser::validate_overwrite_policy("if-exists", value);
+            assert_eq!(policy("if-newer").unwrap(), OverwritePolicy::IfNewer);
+            assert_eq!(policy("Rename").unwrap(), OverwritePolicy::Rename);
+            assert!(matches!(policy("clobber"), Err(ParseError::InvalidValue { .. })));
+        }
+
+        #[test]
+        fn parses_file_modes() {
+            let mode = |value| SecureArgParser::validate_file_mode("output-mode", value);
+            assert_eq!(mode("0640").unwrap().bits(), 0o640);
+            assert_eq!(mode("750").unwrap().bits(), 0o750);
+            assert!(matches!(mode("0648"), Err(ParseError::InvalidValue { .. })));
+            assert!(matches!(mode("rw-r-----"), Err(ParseError::InvalidValue { .. })));
+        }
+
+        #[test]
+        fn parses_byte_ranges() {
+            let range = |value| SecureArgParser::validate_byte_range("range", value);
+            assert_eq!(range("1024-2047").unwrap().len_within(1 << 20), 1024);
+            assert_eq!(range("1024-").unwrap().end(), None);
+            assert!(matches!(range("2048-1024"), Err(ParseError::InvalidValue { .. })));
+            assert!(matches!(range("1024"), Err(ParseError::InvalidValue { .. })));
+        }
+
+        #[test]
+        fn parses_job_priorities() {
+            let priority = |value| SecureArgParser::validate_job_priority("priority", value);
+            assert_eq!(priority("interactive").unwrap(), JobPriority::Interactive);
+            assert_eq!(priority("Batch").unwrap(), JobPriority::Batch);
+            assert!(matches!(priority("urgent"), Err(ParseError::InvalidValue { .. })));
+        }
+    }
+
+    mod passphrase_validation {
+        use super::*;
+
+        #[test]
+        fn parses_minimum_scores() {
+            assert_eq!(
+                SecureArgParser::validate_passphrase_policy("--min-passphrase-score", "2").unwrap(),
+                PassphrasePolicy::new(2)
+            );
+            assert!(SecureArgParser::validate_passphrase_policy("--min-passphrase-score", "5").is_err());
+            assert!(SecureArgParser::validate_passphrase_policy("--min-passphrase-score", "strong").is_err());
+        }
+
+        #[test]
+        fn refuses_weak_passphrases_only_for_sensitive_pipelines() {
+            let policy = PassphrasePolicy::default();
+            let weak = "password1";
+            let strong = "correct horse battery staple";
+
+            assert!(SecureArgParser::validate_passphrase(weak, &SecurityLevel::Internal, &policy, &[]).is_ok());
+            let err = SecureArgParser::validate_passphrase(weak, &SecurityLevel::Confidential, &policy, &[])
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("too weak"), "{}", err);
+            assert!(!err.contains(weak), "{}", err);
+            assert!(SecureArgParser::validate_passphrase(strong, &SecurityLevel::Secret, &policy, &[]).is_ok());
+            assert!(
+                SecureArgParser::validate_passphrase(weak, &SecurityLevel::Secret, &PassphrasePolicy::new(0), &[])
+                    .is_ok()
+            );
+        }
+
+        #[test]
+        fn penalizes_passphrases_built_from_context() {
+            let policy = PassphrasePolicy::new(PassphrasePolicy::MAX_SCORE);
+            let passphrase = "ledgerquarterlyfinance";
+            let level = SecurityLevel::Confidential;
+
+            assert!(SecureArgParser::validate_passphrase(passphrase, &level, &policy, &[]).is_ok());
+            let context = ["ledger", "quarterly", "finance"];
+            assert!(SecureArgParser::validate_passphrase(passphrase, &level, &policy, &context).is_err());
+        }
+    }
+
     mod parsing {
         use super::*;
 
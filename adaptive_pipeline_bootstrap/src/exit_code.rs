@@ -16,10 +16,16 @@
 //! - **1**: General error
 //! - **2**: Misuse of shell command (reserved by Bash)
 //! - **64-78**: Specific error conditions (BSD sysexits.h)
+//! - **80-82**: Pipeline outcomes sysexits.h has no code for (partial
+//!   success, integrity failure, cancellation)
 //! - **126**: Command cannot execute
 //! - **127**: Command not found
 //! - **128+N**: Fatal signal N (e.g., 130 = SIGINT)
 //!
+//! These values are part of the CLI's contract: scripts and runbooks may
+//! branch on them, so a code is never renumbered or reused. [`ExitCode::ALL`]
+//! lists them all, and `adaptive-pipeline exit-codes` prints the table.
+//!
 //! ## Usage
 //!
 //! ```rust,no_run
@@ -118,6 +124,7 @@ pub enum ExitCode {
 
     /// Temporary failure, retry (75)
     /// - Resource temporarily unavailable
+    /// - Namespace quota exceeded
     /// - Retry operation
     TempFail = 75,
 
@@ -139,6 +146,21 @@ pub enum ExitCode {
     /// - Configuration validation failed
     Config = 78,
 
+    /// Partial success (80)
+    /// - Some files of a batch were processed, others failed
+    /// - The failures can be retried on their own
+    PartialSuccess = 80,
+
+    /// Integrity failure (81)
+    /// - Restored data does not match its recorded checksum
+    /// - Manifest signature or contents do not verify
+    IntegrityFailure = 81,
+
+    /// Cancelled (82)
+    /// - Shutdown requested by a signal or the service manager
+    /// - In-flight work drained or aborted; see the checkpoint
+    Cancelled = 82,
+
     /// Interrupted by signal (SIGINT - Ctrl+C) (130)
     /// - User interrupted (Ctrl+C)
     /// - SIGINT received
@@ -151,6 +173,32 @@ pub enum ExitCode {
 }
 
 impl ExitCode {
+    /// Every exit code, in ascending order
+    pub const ALL: [ExitCode; 22] = [
+        ExitCode::Success,
+        ExitCode::Error,
+        ExitCode::UsageError,
+        ExitCode::DataError,
+        ExitCode::NoInput,
+        ExitCode::NoUser,
+        ExitCode::NoHost,
+        ExitCode::Unavailable,
+        ExitCode::Software,
+        ExitCode::OsError,
+        ExitCode::OsFile,
+        ExitCode::CantCreate,
+        ExitCode::IoError,
+        ExitCode::TempFail,
+        ExitCode::Protocol,
+        ExitCode::NoPerm,
+        ExitCode::Config,
+        ExitCode::PartialSuccess,
+        ExitCode::IntegrityFailure,
+        ExitCode::Cancelled,
+        ExitCode::Interrupted,
+        ExitCode::Terminated,
+    ];
+
     /// Convert to i32 for use with std::process::exit
     pub fn as_i32(self) -> i32 {
         self as i32
@@ -208,11 +256,70 @@ impl ExitCode {
             ExitCode::Protocol => "Remote error in protocol",
             ExitCode::NoPerm => "Permission denied",
             ExitCode::Config => "Configuration error",
+            ExitCode::PartialSuccess => "Partial success",
+            ExitCode::IntegrityFailure => "Integrity check failed",
+            ExitCode::Cancelled => "Cancelled by shutdown request",
             ExitCode::Interrupted => "Interrupted by signal (SIGINT)",
             ExitCode::Terminated => "Terminated by signal (SIGTERM)",
         }
     }
 
+    /// Symbolic name: the sysexits.h constant where there is one
+    pub fn name(self) -> &'static str {
+        match self {
+            ExitCode::Success => "EX_OK",
+            ExitCode::Error => "EX_GENERAL",
+            ExitCode::UsageError => "EX_USAGE",
+            ExitCode::DataError => "EX_DATAERR",
+            ExitCode::NoInput => "EX_NOINPUT",
+            ExitCode::NoUser => "EX_NOUSER",
+            ExitCode::NoHost => "EX_NOHOST",
+            ExitCode::Unavailable => "EX_UNAVAILABLE",
+            ExitCode::Software => "EX_SOFTWARE",
+            ExitCode::OsError => "EX_OSERR",
+            ExitCode::OsFile => "EX_OSFILE",
+            ExitCode::CantCreate => "EX_CANTCREAT",
+            ExitCode::IoError => "EX_IOERR",
+            ExitCode::TempFail => "EX_TEMPFAIL",
+            ExitCode::Protocol => "EX_PROTOCOL",
+            ExitCode::NoPerm => "EX_NOPERM",
+            ExitCode::Config => "EX_CONFIG",
+            ExitCode::PartialSuccess => "EX_PARTIAL",
+            ExitCode::IntegrityFailure => "EX_INTEGRITY",
+            ExitCode::Cancelled => "EX_CANCELLED",
+            ExitCode::Interrupted => "EX_SIGINT",
+            ExitCode::Terminated => "EX_SIGTERM",
+        }
+    }
+
+    /// When the pipeline returns this code, for runbooks
+    pub fn cause(self) -> &'static str {
+        match self {
+            ExitCode::Success => "The command completed",
+            ExitCode::Error => "An error no other code covers",
+            ExitCode::UsageError => "Not returned; arguments that do not parse exit with 2",
+            ExitCode::DataError => "An argument, input or configuration value was invalid",
+            ExitCode::NoInput => "An input file or pipeline was not found",
+            ExitCode::NoUser => "Not returned",
+            ExitCode::NoHost => "Not returned",
+            ExitCode::Unavailable => "Not returned",
+            ExitCode::Software => "Initialization failed or an internal invariant broke",
+            ExitCode::OsError => "The process could not daemonize or start as a service",
+            ExitCode::OsFile => "Not returned",
+            ExitCode::CantCreate => "Not returned",
+            ExitCode::IoError => "Reading or writing a file failed",
+            ExitCode::TempFail => "A namespace quota was exceeded; retry later",
+            ExitCode::Protocol => "Not returned",
+            ExitCode::NoPerm => "The active role does not permit the operation",
+            ExitCode::Config => "Not returned",
+            ExitCode::PartialSuccess => "A batch finished with some files failed; retry the failures",
+            ExitCode::IntegrityFailure => "A checksum, signature or manifest did not verify; the data is suspect",
+            ExitCode::Cancelled => "Shutdown stopped the command; a checkpoint may be next to the output",
+            ExitCode::Interrupted => "Not returned; the shell reports it for an unhandled SIGINT",
+            ExitCode::Terminated => "Not returned; the shell reports it for an unhandled SIGTERM",
+        }
+    }
+
     /// Check if this is a success exit code
     pub fn is_success(self) -> bool {
         matches!(self, ExitCode::Success)
@@ -254,7 +361,12 @@ impl From<ExitCode> for std::process::ExitCode {
 ///
 /// # Exit Code Mappings
 ///
+/// - `82` (EX_CANCELLED) - Stopped by a shutdown request
+/// - `80` (EX_PARTIAL) - Some files of a batch failed
+/// - `81` (EX_INTEGRITY) - Integrity check failed
 /// - `70` (EX_SOFTWARE) - Internal software error (initialization failures)
+/// - `77` (EX_NOPERM) - The role does not permit the operation
+/// - `75` (EX_TEMPFAIL) - Namespace quota exceeded
 /// - `66` (EX_NOINPUT) - Cannot open input (file not found)
 /// - `65` (EX_DATAERR) - Data format error (invalid input)
 /// - `74` (EX_IOERR) - Input/output error (read/write failures)
@@ -277,8 +389,20 @@ impl From<ExitCode> for std::process::ExitCode {
 /// assert_eq!(code.as_i32(), 70); // EX_SOFTWARE
 /// ```
 pub fn map_error_to_exit_code(error_message: &str) -> ExitCode {
-    if error_message.contains("Failed to initialize") {
+    // Outcome categories first: their messages often quote an underlying
+    // error that would match a broader pattern below
+    if error_message.contains("Cancelled:") {
+        ExitCode::Cancelled
+    } else if error_message.contains("Partial success:") {
+        ExitCode::PartialSuccess
+    } else if error_message.contains("Integrity check failed") {
+        ExitCode::IntegrityFailure
+    } else if error_message.contains("Failed to initialize") {
         ExitCode::Software // 70 - internal software error
+    } else if error_message.contains("Permission denied") {
+        ExitCode::NoPerm // 77 - role does not permit the operation
+    } else if error_message.contains("Quota exceeded") {
+        ExitCode::TempFail // 75 - namespace limit reached, retry later
     } else if error_message.contains("not found") || error_message.contains("does not exist") {
         ExitCode::NoInput // 66 - cannot open input
     } else if error_message.contains("invalid") || error_message.contains("Invalid") {
@@ -342,6 +466,38 @@ mod tests {
         assert_eq!(ExitCode::Terminated.as_i32(), 143);
     }
 
+    #[test]
+    fn test_all_lists_each_code_once_in_order() {
+        let codes: Vec<i32> = ExitCode::ALL.iter().map(|code| code.as_i32()).collect();
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", codes);
+        assert!(ExitCode::ALL.iter().all(|code| code.name().starts_with("EX_")));
+        assert_eq!(ExitCode::PartialSuccess.as_i32(), 80);
+        assert_eq!(ExitCode::IntegrityFailure.as_i32(), 81);
+        assert_eq!(ExitCode::Cancelled.as_i32(), 82);
+    }
+
+    #[test]
+    fn test_map_error_outcome_categories() {
+        assert_eq!(
+            map_error_to_exit_code(
+                "File processing failed: Cancelled: 3 of 6 chunks completed; in-flight work drained"
+            ),
+            ExitCode::Cancelled
+        );
+        assert_eq!(
+            map_error_to_exit_code("Partial success: 2 of 5 files failed; retry manifest written"),
+            ExitCode::PartialSuccess
+        );
+        assert_eq!(
+            map_error_to_exit_code("Integrity check failed: Checksum mismatch, file not restored"),
+            ExitCode::IntegrityFailure
+        );
+        assert_eq!(
+            map_error_to_exit_code("Quota exceeded: namespace 'analytics' daily output"),
+            ExitCode::TempFail
+        );
+    }
+
     #[test]
     fn test_is_success() {
         assert!(ExitCode::Success.is_success());
@@ -457,7 +613,19 @@ mod tests {
         // Test exact error messages from the codebase
         assert_eq!(map_error_to_exit_code("Pipeline 'test' not found").as_i32(), 66);
         assert_eq!(map_error_to_exit_code("I/O error: permission denied").as_i32(), 74);
+        assert_eq!(
+            map_error_to_exit_code("Security violation: Permission denied: delete pipeline requires Admin permission")
+                .as_i32(),
+            77
+        );
         assert_eq!(map_error_to_exit_code("Invalid pipeline name").as_i32(), 65);
+        assert_eq!(
+            map_error_to_exit_code(
+                "Quota exceeded: namespace 'default' max_concurrent_jobs: 2 job(s) running, limit is 2"
+            )
+            .as_i32(),
+            75
+        );
     }
 
     #[test]
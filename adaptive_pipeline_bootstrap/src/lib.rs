@@ -20,6 +20,7 @@
 //! - **Dependency injection** - Composition root for wiring dependencies
 //! - **Error handling** - Unix exit code mapping
 //! - **Async coordination** - Shutdown coordination and cancellation
+//! - **Service integration** - Daemonizing, systemd notify, Windows services
 //!
 //! ## Architecture Position
 //!
@@ -117,6 +118,7 @@
 //! - `config` - Application configuration
 //! - `exit_code` - Unix exit code enumeration
 //! - `logger` - Bootstrap-specific logging
+//! - `service` - Service manager notification (systemd, Windows SCM)
 //! - `shutdown` - Shutdown coordination
 //! - `composition_root` - Dependency injection container
 //! - `app_runner` - Application lifecycle management
@@ -127,6 +129,7 @@ pub mod config;
 pub mod exit_code;
 pub mod logger;
 pub mod platform;
+pub mod service;
 pub mod shutdown;
 pub mod signals;
 
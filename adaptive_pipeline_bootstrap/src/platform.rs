@@ -54,7 +54,128 @@ mod windows;
 pub use unix::UnixPlatform;
 
 #[cfg(windows)]
-pub use windows::WindowsPlatform;
+pub use windows::{run_as_service, WindowsPlatform};
+
+/// Class of storage backing a path
+///
+/// Used to pick I/O concurrency and read-ahead defaults when the user has
+/// not specified a storage type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageClass {
+    /// NVMe solid-state device
+    Nvme,
+    /// Non-rotational (SATA/SAS) solid-state device
+    Ssd,
+    /// Rotational hard disk
+    Hdd,
+    /// Network filesystem (NFS, SMB, ...)
+    Network,
+    /// Could not be determined
+    Unknown,
+}
+
+impl std::fmt::Display for StorageClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StorageClass::Nvme => "nvme",
+            StorageClass::Ssd => "ssd",
+            StorageClass::Hdd => "hdd",
+            StorageClass::Network => "network",
+            StorageClass::Unknown => "unknown",
+        })
+    }
+}
+
+/// Storage detected for a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageInfo {
+    /// Detected storage class
+    pub class: StorageClass,
+    /// Block device name (e.g. `nvme0n1`), if any
+    pub device: Option<String>,
+    /// Filesystem type (e.g. `ext4`, `nfs4`), if known
+    pub filesystem: Option<String>,
+}
+
+impl StorageInfo {
+    /// Storage that could not be identified
+    pub fn unknown() -> Self {
+        Self {
+            class: StorageClass::Unknown,
+            device: None,
+            filesystem: None,
+        }
+    }
+}
+
+/// Cores selected for pinning worker threads (`--pin-workers`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreSelection {
+    /// Every core the process is allowed to run on
+    Allowed,
+    /// An explicit list of cores
+    Cores(Vec<usize>),
+}
+
+impl CoreSelection {
+    /// Highest core index accepted in a core list
+    pub const MAX_CORE: usize = 4095;
+
+    /// Parses `all` or a core list such as `0-3,8,10-11`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(CoreSelection::Allowed);
+        }
+
+        let parse_core = |part: &str| -> Result<usize, String> {
+            let core = part
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("'{}' is not a core number", part.trim()))?;
+            if core > Self::MAX_CORE {
+                return Err(format!("core {} is above the maximum of {}", core, Self::MAX_CORE));
+            }
+            Ok(core)
+        };
+
+        let mut cores = Vec::new();
+        for part in s.split(',') {
+            match part.split_once('-') {
+                Some((first, last)) => {
+                    let (first, last) = (parse_core(first)?, parse_core(last)?);
+                    if first > last {
+                        return Err(format!("range {}-{} is reversed", first, last));
+                    }
+                    cores.extend(first..=last);
+                }
+                None => cores.push(parse_core(part)?),
+            }
+        }
+        cores.sort_unstable();
+        cores.dedup();
+        Ok(CoreSelection::Cores(cores))
+    }
+
+    /// The selected cores, checked against those the process may use
+    ///
+    /// # Errors
+    /// `Other` naming the first listed core outside
+    /// [`Platform::allowed_cores`]
+    pub fn resolve(&self, platform: &dyn Platform) -> Result<Vec<usize>, PlatformError> {
+        let allowed = platform.allowed_cores();
+        match self {
+            CoreSelection::Allowed => Ok(allowed),
+            CoreSelection::Cores(cores) => match cores.iter().find(|core| !allowed.contains(core)) {
+                Some(core) => Err(PlatformError::Other(format!(
+                    "core {} is not available to this process (allowed: {:?})",
+                    core, allowed
+                ))),
+                None => Ok(cores.clone()),
+            },
+        }
+    }
+}
 
 /// Platform-specific errors
 #[derive(Debug, Error)]
@@ -206,6 +327,71 @@ pub trait Platform: Send + Sync {
     /// # Errors
     /// Returns error if sync operation fails
     async fn sync_file(&self, file: &tokio::fs::File) -> Result<(), PlatformError>;
+
+    // === Storage ===
+
+    /// Detect the kind of storage a path lives on
+    ///
+    /// # Arguments
+    /// - `path`: File or directory to inspect (need not exist; its nearest
+    ///   existing ancestor is used)
+    ///
+    /// # Returns
+    /// - Linux: device class from sysfs (NVMe, rotational flag) or network
+    ///   filesystem type from `/proc/self/mountinfo`
+    /// - macOS: network filesystems only
+    /// - Others: [`StorageClass::Unknown`]
+    fn detect_storage(&self, _path: &Path) -> StorageInfo {
+        StorageInfo::unknown()
+    }
+
+    // === CPU Affinity ===
+
+    /// Cores this process is allowed to run on
+    ///
+    /// # Returns
+    /// - Linux: the process's affinity mask (honours cpusets and `taskset`)
+    /// - Others: `0..cpu_count()`
+    fn allowed_cores(&self) -> Vec<usize> {
+        (0..self.cpu_count()).collect()
+    }
+
+    /// Pin the calling thread to a single core
+    ///
+    /// # Arguments
+    /// - `core`: Zero-based core index
+    ///
+    /// # Errors
+    /// - `NotSupported` where the OS has no hard thread affinity (macOS)
+    /// - `Io`/`Other` if the OS rejects the core
+    fn pin_current_thread(&self, core: usize) -> Result<(), PlatformError> {
+        Err(PlatformError::NotSupported(format!(
+            "pinning threads to core {} on {}",
+            core,
+            self.platform_name()
+        )))
+    }
+
+    // === Process ===
+
+    /// Detach from the terminal and keep running in the background
+    ///
+    /// The calling process exits once the detached one has written
+    /// `pid_file` (if given) and pointed its standard streams at the null
+    /// device; the working directory is kept so relative paths still
+    /// resolve. Must be called before any threads are started, i.e. before
+    /// the async runtime is built.
+    ///
+    /// # Errors
+    /// - `NotSupported` where processes cannot detach this way (Windows:
+    ///   use `run_as_service`)
+    /// - `Io` if forking, the new session or the pid file fails
+    fn daemonize(&self, _pid_file: Option<&Path>) -> Result<(), PlatformError> {
+        Err(PlatformError::NotSupported(format!(
+            "daemonizing on {}",
+            self.platform_name()
+        )))
+    }
 }
 
 // === Platform Selection ===
@@ -279,4 +465,24 @@ mod tests {
         #[cfg(windows)]
         assert_eq!(sep, ';');
     }
+
+    #[test]
+    fn test_core_selection_parsing() {
+        assert_eq!(CoreSelection::parse("all"), Ok(CoreSelection::Allowed));
+        assert_eq!(
+            CoreSelection::parse("0-3,8, 2"),
+            Ok(CoreSelection::Cores(vec![0, 1, 2, 3, 8]))
+        );
+        assert!(CoreSelection::parse("3-1").is_err());
+        assert!(CoreSelection::parse("x").is_err());
+        assert!(CoreSelection::parse("").is_err());
+        assert!(CoreSelection::parse("5000").is_err());
+
+        let platform = create_platform();
+        let allowed = platform.allowed_cores();
+        assert_eq!(CoreSelection::Allowed.resolve(platform.as_ref()).unwrap(), allowed);
+        assert!(CoreSelection::Cores(vec![CoreSelection::MAX_CORE])
+            .resolve(platform.as_ref())
+            .is_err());
+    }
 }
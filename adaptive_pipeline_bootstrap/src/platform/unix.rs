@@ -18,11 +18,34 @@
 //! - **Security**: `libc::geteuid` for privilege checking
 //! - **Permissions**: `std::os::unix::fs::PermissionsExt`
 //! - **File Sync**: `tokio::fs::File::sync_all`
+//! - **Daemonizing**: `libc::fork`, `libc::setsid` and `libc::dup2`
+//! - **Storage Detection**:
+//!   - Linux: `/proc/self/mountinfo` and `/sys/dev/block/<major>:<minor>`
+//!   - macOS: `libc::statfs` filesystem type
 
-use super::{Platform, PlatformError};
+use super::{Platform, PlatformError, StorageClass, StorageInfo};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 
+/// Filesystem types served over the network
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "afpfs",
+    "webdav",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "lustre",
+    "afs",
+    "fuse.sshfs",
+    "fuse.s3fs",
+];
+
 /// Unix (POSIX) platform implementation
 ///
 /// Supports Linux and macOS using POSIX APIs and platform-specific syscalls.
@@ -150,6 +173,154 @@ impl Default for UnixPlatform {
     }
 }
 
+/// The mount a path lives on, from `/proc/self/mountinfo`
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq, Eq)]
+struct MountEntry {
+    /// `major:minor` of the backing device
+    device_id: String,
+    mount_point: PathBuf,
+    fs_type: String,
+}
+
+/// Finds the mount containing `path` (the longest matching mount point; the
+/// last one wins when mounts are stacked)
+#[cfg(any(target_os = "linux", test))]
+fn find_mount(mountinfo: &str, path: &Path) -> Option<MountEntry> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (mount, fs) = line.split_once(" - ")?;
+            let fields: Vec<&str> = mount.split_whitespace().collect();
+            Some(MountEntry {
+                device_id: fields.get(2)?.to_string(),
+                mount_point: PathBuf::from(unescape_mount_path(fields.get(4)?)),
+                fs_type: fs.split_whitespace().next()?.to_string(),
+            })
+        })
+        .filter(|entry| path.starts_with(&entry.mount_point))
+        .fold(None, |best: Option<MountEntry>, entry| match best {
+            Some(best) if best.mount_point.components().count() > entry.mount_point.components().count() => Some(best),
+            _ => Some(entry),
+        })
+}
+
+/// Decodes the octal escapes (`\040` for space, ...) mountinfo uses
+#[cfg(any(target_os = "linux", test))]
+fn unescape_mount_path(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        });
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                out.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Classifies a block device from its name and sysfs `queue/rotational` flag
+#[cfg(any(target_os = "linux", test))]
+fn classify_block_device(name: &str, rotational: Option<&str>) -> StorageClass {
+    if name.starts_with("nvme") {
+        return StorageClass::Nvme;
+    }
+    match rotational.map(str::trim) {
+        Some("1") => StorageClass::Hdd,
+        Some("0") => StorageClass::Ssd,
+        _ => StorageClass::Unknown,
+    }
+}
+
+/// Nearest existing ancestor of `path`, canonicalized
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    absolute.ancestors().find_map(|dir| dir.canonicalize().ok())
+}
+
+impl UnixPlatform {
+    /// Detects storage on Linux via mountinfo and sysfs
+    #[cfg(target_os = "linux")]
+    fn detect_storage_linux(path: &Path) -> Option<StorageInfo> {
+        let path = existing_ancestor(path)?;
+        let mountinfo = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+        let mount = find_mount(&mountinfo, &path)?;
+        let filesystem = Some(mount.fs_type.clone());
+
+        if NETWORK_FILESYSTEMS.contains(&mount.fs_type.as_str()) {
+            return Some(StorageInfo {
+                class: StorageClass::Network,
+                device: None,
+                filesystem,
+            });
+        }
+
+        // Partitions share their parent disk's queue settings
+        let mut device_dir = Path::new("/sys/dev/block").join(&mount.device_id).canonicalize().ok()?;
+        if device_dir.join("partition").exists() {
+            device_dir = device_dir.parent()?.to_path_buf();
+        }
+        let name = device_dir.file_name()?.to_string_lossy().into_owned();
+        let rotational = std::fs::read_to_string(device_dir.join("queue/rotational")).ok();
+
+        Some(StorageInfo {
+            class: classify_block_device(&name, rotational.as_deref()),
+            device: Some(name),
+            filesystem,
+        })
+    }
+
+    /// Detects network filesystems on macOS via statfs
+    #[cfg(target_os = "macos")]
+    fn detect_storage_macos(path: &Path) -> Option<StorageInfo> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = existing_ancestor(path)?;
+        let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+        // SAFETY: statfs is passed a valid NUL-terminated path and a zeroed
+        // struct it fills in; the result is checked before the struct is read.
+        let stat = unsafe {
+            let mut stat: libc::statfs = std::mem::zeroed();
+            if libc::statfs(c_path.as_ptr(), &mut stat) != 0 {
+                return None;
+            }
+            stat
+        };
+        // SAFETY: f_fstypename is a NUL-terminated C string filled by statfs
+        let fs_type = unsafe { std::ffi::CStr::from_ptr(stat.f_fstypename.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+        let class = if NETWORK_FILESYSTEMS.contains(&fs_type.as_str()) {
+            StorageClass::Network
+        } else {
+            StorageClass::Unknown
+        };
+
+        Some(StorageInfo {
+            class,
+            device: None,
+            filesystem: Some(fs_type),
+        })
+    }
+}
+
 #[async_trait]
 impl Platform for UnixPlatform {
     fn page_size(&self) -> usize {
@@ -262,6 +433,125 @@ impl Platform for UnixPlatform {
         file.sync_all().await?;
         Ok(())
     }
+
+    fn detect_storage(&self, path: &Path) -> StorageInfo {
+        #[cfg(target_os = "linux")]
+        let detected = Self::detect_storage_linux(path);
+
+        #[cfg(target_os = "macos")]
+        let detected = Self::detect_storage_macos(path);
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        let detected = {
+            let _ = path;
+            None
+        };
+
+        detected.unwrap_or_else(StorageInfo::unknown)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn allowed_cores(&self) -> Vec<usize> {
+        // SAFETY: cpu_set_t is plain data; sched_getaffinity fills it for
+        // the calling process
+        let cores = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+                return (0..self.cpu_count()).collect();
+            }
+            (0..libc::CPU_SETSIZE as usize)
+                .filter(|&core| libc::CPU_ISSET(core, &set))
+                .collect::<Vec<_>>()
+        };
+        if cores.is_empty() {
+            (0..self.cpu_count()).collect()
+        } else {
+            cores
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn pin_current_thread(&self, core: usize) -> Result<(), PlatformError> {
+        if core >= libc::CPU_SETSIZE as usize {
+            return Err(PlatformError::Other(format!("core {} is out of range", core)));
+        }
+        // SAFETY: cpu_set_t is plain data; pid 0 targets the calling thread
+        unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_SET(core, &mut set);
+            if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+                return Err(PlatformError::Io(std::io::Error::last_os_error()));
+            }
+        }
+        Ok(())
+    }
+
+    fn daemonize(&self, pid_file: Option<&Path>) -> Result<(), PlatformError> {
+        use std::io::{Read, Write};
+        use std::os::unix::io::{AsRawFd, FromRawFd};
+
+        let last_error = || PlatformError::Io(std::io::Error::last_os_error());
+
+        // The original process waits on this pipe so it only exits 0 once
+        // the daemon is fully set up; EOF without a byte means it failed
+        let mut fds = [0; 2];
+        // SAFETY: pipe writes two descriptors into the array it is given
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(last_error());
+        }
+        // SAFETY: both descriptors were just created and are owned here
+        let (mut ready_rx, mut ready_tx) =
+            unsafe { (std::fs::File::from_raw_fd(fds[0]), std::fs::File::from_raw_fd(fds[1])) };
+
+        // SAFETY: no other threads exist yet (see the trait docs), so the
+        // child's copy of the address space is consistent
+        match unsafe { libc::fork() } {
+            -1 => return Err(last_error()),
+            0 => {}
+            _ => {
+                drop(ready_tx);
+                let mut ready = [0u8; 1];
+                let code = match ready_rx.read(&mut ready) {
+                    Ok(1) => 0,
+                    _ => 1,
+                };
+                // SAFETY: _exit skips destructors and atexit handlers, which
+                // belong to the daemon now
+                unsafe { libc::_exit(code) };
+            }
+        }
+        drop(ready_rx);
+
+        // A new session drops the controlling terminal; forking again means
+        // the daemon is not a session leader and can never reacquire one.
+        // SAFETY: setsid and fork take no pointers, and _exit skips the
+        // destructors the daemon still relies on
+        unsafe {
+            if libc::setsid() == -1 {
+                return Err(last_error());
+            }
+            match libc::fork() {
+                -1 => return Err(last_error()),
+                0 => {}
+                _ => libc::_exit(0),
+            }
+        }
+
+        if let Some(pid_file) = pid_file {
+            std::fs::write(pid_file, format!("{}\n", std::process::id()))?;
+        }
+
+        let null = std::fs::OpenOptions::new().read(true).write(true).open("/dev/null")?;
+        for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+            // SAFETY: both descriptors are open; dup2 replaces fd atomically
+            if unsafe { libc::dup2(null.as_raw_fd(), fd) } == -1 {
+                return Err(last_error());
+            }
+        }
+
+        ready_tx.write_all(&[1])?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -318,6 +608,60 @@ mod tests {
         assert!(temp.exists());
     }
 
+    #[test]
+    fn test_find_mount_picks_longest_mount_point() {
+        let mountinfo = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+40 22 8:17 / /data rw,relatime shared:20 - xfs /dev/sdb1 rw
+41 40 0:52 / /data/shared rw,relatime shared:21 - nfs4 server:/export rw
+42 22 8:33 / /mnt/my\\040disk rw,relatime shared:22 - ext4 /dev/sdc1 rw
+";
+        let mount = find_mount(mountinfo, Path::new("/data/shared/file.bin")).unwrap();
+        assert_eq!(mount.fs_type, "nfs4");
+
+        let mount = find_mount(mountinfo, Path::new("/data/sharedness")).unwrap();
+        assert_eq!(mount.device_id, "8:17");
+
+        let mount = find_mount(mountinfo, Path::new("/home/user")).unwrap();
+        assert_eq!(mount.mount_point, PathBuf::from("/"));
+
+        let mount = find_mount(mountinfo, Path::new("/mnt/my disk/a")).unwrap();
+        assert_eq!(mount.device_id, "8:33");
+    }
+
+    #[test]
+    fn test_classify_block_device() {
+        assert_eq!(classify_block_device("nvme0n1", Some("0\n")), StorageClass::Nvme);
+        assert_eq!(classify_block_device("sda", Some("1\n")), StorageClass::Hdd);
+        assert_eq!(classify_block_device("sdb", Some("0\n")), StorageClass::Ssd);
+        assert_eq!(classify_block_device("loop0", None), StorageClass::Unknown);
+    }
+
+    #[test]
+    fn test_detect_storage_does_not_fail() {
+        let platform = UnixPlatform::new();
+        // Any class is acceptable; missing paths resolve via their ancestors
+        let _ = platform.detect_storage(&platform.temp_dir().join("does/not/exist"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pin_current_thread_to_allowed_core() {
+        let platform = UnixPlatform::new();
+        let cores = platform.allowed_cores();
+        assert!(!cores.is_empty());
+
+        // Pin a scratch thread so the test runner's threads keep their mask
+        let core = cores[cores.len() - 1];
+        std::thread::spawn(move || {
+            let platform = UnixPlatform::new();
+            platform.pin_current_thread(core).unwrap();
+            assert_eq!(platform.allowed_cores(), vec![core]);
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn test_is_elevated() {
         let platform = UnixPlatform::new();
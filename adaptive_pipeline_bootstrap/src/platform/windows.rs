@@ -19,11 +19,16 @@
 //! - `GlobalMemoryStatusEx` - Memory information
 //! - `GetSystemInfo` - CPU count and page size
 //! - `IsUserAnAdmin` - Privilege checking
+//! - `StartServiceCtrlDispatcherW`, `RegisterServiceCtrlHandlerExW`,
+//!   `SetServiceStatus` - Running under the Service Control Manager
 //! - File APIs via tokio (cross-platform)
 
 use super::{Platform, PlatformError};
+use crate::service::ServiceNotifier;
+use crate::shutdown::ShutdownCoordinator;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Windows platform implementation
 ///
@@ -113,6 +118,32 @@ impl WindowsPlatform {
         // Stub returns false
         false
     }
+
+    #[cfg(windows)]
+    fn pin_current_thread_impl(core: usize) -> Result<(), PlatformError> {
+        use winapi::um::processthreadsapi::GetCurrentThread;
+        use winapi::um::winbase::SetThreadAffinityMask;
+
+        // Affinity masks cover the 64 cores of one processor group
+        if core >= usize::BITS as usize {
+            return Err(PlatformError::Other(format!(
+                "core {} is outside processor group 0",
+                core
+            )));
+        }
+        unsafe {
+            if SetThreadAffinityMask(GetCurrentThread(), 1usize << core) == 0 {
+                return Err(PlatformError::Io(std::io::Error::last_os_error()));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    fn pin_current_thread_impl(core: usize) -> Result<(), PlatformError> {
+        // Stub for cross-compilation
+        Err(PlatformError::NotSupported(format!("pinning threads to core {}", core)))
+    }
 }
 
 impl Default for WindowsPlatform {
@@ -179,6 +210,210 @@ impl Platform for WindowsPlatform {
         file.sync_all().await?;
         Ok(())
     }
+
+    fn pin_current_thread(&self, core: usize) -> Result<(), PlatformError> {
+        Self::pin_current_thread_impl(core)
+    }
+}
+
+/// Body of a service: runs it and returns the process exit code
+type ServiceBody = Box<dyn FnOnce(Arc<dyn ServiceNotifier>) -> i32 + Send>;
+
+/// Run `body` as the Windows service `name`
+///
+/// Connects to the Service Control Manager, which calls back on a thread of
+/// its own to run `body`; this blocks until that has returned. The service
+/// reports `SERVICE_START_PENDING` until `body` sends
+/// [`ServiceState::Ready`](crate::service::ServiceState::Ready), and a stop
+/// or system shutdown request initiates `shutdown` so `body` can wind down.
+///
+/// # Errors
+/// - `Other` if a service is already running in this process
+/// - `Io` if the process was not started by the Service Control Manager
+///   (`ERROR_FAILED_SERVICE_CONTROLLER_CONNECT`)
+#[cfg(windows)]
+pub fn run_as_service<F>(name: &str, shutdown: ShutdownCoordinator, body: F) -> Result<i32, PlatformError>
+where
+    F: FnOnce(Arc<dyn ServiceNotifier>) -> i32 + Send + 'static,
+{
+    use winapi::um::winsvc::{StartServiceCtrlDispatcherW, SERVICE_TABLE_ENTRYW};
+
+    let name: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    let context = scm::ServiceContext::new(name, shutdown, Box::new(body));
+    let context = match scm::SERVICE.set(context) {
+        Ok(()) => scm::SERVICE.get(),
+        Err(_) => None,
+    }
+    .ok_or_else(|| PlatformError::Other("a service is already running in this process".to_string()))?;
+
+    let table = [
+        SERVICE_TABLE_ENTRYW {
+            lpServiceName: context.name.as_ptr() as *mut u16,
+            lpServiceProc: Some(scm::service_main),
+        },
+        SERVICE_TABLE_ENTRYW {
+            lpServiceName: std::ptr::null_mut(),
+            lpServiceProc: None,
+        },
+    ];
+    // SAFETY: the table is null-terminated and outlives the call, which
+    // returns only once the service has stopped
+    if unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) } == 0 {
+        return Err(PlatformError::Io(std::io::Error::last_os_error()));
+    }
+    Ok(context.exit_code.load(std::sync::atomic::Ordering::SeqCst))
+}
+
+#[cfg(not(windows))]
+pub fn run_as_service<F>(name: &str, _shutdown: ShutdownCoordinator, _body: F) -> Result<i32, PlatformError>
+where
+    F: FnOnce(Arc<dyn ServiceNotifier>) -> i32 + Send + 'static,
+{
+    // Stub for cross-compilation
+    Err(PlatformError::NotSupported(format!(
+        "running {} as a Windows service",
+        name
+    )))
+}
+
+/// Service Control Manager callbacks and the state they share
+#[cfg(windows)]
+mod scm {
+    use super::ServiceBody;
+    use crate::service::{ServiceNotifier, ServiceState};
+    use crate::shutdown::ShutdownCoordinator;
+    use std::sync::atomic::{AtomicI32, AtomicPtr, AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex, OnceLock};
+    use winapi::shared::minwindef::{DWORD, LPVOID};
+    use winapi::shared::winerror::{ERROR_CALL_NOT_IMPLEMENTED, ERROR_SERVICE_SPECIFIC_ERROR, NO_ERROR};
+    use winapi::um::winnt::{LPWSTR, SERVICE_WIN32_OWN_PROCESS};
+    use winapi::um::winsvc::{
+        RegisterServiceCtrlHandlerExW, SetServiceStatus, SERVICE_ACCEPT_SHUTDOWN, SERVICE_ACCEPT_STOP,
+        SERVICE_CONTROL_INTERROGATE, SERVICE_CONTROL_SHUTDOWN, SERVICE_CONTROL_STOP, SERVICE_RUNNING,
+        SERVICE_START_PENDING, SERVICE_STATUS, SERVICE_STATUS_HANDLE__, SERVICE_STOPPED, SERVICE_STOP_PENDING,
+    };
+
+    /// How long the manager should wait between pending-state reports
+    const PENDING_WAIT_HINT_MS: DWORD = 30_000;
+
+    /// The one service this process runs
+    pub(super) static SERVICE: OnceLock<ServiceContext> = OnceLock::new();
+
+    pub(super) struct ServiceContext {
+        pub(super) name: Vec<u16>,
+        pub(super) exit_code: AtomicI32,
+        shutdown: ShutdownCoordinator,
+        body: Mutex<Option<ServiceBody>>,
+        status_handle: AtomicPtr<SERVICE_STATUS_HANDLE__>,
+        check_point: AtomicU32,
+    }
+
+    impl ServiceContext {
+        pub(super) fn new(name: Vec<u16>, shutdown: ShutdownCoordinator, body: ServiceBody) -> Self {
+            Self {
+                name,
+                exit_code: AtomicI32::new(0),
+                shutdown,
+                body: Mutex::new(Some(body)),
+                status_handle: AtomicPtr::new(std::ptr::null_mut()),
+                check_point: AtomicU32::new(0),
+            }
+        }
+
+        fn report(&self, state: DWORD, exit_code: i32) {
+            let handle = self.status_handle.load(Ordering::SeqCst);
+            if handle.is_null() {
+                return;
+            }
+            let pending = state == SERVICE_START_PENDING || state == SERVICE_STOP_PENDING;
+            let mut status = SERVICE_STATUS {
+                dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+                dwCurrentState: state,
+                dwControlsAccepted: if state == SERVICE_RUNNING {
+                    SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN
+                } else {
+                    0
+                },
+                dwWin32ExitCode: if exit_code == 0 {
+                    NO_ERROR
+                } else {
+                    ERROR_SERVICE_SPECIFIC_ERROR
+                },
+                dwServiceSpecificExitCode: exit_code as DWORD,
+                dwCheckPoint: if pending {
+                    self.check_point.fetch_add(1, Ordering::SeqCst) + 1
+                } else {
+                    0
+                },
+                dwWaitHint: if pending { PENDING_WAIT_HINT_MS } else { 0 },
+            };
+            // SAFETY: the handle came from RegisterServiceCtrlHandlerExW and
+            // stays valid until SERVICE_STOPPED is reported
+            if unsafe { SetServiceStatus(handle, &mut status) } == 0 {
+                tracing::warn!(
+                    "Could not report service state {}: {}",
+                    state,
+                    std::io::Error::last_os_error()
+                );
+            }
+        }
+    }
+
+    /// Reports [`ServiceState`]s as Service Control Manager states
+    struct ScmNotifier;
+
+    impl ServiceNotifier for ScmNotifier {
+        fn notify(&self, state: ServiceState) {
+            let Some(context) = SERVICE.get() else {
+                return;
+            };
+            match state {
+                ServiceState::Ready => context.report(SERVICE_RUNNING, 0),
+                ServiceState::Stopping => context.report(SERVICE_STOP_PENDING, 0),
+                // The manager has no reloading state or watchdog
+                ServiceState::Reloading | ServiceState::Watchdog => {}
+                ServiceState::Status(status) => tracing::debug!("Service status: {}", status),
+            }
+        }
+    }
+
+    /// `ServiceMain`, called by the dispatcher on its own thread
+    pub(super) unsafe extern "system" fn service_main(_argc: DWORD, _argv: *mut LPWSTR) {
+        let Some(context) = SERVICE.get() else {
+            return;
+        };
+        let handle = RegisterServiceCtrlHandlerExW(context.name.as_ptr(), Some(control_handler), std::ptr::null_mut());
+        if handle.is_null() {
+            return;
+        }
+        context.status_handle.store(handle, Ordering::SeqCst);
+        context.report(SERVICE_START_PENDING, 0);
+
+        let body = context.body.lock().ok().and_then(|mut body| body.take());
+        let exit_code = body.map_or(1, |body| body(Arc::new(ScmNotifier)));
+        context.exit_code.store(exit_code, Ordering::SeqCst);
+        context.report(SERVICE_STOPPED, exit_code);
+    }
+
+    /// `HandlerEx`, called by the dispatcher for control requests
+    unsafe extern "system" fn control_handler(
+        control: DWORD,
+        _event_type: DWORD,
+        _event_data: LPVOID,
+        _context: LPVOID,
+    ) -> DWORD {
+        match control {
+            SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+                if let Some(context) = SERVICE.get() {
+                    context.report(SERVICE_STOP_PENDING, 0);
+                    context.shutdown.initiate_shutdown();
+                }
+                NO_ERROR
+            }
+            SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+            _ => ERROR_CALL_NOT_IMPLEMENTED,
+        }
+    }
 }
 
 #[cfg(test)]
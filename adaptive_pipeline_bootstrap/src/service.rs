@@ -0,0 +1,309 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Service Manager Integration
+//!
+//! Lets a long-running process tell the service manager that started it how
+//! it is doing: when startup has finished, when it is reloading or stopping,
+//! and that it is still alive.
+//!
+//! ## systemd
+//!
+//! Under a `Type=notify` unit systemd sets `NOTIFY_SOCKET`, and
+//! [`SystemdNotifier`] speaks the `sd_notify` protocol to it: one datagram per
+//! state change (`READY=1`, `RELOADING=1`, `STOPPING=1`, `STATUS=...`). With
+//! `WatchdogSec=` set, systemd also sets `WATCHDOG_USEC` and restarts the
+//! service unless it hears `WATCHDOG=1` that often; [`spawn_watchdog`] sends
+//! those pings at half the interval.
+//!
+//! ## Everywhere Else
+//!
+//! [`create_service_notifier`] returns a [`NoOpNotifier`] when no service
+//! manager is listening, so callers notify unconditionally. On Windows the
+//! Service Control Manager plays the same role through
+//! `platform::run_as_service`.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_bootstrap::service::{create_service_notifier, spawn_watchdog, ServiceState};
+//!
+//! # async fn example() {
+//! let notifier = create_service_notifier();
+//! let watchdog = spawn_watchdog(notifier.clone());
+//!
+//! // ... startup ...
+//! notifier.notify(ServiceState::Ready);
+//!
+//! // ... work ...
+//! notifier.notify(ServiceState::Stopping);
+//! if let Some(watchdog) = watchdog {
+//!     watchdog.abort();
+//! }
+//! # }
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Environment variable naming the socket systemd listens on
+pub const NOTIFY_SOCKET_ENV: &str = "NOTIFY_SOCKET";
+
+/// Environment variable holding the watchdog interval in microseconds
+pub const WATCHDOG_USEC_ENV: &str = "WATCHDOG_USEC";
+
+/// Environment variable naming the process the watchdog applies to
+pub const WATCHDOG_PID_ENV: &str = "WATCHDOG_PID";
+
+/// State changes reported to the service manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceState {
+    /// Startup has finished and the service is doing its work
+    Ready,
+    /// Configuration is being reloaded; report `Ready` again when done
+    Reloading,
+    /// Shutdown has begun
+    Stopping,
+    /// Keep-alive ping for the manager's watchdog
+    Watchdog,
+    /// Free-form status line, shown by e.g. `systemctl status`
+    Status(String),
+}
+
+/// Reports service state to whatever manager started the process
+///
+/// Notification is best effort: a manager that cannot be reached is logged,
+/// never surfaced as an error, since the service itself is unaffected.
+pub trait ServiceNotifier: Send + Sync {
+    /// Report a state change
+    fn notify(&self, state: ServiceState);
+
+    /// How often the manager expects [`ServiceState::Watchdog`] pings, if at
+    /// all
+    fn watchdog_interval(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Notifier for processes no service manager is watching
+pub struct NoOpNotifier;
+
+impl NoOpNotifier {
+    /// Create a new no-op notifier
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NoOpNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceNotifier for NoOpNotifier {
+    fn notify(&self, _state: ServiceState) {}
+}
+
+/// `sd_notify` client for systemd `Type=notify` units
+///
+/// A socket path starting with `@` is a Linux abstract socket, as systemd
+/// uses by default.
+#[cfg(unix)]
+pub struct SystemdNotifier {
+    socket: std::ffi::OsString,
+    watchdog_interval: Option<Duration>,
+}
+
+#[cfg(unix)]
+impl SystemdNotifier {
+    /// Create a notifier for `socket`
+    pub fn new(socket: impl Into<std::ffi::OsString>, watchdog_interval: Option<Duration>) -> Self {
+        Self {
+            socket: socket.into(),
+            watchdog_interval,
+        }
+    }
+
+    /// The notifier systemd asked for, or `None` when `NOTIFY_SOCKET` is
+    /// unset
+    pub fn from_env() -> Option<Self> {
+        let socket = std::env::var_os(NOTIFY_SOCKET_ENV).filter(|socket| !socket.is_empty())?;
+        let watchdog_interval = parse_watchdog_interval(
+            std::env::var(WATCHDOG_USEC_ENV).ok().as_deref(),
+            std::env::var(WATCHDOG_PID_ENV).ok().as_deref(),
+            std::process::id(),
+        );
+        Some(Self::new(socket, watchdog_interval))
+    }
+
+    fn message(state: &ServiceState) -> String {
+        match state {
+            ServiceState::Ready => "READY=1".to_string(),
+            // systemd requires the reload's start time alongside RELOADING=1
+            ServiceState::Reloading => format!("RELOADING=1\nMONOTONIC_USEC={}", monotonic_usec()),
+            ServiceState::Stopping => "STOPPING=1".to_string(),
+            ServiceState::Watchdog => "WATCHDOG=1".to_string(),
+            ServiceState::Status(status) => format!("STATUS={}", status.replace('\n', " ")),
+        }
+    }
+
+    fn send(&self, message: &str) -> std::io::Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::net::UnixDatagram;
+
+        let datagram = UnixDatagram::unbound()?;
+        match self.socket.as_bytes().strip_prefix(b"@") {
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                let address = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+                datagram.send_to_addr(message.as_bytes(), &address)?;
+            }
+            #[cfg(not(target_os = "linux"))]
+            Some(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "abstract notify sockets are Linux-only",
+                ));
+            }
+            None => {
+                datagram.send_to(message.as_bytes(), &self.socket)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+impl ServiceNotifier for SystemdNotifier {
+    fn notify(&self, state: ServiceState) {
+        if let Err(e) = self.send(&Self::message(&state)) {
+            tracing::warn!("Could not notify systemd of {:?}: {}", state, e);
+        }
+    }
+
+    fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+}
+
+/// Watchdog interval from `WATCHDOG_USEC`, or `None` when it is unset,
+/// malformed, or `WATCHDOG_PID` names another process
+#[cfg(unix)]
+fn parse_watchdog_interval(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.trim().parse::<u32>().ok()? != own_pid {
+            return None;
+        }
+    }
+    match usec?.trim().parse::<u64>().ok()? {
+        0 => None,
+        usec => Some(Duration::from_micros(usec)),
+    }
+}
+
+/// `CLOCK_MONOTONIC` in microseconds, the clock systemd compares reload
+/// timestamps against
+#[cfg(unix)]
+fn monotonic_usec() -> u64 {
+    let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: clock_gettime only writes the timespec it is given
+    if unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) } != 0 {
+        return 0;
+    }
+    (now.tv_sec as u64) * 1_000_000 + (now.tv_nsec as u64) / 1_000
+}
+
+/// Create the notifier for the service manager that started this process
+///
+/// # Returns
+/// - Unix under systemd (`NOTIFY_SOCKET` set): [`SystemdNotifier`]
+/// - Otherwise: [`NoOpNotifier`]
+pub fn create_service_notifier() -> Arc<dyn ServiceNotifier> {
+    #[cfg(unix)]
+    if let Some(notifier) = SystemdNotifier::from_env() {
+        return Arc::new(notifier);
+    }
+    Arc::new(NoOpNotifier::new())
+}
+
+/// Ping the manager's watchdog at half its interval until aborted
+///
+/// # Returns
+/// The ping task, or `None` when the manager has no watchdog
+pub fn spawn_watchdog(notifier: Arc<dyn ServiceNotifier>) -> Option<tokio::task::JoinHandle<()>> {
+    let period = notifier.watchdog_interval()? / 2;
+    Some(tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(period);
+        loop {
+            ticks.tick().await;
+            notifier.notify(ServiceState::Watchdog);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_systemd_notifier_sends_sd_notify_messages() {
+        use std::os::unix::net::UnixDatagram;
+
+        let dir = std::env::temp_dir().join(format!("adapipe-notify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.sock");
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixDatagram::bind(&path).unwrap();
+
+        let notifier = SystemdNotifier::new(path.as_os_str(), None);
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 256];
+        for state in [
+            ServiceState::Ready,
+            ServiceState::Status("2 files\nqueued".to_string()),
+            ServiceState::Reloading,
+            ServiceState::Watchdog,
+            ServiceState::Stopping,
+        ] {
+            notifier.notify(state);
+            let len = listener.recv(&mut buffer).unwrap();
+            received.push(String::from_utf8(buffer[..len].to_vec()).unwrap());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(received[0], "READY=1");
+        assert_eq!(received[1], "STATUS=2 files queued");
+        assert!(received[2].starts_with("RELOADING=1\nMONOTONIC_USEC="));
+        assert_eq!(received[3], "WATCHDOG=1");
+        assert_eq!(received[4], "STOPPING=1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_watchdog_interval_parsing() {
+        assert_eq!(
+            parse_watchdog_interval(Some("30000000"), None, 42),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_watchdog_interval(Some("30000000"), Some("42"), 42),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_watchdog_interval(Some("30000000"), Some("7"), 42), None);
+        assert_eq!(parse_watchdog_interval(Some("0"), None, 42), None);
+        assert_eq!(parse_watchdog_interval(Some("soon"), None, 42), None);
+        assert_eq!(parse_watchdog_interval(None, None, 42), None);
+    }
+
+    #[tokio::test]
+    async fn test_no_watchdog_without_interval() {
+        assert!(spawn_watchdog(Arc::new(NoOpNotifier::new())).is_none());
+    }
+}
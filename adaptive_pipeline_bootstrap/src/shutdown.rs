@@ -51,6 +51,8 @@
 //! }
 //! ```
 
+use adaptive_pipeline_domain::services::ShutdownSignal;
+use async_trait::async_trait;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -119,10 +121,27 @@ impl CancellationToken {
 
     /// Wait for cancellation (async)
     pub async fn cancelled(&self) {
+        // Register as a waiter before checking the flag, so a cancel landing
+        // between the check and the await still wakes us
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
         if self.is_cancelled() {
             return;
         }
-        self.notify.notified().await;
+        notified.await;
+    }
+}
+
+/// Lets the domain's shutdown-aware services observe this token
+#[async_trait]
+impl ShutdownSignal for CancellationToken {
+    fn is_requested(&self) -> bool {
+        self.is_cancelled()
+    }
+
+    async fn requested(&self) {
+        self.cancelled().await;
     }
 }
 
@@ -310,6 +329,21 @@ mod tests {
         assert!(token.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_cancellation_token_as_shutdown_signal() {
+        let token = CancellationToken::new();
+        let signal: Arc<dyn ShutdownSignal> = Arc::new(token.clone());
+        assert!(!signal.is_requested());
+
+        let waiter = tokio::spawn(async move { signal.requested().await });
+        tokio::task::yield_now().await;
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
     #[test]
     fn test_shutdown_coordinator_create() {
         let coordinator = ShutdownCoordinator::new(Duration::from_secs(5));
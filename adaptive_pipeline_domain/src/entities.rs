@@ -177,11 +177,15 @@ pub mod pipeline;
 pub mod pipeline_stage;
 pub mod processing_context;
 pub mod processing_metrics;
+pub mod queued_job;
 pub mod security_context;
+pub mod session;
 
 // Re-export all entity types for convenient access
 pub use pipeline::Pipeline;
 pub use pipeline_stage::{Operation, PipelineStage, StageConfiguration, StagePosition, StageType};
 pub use processing_context::ProcessingContext;
 pub use processing_metrics::ProcessingMetrics;
+pub use queued_job::{JobState, QueuedJob};
 pub use security_context::{SecurityContext, SecurityLevel};
+pub use session::Session;
@@ -13,9 +13,13 @@
 //! principles with unique identity, business rule enforcement, and repository
 //! support. See mdBook for usage examples and architecture details.
 
-use crate::entities::{PipelineStage, ProcessingMetrics};
+use crate::entities::{PipelineStage, ProcessingMetrics, StageType};
 use crate::services::datetime_serde;
-use crate::value_objects::PipelineId;
+use crate::value_objects::chunk_encryption_spec::ASSOCIATED_DATA_KEY;
+use crate::value_objects::{
+    ChecksumAlgorithm, ChunkBinding, ChunkSize, ContentType, ExecutionTopology, Namespace, PipelineId, SecurityPolicy,
+    StageGraph, WorkerCount, CHECKSUM_ALGORITHM_KEY, CHUNK_SIZE_KEY, EXECUTION_TOPOLOGY_KEY, WORKER_COUNT_KEY,
+};
 use crate::PipelineError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -41,6 +45,7 @@ use std::collections::HashMap;
 pub struct PipelineData {
     pub id: PipelineId,
     pub name: String,
+    pub namespace: Namespace,
     pub archived: bool,
     pub configuration: HashMap<String, String>,
     pub metrics: ProcessingMetrics,
@@ -231,6 +236,8 @@ pub struct Pipeline {
     // Identity fields (always first)
     id: PipelineId,
     name: String,
+    #[serde(default)]
+    namespace: Namespace,
 
     // Core business fields (alphabetical within group)
     archived: bool,
@@ -380,6 +387,7 @@ impl Pipeline {
             // Identity fields
             id: PipelineId::new(),
             name,
+            namespace: Namespace::default(),
 
             // Core business fields (alphabetical)
             archived: false,
@@ -422,6 +430,20 @@ impl Pipeline {
         &self.name
     }
 
+    /// Gets the namespace this pipeline belongs to
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Moves the pipeline into `namespace`
+    ///
+    /// Used when creating a pipeline; pipeline names only need to be unique
+    /// within their namespace.
+    pub fn with_namespace(mut self, namespace: Namespace) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
     /// Gets the ordered list of processing stages in this pipeline
     ///
     /// Returns all stages including automatically-inserted checksum stages.
@@ -443,6 +465,34 @@ impl Pipeline {
         &self.stages
     }
 
+    /// Returns the pipeline as it runs for a file of `content_type`: a copy
+    /// without the stages whose `skip_content` rule matches it, and the
+    /// names of the stages left out
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if a stage's rule is malformed.
+    pub fn for_content(&self, content_type: &ContentType) -> Result<(Pipeline, Vec<String>), PipelineError> {
+        let mut pipeline = self.clone();
+        let mut skipped = Vec::new();
+        let mut stages = Vec::with_capacity(self.stages.len());
+        for stage in &self.stages {
+            if stage.skips_content(content_type)? {
+                skipped.push(stage.name().to_string());
+            } else {
+                stages.push(stage.clone());
+            }
+        }
+        pipeline.stages = stages;
+        Ok((pipeline, skipped))
+    }
+
+    /// Builds the stage graph of the pipeline, from input through every
+    /// stage to output, for rendering as DOT or Mermaid
+    pub fn stage_graph(&self) -> StageGraph {
+        StageGraph::from_stages(self.name.clone(), &self.stages)
+    }
+
     /// Gets the pipeline configuration parameters
     ///
     /// Configuration parameters are key-value pairs that control pipeline
@@ -461,6 +511,206 @@ impl Pipeline {
         &self.configuration
     }
 
+    /// Gets how this pipeline's stages are spread over workers
+    ///
+    /// Read from the `execution_topology` configuration key; pipelines
+    /// without one run chunk-parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the stored value is not a known
+    /// topology.
+    pub fn execution_topology(&self) -> Result<ExecutionTopology, PipelineError> {
+        self.configuration
+            .get(EXECUTION_TOPOLOGY_KEY)
+            .map_or(Ok(ExecutionTopology::default()), |value| value.parse())
+    }
+
+    /// Sets how this pipeline's stages are spread over workers
+    ///
+    /// Stored in the configuration under `execution_topology`; updates the
+    /// `updated_at` timestamp.
+    pub fn set_execution_topology(&mut self, topology: ExecutionTopology) {
+        self.configuration
+            .insert(EXECUTION_TOPOLOGY_KEY.to_string(), topology.to_string());
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Gets the hash used for the whole-file checksum of this pipeline's
+    /// input
+    ///
+    /// Read from the `checksum_algorithm` configuration key; pipelines
+    /// without one use SHA-256.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the stored value is not a known
+    /// algorithm.
+    pub fn checksum_algorithm(&self) -> Result<ChecksumAlgorithm, PipelineError> {
+        self.configuration
+            .get(CHECKSUM_ALGORITHM_KEY)
+            .map_or(Ok(ChecksumAlgorithm::default()), |value| value.parse())
+    }
+
+    /// Sets the hash used for the whole-file checksum of this pipeline's
+    /// input
+    ///
+    /// Stored in the configuration under `checksum_algorithm`, and applied
+    /// to the automatic `input_checksum` and `output_checksum` stages;
+    /// updates the `updated_at` timestamp.
+    pub fn set_checksum_algorithm(&mut self, algorithm: ChecksumAlgorithm) {
+        self.configuration
+            .insert(CHECKSUM_ALGORITHM_KEY.to_string(), algorithm.to_string());
+        for stage in self
+            .stages
+            .iter_mut()
+            .filter(|stage| matches!(stage.name(), "input_checksum" | "output_checksum"))
+        {
+            let mut configuration = stage.configuration().clone();
+            configuration.algorithm = algorithm.to_string();
+            stage.update_configuration(configuration);
+        }
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Binds the chunks every encryption stage seals to one file
+    ///
+    /// Stores `binding` under the `associated_data` stage parameter, so each
+    /// chunk is authenticated with it and its sequence number. Call it on the
+    /// copy of the pipeline that processes the file, not the stored one.
+    pub fn bind_chunks(&mut self, binding: &ChunkBinding) {
+        self.set_encryption_parameter(ASSOCIATED_DATA_KEY, binding.to_parameter());
+    }
+
+    /// Whether any stage of this pipeline encrypts
+    pub fn encrypts(&self) -> bool {
+        self.stages
+            .iter()
+            .any(|stage| stage.stage_type() == &StageType::Encryption)
+    }
+
+    /// Hands every encryption stage the key to seal or open chunks with
+    ///
+    /// `encoded_key` is the base64 key stored under the `key` stage
+    /// parameter. Like [`Self::bind_chunks`], call it on the copy of the
+    /// pipeline that processes the file so the key is never persisted.
+    pub fn set_encryption_key(&mut self, encoded_key: &str) {
+        self.set_encryption_parameter("key", encoded_key.to_string());
+    }
+
+    /// Whether every encryption stage already has a key
+    pub fn has_encryption_key(&self) -> bool {
+        self.stages
+            .iter()
+            .filter(|stage| stage.stage_type() == &StageType::Encryption)
+            .all(|stage| stage.configuration().parameters.contains_key("key"))
+    }
+
+    fn set_encryption_parameter(&mut self, name: &str, value: String) {
+        for stage in self
+            .stages
+            .iter_mut()
+            .filter(|stage| stage.stage_type() == &StageType::Encryption)
+        {
+            let mut configuration = stage.configuration().clone();
+            configuration.parameters.insert(name.to_string(), value.clone());
+            stage.update_configuration(configuration);
+        }
+    }
+
+    /// Gets the chunk size this pipeline is pinned to, if any
+    ///
+    /// Read from the `chunk_size` configuration key, in bytes; typically
+    /// written by `benchmark --sweep-chunk-size --apply`. Pipelines without
+    /// one use the adaptive chunk size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the stored value is not a valid
+    /// chunk size.
+    pub fn chunk_size(&self) -> Result<Option<ChunkSize>, PipelineError> {
+        let Some(value) = self.configuration.get(CHUNK_SIZE_KEY) else {
+            return Ok(None);
+        };
+        let bytes = value.trim().parse::<usize>().map_err(|_| {
+            PipelineError::InvalidConfiguration(format!(
+                "Invalid {} '{}': expected a byte count",
+                CHUNK_SIZE_KEY, value
+            ))
+        })?;
+        ChunkSize::new(bytes).map(Some)
+    }
+
+    /// Pins this pipeline to `chunk_size`
+    ///
+    /// Stored in the configuration under `chunk_size`; updates the
+    /// `updated_at` timestamp.
+    pub fn set_chunk_size(&mut self, chunk_size: ChunkSize) {
+        self.configuration
+            .insert(CHUNK_SIZE_KEY.to_string(), chunk_size.bytes().to_string());
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Gets the worker count this pipeline runs with unless one is given
+    ///
+    /// Read from the `worker_count` configuration key. Pipelines without one
+    /// size their workers from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the stored value is not a worker
+    /// count from 1 to [`WorkerCount::MAX_WORKERS`].
+    pub fn worker_count(&self) -> Result<Option<WorkerCount>, PipelineError> {
+        let Some(value) = self.configuration.get(WORKER_COUNT_KEY) else {
+            return Ok(None);
+        };
+        match value.trim().parse::<usize>() {
+            Ok(count) if (WorkerCount::MIN_WORKERS..=WorkerCount::MAX_WORKERS).contains(&count) => {
+                Ok(Some(WorkerCount::new(count)))
+            }
+            _ => Err(PipelineError::InvalidConfiguration(format!(
+                "Invalid {} '{}': expected {} to {} workers",
+                WORKER_COUNT_KEY,
+                value,
+                WorkerCount::MIN_WORKERS,
+                WorkerCount::MAX_WORKERS
+            ))),
+        }
+    }
+
+    /// Runs this pipeline with `worker_count` workers unless one is given
+    ///
+    /// Stored in the configuration under `worker_count`; updates the
+    /// `updated_at` timestamp.
+    pub fn set_worker_count(&mut self, worker_count: WorkerCount) {
+        self.configuration
+            .insert(WORKER_COUNT_KEY.to_string(), worker_count.count().to_string());
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Gets the security level and permissions a context needs to run this
+    /// pipeline or restore its output
+    ///
+    /// Read from the `minimum_security_level` and `required_permissions`
+    /// configuration keys; pipelines without them are unrestricted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if a stored value is not a known level
+    /// or permission.
+    pub fn security_policy(&self) -> Result<SecurityPolicy, PipelineError> {
+        SecurityPolicy::from_entries(&self.configuration)
+    }
+
+    /// Sets the security level and permissions a context needs to use this
+    /// pipeline
+    ///
+    /// Stored in the configuration; updates the `updated_at` timestamp.
+    pub fn set_security_policy(&mut self, policy: &SecurityPolicy) {
+        policy.write_entries(&mut self.configuration);
+        self.updated_at = chrono::Utc::now();
+    }
+
     /// Gets the current processing metrics for this pipeline
     ///
     /// Metrics track performance and execution statistics including:
@@ -776,7 +1026,8 @@ impl Pipeline {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - `InvalidConfiguration`: No stages present in pipeline
+    /// - `InvalidConfiguration`: No stages present in pipeline, or an unknown
+    ///   execution topology or security policy is configured
     /// - `IncompatibleStage`: Adjacent stages are incompatible
     ///
     /// # Examples
@@ -804,6 +1055,12 @@ impl Pipeline {
             }
         }
 
+        self.execution_topology()?;
+        self.checksum_algorithm()?;
+        self.chunk_size()?;
+        self.worker_count()?;
+        self.security_policy()?;
+
         Ok(())
     }
 
@@ -838,6 +1095,7 @@ impl Pipeline {
         Ok(Pipeline {
             id: data.id,
             name: data.name,
+            namespace: data.namespace,
             archived: data.archived,
             configuration: data.configuration,
             metrics: data.metrics,
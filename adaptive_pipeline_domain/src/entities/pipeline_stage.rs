@@ -8,7 +8,7 @@
 //! Stage configuration example:
 
 use crate::services::datetime_serde;
-use crate::value_objects::StageId;
+use crate::value_objects::{ContentType, StageId};
 use crate::PipelineError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -258,6 +258,13 @@ impl Default for StageConfiguration {
     }
 }
 
+/// Stage parameter listing content type patterns (see [`ContentType`]) of
+/// files the stage sits out, e.g. `compressed,image/*`
+///
+/// A skipped stage is left out of the file's `.adapipe` header, so restoring
+/// the file does not try to reverse it.
+pub const SKIP_CONTENT_PARAMETER: &str = "skip_content";
+
 /// Core pipeline stage entity representing a single processing step.
 ///
 /// A `PipelineStage` is a domain entity that encapsulates a specific data
@@ -695,6 +702,20 @@ impl PipelineStage {
         }
     }
 
+    /// Checks whether the stage's `skip_content` parameter matches
+    /// `content_type`; stages without the parameter never skip
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the parameter holds a malformed
+    /// pattern.
+    pub fn skips_content(&self, content_type: &ContentType) -> Result<bool, PipelineError> {
+        match self.configuration.parameters.get(SKIP_CONTENT_PARAMETER) {
+            Some(patterns) => content_type.matches_any(patterns),
+            None => Ok(false),
+        }
+    }
+
     /// Validates the stage configuration
     pub fn validate(&self) -> Result<(), PipelineError> {
         if self.name.is_empty() {
@@ -718,6 +739,17 @@ impl PipelineStage {
             }
         }
 
+        if let Some(patterns) = self.configuration.parameters.get(SKIP_CONTENT_PARAMETER) {
+            // Content must never decide whether a file is encrypted
+            if self.stage_type == StageType::Encryption {
+                return Err(PipelineError::InvalidConfiguration(format!(
+                    "Encryption stage '{}' cannot skip content ({})",
+                    self.name, SKIP_CONTENT_PARAMETER
+                )));
+            }
+            ContentType::validate_patterns(patterns)?;
+        }
+
         Ok(())
     }
 }
@@ -868,6 +900,31 @@ mod tests {
         assert_eq!(parsed_type, *original_stage.stage_type());
     }
 
+    #[test]
+    fn test_skip_content_rule() {
+        let mut stage = create_test_stage("compress", StageType::Compression, "zstd");
+        let jpeg = ContentType::detect(b"\xff\xd8\xff\xe0");
+        assert!(!stage.skips_content(&jpeg).unwrap());
+
+        let mut config = stage.configuration().clone();
+        config
+            .parameters
+            .insert(SKIP_CONTENT_PARAMETER.to_string(), "compressed".to_string());
+        stage.update_configuration(config.clone());
+        assert!(stage.validate().is_ok());
+        assert!(stage.skips_content(&jpeg).unwrap());
+        assert!(!stage.skips_content(&ContentType::detect(b"plain text")).unwrap());
+
+        let encryption = PipelineStage::new("encrypt".to_string(), StageType::Encryption, config.clone(), 2).unwrap();
+        assert!(encryption.validate().is_err());
+
+        config
+            .parameters
+            .insert(SKIP_CONTENT_PARAMETER.to_string(), "images".to_string());
+        stage.update_configuration(config);
+        assert!(stage.validate().is_err());
+    }
+
     // Helper function to create test stages
     fn create_test_stage(name: &str, stage_type: StageType, algorithm: &str) -> PipelineStage {
         let config = StageConfiguration {
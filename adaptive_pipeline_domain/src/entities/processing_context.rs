@@ -47,12 +47,24 @@
 //! - Processing metrics and performance data
 //! - Stage-specific results and outputs
 //! - Custom metadata and annotations
+//!
+//! ## Parent and Child Contexts
+//!
+//! Concurrent workers must not share one mutable context, and cloning a
+//! context per chunk silently drops whatever the clone records. Instead the
+//! file-level context acts as a parent and each chunk runs in a child created
+//! with [`ProcessingContext::child`]. Children start with empty metrics and
+//! metadata but share a thread-safe roll-up target with their parent. When a
+//! child is finished with [`ProcessingContext::finalize`], its metrics are
+//! merged into that target, and the parent folds all rolled-up metrics into
+//! its own with [`ProcessingContext::collect_child_metrics`].
 
 use crate::services::datetime_serde;
 use crate::value_objects::{ChunkSize, ProcessingContextId, WorkerCount};
 use crate::{ProcessingMetrics, SecurityContext};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Processing context entity that maintains runtime state during pipeline
 /// execution.
@@ -129,6 +141,9 @@ use std::collections::HashMap;
 pub struct ProcessingContext {
     // Identity fields (always first)
     id: ProcessingContextId,
+    // Identifier of the root context, shared with every child
+    #[serde(default)]
+    run_id: ProcessingContextId,
 
     // Core business fields (alphabetical within group)
     chunk_size: ChunkSize,
@@ -140,6 +155,10 @@ pub struct ProcessingContext {
     stage_results: HashMap<String, String>,
     worker_count: WorkerCount,
 
+    // Roll-up target shared by a parent and all of its children (runtime only)
+    #[serde(skip)]
+    rollup: Arc<Mutex<ProcessingMetrics>>,
+
     // Metadata fields (always last)
     #[serde(with = "datetime_serde")]
     created_at: chrono::DateTime<chrono::Utc>,
@@ -174,9 +193,12 @@ impl ProcessingContext {
     pub fn new(file_size: u64, security_context: SecurityContext) -> Self {
         let now = chrono::Utc::now();
 
+        let id = ProcessingContextId::new();
+
         ProcessingContext {
             // Identity fields
-            id: ProcessingContextId::new(),
+            run_id: id.clone(),
+            id,
 
             // Core business fields (alphabetical)
             chunk_size: ChunkSize::from_mb(1).unwrap_or_else(|_| ChunkSize::default()),
@@ -188,12 +210,72 @@ impl ProcessingContext {
             stage_results: HashMap::new(),
             worker_count: WorkerCount::new(4), // Default to 4 workers
 
+            rollup: Arc::new(Mutex::new(ProcessingMetrics::default())),
+
             // Metadata fields
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Creates a child context for processing a single chunk
+    ///
+    /// The child inherits the parent's configuration (file size, chunk size,
+    /// worker count, security context and run id) but starts with empty metrics,
+    /// metadata and stage results, so concurrent children never contend on
+    /// per-chunk state. All children share the parent's roll-up target;
+    /// children of children roll up to the same root.
+    ///
+    /// # Returns
+    ///
+    /// A new chunk-scoped `ProcessingContext` with its own identifier
+    pub fn child(&self) -> Self {
+        let now = chrono::Utc::now();
+
+        ProcessingContext {
+            id: ProcessingContextId::new(),
+            run_id: self.run_id.clone(),
+            chunk_size: self.chunk_size,
+            file_size: self.file_size,
+            metadata: HashMap::new(),
+            metrics: ProcessingMetrics::default(),
+            processed_bytes: 0,
+            security_context: self.security_context.clone(),
+            stage_results: HashMap::new(),
+            worker_count: self.worker_count,
+            rollup: Arc::clone(&self.rollup),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Finishes a child context, rolling its metrics up to the parent
+    ///
+    /// The child's metrics are merged into the shared roll-up target, where
+    /// per-stage metrics from every child accumulate. Consuming the child
+    /// guarantees each chunk is counted exactly once.
+    pub fn finalize(self) {
+        self.rollup
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .merge(&self.metrics);
+    }
+
+    /// Folds all metrics rolled up by finalized children into this context
+    ///
+    /// Call this on the parent once its workers have completed. The roll-up
+    /// target is drained, so calling it again only picks up children
+    /// finalized since the previous call.
+    ///
+    /// # Side Effects
+    ///
+    /// Updates the `updated_at` timestamp
+    pub fn collect_child_metrics(&mut self) {
+        let rolled_up = std::mem::take(&mut *self.rollup.lock().unwrap_or_else(PoisonError::into_inner));
+        self.metrics.merge(&rolled_up);
+        self.updated_at = chrono::Utc::now();
+    }
+
     /// Gets the unique identifier for this processing context
     ///
     /// # Returns
@@ -203,6 +285,15 @@ impl ProcessingContext {
         &self.id
     }
 
+    /// Gets the identifier of the run this context belongs to
+    ///
+    /// A root context's run id is its own id; children created with
+    /// [`child`](Self::child) share their parent's, so every chunk of one
+    /// file run reports the same value.
+    pub fn run_id(&self) -> &ProcessingContextId {
+        &self.run_id
+    }
+
     /// Gets the total size of the file being processed
     ///
     /// # Returns
@@ -321,6 +412,38 @@ impl ProcessingContext {
         self.updated_at = chrono::Utc::now();
     }
 
+    /// Records one execution of a stage in this context's metrics
+    ///
+    /// Executions of the same stage accumulate, so a parent that collects its
+    /// children's metrics sees per-stage totals across all chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage_name` - Name of the stage that ran
+    /// * `bytes` - Number of input bytes the stage processed
+    /// * `duration` - Time spent in the stage
+    ///
+    /// # Side Effects
+    ///
+    /// Updates the `updated_at` timestamp
+    pub fn record_stage_execution(&mut self, stage_name: &str, bytes: u64, duration: std::time::Duration) {
+        self.metrics.record_stage(stage_name, bytes, duration);
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Records that a chunk of `bytes` finished all stages in this context
+    ///
+    /// # Side Effects
+    ///
+    /// Increments processed bytes, adds the chunk to the metrics and updates
+    /// the `updated_at` timestamp
+    pub fn record_chunk_processed(&mut self, bytes: u64) {
+        self.processed_bytes += bytes;
+        self.metrics.add_bytes_processed(bytes);
+        self.metrics.add_chunks_processed(1);
+        self.updated_at = chrono::Utc::now();
+    }
+
     /// Adds or updates a metadata key-value pair
     ///
     /// # Arguments
@@ -420,3 +543,76 @@ impl ProcessingContext {
         self.updated_at
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::SecurityLevel;
+    use std::time::Duration;
+
+    fn parent() -> ProcessingContext {
+        ProcessingContext::new(4096, SecurityContext::new(None, SecurityLevel::Internal))
+    }
+
+    #[test]
+    fn test_child_inherits_configuration_but_not_state() {
+        let mut parent = parent();
+        parent.add_metadata("encrypted".to_string(), "true".to_string());
+
+        let child = parent.child();
+
+        assert_ne!(child.id(), parent.id());
+        assert_eq!(child.run_id(), parent.id());
+        assert_eq!(child.child().run_id(), parent.run_id());
+        assert_eq!(child.file_size(), parent.file_size());
+        assert_eq!(child.chunk_size(), parent.chunk_size());
+        assert!(child.metadata().is_empty());
+        assert_eq!(child.metrics().chunks_processed(), 0);
+    }
+
+    #[test]
+    fn test_child_metrics_roll_up_to_parent() {
+        let mut parent = parent();
+
+        for _ in 0..4 {
+            let mut child = parent.child();
+            child.record_stage_execution("compression", 1024, Duration::from_millis(2));
+            child.record_chunk_processed(1024);
+            child.finalize();
+        }
+
+        // Nothing is visible until the parent collects
+        assert_eq!(parent.metrics().chunks_processed(), 0);
+
+        parent.collect_child_metrics();
+        assert_eq!(parent.metrics().chunks_processed(), 4);
+        assert_eq!(parent.metrics().bytes_processed(), 4096);
+
+        let stage = &parent.metrics().stage_metrics()["compression"];
+        assert_eq!(stage.bytes_processed, 4096);
+        assert_eq!(stage.processing_time, Duration::from_millis(8));
+    }
+
+    #[test]
+    fn test_concurrent_children_lose_no_metrics() {
+        let mut parent = parent();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                let template = parent.child();
+                scope.spawn(move || {
+                    for _ in 0..16 {
+                        let mut child = template.child();
+                        child.record_stage_execution("checksum", 10, Duration::from_micros(5));
+                        child.record_chunk_processed(10);
+                        child.finalize();
+                    }
+                });
+            }
+        });
+
+        parent.collect_child_metrics();
+        assert_eq!(parent.metrics().chunks_processed(), 128);
+        assert_eq!(parent.metrics().stage_metrics()["checksum"].bytes_processed, 1280);
+    }
+}
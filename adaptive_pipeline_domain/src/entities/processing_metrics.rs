@@ -39,6 +39,7 @@
 //! Metrics are calculated and updated in real-time as processing progresses,
 //! providing immediate feedback on performance characteristics.
 
+use crate::value_objects::ConcurrencySummary;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
@@ -127,6 +128,9 @@ pub struct ProcessingMetrics {
     input_file_checksum: Option<String>,
     output_file_checksum: Option<String>,
     stage_metrics: std::collections::HashMap<String, StageMetrics>,
+    // How the run used its workers, once processing has finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    concurrency: Option<ConcurrencySummary>,
 }
 
 /// Stage-specific metrics entity for detailed performance analysis.
@@ -209,6 +213,7 @@ impl Default for ProcessingMetrics {
             input_file_checksum: None,
             output_file_checksum: None,
             stage_metrics: std::collections::HashMap::new(),
+            concurrency: None,
         }
     }
 }
@@ -281,6 +286,20 @@ impl ProcessingMetrics {
         self.stage_metrics.insert(metrics.stage_name.clone(), metrics);
     }
 
+    /// Accumulates one execution of a stage into its aggregate metrics
+    ///
+    /// Unlike `add_stage_metrics`, which replaces the entry, this adds the
+    /// bytes and time to any existing totals so per-chunk executions sum to
+    /// an accurate per-stage aggregate.
+    pub fn record_stage(&mut self, stage_name: &str, bytes_processed: u64, processing_time: Duration) {
+        let mut sample = StageMetrics::new(stage_name.to_string());
+        sample.update(bytes_processed, processing_time);
+        self.stage_metrics
+            .entry(stage_name.to_string())
+            .and_modify(|existing| existing.merge(&sample))
+            .or_insert(sample);
+    }
+
     /// Gets bytes processed
     pub fn bytes_processed(&self) -> u64 {
         self.bytes_processed
@@ -350,6 +369,16 @@ impl ProcessingMetrics {
         &self.stage_metrics
     }
 
+    /// Records how the run used its workers
+    pub fn set_concurrency(&mut self, concurrency: ConcurrencySummary) {
+        self.concurrency = Some(concurrency);
+    }
+
+    /// Gets how the run used its workers, once recorded
+    pub fn concurrency(&self) -> Option<&ConcurrencySummary> {
+        self.concurrency.as_ref()
+    }
+
     /// Gets input file size in bytes
     pub fn input_file_size_bytes(&self) -> u64 {
         self.input_file_size_bytes
@@ -456,9 +485,12 @@ impl ProcessingMetrics {
         self.error_count += other.error_count;
         self.warning_count += other.warning_count;
 
-        // Merge stage metrics
+        // Merge stage metrics, accumulating stages seen by both sides
         for (stage_name, stage_metrics) in &other.stage_metrics {
-            self.stage_metrics.insert(stage_name.clone(), stage_metrics.clone());
+            self.stage_metrics
+                .entry(stage_name.clone())
+                .and_modify(|existing| existing.merge(stage_metrics))
+                .or_insert_with(|| stage_metrics.clone());
         }
 
         // Recalculate throughput
@@ -492,6 +524,29 @@ impl StageMetrics {
         }
     }
 
+    /// Accumulates another sample of the same stage into these metrics
+    ///
+    /// Bytes, time and errors are summed and throughput is recomputed from
+    /// the totals. Resource usage keeps the peak of the two samples.
+    pub fn merge(&mut self, other: &StageMetrics) {
+        self.bytes_processed += other.bytes_processed;
+        self.processing_time += other.processing_time;
+        self.error_count += other.error_count;
+        self.memory_usage = match (self.memory_usage, other.memory_usage) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.cpu_usage = match (self.cpu_usage, other.cpu_usage) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+
+        let seconds = self.processing_time.as_secs_f64();
+        if seconds > 0.0 {
+            self.throughput = (self.bytes_processed as f64) / seconds;
+        }
+    }
+
     /// Sets memory usage
     pub fn set_memory_usage(&mut self, memory_usage: u64) {
         self.memory_usage = Some(memory_usage);
@@ -0,0 +1,401 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Queued Job Entity
+//!
+//! A request to process one file through a pipeline, submitted to the job
+//! queue and run later by the daemon. The job records who submitted it, in
+//! which namespace, and what became of it.
+//!
+//! ## Lifecycle
+//!
+//! ```text
+//! queued ──► running ──► completed
+//!   │           ├──────► failed
+//!   │           └──────► queued      (daemon stopped; run again later)
+//!   └───────────┴──────► cancelled
+//! ```
+//!
+//! Completed, failed and cancelled jobs are finished and never change
+//! again; resubmit the file to run it again.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::entities::{JobState, QueuedJob};
+//! use adaptive_pipeline_domain::value_objects::Namespace;
+//! use chrono::Utc;
+//!
+//! let mut job = QueuedJob::submit(Namespace::default(), "alice", "compress", "in.bin".into(), "out.adapipe".into());
+//! job.start(Utc::now()).unwrap();
+//! job.complete(Utc::now(), 1000, 250).unwrap();
+//! assert_eq!(job.state(), JobState::Completed);
+//! assert!(job.cancel(Utc::now()).is_err());
+//! ```
+
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::value_objects::{JobId, Namespace};
+use crate::PipelineError;
+
+/// Where a job is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    /// Waiting for a free job slot
+    Queued,
+    /// Being processed
+    Running,
+    /// Finished; the result is recorded
+    Completed,
+    /// Stopped by an error; the error is recorded
+    Failed,
+    /// Stopped on request before it finished
+    Cancelled,
+}
+
+impl JobState {
+    /// Every state, in lifecycle order
+    pub const ALL: [JobState; 5] = [
+        JobState::Queued,
+        JobState::Running,
+        JobState::Completed,
+        JobState::Failed,
+        JobState::Cancelled,
+    ];
+
+    /// Gets the state's lowercase name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Completed => "completed",
+            JobState::Failed => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether the job has finished and will not change again
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobState::Completed | JobState::Failed | JobState::Cancelled)
+    }
+}
+
+impl Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for JobState {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        JobState::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .ok_or_else(|| PipelineError::InvalidParameter(format!("Unknown job state '{}'", s)))
+    }
+}
+
+/// A file submitted for processing through the job queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedJob {
+    id: JobId,
+    namespace: Namespace,
+    submitted_by: String,
+    pipeline: String,
+    input: PathBuf,
+    output: PathBuf,
+    workers: Option<usize>,
+    state: JobState,
+    submitted_at: DateTime<Utc>,
+    started_at: Option<DateTime<Utc>>,
+    finished_at: Option<DateTime<Utc>>,
+    error: Option<String>,
+    input_bytes: Option<u64>,
+    output_bytes: Option<u64>,
+}
+
+impl QueuedJob {
+    /// Queues `input` to be processed through `pipeline` into `output`
+    pub fn submit(
+        namespace: Namespace,
+        submitted_by: impl Into<String>,
+        pipeline: impl Into<String>,
+        input: PathBuf,
+        output: PathBuf,
+    ) -> Self {
+        let id = JobId::new();
+        Self {
+            submitted_at: id.datetime(),
+            id,
+            namespace,
+            submitted_by: submitted_by.into(),
+            pipeline: pipeline.into(),
+            input,
+            output,
+            workers: None,
+            state: JobState::Queued,
+            started_at: None,
+            finished_at: None,
+            error: None,
+            input_bytes: None,
+            output_bytes: None,
+        }
+    }
+
+    /// Runs the job with `workers` workers instead of the pipeline's choice
+    pub fn with_workers(mut self, workers: Option<usize>) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Rebuilds a job from persisted fields
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        id: JobId,
+        namespace: Namespace,
+        submitted_by: String,
+        pipeline: String,
+        input: PathBuf,
+        output: PathBuf,
+        workers: Option<usize>,
+        state: JobState,
+        submitted_at: DateTime<Utc>,
+        started_at: Option<DateTime<Utc>>,
+        finished_at: Option<DateTime<Utc>>,
+        error: Option<String>,
+        input_bytes: Option<u64>,
+        output_bytes: Option<u64>,
+    ) -> Self {
+        Self {
+            id,
+            namespace,
+            submitted_by,
+            pipeline,
+            input,
+            output,
+            workers,
+            state,
+            submitted_at,
+            started_at,
+            finished_at,
+            error,
+            input_bytes,
+            output_bytes,
+        }
+    }
+
+    /// Marks the queued job as running from `at`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` unless the job is queued.
+    pub fn start(&mut self, at: DateTime<Utc>) -> Result<(), PipelineError> {
+        self.expect_state(JobState::Queued, "start")?;
+        self.state = JobState::Running;
+        self.started_at = Some(at);
+        Ok(())
+    }
+
+    /// Records that the running job finished at `at`, having read
+    /// `input_bytes` and written `output_bytes`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` unless the job is running.
+    pub fn complete(&mut self, at: DateTime<Utc>, input_bytes: u64, output_bytes: u64) -> Result<(), PipelineError> {
+        self.expect_state(JobState::Running, "complete")?;
+        self.state = JobState::Completed;
+        self.finished_at = Some(at);
+        self.input_bytes = Some(input_bytes);
+        self.output_bytes = Some(output_bytes);
+        Ok(())
+    }
+
+    /// Records that the running job stopped at `at` with `error`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` unless the job is running.
+    pub fn fail(&mut self, at: DateTime<Utc>, error: impl Into<String>) -> Result<(), PipelineError> {
+        self.expect_state(JobState::Running, "fail")?;
+        self.state = JobState::Failed;
+        self.finished_at = Some(at);
+        self.error = Some(error.into());
+        Ok(())
+    }
+
+    /// Puts the running job back in the queue, e.g. because the daemon
+    /// running it stopped
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` unless the job is running.
+    pub fn requeue(&mut self) -> Result<(), PipelineError> {
+        self.expect_state(JobState::Running, "requeue")?;
+        self.state = JobState::Queued;
+        self.started_at = None;
+        Ok(())
+    }
+
+    /// Cancels the job at `at`; a running job is stopped by the daemon
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the job has already finished.
+    pub fn cancel(&mut self, at: DateTime<Utc>) -> Result<(), PipelineError> {
+        if self.state.is_finished() {
+            return Err(PipelineError::validation_error(format!(
+                "Cannot cancel job {}: it is already {}",
+                self.id, self.state
+            )));
+        }
+        self.state = JobState::Cancelled;
+        self.finished_at = Some(at);
+        Ok(())
+    }
+
+    fn expect_state(&self, expected: JobState, action: &str) -> Result<(), PipelineError> {
+        if self.state != expected {
+            return Err(PipelineError::validation_error(format!(
+                "Cannot {} job {}: it is {}, not {}",
+                action, self.id, self.state, expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Gets the job ID
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Gets the namespace the job runs in
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the principal that submitted the job
+    pub fn submitted_by(&self) -> &str {
+        &self.submitted_by
+    }
+
+    /// Gets the name of the pipeline to run
+    pub fn pipeline(&self) -> &str {
+        &self.pipeline
+    }
+
+    /// Gets the file to process
+    pub fn input(&self) -> &PathBuf {
+        &self.input
+    }
+
+    /// Gets the `.adapipe` file to write
+    pub fn output(&self) -> &PathBuf {
+        &self.output
+    }
+
+    /// Gets the requested worker count, if any
+    pub fn workers(&self) -> Option<usize> {
+        self.workers
+    }
+
+    /// Gets the job's state
+    pub fn state(&self) -> JobState {
+        self.state
+    }
+
+    /// Gets when the job was submitted
+    pub fn submitted_at(&self) -> DateTime<Utc> {
+        self.submitted_at
+    }
+
+    /// Gets when the job last started running
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.started_at
+    }
+
+    /// Gets when the job finished
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+
+    /// Gets why the job failed
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Gets the bytes a completed job read
+    pub fn input_bytes(&self) -> Option<u64> {
+        self.input_bytes
+    }
+
+    /// Gets the bytes a completed job wrote
+    pub fn output_bytes(&self) -> Option<u64> {
+        self.output_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job() -> QueuedJob {
+        QueuedJob::submit(
+            Namespace::default(),
+            "alice",
+            "compress",
+            PathBuf::from("in.bin"),
+            PathBuf::from("out.adapipe"),
+        )
+    }
+
+    #[test]
+    fn test_jobs_move_through_their_lifecycle() {
+        let mut job = job();
+        assert_eq!(job.state(), JobState::Queued);
+        assert!(job.complete(Utc::now(), 1, 1).is_err());
+
+        job.start(Utc::now()).unwrap();
+        job.requeue().unwrap();
+        assert_eq!(job.state(), JobState::Queued);
+        assert!(job.started_at().is_none());
+
+        job.start(Utc::now()).unwrap();
+        job.fail(Utc::now(), "boom").unwrap();
+        assert_eq!(job.state(), JobState::Failed);
+        assert_eq!(job.error(), Some("boom"));
+        assert!(job.start(Utc::now()).is_err());
+        assert!(job.cancel(Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_unfinished_jobs_can_be_cancelled() {
+        let mut queued = job();
+        queued.cancel(Utc::now()).unwrap();
+        assert_eq!(queued.state(), JobState::Cancelled);
+        assert!(queued.finished_at().is_some());
+
+        let mut running = job();
+        running.start(Utc::now()).unwrap();
+        running.cancel(Utc::now()).unwrap();
+        assert!(running.state().is_finished());
+    }
+
+    #[test]
+    fn test_states_parse_from_their_names() {
+        for state in JobState::ALL {
+            assert_eq!(state.as_str().parse::<JobState>().unwrap(), state);
+        }
+        assert!("paused".parse::<JobState>().is_err());
+        assert_eq!(serde_json::to_string(&JobState::Cancelled).unwrap(), "\"cancelled\"");
+    }
+}
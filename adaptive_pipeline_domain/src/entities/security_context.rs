@@ -46,6 +46,7 @@
 //! Each context maintains a unique session for audit trails and tracking.
 
 use crate::services::datetime_serde;
+use crate::value_objects::{ProtectedOperation, Role};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -87,6 +88,14 @@ use uuid::Uuid;
 ///
 ///
 /// ### Audit and Session Management
+///
+/// ## Expiration and Refresh
+///
+/// A context may carry an expiry timestamp (for example the lifetime of the
+/// credential it was derived from). Long-running jobs check
+/// [`SecurityContext::ensure_not_expired`] at stage boundaries and, when the
+/// context has expired, obtain a replacement from a refresh hook and adopt it
+/// with [`SecurityContext::refresh`] instead of failing midway.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityContext {
     user_id: Option<String>,
@@ -99,6 +108,8 @@ pub struct SecurityContext {
     metadata: HashMap<String, String>,
     #[serde(with = "datetime_serde")]
     created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default, with = "datetime_serde::optional")]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Security permission enumeration for fine-grained access control.
@@ -221,6 +232,7 @@ impl Default for SecurityContext {
             security_level: SecurityLevel::Internal,
             metadata: HashMap::new(),
             created_at: chrono::Utc::now(),
+            expires_at: None,
         }
     }
 }
@@ -249,6 +261,13 @@ impl SecurityContext {
         }
     }
 
+    /// Creates a security context carrying the permissions of `role`
+    pub fn for_role(user_id: Option<String>, role: Role, security_level: SecurityLevel) -> Self {
+        let mut context = Self::with_permissions(user_id, role.permissions(), security_level);
+        context.add_metadata("role".to_string(), role.to_string());
+        context
+    }
+
     /// Gets the user ID
     pub fn user_id(&self) -> Option<&str> {
         self.user_id.as_deref()
@@ -294,6 +313,85 @@ impl SecurityContext {
         self.created_at
     }
 
+    /// Gets the expiry timestamp, if the context expires
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at
+    }
+
+    /// Returns the context with an expiry timestamp
+    pub fn with_expiry(mut self, expires_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns the context expiring `ttl` from now
+    pub fn with_ttl(self, ttl: chrono::Duration) -> Self {
+        self.with_expiry(chrono::Utc::now() + ttl)
+    }
+
+    /// Returns the context bound to an existing session, e.g. an API
+    /// session, instead of a fresh one
+    pub fn with_session_id(mut self, session_id: Uuid) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
+    /// Sets or clears the expiry timestamp
+    pub fn set_expires_at(&mut self, expires_at: Option<chrono::DateTime<chrono::Utc>>) {
+        self.expires_at = expires_at;
+    }
+
+    /// Checks if the context has expired
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now())
+    }
+
+    /// Checks if the context is expired at the given instant
+    pub fn is_expired_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Gets the time left before expiry (zero once expired, `None` if the
+    /// context never expires)
+    pub fn time_remaining(&self) -> Option<chrono::Duration> {
+        self.expires_at
+            .map(|expires_at| (expires_at - chrono::Utc::now()).max(chrono::Duration::zero()))
+    }
+
+    /// Fails with `SecurityContextExpired` if the context has expired
+    pub fn ensure_not_expired(&self) -> Result<(), crate::PipelineError> {
+        match self.expires_at {
+            Some(expires_at) if self.is_expired() => Err(crate::PipelineError::security_context_expired(format!(
+                "session {} expired at {}",
+                self.session_id,
+                expires_at.to_rfc3339()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adopts refreshed credentials from `refreshed`
+    ///
+    /// Permissions, security level, encryption key and expiry are taken from
+    /// the refreshed context while the session ID and creation time are kept,
+    /// so audit trails for a long-running job stay continuous. The refreshed
+    /// context must belong to the same user and must not itself be expired.
+    pub fn refresh(&mut self, refreshed: SecurityContext) -> Result<(), crate::PipelineError> {
+        if refreshed.user_id != self.user_id {
+            return Err(crate::PipelineError::SecurityViolation(
+                "Refreshed security context belongs to a different user".to_string(),
+            ));
+        }
+        refreshed.ensure_not_expired()?;
+
+        self.permissions = refreshed.permissions;
+        self.security_level = refreshed.security_level;
+        self.encryption_key_id = refreshed.encryption_key_id;
+        self.expires_at = refreshed.expires_at;
+        self.metadata.extend(refreshed.metadata);
+        Ok(())
+    }
+
     /// Sets the user ID
     pub fn set_user_id(&mut self, user_id: Option<String>) {
         self.user_id = user_id;
@@ -346,6 +444,31 @@ impl SecurityContext {
         self.permissions.contains(permission) || self.permissions.contains(&Permission::Admin)
     }
 
+    /// Checks that the context holds every permission `operation` requires
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityViolation` naming the operation and
+    /// the first missing permission.
+    pub fn authorize(&self, operation: ProtectedOperation) -> Result<(), crate::PipelineError> {
+        match operation
+            .required_permissions()
+            .into_iter()
+            .find(|p| !self.has_permission(p))
+        {
+            None => Ok(()),
+            Some(missing) => Err(crate::PipelineError::security_violation(format!(
+                "Permission denied: {} requires {} permission{}",
+                operation,
+                missing,
+                self.metadata
+                    .get("role")
+                    .map(|role| format!(" (active role: {})", role))
+                    .unwrap_or_default()
+            ))),
+        }
+    }
+
     /// Checks if the context can perform encryption
     pub fn can_encrypt(&self) -> bool {
         self.has_permission(&Permission::Encrypt) || self.has_permission(&Permission::Admin)
@@ -405,6 +528,7 @@ impl SecurityContext {
             security_level: self.security_level.clone(),
             metadata: self.metadata.clone(),
             created_at: self.created_at,
+            expires_at: self.expires_at,
         }
     }
 
@@ -416,6 +540,8 @@ impl SecurityContext {
             ));
         }
 
+        self.ensure_not_expired()?;
+
         if self.integrity_required && self.encryption_key_id.is_none() {
             return Err(crate::PipelineError::SecurityViolation(
                 "Integrity required but no encryption key specified".to_string(),
@@ -454,3 +580,160 @@ impl std::fmt::Display for Permission {
         }
     }
 }
+
+impl SecurityLevel {
+    /// Returns every level, lowest first
+    pub fn all() -> [SecurityLevel; 6] {
+        [
+            SecurityLevel::Public,
+            SecurityLevel::Internal,
+            SecurityLevel::Medium,
+            SecurityLevel::Confidential,
+            SecurityLevel::Secret,
+            SecurityLevel::TopSecret,
+        ]
+    }
+
+    /// Returns the kebab-case name used on the command line and in
+    /// configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityLevel::Public => "public",
+            SecurityLevel::Internal => "internal",
+            SecurityLevel::Medium => "medium",
+            SecurityLevel::Confidential => "confidential",
+            SecurityLevel::Secret => "secret",
+            SecurityLevel::TopSecret => "top-secret",
+        }
+    }
+}
+
+impl std::str::FromStr for SecurityLevel {
+    type Err = crate::PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['_', ' '], "-");
+        SecurityLevel::all()
+            .into_iter()
+            .find(|level| level.as_str() == name || (name == "topsecret" && *level == SecurityLevel::TopSecret))
+            .ok_or_else(|| {
+                crate::PipelineError::invalid_config(format!(
+                    "Unknown security level '{}'. Valid levels: public, internal, medium, confidential, secret, \
+                     top-secret",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+impl Permission {
+    /// Returns the lowercase name used on the command line and in
+    /// configuration; custom permissions are written `custom:<name>`
+    pub fn name(&self) -> String {
+        match self {
+            Permission::Custom(name) => format!("custom:{}", name),
+            other => other.to_string().to_lowercase(),
+        }
+    }
+}
+
+impl std::str::FromStr for Permission {
+    type Err = crate::PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        if let Some(custom) = name.strip_prefix("custom:").map(str::trim) {
+            if !custom.is_empty() {
+                return Ok(Permission::Custom(custom.to_string()));
+            }
+        }
+        match name.to_lowercase().as_str() {
+            "read" => Ok(Permission::Read),
+            "write" => Ok(Permission::Write),
+            "execute" => Ok(Permission::Execute),
+            "admin" => Ok(Permission::Admin),
+            "encrypt" => Ok(Permission::Encrypt),
+            "decrypt" => Ok(Permission::Decrypt),
+            "compress" => Ok(Permission::Compress),
+            "decompress" => Ok(Permission::Decompress),
+            _ => Err(crate::PipelineError::invalid_config(format!(
+                "Unknown permission '{}'. Valid permissions: read, write, execute, admin, encrypt, decrypt, \
+                 compress, decompress, custom:<name>",
+                name
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_context_authorizes_operations() {
+        let operator = SecurityContext::for_role(Some("alice".to_string()), Role::Operator, SecurityLevel::Internal);
+        assert!(operator.authorize(ProtectedOperation::RestoreFile).is_ok());
+
+        let err = operator.authorize(ProtectedOperation::DeletePipeline).unwrap_err();
+        assert!(matches!(err, crate::PipelineError::SecurityViolation(_)));
+        assert!(err.to_string().contains("active role: operator"));
+
+        let admin = SecurityContext::for_role(None, Role::Admin, SecurityLevel::Internal);
+        assert!(admin.authorize(ProtectedOperation::DeletePipeline).is_ok());
+    }
+
+    #[test]
+    fn test_level_and_permission_names_round_trip() {
+        for level in SecurityLevel::all() {
+            assert_eq!(level.as_str().parse::<SecurityLevel>().unwrap(), level);
+        }
+        assert_eq!("Top Secret".parse::<SecurityLevel>().unwrap(), SecurityLevel::TopSecret);
+        assert!("classified".parse::<SecurityLevel>().is_err());
+
+        for permission in [Permission::Decrypt, Permission::Custom("finance".to_string())] {
+            assert_eq!(permission.name().parse::<Permission>().unwrap(), permission);
+        }
+        assert_eq!("READ".parse::<Permission>().unwrap(), Permission::Read);
+        assert!("custom:".parse::<Permission>().is_err());
+    }
+
+    #[test]
+    fn test_context_without_expiry_never_expires() {
+        let context = SecurityContext::new(None, SecurityLevel::Internal);
+        assert!(!context.is_expired());
+        assert!(context.time_remaining().is_none());
+        assert!(context.ensure_not_expired().is_ok());
+    }
+
+    #[test]
+    fn test_expired_context_fails_validation() {
+        let context = SecurityContext::new(None, SecurityLevel::Internal)
+            .with_expiry(chrono::Utc::now() - chrono::Duration::seconds(1));
+
+        assert!(context.is_expired());
+        assert_eq!(context.time_remaining(), Some(chrono::Duration::zero()));
+        assert!(matches!(
+            context.validate(),
+            Err(crate::PipelineError::SecurityContextExpired(_))
+        ));
+    }
+
+    #[test]
+    fn test_refresh_keeps_session_and_rejects_other_users() {
+        let mut context = SecurityContext::new(Some("alice".to_string()), SecurityLevel::Internal)
+            .with_expiry(chrono::Utc::now() - chrono::Duration::seconds(1));
+        let session_id = context.session_id();
+
+        let other_user = SecurityContext::new(Some("mallory".to_string()), SecurityLevel::Secret)
+            .with_ttl(chrono::Duration::hours(1));
+        assert!(context.refresh(other_user).is_err());
+        assert!(context.is_expired());
+
+        let renewed = SecurityContext::new(Some("alice".to_string()), SecurityLevel::Confidential)
+            .with_ttl(chrono::Duration::hours(1));
+        context.refresh(renewed).unwrap();
+        assert!(!context.is_expired());
+        assert_eq!(context.session_id(), session_id);
+        assert_eq!(*context.security_level(), SecurityLevel::Confidential);
+    }
+}
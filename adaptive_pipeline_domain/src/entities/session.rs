@@ -0,0 +1,320 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Session Entity
+//!
+//! An authenticated session of one user in one namespace, as used by the API
+//! interfaces: a [`SessionId`] identifying it, the [`UserId`] it acts for and
+//! the window in which its bearer token is accepted.
+//!
+//! ## Tokens
+//!
+//! [`Session::issue`] returns the session with its bearer token, formatted
+//! `<session-id>.<secret>`. Only a SHA-256 digest of the secret is kept, so
+//! a stored session cannot be turned back into a token; [`Session::verify`]
+//! compares digests in constant time.
+//!
+//! ## Lifecycle
+//!
+//! A session is usable from issue until it expires or is revoked, whichever
+//! comes first. Neither can be undone: a user whose session ended is issued
+//! a new one.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::entities::Session;
+//! use adaptive_pipeline_domain::value_objects::{Namespace, UserId};
+//!
+//! let user = UserId::parse("alice").unwrap();
+//! let (mut session, token) = Session::issue(user, Namespace::default(), chrono::Duration::hours(1)).unwrap();
+//! assert!(session.verify(&token).is_ok());
+//!
+//! session.revoke(chrono::Utc::now());
+//! assert!(session.verify(&token).is_err());
+//! ```
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::services::constant_time::constant_time_eq_str;
+use crate::value_objects::{Namespace, SessionId, UserId};
+use crate::PipelineError;
+
+/// Lifetime of a session when the deployment does not configure one
+pub const DEFAULT_SESSION_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+/// Longest a session may last; session IDs older than this do not validate
+pub const MAX_SESSION_TTL: chrono::Duration = chrono::Duration::days(30);
+
+/// Requests a session may make per minute when the deployment does not
+/// configure a limit
+pub const DEFAULT_SESSION_REQUESTS_PER_MINUTE: u32 = 600;
+
+/// Bytes of randomness in a token's secret
+const TOKEN_SECRET_BYTES: usize = 32;
+
+/// An authenticated user session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    id: SessionId,
+    user_id: UserId,
+    namespace: Namespace,
+    token_hash: String,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    /// Starts a session for `user_id` in `namespace` lasting `ttl`
+    ///
+    /// Returns the session and its bearer token. The token is not stored
+    /// anywhere; it must be handed to the user now.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if `ttl` is not positive or exceeds
+    /// [`MAX_SESSION_TTL`].
+    pub fn issue(
+        user_id: UserId,
+        namespace: Namespace,
+        ttl: chrono::Duration,
+    ) -> Result<(Self, String), PipelineError> {
+        if ttl <= chrono::Duration::zero() || ttl > MAX_SESSION_TTL {
+            return Err(PipelineError::invalid_config(format!(
+                "Session lifetime must be between 1 second and {} days",
+                MAX_SESSION_TTL.num_days()
+            )));
+        }
+
+        let id = SessionId::new();
+        let secret = hex::encode(rand::random::<[u8; TOKEN_SECRET_BYTES]>());
+        let issued_at = id.datetime();
+        let session = Self {
+            token_hash: Self::hash_secret(&secret),
+            id: id.clone(),
+            user_id,
+            namespace,
+            issued_at,
+            expires_at: issued_at + ttl,
+            last_seen_at: issued_at,
+            revoked_at: None,
+        };
+        Ok((session, format!("{}.{}", id, secret)))
+    }
+
+    /// Rebuilds a session from persisted fields
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_parts(
+        id: SessionId,
+        user_id: UserId,
+        namespace: Namespace,
+        token_hash: String,
+        issued_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        last_seen_at: DateTime<Utc>,
+        revoked_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            namespace,
+            token_hash,
+            issued_at,
+            expires_at,
+            last_seen_at,
+            revoked_at,
+        }
+    }
+
+    /// Reads the session ID out of a bearer token without verifying it
+    ///
+    /// # Errors
+    ///
+    /// Returns `SecurityViolation` if the token is not
+    /// `<session-id>.<secret>`.
+    pub fn token_session_id(token: &str) -> Result<SessionId, PipelineError> {
+        token
+            .trim()
+            .split_once('.')
+            .and_then(|(id, _)| SessionId::from_string(id).ok())
+            .ok_or_else(|| PipelineError::security_violation("Permission denied: malformed session token"))
+    }
+
+    /// Checks that `token` is this session's and that the session is still
+    /// usable now
+    ///
+    /// # Errors
+    ///
+    /// Returns `SecurityViolation` for a token of another session, a wrong
+    /// secret or a revoked session, and `SecurityContextExpired` once the
+    /// session has expired.
+    pub fn verify(&self, token: &str) -> Result<(), PipelineError> {
+        self.verify_at(token, Utc::now())
+    }
+
+    /// [`Session::verify`] as of `now`
+    pub fn verify_at(&self, token: &str, now: DateTime<Utc>) -> Result<(), PipelineError> {
+        let secret = match token.trim().split_once('.') {
+            Some((id, secret)) if id == self.id.to_string() => secret,
+            _ => {
+                return Err(PipelineError::security_violation(
+                    "Permission denied: invalid session token",
+                ))
+            }
+        };
+        if !constant_time_eq_str(&Self::hash_secret(secret), &self.token_hash) {
+            return Err(PipelineError::security_violation(
+                "Permission denied: invalid session token",
+            ));
+        }
+        self.ensure_active_at(now)
+    }
+
+    /// Checks that the session is neither revoked nor expired as of `now`
+    ///
+    /// # Errors
+    ///
+    /// See [`Session::verify`].
+    pub fn ensure_active_at(&self, now: DateTime<Utc>) -> Result<(), PipelineError> {
+        if let Some(revoked_at) = self.revoked_at {
+            return Err(PipelineError::security_violation(format!(
+                "Permission denied: session {} was revoked at {}",
+                self.id,
+                revoked_at.to_rfc3339()
+            )));
+        }
+        if self.is_expired_at(now) {
+            return Err(PipelineError::security_context_expired(format!(
+                "Permission denied: session {} expired at {}",
+                self.id,
+                self.expires_at.to_rfc3339()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether the session has expired as of `now`
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether the session is neither revoked nor expired as of `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.ensure_active_at(now).is_ok()
+    }
+
+    /// Records a request made with the session at `at`
+    pub fn touch(&mut self, at: DateTime<Utc>) {
+        self.last_seen_at = self.last_seen_at.max(at);
+    }
+
+    /// Ends the session at `at`; revoking it again keeps the first time
+    pub fn revoke(&mut self, at: DateTime<Utc>) {
+        self.revoked_at.get_or_insert(at);
+    }
+
+    /// Gets the session ID
+    pub fn id(&self) -> &SessionId {
+        &self.id
+    }
+
+    /// Gets the user the session acts for
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    /// Gets the namespace the session is scoped to
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the hex SHA-256 digest of the token's secret
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    /// Gets when the session was issued
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+
+    /// Gets when the session expires
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// Gets when a request last used the session
+    pub fn last_seen_at(&self) -> DateTime<Utc> {
+        self.last_seen_at
+    }
+
+    /// Gets when the session was revoked, if it was
+    pub fn revoked_at(&self) -> Option<DateTime<Utc>> {
+        self.revoked_at
+    }
+
+    fn hash_secret(secret: &str) -> String {
+        hex::encode(Sha256::digest(secret.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(ttl: chrono::Duration) -> (Session, String) {
+        Session::issue(UserId::parse("alice").unwrap(), Namespace::default(), ttl).unwrap()
+    }
+
+    #[test]
+    fn test_token_verifies_only_against_its_session() {
+        let (session, token) = issue(chrono::Duration::hours(1));
+        let (other, other_token) = issue(chrono::Duration::hours(1));
+
+        assert!(session.verify(&token).is_ok());
+        assert_eq!(Session::token_session_id(&token).unwrap(), *session.id());
+        assert!(!session.token_hash().contains(token.split_once('.').unwrap().1));
+
+        assert!(session.verify(&other_token).is_err());
+        let (id, _) = token.split_once('.').unwrap();
+        assert!(session.verify(&format!("{}.{}", id, "0".repeat(64))).is_err());
+        assert!(other.verify(&token).is_err());
+        assert!(Session::token_session_id("not-a-token").is_err());
+    }
+
+    #[test]
+    fn test_expiry_and_revocation_end_the_session() {
+        let (mut session, token) = issue(chrono::Duration::minutes(5));
+        let later = session.issued_at() + chrono::Duration::minutes(5);
+
+        let expired = session.verify_at(&token, later).unwrap_err();
+        assert!(matches!(expired, PipelineError::SecurityContextExpired(_)));
+        assert!(session.is_active_at(later - chrono::Duration::seconds(1)));
+
+        let revoked_at = session.issued_at() + chrono::Duration::minutes(1);
+        session.revoke(revoked_at);
+        session.revoke(revoked_at + chrono::Duration::minutes(1));
+        assert_eq!(session.revoked_at(), Some(revoked_at));
+        assert!(matches!(
+            session.verify(&token).unwrap_err(),
+            PipelineError::SecurityViolation(_)
+        ));
+    }
+
+    #[test]
+    fn test_lifetime_is_bounded() {
+        let user = UserId::parse("alice").unwrap();
+        assert!(Session::issue(user.clone(), Namespace::default(), chrono::Duration::zero()).is_err());
+        assert!(Session::issue(
+            user,
+            Namespace::default(),
+            MAX_SESSION_TTL + chrono::Duration::seconds(1)
+        )
+        .is_err());
+    }
+}
@@ -97,6 +97,9 @@
 //! - **Document Errors**: Document which errors can be returned from functions
 //! - **Test Error Paths**: Ensure error handling paths are tested
 
+// The module docs sketch how error paths are tested
+#![allow(clippy::test_attr_in_doctest)]
+
 mod pipeline_error;
 
 pub use pipeline_error::PipelineError;
@@ -45,6 +45,7 @@
 //!
 //! #### Security Errors
 //! - **SecurityViolation**: Access control and permission violations
+//! - **SecurityContextExpired**: Credentials expired during processing
 //! - **EncryptionError**: Cryptographic operation failures
 //! - **IntegrityError**: Data tampering or corruption detection
 //!
@@ -52,13 +53,18 @@
 //! - **IoError**: File system and network I/O failures
 //! - **DatabaseError**: Database operation failures
 //! - **ResourceExhausted**: Memory, disk space, or other resource limitations
+//! - **QuotaExceeded**: Configured namespace limits reached (input size,
+//!   concurrent jobs, daily output)
 //! - **TimeoutError**: Operation timeout failures
+//! - **StageTimeout**: A stage exceeded its per-stage or per-chunk deadline
+//! - **StagePanicked**: A stage service panicked while processing a chunk
 //!
 //! #### System Errors
 //! - **InternalError**: Unexpected system failures
 //! - **PluginError**: Plugin loading or execution failures
 //! - **MetricsError**: Metrics collection and reporting failures
 //! - **Cancelled**: User or system-initiated operation cancellation
+//! - **PartialSuccess**: A batch finished but some of its items failed
 //!
 //! ## Error Handling Patterns
 //!
@@ -87,6 +93,7 @@
 //! - **TimeoutError**: Network or I/O timeouts
 //! - **ResourceExhausted**: Temporary resource limitations
 //! - **IoError**: Transient file system issues
+//! - **SecurityContextExpired**: Retry after re-authenticating
 //!
 //! ### Non-Recoverable Errors
 //!
@@ -95,6 +102,7 @@
 //! - **SecurityViolation**: Access control violations
 //! - **InvalidConfiguration**: Malformed configuration data
 //! - **IntegrityError**: Data corruption or tampering
+//! - **StageTimeout**: A stage that hung on a chunk will hang on a retry too
 //!
 //! ## Integration with External Systems
 //!
@@ -153,6 +161,9 @@ pub enum PipelineError {
     #[error("Incompatible stage: {0}")]
     IncompatibleStage(String),
 
+    #[error("Unsupported operation: {0}")]
+    UnsupportedOperation(String),
+
     #[error("Invalid chunk: {0}")]
     InvalidChunk(String),
 
@@ -171,9 +182,15 @@ pub enum PipelineError {
     #[error("Security violation: {0}")]
     SecurityViolation(String),
 
+    #[error("Security context expired: {0}")]
+    SecurityContextExpired(String),
+
     #[error("Resource exhausted: {0}")]
     ResourceExhausted(String),
 
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("IO error: {0}")]
     IoError(String),
 
@@ -192,9 +209,18 @@ pub enum PipelineError {
     #[error("Timeout error: {0}")]
     TimeoutError(String),
 
+    #[error("Stage timeout: {0}")]
+    StageTimeout(String),
+
+    #[error("Stage panicked: {0}")]
+    StagePanicked(String),
+
     #[error("Cancelled: {0}")]
     Cancelled(String),
 
+    #[error("Partial success: {0}")]
+    PartialSuccess(String),
+
     #[error("Pipeline not found: {0}")]
     PipelineNotFound(String),
 
@@ -211,6 +237,11 @@ impl PipelineError {
         Self::InvalidConfiguration(msg.into())
     }
 
+    /// Creates a new unsupported operation error
+    pub fn unsupported_operation(msg: impl Into<String>) -> Self {
+        Self::UnsupportedOperation(msg.into())
+    }
+
     /// Creates a new processing error
     pub fn processing_failed(msg: impl Into<String>) -> Self {
         Self::ProcessingFailed(msg.into())
@@ -221,11 +252,21 @@ impl PipelineError {
         Self::SecurityViolation(msg.into())
     }
 
+    /// Creates a new security context expired error
+    pub fn security_context_expired(msg: impl Into<String>) -> Self {
+        Self::SecurityContextExpired(msg.into())
+    }
+
     /// Creates a new resource exhausted error
     pub fn resource_exhausted(msg: impl Into<String>) -> Self {
         Self::ResourceExhausted(msg.into())
     }
 
+    /// Creates a new quota exceeded error
+    pub fn quota_exceeded(msg: impl Into<String>) -> Self {
+        Self::QuotaExceeded(msg.into())
+    }
+
     /// Creates a new IO error
     pub fn io_error(msg: impl Into<String>) -> Self {
         Self::IoError(msg.into())
@@ -246,6 +287,16 @@ impl PipelineError {
         Self::MetricsError(msg.into())
     }
 
+    /// Creates a new stage timeout error
+    pub fn stage_timeout(msg: impl Into<String>) -> Self {
+        Self::StageTimeout(msg.into())
+    }
+
+    /// Creates a new stage panic error
+    pub fn stage_panicked(msg: impl Into<String>) -> Self {
+        Self::StagePanicked(msg.into())
+    }
+
     /// Creates a new validation error
     pub fn validation_error(msg: impl Into<String>) -> Self {
         Self::ValidationError(msg.into())
@@ -261,11 +312,19 @@ impl PipelineError {
         Self::Cancelled(msg.into())
     }
 
+    /// Creates an error for a batch in which some items failed
+    pub fn partial_success(msg: impl Into<String>) -> Self {
+        Self::PartialSuccess(msg.into())
+    }
+
     /// Checks if the error is recoverable
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
-            PipelineError::TimeoutError(_) | PipelineError::ResourceExhausted(_) | PipelineError::IoError(_)
+            PipelineError::TimeoutError(_)
+                | PipelineError::ResourceExhausted(_)
+                | PipelineError::IoError(_)
+                | PipelineError::SecurityContextExpired(_)
         )
     }
 
@@ -273,7 +332,10 @@ impl PipelineError {
     pub fn is_security_error(&self) -> bool {
         matches!(
             self,
-            PipelineError::SecurityViolation(_) | PipelineError::EncryptionError(_) | PipelineError::IntegrityError(_)
+            PipelineError::SecurityViolation(_)
+                | PipelineError::SecurityContextExpired(_)
+                | PipelineError::EncryptionError(_)
+                | PipelineError::IntegrityError(_)
         )
     }
 
@@ -284,20 +346,26 @@ impl PipelineError {
             PipelineError::MissingParameter(_) => "configuration",
             PipelineError::InvalidParameter(_) => "configuration",
             PipelineError::IncompatibleStage(_) => "configuration",
+            PipelineError::UnsupportedOperation(_) => "configuration",
             PipelineError::InvalidChunk(_) => "data",
             PipelineError::ProcessingFailed(_) => "processing",
             PipelineError::CompressionError(_) => "compression",
             PipelineError::EncryptionError(_) => "encryption",
             PipelineError::IntegrityError(_) => "integrity",
             PipelineError::SecurityViolation(_) => "security",
+            PipelineError::SecurityContextExpired(_) => "security",
             PipelineError::ResourceExhausted(_) => "resource",
+            PipelineError::QuotaExceeded(_) => "quota",
             PipelineError::IoError(_) => "io",
             PipelineError::DatabaseError(_) => "database",
             PipelineError::SerializationError(_) => "serialization",
             PipelineError::ValidationError(_) => "validation",
             PipelineError::PluginError(_) => "plugin",
             PipelineError::TimeoutError(_) => "timeout",
+            PipelineError::StageTimeout(_) => "timeout",
+            PipelineError::StagePanicked(_) => "panic",
             PipelineError::Cancelled(_) => "cancellation",
+            PipelineError::PartialSuccess(_) => "partial_success",
             PipelineError::PipelineNotFound(_) => "pipeline",
             PipelineError::InternalError(_) => "internal",
             PipelineError::MetricsError(_) => "metrics",
@@ -50,6 +50,8 @@
 //! - `ChunkProcessed`: Individual data chunk processed
 //! - `MetricsUpdated`: Performance metrics updated
 //! - `SecurityViolation`: Security policy violation detected
+//! - `PermissionDenied`: Security context does not satisfy a pipeline's
+//!   security policy
 //! - `ResourceExhausted`: System resource limits reached
 //!
 //! ## Event Structure
@@ -152,9 +154,37 @@ pub enum PipelineEvent {
     ChunkProcessed(ChunkProcessedEvent),
     MetricsUpdated(MetricsUpdatedEvent),
     SecurityViolation(SecurityViolationEvent),
+    SecurityContextExpired(SecurityContextExpiredEvent),
+    PermissionDenied(PermissionDeniedEvent),
     ResourceExhausted(ResourceExhaustedEvent),
 }
 
+impl PipelineEvent {
+    /// Name of the event kind, as stored alongside the event
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::PipelineCreated(_) => "PipelineCreated",
+            Self::PipelineUpdated(_) => "PipelineUpdated",
+            Self::PipelineDeleted(_) => "PipelineDeleted",
+            Self::ProcessingStarted(_) => "ProcessingStarted",
+            Self::ProcessingCompleted(_) => "ProcessingCompleted",
+            Self::ProcessingFailed(_) => "ProcessingFailed",
+            Self::ProcessingPaused(_) => "ProcessingPaused",
+            Self::ProcessingResumed(_) => "ProcessingResumed",
+            Self::ProcessingCancelled(_) => "ProcessingCancelled",
+            Self::StageStarted(_) => "StageStarted",
+            Self::StageCompleted(_) => "StageCompleted",
+            Self::StageFailed(_) => "StageFailed",
+            Self::ChunkProcessed(_) => "ChunkProcessed",
+            Self::MetricsUpdated(_) => "MetricsUpdated",
+            Self::SecurityViolation(_) => "SecurityViolation",
+            Self::SecurityContextExpired(_) => "SecurityContextExpired",
+            Self::PermissionDenied(_) => "PermissionDenied",
+            Self::ResourceExhausted(_) => "ResourceExhausted",
+        }
+    }
+}
+
 /// Base event trait
 pub trait DomainEvent {
     fn event_id(&self) -> Uuid;
@@ -369,6 +399,43 @@ pub struct SecurityViolationEvent {
     pub version: u64,
 }
 
+/// Security context expired event
+///
+/// Raised when a job reaches a stage boundary with an expired security
+/// context, before any refresh is attempted. Daemon-mode hosts subscribe to
+/// it to re-authenticate rather than letting the job fail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityContextExpiredEvent {
+    pub event_id: Uuid,
+    pub pipeline_id: Uuid,
+    pub processing_id: Option<Uuid>,
+    pub session_id: Uuid,
+    pub user_id: Option<String>,
+    pub stage_name: Option<String>,
+    #[serde(with = "datetime_serde::optional")]
+    pub expired_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(with = "datetime_serde")]
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    pub version: u64,
+}
+
+/// Permission denied event
+///
+/// Raised when a security context does not satisfy a pipeline's security
+/// policy, before any processing or restoration starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionDeniedEvent {
+    pub event_id: Uuid,
+    pub pipeline_id: Uuid,
+    pub session_id: Uuid,
+    pub user_id: Option<String>,
+    pub operation: String,
+    pub reason: String,
+    #[serde(with = "datetime_serde")]
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    pub version: u64,
+}
+
 /// Resource exhausted event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceExhaustedEvent {
@@ -448,6 +515,42 @@ impl DomainEvent for ProcessingCompletedEvent {
     }
 }
 
+impl DomainEvent for SecurityContextExpiredEvent {
+    fn event_id(&self) -> Uuid {
+        self.event_id
+    }
+    fn aggregate_id(&self) -> Uuid {
+        self.pipeline_id
+    }
+    fn event_type(&self) -> &'static str {
+        "SecurityContextExpired"
+    }
+    fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.occurred_at
+    }
+    fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl DomainEvent for PermissionDeniedEvent {
+    fn event_id(&self) -> Uuid {
+        self.event_id
+    }
+    fn aggregate_id(&self) -> Uuid {
+        self.pipeline_id
+    }
+    fn event_type(&self) -> &'static str {
+        "PermissionDenied"
+    }
+    fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.occurred_at
+    }
+    fn version(&self) -> u64 {
+        self.version
+    }
+}
+
 // Factory functions for creating events
 impl PipelineCreatedEvent {
     pub fn new(pipeline_id: Uuid, pipeline_name: String, stage_count: usize, created_by: Option<String>) -> Self {
@@ -463,6 +566,18 @@ impl PipelineCreatedEvent {
     }
 }
 
+impl PipelineDeletedEvent {
+    pub fn new(pipeline_id: Uuid, deleted_by: Option<String>) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            pipeline_id,
+            deleted_by,
+            occurred_at: chrono::Utc::now(),
+            version: 1,
+        }
+    }
+}
+
 impl ProcessingStartedEvent {
     pub fn new(
         pipeline_id: Uuid,
@@ -521,3 +636,34 @@ impl SecurityViolationEvent {
         }
     }
 }
+
+impl SecurityContextExpiredEvent {
+    pub fn new(pipeline_id: Uuid, security_context: &SecurityContext, stage_name: Option<String>) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            pipeline_id,
+            processing_id: None,
+            session_id: security_context.session_id(),
+            user_id: security_context.user_id().map(str::to_string),
+            stage_name,
+            expired_at: security_context.expires_at(),
+            occurred_at: chrono::Utc::now(),
+            version: 1,
+        }
+    }
+}
+
+impl PermissionDeniedEvent {
+    pub fn new(pipeline_id: Uuid, security_context: &SecurityContext, operation: &str, reason: String) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            pipeline_id,
+            session_id: security_context.session_id(),
+            user_id: security_context.user_id().map(str::to_string),
+            operation: operation.to_string(),
+            reason,
+            occurred_at: chrono::Utc::now(),
+            version: 1,
+        }
+    }
+}
@@ -93,8 +93,24 @@
 //! - Audit sensitive operations
 //! - Use parameterized queries in implementations
 
+pub mod chunk_size_history_repository;
+pub mod idempotency_repository;
+pub mod job_repository;
 pub mod pipeline_repository;
+pub mod role_repository;
+pub mod running_job_repository;
+pub mod session_repository;
 pub mod stage_executor;
+pub mod unit_of_work;
+pub mod usage_repository;
 
+pub use chunk_size_history_repository::ChunkSizeHistoryRepository;
+pub use idempotency_repository::IdempotencyRepository;
+pub use job_repository::JobRepository;
 pub use pipeline_repository::PipelineRepository;
+pub use role_repository::RoleRepository;
+pub use running_job_repository::RunningJobRepository;
+pub use session_repository::SessionRepository;
 pub use stage_executor::StageExecutor;
+pub use unit_of_work::UnitOfWork;
+pub use usage_repository::UsageRepository;
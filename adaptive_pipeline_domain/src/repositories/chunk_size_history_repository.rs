@@ -0,0 +1,41 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Size History Repository Interface
+//!
+//! Persistence contract for the throughput each pipeline achieved at each
+//! chunk size. Implementations accumulate totals per pipeline, storage type
+//! and chunk size; chunk-size selection reads them back through
+//! [`ChunkSize::learned_for_file_size`](crate::value_objects::ChunkSize::learned_for_file_size).
+
+use crate::value_objects::{ChunkSize, ChunkThroughput, PipelineId};
+use crate::PipelineError;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Repository interface for per-pipeline chunk-size throughput history
+#[async_trait]
+pub trait ChunkSizeHistoryRepository: Send + Sync {
+    /// Adds one successful run to the totals for its pipeline, storage type
+    /// and chunk size
+    async fn record_run(
+        &self,
+        pipeline_id: &PipelineId,
+        storage_type: &str,
+        chunk_size: ChunkSize,
+        bytes_processed: u64,
+        duration: Duration,
+    ) -> Result<(), PipelineError>;
+
+    /// Gets the totals for every chunk size the pipeline has run with on
+    /// `storage_type`, empty if it never has
+    async fn history(
+        &self,
+        pipeline_id: &PipelineId,
+        storage_type: &str,
+    ) -> Result<Vec<ChunkThroughput>, PipelineError>;
+}
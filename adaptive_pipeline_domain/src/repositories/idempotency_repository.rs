@@ -0,0 +1,32 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Idempotency Repository Interface
+//!
+//! Persistence contract for idempotency keys. Implementations store one
+//! record per namespace and key; the first completed request wins.
+
+use crate::value_objects::{IdempotencyKey, IdempotencyRecord, Namespace};
+use crate::PipelineError;
+use async_trait::async_trait;
+
+/// Repository interface for completed idempotent requests
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// Gets the record bound to `key` in `namespace`, if any
+    async fn find(
+        &self,
+        namespace: &Namespace,
+        key: &IdempotencyKey,
+    ) -> Result<Option<IdempotencyRecord>, PipelineError>;
+
+    /// Stores `record`, keeping the existing record if the key is already
+    /// bound
+    ///
+    /// Returns whether `record` was stored.
+    async fn save(&self, record: &IdempotencyRecord) -> Result<bool, PipelineError>;
+}
@@ -0,0 +1,62 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Job Repository Interface
+//!
+//! Persistence contract for the job queue (see `QueuedJob`). Submitting
+//! processes and the daemon share the queue through the store, so state
+//! changes are conditional: [`JobRepository::claim_next`] hands each queued
+//! job to one runner, and [`JobRepository::update`] only applies if nobody
+//! changed the job's state in the meantime, e.g. by cancelling it.
+//!
+//! Only one daemon may run a namespace's jobs, since a starting daemon
+//! requeues the jobs left running. Daemons hold a lease on their namespace
+//! ([`JobRepository::acquire_lease`]) that they renew while they run.
+
+use crate::entities::{JobState, QueuedJob};
+use crate::value_objects::{JobId, Namespace};
+use crate::PipelineError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository interface for queued jobs
+///
+/// Implementations must be thread-safe (`Send + Sync`) so they can be shared
+/// between the daemon's job runners.
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    /// Stores a newly submitted job
+    async fn enqueue(&self, job: &QueuedJob) -> Result<(), PipelineError>;
+
+    /// Finds a job by ID, in any namespace and state
+    async fn find_by_id(&self, id: &JobId) -> Result<Option<QueuedJob>, PipelineError>;
+
+    /// Lists the jobs in `namespace`, oldest first
+    async fn list(&self, namespace: &Namespace) -> Result<Vec<QueuedJob>, PipelineError>;
+
+    /// Starts the oldest queued job in `namespace` at `at` and returns it,
+    /// or `None` if nothing is queued
+    async fn claim_next(&self, namespace: &Namespace, at: DateTime<Utc>) -> Result<Option<QueuedJob>, PipelineError>;
+
+    /// Stores `job` if its stored state is still `expected`, returning
+    /// whether it was stored
+    async fn update(&self, job: &QueuedJob, expected: JobState) -> Result<bool, PipelineError>;
+
+    /// Takes or renews `holder`'s lease on running the jobs of `namespace`
+    /// until `expires_at`, returning `false` if another holder's lease is
+    /// still valid at `now`
+    async fn acquire_lease(
+        &self,
+        namespace: &Namespace,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, PipelineError>;
+
+    /// Gives up `holder`'s lease on `namespace`, if it still holds it
+    async fn release_lease(&self, namespace: &Namespace, holder: &str) -> Result<(), PipelineError>;
+}
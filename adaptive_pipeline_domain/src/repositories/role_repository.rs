@@ -0,0 +1,53 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Role Repository Interface
+//!
+//! Persistence contract for role assignments used by role-based access
+//! control. Each principal holds at most one role per namespace; assigning a
+//! new role replaces the previous one in that namespace.
+//!
+//! The application layer resolves the active role for a principal through
+//! this repository before gating protected operations (see
+//! `ProtectedOperation`).
+
+use crate::value_objects::{Namespace, Role, RoleAssignment};
+use crate::PipelineError;
+use async_trait::async_trait;
+
+/// Repository interface for role assignments
+///
+/// Implementations must be thread-safe (`Send + Sync`) so they can be shared
+/// between the CLI and long-running services.
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    /// Assigns a role, replacing any existing assignment for the principal in
+    /// the assignment's namespace
+    async fn assign(&self, assignment: &RoleAssignment) -> Result<(), PipelineError>;
+
+    /// Removes the principal's assignment in `namespace`, returning whether
+    /// one existed
+    async fn revoke(&self, namespace: &Namespace, principal: &str) -> Result<bool, PipelineError>;
+
+    /// Finds the assignment for a principal in `namespace`
+    async fn find_by_principal(
+        &self,
+        namespace: &Namespace,
+        principal: &str,
+    ) -> Result<Option<RoleAssignment>, PipelineError>;
+
+    /// Lists the assignments in `namespace` ordered by principal
+    async fn list_by_namespace(&self, namespace: &Namespace) -> Result<Vec<RoleAssignment>, PipelineError>;
+
+    /// Counts assignments across all namespaces
+    async fn count(&self) -> Result<usize, PipelineError>;
+
+    /// Finds the role assigned to a principal in `namespace`
+    async fn find_role(&self, namespace: &Namespace, principal: &str) -> Result<Option<Role>, PipelineError> {
+        Ok(self.find_by_principal(namespace, principal).await?.map(|a| a.role()))
+    }
+}
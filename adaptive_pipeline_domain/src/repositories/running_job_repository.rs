@@ -0,0 +1,44 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Running Job Repository Interface
+//!
+//! Persistence contract for the jobs running in each namespace, which the
+//! `max_concurrent_jobs` quota counts across every process sharing the
+//! store. Each running job holds a lease that its process renews; a job
+//! whose lease has expired belongs to a process that died and no longer
+//! counts.
+
+use crate::value_objects::Namespace;
+use crate::PipelineError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository interface for leases on running jobs
+#[async_trait]
+pub trait RunningJobRepository: Send + Sync {
+    /// Records job `id` as running in `namespace` until `expires_at`, unless
+    /// `limit` jobs with leases valid at `now` already run there; returns
+    /// whether it was recorded
+    async fn start(
+        &self,
+        namespace: &Namespace,
+        id: &str,
+        limit: usize,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, PipelineError>;
+
+    /// Counts the jobs in `namespace` whose leases are valid at `now`
+    async fn count_running(&self, namespace: &Namespace, now: DateTime<Utc>) -> Result<usize, PipelineError>;
+
+    /// Extends the lease of job `id` to `expires_at`
+    async fn renew(&self, id: &str, expires_at: DateTime<Utc>) -> Result<(), PipelineError>;
+
+    /// Removes job `id`, which has finished
+    async fn finish(&self, id: &str) -> Result<(), PipelineError>;
+}
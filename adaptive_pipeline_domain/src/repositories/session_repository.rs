@@ -0,0 +1,46 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Session Repository Interface
+//!
+//! Persistence contract for API sessions (see `Session`). Sessions are
+//! stored with the digest of their token, never the token itself, and are
+//! kept after they end so audit entries can still name them until
+//! [`SessionRepository::purge_ended`] removes them.
+
+use crate::entities::Session;
+use crate::value_objects::{Namespace, SessionId};
+use crate::PipelineError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository interface for sessions
+///
+/// Implementations must be thread-safe (`Send + Sync`) so they can be shared
+/// between request handlers.
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    /// Stores a newly issued session
+    async fn create(&self, session: &Session) -> Result<(), PipelineError>;
+
+    /// Finds a session by ID, whether or not it is still active
+    async fn find_by_id(&self, id: &SessionId) -> Result<Option<Session>, PipelineError>;
+
+    /// Lists the sessions in `namespace` active at `now`, oldest first
+    async fn list_active(&self, namespace: &Namespace, now: DateTime<Utc>) -> Result<Vec<Session>, PipelineError>;
+
+    /// Records that the session `id` was used at `at`
+    async fn touch(&self, id: &SessionId, at: DateTime<Utc>) -> Result<(), PipelineError>;
+
+    /// Revokes the session `id` at `at`, returning whether an active session
+    /// was revoked
+    async fn revoke(&self, id: &SessionId, at: DateTime<Utc>) -> Result<bool, PipelineError>;
+
+    /// Deletes sessions that expired or were revoked before `before`,
+    /// returning how many were deleted
+    async fn purge_ended(&self, before: DateTime<Utc>) -> Result<usize, PipelineError>;
+}
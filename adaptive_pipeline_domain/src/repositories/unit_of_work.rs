@@ -0,0 +1,51 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Unit of Work Interface
+//!
+//! Groups mutations of several kinds into one transaction. A change to a
+//! pipeline, the audit record of who made it and the domain event announcing
+//! it either all become visible on [`UnitOfWork::commit`] or none do.
+//!
+//! A unit of work dropped without being committed is rolled back, so an
+//! early return on error leaves nothing behind.
+//!
+//! ```rust,ignore
+//! let mut work = pipeline_repository.begin().await?;
+//! work.save_pipeline(&pipeline).await?;
+//! work.record_audit(&AuditRecord::new(principal, "pipeline.create", "")).await?;
+//! work.record_event(&event).await?;
+//! work.commit().await?;
+//! ```
+
+use crate::entities::Pipeline;
+use crate::events::PipelineEvent;
+use crate::value_objects::{AuditRecord, PipelineId};
+use crate::PipelineError;
+use async_trait::async_trait;
+
+/// Transaction spanning pipeline changes, audit records and domain events
+#[async_trait]
+pub trait UnitOfWork: Send {
+    /// Inserts `pipeline`
+    async fn save_pipeline(&mut self, pipeline: &Pipeline) -> Result<(), PipelineError>;
+
+    /// Archives the pipeline `id`; returns whether it existed
+    async fn delete_pipeline(&mut self, id: &PipelineId) -> Result<bool, PipelineError>;
+
+    /// Appends `record` to the audit trail
+    async fn record_audit(&mut self, record: &AuditRecord) -> Result<(), PipelineError>;
+
+    /// Appends `event` to the stored domain events
+    async fn record_event(&mut self, event: &PipelineEvent) -> Result<(), PipelineError>;
+
+    /// Makes every change visible at once
+    async fn commit(self: Box<Self>) -> Result<(), PipelineError>;
+
+    /// Discards every change
+    async fn rollback(self: Box<Self>) -> Result<(), PipelineError>;
+}
@@ -0,0 +1,33 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Usage Repository Interface
+//!
+//! Persistence contract for per-namespace usage accounting. Implementations
+//! accumulate totals per namespace and UTC day.
+
+use crate::value_objects::{Namespace, NamespaceUsage};
+use crate::PipelineError;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// Repository interface for per-namespace usage totals
+#[async_trait]
+pub trait UsageRepository: Send + Sync {
+    /// Adds one completed job and its byte counts to the namespace's totals
+    /// for `day`
+    async fn record_job(
+        &self,
+        namespace: &Namespace,
+        day: NaiveDate,
+        input_bytes: u64,
+        output_bytes: u64,
+    ) -> Result<(), PipelineError>;
+
+    /// Gets the namespace's totals for `day`, zero if nothing was recorded
+    async fn usage_on(&self, namespace: &Namespace, day: NaiveDate) -> Result<NamespaceUsage, PipelineError>;
+}
@@ -45,6 +45,7 @@
 //! - **Generic Config Manager**: Configuration management abstractions
 //! - **Generic Metrics Collector**: Performance and operational metrics
 //! - **Generic Result Builder**: Standardized result construction
+//! - **Constant Time**: Timing-safe comparison of checksums and tags
 //!
 //! ### Compliance and Standards
 //! Services ensuring compliance with standards:
@@ -424,17 +425,24 @@
 // - Include sufficient context for auditing
 // - Protect log data integrity
 
+pub mod auth_provider;
 pub mod checksum_service;
 pub mod compression_service;
+pub mod constant_time;
+pub mod cost_model;
 pub mod datetime_compliance_service;
 pub mod datetime_serde;
 pub mod encryption_service;
 pub mod file_io_service;
 pub mod file_processor_service;
 pub mod pipeline_service;
+pub mod random_access_sink;
 pub mod stage_service;
 
+pub use auth_provider::{AuthProvider, AuthenticatedIdentity, Credentials};
 pub use compression_service::*;
+pub use cost_model::{CostBasis, CostEstimate, CostModel};
 pub use encryption_service::*;
 pub use pipeline_service::*;
+pub use random_access_sink::RandomAccessSink;
 pub use stage_service::{FromParameters, StageService};
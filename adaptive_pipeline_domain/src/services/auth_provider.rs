@@ -0,0 +1,126 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Authentication Provider Interface
+//!
+//! Port through which API callers prove who they are. A provider turns the
+//! [`Credentials`] a caller presents (an API key, a bearer token, or the
+//! client certificate of a mutually authenticated TLS connection) into an
+//! [`AuthenticatedIdentity`]: the principal that role-based access control
+//! resolves roles for, plus any role and namespace the provider's
+//! configuration grants it.
+//!
+//! ## Contract
+//!
+//! - [`supports`](AuthProvider::supports) is a cheap check of the credential
+//!   kind only; it never validates.
+//! - [`authenticate`](AuthProvider::authenticate) fails with
+//!   `SecurityViolation` for credentials it rejects, and
+//!   `SecurityContextExpired` for credentials that were valid but have
+//!   expired. Messages start with "Permission denied" and never include the
+//!   secret.
+//! - Client certificates are passed in DER form after the TLS handshake has
+//!   verified that the caller holds the certificate's private key; providers
+//!   only decide whose certificate it is.
+//!
+//! ## Architecture Note - Infrastructure Port
+//!
+//! Like [`RandomAccessSink`](super::random_access_sink::RandomAccessSink),
+//! this trait is async so that implementations may consult remote identity
+//! providers. Implementations (static API keys, JWT validation, client
+//! certificates) live in the infrastructure layer.
+
+use async_trait::async_trait;
+use std::fmt;
+
+use crate::value_objects::{Namespace, Role};
+use crate::PipelineError;
+
+/// Credentials presented by an API caller
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// A static API key
+    ApiKey(String),
+    /// A bearer token, such as an OIDC-issued JWT
+    BearerToken(String),
+    /// The DER-encoded certificate of a TLS client
+    ClientCertificate(Vec<u8>),
+}
+
+impl Credentials {
+    /// Names the credential kind, for messages and logs
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Credentials::ApiKey(_) => "API key",
+            Credentials::BearerToken(_) => "bearer token",
+            Credentials::ClientCertificate(_) => "client certificate",
+        }
+    }
+}
+
+impl fmt::Debug for Credentials {
+    // Secrets must never reach logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Credentials::{}(<redacted>)", self.kind())
+    }
+}
+
+/// Identity established by an [`AuthProvider`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedIdentity {
+    /// Principal that role-based access control resolves roles for
+    pub principal: String,
+    /// Name of the provider that authenticated the caller
+    pub provider: String,
+    /// Role the provider's configuration grants the caller, in addition to
+    /// any role assigned in the role repository
+    pub role: Option<Role>,
+    /// Namespace the caller is confined to, if any
+    pub namespace: Option<Namespace>,
+}
+
+impl AuthenticatedIdentity {
+    /// Creates an identity for `principal` with no granted role or
+    /// namespace
+    pub fn new(principal: impl Into<String>, provider: impl Into<String>) -> Self {
+        Self {
+            principal: principal.into(),
+            provider: provider.into(),
+            role: None,
+            namespace: None,
+        }
+    }
+
+    /// Grants the identity `role`
+    pub fn with_role(mut self, role: Option<Role>) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Confines the identity to `namespace`
+    pub fn with_namespace(mut self, namespace: Option<Namespace>) -> Self {
+        self.namespace = namespace;
+        self
+    }
+}
+
+/// Authenticates API callers from the credentials they present
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Provider name, recorded on the identities it establishes
+    fn name(&self) -> &str;
+
+    /// Whether the provider handles this kind of credential
+    fn supports(&self, credentials: &Credentials) -> bool;
+
+    /// Establishes who presented `credentials`
+    ///
+    /// # Errors
+    ///
+    /// See the module documentation.
+    async fn authenticate(&self, credentials: &Credentials) -> Result<AuthenticatedIdentity, PipelineError>;
+}
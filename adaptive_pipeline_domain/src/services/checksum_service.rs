@@ -241,7 +241,9 @@ pub trait ChecksumService: Send + Sync {
 ///
 /// ### Algorithm Selection
 /// - **algorithm**: Hash algorithm identifier (currently supports "SHA256")
-/// - Future versions may support SHA-512, Blake3, etc.
+/// - Whole-file checksums may instead use BLAKE3 (see
+///   [`ChecksumAlgorithm`](crate::value_objects::ChecksumAlgorithm)),
+///   hashed by the infrastructure checksum adapter
 ///
 /// ### Verification Mode
 /// - **verify_existing**: When `true`, verifies existing checksums before
@@ -8,11 +8,12 @@
 //! # Compression Service
 //!
 //! Domain service trait for data compression/decompression with support for
-//! multiple algorithms (Brotli, Gzip, Zstd, Lz4) and configurable levels.
+//! multiple algorithms (Brotli, Gzip, Zstd, Lz4, Xz) and configurable levels.
 //! Provides chunk-by-chunk streaming for large files, algorithm selection,
 //! performance optimization, and benchmarking. Thread-safe, stateless
 //! operations. See mdBook for algorithm characteristics and usage examples.
 
+use crate::value_objects::Algorithm;
 use crate::{FileChunk, PipelineError, ProcessingContext};
 
 // NOTE: Domain traits are synchronous. Async execution is an infrastructure
@@ -32,6 +33,7 @@ use crate::{FileChunk, PipelineError, ProcessingContext};
 /// - **Gzip**: Good balance of speed and compression, widely supported
 /// - **Zstd**: Modern algorithm with excellent speed/ratio balance
 /// - **Lz4**: Fastest compression, good for real-time processing
+/// - **Xz**: Highest ratios (LZMA2), slowest compression, for archival data
 /// - **Custom**: User-defined algorithms for specialized requirements
 ///
 /// # Examples
@@ -41,6 +43,7 @@ pub enum CompressionAlgorithm {
     Gzip,
     Zstd,
     Lz4,
+    Xz,
     Custom(String),
 }
 
@@ -434,13 +437,7 @@ impl super::stage_service::FromParameters for CompressionConfig {
             .get("algorithm")
             .ok_or_else(|| PipelineError::MissingParameter("algorithm".into()))?;
 
-        let algorithm = match algorithm_str.to_lowercase().as_str() {
-            "brotli" => CompressionAlgorithm::Brotli,
-            "gzip" => CompressionAlgorithm::Gzip,
-            "zstd" => CompressionAlgorithm::Zstd,
-            "lz4" => CompressionAlgorithm::Lz4,
-            other => CompressionAlgorithm::Custom(other.to_string()),
-        };
+        let algorithm = CompressionAlgorithm::try_from(&Algorithm::parse(algorithm_str)?)?;
 
         // Optional: level (default to balanced)
         let level = params
@@ -484,6 +481,11 @@ impl CompressionLevel {
             (CompressionLevel::Balanced, CompressionAlgorithm::Zstd) => 9,
             (CompressionLevel::Best, CompressionAlgorithm::Zstd) => 19,
 
+            (CompressionLevel::Fastest, CompressionAlgorithm::Xz) => 0,
+            (CompressionLevel::Fast, CompressionAlgorithm::Xz) => 3,
+            (CompressionLevel::Balanced, CompressionAlgorithm::Xz) => 6,
+            (CompressionLevel::Best, CompressionAlgorithm::Xz) => 9,
+
             (CompressionLevel::Custom(level), _) => *level,
 
             _ => 6, // Default balanced level
@@ -491,6 +493,45 @@ impl CompressionLevel {
     }
 }
 
+/// Resolves a canonical [`Algorithm`] to the compression algorithm it names.
+///
+/// Custom algorithm names are passed through as
+/// [`CompressionAlgorithm::Custom`]; encryption and hashing algorithms are
+/// rejected.
+impl TryFrom<&Algorithm> for CompressionAlgorithm {
+    type Error = PipelineError;
+
+    fn try_from(algorithm: &Algorithm) -> Result<Self, Self::Error> {
+        match algorithm {
+            Algorithm::Brotli => Ok(CompressionAlgorithm::Brotli),
+            Algorithm::Gzip => Ok(CompressionAlgorithm::Gzip),
+            Algorithm::Zstd => Ok(CompressionAlgorithm::Zstd),
+            Algorithm::Lz4 => Ok(CompressionAlgorithm::Lz4),
+            Algorithm::Xz => Ok(CompressionAlgorithm::Xz),
+            Algorithm::Deflate | Algorithm::Custom(_) => Ok(CompressionAlgorithm::Custom(algorithm.to_string())),
+            other => Err(PipelineError::InvalidParameter(format!(
+                "Algorithm '{}' is not a compression algorithm",
+                other
+            ))),
+        }
+    }
+}
+
+impl From<&CompressionAlgorithm> for Algorithm {
+    fn from(algorithm: &CompressionAlgorithm) -> Self {
+        match algorithm {
+            CompressionAlgorithm::Brotli => Algorithm::Brotli,
+            CompressionAlgorithm::Gzip => Algorithm::Gzip,
+            CompressionAlgorithm::Zstd => Algorithm::Zstd,
+            CompressionAlgorithm::Lz4 => Algorithm::Lz4,
+            CompressionAlgorithm::Xz => Algorithm::Xz,
+            CompressionAlgorithm::Custom(name) => {
+                Algorithm::parse(name).unwrap_or_else(|_| Algorithm::Custom(name.clone()))
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for CompressionAlgorithm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -498,6 +539,7 @@ impl std::fmt::Display for CompressionAlgorithm {
             CompressionAlgorithm::Gzip => write!(f, "Gzip"),
             CompressionAlgorithm::Zstd => write!(f, "Zstd"),
             CompressionAlgorithm::Lz4 => write!(f, "LZ4"),
+            CompressionAlgorithm::Xz => write!(f, "XZ"),
             CompressionAlgorithm::Custom(name) => write!(f, "Custom({})", name),
         }
     }
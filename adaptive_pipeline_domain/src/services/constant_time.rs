@@ -0,0 +1,67 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Constant-Time Comparison
+//!
+//! Equality checks for checksums, integrity hashes and authentication tags.
+//! `==` on strings and slices returns at the first differing byte, so the
+//! time a comparison takes reveals how much of a forged value was correct.
+//! These helpers always examine every byte of equal-length inputs.
+//!
+//! Only the *length* of the inputs may influence timing. Digest lengths are
+//! fixed by the algorithm and are not secret.
+//!
+//! Use these helpers wherever a computed value is compared against one that
+//! came from a file, a header or the caller; plain `==` remains fine for
+//! comparisons that do not involve integrity or authentication.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::services::constant_time::{constant_time_eq, constant_time_eq_str};
+//!
+//! assert!(constant_time_eq(b"tag", b"tag"));
+//! assert!(!constant_time_eq(b"tag", b"tap"));
+//! assert!(constant_time_eq_str("9f86d081", "9f86d081"));
+//! assert!(!constant_time_eq_str("9f86d081", "9f86d08"));
+//! ```
+
+use subtle::ConstantTimeEq;
+
+/// Compares two byte slices without short-circuiting on the first mismatch
+///
+/// Returns `false` immediately when the lengths differ.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && bool::from(a.ct_eq(b))
+}
+
+/// Compares two encoded digests (for example hex SHA-256 strings) without
+/// short-circuiting on the first mismatch
+///
+/// The comparison is byte-for-byte, so it is case-sensitive exactly like
+/// `==`; digests produced by this crate are always lowercase hex.
+pub fn constant_time_eq_str(a: &str, b: &str) -> bool {
+    constant_time_eq(a.as_bytes(), b.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_inputs_match() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(constant_time_eq_str("abc123", "abc123"));
+    }
+
+    #[test]
+    fn test_any_difference_is_detected() {
+        assert!(!constant_time_eq(&[1, 2, 3], &[0, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2]));
+        assert!(!constant_time_eq_str("abc123", "ABC123"));
+    }
+}
@@ -0,0 +1,343 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Cost Model
+//!
+//! Estimates how long a pipeline takes to process an input of a given size
+//! and what memory, CPU and disk the run needs, for capacity planning.
+//!
+//! Every completed run records its throughput per chunk size in the
+//! pipeline's chunk-size history ([`ChunkThroughput`]). The model prefers
+//! that measured throughput over fixed per-stage rates:
+//!
+//! 1. **Chunk size measured**: the throughput recorded for the chunk size a
+//!    run would pick ([`ChunkSize::learned_for_file_size`]).
+//! 2. **Pipeline measured**: the byte-weighted throughput across all recorded
+//!    chunk sizes.
+//! 3. **Stage defaults**: nominal single-stream rates per stage type, used
+//!    only until the pipeline has run on this storage type.
+//!
+//! Recorded throughput is wall-clock time for whole runs, so it already
+//! includes parallel workers, I/O and the automatic checksum stages.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::services::cost_model::{CostBasis, CostModel};
+//! use adaptive_pipeline_domain::value_objects::{ChunkSize, ChunkThroughput};
+//! # use adaptive_pipeline_domain::entities::{PipelineStage, StageConfiguration, StageType};
+//! # use adaptive_pipeline_domain::Pipeline;
+//! # use std::collections::HashMap;
+//! # let stage = PipelineStage::new(
+//! #     "compression".to_string(),
+//! #     StageType::Compression,
+//! #     StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+//! #     1,
+//! # ).unwrap();
+//! # let pipeline = Pipeline::new("backup".to_string(), vec![stage]).unwrap();
+//!
+//! // 4 GiB in 8 seconds on this storage type
+//! let size = 1 << 30;
+//! let chunk_size = ChunkSize::learned_for_file_size(size, &[]);
+//! let history = vec![ChunkThroughput::new(chunk_size, 2, 4 << 30, 8.0)];
+//!
+//! let estimate = CostModel::from_history(history).estimate(&pipeline, size);
+//! assert_eq!(estimate.estimated_duration().as_secs(), 2);
+//! assert!(matches!(estimate.basis, CostBasis::ChunkSizeHistory { runs: 2, .. }));
+//! ```
+
+use crate::entities::StageType;
+use crate::repositories::stage_executor::ResourceRequirements;
+use crate::value_objects::{ChunkSize, ChunkThroughput, WorkerCount};
+use crate::Pipeline;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Where an estimate's throughput comes from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum CostBasis {
+    /// Runs recorded at the chunk size the estimate uses
+    ChunkSizeHistory { runs: u64, bytes_processed: u64 },
+    /// Runs recorded at other chunk sizes
+    PipelineHistory { runs: u64, bytes_processed: u64 },
+    /// Nominal per-stage rates; the pipeline has no recorded runs
+    StageDefaults,
+}
+
+impl Display for CostBasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CostBasis::ChunkSizeHistory { runs, .. } => write!(f, "{} recorded run(s) at this chunk size", runs),
+            CostBasis::PipelineHistory { runs, .. } => write!(f, "{} recorded run(s) at other chunk sizes", runs),
+            CostBasis::StageDefaults => f.write_str("stage defaults (no recorded runs)"),
+        }
+    }
+}
+
+/// Estimated cost of processing one input with one pipeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostEstimate {
+    /// Input size in bytes
+    pub file_size: u64,
+    /// Chunk size a run would use, in bytes
+    pub chunk_size: usize,
+    /// Number of chunks the input would be split into
+    pub chunk_count: u64,
+    /// Adaptive worker count for the input size
+    pub worker_count: usize,
+    /// Expected end-to-end throughput
+    pub bytes_per_second: f64,
+    /// Expected processing time, in seconds
+    pub estimated_seconds: f64,
+    /// Peak chunk buffer memory
+    pub memory_bytes: u64,
+    /// Cores the run keeps busy
+    pub cpu_cores: u32,
+    /// Scratch and output space needed
+    pub disk_space_bytes: u64,
+    /// Where the throughput comes from
+    pub basis: CostBasis,
+}
+
+impl CostEstimate {
+    /// Expected processing time
+    pub fn estimated_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.estimated_seconds)
+    }
+
+    /// The estimate as the requirements a stage executor reports
+    pub fn requirements(&self) -> ResourceRequirements {
+        ResourceRequirements {
+            memory_bytes: self.memory_bytes,
+            cpu_cores: self.cpu_cores,
+            disk_space_bytes: self.disk_space_bytes,
+            network_bandwidth_bps: None,
+            gpu_memory_bytes: None,
+            estimated_duration: self.estimated_duration(),
+        }
+    }
+}
+
+/// Estimates processing cost from a pipeline's recorded throughput
+#[derive(Debug, Clone, Default)]
+pub struct CostModel {
+    history: Vec<ChunkThroughput>,
+}
+
+impl CostModel {
+    /// Nominal compression rate before any run is recorded
+    pub const DEFAULT_COMPRESSION_BYTES_PER_SECOND: f64 = 50.0 * MIB;
+    /// Nominal encryption rate before any run is recorded
+    pub const DEFAULT_ENCRYPTION_BYTES_PER_SECOND: f64 = 100.0 * MIB;
+    /// Nominal rate of every other stage before any run is recorded
+    pub const DEFAULT_STAGE_BYTES_PER_SECOND: f64 = 200.0 * MIB;
+
+    /// Creates a model from the pipeline's chunk-size history on the storage
+    /// type the run will use
+    pub fn from_history(history: Vec<ChunkThroughput>) -> Self {
+        Self { history }
+    }
+
+    /// Estimates the cost of processing `file_size` bytes with `pipeline`
+    pub fn estimate(&self, pipeline: &Pipeline, file_size: u64) -> CostEstimate {
+        let chunk_size = ChunkSize::learned_for_file_size(file_size, &self.history);
+        let (bytes_per_second, basis) = self.throughput(pipeline, chunk_size);
+        let estimated_seconds = if bytes_per_second > 0.0 {
+            file_size as f64 / bytes_per_second
+        } else {
+            0.0
+        };
+
+        // Each stage holds an input and an output buffer per chunk
+        let memory_bytes = pipeline
+            .stages()
+            .iter()
+            .map(|stage| 2 * stage.configuration().chunk_size.unwrap_or(chunk_size.bytes()) as u64)
+            .sum();
+        let cpu_cores = pipeline
+            .stages()
+            .iter()
+            .map(|stage| {
+                if stage.configuration().parallel_processing {
+                    4
+                } else {
+                    1
+                }
+            })
+            .max()
+            .unwrap_or(0);
+
+        CostEstimate {
+            file_size,
+            chunk_size: chunk_size.bytes(),
+            chunk_count: chunk_size.chunks_needed_for_file(file_size),
+            worker_count: WorkerCount::optimal_for_file_size(file_size).count(),
+            bytes_per_second,
+            estimated_seconds,
+            memory_bytes,
+            cpu_cores,
+            // The output plus a staged temporary copy of it
+            disk_space_bytes: file_size.saturating_mul(2),
+            basis,
+        }
+    }
+
+    /// Estimated processing time for `file_size` bytes
+    pub fn estimate_processing_time(&self, pipeline: &Pipeline, file_size: u64) -> Duration {
+        self.estimate(pipeline, file_size).estimated_duration()
+    }
+
+    /// Estimated resource requirements for `file_size` bytes
+    pub fn resource_requirements(&self, pipeline: &Pipeline, file_size: u64) -> ResourceRequirements {
+        self.estimate(pipeline, file_size).requirements()
+    }
+
+    fn throughput(&self, pipeline: &Pipeline, chunk_size: ChunkSize) -> (f64, CostBasis) {
+        if let Some(record) = self
+            .history
+            .iter()
+            .find(|record| record.chunk_size() == chunk_size && record.bytes_per_second() > 0.0)
+        {
+            return (
+                record.bytes_per_second(),
+                CostBasis::ChunkSizeHistory {
+                    runs: record.runs(),
+                    bytes_processed: record.bytes_processed(),
+                },
+            );
+        }
+
+        let measured = self.history.iter().filter(|record| record.processing_seconds() > 0.0);
+        let (runs, bytes, seconds) = measured.fold((0, 0u64, 0.0), |(runs, bytes, seconds), record| {
+            (
+                runs + record.runs(),
+                bytes.saturating_add(record.bytes_processed()),
+                seconds + record.processing_seconds(),
+            )
+        });
+        if bytes > 0 {
+            return (
+                bytes as f64 / seconds,
+                CostBasis::PipelineHistory {
+                    runs,
+                    bytes_processed: bytes,
+                },
+            );
+        }
+
+        // Stages run one after another on each chunk
+        let seconds_per_byte: f64 = pipeline
+            .stages()
+            .iter()
+            .filter(|stage| stage.is_enabled())
+            .map(|stage| {
+                1.0 / match stage.stage_type() {
+                    StageType::Compression => Self::DEFAULT_COMPRESSION_BYTES_PER_SECOND,
+                    StageType::Encryption => Self::DEFAULT_ENCRYPTION_BYTES_PER_SECOND,
+                    _ => Self::DEFAULT_STAGE_BYTES_PER_SECOND,
+                }
+            })
+            .sum();
+        let bytes_per_second = if seconds_per_byte > 0.0 {
+            1.0 / seconds_per_byte
+        } else {
+            0.0
+        };
+        (bytes_per_second, CostBasis::StageDefaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{PipelineStage, StageConfiguration};
+    use std::collections::HashMap;
+
+    fn pipeline() -> Pipeline {
+        let stage = |name: &str, stage_type, algorithm: &str, order| {
+            PipelineStage::new(
+                name.to_string(),
+                stage_type,
+                StageConfiguration::new(algorithm.to_string(), HashMap::new(), false),
+                order,
+            )
+            .unwrap()
+        };
+        Pipeline::new(
+            "estimated".to_string(),
+            vec![
+                stage("compression", StageType::Compression, "brotli", 1),
+                stage("encryption", StageType::Encryption, "aes256gcm", 2),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_defaults_apply_only_without_history() {
+        let pipeline = pipeline();
+        let size = 1 << 30;
+        let estimate = CostModel::default().estimate(&pipeline, size);
+
+        assert_eq!(estimate.basis, CostBasis::StageDefaults);
+        // Compression, encryption and two checksum stages, one after another
+        let expected = 1024.0 / 50.0 + 1024.0 / 100.0 + 2.0 * 1024.0 / 200.0;
+        assert!((estimate.estimated_seconds - expected).abs() < 1e-6);
+        assert_eq!(estimate.disk_space_bytes, 2 * size);
+        assert_eq!(estimate.chunk_count, size.div_ceil(estimate.chunk_size as u64));
+        assert_eq!(
+            estimate.requirements().estimated_duration,
+            estimate.estimated_duration()
+        );
+    }
+
+    #[test]
+    fn test_history_at_the_chosen_chunk_size_wins() {
+        let pipeline = pipeline();
+        let size = 1 << 30;
+        let chosen = ChunkSize::learned_for_file_size(size, &[]);
+        let other = ChunkSize::new(chosen.bytes() / 2).unwrap();
+        let history = vec![
+            ChunkThroughput::new(chosen, 3, 3 << 30, 6.0),
+            ChunkThroughput::new(other, 1, 1 << 30, 4.0),
+        ];
+
+        let estimate = CostModel::from_history(history).estimate(&pipeline, size);
+        assert_eq!(
+            estimate.basis,
+            CostBasis::ChunkSizeHistory {
+                runs: 3,
+                bytes_processed: 3 << 30
+            }
+        );
+        assert!((estimate.estimated_seconds - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_history_at_other_chunk_sizes_is_byte_weighted() {
+        let pipeline = pipeline();
+        let size = 1 << 30;
+        let chosen = ChunkSize::learned_for_file_size(size, &[]);
+        let history = vec![
+            ChunkThroughput::new(ChunkSize::new(chosen.bytes() / 2).unwrap(), 1, 1 << 30, 1.0),
+            ChunkThroughput::new(ChunkSize::new(chosen.bytes() / 4).unwrap(), 2, 3 << 30, 7.0),
+        ];
+
+        let estimate = CostModel::from_history(history).estimate(&pipeline, size);
+        assert_eq!(
+            estimate.basis,
+            CostBasis::PipelineHistory {
+                runs: 3,
+                bytes_processed: 4 << 30
+            }
+        );
+        // 4 GiB in 8 s overall, so 1 GiB takes 2 s
+        assert!((estimate.estimated_seconds - 2.0).abs() < 1e-9);
+    }
+}
@@ -28,7 +28,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::services::datetime_serde;
-use crate::value_objects::EncryptionBenchmark;
+use crate::value_objects::chunk_encryption_spec::ASSOCIATED_DATA_KEY;
+use crate::value_objects::{Algorithm, EncryptionBenchmark, KdfCost, NonceStrategy, SecretBytes, NONCE_STRATEGY_KEY};
 use crate::{FileChunk, PipelineError, ProcessingContext, SecurityContext};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -134,6 +135,7 @@ pub enum KeyDerivationFunction {
 /// - **Key Derivation**: Function for deriving keys from passwords
 /// - **Key Size**: Size of encryption keys in bytes
 /// - **Nonce Size**: Size of nonces/initialization vectors in bytes
+/// - **Nonce Strategy**: How each chunk's nonce is derived
 /// - **Salt Size**: Size of salt for key derivation in bytes
 /// - **Iterations**: Number of iterations for key derivation
 /// - **Memory Cost**: Memory usage for memory-hard functions (optional)
@@ -166,6 +168,9 @@ pub struct EncryptionConfig {
     /// Size of nonces/initialization vectors in bytes
     pub nonce_size: u32,
 
+    /// How each chunk's nonce is derived
+    pub nonce_strategy: NonceStrategy,
+
     /// Size of salt for key derivation in bytes
     pub salt_size: u32,
 
@@ -178,7 +183,8 @@ pub struct EncryptionConfig {
     /// Parallelism level for key derivation functions
     pub parallel_cost: Option<u32>,
 
-    /// Additional authenticated data (not encrypted)
+    /// Additional authenticated data (not encrypted) shared by every chunk
+    /// of a file; each chunk's sequence number is appended to it
     pub associated_data: Option<Vec<u8>>,
 }
 
@@ -212,9 +218,9 @@ pub struct EncryptionConfig {
 ///
 /// # Memory Security
 ///
-/// The key material implements `Zeroize` to ensure sensitive data is securely
-/// cleared from memory:
-///
+/// The key, nonce and salt are [`SecretBytes`], so they are zeroized when
+/// the key material (or any clone of it) is dropped, and `Debug` output shows
+/// only their lengths.
 ///
 /// # Serialization
 ///
@@ -223,16 +229,16 @@ pub struct EncryptionConfig {
 /// - Use secure storage mechanisms
 /// - Implement proper access controls
 /// - Follow key management best practices
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct KeyMaterial {
     /// The encryption/decryption key (sensitive data)
-    pub key: Vec<u8>,
+    pub key: SecretBytes,
 
     /// Nonce/initialization vector for encryption operations
-    pub nonce: Vec<u8>,
+    pub nonce: SecretBytes,
 
     /// Salt used in key derivation (if applicable)
-    pub salt: Vec<u8>,
+    pub salt: SecretBytes,
 
     /// The encryption algorithm this key material is for
     pub algorithm: EncryptionAlgorithm,
@@ -254,8 +260,22 @@ impl Zeroize for KeyMaterial {
     }
 }
 
+// Every secret field is a `SecretBytes`, which wipes itself on drop.
 impl ZeroizeOnDrop for KeyMaterial {}
 
+impl std::fmt::Debug for KeyMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyMaterial")
+            .field("key", &self.key)
+            .field("nonce", &self.nonce)
+            .field("salt", &self.salt)
+            .field("algorithm", &self.algorithm)
+            .field("created_at", &self.created_at)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
 impl KeyMaterial {
     pub fn len(&self) -> usize {
         self.key.len()
@@ -265,11 +285,16 @@ impl KeyMaterial {
         self.key.is_empty()
     }
 
-    pub fn new(key: Vec<u8>, nonce: Vec<u8>, salt: Vec<u8>, algorithm: EncryptionAlgorithm) -> Self {
+    pub fn new(
+        key: impl Into<SecretBytes>,
+        nonce: impl Into<SecretBytes>,
+        salt: impl Into<SecretBytes>,
+        algorithm: EncryptionAlgorithm,
+    ) -> Self {
         Self {
-            key,
-            nonce,
-            salt,
+            key: key.into(),
+            nonce: nonce.into(),
+            salt: salt.into(),
             algorithm,
             created_at: chrono::Utc::now(),
             expires_at: None,
@@ -340,7 +365,7 @@ pub trait EncryptionService: super::stage_service::StageService {
     /// to execute in blocking thread pool when called from async contexts.
     fn derive_key_material(
         &self,
-        password: &str,
+        password: &SecretBytes,
         config: &EncryptionConfig,
         security_context: &SecurityContext,
     ) -> Result<KeyMaterial, PipelineError>;
@@ -438,6 +463,7 @@ impl Default for EncryptionConfig {
             iterations: 100_000,
             memory_cost: Some(65536), // 64MB for Argon2
             parallel_cost: Some(1),
+            nonce_strategy: NonceStrategy::Random,
             associated_data: None,
         }
     }
@@ -482,6 +508,13 @@ impl EncryptionConfig {
         self
     }
 
+    /// Sets the nonce strategy, and the nonce size to match it
+    pub fn with_nonce_strategy(mut self, nonce_strategy: NonceStrategy) -> Self {
+        self.nonce_strategy = nonce_strategy;
+        self.nonce_size = nonce_strategy.nonce_length() as u32;
+        self
+    }
+
     /// Sets associated data
     pub fn with_associated_data(mut self, data: Vec<u8>) -> Self {
         self.associated_data = Some(data);
@@ -495,6 +528,7 @@ impl EncryptionConfig {
             key_derivation: KeyDerivationFunction::Argon2,
             key_size: 32,
             nonce_size: 12,
+            nonce_strategy: NonceStrategy::Random,
             salt_size: 32,              // Larger salt
             iterations: 1_000_000,      // More iterations
             memory_cost: Some(1048576), // 1GB for Argon2
@@ -510,6 +544,7 @@ impl EncryptionConfig {
             key_derivation: KeyDerivationFunction::Argon2,
             key_size: 32,
             nonce_size: 12,
+            nonce_strategy: NonceStrategy::Random,
             salt_size: 16,
             iterations: 10_000,      // Fewer iterations
             memory_cost: Some(8192), // 8MB for Argon2
@@ -527,8 +562,9 @@ impl EncryptionConfig {
 /// ## Expected Parameters
 ///
 /// - **algorithm** (required): Encryption algorithm name
-///   - Valid values: "aes256gcm", "aes128gcm", "chacha20poly1305",
-///     "xchacha20poly1305"
+///   - Valid values: "aes-256-gcm", "aes-192-gcm", "aes-128-gcm",
+///     "chacha20-poly1305", or any alias accepted by `Algorithm::parse` (e.g.
+///     "aes256gcm")
 ///   - Example: `"algorithm" => "aes256gcm"`
 ///
 /// - **key_size** (optional): Key size in bytes
@@ -539,6 +575,17 @@ impl EncryptionConfig {
 ///   - Default: 3
 ///   - Example: `"iterations" => "10000"`
 ///
+/// - **nonce_strategy** (optional): How each chunk's nonce is derived
+///   - Valid values: "random", "counter", "xchacha-random" (the last only
+///     with "chacha20-poly1305")
+///   - Default: "random"
+///   - Example: `"nonce_strategy" => "counter"`
+///
+/// - **associated_data** (optional): Hex of the file's
+///   [`ChunkBinding`](crate::value_objects::ChunkBinding), authenticated with
+///   each chunk together with its sequence number
+///   - Default: none; chunks are sealed with empty associated data
+///
 /// ## Usage Example
 ///
 /// ```rust
@@ -557,17 +604,9 @@ impl super::stage_service::FromParameters for EncryptionConfig {
             .get("algorithm")
             .ok_or_else(|| PipelineError::MissingParameter("algorithm".into()))?;
 
-        let algorithm = match algorithm_str.to_lowercase().as_str() {
-            "aes256gcm" | "aes-256-gcm" => EncryptionAlgorithm::Aes256Gcm,
-            "aes128gcm" | "aes-128-gcm" => EncryptionAlgorithm::Aes128Gcm,
-            "chacha20poly1305" | "chacha20-poly1305" => EncryptionAlgorithm::ChaCha20Poly1305,
-            other => {
-                return Err(PipelineError::InvalidParameter(format!(
-                    "Unknown encryption algorithm: {}",
-                    other
-                )));
-            }
-        };
+        let algorithm = Algorithm::parse(algorithm_str)
+            .and_then(|algorithm| EncryptionAlgorithm::try_from(&algorithm))
+            .map_err(|_| PipelineError::InvalidParameter(format!("Unknown encryption algorithm: {}", algorithm_str)))?;
 
         // Optional parameters with defaults
         let key_size = params.get("key_size").and_then(|s| s.parse::<u32>().ok()).unwrap_or(32);
@@ -577,16 +616,42 @@ impl super::stage_service::FromParameters for EncryptionConfig {
             .and_then(|s| s.parse::<u32>().ok())
             .unwrap_or(3);
 
+        let nonce_strategy = match params.get(NONCE_STRATEGY_KEY) {
+            Some(name) => name.parse::<NonceStrategy>()?,
+            None => NonceStrategy::default(),
+        };
+        if nonce_strategy == NonceStrategy::XChaChaRandom && algorithm != EncryptionAlgorithm::ChaCha20Poly1305 {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Nonce strategy '{}' requires chacha20-poly1305, not {}",
+                nonce_strategy, algorithm
+            )));
+        }
+
+        let associated_data = params
+            .get(ASSOCIATED_DATA_KEY)
+            .map(|data| {
+                hex::decode(data)
+                    .map_err(|e| PipelineError::InvalidParameter(format!("Invalid hex associated data: {}", e)))
+            })
+            .transpose()?;
+
+        // Argon2id cost, validated against the bounds restore accepts
+        let (memory_cost, parallel_cost) = match KdfCost::from_parameters(params)? {
+            Some(cost) => (cost.memory_kib(), cost.parallelism()),
+            None => (65536, 4), // 64MB default
+        };
+
         Ok(Self {
             algorithm,
             key_derivation: KeyDerivationFunction::Argon2,
             key_size,
-            nonce_size: 12,
+            nonce_size: nonce_strategy.nonce_length() as u32,
+            nonce_strategy,
             salt_size: 16,
             iterations,
-            memory_cost: Some(65536), // 64MB default
-            parallel_cost: Some(4),
-            associated_data: None,
+            memory_cost: Some(memory_cost),
+            parallel_cost: Some(parallel_cost),
+            associated_data,
         })
     }
 }
@@ -608,21 +673,11 @@ impl KeyMaterial {
     }
 
     /// Securely clears key material
+    ///
+    /// Dropping the key material has the same effect; this is for callers
+    /// that want to wipe it while the value is still in scope.
     pub fn clear(&mut self) {
-        // Zero out sensitive data
-        self.key.fill(0);
-        self.nonce.fill(0);
-        self.salt.fill(0);
-
-        // Clear vectors
-        self.key.clear();
-        self.nonce.clear();
-        self.salt.clear();
-
-        // Shrink to free memory
-        self.key.shrink_to_fit();
-        self.nonce.shrink_to_fit();
-        self.salt.shrink_to_fit();
+        self.zeroize();
     }
 
     /// Gets key size in bytes
@@ -641,9 +696,38 @@ impl KeyMaterial {
     }
 }
 
-impl Drop for KeyMaterial {
-    fn drop(&mut self) {
-        self.clear();
+/// Resolves a canonical [`Algorithm`] to the AEAD cipher it names.
+///
+/// Only the authenticated ciphers implemented by the pipeline are accepted;
+/// CBC modes, compression, and hashing algorithms are rejected.
+impl TryFrom<&Algorithm> for EncryptionAlgorithm {
+    type Error = PipelineError;
+
+    fn try_from(algorithm: &Algorithm) -> Result<Self, Self::Error> {
+        match algorithm {
+            Algorithm::Aes256Gcm => Ok(EncryptionAlgorithm::Aes256Gcm),
+            Algorithm::Aes192Gcm => Ok(EncryptionAlgorithm::Aes192Gcm),
+            Algorithm::Aes128Gcm => Ok(EncryptionAlgorithm::Aes128Gcm),
+            Algorithm::ChaCha20Poly1305 => Ok(EncryptionAlgorithm::ChaCha20Poly1305),
+            other => Err(PipelineError::InvalidParameter(format!(
+                "Unknown encryption algorithm: {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl From<&EncryptionAlgorithm> for Algorithm {
+    fn from(algorithm: &EncryptionAlgorithm) -> Self {
+        match algorithm {
+            EncryptionAlgorithm::Aes256Gcm => Algorithm::Aes256Gcm,
+            EncryptionAlgorithm::Aes192Gcm => Algorithm::Aes192Gcm,
+            EncryptionAlgorithm::Aes128Gcm => Algorithm::Aes128Gcm,
+            EncryptionAlgorithm::ChaCha20Poly1305 => Algorithm::ChaCha20Poly1305,
+            EncryptionAlgorithm::Custom(name) => {
+                Algorithm::parse(name).unwrap_or_else(|_| Algorithm::Custom(name.clone()))
+            }
+        }
     }
 }
 
@@ -126,6 +126,9 @@ pub struct FileIOConfig {
     pub verify_checksums: bool,
     /// Maximum number of concurrent file operations
     pub max_concurrent_operations: usize,
+    /// Bypass the OS page cache (O_DIRECT and equivalents); disables memory
+    /// mapping
+    pub direct_io: bool,
 }
 
 impl Default for FileIOConfig {
@@ -137,6 +140,7 @@ impl Default for FileIOConfig {
             buffer_size: 8192, // 8KB
             verify_checksums: true,
             max_concurrent_operations: 10,
+            direct_io: false,
         }
     }
 }
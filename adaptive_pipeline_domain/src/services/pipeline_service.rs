@@ -178,9 +178,10 @@
 
 use crate::entities::security_context::SecurityLevel;
 use crate::entities::{Pipeline, ProcessingContext, SecurityContext};
+use crate::events::{PermissionDeniedEvent, SecurityContextExpiredEvent};
 use crate::repositories::stage_executor::ResourceRequirements;
 use crate::services::datetime_serde;
-use crate::value_objects::{FileChunk, PipelineId};
+use crate::value_objects::{ChunkSize, CorrelationId, FileChunk, FileMode, JobPriority, PipelineId, SecretBytes};
 use crate::{PipelineError, ProcessingMetrics};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -207,6 +208,8 @@ use std::sync::Arc;
 /// - **Progress Events**: Periodic progress updates with throughput
 /// - **Lifecycle Events**: Pipeline start/completion events
 /// - **Error Events**: Processing errors and failure notifications
+/// - **Security Events**: Security context expiration at stage boundaries
+///   and security policy denials
 ///
 /// # Examples
 #[async_trait]
@@ -230,6 +233,52 @@ pub trait ProcessingObserver: Send + Sync {
         _final_metrics: Option<&ProcessingMetrics>,
     ) {
     }
+
+    /// Called when a stage boundary finds the security context expired,
+    /// before a refresh is attempted
+    async fn on_security_context_expired(&self, _event: &SecurityContextExpiredEvent) {}
+
+    /// Called when a security context does not satisfy a pipeline's security
+    /// policy, before processing or restoration starts
+    async fn on_permission_denied(&self, _event: &PermissionDeniedEvent) {}
+}
+
+/// Refresh hook for expired security contexts
+///
+/// Long-running jobs check the security context at every stage boundary.
+/// When it has expired, the pipeline asks the configured refresher for a
+/// replacement (for example by re-authenticating against an identity
+/// provider) and continues with it; without a refresher, or if the refresh
+/// fails, processing stops with `PipelineError::SecurityContextExpired`.
+#[async_trait]
+pub trait SecurityContextRefresher: Send + Sync {
+    /// Returns a fresh security context to replace `expired`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if re-authentication is not possible; the job then
+    /// fails with that error.
+    async fn refresh(&self, expired: &SecurityContext) -> Result<SecurityContext, PipelineError>;
+}
+
+/// Grace period a job gets to drain after shutdown is requested, unless the
+/// caller sets one
+pub const DEFAULT_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Shutdown request hook for long-running jobs
+///
+/// Once shutdown is requested, a job stops taking new chunks and lets those
+/// already in flight finish, for up to its grace period. Work still running
+/// after that is aborted. A checkpoint of the completed chunks is then
+/// written next to the output, and the job fails with
+/// `PipelineError::Cancelled`.
+#[async_trait]
+pub trait ShutdownSignal: Send + Sync {
+    /// Whether shutdown has been requested
+    fn is_requested(&self) -> bool;
+
+    /// Completes once shutdown has been requested
+    async fn requested(&self);
 }
 
 /// Configuration for processing a file through a pipeline
@@ -240,14 +289,44 @@ pub trait ProcessingObserver: Send + Sync {
 pub struct ProcessFileContext {
     /// Pipeline identifier
     pub pipeline_id: PipelineId,
+    /// The pipeline, when the caller has already loaded it; otherwise it is
+    /// loaded by `pipeline_id`
+    pub pipeline: Option<Pipeline>,
     /// Security context for processing
     pub security_context: SecurityContext,
     /// Optional override for number of worker threads
     pub user_worker_override: Option<usize>,
     /// Optional override for channel depth
     pub channel_depth_override: Option<usize>,
+    /// Optional byte budget for chunk data in flight between reader and
+    /// workers
+    pub inflight_window_override: Option<usize>,
+    /// Chunk size chosen by the caller instead of the file-size heuristic
+    pub chunk_size_override: Option<ChunkSize>,
+    /// Chunks whose stage panicked that a replacement worker may retry before
+    /// the run fails (zero fails on the first panic)
+    pub max_worker_restarts: u32,
+    /// Hash the input on a concurrent task fed by the reader, and take the
+    /// output checksum from the writer, instead of separate passes over each
+    /// file
+    pub checksum_offload: bool,
     /// Optional observer for progress tracking
     pub observer: Option<Arc<dyn ProcessingObserver>>,
+    /// Optional hook for refreshing an expired security context
+    pub security_refresher: Option<Arc<dyn SecurityContextRefresher>>,
+    /// Permissions given to the output file when it is created, instead of
+    /// those implied by the umask
+    pub output_mode: Option<FileMode>,
+    /// Priority at which workers wait for shared CPU tokens
+    pub priority: JobPriority,
+    /// Requests a graceful stop; `None` runs to completion
+    pub shutdown: Option<Arc<dyn ShutdownSignal>>,
+    /// How long in-flight chunks may run once shutdown is requested
+    pub grace_period: std::time::Duration,
+    /// Correlation ID of the run, recorded in the output's header
+    pub correlation_id: Option<CorrelationId>,
+    /// Passphrase the key of every encryption stage is derived from
+    pub password: Option<SecretBytes>,
 }
 
 impl ProcessFileContext {
@@ -256,13 +335,31 @@ impl ProcessFileContext {
     pub fn new(pipeline_id: PipelineId, security_context: SecurityContext) -> Self {
         Self {
             pipeline_id,
+            pipeline: None,
             security_context,
             user_worker_override: None,
             channel_depth_override: None,
+            inflight_window_override: None,
+            chunk_size_override: None,
+            max_worker_restarts: 0,
+            checksum_offload: false,
             observer: None,
+            security_refresher: None,
+            output_mode: None,
+            priority: JobPriority::default(),
+            shutdown: None,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            correlation_id: None,
+            password: None,
         }
     }
 
+    /// Processes with `pipeline` instead of loading it again
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
     /// Sets the worker count override
     pub fn with_workers(mut self, workers: usize) -> Self {
         self.user_worker_override = Some(workers);
@@ -275,11 +372,73 @@ impl ProcessFileContext {
         self
     }
 
+    /// Sets the byte budget for chunk data in flight
+    pub fn with_inflight_window(mut self, bytes: usize) -> Self {
+        self.inflight_window_override = Some(bytes);
+        self
+    }
+
+    /// Sets the chunk size to split the input into
+    pub fn with_chunk_size(mut self, chunk_size: ChunkSize) -> Self {
+        self.chunk_size_override = Some(chunk_size);
+        self
+    }
+
+    /// Sets how many panicked chunks replacement workers may retry
+    pub fn with_worker_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_worker_restarts = max_restarts;
+        self
+    }
+
+    /// Computes checksums alongside the stages instead of in separate passes
+    pub fn with_checksum_offload(mut self) -> Self {
+        self.checksum_offload = true;
+        self
+    }
+
+    /// Sets the permissions of the created output file
+    pub fn with_output_mode(mut self, mode: FileMode) -> Self {
+        self.output_mode = Some(mode);
+        self
+    }
+
+    /// Sets the priority for shared CPU tokens
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Sets the progress observer
     pub fn with_observer(mut self, observer: Arc<dyn ProcessingObserver>) -> Self {
         self.observer = Some(observer);
         self
     }
+
+    /// Sets the security context refresh hook
+    pub fn with_security_refresher(mut self, refresher: Arc<dyn SecurityContextRefresher>) -> Self {
+        self.security_refresher = Some(refresher);
+        self
+    }
+
+    /// Sets the shutdown signal and the grace period in-flight chunks get
+    /// once it fires
+    pub fn with_shutdown(mut self, shutdown: Arc<dyn ShutdownSignal>, grace_period: std::time::Duration) -> Self {
+        self.shutdown = Some(shutdown);
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Sets the correlation ID of the run
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Sets the passphrase encryption keys are derived from
+    pub fn with_password(mut self, password: SecretBytes) -> Self {
+        self.password = Some(password);
+        self
+    }
 }
 
 /// Domain service for pipeline operations
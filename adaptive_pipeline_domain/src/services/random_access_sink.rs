@@ -0,0 +1,59 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Random Access Sink Interface
+//!
+//! Port for positional writes: the contract the `.adapipe` writer relies on
+//! when concurrent workers write their chunks straight to their final
+//! offsets.
+//!
+//! ## Contract
+//!
+//! - [`write_at`](RandomAccessSink::write_at) may be called concurrently
+//!   from many tasks, as long as the ranges they write don't overlap. No
+//!   ordering between calls is implied.
+//! - [`size`](RandomAccessSink::size) is the logical end of the data: the
+//!   highest `offset + len` written so far. Space reserved by
+//!   [`preallocate`](RandomAccessSink::preallocate) does not count.
+//! - [`sync`](RandomAccessSink::sync) makes everything written durable and
+//!   releases reserved space beyond [`size`](RandomAccessSink::size). It is
+//!   called once, after the last write.
+//!
+//! ## Architecture Note - Infrastructure Port
+//!
+//! Like [`FileIOService`](super::file_io_service::FileIOService), this trait
+//! is async because it is I/O-bound. Implementations (local files,
+//! preallocated files, in-memory buffers) live in the infrastructure layer.
+
+use async_trait::async_trait;
+
+use crate::PipelineError;
+
+/// Destination that accepts writes at arbitrary offsets
+#[async_trait]
+pub trait RandomAccessSink: Send + Sync {
+    /// Human-readable name of the destination, for error messages
+    fn describe(&self) -> String;
+
+    /// Writes `data` starting at `offset`
+    ///
+    /// Safe to call concurrently for non-overlapping ranges.
+    async fn write_at(&self, offset: u64, data: &[u8]) -> Result<(), PipelineError>;
+
+    /// Logical size: the end of the furthest write so far
+    async fn size(&self) -> Result<u64, PipelineError>;
+
+    /// Reserves `len` bytes up front so later writes don't run out of space
+    ///
+    /// A hint; sinks that can't reserve space ignore it.
+    async fn preallocate(&self, _len: u64) -> Result<(), PipelineError> {
+        Ok(())
+    }
+
+    /// Flushes all writes to durable storage and trims unused reservations
+    async fn sync(&self) -> Result<(), PipelineError>;
+}
@@ -106,10 +106,11 @@
 //!     fn process_chunk(
 //!         &self,
 //!         chunk: FileChunk,
+//!         operation: Operation,
 //!         config: &StageConfiguration,
 //!         context: &mut ProcessingContext,
 //!     ) -> Result<FileChunk, PipelineError> {
-//!         match config.operation {
+//!         match operation {
 //!             Operation::Forward => {
 //!                 // Compress the chunk
 //!                 let compressed = brotli::compress(&chunk.data)?;
@@ -149,17 +150,18 @@
 //!     fn process_chunk(
 //!         &self,
 //!         chunk: FileChunk,
+//!         operation: Operation,
 //!         config: &StageConfiguration,
 //!         context: &mut ProcessingContext,
 //!     ) -> Result<FileChunk, PipelineError> {
-//!         match config.operation {
+//!         match operation {
 //!             Operation::Forward => {
 //!                 // Mask SSNs, credit cards, etc.
 //!                 let masked = self.mask_pii(&chunk.data)?;
 //!                 Ok(FileChunk::new(chunk.sequence_number, masked))
 //!             }
 //!             Operation::Reverse => {
-//!                 Err(PipelineError::StageConfiguration(
+//!                 Err(PipelineError::UnsupportedOperation(
 //!                     "PII masking is not reversible".to_string()
 //!                 ))
 //!             }
@@ -191,7 +193,7 @@
 //!     chunk: FileChunk,
 //!     context: &mut ProcessingContext,
 //! ) -> Result<FileChunk, PipelineError> {
-//!     service.process_chunk(chunk, stage.configuration(), context)
+//!     service.process_chunk(chunk, stage.configuration().operation, stage.configuration(), context)
 //! }
 //! ```
 //!
@@ -245,7 +247,7 @@
 //! - Follow Domain-Driven Design principles
 //! - Include comprehensive rustdoc documentation
 
-use crate::entities::{ProcessingContext, StageConfiguration, StagePosition, StageType};
+use crate::entities::{Operation, ProcessingContext, StageConfiguration, StagePosition, StageType};
 use crate::value_objects::file_chunk::FileChunk;
 use crate::PipelineError;
 use std::collections::HashMap;
@@ -332,6 +334,7 @@ use std::collections::HashMap;
 ///     fn process_chunk(
 ///         &self,
 ///         chunk: FileChunk,
+///         operation: Operation,
 ///         config: &StageConfiguration,
 ///         context: &mut ProcessingContext,
 ///     ) -> Result<FileChunk, PipelineError> {
@@ -427,22 +430,28 @@ pub trait FromParameters: Sized {
 /// All errors should be returned as `PipelineError` with descriptive messages
 /// to aid debugging and error reporting. Never panic in production code.
 pub trait StageService: Send + Sync {
-    /// Process a file chunk according to the operation (Forward or Reverse).
+    /// Process a file chunk in the requested direction (Forward or Reverse).
     ///
     /// This is the core processing method that applies the stage's
-    /// transformation to a chunk of data. Implementations should:
+    /// transformation to a chunk of data. The caller (normally the stage
+    /// executor) decides the direction and passes it explicitly, so services
+    /// never have to infer it. Implementations should:
     ///
-    /// - Check `config.operation` to determine direction (Forward/Reverse)
+    /// - Dispatch on `operation` (not `config.operation`)
     /// - Extract any needed parameters from `config.parameters`
     /// - Apply the appropriate transformation
     /// - Update metrics in `ProcessingContext`
     /// - Return transformed chunk or detailed error
     ///
+    /// Reversible stages must be symmetric: `Reverse(Forward(chunk))` yields
+    /// the original chunk data. Stages that cannot run in a direction return
+    /// `PipelineError::UnsupportedOperation`.
+    ///
     /// ## Parameters
     ///
     /// * `chunk` - The input chunk to process
-    /// * `config` - Stage configuration including operation, algorithm, and
-    ///   parameters
+    /// * `operation` - Direction to apply the transformation in
+    /// * `config` - Stage configuration including algorithm and parameters
     /// * `context` - Processing context for metrics and metadata
     ///
     /// ## Returns
@@ -454,7 +463,8 @@ pub trait StageService: Send + Sync {
     ///
     /// Implementations should return errors for:
     /// - Invalid parameters in config
-    /// - Unsupported operations (e.g., Reverse when not reversible)
+    /// - Unsupported operations (`PipelineError::UnsupportedOperation`, e.g.
+    ///   Reverse when not reversible)
     /// - Processing failures (compression errors, encryption failures, etc.)
     /// - Resource exhaustion or allocation failures
     ///
@@ -464,6 +474,7 @@ pub trait StageService: Send + Sync {
     /// fn process_chunk(
     ///     &self,
     ///     chunk: FileChunk,
+    ///     operation: Operation,
     ///     config: &StageConfiguration,
     ///     context: &mut ProcessingContext,
     /// ) -> Result<FileChunk, PipelineError> {
@@ -473,7 +484,7 @@ pub trait StageService: Send + Sync {
     ///         .unwrap_or(6);
     ///
     ///     // Process based on operation
-    ///     match config.operation {
+    ///     match operation {
     ///         Operation::Forward => self.compress(chunk, level),
     ///         Operation::Reverse => self.decompress(chunk),
     ///     }
@@ -482,6 +493,7 @@ pub trait StageService: Send + Sync {
     fn process_chunk(
         &self,
         chunk: FileChunk,
+        operation: Operation,
         config: &StageConfiguration,
         context: &mut ProcessingContext,
     ) -> Result<FileChunk, PipelineError>;
@@ -534,14 +546,16 @@ pub trait StageService: Send + Sync {
     ///
     /// - **Non-reversible (false)**: Only supports Forward operation
     ///   - Examples: PII masking, hashing, one-way transformations
-    ///   - Cannot be reversed; errors on `Operation::Reverse`
+    ///   - Cannot be reversed; returns `PipelineError::UnsupportedOperation`
+    ///     for `Operation::Reverse`
     ///   - Files processed with these stages cannot be fully restored
     ///
     /// ## Usage
     ///
     /// - Pipeline validation checks reversibility when creating restoration
     ///   pipelines
-    /// - Non-reversible stages should return error for `Operation::Reverse`
+    /// - Non-reversible stages must return `PipelineError::UnsupportedOperation`
+    ///   for `Operation::Reverse`
     /// - Documentation should clearly state if stage is one-way
     ///
     /// ## Example
@@ -552,9 +566,9 @@ pub trait StageService: Send + Sync {
     /// }
     ///
     /// fn process_chunk(...) -> Result<FileChunk, PipelineError> {
-    ///     match config.operation {
+    ///     match operation {
     ///         Operation::Forward => self.mask_pii(chunk),
-    ///         Operation::Reverse => Err(PipelineError::StageConfiguration(
+    ///         Operation::Reverse => Err(PipelineError::UnsupportedOperation(
     ///             "PII masking is not reversible".to_string()
     ///         )),
     ///     }
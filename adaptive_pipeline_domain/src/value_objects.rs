@@ -26,12 +26,14 @@
 //! ### Identifiers
 //! Strongly-typed identifiers that prevent mixing different types of IDs:
 //!
+//! - [`PipelineDefinition`]: Portable pipeline document for import and export
 //! - [`PipelineId`]: Unique identifier for pipeline instances
 //! - [`StageId`]: Identifier for individual pipeline stages
 //! - [`FileChunkId`]: Identifier for file chunks in processing
 //! - [`EncryptionKeyId`]: Identifier for encryption keys
 //! - [`UserId`]: Identifier for user accounts and sessions
 //! - [`SessionId`]: Identifier for user sessions
+//! - [`JobId`]: Identifier for queued jobs
 //! - [`ProcessingContextId`]: Identifier for processing contexts
 //! - [`SecurityContextId`]: Identifier for security contexts
 //! - [`GenericId`]: Generic type-safe identifier system
@@ -170,24 +172,58 @@
 //! }
 //! ```
 
+// The module docs sketch how value objects are tested
+#![allow(clippy::test_attr_in_doctest)]
+
 pub mod algorithm;
+pub mod audit_record;
+pub mod batch_retry_manifest;
 pub mod binary_file_format;
+pub mod build_provenance;
+pub mod byte_range;
+pub mod checksum_algorithm;
+pub mod chunk_encryption_spec;
 pub mod chunk_metadata;
 pub mod chunk_size;
+pub mod chunk_throughput;
+pub mod concurrency_summary;
+pub mod content_type;
+pub mod correlation_id;
 pub mod encryption_benchmark;
 pub mod encryption_key_id;
+pub mod execution_topology;
+pub mod feature_flags;
 pub mod file_chunk;
 pub mod file_chunk_id;
+pub mod file_mode;
 pub mod file_path;
 pub mod file_permissions;
 pub mod generic_id;
 pub mod generic_size;
+pub mod idempotency_key;
+pub mod job_id;
+pub mod job_priority;
+pub mod namespace;
+pub mod namespace_usage;
+pub mod nonce_strategy;
+pub mod overwrite_policy;
+pub mod password_kdf;
+pub mod path_filter;
+pub mod pipeline_definition;
 pub mod pipeline_id;
 pub mod pipeline_requirements;
 pub mod processing_context_id;
+pub mod processing_manifest;
 pub mod processing_step_descriptor;
+pub mod quota_limits;
+pub mod restore_report;
+pub mod role;
+pub mod secret_bytes;
 pub mod security_context_id;
+pub mod security_policy;
 pub mod session_id;
+pub mod shutdown_checkpoint;
+pub mod stage_graph;
 pub mod stage_id;
 pub mod stage_order;
 pub mod stage_parameters;
@@ -195,26 +231,57 @@ pub mod user_id;
 pub mod worker_count;
 
 // Re-export all value object types for convenient access
-pub use algorithm::Algorithm;
-pub use binary_file_format::{ChunkFormat, FileHeader, ProcessingStepType};
+pub use algorithm::{Algorithm, FIPS_MODE};
+pub use audit_record::AuditRecord;
+pub use batch_retry_manifest::{BatchFailure, BatchRetryManifest};
+pub use binary_file_format::{ChunkFormat, ChunkIndexEntry, FileHeader, ProcessingStepType};
+pub use build_provenance::BuildProvenance;
+pub use byte_range::ByteRange;
+pub use checksum_algorithm::{ChecksumAlgorithm, CHECKSUM_ALGORITHM_KEY};
+pub use chunk_encryption_spec::{ChunkBinding, ChunkTestVector, TestVectorSuite};
 pub use chunk_metadata::ChunkMetadata;
-pub use chunk_size::ChunkSize;
+pub use chunk_size::{ChunkSize, CHUNK_SIZE_KEY};
+pub use chunk_throughput::ChunkThroughput;
+pub use concurrency_summary::ConcurrencySummary;
+pub use content_type::ContentType;
+pub use correlation_id::CorrelationId;
 pub use encryption_benchmark::EncryptionBenchmark;
 pub use encryption_key_id::EncryptionKeyId;
+pub use execution_topology::{ExecutionTopology, EXECUTION_TOPOLOGY_KEY};
+pub use feature_flags::{FeatureFlagSpec, FeatureFlagState, FeatureFlags, FlagLifecycle, FEATURE_FLAGS};
 pub use file_chunk::FileChunk;
 pub use file_chunk_id::FileChunkId;
+pub use file_mode::FileMode;
 pub use file_path::FilePath;
 pub use file_permissions::FilePermissions;
 pub use generic_id::GenericId;
 pub use generic_size::GenericSize;
+pub use idempotency_key::{IdempotencyKey, IdempotencyRecord};
+pub use job_id::JobId;
+pub use job_priority::JobPriority;
+pub use namespace::{Namespace, DEFAULT_NAMESPACE};
+pub use namespace_usage::NamespaceUsage;
+pub use nonce_strategy::{NonceStrategy, NONCE_STRATEGY_KEY};
+pub use overwrite_policy::{OutputResolution, OverwritePolicy};
+pub use password_kdf::{KdfCost, PasswordKdf};
+pub use path_filter::PathFilter;
+pub use pipeline_definition::{DefinitionFormat, PipelineDefinition, StageDefinition};
 pub use pipeline_id::PipelineId;
 pub use pipeline_requirements::PipelineRequirements;
 pub use processing_context_id::ProcessingContextId;
+pub use processing_manifest::{ManifestSignature, ProcessingManifest};
 pub use processing_step_descriptor::ProcessingStepDescriptor;
+pub use quota_limits::{QuotaLimit, QuotaLimits};
+pub use restore_report::{ChecksumResult, RestoreReport, StageTiming};
+pub use role::{ProtectedOperation, Role, RoleAssignment};
+pub use secret_bytes::SecretBytes;
 pub use security_context_id::SecurityContextId;
+pub use security_policy::{SecurityPolicy, MINIMUM_SECURITY_LEVEL_KEY, REQUIRED_PERMISSIONS_KEY};
 pub use session_id::SessionId;
+pub use shutdown_checkpoint::ShutdownCheckpoint;
+pub use stage_graph::{GraphEndpoint, GraphFormat, StageEdge, StageGraph, StageNode};
 pub use stage_id::StageId;
 pub use stage_order::StageOrder;
 pub use stage_parameters::StageParameters;
 pub use user_id::UserId;
-pub use worker_count::WorkerCount;
+pub use worker_count::{WorkerCount, WORKER_COUNT_KEY};
@@ -35,7 +35,7 @@
 //!
 //! ### Algorithm Categories
 //!
-//! - **Compression**: Data compression algorithms (brotli, gzip, zstd, lz4)
+//! - **Compression**: Data compression algorithms (brotli, gzip, zstd, lz4, xz)
 //! - **Encryption**: Data encryption algorithms (AES-256-GCM,
 //!   ChaCha20-Poly1305)
 //! - **Hashing**: Cryptographic hash algorithms (SHA-256, SHA-512, Blake3)
@@ -81,6 +81,7 @@
 //! - **gzip**: GNU zip compression algorithm
 //! - **zstd**: Facebook's Zstandard compression algorithm
 //! - **lz4**: LZ4 fast compression algorithm
+//! - **xz**: XZ (LZMA2) compression for the highest ratios
 //! - **deflate**: DEFLATE compression algorithm
 //!
 //! ### Encryption Algorithms
@@ -159,7 +160,7 @@
 //!
 //! ### Language Mappings
 //!
-//! - **Rust**: `Algorithm` newtype wrapper
+//! - **Rust**: `Algorithm` enum with a validated `Custom` variant
 //! - **Go**: `Algorithm` struct with validation
 //! - **Python**: `Algorithm` class with validation
 //! - **JavaScript**: Algorithm validation functions
@@ -204,45 +205,87 @@
 //! - **Plugin System**: Plugin system for custom algorithms
 
 use serde::{Deserialize, Serialize};
-use std::cmp::{Ord, PartialOrd};
+use std::cmp::{Ord, Ordering, PartialOrd};
 use std::fmt::{self, Display};
 
 use crate::PipelineError;
 
+/// Whether this build was compiled with the `fips` feature
+///
+/// In FIPS mode only FIPS-approved cryptographic primitives (AES-GCM and
+/// SHA-2) may be used; compression and transform stages are unaffected
+/// because they are not cryptographic.
+pub const FIPS_MODE: bool = cfg!(feature = "fips");
+
 /// Algorithm value object for pipeline stage processing
 /// # Purpose
-/// Type-safe algorithm specification that provides:
+/// Single canonical representation of every algorithm the pipeline knows
+/// about. Provides:
 /// - Validation of algorithm names and formats
+/// - Normalization of legacy and alternate spellings ("aes256gcm",
+///   "aes256-gcm", "AES-256-GCM") to one canonical name ("aes-256-gcm")
 /// - Algorithm-specific behavior and constraints
 /// - Immutable value semantics (DDD value object)
 /// - Cross-language compatibility
+/// # Why
+/// Stage configurations, database rows, and `.adapipe` headers historically
+/// spelled the same algorithm several different ways. Every place that needs
+/// to decide "which algorithm is this?" must go through `Algorithm` so aliases
+/// cannot drift between layers.
 /// # Supported Algorithms
-/// - **Compression**: brotli, gzip, zstd, lz4
-/// - **Encryption**: aes-256-gcm, chacha20-poly1305, aes-128-gcm
-/// - **Hashing**: sha256, sha512, blake3
+/// - **Compression**: brotli, gzip, zstd, lz4, xz, deflate
+/// - **Encryption**: aes-256-gcm, aes-192-gcm, aes-128-gcm, chacha20-poly1305,
+///   aes-256-cbc, aes-128-cbc
+/// - **Hashing**: sha256, sha512, sha3-256, blake3, md5, sha1
 /// - **Custom**: User-defined algorithms
 /// # Cross-Language Mapping
-/// - **Rust**: `Algorithm` (newtype wrapper)
+/// - **Rust**: `Algorithm` enum with a `Custom` escape hatch
 /// - **Go**: `Algorithm` struct with same interface
-/// - **JSON**: String representation
+/// - **JSON**: String representation (always the canonical name)
 /// - **SQLite**: TEXT column with validation
 /// # Examples
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct Algorithm(String);
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Algorithm {
+    Brotli,
+    Gzip,
+    Zstd,
+    Lz4,
+    Xz,
+    Deflate,
+    Aes128Gcm,
+    Aes192Gcm,
+    Aes256Gcm,
+    Aes128Cbc,
+    Aes256Cbc,
+    ChaCha20Poly1305,
+    Sha256,
+    Sha512,
+    Sha3_256,
+    Blake3,
+    Md5,
+    Sha1,
+    /// User-defined algorithm with a validated name
+    Custom(String),
+}
 
 impl Algorithm {
-    /// Creates a new algorithm with validated name
+    /// Creates a new algorithm from a name, normalizing known aliases
     /// # Purpose
-    /// Creates an `Algorithm` value object after validating the algorithm name
-    /// against strict format rules. This ensures all algorithm instances
-    /// are valid and type-safe. # Why
+    /// Creates an `Algorithm` value object. Names of known algorithms are
+    /// matched case-insensitively against every accepted spelling and
+    /// resolved to their canonical variant; anything else becomes a
+    /// `Custom` algorithm after validating the name against strict format
+    /// rules.
+    /// # Why
     /// Algorithm names must follow a consistent format for:
     /// - Cross-language compatibility (Rust, Go, JSON)
     /// - Database storage validation
     /// - Configuration file parsing
     /// - Prevention of injection attacks
     /// # Arguments
-    /// * `name` - The algorithm name string. Must:
+    /// * `name` - The algorithm name string. Known aliases are always
+    ///   accepted; custom names must:
     ///   - Not be empty
     ///   - Be 1-64 characters long
     ///   - Contain only lowercase letters, hyphens, and digits
@@ -253,17 +296,20 @@ impl Algorithm {
     /// * `Ok(Algorithm)` - Successfully validated algorithm
     /// * `Err(PipelineError::InvalidConfiguration)` - Name validation failed
     /// # Errors
-    /// Returns `PipelineError::InvalidConfiguration` when:
-    /// - Name is empty
-    /// - Name exceeds 64 characters
-    /// - Name contains invalid characters (uppercase, special chars)
-    /// - Name starts/ends with hyphen
-    /// - Name starts with digit
-    /// - Name contains consecutive hyphens ("--")
+    /// Returns `PipelineError::InvalidConfiguration` when a custom name:
+    /// - Is empty
+    /// - Exceeds 64 characters
+    /// - Contains invalid characters (uppercase, special chars)
+    /// - Starts/ends with hyphen
+    /// - Starts with digit
+    /// - Contains consecutive hyphens ("--")
     /// # Examples
     pub fn new(name: String) -> Result<Self, PipelineError> {
+        if let Some(known) = Self::known(&name) {
+            return Ok(known);
+        }
         Self::validate_name(&name)?;
-        Ok(Self(name))
+        Ok(Self::Custom(name))
     }
 
     /// Creates an algorithm from a string slice
@@ -282,14 +328,84 @@ impl Algorithm {
         Self::new(name.to_string())
     }
 
-    /// Gets the algorithm name as a string reference
+    /// Resolves any accepted spelling of a known algorithm to its canonical
+    /// name
     /// # Purpose
-    /// Provides read-only access to the algorithm's underlying name string.
+    /// Normalizes lookup keys (service registries, stored stage
+    /// configurations, file headers) without requiring the key to be a valid
+    /// `Algorithm`. Names such as "pii_masking" or "passthrough" are not
+    /// algorithms and simply return `None`.
+    /// # Returns
+    /// * `Some(name)` - Canonical name of the matching known algorithm
+    /// * `None` - Name does not refer to a known algorithm
+    /// # Examples
+    pub fn canonical_name(name: &str) -> Option<&'static str> {
+        Self::known(name).map(|algorithm| algorithm.static_name())
+    }
+
+    /// Matches a name against every accepted spelling of the known algorithms
+    fn known(name: &str) -> Option<Self> {
+        let algorithm = match name.trim().to_ascii_lowercase().as_str() {
+            "brotli" => Self::Brotli,
+            "gzip" => Self::Gzip,
+            "zstd" | "zstandard" => Self::Zstd,
+            "lz4" => Self::Lz4,
+            "xz" | "lzma" | "lzma2" => Self::Xz,
+            "deflate" => Self::Deflate,
+            "aes-128-gcm" | "aes128-gcm" | "aes128gcm" | "aes_128_gcm" => Self::Aes128Gcm,
+            "aes-192-gcm" | "aes192-gcm" | "aes192gcm" | "aes_192_gcm" => Self::Aes192Gcm,
+            "aes-256-gcm" | "aes256-gcm" | "aes256gcm" | "aes_256_gcm" => Self::Aes256Gcm,
+            "aes-128-cbc" | "aes128-cbc" | "aes128cbc" | "aes_128_cbc" => Self::Aes128Cbc,
+            "aes-256-cbc" | "aes256-cbc" | "aes256cbc" | "aes_256_cbc" => Self::Aes256Cbc,
+            "chacha20-poly1305" | "chacha20poly1305" | "chacha20_poly1305" => Self::ChaCha20Poly1305,
+            "sha256" | "sha-256" => Self::Sha256,
+            "sha512" | "sha-512" => Self::Sha512,
+            "sha3-256" | "sha3_256" | "sha3256" => Self::Sha3_256,
+            "blake3" => Self::Blake3,
+            "md5" => Self::Md5,
+            "sha1" | "sha-1" => Self::Sha1,
+            _ => return None,
+        };
+        Some(algorithm)
+    }
+
+    /// Canonical name of a known algorithm (custom names are not static)
+    fn static_name(&self) -> &'static str {
+        match self {
+            Self::Brotli => "brotli",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Lz4 => "lz4",
+            Self::Xz => "xz",
+            Self::Deflate => "deflate",
+            Self::Aes128Gcm => "aes-128-gcm",
+            Self::Aes192Gcm => "aes-192-gcm",
+            Self::Aes256Gcm => "aes-256-gcm",
+            Self::Aes128Cbc => "aes-128-cbc",
+            Self::Aes256Cbc => "aes-256-cbc",
+            Self::ChaCha20Poly1305 => "chacha20-poly1305",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+            Self::Sha3_256 => "sha3-256",
+            Self::Blake3 => "blake3",
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Gets the canonical algorithm name as a string reference
+    /// # Purpose
+    /// Provides read-only access to the algorithm's canonical name. This is
+    /// the only spelling written to headers, databases, and registries.
     /// # Returns
     /// String slice containing the algorithm name
     /// # Examples
     pub fn name(&self) -> &str {
-        &self.0
+        match self {
+            Self::Custom(name) => name,
+            known => known.static_name(),
+        }
     }
 
     /// Checks if this is a compression algorithm
@@ -300,11 +416,14 @@ impl Algorithm {
     /// Compression algorithms require specific stage configurations and have
     /// different performance characteristics than encryption or hashing
     /// algorithms. # Returns
-    /// * `true` - Algorithm is one of: brotli, gzip, zstd, lz4, deflate
+    /// * `true` - Algorithm is one of: brotli, gzip, zstd, lz4, xz, deflate
     /// * `false` - Algorithm is not a compression algorithm
     /// # Examples
     pub fn is_compression(&self) -> bool {
-        matches!(self.0.as_str(), "brotli" | "gzip" | "zstd" | "lz4" | "deflate")
+        matches!(
+            self,
+            Self::Brotli | Self::Gzip | Self::Zstd | Self::Lz4 | Self::Xz | Self::Deflate
+        )
     }
 
     /// Checks if this is an encryption algorithm
@@ -315,14 +434,19 @@ impl Algorithm {
     /// Encryption algorithms require key management and have different
     /// security properties than compression or hashing algorithms.
     /// # Returns
-    /// * `true` - Algorithm is one of: aes-256-gcm, aes-128-gcm, aes-128-cbc,
-    ///   chacha20-poly1305, aes-256-cbc
+    /// * `true` - Algorithm is one of: aes-256-gcm, aes-192-gcm, aes-128-gcm,
+    ///   aes-128-cbc, chacha20-poly1305, aes-256-cbc
     /// * `false` - Algorithm is not an encryption algorithm
     /// # Examples
     pub fn is_encryption(&self) -> bool {
         matches!(
-            self.0.as_str(),
-            "aes-256-gcm" | "aes-128-gcm" | "aes-128-cbc" | "chacha20-poly1305" | "aes-256-cbc"
+            self,
+            Self::Aes128Gcm
+                | Self::Aes192Gcm
+                | Self::Aes256Gcm
+                | Self::Aes128Cbc
+                | Self::Aes256Cbc
+                | Self::ChaCha20Poly1305
         )
     }
 
@@ -343,8 +467,8 @@ impl Algorithm {
     /// # Examples
     pub fn is_hashing(&self) -> bool {
         matches!(
-            self.0.as_str(),
-            "sha256" | "sha512" | "sha3-256" | "blake3" | "md5" | "sha1"
+            self,
+            Self::Sha256 | Self::Sha512 | Self::Sha3_256 | Self::Blake3 | Self::Md5 | Self::Sha1
         )
     }
 
@@ -356,12 +480,12 @@ impl Algorithm {
     /// Custom algorithms allow extensibility for domain-specific processing
     /// while maintaining type safety and validation.
     /// # Returns
-    /// * `true` - Algorithm starts with "custom-" prefix OR doesn't match any
-    ///   predefined compression, encryption, or hashing algorithm
+    /// * `true` - Algorithm doesn't match any predefined compression,
+    ///   encryption, or hashing algorithm
     /// * `false` - Algorithm is a predefined standard algorithm
     /// # Examples
     pub fn is_custom(&self) -> bool {
-        self.0.starts_with("custom-") || (!self.is_compression() && !self.is_encryption() && !self.is_hashing())
+        matches!(self, Self::Custom(_))
     }
 
     /// Gets the algorithm category
@@ -387,6 +511,41 @@ impl Algorithm {
         }
     }
 
+    /// Checks if this is a FIPS-approved cryptographic primitive
+    /// # Returns
+    /// * `true` - AES-GCM (128/192/256) or SHA-2 (sha256, sha512)
+    /// * `false` - Any other algorithm, including non-cryptographic ones
+    pub fn is_fips_approved(&self) -> bool {
+        matches!(
+            self,
+            Self::Aes128Gcm | Self::Aes192Gcm | Self::Aes256Gcm | Self::Sha256 | Self::Sha512
+        )
+    }
+
+    /// Checks if this build allows the algorithm
+    /// # Purpose
+    /// Every algorithm is permitted in a standard build. In a FIPS build
+    /// ([`FIPS_MODE`]) encryption and hashing algorithms must be
+    /// FIPS-approved; compression and custom algorithms remain available.
+    pub fn is_permitted(&self) -> bool {
+        !FIPS_MODE || self.is_fips_approved() || !(self.is_encryption() || self.is_hashing())
+    }
+
+    /// Returns an error if this build does not allow the algorithm
+    /// # Errors
+    /// `PipelineError::UnsupportedOperation` naming the algorithm when it is
+    /// a non-approved cryptographic primitive in a FIPS build.
+    pub fn ensure_permitted(&self) -> Result<(), PipelineError> {
+        if self.is_permitted() {
+            Ok(())
+        } else {
+            Err(PipelineError::unsupported_operation(format!(
+                "'{}' is not FIPS-approved and is disabled in this FIPS-mode build",
+                self
+            )))
+        }
+    }
+
     /// Validates the algorithm name format
     fn validate_name(name: &str) -> Result<(), PipelineError> {
         if name.is_empty() {
@@ -449,7 +608,7 @@ impl Algorithm {
     /// See [`Algorithm::new`] for validation rules and error conditions.
     /// # Examples
     pub fn validate(&self) -> Result<(), PipelineError> {
-        Self::validate_name(&self.0)
+        Self::validate_name(self.name())
     }
 }
 
@@ -465,7 +624,20 @@ pub enum AlgorithmCategory {
 
 impl Display for Algorithm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Algorithms order lexicographically by canonical name
+impl Ord for Algorithm {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name().cmp(other.name())
+    }
+}
+
+impl PartialOrd for Algorithm {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -477,15 +649,26 @@ impl std::str::FromStr for Algorithm {
     }
 }
 
+impl TryFrom<String> for Algorithm {
+    type Error = PipelineError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::new(name)
+    }
+}
+
 impl From<Algorithm> for String {
     fn from(algorithm: Algorithm) -> Self {
-        algorithm.0
+        match algorithm {
+            Algorithm::Custom(name) => name,
+            known => known.static_name().to_string(),
+        }
     }
 }
 
 impl AsRef<str> for Algorithm {
     fn as_ref(&self) -> &str {
-        &self.0
+        self.name()
     }
 }
 
@@ -499,7 +682,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for Brotli
     /// # Examples
     pub fn brotli() -> Self {
-        Self("brotli".to_string())
+        Self::Brotli
     }
 
     /// Creates a Gzip compression algorithm
@@ -510,7 +693,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for Gzip
     /// # Examples
     pub fn gzip() -> Self {
-        Self("gzip".to_string())
+        Self::Gzip
     }
 
     /// Creates a Zstandard compression algorithm
@@ -521,7 +704,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for Zstandard
     /// # Examples
     pub fn zstd() -> Self {
-        Self("zstd".to_string())
+        Self::Zstd
     }
 
     /// Creates an LZ4 compression algorithm
@@ -532,7 +715,18 @@ impl Algorithm {
     /// Validated `Algorithm` instance for LZ4
     /// # Examples
     pub fn lz4() -> Self {
-        Self("lz4".to_string())
+        Self::Lz4
+    }
+
+    /// Creates an XZ compression algorithm
+    /// # Purpose
+    /// Factory method for XZ (LZMA2) compression.
+    /// Slow to compress but gives the highest ratios, for long-term archives.
+    /// # Returns
+    /// Validated `Algorithm` instance for XZ
+    /// # Examples
+    pub fn xz() -> Self {
+        Self::Xz
     }
 
     /// Creates an AES-256-GCM encryption algorithm
@@ -543,7 +737,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for AES-256-GCM
     /// # Examples
     pub fn aes_256_gcm() -> Self {
-        Self("aes-256-gcm".to_string())
+        Self::Aes256Gcm
     }
 
     /// Creates a ChaCha20-Poly1305 encryption algorithm
@@ -554,7 +748,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for ChaCha20-Poly1305
     /// # Examples
     pub fn chacha20_poly1305() -> Self {
-        Self("chacha20-poly1305".to_string())
+        Self::ChaCha20Poly1305
     }
 
     /// Creates a SHA-256 hashing algorithm
@@ -565,7 +759,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for SHA-256
     /// # Examples
     pub fn sha256() -> Self {
-        Self("sha256".to_string())
+        Self::Sha256
     }
 
     /// Creates a SHA-512 hashing algorithm
@@ -576,7 +770,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for SHA-512
     /// # Examples
     pub fn sha512() -> Self {
-        Self("sha512".to_string())
+        Self::Sha512
     }
 
     /// Creates a BLAKE3 hashing algorithm
@@ -587,7 +781,7 @@ impl Algorithm {
     /// Validated `Algorithm` instance for BLAKE3
     /// # Examples
     pub fn blake3() -> Self {
-        Self("blake3".to_string())
+        Self::Blake3
     }
 
     /// AES-256-GCM encryption algorithm (alias for test framework
@@ -596,19 +790,24 @@ impl Algorithm {
         Self::aes_256_gcm()
     }
 
+    /// AES-128-GCM encryption
+    pub fn aes_128_gcm() -> Self {
+        Self::Aes128Gcm
+    }
+
     /// No algorithm / passthrough
     pub fn none() -> Self {
-        Self("none".to_string())
+        Self::Custom("none".to_string())
     }
 
     /// AES-128-CBC encryption
     pub fn aes_128_cbc() -> Self {
-        Self("aes-128-cbc".to_string())
+        Self::Aes128Cbc
     }
 
     /// SHA3-256 hashing
     pub fn sha3_256() -> Self {
-        Self("sha3-256".to_string())
+        Self::Sha3_256
     }
 }
 
@@ -621,7 +820,7 @@ pub mod algorithm_utils {
     /// Returns a collection of all predefined compression algorithms.
     /// Useful for configuration validation and algorithm selection UIs.
     /// # Returns
-    /// Vector containing: brotli, gzip, zstd, lz4
+    /// Vector containing: brotli, gzip, zstd, lz4, xz
     /// # Examples
     pub fn compression_algorithms() -> Vec<Algorithm> {
         vec![
@@ -629,6 +828,7 @@ pub mod algorithm_utils {
             Algorithm::gzip(),
             Algorithm::zstd(),
             Algorithm::lz4(),
+            Algorithm::xz(),
         ]
     }
 
@@ -726,7 +926,7 @@ mod tests {
     //
     // Tests cover creation, validation, categorization, and serialization.
 
-    use crate::value_objects::algorithm::AlgorithmCategory;
+    use crate::value_objects::algorithm::{AlgorithmCategory, FIPS_MODE};
     use crate::value_objects::Algorithm;
     use serde_json;
     use std::collections::HashMap;
@@ -839,6 +1039,11 @@ mod tests {
         assert_eq!(zstd.name(), "zstd");
         assert!(zstd.is_compression());
 
+        let xz = Algorithm::xz();
+        assert_eq!(xz.name(), "xz");
+        assert!(xz.is_compression());
+        assert_eq!(Algorithm::parse("lzma").unwrap(), xz);
+
         // Test encryption algorithms
         let aes_128 = Algorithm::aes_128_cbc();
         assert_eq!(aes_128.name(), "aes-128-cbc");
@@ -1041,6 +1246,7 @@ mod tests {
             Algorithm::brotli(),
             Algorithm::gzip(),
             Algorithm::lz4(),
+            Algorithm::xz(),
             Algorithm::zstd(),
         ];
 
@@ -1160,6 +1366,44 @@ mod tests {
         assert_eq!(format!("{:?}", AlgorithmCategory::Unknown), "Unknown");
     }
 
+    /// Tests normalization of alternate algorithm spellings.
+    /// Validates that:
+    /// - Legacy compact names ("aes256gcm") resolve to canonical variants
+    /// - Hyphen, underscore, and case variants resolve to the same algorithm
+    /// - Display and serialization always use the canonical name
+    /// - Non-algorithm registry keys are not canonicalized
+    #[test]
+    fn test_algorithm_alias_normalization() {
+        let aliases = vec![
+            ("aes256gcm", Algorithm::Aes256Gcm),
+            ("aes256-gcm", Algorithm::Aes256Gcm),
+            ("AES-256-GCM", Algorithm::Aes256Gcm),
+            ("aes_256_gcm", Algorithm::Aes256Gcm),
+            ("aes128gcm", Algorithm::Aes128Gcm),
+            ("aes192-gcm", Algorithm::Aes192Gcm),
+            ("chacha20poly1305", Algorithm::ChaCha20Poly1305),
+            ("ChaCha20-Poly1305", Algorithm::ChaCha20Poly1305),
+            ("SHA-256", Algorithm::Sha256),
+            ("Brotli", Algorithm::Brotli),
+            ("zstandard", Algorithm::Zstd),
+        ];
+
+        for (alias, expected) in aliases {
+            let parsed = Algorithm::parse(alias).unwrap();
+            assert_eq!(parsed, expected, "'{}' should normalize to {:?}", alias, expected);
+            assert_eq!(parsed.to_string(), expected.name());
+            assert_eq!(Algorithm::canonical_name(alias), Some(expected.name()));
+        }
+
+        let json = serde_json::to_string(&Algorithm::parse("aes256gcm").unwrap()).unwrap();
+        assert_eq!(json, "\"aes-256-gcm\"");
+        let legacy: Algorithm = serde_json::from_str("\"chacha20poly1305\"").unwrap();
+        assert_eq!(legacy, Algorithm::chacha20_poly1305());
+
+        assert_eq!(Algorithm::canonical_name("pii_masking"), None);
+        assert_eq!(Algorithm::canonical_name("passthrough"), None);
+    }
+
     /// Tests Clone and Copy trait implementations for Algorithm.
     /// Validates that:
     /// - Algorithm objects can be cloned correctly
@@ -1181,4 +1425,28 @@ mod tests {
         drop(original);
         assert_eq!(cloned.name(), "aes-256-gcm");
     }
+
+    /// Tests FIPS classification and the build-dependent permission check.
+    /// Validates that:
+    /// - Only AES-GCM and SHA-2 are FIPS-approved
+    /// - Compression and custom algorithms are always permitted
+    /// - Non-approved cryptography is rejected only in FIPS builds
+    #[test]
+    fn test_algorithm_fips_approval() {
+        for approved in [Algorithm::aes_256_gcm(), Algorithm::aes_128_gcm(), Algorithm::sha256()] {
+            assert!(approved.is_fips_approved(), "{} should be approved", approved);
+            assert!(approved.is_permitted());
+        }
+        for restricted in [
+            Algorithm::chacha20_poly1305(),
+            Algorithm::blake3(),
+            Algorithm::parse("md5").unwrap(),
+        ] {
+            assert!(!restricted.is_fips_approved(), "{} should not be approved", restricted);
+            assert_eq!(restricted.is_permitted(), !FIPS_MODE);
+            assert_eq!(restricted.ensure_permitted().is_err(), FIPS_MODE);
+        }
+        assert!(Algorithm::brotli().is_permitted());
+        assert!(Algorithm::new("custom-transform".to_string()).unwrap().is_permitted());
+    }
 }
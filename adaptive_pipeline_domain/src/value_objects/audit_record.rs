@@ -0,0 +1,86 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Audit Record Value Object
+//!
+//! Who changed what, stored with the change itself. A unit of work records
+//! it in the same transaction as the mutation it describes, so the audit
+//! trail never shows a change that was rolled back or misses one that was
+//! committed. Changes made through an API session name the session too, and
+//! every record names the run that made it by its correlation ID.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{AuditRecord, PipelineId};
+//!
+//! let id = PipelineId::new();
+//! let record = AuditRecord::new("alice", "pipeline.create", "3 stages").with_pipeline(id.clone());
+//! assert_eq!(record.pipeline_id, Some(id));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::value_objects::{CorrelationId, PipelineId, SessionId};
+
+/// One audited change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Who made the change
+    pub principal: String,
+
+    /// What was done, as `<entity>.<verb>`, e.g. `pipeline.delete`
+    pub action: String,
+
+    /// The pipeline changed, if any
+    pub pipeline_id: Option<PipelineId>,
+
+    /// Human-readable detail
+    pub detail: String,
+
+    /// The session the change was made through, if any
+    #[serde(default)]
+    pub session_id: Option<SessionId>,
+
+    /// The run the change was made in, if known
+    #[serde(default)]
+    pub correlation_id: Option<CorrelationId>,
+
+    /// When the change was made
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditRecord {
+    /// Creates a record of `principal` doing `action`, timestamped now
+    pub fn new(principal: impl Into<String>, action: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            principal: principal.into(),
+            action: action.into(),
+            pipeline_id: None,
+            detail: detail.into(),
+            session_id: None,
+            correlation_id: None,
+            occurred_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Attributes the change to the pipeline `id`
+    pub fn with_pipeline(mut self, id: PipelineId) -> Self {
+        self.pipeline_id = Some(id);
+        self
+    }
+
+    /// Attributes the change to the session `id`
+    pub fn with_session(mut self, id: SessionId) -> Self {
+        self.session_id = Some(id);
+        self
+    }
+
+    /// Attributes the change to the run `id`
+    pub fn with_correlation(mut self, id: CorrelationId) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
+}
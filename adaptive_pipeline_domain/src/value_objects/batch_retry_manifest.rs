@@ -0,0 +1,145 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Batch Retry Manifest Value Object
+//!
+//! The files a batch run could not process, written as JSON when a batch
+//! finishes with failures. Passing it back to `process-batch --retry-failed`
+//! reprocesses only those files, into the same outputs with the same
+//! pipeline.
+//!
+//! Each failure keeps the error that stopped it, so the manifest also serves
+//! as the batch's failure report.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{BatchFailure, BatchRetryManifest};
+//!
+//! let manifest = BatchRetryManifest::new("compress-encrypt").with_failures(vec![BatchFailure::new(
+//!     "logs/b.log",
+//!     "out/b.log.adapipe",
+//!     "IO error: permission denied",
+//! )]);
+//! let parsed = BatchRetryManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+//! assert_eq!(parsed.failures[0].input, "logs/b.log");
+//! assert!(!parsed.is_empty());
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::PipelineError;
+
+/// Current retry manifest layout version
+pub const RETRY_MANIFEST_VERSION: u16 = 1;
+
+/// File name a batch writes its retry manifest to, in the output directory
+pub const RETRY_MANIFEST_FILE: &str = "batch-failures.json";
+
+/// One file a batch run failed to process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchFailure {
+    /// File that was being processed
+    pub input: String,
+
+    /// Archive it was to be written to
+    pub output: String,
+
+    /// Why processing failed
+    pub error: String,
+}
+
+impl BatchFailure {
+    /// Creates a failure record
+    pub fn new(input: impl Into<String>, output: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            error: error.into(),
+        }
+    }
+}
+
+/// Files a batch run failed to process, for retrying
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRetryManifest {
+    /// Manifest layout version
+    pub manifest_version: u16,
+
+    /// Pipeline name or ID the batch ran
+    pub pipeline: String,
+
+    /// Failed files, in the order the batch attempted them
+    pub failures: Vec<BatchFailure>,
+
+    /// When the batch finished
+    pub created_at: DateTime<Utc>,
+}
+
+impl BatchRetryManifest {
+    /// Creates an empty manifest for a batch run with `pipeline`, taken now
+    pub fn new(pipeline: impl Into<String>) -> Self {
+        Self {
+            manifest_version: RETRY_MANIFEST_VERSION,
+            pipeline: pipeline.into(),
+            failures: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Records the failed files
+    pub fn with_failures(mut self, failures: Vec<BatchFailure>) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Whether no file failed
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Pretty-printed JSON for writing to disk
+    pub fn to_json(&self) -> Result<String, PipelineError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode retry manifest: {}", e)))
+    }
+
+    /// Parses a manifest read from disk
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let manifest: Self = serde_json::from_str(json)
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid retry manifest: {}", e)))?;
+        if manifest.manifest_version > RETRY_MANIFEST_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Retry manifest version {} is newer than supported version {}",
+                manifest.manifest_version, RETRY_MANIFEST_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip_and_version_check() {
+        let manifest = BatchRetryManifest::new("p-1").with_failures(vec![
+            BatchFailure::new("a.bin", "out/a.bin.adapipe", "IO error: disk full"),
+            BatchFailure::new("b.bin", "out/b.bin.adapipe", "Cancelled: not attempted"),
+        ]);
+        assert_eq!(
+            BatchRetryManifest::from_json(&manifest.to_json().unwrap()).unwrap(),
+            manifest
+        );
+        assert!(BatchRetryManifest::new("p-1").is_empty());
+
+        let mut future = manifest;
+        future.manifest_version = RETRY_MANIFEST_VERSION + 1;
+        assert!(BatchRetryManifest::from_json(&future.to_json().unwrap()).is_err());
+        assert!(BatchRetryManifest::from_json("[]").is_err());
+    }
+}
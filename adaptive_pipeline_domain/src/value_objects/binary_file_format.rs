@@ -78,6 +78,8 @@
 //! - **Format Version**: 2 bytes - Current version number
 //! - **Header Length**: 4 bytes - Length of JSON header in bytes
 //! - **JSON Header**: Variable length - Metadata and processing information
+//! - **Chunk Index**: 20 bytes per chunk (format 2) - Where each chunk lies
+//!   in the file and in the original
 //! - **Processed Data**: Variable length - Actual processed file content
 //!
 //! ### JSON Header Structure
@@ -197,6 +199,16 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
+use super::algorithm::Algorithm;
+use super::build_provenance::BuildProvenance;
+use super::checksum_algorithm::ChecksumAlgorithm;
+use super::chunk_encryption_spec::{CHUNK_BINDING_KEY, CHUNK_BINDING_VERSION};
+use super::correlation_id::CorrelationId;
+use super::nonce_strategy::{NonceStrategy, NONCE_STRATEGY_KEY};
+use super::password_kdf::PasswordKdf;
+use super::content_type::ContentType;
+use super::chunk_size::ChunkSize;
+use crate::services::constant_time::constant_time_eq_str;
 use crate::PipelineError;
 
 /// Magic bytes to identify our file format: "ADAPIPE\0"
@@ -227,7 +239,26 @@ pub const MAGIC_BYTES: [u8; 8] = [0x41, 0x44, 0x41, 0x50, 0x49, 0x50, 0x45, 0x00
 ///
 /// Version history:
 /// - Version 1: Initial format with basic compression and encryption support
-pub const CURRENT_FORMAT_VERSION: u16 = 1;
+/// - Version 2: Optional chunk index table before the JSON header
+pub const CURRENT_FORMAT_VERSION: u16 = 2;
+
+/// Bytes of one entry in the chunk index table
+pub const CHUNK_INDEX_ENTRY_SIZE: usize = 20;
+
+/// Largest JSON header a footer may declare
+///
+/// Real headers are a few kilobytes; the cap keeps a forged length field
+/// from making a reader fetch and buffer gigabytes before parsing fails.
+pub const MAX_HEADER_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Largest payload a single chunk may declare
+///
+/// The largest chunk size plus the growth any supported codec can add to
+/// incompressible data, with room for the encryption tag.
+pub const MAX_CHUNK_PAYLOAD_LENGTH: usize = ChunkSize::MAX_SIZE + ChunkSize::MAX_SIZE / 64 + 64;
+
+/// Header metadata key set to `"true"` on files written by a FIPS-mode build
+pub const FIPS_MODE_METADATA_KEY: &str = "fips_mode";
 
 /// File header for Adaptive Pipeline processed files (.adapipe format)
 ///
@@ -238,7 +269,7 @@ pub const CURRENT_FORMAT_VERSION: u16 = 1;
 ///
 /// # Adaptive Pipeline File Format (.adapipe)
 /// ```text
-/// [CHUNK_DATA][JSON_HEADER][HEADER_LENGTH][FORMAT_VERSION][MAGIC_BYTES]
+/// [CHUNK_DATA][CHUNK_INDEX][JSON_HEADER][HEADER_LENGTH][FORMAT_VERSION][MAGIC_BYTES]
 /// ```
 ///
 /// Note: This is NOT a general binary file format like .png or .exe.
@@ -260,7 +291,8 @@ pub struct FileHeader {
     /// Original file size in bytes (for validation)
     pub original_size: u64,
 
-    /// SHA256 checksum of original input file (for validation)
+    /// Checksum of original input file, hashed with `checksum_algorithm`
+    /// (for validation)
     pub original_checksum: String,
 
     /// SHA256 checksum of this output file (for integrity verification)
@@ -283,6 +315,61 @@ pub struct FileHeader {
 
     /// Additional metadata for debugging/auditing
     pub metadata: HashMap<String, String>,
+
+    /// Build identity of the binary that wrote this file (absent in files
+    /// written before provenance was recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<BuildProvenance>,
+
+    /// Correlation ID of the run that wrote this file, matching its log
+    /// lines, audit records and events (absent in older files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
+
+    /// Content type detected from the original file's first bytes (absent
+    /// in files written before it was recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<ContentType>,
+
+    /// Hash of `original_checksum` (left out for SHA-256, so older files
+    /// read back as SHA-256)
+    #[serde(default, skip_serializing_if = "ChecksumAlgorithm::is_default")]
+    pub checksum_algorithm: ChecksumAlgorithm,
+
+    /// Last modification time of the original file (absent in older files
+    /// and where the filesystem doesn't report it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_modified: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Where each chunk lies in this file and in the original, by index,
+    /// so a reader can seek straight to any chunk. Stored as a binary table
+    /// before the JSON header rather than in it; empty for files written
+    /// without one, whose chunks are found by walking the chunk headers
+    #[serde(skip)]
+    pub chunk_index: Vec<ChunkIndexEntry>,
+}
+
+/// The JSON header as stored in the footer, with the number of chunk index
+/// entries stored before it
+#[derive(Serialize)]
+struct FooterJson<'a> {
+    #[serde(flatten)]
+    header: &'a FileHeader,
+    #[serde(skip_serializing_if = "is_zero")]
+    chunk_index_entries: u32,
+}
+
+/// [`FooterJson`] as read back
+#[derive(Deserialize)]
+struct StoredFooterJson {
+    #[serde(flatten)]
+    header: FileHeader,
+    #[serde(default)]
+    chunk_index_entries: u32,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
 }
 
 /// A single processing step that was applied to the file
@@ -318,6 +405,54 @@ pub enum ProcessingStepType {
     Custom(String),
 }
 
+/// Where one chunk lies in an `.adapipe` file and in the original
+///
+/// Stored in the chunk index table as
+/// `[OFFSET (8)][LENGTH (4)][ORIGINAL_OFFSET (8)]`, little-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkIndexEntry {
+    /// File offset of the chunk's 16-byte chunk header
+    pub offset: u64,
+
+    /// Length of the chunk's stored (compressed and/or encrypted) payload
+    pub length: u32,
+
+    /// Offset in the original file of the first byte the chunk restores to
+    pub original_offset: u64,
+}
+
+impl ChunkIndexEntry {
+    pub fn new(offset: u64, length: u32, original_offset: u64) -> Self {
+        Self {
+            offset,
+            length,
+            original_offset,
+        }
+    }
+
+    /// File offset just past the chunk's payload
+    pub fn end(&self) -> u64 {
+        self.offset + 16 + self.length as u64
+    }
+
+    fn to_bytes(self) -> [u8; CHUNK_INDEX_ENTRY_SIZE] {
+        let mut bytes = [0u8; CHUNK_INDEX_ENTRY_SIZE];
+        bytes[..8].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.length.to_le_bytes());
+        bytes[12..].copy_from_slice(&self.original_offset.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap_or_default());
+        Self {
+            offset: u64_at(0),
+            length: u32::from_le_bytes(bytes[8..12].try_into().unwrap_or_default()),
+            original_offset: u64_at(12),
+        }
+    }
+}
+
 /// Format for individual chunks in the file
 #[derive(Debug, Clone, PartialEq)]
 pub struct ChunkFormat {
@@ -356,7 +491,8 @@ impl FileHeader {
     ///   restoration)
     /// * `original_size` - Size of the original file in bytes (for validation)
     /// * `original_checksum` - SHA256 checksum of original file (for
-    ///   validation)
+    ///   validation); see [`with_checksum_algorithm`](Self::with_checksum_algorithm)
+    ///   for other hashes
     ///
     /// # Returns
     /// `FileHeader` with default values:
@@ -381,6 +517,12 @@ impl FileHeader {
             processed_at: chrono::Utc::now(),
             pipeline_id: String::new(),
             metadata: HashMap::new(),
+            provenance: None,
+            correlation_id: None,
+            content_type: None,
+            checksum_algorithm: ChecksumAlgorithm::default(),
+            original_modified: None,
+            chunk_index: Vec::new(),
         }
     }
 
@@ -405,7 +547,7 @@ impl FileHeader {
 
         self.processing_steps.push(ProcessingStep {
             step_type: ProcessingStepType::Compression,
-            algorithm: algorithm.to_string(),
+            algorithm: canonical_algorithm(algorithm),
             parameters,
             order: self.processing_steps.len() as u32,
         });
@@ -413,21 +555,27 @@ impl FileHeader {
     }
 
     /// Adds an encryption step
+    ///
+    /// The nonce strategy is recorded only when it is not the default, so
+    /// restore decrypts with the matching AEAD and nonce length.
     pub fn add_encryption_step(
         mut self,
         algorithm: &str,
         key_derivation: &str,
         key_size: u32,
-        nonce_size: u32,
+        nonce_strategy: NonceStrategy,
     ) -> Self {
         let mut parameters = HashMap::new();
         parameters.insert("key_derivation".to_string(), key_derivation.to_string());
         parameters.insert("key_size".to_string(), key_size.to_string());
-        parameters.insert("nonce_size".to_string(), nonce_size.to_string());
+        parameters.insert("nonce_size".to_string(), nonce_strategy.nonce_length().to_string());
+        if !nonce_strategy.is_default() {
+            parameters.insert(NONCE_STRATEGY_KEY.to_string(), nonce_strategy.to_string());
+        }
 
         self.processing_steps.push(ProcessingStep {
             step_type: ProcessingStepType::Encryption,
-            algorithm: algorithm.to_string(),
+            algorithm: canonical_algorithm(algorithm),
             parameters,
             order: self.processing_steps.len() as u32,
         });
@@ -464,7 +612,7 @@ impl FileHeader {
     pub fn add_checksum_step(mut self, algorithm: &str) -> Self {
         self.processing_steps.push(ProcessingStep {
             step_type: ProcessingStepType::Checksum,
-            algorithm: algorithm.to_string(),
+            algorithm: canonical_algorithm(algorithm),
             parameters: HashMap::new(),
             order: self.processing_steps.len() as u32,
         });
@@ -495,12 +643,104 @@ impl FileHeader {
         self
     }
 
+    /// Marks every encryption step as sealing its chunks with a
+    /// [`ChunkBinding`](super::ChunkBinding) to this file
+    pub fn with_chunk_binding(mut self) -> Self {
+        for step in &mut self.processing_steps {
+            if step.step_type == ProcessingStepType::Encryption {
+                step.parameters
+                    .insert(CHUNK_BINDING_KEY.to_string(), CHUNK_BINDING_VERSION.to_string());
+            }
+        }
+        self
+    }
+
+    /// Records on every encryption step the password KDF its key was
+    /// derived with
+    pub fn with_password_kdf(mut self, kdf: &PasswordKdf) -> Self {
+        for step in &mut self.processing_steps {
+            if step.step_type == ProcessingStepType::Encryption {
+                kdf.write_parameters(&mut step.parameters);
+            }
+        }
+        self
+    }
+
     /// Sets output file checksum (call after processing is complete)
     pub fn with_output_checksum(mut self, checksum: String) -> Self {
         self.output_checksum = checksum;
         self
     }
 
+    /// Records the build provenance of the producing binary
+    pub fn with_provenance(mut self, provenance: BuildProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Records the correlation ID of the producing run
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Records the detected content type of the original file
+    pub fn with_content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Records the hash used for `original_checksum`
+    pub fn with_checksum_algorithm(mut self, checksum_algorithm: ChecksumAlgorithm) -> Self {
+        self.checksum_algorithm = checksum_algorithm;
+        self
+    }
+
+    /// Records when the original file was last modified
+    pub fn with_original_modified(mut self, modified: chrono::DateTime<chrono::Utc>) -> Self {
+        self.original_modified = Some(modified);
+        self
+    }
+
+    /// Records where each chunk lies, by index
+    pub fn with_chunk_index(mut self, chunk_index: Vec<ChunkIndexEntry>) -> Self {
+        self.chunk_index = chunk_index;
+        self
+    }
+
+    /// Index of the chunk holding byte `offset` of the original
+    ///
+    /// Uses the chunk index when the file has one; otherwise every chunk
+    /// but the last holds `chunk_size` bytes.
+    pub fn chunk_at_original_offset(&self, offset: u64) -> u32 {
+        if self.chunk_index.is_empty() {
+            return (offset / (self.chunk_size.max(1) as u64)) as u32;
+        }
+        let following = self
+            .chunk_index
+            .partition_point(|entry| entry.original_offset <= offset);
+        following.saturating_sub(1) as u32
+    }
+
+    /// Bytes of the original that chunk `index` restores to
+    pub fn original_range_of_chunk(&self, index: u32) -> std::ops::Range<u64> {
+        let index = index as usize;
+        match self.chunk_index.get(index) {
+            Some(entry) => {
+                let end = self
+                    .chunk_index
+                    .get(index + 1)
+                    .map_or(self.original_size, |next| next.original_offset);
+                entry.original_offset..end
+            }
+            None => {
+                let chunk_size = self.chunk_size as u64;
+                let start = (index as u64 * chunk_size).min(self.original_size);
+                start..(start + chunk_size).min(self.original_size)
+            }
+        }
+    }
+
     /// Adds metadata
     pub fn with_metadata(mut self, key: String, value: String) -> Self {
         self.metadata.insert(key, value);
@@ -522,9 +762,11 @@ impl FileHeader {
     ///
     /// # Binary Format
     /// ```text
-    /// [JSON_HEADER][HEADER_LENGTH (4 bytes)][FORMAT_VERSION (2 bytes)][MAGIC_BYTES (8 bytes)]
+    /// [CHUNK_INDEX][JSON_HEADER][HEADER_LENGTH (4 bytes)][FORMAT_VERSION (2 bytes)][MAGIC_BYTES (8 bytes)]
     /// ```
     ///
+    /// The chunk index table is left out when `chunk_index` is empty.
+    ///
     /// # Returns
     /// * `Ok(Vec<u8>)` - Serialized footer bytes
     /// * `Err(PipelineError::SerializationError)` - JSON serialization failed
@@ -535,14 +777,22 @@ impl FileHeader {
     /// # Examples
     pub fn to_footer_bytes(&self) -> Result<Vec<u8>, PipelineError> {
         // Serialize header to JSON
-        let header_json = serde_json::to_string(self)
-            .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize header: {}", e)))?;
+        let header_json = serde_json::to_string(&FooterJson {
+            header: self,
+            chunk_index_entries: self.chunk_index.len() as u32,
+        })
+        .map_err(|e| PipelineError::SerializationError(format!("Failed to serialize header: {}", e)))?;
 
         let header_bytes = header_json.as_bytes();
         let header_length = header_bytes.len() as u32;
 
         // Build footer format
-        let mut result = Vec::new();
+        let mut result = Vec::with_capacity(self.chunk_index.len() * CHUNK_INDEX_ENTRY_SIZE + header_bytes.len() + 14);
+
+        // Chunk index table
+        for entry in &self.chunk_index {
+            result.extend_from_slice(&entry.to_bytes());
+        }
 
         // JSON header data
         result.extend_from_slice(header_bytes);
@@ -577,7 +827,7 @@ impl FileHeader {
     ///
     /// # Returns
     /// * `Ok((FileHeader, usize))` - Parsed header and total footer size in
-    ///   bytes
+    ///   bytes, chunk index table included
     /// * `Err(PipelineError)` - Validation or parsing error
     ///
     /// # Errors
@@ -585,12 +835,63 @@ impl FileHeader {
     /// - File too short (< 14 bytes minimum footer size)
     /// - Invalid magic bytes (not an .adapipe file)
     /// - Unsupported format version
+    /// - Header length above [`MAX_HEADER_LENGTH`]
     /// - Incomplete footer data
     /// - Invalid UTF-8 in JSON header
     /// - JSON deserialization fails
+    /// - Chunk index table missing or not listing every chunk
     ///
     /// # Examples
     pub fn from_footer_bytes(file_data: &[u8]) -> Result<(Self, usize), PipelineError> {
+        let (mut header, json_footer_size, table_size) = Self::parse_footer(file_data)?;
+        let footer_size = json_footer_size + table_size;
+        if file_data.len() < footer_size {
+            return Err(PipelineError::ValidationError(
+                "File too short for chunk index".to_string(),
+            ));
+        }
+        let table_start = file_data.len() - footer_size;
+        header.read_chunk_index(&file_data[table_start..table_start + table_size])?;
+        Ok((header, footer_size))
+    }
+
+    /// Parses the JSON header and trailer that end `footer`, leaving
+    /// `chunk_index` empty
+    ///
+    /// Returns the header and the size of the chunk index table stored just
+    /// before the JSON header, for readers that fetch the footer in parts;
+    /// the table is parsed with [`read_chunk_index`](Self::read_chunk_index).
+    ///
+    /// # Errors
+    /// As [`from_footer_bytes`](Self::from_footer_bytes).
+    pub fn from_footer_json(footer: &[u8]) -> Result<(Self, usize), PipelineError> {
+        let (header, _json_footer_size, table_size) = Self::parse_footer(footer)?;
+        Ok((header, table_size))
+    }
+
+    /// Fills `chunk_index` from a chunk index table
+    ///
+    /// # Errors
+    /// Returns `PipelineError::ValidationError` if `table` is not a whole
+    /// number of entries.
+    pub fn read_chunk_index(&mut self, table: &[u8]) -> Result<(), PipelineError> {
+        if !table.len().is_multiple_of(CHUNK_INDEX_ENTRY_SIZE) {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk index table of {} bytes is not a whole number of entries",
+                table.len()
+            )));
+        }
+        self.chunk_index = table
+            .chunks_exact(CHUNK_INDEX_ENTRY_SIZE)
+            .map(ChunkIndexEntry::from_bytes)
+            .collect();
+        Ok(())
+    }
+
+    /// Parses the JSON header and trailer at the end of `file_data`,
+    /// returning the header, their size and the size of the chunk index
+    /// table before them
+    fn parse_footer(file_data: &[u8]) -> Result<(Self, usize, usize), PipelineError> {
         let file_size = file_data.len();
 
         if file_size < 14 {
@@ -626,6 +927,13 @@ impl FileHeader {
         let header_length =
             u32::from_le_bytes([length_bytes[0], length_bytes[1], length_bytes[2], length_bytes[3]]) as usize;
 
+        if header_length > MAX_HEADER_LENGTH {
+            return Err(PipelineError::ValidationError(format!(
+                "Header length {} exceeds the maximum of {} bytes",
+                header_length, MAX_HEADER_LENGTH
+            )));
+        }
+
         // Calculate total footer size
         let footer_size = header_length + 14; // JSON + length + version + magic
         if file_size < footer_size {
@@ -640,10 +948,17 @@ impl FileHeader {
         let header_str = std::str::from_utf8(header_json)
             .map_err(|e| PipelineError::ValidationError(format!("Invalid UTF-8 in header: {}", e)))?;
 
-        let header: FileHeader = serde_json::from_str(header_str)
+        let stored: StoredFooterJson = serde_json::from_str(header_str)
             .map_err(|e| PipelineError::SerializationError(format!("Failed to deserialize header: {}", e)))?;
+        let entries = stored.chunk_index_entries;
+        if entries != 0 && entries != stored.header.chunk_count {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk index lists {} chunks but the footer records {}",
+                entries, stored.header.chunk_count
+            )));
+        }
 
-        Ok((header, footer_size))
+        Ok((stored.header, footer_size, entries as usize * CHUNK_INDEX_ENTRY_SIZE))
     }
 
     /// Verifies the integrity of the processed output file
@@ -684,7 +999,7 @@ impl FileHeader {
         let digest = hasher.finalize();
         let calculated_checksum = hex::encode(digest);
 
-        Ok(calculated_checksum == self.output_checksum)
+        Ok(constant_time_eq_str(&calculated_checksum, &self.output_checksum))
     }
 
     /// Gets the processing steps in reverse order for file restoration
@@ -706,7 +1021,7 @@ impl FileHeader {
     /// # Examples
     pub fn get_restoration_steps(&self) -> Vec<&ProcessingStep> {
         let mut steps: Vec<&ProcessingStep> = self.processing_steps.iter().collect();
-        steps.sort_by(|a, b| b.order.cmp(&a.order)); // Reverse order
+        steps.sort_by_key(|s| std::cmp::Reverse(s.order)); // Reverse order
         steps
     }
 
@@ -732,6 +1047,15 @@ impl FileHeader {
     ///
     /// # Examples
     pub fn validate_restored_file(&self, restored_data: &[u8]) -> Result<bool, PipelineError> {
+        // Only SHA-256 is implemented in the domain; other hashes are verified
+        // by the infrastructure checksum adapter
+        if self.checksum_algorithm != ChecksumAlgorithm::Sha256 {
+            return Err(PipelineError::unsupported_operation(format!(
+                "Cannot verify a {} checksum here",
+                self.checksum_algorithm
+            )));
+        }
+
         // Check size
         if (restored_data.len() as u64) != self.original_size {
             return Ok(false);
@@ -743,7 +1067,7 @@ impl FileHeader {
         let digest = hasher.finalize();
         let calculated_checksum = hex::encode(digest);
 
-        Ok(calculated_checksum == self.original_checksum)
+        Ok(constant_time_eq_str(&calculated_checksum, &self.original_checksum))
     }
 
     /// Gets information about what processing was applied
@@ -774,6 +1098,13 @@ impl FileHeader {
             .any(|step| matches!(step.step_type, ProcessingStepType::Compression))
     }
 
+    /// Checks if the file was written by a FIPS-mode build
+    pub fn is_fips_mode(&self) -> bool {
+        self.metadata
+            .get(FIPS_MODE_METADATA_KEY)
+            .is_some_and(|value| value == "true")
+    }
+
     /// Checks if the file uses encryption
     pub fn is_encrypted(&self) -> bool {
         self.processing_steps
@@ -825,6 +1156,14 @@ impl FileHeader {
             ));
         }
 
+        if self.chunk_size as usize > ChunkSize::MAX_SIZE {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk size {} exceeds the maximum of {} bytes",
+                self.chunk_size,
+                ChunkSize::MAX_SIZE
+            )));
+        }
+
         if self.original_size > 0 && self.chunk_count == 0 {
             return Err(PipelineError::ValidationError(
                 "Non-empty file must have chunks".to_string(),
@@ -850,6 +1189,15 @@ impl FileHeader {
     }
 }
 
+/// Canonical spelling of a known algorithm for storage in the header.
+///
+/// Unknown names are stored as given so custom stages keep round-tripping.
+fn canonical_algorithm(algorithm: &str) -> String {
+    Algorithm::canonical_name(algorithm)
+        .map(str::to_string)
+        .unwrap_or_else(|| algorithm.to_string())
+}
+
 impl ChunkFormat {
     /// Creates a new chunk format
     pub fn new(nonce: [u8; 12], payload: Vec<u8>) -> Self {
@@ -893,6 +1241,9 @@ impl ChunkFormat {
 
     /// Deserializes chunk from binary format
     /// Returns (chunk, bytes_consumed)
+    ///
+    /// Payloads declared longer than [`MAX_CHUNK_PAYLOAD_LENGTH`] are
+    /// rejected before anything is copied.
     pub fn from_bytes(data: &[u8]) -> Result<(Self, usize), PipelineError> {
         if data.len() < 16 {
             // 12 + 4 = minimum chunk header size
@@ -907,6 +1258,12 @@ impl ChunkFormat {
 
         // Read data length
         let data_length = u32::from_le_bytes([data[12], data[13], data[14], data[15]]) as usize;
+        if data_length > MAX_CHUNK_PAYLOAD_LENGTH {
+            return Err(PipelineError::ValidationError(format!(
+                "Chunk payload length {} exceeds the maximum of {} bytes",
+                data_length, MAX_CHUNK_PAYLOAD_LENGTH
+            )));
+        }
 
         // Check if we have enough data
         let total_size = 16 + data_length;
@@ -979,7 +1336,7 @@ mod tests {
     fn test_header_creation_and_serialization() {
         let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
             .add_compression_step("brotli", 6)
-            .add_encryption_step("aes256gcm", "argon2", 32, 12)
+            .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random)
             .with_chunk_info(1024 * 1024, 1)
             .with_pipeline_id("test-pipeline".to_string())
             .with_output_checksum("def456".to_string());
@@ -988,7 +1345,8 @@ mod tests {
         assert!(header.is_compressed());
         assert!(header.is_encrypted());
         assert_eq!(header.compression_algorithm(), Some("brotli"));
-        assert_eq!(header.encryption_algorithm(), Some("aes256gcm"));
+        // Legacy spellings are stored under the canonical algorithm name
+        assert_eq!(header.encryption_algorithm(), Some("aes-256-gcm"));
     }
 
     /// Tests header serialization and deserialization roundtrip.
@@ -1064,7 +1422,7 @@ mod tests {
     fn test_restoration_steps_order() {
         let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
             .add_compression_step("brotli", 6) // Order 0
-            .add_encryption_step("aes256gcm", "argon2", 32, 12); // Order 1
+            .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random); // Order 1
 
         let restoration_steps = header.get_restoration_steps();
 
@@ -1183,11 +1541,11 @@ mod tests {
     fn test_processing_summary() {
         let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
             .add_compression_step("brotli", 6)
-            .add_encryption_step("aes256gcm", "argon2", 32, 12);
+            .add_encryption_step("aes256gcm", "argon2", 32, NonceStrategy::Random);
 
         let summary = header.get_processing_summary();
         assert!(summary.contains("Compression (brotli)"));
-        assert!(summary.contains("Encryption (aes256gcm)"));
+        assert!(summary.contains("Encryption (aes-256-gcm)"));
         assert!(summary.contains("→")); // Should show processing flow
     }
 
@@ -1227,4 +1585,172 @@ mod tests {
         assert!(!header.is_encrypted());
         assert_eq!(header.get_processing_summary(), "No processing applied (pass-through)");
     }
+
+    /// Tests that build provenance survives the footer roundtrip and that
+    /// footers written before provenance existed still parse.
+    #[test]
+    fn test_provenance_roundtrip_and_legacy_footer() {
+        let provenance = BuildProvenance::new("2.0.0", "0123456789abcdef", "ci", "x86_64-unknown-linux-gnu");
+        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
+            .with_output_checksum("def456".to_string())
+            .with_provenance(provenance.clone());
+
+        let (restored, _) = FileHeader::from_footer_bytes(&header.to_footer_bytes().unwrap()).unwrap();
+        assert_eq!(restored.provenance, Some(provenance));
+
+        let legacy = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        let (restored, _) = FileHeader::from_footer_bytes(&legacy.to_footer_bytes().unwrap()).unwrap();
+        assert_eq!(restored.provenance, None);
+    }
+
+    /// Tests that the producing run's correlation ID survives the footer
+    /// roundtrip and is left out of footers that have none.
+    #[test]
+    fn test_correlation_id_roundtrip() {
+        let correlation_id = CorrelationId::generate();
+        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
+            .with_correlation_id(correlation_id.clone());
+
+        let (restored, _) = FileHeader::from_footer_bytes(&header.to_footer_bytes().unwrap()).unwrap();
+        assert_eq!(restored.correlation_id, Some(correlation_id));
+
+        let legacy = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        assert!(!String::from_utf8_lossy(&legacy.to_footer_bytes().unwrap()).contains("correlation_id"));
+    }
+
+    /// Tests that the checksum algorithm survives the footer roundtrip and
+    /// that footers without one read back as SHA-256.
+    #[test]
+    fn test_checksum_algorithm_roundtrip() {
+        let header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
+            .with_checksum_algorithm(ChecksumAlgorithm::Blake3);
+
+        let (restored, _) = FileHeader::from_footer_bytes(&header.to_footer_bytes().unwrap()).unwrap();
+        assert_eq!(restored.checksum_algorithm, ChecksumAlgorithm::Blake3);
+
+        let legacy = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        let footer = legacy.to_footer_bytes().unwrap();
+        assert!(!String::from_utf8_lossy(&footer).contains("checksum_algorithm"));
+        let (restored, _) = FileHeader::from_footer_bytes(&footer).unwrap();
+        assert_eq!(restored.checksum_algorithm, ChecksumAlgorithm::Sha256);
+    }
+
+    /// Tests that the content type survives the footer roundtrip and is left
+    /// out of footers that have none.
+    #[test]
+    fn test_content_type_roundtrip() {
+        let header = FileHeader::new("photo.png".to_string(), 1024, "abc123".to_string())
+            .with_content_type(ContentType::detect(b"\x89PNG\r\n\x1a\n"));
+        let (restored, _) = FileHeader::from_footer_bytes(&header.to_footer_bytes().unwrap()).unwrap();
+        assert_eq!(restored.content_type.as_ref().map(ContentType::mime), Some("image/png"));
+
+        let legacy = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        let footer = legacy.to_footer_bytes().unwrap();
+        assert!(!String::from_utf8_lossy(&footer).contains("content_type"));
+    }
+
+    /// Tests that the chunk index survives the footer roundtrip and is left
+    /// out of footers that have none.
+    #[test]
+    fn test_chunk_index_roundtrip() {
+        let mut header = FileHeader::new("test.txt".to_string(), 2500, "abc123".to_string()).with_chunk_index(vec![
+            ChunkIndexEntry::new(0, 516, 0),
+            ChunkIndexEntry::new(532, 516, 1024),
+            ChunkIndexEntry::new(1064, 300, 2048),
+        ]);
+        header.chunk_count = 3;
+        let footer = header.to_footer_bytes().unwrap();
+        let (restored, footer_size) = FileHeader::from_footer_bytes(&footer).unwrap();
+        assert_eq!(footer_size, footer.len());
+        assert_eq!(restored.chunk_index, header.chunk_index);
+        assert_eq!(&footer[20..32], &[20, 2, 0, 0, 0, 0, 0, 0, 4, 2, 0, 0]);
+
+        // Readers fetching the footer in parts learn the table's size first
+        let table_size = 3 * CHUNK_INDEX_ENTRY_SIZE;
+        let (mut partial, size) = FileHeader::from_footer_json(&footer[table_size..]).unwrap();
+        assert_eq!(size, table_size);
+        assert!(partial.chunk_index.is_empty());
+        partial.read_chunk_index(&footer[..table_size]).unwrap();
+        assert_eq!(partial, restored);
+        assert!(FileHeader::from_footer_bytes(&footer[1..]).is_err());
+
+        // Format 1 footers have no table
+        let mut legacy = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        legacy.format_version = 1;
+        let footer = legacy.to_footer_bytes().unwrap();
+        assert!(!String::from_utf8_lossy(&footer).contains("chunk_index"));
+        let (restored, _) = FileHeader::from_footer_bytes(&footer).unwrap();
+        assert_eq!(restored.format_version, 1);
+        assert!(restored.chunk_index.is_empty());
+    }
+
+    #[test]
+    fn test_chunk_index_must_list_every_chunk() {
+        let mut header = FileHeader::new("test.txt".to_string(), 2048, "abc123".to_string())
+            .with_chunk_index(vec![ChunkIndexEntry::new(0, 516, 0)]);
+        header.chunk_count = 2;
+        let error = FileHeader::from_footer_bytes(&header.to_footer_bytes().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("lists 1 chunks"), "{}", error);
+    }
+
+    #[test]
+    fn test_original_offsets_map_to_chunks() {
+        let mut header = FileHeader::new("test.txt".to_string(), 2500, "abc123".to_string());
+        header.chunk_size = 1024;
+        assert_eq!(header.chunk_at_original_offset(1023), 0);
+        assert_eq!(header.chunk_at_original_offset(2048), 2);
+        assert_eq!(header.original_range_of_chunk(2), 2048..2500);
+
+        // The index is used over the chunk size when present
+        header = header.with_chunk_index(vec![
+            ChunkIndexEntry::new(0, 100, 0),
+            ChunkIndexEntry::new(116, 100, 500),
+            ChunkIndexEntry::new(232, 100, 2000),
+        ]);
+        assert_eq!(header.chunk_at_original_offset(499), 0);
+        assert_eq!(header.chunk_at_original_offset(500), 1);
+        assert_eq!(header.chunk_at_original_offset(2499), 2);
+        assert_eq!(header.original_range_of_chunk(1), 500..2000);
+        assert_eq!(header.original_range_of_chunk(2), 2000..2500);
+    }
+
+    /// Tests that forged length fields and truncated input are rejected with
+    /// errors instead of panicking or allocating what the length claims.
+    #[test]
+    fn test_malformed_lengths_are_rejected() {
+        // Footer claiming a 4 GiB header
+        let mut footer = u32::MAX.to_le_bytes().to_vec();
+        footer.extend_from_slice(&CURRENT_FORMAT_VERSION.to_le_bytes());
+        footer.extend_from_slice(&MAGIC_BYTES);
+        let error = FileHeader::from_footer_bytes(&footer).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum"));
+
+        // Chunk claiming a 4 GiB payload
+        let mut chunk = vec![0u8; 12];
+        chunk.extend_from_slice(&u32::MAX.to_le_bytes());
+        let error = ChunkFormat::from_bytes(&chunk).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum"));
+
+        // Every truncation of a valid footer and chunk fails cleanly
+        let footer = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string())
+            .to_footer_bytes()
+            .unwrap();
+        for len in 0..footer.len() {
+            assert!(FileHeader::from_footer_bytes(&footer[len + 1..]).is_err());
+        }
+        let chunk = ChunkFormat::new([7u8; 12], vec![1, 2, 3, 4]).to_bytes();
+        for len in 0..chunk.len() {
+            assert!(ChunkFormat::from_bytes(&chunk[..len]).is_err());
+        }
+    }
+
+    /// Tests that a header declaring a chunk size above the maximum fails
+    /// validation, so restoration never sizes buffers from it.
+    #[test]
+    fn test_oversized_chunk_size_fails_validation() {
+        let mut header = FileHeader::new("test.txt".to_string(), 1024, "abc123".to_string());
+        header.chunk_size = u32::MAX;
+        let error = header.validate().unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum"));
+    }
 }
@@ -0,0 +1,119 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Build Provenance Value Object
+//!
+//! Identifies the exact binary that produced an `.adapipe` file: crate
+//! version, source commit, build target and who (or which CI job) built it.
+//! The writer embeds it in the file header so an archive can be traced back
+//! to its producer during forensic review.
+//!
+//! The values are captured at compile time by the application crate; the
+//! domain only defines their shape. Fields that could not be determined are
+//! recorded as [`UNKNOWN`] rather than omitted, so a missing value is visible.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::BuildProvenance;
+//!
+//! let provenance = BuildProvenance::new("2.0.0", "4bacd47e0c1d2e3f", "ci@build-01", "x86_64-unknown-linux-gnu");
+//! assert_eq!(provenance.short_commit(), "4bacd47e0c1d");
+//! assert_eq!(
+//!     provenance.to_string(),
+//!     "adaptive-pipeline 2.0.0 (4bacd47e0c1d, x86_64-unknown-linux-gnu) built by ci@build-01"
+//! );
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Placeholder recorded for provenance fields that were not available at
+/// build time
+pub const UNKNOWN: &str = "unknown";
+
+const SHORT_COMMIT_LEN: usize = 12;
+
+/// Build identity of the binary that wrote a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildProvenance {
+    /// Version of the producing crate (`CARGO_PKG_VERSION`)
+    pub crate_version: String,
+
+    /// Full git commit hash the binary was built from
+    pub git_commit: String,
+
+    /// Builder identity (CI job or `user@host`)
+    pub builder: String,
+
+    /// Target triple the binary was compiled for
+    pub target: String,
+}
+
+impl BuildProvenance {
+    /// Creates provenance from the captured build values; empty values are
+    /// recorded as [`UNKNOWN`]
+    pub fn new(
+        crate_version: impl Into<String>,
+        git_commit: impl Into<String>,
+        builder: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        fn or_unknown(value: String) -> String {
+            if value.trim().is_empty() {
+                UNKNOWN.to_string()
+            } else {
+                value
+            }
+        }
+
+        Self {
+            crate_version: or_unknown(crate_version.into()),
+            git_commit: or_unknown(git_commit.into()),
+            builder: or_unknown(builder.into()),
+            target: or_unknown(target.into()),
+        }
+    }
+
+    /// Abbreviated commit hash for display
+    pub fn short_commit(&self) -> &str {
+        self.git_commit
+            .get(..SHORT_COMMIT_LEN)
+            .unwrap_or(self.git_commit.as_str())
+    }
+}
+
+impl Display for BuildProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "adaptive-pipeline {} ({}, {}) built by {}",
+            self.crate_version,
+            self.short_commit(),
+            self.target,
+            self.builder
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_values_are_recorded_as_unknown() {
+        let provenance = BuildProvenance::new("2.0.0", "", " ", "aarch64-apple-darwin");
+        assert_eq!(provenance.git_commit, UNKNOWN);
+        assert_eq!(provenance.builder, UNKNOWN);
+        assert_eq!(provenance.short_commit(), UNKNOWN);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let provenance = BuildProvenance::new("2.0.0", "abc", "ci", "x86_64-unknown-linux-gnu");
+        let json = serde_json::to_string(&provenance).unwrap();
+        assert_eq!(serde_json::from_str::<BuildProvenance>(&json).unwrap(), provenance);
+    }
+}
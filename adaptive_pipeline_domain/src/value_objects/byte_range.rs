@@ -0,0 +1,148 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Byte Range Value Object
+//!
+//! A span of bytes of an original file for `restore --range`, written as on
+//! the command line: `START-END` with both ends inclusive, as in an HTTP
+//! `Range` header, or `START-` for everything from `START` on.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ByteRange;
+//!
+//! let range: ByteRange = "1024-2047".parse().unwrap();
+//! assert_eq!(range.start(), 1024);
+//! assert_eq!(range.len_within(4096), 1024);
+//! assert_eq!(range.to_string(), "1024-2047");
+//!
+//! let tail: ByteRange = "4000-".parse().unwrap();
+//! assert_eq!(tail.len_within(4096), 96);
+//! assert!("2048-1024".parse::<ByteRange>().is_err());
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Inclusive span of bytes, open-ended when `end` is `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ByteRange {
+    start: u64,
+    end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Creates the range `start..=end`, or from `start` to the end of the
+    /// file without `end`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidParameter` if `end` is before `start`.
+    pub fn new(start: u64, end: Option<u64>) -> Result<Self, PipelineError> {
+        if end.is_some_and(|end| end < start) {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Byte range {}-{} ends before it starts",
+                start,
+                end.unwrap_or_default()
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// First byte of the range
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Last byte of the range, if it has one
+    pub fn end(&self) -> Option<u64> {
+        self.end
+    }
+
+    /// Number of bytes the range covers in a file of `size` bytes; zero if
+    /// it starts at or past the end
+    pub fn len_within(&self, size: u64) -> u64 {
+        let end = self.end.map_or(size, |end| end.saturating_add(1).min(size));
+        end.saturating_sub(self.start)
+    }
+}
+
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}-", self.start),
+        }
+    }
+}
+
+impl std::str::FromStr for ByteRange {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            PipelineError::InvalidParameter(format!(
+                "Invalid byte range '{}'; expected START-END or START-, such as 1024-2047",
+                s.trim()
+            ))
+        };
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let start = start.trim().parse().map_err(|_| invalid())?;
+        let end = match end.trim() {
+            "" => None,
+            end => Some(end.parse().map_err(|_| invalid())?),
+        };
+        Self::new(start, end)
+    }
+}
+
+impl TryFrom<String> for ByteRange {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ByteRange> for String {
+    fn from(range: ByteRange) -> Self {
+        range.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_closed_and_open_ranges() {
+        let range: ByteRange = "1024-2048".parse().unwrap();
+        assert_eq!((range.start(), range.end()), (1024, Some(2048)));
+        assert_eq!(" 5 - ".parse::<ByteRange>().unwrap(), ByteRange::new(5, None).unwrap());
+        assert_eq!("7-7".parse::<ByteRange>().unwrap().len_within(100), 1);
+        for invalid in ["", "1024", "-2048", "a-b", "10-5", "1-2-3", "1.5-2"] {
+            assert!(invalid.parse::<ByteRange>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_length_is_clamped_to_the_file() {
+        let range = ByteRange::new(90, Some(200)).unwrap();
+        assert_eq!(range.len_within(100), 10);
+        assert_eq!(range.len_within(1000), 111);
+        assert_eq!(ByteRange::new(100, None).unwrap().len_within(100), 0);
+        assert_eq!(ByteRange::new(0, Some(u64::MAX)).unwrap().len_within(10), 10);
+    }
+
+    #[test]
+    fn test_serializes_as_range_string() {
+        let range = ByteRange::new(0, Some(99)).unwrap();
+        assert_eq!(serde_json::to_string(&range).unwrap(), "\"0-99\"");
+        assert_eq!(serde_json::from_str::<ByteRange>("\"0-99\"").unwrap(), range);
+    }
+}
@@ -0,0 +1,169 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Checksum Algorithm Value Object
+//!
+//! The hash a pipeline uses for the whole-file checksum of its input. The
+//! algorithm is a property of the pipeline, stored in its configuration
+//! under [`CHECKSUM_ALGORITHM_KEY`], and is recorded in the header of every
+//! `.adapipe` file so restore verifies the original with the same hash.
+//!
+//! | Algorithm | Notes                                                   |
+//! |-----------|---------------------------------------------------------|
+//! | `sha256`  | The default; FIPS-approved                              |
+//! | `blake3`  | Several times faster on large files; not FIPS-approved  |
+//!
+//! Files written before the algorithm was recorded carry SHA-256 checksums
+//! and read back as `sha256`. The checksum of the `.adapipe` container
+//! itself is always SHA-256.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ChecksumAlgorithm;
+//!
+//! let algorithm: ChecksumAlgorithm = "blake3".parse().unwrap();
+//! assert_eq!(algorithm, ChecksumAlgorithm::Blake3);
+//! assert_eq!(ChecksumAlgorithm::default().to_string(), "sha256");
+//! ```
+
+use crate::value_objects::Algorithm;
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Pipeline configuration key holding the checksum algorithm
+pub const CHECKSUM_ALGORITHM_KEY: &str = "checksum_algorithm";
+
+/// Hash used for a file's whole-content checksum
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    /// SHA-256
+    #[default]
+    Sha256,
+    /// BLAKE3 with a 256-bit output
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns every checksum algorithm, default first
+    pub fn all() -> [ChecksumAlgorithm; 2] {
+        [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Blake3]
+    }
+
+    /// Returns the name used on the command line, in pipeline
+    /// configuration and in file headers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Returns true for SHA-256, the algorithm of files without a recorded
+    /// one
+    pub fn is_default(&self) -> bool {
+        *self == ChecksumAlgorithm::default()
+    }
+
+    /// Returns an error if this build does not allow the algorithm
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::UnsupportedOperation` for BLAKE3 in a FIPS-mode
+    /// build.
+    pub fn ensure_permitted(&self) -> Result<(), PipelineError> {
+        match self {
+            ChecksumAlgorithm::Sha256 => Algorithm::sha256(),
+            ChecksumAlgorithm::Blake3 => Algorithm::blake3(),
+        }
+        .ensure_permitted()
+    }
+}
+
+impl Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ChecksumAlgorithm {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', '_'], "");
+        ChecksumAlgorithm::all()
+            .into_iter()
+            .find(|algorithm| algorithm.as_str() == name)
+            .ok_or_else(|| {
+                PipelineError::invalid_config(format!(
+                    "Unknown checksum algorithm '{}'. Valid algorithms: sha256, blake3",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_algorithm_names_round_trip() {
+        for algorithm in ChecksumAlgorithm::all() {
+            assert_eq!(algorithm.as_str().parse::<ChecksumAlgorithm>().unwrap(), algorithm);
+            assert_eq!(serde_json::to_string(&algorithm).unwrap(), format!("\"{}\"", algorithm));
+        }
+        assert_eq!(
+            "SHA-256".parse::<ChecksumAlgorithm>().unwrap(),
+            ChecksumAlgorithm::Sha256
+        );
+        assert!("md5".parse::<ChecksumAlgorithm>().is_err());
+        assert!(ChecksumAlgorithm::Sha256.ensure_permitted().is_ok());
+        assert_eq!(
+            ChecksumAlgorithm::Blake3.ensure_permitted().is_err(),
+            crate::value_objects::FIPS_MODE
+        );
+    }
+
+    #[test]
+    fn test_pipeline_checksum_algorithm_lives_in_its_configuration() {
+        use crate::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+        use std::collections::HashMap;
+
+        let stage = PipelineStage::new(
+            "compress".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            0,
+        )
+        .unwrap();
+        let mut pipeline = Pipeline::new("backup".to_string(), vec![stage]).unwrap();
+        assert_eq!(pipeline.checksum_algorithm().unwrap(), ChecksumAlgorithm::Sha256);
+
+        pipeline.set_checksum_algorithm(ChecksumAlgorithm::Blake3);
+        assert_eq!(
+            pipeline.configuration().get(CHECKSUM_ALGORITHM_KEY).map(String::as_str),
+            Some("blake3")
+        );
+        assert_eq!(pipeline.checksum_algorithm().unwrap(), ChecksumAlgorithm::Blake3);
+        let checksum_stages: Vec<&str> = pipeline
+            .stages()
+            .iter()
+            .filter(|stage| *stage.stage_type() == StageType::Checksum)
+            .map(|stage| stage.algorithm())
+            .collect();
+        assert_eq!(checksum_stages, ["blake3", "blake3"]);
+
+        pipeline.update_configuration(HashMap::from([(
+            CHECKSUM_ALGORITHM_KEY.to_string(),
+            "crc32".to_string(),
+        )]));
+        assert!(pipeline.validate().is_err());
+    }
+}
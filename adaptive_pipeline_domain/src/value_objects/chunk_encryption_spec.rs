@@ -0,0 +1,471 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Encryption Specification
+//!
+//! The normative description of how encrypted chunks are laid out in an
+//! `.adapipe` file, together with the test vector types used to pin that
+//! layout down. A third-party implementation that reproduces every vector in
+//! a published suite byte for byte can read and write encrypted `.adapipe`
+//! chunks.
+//!
+//! ## File Layout
+//!
+//! ```text
+//! [CHUNK RECORD]* [JSON HEADER] [HEADER LENGTH u32 LE] [FORMAT VERSION u16 LE] [MAGIC "ADAPIPE\0"]
+//! ```
+//!
+//! Chunk records are written back to back in chunk order. The JSON header
+//! lists the processing steps; encryption appears as a step of type
+//! `Encryption` whose algorithm names the AEAD below.
+//!
+//! ## Chunk Record
+//!
+//! ```text
+//! offset  size  field
+//! 0       12    nonce
+//! 12      4     payload length N, u32 little-endian
+//! 16      N     payload = ciphertext || tag (16 bytes)
+//! ```
+//!
+//! Unencrypted chunks use the same record with an all-zero nonce and the
+//! stage output as the payload. N never exceeds
+//! [`MAX_CHUNK_PAYLOAD_LENGTH`](super::binary_file_format::MAX_CHUNK_PAYLOAD_LENGTH).
+//!
+//! ## Encryption
+//!
+//! - **AEAD**: `aes-256-gcm` (NIST SP 800-38D) or `chacha20-poly1305`
+//!   (RFC 8439)
+//! - **Key**: 32 bytes, the same for every chunk of a file
+//! - **Nonce**: unique for each chunk under the key, chosen by the
+//!   encryption step's [`NonceStrategy`](super::NonceStrategy) (see below)
+//! - **Associated data**: the chunk binding (see below), or empty in files
+//!   written without one
+//! - **Plaintext**: the chunk as produced by the stages that precede
+//!   encryption (for example the compressed chunk)
+//!
+//! The payload is the AEAD output: the ciphertext, the same length as the
+//! plaintext, followed by the 16-byte authentication tag. Decryption takes
+//! the nonce from the record, authenticates the payload with the chunk's
+//! associated data, and fails on any mismatch.
+//!
+//! ## Chunk Binding
+//!
+//! Each encryption step records `chunk_binding = 1` among its parameters.
+//! Chunks of such a file are sealed with associated data that ties them to
+//! their place in it:
+//!
+//! ```text
+//! offset  size  field
+//! 0       2     format version, u16 big-endian
+//! 2       P     pipeline ID from the header, UTF-8
+//! 2+P     8     chunk index (0-based position in the file), u64 big-endian
+//! ```
+//!
+//! A chunk moved to another position or into another pipeline's archive
+//! fails to authenticate. Dropping trailing chunks leaves the rest intact,
+//! so readers also check the header's chunk count and the original checksum.
+//! Steps without the parameter use empty associated data.
+//!
+//! ## Nonces
+//!
+//! The encryption step in the header records the strategy as its
+//! `nonce_strategy` parameter; a step without one uses `random`.
+//!
+//! | Strategy         | Nonce                                                |
+//! |------------------|------------------------------------------------------|
+//! | `random`         | 12 random bytes                                      |
+//! | `counter`        | 8 random bytes fixed for the file, then the chunk    |
+//! |                  | sequence number as a u32 big-endian                  |
+//! | `xchacha-random` | 24 random bytes; the AEAD is XChaCha20-Poly1305      |
+//!
+//! A 24-byte nonce does not fit the record's nonce field: the field holds
+//! its first 12 bytes and the payload starts with the remaining 12, so the
+//! payload is `nonce[12..24] || ciphertext || tag` and N counts them.
+//! Readers rebuild the nonce by joining the field with the start of the
+//! payload. A counter nonce never repeats within a file; files with more
+//! than 2^32 chunks cannot use it.
+//!
+//! Writers must never seal two chunks under the same key and nonce. Debug
+//! builds of this implementation check every nonce they issue against the
+//! ones already used with the key and fail the chunk on a repeat.
+//!
+//! ## Test Vectors
+//!
+//! A [`TestVectorSuite`] is a JSON document of [`ChunkTestVector`]s produced
+//! by the encryption code itself (`adapipe vectors generate`). Byte fields are
+//! lowercase hex. Each vector fixes the key, nonce and plaintext and records
+//! the resulting payload and chunk record, so both the cryptography and the
+//! record framing are covered.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{ChunkTestVector, TestVectorSuite};
+//!
+//! let vector = ChunkTestVector::new("empty", "aes-256-gcm", &[0; 32], [0; 12], b"", &[0xab; 16]);
+//! assert!(vector.check_record().is_ok());
+//!
+//! let suite = TestVectorSuite::new().with_vector(vector);
+//! let parsed = TestVectorSuite::from_json(&suite.to_json().unwrap()).unwrap();
+//! assert_eq!(parsed, suite);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::binary_file_format::{ChunkFormat, FileHeader, ProcessingStep, CURRENT_FORMAT_VERSION};
+use super::build_provenance::BuildProvenance;
+use crate::PipelineError;
+
+/// Version of the test vector suite layout
+pub const TEST_VECTOR_SUITE_VERSION: u16 = 1;
+
+/// Encryption key length in bytes
+pub const CHUNK_KEY_LENGTH: usize = 32;
+
+/// Per-chunk nonce length in bytes
+pub const CHUNK_NONCE_LENGTH: usize = 12;
+
+/// Authentication tag length in bytes
+pub const CHUNK_TAG_LENGTH: usize = 16;
+
+/// Chunk record header length: nonce plus the payload length field
+pub const CHUNK_RECORD_HEADER_LENGTH: usize = CHUNK_NONCE_LENGTH + 4;
+
+/// Associated data authenticated with chunks written without a chunk binding
+pub const CHUNK_ASSOCIATED_DATA: &[u8] = b"";
+
+/// Encryption step parameter marking files whose chunks are bound
+pub const CHUNK_BINDING_KEY: &str = "chunk_binding";
+
+/// Chunk binding layout written by this build
+pub const CHUNK_BINDING_VERSION: u16 = 1;
+
+/// Encryption stage parameter carrying the file's [`ChunkBinding`] as hex
+pub const ASSOCIATED_DATA_KEY: &str = "associated_data";
+
+/// What every encrypted chunk of one file is bound to: the format version
+/// and the pipeline that wrote it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkBinding {
+    format_version: u16,
+    pipeline_id: String,
+}
+
+impl ChunkBinding {
+    /// Binds chunks to `pipeline_id` under `format_version`
+    pub fn new(pipeline_id: impl Into<String>, format_version: u16) -> Self {
+        Self {
+            format_version,
+            pipeline_id: pipeline_id.into(),
+        }
+    }
+
+    /// The binding the chunks of `step` were sealed with, or `None` if the
+    /// step predates chunk binding
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidConfiguration` if the step records a binding
+    /// layout this build does not know.
+    pub fn for_step(header: &FileHeader, step: &ProcessingStep) -> Result<Option<Self>, PipelineError> {
+        let Some(version) = step.parameters.get(CHUNK_BINDING_KEY) else {
+            return Ok(None);
+        };
+        if version.parse::<u16>().ok() != Some(CHUNK_BINDING_VERSION) {
+            return Err(PipelineError::InvalidConfiguration(format!(
+                "Unsupported chunk binding '{}' on the {} step",
+                version, step.algorithm
+            )));
+        }
+        Ok(Some(Self::new(header.pipeline_id.clone(), header.format_version)))
+    }
+
+    /// Associated data of the chunk at `chunk_index`
+    pub fn associated_data(&self, chunk_index: u64) -> Vec<u8> {
+        chunk_associated_data(&self.file_data(), chunk_index)
+    }
+
+    /// The part of the associated data shared by every chunk of the file
+    pub fn file_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(2 + self.pipeline_id.len());
+        data.extend_from_slice(&self.format_version.to_be_bytes());
+        data.extend_from_slice(self.pipeline_id.as_bytes());
+        data
+    }
+
+    /// The value of the [`ASSOCIATED_DATA_KEY`] stage parameter
+    pub fn to_parameter(&self) -> String {
+        hex::encode(self.file_data())
+    }
+}
+
+/// Appends `chunk_index` to the file-wide associated data `file_data`
+pub fn chunk_associated_data(file_data: &[u8], chunk_index: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(file_data.len() + 8);
+    data.extend_from_slice(file_data);
+    data.extend_from_slice(&chunk_index.to_be_bytes());
+    data
+}
+
+/// One known-answer test for chunk encryption
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkTestVector {
+    /// Short description of what the vector exercises
+    pub name: String,
+
+    /// Canonical AEAD name (`aes-256-gcm` or `chacha20-poly1305`)
+    pub algorithm: String,
+
+    /// Encryption key (hex)
+    pub key: String,
+
+    /// Chunk nonce (hex)
+    pub nonce: String,
+
+    /// Chunk plaintext (hex)
+    pub plaintext: String,
+
+    /// AEAD output, ciphertext followed by the tag (hex)
+    pub payload: String,
+
+    /// Complete chunk record as written to the file (hex)
+    pub record: String,
+}
+
+impl ChunkTestVector {
+    /// Creates a vector from its inputs and the payload the AEAD produced
+    ///
+    /// The record is framed from the nonce and payload.
+    pub fn new(
+        name: impl Into<String>,
+        algorithm: impl Into<String>,
+        key: &[u8],
+        nonce: [u8; CHUNK_NONCE_LENGTH],
+        plaintext: &[u8],
+        payload: &[u8],
+    ) -> Self {
+        Self {
+            name: name.into(),
+            algorithm: algorithm.into(),
+            key: hex::encode(key),
+            nonce: hex::encode(nonce),
+            plaintext: hex::encode(plaintext),
+            payload: hex::encode(payload),
+            record: hex::encode(ChunkFormat::new(nonce, payload.to_vec()).to_bytes()),
+        }
+    }
+
+    /// Decoded key
+    pub fn key_bytes(&self) -> Result<Vec<u8>, PipelineError> {
+        let key = self.decode("key", &self.key)?;
+        if key.len() != CHUNK_KEY_LENGTH {
+            return Err(self.invalid(format!("key is {} bytes, expected {}", key.len(), CHUNK_KEY_LENGTH)));
+        }
+        Ok(key)
+    }
+
+    /// Decoded nonce
+    pub fn nonce_bytes(&self) -> Result<[u8; CHUNK_NONCE_LENGTH], PipelineError> {
+        let nonce = self.decode("nonce", &self.nonce)?;
+        nonce.as_slice().try_into().map_err(|_| {
+            self.invalid(format!(
+                "nonce is {} bytes, expected {}",
+                nonce.len(),
+                CHUNK_NONCE_LENGTH
+            ))
+        })
+    }
+
+    /// Decoded plaintext
+    pub fn plaintext_bytes(&self) -> Result<Vec<u8>, PipelineError> {
+        self.decode("plaintext", &self.plaintext)
+    }
+
+    /// Decoded payload
+    pub fn payload_bytes(&self) -> Result<Vec<u8>, PipelineError> {
+        self.decode("payload", &self.payload)
+    }
+
+    /// Decoded chunk record
+    pub fn record_bytes(&self) -> Result<Vec<u8>, PipelineError> {
+        self.decode("record", &self.record)
+    }
+
+    /// Checks the parts of the vector that follow from the layout alone
+    ///
+    /// The payload must be the plaintext length plus the tag, and the record
+    /// must frame the nonce and payload exactly as
+    /// [`ChunkFormat`] writes and reads them.
+    pub fn check_record(&self) -> Result<(), PipelineError> {
+        let nonce = self.nonce_bytes()?;
+        let plaintext = self.plaintext_bytes()?;
+        let payload = self.payload_bytes()?;
+        let record = self.record_bytes()?;
+
+        if payload.len() != plaintext.len() + CHUNK_TAG_LENGTH {
+            return Err(self.invalid(format!(
+                "payload is {} bytes, expected plaintext length {} plus a {}-byte tag",
+                payload.len(),
+                plaintext.len(),
+                CHUNK_TAG_LENGTH
+            )));
+        }
+        if record != ChunkFormat::new(nonce, payload.clone()).to_bytes() {
+            return Err(self.invalid("record does not frame the nonce and payload"));
+        }
+
+        let (parsed, consumed) = ChunkFormat::from_bytes(&record)?;
+        if consumed != record.len() || parsed.nonce != nonce || parsed.payload != payload {
+            return Err(self.invalid("record does not parse back to the nonce and payload"));
+        }
+        Ok(())
+    }
+
+    fn decode(&self, field: &str, value: &str) -> Result<Vec<u8>, PipelineError> {
+        hex::decode(value).map_err(|e| self.invalid(format!("{} is not valid hex: {}", field, e)))
+    }
+
+    fn invalid(&self, message: impl std::fmt::Display) -> PipelineError {
+        PipelineError::validation_error(format!("Test vector '{}': {}", self.name, message))
+    }
+}
+
+/// A published set of chunk encryption test vectors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestVectorSuite {
+    /// Suite layout version
+    pub suite_version: u16,
+
+    /// `.adapipe` format version the vectors describe
+    pub format_version: u16,
+
+    /// Build identity of the binary that generated the vectors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_by: Option<BuildProvenance>,
+
+    /// The vectors
+    pub vectors: Vec<ChunkTestVector>,
+}
+
+impl Default for TestVectorSuite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestVectorSuite {
+    /// Creates an empty suite for the current format version
+    pub fn new() -> Self {
+        Self {
+            suite_version: TEST_VECTOR_SUITE_VERSION,
+            format_version: CURRENT_FORMAT_VERSION,
+            generated_by: None,
+            vectors: Vec::new(),
+        }
+    }
+
+    /// Adds a vector
+    pub fn with_vector(mut self, vector: ChunkTestVector) -> Self {
+        self.vectors.push(vector);
+        self
+    }
+
+    /// Records the build that generated the suite
+    pub fn with_provenance(mut self, provenance: BuildProvenance) -> Self {
+        self.generated_by = Some(provenance);
+        self
+    }
+
+    /// Pretty-printed JSON for publishing
+    pub fn to_json(&self) -> Result<String, PipelineError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode test vectors: {}", e)))
+    }
+
+    /// Parses a published suite
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let suite: Self = serde_json::from_str(json)
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid test vector suite: {}", e)))?;
+        if suite.suite_version > TEST_VECTOR_SUITE_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Test vector suite version {} is newer than supported version {}",
+                suite.suite_version, TEST_VECTOR_SUITE_VERSION
+            )));
+        }
+        if suite.format_version != CURRENT_FORMAT_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Test vectors describe format version {}, this build writes version {}",
+                suite.format_version, CURRENT_FORMAT_VERSION
+            )));
+        }
+        Ok(suite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::NonceStrategy;
+
+    #[test]
+    fn test_record_frames_nonce_and_payload() {
+        let vector = ChunkTestVector::new("short", "chacha20-poly1305", &[7; 32], [1; 12], b"abc", &[9; 19]);
+        assert!(vector.check_record().is_ok());
+        assert_eq!(&vector.record[..32], "01010101010101010101010113000000");
+
+        let mut truncated = vector.clone();
+        truncated.payload = hex::encode([9u8; 18]);
+        assert!(truncated.check_record().is_err());
+
+        let mut reframed = vector;
+        reframed.record = hex::encode(ChunkFormat::new([2; 12], vec![9; 19]).to_bytes());
+        assert!(reframed.check_record().is_err());
+    }
+
+    #[test]
+    fn test_chunk_binding_layout_and_step_marker() {
+        let binding = ChunkBinding::new("pipe", 1);
+        assert_eq!(
+            binding.associated_data(2),
+            b"\x00\x01pipe\x00\x00\x00\x00\x00\x00\x00\x02"
+        );
+        assert_ne!(binding.associated_data(0), binding.associated_data(1));
+        assert_ne!(
+            binding.associated_data(0),
+            ChunkBinding::new("other", 1).associated_data(0)
+        );
+        assert_eq!(hex::decode(binding.to_parameter()).unwrap(), binding.file_data());
+
+        let header = FileHeader::new("data.bin".to_string(), 1024, "0".repeat(64))
+            .add_encryption_step("aes-256-gcm", "argon2", 32, NonceStrategy::Random)
+            .with_pipeline_id("pipe".to_string());
+        assert_eq!(
+            ChunkBinding::for_step(&header, &header.processing_steps[0]).unwrap(),
+            None
+        );
+
+        let header = header.with_chunk_binding();
+        assert_eq!(
+            ChunkBinding::for_step(&header, &header.processing_steps[0]).unwrap(),
+            Some(ChunkBinding::new("pipe", CURRENT_FORMAT_VERSION))
+        );
+        let mut step = header.processing_steps[0].clone();
+        step.parameters.insert(CHUNK_BINDING_KEY.to_string(), "2".to_string());
+        assert!(ChunkBinding::for_step(&header, &step).is_err());
+    }
+
+    #[test]
+    fn test_suite_rejects_newer_layouts_and_other_formats() {
+        let suite = TestVectorSuite::new();
+        let mut newer = suite.clone();
+        newer.suite_version = TEST_VECTOR_SUITE_VERSION + 1;
+        assert!(TestVectorSuite::from_json(&newer.to_json().unwrap()).is_err());
+
+        let mut other_format = suite;
+        other_format.format_version = CURRENT_FORMAT_VERSION + 1;
+        assert!(TestVectorSuite::from_json(&other_format.to_json().unwrap()).is_err());
+    }
+}
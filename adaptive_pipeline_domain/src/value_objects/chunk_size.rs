@@ -163,9 +163,13 @@
 //! - **Compression-Aware Sizing**: Chunk sizes optimized for compression
 //!   algorithms
 
+use crate::value_objects::ChunkThroughput;
 use crate::PipelineError;
 use serde::{Deserialize, Serialize};
 
+/// Pipeline configuration key holding a fixed chunk size, in bytes
+pub const CHUNK_SIZE_KEY: &str = "chunk_size";
+
 /// Value object representing a chunk size with validation
 ///
 /// This struct provides a type-safe representation of chunk sizes used
@@ -339,6 +343,73 @@ impl ChunkSize {
         ChunkSize { bytes: clamped_size }
     }
 
+    /// Chunk sizes further than this factor from the static heuristic are
+    /// never learned, since their throughput was measured on files of a very
+    /// different size
+    pub const LEARNING_WINDOW: usize = 4;
+
+    /// A learned size must beat the heuristic's measured throughput by this
+    /// fraction, so measurement noise doesn't flip the choice between runs
+    pub const LEARNING_MARGIN: f64 = 0.05;
+
+    /// Lists the chunk sizes a benchmark sweep from `min` to `max` tries
+    ///
+    /// Starts at `min` and doubles until `max`, which is always the last
+    /// size even when it is not a power-of-two multiple of `min`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if `min` is larger than `max`.
+    pub fn sweep(min: ChunkSize, max: ChunkSize) -> Result<Vec<ChunkSize>, PipelineError> {
+        if min > max {
+            return Err(PipelineError::InvalidConfiguration(format!(
+                "Sweep starts at {} but ends at the smaller {}",
+                min, max
+            )));
+        }
+
+        let mut sizes = Vec::new();
+        let mut bytes = min.bytes;
+        while bytes < max.bytes {
+            sizes.push(ChunkSize { bytes });
+            bytes = bytes.saturating_mul(2);
+        }
+        sizes.push(max);
+        Ok(sizes)
+    }
+
+    /// Picks a chunk size for `file_size`, biased by the throughput `history`
+    /// of earlier runs
+    ///
+    /// Starts from [`optimal_for_file_size`](Self::optimal_for_file_size)
+    /// and switches to the historically fastest size when:
+    /// - the heuristic size has been measured at least once (until then it is
+    ///   run as is, so every choice is compared against a measured baseline),
+    /// - the candidate is within [`LEARNING_WINDOW`](Self::LEARNING_WINDOW)
+    ///   of the heuristic and no larger than the file, and
+    /// - it beat the heuristic by more than
+    ///   [`LEARNING_MARGIN`](Self::LEARNING_MARGIN).
+    pub fn learned_for_file_size(file_size: u64, history: &[ChunkThroughput]) -> Self {
+        let heuristic = Self::optimal_for_file_size(file_size);
+        let Some(baseline) = history.iter().find(|record| record.chunk_size() == heuristic) else {
+            return heuristic;
+        };
+
+        let in_window = |size: usize| {
+            size >= heuristic.bytes / Self::LEARNING_WINDOW
+                && size <= heuristic.bytes.saturating_mul(Self::LEARNING_WINDOW)
+                && (file_size == 0 || size as u64 <= file_size)
+        };
+        let threshold = baseline.bytes_per_second() * (1.0 + Self::LEARNING_MARGIN);
+
+        history
+            .iter()
+            .filter(|record| in_window(record.chunk_size().bytes()))
+            .filter(|record| record.bytes_per_second() > threshold)
+            .max_by(|a, b| a.bytes_per_second().total_cmp(&b.bytes_per_second()))
+            .map_or(heuristic, |record| record.chunk_size())
+    }
+
     /// Calculates the number of chunks needed for a given file size
     pub fn chunks_needed_for_file(&self, file_size: u64) -> u64 {
         if file_size == 0 {
@@ -387,24 +458,34 @@ impl ChunkSize {
             ));
         }
 
+        Self::new(user_chunk_size_bytes)
+            .map_err(|e| e.to_string())?
+            .validate_for_file_size(file_size)?;
+
+        Ok(user_chunk_size_bytes)
+    }
+
+    /// Checks that a user-chosen chunk size is sensible for a file
+    /// of the given size, returning a message explaining why not if it isn't
+    pub fn validate_for_file_size(&self, file_size: u64) -> Result<(), String> {
         // Efficiency warnings for very small files
-        if file_size > 0 && user_chunk_size_bytes > (file_size as usize) {
+        if file_size > 0 && self.bytes as u64 > file_size {
             return Err(format!(
-                "Chunk size {} MB is larger than file size ({} bytes). Consider smaller chunk size",
-                user_chunk_size_mb, file_size
+                "Chunk size {} is larger than file size ({} bytes). Consider smaller chunk size",
+                self, file_size
             ));
         }
 
         // Warning for very large chunks on small files
-        if file_size < 10_485_760 && user_chunk_size_mb > 10 {
+        if file_size < 10_485_760 && self.bytes > 10 * 1024 * 1024 {
             // File < 10MB, chunk > 10MB
             return Err(format!(
-                "Chunk size {} MB is excessive for small file ({} bytes). Consider 1-10 MB",
-                user_chunk_size_mb, file_size
+                "Chunk size {} is excessive for small file ({} bytes). Consider 1-10 MB",
+                self, file_size
             ));
         }
 
-        Ok(user_chunk_size_bytes)
+        Ok(())
     }
 
     /// Returns a description of the chunk size strategy for the given file size
@@ -448,6 +529,95 @@ mod tests {
 
     use serde_json;
 
+    fn record(kb: usize, bytes_per_second: f64) -> ChunkThroughput {
+        let size = ChunkSize::from_kb(kb).unwrap();
+        ChunkThroughput::new(size, 3, (bytes_per_second * 10.0) as u64, 10.0)
+    }
+
+    /// Tests that history only moves the choice away from the heuristic
+    /// once the heuristic is measured, within the window, by a clear margin.
+    #[test]
+    fn test_learned_chunk_size_follows_measured_throughput() {
+        let file_size = 300 * 1024 * 1024; // heuristic: 16MB
+        let learned = |history: &[ChunkThroughput]| ChunkSize::learned_for_file_size(file_size, history).kilobytes();
+
+        // No history, or only other sizes: run the heuristic to measure it
+        assert_eq!(learned(&[]), 16384.0);
+        assert_eq!(learned(&[record(32768, 900.0)]), 16384.0);
+
+        // A faster measured size in the window wins
+        let history = [record(16384, 500.0), record(32768, 900.0), record(8192, 700.0)];
+        assert_eq!(learned(&history), 32768.0);
+
+        // Within the noise margin the heuristic stays
+        assert_eq!(learned(&[record(16384, 500.0), record(32768, 510.0)]), 16384.0);
+
+        // Outside the window, never
+        let history = [record(16384, 500.0), record(131072, 5000.0), record(2048, 5000.0)];
+        assert_eq!(learned(&history), 16384.0);
+
+        // Nor larger than the file (100KB file, heuristic 64KB)
+        let history = [record(64, 500.0), record(128, 900.0), record(96, 700.0)];
+        assert_eq!(ChunkSize::learned_for_file_size(100 * 1024, &history).kilobytes(), 96.0);
+    }
+
+    /// Tests that user-chosen sizes are checked against the file they
+    /// will split, whatever unit they were given in.
+    #[test]
+    fn test_sweep_doubles_up_to_the_maximum() {
+        let mib = |mb| ChunkSize::from_mb(mb).unwrap();
+        let megabytes = |sizes: Vec<ChunkSize>| sizes.iter().map(|size| size.megabytes()).collect::<Vec<_>>();
+
+        assert_eq!(
+            megabytes(ChunkSize::sweep(mib(1), mib(64)).unwrap()),
+            vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]
+        );
+        assert_eq!(
+            megabytes(ChunkSize::sweep(mib(3), mib(10)).unwrap()),
+            vec![3.0, 6.0, 10.0]
+        );
+        assert_eq!(ChunkSize::sweep(mib(8), mib(8)).unwrap(), vec![mib(8)]);
+        assert!(ChunkSize::sweep(mib(64), mib(1)).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_chunk_size_lives_in_its_configuration() {
+        use crate::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+        use std::collections::HashMap;
+
+        let stage = PipelineStage::new(
+            "compress".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            0,
+        )
+        .unwrap();
+        let mut pipeline = Pipeline::new("backup".to_string(), vec![stage]).unwrap();
+        assert_eq!(pipeline.chunk_size().unwrap(), None);
+
+        pipeline.set_chunk_size(ChunkSize::from_mb(8).unwrap());
+        assert_eq!(
+            pipeline.configuration().get(CHUNK_SIZE_KEY).map(String::as_str),
+            Some("8388608")
+        );
+        assert_eq!(pipeline.chunk_size().unwrap(), Some(ChunkSize::from_mb(8).unwrap()));
+
+        pipeline.update_configuration(HashMap::from([(CHUNK_SIZE_KEY.to_string(), "8MiB".to_string())]));
+        assert!(pipeline.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_for_file_size() {
+        let chunk = ChunkSize::from_kb(512).unwrap();
+        assert!(chunk.validate_for_file_size(4 * 1024 * 1024).is_ok());
+        assert!(chunk.validate_for_file_size(0).is_ok());
+        assert!(chunk.validate_for_file_size(1024).is_err());
+
+        let chunk = ChunkSize::from_mb(16).unwrap();
+        assert!(chunk.validate_for_file_size(8 * 1024 * 1024).is_err());
+        assert!(chunk.validate_for_file_size(64 * 1024 * 1024).is_ok());
+    }
+
     /// Tests ChunkSize creation with valid input values.
     ///
     /// Validates that:
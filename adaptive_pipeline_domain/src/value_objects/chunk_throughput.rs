@@ -0,0 +1,67 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Chunk Throughput Value Object
+//!
+//! Accumulated throughput of one pipeline on one storage type at one chunk
+//! size, recorded after each successful run. The history of these records
+//! lets [`ChunkSize::learned_for_file_size`] bias chunk-size selection toward
+//! the sizes that have actually been fastest for a pipeline.
+
+use crate::value_objects::ChunkSize;
+use serde::{Deserialize, Serialize};
+
+/// Throughput totals for one (pipeline, storage type, chunk size)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkThroughput {
+    chunk_size: ChunkSize,
+    runs: u64,
+    bytes_processed: u64,
+    processing_seconds: f64,
+}
+
+impl ChunkThroughput {
+    /// Creates throughput totals
+    pub fn new(chunk_size: ChunkSize, runs: u64, bytes_processed: u64, processing_seconds: f64) -> Self {
+        Self {
+            chunk_size,
+            runs,
+            bytes_processed,
+            processing_seconds,
+        }
+    }
+
+    /// Gets the chunk size the runs used
+    pub fn chunk_size(&self) -> ChunkSize {
+        self.chunk_size
+    }
+
+    /// Gets the number of recorded runs
+    pub fn runs(&self) -> u64 {
+        self.runs
+    }
+
+    /// Gets the total bytes processed across the runs
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
+    /// Gets the total processing time across the runs, in seconds
+    pub fn processing_seconds(&self) -> f64 {
+        self.processing_seconds
+    }
+
+    /// Byte-weighted average throughput, so large runs count for more than
+    /// small ones
+    pub fn bytes_per_second(&self) -> f64 {
+        if self.processing_seconds > 0.0 {
+            self.bytes_processed as f64 / self.processing_seconds
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Concurrency Summary Value Object
+//!
+//! How one processing run used its workers: how long chunks queued for a
+//! worker, how long workers waited for a shared CPU token, and how much of
+//! the run the workers spent processing. The completion summary and the
+//! processing manifest report it, with a hint when the numbers point at a
+//! better `--workers` or `--chunk-size` setting.
+//!
+//! Wait percentiles come from latency histograms, so they are the upper
+//! bound of the bucket holding the percentile (1, 5, 10, 50 or 100 ms);
+//! 100 ms means 100 ms or more.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ConcurrencySummary;
+//!
+//! let summary = ConcurrencySummary {
+//!     workers: 8,
+//!     queue_wait_p50_ms: 1,
+//!     queue_wait_p95_ms: 5,
+//!     cpu_wait_p50_ms: 1,
+//!     cpu_wait_p95_ms: 1,
+//!     worker_utilization_percent: 31.0,
+//! };
+//! assert!(summary.tuning_hint().unwrap().contains("fewer --workers"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// A p95 wait at or above this many milliseconds is worth tuning for
+pub const SIGNIFICANT_WAIT_MS: u64 = 10;
+
+/// Workers busy less than this share of the run were mostly idle
+pub const LOW_UTILIZATION_PERCENT: f64 = 50.0;
+
+/// Workers busy at least this share of the run were saturated
+pub const HIGH_UTILIZATION_PERCENT: f64 = 90.0;
+
+/// Worker concurrency of one processing run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcurrencySummary {
+    /// Workers in the pool (all stage pools, for stage-parallel pipelines)
+    pub workers: usize,
+
+    /// Median time a chunk waited in the queue for a free worker
+    pub queue_wait_p50_ms: u64,
+
+    /// 95th percentile time a chunk waited for a free worker
+    pub queue_wait_p95_ms: u64,
+
+    /// Median time a worker waited for a CPU token
+    pub cpu_wait_p50_ms: u64,
+
+    /// 95th percentile time a worker waited for a CPU token
+    pub cpu_wait_p95_ms: u64,
+
+    /// Share of the workers' combined wall time spent processing chunks
+    pub worker_utilization_percent: f64,
+}
+
+impl ConcurrencySummary {
+    /// Suggests a better worker or chunk setting, if the run shows one
+    ///
+    /// CPU token contention is reported first, since more workers would
+    /// only wait longer; then idle workers; then chunks queuing for
+    /// saturated workers.
+    pub fn tuning_hint(&self) -> Option<String> {
+        if self.cpu_wait_p95_ms >= SIGNIFICANT_WAIT_MS {
+            return Some(format!(
+                "workers waited up to {} ms (p95) for CPU tokens; try fewer --workers or more --cpu-threads",
+                self.cpu_wait_p95_ms
+            ));
+        }
+        if self.worker_utilization_percent < LOW_UTILIZATION_PERCENT {
+            return Some(format!(
+                "workers were busy only {:.0}% of the run; reading or writing limits throughput, so fewer --workers \
+                 or a larger --chunk-size should do as well",
+                self.worker_utilization_percent
+            ));
+        }
+        if self.worker_utilization_percent >= HIGH_UTILIZATION_PERCENT && self.queue_wait_p95_ms >= SIGNIFICANT_WAIT_MS
+        {
+            return Some(format!(
+                "chunks queued up to {} ms (p95) for busy workers; more --workers may raise throughput",
+                self.queue_wait_p95_ms
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(queue_wait_p95_ms: u64, cpu_wait_p95_ms: u64, worker_utilization_percent: f64) -> ConcurrencySummary {
+        ConcurrencySummary {
+            workers: 4,
+            queue_wait_p50_ms: 1,
+            queue_wait_p95_ms,
+            cpu_wait_p50_ms: 1,
+            cpu_wait_p95_ms,
+            worker_utilization_percent,
+        }
+    }
+
+    #[test]
+    fn test_tuning_hints() {
+        assert!(summary(50, 50, 95.0).tuning_hint().unwrap().contains("CPU tokens"));
+        assert!(summary(1, 1, 20.0).tuning_hint().unwrap().contains("fewer --workers"));
+        assert!(summary(50, 1, 95.0).tuning_hint().unwrap().contains("more --workers"));
+        assert_eq!(summary(1, 1, 95.0).tuning_hint(), None);
+        assert_eq!(summary(50, 1, 70.0).tuning_hint(), None);
+    }
+}
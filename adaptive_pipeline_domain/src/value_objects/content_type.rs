@@ -0,0 +1,304 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Content Type Value Object
+//!
+//! The MIME type of a file's contents, detected from the magic bytes at its
+//! start rather than from its name. It is detected while the input is first
+//! read, recorded in the `.adapipe` header, and lets a stage sit out files
+//! whose contents it cannot help with (see [`ContentType::matches_any`]).
+//!
+//! | Magic bytes                 | Content type                  | Compressed |
+//! |-----------------------------|-------------------------------|------------|
+//! | `PK\x03\x04`                | `application/zip`             | yes        |
+//! | `\x1f\x8b`                  | `application/gzip`            | yes        |
+//! | `BZh`                       | `application/x-bzip2`         | yes        |
+//! | `\xfd7zXZ\0`                | `application/x-xz`            | yes        |
+//! | `\x28\xb5\x2f\xfd`          | `application/zstd`            | yes        |
+//! | `7z\xbc\xaf\x27\x1c`        | `application/x-7z-compressed` | yes        |
+//! | `\x04\x22\x4d\x18`          | `application/x-lz4`           | yes        |
+//! | `\xff\xd8\xff`              | `image/jpeg`                  | yes        |
+//! | `\x89PNG\r\n\x1a\n`         | `image/png`                   | yes        |
+//! | `GIF87a`, `GIF89a`          | `image/gif`                   | yes        |
+//! | `RIFF....WEBP`              | `image/webp`                  | yes        |
+//! | `....ftyp`                  | `video/mp4`                   | yes        |
+//! | `ID3`                       | `audio/mpeg`                  | yes        |
+//! | `%PDF-`                     | `application/pdf`             | no         |
+//! | `\x7fELF`                   | `application/x-elf`           | no         |
+//! | `ustar` at offset 257       | `application/x-tar`           | no         |
+//! | UTF-8 without NUL bytes     | `text/plain`                  | no         |
+//! | anything else               | `application/octet-stream`    | no         |
+//!
+//! ## Patterns
+//!
+//! Stage rules name content types with comma-separated patterns: an exact
+//! type (`image/png`), a top-level wildcard (`image/*`), or `compressed` for
+//! every type marked compressed above.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ContentType;
+//!
+//! let content_type = ContentType::detect(b"\x89PNG\r\n\x1a\n....");
+//! assert_eq!(content_type.mime(), "image/png");
+//! assert!(content_type.matches_any("compressed").unwrap());
+//! assert!(content_type.matches_any("text/plain, image/*").unwrap());
+//! assert!(!ContentType::detect(b"hello").matches_any("compressed").unwrap());
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Bytes at the start of a file that detection looks at
+pub const DETECTION_LENGTH: usize = 512;
+
+/// Processing context metadata key under which stages find the detected
+/// content type of the file being processed
+pub const CONTENT_TYPE_METADATA_KEY: &str = "content_type";
+
+/// Pattern matching every content type that is already compressed
+pub const COMPRESSED_PATTERN: &str = "compressed";
+
+/// Content type of anything detection doesn't recognize
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Content types whose data is already compressed, so compressing it again
+/// gains little
+const COMPRESSED_TYPES: &[&str] = &[
+    "application/zip",
+    "application/gzip",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/zstd",
+    "application/x-7z-compressed",
+    "application/x-lz4",
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "video/mp4",
+    "audio/mpeg",
+];
+
+/// Signatures matched at the start of the data, most specific first
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xfd7zXZ\x00", "application/x-xz"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (b"\x04\x22\x4d\x18", "application/x-lz4"),
+    (b"\x7fELF", "application/x-elf"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"BZh", "application/x-bzip2"),
+    (b"ID3", "audio/mpeg"),
+    (b"\x1f\x8b", "application/gzip"),
+];
+
+/// MIME type of a file's contents
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContentType(String);
+
+impl ContentType {
+    /// Detects the content type from the first bytes of a file
+    ///
+    /// Only the first [`DETECTION_LENGTH`] bytes are looked at; passing more
+    /// is harmless. Empty data is `application/octet-stream`.
+    pub fn detect(data: &[u8]) -> Self {
+        let head = &data[..data.len().min(DETECTION_LENGTH)];
+        let mime = SIGNATURES
+            .iter()
+            .find(|(magic, _)| head.starts_with(magic))
+            .map(|(_, mime)| *mime)
+            .or_else(|| {
+                if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+                    Some("image/webp")
+                } else if head.len() >= 8 && &head[4..8] == b"ftyp" {
+                    Some("video/mp4")
+                } else if head.len() >= 262 && &head[257..262] == b"ustar" {
+                    Some("application/x-tar")
+                } else if is_text(head) {
+                    Some("text/plain")
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(OCTET_STREAM);
+        Self(mime.to_string())
+    }
+
+    /// The MIME type, e.g. `image/png`
+    pub fn mime(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the data is already compressed
+    pub fn is_compressed(&self) -> bool {
+        COMPRESSED_TYPES.contains(&self.0.as_str())
+    }
+
+    /// Whether this content type matches one pattern: an exact type, a
+    /// `type/*` wildcard or `compressed`
+    pub fn matches(&self, pattern: &str) -> bool {
+        if pattern == COMPRESSED_PATTERN {
+            return self.is_compressed();
+        }
+        match pattern.strip_suffix("/*") {
+            Some(top_level) => self.0.split('/').next() == Some(top_level),
+            None => self.0 == pattern,
+        }
+    }
+
+    /// Whether this content type matches any of the comma-separated
+    /// `patterns`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if a pattern is malformed (see
+    /// [`ContentType::validate_patterns`]).
+    pub fn matches_any(&self, patterns: &str) -> Result<bool, PipelineError> {
+        Ok(Self::parse_patterns(patterns)?.any(|pattern| self.matches(pattern)))
+    }
+
+    /// Checks comma-separated content type patterns without matching them
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` for an empty list, or a pattern that is
+    /// neither `compressed`, `type/*` nor a `type/subtype`.
+    pub fn validate_patterns(patterns: &str) -> Result<(), PipelineError> {
+        Self::parse_patterns(patterns).map(|_| ())
+    }
+
+    fn parse_patterns(patterns: &str) -> Result<impl Iterator<Item = &str>, PipelineError> {
+        let parsed: Vec<&str> = patterns.split(',').map(str::trim).collect();
+        for pattern in &parsed {
+            if *pattern != COMPRESSED_PATTERN && !is_mime(pattern) {
+                return Err(PipelineError::InvalidConfiguration(format!(
+                    "Invalid content type pattern '{}': expected 'compressed', 'type/*' or 'type/subtype'",
+                    pattern
+                )));
+            }
+        }
+        Ok(parsed.into_iter())
+    }
+}
+
+impl Display for ContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ContentType {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !is_mime(s) || s.contains('*') {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Invalid content type '{}': expected 'type/subtype'",
+                s
+            )));
+        }
+        Ok(Self(s.to_ascii_lowercase()))
+    }
+}
+
+impl TryFrom<String> for ContentType {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ContentType> for String {
+    fn from(content_type: ContentType) -> Self {
+        content_type.0
+    }
+}
+
+/// Whether `s` has the `type/subtype` shape, allowing `*` as the subtype
+fn is_mime(s: &str) -> bool {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.' | '_'))
+    };
+    match s.split_once('/') {
+        Some((top_level, subtype)) => valid(top_level) && (subtype == "*" || valid(subtype)),
+        None => false,
+    }
+}
+
+/// Whether `head` looks like text: UTF-8 without NUL bytes, allowing a
+/// character cut off at the end
+fn is_text(head: &[u8]) -> bool {
+    if head.is_empty() || head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none() && head.len() - e.valid_up_to() < 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_signatures_and_falls_back() {
+        assert_eq!(ContentType::detect(b"\x1f\x8b\x08\x00").mime(), "application/gzip");
+        assert_eq!(
+            ContentType::detect(b"RIFF\x00\x00\x00\x00WEBPVP8 ").mime(),
+            "image/webp"
+        );
+        assert_eq!(ContentType::detect(b"\x00\x00\x00\x18ftypmp42").mime(), "video/mp4");
+
+        let mut tar = vec![b'a'; 512];
+        tar[100] = 0;
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(ContentType::detect(&tar).mime(), "application/x-tar");
+
+        // A multi-byte character cut off at the end of the first chunk
+        assert_eq!(ContentType::detect(&"naïve".as_bytes()[..3]).mime(), "text/plain");
+        assert_eq!(ContentType::detect(b"a\x00b").mime(), "application/octet-stream");
+        assert_eq!(ContentType::detect(b"").mime(), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_patterns() {
+        let jpeg = ContentType::detect(b"\xff\xd8\xff\xe0");
+        assert!(jpeg.matches("compressed"));
+        assert!(jpeg.matches("image/*"));
+        assert!(jpeg.matches("image/jpeg"));
+        assert!(!jpeg.matches("image/png"));
+        assert!(!jpeg.matches("application/*"));
+
+        assert!(ContentType::validate_patterns("compressed, image/*,text/plain").is_ok());
+        for invalid in ["", "image", "*/*", "image/", "compressed,,image/png"] {
+            assert!(ContentType::validate_patterns(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_serializes_as_its_mime_type() {
+        let png = ContentType::detect(b"\x89PNG\r\n\x1a\n");
+        let json = serde_json::to_string(&png).unwrap();
+        assert_eq!(json, "\"image/png\"");
+        assert_eq!(serde_json::from_str::<ContentType>(&json).unwrap(), png);
+        assert!(serde_json::from_str::<ContentType>("\"not a type\"").is_err());
+        assert_eq!("Text/Plain".parse::<ContentType>().unwrap().mime(), "text/plain");
+    }
+}
@@ -0,0 +1,134 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Correlation ID Value Object
+//!
+//! Identifies one run: a CLI invocation or an API request. The same ID is
+//! attached to the run's tracing spans, the audit records and domain events
+//! it stores, and the header of every `.adapipe` file it writes, so a file
+//! can be traced back to the exact logged run that produced it.
+//!
+//! IDs are generated as ULIDs, which sort by creation time. A caller that
+//! already has a request ID (for example from a CI job or a gateway) may
+//! supply it instead, under the same rules as idempotency keys: 1 to 128
+//! characters of ASCII letters, digits, `-`, `_`, `.` and `:`.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::CorrelationId;
+//!
+//! let generated = CorrelationId::generate();
+//! assert_eq!(generated.as_str().len(), 26);
+//!
+//! let supplied: CorrelationId = "ci-build-4711".parse().unwrap();
+//! assert_eq!(supplied.to_string(), "ci-build-4711");
+//! assert!("has space".parse::<CorrelationId>().is_err());
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use ulid::Ulid;
+
+const MAX_ID_LEN: usize = 128;
+
+/// Validated correlation ID of one run
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Generates a fresh, time-ordered ID for a new run
+    pub fn generate() -> Self {
+        Self(Ulid::new().to_string())
+    }
+
+    /// Uses a caller-supplied ID, validating it
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidConfiguration` if the ID is empty, too
+    /// long, or contains characters other than ASCII letters, digits, `-`,
+    /// `_`, `.` and `:`.
+    pub fn new(id: impl Into<String>) -> Result<Self, PipelineError> {
+        let id = id.into();
+        if id.is_empty() || id.len() > MAX_ID_LEN {
+            return Err(PipelineError::invalid_config(format!(
+                "Correlation ID must be 1-{} characters, got {}",
+                MAX_ID_LEN,
+                id.len()
+            )));
+        }
+        if !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        {
+            return Err(PipelineError::invalid_config(format!(
+                "Invalid correlation ID '{}': use ASCII letters, digits, '-', '_', '.' and ':'",
+                id
+            )));
+        }
+        Ok(Self(id))
+    }
+
+    /// Gets the ID
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for CorrelationId {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for CorrelationId {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<CorrelationId> for String {
+    fn from(id: CorrelationId) -> Self {
+        id.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_ids_are_unique_and_valid() {
+        let first = CorrelationId::generate();
+        let second = CorrelationId::generate();
+        assert_ne!(first, second);
+        assert_eq!(CorrelationId::new(first.as_str()).unwrap(), first);
+    }
+
+    #[test]
+    fn test_supplied_ids_are_validated() {
+        assert!(CorrelationId::new("gateway:req-01.a_b").is_ok());
+        assert!(CorrelationId::new("").is_err());
+        assert!(CorrelationId::new("x".repeat(MAX_ID_LEN + 1)).is_err());
+        assert!(CorrelationId::new("line\nbreak").is_err());
+
+        let json = serde_json::to_string(&CorrelationId::new("run-1").unwrap()).unwrap();
+        assert_eq!(json, "\"run-1\"");
+        assert!(serde_json::from_str::<CorrelationId>("\"bad id\"").is_err());
+    }
+}
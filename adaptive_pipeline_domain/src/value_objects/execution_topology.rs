@@ -0,0 +1,134 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Execution Topology Value Object
+//!
+//! How a pipeline's stages are spread over workers when a file is
+//! processed. The topology is a property of the pipeline, stored in its
+//! configuration under [`EXECUTION_TOPOLOGY_KEY`].
+//!
+//! | Topology         | Workers                                              |
+//! |------------------|------------------------------------------------------|
+//! | `chunk-parallel` | Each worker runs every stage on one chunk; the default |
+//! | `stage-parallel` | Each stage has its own worker pool, chained by channels |
+//!
+//! Chunk-parallel has no hand-offs between stages and suits pipelines whose
+//! stages cost about the same. Stage-parallel decouples stage rates: when
+//! one stage is much slower than the others, its pool keeps working while
+//! the fast stages run ahead within the in-flight window, instead of every
+//! worker stalling in the slow stage in turn.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ExecutionTopology;
+//!
+//! let topology: ExecutionTopology = "stage-parallel".parse().unwrap();
+//! assert_eq!(topology, ExecutionTopology::StageParallel);
+//! assert_eq!(ExecutionTopology::default().to_string(), "chunk-parallel");
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Pipeline configuration key holding the execution topology
+pub const EXECUTION_TOPOLOGY_KEY: &str = "execution_topology";
+
+/// How stages are assigned to workers during processing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecutionTopology {
+    /// Every worker runs all stages on the chunks it takes
+    #[default]
+    ChunkParallel,
+    /// Every stage has its own worker pool; chunks flow from pool to pool
+    StageParallel,
+}
+
+impl ExecutionTopology {
+    /// Returns every topology, default first
+    pub fn all() -> [ExecutionTopology; 2] {
+        [ExecutionTopology::ChunkParallel, ExecutionTopology::StageParallel]
+    }
+
+    /// Returns the kebab-case name used on the command line and in
+    /// pipeline configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionTopology::ChunkParallel => "chunk-parallel",
+            ExecutionTopology::StageParallel => "stage-parallel",
+        }
+    }
+}
+
+impl Display for ExecutionTopology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ExecutionTopology {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        ExecutionTopology::all()
+            .into_iter()
+            .find(|topology| topology.as_str() == name)
+            .ok_or_else(|| {
+                PipelineError::invalid_config(format!(
+                    "Unknown execution topology '{}'. Valid topologies: chunk-parallel, stage-parallel",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topology_names_round_trip() {
+        for topology in ExecutionTopology::all() {
+            assert_eq!(topology.as_str().parse::<ExecutionTopology>().unwrap(), topology);
+            assert_eq!(serde_json::to_string(&topology).unwrap(), format!("\"{}\"", topology));
+        }
+        assert_eq!(
+            "STAGE_PARALLEL".parse::<ExecutionTopology>().unwrap(),
+            ExecutionTopology::StageParallel
+        );
+        assert!("round-robin".parse::<ExecutionTopology>().is_err());
+    }
+
+    #[test]
+    fn test_pipeline_topology_lives_in_its_configuration() {
+        use crate::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+        use std::collections::HashMap;
+
+        let stage = PipelineStage::new(
+            "compress".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            0,
+        )
+        .unwrap();
+        let mut pipeline = Pipeline::new("backup".to_string(), vec![stage]).unwrap();
+        assert_eq!(pipeline.execution_topology().unwrap(), ExecutionTopology::ChunkParallel);
+
+        pipeline.set_execution_topology(ExecutionTopology::StageParallel);
+        assert_eq!(
+            pipeline.configuration().get(EXECUTION_TOPOLOGY_KEY).map(String::as_str),
+            Some("stage-parallel")
+        );
+        assert_eq!(pipeline.execution_topology().unwrap(), ExecutionTopology::StageParallel);
+
+        pipeline.update_configuration(HashMap::from([(EXECUTION_TOPOLOGY_KEY.to_string(), "bogus".to_string())]));
+        assert!(pipeline.validate().is_err());
+    }
+}
@@ -0,0 +1,246 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Feature Flags Value Object
+//!
+//! Runtime switches for subsystems that ship dark: compiled into every build,
+//! off until a deployment turns them on in the `[features]` section of its
+//! configuration file.
+//!
+//! Every flag is declared in [`FEATURE_FLAGS`] with its default and
+//! lifecycle. Resolving a deployment's settings against the catalog yields
+//! the effective flags plus warnings for settings that need attention:
+//!
+//! - **Unknown flags** are ignored, so a configuration written for a newer
+//!   version still loads; the warning catches typos
+//! - **Deprecated flags** still take effect; the warning says what replaces
+//!   them before they are removed
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::feature_flags::{FeatureFlags, DEDUP, FEATURE_FLAGS};
+//! use std::collections::BTreeMap;
+//!
+//! let configured = BTreeMap::from([("dedup".to_string(), true), ("dedupe".to_string(), true)]);
+//! let (flags, warnings) = FeatureFlags::resolve(&configured, FEATURE_FLAGS);
+//! assert!(flags.is_enabled(DEDUP));
+//! assert_eq!(warnings.len(), 1, "dedupe is not a flag");
+//! ```
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// Distributed processing across several hosts
+pub const DISTRIBUTED: &str = "distributed";
+
+/// GPU-accelerated stages
+pub const GPU: &str = "gpu";
+
+/// Chunk deduplication
+pub const DEDUP: &str = "dedup";
+
+/// Where a flag is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagLifecycle {
+    /// New and off by default; enable per deployment to try it
+    Experimental,
+
+    /// Ready for general use; the flag remains so it can be turned off
+    Stable,
+
+    /// Will be removed; [`FeatureFlagSpec::note`] says what replaces it
+    Deprecated,
+}
+
+impl Display for FlagLifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Experimental => write!(f, "experimental"),
+            Self::Stable => write!(f, "stable"),
+            Self::Deprecated => write!(f, "deprecated"),
+        }
+    }
+}
+
+/// Declaration of one flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureFlagSpec {
+    /// Key in the `[features]` section
+    pub name: &'static str,
+
+    /// What the flag turns on
+    pub description: &'static str,
+
+    /// Where the flag is in its life
+    pub lifecycle: FlagLifecycle,
+
+    /// Value when the configuration does not set it
+    pub default: bool,
+
+    /// For a deprecated flag, what to do instead
+    pub note: Option<&'static str>,
+}
+
+/// Every flag this version knows
+pub const FEATURE_FLAGS: &[FeatureFlagSpec] = &[
+    FeatureFlagSpec {
+        name: DISTRIBUTED,
+        description: "Distributed processing across several hosts",
+        lifecycle: FlagLifecycle::Experimental,
+        default: false,
+        note: None,
+    },
+    FeatureFlagSpec {
+        name: GPU,
+        description: "GPU-accelerated stages",
+        lifecycle: FlagLifecycle::Experimental,
+        default: false,
+        note: None,
+    },
+    FeatureFlagSpec {
+        name: DEDUP,
+        description: "Chunk deduplication",
+        lifecycle: FlagLifecycle::Experimental,
+        default: false,
+        note: None,
+    },
+];
+
+/// Effective value of one flag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureFlagState {
+    /// Flag name
+    pub name: String,
+
+    /// Whether the flag is on
+    pub enabled: bool,
+
+    /// Where the flag is in its life
+    pub lifecycle: FlagLifecycle,
+
+    /// What the flag turns on
+    pub description: String,
+}
+
+/// Effective flags for a deployment, in catalog order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct FeatureFlags {
+    states: Vec<FeatureFlagState>,
+}
+
+impl FeatureFlags {
+    /// Applies `configured` values over the defaults in `catalog`.
+    ///
+    /// Returns the effective flags and a warning for every unknown or
+    /// deprecated flag `configured` sets.
+    pub fn resolve(configured: &BTreeMap<String, bool>, catalog: &[FeatureFlagSpec]) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        for name in configured.keys() {
+            match catalog.iter().find(|spec| spec.name == name) {
+                None => warnings.push(format!(
+                    "Unknown feature flag '{}' ignored; known flags: {}",
+                    name,
+                    catalog.iter().map(|spec| spec.name).collect::<Vec<_>>().join(", ")
+                )),
+                Some(spec) if spec.lifecycle == FlagLifecycle::Deprecated => warnings.push(format!(
+                    "Feature flag '{}' is deprecated: {}",
+                    name,
+                    spec.note.unwrap_or("it will be removed in a future version")
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let states = catalog
+            .iter()
+            .map(|spec| FeatureFlagState {
+                name: spec.name.to_string(),
+                enabled: configured.get(spec.name).copied().unwrap_or(spec.default),
+                lifecycle: spec.lifecycle,
+                description: spec.description.to_string(),
+            })
+            .collect();
+        (Self { states }, warnings)
+    }
+
+    /// Whether `name` is on; unknown flags are off
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.states.iter().any(|state| state.name == name && state.enabled)
+    }
+
+    /// Every flag, in catalog order
+    pub fn states(&self) -> &[FeatureFlagState] {
+        &self.states
+    }
+}
+
+impl Default for FeatureFlags {
+    /// The catalog defaults
+    fn default() -> Self {
+        Self::resolve(&BTreeMap::new(), FEATURE_FLAGS).0
+    }
+}
+
+impl Display for FeatureFlags {
+    /// `name=on|off` for every flag, e.g. `distributed=off gpu=on dedup=off`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let states: Vec<String> = self
+            .states
+            .iter()
+            .map(|state| format!("{}={}", state.name, if state.enabled { "on" } else { "off" }))
+            .collect();
+        write!(f, "{}", states.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_applies_settings_and_warns_about_unknown_and_deprecated_flags() {
+        let catalog = [
+            FeatureFlagSpec {
+                name: "fast-path",
+                description: "Fast path",
+                lifecycle: FlagLifecycle::Stable,
+                default: true,
+                note: None,
+            },
+            FeatureFlagSpec {
+                name: "legacy-io",
+                description: "Old I/O",
+                lifecycle: FlagLifecycle::Deprecated,
+                default: false,
+                note: Some("use direct_io instead"),
+            },
+        ];
+        let configured = BTreeMap::from([("legacy-io".to_string(), true), ("fast-pth".to_string(), false)]);
+
+        let (flags, warnings) = FeatureFlags::resolve(&configured, &catalog);
+        assert!(flags.is_enabled("fast-path"), "an unset flag keeps its default");
+        assert!(flags.is_enabled("legacy-io"), "a deprecated flag still applies");
+        assert!(!flags.is_enabled("fast-pth"));
+        assert_eq!(flags.to_string(), "fast-path=on legacy-io=on");
+        assert_eq!(
+            warnings,
+            vec![
+                "Unknown feature flag 'fast-pth' ignored; known flags: fast-path, legacy-io".to_string(),
+                "Feature flag 'legacy-io' is deprecated: use direct_io instead".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalog_flags_ship_dark() {
+        let flags = FeatureFlags::default();
+        assert_eq!(flags.states().len(), FEATURE_FLAGS.len());
+        assert!(flags.states().iter().all(|state| !state.enabled));
+    }
+}
@@ -133,6 +133,7 @@
 //! - **Streaming**: Streaming chunk processing for large files
 //! - **Caching**: Intelligent caching of frequently accessed chunks
 
+use crate::services::constant_time::constant_time_eq_str;
 use crate::services::datetime_serde;
 use crate::{ChunkSize, PipelineError};
 use hex;
@@ -434,7 +435,7 @@ impl FileChunk {
             hasher.update(&self.data);
             let digest = hasher.finalize();
             let calculated_checksum = hex::encode(digest);
-            Ok(calculated_checksum == *stored_checksum)
+            Ok(constant_time_eq_str(&calculated_checksum, stored_checksum))
         } else {
             Err(PipelineError::InvalidChunk(
                 "No checksum available for verification".to_string(),
@@ -0,0 +1,120 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # File Mode Value Object
+//!
+//! Unix permission bits for files and directories the pipeline creates,
+//! written in octal as on the command line (`0640`, `750`). Setting a mode
+//! explicitly gives outputs the same permissions whatever the umask of the
+//! process that wrote them.
+//!
+//! Modes cover the permission, setuid, setgid and sticky bits (at most
+//! `07777`). Platforms without Unix permissions ignore them.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::FileMode;
+//!
+//! let mode: FileMode = "0640".parse().unwrap();
+//! assert_eq!(mode.bits(), 0o640);
+//! assert_eq!(mode.to_string(), "0640");
+//! assert!("0999".parse::<FileMode>().is_err());
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Largest mode: permission bits plus setuid, setgid and sticky
+const MAX_MODE: u32 = 0o7777;
+
+/// Validated Unix permission mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FileMode(u32);
+
+impl FileMode {
+    /// Creates a mode from its bits
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidConfiguration` if `bits` exceeds
+    /// `0o7777`.
+    pub fn new(bits: u32) -> Result<Self, PipelineError> {
+        if bits > MAX_MODE {
+            return Err(PipelineError::invalid_config(format!(
+                "File mode {:o} is out of range; expected at most {:04o}",
+                bits, MAX_MODE
+            )));
+        }
+        Ok(Self(bits))
+    }
+
+    /// Returns the mode bits
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for FileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04o}", self.0)
+    }
+}
+
+impl std::str::FromStr for FileMode {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.trim();
+        let digits = digits.strip_prefix("0o").unwrap_or(digits);
+        let bits = u32::from_str_radix(digits, 8).map_err(|_| {
+            PipelineError::invalid_config(format!(
+                "Invalid file mode '{}'; expected octal digits such as 0640",
+                s.trim()
+            ))
+        })?;
+        Self::new(bits)
+    }
+}
+
+impl TryFrom<String> for FileMode {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<FileMode> for String {
+    fn from(mode: FileMode) -> Self {
+        mode.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_octal_modes() {
+        assert_eq!("0640".parse::<FileMode>().unwrap().bits(), 0o640);
+        assert_eq!("750".parse::<FileMode>().unwrap().bits(), 0o750);
+        assert_eq!("0o600".parse::<FileMode>().unwrap().bits(), 0o600);
+        assert_eq!("4755".parse::<FileMode>().unwrap().to_string(), "4755");
+        for invalid in ["", "rw-r-----", "0648", "17777", "-1"] {
+            assert!(invalid.parse::<FileMode>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_serializes_as_octal_string() {
+        let mode = FileMode::new(0o640).unwrap();
+        assert_eq!(serde_json::to_string(&mode).unwrap(), "\"0640\"");
+        assert_eq!(serde_json::from_str::<FileMode>("\"0640\"").unwrap(), mode);
+        assert!(serde_json::from_str::<FileMode>("\"9\"").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Idempotency Key Value Object
+//!
+//! Caller-chosen key that makes a processing request safe to retry. Retried
+//! automation (for example a flaky CI step that resubmits) sends the same key
+//! again; once a request with that key has completed, later requests with the
+//! key return the original result instead of processing the file again.
+//!
+//! ## Rules
+//!
+//! - 1 to 128 characters of ASCII letters, digits, `-`, `_`, `.` and `:`
+//! - Keys are scoped to a namespace, so two teams may use the same key
+//! - A key is bound to the request it first completed: reusing it for a
+//!   different request is an error rather than a silent replay
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::IdempotencyKey;
+//!
+//! let key: IdempotencyKey = "ci-build-4711:step-3".parse().unwrap();
+//! assert_eq!(key.as_str(), "ci-build-4711:step-3");
+//! assert!("has space".parse::<IdempotencyKey>().is_err());
+//! ```
+
+use crate::value_objects::Namespace;
+use crate::PipelineError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+const MAX_KEY_LEN: usize = 128;
+
+/// Validated idempotency key
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Creates a key, validating it
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidConfiguration` if the key is empty, too
+    /// long, or contains characters other than ASCII letters, digits, `-`,
+    /// `_`, `.` and `:`.
+    pub fn new(key: impl Into<String>) -> Result<Self, PipelineError> {
+        let key = key.into();
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(PipelineError::invalid_config(format!(
+                "Idempotency key must be 1-{} characters, got {}",
+                MAX_KEY_LEN,
+                key.len()
+            )));
+        }
+        if !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        {
+            return Err(PipelineError::invalid_config(format!(
+                "Invalid idempotency key '{}': use ASCII letters, digits, '-', '_', '.' and ':'",
+                key
+            )));
+        }
+        Ok(Self(key))
+    }
+
+    /// Gets the key
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for IdempotencyKey {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for IdempotencyKey {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<IdempotencyKey> for String {
+    fn from(key: IdempotencyKey) -> Self {
+        key.0
+    }
+}
+
+/// The completed request an idempotency key is bound to
+///
+/// `fingerprint` identifies the request's parameters, so a reused key can be
+/// told apart from a retry; `result` is the serialized result returned to
+/// retries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    namespace: Namespace,
+    key: IdempotencyKey,
+    fingerprint: String,
+    result: String,
+    created_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    /// Creates a record of a request completed now
+    pub fn new(namespace: Namespace, key: IdempotencyKey, fingerprint: String, result: String) -> Self {
+        Self::from_parts(namespace, key, fingerprint, result, Utc::now())
+    }
+
+    /// Recreates a stored record
+    pub fn from_parts(
+        namespace: Namespace,
+        key: IdempotencyKey,
+        fingerprint: String,
+        result: String,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            namespace,
+            key,
+            fingerprint,
+            result,
+            created_at,
+        }
+    }
+
+    /// Gets the namespace the key belongs to
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the key
+    pub fn key(&self) -> &IdempotencyKey {
+        &self.key
+    }
+
+    /// Gets the fingerprint of the original request
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Gets the serialized result of the original request
+    pub fn result(&self) -> &str {
+        &self.result
+    }
+
+    /// Gets when the original request completed
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Checks whether a request with `fingerprint` is a retry of the
+    /// original request
+    pub fn matches(&self, fingerprint: &str) -> bool {
+        self.fingerprint == fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idempotency_key_validation() {
+        assert!(IdempotencyKey::new("run-42").is_ok());
+        assert!(IdempotencyKey::new("CI.build_7:step").is_ok());
+        assert!(IdempotencyKey::new("").is_err());
+        assert!(IdempotencyKey::new("a b").is_err());
+        assert!(IdempotencyKey::new("../etc").is_err());
+        assert!(IdempotencyKey::new("k".repeat(129)).is_err());
+        assert!(IdempotencyKey::new("k".repeat(128)).is_ok());
+    }
+
+    #[test]
+    fn test_record_matches_fingerprint() {
+        let record = IdempotencyRecord::new(
+            Namespace::default(),
+            IdempotencyKey::new("run-42").unwrap(),
+            "abc".to_string(),
+            "{}".to_string(),
+        );
+        assert!(record.matches("abc"));
+        assert!(!record.matches("abd"));
+    }
+}
@@ -0,0 +1,106 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Job ID Value Object
+//!
+//! Identifies a job submitted to the job queue. IDs are ULIDs, so they sort
+//! by submission time and the queue can hand out jobs oldest first by ID.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::JobId;
+//!
+//! let id = JobId::new();
+//! assert_eq!(id.to_string().parse::<JobId>().unwrap(), id);
+//! assert!("not-a-job".parse::<JobId>().is_err());
+//! ```
+
+use crate::PipelineError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use ulid::Ulid;
+
+/// Identifier of a queued job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct JobId(Ulid);
+
+impl JobId {
+    /// Generates a new ID, later than any generated before it
+    pub fn new() -> Self {
+        Self(Ulid::new())
+    }
+
+    /// Parses an ID as printed by `Display`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `id` is not a ULID.
+    pub fn parse(id: &str) -> Result<Self, PipelineError> {
+        Ulid::from_string(id.trim())
+            .map(Self)
+            .map_err(|_| PipelineError::InvalidParameter(format!("Invalid job ID '{}'", id)))
+    }
+
+    /// Gets when the ID was generated
+    pub fn datetime(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.0.timestamp_ms() as i64).unwrap_or_default()
+    }
+}
+
+impl Default for JobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for JobId {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for JobId {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<JobId> for String {
+    fn from(id: JobId) -> Self {
+        id.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_sort_by_generation_and_round_trip() {
+        let first = JobId::new();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = JobId::new();
+        assert!(first < second);
+        assert!(first.to_string() < second.to_string());
+        assert!((Utc::now() - first.datetime()).num_seconds() < 60);
+
+        let json = serde_json::to_string(&first).unwrap();
+        assert_eq!(serde_json::from_str::<JobId>(&json).unwrap(), first);
+        assert!(serde_json::from_str::<JobId>("\"bad id\"").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Job Priority Value Object
+//!
+//! The priority class a job draws shared CPU and I/O tokens at. When jobs
+//! of several classes wait for tokens at once, each released token goes to
+//! a class in proportion to its weight, so higher classes get tokens first
+//! without starving lower ones.
+//!
+//! | Priority      | Weight | Typical job                                  |
+//! |---------------|--------|----------------------------------------------|
+//! | `interactive` | 16     | A restore someone is waiting on              |
+//! | `normal`      | 4      | A `process` run; the default                 |
+//! | `batch`       | 1      | Background bulk processing                   |
+//!
+//! With an interactive and a batch job both waiting, the interactive job
+//! receives 16 of every 17 tokens released.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::JobPriority;
+//!
+//! let priority: JobPriority = "interactive".parse().unwrap();
+//! assert!(priority.weight() > JobPriority::Batch.weight());
+//! assert_eq!(JobPriority::default(), JobPriority::Normal);
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Priority class for acquiring shared resource tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobPriority {
+    /// Background work that may wait behind everything else
+    Batch,
+    /// Ordinary processing
+    #[default]
+    Normal,
+    /// Work a user is waiting on
+    Interactive,
+}
+
+impl JobPriority {
+    /// Returns every priority, highest first
+    pub fn all() -> [JobPriority; 3] {
+        [JobPriority::Interactive, JobPriority::Normal, JobPriority::Batch]
+    }
+
+    /// Share of released tokens this class receives while others also wait
+    pub fn weight(&self) -> u32 {
+        match self {
+            JobPriority::Interactive => 16,
+            JobPriority::Normal => 4,
+            JobPriority::Batch => 1,
+        }
+    }
+
+    /// Returns the name used on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobPriority::Interactive => "interactive",
+            JobPriority::Normal => "normal",
+            JobPriority::Batch => "batch",
+        }
+    }
+}
+
+impl Display for JobPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for JobPriority {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        JobPriority::all()
+            .into_iter()
+            .find(|priority| priority.as_str() == name)
+            .ok_or_else(|| {
+                PipelineError::invalid_config(format!(
+                    "Unknown job priority '{}'. Valid priorities: interactive, normal, batch",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_names_round_trip_and_weights_are_ordered() {
+        for priority in JobPriority::all() {
+            assert_eq!(priority.as_str().parse::<JobPriority>().unwrap(), priority);
+            assert_eq!(serde_json::to_string(&priority).unwrap(), format!("\"{}\"", priority));
+        }
+        assert!(JobPriority::Interactive.weight() > JobPriority::Normal.weight());
+        assert!(JobPriority::Normal.weight() > JobPriority::Batch.weight());
+        assert!(JobPriority::Interactive > JobPriority::Batch);
+        assert!("urgent".parse::<JobPriority>().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Namespace Value Object
+//!
+//! Tenant dimension that isolates pipeline sets, role assignments and usage
+//! accounting so one installation (or one serve-mode daemon) can host several
+//! teams.
+//!
+//! ## Rules
+//!
+//! - 1 to 63 characters of lowercase ASCII letters, digits, `-` and `_`
+//! - Must start with a letter or digit
+//! - `default` is used when no namespace is given, so single-tenant
+//!   installations never need to name one
+//!
+//! Pipeline IDs remain globally unique ULIDs; a pipeline belongs to exactly
+//! one namespace, and pipeline names are unique within a namespace rather
+//! than globally.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::Namespace;
+//!
+//! let team: Namespace = "data-eng".parse().unwrap();
+//! assert_eq!(team.as_str(), "data-eng");
+//! assert!(Namespace::default().is_default());
+//! assert!("Data Eng".parse::<Namespace>().is_err());
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Name of the namespace used when none is specified
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+const MAX_NAMESPACE_LEN: usize = 63;
+
+/// Validated tenant namespace
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Namespace(String);
+
+impl Namespace {
+    /// Creates a namespace, validating its name
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidConfiguration` if the name is empty, too
+    /// long, or contains characters other than lowercase letters, digits,
+    /// `-` and `_`.
+    pub fn new(name: impl Into<String>) -> Result<Self, PipelineError> {
+        let name = name.into();
+        Self::validate(&name)?;
+        Ok(Self(name))
+    }
+
+    /// Gets the namespace name
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks whether this is the default namespace
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_NAMESPACE
+    }
+
+    fn validate(name: &str) -> Result<(), PipelineError> {
+        if name.is_empty() || name.len() > MAX_NAMESPACE_LEN {
+            return Err(PipelineError::invalid_config(format!(
+                "Namespace must be 1-{} characters, got {}",
+                MAX_NAMESPACE_LEN,
+                name.len()
+            )));
+        }
+        let valid_chars = name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        let valid_start = name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit());
+        if !valid_chars || !valid_start {
+            return Err(PipelineError::invalid_config(format!(
+                "Invalid namespace '{}': use lowercase letters, digits, '-' and '_', starting with a letter or digit",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Namespace {
+    fn default() -> Self {
+        Self(DEFAULT_NAMESPACE.to_string())
+    }
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for Namespace {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Namespace {
+    type Error = PipelineError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Namespace> for String {
+    fn from(namespace: Namespace) -> Self {
+        namespace.0
+    }
+}
+
+impl AsRef<str> for Namespace {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_validation() {
+        assert!(Namespace::new("team-a").is_ok());
+        assert!(Namespace::new("team_2").is_ok());
+        assert!(Namespace::new("").is_err());
+        assert!(Namespace::new("-team").is_err());
+        assert!(Namespace::new("Team").is_err());
+        assert!(Namespace::new("team/a").is_err());
+        assert!(Namespace::new("a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_namespace_serde_validates() {
+        let namespace: Namespace = serde_json::from_str("\"ops\"").unwrap();
+        assert_eq!(namespace.as_str(), "ops");
+        assert!(serde_json::from_str::<Namespace>("\"../etc\"").is_err());
+    }
+}
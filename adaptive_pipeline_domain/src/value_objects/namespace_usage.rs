@@ -0,0 +1,68 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Namespace Usage Value Object
+//!
+//! Per-namespace, per-day totals of processing jobs and bytes, recorded after
+//! each successful job. These totals are the basis for per-namespace quotas.
+
+use crate::value_objects::Namespace;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Processing totals for one namespace on one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceUsage {
+    namespace: Namespace,
+    day: NaiveDate,
+    jobs: u64,
+    input_bytes: u64,
+    output_bytes: u64,
+}
+
+impl NamespaceUsage {
+    /// Creates usage totals
+    pub fn new(namespace: Namespace, day: NaiveDate, jobs: u64, input_bytes: u64, output_bytes: u64) -> Self {
+        Self {
+            namespace,
+            day,
+            jobs,
+            input_bytes,
+            output_bytes,
+        }
+    }
+
+    /// Creates zero usage for a namespace and day
+    pub fn empty(namespace: Namespace, day: NaiveDate) -> Self {
+        Self::new(namespace, day, 0, 0, 0)
+    }
+
+    /// Gets the namespace
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the UTC day
+    pub fn day(&self) -> NaiveDate {
+        self.day
+    }
+
+    /// Gets the number of completed jobs
+    pub fn jobs(&self) -> u64 {
+        self.jobs
+    }
+
+    /// Gets the total input bytes processed
+    pub fn input_bytes(&self) -> u64 {
+        self.input_bytes
+    }
+
+    /// Gets the total output bytes written
+    pub fn output_bytes(&self) -> u64 {
+        self.output_bytes
+    }
+}
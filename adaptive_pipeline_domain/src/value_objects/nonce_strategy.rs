@@ -0,0 +1,210 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Nonce Strategy Value Object
+//!
+//! How the encryption stage picks the nonce for each chunk. Every chunk of a
+//! file is sealed under the same key, so each chunk must get a nonce that is
+//! never used again with that key. The strategy is an encryption stage
+//! parameter stored under [`NONCE_STRATEGY_KEY`] and recorded with the
+//! encryption step in the `.adapipe` header.
+//!
+//! | Strategy         | Nonce                                          | Bits |
+//! |------------------|------------------------------------------------|------|
+//! | `random`         | Drawn at random for each chunk (the default)   | 96   |
+//! | `counter`        | Per-file random prefix, then the chunk index   | 96   |
+//! | `xchacha-random` | Drawn at random for each chunk, XChaCha20      | 192  |
+//!
+//! Random 96-bit nonces should not be used for more than 2^32 chunks under
+//! one key. Counter nonces cannot repeat within a file and only collide
+//! across files if two runs draw the same 64-bit prefix. XChaCha's 192-bit
+//! nonces are large enough that random collisions are not a concern, and
+//! require `chacha20-poly1305`. See
+//! [`chunk_encryption_spec`](super::chunk_encryption_spec) for how each is
+//! laid out in a chunk record.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::NonceStrategy;
+//!
+//! let strategy: NonceStrategy = "counter".parse().unwrap();
+//! let nonce = NonceStrategy::counter_nonce([0xab; 8], 3).unwrap();
+//! assert_eq!(&nonce[8..], &[0, 0, 0, 3]);
+//! assert_eq!(strategy.nonce_length(), 12);
+//! ```
+
+use crate::value_objects::chunk_encryption_spec::CHUNK_NONCE_LENGTH;
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Encryption stage parameter holding the nonce strategy
+pub const NONCE_STRATEGY_KEY: &str = "nonce_strategy";
+
+/// XChaCha20-Poly1305 nonce length in bytes
+pub const EXTENDED_NONCE_LENGTH: usize = 24;
+
+/// Bytes of a counter nonce taken by the per-file prefix
+pub const COUNTER_NONCE_PREFIX_LENGTH: usize = 8;
+
+/// How the encryption stage derives each chunk's nonce
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NonceStrategy {
+    /// 96 random bits per chunk
+    #[default]
+    Random,
+    /// 64-bit random prefix per file followed by the 32-bit chunk index
+    Counter,
+    /// 192 random bits per chunk, sealed with XChaCha20-Poly1305
+    #[serde(rename = "xchacha-random")]
+    XChaChaRandom,
+}
+
+impl NonceStrategy {
+    /// Returns every nonce strategy, default first
+    pub fn all() -> [NonceStrategy; 3] {
+        [
+            NonceStrategy::Random,
+            NonceStrategy::Counter,
+            NonceStrategy::XChaChaRandom,
+        ]
+    }
+
+    /// Returns the name used in stage parameters and file headers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NonceStrategy::Random => "random",
+            NonceStrategy::Counter => "counter",
+            NonceStrategy::XChaChaRandom => "xchacha-random",
+        }
+    }
+
+    /// Returns true for random 96-bit nonces, the strategy of files without
+    /// a recorded one
+    pub fn is_default(&self) -> bool {
+        *self == NonceStrategy::default()
+    }
+
+    /// Returns the nonce length in bytes
+    pub fn nonce_length(&self) -> usize {
+        match self {
+            NonceStrategy::Random | NonceStrategy::Counter => CHUNK_NONCE_LENGTH,
+            NonceStrategy::XChaChaRandom => EXTENDED_NONCE_LENGTH,
+        }
+    }
+
+    /// Builds the counter nonce for the chunk at `chunk_index`: `prefix`
+    /// followed by the index as a 32-bit big-endian integer
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::EncryptionError` if the index does not fit in 32
+    /// bits, since the counter would wrap and repeat a nonce.
+    pub fn counter_nonce(
+        prefix: [u8; COUNTER_NONCE_PREFIX_LENGTH],
+        chunk_index: u64,
+    ) -> Result<[u8; CHUNK_NONCE_LENGTH], PipelineError> {
+        let counter = u32::try_from(chunk_index).map_err(|_| {
+            PipelineError::EncryptionError(format!("Chunk index {} exceeds the counter nonce space", chunk_index))
+        })?;
+        let mut nonce = [0u8; CHUNK_NONCE_LENGTH];
+        nonce[..COUNTER_NONCE_PREFIX_LENGTH].copy_from_slice(&prefix);
+        nonce[COUNTER_NONCE_PREFIX_LENGTH..].copy_from_slice(&counter.to_be_bytes());
+        Ok(nonce)
+    }
+}
+
+impl Display for NonceStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for NonceStrategy {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        NonceStrategy::all()
+            .into_iter()
+            .find(|strategy| strategy.as_str() == name)
+            .ok_or_else(|| {
+                PipelineError::InvalidParameter(format!(
+                    "Unknown nonce strategy '{}'. Valid strategies: random, counter, xchacha-random",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_strategy_names_round_trip() {
+        for strategy in NonceStrategy::all() {
+            assert_eq!(strategy.as_str().parse::<NonceStrategy>().unwrap(), strategy);
+            assert_eq!(serde_json::to_string(&strategy).unwrap(), format!("\"{}\"", strategy));
+        }
+        assert_eq!(
+            "XChaCha_Random".parse::<NonceStrategy>().unwrap(),
+            NonceStrategy::XChaChaRandom
+        );
+        assert!("sequential".parse::<NonceStrategy>().is_err());
+        assert_eq!(NonceStrategy::XChaChaRandom.nonce_length(), 24);
+    }
+
+    #[test]
+    fn test_counter_nonces_are_unique_per_chunk_and_bounded() {
+        let prefix = [7u8; COUNTER_NONCE_PREFIX_LENGTH];
+        let first = NonceStrategy::counter_nonce(prefix, 0).unwrap();
+        let second = NonceStrategy::counter_nonce(prefix, 1).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(&first[..COUNTER_NONCE_PREFIX_LENGTH], &prefix);
+
+        let last = NonceStrategy::counter_nonce(prefix, u32::MAX as u64).unwrap();
+        assert_eq!(&last[COUNTER_NONCE_PREFIX_LENGTH..], &[0xff; 4]);
+        assert!(NonceStrategy::counter_nonce(prefix, u32::MAX as u64 + 1).is_err());
+    }
+
+    #[test]
+    fn test_strategy_is_read_from_stage_parameters_and_recorded_in_the_header() {
+        use crate::services::{EncryptionConfig, FromParameters};
+        use crate::value_objects::FileHeader;
+        use std::collections::HashMap;
+
+        let mut params = HashMap::from([
+            ("algorithm".to_string(), "chacha20-poly1305".to_string()),
+            (NONCE_STRATEGY_KEY.to_string(), "xchacha-random".to_string()),
+        ]);
+        let config = EncryptionConfig::from_parameters(&params).unwrap();
+        assert_eq!(config.nonce_strategy, NonceStrategy::XChaChaRandom);
+        assert_eq!(config.nonce_size, 24);
+
+        params.insert("algorithm".to_string(), "aes-256-gcm".to_string());
+        assert!(EncryptionConfig::from_parameters(&params).is_err());
+        params.remove(NONCE_STRATEGY_KEY);
+        assert_eq!(
+            EncryptionConfig::from_parameters(&params).unwrap().nonce_strategy,
+            NonceStrategy::Random
+        );
+
+        let header = FileHeader::new("data.bin".to_string(), 1024, "0".repeat(64))
+            .add_encryption_step("chacha20-poly1305", "argon2", 32, NonceStrategy::XChaChaRandom)
+            .add_encryption_step("aes-256-gcm", "argon2", 32, NonceStrategy::Random);
+        let recorded: Vec<Option<&String>> = header
+            .processing_steps
+            .iter()
+            .map(|step| step.parameters.get(NONCE_STRATEGY_KEY))
+            .collect();
+        assert_eq!(recorded, [Some(&"xchacha-random".to_string()), None]);
+        assert_eq!(header.processing_steps[0].parameters["nonce_size"], "24");
+    }
+}
@@ -0,0 +1,306 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Overwrite Policy Value Object
+//!
+//! What to do when an output file (a processed `.adapipe` file or a restored
+//! file) already exists at its target path.
+//!
+//! | Policy      | Existing target                                         |
+//! |-------------|---------------------------------------------------------|
+//! | `fail`      | Error; the default                                      |
+//! | `overwrite` | Replaced                                                |
+//! | `skip`      | Left alone and the output is not written                |
+//! | `rename`    | Left alone; the output goes to `name-1.ext`, `name-2.ext`, ... |
+//! | `if-newer`  | Replaced if the source is newer than it, otherwise skipped |
+//!
+//! The source is the file the output is produced from: the input file when
+//! processing, the archive when restoring.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{OutputResolution, OverwritePolicy};
+//! # let dir = tempfile::TempDir::new().unwrap();
+//! # let source = dir.path().join("data.txt");
+//! # std::fs::write(&source, b"data").unwrap();
+//! let target = dir.path().join("data.txt.adapipe");
+//! std::fs::write(&target, b"existing").unwrap();
+//!
+//! let policy: OverwritePolicy = "rename".parse().unwrap();
+//! let resolution = policy.resolve(&target, &source).unwrap();
+//! assert_eq!(resolution, OutputResolution::Renamed(dir.path().join("data-1.txt.adapipe")));
+//! ```
+
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+/// Highest suffix tried when looking for a free name under `rename`
+const MAX_RENAME_SUFFIX: u32 = 9999;
+
+/// Policy applied when an output's target path already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverwritePolicy {
+    /// Refuse to touch an existing target
+    #[default]
+    Fail,
+    /// Replace an existing target
+    Overwrite,
+    /// Keep an existing target and don't write the output
+    Skip,
+    /// Keep an existing target and write the output under a free
+    /// suffixed name next to it
+    Rename,
+    /// Replace an existing target only if the source was modified after it
+    IfNewer,
+}
+
+/// Where an output is written once its policy has been applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputResolution {
+    /// Nothing exists at the target; write it
+    New(PathBuf),
+    /// Write the target, replacing the existing file
+    Replace(PathBuf),
+    /// Write this free sibling of the existing target instead
+    Renamed(PathBuf),
+    /// Don't write anything
+    Skip,
+}
+
+impl OverwritePolicy {
+    /// Returns every policy, default first
+    pub fn all() -> [OverwritePolicy; 5] {
+        [
+            OverwritePolicy::Fail,
+            OverwritePolicy::Overwrite,
+            OverwritePolicy::Skip,
+            OverwritePolicy::Rename,
+            OverwritePolicy::IfNewer,
+        ]
+    }
+
+    /// Returns the kebab-case name used on the command line and in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverwritePolicy::Fail => "fail",
+            OverwritePolicy::Overwrite => "overwrite",
+            OverwritePolicy::Skip => "skip",
+            OverwritePolicy::Rename => "rename",
+            OverwritePolicy::IfNewer => "if-newer",
+        }
+    }
+
+    /// Checks whether the policy may replace an existing target
+    pub fn may_replace(&self) -> bool {
+        matches!(self, OverwritePolicy::Overwrite | OverwritePolicy::IfNewer)
+    }
+
+    /// Applies the policy to an output of `source` bound for `target`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the target is a directory, or exists
+    /// under `fail`, or `rename` finds no free name; `IoError` if `if-newer`
+    /// cannot read either file's modification time.
+    pub fn resolve(&self, target: &Path, source: &Path) -> Result<OutputResolution, PipelineError> {
+        let Ok(existing) = std::fs::metadata(target) else {
+            return Ok(OutputResolution::New(target.to_path_buf()));
+        };
+        if existing.is_dir() {
+            return Err(PipelineError::validation_error(format!(
+                "Target path is a directory: {}",
+                target.display()
+            )));
+        }
+
+        match self {
+            OverwritePolicy::Fail => Err(PipelineError::validation_error(format!(
+                "Target file already exists: {} (use --if-exists overwrite, skip, rename or if-newer)",
+                target.display()
+            ))),
+            OverwritePolicy::Overwrite => Ok(OutputResolution::Replace(target.to_path_buf())),
+            OverwritePolicy::Skip => Ok(OutputResolution::Skip),
+            OverwritePolicy::Rename => free_sibling(target).map(OutputResolution::Renamed),
+            OverwritePolicy::IfNewer => {
+                let modified = |path: &Path, metadata: std::io::Result<std::fs::Metadata>| {
+                    metadata.and_then(|m| m.modified()).map_err(|e| {
+                        PipelineError::io_error(format!("Cannot read modification time of {}: {}", path.display(), e))
+                    })
+                };
+                let source_modified = modified(source, std::fs::metadata(source))?;
+                let target_modified = modified(target, Ok(existing))?;
+                if source_modified > target_modified {
+                    Ok(OutputResolution::Replace(target.to_path_buf()))
+                } else {
+                    Ok(OutputResolution::Skip)
+                }
+            }
+        }
+    }
+}
+
+impl OutputResolution {
+    /// Returns the path to write, or `None` when the output is skipped
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            OutputResolution::New(path) | OutputResolution::Replace(path) | OutputResolution::Renamed(path) => {
+                Some(path)
+            }
+            OutputResolution::Skip => None,
+        }
+    }
+}
+
+impl Display for OverwritePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for OverwritePolicy {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        OverwritePolicy::all()
+            .into_iter()
+            .find(|policy| policy.as_str() == name)
+            .ok_or_else(|| {
+                PipelineError::invalid_config(format!(
+                    "Unknown overwrite policy '{}'. Valid policies: fail, overwrite, skip, rename, if-newer",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+/// First of `name-1.ext`, `name-2.ext`, ... next to `target` that does not
+/// exist; the suffix goes before the first extension so `data.txt.adapipe`
+/// becomes `data-1.txt.adapipe`
+fn free_sibling(target: &Path) -> Result<PathBuf, PipelineError> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // A leading dot marks a hidden file, not an extension
+    let split = file_name
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '.')
+        .map_or(file_name.len(), |(index, _)| index);
+    let (stem, extensions) = file_name.split_at(split);
+
+    (1..=MAX_RENAME_SUFFIX)
+        .map(|n| target.with_file_name(format!("{}-{}{}", stem, n, extensions)))
+        .find(|candidate| !candidate.exists())
+        .ok_or_else(|| {
+            PipelineError::validation_error(format!(
+                "No free name for {} after {} attempts",
+                target.display(),
+                MAX_RENAME_SUFFIX
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_policy_names_round_trip() {
+        for policy in OverwritePolicy::all() {
+            assert_eq!(policy.as_str().parse::<OverwritePolicy>().unwrap(), policy);
+            assert_eq!(serde_json::to_string(&policy).unwrap(), format!("\"{}\"", policy));
+        }
+        assert_eq!("IF_NEWER".parse::<OverwritePolicy>().unwrap(), OverwritePolicy::IfNewer);
+        assert!("clobber".parse::<OverwritePolicy>().is_err());
+        assert_eq!(OverwritePolicy::default(), OverwritePolicy::Fail);
+    }
+
+    #[test]
+    fn test_resolve_applies_each_policy_to_an_existing_target() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("data.txt");
+        let target = dir.path().join("data.txt.adapipe");
+        std::fs::write(&source, b"source").unwrap();
+
+        for policy in OverwritePolicy::all() {
+            assert_eq!(
+                policy.resolve(&target, &source).unwrap(),
+                OutputResolution::New(target.clone())
+            );
+        }
+
+        std::fs::write(&target, b"existing").unwrap();
+        assert!(matches!(
+            OverwritePolicy::Fail.resolve(&target, &source),
+            Err(PipelineError::ValidationError(_))
+        ));
+        assert_eq!(
+            OverwritePolicy::Overwrite.resolve(&target, &source).unwrap(),
+            OutputResolution::Replace(target.clone())
+        );
+        assert_eq!(
+            OverwritePolicy::Skip.resolve(&target, &source).unwrap(),
+            OutputResolution::Skip
+        );
+
+        std::fs::write(dir.path().join("data-1.txt.adapipe"), b"taken").unwrap();
+        assert_eq!(
+            OverwritePolicy::Rename.resolve(&target, &source).unwrap(),
+            OutputResolution::Renamed(dir.path().join("data-2.txt.adapipe"))
+        );
+        assert!(matches!(
+            OverwritePolicy::Overwrite.resolve(dir.path(), &source),
+            Err(PipelineError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_if_newer_compares_modification_times() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("data.txt");
+        let target = dir.path().join("data.txt.adapipe");
+        std::fs::write(&source, b"source").unwrap();
+        std::fs::write(&target, b"existing").unwrap();
+
+        let set_modified = |path: &Path, secs: u64| {
+            let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        };
+        set_modified(&source, 2_000_000_000);
+        set_modified(&target, 1_000_000_000);
+        assert_eq!(
+            OverwritePolicy::IfNewer.resolve(&target, &source).unwrap(),
+            OutputResolution::Replace(target.clone())
+        );
+
+        set_modified(&target, 2_000_000_000);
+        assert_eq!(
+            OverwritePolicy::IfNewer.resolve(&target, &source).unwrap(),
+            OutputResolution::Skip
+        );
+    }
+
+    #[test]
+    fn test_rename_keeps_hidden_files_hidden() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join(".profile");
+        std::fs::write(&target, b"existing").unwrap();
+        assert_eq!(free_sibling(&target).unwrap(), dir.path().join(".profile-1"));
+    }
+}
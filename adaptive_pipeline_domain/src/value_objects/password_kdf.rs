@@ -0,0 +1,349 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Password KDF Value Object
+//!
+//! How the encryption key of a password-protected file is derived from its
+//! passphrase. Processing draws a fresh salt, derives the key with Argon2id
+//! and records the salt and cost with the encryption step in the `.adapipe`
+//! header; restoring reads them back and derives the same key from the same
+//! passphrase. The key itself is never written anywhere.
+//!
+//! | Parameter         | Meaning                         | Default  |
+//! |-------------------|---------------------------------|----------|
+//! | `kdf`             | Always `argon2id`               |          |
+//! | `kdf_salt`        | Random salt (hex)               | 16 bytes |
+//! | `kdf_memory_kib`  | Memory cost in KiB              | 65536    |
+//! | `kdf_iterations`  | Number of passes                | 3        |
+//! | `kdf_parallelism` | Lanes                           | 1        |
+//!
+//! The three cost parameters may also be set on an encryption stage
+//! ([`KdfCost`]); a stage that sets none of them uses a cost calibrated on
+//! the host. Costs are bounded so a crafted file cannot make restore
+//! allocate unbounded memory or spin indefinitely.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::PasswordKdf;
+//! use std::collections::HashMap;
+//!
+//! let kdf = PasswordKdf::new(vec![7; 16]).unwrap();
+//! let mut parameters = HashMap::new();
+//! kdf.write_parameters(&mut parameters);
+//! assert_eq!(PasswordKdf::from_parameters(&parameters).unwrap(), Some(kdf));
+//! ```
+
+use crate::PipelineError;
+use std::collections::HashMap;
+
+/// Encryption step parameter naming the password KDF
+pub const KDF_KEY: &str = "kdf";
+
+/// The only KDF recorded in [`KDF_KEY`]
+pub const ARGON2ID: &str = "argon2id";
+
+/// Encryption step parameter holding the KDF salt as hex
+pub const KDF_SALT_KEY: &str = "kdf_salt";
+
+/// Encryption step parameter holding the memory cost in KiB
+pub const KDF_MEMORY_KEY: &str = "kdf_memory_kib";
+
+/// Encryption step parameter holding the number of passes
+pub const KDF_ITERATIONS_KEY: &str = "kdf_iterations";
+
+/// Encryption step parameter holding the number of lanes
+pub const KDF_PARALLELISM_KEY: &str = "kdf_parallelism";
+
+/// Length of the salt drawn for each processed file
+pub const PASSWORD_SALT_LENGTH: usize = 16;
+
+/// Argon2id cost: how much memory and time deriving one key takes
+///
+/// An encryption stage may set the cost through the `kdf_memory_kib`,
+/// `kdf_iterations` and `kdf_parallelism` parameters; any it leaves out take
+/// their default. A stage that sets none of them gets a cost calibrated on
+/// the host at startup instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfCost {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl KdfCost {
+    /// Default memory cost (64 MiB)
+    pub const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+    /// Default number of passes
+    pub const DEFAULT_ITERATIONS: u32 = 3;
+    /// Default number of lanes
+    pub const DEFAULT_PARALLELISM: u32 = 1;
+
+    /// Largest memory cost accepted (1 GiB)
+    pub const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+    /// Largest number of passes accepted
+    pub const MAX_ITERATIONS: u32 = 64;
+    /// Largest number of lanes accepted
+    pub const MAX_PARALLELISM: u32 = 16;
+
+    /// Memory × passes below which a cost counts as weak. This is the
+    /// cheapest of OWASP's recommended Argon2id settings (7 MiB, 5 passes).
+    pub const WEAK_BELOW_KIB_PASSES: u64 = 7 * 1024 * 5;
+
+    /// Creates a cost
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` if a cost is outside the accepted
+    /// bounds. Argon2 also needs at least 8 KiB of memory per lane.
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, PipelineError> {
+        if !(1..=Self::MAX_PARALLELISM).contains(&parallelism) {
+            return Err(PipelineError::InvalidParameter(format!(
+                "KDF parallelism must be between 1 and {}, got {}",
+                Self::MAX_PARALLELISM,
+                parallelism
+            )));
+        }
+        if !(8 * parallelism..=Self::MAX_MEMORY_KIB).contains(&memory_kib) {
+            return Err(PipelineError::InvalidParameter(format!(
+                "KDF memory must be between {} and {} KiB, got {}",
+                8 * parallelism,
+                Self::MAX_MEMORY_KIB,
+                memory_kib
+            )));
+        }
+        if !(1..=Self::MAX_ITERATIONS).contains(&iterations) {
+            return Err(PipelineError::InvalidParameter(format!(
+                "KDF iterations must be between 1 and {}, got {}",
+                Self::MAX_ITERATIONS,
+                iterations
+            )));
+        }
+        Ok(Self {
+            memory_kib,
+            iterations,
+            parallelism,
+        })
+    }
+
+    /// The cost an encryption stage sets, or `None` if it sets no cost
+    /// parameter. Parameters the stage leaves out take their default.
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` if a parameter is malformed or out
+    /// of bounds.
+    pub fn from_parameters(parameters: &HashMap<String, String>) -> Result<Option<Self>, PipelineError> {
+        if ![KDF_MEMORY_KEY, KDF_ITERATIONS_KEY, KDF_PARALLELISM_KEY]
+            .iter()
+            .any(|key| parameters.contains_key(*key))
+        {
+            return Ok(None);
+        }
+        let number = |key: &str, default: u32| {
+            parameters.get(key).map_or(Ok(default), |value| {
+                value
+                    .parse::<u32>()
+                    .map_err(|e| PipelineError::InvalidParameter(format!("Invalid '{}': {}", key, e)))
+            })
+        };
+        Self::new(
+            number(KDF_MEMORY_KEY, Self::DEFAULT_MEMORY_KIB)?,
+            number(KDF_ITERATIONS_KEY, Self::DEFAULT_ITERATIONS)?,
+            number(KDF_PARALLELISM_KEY, Self::DEFAULT_PARALLELISM)?,
+        )
+        .map(Some)
+    }
+
+    /// Records the cost in an encryption step's parameters
+    pub fn write_parameters(&self, parameters: &mut HashMap<String, String>) {
+        parameters.insert(KDF_MEMORY_KEY.to_string(), self.memory_kib.to_string());
+        parameters.insert(KDF_ITERATIONS_KEY.to_string(), self.iterations.to_string());
+        parameters.insert(KDF_PARALLELISM_KEY.to_string(), self.parallelism.to_string());
+    }
+
+    /// Whether the cost is below [`Self::WEAK_BELOW_KIB_PASSES`], cheap
+    /// enough that guessing passphrases offline is practical
+    pub fn is_weak(&self) -> bool {
+        u64::from(self.memory_kib) * u64::from(self.iterations) < Self::WEAK_BELOW_KIB_PASSES
+    }
+
+    pub fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+}
+
+impl Default for KdfCost {
+    fn default() -> Self {
+        Self {
+            memory_kib: Self::DEFAULT_MEMORY_KIB,
+            iterations: Self::DEFAULT_ITERATIONS,
+            parallelism: Self::DEFAULT_PARALLELISM,
+        }
+    }
+}
+
+impl std::fmt::Display for KdfCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} KiB, {} passes, {} lanes",
+            self.memory_kib, self.iterations, self.parallelism
+        )
+    }
+}
+
+/// Argon2id salt and cost a file's encryption key was derived with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordKdf {
+    salt: Vec<u8>,
+    cost: KdfCost,
+}
+
+impl PasswordKdf {
+    /// Shortest salt accepted
+    pub const MIN_SALT_LENGTH: usize = 8;
+
+    /// Default cost with `salt`
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` if the salt is shorter than
+    /// [`Self::MIN_SALT_LENGTH`].
+    pub fn new(salt: Vec<u8>) -> Result<Self, PipelineError> {
+        Self::with_cost(salt, KdfCost::default())
+    }
+
+    /// Explicit cost with `salt`
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` if the salt is shorter than
+    /// [`Self::MIN_SALT_LENGTH`].
+    pub fn with_cost(salt: Vec<u8>, cost: KdfCost) -> Result<Self, PipelineError> {
+        if salt.len() < Self::MIN_SALT_LENGTH {
+            return Err(PipelineError::InvalidParameter(format!(
+                "KDF salt must be at least {} bytes, got {}",
+                Self::MIN_SALT_LENGTH,
+                salt.len()
+            )));
+        }
+        Ok(Self { salt, cost })
+    }
+
+    /// The KDF recorded in an encryption step's parameters, or `None` if the
+    /// step's key was not derived from a password
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` if the KDF is not Argon2id or a
+    /// parameter is missing, malformed or out of bounds.
+    pub fn from_parameters(parameters: &HashMap<String, String>) -> Result<Option<Self>, PipelineError> {
+        let Some(kdf) = parameters.get(KDF_KEY) else {
+            return Ok(None);
+        };
+        if kdf != ARGON2ID {
+            return Err(PipelineError::InvalidParameter(format!(
+                "Unsupported password KDF '{}'",
+                kdf
+            )));
+        }
+        let get = |key: &str| {
+            parameters
+                .get(key)
+                .ok_or_else(|| PipelineError::InvalidParameter(format!("Password KDF is missing '{}'", key)))
+        };
+        let salt = hex::decode(get(KDF_SALT_KEY)?)
+            .map_err(|e| PipelineError::InvalidParameter(format!("Invalid '{}': {}", KDF_SALT_KEY, e)))?;
+        // A header records the whole cost; unlike a stage, it has no defaults
+        for key in [KDF_MEMORY_KEY, KDF_ITERATIONS_KEY, KDF_PARALLELISM_KEY] {
+            get(key)?;
+        }
+        let cost = KdfCost::from_parameters(parameters)?.unwrap_or_default();
+        Self::with_cost(salt, cost).map(Some)
+    }
+
+    /// Records the KDF in an encryption step's parameters
+    pub fn write_parameters(&self, parameters: &mut HashMap<String, String>) {
+        parameters.insert(KDF_KEY.to_string(), ARGON2ID.to_string());
+        parameters.insert(KDF_SALT_KEY.to_string(), hex::encode(&self.salt));
+        self.cost.write_parameters(parameters);
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    pub fn cost(&self) -> KdfCost {
+        self.cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameters_round_trip() {
+        let cost = KdfCost::new(256, 2, 2).unwrap();
+        let kdf = PasswordKdf::with_cost(vec![1; PASSWORD_SALT_LENGTH], cost).unwrap();
+        let mut parameters = HashMap::new();
+        kdf.write_parameters(&mut parameters);
+
+        assert_eq!(parameters[KDF_KEY], ARGON2ID);
+        assert_eq!(PasswordKdf::from_parameters(&parameters).unwrap(), Some(kdf));
+        assert_eq!(PasswordKdf::from_parameters(&HashMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rejects_unbounded_or_malformed_parameters() {
+        assert!(PasswordKdf::new(vec![1; 4]).is_err());
+        assert!(KdfCost::new(KdfCost::MAX_MEMORY_KIB + 1, 3, 1).is_err());
+        assert!(KdfCost::new(16, 3, 4).is_err());
+        assert!(KdfCost::new(1024, 0, 1).is_err());
+        assert!(KdfCost::new(1024, 3, 0).is_err());
+
+        let mut parameters = HashMap::new();
+        PasswordKdf::new(vec![1; 16]).unwrap().write_parameters(&mut parameters);
+        let with = |key: &str, value: &str| {
+            let mut changed = parameters.clone();
+            changed.insert(key.to_string(), value.to_string());
+            PasswordKdf::from_parameters(&changed)
+        };
+        assert!(with(KDF_KEY, "scrypt").is_err());
+        assert!(with(KDF_SALT_KEY, "not hex").is_err());
+        assert!(with(KDF_ITERATIONS_KEY, "-1").is_err());
+        assert!(with(KDF_MEMORY_KEY, "4294967295").is_err());
+
+        parameters.remove(KDF_PARALLELISM_KEY);
+        assert!(PasswordKdf::from_parameters(&parameters).is_err());
+    }
+
+    #[test]
+    fn test_stage_cost_fills_defaults_and_flags_weak_settings() {
+        assert_eq!(KdfCost::from_parameters(&HashMap::new()).unwrap(), None);
+
+        let parameters = HashMap::from([(KDF_ITERATIONS_KEY.to_string(), "4".to_string())]);
+        let cost = KdfCost::from_parameters(&parameters).unwrap().unwrap();
+        assert_eq!(cost.memory_kib(), KdfCost::DEFAULT_MEMORY_KIB);
+        assert_eq!(cost.iterations(), 4);
+        assert_eq!(cost.parallelism(), KdfCost::DEFAULT_PARALLELISM);
+
+        assert!(!KdfCost::default().is_weak());
+        assert!(!KdfCost::new(7 * 1024, 5, 1).unwrap().is_weak());
+        assert!(KdfCost::new(7 * 1024, 4, 1).unwrap().is_weak());
+        assert!(KdfCost::new(64, 1, 1).unwrap().is_weak());
+    }
+}
@@ -0,0 +1,149 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Path Filter Value Object
+//!
+//! Which files under a directory `process --recursive` selects, as include
+//! and exclude globs matched against each file's path relative to the
+//! directory.
+//!
+//! - A file is selected if it matches any include glob (or there are none)
+//!   and no exclude glob
+//! - `*` and `?` match across `/`, so `*.log` selects logs at any depth;
+//!   `**` is also accepted
+//! - A glob is also tried against the file name alone, so `app.log` selects
+//!   `logs/app.log`
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::PathFilter;
+//! use std::path::Path;
+//!
+//! let filter = PathFilter::new(vec!["*.log".into()], vec!["*.tmp.log".into()]).unwrap();
+//! assert!(filter.matches(Path::new("service/app.log")));
+//! assert!(!filter.matches(Path::new("service/app.tmp.log")));
+//! assert!(!filter.matches(Path::new("service/app.txt")));
+//! ```
+
+use crate::PipelineError;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// Include and exclude globs selecting files under a directory
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    include_set: GlobSet,
+    exclude_set: GlobSet,
+}
+
+impl PathFilter {
+    /// Creates a filter from include and exclude globs
+    ///
+    /// # Errors
+    ///
+    /// `PipelineError::InvalidParameter` naming the first glob that doesn't
+    /// parse.
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Result<Self, PipelineError> {
+        Ok(Self {
+            include_set: Self::compile(&include)?,
+            exclude_set: Self::compile(&exclude)?,
+            include,
+            exclude,
+        })
+    }
+
+    fn compile(patterns: &[String]) -> Result<GlobSet, PipelineError> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern)
+                .map_err(|e| PipelineError::InvalidParameter(format!("Invalid glob '{}': {}", pattern, e)))?;
+            builder.add(glob);
+        }
+        builder
+            .build()
+            .map_err(|e| PipelineError::InvalidParameter(format!("Invalid globs: {}", e)))
+    }
+
+    /// Whether the file at `relative`, a path relative to the directory
+    /// being walked, is selected
+    pub fn matches(&self, relative: &Path) -> bool {
+        let hit = |set: &GlobSet| set.is_match(relative) || relative.file_name().is_some_and(|name| set.is_match(name));
+        (self.include.is_empty() || hit(&self.include_set)) && !hit(&self.exclude_set)
+    }
+
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+}
+
+impl Default for PathFilter {
+    /// Selects every file
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_set: GlobSet::empty(),
+            exclude_set: GlobSet::empty(),
+        }
+    }
+}
+
+impl PartialEq for PathFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.include == other.include && self.exclude == other.exclude
+    }
+}
+
+impl Eq for PathFilter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selects_everything_without_globs() {
+        let filter = PathFilter::default();
+        assert!(filter.matches(Path::new("a/b/c.bin")));
+        assert_eq!(filter, PathFilter::new(Vec::new(), Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let filter = PathFilter::new(
+            vec!["*.log".to_string(), "reports/**".to_string()],
+            vec!["*.tmp".to_string(), "reports/drafts/**".to_string()],
+        )
+        .unwrap();
+
+        assert!(filter.matches(Path::new("app.log")));
+        assert!(filter.matches(Path::new("nested/deeper/app.log")));
+        assert!(filter.matches(Path::new("reports/q3.pdf")));
+        assert!(!filter.matches(Path::new("reports/drafts/q4.pdf")));
+        assert!(!filter.matches(Path::new("reports/q3.tmp")));
+        assert!(!filter.matches(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_globs_also_match_the_file_name() {
+        let filter = PathFilter::new(Vec::new(), vec!["Thumbs.db".to_string()]).unwrap();
+        assert!(!filter.matches(Path::new("photos/2024/Thumbs.db")));
+        assert!(filter.matches(Path::new("photos/2024/beach.jpg")));
+    }
+
+    #[test]
+    fn test_rejects_malformed_globs() {
+        let err = PathFilter::new(vec!["logs/[".to_string()], Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("logs/["), "{}", err);
+    }
+}
@@ -0,0 +1,475 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Pipeline Definition Value Object
+//!
+//! A pipeline as a portable document, for `pipeline export` and
+//! `pipeline import`. A definition holds what a pipeline is made of and
+//! none of what is stored about it (ID, namespace, metrics, timestamps), so
+//! the same file can be reviewed, versioned and imported into any database
+//! or namespace.
+//!
+//! The mandatory `input_checksum` and `output_checksum` stages are not
+//! listed; every pipeline gets them, hashing with `checksum`. Settings with
+//! their own fields (chunk size, workers, topology, checksum, security
+//! policy) are kept out of `configuration`, which carries any other keys
+//! through unchanged.
+//!
+//! Which serialization format a definition is written in is up to the
+//! caller; [`DefinitionFormat`] names the supported ones. In YAML:
+//!
+//! ```yaml
+//! name: secure-backup
+//! chunk_size: 4194304
+//! workers: 8
+//! topology: stage-parallel
+//! security_level: confidential
+//! required_permissions:
+//! - decrypt
+//! stages:
+//! - name: compression
+//!   type: compression
+//!   algorithm: zstd
+//!   parameters:
+//!     level: '6'
+//! - name: encryption
+//!   type: encryption
+//!   algorithm: aes256gcm
+//! ```
+//!
+//! ## Validation
+//!
+//! [`PipelineDefinition::to_pipeline`] builds the pipeline and checks it
+//! against the same invariants as a created one: every stage's algorithm
+//! belongs to its stage type and is permitted in this build, settings parse,
+//! and the stage sequence is valid. It then checks that exporting the
+//! pipeline again yields a definition that builds the same pipeline, so
+//! nothing in the document is silently dropped or reinterpreted.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{Pipeline, PipelineStage, SecurityLevel, StageConfiguration, StageType};
+use crate::value_objects::{
+    Algorithm, ChecksumAlgorithm, ChunkSize, ExecutionTopology, SecurityPolicy, WorkerCount, CHECKSUM_ALGORITHM_KEY,
+    CHUNK_SIZE_KEY, EXECUTION_TOPOLOGY_KEY, MINIMUM_SECURITY_LEVEL_KEY, REQUIRED_PERMISSIONS_KEY, WORKER_COUNT_KEY,
+};
+use crate::PipelineError;
+
+/// Pipeline configuration keys that have their own definition field
+const DEDICATED_KEYS: [&str; 6] = [
+    CHUNK_SIZE_KEY,
+    WORKER_COUNT_KEY,
+    EXECUTION_TOPOLOGY_KEY,
+    CHECKSUM_ALGORITHM_KEY,
+    MINIMUM_SECURITY_LEVEL_KEY,
+    REQUIRED_PERMISSIONS_KEY,
+];
+
+/// Names of the checksum stages every pipeline gets
+const AUTOMATIC_STAGES: [&str; 2] = ["input_checksum", "output_checksum"];
+
+/// Stage parameter create records the algorithm under
+const ALGORITHM_PARAMETER: &str = "algorithm";
+
+/// File format a [`PipelineDefinition`] is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionFormat {
+    /// TOML
+    Toml,
+    /// YAML
+    Yaml,
+    /// JSON
+    Json,
+}
+
+impl DefinitionFormat {
+    /// Picks the format from a file extension (`.toml`, `.yaml`, `.yml` or
+    /// `.json`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.parse().ok())
+    }
+
+    /// Gets the format's lowercase name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DefinitionFormat::Toml => "toml",
+            DefinitionFormat::Yaml => "yaml",
+            DefinitionFormat::Json => "json",
+        }
+    }
+}
+
+impl Display for DefinitionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DefinitionFormat {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "toml" => Ok(DefinitionFormat::Toml),
+            "yaml" | "yml" => Ok(DefinitionFormat::Yaml),
+            "json" => Ok(DefinitionFormat::Json),
+            other => Err(PipelineError::invalid_config(format!(
+                "Unknown definition format '{}' (expected toml, yaml or json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// A pipeline's stages and settings, without its identity or history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineDefinition {
+    /// Pipeline name
+    pub name: String,
+    /// Chunk size the pipeline is pinned to, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    /// Workers the pipeline runs with unless a run gives its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workers: Option<usize>,
+    /// How stages are spread over workers: chunk-parallel or stage-parallel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topology: Option<String>,
+    /// Hash for the whole-file checksums: sha256 or blake3
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Lowest security level allowed to run the pipeline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_level: Option<String>,
+    /// Permissions needed to run the pipeline
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_permissions: Vec<String>,
+    /// Processing stages, in order
+    pub stages: Vec<StageDefinition>,
+    /// Other configuration keys, carried through unchanged
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub configuration: BTreeMap<String, String>,
+}
+
+/// One processing stage of a [`PipelineDefinition`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StageDefinition {
+    /// Stage name
+    pub name: String,
+    /// Stage type: compression, encryption, transform, checksum or
+    /// passthrough
+    #[serde(rename = "type")]
+    pub stage_type: String,
+    /// Algorithm the stage runs
+    pub algorithm: String,
+    /// Whether the stage may process chunks in parallel, as stages do
+    /// unless told otherwise
+    #[serde(default = "parallel_by_default", skip_serializing_if = "is_parallel")]
+    pub parallel: bool,
+    /// Chunk size the stage prefers, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    /// Algorithm parameters, e.g. a compression level
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
+}
+
+impl PipelineDefinition {
+    /// Describes `pipeline`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if a stored setting does not parse.
+    pub fn from_pipeline(pipeline: &Pipeline) -> Result<Self, PipelineError> {
+        let configuration = pipeline.configuration();
+        let policy = pipeline.security_policy()?;
+        Ok(Self {
+            name: pipeline.name().to_string(),
+            chunk_size: pipeline.chunk_size()?.map(|chunk_size| chunk_size.bytes()),
+            workers: pipeline.worker_count()?.map(|workers| workers.count()),
+            topology: match configuration.contains_key(EXECUTION_TOPOLOGY_KEY) {
+                true => Some(pipeline.execution_topology()?.to_string()),
+                false => None,
+            },
+            checksum: match configuration.contains_key(CHECKSUM_ALGORITHM_KEY) {
+                true => Some(pipeline.checksum_algorithm()?.to_string()),
+                false => None,
+            },
+            security_level: (*policy.minimum_level() != SecurityLevel::Public)
+                .then(|| policy.minimum_level().as_str().to_string()),
+            required_permissions: policy.required_permissions().iter().map(|p| p.name()).collect(),
+            stages: pipeline
+                .stages()
+                .iter()
+                .filter(|stage| !AUTOMATIC_STAGES.contains(&stage.name()))
+                .map(StageDefinition::from_stage)
+                .collect(),
+            configuration: configuration
+                .iter()
+                .filter(|(key, _)| !DEDICATED_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        })
+    }
+
+    /// Builds the pipeline this definition describes, in the default
+    /// namespace
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the definition breaks a pipeline
+    /// invariant or does not survive being exported again, and
+    /// `IncompatibleStage` for an invalid stage sequence.
+    pub fn to_pipeline(&self) -> Result<Pipeline, PipelineError> {
+        let pipeline = self.build()?;
+        // The pipeline must export to a definition that builds it again
+        let exported = Self::from_pipeline(&pipeline)?;
+        if Self::from_pipeline(&exported.build()?)? != exported {
+            return Err(PipelineError::invalid_config(format!(
+                "Pipeline definition '{}' does not round-trip",
+                self.name
+            )));
+        }
+        Ok(pipeline)
+    }
+
+    fn build(&self) -> Result<Pipeline, PipelineError> {
+        if let Some(key) = self
+            .configuration
+            .keys()
+            .find(|key| DEDICATED_KEYS.contains(&key.as_str()))
+        {
+            return Err(PipelineError::invalid_config(format!(
+                "Configuration key '{}' has its own field in a pipeline definition",
+                key
+            )));
+        }
+        let stages = self
+            .stages
+            .iter()
+            .enumerate()
+            .map(|(index, stage)| stage.to_stage(index as u32))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut pipeline = Pipeline::new(self.name.clone(), stages)?;
+        pipeline.update_configuration(self.configuration.clone().into_iter().collect());
+        if let Some(bytes) = self.chunk_size {
+            pipeline.set_chunk_size(ChunkSize::new(bytes)?);
+        }
+        if let Some(count) = self.workers {
+            if !(WorkerCount::MIN_WORKERS..=WorkerCount::MAX_WORKERS).contains(&count) {
+                return Err(PipelineError::invalid_config(format!(
+                    "Invalid workers {}: expected {} to {}",
+                    count,
+                    WorkerCount::MIN_WORKERS,
+                    WorkerCount::MAX_WORKERS
+                )));
+            }
+            pipeline.set_worker_count(WorkerCount::new(count));
+        }
+        if let Some(topology) = &self.topology {
+            pipeline.set_execution_topology(topology.parse::<ExecutionTopology>()?);
+        }
+        if let Some(checksum) = &self.checksum {
+            let algorithm = checksum.parse::<ChecksumAlgorithm>()?;
+            algorithm.ensure_permitted()?;
+            pipeline.set_checksum_algorithm(algorithm);
+        }
+        let minimum_level = match &self.security_level {
+            Some(level) => level.parse()?,
+            None => SecurityLevel::Public,
+        };
+        let permissions = self
+            .required_permissions
+            .iter()
+            .map(|permission| permission.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        let policy = SecurityPolicy::new(minimum_level).with_required_permissions(permissions);
+        if !policy.is_unrestricted() {
+            pipeline.set_security_policy(&policy);
+        }
+
+        pipeline.validate()?;
+        Ok(pipeline)
+    }
+}
+
+fn parallel_by_default() -> bool {
+    true
+}
+
+fn is_parallel(parallel: &bool) -> bool {
+    *parallel
+}
+
+impl StageDefinition {
+    fn from_stage(stage: &PipelineStage) -> Self {
+        let configuration = stage.configuration();
+        Self {
+            name: stage.name().to_string(),
+            stage_type: stage.stage_type().to_string(),
+            algorithm: configuration.algorithm.clone(),
+            parallel: configuration.parallel_processing,
+            chunk_size: configuration.chunk_size,
+            // The algorithm is already recorded once
+            parameters: configuration
+                .parameters
+                .iter()
+                .filter(|(key, value)| !(key.as_str() == ALGORITHM_PARAMETER && **value == configuration.algorithm))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn to_stage(&self, order: u32) -> Result<PipelineStage, PipelineError> {
+        if AUTOMATIC_STAGES.contains(&self.name.as_str()) {
+            return Err(PipelineError::invalid_config(format!(
+                "Stage name '{}' is reserved for the automatic checksum stages",
+                self.name
+            )));
+        }
+        let stage_type = self.stage_type.parse::<StageType>()?;
+        self.check_algorithm(stage_type)?;
+
+        // Stage services read the algorithm from the parameters
+        let mut parameters: std::collections::HashMap<_, _> = self.parameters.clone().into_iter().collect();
+        parameters
+            .entry(ALGORITHM_PARAMETER.to_string())
+            .or_insert_with(|| self.algorithm.clone());
+        let configuration = StageConfiguration {
+            algorithm: self.algorithm.clone(),
+            parameters,
+            parallel_processing: self.parallel,
+            chunk_size: self.chunk_size,
+            ..Default::default()
+        };
+        let stage = PipelineStage::new(self.name.clone(), stage_type, configuration, order)?;
+        stage.validate()?;
+        Ok(stage)
+    }
+
+    /// Checks that the algorithm belongs to the stage type and is permitted
+    fn check_algorithm(&self, stage_type: StageType) -> Result<(), PipelineError> {
+        let (belongs, kind): (fn(&Algorithm) -> bool, _) = match stage_type {
+            StageType::Compression => (Algorithm::is_compression, "compression"),
+            StageType::Encryption => (Algorithm::is_encryption, "encryption"),
+            StageType::Checksum => (Algorithm::is_hashing, "checksum"),
+            // Transforms may be custom stages registered at runtime
+            StageType::Transform | StageType::PassThrough => {
+                return match self.algorithm.trim().is_empty() {
+                    true => Err(PipelineError::invalid_config(format!(
+                        "Stage '{}' has no algorithm",
+                        self.name
+                    ))),
+                    false => Ok(()),
+                };
+            }
+        };
+        match Algorithm::parse(&self.algorithm) {
+            Ok(algorithm) if belongs(&algorithm) => algorithm.ensure_permitted(),
+            _ => Err(PipelineError::invalid_config(format!(
+                "Stage '{}': '{}' is not a {} algorithm",
+                self.name, self.algorithm, kind
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, stage_type: &str, algorithm: &str) -> StageDefinition {
+        StageDefinition {
+            name: name.to_string(),
+            stage_type: stage_type.to_string(),
+            algorithm: algorithm.to_string(),
+            parallel: true,
+            chunk_size: None,
+            parameters: BTreeMap::new(),
+        }
+    }
+
+    fn definition() -> PipelineDefinition {
+        let mut compression = stage("compression", "compression", "zstd");
+        compression.parameters.insert("level".to_string(), "6".to_string());
+        PipelineDefinition {
+            name: "secure-backup".to_string(),
+            chunk_size: Some(4 * 1024 * 1024),
+            workers: Some(8),
+            topology: Some("stage-parallel".to_string()),
+            checksum: None,
+            security_level: Some("confidential".to_string()),
+            required_permissions: vec!["decrypt".to_string()],
+            stages: vec![compression, stage("encryption", "encryption", "aes256gcm")],
+            configuration: BTreeMap::from([("owner".to_string(), "ops".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_definitions_round_trip_through_pipelines() {
+        let pipeline = definition().to_pipeline().unwrap();
+        assert_eq!(pipeline.stages().len(), 4);
+        assert_eq!(pipeline.chunk_size().unwrap().unwrap().bytes(), 4 * 1024 * 1024);
+        assert_eq!(pipeline.worker_count().unwrap().unwrap().count(), 8);
+        assert_eq!(pipeline.execution_topology().unwrap(), ExecutionTopology::StageParallel);
+        assert_eq!(pipeline.stages()[1].configuration().parameters["algorithm"], "zstd");
+
+        assert_eq!(PipelineDefinition::from_pipeline(&pipeline).unwrap(), definition());
+    }
+
+    #[test]
+    fn test_definitions_breaking_invariants_are_rejected() {
+        let mut wrong_kind = definition();
+        wrong_kind.stages[0].algorithm = "aes256gcm".to_string();
+        let mut unknown_type = definition();
+        unknown_type.stages[1].stage_type = "teleport".to_string();
+        let mut no_stages = definition();
+        no_stages.stages.clear();
+        let mut reserved = definition();
+        reserved.stages[0].name = "input_checksum".to_string();
+        let mut too_many_workers = definition();
+        too_many_workers.workers = Some(WorkerCount::MAX_WORKERS + 1);
+        let mut shadowed = definition();
+        shadowed
+            .configuration
+            .insert(CHUNK_SIZE_KEY.to_string(), "1".to_string());
+        let mut bad_level = definition();
+        bad_level.security_level = Some("classified".to_string());
+
+        for invalid in [
+            wrong_kind,
+            unknown_type,
+            no_stages,
+            reserved,
+            too_many_workers,
+            shadowed,
+            bad_level,
+        ] {
+            assert!(invalid.to_pipeline().is_err(), "{:?} was accepted", invalid);
+        }
+    }
+
+    #[test]
+    fn test_formats_parse_from_names_and_extensions() {
+        assert_eq!("YML".parse::<DefinitionFormat>().unwrap(), DefinitionFormat::Yaml);
+        assert!("xml".parse::<DefinitionFormat>().is_err());
+        assert_eq!(
+            DefinitionFormat::from_path(Path::new("backup.toml")),
+            Some(DefinitionFormat::Toml)
+        );
+        assert_eq!(DefinitionFormat::from_path(Path::new("backup")), None);
+    }
+}
@@ -277,6 +277,14 @@ impl PipelineId {
         self.0.as_ulid()
     }
 
+    /// Gets the same 128 bits as a UUID
+    ///
+    /// # Use Cases
+    /// - Domain events, which identify pipelines by UUID
+    pub fn as_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.as_ulid().0)
+    }
+
     /// Gets the timestamp component of the pipeline ID
     ///
     /// # Returns
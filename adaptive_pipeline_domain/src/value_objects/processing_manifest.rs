@@ -0,0 +1,291 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Processing Manifest Value Object
+//!
+//! A detached record of one completed processing job, written next to the
+//! archive as `<output>.adapipe.manifest`. It captures the completion metrics
+//! an auditor needs to vouch for the archive without opening it: input and
+//! output checksums, sizes, the stage list, timestamps, how the run used its
+//! workers, and the producing binary's provenance.
+//!
+//! A manifest may carry a detached [`ManifestSignature`]. The signature
+//! covers [`ProcessingManifest::signing_payload`], which is the manifest's
+//! JSON encoding with the signature removed, so any edit to a signed field
+//! invalidates it. Signing and verification are performed by the
+//! infrastructure layer; the domain only defines what is signed.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ProcessingManifest;
+//! use std::path::Path;
+//!
+//! let manifest = ProcessingManifest::new("data.csv", 1024, "ab12", "data.adapipe", 512, "cd34")
+//!     .with_pipeline("01H2X3Y4Z5", "nightly-backup", vec!["compression".to_string()]);
+//! assert!(!manifest.is_signed());
+//! assert_eq!(
+//!     ProcessingManifest::path_for(Path::new("out/data.adapipe")),
+//!     Path::new("out/data.adapipe.manifest")
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::build_provenance::BuildProvenance;
+use super::checksum_algorithm::ChecksumAlgorithm;
+use super::concurrency_summary::ConcurrencySummary;
+use crate::PipelineError;
+
+/// Current manifest layout version
+pub const MANIFEST_VERSION: u16 = 1;
+
+/// Extension appended to the archive path to form the manifest path
+pub const MANIFEST_EXTENSION: &str = "manifest";
+
+/// Signature algorithm identifier recorded in signed manifests
+pub const ED25519: &str = "ed25519";
+
+/// Completion metrics of one processing job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessingManifest {
+    /// Manifest layout version
+    pub manifest_version: u16,
+
+    /// File name of the processed input
+    pub input_file: String,
+
+    /// Input size in bytes
+    pub input_size: u64,
+
+    /// Checksum of the input (hex), hashed with `input_checksum_algorithm`
+    pub input_checksum: String,
+
+    /// Hash of `input_checksum` (left out for SHA-256)
+    #[serde(default, skip_serializing_if = "ChecksumAlgorithm::is_default")]
+    pub input_checksum_algorithm: ChecksumAlgorithm,
+
+    /// File name of the produced archive
+    pub output_file: String,
+
+    /// Archive size in bytes
+    pub output_size: u64,
+
+    /// SHA-256 of the complete archive file (hex)
+    pub output_checksum: String,
+
+    /// ID of the pipeline that processed the file
+    pub pipeline_id: String,
+
+    /// Name of the pipeline that processed the file
+    pub pipeline_name: String,
+
+    /// Stage names in execution order
+    pub stages: Vec<String>,
+
+    /// When processing started
+    pub started_at: DateTime<Utc>,
+
+    /// When the archive was complete
+    pub completed_at: DateTime<Utc>,
+
+    /// Queue and CPU token waits and worker utilization of the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<ConcurrencySummary>,
+
+    /// Build identity of the binary that produced the archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<BuildProvenance>,
+
+    /// Detached signature over [`ProcessingManifest::signing_payload`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+}
+
+/// Signature attached to a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// Signature algorithm (currently always [`ED25519`])
+    pub algorithm: String,
+
+    /// Signer's public key (hex)
+    pub public_key: String,
+
+    /// Signature bytes (hex)
+    pub value: String,
+}
+
+impl ProcessingManifest {
+    /// Creates an unsigned manifest for the given input and archive
+    ///
+    /// Both timestamps default to now; use
+    /// [`ProcessingManifest::with_timestamps`] to record the actual start.
+    pub fn new(
+        input_file: impl Into<String>,
+        input_size: u64,
+        input_checksum: impl Into<String>,
+        output_file: impl Into<String>,
+        output_size: u64,
+        output_checksum: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            input_file: input_file.into(),
+            input_size,
+            input_checksum: input_checksum.into(),
+            input_checksum_algorithm: ChecksumAlgorithm::default(),
+            output_file: output_file.into(),
+            output_size,
+            output_checksum: output_checksum.into(),
+            pipeline_id: String::new(),
+            pipeline_name: String::new(),
+            stages: Vec::new(),
+            started_at: now,
+            completed_at: now,
+            concurrency: None,
+            provenance: None,
+            signature: None,
+        }
+    }
+
+    /// Records the pipeline and its stage names
+    pub fn with_pipeline(
+        mut self,
+        pipeline_id: impl Into<String>,
+        pipeline_name: impl Into<String>,
+        stages: Vec<String>,
+    ) -> Self {
+        self.pipeline_id = pipeline_id.into();
+        self.pipeline_name = pipeline_name.into();
+        self.stages = stages;
+        self
+    }
+
+    /// Records when processing started and completed
+    pub fn with_timestamps(mut self, started_at: DateTime<Utc>, completed_at: DateTime<Utc>) -> Self {
+        self.started_at = started_at;
+        self.completed_at = completed_at;
+        self
+    }
+
+    /// Records the hash of the input checksum
+    pub fn with_input_checksum_algorithm(mut self, algorithm: ChecksumAlgorithm) -> Self {
+        self.input_checksum_algorithm = algorithm;
+        self
+    }
+
+    /// Records how the run used its workers
+    pub fn with_concurrency(mut self, concurrency: ConcurrencySummary) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Records the build provenance of the producing binary
+    pub fn with_provenance(mut self, provenance: BuildProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Attaches a signature produced over [`Self::signing_payload`]
+    pub fn with_signature(mut self, signature: ManifestSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Whether the manifest carries a signature
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    /// Bytes covered by the signature: the compact JSON encoding of the
+    /// manifest without its `signature` field
+    pub fn signing_payload(&self) -> Result<Vec<u8>, PipelineError> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode manifest: {}", e)))
+    }
+
+    /// Pretty-printed JSON for writing to disk
+    pub fn to_json(&self) -> Result<String, PipelineError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode manifest: {}", e)))
+    }
+
+    /// Parses a manifest read from disk
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let manifest: Self = serde_json::from_str(json)
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid manifest: {}", e)))?;
+        if manifest.manifest_version > MANIFEST_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Manifest version {} is newer than supported version {}",
+                manifest.manifest_version, MANIFEST_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Manifest path for an archive: the archive path with `.manifest`
+    /// appended (`data.adapipe` → `data.adapipe.manifest`)
+    pub fn path_for(archive: &Path) -> PathBuf {
+        let mut path = archive.as_os_str().to_owned();
+        path.push(".");
+        path.push(MANIFEST_EXTENSION);
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ProcessingManifest {
+        ProcessingManifest::new("in.txt", 100, "aa", "in.adapipe", 60, "bb").with_pipeline(
+            "01H",
+            "test-pipeline",
+            vec!["compression".to_string(), "encryption".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_signing_payload_excludes_signature() {
+        let unsigned = sample();
+        let signed = unsigned.clone().with_signature(ManifestSignature {
+            algorithm: ED25519.to_string(),
+            public_key: "00".to_string(),
+            value: "11".to_string(),
+        });
+        assert_eq!(unsigned.signing_payload().unwrap(), signed.signing_payload().unwrap());
+
+        let mut tampered = unsigned.clone();
+        tampered.output_checksum = "cc".to_string();
+        assert_ne!(unsigned.signing_payload().unwrap(), tampered.signing_payload().unwrap());
+    }
+
+    #[test]
+    fn test_json_round_trip_and_version_check() {
+        let manifest = sample();
+        assert_eq!(
+            ProcessingManifest::from_json(&manifest.to_json().unwrap()).unwrap(),
+            manifest
+        );
+
+        let blake3 = sample().with_input_checksum_algorithm(ChecksumAlgorithm::Blake3);
+        assert_eq!(
+            ProcessingManifest::from_json(&blake3.to_json().unwrap()).unwrap(),
+            blake3
+        );
+        assert!(!manifest.to_json().unwrap().contains("input_checksum_algorithm"));
+
+        let mut future = manifest;
+        future.manifest_version = MANIFEST_VERSION + 1;
+        assert!(ProcessingManifest::from_json(&future.to_json().unwrap()).is_err());
+    }
+}
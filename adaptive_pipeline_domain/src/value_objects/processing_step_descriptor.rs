@@ -243,7 +243,9 @@ impl Algorithm {
     /// * `value` - Algorithm name (alphanumeric, hyphens, underscores)
     ///
     /// # Returns
-    /// * `Ok(Algorithm)` - Validated algorithm (normalized to lowercase)
+    /// * `Ok(Algorithm)` - Validated algorithm (normalized to lowercase, with
+    ///   known aliases resolved to their canonical
+    ///   [`value_objects::Algorithm`](super::Algorithm) name)
     /// * `Err(PipelineError)` - Invalid format
     ///
     /// # Errors
@@ -267,7 +269,10 @@ impl Algorithm {
             )));
         }
 
-        Ok(Algorithm(trimmed.to_lowercase()))
+        match super::algorithm::Algorithm::canonical_name(trimmed) {
+            Some(canonical) => Ok(Algorithm(canonical.to_string())),
+            None => Ok(Algorithm(trimmed.to_lowercase())),
+        }
     }
 
     /// Get the algorithm name as a string
@@ -0,0 +1,219 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Quota Limits Value Object
+//!
+//! Configurable per-namespace limits checked before a processing job starts.
+//! Every limit is optional; an unset limit is not enforced.
+//!
+//! | Limit                      | Checked against                           |
+//! |----------------------------|-------------------------------------------|
+//! | `max_input_bytes`          | Size of the file about to be processed    |
+//! | `max_concurrent_jobs`      | Jobs already running in the namespace     |
+//! | `max_output_bytes_per_day` | Output bytes written today (UTC)          |
+//!
+//! Violations are reported as `PipelineError::QuotaExceeded`.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{Namespace, QuotaLimits};
+//!
+//! let limits = QuotaLimits::unlimited().with_max_input_bytes(1024);
+//! let namespace = Namespace::default();
+//! assert!(limits.check_input_size(&namespace, 512).is_ok());
+//! assert!(limits.check_input_size(&namespace, 4096).is_err());
+//! ```
+
+use crate::value_objects::{Namespace, NamespaceUsage};
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Identifies which limit a job exceeded, e.g. for metric labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaLimit {
+    /// Maximum size of a single input file
+    MaxInputBytes,
+    /// Maximum number of jobs running at once
+    MaxConcurrentJobs,
+    /// Maximum output bytes written per UTC day
+    MaxOutputBytesPerDay,
+}
+
+impl QuotaLimit {
+    /// Returns the snake_case name used in configuration and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaLimit::MaxInputBytes => "max_input_bytes",
+            QuotaLimit::MaxConcurrentJobs => "max_concurrent_jobs",
+            QuotaLimit::MaxOutputBytesPerDay => "max_output_bytes_per_day",
+        }
+    }
+}
+
+impl Display for QuotaLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Optional processing limits for a namespace
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaLimits {
+    #[serde(default)]
+    pub max_input_bytes: Option<u64>,
+    #[serde(default)]
+    pub max_concurrent_jobs: Option<usize>,
+    #[serde(default)]
+    pub max_output_bytes_per_day: Option<u64>,
+}
+
+impl QuotaLimits {
+    /// Creates limits with nothing enforced
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum input file size
+    pub fn with_max_input_bytes(mut self, bytes: u64) -> Self {
+        self.max_input_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of concurrent jobs
+    pub fn with_max_concurrent_jobs(mut self, jobs: usize) -> Self {
+        self.max_concurrent_jobs = Some(jobs);
+        self
+    }
+
+    /// Sets the maximum output bytes per UTC day
+    pub fn with_max_output_bytes_per_day(mut self, bytes: u64) -> Self {
+        self.max_output_bytes_per_day = Some(bytes);
+        self
+    }
+
+    /// Fills limits left unset here from `fallback`
+    pub fn or(self, fallback: QuotaLimits) -> Self {
+        Self {
+            max_input_bytes: self.max_input_bytes.or(fallback.max_input_bytes),
+            max_concurrent_jobs: self.max_concurrent_jobs.or(fallback.max_concurrent_jobs),
+            max_output_bytes_per_day: self.max_output_bytes_per_day.or(fallback.max_output_bytes_per_day),
+        }
+    }
+
+    /// Checks whether any limit is set
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::unlimited()
+    }
+
+    /// Checks an input file size against `max_input_bytes`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::QuotaExceeded` if the file is too large.
+    pub fn check_input_size(&self, namespace: &Namespace, input_bytes: u64) -> Result<(), PipelineError> {
+        match self.max_input_bytes {
+            Some(max) if input_bytes > max => Err(PipelineError::quota_exceeded(format!(
+                "namespace '{}' {}: input is {} bytes, limit is {} bytes",
+                namespace,
+                QuotaLimit::MaxInputBytes,
+                input_bytes,
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the number of already running jobs against
+    /// `max_concurrent_jobs`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::QuotaExceeded` if starting another job would
+    /// exceed the limit.
+    pub fn check_concurrent_jobs(&self, namespace: &Namespace, running_jobs: usize) -> Result<(), PipelineError> {
+        match self.max_concurrent_jobs {
+            Some(max) if running_jobs >= max => Err(PipelineError::quota_exceeded(format!(
+                "namespace '{}' {}: {} job(s) running, limit is {}",
+                namespace,
+                QuotaLimit::MaxConcurrentJobs,
+                running_jobs,
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks today's usage against `max_output_bytes_per_day`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::QuotaExceeded` if the daily output budget is
+    /// already used up.
+    pub fn check_daily_output(&self, usage: &NamespaceUsage) -> Result<(), PipelineError> {
+        match self.max_output_bytes_per_day {
+            Some(max) if usage.output_bytes() >= max => Err(PipelineError::quota_exceeded(format!(
+                "namespace '{}' {}: {} bytes written on {}, limit is {} bytes",
+                usage.namespace(),
+                QuotaLimit::MaxOutputBytesPerDay,
+                usage.output_bytes(),
+                usage.day(),
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_limits_are_optional() {
+        let namespace = Namespace::default();
+        let limits = QuotaLimits::unlimited();
+        assert!(limits.is_unlimited());
+        assert!(limits.check_input_size(&namespace, u64::MAX).is_ok());
+        assert!(limits.check_concurrent_jobs(&namespace, usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_limit_checks() {
+        let namespace = Namespace::new("team-a").unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        let limits = QuotaLimits::unlimited()
+            .with_max_input_bytes(100)
+            .with_max_concurrent_jobs(2)
+            .with_max_output_bytes_per_day(1_000);
+
+        assert!(limits.check_input_size(&namespace, 100).is_ok());
+        let err = limits.check_input_size(&namespace, 101).unwrap_err();
+        assert!(matches!(err, PipelineError::QuotaExceeded(_)));
+        assert!(err.to_string().contains("max_input_bytes"));
+
+        assert!(limits.check_concurrent_jobs(&namespace, 1).is_ok());
+        assert!(limits.check_concurrent_jobs(&namespace, 2).is_err());
+
+        let usage = NamespaceUsage::new(namespace.clone(), day, 3, 5_000, 999);
+        assert!(limits.check_daily_output(&usage).is_ok());
+        let usage = NamespaceUsage::new(namespace, day, 4, 6_000, 1_000);
+        assert!(limits.check_daily_output(&usage).is_err());
+    }
+
+    #[test]
+    fn test_namespace_limits_fall_back_to_defaults() {
+        let defaults = QuotaLimits::unlimited()
+            .with_max_input_bytes(10)
+            .with_max_concurrent_jobs(4);
+        let merged = QuotaLimits::unlimited().with_max_concurrent_jobs(1).or(defaults);
+        assert_eq!(merged.max_input_bytes, Some(10));
+        assert_eq!(merged.max_concurrent_jobs, Some(1));
+        assert_eq!(merged.max_output_bytes_per_day, None);
+    }
+}
@@ -0,0 +1,294 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Restore Report Value Object
+//!
+//! A record of one completed restore, written next to the restored file as
+//! `<file>.restore-report.json`. Disaster-recovery drills archive it as proof
+//! that a restore ran to completion and that the restored data matched the
+//! checksum recorded when the archive was made.
+//!
+//! The report holds the chunk count, the time spent in each restoration
+//! stage, the checksum comparison, the size check and any warnings (for
+//! example an archive that recorded no checksum to verify against).
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::RestoreReport;
+//! use std::path::Path;
+//!
+//! let report = RestoreReport::new("data.adapipe", "restore/data.csv", 1024, 1024, 1)
+//!     .with_checksum("ab12", "ab12", true);
+//! assert!(report.is_verified());
+//! assert_eq!(
+//!     RestoreReport::path_for(Path::new("restore/data.csv")),
+//!     Path::new("restore/data.csv.restore-report.json")
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::build_provenance::BuildProvenance;
+use super::byte_range::ByteRange;
+use crate::PipelineError;
+
+/// Current report layout version
+pub const RESTORE_REPORT_VERSION: u16 = 1;
+
+/// Extension appended to the restored file's path to form the report path
+pub const RESTORE_REPORT_EXTENSION: &str = "restore-report.json";
+
+/// Time one restoration stage spent across all chunks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    /// Stage name
+    pub name: String,
+
+    /// Stage algorithm
+    pub algorithm: String,
+
+    /// Chunks the stage processed
+    pub chunks: u64,
+
+    /// Total time in the stage, in milliseconds
+    pub duration_ms: f64,
+}
+
+/// Outcome of comparing the restored data against the archive's checksum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecksumResult {
+    /// Digest algorithm
+    pub algorithm: String,
+
+    /// Checksum recorded in the archive (hex); empty if none was recorded
+    pub expected: String,
+
+    /// Checksum of the restored data (hex)
+    pub calculated: String,
+
+    /// Whether the two matched
+    pub verified: bool,
+}
+
+/// Integrity evidence of one completed restore
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestoreReport {
+    /// Report layout version
+    pub report_version: u16,
+
+    /// Archive the file was restored from
+    pub archive: String,
+
+    /// Path of the restored file
+    pub restored_file: String,
+
+    /// Original size recorded in the archive
+    pub original_size: u64,
+
+    /// Bytes written to the restored file
+    pub bytes_restored: u64,
+
+    /// Whether the restored size matched the original size, or the size of
+    /// `range` for a ranged restore
+    pub size_verified: bool,
+
+    /// Bytes of the original that were restored, when not all of them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<ByteRange>,
+
+    /// Chunks restored
+    pub chunks_processed: u64,
+
+    /// Time spent in each restoration stage, in execution order
+    pub stages: Vec<StageTiming>,
+
+    /// Checksum comparison of the restored data
+    pub checksum: ChecksumResult,
+
+    /// Conditions that did not fail the restore but weaken its evidence
+    pub warnings: Vec<String>,
+
+    /// When the restore started
+    pub started_at: DateTime<Utc>,
+
+    /// When the restored file was in place
+    pub completed_at: DateTime<Utc>,
+
+    /// Build identity of the binary that performed the restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<BuildProvenance>,
+}
+
+impl RestoreReport {
+    /// Creates a report for a restore of `archive` into `restored_file`
+    ///
+    /// The checksum starts out unverified and both timestamps default to
+    /// now.
+    pub fn new(
+        archive: impl Into<String>,
+        restored_file: impl Into<String>,
+        original_size: u64,
+        bytes_restored: u64,
+        chunks_processed: u64,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            report_version: RESTORE_REPORT_VERSION,
+            archive: archive.into(),
+            restored_file: restored_file.into(),
+            original_size,
+            bytes_restored,
+            size_verified: original_size == bytes_restored,
+            range: None,
+            chunks_processed,
+            stages: Vec::new(),
+            checksum: ChecksumResult {
+                algorithm: "sha256".to_string(),
+                expected: String::new(),
+                calculated: String::new(),
+                verified: false,
+            },
+            warnings: Vec::new(),
+            started_at: now,
+            completed_at: now,
+            provenance: None,
+        }
+    }
+
+    /// Records the time a stage spent over `chunks` chunks
+    pub fn with_stage(
+        mut self,
+        name: impl Into<String>,
+        algorithm: impl Into<String>,
+        chunks: u64,
+        duration: Duration,
+    ) -> Self {
+        self.stages.push(StageTiming {
+            name: name.into(),
+            algorithm: algorithm.into(),
+            chunks,
+            duration_ms: duration.as_secs_f64() * 1000.0,
+        });
+        self
+    }
+
+    /// Records the SHA-256 comparison of the restored data
+    pub fn with_checksum(mut self, expected: impl Into<String>, calculated: impl Into<String>, verified: bool) -> Self {
+        self.checksum.expected = expected.into();
+        self.checksum.calculated = calculated.into();
+        self.checksum.verified = verified;
+        self
+    }
+
+    /// Records that only `range` of the original was restored; the size is
+    /// then checked against the range
+    pub fn with_range(mut self, range: ByteRange) -> Self {
+        self.size_verified = self.bytes_restored == range.len_within(self.original_size);
+        self.range = Some(range);
+        self
+    }
+
+    /// Records a warning
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// Records when the restore started and completed
+    pub fn with_timestamps(mut self, started_at: DateTime<Utc>, completed_at: DateTime<Utc>) -> Self {
+        self.started_at = started_at;
+        self.completed_at = completed_at;
+        self
+    }
+
+    /// Records the build provenance of the restoring binary
+    pub fn with_provenance(mut self, provenance: BuildProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Whether both the checksum and the size of the restored data matched
+    /// the archive
+    pub fn is_verified(&self) -> bool {
+        self.checksum.verified && self.size_verified
+    }
+
+    /// Pretty-printed JSON for writing to disk
+    pub fn to_json(&self) -> Result<String, PipelineError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode restore report: {}", e)))
+    }
+
+    /// Parses a report read from disk
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let report: Self = serde_json::from_str(json)
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid restore report: {}", e)))?;
+        if report.report_version > RESTORE_REPORT_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Restore report version {} is newer than supported version {}",
+                report.report_version, RESTORE_REPORT_VERSION
+            )));
+        }
+        Ok(report)
+    }
+
+    /// Report path for a restored file: its path with
+    /// `.restore-report.json` appended (`data.csv` →
+    /// `data.csv.restore-report.json`)
+    pub fn path_for(restored_file: &Path) -> PathBuf {
+        let mut path = restored_file.as_os_str().to_owned();
+        path.push(".");
+        path.push(RESTORE_REPORT_EXTENSION);
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip_and_version_check() {
+        let report = RestoreReport::new("in.adapipe", "out/in.txt", 100, 100, 2)
+            .with_stage("decompression", "brotli", 2, Duration::from_millis(3))
+            .with_checksum("aa", "aa", true)
+            .with_warning("restored to out/in (1).txt");
+        assert!(report.is_verified());
+        assert_eq!(RestoreReport::from_json(&report.to_json().unwrap()).unwrap(), report);
+
+        let mut future = report;
+        future.report_version = RESTORE_REPORT_VERSION + 1;
+        assert!(RestoreReport::from_json(&future.to_json().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_unverified_without_a_matching_checksum_or_size() {
+        let report = RestoreReport::new("in.adapipe", "in.txt", 100, 100, 1);
+        assert!(!report.is_verified());
+        let short = RestoreReport::new("in.adapipe", "in.txt", 100, 90, 1).with_checksum("aa", "aa", true);
+        assert!(!short.size_verified);
+        assert!(!short.is_verified());
+    }
+
+    #[test]
+    fn test_ranged_restore_checks_size_against_the_range() {
+        let range = ByteRange::new(50, Some(199)).unwrap();
+        let report = RestoreReport::new("in.adapipe", "in.txt", 100, 50, 1).with_range(range);
+        assert!(report.size_verified);
+        assert!(!report.is_verified());
+        assert_eq!(
+            RestoreReport::from_json(&report.to_json().unwrap()).unwrap().range,
+            Some(range)
+        );
+        assert!(!RestoreReport::new("in.adapipe", "in.txt", 100, 100, 1)
+            .to_json()
+            .unwrap()
+            .contains("range"));
+    }
+}
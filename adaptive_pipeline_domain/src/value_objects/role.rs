@@ -0,0 +1,338 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Role Value Object
+//!
+//! Role-based access control (RBAC) on top of the fine-grained `Permission`
+//! model of `SecurityContext`.
+//!
+//! ## Overview
+//!
+//! A principal is assigned at most one [`Role`] per namespace; admins of the
+//! default namespace administer every namespace. Each role maps to a fixed set
+//! of permissions, and each [`ProtectedOperation`] (the CLI and API entry
+//! points worth guarding) requires a set of permissions. An operation is
+//! allowed when the role's permissions satisfy every requirement.
+//!
+//! | Role       | Permissions                                               |
+//! |------------|-----------------------------------------------------------|
+//! | `auditor`  | Read                                                      |
+//! | `operator` | Read, Write, Execute, Encrypt, Decrypt, Compress, Decompress |
+//! | `admin`    | Admin (implies every permission)                          |
+//!
+//! Roles are ordered `Auditor < Operator < Admin`; a higher role grants
+//! everything a lower one does, so a principal may act with any role up to
+//! the one assigned.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::{ProtectedOperation, Role};
+//!
+//! let role: Role = "operator".parse().unwrap();
+//! assert!(role.permits(ProtectedOperation::RestoreFile));
+//! assert!(!role.permits(ProtectedOperation::DeletePipeline));
+//! ```
+
+use crate::entities::security_context::Permission;
+use crate::services::datetime_serde;
+use crate::value_objects::Namespace;
+use crate::PipelineError;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Role assigned to a principal, mapping to a fixed permission set
+///
+/// Variants are declared in ascending order of privilege so that `Ord`
+/// reflects the role hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Read-only access to pipeline definitions and processed file metadata
+    Auditor,
+    /// Day-to-day processing: create pipelines, process and restore files
+    Operator,
+    /// Full access, including pipeline deletion, key and role management
+    Admin,
+}
+
+impl Role {
+    /// Returns every role in ascending order of privilege
+    pub fn all() -> [Role; 3] {
+        [Role::Auditor, Role::Operator, Role::Admin]
+    }
+
+    /// Returns the lowercase name used in configuration and storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Auditor => "auditor",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Returns the permissions granted by this role
+    pub fn permissions(&self) -> Vec<Permission> {
+        match self {
+            Role::Auditor => vec![Permission::Read],
+            Role::Operator => vec![
+                Permission::Read,
+                Permission::Write,
+                Permission::Execute,
+                Permission::Encrypt,
+                Permission::Decrypt,
+                Permission::Compress,
+                Permission::Decompress,
+            ],
+            Role::Admin => vec![Permission::Admin],
+        }
+    }
+
+    /// Checks whether this role grants `permission`
+    pub fn grants(&self, permission: &Permission) -> bool {
+        let permissions = self.permissions();
+        permissions.contains(&Permission::Admin) || permissions.contains(permission)
+    }
+
+    /// Checks whether this role may perform `operation`
+    pub fn permits(&self, operation: ProtectedOperation) -> bool {
+        operation.required_permissions().iter().all(|p| self.grants(p))
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auditor" => Ok(Role::Auditor),
+            "operator" => Ok(Role::Operator),
+            "admin" => Ok(Role::Admin),
+            other => Err(PipelineError::invalid_config(format!(
+                "Unknown role '{}'. Valid roles: auditor, operator, admin",
+                other
+            ))),
+        }
+    }
+}
+
+/// Operations gated by role-based access control
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedOperation {
+    /// List or show pipeline definitions
+    ViewPipelines,
+    /// Create a new pipeline definition
+    CreatePipeline,
+    /// Delete a pipeline definition
+    DeletePipeline,
+    /// Process a file through a pipeline
+    ProcessFile,
+    /// Restore an original file from a `.adapipe` file
+    RestoreFile,
+    /// Create, rotate or export encryption keys
+    ManageKeys,
+    /// Assign or revoke roles
+    ManageRoles,
+    /// Back up or restore the pipeline database
+    ManageDatabase,
+    /// Issue or revoke API sessions
+    ManageSessions,
+}
+
+impl ProtectedOperation {
+    /// Returns the permissions a principal must hold to perform the operation
+    pub fn required_permissions(&self) -> Vec<Permission> {
+        match self {
+            ProtectedOperation::ViewPipelines => vec![Permission::Read],
+            ProtectedOperation::CreatePipeline => vec![Permission::Write],
+            ProtectedOperation::ProcessFile => vec![Permission::Read, Permission::Write, Permission::Execute],
+            ProtectedOperation::RestoreFile => vec![
+                Permission::Read,
+                Permission::Write,
+                Permission::Decrypt,
+                Permission::Decompress,
+            ],
+            ProtectedOperation::DeletePipeline
+            | ProtectedOperation::ManageKeys
+            | ProtectedOperation::ManageRoles
+            | ProtectedOperation::ManageDatabase
+            | ProtectedOperation::ManageSessions => vec![Permission::Admin],
+        }
+    }
+
+    /// Returns a human-readable name for error messages and audit logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtectedOperation::ViewPipelines => "view pipelines",
+            ProtectedOperation::CreatePipeline => "create pipeline",
+            ProtectedOperation::DeletePipeline => "delete pipeline",
+            ProtectedOperation::ProcessFile => "process file",
+            ProtectedOperation::RestoreFile => "restore file",
+            ProtectedOperation::ManageKeys => "manage keys",
+            ProtectedOperation::ManageRoles => "manage roles",
+            ProtectedOperation::ManageDatabase => "manage database",
+            ProtectedOperation::ManageSessions => "manage sessions",
+        }
+    }
+}
+
+impl Display for ProtectedOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Persistent binding of a principal to a role within a namespace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleAssignment {
+    namespace: Namespace,
+    principal: String,
+    role: Role,
+    assigned_by: Option<String>,
+    #[serde(with = "datetime_serde")]
+    assigned_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RoleAssignment {
+    /// Creates an assignment of `role` to `principal` in `namespace`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::InvalidConfiguration` if `principal` is empty.
+    pub fn new(
+        namespace: Namespace,
+        principal: impl Into<String>,
+        role: Role,
+        assigned_by: Option<String>,
+    ) -> Result<Self, PipelineError> {
+        let principal = principal.into().trim().to_string();
+        if principal.is_empty() {
+            return Err(PipelineError::invalid_config(
+                "Role assignment principal cannot be empty",
+            ));
+        }
+        Ok(Self {
+            namespace,
+            principal,
+            role,
+            assigned_by,
+            assigned_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Rebuilds an assignment from persisted fields
+    pub fn from_parts(
+        namespace: Namespace,
+        principal: String,
+        role: Role,
+        assigned_by: Option<String>,
+        assigned_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            namespace,
+            principal,
+            role,
+            assigned_by,
+            assigned_at,
+        }
+    }
+
+    /// Gets the namespace the role applies to
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Gets the principal (user or service account name)
+    pub fn principal(&self) -> &str {
+        &self.principal
+    }
+
+    /// Gets the assigned role
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Gets the principal that made the assignment
+    pub fn assigned_by(&self) -> Option<&str> {
+        self.assigned_by.as_deref()
+    }
+
+    /// Gets when the assignment was made
+    pub fn assigned_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.assigned_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_parsing_and_display() {
+        for role in Role::all() {
+            assert_eq!(role.to_string().parse::<Role>().unwrap(), role);
+        }
+        assert_eq!(" Admin ".parse::<Role>().unwrap(), Role::Admin);
+        assert!("root".parse::<Role>().is_err());
+    }
+
+    #[test]
+    fn test_role_hierarchy_is_monotonic() {
+        let operations = [
+            ProtectedOperation::ViewPipelines,
+            ProtectedOperation::CreatePipeline,
+            ProtectedOperation::DeletePipeline,
+            ProtectedOperation::ProcessFile,
+            ProtectedOperation::RestoreFile,
+            ProtectedOperation::ManageKeys,
+            ProtectedOperation::ManageRoles,
+            ProtectedOperation::ManageDatabase,
+            ProtectedOperation::ManageSessions,
+        ];
+        // Anything a lower role may do, every higher role may do too
+        for lower in Role::all() {
+            for higher in Role::all().into_iter().filter(|r| *r > lower) {
+                for operation in operations {
+                    assert!(!lower.permits(operation) || higher.permits(operation));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_role_operation_matrix() {
+        assert!(Role::Auditor.permits(ProtectedOperation::ViewPipelines));
+        assert!(!Role::Auditor.permits(ProtectedOperation::ProcessFile));
+        assert!(!Role::Auditor.permits(ProtectedOperation::RestoreFile));
+
+        assert!(Role::Operator.permits(ProtectedOperation::ProcessFile));
+        assert!(Role::Operator.permits(ProtectedOperation::RestoreFile));
+        assert!(!Role::Operator.permits(ProtectedOperation::DeletePipeline));
+        assert!(!Role::Operator.permits(ProtectedOperation::ManageKeys));
+        assert!(!Role::Operator.permits(ProtectedOperation::ManageDatabase));
+        assert!(!Role::Operator.permits(ProtectedOperation::ManageSessions));
+
+        assert!(Role::Admin.permits(ProtectedOperation::DeletePipeline));
+        assert!(Role::Admin.permits(ProtectedOperation::ManageRoles));
+    }
+
+    #[test]
+    fn test_role_assignment_rejects_empty_principal() {
+        assert!(RoleAssignment::new(Namespace::default(), "  ", Role::Operator, None).is_err());
+        let assignment =
+            RoleAssignment::new(Namespace::default(), "alice", Role::Operator, Some("root".to_string())).unwrap();
+        assert_eq!(assignment.principal(), "alice");
+        assert_eq!(assignment.assigned_by(), Some("root"));
+    }
+}
@@ -0,0 +1,155 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Secret Bytes Value Object
+//!
+//! Owned buffer for key material, nonces, salts and passwords. The buffer is
+//! zeroized when dropped (including every clone), and its `Debug` output only
+//! reports the length, so secrets cannot leak through logs, panics or
+//! `{:?}`-formatted errors.
+//!
+//! Access to the contents is explicit through [`SecretBytes::expose_secret`],
+//! which keeps every read of sensitive data easy to find in review.
+//! `SecretBytes` deliberately does not implement `PartialEq`; compare
+//! secrets with a constant-time routine instead.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::SecretBytes;
+//!
+//! let key = SecretBytes::new(vec![0x42; 32]);
+//! assert_eq!(key.len(), 32);
+//! assert_eq!(key.expose_secret()[0], 0x42);
+//! assert_eq!(format!("{:?}", key), "SecretBytes([REDACTED; 32 bytes])");
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Byte buffer that is zeroized on drop and redacted in `Debug` output
+#[derive(Clone, Default)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// Takes ownership of `bytes` without copying them
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a zero-filled buffer of `len` bytes, for APIs that write
+    /// secrets into a caller-provided slice
+    pub fn zeroed(len: usize) -> Self {
+        Self(vec![0u8; len])
+    }
+
+    /// Copies `bytes` into a new secret buffer
+    ///
+    /// The caller remains responsible for wiping the source slice.
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+
+    /// Returns the secret contents
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the secret contents for in-place writes
+    pub fn expose_secret_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    /// Number of bytes held
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretBytes {}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {} bytes])", self.0.len())
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<String> for SecretBytes {
+    fn from(password: String) -> Self {
+        Self::new(password.into_bytes())
+    }
+}
+
+impl From<&str> for SecretBytes {
+    fn from(password: &str) -> Self {
+        Self::from_slice(password.as_bytes())
+    }
+}
+
+impl Serialize for SecretBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_does_not_leak_contents() {
+        let secret = SecretBytes::from("hunter2");
+        let rendered = format!("{:?}", secret);
+        assert!(!rendered.contains("hunter2"));
+        assert!(!rendered.contains("104")); // 'h'
+        assert_eq!(rendered, "SecretBytes([REDACTED; 7 bytes])");
+    }
+
+    #[test]
+    fn test_zeroize_clears_contents() {
+        let mut secret = SecretBytes::new(vec![0xAA; 16]);
+        secret.zeroize();
+        assert!(secret.is_empty());
+    }
+
+    #[test]
+    fn test_serde_round_trip_preserves_bytes() {
+        let secret = SecretBytes::new(vec![1, 2, 3]);
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let restored: SecretBytes = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.expose_secret(), &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,259 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Security Policy Value Object
+//!
+//! What a security context must hold to run a pipeline or restore its
+//! archives: a minimum [`SecurityLevel`] and a set of [`Permission`]s. The
+//! policy is a property of the pipeline, stored in its configuration under
+//! [`MINIMUM_SECURITY_LEVEL_KEY`] and [`REQUIRED_PERMISSIONS_KEY`]. The same
+//! keys carry it into the metadata of every `.adapipe` file the pipeline
+//! writes, so restore enforces it without the pipeline's definition.
+//!
+//! A pipeline without these keys has the unrestricted policy: level
+//! `public`, no permissions beyond those of the operation itself.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::entities::security_context::{Permission, SecurityContext, SecurityLevel};
+//! use adaptive_pipeline_domain::value_objects::SecurityPolicy;
+//!
+//! let policy = SecurityPolicy::new(SecurityLevel::Confidential).with_required_permissions(vec![Permission::Decrypt]);
+//!
+//! let analyst = SecurityContext::with_permissions(None, vec![Permission::Read], SecurityLevel::Secret);
+//! assert!(policy.check(&analyst).is_err());
+//!
+//! let officer = SecurityContext::with_permissions(None, vec![Permission::Decrypt], SecurityLevel::Secret);
+//! assert!(policy.check(&officer).is_ok());
+//! ```
+
+use crate::entities::security_context::{Permission, SecurityContext, SecurityLevel};
+use crate::PipelineError;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// Pipeline configuration (and archive metadata) key holding the minimum
+/// security level
+pub const MINIMUM_SECURITY_LEVEL_KEY: &str = "minimum_security_level";
+
+/// Pipeline configuration (and archive metadata) key holding the required
+/// permissions, comma-separated
+pub const REQUIRED_PERMISSIONS_KEY: &str = "required_permissions";
+
+/// Security requirements a context must meet to use a pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityPolicy {
+    minimum_level: SecurityLevel,
+    required_permissions: Vec<Permission>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self::new(SecurityLevel::Public)
+    }
+}
+
+impl SecurityPolicy {
+    /// Creates a policy requiring `minimum_level` and no extra permissions
+    pub fn new(minimum_level: SecurityLevel) -> Self {
+        Self {
+            minimum_level,
+            required_permissions: Vec::new(),
+        }
+    }
+
+    /// Sets the permissions a context must hold, dropping duplicates
+    pub fn with_required_permissions(mut self, permissions: Vec<Permission>) -> Self {
+        self.required_permissions.clear();
+        for permission in permissions {
+            if !self.required_permissions.contains(&permission) {
+                self.required_permissions.push(permission);
+            }
+        }
+        self
+    }
+
+    /// Gets the lowest security level allowed
+    pub fn minimum_level(&self) -> &SecurityLevel {
+        &self.minimum_level
+    }
+
+    /// Gets the permissions a context must hold
+    pub fn required_permissions(&self) -> &[Permission] {
+        &self.required_permissions
+    }
+
+    /// Whether every security context satisfies this policy
+    pub fn is_unrestricted(&self) -> bool {
+        self.minimum_level == SecurityLevel::Public && self.required_permissions.is_empty()
+    }
+
+    /// Checks that `context` meets the minimum level and holds every
+    /// required permission
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::SecurityViolation` naming the first unmet
+    /// requirement.
+    pub fn check(&self, context: &SecurityContext) -> Result<(), PipelineError> {
+        if !context.meets_security_level(&self.minimum_level) {
+            return Err(PipelineError::security_violation(format!(
+                "Permission denied: security level {} required, context has {}",
+                self.minimum_level,
+                context.security_level()
+            )));
+        }
+        match self.required_permissions.iter().find(|p| !context.has_permission(p)) {
+            Some(missing) => Err(PipelineError::security_violation(format!(
+                "Permission denied: {} permission required",
+                missing
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the policy from pipeline configuration or archive metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if a stored value is not a known level
+    /// or permission.
+    pub fn from_entries(entries: &HashMap<String, String>) -> Result<Self, PipelineError> {
+        let minimum_level = entries
+            .get(MINIMUM_SECURITY_LEVEL_KEY)
+            .map_or(Ok(SecurityLevel::Public), |value| value.parse())?;
+        let required_permissions = entries
+            .get(REQUIRED_PERMISSIONS_KEY)
+            .map_or(Ok(Vec::new()), |value| Self::parse_permissions(value))?;
+        Ok(Self::new(minimum_level).with_required_permissions(required_permissions))
+    }
+
+    /// Writes the policy into pipeline configuration or archive metadata,
+    /// removing the keys when it is unrestricted
+    pub fn write_entries(&self, entries: &mut HashMap<String, String>) {
+        if self.minimum_level == SecurityLevel::Public {
+            entries.remove(MINIMUM_SECURITY_LEVEL_KEY);
+        } else {
+            entries.insert(
+                MINIMUM_SECURITY_LEVEL_KEY.to_string(),
+                self.minimum_level.as_str().to_string(),
+            );
+        }
+        if self.required_permissions.is_empty() {
+            entries.remove(REQUIRED_PERMISSIONS_KEY);
+        } else {
+            entries.insert(REQUIRED_PERMISSIONS_KEY.to_string(), self.permission_names());
+        }
+    }
+
+    /// Parses a comma-separated permission list, e.g. `decrypt,custom:audit`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` naming the first unknown permission.
+    pub fn parse_permissions(list: &str) -> Result<Vec<Permission>, PipelineError> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    fn permission_names(&self) -> String {
+        self.required_permissions
+            .iter()
+            .map(Permission::name)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl Display for SecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "level {}", self.minimum_level.as_str())?;
+        if !self.required_permissions.is_empty() {
+            write!(f, ", permissions {}", self.permission_names())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_round_trips_through_entries() {
+        let policy = SecurityPolicy::new(SecurityLevel::Secret).with_required_permissions(vec![
+            Permission::Decrypt,
+            Permission::Custom("finance".to_string()),
+            Permission::Decrypt,
+        ]);
+        let mut entries = HashMap::new();
+        policy.write_entries(&mut entries);
+        assert_eq!(entries[REQUIRED_PERMISSIONS_KEY], "decrypt,custom:finance");
+        assert_eq!(SecurityPolicy::from_entries(&entries).unwrap(), policy);
+
+        SecurityPolicy::default().write_entries(&mut entries);
+        assert!(entries.is_empty());
+        assert!(SecurityPolicy::from_entries(&entries).unwrap().is_unrestricted());
+
+        entries.insert(MINIMUM_SECURITY_LEVEL_KEY.to_string(), "classified".to_string());
+        assert!(SecurityPolicy::from_entries(&entries).is_err());
+    }
+
+    #[test]
+    fn test_check_requires_level_and_permissions() {
+        let policy =
+            SecurityPolicy::new(SecurityLevel::Confidential).with_required_permissions(vec![Permission::Decrypt]);
+
+        let low = SecurityContext::with_permissions(None, vec![Permission::Decrypt], SecurityLevel::Internal);
+        let err = policy.check(&low).unwrap_err();
+        assert!(matches!(err, PipelineError::SecurityViolation(_)));
+        assert!(err.to_string().contains("Confidential"), "{}", err);
+
+        let unpermitted = SecurityContext::with_permissions(None, vec![Permission::Read], SecurityLevel::Secret);
+        assert!(policy.check(&unpermitted).unwrap_err().to_string().contains("Decrypt"));
+
+        let admin = SecurityContext::with_permissions(None, vec![Permission::Admin], SecurityLevel::Confidential);
+        assert!(policy.check(&admin).is_ok());
+        assert!(SecurityPolicy::default().check(&low).is_ok());
+    }
+
+    #[test]
+    fn test_pipeline_policy_lives_in_its_configuration() {
+        use crate::entities::{Pipeline, PipelineStage, StageConfiguration, StageType};
+
+        let stage = PipelineStage::new(
+            "compress".to_string(),
+            StageType::Compression,
+            StageConfiguration::new("brotli".to_string(), HashMap::new(), false),
+            0,
+        )
+        .unwrap();
+        let mut pipeline = Pipeline::new("payroll".to_string(), vec![stage]).unwrap();
+        assert!(pipeline.security_policy().unwrap().is_unrestricted());
+
+        let policy = SecurityPolicy::new(SecurityLevel::Confidential);
+        pipeline.set_security_policy(&policy);
+        assert_eq!(
+            pipeline
+                .configuration()
+                .get(MINIMUM_SECURITY_LEVEL_KEY)
+                .map(String::as_str),
+            Some("confidential")
+        );
+        assert_eq!(pipeline.security_policy().unwrap(), policy);
+
+        pipeline.update_configuration(HashMap::from([(
+            REQUIRED_PERMISSIONS_KEY.to_string(),
+            "teleport".to_string(),
+        )]));
+        assert!(pipeline.validate().is_err());
+    }
+}
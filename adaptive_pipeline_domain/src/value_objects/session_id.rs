@@ -253,6 +253,12 @@ impl SessionId {
         self.0.as_ulid()
     }
 
+    /// Gets the ID as a UUID with the same 128 bits, e.g. for a
+    /// `SecurityContext` session
+    pub fn as_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.as_ulid().0)
+    }
+
     /// Gets the timestamp component
     pub fn timestamp_ms(&self) -> u64 {
         self.0.timestamp_ms()
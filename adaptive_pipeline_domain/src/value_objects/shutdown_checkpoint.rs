@@ -0,0 +1,197 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Shutdown Checkpoint Value Object
+//!
+//! A record of how far a job got before shutdown stopped it. It is written
+//! next to the unfinished output as `<output>.checkpoint.json`. It lists the
+//! chunks that were fully processed and written, and says whether in-flight
+//! chunks drained within the grace period or had to be aborted.
+//!
+//! The unfinished output has no footer, so it cannot be restored. The
+//! checkpoint tells an operator, or a later resume, exactly what is missing.
+//!
+//! ```rust
+//! use adaptive_pipeline_domain::value_objects::ShutdownCheckpoint;
+//! use std::path::Path;
+//! use std::time::Duration;
+//!
+//! let checkpoint = ShutdownCheckpoint::new("data.csv", "data.adapipe", "p-1", 1024, 4)
+//!     .with_completed_chunks(vec![2, 0, 1])
+//!     .with_drain(false, Duration::from_secs(5));
+//! assert_eq!(checkpoint.missing_chunks(), vec![3]);
+//! assert_eq!(checkpoint.summary(), "3 of 4 chunks completed; in-flight work aborted after 5s");
+//! assert_eq!(
+//!     ShutdownCheckpoint::path_for(Path::new("data.adapipe")),
+//!     Path::new("data.adapipe.checkpoint.json")
+//! );
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::PipelineError;
+
+/// Current checkpoint layout version
+pub const CHECKPOINT_VERSION: u16 = 1;
+
+/// Extension appended to the output's path to form the checkpoint path
+pub const CHECKPOINT_EXTENSION: &str = "checkpoint.json";
+
+/// Progress of a job interrupted by shutdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShutdownCheckpoint {
+    /// Checkpoint layout version
+    pub checkpoint_version: u16,
+
+    /// File being processed
+    pub input: String,
+
+    /// Unfinished output
+    pub output: String,
+
+    /// Pipeline the job ran
+    pub pipeline_id: String,
+
+    /// Bytes per chunk
+    pub chunk_size: u64,
+
+    /// Chunks the input splits into
+    pub total_chunks: u64,
+
+    /// Indices of chunks processed and written, ascending
+    pub completed_chunks: Vec<u64>,
+
+    /// Whether in-flight chunks finished within the grace period; `false`
+    /// means they were aborted
+    pub drained: bool,
+
+    /// Grace period in-flight chunks were given, in milliseconds
+    pub grace_period_ms: u64,
+
+    /// When the checkpoint was taken
+    pub interrupted_at: DateTime<Utc>,
+}
+
+impl ShutdownCheckpoint {
+    /// Creates a checkpoint with no completed chunks, drained with no grace
+    /// period, taken now
+    pub fn new(
+        input: impl Into<String>,
+        output: impl Into<String>,
+        pipeline_id: impl Into<String>,
+        chunk_size: u64,
+        total_chunks: u64,
+    ) -> Self {
+        Self {
+            checkpoint_version: CHECKPOINT_VERSION,
+            input: input.into(),
+            output: output.into(),
+            pipeline_id: pipeline_id.into(),
+            chunk_size,
+            total_chunks,
+            completed_chunks: Vec::new(),
+            drained: true,
+            grace_period_ms: 0,
+            interrupted_at: Utc::now(),
+        }
+    }
+
+    /// Records the completed chunks, in any order
+    pub fn with_completed_chunks(mut self, mut chunks: Vec<u64>) -> Self {
+        chunks.sort_unstable();
+        chunks.dedup();
+        self.completed_chunks = chunks;
+        self
+    }
+
+    /// Records whether in-flight chunks drained within `grace_period`
+    pub fn with_drain(mut self, drained: bool, grace_period: Duration) -> Self {
+        self.drained = drained;
+        self.grace_period_ms = u64::try_from(grace_period.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Indices of chunks still to be processed
+    pub fn missing_chunks(&self) -> Vec<u64> {
+        (0..self.total_chunks)
+            .filter(|index| self.completed_chunks.binary_search(index).is_err())
+            .collect()
+    }
+
+    /// One-line account of the interruption, e.g. `3 of 4 chunks completed;
+    /// in-flight work drained`
+    pub fn summary(&self) -> String {
+        let ending = if self.drained {
+            "in-flight work drained".to_string()
+        } else {
+            format!(
+                "in-flight work aborted after {:?}",
+                Duration::from_millis(self.grace_period_ms)
+            )
+        };
+        format!(
+            "{} of {} chunks completed; {}",
+            self.completed_chunks.len(),
+            self.total_chunks,
+            ending
+        )
+    }
+
+    /// Pretty-printed JSON for writing to disk
+    pub fn to_json(&self) -> Result<String, PipelineError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PipelineError::SerializationError(format!("Failed to encode checkpoint: {}", e)))
+    }
+
+    /// Parses a checkpoint read from disk
+    pub fn from_json(json: &str) -> Result<Self, PipelineError> {
+        let checkpoint: Self = serde_json::from_str(json)
+            .map_err(|e| PipelineError::SerializationError(format!("Invalid checkpoint: {}", e)))?;
+        if checkpoint.checkpoint_version > CHECKPOINT_VERSION {
+            return Err(PipelineError::validation_error(format!(
+                "Checkpoint version {} is newer than supported version {}",
+                checkpoint.checkpoint_version, CHECKPOINT_VERSION
+            )));
+        }
+        Ok(checkpoint)
+    }
+
+    /// Checkpoint path for an output: its path with `.checkpoint.json`
+    /// appended (`data.adapipe` → `data.adapipe.checkpoint.json`)
+    pub fn path_for(output: &Path) -> PathBuf {
+        let mut path = output.as_os_str().to_owned();
+        path.push(".");
+        path.push(CHECKPOINT_EXTENSION);
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip_and_version_check() {
+        let checkpoint = ShutdownCheckpoint::new("in.bin", "in.adapipe", "p-1", 4096, 3)
+            .with_completed_chunks(vec![1, 1, 0])
+            .with_drain(true, Duration::from_millis(250));
+        assert_eq!(checkpoint.completed_chunks, vec![0, 1]);
+        assert_eq!(checkpoint.missing_chunks(), vec![2]);
+        assert_eq!(checkpoint.summary(), "2 of 3 chunks completed; in-flight work drained");
+        assert_eq!(
+            ShutdownCheckpoint::from_json(&checkpoint.to_json().unwrap()).unwrap(),
+            checkpoint
+        );
+
+        let mut future = checkpoint;
+        future.checkpoint_version = CHECKPOINT_VERSION + 1;
+        assert!(ShutdownCheckpoint::from_json(&future.to_json().unwrap()).is_err());
+    }
+}
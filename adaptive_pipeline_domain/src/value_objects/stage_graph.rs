@@ -0,0 +1,317 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! # Stage Graph Value Object
+//!
+//! The stage graph of a pipeline: one node per stage plus the input and
+//! output ends, joined by directed edges. Pipelines are linear today, so every
+//! stage has one successor, but the graph keeps nodes and edges separate so
+//! branching stages only have to add edges.
+//!
+//! The graph renders to Graphviz DOT or Mermaid, giving documentation and
+//! reviews a diagram generated from the pipeline itself:
+//!
+//! ```text
+//! flowchart LR
+//!     input([input])
+//!     s0["input_checksum<br/>checksum: sha256"]
+//!     s1["compression<br/>compression: brotli"]
+//!     output([output])
+//!     input --> s0
+//!     s0 --> s1
+//!     s1 --> output
+//! ```
+
+use crate::entities::PipelineStage;
+use crate::PipelineError;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Diagram language a [`StageGraph`] renders to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = PipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            other => Err(PipelineError::invalid_config(format!(
+                "Unknown graph format '{}' (expected dot or mermaid)",
+                other
+            ))),
+        }
+    }
+}
+
+/// One stage in a [`StageGraph`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageNode {
+    name: String,
+    stage_type: String,
+    algorithm: String,
+    enabled: bool,
+}
+
+impl StageNode {
+    /// Gets the stage name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the stage type
+    pub fn stage_type(&self) -> &str {
+        &self.stage_type
+    }
+
+    /// Gets the stage algorithm
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    /// Whether the stage runs; disabled stages are drawn dashed
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// An end of a [`StageEdge`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphEndpoint {
+    /// The data read from the input file
+    Input,
+    /// The stage at this index in [`StageGraph::nodes`]
+    Stage(usize),
+    /// The data written to the output file
+    Output,
+}
+
+/// A directed edge between two endpoints of a [`StageGraph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageEdge {
+    /// Where the data comes from
+    pub from: GraphEndpoint,
+    /// Where the data goes
+    pub to: GraphEndpoint,
+}
+
+/// Stage graph of a pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageGraph {
+    name: String,
+    nodes: Vec<StageNode>,
+    edges: Vec<StageEdge>,
+}
+
+impl StageGraph {
+    /// Builds the graph of stages run in order, from input to output
+    pub fn from_stages(name: impl Into<String>, stages: &[PipelineStage]) -> Self {
+        let mut ordered: Vec<&PipelineStage> = stages.iter().collect();
+        ordered.sort_by_key(|stage| stage.order());
+
+        let nodes = ordered
+            .iter()
+            .map(|stage| StageNode {
+                name: stage.name().to_string(),
+                stage_type: stage.stage_type().to_string(),
+                algorithm: stage.algorithm().to_string(),
+                enabled: stage.is_enabled(),
+            })
+            .collect::<Vec<_>>();
+
+        let endpoints = std::iter::once(GraphEndpoint::Input)
+            .chain((0..nodes.len()).map(GraphEndpoint::Stage))
+            .chain(std::iter::once(GraphEndpoint::Output))
+            .collect::<Vec<_>>();
+        let edges = endpoints
+            .windows(2)
+            .map(|pair| StageEdge {
+                from: pair[0],
+                to: pair[1],
+            })
+            .collect();
+
+        Self {
+            name: name.into(),
+            nodes,
+            edges,
+        }
+    }
+
+    /// Gets the pipeline name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the stages, in processing order
+    pub fn nodes(&self) -> &[StageNode] {
+        &self.nodes
+    }
+
+    /// Gets the edges
+    pub fn edges(&self) -> &[StageEdge] {
+        &self.edges
+    }
+
+    /// Renders the graph in the given diagram language
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    /// Renders the graph as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph \"{}\" {{", escape_dot(&self.name));
+        let _ = writeln!(out, "    rankdir=LR;");
+        let _ = writeln!(out, "    node [shape=box];");
+        let _ = writeln!(out, "    input [label=\"input\", shape=ellipse];");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let style = if node.enabled { "" } else { ", style=dashed" };
+            let _ = writeln!(
+                out,
+                "    s{} [label=\"{}\\n{}: {}\"{}];",
+                index,
+                escape_dot(&node.name),
+                escape_dot(&node.stage_type),
+                escape_dot(&node.algorithm),
+                style
+            );
+        }
+        let _ = writeln!(out, "    output [label=\"output\", shape=ellipse];");
+        for edge in &self.edges {
+            let _ = writeln!(out, "    {} -> {};", endpoint_id(edge.from), endpoint_id(edge.to));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "flowchart LR");
+        let _ = writeln!(out, "    input([input])");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    s{}[\"{}<br/>{}: {}\"]",
+                index,
+                escape_mermaid(&node.name),
+                escape_mermaid(&node.stage_type),
+                escape_mermaid(&node.algorithm)
+            );
+        }
+        let _ = writeln!(out, "    output([output])");
+        for edge in &self.edges {
+            let _ = writeln!(out, "    {} --> {}", endpoint_id(edge.from), endpoint_id(edge.to));
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            if !node.enabled {
+                let _ = writeln!(out, "    style s{} stroke-dasharray: 5 5", index);
+            }
+        }
+        out
+    }
+}
+
+fn endpoint_id(endpoint: GraphEndpoint) -> String {
+    match endpoint {
+        GraphEndpoint::Input => "input".to_string(),
+        GraphEndpoint::Stage(index) => format!("s{}", index),
+        GraphEndpoint::Output => "output".to_string(),
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{StageConfiguration, StageType};
+    use std::collections::HashMap;
+
+    fn stage(name: &str, stage_type: StageType, algorithm: &str, order: u32) -> PipelineStage {
+        PipelineStage::new(
+            name.to_string(),
+            stage_type,
+            StageConfiguration::new(algorithm.to_string(), HashMap::new(), false),
+            order,
+        )
+        .unwrap()
+    }
+
+    fn graph() -> StageGraph {
+        let mut encryption = stage("encrypt", StageType::Encryption, "aes256gcm", 1);
+        encryption.set_enabled(false);
+        StageGraph::from_stages(
+            "backup",
+            &[encryption, stage("compress", StageType::Compression, "brotli", 0)],
+        )
+    }
+
+    #[test]
+    fn test_graph_follows_stage_order() {
+        let graph = graph();
+        let names: Vec<&str> = graph.nodes().iter().map(|node| node.name()).collect();
+        assert_eq!(names, vec!["compress", "encrypt"]);
+        assert_eq!(
+            graph.edges(),
+            &[
+                StageEdge {
+                    from: GraphEndpoint::Input,
+                    to: GraphEndpoint::Stage(0)
+                },
+                StageEdge {
+                    from: GraphEndpoint::Stage(0),
+                    to: GraphEndpoint::Stage(1)
+                },
+                StageEdge {
+                    from: GraphEndpoint::Stage(1),
+                    to: GraphEndpoint::Output
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_dot_and_mermaid() {
+        let graph = graph();
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph \"backup\" {"));
+        assert!(dot.contains("s0 [label=\"compress\\ncompression: brotli\"];"));
+        assert!(dot.contains("s1 [label=\"encrypt\\nencryption: aes256gcm\", style=dashed];"));
+        assert!(dot.contains("s1 -> output;"));
+
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("input --> s0"));
+        assert!(mermaid.contains("style s1 stroke-dasharray: 5 5"));
+    }
+
+    #[test]
+    fn test_graph_format_from_str() {
+        assert_eq!("DOT".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert_eq!("mermaid".parse::<GraphFormat>().unwrap(), GraphFormat::Mermaid);
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
@@ -503,12 +503,10 @@ impl ParameterValue {
     /// Validates the parameter value
     fn validate(&self) -> Result<(), PipelineError> {
         match self {
-            ParameterValue::String(s) => {
-                if s.len() > 10_000 {
-                    return Err(PipelineError::InvalidConfiguration(
-                        "String parameter value too long (maximum 10,000 characters)".to_string(),
-                    ));
-                }
+            ParameterValue::String(s) if s.len() > 10_000 => {
+                return Err(PipelineError::InvalidConfiguration(
+                    "String parameter value too long (maximum 10,000 characters)".to_string(),
+                ));
             }
             ParameterValue::Array(arr) => {
                 if arr.len() > 1000 {
@@ -531,14 +529,12 @@ impl ParameterValue {
                     value.validate()?;
                 }
             }
-            ParameterValue::Float(f) => {
-                if f.parse::<f64>().is_err() {
-                    return Err(PipelineError::InvalidConfiguration(
-                        "Invalid float parameter value".to_string(),
-                    ));
-                }
+            ParameterValue::Float(f) if f.parse::<f64>().is_err() => {
+                return Err(PipelineError::InvalidConfiguration(
+                    "Invalid float parameter value".to_string(),
+                ));
             }
-            _ => {} // Integer and Boolean are always valid
+            _ => {} // Integer, Boolean, and in-range strings/floats are always valid
         }
         Ok(())
     }
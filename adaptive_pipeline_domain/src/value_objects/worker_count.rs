@@ -133,6 +133,10 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Pipeline configuration key holding the worker count to run with when
+/// none is given
+pub const WORKER_COUNT_KEY: &str = "worker_count";
+
 /// Worker count value object for adaptive parallel processing optimization
 ///
 /// This value object provides adaptive parallel processing optimization with
@@ -0,0 +1,71 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Fuzzes chunk framing: `ChunkFormat::from_bytes` over a byte stream, and
+//! the streaming .adapipe reader over a whole in-memory file.
+//!
+//! Neither may panic, read out of bounds, or allocate more than a chunk's
+//! declared length allows.
+
+#![no_main]
+
+use adaptive_pipeline::infrastructure::services::{AdapipeFormat, BinaryFormatService, ChunkSource};
+use adaptive_pipeline_domain::value_objects::binary_file_format::MAX_CHUNK_PAYLOAD_LENGTH;
+use adaptive_pipeline_domain::value_objects::ChunkFormat;
+use adaptive_pipeline_domain::PipelineError;
+use async_trait::async_trait;
+use libfuzzer_sys::fuzz_target;
+use std::sync::Arc;
+
+/// Serves the fuzz input as an .adapipe file
+struct MemorySource(Vec<u8>);
+
+#[async_trait]
+impl ChunkSource for MemorySource {
+    fn location(&self) -> String {
+        "fuzz input".to_string()
+    }
+
+    async fn size(&self) -> Result<u64, PipelineError> {
+        Ok(self.0.len() as u64)
+    }
+
+    async fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>, PipelineError> {
+        let start = usize::try_from(offset).map_err(|_| PipelineError::IoError("offset out of range".into()))?;
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= self.0.len())
+            .ok_or_else(|| PipelineError::IoError("read past end of input".into()))?;
+        Ok(self.0[start..end].to_vec())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Raw framing: consume chunks until the stream no longer parses
+    let mut rest = data;
+    while let Ok((chunk, consumed)) = ChunkFormat::from_bytes(rest) {
+        assert!(consumed <= rest.len());
+        assert!(chunk.payload.len() <= MAX_CHUNK_PAYLOAD_LENGTH);
+        rest = &rest[consumed..];
+    }
+
+    // Whole-file reads through the streaming reader
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("runtime builds");
+    runtime.block_on(async {
+        let source = Arc::new(MemorySource(data.to_vec()));
+        let Ok(mut reader) = AdapipeFormat::new().create_reader_from(source).await else {
+            return;
+        };
+        while let Ok(Some(chunk)) = reader.read_next_chunk().await {
+            assert!(chunk.payload.len() <= data.len());
+        }
+        let _ = reader.seek_to_chunk(reader.chunk_count() / 2).await;
+        let _ = reader.validate_integrity().await;
+    });
+});
@@ -0,0 +1,31 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Fuzzes `FileHeader::from_footer_bytes` with arbitrary file tails.
+//!
+//! Any input must parse or fail with an error; a header that parses and
+//! validates must survive a footer roundtrip unchanged.
+
+#![no_main]
+
+use adaptive_pipeline_domain::value_objects::binary_file_format::MAX_HEADER_LENGTH;
+use adaptive_pipeline_domain::value_objects::FileHeader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok((header, footer_size)) = FileHeader::from_footer_bytes(data) else {
+        return;
+    };
+    assert!(footer_size <= data.len());
+    assert!(footer_size <= MAX_HEADER_LENGTH + 14);
+
+    if header.validate().is_ok() {
+        let footer = header.to_footer_bytes().expect("validated header serializes");
+        let (reparsed, _) = FileHeader::from_footer_bytes(&footer).expect("written footer parses");
+        assert_eq!(reparsed, header);
+    }
+});
@@ -0,0 +1,32 @@
+// /////////////////////////////////////////////////////////////////////////////
+// Adaptive Pipeline
+// Copyright (c) 2025 Michael Gardner, A Bit of Help, Inc.
+// SPDX-License-Identifier: BSD-3-Clause
+// See LICENSE file in the project root.
+// /////////////////////////////////////////////////////////////////////////////
+
+//! Fuzzes the restoration pipeline builder with arbitrary header JSON.
+//!
+//! The JSON is parsed directly rather than through a footer so the fuzzer
+//! spends its time on processing steps, not on finding the magic bytes. Any
+//! header that validates must yield a pipeline or an error, never a panic.
+
+#![no_main]
+
+use adaptive_pipeline::application::use_cases::create_restoration_pipeline;
+use adaptive_pipeline_domain::value_objects::FileHeader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(header) = serde_json::from_slice::<FileHeader>(data) else {
+        return;
+    };
+    if header.validate().is_err() {
+        return;
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("runtime builds");
+    let _ = runtime.block_on(create_restoration_pipeline(&header));
+});